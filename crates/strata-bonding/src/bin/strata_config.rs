@@ -0,0 +1,71 @@
+//! # strata-config
+//!
+//! Checks bonding TOML configs against the schema and prints a fully
+//! commented example generated from it.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! # Validate one or more configs (exit status 1 on any error)
+//! strata-config check sender.toml
+//!
+//! # Write a documented starting point
+//! strata-config example > sender.toml
+//! ```
+
+use strata_bonding::config::schema::{self, Severity};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("check") if args.len() > 1 => std::process::exit(check(&args[1..])),
+        Some("example") if args.len() == 1 => print!("{}", schema::example()),
+        Some("--help" | "-h" | "help") => print_help(),
+        _ => {
+            print_help();
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Check each file, printing `file:line: severity: message`. Returns the
+/// process exit status.
+fn check(paths: &[String]) -> i32 {
+    let mut status = 0;
+    for path in paths {
+        let input = match std::fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{path}: error: {e}");
+                status = 1;
+                continue;
+            }
+        };
+        let found = schema::check(&input);
+        if found.is_empty() {
+            println!("{path}: ok");
+        }
+        for d in &found {
+            let at = d.line.map(|line| format!(":{line}")).unwrap_or_default();
+            println!("{path}{at}: {}: {}", d.severity, d.message);
+            if d.severity == Severity::Error {
+                status = 1;
+            }
+        }
+    }
+    status
+}
+
+fn print_help() {
+    println!(
+        "\
+strata-config — validate and document bonding TOML configs
+
+USAGE:
+    strata-config check <FILE>...   Validate configs; exit 1 on any error
+    strata-config example           Print a fully commented example config
+
+A config passes when it has no errors. Warnings point at entries the
+runtime silently ignores."
+    );
+}
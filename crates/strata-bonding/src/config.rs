@@ -3,8 +3,18 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+pub mod schema;
+
 pub const CONFIG_VERSION: u32 = 1;
 
+/// Upper bound on [`SchedulerConfig::duplicate_spread_ms`]. Past a few
+/// milliseconds the late copy stops being redundancy and starts eating
+/// the receiver's reorder budget.
+pub const MAX_DUPLICATE_SPREAD_MS: f64 = 5.0;
+
+/// Upper bound on a [`SchedulerConfig::link_weights`] entry.
+pub const MAX_LINK_WEIGHT: f64 = 100.0;
+
 /// Operating profile, keyed to the egress target's latency budget.
 ///
 /// The latency a bonded stream can afford is a property of *where it is going*,
@@ -120,7 +130,14 @@ pub struct BondingConfigInput {
     /// Selects coherent baselines for playout, probing, failover and bitrate;
     /// explicit fields below still override.
     pub profile: Option<String>,
+    /// Per-stream ingest key minted by the control plane. The sender
+    /// presents it in every link's session HELLO; a receiver with a key
+    /// only accepts traffic from source addresses that presented it.
+    pub ingest_key: Option<String>,
     pub links: Vec<LinkConfigInput>,
+    /// Extra receiver endpoints the stream is replicated to (e.g. a
+    /// backup receiver), each over the same physical links.
+    pub replicas: Vec<ReplicaConfigInput>,
     pub receiver: ReceiverConfigInput,
     pub lifecycle: LinkLifecycleConfigInput,
     pub scheduler: SchedulerConfigInput,
@@ -138,6 +155,33 @@ pub struct LinkConfigInput {
     /// (infer from measurement). Only affects the regime reported in
     /// metrics — the control path stays path-relative regardless.
     pub profile: Option<String>,
+    /// Hard ceiling on this link's send rate (bps), independent of its
+    /// measured capacity. Bounds usage of a metered SIM. `0` or absent
+    /// means uncapped.
+    pub rate_cap_bps: Option<u64>,
+}
+
+/// Raw replica receiver configuration from TOML input.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ReplicaConfigInput {
+    /// Label for logs and metrics. Defaults to `replica-<n>`.
+    pub name: Option<String>,
+    /// Share of each shared link's measured capacity the replica may use,
+    /// in `(0, 1]`. Defaults to `1.0` (a full second copy).
+    pub weight: Option<f64>,
+    /// One entry per primary link the replica rides on.
+    pub links: Vec<ReplicaLinkInput>,
+}
+
+/// A replica's endpoint on one primary link.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ReplicaLinkInput {
+    /// ID of the primary link whose interface this replica link shares.
+    pub id: usize,
+    /// The replica receiver's address for this link.
+    pub uri: String,
 }
 
 /// Raw receiver configuration from TOML input.
@@ -181,6 +225,8 @@ pub struct SchedulerConfigInput {
     pub redundancy_target_links: Option<usize>,
     /// Whether critical packets (keyframes) broadcast to all alive links
     pub critical_broadcast: Option<bool>,
+    /// Spread over which broadcast/redundant copies are staggered (ms, 0-5)
+    pub duplicate_spread_ms: Option<f64>,
     /// Master toggle for fast-failover mode
     pub failover_enabled: Option<bool>,
     /// Duration of failover broadcast after trigger (ms)
@@ -215,6 +261,8 @@ pub struct SchedulerConfigInput {
     /// Interval between PPD (Packet-Pair Dispersion) probe pairs per link
     /// (seconds). Set very large to disable PPD probing (isolation sentinel).
     pub ppd_probe_interval_s: Option<f64>,
+    /// Per-link scheduling weight, indexed by link ID
+    pub link_weights: Option<Vec<f64>>,
 }
 
 /// Resolved link configuration with concrete values.
@@ -225,6 +273,20 @@ pub struct LinkConfig {
     pub interface: Option<String>,
     /// Path-regime override (`auto` → `None`). See [`LinkConfigInput::profile`].
     pub profile: Option<String>,
+    /// Hard send-rate cap in bps (`0` → `None`). See [`LinkConfigInput::rate_cap_bps`].
+    pub rate_cap_bps: Option<u64>,
+}
+
+/// Resolved replica receiver: its own sessions, and so its own ARQ and
+/// FEC state, to a second receiver over the primary's interfaces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaConfig {
+    pub name: String,
+    /// See [`ReplicaConfigInput::weight`].
+    pub weight: f64,
+    /// Replica links, keyed by the primary link ID and carrying its
+    /// interface and rate cap.
+    pub links: Vec<LinkConfig>,
 }
 
 /// Resolved receiver configuration.
@@ -310,6 +372,12 @@ pub struct SchedulerConfig {
     pub redundancy_max_packet_bytes: usize,
     pub redundancy_target_links: usize,
     pub critical_broadcast: bool,
+    /// Window (ms) over which the second and later copies of a broadcast
+    /// or redundant packet are staggered with random jitter, so duplicates
+    /// don't enter a shared bottleneck (one tower, one backhaul) in the
+    /// same instant and get dropped together. The first copy always goes
+    /// out immediately. 0 sends every copy back-to-back.
+    pub duplicate_spread_ms: f64,
     pub failover_enabled: bool,
     pub failover_duration_ms: u64,
     /// RTT multiple that triggers the fast-failover broadcast: this tick's
@@ -344,6 +412,28 @@ pub struct SchedulerConfig {
     /// Interval between PPD (Packet-Pair Dispersion) probe pairs per link (seconds).
     /// PPD provides continuous capacity samples between saturation probes.
     pub ppd_probe_interval_s: f64,
+    /// Scheduling weight per link, indexed by link ID (see
+    /// [`link_weight`](Self::link_weight)). Empty weighs every link 1.0.
+    pub link_weights: Vec<f64>,
+}
+
+impl SchedulerConfig {
+    /// Link `id`'s scheduling weight: EDPF credits the link with this
+    /// multiple of its estimated capacity, so 2.0 draws about twice the
+    /// share and 0.0 leaves the link for last-resort use only. Links past
+    /// the end of [`link_weights`](Self::link_weights) weigh 1.0.
+    pub fn link_weight(&self, id: usize) -> f64 {
+        self.link_weights.get(id).copied().unwrap_or(1.0)
+    }
+}
+
+/// Clamp a link weight into `[0, MAX_LINK_WEIGHT]`; NaN becomes 0.
+pub fn clamp_link_weight(weight: f64) -> f64 {
+    if weight.is_nan() {
+        0.0
+    } else {
+        weight.clamp(0.0, MAX_LINK_WEIGHT)
+    }
 }
 
 impl Default for SchedulerConfig {
@@ -359,6 +449,7 @@ impl Default for SchedulerConfig {
             redundancy_max_packet_bytes: 10_000,
             redundancy_target_links: 2,
             critical_broadcast: false,
+            duplicate_spread_ms: 0.0,
             failover_enabled: true,
             failover_duration_ms: 3000,
             failover_rtt_spike_factor: 3.0,
@@ -387,6 +478,7 @@ impl Default for SchedulerConfig {
             // to disrupt the radio, kept enabled for continuous capacity
             // signal between any (now-opt-in) saturation probes.
             ppd_probe_interval_s: 2.0,
+            link_weights: Vec::new(),
         }
     }
 }
@@ -396,7 +488,10 @@ impl Default for SchedulerConfig {
 pub struct BondingConfig {
     pub version: u32,
     pub profile: StreamProfile,
+    /// See [`BondingConfigInput::ingest_key`]. `None` = accept any source.
+    pub ingest_key: Option<String>,
     pub links: Vec<LinkConfig>,
+    pub replicas: Vec<ReplicaConfig>,
     pub receiver: ReceiverConfig,
     pub lifecycle: LinkLifecycleConfig,
     pub scheduler: SchedulerConfig,
@@ -407,7 +502,9 @@ impl Default for BondingConfig {
         Self {
             version: CONFIG_VERSION,
             profile: StreamProfile::default(),
+            ingest_key: None,
             links: Vec::new(),
+            replicas: Vec::new(),
             receiver: ReceiverConfig::default(),
             lifecycle: LinkLifecycleConfig::default(),
             scheduler: SchedulerConfig::default(),
@@ -469,6 +566,10 @@ impl SchedulerConfigInput {
             critical_broadcast: self
                 .critical_broadcast
                 .unwrap_or(defaults.critical_broadcast),
+            duplicate_spread_ms: self
+                .duplicate_spread_ms
+                .unwrap_or(defaults.duplicate_spread_ms)
+                .clamp(0.0, MAX_DUPLICATE_SPREAD_MS),
             failover_enabled: self.failover_enabled.unwrap_or(defaults.failover_enabled),
             failover_duration_ms: self
                 .failover_duration_ms
@@ -521,6 +622,10 @@ impl SchedulerConfigInput {
                 .ppd_probe_interval_s
                 .unwrap_or(defaults.ppd_probe_interval_s)
                 .max(0.01),
+            link_weights: self
+                .link_weights
+                .map(|w| w.into_iter().map(clamp_link_weight).collect())
+                .unwrap_or(defaults.link_weights),
         }
     }
 }
@@ -549,6 +654,17 @@ impl BondingConfigInput {
         };
         let playout = profile.playout();
 
+        let ingest_key = self.ingest_key.filter(|k| !k.is_empty());
+        if let Some(ref key) = ingest_key
+            && !strata_transport::session::ingest_key_fits(key.as_bytes())
+        {
+            return Err(format!(
+                "ingest_key is {} bytes (max {})",
+                key.len(),
+                strata_transport::wire::MAX_INGEST_KEY_LEN
+            ));
+        }
+
         let receiver = ReceiverConfig {
             start_latency: Duration::from_millis(
                 self.receiver.start_latency_ms.unwrap_or(playout.start_ms),
@@ -597,13 +713,59 @@ impl BondingConfigInput {
                 uri: link.uri,
                 interface: iface,
                 profile,
+                rate_cap_bps: link.rate_cap_bps.filter(|&bps| bps > 0),
+            });
+        }
+
+        let mut replicas = Vec::new();
+        let mut seen_names = HashSet::new();
+        for (idx, replica) in self.replicas.into_iter().enumerate() {
+            let name = replica
+                .name
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("replica-{}", idx));
+            if !seen_names.insert(name.clone()) {
+                return Err(format!("replica '{}' is defined more than once", name));
+            }
+            let weight = replica.weight.unwrap_or(1.0);
+            if !(weight > 0.0 && weight <= 1.0) {
+                return Err(format!(
+                    "replica '{}' weight {} is out of range (0, 1]",
+                    name, weight
+                ));
+            }
+            let mut links = Vec::new();
+            for link in replica.links {
+                let primary = out.iter().find(|l| l.id == link.id).ok_or_else(|| {
+                    format!(
+                        "replica '{}' names link {} which is not configured",
+                        name, link.id
+                    )
+                })?;
+                if links.iter().any(|l: &LinkConfig| l.id == link.id) {
+                    return Err(format!(
+                        "replica '{}' lists link {} more than once",
+                        name, link.id
+                    ));
+                }
+                links.push(LinkConfig {
+                    uri: link.uri,
+                    ..primary.clone()
+                });
+            }
+            replicas.push(ReplicaConfig {
+                name,
+                weight,
+                links,
             });
         }
 
         Ok(BondingConfig {
             version,
             profile,
+            ingest_key,
             links: out,
+            replicas,
             receiver,
             lifecycle,
             scheduler,
@@ -732,6 +894,48 @@ mod tests {
         assert_eq!(cfg.links[1].interface.as_deref(), Some("wlan0"));
     }
 
+    #[test]
+    fn parse_toml_link_rate_cap() {
+        let toml = r#"
+            version = 1
+
+            [[links]]
+            id = 1
+            uri = "10.0.0.1:5000"
+            rate_cap_bps = 2000000
+
+            [[links]]
+            id = 2
+            uri = "10.0.0.2:5000"
+            rate_cap_bps = 0
+
+            [[links]]
+            id = 3
+            uri = "10.0.0.3:5000"
+        "#;
+
+        let cfg = BondingConfig::from_toml_str(toml).unwrap();
+        assert_eq!(cfg.links[0].rate_cap_bps, Some(2_000_000));
+        assert_eq!(cfg.links[1].rate_cap_bps, None);
+        assert_eq!(cfg.links[2].rate_cap_bps, None);
+    }
+
+    #[test]
+    fn parse_toml_link_weights() {
+        let toml = r#"
+            [scheduler]
+            link_weights = [1, 0.5, -2.0, 1e9]
+        "#;
+
+        let cfg = BondingConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            cfg.scheduler.link_weights,
+            vec![1.0, 0.5, 0.0, MAX_LINK_WEIGHT]
+        );
+        assert_eq!(cfg.scheduler.link_weight(1), 0.5);
+        assert_eq!(cfg.scheduler.link_weight(7), 1.0);
+    }
+
     #[test]
     fn parse_toml_scheduler_config() {
         let toml = r#"
@@ -1030,6 +1234,28 @@ mod tests {
         assert_eq!(cfg.scheduler.redundancy_target_links, 1);
     }
 
+    #[test]
+    fn duplicate_spread_clamped() {
+        let cfg = BondingConfig::from_toml_str("version = 1").unwrap();
+        assert_eq!(cfg.scheduler.duplicate_spread_ms, 0.0);
+
+        let toml = r#"
+            version = 1
+            [scheduler]
+            duplicate_spread_ms = 2.5
+        "#;
+        let cfg = BondingConfig::from_toml_str(toml).unwrap();
+        assert!((cfg.scheduler.duplicate_spread_ms - 2.5).abs() < 1e-6);
+
+        let toml2 = r#"
+            version = 1
+            [scheduler]
+            duplicate_spread_ms = 40.0
+        "#;
+        let cfg2 = BondingConfig::from_toml_str(toml2).unwrap();
+        assert_eq!(cfg2.scheduler.duplicate_spread_ms, MAX_DUPLICATE_SPREAD_MS);
+    }
+
     #[test]
     fn parse_toml_per_link_profile() {
         let toml = r#"
@@ -1079,4 +1305,67 @@ mod tests {
         assert_eq!(cfg.links[0].id, 0);
         assert_eq!(cfg.links[1].id, 1);
     }
+
+    #[test]
+    fn ingest_key_parsed_and_bounded() {
+        let cfg = BondingConfig::from_toml_str("ingest_key = \"isk_abc\"").unwrap();
+        assert_eq!(cfg.ingest_key.as_deref(), Some("isk_abc"));
+
+        let cfg = BondingConfig::from_toml_str("ingest_key = \"\"").unwrap();
+        assert!(cfg.ingest_key.is_none(), "empty key means no key");
+
+        let long = format!("ingest_key = \"{}\"", "k".repeat(300));
+        assert!(BondingConfig::from_toml_str(&long).is_err());
+    }
+
+    #[test]
+    fn parse_toml_config_replicas_share_primary_links() {
+        let toml = r#"
+            [[links]]
+            id = 0
+            uri = "10.0.0.1:5000"
+            interface = "wwan0"
+            rate_cap_bps = 4000000
+            [[links]]
+            id = 1
+            uri = "10.0.0.1:5002"
+            interface = "wwan1"
+
+            [[replicas]]
+            name = "backup"
+            weight = 0.5
+            [[replicas.links]]
+            id = 1
+            uri = "10.0.1.1:6002"
+            [[replicas.links]]
+            id = 0
+            uri = "10.0.1.1:6000"
+        "#;
+        let cfg = BondingConfig::from_toml_str(toml).unwrap();
+        assert_eq!(cfg.replicas.len(), 1);
+        let backup = &cfg.replicas[0];
+        assert_eq!(backup.name, "backup");
+        assert_eq!(backup.weight, 0.5);
+        assert_eq!(backup.links[1].id, 0);
+        assert_eq!(backup.links[1].uri, "10.0.1.1:6000");
+        assert_eq!(backup.links[1].interface.as_deref(), Some("wwan0"));
+        assert_eq!(backup.links[1].rate_cap_bps, Some(4_000_000));
+    }
+
+    #[test]
+    fn parse_toml_config_rejects_bad_replicas() {
+        let with_replica = |replica: &str| {
+            BondingConfig::from_toml_str(&format!(
+                "[[links]]\nid = 0\nuri = \"10.0.0.1:5000\"\n[[replicas]]\n{replica}"
+            ))
+        };
+        let unknown_link = with_replica("[[replicas.links]]\nid = 3\nuri = \"10.0.1.1:6000\"");
+        assert!(unknown_link.unwrap_err().contains("not configured"));
+        let zero_weight = with_replica("weight = 0.0");
+        assert!(zero_weight.unwrap_err().contains("out of range"));
+
+        let defaulted = with_replica("").unwrap();
+        assert_eq!(defaulted.replicas[0].name, "replica-0");
+        assert_eq!(defaulted.replicas[0].weight, 1.0);
+    }
 }
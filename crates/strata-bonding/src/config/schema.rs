@@ -0,0 +1,952 @@
+//! # Config Schema
+//!
+//! Validation and documentation for the bonding TOML, driven by the typed
+//! input structs in [`super`].
+//!
+//! The key/type tree is not hand-maintained: [`shape`] walks the
+//! `Deserialize` impls of [`BondingConfigInput`] with a probe deserializer,
+//! so a field added to a struct shows up here automatically. Only the prose
+//! lives in [`DOCS`], and a test fails if the two drift apart.
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+use super::BondingConfigInput;
+
+// ─── Shape ──────────────────────────────────────────────────────────────────
+
+/// Type of one config value, as the input structs deserialize it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Bool,
+    Integer,
+    Float,
+    String,
+    /// A table and its keys in declaration order.
+    Table(Vec<(&'static str, Shape)>),
+    /// An array; an array of tables is written `[[name]]`.
+    Array(Box<Shape>),
+}
+
+impl Shape {
+    fn describe(&self) -> &'static str {
+        match self {
+            Shape::Bool => "boolean",
+            Shape::Integer => "integer",
+            Shape::Float => "number",
+            Shape::String => "string",
+            Shape::Table(_) => "table",
+            Shape::Array(_) => "array",
+        }
+    }
+
+    fn is_section(&self) -> bool {
+        match self {
+            Shape::Table(_) => true,
+            Shape::Array(item) => matches!(**item, Shape::Table(_)),
+            _ => false,
+        }
+    }
+}
+
+/// The schema of the bonding TOML, read off [`BondingConfigInput`].
+pub fn shape() -> Shape {
+    let mut root = None;
+    BondingConfigInput::deserialize(Probe(&mut root))
+        .expect("config input structs only use probe-able types");
+    root.expect("probe records the root table")
+}
+
+type ProbeError = de::value::Error;
+
+/// Deserializer that feeds every field a placeholder value and records the
+/// type each `Deserialize` impl asked for.
+struct Probe<'a>(&'a mut Option<Shape>);
+
+macro_rules! probe_scalar {
+    ($($method:ident => $shape:ident, $visit:ident($value:expr);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+                *self.0 = Some(Shape::$shape);
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ProbeError> {
+        Err(de::Error::custom("config field without a concrete type"))
+    }
+
+    probe_scalar! {
+        deserialize_bool => Bool, visit_bool(false);
+        deserialize_u8 => Integer, visit_u64(0);
+        deserialize_u16 => Integer, visit_u64(0);
+        deserialize_u32 => Integer, visit_u64(0);
+        deserialize_u64 => Integer, visit_u64(0);
+        deserialize_i8 => Integer, visit_i64(0);
+        deserialize_i16 => Integer, visit_i64(0);
+        deserialize_i32 => Integer, visit_i64(0);
+        deserialize_i64 => Integer, visit_i64(0);
+        deserialize_f32 => Float, visit_f64(0.0);
+        deserialize_f64 => Float, visit_f64(0.0);
+        deserialize_str => String, visit_str("");
+        deserialize_string => String, visit_str("");
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        let mut item = None;
+        let value = visitor.visit_seq(ProbeSeq {
+            item: &mut item,
+            done: false,
+        })?;
+        let item = item.ok_or_else(|| de::Error::custom("array without an element type"))?;
+        *self.0 = Some(Shape::Array(Box::new(item)));
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        let mut shapes = Vec::with_capacity(fields.len());
+        let value = visitor.visit_map(ProbeMap {
+            fields,
+            shapes: &mut shapes,
+        })?;
+        *self.0 = Some(Shape::Table(shapes));
+        Ok(value)
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char bytes byte_buf unit unit_struct newtype_struct tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// A one-element array.
+struct ProbeSeq<'a> {
+    item: &'a mut Option<Shape>,
+    done: bool,
+}
+
+impl<'de> SeqAccess<'de> for ProbeSeq<'_> {
+    type Error = ProbeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ProbeError> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+        seed.deserialize(Probe(&mut *self.item)).map(Some)
+    }
+}
+
+/// A table presenting every field the struct declares.
+struct ProbeMap<'a> {
+    fields: &'static [&'static str],
+    shapes: &'a mut Vec<(&'static str, Shape)>,
+}
+
+impl<'de> MapAccess<'de> for ProbeMap<'_> {
+    type Error = ProbeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ProbeError> {
+        let Some(&field) = self.fields.get(self.shapes.len()) else {
+            return Ok(None);
+        };
+        seed.deserialize(BorrowedStrDeserializer::new(field))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ProbeError> {
+        let field = self.fields[self.shapes.len()];
+        let mut slot = None;
+        let value = seed.deserialize(Probe(&mut slot))?;
+        let shape = slot.ok_or_else(|| de::Error::custom(format!("no type for `{field}`")))?;
+        self.shapes.push((field, shape));
+        Ok(value)
+    }
+}
+
+// ─── Documentation ──────────────────────────────────────────────────────────
+
+/// How a key appears in the generated example.
+#[derive(Debug, Clone, Copy)]
+enum Sample {
+    /// Written live: required, or needed for the example to mean anything.
+    Live(&'static str),
+    /// Commented out; this is the built-in default (for the default
+    /// `broadcast` profile where the profile matters).
+    Builtin(&'static str),
+    /// Commented out; unset by default, this is only an illustration.
+    Unset(&'static str),
+    /// A section header.
+    Section,
+    /// A section written entirely commented out.
+    OptionalSection,
+}
+
+struct Doc {
+    path: &'static str,
+    text: &'static str,
+    sample: Sample,
+}
+
+const fn doc(path: &'static str, sample: Sample, text: &'static str) -> Doc {
+    Doc { path, text, sample }
+}
+
+use Sample::{Builtin, Live, OptionalSection, Section, Unset};
+
+/// Prose for every key in [`shape`], keyed by dotted path.
+const DOCS: &[Doc] = &[
+    doc(
+        "version",
+        Live("1"),
+        "Config schema version. Only 1 is supported; 0 or absent means the current version.",
+    ),
+    doc(
+        "profile",
+        Builtin("\"broadcast\""),
+        "Operating profile: broadcast, low-latency or realtime. Sets coherent baselines for playout, probing, failover and bitrate; explicit keys below still override. Defaults marked (profile) below are the broadcast values.",
+    ),
+    doc(
+        "ingest_key",
+        Unset("\"isk_...\""),
+        "Per-stream ingest key minted by the control plane, presented in every link's session HELLO and used to prove each path. Unset: the receiver accepts any source.",
+    ),
+    doc("links", Section, "One entry per bonded link."),
+    doc(
+        "links.id",
+        Live("0"),
+        "Link identifier. Defaults to the entry's position; an entry reusing an earlier ID is ignored.",
+    ),
+    doc(
+        "links.uri",
+        Live("\"203.0.113.10:5000\""),
+        "Receiver address this link sends to (host:port or strata://host:port). Required.",
+    ),
+    doc(
+        "links.interface",
+        Live("\"wwan0\""),
+        "Network interface to bind, e.g. a modem. Each interface may back only one link.",
+    ),
+    doc(
+        "links.profile",
+        Builtin("\"auto\""),
+        "Path-regime override: auto, cellular, fiber, satellite, wifi or lossy. Only affects the regime reported in metrics.",
+    ),
+    doc(
+        "links.rate_cap_bps",
+        Builtin("0"),
+        "Hard ceiling on this link's send rate in bps, e.g. for a metered SIM. 0 means uncapped.",
+    ),
+    doc(
+        "replicas",
+        OptionalSection,
+        "Extra receivers the stream is replicated to (e.g. a backup), each over the same physical links with its own ARQ and FEC sessions.",
+    ),
+    doc(
+        "replicas.name",
+        Live("\"backup\""),
+        "Label for logs and metrics. Defaults to replica-<n>.",
+    ),
+    doc(
+        "replicas.weight",
+        Builtin("1.0"),
+        "Share of each shared link's measured capacity the replica may use, in (0, 1].",
+    ),
+    doc(
+        "replicas.links",
+        Section,
+        "One entry per primary link the replica rides on.",
+    ),
+    doc(
+        "replicas.links.id",
+        Live("0"),
+        "ID of the primary link whose interface this replica link shares.",
+    ),
+    doc(
+        "replicas.links.uri",
+        Live("\"198.51.100.7:5000\""),
+        "The replica receiver's address for this link.",
+    ),
+    doc("receiver", Section, "Receiver playout buffer."),
+    doc(
+        "receiver.start_latency_ms",
+        Builtin("1500"),
+        "Initial playout latency in ms (profile).",
+    ),
+    doc(
+        "receiver.buffer_capacity",
+        Builtin("2048"),
+        "Reassembly buffer size in packets (minimum 16).",
+    ),
+    doc(
+        "receiver.skip_after_ms",
+        Unset("40"),
+        "Skip a missing packet after waiting this long in ms. Unset: wait out the playout window.",
+    ),
+    doc(
+        "lifecycle",
+        Section,
+        "Link phase transitions (Probe, Warm, Live, Degrade, Cooldown), counted in consecutive stats observations.",
+    ),
+    doc(
+        "lifecycle.good_loss_rate_max",
+        Builtin("0.2"),
+        "Highest loss rate (0-1) an observation may show and still count as good.",
+    ),
+    doc(
+        "lifecycle.good_rtt_ms_min",
+        Builtin("1.0"),
+        "Lowest RTT in ms a good observation must show (filters empty stats).",
+    ),
+    doc(
+        "lifecycle.good_capacity_bps_min",
+        Builtin("1.0"),
+        "Lowest capacity estimate in bps a good observation must show.",
+    ),
+    doc(
+        "lifecycle.stats_fresh_ms",
+        Builtin("1500"),
+        "Stats younger than this (ms) are fresh.",
+    ),
+    doc(
+        "lifecycle.stats_stale_ms",
+        Builtin("3000"),
+        "Stats older than this (ms) count as a bad observation.",
+    ),
+    doc(
+        "lifecycle.probe_to_warm_good",
+        Builtin("3"),
+        "Good observations to move from Probe to Warm.",
+    ),
+    doc(
+        "lifecycle.warm_to_live_good",
+        Builtin("10"),
+        "Good observations to move from Warm to Live.",
+    ),
+    doc(
+        "lifecycle.warm_to_degrade_bad",
+        Builtin("3"),
+        "Bad observations to move from Warm to Degrade.",
+    ),
+    doc(
+        "lifecycle.live_to_degrade_bad",
+        Builtin("3"),
+        "Bad observations to move from Live to Degrade.",
+    ),
+    doc(
+        "lifecycle.degrade_to_warm_good",
+        Builtin("5"),
+        "Good observations to recover from Degrade to Warm.",
+    ),
+    doc(
+        "lifecycle.degrade_to_cooldown_bad",
+        Builtin("10"),
+        "Bad observations to move from Degrade to Cooldown.",
+    ),
+    doc(
+        "lifecycle.cooldown_ms",
+        Builtin("2000"),
+        "Time in Cooldown before a link is probed again (ms).",
+    ),
+    doc(
+        "scheduler",
+        Section,
+        "Scheduler tuning. The defaults are field-tested; change one knob at a time.",
+    ),
+    doc(
+        "scheduler.redundancy_enabled",
+        Builtin("false"),
+        "Adaptive packet duplication onto spare capacity.",
+    ),
+    doc(
+        "scheduler.redundancy_spare_ratio",
+        Builtin("0.5"),
+        "Spare-capacity ratio (0-1) above which packets are duplicated.",
+    ),
+    doc(
+        "scheduler.redundancy_max_packet_bytes",
+        Builtin("10000"),
+        "Largest packet in bytes eligible for duplication.",
+    ),
+    doc(
+        "scheduler.redundancy_target_links",
+        Builtin("2"),
+        "Number of links a duplicated packet goes to.",
+    ),
+    doc(
+        "scheduler.critical_broadcast",
+        Builtin("false"),
+        "Broadcast keyframes to every alive link. Doubles load when a link is marginal; leave off on LTE.",
+    ),
+    doc(
+        "scheduler.duplicate_spread_ms",
+        Builtin("0.0"),
+        "Stagger broadcast and redundant copies over this window (ms, 0-5) so they don't hit a shared bottleneck together.",
+    ),
+    doc(
+        "scheduler.failover_enabled",
+        Builtin("false"),
+        "Fast-failover: broadcast all traffic for a while after an RTT spike (profile).",
+    ),
+    doc(
+        "scheduler.failover_duration_ms",
+        Builtin("3000"),
+        "How long a failover broadcast lasts (ms).",
+    ),
+    doc(
+        "scheduler.failover_rtt_spike_factor",
+        Builtin("3.0"),
+        "RTT jump between ticks, as a multiple, that triggers failover.",
+    ),
+    doc(
+        "scheduler.ewma_alpha",
+        Builtin("0.125"),
+        "Smoothing factor (0-1) for link stats.",
+    ),
+    doc(
+        "scheduler.prediction_horizon_s",
+        Builtin("0.5"),
+        "How far ahead link trends are extrapolated (s).",
+    ),
+    doc(
+        "scheduler.capacity_floor_bps",
+        Builtin("1500000.0"),
+        "Bootstrap capacity assumed for a link with no estimate yet (bps).",
+    ),
+    doc(
+        "scheduler.penalty_decay",
+        Builtin("0.7"),
+        "Penalty multiplier (0-1) applied when a link's capacity drops.",
+    ),
+    doc(
+        "scheduler.penalty_recovery",
+        Builtin("0.05"),
+        "Penalty recovered per refresh (0-1).",
+    ),
+    doc(
+        "scheduler.jitter_latency_multiplier",
+        Builtin("2.0"),
+        "Multiple of p95 jitter added to the adaptive latency.",
+    ),
+    doc(
+        "scheduler.max_latency_ms",
+        Unset("3000"),
+        "Ceiling on the adaptive playout window (ms). Unset: the profile's ceiling, 3000 for broadcast.",
+    ),
+    doc(
+        "scheduler.stats_interval_ms",
+        Builtin("1000"),
+        "Stats and bitrate-adaptation tick (ms, minimum 100). Adaptation sustain windows are counted in ticks.",
+    ),
+    doc(
+        "scheduler.channel_capacity",
+        Builtin("1000"),
+        "Runtime packet channel depth (minimum 16).",
+    ),
+    doc(
+        "scheduler.saturation_probe_interval_s",
+        Builtin("1e10"),
+        "Interval between saturation probes per link (s). The default effectively disables them.",
+    ),
+    doc(
+        "scheduler.saturation_probe_duration_s",
+        Builtin("0.4"),
+        "Duration of one saturation probe (s).",
+    ),
+    doc(
+        "scheduler.ppd_probe_interval_s",
+        Builtin("2.0"),
+        "Interval between packet-pair capacity probes per link (s).",
+    ),
+    doc(
+        "scheduler.link_weights",
+        Unset("[1.0, 1.0, 0.5]"),
+        "Scheduling weight per link, indexed by link ID (0-100): EDPF credits a link with this multiple of its capacity, and 0 keeps it for last-resort use only. Links without an entry weigh 1.0.",
+    ),
+];
+
+fn lookup(path: &str) -> Option<&'static Doc> {
+    DOCS.iter().find(|d| d.path == path)
+}
+
+// ─── Example ────────────────────────────────────────────────────────────────
+
+/// A fully commented example config, generated from [`shape`] and the
+/// field docs. Commented-out keys show their built-in default.
+pub fn example() -> String {
+    render(false)
+}
+
+fn render(defaults_live: bool) -> String {
+    let Shape::Table(root) = shape() else {
+        unreachable!("the root is a table");
+    };
+    let mut out = String::from(
+        "# Strata bonding configuration (generated by `strata-config example`).\n\
+         # Commented-out keys show their built-in default; uncomment to change.\n",
+    );
+    render_table(&mut out, "", &root, false, defaults_live);
+    out
+}
+
+fn render_table(
+    out: &mut String,
+    prefix: &str,
+    fields: &[(&'static str, Shape)],
+    commented: bool,
+    defaults_live: bool,
+) {
+    for (name, _) in fields.iter().filter(|(_, s)| !s.is_section()) {
+        let path = join(prefix, name);
+        let doc = lookup(&path);
+        out.push('\n');
+        push_doc(out, doc);
+        let (value, live) = match doc.map(|d| d.sample) {
+            Some(Live(v)) => (v, !commented),
+            Some(Builtin(v)) => (v, !commented && defaults_live),
+            Some(Unset(v)) => (v, false),
+            _ => continue,
+        };
+        push_line(out, !live, &format!("{name} = {value}"));
+    }
+    for (name, shape) in fields.iter().filter(|(_, s)| s.is_section()) {
+        let path = join(prefix, name);
+        let doc = lookup(&path);
+        let commented = commented || matches!(doc.map(|d| d.sample), Some(OptionalSection));
+        let (header, inner) = match shape {
+            Shape::Table(inner) => (format!("[{path}]"), inner),
+            Shape::Array(item) => match &**item {
+                Shape::Table(inner) => (format!("[[{path}]]"), inner),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        out.push('\n');
+        push_doc(out, doc);
+        push_line(out, commented, &header);
+        render_table(out, &path, inner, commented, defaults_live);
+    }
+}
+
+fn push_doc(out: &mut String, doc: Option<&Doc>) {
+    let Some(doc) = doc else {
+        return;
+    };
+    let mut line = String::new();
+    for word in doc.text.split_whitespace() {
+        if !line.is_empty() && line.len() + word.len() + 1 > 76 {
+            push_line(out, true, &line);
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        push_line(out, true, &line);
+    }
+}
+
+fn push_line(out: &mut String, commented: bool, text: &str) {
+    if commented {
+        out.push_str("# ");
+    }
+    out.push_str(text);
+    out.push('\n');
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+// ─── Check ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One finding from [`check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line in the checked text, when the finding has a location.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Validate a bonding TOML against the schema and the same resolution the
+/// runtime performs. Returns every finding; the config is usable iff none
+/// is an [`Severity::Error`].
+pub fn check(input: &str) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    let root = match DeTable::parse(input) {
+        Ok(root) => root,
+        Err(e) => {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                line: e.span().map(|s| line_of(input, s.start)),
+                message: e.message().trim().to_string(),
+            });
+            return out;
+        }
+    };
+    let Shape::Table(fields) = shape() else {
+        unreachable!("the root is a table");
+    };
+    check_table(input, root.get_ref(), &fields, "", &mut out);
+    if !out.is_empty() {
+        return out;
+    }
+
+    lint_links(input, root.get_ref(), &mut out);
+    // Structure is right; what remains is range errors the types catch
+    // (e.g. a negative count) and the cross-field rules in `resolve`.
+    match toml::from_str::<BondingConfigInput>(input) {
+        Ok(parsed) => {
+            if let Err(message) = parsed.resolve() {
+                out.push(Diagnostic {
+                    severity: Severity::Error,
+                    line: None,
+                    message,
+                });
+            }
+        }
+        Err(e) => out.push(Diagnostic {
+            severity: Severity::Error,
+            line: e.span().map(|s| line_of(input, s.start)),
+            message: e.message().trim().to_string(),
+        }),
+    }
+    out
+}
+
+fn check_table(
+    input: &str,
+    table: &DeTable<'_>,
+    fields: &[(&'static str, Shape)],
+    prefix: &str,
+    out: &mut Vec<Diagnostic>,
+) {
+    for (key, value) in table.iter() {
+        let name: &str = key.get_ref();
+        let path = join(prefix, name);
+        match fields.iter().find(|(f, _)| *f == name) {
+            Some((_, shape)) => check_value(input, value, shape, &path, out),
+            None => {
+                let section = if prefix.is_empty() {
+                    "at the top level".to_string()
+                } else {
+                    format!("in [{prefix}]")
+                };
+                let hint = suggest(name, fields)
+                    .map(|s| format!(" (did you mean `{s}`?)"))
+                    .unwrap_or_default();
+                out.push(Diagnostic {
+                    severity: Severity::Error,
+                    line: Some(line_of(input, key.span().start)),
+                    message: format!("unknown key `{name}` {section}{hint}"),
+                });
+            }
+        }
+    }
+}
+
+fn check_value(
+    input: &str,
+    value: &Spanned<DeValue<'_>>,
+    shape: &Shape,
+    path: &str,
+    out: &mut Vec<Diagnostic>,
+) {
+    match (shape, value.get_ref()) {
+        (Shape::Bool, DeValue::Boolean(_))
+        | (Shape::Integer, DeValue::Integer(_))
+        | (Shape::Float, DeValue::Float(_) | DeValue::Integer(_))
+        | (Shape::String, DeValue::String(_)) => {}
+        (Shape::Table(fields), DeValue::Table(table)) => {
+            check_table(input, table, fields, path, out);
+        }
+        (Shape::Array(item), DeValue::Array(items)) => {
+            for v in items.iter() {
+                check_value(input, v, item, path, out);
+            }
+        }
+        (shape, found) => out.push(Diagnostic {
+            severity: Severity::Error,
+            line: Some(line_of(input, value.span().start)),
+            message: format!(
+                "`{path}` must be a {}, found a {}",
+                shape.describe(),
+                kind(found)
+            ),
+        }),
+    }
+}
+
+/// Link mistakes `resolve` tolerates silently but that are never intended.
+fn lint_links(input: &str, root: &DeTable<'_>, out: &mut Vec<Diagnostic>) {
+    let Some(DeValue::Array(links)) = root
+        .iter()
+        .find(|(k, _)| k.get_ref() == "links")
+        .map(|(_, v)| v.get_ref())
+    else {
+        out.push(Diagnostic {
+            severity: Severity::Warning,
+            line: None,
+            message: "no [[links]] configured; the sender has nothing to bond".to_string(),
+        });
+        return;
+    };
+    let mut seen: Vec<(i64, usize)> = Vec::new();
+    for (idx, link) in links.iter().enumerate() {
+        let DeValue::Table(table) = link.get_ref() else {
+            continue;
+        };
+        let field = |name: &str| table.iter().find(|(k, _)| k.get_ref() == name);
+        let line = line_of(input, link.span().start);
+        if field("uri")
+            .is_none_or(|(_, v)| matches!(v.get_ref(), DeValue::String(s) if s.trim().is_empty()))
+        {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                line: Some(line),
+                message: format!("links entry {idx} has no `uri`"),
+            });
+        }
+        let id = match field("id").map(|(_, v)| v) {
+            Some(v) => match v.get_ref() {
+                DeValue::Integer(i) => i
+                    .as_str()
+                    .parse()
+                    .ok()
+                    .map(|id| (id, line_of(input, v.span().start))),
+                _ => None,
+            },
+            None => Some((idx as i64, line)),
+        };
+        let Some((id, line)) = id else {
+            continue;
+        };
+        if let Some((_, first)) = seen.iter().find(|(seen_id, _)| *seen_id == id) {
+            out.push(Diagnostic {
+                severity: Severity::Warning,
+                line: Some(line),
+                message: format!(
+                    "link id {id} is already used on line {first}; this entry is ignored"
+                ),
+            });
+        } else {
+            seen.push((id, line));
+        }
+    }
+}
+
+fn kind(value: &DeValue<'_>) -> &'static str {
+    match value {
+        DeValue::String(_) => "string",
+        DeValue::Integer(_) => "integer",
+        DeValue::Float(_) => "float",
+        DeValue::Boolean(_) => "boolean",
+        DeValue::Datetime(_) => "datetime",
+        DeValue::Array(_) => "array",
+        DeValue::Table(_) => "table",
+    }
+}
+
+/// Closest known key within two edits, for typos.
+fn suggest(name: &str, fields: &[(&'static str, Shape)]) -> Option<&'static str> {
+    fields
+        .iter()
+        .map(|(f, _)| (*f, edit_distance(name, f)))
+        .filter(|(_, d)| *d <= 2)
+        .min_by_key(|(_, d)| *d)
+        .map(|(f, _)| f)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let sub = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = sub.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+fn line_of(input: &str, offset: usize) -> usize {
+    input[..offset.min(input.len())].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BondingConfig;
+
+    fn leaf_paths(prefix: &str, fields: &[(&'static str, Shape)], out: &mut Vec<String>) {
+        for (name, shape) in fields {
+            let path = join(prefix, name);
+            match shape {
+                Shape::Table(inner) => leaf_paths(&path, inner, out),
+                Shape::Array(item) => {
+                    if let Shape::Table(inner) = &**item {
+                        leaf_paths(&path, inner, out);
+                    }
+                }
+                _ => {}
+            }
+            out.push(path);
+        }
+    }
+
+    #[test]
+    fn every_key_is_documented_and_every_doc_is_a_key() {
+        let Shape::Table(root) = shape() else {
+            panic!("root is a table");
+        };
+        let mut paths = Vec::new();
+        leaf_paths("", &root, &mut paths);
+        for path in &paths {
+            assert!(lookup(path).is_some(), "`{path}` has no entry in DOCS");
+        }
+        for doc in DOCS {
+            assert!(
+                paths.iter().any(|p| p == doc.path),
+                "DOCS has stale `{}`",
+                doc.path
+            );
+        }
+    }
+
+    #[test]
+    fn example_is_a_clean_config() {
+        let example = example();
+        assert_eq!(check(&example), Vec::new(), "{example}");
+        assert!(example.contains("\n[[links]]\n"));
+        assert!(example.contains("\n# [[replicas.links]]\n"));
+        assert!(example.contains("\n# ewma_alpha = 0.125\n"));
+    }
+
+    #[test]
+    fn documented_defaults_match_the_code() {
+        // Uncommenting every default must not change what the runtime gets.
+        let live = BondingConfig::from_toml_str(&render(true)).unwrap();
+        let implicit = BondingConfig::from_toml_str(
+            "[[links]]\nid = 0\nuri = \"203.0.113.10:5000\"\ninterface = \"wwan0\"\n",
+        )
+        .unwrap();
+        assert_eq!(live.profile, implicit.profile);
+        assert_eq!(live.links, implicit.links);
+        assert_eq!(live.receiver, implicit.receiver);
+        assert_eq!(
+            format!("{:?}", live.lifecycle),
+            format!("{:?}", implicit.lifecycle)
+        );
+        assert_eq!(
+            format!("{:?}", live.scheduler),
+            format!("{:?}", implicit.scheduler)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_and_wrong_types_are_located() {
+        let input = "version = 1\n\n[[links]]\nuri = \"a:1\"\n\n[scheduler]\newma_alhpa = 0.2\nfailover_enabled = \"yes\"\n";
+        let found = check(input);
+        assert_eq!(found.len(), 2, "{found:?}");
+        assert_eq!(found[0].line, Some(7));
+        assert!(found[0].message.contains("`ewma_alhpa` in [scheduler]"));
+        assert!(found[0].message.contains("did you mean `ewma_alpha`?"));
+        assert_eq!(found[1].line, Some(8));
+        assert_eq!(
+            found[1].message,
+            "`scheduler.failover_enabled` must be a boolean, found a string"
+        );
+    }
+
+    #[test]
+    fn syntax_range_and_resolve_errors_are_reported() {
+        let syntax = check("[[links]\nuri = 1\n");
+        assert_eq!(syntax.len(), 1);
+        assert_eq!(syntax[0].line, Some(1));
+
+        let range = check("[[links]]\nuri = \"a:1\"\n\n[receiver]\nbuffer_capacity = -4\n");
+        assert_eq!(range.len(), 1, "{range:?}");
+        assert_eq!(range[0].line, Some(5));
+
+        let resolve = check("profile = \"fastest\"\n[[links]]\nuri = \"a:1\"\n");
+        assert_eq!(resolve.len(), 1);
+        assert!(resolve[0].message.starts_with("unknown profile 'fastest'"));
+    }
+
+    #[test]
+    fn link_mistakes_are_flagged() {
+        let input = "[[links]]\nid = 1\nuri = \"a:1\"\n\n[[links]]\nid = 1\nuri = \"b:1\"\n\n[[links]]\nid = 2\n";
+        let found = check(input);
+        assert_eq!(found.len(), 2, "{found:?}");
+        assert_eq!(found[0].severity, Severity::Warning);
+        assert_eq!(found[0].line, Some(6));
+        assert!(found[0].message.contains("already used on line 2"));
+        assert_eq!(found[1].severity, Severity::Error);
+        assert_eq!(found[1].line, Some(9));
+
+        let none = check("version = 1\n");
+        assert_eq!(none.len(), 1);
+        assert_eq!(none[0].severity, Severity::Warning);
+    }
+}
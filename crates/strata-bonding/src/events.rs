@@ -0,0 +1,189 @@
+//! Structured state-transition events.
+//!
+//! Periodic stats say where the bond *is*; events say what *changed* and in
+//! which order — a link going down before failover kicks in, a phase walk
+//! from `probe` to `live`. The scheduler emits them on every metrics
+//! refresh into an [`EventLog`], which fans each one out to every
+//! subscriber. Subscribers that stop reading lose events rather than stall
+//! the scheduler.
+
+use crate::net::interface::LinkPhase;
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Events buffered per subscriber before new ones are dropped.
+const SUBSCRIBER_CAPACITY: usize = 1024;
+
+/// Why fast-failover was entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailoverCause {
+    /// A link fell from `live` to `degrade`, or into `cooldown`/`reset`.
+    PhaseDegraded,
+    /// A link's RTT stayed above the spike threshold.
+    RttSpike,
+}
+
+/// A bonding state transition.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BondingEvent {
+    LinkAdded {
+        link_id: usize,
+    },
+    LinkRemoved {
+        link_id: usize,
+    },
+    /// The link became usable for scheduling.
+    LinkUp {
+        link_id: usize,
+    },
+    /// The link stopped being usable for scheduling.
+    LinkDown {
+        link_id: usize,
+    },
+    PhaseChanged {
+        link_id: usize,
+        #[serde(serialize_with = "phase_str")]
+        from: LinkPhase,
+        #[serde(serialize_with = "phase_str")]
+        to: LinkPhase,
+    },
+    FailoverEntered {
+        link_id: usize,
+        cause: FailoverCause,
+    },
+    FailoverExited,
+    /// The link collapsed and is avoided unless every link is.
+    LinkBlacklisted {
+        link_id: usize,
+    },
+    LinkUnblacklisted {
+        link_id: usize,
+    },
+    /// The receiver asked for an early IDR after a gap it could not
+    /// repair. `link_id` is the link the request arrived on first.
+    KeyframeRequested {
+        link_id: usize,
+        request_id: u32,
+        lost_packets: u32,
+    },
+}
+
+fn phase_str<S: serde::Serializer>(phase: &LinkPhase, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(phase.as_str())
+}
+
+/// A [`BondingEvent`] with its position in the log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventRecord {
+    /// Strictly increasing per log; gaps mean this subscriber dropped events.
+    pub seq: u64,
+    pub wall_time_ms: u64,
+    #[serde(flatten)]
+    pub event: BondingEvent,
+}
+
+/// Fan-out event channel. Clones share the same subscribers.
+#[derive(Clone, Default)]
+pub struct EventLog {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_seq: AtomicU64,
+    subscribers: Mutex<Vec<Sender<EventRecord>>>,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A receiver for every event emitted from now on.
+    pub fn subscribe(&self) -> Receiver<EventRecord> {
+        let (tx, rx) = crossbeam_channel::bounded(SUBSCRIBER_CAPACITY);
+        self.lock().push(tx);
+        rx
+    }
+
+    pub fn emit(&self, event: BondingEvent) {
+        let mut subscribers = self.lock();
+        if subscribers.is_empty() {
+            return;
+        }
+        let record = EventRecord {
+            seq: self.inner.next_seq.fetch_add(1, Ordering::Relaxed),
+            wall_time_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            event,
+        };
+        tracing::debug!(target: "strata::events", ?record, "bonding event");
+        subscribers.retain(|tx| match tx.try_send(record.clone()) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Sender<EventRecord>>> {
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_subscriber_sees_every_event_in_order() {
+        let log = EventLog::new();
+        let (a, b) = (log.subscribe(), log.subscribe());
+        log.emit(BondingEvent::LinkUp { link_id: 1 });
+        log.emit(BondingEvent::FailoverExited);
+
+        for rx in [a, b] {
+            let got: Vec<_> = rx.try_iter().collect();
+            assert_eq!(got.len(), 2);
+            assert_eq!(got[0].event, BondingEvent::LinkUp { link_id: 1 });
+            assert_eq!(got[1].event, BondingEvent::FailoverExited);
+            assert!(got[0].seq < got[1].seq);
+        }
+    }
+
+    #[test]
+    fn dropped_subscribers_are_pruned() {
+        let log = EventLog::new();
+        drop(log.subscribe());
+        let kept = log.subscribe();
+        log.emit(BondingEvent::LinkAdded { link_id: 0 });
+        assert_eq!(log.lock().len(), 1);
+        assert_eq!(kept.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn events_serialize_flat_with_snake_case_tags() {
+        let record = EventRecord {
+            seq: 3,
+            wall_time_ms: 1,
+            event: BondingEvent::PhaseChanged {
+                link_id: 2,
+                from: LinkPhase::Probe,
+                to: LinkPhase::Live,
+            },
+        };
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["event"], "phase_changed");
+        assert_eq!(json["from"], "probe");
+        assert_eq!(json["to"], "live");
+        assert_eq!(json["seq"], 3);
+    }
+}
@@ -11,9 +11,12 @@
 //! - [`receiver`] — Bonding receiver with jitter-buffer reassembly
 //! - [`config`] — TOML-based configuration with versioned schema
 //! - [`runtime`] — Thread-safe runtime that owns the scheduler loop
+//! - [`events`] — Link and failover state-transition event stream
+//! - [`telemetry`] — Pluggable sinks for periodic snapshots and events
 
 pub mod adaptation;
 pub mod config;
+pub mod events;
 pub mod media;
 pub mod metrics;
 pub mod modem;
@@ -23,6 +26,7 @@ pub mod receiver;
 pub mod runtime;
 pub mod scheduler;
 pub mod signal;
+pub mod telemetry;
 
 /// Initialize the strata-bonding library.
 ///
@@ -0,0 +1,318 @@
+//! # A/V Sync Monitoring
+//!
+//! Watches the reassembled MPEG-TS stream for the clocks that decide
+//! lipsync: the PES presentation timestamps of the audio and video
+//! elementary streams and the programme clock reference (PCR).
+//!
+//! Three independent measurements, so a lipsync complaint can be placed:
+//!
+//! - **A/V skew and drift** — video PTS minus audio PTS as the two streams
+//!   are interleaved by the mux. A constant offset is the encoder's
+//!   interleave; a skew that *moves* over the stream is the sender's audio
+//!   and video clocks drifting apart.
+//! - **PCR jitter** — RFC 3550 inter-arrival jitter of the PCR against the
+//!   time reassembly released it. What the jitter buffer failed to absorb
+//!   and handed to the demuxer — transport, not the sender.
+//! - **Video PTS lead** — how far ahead of the PCR video frames arrive.
+//!   Healthy transport and a healthy encoder keep it steady; if skew and
+//!   jitter are clean but downstream still drifts, look past the receiver.
+//!
+//! Non-TS payloads are ignored. All parsing is bounds-checked; a malformed
+//! packet is skipped, never a panic on the delivery path.
+
+use quanta::Instant;
+
+const TS_PACKET_LEN: usize = 188;
+const SYNC_BYTE: u8 = 0x47;
+/// PTS/DTS and the PCR base tick at 90 kHz.
+const TICKS_PER_MS: f64 = 90.0;
+/// PTS/DTS and the PCR base are 33-bit counters.
+const TS_WRAP: i64 = 1 << 33;
+/// A PCR step outside `0..=PCR_DISCONT_MS` is a splice or encoder restart,
+/// not jitter: the clock baselines start over.
+const PCR_DISCONT_MS: f64 = 1000.0;
+/// EWMA weight of the skew and PTS-lead estimates.
+const SMOOTHING: f64 = 1.0 / 16.0;
+/// Skew samples taken before the drift baseline is fixed — long enough for
+/// the EWMA to settle past the first GOP.
+const BASELINE_SAMPLES: u32 = 64;
+
+/// A/V sync measurements of the delivered stream, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AvSyncStats {
+    /// Smoothed video PTS − audio PTS across the mux interleave.
+    pub av_skew_ms: f64,
+    /// Change in skew since the stream settled. Non-zero and growing means
+    /// the sender's audio and video clocks disagree.
+    pub av_drift_ms: f64,
+    /// PCR inter-arrival jitter after reassembly.
+    pub pcr_jitter_ms: f64,
+    /// Smoothed video PTS − PCR: how early frames reach the demuxer.
+    pub video_pts_lead_ms: f64,
+}
+
+/// Stateful monitor; one per stream, fed every released payload in order.
+#[derive(Debug, Default)]
+pub struct AvSyncMonitor {
+    pcr_pid: Option<u16>,
+    /// Last PCR base and when it was released.
+    last_pcr: Option<(i64, Instant)>,
+    pcr_jitter_ms: Option<f64>,
+    last_video_pts: Option<i64>,
+    last_audio_pts: Option<i64>,
+    av_skew_ms: Option<f64>,
+    skew_samples: u32,
+    baseline_skew_ms: Option<f64>,
+    video_pts_lead_ms: Option<f64>,
+}
+
+impl AvSyncMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one released payload. `discont` marks a hole before it: the
+    /// clocks are not compared across it.
+    pub fn observe(&mut self, data: &[u8], discont: bool, now: Instant) {
+        if data.first() != Some(&SYNC_BYTE) {
+            return;
+        }
+        if discont {
+            self.last_pcr = None;
+            self.last_video_pts = None;
+            self.last_audio_pts = None;
+        }
+        for p in data.chunks_exact(TS_PACKET_LEN) {
+            if p[0] == SYNC_BYTE {
+                self.packet(p, now);
+            }
+        }
+    }
+
+    /// Current measurements, once the stream has shown a PCR or both
+    /// elementary streams.
+    pub fn stats(&self) -> Option<AvSyncStats> {
+        if self.pcr_jitter_ms.is_none() && self.av_skew_ms.is_none() {
+            return None;
+        }
+        let skew = self.av_skew_ms.unwrap_or(0.0);
+        Some(AvSyncStats {
+            av_skew_ms: skew,
+            av_drift_ms: self.baseline_skew_ms.map_or(0.0, |b| skew - b),
+            pcr_jitter_ms: self.pcr_jitter_ms.unwrap_or(0.0),
+            video_pts_lead_ms: self.video_pts_lead_ms.unwrap_or(0.0),
+        })
+    }
+
+    fn packet(&mut self, p: &[u8], now: Instant) {
+        let pusi = (p[1] & 0x40) != 0;
+        let pid = (((p[1] & 0x1F) as u16) << 8) | p[2] as u16;
+        let afc = (p[3] >> 4) & 0x3;
+        let has_af = afc & 0b10 != 0;
+        let has_payload = afc & 0b01 != 0;
+
+        let mut payload_off = 4;
+        if has_af {
+            let af_len = p[4] as usize;
+            // PCR_flag (0x10) in the flags byte, then 6 bytes of PCR.
+            if af_len >= 7 && p[5] & 0x10 != 0 && *self.pcr_pid.get_or_insert(pid) == pid {
+                self.on_pcr(pcr_base(&p[6..11]), now);
+            }
+            payload_off = 5 + af_len;
+        }
+        if pusi
+            && has_payload
+            && let Some(pes) = p.get(payload_off..)
+        {
+            self.pes_start(pes, now);
+        }
+    }
+
+    fn pes_start(&mut self, pes: &[u8], now: Instant) {
+        // packet_start_code_prefix, stream_id, length, then the optional
+        // header: flags byte 7 carries PTS_DTS_flags, PTS from byte 9.
+        if pes.len() < 14 || pes[..3] != [0, 0, 1] || pes[7] & 0x80 == 0 {
+            return;
+        }
+        let pts = timestamp(&pes[9..14]);
+        match pes[3] {
+            0xE0..=0xEF => {
+                self.last_video_pts = Some(pts);
+                if let Some((pcr, at)) = self.last_pcr {
+                    let pcr_now_ms = now.saturating_duration_since(at).as_secs_f64() * 1000.0;
+                    let lead = ts_diff(pts, pcr) as f64 / TICKS_PER_MS - pcr_now_ms;
+                    smooth(&mut self.video_pts_lead_ms, lead);
+                }
+            }
+            // MPEG audio, and private stream 1 (AC-3, Opus in TS).
+            0xC0..=0xDF | 0xBD => self.last_audio_pts = Some(pts),
+            _ => return,
+        }
+        if let (Some(v), Some(a)) = (self.last_video_pts, self.last_audio_pts) {
+            let skew = ts_diff(v, a) as f64 / TICKS_PER_MS;
+            smooth(&mut self.av_skew_ms, skew);
+            self.skew_samples += 1;
+            if self.skew_samples == BASELINE_SAMPLES {
+                self.baseline_skew_ms = self.av_skew_ms;
+            }
+        }
+    }
+
+    fn on_pcr(&mut self, pcr: i64, now: Instant) {
+        if let Some((prev, at)) = self.last_pcr {
+            let pcr_ms = ts_diff(pcr, prev) as f64 / TICKS_PER_MS;
+            if (0.0..=PCR_DISCONT_MS).contains(&pcr_ms) {
+                let wall_ms = now.saturating_duration_since(at).as_secs_f64() * 1000.0;
+                let d = (wall_ms - pcr_ms).abs();
+                let j = self.pcr_jitter_ms.get_or_insert(0.0);
+                *j += (d - *j) / 16.0;
+            } else {
+                // New timeline: the old skew is no reference for drift.
+                self.skew_samples = 0;
+                self.baseline_skew_ms = None;
+                self.av_skew_ms = None;
+            }
+        }
+        self.last_pcr = Some((pcr, now));
+    }
+}
+
+fn smooth(estimate: &mut Option<f64>, sample: f64) {
+    *estimate = Some(match *estimate {
+        Some(e) => e + SMOOTHING * (sample - e),
+        None => sample,
+    });
+}
+
+/// `a − b` on the 33-bit timestamp circle.
+fn ts_diff(a: i64, b: i64) -> i64 {
+    let d = (a - b).rem_euclid(TS_WRAP);
+    if d >= TS_WRAP / 2 { d - TS_WRAP } else { d }
+}
+
+/// 33-bit PTS/DTS from its 5-byte marker-bit encoding.
+fn timestamp(b: &[u8]) -> i64 {
+    (((b[0] >> 1) & 0x07) as i64) << 30
+        | (b[1] as i64) << 22
+        | ((b[2] >> 1) as i64) << 15
+        | (b[3] as i64) << 7
+        | (b[4] >> 1) as i64
+}
+
+/// 33-bit PCR base (90 kHz); the 27 MHz extension is below our resolution.
+fn pcr_base(b: &[u8]) -> i64 {
+    (b[0] as i64) << 25
+        | (b[1] as i64) << 17
+        | (b[2] as i64) << 9
+        | (b[3] as i64) << 1
+        | (b[4] >> 7) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const PCR_PID: u16 = 0x100;
+    const AUDIO_PID: u16 = 0x101;
+
+    fn encode_timestamp(ts: i64) -> [u8; 5] {
+        [
+            0x21 | (((ts >> 30) & 0x07) << 1) as u8,
+            (ts >> 22) as u8,
+            (((ts >> 15) & 0x7F) << 1) as u8 | 1,
+            (ts >> 7) as u8,
+            ((ts & 0x7F) << 1) as u8 | 1,
+        ]
+    }
+
+    /// One TS packet: optional PCR in the adaptation field, optional PES
+    /// header with a PTS.
+    fn ts_packet(pid: u16, pcr: Option<i64>, pes: Option<(u8, i64)>) -> Vec<u8> {
+        let mut p = vec![SYNC_BYTE, 0, 0, 0];
+        p[1] = ((pid >> 8) as u8 & 0x1F) | if pes.is_some() { 0x40 } else { 0 };
+        p[2] = pid as u8;
+        let mut afc = 0b01;
+        if let Some(pcr) = pcr {
+            afc |= 0b10;
+            p.extend_from_slice(&[7, 0x10]);
+            p.extend_from_slice(&[
+                (pcr >> 25) as u8,
+                (pcr >> 17) as u8,
+                (pcr >> 9) as u8,
+                (pcr >> 1) as u8,
+                ((pcr & 1) << 7) as u8 | 0x7E,
+                0,
+            ]);
+        }
+        p[3] = afc << 4;
+        if let Some((stream_id, pts)) = pes {
+            p.extend_from_slice(&[0, 0, 1, stream_id, 0, 0, 0x80, 0x80, 5]);
+            p.extend_from_slice(&encode_timestamp(pts));
+        }
+        p.resize(TS_PACKET_LEN, 0xFF);
+        p
+    }
+
+    #[test]
+    fn timestamps_roundtrip() {
+        for ts in [0, 1, 90_000, TS_WRAP - 1] {
+            assert_eq!(timestamp(&encode_timestamp(ts)), ts);
+        }
+        let pcr = ts_packet(PCR_PID, Some(123_456_789), None);
+        assert_eq!(pcr_base(&pcr[6..11]), 123_456_789);
+        assert_eq!(ts_diff(5, TS_WRAP - 5), 10);
+        assert_eq!(ts_diff(TS_WRAP - 5, 5), -10);
+    }
+
+    #[test]
+    fn ignores_non_ts_payloads() {
+        let mut m = AvSyncMonitor::new();
+        m.observe(b"not a transport stream", false, Instant::now());
+        assert_eq!(m.stats(), None);
+    }
+
+    #[test]
+    fn measures_skew_drift_and_pcr_jitter() {
+        let mut m = AvSyncMonitor::new();
+        let start = Instant::now();
+        // 40 ms frames; audio runs 0.5 ms/frame slow against video and
+        // trails it by 100 ms to begin with. PCRs arrive on time.
+        for i in 0..200i64 {
+            let now = start + Duration::from_millis(40 * i as u64);
+            let pcr = 900_000 + i * 40 * 90;
+            let video = pcr + 200 * 90;
+            let audio = video - 100 * 90 - i * 45;
+            let mut buf = ts_packet(PCR_PID, Some(pcr), Some((0xE0, video)));
+            buf.extend(ts_packet(AUDIO_PID, None, Some((0xC0, audio))));
+            m.observe(&buf, false, now);
+        }
+        let s = m.stats().unwrap();
+        assert!(s.pcr_jitter_ms < 0.01, "on-time PCRs: {s:?}");
+        assert!((s.video_pts_lead_ms - 200.0).abs() < 0.5, "{s:?}");
+        assert!(s.av_skew_ms > 100.0, "{s:?}");
+        // The baseline lands ~32 frames in (two samples a frame), leaving
+        // ~167 frames of 0.5 ms drift.
+        assert!(s.av_drift_ms > 75.0 && s.av_drift_ms < 90.0, "{s:?}");
+    }
+
+    #[test]
+    fn late_pcrs_show_as_jitter_and_a_jump_resets_the_baseline() {
+        let mut m = AvSyncMonitor::new();
+        let start = Instant::now();
+        for i in 0..100u64 {
+            // Every other PCR released 10 ms late.
+            let late = if i % 2 == 1 { 10 } else { 0 };
+            let now = start + Duration::from_millis(40 * i + late);
+            let buf = ts_packet(PCR_PID, Some(i as i64 * 40 * 90), None);
+            m.observe(&buf, false, now);
+        }
+        let jitter = m.stats().unwrap().pcr_jitter_ms;
+        assert!((jitter - 10.0).abs() < 1.0, "jitter {jitter}");
+
+        // A 10 s jump is a new timeline, not 10 s of jitter.
+        let now = start + Duration::from_millis(4_040);
+        m.observe(&ts_packet(PCR_PID, Some(20_000 * 90), None), false, now);
+        assert!((m.stats().unwrap().pcr_jitter_ms - jitter).abs() < f64::EPSILON);
+    }
+}
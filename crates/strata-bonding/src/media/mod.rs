@@ -2,7 +2,9 @@
 //!
 //! Parses H.264/H.265/AV1 bitstreams to classify packets by importance.
 //! This enables the scheduler to protect keyframes, broadcast parameter sets,
-//! and drop non-reference B-frames under pressure.
+//! and drop non-reference B-frames under pressure. On the receive side,
+//! [`av_sync`] watches the delivered MPEG-TS clocks for lipsync problems.
 
+pub mod av_sync;
 pub mod nal;
 pub mod priority;
@@ -12,6 +12,7 @@ use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
+use strata_transport::stats::{LOSS_HIST_BUCKETS, LossPatternStats};
 
 /// Render a set of link metrics as Prometheus text exposition format.
 pub fn render_prometheus(links: &HashMap<usize, LinkMetrics>) -> String {
@@ -467,6 +468,23 @@ pub fn to_telemetry_json(links: &HashMap<usize, LinkMetrics>) -> String {
                 if let Some(rtp) = m.rtprop_ms {
                     obj["rtprop_ms"] = serde_json::json!(rtp);
                 }
+                if let Some(t) = &m.transport {
+                    obj["packets_sent"] = serde_json::json!(t.packets_sent);
+                    obj["pacing_bps"] = serde_json::json!(m.pacing_rate_bps.round() as u64);
+                    obj["cwnd_bytes"] = serde_json::json!(m.inflight_cap_bytes.round() as u64);
+                }
+                if m.owd_ms > 0.0 {
+                    obj["owd_ms"] = serde_json::json!(m.owd_ms);
+                }
+                if let Some(offset) = m.clock_offset_us {
+                    obj["clock_offset_us"] = serde_json::json!(offset);
+                }
+                if let Some(drift) = m.clock_drift_ppm {
+                    obj["clock_drift_ppm"] = serde_json::json!(drift);
+                }
+                if let Some(report) = &m.receiver_report {
+                    obj["mean_loss_burst"] = serde_json::json!(report.mean_loss_burst);
+                }
                 obj
             }
         })
@@ -612,9 +630,102 @@ pub fn render_receiver_prometheus(stats: &ReassemblyStats) -> String {
     )
     .unwrap();
 
+    writeln!(
+        out,
+        "# HELP strata_receiver_redundant_copies_total Cross-link duplicate copies discarded before reassembly."
+    )
+    .unwrap();
+    writeln!(out, "# TYPE strata_receiver_redundant_copies_total counter").unwrap();
+    writeln!(
+        out,
+        "strata_receiver_redundant_copies_total {}",
+        stats.redundant_copies
+    )
+    .unwrap();
+
+    if let Some(av) = &stats.av_sync {
+        for (name, help, value) in [
+            (
+                "av_skew_ms",
+                "Smoothed video PTS minus audio PTS in the delivered TS.",
+                av.av_skew_ms,
+            ),
+            (
+                "av_drift_ms",
+                "Change in A/V skew since the stream settled.",
+                av.av_drift_ms,
+            ),
+            (
+                "pcr_jitter_ms",
+                "PCR inter-arrival jitter after reassembly.",
+                av.pcr_jitter_ms,
+            ),
+            (
+                "video_pts_lead_ms",
+                "Smoothed video PTS minus PCR at release.",
+                av.video_pts_lead_ms,
+            ),
+        ] {
+            writeln!(out, "# HELP strata_receiver_{name} {help}").unwrap();
+            writeln!(out, "# TYPE strata_receiver_{name} gauge").unwrap();
+            writeln!(out, "strata_receiver_{name} {value:.3}").unwrap();
+        }
+    }
+
+    render_loss_histogram(
+        &mut out,
+        "strata_receiver_link_loss_run_length",
+        "Consecutive channel-loss run lengths in packets, per link.",
+        stats,
+        |p| (&p.run_length_hist, p.run_packets),
+    );
+    render_loss_histogram(
+        &mut out,
+        "strata_receiver_link_loss_distance",
+        "Packets received between channel-loss runs, per link.",
+        stats,
+        |p| (&p.distance_hist, p.distance_packets),
+    );
+
     out
 }
 
+/// Render one per-link loss-pattern histogram. The tracker's log2 buckets
+/// map onto cumulative `le` buckets at `2^(i+1) − 1`.
+fn render_loss_histogram(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    stats: &ReassemblyStats,
+    hist: impl Fn(&LossPatternStats) -> (&[u64; LOSS_HIST_BUCKETS], u64),
+) {
+    writeln!(out, "# HELP {name} {help}").unwrap();
+    writeln!(out, "# TYPE {name} histogram").unwrap();
+    for link in &stats.per_link {
+        let id = link.link_id;
+        let (counts, sum) = hist(&link.loss_pattern);
+        let mut cumulative = 0u64;
+        for (i, &count) in counts.iter().enumerate() {
+            cumulative += count;
+            if i + 1 < LOSS_HIST_BUCKETS {
+                let le = (1u64 << (i + 1)) - 1;
+                writeln!(
+                    out,
+                    "{name}_bucket{{link_id=\"{id}\",le=\"{le}\"}} {cumulative}"
+                )
+                .unwrap();
+            }
+        }
+        writeln!(
+            out,
+            "{name}_bucket{{link_id=\"{id}\",le=\"+Inf\"}} {cumulative}"
+        )
+        .unwrap();
+        writeln!(out, "{name}_sum{{link_id=\"{id}\"}} {sum}").unwrap();
+        writeln!(out, "{name}_count{{link_id=\"{id}\"}} {cumulative}").unwrap();
+    }
+}
+
 /// A lightweight HTTP server that serves receiver `/metrics` for Prometheus.
 ///
 /// Similar to `MetricsServer` but reads `ReassemblyStats` instead of link metrics.
@@ -761,6 +872,8 @@ mod tests {
                 ack_bytes: 0,
                 estimated_capacity_bps: 0.0,
                 owd_ms: 0.0,
+                clock_offset_us: None,
+                clock_drift_ppm: None,
                 btlbw_bps: None,
                 rtprop_ms: None,
                 receiver_report: None,
@@ -799,6 +912,8 @@ mod tests {
                 ack_bytes: 0,
                 estimated_capacity_bps: 0.0,
                 owd_ms: 0.0,
+                clock_offset_us: None,
+                clock_drift_ppm: None,
                 btlbw_bps: None,
                 rtprop_ms: None,
                 receiver_report: None,
@@ -890,6 +1005,64 @@ mod tests {
         assert!(out.contains("strata_fec_repairs_total 320")); // 200+120
     }
 
+    #[test]
+    fn receiver_prometheus_renders_av_sync_once_seen() {
+        let stats = ReassemblyStats::default();
+        assert!(!render_receiver_prometheus(&stats).contains("av_skew"));
+
+        let stats = ReassemblyStats {
+            av_sync: Some(crate::media::av_sync::AvSyncStats {
+                av_skew_ms: 42.0,
+                pcr_jitter_ms: 1.5,
+                ..Default::default()
+            }),
+            ..ReassemblyStats::default()
+        };
+        let out = render_receiver_prometheus(&stats);
+        assert!(out.contains("# TYPE strata_receiver_av_skew_ms gauge"));
+        assert!(out.contains("strata_receiver_av_skew_ms 42.000"));
+        assert!(out.contains("strata_receiver_pcr_jitter_ms 1.500"));
+        assert!(out.contains("strata_receiver_av_drift_ms 0.000"));
+    }
+
+    #[test]
+    fn receiver_prometheus_renders_loss_histograms() {
+        use crate::receiver::aggregator::ReassemblyLinkStats;
+
+        let mut loss_pattern = LossPatternStats::new();
+        loss_pattern.run_length_hist[0] = 3; // three isolated losses
+        loss_pattern.run_length_hist[4] = 1; // one 20-packet burst
+        loss_pattern.loss_runs = 4;
+        loss_pattern.run_packets = 23;
+        let stats = ReassemblyStats {
+            per_link: vec![ReassemblyLinkStats {
+                link_id: 2,
+                loss_pattern,
+                ..ReassemblyLinkStats::default()
+            }],
+            ..ReassemblyStats::default()
+        };
+
+        let out = render_receiver_prometheus(&stats);
+        assert!(out.contains("# TYPE strata_receiver_link_loss_run_length histogram"));
+        assert!(
+            out.contains("strata_receiver_link_loss_run_length_bucket{link_id=\"2\",le=\"1\"} 3")
+        );
+        assert!(
+            out.contains("strata_receiver_link_loss_run_length_bucket{link_id=\"2\",le=\"15\"} 3")
+        );
+        assert!(
+            out.contains("strata_receiver_link_loss_run_length_bucket{link_id=\"2\",le=\"31\"} 4")
+        );
+        assert!(
+            out.contains(
+                "strata_receiver_link_loss_run_length_bucket{link_id=\"2\",le=\"+Inf\"} 4"
+            )
+        );
+        assert!(out.contains("strata_receiver_link_loss_run_length_sum{link_id=\"2\"} 23"));
+        assert!(out.contains("strata_receiver_link_loss_distance_count{link_id=\"2\"} 0"));
+    }
+
     #[test]
     fn phase_to_u8_all_variants() {
         assert_eq!(phase_to_u8(&LinkPhase::Init), 0);
@@ -122,7 +122,15 @@ pub struct LinkMetrics {
     /// Cumulative bytes acknowledged by the receiver.
     pub ack_bytes: u64,
     /// One-way delay estimate in milliseconds (0.0 if not available).
+    /// Receiver-measured once sender and receiver clocks are synchronized,
+    /// otherwise half the smoothed RTT.
     pub owd_ms: f64,
+    /// Receiver wire clock minus ours in µs, from PING/PONG exchanges.
+    /// `None` until the first exchange completes.
+    pub clock_offset_us: Option<i64>,
+    /// Receiver clock rate relative to ours in ppm (positive: receiver
+    /// runs fast). `None` until enough exchanges span a fit.
+    pub clock_drift_ppm: Option<f64>,
     /// Latest receiver report from the remote receiver (if any).
     pub receiver_report: Option<ReceiverReportMetrics>,
     /// True while a saturation probe is active on this link or within the
@@ -168,6 +176,13 @@ pub struct ReceiverReportMetrics {
     /// Relative one-way-delay gradient in microseconds (F3): the
     /// queue-building magnitude measured receiver-side, drift-immune.
     pub delay_gradient_us: u32,
+    /// Absolute one-way delay in microseconds against the synchronized
+    /// clock; 0 until the receiver has adopted a clock offset.
+    pub owd_us: u32,
+    /// Mean channel loss-run length in packets over the last report
+    /// interval: 1.0 is random loss, larger is bursty (handover) loss,
+    /// 0.0 means no loss.
+    pub mean_loss_burst: f32,
 }
 
 /// Transport-layer statistics from `strata-transport`.
@@ -227,6 +242,14 @@ pub trait LinkSender: Send + Sync {
     /// Flush any pending packets in the paced send queue.
     fn flush_paced(&self) {}
 
+    /// Take the keyframe request the receiver most recently sent on this
+    /// link, if one arrived since the last call. The scheduler turns each
+    /// distinct request into a [`crate::events::BondingEvent::KeyframeRequested`].
+    /// Default `None` for links without a receiver feedback path.
+    fn take_keyframe_request(&self) -> Option<strata_transport::wire::KeyframeRequestPacket> {
+        None
+    }
+
     /// Forward RF metrics from the modem supervisor to this link's congestion
     /// controller. Called whenever the modem poller produces updated
     /// CQI/RSRP/SINR readings.
@@ -298,6 +321,25 @@ pub trait LinkSender: Send + Sync {
     /// in metrics — the control path stays path-relative. Default no-op.
     fn set_profile(&self, _regime: Option<&str>) {}
 
+    /// Set the per-stream ingest key this link presents in its session
+    /// HELLO (`None` = send none). Default no-op for mock links.
+    fn set_ingest_key(&self, _key: Option<&[u8]>) {}
+
+    /// Packets sent but not yet acknowledged (still eligible for ARQ).
+    /// Default `0` for links without retransmission.
+    fn in_flight(&self) -> usize {
+        0
+    }
+
+    /// Emit repair packets for the partially filled FEC generation so its
+    /// tail is protected without waiting for more source packets. Default
+    /// no-op.
+    fn flush_fec(&self) {}
+
+    /// Tell the receiver this link is done: a session TEARDOWN, sent after
+    /// a drain. Default no-op.
+    fn send_teardown(&self) {}
+
     /// Opportunistic modem flow-control (F5). A modem backend that exposes
     /// QMAP DFC (Qualcomm/rmnet) or vendor AT transmit-backpressure stats
     /// calls this with `slow_down = true` when the modem's own TX ring is
@@ -18,6 +18,7 @@ use std::time::Instant;
 use crate::net::interface::{LinkMetrics, LinkPhase, LinkSender};
 use crate::scheduler::oracle::CapacityOracle;
 use strata_transport::congestion::{BbrPhase, BiscayController, BiscayState};
+use strata_transport::session::{PathResponseOutcome, PathValidator};
 
 /// Per-link token bucket for send-path pacing.
 ///
@@ -60,7 +61,7 @@ use strata_transport::pool::Priority;
 use strata_transport::pool::TimestampClock;
 use strata_transport::sender::{Sender, SenderConfig};
 use strata_transport::session::RttTracker;
-use strata_transport::wire::{Packet, PacketHeader, ReceiverReportPacket};
+use strata_transport::wire::{KeyframeRequestPacket, Packet, PacketHeader, ReceiverReportPacket};
 
 /// Explicit state for whether receiver feedback on this link is
 /// probe-contaminated and should be ignored by the `BitrateAdapter`.
@@ -164,6 +165,8 @@ pub struct TransportLink {
     ack_rate_was_zeroed: AtomicBool,
     /// Latest receiver report from the remote receiver (if any).
     receiver_report: Mutex<Option<ReceiverReportPacket>>,
+    /// Keyframe request from the receiver not yet taken by the scheduler.
+    keyframe_request: Mutex<Option<KeyframeRequestPacket>>,
     /// Most recent receiver-side cumulative `bytes_delivered` for this link.
     /// Updated on every ReceiverReport. Used by the sender's saturation-probe
     /// path to compute receiver-observed throughput, which is independent of
@@ -213,6 +216,16 @@ pub struct TransportLink {
     /// `(last_resize_at, last_target_bytes)` throttle for the dynamic
     /// `SO_SNDBUF` sizing (F2/ex-F4). `(_, 0)` = never resized yet.
     sndbuf_state: Mutex<(std::time::Instant, usize)>,
+    /// Per-stream ingest key presented in session HELLOs. `None` = the
+    /// receiver trusts any source and no HELLO is sent.
+    ingest_key: Mutex<Option<Vec<u8>>>,
+    /// When the last HELLO went out (`None` = never), see [`HELLO_INTERVAL`].
+    last_hello: Mutex<Option<Instant>>,
+    /// Keyed proof-of-liveness for this path. Until the receiver has
+    /// answered a challenge, feedback arriving here is not trusted for
+    /// RTT/capacity estimates and the link carries only a probe trickle.
+    /// `None` = no ingest key, the path is trusted as before.
+    path: Mutex<Option<PathValidator>>,
 }
 
 /// A link is only treated as delivery-starved once it has sent at least
@@ -380,6 +393,11 @@ const BTLBW_ABSOLUTE_CEILING_BPS: f64 = 50_000_000.0;
 /// (per audit) — don't merge them into one constant.
 const ACK_RATE_FALLBACK_HEADROOM_MULT: f64 = 1.2;
 
+/// How often a keyed link re-sends its session HELLO. The receiver admits
+/// source addresses, not links, so a carrier NAT rebinding mid-stream
+/// moves the link to an unadmitted address; the next HELLO re-admits it.
+const HELLO_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// Microsecond monotonic clock shared by the ACK-rate sampler and the
 /// paced-queue empty stamp — a single epoch so the two timestamps are
 /// directly comparable for app-limited detection.
@@ -594,6 +612,7 @@ impl TransportLink {
             prev_pkts_acked_us: AtomicU64::new(0),
             ack_rate_was_zeroed: AtomicBool::new(false),
             receiver_report: Mutex::new(None),
+            keyframe_request: Mutex::new(None),
             last_recv_bytes_delivered: AtomicU64::new(0),
             last_recv_report_at: Mutex::new(Instant::now()),
             iface,
@@ -615,6 +634,9 @@ impl TransportLink {
             last_ack_or_report: Mutex::new(Instant::now()),
             was_delivery_starved: std::sync::atomic::AtomicBool::new(false),
             sndbuf_state: Mutex::new((std::time::Instant::now(), 0)),
+            ingest_key: Mutex::new(None),
+            last_hello: Mutex::new(None),
+            path: Mutex::new(None),
         }
     }
 
@@ -897,6 +919,70 @@ impl TransportLink {
         }
     }
 
+    /// Send a session HELLO carrying the ingest key if one is configured
+    /// and [`HELLO_INTERVAL`] has passed since the last one.
+    fn maybe_send_hello(&self) {
+        use strata_transport::wire::{SessionAction, SessionPacket};
+
+        let Some(key) = self.ingest_key.lock().unwrap().clone() else {
+            return;
+        };
+        {
+            let mut last = self.last_hello.lock().unwrap();
+            if last.is_some_and(|t| t.elapsed() < HELLO_INTERVAL) {
+                return;
+            }
+            *last = Some(Instant::now());
+        }
+        let hello = SessionPacket {
+            action: SessionAction::Hello,
+            session_id: 0,
+            link_id: u8::try_from(self.id).ok(),
+            ingest_key: Some(key),
+        };
+        let mut body = BytesMut::with_capacity(16 + strata_transport::wire::MAX_INGEST_KEY_LEN);
+        hello.encode(&mut body);
+        let body_bytes = body.freeze();
+        let ts = self.clock.lock().unwrap().now_us();
+        let pkt = Packet {
+            header: PacketHeader::control(0, ts, body_bytes.len() as u16),
+            payload: body_bytes,
+        };
+        let _ = self.socket.send(&pkt.encode());
+    }
+
+    /// Send a path challenge if the validator has one due.
+    fn maybe_send_path_challenge(&self) {
+        let challenge = {
+            let mut path = self.path.lock().unwrap();
+            match path.as_mut() {
+                Some(v) => v.poll(quanta::Instant::now()),
+                None => return,
+            }
+        };
+        let Some(challenge) = challenge else {
+            return;
+        };
+        let mut body = BytesMut::with_capacity(16);
+        challenge.encode(&mut body);
+        let body_bytes = body.freeze();
+        let ts = self.clock.lock().unwrap().now_us();
+        let pkt = Packet {
+            header: PacketHeader::control(0, ts, body_bytes.len() as u16),
+            payload: body_bytes,
+        };
+        let _ = self.socket.send(&pkt.encode());
+    }
+
+    /// Whether this path's feedback may drive scheduling estimates.
+    fn path_admitted(&self) -> bool {
+        self.path
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_none_or(PathValidator::is_admitted)
+    }
+
     /// Process an incoming ACK/NACK packet from the receiver.
     pub fn process_feedback(&self, data: &[u8]) -> Result<()> {
         use strata_transport::wire::{ControlBody, Packet, PacketType};
@@ -911,8 +997,39 @@ impl TransportLink {
 
         let mut payload_cursor = &packet.payload[..];
         if let Some(ctrl) = ControlBody::decode(&mut payload_cursor) {
+            let admitted = self.path_admitted();
             let mut sender = self.sender.lock().unwrap();
             match &ctrl {
+                ControlBody::PathResponse(resp) => {
+                    let mut path = self.path.lock().unwrap();
+                    let Some(v) = path.as_mut() else {
+                        return Ok(());
+                    };
+                    let was_admitted = v.is_admitted();
+                    match v.handle_response(resp, quanta::Instant::now()) {
+                        PathResponseOutcome::Validated if !was_admitted => {
+                            tracing::info!(link_id = self.id, "path validated: admitting link");
+                        }
+                        PathResponseOutcome::Forged => {
+                            tracing::warn!(
+                                link_id = self.id,
+                                "path response with a bad tag: far end does not hold the ingest key"
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                // An unproven path still releases pool slots and serves
+                // repairs, but its timings and rates stay out of the
+                // estimators: on a misrouted or spoofed path they describe
+                // somebody else's network.
+                ControlBody::Ack(ack) if !admitted => {
+                    sender.process_ack(ack);
+                }
+                ControlBody::Pong(_)
+                | ControlBody::ReceiverReport(_)
+                | ControlBody::PpdReport(_)
+                    if !admitted => {}
                 ControlBody::Ack(ack) => {
                     let _newly_acked = sender.process_ack(ack);
 
@@ -1048,8 +1165,9 @@ impl TransportLink {
                     }
                 }
                 ControlBody::Pong(pong) => {
+                    let now_us = self.clock.lock().unwrap().now_us();
                     let mut rtt = self.rtt.lock().unwrap();
-                    rtt.handle_pong(pong);
+                    rtt.handle_pong(pong, now_us);
                     // Feed RTT sample to BiscayController
                     let rtt_us = rtt.srtt_us();
                     if rtt_us > 0.0 {
@@ -1102,6 +1220,21 @@ impl TransportLink {
                         "PPD report received"
                     );
                 }
+                ControlBody::KeyframeRequest(request) => {
+                    tracing::debug!(
+                        target: "strata::transport",
+                        link_id = self.id,
+                        request_id = request.request_id,
+                        lost_packets = request.lost_packets,
+                        "Keyframe request received"
+                    );
+                    *self.keyframe_request.lock().unwrap() = Some(request.clone());
+                }
+                ControlBody::Sack(sack) => {
+                    // Delivery-rate sampling stays on the ACK path; a SACK
+                    // only releases pool slots and retransmit state.
+                    sender.process_sack(sack);
+                }
                 _ => {}
             }
         }
@@ -1163,9 +1296,10 @@ impl LinkSender for TransportLink {
             (s, q)
         };
 
-        let rtt_ms = {
+        let (rtt_ms, clock_offset_us, clock_drift_ppm) = {
             let rtt = self.rtt.lock().unwrap();
-            rtt.srtt_us() / 1000.0
+            let clock = rtt.clock();
+            (rtt.srtt_us() / 1000.0, clock.offset_us(), clock.drift_ppm())
         };
 
         // --- Socket-level rate (includes FEC/retransmit overhead) ---
@@ -1494,6 +1628,14 @@ impl LinkSender for TransportLink {
         } else {
             capacity_bps
         };
+        // A keyed path that has not (or no longer) answered its challenge
+        // is not yet part of the bond: same near-zero weight as a hard
+        // blackhole, still alive so the challenges keep going out.
+        let capacity_bps = if self.path_admitted() {
+            capacity_bps
+        } else {
+            capacity_bps.min(STARVED_HARD_BLACKHOLE_FLOOR_BPS)
+        };
 
         let btlbw_bps = if btl_bw_bps > 0.0 {
             Some(btl_bw_bps)
@@ -1566,7 +1708,14 @@ impl LinkSender for TransportLink {
             ack_delivery_bps: per_link_ack_rate,
             ack_bytes: per_link_ack_bytes,
             estimated_capacity_bps: capacity_bps,
-            owd_ms: rtt_ms / 2.0,
+            // Receiver-measured against the synchronized clock once it
+            // reports one; half the RTT until then.
+            owd_ms: self
+                .latest_receiver_report()
+                .filter(|r| r.owd_us > 0)
+                .map_or(rtt_ms / 2.0, |r| r.owd_us as f64 / 1000.0),
+            clock_offset_us,
+            clock_drift_ppm,
             receiver_report: self.latest_receiver_report().map(|r| {
                 crate::net::interface::ReceiverReportMetrics {
                     goodput_bps: r.goodput_bps,
@@ -1575,6 +1724,8 @@ impl LinkSender for TransportLink {
                     loss_after_fec: r.loss_after_fec_f32(),
                     late_rate: r.late_rate_f32(),
                     delay_gradient_us: r.delay_gradient_us,
+                    owd_us: r.owd_us,
+                    mean_loss_burst: r.mean_loss_burst_f32(),
                 }
             }),
             probe_active: self
@@ -1664,6 +1815,18 @@ impl LinkSender for TransportLink {
         let r = ((FEC_BASE_K as f64) * ratio).round() as usize;
         let mut r = r.clamp(1, FEC_BASE_K);
 
+        // Bursty loss: a handover wipes out a run of consecutive symbols,
+        // and a generation with fewer repairs than the run recovers none
+        // of it, whatever the mean rate says. Cover the mean run length
+        // the receiver last reported.
+        if let Some(burst) = self
+            .latest_receiver_report()
+            .map(|rep| rep.mean_loss_burst_f32())
+            .filter(|&b| b > 1.0)
+        {
+            r = r.max(burst.ceil() as usize).min(FEC_BASE_K);
+        }
+
         // Diagnostic isolation lever (default OFF): `STRATA_FEC=off` forces
         // zero repair symbols so a field run can prove whether FEC repair
         // is the source of "clean stats, corrupt video". Not a normal mode
@@ -1686,6 +1849,54 @@ impl LinkSender for TransportLink {
         self.congestion.lock().unwrap().set_profile_override(parsed);
     }
 
+    fn set_ingest_key(&self, key: Option<&[u8]>) {
+        {
+            let mut current = self.ingest_key.lock().unwrap();
+            if current.as_deref() == key {
+                return;
+            }
+            *current = key.map(<[u8]>::to_vec);
+        }
+        // A new key invalidates any earlier proof; re-challenge from scratch.
+        *self.path.lock().unwrap() = key.map(PathValidator::new);
+        // Announce a new key right away — until the receiver has seen it,
+        // everything this link sends is dropped.
+        *self.last_hello.lock().unwrap() = None;
+        self.maybe_send_hello();
+    }
+
+    fn in_flight(&self) -> usize {
+        self.sender.lock().unwrap().in_flight()
+    }
+
+    fn take_keyframe_request(&self) -> Option<KeyframeRequestPacket> {
+        self.keyframe_request.lock().unwrap().take()
+    }
+
+    fn flush_fec(&self) {
+        let _ = TransportLink::flush_fec(self);
+    }
+
+    fn send_teardown(&self) {
+        use strata_transport::wire::{SessionAction, SessionPacket};
+
+        let teardown = SessionPacket {
+            action: SessionAction::Teardown,
+            session_id: 0,
+            link_id: u8::try_from(self.id).ok(),
+            ingest_key: None,
+        };
+        let mut body = BytesMut::with_capacity(16);
+        teardown.encode(&mut body);
+        let body_bytes = body.freeze();
+        let ts = self.clock.lock().unwrap().now_us();
+        let pkt = Packet {
+            header: PacketHeader::control(0, ts, body_bytes.len() as u16),
+            payload: body_bytes,
+        };
+        let _ = self.socket.send(&pkt.encode());
+    }
+
     fn on_modem_flow_control(&self, slow_down: bool) {
         self.congestion
             .lock()
@@ -1723,6 +1934,9 @@ impl LinkSender for TransportLink {
             );
         }
 
+        self.maybe_send_hello();
+        self.maybe_send_path_challenge();
+
         // Send periodic Pings for RTT measurement.
         let mut rtt = self.rtt.lock().unwrap();
         if rtt.needs_ping() {
@@ -1983,6 +2197,88 @@ mod tests {
         assert_eq!(q.len(), 10, "small queue must be left intact");
     }
 
+    #[test]
+    fn ingest_key_is_announced_in_session_hello() {
+        use strata_transport::wire::{ControlBody, SessionAction};
+
+        let link = make_loopback_link(4);
+        link.set_ingest_key(Some(b"isk_test"));
+
+        let mut buf = [0u8; 512];
+        let mut received = None;
+        for _ in 0..50 {
+            if let Ok(n) = link.socket.recv(&mut buf) {
+                received = Some(n);
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let n = received.expect("hello datagram");
+        let mut cursor = &buf[..n];
+        let pkt = Packet::decode(&mut cursor).unwrap();
+        let mut body = &pkt.payload[..];
+        match ControlBody::decode(&mut body) {
+            Some(ControlBody::Session(hello)) => {
+                assert_eq!(hello.action, SessionAction::Hello);
+                assert_eq!(hello.ingest_key.as_deref(), Some(&b"isk_test"[..]));
+            }
+            other => panic!("expected session hello, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn keyed_link_is_admitted_only_after_its_path_answers() {
+        use strata_transport::session::make_path_response;
+        use strata_transport::wire::PathResponsePacket;
+
+        let link = make_loopback_link(5);
+        assert!(link.path_admitted(), "unkeyed paths are trusted as before");
+        link.set_ingest_key(Some(b"isk_test"));
+        assert!(!link.path_admitted());
+
+        // Setting the key already sent a challenge on the loopback; take
+        // over with a fresh validator so the nonce is known here.
+        let mut validator = PathValidator::new(b"isk_test");
+        let challenge = validator
+            .poll(quanta::Instant::now())
+            .expect("unvalidated path is challenged");
+        *link.path.lock().unwrap() = Some(validator);
+        let datagram = |resp: PathResponsePacket| {
+            let mut body = BytesMut::new();
+            resp.encode(&mut body);
+            let body = body.freeze();
+            Packet {
+                header: PacketHeader::control(0, 0, body.len() as u16),
+                payload: body,
+            }
+            .encode()
+        };
+
+        link.process_feedback(&datagram(make_path_response(b"isk_other", &challenge)))
+            .unwrap();
+        assert!(!link.path_admitted(), "a wrong-key echo must not admit");
+        assert!(link.get_metrics().capacity_bps <= STARVED_HARD_BLACKHOLE_FLOOR_BPS);
+
+        link.process_feedback(&datagram(make_path_response(b"isk_test", &challenge)))
+            .unwrap();
+        assert!(link.path_admitted());
+    }
+
+    #[test]
+    fn keyed_link_that_never_answers_stays_clamped() {
+        let link = make_loopback_link(6);
+        link.set_ingest_key(Some(b"isk_test"));
+
+        // Nothing on the loopback answers challenges.
+        let mut validator = PathValidator::new(b"isk_test");
+        validator.challenge_timeout = std::time::Duration::ZERO;
+        *link.path.lock().unwrap() = Some(validator);
+        for _ in 0..10 {
+            link.maybe_send_path_challenge();
+        }
+        assert!(!link.path_admitted(), "silence must not admit a path");
+    }
+
     #[test]
     fn set_profile_overrides_inferred_regime() {
         let link = make_loopback_link(13);
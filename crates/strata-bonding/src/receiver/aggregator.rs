@@ -1,7 +1,10 @@
 use bytes::Bytes;
 use quanta::Instant;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::time::Duration;
+use strata_transport::stats::LossPatternStats;
+
+use crate::media::av_sync::AvSyncStats;
 
 /// An incoming packet with its bonding sequence ID and arrival timestamp.
 pub struct Packet {
@@ -16,6 +19,56 @@ pub struct Packet {
     /// sole input) is blind to this — it reads ~19 ms while the real
     /// cross-link spread during a one-modem fade is 250–400 ms.
     pub send_ts_us: u32,
+    /// Link that delivered it.
+    pub link_id: usize,
+}
+
+/// Horizon of the delay-spread window, and how long a link counts as
+/// carrying traffic after its last packet.
+const DELAY_SPREAD_WINDOW: Duration = Duration::from_secs(4);
+
+/// Delay class of a bonded link, from its round-trip time.
+///
+/// Sizes each link's reorder tolerance: a link is waited for by its
+/// one-way delay above the fastest active link, scaled by how much that
+/// delay wanders for its class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RttClass {
+    /// Fibre, cable, LEO satellite: RTT up to 40 ms.
+    Wired,
+    /// LTE/5G: RTT up to 400 ms, with HARQ and queueing tails.
+    Cellular,
+    /// GEO satellite and other long-haul backups.
+    Satellite,
+}
+
+impl RttClass {
+    pub fn from_rtt(rtt: Duration) -> Self {
+        match rtt.as_millis() {
+            0..=40 => Self::Wired,
+            41..=400 => Self::Cellular,
+            _ => Self::Satellite,
+        }
+    }
+
+    /// Reorder tolerance per unit of excess one-way delay. Wired and
+    /// satellite delay is long-lived (1.15× covers estimate lag, as for
+    /// the delay spread); cellular delay swings with radio retries, and
+    /// the smoothed estimate trails the tail.
+    fn excess_delay_factor(self) -> f64 {
+        match self {
+            Self::Wired | Self::Satellite => 1.15,
+            Self::Cellular => 1.5,
+        }
+    }
+}
+
+/// What the buffer knows about one link's delay.
+#[derive(Debug, Clone, Copy, Default)]
+struct LinkDelay {
+    /// Smoothed one-way delay, once the transport has measured it.
+    owd: Option<Duration>,
+    last_arrival: Option<Instant>,
 }
 
 /// Jitter buffer that reorders and releases packets in sequence order.
@@ -57,6 +110,13 @@ pub struct ReassemblyBuffer {
     rel_max_deque: VecDeque<(Instant, i64)>,
     delay_spread_us: i64,
 
+    // Adaptive latency — per-link reorder tolerance by RTT class. Only
+    // links that carried a packet within `DELAY_SPREAD_WINDOW` count, so
+    // an idle high-RTT backup doesn't hold the window at its delay, while
+    // one that starts carrying traffic widens it from its first packet
+    // rather than after the spread has seen it arrive late.
+    links: BTreeMap<usize, LinkDelay>,
+
     // Adaptive latency — bidirectional smoothing
     target_latency: Duration,
     ramp_up_alpha: f64,
@@ -171,6 +231,18 @@ pub struct ReassemblyLinkStats {
     pub packets_delivered: u64,
     pub bytes_received: u64,
     pub loss_rate: f64,
+    /// Smoothed absolute one-way delay in ms against the synchronized
+    /// sender clock; 0.0 until the sender has shared a clock offset.
+    pub owd_ms: f64,
+    /// Sender wire clock minus ours in µs, as adopted from its PINGs.
+    pub clock_offset_us: Option<i64>,
+    /// Sender clock rate relative to ours in ppm.
+    pub clock_drift_ppm: Option<f64>,
+    /// Channel loss-run and loss-distance histograms for this link.
+    pub loss_pattern: LossPatternStats,
+    /// Copies this link delivered after another link already had
+    /// (broadcast/redundancy), discarded ahead of reassembly.
+    pub redundant_copies: u64,
 }
 
 /// Snapshot of reassembly buffer statistics for telemetry.
@@ -193,6 +265,12 @@ pub struct ReassemblyStats {
     pub packets_delivered: u64,
     /// Per-link receive/delivery stats from transport readers.
     pub per_link: Vec<ReassemblyLinkStats>,
+    /// Cross-link redundant copies discarded before reassembly. Set by
+    /// the transport receiver; the buffer itself only sees first copies.
+    pub redundant_copies: u64,
+    /// A/V sync of the released stream, once it has shown TS clocks. Set
+    /// by the transport receiver, which sees what reassembly releases.
+    pub av_sync: Option<AvSyncStats>,
 }
 
 fn percentile(samples: &VecDeque<f64>, pct: f64) -> f64 {
@@ -256,6 +334,7 @@ impl ReassemblyBuffer {
             rel_min_deque: VecDeque::new(),
             rel_max_deque: VecDeque::new(),
             delay_spread_us: 0,
+            links: BTreeMap::new(),
             target_latency: config.start_latency,
             ramp_up_alpha: config.ramp_up_alpha,
             ramp_down_alpha: config.ramp_down_alpha,
@@ -286,6 +365,8 @@ impl ReassemblyBuffer {
             loss_rate: self.loss_rate_smoothed,
             packets_delivered: self.packets_delivered,
             per_link: Vec::new(),
+            redundant_copies: 0,
+            av_sync: None,
         }
     }
 
@@ -299,11 +380,31 @@ impl ReassemblyBuffer {
         self.push_with_ts(seq_id, payload, now, synthetic_ts);
     }
 
+    /// Single-link entry point: [`Self::push_packet`] from link 0.
+    pub fn push_with_ts(&mut self, seq_id: u64, payload: Bytes, now: Instant, send_ts_us: u32) {
+        self.push_packet(Packet {
+            seq_id,
+            payload,
+            arrival_time: now,
+            send_ts_us,
+            link_id: 0,
+        });
+    }
+
+    /// Record a link's smoothed one-way delay, as measured by the
+    /// transport once the sender clock is synced. Sizes that link's
+    /// reorder tolerance by its [`RttClass`].
+    pub fn set_link_owd(&mut self, link_id: usize, owd: Duration) {
+        self.links.entry(link_id).or_default().owd = Some(owd);
+    }
+
     /// Production entry point. `send_ts_us` is the sender's wire send-time;
     /// used to size the playout window from the bonded inter-link delay
     /// spread (the signal that actually governs lateness on heterogeneous
     /// bonded links, unlike naive inter-arrival jitter).
-    pub fn push_with_ts(&mut self, seq_id: u64, payload: Bytes, now: Instant, send_ts_us: u32) {
+    pub fn push_packet(&mut self, packet: Packet) {
+        let (seq_id, now, send_ts_us) = (packet.seq_id, packet.arrival_time, packet.send_ts_us);
+        self.links.entry(packet.link_id).or_default().last_arrival = Some(now);
         // Bonded inter-link delay spread. `rel` = arrival (local µs since
         // epoch) − sender send-time. The absolute value is meaningless
         // (two unsynced clocks) but its spread over a short sliding window
@@ -314,7 +415,6 @@ impl ReassemblyBuffer {
         // and max_latency clamps the final window regardless.
         let arrival_us = now.saturating_duration_since(self.epoch).as_micros() as i64;
         let rel = arrival_us - send_ts_us as i64;
-        const DELAY_SPREAD_WINDOW: Duration = Duration::from_secs(4);
         let cutoff = now.checked_sub(DELAY_SPREAD_WINDOW);
         // Sliding-window MIN via monotonic-increasing deque.
         while self.rel_min_deque.back().is_some_and(|&(_, v)| v >= rel) {
@@ -377,6 +477,10 @@ impl ReassemblyBuffer {
             // sampling lag in the windowed max.
             let spread_component = (self.delay_spread_us as f64 / 1000.0) * 1.15;
 
+            // Per-link RTT-class floor: known ahead of the spread for a
+            // link that just started carrying traffic.
+            let link_component = self.link_reorder_ms(now);
+
             // Loss-aware component: more buffer when losing packets
             let loss_component = self.loss_rate_smoothed * self.loss_penalty_ms;
 
@@ -403,7 +507,7 @@ impl ReassemblyBuffer {
             // the bonded delay spread — never let the window sit below the
             // measured cross-link skew (the floor), while still honouring
             // single-link jitter when it is the larger effect.
-            let dynamic_component = jitter_component.max(spread_component);
+            let dynamic_component = jitter_component.max(spread_component).max(link_component);
 
             // Compute target latency: formula gives the floor, late-pressure
             // (closed-loop) trims around it.
@@ -592,12 +696,31 @@ impl ReassemblyBuffer {
             self.buffered += 1;
         }
 
-        self.buffer[idx] = Some(Packet {
-            seq_id,
-            payload,
-            arrival_time: now,
-            send_ts_us,
-        });
+        self.buffer[idx] = Some(packet);
+    }
+
+    /// Reorder tolerance (ms) of the links active at `now`: each one's
+    /// one-way delay above the fastest active link, scaled by its
+    /// [`RttClass`]. Links without a delay estimate are left to the spread.
+    fn link_reorder_ms(&self, now: Instant) -> f64 {
+        let active = || {
+            self.links
+                .values()
+                .filter(|l| {
+                    l.last_arrival
+                        .is_some_and(|t| now.saturating_duration_since(t) <= DELAY_SPREAD_WINDOW)
+                })
+                .filter_map(|l| l.owd)
+        };
+        let Some(fastest) = active().min() else {
+            return 0.0;
+        };
+        active()
+            .map(|owd| {
+                let excess_ms = owd.saturating_sub(fastest).as_secs_f64() * 1000.0;
+                excess_ms * RttClass::from_rtt(owd * 2).excess_delay_factor()
+            })
+            .fold(0.0, f64::max)
     }
 
     /// Release ready packets. Returns `(payload, discont)` pairs where
@@ -1830,6 +1953,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn idle_high_rtt_link_does_not_hold_the_window_open() {
+        // A GEO satellite backup (~600 ms RTT) bonded with a cellular
+        // link: while it sits idle the window follows the cellular link
+        // alone; the moment it carries a packet its excess delay is
+        // covered, and once it falls idle again the floor lifts.
+        assert_eq!(
+            RttClass::from_rtt(Duration::from_millis(20)),
+            RttClass::Wired
+        );
+        assert_eq!(
+            RttClass::from_rtt(Duration::from_millis(80)),
+            RttClass::Cellular
+        );
+        assert_eq!(
+            RttClass::from_rtt(Duration::from_millis(600)),
+            RttClass::Satellite
+        );
+
+        let mut buf = ReassemblyBuffer::new_for_test(0, Duration::from_millis(50));
+        buf.set_link_owd(0, Duration::from_millis(40));
+        buf.set_link_owd(1, Duration::from_millis(300));
+        let start = Instant::now();
+        let mut seq = 0u64;
+        let mut push = |buf: &mut ReassemblyBuffer, link_id: usize, at_ms: u64| {
+            let t = start + Duration::from_millis(at_ms);
+            buf.push_packet(Packet {
+                seq_id: seq,
+                payload: Bytes::from_static(b"x"),
+                arrival_time: t,
+                // Constant send-vs-arrival lag: no delay spread, so only
+                // the per-link floor can widen the window.
+                send_ts_us: (at_ms * 1000) as u32,
+                link_id,
+            });
+            seq += 1;
+        };
+
+        for i in 0..20 {
+            push(&mut buf, 0, i * 10);
+        }
+        let idle_ms = buf.target_latency.as_millis();
+        assert!(
+            idle_ms < 150,
+            "idle backup widened the window to {idle_ms} ms"
+        );
+
+        push(&mut buf, 1, 200);
+        let active_ms = buf.target_latency.as_millis();
+        // (300 − 40) ms excess × 1.15 on top of the 50 ms start.
+        assert!(
+            active_ms >= 340,
+            "active backup not covered: {active_ms} ms"
+        );
+
+        for i in 21..440 {
+            push(&mut buf, 0, i * 10);
+        }
+        let idle_again_ms = buf.target_latency.as_millis();
+        assert!(
+            idle_again_ms < 150,
+            "floor outlived the backup: {idle_again_ms} ms"
+        );
+    }
+
     #[test]
     fn tick_sets_discont_after_gap_skip() {
         let mut buf = ReassemblyBuffer::new_for_test(0, Duration::from_millis(50));
@@ -35,6 +35,12 @@ impl ReceiverBackend {
         }
     }
 
+    /// Require a per-stream ingest key on links added after this call
+    /// (see [`TransportBondingReceiver::set_ingest_key`]).
+    pub fn set_ingest_key(&self, key: Option<&[u8]>) -> Result<()> {
+        self.inner.set_ingest_key(key)
+    }
+
     /// Add a link by address string.
     ///
     /// Accepts plain socket addresses (e.g. `0.0.0.0:5000`), `strata://`
@@ -62,6 +68,12 @@ impl ReceiverBackend {
         self.inner.stats_handle()
     }
 
+    /// Whether the sender has drained and torn down every link (see
+    /// [`TransportBondingReceiver::is_end_of_stream`]).
+    pub fn is_end_of_stream(&self) -> bool {
+        self.inner.is_end_of_stream()
+    }
+
     /// Shut down the receiver.
     pub fn shutdown(&mut self) {
         self.inner.shutdown();
@@ -5,6 +5,7 @@
 //! reordering), strips the bonding header, then feeds payloads into a
 //! shared [`ReassemblyBuffer`] for multi-link jitter buffering.
 
+use crate::media::av_sync::AvSyncMonitor;
 use crate::protocol::header::BondingHeader;
 use crate::receiver::aggregator::{
     Packet, ReassemblyBuffer, ReassemblyConfig, ReassemblyLinkStats, ReassemblyStats,
@@ -21,10 +22,14 @@ use std::sync::{
 };
 use std::thread;
 use std::time::Duration;
+use strata_transport::dedup::{DedupCache, DedupVerdict};
 use strata_transport::pool::TimestampClock;
 use strata_transport::receiver::{Receiver as TransportReceiver, ReceiverConfig, ReceiverEvent};
 use strata_transport::session::RttTracker;
-use strata_transport::wire::{ControlBody, Packet as WirePacket, PacketHeader};
+use strata_transport::stats::LossPatternStats;
+use strata_transport::wire::{
+    ControlBody, KeyframeRequestPacket, Packet as WirePacket, PacketHeader,
+};
 use tracing::{debug, info, warn};
 
 /// Bind a UDP socket with `SO_REUSEADDR`.
@@ -73,6 +78,11 @@ struct LinkRuntimeStats {
     packets_delivered: u64,
     bytes_received: u64,
     loss_rate: f64,
+    owd_ms: f64,
+    clock_offset_us: Option<i64>,
+    clock_drift_ppm: Option<f64>,
+    loss_pattern: LossPatternStats,
+    redundant_copies: u64,
 }
 
 pub struct TransportBondingReceiver {
@@ -85,6 +95,18 @@ pub struct TransportBondingReceiver {
     link_stats: Arc<Mutex<BTreeMap<usize, LinkRuntimeStats>>>,
     next_link_id: AtomicUsize,
     thread_handles: Mutex<Vec<thread::JoinHandle<()>>>,
+    /// Per-stream ingest key; links added while set only accept sources
+    /// that presented it in a session HELLO.
+    ingest_key: Mutex<Option<Arc<[u8]>>>,
+    /// Per link that has carried data: whether its sender has since sent a
+    /// session TEARDOWN.
+    torn_down: Arc<Mutex<BTreeMap<usize, bool>>>,
+    end_of_stream: Arc<AtomicBool>,
+    /// Cross-link duplicate filter shared by every link reader.
+    dedup: Arc<Mutex<DedupCache>>,
+    /// Keyframe requests raised by the jitter thread for the link readers
+    /// to send.
+    keyframe_requests: Arc<Mutex<KeyframeRequests>>,
 }
 
 impl TransportBondingReceiver {
@@ -108,7 +130,15 @@ impl TransportBondingReceiver {
         let running = Arc::new(AtomicBool::new(true));
         let stats = Arc::new(Mutex::new(ReassemblyStats::default()));
         let link_stats = Arc::new(Mutex::new(BTreeMap::<usize, LinkRuntimeStats>::new()));
-
+        let torn_down = Arc::new(Mutex::new(BTreeMap::<usize, bool>::new()));
+        let end_of_stream = Arc::new(AtomicBool::new(false));
+        let dedup = Arc::new(Mutex::new(DedupCache::new(config.buffer_capacity)));
+        let keyframe_requests = Arc::new(Mutex::new(KeyframeRequests::default()));
+
+        let dedup_clone = dedup.clone();
+        let keyframe_requests_clone = keyframe_requests.clone();
+        let torn_down_clone = torn_down.clone();
+        let end_of_stream_clone = end_of_stream.clone();
         let stats_clone = stats.clone();
         let link_stats_clone = link_stats.clone();
         let running_clone = running.clone();
@@ -127,6 +157,11 @@ impl TransportBondingReceiver {
                 let mut carry_discont = false;
                 let mut last_drop_log = Instant::now();
                 let drop_log_interval = Duration::from_secs(1);
+                // Reassembly loss counter when the last keyframe request
+                // was raised.
+                let mut lost_at_request: u64 = 0;
+                // Lipsync clocks of the stream as reassembly releases it.
+                let mut av_sync = AvSyncMonitor::new();
 
                 while running_clone.load(Ordering::Relaxed) {
                     // Drain all available input packets (non-blocking after
@@ -134,20 +169,10 @@ impl TransportBondingReceiver {
                     // waiting on a full input channel.
                     match input_rx.recv_timeout(tick_interval) {
                         Ok(packet) => {
-                            buffer.push_with_ts(
-                                packet.seq_id,
-                                packet.payload,
-                                packet.arrival_time,
-                                packet.send_ts_us,
-                            );
+                            buffer.push_packet(packet);
                             // Drain any additional queued packets without blocking.
                             while let Ok(p) = input_rx.try_recv() {
-                                buffer.push_with_ts(
-                                    p.seq_id,
-                                    p.payload,
-                                    p.arrival_time,
-                                    p.send_ts_us,
-                                );
+                                buffer.push_packet(p);
                             }
                         }
                         Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
@@ -160,6 +185,14 @@ impl TransportBondingReceiver {
                     if let Ok(mut s) = stats_clone.lock() {
                         let mut snapshot = buffer.get_stats();
                         if let Ok(link) = link_stats_clone.lock() {
+                            // Each link's reorder tolerance follows its
+                            // delay class (0 until the clocks are synced).
+                            for (link_id, ls) in link.iter().filter(|(_, ls)| ls.owd_ms > 0.0) {
+                                buffer.set_link_owd(
+                                    *link_id,
+                                    Duration::from_secs_f64(ls.owd_ms / 1000.0),
+                                );
+                            }
                             snapshot.per_link = link
                                 .iter()
                                 .map(|(link_id, ls)| ReassemblyLinkStats {
@@ -168,12 +201,22 @@ impl TransportBondingReceiver {
                                     packets_delivered: ls.packets_delivered,
                                     bytes_received: ls.bytes_received,
                                     loss_rate: ls.loss_rate,
+                                    owd_ms: ls.owd_ms,
+                                    clock_offset_us: ls.clock_offset_us,
+                                    clock_drift_ppm: ls.clock_drift_ppm,
+                                    loss_pattern: ls.loss_pattern.clone(),
+                                    redundant_copies: ls.redundant_copies,
                                 })
                                 .collect();
                         }
+                        if let Ok(d) = dedup_clone.lock() {
+                            snapshot.redundant_copies = d.duplicates();
+                        }
+                        snapshot.av_sync = av_sync.stats();
                         *s = snapshot;
                     }
 
+                    let mut hole = false;
                     for mut p in ready {
                         // Use try_send to avoid blocking the jitter thread
                         // when the downstream consumer (GStreamer) stalls.
@@ -190,6 +233,8 @@ impl TransportBondingReceiver {
                             p.1 = true;
                             carry_discont = false;
                         }
+                        hole |= p.1;
+                        av_sync.observe(&p.0, p.1, now);
                         if output_tx_clone.try_send(p).is_err() {
                             // The drop itself is a discontinuity (and may have
                             // carried a DISCONT we just lost) — flag the next
@@ -199,6 +244,23 @@ impl TransportBondingReceiver {
                             total_dropped += 1;
                         }
                     }
+                    // Everything downstream of a hole decodes corrupt until
+                    // the next IDR; ask the sender for one now rather than
+                    // wait out the rest of the GOP.
+                    if hole {
+                        let lost = buffer.get_stats().lost_packets;
+                        let lost_since = lost.saturating_sub(lost_at_request);
+                        if let Ok(mut requests) = keyframe_requests_clone.lock()
+                            && requests.on_gap(now, lost_since)
+                        {
+                            debug!(
+                                lost_packets = lost_since,
+                                "unrepaired gap, requesting keyframe"
+                            );
+                            lost_at_request = lost;
+                        }
+                    }
+
                     if dropped_since_log > 0
                         && now.duration_since(last_drop_log) >= drop_log_interval
                     {
@@ -210,6 +272,18 @@ impl TransportBondingReceiver {
                         dropped_since_log = 0;
                         last_drop_log = now;
                     }
+
+                    // End of stream once every link that carried data has
+                    // torn down and everything it delivered has been
+                    // released.
+                    let ended = buffer.get_stats().queue_depth == 0
+                        && input_rx.is_empty()
+                        && torn_down_clone
+                            .lock()
+                            .is_ok_and(|t| !t.is_empty() && t.values().all(|&done| done));
+                    if ended != end_of_stream_clone.swap(ended, Ordering::Relaxed) && ended {
+                        info!("all links torn down, end of stream");
+                    }
                 }
             })
             .expect("failed to spawn jitter buffer thread");
@@ -223,9 +297,31 @@ impl TransportBondingReceiver {
             link_stats,
             next_link_id: AtomicUsize::new(0),
             thread_handles: Mutex::new(vec![jitter_handle]),
+            ingest_key: Mutex::new(None),
+            torn_down,
+            end_of_stream,
+            dedup,
+            keyframe_requests,
         }
     }
 
+    /// Require a per-stream ingest key on links added from now on: each
+    /// link drops datagrams from any source address that hasn't presented
+    /// the key in a session HELLO. `None` trusts every source. A key
+    /// longer than a HELLO can carry is refused: no sender could match it.
+    pub fn set_ingest_key(&self, key: Option<&[u8]>) -> Result<()> {
+        if let Some(key) = key {
+            anyhow::ensure!(
+                strata_transport::session::ingest_key_fits(key),
+                "ingest key is {} bytes (max {})",
+                key.len(),
+                strata_transport::wire::MAX_INGEST_KEY_LEN
+            );
+        }
+        *self.ingest_key.lock().unwrap_or_else(|e| e.into_inner()) = key.map(Arc::from);
+        Ok(())
+    }
+
     /// Add a link by binding a UDP socket to `bind_addr`.
     ///
     /// Spawns a reader thread running a monoio event loop (io_uring on
@@ -242,14 +338,25 @@ impl TransportBondingReceiver {
         let local_addr = socket.local_addr()?;
         let link_id = self.next_link_id.fetch_add(1, Ordering::Relaxed);
 
-        let input_tx = self
-            .input_tx
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Receiver shut down"))?
-            .clone();
-        let running = self.running.clone();
-        let stats = self.stats.clone();
-        let link_stats = self.link_stats.clone();
+        let shared = LinkReaderShared {
+            input_tx: self
+                .input_tx
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Receiver shut down"))?
+                .clone(),
+            running: self.running.clone(),
+            reassembly_stats: self.stats.clone(),
+            link_stats: self.link_stats.clone(),
+            torn_down: self.torn_down.clone(),
+            dedup: self.dedup.clone(),
+            keyframe_requests: self.keyframe_requests.clone(),
+        };
+        let gate = self
+            .ingest_key
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .map(IngestGate::new);
 
         let handle = thread::Builder::new()
             .name(format!("strata-rcv-{}-{}", link_id, local_addr))
@@ -258,8 +365,7 @@ impl TransportBondingReceiver {
                 rt.block_on(async move {
                     let mono_socket = monoio::net::udp::UdpSocket::from_std(socket)
                         .expect("failed to convert socket for monoio");
-                    link_reader_async(link_id, mono_socket, input_tx, running, stats, link_stats)
-                        .await;
+                    link_reader_async(link_id, mono_socket, shared, gate).await;
                 });
             })?;
 
@@ -291,6 +397,15 @@ impl TransportBondingReceiver {
         self.stats.clone()
     }
 
+    /// Whether the sender has ended the stream cleanly: every link that
+    /// carried data has received a session TEARDOWN (see
+    /// `BondingRuntime::drain`) and the jitter buffer has released
+    /// everything. Cleared again if data arrives on a torn-down link, as
+    /// when a new sender takes over the stream.
+    pub fn is_end_of_stream(&self) -> bool {
+        self.end_of_stream.load(Ordering::Relaxed)
+    }
+
     /// Check if the receiver is still running.
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::Relaxed)
@@ -394,6 +509,89 @@ impl DelayGradientTracker {
     }
 }
 
+/// Handles a link reader shares with the receiver and its sibling links.
+struct LinkReaderShared {
+    input_tx: Sender<Packet>,
+    running: Arc<AtomicBool>,
+    reassembly_stats: Arc<Mutex<ReassemblyStats>>,
+    link_stats: Arc<Mutex<BTreeMap<usize, LinkRuntimeStats>>>,
+    torn_down: Arc<Mutex<BTreeMap<usize, bool>>>,
+    dedup: Arc<Mutex<DedupCache>>,
+    keyframe_requests: Arc<Mutex<KeyframeRequests>>,
+}
+
+/// Minimum spacing between keyframe requests. One IDR repairs every gap
+/// before it, so a gap inside this window rides on the request already out
+/// instead of costing the encoder a second IDR.
+const KEYFRAME_REQUEST_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The keyframe request the jitter thread raised last. Each link reader
+/// sends every new request to its sender once, so a request survives the
+/// loss of any one link; the sender acts on each `request_id` once.
+#[derive(Default)]
+struct KeyframeRequests {
+    latest: Option<KeyframeRequestPacket>,
+    raised_at: Option<Instant>,
+}
+
+impl KeyframeRequests {
+    /// Raise a request for an unrepaired gap, unless one went out within
+    /// [`KEYFRAME_REQUEST_INTERVAL`]. Returns whether it raised one.
+    fn on_gap(&mut self, now: Instant, lost_packets: u64) -> bool {
+        if self
+            .raised_at
+            .is_some_and(|at| now.duration_since(at) < KEYFRAME_REQUEST_INTERVAL)
+        {
+            return false;
+        }
+        let request_id = self
+            .latest
+            .as_ref()
+            .map_or(1, |r| r.request_id.wrapping_add(1));
+        self.latest = Some(KeyframeRequestPacket {
+            request_id,
+            lost_packets: lost_packets.min(u32::MAX as u64) as u32,
+        });
+        self.raised_at = Some(now);
+        true
+    }
+
+    /// The latest request, unless it is the one `sent` by this link already.
+    fn pending(&self, sent: Option<u32>) -> Option<KeyframeRequestPacket> {
+        self.latest.clone().filter(|r| Some(r.request_id) != sent)
+    }
+}
+
+/// Push a delivered bonding payload into reassembly, unless another link
+/// already delivered the same sequence — broadcast and redundancy copies
+/// are discarded here, before any jitter-buffer work. Returns `false` for
+/// a discarded copy.
+fn forward_delivery(
+    dedup: &Mutex<DedupCache>,
+    input_tx: &Sender<Packet>,
+    link_id: usize,
+    seq_id: u64,
+    payload: Bytes,
+    send_ts_us: u32,
+) -> bool {
+    let verdict = dedup
+        .lock()
+        .map_or(DedupVerdict::First, |mut d| d.check(seq_id));
+    if verdict == DedupVerdict::Duplicate {
+        return false;
+    }
+    // Non-blocking: drop packet rather than stall the async reader (and
+    // ACK/NACK generation).
+    let _ = input_tx.try_send(Packet {
+        seq_id,
+        payload,
+        arrival_time: Instant::now(),
+        send_ts_us,
+        link_id,
+    });
+    true
+}
+
 /// Per-link reader loop (async, runs on a monoio event loop).
 ///
 /// Uses io_uring (or epoll fallback) for async UDP receives, feeding
@@ -403,11 +601,18 @@ impl DelayGradientTracker {
 async fn link_reader_async(
     link_id: usize,
     socket: monoio::net::udp::UdpSocket,
-    input_tx: Sender<Packet>,
-    running: Arc<AtomicBool>,
-    reassembly_stats: Arc<Mutex<ReassemblyStats>>,
-    link_stats: Arc<Mutex<BTreeMap<usize, LinkRuntimeStats>>>,
+    shared: LinkReaderShared,
+    mut gate: Option<IngestGate>,
 ) {
+    let LinkReaderShared {
+        input_tx,
+        running,
+        reassembly_stats,
+        link_stats,
+        torn_down,
+        dedup,
+        keyframe_requests,
+    } = shared;
     let config = ReceiverConfig {
         nack_rearm_ms: 100,      // Re-ask for lost frames less frantically
         max_nack_retries: 10,    // Give cellular links up to 1000ms to deliver packets
@@ -435,10 +640,29 @@ async fn link_reader_async(
     let mut prev_reassembly_lost: u64 = 0;
     let mut prev_reassembly_delivered: u64 = 0;
     let mut prev_reassembly_late: u64 = 0;
+    // Loss-run counters at the last report, for the per-interval mean.
+    let mut prev_loss_runs: u64 = 0;
+    let mut prev_run_packets: u64 = 0;
+    // Copies this link delivered after another link already had.
+    let mut redundant_copies: u64 = 0;
     // Most recently seen sender address on this socket.
     let mut sender_addr: Option<std::net::SocketAddr> = None;
     // F3: per-link relative one-way-delay gradient (queue-build detector).
     let mut grad_tracker = DelayGradientTracker::new();
+    // Sender clock offset, adopted from the estimate its PINGs carry; maps
+    // DATA send timestamps onto our clock for absolute one-way delay.
+    let mut rtt = RttTracker::new();
+    // Smoothed absolute one-way delay (µs); 0 until the clocks are synced.
+    let mut owd_ewma_us: f64 = 0.0;
+    // Session state mirrored into `torn_down`: None until the first DATA
+    // packet, then whether the sender has torn this link down since.
+    let mut link_torn_down: Option<bool> = None;
+    // Last keyframe request sent on this link. Seeded with the current one
+    // so a link added mid-stream doesn't replay a stale request.
+    let mut keyframe_request_sent = keyframe_requests
+        .lock()
+        .ok()
+        .and_then(|k| k.latest.as_ref().map(|r| r.request_id));
 
     // ── Per-link RX diagnostics ─────────────────────────────────────────
     // A blackholed link receives nothing, so its receiver stats never
@@ -456,6 +680,19 @@ async fn link_reader_async(
     while running.load(Ordering::Relaxed) {
         // Await next datagram with a timeout so we can check the running flag.
         match monoio::time::timeout(Duration::from_millis(50), socket.recv_from(buf)).await {
+            // Keyed link, unadmitted source: nothing but a HELLO with the
+            // right key gets past this point — not even Ping or ACK traffic
+            // is sent back to it.
+            Ok((Ok((n, addr)), returned_buf))
+                if gate.as_ref().is_some_and(|g| !g.is_admitted(&addr)) =>
+            {
+                if let Some(g) = gate.as_mut()
+                    && let Some(accept) = g.try_admit(link_id, addr, &returned_buf[..n], &clock)
+                {
+                    let _ = socket.send_to(accept, addr).await;
+                }
+                buf = returned_buf;
+            }
             Ok((Ok((n, addr)), returned_buf)) => {
                 sender_addr = Some(addr);
                 if !first_packet_logged {
@@ -479,16 +716,55 @@ async fn link_reader_async(
                     if let Some(hdr) = PacketHeader::decode(&mut hdr_cur)
                         && hdr.packet_type == strata_transport::wire::PacketType::Data
                     {
-                        let rel_us = clock.now_us() as i64 - hdr.timestamp_us as i64;
+                        let recv_us = clock.now_us();
+                        let rel_us = recv_us as i64 - hdr.timestamp_us as i64;
                         grad_tracker.observe(std::time::Instant::now(), rel_us);
+                        if let Some(sent_us) = rtt
+                            .clock()
+                            .remote_to_local(hdr.timestamp_us, Instant::now())
+                        {
+                            // Offset error from path asymmetry can push a
+                            // sample slightly negative; clamp at zero.
+                            let owd_us = (recv_us.wrapping_sub(sent_us) as i32).max(0) as f64;
+                            owd_ewma_us = if owd_ewma_us == 0.0 {
+                                owd_us
+                            } else {
+                                0.875 * owd_ewma_us + 0.125 * owd_us
+                            };
+                        }
+                        if link_torn_down != Some(false) {
+                            link_torn_down = Some(false);
+                            if let Ok(mut t) = torn_down.lock() {
+                                t.insert(link_id, false);
+                            }
+                        }
+                    }
+                }
+
+                if link_torn_down == Some(false)
+                    && decode_session_packet(&returned_buf[..n]).is_some_and(|s| {
+                        s.action == strata_transport::wire::SessionAction::Teardown
+                    })
+                {
+                    info!(link_id, peer = %addr, "rx link torn down by sender");
+                    link_torn_down = Some(true);
+                    if let Ok(mut t) = torn_down.lock() {
+                        t.insert(link_id, true);
                     }
                 }
 
                 // Check for control packets (Ping) before handing to transport_rx.
                 // Respond with Pong immediately.
-                if let Some(pong_bytes) = try_make_pong(&returned_buf[..n], &clock) {
+                if let Some(pong_bytes) = try_make_pong(&returned_buf[..n], &clock, &mut rtt) {
                     let _ = socket.send_to(pong_bytes, addr).await;
                 }
+                // Keyed links prove each path to the sender by answering
+                // its challenges with a tag only the key holder can make.
+                if let Some(g) = gate.as_ref()
+                    && let Some(resp) = try_make_path_response(&returned_buf[..n], &g.key, &clock)
+                {
+                    let _ = socket.send_to(resp, addr).await;
+                }
 
                 transport_rx.receive(raw);
                 packets_since_ack += 1;
@@ -500,15 +776,16 @@ async fn link_reader_async(
                             if let Some((header, original_payload)) =
                                 BondingHeader::unwrap(delivered.payload)
                             {
-                                let packet = Packet {
-                                    seq_id: header.seq_id,
-                                    payload: original_payload,
-                                    arrival_time: quanta::Instant::now(),
-                                    send_ts_us: delivered.timestamp_us,
-                                };
-                                // Non-blocking: drop packet rather than stall
-                                // the async reader (and ACK/NACK generation).
-                                let _ = input_tx.try_send(packet);
+                                if !forward_delivery(
+                                    &dedup,
+                                    &input_tx,
+                                    link_id,
+                                    header.seq_id,
+                                    original_payload,
+                                    delivered.timestamp_us,
+                                ) {
+                                    redundant_copies += 1;
+                                }
                             } else {
                                 debug!("Dropped packet with invalid bonding header");
                             }
@@ -525,6 +802,12 @@ async fn link_reader_async(
                                 let _ = socket.send_to(pkt_bytes, addr).await;
                             }
                         }
+                        ReceiverEvent::SendSack(sack) => {
+                            if let Some(addr) = sender_addr {
+                                let pkt_bytes = encode_sack_packet(&sack, &clock);
+                                let _ = socket.send_to(pkt_bytes, addr).await;
+                            }
+                        }
                         ReceiverEvent::SendPpdReport(ppd) => {
                             if let Some(addr) = sender_addr {
                                 let pkt_bytes = encode_ppd_report(&ppd, &clock);
@@ -541,6 +824,14 @@ async fn link_reader_async(
                         let pkt_bytes = encode_control_packet(&ack, &clock);
                         let _ = socket.send_to(pkt_bytes, addr).await;
                     }
+                    // Receptions past the ACK bitmap, while a hole holds
+                    // the cumulative sequence back.
+                    if let Some(sack) = transport_rx.generate_sack()
+                        && let Some(addr) = sender_addr
+                    {
+                        let pkt_bytes = encode_sack_packet(&sack, &clock);
+                        let _ = socket.send_to(pkt_bytes, addr).await;
+                    }
                     // Also generate NACKs for missing packets.
                     if let Some(nack) = transport_rx.generate_nacks()
                         && let Some(addr) = sender_addr
@@ -554,14 +845,16 @@ async fn link_reader_async(
                         if let ReceiverEvent::Deliver(delivered) = event
                             && let Some((header, original_payload)) =
                                 BondingHeader::unwrap(delivered.payload)
+                            && !forward_delivery(
+                                &dedup,
+                                &input_tx,
+                                link_id,
+                                header.seq_id,
+                                original_payload,
+                                delivered.timestamp_us,
+                            )
                         {
-                            let packet = Packet {
-                                seq_id: header.seq_id,
-                                payload: original_payload,
-                                arrival_time: quanta::Instant::now(),
-                                send_ts_us: delivered.timestamp_us,
-                            };
-                            let _ = input_tx.try_send(packet);
+                            redundant_copies += 1;
                         }
                     }
                     last_ack = std::time::Instant::now();
@@ -678,10 +971,30 @@ async fn link_reader_async(
                                     packets_delivered: rx_stats.packets_delivered,
                                     bytes_received: rx_stats.bytes_received,
                                     loss_rate: loss_after_fec,
+                                    owd_ms: owd_ewma_us / 1000.0,
+                                    clock_offset_us: rtt.clock().offset_us(),
+                                    clock_drift_ppm: rtt.clock().drift_ppm(),
+                                    loss_pattern: rx_stats.loss_pattern.clone(),
+                                    redundant_copies,
                                 },
                             );
                         }
 
+                        // Mean loss-run length over this interval (delta,
+                        // not cumulative, so a past handover doesn't keep
+                        // inflating FEC after the link has settled).
+                        let pattern = &rx_stats.loss_pattern;
+                        let d_runs = pattern.loss_runs.saturating_sub(prev_loss_runs);
+                        let d_run_packets = pattern.run_packets.saturating_sub(prev_run_packets);
+                        prev_loss_runs = pattern.loss_runs;
+                        prev_run_packets = pattern.run_packets;
+                        let mean_loss_burst = if d_runs > 0 {
+                            (d_run_packets as f64 / d_runs as f64 * 100.0).min(u16::MAX as f64)
+                                as u16
+                        } else {
+                            0
+                        };
+
                         let report = strata_transport::wire::ReceiverReportPacket {
                             goodput_bps,
                             fec_repair_rate: (fec_rate * 10000.0) as u16,
@@ -697,6 +1010,10 @@ async fn link_reader_async(
                             // magnitude in µs, drives delay-bounded backoff
                             // on the sender before loss appears.
                             delay_gradient_us: grad_tracker.gradient_us(),
+                            // Absolute OWD against the clock offset the
+                            // sender shares in its PINGs.
+                            owd_us: owd_ewma_us as u32,
+                            mean_loss_burst,
                         };
                         let pkt_bytes = encode_receiver_report(&report, &clock);
                         let _ = socket.send_to(pkt_bytes, addr).await;
@@ -720,20 +1037,26 @@ async fn link_reader_async(
                         let ack = transport_rx.generate_ack();
                         let pkt_bytes = encode_control_packet(&ack, &clock);
                         let _ = socket.send_to(pkt_bytes, addr).await;
+                        if let Some(sack) = transport_rx.generate_sack() {
+                            let pkt_bytes = encode_sack_packet(&sack, &clock);
+                            let _ = socket.send_to(pkt_bytes, addr).await;
+                        }
 
                         // Drain gap-skip deliveries.
                         for event in transport_rx.drain_events() {
                             if let ReceiverEvent::Deliver(delivered) = event
                                 && let Some((header, original_payload)) =
                                     BondingHeader::unwrap(delivered.payload)
+                                && !forward_delivery(
+                                    &dedup,
+                                    &input_tx,
+                                    link_id,
+                                    header.seq_id,
+                                    original_payload,
+                                    delivered.timestamp_us,
+                                )
                             {
-                                let packet = Packet {
-                                    seq_id: header.seq_id,
-                                    payload: original_payload,
-                                    arrival_time: quanta::Instant::now(),
-                                    send_ts_us: delivered.timestamp_us,
-                                };
-                                let _ = input_tx.try_send(packet);
+                                redundant_copies += 1;
                             }
                         }
                     }
@@ -743,6 +1066,21 @@ async fn link_reader_async(
             }
         }
 
+        // Forward a new keyframe request from the jitter thread. Checked
+        // every iteration so an idle link still carries it within one recv
+        // timeout.
+        if let Some(addr) = sender_addr {
+            let pending = keyframe_requests
+                .lock()
+                .ok()
+                .and_then(|k| k.pending(keyframe_request_sent));
+            if let Some(request) = pending {
+                let pkt_bytes = encode_keyframe_request(&request, &clock);
+                let _ = socket.send_to(pkt_bytes, addr).await;
+                keyframe_request_sent = Some(request.request_id);
+            }
+        }
+
         // Per-link RX heartbeat — runs every iteration regardless of the
         // match arm, so a link that has received NOTHING still emits a
         // line every `rx_log_interval` (rx_pkts=0), making a blackholed
@@ -772,8 +1110,121 @@ async fn link_reader_async(
     }
 }
 
-/// Try to decode a Ping control packet and produce a Pong response.
-fn try_make_pong(data: &[u8], clock: &TimestampClock) -> Option<Vec<u8>> {
+/// Most source addresses a keyed link keeps admitted at once. A link has
+/// one sender; more than one address only happens when a carrier NAT
+/// rebinds mid-stream, and the oldest mapping is the one that went stale.
+const MAX_ADMITTED_SOURCES: usize = 4;
+
+/// How often a keyed link logs the datagrams it has dropped from
+/// unadmitted sources.
+const REJECT_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Source-address admission for a link that requires an ingest key.
+///
+/// Replaces trusting whoever reaches the port: a source is admitted only
+/// after its session HELLO presents the stream's key, and is answered
+/// with an ACCEPT.
+struct IngestGate {
+    key: Arc<[u8]>,
+    admitted: std::collections::VecDeque<SocketAddr>,
+    rejected: u64,
+    last_reject_log: Option<std::time::Instant>,
+}
+
+impl IngestGate {
+    fn new(key: Arc<[u8]>) -> Self {
+        Self {
+            key,
+            admitted: std::collections::VecDeque::with_capacity(MAX_ADMITTED_SOURCES),
+            rejected: 0,
+            last_reject_log: None,
+        }
+    }
+
+    fn is_admitted(&self, addr: &SocketAddr) -> bool {
+        self.admitted.contains(addr)
+    }
+
+    /// Admit `addr` if `data` is a HELLO carrying the right key, returning
+    /// the encoded ACCEPT to send back. Anything else is dropped.
+    fn try_admit(
+        &mut self,
+        link_id: usize,
+        addr: SocketAddr,
+        data: &[u8],
+        clock: &TimestampClock,
+    ) -> Option<Vec<u8>> {
+        use strata_transport::wire::{SessionAction, SessionPacket};
+        use subtle::ConstantTimeEq;
+
+        let hello = decode_session_packet(data).filter(|s| {
+            s.action == SessionAction::Hello
+                && s.ingest_key
+                    .as_deref()
+                    .is_some_and(|k| self.key.ct_eq(k).into())
+        });
+        let Some(hello) = hello else {
+            self.rejected += 1;
+            if self
+                .last_reject_log
+                .is_none_or(|t| t.elapsed() >= REJECT_LOG_INTERVAL)
+            {
+                warn!(
+                    link_id,
+                    peer = %addr,
+                    rejected_total = self.rejected,
+                    "dropping datagrams from source without a valid ingest key"
+                );
+                self.last_reject_log = Some(std::time::Instant::now());
+            }
+            return None;
+        };
+
+        if self.admitted.len() >= MAX_ADMITTED_SOURCES {
+            self.admitted.pop_front();
+        }
+        self.admitted.push_back(addr);
+        info!(link_id, peer = %addr, "rx link source admitted by ingest key");
+
+        let accept = SessionPacket {
+            action: SessionAction::Accept,
+            session_id: hello.session_id,
+            link_id: hello.link_id,
+            ingest_key: None,
+        };
+        let mut body = BytesMut::with_capacity(16);
+        accept.encode(&mut body);
+        let body_bytes = body.freeze();
+        let header = PacketHeader::control(0, clock.now_us(), body_bytes.len() as u16);
+        Some(
+            WirePacket {
+                header,
+                payload: body_bytes,
+            }
+            .encode()
+            .to_vec(),
+        )
+    }
+}
+
+/// Decode a datagram as a session control packet, if it is one.
+fn decode_session_packet(data: &[u8]) -> Option<strata_transport::wire::SessionPacket> {
+    use strata_transport::wire::PacketType;
+    let mut cursor: &[u8] = data;
+    let pkt = WirePacket::decode(&mut cursor)?;
+    if pkt.header.packet_type != PacketType::Control {
+        return None;
+    }
+    let mut payload_cursor = &pkt.payload[..];
+    match ControlBody::decode(&mut payload_cursor) {
+        Some(ControlBody::Session(session)) => Some(session),
+        _ => None,
+    }
+}
+
+/// Try to decode a Ping control packet and produce a Pong response,
+/// adopting the sender's clock offset estimate if the Ping carries one.
+fn try_make_pong(data: &[u8], clock: &TimestampClock, rtt: &mut RttTracker) -> Option<Vec<u8>> {
     use strata_transport::wire::Packet as WP;
     use strata_transport::wire::PacketType;
     let mut cursor: &[u8] = data;
@@ -783,6 +1234,7 @@ fn try_make_pong(data: &[u8], clock: &TimestampClock) -> Option<Vec<u8>> {
     }
     let mut payload_cursor = &pkt.payload[..];
     if let Some(ControlBody::Ping(ping)) = ControlBody::decode(&mut payload_cursor) {
+        rtt.handle_ping(&ping);
         let pong = RttTracker::make_pong(&ping, clock.now_us());
         let mut body = BytesMut::with_capacity(16);
         pong.encode(&mut body);
@@ -798,6 +1250,35 @@ fn try_make_pong(data: &[u8], clock: &TimestampClock) -> Option<Vec<u8>> {
     }
 }
 
+/// Try to decode a PathChallenge control packet and produce the keyed
+/// PathResponse for it.
+fn try_make_path_response(data: &[u8], key: &[u8], clock: &TimestampClock) -> Option<Vec<u8>> {
+    use strata_transport::wire::PacketType;
+    let mut cursor: &[u8] = data;
+    let pkt = WirePacket::decode(&mut cursor)?;
+    if pkt.header.packet_type != PacketType::Control {
+        return None;
+    }
+    let mut payload_cursor = &pkt.payload[..];
+    let Some(ControlBody::PathChallenge(challenge)) = ControlBody::decode(&mut payload_cursor)
+    else {
+        return None;
+    };
+    let resp = strata_transport::session::make_path_response(key, &challenge);
+    let mut body = BytesMut::with_capacity(32);
+    resp.encode(&mut body);
+    let body_bytes = body.freeze();
+    let header = PacketHeader::control(0, clock.now_us(), body_bytes.len() as u16);
+    Some(
+        WirePacket {
+            header,
+            payload: body_bytes,
+        }
+        .encode()
+        .to_vec(),
+    )
+}
+
 /// Encode an ACK as a wire-format control packet.
 fn encode_control_packet(
     ack: &strata_transport::wire::AckPacket,
@@ -830,6 +1311,22 @@ fn encode_nack_packet(
     pkt.encode().to_vec()
 }
 
+/// Encode a selective ACK as a wire-format control packet.
+fn encode_sack_packet(
+    sack: &strata_transport::wire::SackPacket,
+    clock: &TimestampClock,
+) -> Vec<u8> {
+    let mut body = BytesMut::with_capacity(256);
+    sack.encode(&mut body);
+    let body_bytes = body.freeze();
+    let header = PacketHeader::control(0, clock.now_us(), body_bytes.len() as u16);
+    let pkt = WirePacket {
+        header,
+        payload: body_bytes,
+    };
+    pkt.encode().to_vec()
+}
+
 /// Encode a ReceiverReport as a wire-format control packet.
 fn encode_receiver_report(
     report: &strata_transport::wire::ReceiverReportPacket,
@@ -862,11 +1359,43 @@ fn encode_ppd_report(
     pkt.encode().to_vec()
 }
 
+/// Encode a KeyframeRequest as a wire-format control packet.
+fn encode_keyframe_request(request: &KeyframeRequestPacket, clock: &TimestampClock) -> Vec<u8> {
+    let mut body = BytesMut::with_capacity(16);
+    request.encode(&mut body);
+    let body_bytes = body.freeze();
+    let header = PacketHeader::control(0, clock.now_us(), body_bytes.len() as u16);
+    let pkt = WirePacket {
+        header,
+        payload: body_bytes,
+    };
+    pkt.encode().to_vec()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::net::UdpSocket;
 
+    #[test]
+    fn keyframe_requests_are_rate_limited_and_sent_once_per_link() {
+        let mut requests = KeyframeRequests::default();
+        let t0 = Instant::now();
+        assert!(requests.pending(None).is_none());
+
+        assert!(requests.on_gap(t0, 12));
+        let first = requests.pending(None).unwrap();
+        assert_eq!((first.request_id, first.lost_packets), (1, 12));
+        assert!(requests.pending(Some(1)).is_none(), "already sent");
+
+        // A second gap inside the interval rides on the first request.
+        assert!(!requests.on_gap(t0 + Duration::from_millis(200), 3));
+        assert_eq!(requests.pending(None).unwrap().request_id, 1);
+
+        assert!(requests.on_gap(t0 + KEYFRAME_REQUEST_INTERVAL, 3));
+        assert_eq!(requests.pending(Some(1)).unwrap().request_id, 2);
+    }
+
     #[test]
     fn new_receiver_has_empty_stats() {
         let rcv = TransportBondingReceiver::new(Duration::from_millis(50));
@@ -1018,6 +1547,136 @@ mod tests {
         }
     }
 
+    #[test]
+    fn teardown_on_every_link_ends_the_stream() {
+        use crate::net::interface::LinkSender;
+
+        let rcv = TransportBondingReceiver::new(Duration::from_millis(20));
+        let rcv_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let rcv_addr = rcv_socket.local_addr().unwrap();
+        rcv.add_link_socket(rcv_socket).unwrap();
+        let send_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        send_socket.connect(rcv_addr).unwrap();
+        let sender = crate::net::transport::TransportLink::new(
+            0,
+            send_socket,
+            strata_transport::sender::SenderConfig::default(),
+            None,
+        );
+        let wrap =
+            |seq| crate::protocol::header::BondingHeader::new(seq).wrap(Bytes::from_static(b"ts"));
+        let wait_for = |eos: bool| {
+            let deadline = std::time::Instant::now() + Duration::from_secs(3);
+            while rcv.is_end_of_stream() != eos && std::time::Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(20));
+            }
+            rcv.is_end_of_stream() == eos
+        };
+
+        sender.send(&wrap(0)).unwrap();
+        rcv.output_rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert!(!rcv.is_end_of_stream(), "no teardown yet");
+
+        sender.send_teardown();
+        assert!(wait_for(true), "teardown should end the stream");
+
+        // A new sender taking over the link resumes the stream.
+        sender.send(&wrap(1)).unwrap();
+        assert!(wait_for(false), "data after teardown should clear EOS");
+    }
+
+    /// Loopback receiver with an ingest key and a sender link pointed at it.
+    fn keyed_loopback(
+        sender_key: Option<&[u8]>,
+    ) -> (
+        TransportBondingReceiver,
+        crate::net::transport::TransportLink,
+    ) {
+        use crate::net::interface::LinkSender;
+
+        let rcv = TransportBondingReceiver::new(Duration::from_millis(20));
+        rcv.set_ingest_key(Some(b"isk_right")).unwrap();
+        let rcv_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let rcv_addr = rcv_socket.local_addr().unwrap();
+        rcv.add_link_socket(rcv_socket).unwrap();
+
+        let send_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        send_socket.connect(rcv_addr).unwrap();
+        let sender = crate::net::transport::TransportLink::new(
+            0,
+            send_socket,
+            strata_transport::sender::SenderConfig::default(),
+            None,
+        );
+        sender.set_ingest_key(sender_key);
+        (rcv, sender)
+    }
+
+    #[test]
+    fn keyed_link_accepts_sender_with_matching_key() {
+        use crate::net::interface::LinkSender;
+
+        let (rcv, sender) = keyed_loopback(Some(b"isk_right"));
+        let payload = Bytes::from_static(b"keyed");
+        let wrapped = crate::protocol::header::BondingHeader::new(0).wrap(payload.clone());
+        sender.send(&wrapped).unwrap();
+
+        let (received, _) = rcv
+            .output_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("keyed sender must be admitted");
+        assert_eq!(received, payload);
+    }
+
+    #[test]
+    fn keyed_link_drops_sender_without_key() {
+        use crate::net::interface::LinkSender;
+
+        for key in [None, Some(&b"isk_wrong"[..])] {
+            let (rcv, sender) = keyed_loopback(key);
+            let wrapped = crate::protocol::header::BondingHeader::new(0)
+                .wrap(Bytes::from_static(b"intruder"));
+            sender.send(&wrapped).unwrap();
+            assert!(
+                rcv.output_rx
+                    .recv_timeout(Duration::from_millis(300))
+                    .is_err(),
+                "source with key {key:?} must not be admitted"
+            );
+        }
+    }
+
+    #[test]
+    fn ingest_gate_evicts_oldest_source() {
+        let clock = TimestampClock::new();
+        let mut gate = IngestGate::new(Arc::from(&b"k"[..]));
+        let hello = {
+            let mut body = BytesMut::new();
+            strata_transport::wire::SessionPacket {
+                action: strata_transport::wire::SessionAction::Hello,
+                session_id: 0,
+                link_id: None,
+                ingest_key: Some(b"k".to_vec()),
+            }
+            .encode(&mut body);
+            let body = body.freeze();
+            WirePacket {
+                header: PacketHeader::control(0, 0, body.len() as u16),
+                payload: body,
+            }
+            .encode()
+            .to_vec()
+        };
+        let addrs: Vec<SocketAddr> = (0..=MAX_ADMITTED_SOURCES as u16)
+            .map(|i| SocketAddr::from(([10, 0, 0, 1], 4000 + i)))
+            .collect();
+        for addr in &addrs {
+            assert!(gate.try_admit(0, *addr, &hello, &clock).is_some());
+        }
+        assert!(!gate.is_admitted(&addrs[0]), "oldest mapping evicted");
+        assert!(gate.is_admitted(addrs.last().unwrap()));
+    }
+
     #[test]
     fn multi_packet_ordering() {
         let rcv = TransportBondingReceiver::new(Duration::from_millis(20));
@@ -1068,6 +1727,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn broadcast_copies_are_discarded_before_reassembly() {
+        use crate::net::interface::LinkSender;
+
+        let rcv = TransportBondingReceiver::new(Duration::from_millis(20));
+        let senders: Vec<_> = (0..2)
+            .map(|id| {
+                let rcv_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+                let rcv_addr = rcv_socket.local_addr().unwrap();
+                rcv.add_link_socket(rcv_socket).unwrap();
+                let send_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+                send_socket.connect(rcv_addr).unwrap();
+                crate::net::transport::TransportLink::new(
+                    id,
+                    send_socket,
+                    strata_transport::sender::SenderConfig::default(),
+                    None,
+                )
+            })
+            .collect();
+
+        // Every payload goes out on both links, as in broadcast mode.
+        let count = 5;
+        for i in 0..count {
+            let wrapped = crate::protocol::header::BondingHeader::new(i)
+                .wrap(Bytes::from(format!("packet-{i}")));
+            for sender in &senders {
+                sender.send(&wrapped).unwrap();
+            }
+        }
+
+        for i in 0..count {
+            let (data, _) = rcv
+                .output_rx
+                .recv_timeout(Duration::from_secs(3))
+                .expect("each sequence delivered once");
+            assert_eq!(data, Bytes::from(format!("packet-{i}")));
+        }
+        assert!(
+            rcv.output_rx
+                .recv_timeout(Duration::from_millis(200))
+                .is_err(),
+            "no second copy delivered"
+        );
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(3);
+        while rcv.get_stats().redundant_copies < count && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        let stats = rcv.get_stats();
+        assert_eq!(stats.redundant_copies, count);
+        assert_eq!(
+            stats.duplicate_packets, 0,
+            "copies never reached reassembly"
+        );
+    }
+
     #[test]
     fn add_link_after_shutdown_fails() {
         let mut rcv = TransportBondingReceiver::new(Duration::from_millis(50));
@@ -1,9 +1,11 @@
-use crate::config::{BondingConfig, LinkConfig, SchedulerConfig};
+use crate::config::{BondingConfig, LinkConfig, ReplicaConfig, SchedulerConfig, clamp_link_weight};
+use crate::events::{EventLog, EventRecord};
 use crate::media::priority::DegradationStage;
 use crate::metrics::MetricsServer;
 use crate::net::interface::{LinkMetrics, LinkSender};
 use crate::net::transport::TransportLink;
-use crate::scheduler::bonding::BondingScheduler;
+use crate::scheduler::bonding::{BondingScheduler, SpreadStats};
+use crate::telemetry::{TelemetryForwarder, TelemetrySink};
 
 /// Build a monoio runtime with io_uring SQPOLL if available.
 ///
@@ -62,6 +64,20 @@ use tracing::warn;
 pub enum PacketSendError {
     Full,
     Disconnected,
+    /// [`BondingRuntime::drain`] has been called; no new packets are taken.
+    Draining,
+}
+
+/// Outcome of [`BondingRuntime::drain`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DrainReport {
+    /// Packets still queued for the scheduler when the drain started.
+    pub packets_flushed: u64,
+    /// Packets left unacknowledged when the drain finished; non-zero only
+    /// if it timed out.
+    pub unacked: usize,
+    /// The timeout elapsed before every link was acknowledged.
+    pub timed_out: bool,
 }
 
 /// Control messages for the worker thread (cold path).
@@ -71,9 +87,28 @@ enum ControlMessage {
     RemoveLink(usize),
     SetDegradationStage(DegradationStage),
     SetFecOverhead(f64),
+    SetLinkWeights(Vec<f64>),
+    Drain {
+        deadline: std::time::Instant,
+        reply: Sender<DrainReport>,
+    },
     Shutdown,
 }
 
+/// Per-replica link metrics, keyed by replica name.
+type ReplicaMetrics = HashMap<String, HashMap<usize, LinkMetrics>>;
+
+/// A replica receiver (see [`ReplicaConfig`]) inside the worker: its own
+/// scheduler, and so its own link sessions with independent ARQ and FEC
+/// state, fed a copy of every packet the primary gets.
+struct Replica {
+    config: ReplicaConfig,
+    scheduler: BondingScheduler<dyn LinkSender>,
+    links: HashMap<usize, LinkConfig>,
+    /// Rate caps currently applied, per link.
+    caps: HashMap<usize, Option<u64>>,
+}
+
 /// Thread-safe handle to the bonding scheduler worker.
 ///
 /// Owns a background thread that runs the [`BondingScheduler`]
@@ -88,9 +123,16 @@ pub struct BondingRuntime {
     packet_tx: rtrb::Producer<(Bytes, PacketProfile)>,
     control_tx: Sender<ControlMessage>,
     alive: Arc<AtomicBool>,
+    draining: bool,
     metrics: Arc<Mutex<HashMap<usize, LinkMetrics>>>,
+    replica_metrics: Arc<Mutex<ReplicaMetrics>>,
+    events: EventLog,
+    spread_stats: Arc<SpreadStats>,
     handle: Option<thread::JoinHandle<()>>,
     metrics_server: Option<MetricsServer>,
+    /// Snapshot cadence for telemetry sinks (`stats_interval_ms`).
+    stats_interval: Duration,
+    telemetry: Option<TelemetryForwarder>,
 }
 
 impl BondingRuntime {
@@ -111,16 +153,31 @@ impl BondingRuntime {
         let (control_tx, control_rx) = crossbeam_channel::unbounded();
         let metrics = Arc::new(Mutex::new(HashMap::new()));
         let metrics_clone = metrics.clone();
+        let replica_metrics = Arc::new(Mutex::new(HashMap::new()));
+        let replica_metrics_clone = replica_metrics.clone();
         let alive = Arc::new(AtomicBool::new(true));
         let alive_clone = alive.clone();
+        let events = EventLog::new();
+        let events_clone = events.clone();
+        let spread_stats = Arc::new(SpreadStats::default());
+        let spread_stats_clone = spread_stats.clone();
+        let stats_interval = Duration::from_millis(scheduler_config.stats_interval_ms);
 
         let handle = thread::Builder::new()
             .name("strata-worker".into())
             .spawn(move || {
                 let mut rt = build_monoio_runtime!();
                 rt.block_on(async move {
-                    runtime_worker_async(packet_rx, control_rx, metrics_clone, scheduler_config)
-                        .await;
+                    runtime_worker_async(
+                        packet_rx,
+                        control_rx,
+                        metrics_clone,
+                        replica_metrics_clone,
+                        events_clone,
+                        spread_stats_clone,
+                        scheduler_config,
+                    )
+                    .await;
                 });
                 alive_clone.store(false, Ordering::Relaxed);
             })
@@ -130,16 +187,23 @@ impl BondingRuntime {
             packet_tx,
             control_tx,
             alive,
+            draining: false,
             metrics,
+            replica_metrics,
+            events,
+            spread_stats,
             handle: Some(handle),
             metrics_server: None,
+            stats_interval,
+            telemetry: None,
         }
     }
 
     /// Enqueues a packet for transmission. Returns immediately.
     ///
     /// Returns `PacketSendError::Full` if the internal ring buffer is saturated,
-    /// or `PacketSendError::Disconnected` if the worker thread has exited.
+    /// `PacketSendError::Disconnected` if the worker thread has exited, or
+    /// `PacketSendError::Draining` once [`Self::drain`] has been called.
     pub fn try_send_packet(
         &mut self,
         data: Bytes,
@@ -148,13 +212,25 @@ impl BondingRuntime {
         if !self.alive.load(Ordering::Relaxed) {
             return Err(PacketSendError::Disconnected);
         }
+        if self.draining {
+            return Err(PacketSendError::Draining);
+        }
         self.packet_tx
             .push((data, profile))
             .map_err(|_| PacketSendError::Full)
     }
 
-    /// Sends a full configuration update to the worker thread.
+    /// Sends a full configuration update to the worker thread. Refuses an
+    /// ingest key too long for a session HELLO to carry.
     pub fn apply_config(&self, config: BondingConfig) -> anyhow::Result<()> {
+        if let Some(key) = &config.ingest_key {
+            anyhow::ensure!(
+                strata_transport::session::ingest_key_fits(key.as_bytes()),
+                "ingest_key is {} bytes (max {})",
+                key.len(),
+                strata_transport::wire::MAX_INGEST_KEY_LEN
+            );
+        }
         self.control_tx
             .send(ControlMessage::ApplyConfig(Box::new(config)))
             .map_err(|e| anyhow::anyhow!("Failed to send config: {}", e))
@@ -188,6 +264,42 @@ impl BondingRuntime {
         let _ = self.control_tx.send(ControlMessage::SetFecOverhead(ratio));
     }
 
+    /// Replaces the per-link scheduling weights, indexed by link ID
+    /// (thread-safe). Each is clamped with [`clamp_link_weight`]; see
+    /// [`SchedulerConfig::link_weight`].
+    pub fn set_link_weights(&self, weights: Vec<f64>) {
+        let weights = weights.into_iter().map(clamp_link_weight).collect();
+        let _ = self
+            .control_tx
+            .send(ControlMessage::SetLinkWeights(weights));
+    }
+
+    /// Ends the stream cleanly: stops accepting packets, sends everything
+    /// already queued, flushes each link's partial FEC generation, waits up
+    /// to `timeout` for ARQ to get the in-flight packets acknowledged, then
+    /// sends a session TEARDOWN on every link (a few times, in case one is
+    /// lost) so the receiver can signal end-of-stream instead of waiting
+    /// out a timeout.
+    ///
+    /// The runtime stays up afterwards (metrics keep refreshing) but
+    /// rejects packets with [`PacketSendError::Draining`]; follow with
+    /// [`Self::shutdown`].
+    pub fn drain(&mut self, timeout: Duration) -> anyhow::Result<DrainReport> {
+        self.draining = true;
+        let (reply, report) = crossbeam_channel::bounded(1);
+        self.control_tx
+            .send(ControlMessage::Drain {
+                deadline: std::time::Instant::now() + timeout,
+                reply,
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to drain: {}", e))?;
+        // The worker bounds the ACK wait itself; the margin covers the
+        // flush and teardown on either side of it.
+        report
+            .recv_timeout(timeout + Duration::from_secs(1))
+            .map_err(|e| anyhow::anyhow!("Drain did not complete: {}", e))
+    }
+
     /// Returns a snapshot of all link metrics (thread-safe clone).
     pub fn get_metrics(&self) -> HashMap<usize, LinkMetrics> {
         self.metrics
@@ -196,11 +308,51 @@ impl BondingRuntime {
             .clone()
     }
 
+    /// Returns a snapshot of each replica's link metrics, keyed by replica
+    /// name. Link IDs match the primary links they share.
+    pub fn get_replica_metrics(&self) -> HashMap<String, HashMap<usize, LinkMetrics>> {
+        self.replica_metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     /// Returns a shared handle to the metrics map for external polling.
     pub fn metrics_handle(&self) -> Arc<Mutex<HashMap<usize, LinkMetrics>>> {
         self.metrics.clone()
     }
 
+    /// Returns a shared handle to the primary scheduler's
+    /// duplicate-staggering counters.
+    pub fn spread_stats(&self) -> Arc<SpreadStats> {
+        self.spread_stats.clone()
+    }
+
+    /// Subscribes to link and failover state transitions (see
+    /// [`crate::events`]). Each subscriber gets every event emitted after
+    /// the call.
+    pub fn subscribe_events(&self) -> crossbeam_channel::Receiver<EventRecord> {
+        self.events.subscribe()
+    }
+
+    /// Attach a telemetry sink (see [`crate::telemetry`]). It gets every
+    /// event emitted from now on and a snapshot every `stats_interval_ms`
+    /// until the runtime shuts down.
+    pub fn add_telemetry_sink(&mut self, sink: impl TelemetrySink + 'static) {
+        match &self.telemetry {
+            Some(telemetry) => telemetry.add(Box::new(sink)),
+            None => {
+                self.telemetry = Some(TelemetryForwarder::spawn(
+                    self.metrics.clone(),
+                    self.spread_stats.clone(),
+                    &self.events,
+                    self.stats_interval,
+                    Box::new(sink),
+                ));
+            }
+        }
+    }
+
     /// Start a Prometheus-compatible HTTP metrics server on the given address.
     ///
     /// The server responds to `GET /metrics` with Prometheus text exposition
@@ -228,6 +380,10 @@ impl BondingRuntime {
         if let Some(handle) = self.handle.take() {
             let _ = handle.join();
         }
+        // After the worker, so sinks see what it emitted while stopping.
+        if let Some(mut telemetry) = self.telemetry.take() {
+            telemetry.stop();
+        }
     }
 }
 
@@ -247,11 +403,20 @@ async fn runtime_worker_async(
     mut packet_rx: rtrb::Consumer<(Bytes, PacketProfile)>,
     control_rx: Receiver<ControlMessage>,
     metrics: Arc<Mutex<HashMap<usize, LinkMetrics>>>,
+    replica_metrics: Arc<Mutex<ReplicaMetrics>>,
+    events: EventLog,
+    spread_stats: Arc<SpreadStats>,
     scheduler_config: SchedulerConfig,
 ) {
     let mut scheduler: BondingScheduler<dyn LinkSender> =
         BondingScheduler::with_config(scheduler_config.clone());
+    scheduler.set_event_log(events);
+    scheduler.set_spread_stats(spread_stats);
     let mut current_links: HashMap<usize, LinkConfig> = HashMap::new();
+    let mut replicas: Vec<Replica> = Vec::new();
+    // Links added before the config arrives start unkeyed and are keyed
+    // when it does; links added after get the key at creation.
+    let mut ingest_key: Option<Vec<u8>> = None;
 
     let mut last_fast_stats = Instant::now();
     let fast_stats_interval = Duration::from_millis(100);
@@ -264,6 +429,7 @@ async fn runtime_worker_async(
 
         // Drain all available packets from the lock-free ring buffer.
         while let Ok((data, profile)) = packet_rx.pop() {
+            send_to_replicas(&mut replicas, &data, profile);
             let result = scheduler.send(data, profile);
             if let Err(ref e) = result {
                 tracing::warn!(target: "strata::runtime", error = %e, "scheduler.send() failed");
@@ -272,6 +438,12 @@ async fn runtime_worker_async(
             total_pkts_drained += 1;
         }
 
+        // Staggered duplicates come due between packets too.
+        scheduler.flush_staggered();
+        for replica in &mut replicas {
+            replica.scheduler.flush_staggered();
+        }
+
         // Periodic drain summary (every 2s)
         if last_drain_log.elapsed() >= Duration::from_secs(2) {
             tracing::debug!(
@@ -289,21 +461,119 @@ async fn runtime_worker_async(
                     did_work = true;
                     match msg {
                         ControlMessage::AddLink(link) => {
-                            apply_link(&mut scheduler, &mut current_links, link);
+                            apply_link(
+                                &mut scheduler,
+                                &mut current_links,
+                                link,
+                                ingest_key.as_deref(),
+                            );
                         }
                         ControlMessage::RemoveLink(id) => {
                             scheduler.remove_link(id);
                             current_links.remove(&id);
                         }
-                        ControlMessage::ApplyConfig(config) => {
+                        ControlMessage::ApplyConfig(mut config) => {
                             scheduler.update_config(config.scheduler.clone());
-                            apply_config(&mut scheduler, &mut current_links, *config);
+                            let key = config.ingest_key.as_ref().map(|k| k.as_bytes().to_vec());
+                            if key != ingest_key {
+                                ingest_key = key;
+                                scheduler.set_ingest_key(ingest_key.as_deref());
+                                for replica in &replicas {
+                                    replica.scheduler.set_ingest_key(ingest_key.as_deref());
+                                }
+                            }
+                            // Replicas ride on the primary links, so they
+                            // are reconciled whenever the links are.
+                            if !config.links.is_empty() {
+                                apply_replicas(
+                                    &mut replicas,
+                                    std::mem::take(&mut config.replicas),
+                                    &config.scheduler,
+                                    ingest_key.as_deref(),
+                                );
+                            } else {
+                                for replica in &mut replicas {
+                                    replica.scheduler.update_config(config.scheduler.clone());
+                                }
+                            }
+                            apply_config(
+                                &mut scheduler,
+                                &mut current_links,
+                                *config,
+                                ingest_key.as_deref(),
+                            );
                         }
+                        // The encoder and the first mile are shared, so
+                        // replicas follow the primary's degradation stage
+                        // and repair strength.
                         ControlMessage::SetDegradationStage(stage) => {
                             scheduler.set_degradation_stage(stage);
+                            for replica in &mut replicas {
+                                replica.scheduler.set_degradation_stage(stage);
+                            }
                         }
                         ControlMessage::SetFecOverhead(ratio) => {
                             scheduler.set_fec_overhead(ratio);
+                            for replica in &replicas {
+                                replica.scheduler.set_fec_overhead(ratio);
+                            }
+                        }
+                        ControlMessage::SetLinkWeights(weights) => {
+                            for replica in &mut replicas {
+                                replica.scheduler.set_link_weights(weights.clone());
+                            }
+                            scheduler.set_link_weights(weights);
+                        }
+                        ControlMessage::Drain { deadline, reply } => {
+                            let mut report = DrainReport::default();
+                            while let Ok((data, profile)) = packet_rx.pop() {
+                                send_to_replicas(&mut replicas, &data, profile);
+                                if let Err(e) = scheduler.send(data, profile) {
+                                    tracing::warn!(target: "strata::runtime", error = %e, "scheduler.send() failed during drain");
+                                }
+                                report.packets_flushed += 1;
+                            }
+                            scheduler.release_staggered();
+                            scheduler.flush_fec();
+                            for replica in &mut replicas {
+                                replica.scheduler.release_staggered();
+                                replica.scheduler.flush_fec();
+                            }
+                            loop {
+                                report.unacked = scheduler.in_flight()
+                                    + replicas
+                                        .iter()
+                                        .map(|r| r.scheduler.in_flight())
+                                        .sum::<usize>();
+                                if report.unacked == 0 {
+                                    break;
+                                }
+                                if std::time::Instant::now() >= deadline {
+                                    report.timed_out = true;
+                                    break;
+                                }
+                                monoio::time::sleep(Duration::from_millis(5)).await;
+                            }
+                            // TEARDOWN is unacknowledged: repeat it, spaced
+                            // out, so one lost datagram doesn't leave the
+                            // receiver waiting out its timeout.
+                            for round in 0..TEARDOWN_REPEATS {
+                                if round > 0 {
+                                    monoio::time::sleep(TEARDOWN_SPACING).await;
+                                }
+                                scheduler.send_teardown();
+                                for replica in &replicas {
+                                    replica.scheduler.send_teardown();
+                                }
+                            }
+                            tracing::info!(
+                                target: "strata::runtime",
+                                packets_flushed = report.packets_flushed,
+                                unacked = report.unacked,
+                                timed_out = report.timed_out,
+                                "runtime drained"
+                            );
+                            let _ = reply.send(report);
                         }
                         ControlMessage::Shutdown => return,
                     }
@@ -321,6 +591,19 @@ async fn runtime_worker_async(
         if last_fast_stats.elapsed() >= fast_stats_interval {
             scheduler.refresh_metrics();
             let all_metrics = scheduler.get_all_metrics();
+            if !replicas.is_empty() {
+                let mut snapshot = HashMap::new();
+                for replica in &mut replicas {
+                    replica.refresh(&all_metrics);
+                    snapshot.insert(
+                        replica.config.name.clone(),
+                        replica.scheduler.get_all_metrics(),
+                    );
+                }
+                if let Ok(mut m) = replica_metrics.lock() {
+                    *m = snapshot;
+                }
+            }
             if let Ok(mut m) = metrics.lock() {
                 *m = all_metrics;
             }
@@ -334,45 +617,185 @@ async fn runtime_worker_async(
     }
 }
 
+/// Times [`BondingRuntime::drain`] sends the session TEARDOWN on each
+/// link, [`TEARDOWN_SPACING`] apart.
+const TEARDOWN_REPEATS: u32 = 3;
+const TEARDOWN_SPACING: Duration = Duration::from_millis(20);
+
 fn apply_config(
     scheduler: &mut BondingScheduler<dyn LinkSender>,
     current_links: &mut HashMap<usize, LinkConfig>,
     config: BondingConfig,
+    ingest_key: Option<&[u8]>,
 ) {
     // Only reconcile links if the config explicitly defines them.
     // An empty links list means "don't touch existing links" — this allows
     // scheduler-only config updates without removing pad-configured links.
     if !config.links.is_empty() {
-        let desired_ids: std::collections::HashSet<usize> =
-            config.links.iter().map(|l| l.id).collect();
-
-        // Remove links no longer present in config
-        let existing_ids: Vec<usize> = current_links.keys().copied().collect();
-        for id in existing_ids {
-            if !desired_ids.contains(&id) {
-                scheduler.remove_link(id);
-                current_links.remove(&id);
+        reconcile_links(scheduler, current_links, config.links, ingest_key);
+    }
+}
+
+/// Bring a scheduler's links in line with `links`, keeping the sessions
+/// of links that are unchanged or only had their rate cap changed.
+fn reconcile_links(
+    scheduler: &mut BondingScheduler<dyn LinkSender>,
+    current_links: &mut HashMap<usize, LinkConfig>,
+    links: Vec<LinkConfig>,
+    ingest_key: Option<&[u8]>,
+) {
+    let desired_ids: std::collections::HashSet<usize> = links.iter().map(|l| l.id).collect();
+
+    // Remove links no longer present in config
+    let existing_ids: Vec<usize> = current_links.keys().copied().collect();
+    for id in existing_ids {
+        if !desired_ids.contains(&id) {
+            scheduler.remove_link(id);
+            current_links.remove(&id);
+        }
+    }
+
+    // Add or update links that changed
+    for link in links {
+        match current_links.get(&link.id) {
+            Some(existing) if existing == &link => {}
+            // Only the rate cap changed: retune it in place rather than
+            // tearing down the link's session.
+            Some(existing)
+                if *existing
+                    == (LinkConfig {
+                        rate_cap_bps: existing.rate_cap_bps,
+                        ..link.clone()
+                    }) =>
+            {
+                scheduler.set_link_rate_cap(link.id, link.rate_cap_bps);
+                current_links.insert(link.id, link);
             }
+            _ => apply_link(scheduler, current_links, link, ingest_key),
         }
+    }
+}
 
-        // Add or update links that changed
-        for link in config.links {
-            let needs_update = match current_links.get(&link.id) {
-                Some(existing) => existing != &link,
-                None => true,
-            };
+/// Reconcile the running replicas against `desired`: replicas no longer
+/// configured are torn down, new ones start with their own scheduler, and
+/// the links of the rest are reconciled in place so unchanged sessions
+/// keep their ARQ and FEC state.
+fn apply_replicas(
+    replicas: &mut Vec<Replica>,
+    desired: Vec<ReplicaConfig>,
+    scheduler_config: &SchedulerConfig,
+    ingest_key: Option<&[u8]>,
+) {
+    replicas.retain(|replica| {
+        let keep = desired.iter().any(|d| d.name == replica.config.name);
+        if !keep {
+            replica.scheduler.send_teardown();
+        }
+        keep
+    });
+    for config in desired {
+        let idx = match replicas.iter().position(|r| r.config.name == config.name) {
+            Some(idx) => {
+                replicas[idx]
+                    .scheduler
+                    .update_config(scheduler_config.clone());
+                idx
+            }
+            None => {
+                let scheduler = BondingScheduler::with_config(scheduler_config.clone());
+                scheduler.set_ingest_key(ingest_key);
+                replicas.push(Replica {
+                    config: config.clone(),
+                    scheduler,
+                    links: HashMap::new(),
+                    caps: HashMap::new(),
+                });
+                replicas.len() - 1
+            }
+        };
+        let replica = &mut replicas[idx];
+        reconcile_links(
+            &mut replica.scheduler,
+            &mut replica.links,
+            config.links.clone(),
+            ingest_key,
+        );
+        // Links were recreated with their configured caps; reapply the
+        // weighted ones on the next refresh.
+        replica.caps.clear();
+        replica.config = config;
+    }
+}
 
-            if needs_update {
-                apply_link(scheduler, current_links, link);
+/// Hand a copy of a packet to every replica. A replica over its weighted
+/// share sheds packets on its own; that never holds up the primary.
+fn send_to_replicas(replicas: &mut [Replica], data: &Bytes, profile: PacketProfile) {
+    for replica in replicas {
+        if let Err(e) = replica.scheduler.send(data.clone(), profile) {
+            tracing::debug!(
+                target: "strata::runtime",
+                replica = %replica.config.name,
+                error = %e,
+                "replica send failed"
+            );
+        }
+    }
+}
+
+impl Replica {
+    /// Refresh the replica's link metrics and retune each link's rate cap
+    /// to the replica's weighted share of the primary link it rides on.
+    fn refresh(&mut self, primary: &HashMap<usize, LinkMetrics>) {
+        self.scheduler.refresh_metrics();
+        for (&id, link) in &self.links {
+            let capacity_bps = primary.get(&id).map_or(0.0, |m| m.capacity_bps);
+            let current = self.caps.get(&id).copied().flatten();
+            let cap =
+                replica_rate_cap(link.rate_cap_bps, capacity_bps, self.config.weight, current);
+            if self.caps.get(&id) != Some(&cap) {
+                self.scheduler.set_link_rate_cap(id, cap);
+                self.caps.insert(id, cap);
             }
         }
     }
 }
 
+/// Fraction a weighted cap may drift from its target before it is
+/// retuned; a rate change restarts the token bucket, so capacity jitter
+/// alone shouldn't.
+const REPLICA_CAP_TOLERANCE: f64 = 0.1;
+
+/// A replica link's rate cap: its configured cap, tightened to `weight`
+/// of the primary link's measured capacity. A full-weight replica, or one
+/// whose primary link hasn't measured a capacity yet, keeps only the
+/// configured cap. `current` is returned as-is while it is within
+/// [`REPLICA_CAP_TOLERANCE`] of the target.
+fn replica_rate_cap(
+    configured: Option<u64>,
+    primary_capacity_bps: f64,
+    weight: f64,
+    current: Option<u64>,
+) -> Option<u64> {
+    if weight >= 1.0 || primary_capacity_bps <= 0.0 {
+        return configured;
+    }
+    let share = (primary_capacity_bps * weight) as u64;
+    let target = configured.map_or(share, |c| c.min(share)).max(1);
+    match current {
+        Some(cur)
+            if (cur as f64 - target as f64).abs() <= target as f64 * REPLICA_CAP_TOLERANCE =>
+        {
+            Some(cur)
+        }
+        _ => Some(target),
+    }
+}
+
 fn apply_link(
     scheduler: &mut BondingScheduler<dyn LinkSender>,
     current_links: &mut HashMap<usize, LinkConfig>,
     link: LinkConfig,
+    ingest_key: Option<&[u8]>,
 ) {
     scheduler.remove_link(link.id);
 
@@ -381,7 +804,9 @@ fn apply_link(
             // Apply the per-link path-regime override (F6). `None` keeps
             // auto-inference; only metrics labelling is affected.
             tl.set_profile(link.profile.as_deref());
+            tl.set_ingest_key(ingest_key);
             scheduler.add_link(Arc::new(tl) as Arc<dyn LinkSender>);
+            scheduler.set_link_rate_cap(link.id, link.rate_cap_bps);
             current_links.insert(link.id, link);
         }
         Err(err) => {
@@ -579,6 +1004,20 @@ mod tests {
         rt.shutdown();
     }
 
+    #[test]
+    fn apply_config_refuses_an_over_length_ingest_key() {
+        let mut rt = BondingRuntime::new();
+        let too_long = "k".repeat(strata_transport::wire::MAX_INGEST_KEY_LEN + 1);
+        let err = rt
+            .apply_config(BondingConfig {
+                ingest_key: Some(too_long),
+                ..BondingConfig::default()
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("max 255"), "{err}");
+        rt.shutdown();
+    }
+
     #[test]
     fn try_send_packet_disconnected_after_shutdown() {
         let mut rt = BondingRuntime::new();
@@ -605,12 +1044,27 @@ mod tests {
                     break;
                 }
                 Ok(_) => continue,
-                Err(PacketSendError::Disconnected) => break,
+                Err(PacketSendError::Disconnected | PacketSendError::Draining) => break,
             }
         }
         assert!(got_full, "Channel should report Full when saturated");
     }
 
+    #[test]
+    fn drain_reports_and_rejects_new_packets() {
+        let mut rt = BondingRuntime::new();
+        rt.try_send_packet(Bytes::from_static(b"tail"), PacketProfile::default())
+            .unwrap();
+        let report = rt.drain(Duration::from_millis(200)).unwrap();
+        assert!(!report.timed_out, "nothing in flight without links");
+        assert_eq!(report.unacked, 0);
+        let err = rt
+            .try_send_packet(Bytes::from_static(b"late"), PacketProfile::default())
+            .unwrap_err();
+        assert!(matches!(err, PacketSendError::Draining));
+        rt.shutdown();
+    }
+
     #[test]
     fn add_link_via_message() {
         let rt = BondingRuntime::new();
@@ -619,6 +1073,7 @@ mod tests {
             uri: "127.0.0.1:19100".to_string(),
             interface: None,
             profile: None,
+            rate_cap_bps: None,
         };
         assert!(rt.add_link(link).is_ok());
         thread::sleep(Duration::from_millis(250));
@@ -626,6 +1081,27 @@ mod tests {
         assert!(metrics.contains_key(&1), "Link 1 should appear in metrics");
     }
 
+    #[test]
+    fn subscribers_see_link_events() {
+        let rt = BondingRuntime::new();
+        let events = rt.subscribe_events();
+        let link = LinkConfig {
+            id: 1,
+            uri: "127.0.0.1:19104".to_string(),
+            interface: None,
+            profile: None,
+            rate_cap_bps: None,
+        };
+        rt.add_link(link).unwrap();
+        let first = events
+            .recv_timeout(Duration::from_secs(1))
+            .expect("link added event");
+        assert_eq!(
+            first.event,
+            crate::events::BondingEvent::LinkAdded { link_id: 1 }
+        );
+    }
+
     #[test]
     fn remove_link_via_message() {
         let rt = BondingRuntime::new();
@@ -634,6 +1110,7 @@ mod tests {
             uri: "127.0.0.1:19101".to_string(),
             interface: None,
             profile: None,
+            rate_cap_bps: None,
         };
         rt.add_link(link).unwrap();
         thread::sleep(Duration::from_millis(250));
@@ -657,12 +1134,14 @@ mod tests {
                     uri: "127.0.0.1:19102".to_string(),
                     interface: None,
                     profile: None,
+                    rate_cap_bps: None,
                 },
                 LinkConfig {
                     id: 2,
                     uri: "127.0.0.1:19103".to_string(),
                     interface: None,
                     profile: None,
+                    rate_cap_bps: None,
                 },
             ],
             ..BondingConfig::default()
@@ -679,6 +1158,7 @@ mod tests {
                 uri: "127.0.0.1:19103".to_string(),
                 interface: None,
                 profile: None,
+                rate_cap_bps: None,
             }],
             ..BondingConfig::default()
         };
@@ -692,6 +1172,35 @@ mod tests {
         assert!(m.contains_key(&2), "Link 2 should still exist");
     }
 
+    #[test]
+    fn rate_cap_change_keeps_the_link() {
+        let rt = BondingRuntime::new();
+        let events = rt.subscribe_events();
+        let config = |cap| BondingConfig {
+            links: vec![LinkConfig {
+                id: 1,
+                uri: "127.0.0.1:19105".to_string(),
+                interface: None,
+                profile: None,
+                rate_cap_bps: cap,
+            }],
+            ..BondingConfig::default()
+        };
+        rt.apply_config(config(None)).unwrap();
+        rt.apply_config(config(Some(1_000_000))).unwrap();
+        rt.apply_config(config(None)).unwrap();
+        thread::sleep(Duration::from_millis(350));
+
+        let removed = events.try_iter().any(|r| {
+            matches!(
+                r.event,
+                crate::events::BondingEvent::LinkRemoved { link_id: 1 }
+            )
+        });
+        assert!(!removed, "a cap change must not recreate the link");
+        assert!(rt.get_metrics().contains_key(&1));
+    }
+
     #[test]
     fn shutdown_is_idempotent() {
         let mut rt = BondingRuntime::new();
@@ -767,6 +1276,7 @@ mod tests {
             uri: "127.0.0.1:19200".to_string(),
             interface: None,
             profile: None,
+            rate_cap_bps: None,
         };
         assert!(rt.add_link(link).is_ok());
         thread::sleep(Duration::from_millis(250));
@@ -790,6 +1300,7 @@ mod tests {
             uri: format!("{}", rcv_addr),
             interface: None,
             profile: None,
+            rate_cap_bps: None,
         };
         rt.add_link(link).unwrap();
         thread::sleep(Duration::from_millis(200));
@@ -809,6 +1320,71 @@ mod tests {
         assert!(result.unwrap() > 0, "Should have received non-empty data");
     }
 
+    #[test]
+    fn replica_gets_its_own_copy_of_every_packet() {
+        let mut rt = BondingRuntime::new();
+        let primary = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let backup = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let link = |uri: String| LinkConfig {
+            id: 1,
+            uri,
+            interface: None,
+            profile: None,
+            rate_cap_bps: None,
+        };
+        rt.apply_config(BondingConfig {
+            links: vec![link(primary.local_addr().unwrap().to_string())],
+            replicas: vec![ReplicaConfig {
+                name: "backup".into(),
+                weight: 1.0,
+                links: vec![link(backup.local_addr().unwrap().to_string())],
+            }],
+            ..BondingConfig::default()
+        })
+        .unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        rt.try_send_packet(Bytes::from_static(b"replicated"), PacketProfile::default())
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        for sock in [&primary, &backup] {
+            sock.set_read_timeout(Some(Duration::from_millis(500)))
+                .unwrap();
+            let mut buf = [0u8; 4096];
+            assert!(sock.recv(&mut buf).is_ok_and(|n| n > 0));
+        }
+        let replicas = rt.get_replica_metrics();
+        assert!(replicas["backup"].contains_key(&1));
+        assert!(rt.get_metrics().contains_key(&1));
+    }
+
+    #[test]
+    fn replica_rate_cap_follows_the_weighted_share() {
+        // Full weight, or no measurement yet: only the configured cap.
+        assert_eq!(replica_rate_cap(None, 8e6, 1.0, None), None);
+        assert_eq!(
+            replica_rate_cap(Some(3_000_000), 0.0, 0.5, None),
+            Some(3_000_000)
+        );
+
+        assert_eq!(replica_rate_cap(None, 8e6, 0.5, None), Some(4_000_000));
+        assert_eq!(
+            replica_rate_cap(Some(3_000_000), 8e6, 0.5, None),
+            Some(3_000_000)
+        );
+
+        // Capacity jitter within tolerance keeps the current cap.
+        assert_eq!(
+            replica_rate_cap(None, 8.4e6, 0.5, Some(4_000_000)),
+            Some(4_000_000)
+        );
+        assert_eq!(
+            replica_rate_cap(None, 12e6, 0.5, Some(4_000_000)),
+            Some(6_000_000)
+        );
+    }
+
     // ── Regression: snag #6 — SO_BINDTODEVICE must hard-error on EPERM ──
 
     /// Binding to a non-existent interface should return Err, not Ok.
@@ -822,6 +1398,7 @@ mod tests {
             uri: "127.0.0.1:9999".to_string(),
             interface: Some("nonexistent_if_xyz".to_string()),
             profile: None,
+            rate_cap_bps: None,
         };
         let result = create_transport_link(&link);
         assert!(
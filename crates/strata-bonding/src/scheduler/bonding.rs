@@ -1,4 +1,5 @@
 use crate::config::SchedulerConfig;
+use crate::events::{BondingEvent, EventLog, FailoverCause};
 use crate::media::priority::{DegradationStage, Treatment};
 use crate::net::interface::LinkSender;
 use crate::scheduler::blest::BlestGuard;
@@ -35,6 +36,34 @@ const FAILOVER_BROADCAST_COOLDOWN: Duration = Duration::from_millis(500);
 /// reacting quickly to a real handover/route change.
 const RTT_SPIKE_SUSTAIN_TICKS: u32 = 2;
 
+/// Duplicate-staggering counters, shared with whoever reports them (the
+/// runtime hands the same instance to the stats thread).
+#[derive(Debug, Default)]
+pub struct SpreadStats {
+    /// Configured spread in microseconds, mirrored from
+    /// [`SchedulerConfig::duplicate_spread_ms`].
+    pub spread_us: AtomicU64,
+    /// Copies held back and sent after their first sibling, total.
+    pub staggered_copies: AtomicU64,
+}
+
+impl SpreadStats {
+    /// Configured spread in milliseconds.
+    pub fn spread_ms(&self) -> f64 {
+        self.spread_us.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+}
+
+/// A broadcast or redundant copy held back until `due` so it doesn't hit a
+/// shared bottleneck in the same instant as its siblings.
+struct StaggeredCopy<L: ?Sized> {
+    due: Instant,
+    link: Arc<L>,
+    packet: Bytes,
+    priority: Priority,
+    len: u64,
+}
+
 /// Top-level bonding packet scheduler.
 ///
 /// Uses an **Earliest Delivery Path First (EDPF)** scheduler with
@@ -45,6 +74,7 @@ const RTT_SPIKE_SUSTAIN_TICKS: u32 = 2;
 /// - Critical packet broadcast (keyframes sent to all alive links)
 /// - Fast-failover mode (broadcasts all traffic on link instability)
 /// - Adaptive redundancy (duplicates important packets when spare capacity allows)
+/// - Duplicate staggering (spreads the copies above over `duplicate_spread_ms`)
 /// - Escalating dead-link logging
 ///
 /// **Scheduling pipeline** (for standard, non-broadcast packets):
@@ -87,12 +117,28 @@ pub struct BondingScheduler<L: LinkSender + ?Sized + 'static> {
     /// `failover_rtt_spike_factor` × its previous smoothed value. Reset to 0
     /// the moment a tick isn't a spike. See `RTT_SPIKE_SUSTAIN_TICKS`.
     rtt_spike_streak: HashMap<usize, u32>,
+    /// What tripped the most recent failover trigger, for its event.
+    failover_cause: Option<(usize, FailoverCause)>,
+
+    // ─── Events ─────────────────────────────────────────────────────
+    events: EventLog,
+    /// Per-link state as last reported in events: (alive, phase, blacklisted).
+    event_links: HashMap<usize, (bool, crate::net::interface::LinkPhase, bool)>,
+    prev_failover_active: bool,
+    /// Last keyframe request id reported; the receiver repeats each request
+    /// on every link.
+    last_keyframe_request: Option<u32>,
 
     /// Counter for consecutive all-links-dead failures (for escalation)
     consecutive_dead_count: u64,
     /// Total packets dropped due to all links being dead
     pub total_dead_drops: Arc<AtomicU64>,
 
+    // ─── Duplicate staggering ───────────────────────────────────────
+    /// Copies waiting out their jitter offset (see [`Self::flush_staggered`]).
+    staggered: Vec<StaggeredCopy<L>>,
+    spread_stats: Arc<SpreadStats>,
+
     // ─── Phase-shifted probe coordination ───
     /// ID of the link currently holding the BBR probe token.
     ///
@@ -170,6 +216,10 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
     /// Creates a scheduler with the given configuration.
     pub fn with_config(config: SchedulerConfig) -> Self {
         let now = Instant::now();
+        let spread_stats = Arc::new(SpreadStats::default());
+        spread_stats
+            .spread_us
+            .store(spread_us(&config), Ordering::Relaxed);
         Self {
             scheduler: Edpf::with_config(config),
             next_seq: 0,
@@ -183,8 +233,15 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
             prev_phases: HashMap::new(),
             prev_rtts: HashMap::new(),
             rtt_spike_streak: HashMap::new(),
+            failover_cause: None,
+            events: EventLog::new(),
+            event_links: HashMap::new(),
+            prev_failover_active: false,
+            last_keyframe_request: None,
             consecutive_dead_count: 0,
             total_dead_drops: Arc::new(AtomicU64::new(0)),
+            staggered: Vec::new(),
+            spread_stats,
             probe_owner: None,
             last_probe_rotation: now,
             saturation_probe_link: None,
@@ -214,6 +271,17 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
 
     /// Replaces the scheduler configuration at runtime.
     pub fn update_config(&mut self, config: SchedulerConfig) {
+        self.spread_stats
+            .spread_us
+            .store(spread_us(&config), Ordering::Relaxed);
+        self.scheduler.update_config(config);
+    }
+
+    /// Replaces the per-link scheduling weights, leaving the rest of the
+    /// configuration alone. See [`SchedulerConfig::link_weight`].
+    pub fn set_link_weights(&mut self, weights: Vec<f64>) {
+        let mut config = self.scheduler.config().clone();
+        config.link_weights = weights;
         self.scheduler.update_config(config);
     }
 
@@ -234,6 +302,68 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
         }
     }
 
+    /// Hand every link the per-stream ingest key it presents to the
+    /// receiver (`None` = no key).
+    pub fn set_ingest_key(&self, key: Option<&[u8]>) {
+        for id in self.scheduler.link_ids() {
+            if let Some(link) = self.scheduler.get_link(id) {
+                link.set_ingest_key(key);
+            }
+        }
+    }
+
+    /// Flush every link's partial FEC generation (see
+    /// [`LinkSender::flush_fec`]).
+    pub fn flush_fec(&self) {
+        for id in self.scheduler.link_ids() {
+            if let Some(link) = self.scheduler.get_link(id) {
+                link.flush_fec();
+            }
+        }
+    }
+
+    /// Unacknowledged packets summed over all links.
+    pub fn in_flight(&self) -> usize {
+        self.scheduler
+            .link_ids()
+            .into_iter()
+            .filter_map(|id| self.scheduler.get_link(id))
+            .map(|link| link.in_flight())
+            .sum()
+    }
+
+    /// Send a session TEARDOWN on every link.
+    pub fn send_teardown(&self) {
+        for id in self.scheduler.link_ids() {
+            if let Some(link) = self.scheduler.get_link(id) {
+                link.send_teardown();
+            }
+        }
+    }
+
+    /// The log this scheduler emits [`BondingEvent`]s into.
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
+    /// Emit into `log` instead, e.g. one shared with a runtime handle.
+    pub fn set_event_log(&mut self, log: EventLog) {
+        self.events = log;
+    }
+
+    /// Duplicate-staggering counters for this scheduler.
+    pub fn spread_stats(&self) -> &Arc<SpreadStats> {
+        &self.spread_stats
+    }
+
+    /// Count into `stats` instead, e.g. one shared with a runtime handle.
+    pub fn set_spread_stats(&mut self, stats: Arc<SpreadStats>) {
+        stats
+            .spread_us
+            .store(spread_us(self.config()), Ordering::Relaxed);
+        self.spread_stats = stats;
+    }
+
     /// Returns the current degradation stage.
     pub fn degradation_stage(&self) -> DegradationStage {
         self.degradation_stage
@@ -251,14 +381,33 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
         self.blest.update_link_owd(id, DEFAULT_OWD_SEED_S);
         self.kalman_rtt
             .insert(id, KalmanFilter::new(&KalmanConfig::for_rtt()));
+        self.events.emit(BondingEvent::LinkAdded { link_id: id });
     }
 
     /// Removes a link by ID, stopping all traffic to it.
     pub fn remove_link(&mut self, id: usize) {
+        let existed = self.scheduler.get_link(id).is_some();
         self.scheduler.remove_link(id);
         self.iods.remove_link(id);
         self.blest.remove_link(id);
         self.kalman_rtt.remove(&id);
+        self.staggered.retain(|copy| copy.link.id() != id);
+        if self
+            .event_links
+            .remove(&id)
+            .is_some_and(|(alive, ..)| alive)
+        {
+            self.events.emit(BondingEvent::LinkDown { link_id: id });
+        }
+        if existed {
+            self.events.emit(BondingEvent::LinkRemoved { link_id: id });
+        }
+    }
+
+    /// Caps a link's send rate at `bps` (`None` lifts the cap). Takes effect
+    /// on the next packet; the link itself is left untouched.
+    pub fn set_link_rate_cap(&mut self, id: usize, bps: Option<u64>) {
+        self.scheduler.set_rate_cap(id, bps);
     }
 
     /// Refreshes link metrics from all links, feeds intelligence overlays,
@@ -302,6 +451,85 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
         self.drive_ppd_probes(&metrics);
 
         self.check_failover_conditions();
+        self.emit_events(&metrics);
+    }
+
+    /// Diff link and failover state against what was last reported and
+    /// emit an event per transition, links first so a failover follows the
+    /// link change that caused it.
+    fn emit_events(&mut self, metrics: &[(usize, crate::net::interface::LinkMetrics)]) {
+        let mut metrics: Vec<_> = metrics.iter().collect();
+        metrics.sort_by_key(|(id, _)| *id);
+        for (id, m) in metrics {
+            let link_id = *id;
+            let blacklisted = self.scheduler.is_temporarily_avoided(link_id);
+            let Some(prev) = self
+                .event_links
+                .insert(link_id, (m.alive, m.phase, blacklisted))
+            else {
+                if m.alive {
+                    self.events.emit(BondingEvent::LinkUp { link_id });
+                }
+                continue;
+            };
+            let (was_alive, prev_phase, was_blacklisted) = prev;
+            if prev_phase != m.phase {
+                self.events.emit(BondingEvent::PhaseChanged {
+                    link_id,
+                    from: prev_phase,
+                    to: m.phase,
+                });
+            }
+            if was_alive != m.alive {
+                self.events.emit(if m.alive {
+                    BondingEvent::LinkUp { link_id }
+                } else {
+                    BondingEvent::LinkDown { link_id }
+                });
+            }
+            if was_blacklisted != blacklisted {
+                self.events.emit(if blacklisted {
+                    BondingEvent::LinkBlacklisted { link_id }
+                } else {
+                    BondingEvent::LinkUnblacklisted { link_id }
+                });
+            }
+        }
+
+        // Keyframe requests are one-shot, so they are drained from the links
+        // rather than diffed from metrics. Every link carries a copy; only
+        // the first to arrive is reported.
+        let mut link_ids = self.scheduler.link_ids();
+        link_ids.sort_unstable();
+        for link_id in link_ids {
+            let Some(request) = self
+                .scheduler
+                .get_link(link_id)
+                .and_then(|link| link.take_keyframe_request())
+            else {
+                continue;
+            };
+            if self.last_keyframe_request.replace(request.request_id) != Some(request.request_id) {
+                self.events.emit(BondingEvent::KeyframeRequested {
+                    link_id,
+                    request_id: request.request_id,
+                    lost_packets: request.lost_packets,
+                });
+            }
+        }
+
+        let failover_active = self.in_failover_mode();
+        if failover_active != self.prev_failover_active {
+            self.prev_failover_active = failover_active;
+            match (failover_active, self.failover_cause.take()) {
+                (true, Some((link_id, cause))) => {
+                    self.events
+                        .emit(BondingEvent::FailoverEntered { link_id, cause });
+                }
+                (false, _) => self.events.emit(BondingEvent::FailoverExited),
+                (true, None) => {}
+            }
+        }
     }
 
     /// Rotates the BBR probe token across alive links once per second.
@@ -811,7 +1039,7 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
         }
 
         let metrics = self.scheduler.get_active_links();
-        let mut trigger_failover = false;
+        let mut trigger: Option<(usize, FailoverCause)> = None;
         let rtt_spike_factor = self.scheduler.config().failover_rtt_spike_factor;
 
         for (id, m) in &metrics {
@@ -824,7 +1052,7 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
                         | (_, LinkPhase::Reset)
                 );
                 if degraded {
-                    trigger_failover = true;
+                    trigger.get_or_insert((*id, FailoverCause::PhaseDegraded));
                 }
             }
 
@@ -850,13 +1078,16 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
                 self.prev_rtts.insert(*id, m.rtt_ms);
             }
             if *streak >= RTT_SPIKE_SUSTAIN_TICKS {
-                trigger_failover = true;
+                trigger.get_or_insert((*id, FailoverCause::RttSpike));
             }
 
             self.prev_phases.insert(*id, m.phase);
         }
 
-        if trigger_failover {
+        if let Some(cause) = trigger {
+            if !self.in_failover_mode() {
+                self.failover_cause = Some(cause);
+            }
             let failover_duration =
                 Duration::from_millis(self.scheduler.config().failover_duration_ms);
             let now = Instant::now();
@@ -988,6 +1219,71 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
         self.scheduler.get_active_links().into_iter().collect()
     }
 
+    /// Sends every staggered duplicate whose offset has elapsed. [`Self::send`]
+    /// calls this first; the runtime also calls it on each loop iteration so
+    /// late copies go out on time between packets.
+    pub fn flush_staggered(&mut self) {
+        if self.staggered.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.staggered)
+            .into_iter()
+            .partition(|copy| copy.due <= now);
+        self.staggered = pending;
+        for copy in due {
+            self.send_copy(&copy.link, &copy.packet, copy.priority, copy.len);
+        }
+    }
+
+    /// Sends every staggered duplicate now, due or not. Used when draining.
+    pub fn release_staggered(&mut self) {
+        for copy in std::mem::take(&mut self.staggered) {
+            self.send_copy(&copy.link, &copy.packet, copy.priority, copy.len);
+        }
+    }
+
+    /// Sends one wrapped packet to each of `links`. The first copy goes out
+    /// now. With a nonzero `duplicate_spread_ms` the rest are held back by a
+    /// stratified random offset: copy `i` lands somewhere in the `i`-th
+    /// slice of the spread, so siblings neither bunch up on an unlucky draw
+    /// nor settle into a fixed, correlated pattern.
+    fn send_copies(&mut self, links: Vec<Arc<L>>, wrapped: Bytes, priority: Priority, len: u64) {
+        let spread_ms = self.scheduler.config().duplicate_spread_ms;
+        let slices = links.len().saturating_sub(1) as f64;
+        let now = Instant::now();
+        for (i, link) in links.into_iter().enumerate() {
+            if i == 0 || spread_ms <= 0.0 {
+                self.send_copy(&link, &wrapped, priority, len);
+                continue;
+            }
+            let slot = (i - 1) as f64 + rand::random::<f64>();
+            let offset = Duration::from_secs_f64(spread_ms * slot / slices / 1000.0);
+            self.staggered.push(StaggeredCopy {
+                due: now + offset,
+                link,
+                packet: wrapped.clone(),
+                priority,
+                len,
+            });
+            self.spread_stats
+                .staggered_copies
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn send_copy(&mut self, link: &L, packet: &[u8], priority: Priority, len: u64) {
+        match link.send_prioritized(packet, priority) {
+            Ok(_) => {
+                self.scheduler.record_send(link.id(), len);
+            }
+            Err(e) => {
+                self.scheduler.record_send_failed(link.id(), len);
+                tracing::debug!(link_id = link.id(), error = %e, "duplicate send failed");
+            }
+        }
+    }
+
     /// Schedules a packet for transmission across the bonded links.
     ///
     /// Routing decision depends on the packet profile and current link state:
@@ -995,6 +1291,7 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
     /// 2. **Redundancy** — spare capacity available → duplicated to N best links
     /// 3. **Standard** — EDPF selects the best single link (lowest predicted arrival)
     pub fn send(&mut self, payload: Bytes, profile: crate::scheduler::PacketProfile) -> Result<()> {
+        self.flush_staggered();
         let packet_len = payload.len();
         let config = self.scheduler.config();
 
@@ -1058,18 +1355,7 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
             let header = crate::protocol::header::BondingHeader::new(seq);
             let wrapped = header.wrap(payload);
 
-            for link in links {
-                match link.send_prioritized(&wrapped, wire_priority) {
-                    Ok(_) => {
-                        self.scheduler.record_send(link.id(), packet_len as u64);
-                    }
-                    Err(e) => {
-                        self.scheduler
-                            .record_send_failed(link.id(), packet_len as u64);
-                        tracing::debug!(link_id = link.id(), error = %e, "broadcast send failed");
-                    }
-                }
-            }
+            self.send_copies(links, wrapped, wire_priority, packet_len as u64);
             return Ok(());
         }
 
@@ -1112,17 +1398,7 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
                     let header = crate::protocol::header::BondingHeader::new(seq);
                     let wrapped = header.wrap(payload);
 
-                    for link in links {
-                        match link.send_prioritized(&wrapped, wire_priority) {
-                            Ok(_) => {
-                                self.scheduler.record_send(link.id(), packet_len as u64);
-                            }
-                            Err(_) => {
-                                self.scheduler
-                                    .record_send_failed(link.id(), packet_len as u64);
-                            }
-                        }
-                    }
+                    self.send_copies(links, wrapped, wire_priority, packet_len as u64);
                     return Ok(());
                 }
                 // Fall through to standard if duplication failed
@@ -1150,9 +1426,11 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
                     // BBR Probing Starvation Fix:
                     // If another link holds the probe token, send a duplicate of this packet
                     // to that link to provide useful redundant data for probing, instead of
-                    // relying on dummy packets.
+                    // relying on dummy packets. Rate-capped links are skipped:
+                    // these duplicates bypass EDPF and would slip past the cap.
                     if let Some(probe_id) = self.probe_owner
                         && probe_id != link_id
+                        && self.scheduler.rate_cap(probe_id).is_none()
                         && let Some(probe_link) = self.scheduler.get_link(probe_id)
                         && let Some(state) = self
                             .scheduler
@@ -1236,6 +1514,11 @@ impl<L: LinkSender + ?Sized + 'static> BondingScheduler<L> {
     }
 }
 
+/// [`SchedulerConfig::duplicate_spread_ms`] in whole microseconds.
+fn spread_us(config: &SchedulerConfig) -> u64 {
+    (config.duplicate_spread_ms * 1000.0).round() as u64
+}
+
 impl<L: LinkSender + ?Sized> Default for BondingScheduler<L> {
     fn default() -> Self {
         Self::new()
@@ -1256,6 +1539,7 @@ mod tests {
         sent_priorities: Mutex<Vec<Priority>>,
         ppd_probe_count: AtomicUsize,
         broadcast_active_calls: Mutex<Vec<bool>>,
+        keyframe_request: Mutex<Option<strata_transport::wire::KeyframeRequestPacket>>,
     }
 
     impl MockLink {
@@ -1283,6 +1567,8 @@ mod tests {
                     ack_bytes: 0,
                     estimated_capacity_bps: 0.0,
                     owd_ms: 0.0,
+                    clock_offset_us: None,
+                    clock_drift_ppm: None,
                     receiver_report: None,
                     probe_active: false,
                     inferred_regime: None,
@@ -1295,6 +1581,7 @@ mod tests {
                 sent_priorities: Mutex::new(Vec::new()),
                 ppd_probe_count: AtomicUsize::new(0),
                 broadcast_active_calls: Mutex::new(Vec::new()),
+                keyframe_request: Mutex::new(None),
             }
         }
 
@@ -1332,6 +1619,9 @@ mod tests {
         fn set_failover_broadcast_active(&self, active: bool) {
             self.broadcast_active_calls.lock().unwrap().push(active);
         }
+        fn take_keyframe_request(&self) -> Option<strata_transport::wire::KeyframeRequestPacket> {
+            self.keyframe_request.lock().unwrap().take()
+        }
     }
 
     #[test]
@@ -1465,6 +1755,86 @@ mod tests {
         assert_eq!(l2.sent_packets.lock().unwrap().len(), 1);
     }
 
+    #[test]
+    fn broadcast_copies_are_staggered_across_the_spread() {
+        let mut scheduler = BondingScheduler::with_config(SchedulerConfig {
+            critical_broadcast: true,
+            duplicate_spread_ms: 5.0,
+            ..SchedulerConfig::default()
+        });
+        let links: Vec<_> = (1..=3)
+            .map(|id| Arc::new(MockLink::new(id, 10_000_000.0, 10.0)))
+            .collect();
+        for link in &links {
+            scheduler.add_link(link.clone());
+        }
+        let sent = |links: &[Arc<MockLink>]| -> usize {
+            links
+                .iter()
+                .map(|l| l.sent_packets.lock().unwrap().len())
+                .sum()
+        };
+
+        let payload = Bytes::from_static(b"Critical");
+        let profile = crate::scheduler::PacketProfile {
+            is_critical: true,
+            can_drop: false,
+            size_bytes: payload.len(),
+        };
+        scheduler.send(payload, profile).unwrap();
+
+        // One copy goes out at once; the other two wait out their offsets.
+        assert_eq!(sent(&links), 1);
+        assert_eq!(scheduler.spread_stats().spread_ms(), 5.0);
+        assert_eq!(
+            scheduler
+                .spread_stats()
+                .staggered_copies
+                .load(Ordering::Relaxed),
+            2
+        );
+
+        std::thread::sleep(Duration::from_millis(6));
+        scheduler.flush_staggered();
+        for link in &links {
+            assert_eq!(link.sent_packets.lock().unwrap().len(), 1);
+            assert_eq!(
+                link.sent_priorities.lock().unwrap().as_slice(),
+                &[Priority::Critical]
+            );
+        }
+    }
+
+    #[test]
+    fn zero_spread_sends_copies_back_to_back() {
+        let mut scheduler = BondingScheduler::with_config(SchedulerConfig {
+            critical_broadcast: true,
+            ..SchedulerConfig::default()
+        });
+        let l1 = Arc::new(MockLink::new(1, 10_000_000.0, 10.0));
+        let l2 = Arc::new(MockLink::new(2, 10_000_000.0, 10.0));
+        scheduler.add_link(l1.clone());
+        scheduler.add_link(l2.clone());
+
+        let payload = Bytes::from_static(b"Critical");
+        let profile = crate::scheduler::PacketProfile {
+            is_critical: true,
+            can_drop: false,
+            size_bytes: payload.len(),
+        };
+        scheduler.send(payload, profile).unwrap();
+
+        assert_eq!(l1.sent_packets.lock().unwrap().len(), 1);
+        assert_eq!(l2.sent_packets.lock().unwrap().len(), 1);
+        assert_eq!(
+            scheduler
+                .spread_stats()
+                .staggered_copies
+                .load(Ordering::Relaxed),
+            0
+        );
+    }
+
     #[test]
     fn test_fast_failover_triggers_on_phase_degradation() {
         let mut scheduler = BondingScheduler::new();
@@ -1501,6 +1871,89 @@ mod tests {
         assert_eq!(l2.sent_packets.lock().unwrap().len(), 1);
     }
 
+    #[test]
+    fn events_report_transitions_in_order() {
+        use crate::events::{BondingEvent as E, FailoverCause};
+
+        let mut scheduler = BondingScheduler::new();
+        let events = scheduler.events().subscribe();
+        let l1 = Arc::new(MockLink::new(1, 10_000_000.0, 10.0));
+        let l2 = Arc::new(MockLink::new(2, 10_000_000.0, 10.0));
+        scheduler.add_link(l1.clone());
+        scheduler.add_link(l2.clone());
+        scheduler.refresh_metrics();
+        l1.set_phase(LinkPhase::Degrade);
+        scheduler.refresh_metrics();
+        scheduler.remove_link(2);
+
+        let got: Vec<_> = events.try_iter().map(|r| r.event).collect();
+        assert_eq!(
+            got,
+            [
+                E::LinkAdded { link_id: 1 },
+                E::LinkAdded { link_id: 2 },
+                E::LinkUp { link_id: 1 },
+                E::LinkUp { link_id: 2 },
+                E::PhaseChanged {
+                    link_id: 1,
+                    from: LinkPhase::Live,
+                    to: LinkPhase::Degrade,
+                },
+                E::FailoverEntered {
+                    link_id: 1,
+                    cause: FailoverCause::PhaseDegraded,
+                },
+                E::LinkDown { link_id: 2 },
+                E::LinkRemoved { link_id: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn keyframe_requests_are_reported_once_across_links() {
+        use crate::events::BondingEvent as E;
+        use strata_transport::wire::KeyframeRequestPacket;
+
+        let mut scheduler = BondingScheduler::new();
+        let l1 = Arc::new(MockLink::new(1, 10_000_000.0, 10.0));
+        let l2 = Arc::new(MockLink::new(2, 10_000_000.0, 10.0));
+        scheduler.add_link(l1.clone());
+        scheduler.add_link(l2.clone());
+        scheduler.refresh_metrics();
+        let events = scheduler.events().subscribe();
+
+        let request = |request_id| {
+            Some(KeyframeRequestPacket {
+                request_id,
+                lost_packets: 9,
+            })
+        };
+        // The same request on both links, the copy on link 2 a tick late.
+        *l1.keyframe_request.lock().unwrap() = request(1);
+        scheduler.refresh_metrics();
+        *l2.keyframe_request.lock().unwrap() = request(1);
+        scheduler.refresh_metrics();
+        *l2.keyframe_request.lock().unwrap() = request(2);
+        scheduler.refresh_metrics();
+
+        let got: Vec<_> = events.try_iter().map(|r| r.event).collect();
+        assert_eq!(
+            got,
+            [
+                E::KeyframeRequested {
+                    link_id: 1,
+                    request_id: 1,
+                    lost_packets: 9,
+                },
+                E::KeyframeRequested {
+                    link_id: 2,
+                    request_id: 2,
+                    lost_packets: 9,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_fast_failover_triggers_on_rtt_spike() {
         // §2.4.1: the RTT-spike trigger requires RTT_SPIKE_SUSTAIN_TICKS
@@ -27,6 +27,59 @@ const COLLAPSE_LOSS_THRESHOLD: f64 = 0.45;
 const COLLAPSE_QUEUE_THRESHOLD: usize = 48;
 const COLLAPSE_GRADIENT_THRESHOLD_US: u32 = 20_000;
 const COLLAPSE_GRADIENT_QUEUE_THRESHOLD: usize = 24;
+/// Burst allowance of a rate-capped link, as time at the capped rate.
+const RATE_CAP_BURST: Duration = Duration::from_millis(50);
+/// Burst floor so a low cap still admits a couple of full-size packets.
+const RATE_CAP_MIN_BURST_BYTES: f64 = 3000.0;
+
+/// Token bucket enforcing an operator-set ceiling on a link's send rate.
+///
+/// Tokens are bytes, refilled lazily whenever the link is considered for a
+/// packet; the link is only offered packets its balance covers.
+pub(crate) struct RateCap {
+    bps: u64,
+    bytes_per_sec: f64,
+    burst_bytes: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateCap {
+    fn new(bps: u64, now: Instant) -> Self {
+        let bytes_per_sec = bps as f64 / 8.0;
+        let burst_bytes =
+            (bytes_per_sec * RATE_CAP_BURST.as_secs_f64()).max(RATE_CAP_MIN_BURST_BYTES);
+        Self {
+            bps,
+            bytes_per_sec,
+            burst_bytes,
+            tokens: burst_bytes,
+            last_refill: now,
+        }
+    }
+
+    fn bps(&self) -> u64 {
+        self.bps
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.bytes_per_sec).min(self.burst_bytes);
+        self.last_refill = now;
+    }
+
+    /// Whether `bytes` may be sent now.
+    fn admits(&mut self, bytes: usize, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= bytes as f64
+    }
+
+    fn consume(&mut self, bytes: u64) {
+        self.tokens = (self.tokens - bytes as f64).max(0.0);
+    }
+}
 
 /// Per-link state tracked by the EDPF scheduler.
 pub(crate) struct LinkState<L: ?Sized> {
@@ -57,6 +110,8 @@ pub(crate) struct LinkState<L: ?Sized> {
     /// not immediately snap traffic back onto a link that only briefly looked
     /// healthy between refresh ticks.
     pub avoid_until: Option<Instant>,
+    /// Operator-set send-rate ceiling, if any.
+    pub rate_cap: Option<RateCap>,
     /// Stop signal for the feedback thread.
     pub stop_tx: Option<crossbeam_channel::Sender<()>>,
 }
@@ -136,7 +191,13 @@ impl<L: ?Sized> LinkState<L> {
             1.0
         };
 
-        (self.metrics.capacity_bps / 8.0
+        // A rate cap bounds what the link may carry no matter what it measures.
+        let capacity_bps = match &self.rate_cap {
+            Some(cap) => self.metrics.capacity_bps.min(cap.bps() as f64),
+            None => self.metrics.capacity_bps,
+        };
+
+        (capacity_bps / 8.0
             * (1.0 - loss)
             * queue_penalty
             * jitter_penalty
@@ -145,12 +206,13 @@ impl<L: ?Sized> LinkState<L> {
             .max(1.0)
     }
 
-    /// Predicted arrival time (seconds from now) for a packet of `size_bytes`.
+    /// Predicted arrival time (seconds from now) for a packet of `size_bytes`,
+    /// with the link's capacity scaled by its scheduling `weight` (> 0).
     ///
-    /// `arrival = in_flight_bytes / capacity_Bps + base_rtt`
-    fn predicted_arrival(&self, size_bytes: usize) -> f64 {
-        let queue_drain =
-            (self.in_flight_bytes as f64 + size_bytes as f64) / self.capacity_bytes_per_sec();
+    /// `arrival = in_flight_bytes / (capacity_Bps * weight) + base_rtt`
+    fn predicted_arrival(&self, size_bytes: usize, weight: f64) -> f64 {
+        let queue_drain = (self.in_flight_bytes as f64 + size_bytes as f64)
+            / (self.capacity_bytes_per_sec() * weight);
         queue_drain + self.base_rtt_secs()
     }
 
@@ -165,6 +227,13 @@ impl<L: ?Sized> LinkState<L> {
         sender_collapse || receiver_queue_build
     }
 
+    /// Whether the rate cap (if any) leaves room for `bytes` now.
+    fn within_rate_cap(&mut self, bytes: usize, now: Instant) -> bool {
+        self.rate_cap
+            .as_mut()
+            .is_none_or(|cap| cap.admits(bytes, now))
+    }
+
     fn is_temporarily_avoided(&self, now: Instant) -> bool {
         self.avoid_until.as_ref().is_some_and(|until| now < *until)
     }
@@ -256,6 +325,7 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
                 penalty_factor: 1.0,
                 prev_phase: LinkPhase::Init,
                 avoid_until: None,
+                rate_cap: None,
                 stop_tx: Some(stop_tx),
             },
         );
@@ -401,6 +471,31 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
         self.links.get(&id).map(|s| s.link.clone())
     }
 
+    /// Whether the link is inside its post-collapse avoid window.
+    pub fn is_temporarily_avoided(&self, id: usize) -> bool {
+        self.links
+            .get(&id)
+            .is_some_and(|state| state.is_temporarily_avoided(Instant::now()))
+    }
+
+    /// Cap the link's send rate at `bps`, or lift the cap with `None`.
+    /// Changing the rate restarts the bucket full.
+    pub fn set_rate_cap(&mut self, id: usize, bps: Option<u64>) {
+        if let Some(state) = self.links.get_mut(&id) {
+            let current = state.rate_cap.as_ref().map(RateCap::bps);
+            if current != bps {
+                state.rate_cap = bps.map(|bps| RateCap::new(bps, Instant::now()));
+            }
+        }
+    }
+
+    /// The link's configured rate cap in bps, if any.
+    pub fn rate_cap(&self, id: usize) -> Option<u64> {
+        self.links
+            .get(&id)
+            .and_then(|s| s.rate_cap.as_ref().map(RateCap::bps))
+    }
+
     /// IDs of every registered link (alive or not).
     pub fn link_ids(&self) -> Vec<usize> {
         self.links.keys().copied().collect()
@@ -412,6 +507,9 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
             state.sent_bytes = state.sent_bytes.saturating_add(bytes);
             state.in_flight_bytes = state.in_flight_bytes.saturating_add(bytes);
             state.has_traffic = true;
+            if let Some(cap) = state.rate_cap.as_mut() {
+                cap.consume(bytes);
+            }
         }
     }
 
@@ -443,12 +541,19 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
     }
 
     /// Returns all alive links (for broadcasting critical packets).
-    pub fn broadcast_links(&mut self, _packet_len: usize) -> Vec<Arc<L>> {
+    ///
+    /// Links over their rate cap are left out.
+    pub fn broadcast_links(&mut self, packet_len: usize) -> Vec<Arc<L>> {
         let any_alive = self.links.values().any(|state| state.metrics.alive);
+        let now = Instant::now();
         self.links
-            .values()
+            .values_mut()
             .filter(|state| state.metrics.alive || !any_alive)
-            .map(|state| state.link.clone())
+            .filter_map(|state| {
+                state
+                    .within_rate_cap(packet_len, now)
+                    .then(|| state.link.clone())
+            })
             .collect()
     }
 
@@ -457,13 +562,21 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
         let mut selected = Vec::new();
         let mut used_kinds: std::collections::HashSet<String> = std::collections::HashSet::new();
 
+        let now = Instant::now();
+        let config = &self.config;
+
         // Score by predicted arrival time (lower = better)
         let mut scored_links: Vec<_> = self
             .links
-            .iter()
-            .filter(|(_, state)| state.metrics.alive)
+            .iter_mut()
+            .filter(|(id, state)| state.metrics.alive && config.link_weight(**id) > 0.0)
+            .filter_map(|(id, state)| {
+                state
+                    .within_rate_cap(packet_len, now)
+                    .then_some((id, state))
+            })
             .map(|(id, state)| {
-                let arrival = state.predicted_arrival(packet_len);
+                let arrival = state.predicted_arrival(packet_len, config.link_weight(*id));
                 let phase_weight = match state.metrics.phase {
                     LinkPhase::Live => 1.0,
                     LinkPhase::Warm => 0.8,
@@ -518,7 +631,8 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
     /// EDPF link selection: pick the link with the lowest predicted arrival time.
     ///
     /// BDP-blocked links are excluded unless all links are blocked (graceful
-    /// degradation: pick the least-loaded blocked link).
+    /// degradation: pick the least-loaded blocked link). Links over their rate
+    /// cap are excluded outright — the cap is a hard limit.
     pub fn select_link(&mut self, packet_len: usize) -> Option<Arc<L>> {
         let candidates = self.sorted_ids.clone();
         self.select_from_links(packet_len, &candidates)
@@ -538,14 +652,18 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
         let mut preferred: Vec<(usize, f64)> = Vec::new();
         let mut avoided: Vec<(usize, f64)> = Vec::new();
         for &id in candidates {
-            if let Some(state) = self.links.get(&id)
+            if let Some(state) = self.links.get_mut(&id)
                 && (state.metrics.alive || !any_alive)
+                && state.within_rate_cap(packet_len, now)
             {
                 let phase_ok =
                     !matches!(state.metrics.phase, LinkPhase::Cooldown | LinkPhase::Reset);
                 let os_ok = !matches!(state.metrics.os_up, Some(false));
-                if phase_ok && os_ok {
-                    let arrival = state.predicted_arrival(packet_len);
+                // Weight 0 keeps the link out of scoring; it remains a
+                // last resort below.
+                let weight = self.config.link_weight(id);
+                if phase_ok && os_ok && weight > 0.0 {
+                    let arrival = state.predicted_arrival(packet_len, weight);
                     if state.is_temporarily_avoided(now) {
                         avoided.push((id, arrival));
                     } else {
@@ -562,10 +680,11 @@ impl<L: LinkSender + ?Sized + 'static> Edpf<L> {
         };
 
         if scored.is_empty() {
-            // Last resort: any alive link
+            // Last resort: any alive link with rate-cap headroom
             for &id in candidates {
-                if let Some(state) = self.links.get(&id)
+                if let Some(state) = self.links.get_mut(&id)
                     && (state.metrics.alive || !any_alive)
+                    && state.within_rate_cap(packet_len, now)
                 {
                     return Some(state.link.clone());
                 }
@@ -638,6 +757,8 @@ mod tests {
                     ack_bytes: 0,
                     estimated_capacity_bps: 0.0,
                     owd_ms: 0.0,
+                    clock_offset_us: None,
+                    clock_drift_ppm: None,
                     receiver_report: None,
                     probe_active: false,
                     inferred_regime: None,
@@ -704,6 +825,80 @@ mod tests {
         assert_eq!(selected.id(), 2);
     }
 
+    #[test]
+    fn rate_capped_link_yields_once_its_bucket_is_spent() {
+        let mut edpf = Edpf::new();
+        let l1 = Arc::new(MockLink::new(1, 10_000_000.0, 10.0, LinkPhase::Live));
+        let l2 = Arc::new(MockLink::new(2, 3_000_000.0, 10.0, LinkPhase::Live));
+        edpf.add_link(l1.clone());
+        edpf.add_link(l2.clone());
+        edpf.refresh_metrics();
+
+        // 800 kbps → 5 000-byte burst: three 1400-byte packets fit, the
+        // fourth does not, even though L1 is otherwise the faster link.
+        edpf.set_rate_cap(1, Some(800_000));
+        assert_eq!(edpf.rate_cap(1), Some(800_000));
+        for _ in 0..3 {
+            let selected = edpf.select_from_links(1400, &[1]).unwrap();
+            assert_eq!(selected.id(), 1);
+            edpf.record_send(1, 1400);
+        }
+        assert!(edpf.select_from_links(1400, &[1]).is_none());
+        assert_eq!(edpf.select_link(1400).unwrap().id(), 2);
+        assert!(
+            edpf.broadcast_links(1400).iter().all(|l| l.id() != 1),
+            "broadcast must respect the cap too"
+        );
+
+        edpf.set_rate_cap(1, None);
+        assert_eq!(edpf.rate_cap(1), None);
+        assert!(edpf.select_from_links(1400, &[1]).is_some());
+    }
+
+    #[test]
+    fn link_weights_scale_the_credited_capacity() {
+        let mut edpf = Edpf::new();
+        let l1 = Arc::new(MockLink::new(1, 10_000_000.0, 10.0, LinkPhase::Live));
+        let l2 = Arc::new(MockLink::new(2, 3_000_000.0, 10.0, LinkPhase::Live));
+        edpf.add_link(l1.clone());
+        edpf.add_link(l2.clone());
+        edpf.refresh_metrics();
+        assert_eq!(edpf.select_link(1400).unwrap().id(), 1);
+
+        // Weighted down to an effective 1 Mbps, L1 loses to L2.
+        edpf.update_config(SchedulerConfig {
+            link_weights: vec![1.0, 0.1, 1.0],
+            ..SchedulerConfig::default()
+        });
+        assert_eq!(edpf.select_link(1400).unwrap().id(), 2);
+
+        // Weight 0 takes L1 out of scoring but keeps it as a last resort.
+        edpf.update_config(SchedulerConfig {
+            link_weights: vec![1.0, 0.0],
+            ..SchedulerConfig::default()
+        });
+        assert_eq!(edpf.select_link(1400).unwrap().id(), 2);
+        assert_eq!(edpf.select_from_links(1400, &[1]).unwrap().id(), 1);
+        assert!(
+            edpf.select_best_n_links(1400, 2)
+                .iter()
+                .all(|l| l.id() != 1)
+        );
+    }
+
+    #[test]
+    fn rate_cap_refills_at_the_capped_rate() {
+        let start = Instant::now();
+        let mut cap = RateCap::new(800_000, start);
+        cap.consume(5_000);
+        assert!(!cap.admits(1400, start));
+        // 100 kB/s → 1400 bytes takes 14 ms.
+        assert!(!cap.admits(1400, start + Duration::from_millis(10)));
+        assert!(cap.admits(1400, start + Duration::from_millis(15)));
+        // Refill never exceeds the burst.
+        assert!(!cap.admits(5_001, start + Duration::from_secs(10)));
+    }
+
     #[test]
     fn transport_link_routes_to_least_loaded() {
         // Transport link with high in-flight → EDPF routes to the other
@@ -0,0 +1,290 @@
+//! Pluggable telemetry sinks.
+//!
+//! Instead of polling [`BondingRuntime::metrics_handle`] and draining
+//! [`BondingRuntime::subscribe_events`] on a thread of their own, embedders
+//! attach a [`TelemetrySink`] with [`BondingRuntime::add_telemetry_sink`].
+//! One telemetry thread per runtime then feeds every sink each
+//! [`EventRecord`] as it is emitted and a [`TelemetrySnapshot`] of the link
+//! metrics every `stats_interval_ms`, events first so a sink sees a
+//! transition before the snapshot that reflects it.
+//!
+//! Built in: [`TracingSink`] and [`ChannelSink`]. The GStreamer bus sink
+//! lives in strata-gst, which owns the message layout.
+//!
+//! [`BondingRuntime::metrics_handle`]: crate::runtime::BondingRuntime::metrics_handle
+//! [`BondingRuntime::subscribe_events`]: crate::runtime::BondingRuntime::subscribe_events
+//! [`BondingRuntime::add_telemetry_sink`]: crate::runtime::BondingRuntime::add_telemetry_sink
+
+use crate::events::{EventLog, EventRecord};
+use crate::net::interface::LinkMetrics;
+use crate::scheduler::bonding::SpreadStats;
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How often the telemetry thread forwards events and checks whether a
+/// snapshot is due.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Periodic view of the bond handed to every sink.
+#[derive(Debug, Clone, Default)]
+pub struct TelemetrySnapshot {
+    /// Counts snapshots from 0, one per `stats_interval_ms`.
+    pub seq: u64,
+    /// Time since the telemetry thread started.
+    pub uptime: Duration,
+    pub wall_time_ms: u64,
+    pub links: HashMap<usize, LinkMetrics>,
+    /// Configured broadcast/redundancy copy spread (see [`SpreadStats`]).
+    pub duplicate_spread_ms: f64,
+    /// Copies held back and sent after their first sibling, total.
+    pub staggered_copies: u64,
+}
+
+impl TelemetrySnapshot {
+    /// Summed capacity of the links that are alive.
+    pub fn total_capacity_bps(&self) -> f64 {
+        self.links
+            .values()
+            .filter(|m| m.alive)
+            .map(|m| m.capacity_bps)
+            .sum()
+    }
+
+    pub fn alive_links(&self) -> usize {
+        self.links.values().filter(|m| m.alive).count()
+    }
+}
+
+/// Destination for a runtime's telemetry. Called from the runtime's
+/// telemetry thread, never from the scheduler, so a slow sink delays
+/// other sinks but not packets.
+pub trait TelemetrySink: Send {
+    /// A periodic snapshot, every `stats_interval_ms`.
+    fn snapshot(&mut self, snapshot: &TelemetrySnapshot);
+
+    /// A state transition, as it happens.
+    fn event(&mut self, _record: &EventRecord) {}
+}
+
+/// Logs snapshots at `debug` and events at `info`, under
+/// `strata::telemetry`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingSink;
+
+impl TelemetrySink for TracingSink {
+    fn snapshot(&mut self, snapshot: &TelemetrySnapshot) {
+        tracing::debug!(
+            target: "strata::telemetry",
+            seq = snapshot.seq,
+            alive_links = snapshot.alive_links(),
+            total_capacity_bps = snapshot.total_capacity_bps(),
+            "bonding snapshot"
+        );
+        for (id, m) in &snapshot.links {
+            tracing::debug!(
+                target: "strata::telemetry",
+                link_id = id,
+                phase = m.phase.as_str(),
+                alive = m.alive,
+                rtt_ms = m.rtt_ms,
+                capacity_bps = m.capacity_bps,
+                loss_rate = m.loss_rate,
+                "link snapshot"
+            );
+        }
+    }
+
+    fn event(&mut self, record: &EventRecord) {
+        tracing::info!(target: "strata::telemetry", ?record, "bonding event");
+    }
+}
+
+/// One item delivered by a [`ChannelSink`].
+#[derive(Debug, Clone)]
+pub enum Telemetry {
+    Snapshot(Box<TelemetrySnapshot>),
+    Event(EventRecord),
+}
+
+/// Forwards telemetry into a bounded channel. A receiver that stops
+/// reading loses items rather than stall the other sinks.
+pub struct ChannelSink {
+    tx: Sender<Telemetry>,
+}
+
+impl ChannelSink {
+    /// A sink and the receiver it feeds, buffering up to `capacity` items.
+    pub fn new(capacity: usize) -> (Self, Receiver<Telemetry>) {
+        let (tx, rx) = crossbeam_channel::bounded(capacity);
+        (Self { tx }, rx)
+    }
+
+    fn send(&self, item: Telemetry) {
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(item) {
+            tracing::trace!(target: "strata::telemetry", "telemetry channel full, dropping");
+        }
+    }
+}
+
+impl TelemetrySink for ChannelSink {
+    fn snapshot(&mut self, snapshot: &TelemetrySnapshot) {
+        self.send(Telemetry::Snapshot(Box::new(snapshot.clone())));
+    }
+
+    fn event(&mut self, record: &EventRecord) {
+        self.send(Telemetry::Event(record.clone()));
+    }
+}
+
+/// The runtime's telemetry thread. Started with the first sink, which
+/// sees every event emitted from then on.
+pub(crate) struct TelemetryForwarder {
+    sinks_tx: Sender<Box<dyn TelemetrySink>>,
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl TelemetryForwarder {
+    pub(crate) fn spawn(
+        metrics: Arc<Mutex<HashMap<usize, LinkMetrics>>>,
+        spread_stats: Arc<SpreadStats>,
+        events: &EventLog,
+        stats_interval: Duration,
+        first: Box<dyn TelemetrySink>,
+    ) -> Self {
+        let (sinks_tx, sinks_rx) = crossbeam_channel::unbounded::<Box<dyn TelemetrySink>>();
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = running.clone();
+        let events = events.subscribe();
+
+        let handle = thread::Builder::new()
+            .name("strata-telemetry".into())
+            .spawn(move || {
+                let mut sinks = vec![first];
+                let start = Instant::now();
+                let mut last_snapshot = start;
+                let mut seq = 0u64;
+                while running_clone.load(Ordering::Relaxed) {
+                    sinks.extend(sinks_rx.try_iter());
+                    for record in events.try_iter() {
+                        for sink in &mut sinks {
+                            sink.event(&record);
+                        }
+                    }
+                    if last_snapshot.elapsed() >= stats_interval {
+                        let snapshot = TelemetrySnapshot {
+                            seq,
+                            uptime: start.elapsed(),
+                            wall_time_ms: SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .map(|d| d.as_millis() as u64)
+                                .unwrap_or(0),
+                            links: metrics.lock().map(|m| m.clone()).unwrap_or_default(),
+                            duplicate_spread_ms: spread_stats.spread_ms(),
+                            staggered_copies: spread_stats.staggered_copies.load(Ordering::Relaxed),
+                        };
+                        for sink in &mut sinks {
+                            sink.snapshot(&snapshot);
+                        }
+                        seq = seq.wrapping_add(1);
+                        last_snapshot = Instant::now();
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+                // Events the worker emitted on its way down (stopped after
+                // it, see `BondingRuntime::shutdown`) still reach the sinks.
+                sinks.extend(sinks_rx.try_iter());
+                for record in events.try_iter() {
+                    for sink in &mut sinks {
+                        sink.event(&record);
+                    }
+                }
+            })
+            .expect("failed to spawn telemetry thread");
+
+        Self {
+            sinks_tx,
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    pub(crate) fn add(&self, sink: Box<dyn TelemetrySink>) {
+        let _ = self.sinks_tx.send(sink);
+    }
+
+    /// Stop the thread and drop its sinks. Idempotent.
+    pub(crate) fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for TelemetryForwarder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::BondingEvent;
+
+    #[test]
+    fn channel_sink_gets_events_and_snapshots() {
+        let metrics = Arc::new(Mutex::new(HashMap::from([(
+            3,
+            LinkMetrics {
+                alive: true,
+                capacity_bps: 2_000_000.0,
+                ..Default::default()
+            },
+        )])));
+        let events = EventLog::new();
+        let (sink, rx) = ChannelSink::new(64);
+        let mut forwarder = TelemetryForwarder::spawn(
+            metrics,
+            Arc::new(SpreadStats::default()),
+            &events,
+            Duration::from_millis(10),
+            Box::new(sink),
+        );
+
+        // The first snapshot shows the sink is attached.
+        let Ok(Telemetry::Snapshot(snapshot)) = rx.recv_timeout(Duration::from_secs(5)) else {
+            panic!("expected a snapshot first");
+        };
+        assert_eq!(snapshot.alive_links(), 1);
+        assert_eq!(snapshot.total_capacity_bps(), 2_000_000.0);
+
+        events.emit(BondingEvent::LinkUp { link_id: 3 });
+        let event = rx
+            .iter()
+            .find_map(|item| match item {
+                Telemetry::Event(record) => Some(record.event),
+                Telemetry::Snapshot(_) => None,
+            })
+            .unwrap();
+        assert_eq!(event, BondingEvent::LinkUp { link_id: 3 });
+
+        // An event emitted just before stopping is still delivered, then
+        // stopping drops the sink, which disconnects the channel.
+        events.emit(BondingEvent::LinkDown { link_id: 3 });
+        forwarder.stop();
+        let rest: Vec<_> = rx
+            .iter()
+            .filter_map(|item| match item {
+                Telemetry::Event(record) => Some(record.event),
+                Telemetry::Snapshot(_) => None,
+            })
+            .collect();
+        assert_eq!(rest, [BondingEvent::LinkDown { link_id: 3 }]);
+    }
+}
@@ -47,6 +47,8 @@ impl MockLink {
                 ack_bytes: 0,
                 estimated_capacity_bps: 0.0,
                 owd_ms: 0.0,
+                clock_offset_us: None,
+                clock_drift_ppm: None,
                 receiver_report: None,
                 probe_active: false,
                 inferred_regime: None,
@@ -38,6 +38,7 @@ fn runtime_metrics_server_serves_prometheus() {
         uri: format!("{}", rcv_addr),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(250));
@@ -93,6 +94,7 @@ fn runtime_metrics_reflect_link_changes() {
         uri: format!("{}", rcv_addr),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(250));
@@ -43,6 +43,7 @@ fn runtime_to_receiver_single_link() {
         uri: format!("{}", rcv_addr),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(200));
@@ -90,6 +91,7 @@ fn runtime_to_receiver_multi_link() {
         uri: format!("{}", rcv_addr_1),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -97,6 +99,7 @@ fn runtime_to_receiver_multi_link() {
         uri: format!("{}", rcv_addr_2),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(200));
@@ -191,6 +194,7 @@ fn critical_broadcast_deduplication() {
         uri: format!("{}", rcv_addr_1),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -198,6 +202,7 @@ fn critical_broadcast_deduplication() {
         uri: format!("{}", rcv_addr_2),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(200));
@@ -312,6 +317,7 @@ fn three_link_heterogeneous_all_delivered() {
         uri: format!("{}", rcv_addr_1),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -319,6 +325,7 @@ fn three_link_heterogeneous_all_delivered() {
         uri: format!("{}", rcv_addr_2),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -326,6 +333,7 @@ fn three_link_heterogeneous_all_delivered() {
         uri: format!("{}", rcv_addr_3),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(200));
@@ -390,6 +398,7 @@ fn link_failure_mid_stream_failover() {
         uri: format!("{}", rcv_addr_1),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -397,6 +406,7 @@ fn link_failure_mid_stream_failover() {
         uri: format!("{}", rcv_addr_2),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     rt.add_link(LinkConfig {
@@ -404,6 +414,7 @@ fn link_failure_mid_stream_failover() {
         uri: format!("{}", rcv_addr_3),
         interface: None,
         profile: None,
+        rate_cap_bps: None,
     })
     .unwrap();
     std::thread::sleep(Duration::from_millis(200));
@@ -0,0 +1,45 @@
+//! Server-side error carrying a stable [`ErrorCode`].
+//!
+//! The code catalogue itself lives in `strata-protocol` so the dashboard
+//! can render it; this is the error type services return and convert into
+//! their REST error bodies or NAKs.
+
+pub use strata_protocol::{ErrorCategory, ErrorCode};
+
+use crate::auth::AuthError;
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub struct StrataError {
+    pub code: ErrorCode,
+    /// Detail for humans and logs; clients should branch on `code`.
+    pub message: String,
+}
+
+impl StrataError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        self.code.category()
+    }
+}
+
+impl From<AuthError> for StrataError {
+    fn from(e: AuthError) -> Self {
+        let code = match e {
+            AuthError::InvalidPassword => ErrorCode::InvalidCredentials,
+            AuthError::JwtError(_) | AuthError::InvalidKey | AuthError::WrongScope(_) => {
+                ErrorCode::Unauthenticated
+            }
+            AuthError::HashError(_) | AuthError::InvalidTotpSecret | AuthError::Unseal => {
+                ErrorCode::Internal
+            }
+        };
+        Self::new(code, e.to_string())
+    }
+}
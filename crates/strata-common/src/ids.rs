@@ -2,10 +2,14 @@
 //!
 //! All entity IDs use a `prefix_` followed by a UUIDv7 (time-ordered).
 //! This makes IDs globally unique, sortable by creation time, and instantly
-//! identifiable by type when reading logs or database rows.
+//! identifiable by type when reading logs or database rows. Users,
+//! senders, streams and destinations get typed IDs (re-exported from
+//! `strata_protocol::ids`) so they can't be mixed up.
 
 use uuid::Uuid;
 
+pub use strata_protocol::ids::{DestinationId, InvalidId, SenderId, StreamId, UserId};
+
 /// Generate a prefixed ID using UUIDv7.
 fn prefixed_id(prefix: &str) -> String {
     let id = Uuid::now_v7();
@@ -13,23 +17,23 @@ fn prefixed_id(prefix: &str) -> String {
 }
 
 /// Generate a user ID: `usr_<uuid7>`
-pub fn user_id() -> String {
-    prefixed_id("usr")
+pub fn user_id() -> UserId {
+    UserId::from_trusted(prefixed_id(UserId::PREFIX))
 }
 
 /// Generate a sender (device) ID: `snd_<uuid7>`
-pub fn sender_id() -> String {
-    prefixed_id("snd")
+pub fn sender_id() -> SenderId {
+    SenderId::from_trusted(prefixed_id(SenderId::PREFIX))
 }
 
 /// Generate a stream ID: `str_<uuid7>`
-pub fn stream_id() -> String {
-    prefixed_id("str")
+pub fn stream_id() -> StreamId {
+    StreamId::from_trusted(prefixed_id(StreamId::PREFIX))
 }
 
 /// Generate a destination ID: `dst_<uuid7>`
-pub fn destination_id() -> String {
-    prefixed_id("dst")
+pub fn destination_id() -> DestinationId {
+    DestinationId::from_trusted(prefixed_id(DestinationId::PREFIX))
 }
 
 /// Generate a receiver ID: `rcv_<uuid7>`
@@ -37,27 +41,185 @@ pub fn receiver_id() -> String {
     prefixed_id("rcv")
 }
 
+/// Generate a maintenance window ID: `mnt_<uuid7>`
+pub fn maintenance_window_id() -> String {
+    prefixed_id("mnt")
+}
+
+/// Generate an alert event ID: `alr_<uuid7>`
+pub fn alert_event_id() -> String {
+    prefixed_id("alr")
+}
+
+/// Generate an audit log entry ID: `aud_<uuid7>`
+pub fn audit_entry_id() -> String {
+    prefixed_id("aud")
+}
+
+/// Generate a scheduled stream ID: `sch_<uuid7>`
+pub fn scheduled_stream_id() -> String {
+    prefixed_id("sch")
+}
+
+/// Generate a share link ID: `shr_<uuid7>`
+pub fn share_link_id() -> String {
+    prefixed_id("shr")
+}
+
+/// Generate a kit ID: `kit_<uuid7>`
+pub fn kit_id() -> String {
+    prefixed_id("kit")
+}
+
+/// Generate a kit accessory ID: `kac_<uuid7>`
+pub fn kit_accessory_id() -> String {
+    prefixed_id("kac")
+}
+
+/// Generate a kit check-out record ID: `kco_<uuid7>`
+pub fn kit_checkout_id() -> String {
+    prefixed_id("kco")
+}
+
+/// Generate a TLS certificate ID: `crt_<uuid7>`
+pub fn certificate_id() -> String {
+    prefixed_id("crt")
+}
+
+/// Unambiguous charset for tokens people type: digits 2-9, letters A-Z
+/// minus I and O (no 0/O, 1/I/l confusion).
+const TYPEABLE_CHARSET: &[u8; 32] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+
 /// Generate a short, human-readable enrollment token: `XXXX-XXXX`.
 ///
-/// Uses an unambiguous character set (no 0/O, 1/I/l confusion).
-/// 32^8 ≈ 1.1 trillion combinations — more than sufficient for
-/// single-use, rate-limited enrollment tokens.
+/// Seven random characters and a Luhn mod 32 check character, so
+/// [`validate_enrollment_token`] catches any single mistyped character and
+/// most swapped neighbours without asking the control plane.
+/// 32^7 ≈ 34 billion combinations — more than sufficient for single-use,
+/// rate-limited enrollment tokens.
 pub fn enrollment_token() -> String {
     use rand::RngExt;
-    // Unambiguous charset: digits 2-9, letters A-Z minus I and O
-    const CHARSET: &[u8] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
     let mut rng = rand::rng();
-    let mut token = String::with_capacity(9);
-    for i in 0..8 {
-        if i == 4 {
+    let mut values: Vec<usize> = (0..ENROLLMENT_TOKEN_LEN - 1)
+        .map(|_| rng.random_range(0..TYPEABLE_CHARSET.len()))
+        .collect();
+    values.push(luhn_check_value(&values));
+    let mut token = String::with_capacity(ENROLLMENT_TOKEN_LEN + 1);
+    for (i, v) in values.into_iter().enumerate() {
+        if i == ENROLLMENT_TOKEN_LEN / 2 {
             token.push('-');
         }
-        let idx = rng.random_range(0..CHARSET.len());
-        token.push(CHARSET[idx] as char);
+        token.push(TYPEABLE_CHARSET[v] as char);
     }
     token
 }
 
+/// Characters in an enrollment token secret, check character included.
+const ENROLLMENT_TOKEN_LEN: usize = 8;
+
+/// Why an operator-entered enrollment token can't be right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EnrollmentTokenError {
+    #[error("enrollment token should look like <device id>.XXXX-XXXX")]
+    Malformed,
+    #[error("enrollment token has a typo; check it against the dashboard")]
+    Checksum,
+}
+
+/// Check a composite enrollment token (`<device_id>.XXXX-XXXX`) offline:
+/// shape, charset and check character. Passing doesn't mean the token is
+/// valid — only the control plane knows that — but failing means it was
+/// mistyped. Tokens issued before the check character have the same shape
+/// and fail with [`EnrollmentTokenError::Checksum`]; only the control
+/// plane can tell those apart, from the device row.
+pub fn validate_enrollment_token(raw: &str) -> Result<(), EnrollmentTokenError> {
+    let (_, secret) = split_enrollment_token(raw).ok_or(EnrollmentTokenError::Malformed)?;
+    let values: Vec<usize> = secret
+        .bytes()
+        .map(|b| TYPEABLE_CHARSET.iter().position(|&c| c == b))
+        .collect::<Option<_>>()
+        .ok_or(EnrollmentTokenError::Malformed)?;
+    let [payload @ .., check] = values.as_slice() else {
+        return Err(EnrollmentTokenError::Malformed);
+    };
+    if values.len() != ENROLLMENT_TOKEN_LEN {
+        return Err(EnrollmentTokenError::Malformed);
+    }
+    if luhn_check_value(payload) != *check {
+        return Err(EnrollmentTokenError::Checksum);
+    }
+    Ok(())
+}
+
+/// Luhn mod N check value over charset indices: double every other value
+/// from the right, fold each back into base N, and pick the value that
+/// brings the sum to a multiple of N.
+fn luhn_check_value(payload: &[usize]) -> usize {
+    let n = TYPEABLE_CHARSET.len();
+    let sum: usize = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &v)| {
+            let addend = if i % 2 == 0 { v * 2 } else { v };
+            addend / n + addend % n
+        })
+        .sum();
+    (n - sum % n) % n
+}
+
+/// Generate a random password: four groups of four from the unambiguous
+/// enrollment charset (`XXXX-XXXX-XXXX-XXXX`, 80 bits). The control plane
+/// uses it as the unknown placeholder password of an invited or reset
+/// account, stored only as a hash.
+pub fn temporary_password() -> String {
+    use rand::RngExt;
+    let mut rng = rand::rng();
+    let mut password = String::with_capacity(19);
+    for i in 0..16 {
+        if i > 0 && i % 4 == 0 {
+            password.push('-');
+        }
+        password.push(TYPEABLE_CHARSET[rng.random_range(0..TYPEABLE_CHARSET.len())] as char);
+    }
+    password
+}
+
+/// Generate a per-stream ingest key: `isk_` + 32 random characters
+/// (160 bits). Minted by the control plane at stream start and handed to
+/// both ends; the receiver only accepts link traffic from sources that
+/// present it. Never stored — a restarted stream gets a fresh key.
+pub fn ingest_key() -> String {
+    random_key("isk_")
+}
+
+/// Generate a per-stream preview key: `pvk_` + 32 random characters. It
+/// sits in the receiver's preview URL path, so anyone holding the URL can
+/// watch the stream while it is live — like [`ingest_key`], it dies with
+/// the stream.
+pub fn preview_key() -> String {
+    random_key("pvk_")
+}
+
+/// Generate a sender portal session token: `pst_` + 32 random characters.
+/// Held in a cookie after the portal PIN is entered; lives only in the
+/// agent's memory.
+pub fn portal_session_token() -> String {
+    random_key("pst_")
+}
+
+fn random_key(prefix: &str) -> String {
+    use rand::RngExt;
+    const CHARSET: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz_";
+    let mut rng = rand::rng();
+    let mut key = String::with_capacity(prefix.len() + 32);
+    key.push_str(prefix);
+    for _ in 0..32 {
+        key.push(CHARSET[rng.random_range(0..CHARSET.len())] as char);
+    }
+    key
+}
+
 /// Build the composite enrollment token handed to the operator:
 /// `<device_id>.<SECRET>`. Embedding the device id lets the control plane
 /// look up exactly one row and run exactly one argon2 verification —
@@ -95,6 +257,14 @@ mod tests {
         assert!(sender_id().starts_with("snd_"));
         assert!(stream_id().starts_with("str_"));
         assert!(destination_id().starts_with("dst_"));
+        assert!(maintenance_window_id().starts_with("mnt_"));
+        assert!(alert_event_id().starts_with("alr_"));
+        assert!(audit_entry_id().starts_with("aud_"));
+        assert!(scheduled_stream_id().starts_with("sch_"));
+        assert!(share_link_id().starts_with("shr_"));
+        // Generated IDs pass their own type's parse.
+        assert!(StreamId::parse(stream_id().into_string()).is_ok());
+        assert!(SenderId::parse(sender_id().into_string()).is_ok());
     }
 
     #[test]
@@ -114,6 +284,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn temporary_password_format() {
+        let password = temporary_password();
+        assert_eq!(password.len(), 19);
+        assert_eq!(password.matches('-').count(), 3);
+        assert_ne!(password, temporary_password());
+    }
+
+    #[test]
+    fn ingest_key_format() {
+        let key = ingest_key();
+        assert!(key.starts_with("isk_"));
+        assert_eq!(key.len(), 36);
+        assert_ne!(key, ingest_key());
+    }
+
+    #[test]
+    fn preview_key_format() {
+        let key = preview_key();
+        assert!(key.starts_with("pvk_"));
+        assert_eq!(key.len(), 36);
+        assert_ne!(key, preview_key());
+    }
+
+    #[test]
+    fn enrollment_token_checksum_catches_typos() {
+        let token = enrollment_token();
+        let composite = composite_enrollment_token("snd_abc123", &token);
+        assert_eq!(validate_enrollment_token(&composite), Ok(()));
+        // Operators retype tokens loosely.
+        let loose =
+            composite_enrollment_token("snd_abc123", &token.to_lowercase().replace('-', " "));
+        assert_eq!(validate_enrollment_token(&loose), Ok(()));
+        // The fixed dev seed tokens.
+        assert_eq!(validate_enrollment_token("snd_dev.DEVT-ESTD"), Ok(()));
+        assert_eq!(validate_enrollment_token("rcv_dev.RCVT-ESTN"), Ok(()));
+
+        // Every single-character substitution is caught.
+        let secret = normalize_enrollment_token(&token);
+        for pos in 0..secret.len() {
+            for &c in TYPEABLE_CHARSET {
+                let mut typo = secret.clone().into_bytes();
+                if typo[pos] == c {
+                    continue;
+                }
+                typo[pos] = c;
+                let typo = format!("snd_abc123.{}", String::from_utf8(typo).unwrap());
+                assert_eq!(
+                    validate_enrollment_token(&typo),
+                    Err(EnrollmentTokenError::Checksum)
+                );
+            }
+        }
+
+        assert_eq!(
+            validate_enrollment_token(&token),
+            Err(EnrollmentTokenError::Malformed)
+        );
+        assert_eq!(
+            validate_enrollment_token("snd_abc123.ABCD-EF0H"),
+            Err(EnrollmentTokenError::Malformed)
+        );
+        // A dropped or extra character is a shape error, not a checksum one.
+        assert_eq!(
+            validate_enrollment_token(&format!("snd_abc123.{}", &secret[1..])),
+            Err(EnrollmentTokenError::Malformed)
+        );
+        assert_eq!(
+            validate_enrollment_token(&format!("snd_abc123.{secret}2")),
+            Err(EnrollmentTokenError::Malformed)
+        );
+    }
+
     #[test]
     fn enrollment_tokens_are_unique() {
         let a = enrollment_token();
@@ -2,13 +2,26 @@
 //!
 //! This crate contains:
 //! - **Auth primitives** — JWT creation/validation, Argon2id password hashing
-//! - **ID generation** — Prefixed nanoid helpers (`usr_`, `snd_`, `str_`, `dst_`)
+//! - **Errors** — `StrataError`, pairing a message with a stable error code
+//! - **ID generation** — Prefixed ID helpers (`usr_`, `snd_`, `str_`, `dst_`), typed
+//!   for users, senders, streams and destinations
 //! - **Metrics rendering** — Prometheus text exposition
+//! - **TLS** — certificate inspection and on-device storage; with the
+//!   `tls-server` feature, HTTPS serving with hot certificate reload
+//! - **Telemetry schema** — `LinkSample`/`TelemetrySample` with unit-typed fields
+//! - **Test vectors** — golden wire encodings of every protocol message,
+//!   checked by the agent and control-plane test suites
+//! - **Validation** — config checks shared by the control-plane API and agents
 //!
 //! Wire types (protocol messages, data models, REST API types, profiles)
 //! live in `strata-protocol` — the wasm-safe single source of truth.
 
 pub mod auth;
+pub mod error;
 pub mod identity;
 pub mod ids;
 pub mod metrics;
+pub mod telemetry;
+pub mod testvectors;
+pub mod tls;
+pub mod validation;
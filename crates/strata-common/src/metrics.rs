@@ -1,13 +1,14 @@
 //! Prometheus metrics rendering for link stats.
 //!
-//! Renders `LinkStats` in Prometheus text exposition format, suitable
+//! Renders `LinkSample` in Prometheus text exposition format, suitable
 //! for scraping by Prometheus or compatible collectors.
 
 use std::fmt::Write;
-use strata_protocol::models::{LinkStats, TransportReceiverMetrics, TransportSenderMetrics};
+use strata_protocol::models::{TransportReceiverMetrics, TransportSenderMetrics};
+use strata_protocol::telemetry::LinkSample;
 
-/// Render a slice of `LinkStats` as Prometheus text exposition format.
-pub fn render_prometheus(links: &[LinkStats]) -> String {
+/// Render a slice of `LinkSample` as Prometheus text exposition format.
+pub fn render_prometheus(links: &[LinkSample]) -> String {
     let mut out = String::with_capacity(2048);
 
     // ── Per-link gauges ─────────────────────────────────────────
@@ -112,12 +113,12 @@ pub fn render_prometheus(links: &[LinkStats]) -> String {
     let total_capacity: u64 = links
         .iter()
         .filter(|l| l.state == "Live" || l.state == "live")
-        .map(|l| l.capacity_bps)
+        .map(|l| l.capacity_bps.0)
         .sum();
     let total_observed: u64 = links
         .iter()
         .filter(|l| l.state == "Live" || l.state == "live")
-        .map(|l| l.observed_bps)
+        .map(|l| l.observed_bps.0)
         .sum();
 
     writeln!(
@@ -363,7 +364,7 @@ pub fn render_receiver_prometheus(stats: &TransportReceiverMetrics) -> String {
 /// Combines link stats, optional sender transport stats, and optional
 /// receiver transport stats into one text block.
 pub fn render_all_prometheus(
-    links: &[LinkStats],
+    links: &[LinkSample],
     sender: Option<&TransportSenderMetrics>,
     receiver: Option<&TransportReceiverMetrics>,
 ) -> String {
@@ -380,44 +381,47 @@ pub fn render_all_prometheus(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use strata_protocol::telemetry::{Bps, Dbm, Millis};
 
-    fn sample_stats() -> Vec<LinkStats> {
+    fn sample_stats() -> Vec<LinkSample> {
         vec![
-            LinkStats {
+            LinkSample {
                 id: 0,
                 interface: "wwan0".into(),
                 state: "Live".into(),
-                rtt_ms: 25.5,
+                rtt_ms: Millis(25.5),
                 loss_rate: 0.02,
-                capacity_bps: 5_000_000,
+                capacity_bps: Bps(5_000_000),
                 sent_bytes: 100_000,
-                observed_bps: 3_000_000,
-                signal_dbm: Some(-65),
+                observed_bps: Bps(3_000_000),
+                signal_dbm: Some(Dbm(-65)),
                 link_kind: Some("cellular".into()),
                 rsrp: None,
                 rsrq: None,
                 sinr: None,
                 cqi: None,
-                btlbw_bps: Some(4_500_000),
-                rtprop_ms: Some(20.0),
+                btlbw_bps: Some(Bps(4_500_000)),
+                rtprop_ms: Some(Millis(20.0)),
+                transport: None,
             },
-            LinkStats {
+            LinkSample {
                 id: 1,
                 interface: "wwan1".into(),
                 state: "Live".into(),
-                rtt_ms: 50.0,
+                rtt_ms: Millis(50.0),
                 loss_rate: 0.05,
-                capacity_bps: 2_000_000,
+                capacity_bps: Bps(2_000_000),
                 sent_bytes: 50_000,
-                observed_bps: 1_500_000,
-                signal_dbm: Some(-72),
+                observed_bps: Bps(1_500_000),
+                signal_dbm: Some(Dbm(-72)),
                 link_kind: Some("cellular".into()),
                 rsrp: None,
                 rsrq: None,
                 sinr: None,
                 cqi: None,
-                btlbw_bps: Some(1_800_000),
-                rtprop_ms: Some(45.0),
+                btlbw_bps: Some(Bps(1_800_000)),
+                rtprop_ms: Some(Millis(45.0)),
+                transport: None,
             },
         ]
     }
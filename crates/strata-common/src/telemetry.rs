@@ -0,0 +1,7 @@
+//! Telemetry sample schema.
+//!
+//! The types are defined in `strata_protocol::telemetry` so the dashboard
+//! deserializes exactly what the agents send and the control plane stores;
+//! they are re-exported here for the server-side crates.
+
+pub use strata_protocol::telemetry::{Bps, Dbm, LinkSample, Millis, TelemetrySample};
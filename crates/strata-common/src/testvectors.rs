@@ -0,0 +1,350 @@
+//! Protocol conformance test vectors.
+//!
+//! Golden wire encodings of every message type, one JSON file per
+//! WebSocket leg under `testvectors/v<PROTOCOL_VERSION>/`. Each file is an
+//! array of frames exactly as they cross the wire: envelopes with a fixed
+//! `id` and `ts` for the device legs and the dashboard client, bare tagged
+//! events for [`DashboardEvent`].
+//!
+//! [`check`] parses every frame into its direction enum, re-encodes it and
+//! compares the result with the golden frame, so a renamed field, a
+//! changed tag or a new `skip_serializing_if` fails the test suites of
+//! both the agent and the control plane instead of surfacing as a silent
+//! mismatch between two releases. Every variant of every direction enum
+//! must have at least one vector: adding a message type fails to compile
+//! here until it is listed, and fails [`check`] until it has a vector.
+//!
+//! Binary (CBOR) encodings will sit next to the JSON files once their
+//! byte layout is frozen.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use strata_protocol::{
+    AgentMessage, ControlMessage, DashboardClientMessage, DashboardEvent, Envelope,
+    ReceiverControlMessage, ReceiverMessage,
+};
+
+/// Protocol version the vectors in this build encode. Bumping
+/// [`strata_protocol::PROTOCOL_VERSION`] means adding a `v<N>` directory.
+pub const VERSION: u32 = 1;
+
+/// One WebSocket leg, and the direction enum its frames parse into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Leg {
+    /// Agent → control plane ([`AgentMessage`]).
+    Agent,
+    /// Control plane → agent ([`ControlMessage`]).
+    Control,
+    /// Receiver → control plane ([`ReceiverMessage`]).
+    Receiver,
+    /// Control plane → receiver ([`ReceiverControlMessage`]).
+    ReceiverControl,
+    /// Dashboard → control plane ([`DashboardClientMessage`]).
+    DashboardClient,
+    /// Control plane → dashboard ([`DashboardEvent`]).
+    DashboardEvent,
+}
+
+impl Leg {
+    pub const ALL: [Leg; 6] = [
+        Leg::Agent,
+        Leg::Control,
+        Leg::Receiver,
+        Leg::ReceiverControl,
+        Leg::DashboardClient,
+        Leg::DashboardEvent,
+    ];
+
+    fn golden(self) -> &'static str {
+        match self {
+            Leg::Agent => include_str!("../testvectors/v1/agent.json"),
+            Leg::Control => include_str!("../testvectors/v1/control.json"),
+            Leg::Receiver => include_str!("../testvectors/v1/receiver.json"),
+            Leg::ReceiverControl => include_str!("../testvectors/v1/receiver_control.json"),
+            Leg::DashboardClient => include_str!("../testvectors/v1/dashboard_client.json"),
+            Leg::DashboardEvent => include_str!("../testvectors/v1/dashboard_event.json"),
+        }
+    }
+}
+
+/// A golden frame that no longer encodes the way it is recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub leg: Leg,
+    /// Position of the frame in the leg's file; `None` for a coverage gap.
+    pub index: Option<usize>,
+    pub detail: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(i) => write!(f, "{:?} vector #{i}: {}", self.leg, self.detail),
+            None => write!(f, "{:?}: {}", self.leg, self.detail),
+        }
+    }
+}
+
+/// The golden frames for a leg, as recorded.
+pub fn vectors(leg: Leg) -> Vec<Value> {
+    serde_json::from_str(leg.golden()).expect("test vector files are valid JSON arrays")
+}
+
+/// Round-trip every golden frame of a leg through its direction enum.
+pub fn check(leg: Leg) -> Result<(), Vec<Mismatch>> {
+    match leg {
+        Leg::Agent => check_leg::<AgentMessage>(leg),
+        Leg::Control => check_leg::<ControlMessage>(leg),
+        Leg::Receiver => check_leg::<ReceiverMessage>(leg),
+        Leg::ReceiverControl => check_leg::<ReceiverControlMessage>(leg),
+        Leg::DashboardClient => check_leg::<DashboardClientMessage>(leg),
+        Leg::DashboardEvent => check_leg::<DashboardEvent>(leg),
+    }
+}
+
+/// [`check`] over every leg.
+pub fn check_all() -> Result<(), Vec<Mismatch>> {
+    let mismatches: Vec<Mismatch> = Leg::ALL
+        .into_iter()
+        .filter_map(|leg| check(leg).err())
+        .flatten()
+        .collect();
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+fn check_leg<M: Message>(leg: Leg) -> Result<(), Vec<Mismatch>> {
+    let mut mismatches = Vec::new();
+    let mut covered = BTreeSet::new();
+    for (index, frame) in vectors(leg).into_iter().enumerate() {
+        let mismatch = |detail: String| Mismatch {
+            leg,
+            index: Some(index),
+            detail,
+        };
+        match M::round_trip(&frame) {
+            Ok((msg, encoded)) => {
+                covered.insert(msg.variant());
+                if encoded != frame {
+                    mismatches.push(mismatch(format!(
+                        "re-encodes as {encoded}, golden is {frame}"
+                    )));
+                }
+            }
+            Err(e) => mismatches.push(mismatch(format!("does not decode: {e}"))),
+        }
+    }
+    if covered.len() < M::VARIANTS {
+        mismatches.push(Mismatch {
+            leg,
+            index: None,
+            detail: format!(
+                "vectors cover {} of {} variants ({}); add one for each new message",
+                covered.len(),
+                M::VARIANTS,
+                covered.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+        });
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// A direction enum with golden vectors.
+trait Message: Serialize + DeserializeOwned {
+    /// Number of variants; [`Message::variant`] maps each to its name.
+    const VARIANTS: usize;
+
+    fn variant(&self) -> &'static str;
+
+    /// Decode a golden frame and encode the message again.
+    fn round_trip(frame: &Value) -> Result<(Self, Value), serde_json::Error> {
+        let envelope: Envelope = serde_json::from_value(frame.clone())?;
+        let msg: Self = envelope.parse_message()?;
+        let mut encoded = Envelope::from_message(&msg)?;
+        encoded.id = envelope.id;
+        encoded.ts = envelope.ts;
+        Ok((msg, serde_json::to_value(&encoded)?))
+    }
+}
+
+impl Message for AgentMessage {
+    const VARIANTS: usize = 27;
+
+    fn variant(&self) -> &'static str {
+        use AgentMessage::*;
+        match self {
+            AuthLogin(_) => "AuthLogin",
+            DeviceStatus(_) => "DeviceStatus",
+            StreamStats(_) => "StreamStats",
+            StreamEnded(_) => "StreamEnded",
+            AuthChallengeResponse(_) => "AuthChallengeResponse",
+            ConfigSetResponse(_) => "ConfigSetResponse",
+            ConfigUpdateResponse(_) => "ConfigUpdateResponse",
+            TestRunResponse(_) => "TestRunResponse",
+            InterfacesScanResponse(_) => "InterfacesScanResponse",
+            InterfaceCommandResponse(_) => "InterfaceCommandResponse",
+            FilesListResponse(_) => "FilesListResponse",
+            NetworkToolResponse(_) => "NetworkToolResponse",
+            PcapCaptureResponse(_) => "PcapCaptureResponse",
+            LogsResponse(_) => "LogsResponse",
+            PowerCommandResponse(_) => "PowerCommandResponse",
+            TlsStatusResponse(_) => "TlsStatusResponse",
+            TlsRenewResponse(_) => "TlsRenewResponse",
+            ConfigExportResponse(_) => "ConfigExportResponse",
+            ConfigImportResponse(_) => "ConfigImportResponse",
+            UpdatesCheckResponse(_) => "UpdatesCheckResponse",
+            UpdatesInstallResponse(_) => "UpdatesInstallResponse",
+            StreamDestinationsResponse(_) => "StreamDestinationsResponse",
+            JitterBufferResponse(_) => "JitterBufferResponse",
+            SourceSwitchResponse(_) => "SourceSwitchResponse",
+            SourceSelectResponse(_) => "SourceSelectResponse",
+            CommandAck(_) => "CommandAck",
+            Nak(_) => "Nak",
+        }
+    }
+}
+
+impl Message for ControlMessage {
+    const VARIANTS: usize = 28;
+
+    fn variant(&self) -> &'static str {
+        use ControlMessage::*;
+        match self {
+            AuthLoginResponse(_) => "AuthLoginResponse",
+            AuthChallenge(_) => "AuthChallenge",
+            StreamStart(_) => "StreamStart",
+            StreamStop(_) => "StreamStop",
+            ConfigUpdate(_) => "ConfigUpdate",
+            SourceSwitch(_) => "SourceSwitch",
+            SourceSelect(_) => "SourceSelect",
+            InterfaceCommand(_) => "InterfaceCommand",
+            ConfigSet(_) => "ConfigSet",
+            TestRun(_) => "TestRun",
+            InterfacesScan(_) => "InterfacesScan",
+            FilesList(_) => "FilesList",
+            NetworkTool(_) => "NetworkTool",
+            PcapCapture(_) => "PcapCapture",
+            LogsGet(_) => "LogsGet",
+            PowerCommand(_) => "PowerCommand",
+            TlsStatus(_) => "TlsStatus",
+            TlsRenew(_) => "TlsRenew",
+            TlsInstall(_) => "TlsInstall",
+            ConfigExport(_) => "ConfigExport",
+            ConfigImport(_) => "ConfigImport",
+            UpdatesCheck(_) => "UpdatesCheck",
+            UpdatesInstall(_) => "UpdatesInstall",
+            StreamDestinations(_) => "StreamDestinations",
+            JitterBuffer(_) => "JitterBuffer",
+            MaintenanceSchedule(_) => "MaintenanceSchedule",
+            PortalAuth(_) => "PortalAuth",
+            Nak(_) => "Nak",
+        }
+    }
+}
+
+impl Message for ReceiverMessage {
+    const VARIANTS: usize = 6;
+
+    fn variant(&self) -> &'static str {
+        use ReceiverMessage::*;
+        match self {
+            AuthLogin(_) => "AuthLogin",
+            AuthChallengeResponse(_) => "AuthChallengeResponse",
+            StreamStarted(_) => "StreamStarted",
+            Status(_) => "Status",
+            StreamStats(_) => "StreamStats",
+            StreamEnded(_) => "StreamEnded",
+        }
+    }
+}
+
+impl Message for ReceiverControlMessage {
+    const VARIANTS: usize = 5;
+
+    fn variant(&self) -> &'static str {
+        use ReceiverControlMessage::*;
+        match self {
+            AuthLoginResponse(_) => "AuthLoginResponse",
+            AuthChallenge(_) => "AuthChallenge",
+            StreamStart(_) => "StreamStart",
+            StreamStop(_) => "StreamStop",
+            TlsInstall(_) => "TlsInstall",
+        }
+    }
+}
+
+impl Message for DashboardClientMessage {
+    const VARIANTS: usize = 2;
+
+    fn variant(&self) -> &'static str {
+        match self {
+            DashboardClientMessage::Subscribe(_) => "Subscribe",
+            DashboardClientMessage::Unsubscribe(_) => "Unsubscribe",
+        }
+    }
+}
+
+impl Message for DashboardEvent {
+    const VARIANTS: usize = 6;
+
+    fn variant(&self) -> &'static str {
+        match self {
+            DashboardEvent::SenderStatus { .. } => "SenderStatus",
+            DashboardEvent::StreamStats(_) => "StreamStats",
+            DashboardEvent::ReceiverStreamStats(_) => "ReceiverStreamStats",
+            DashboardEvent::StreamStateChanged { .. } => "StreamStateChanged",
+            DashboardEvent::Alert(_) => "Alert",
+            DashboardEvent::CommandResult { .. } => "CommandResult",
+        }
+    }
+
+    /// Dashboard events travel bare, not in an envelope.
+    fn round_trip(frame: &Value) -> Result<(Self, Value), serde_json::Error> {
+        let event: Self = serde_json::from_value(frame.clone())?;
+        let encoded = serde_json::to_value(&event)?;
+        Ok((event, encoded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_match_the_current_protocol_version() {
+        assert_eq!(VERSION, strata_protocol::PROTOCOL_VERSION);
+        for leg in Leg::ALL.into_iter().filter(|l| *l != Leg::DashboardEvent) {
+            for frame in vectors(leg) {
+                assert_eq!(frame["proto_version"], VERSION, "{leg:?}: {frame}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_golden_frame_round_trips() {
+        if let Err(mismatches) = check_all() {
+            let report: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+            panic!("wire format drifted:\n{}", report.join("\n"));
+        }
+    }
+
+    #[test]
+    fn a_changed_field_is_reported() {
+        let mut frame = vectors(Leg::Control).remove(0);
+        frame["payload"]["sender_id"] = Value::Null;
+        frame["payload"]["senderId"] = "snd_x".into();
+        let (_, encoded) = ControlMessage::round_trip(&frame).unwrap();
+        assert_ne!(encoded, frame, "unknown fields are dropped on re-encode");
+    }
+}
@@ -0,0 +1,429 @@
+//! TLS certificate handling shared by the control plane and the daemons.
+//!
+//! The control plane issues certificates (uploaded or via ACME) and pushes
+//! them to agents and receivers as PEM; each side needs to know what it is
+//! holding — subject, issuer, expiry — without a full X.509 stack.
+//! [`cert_info`] reads just those fields from the DER, and [`CertStore`]
+//! is the on-device home of the installed pair.
+//!
+//! With the `tls-server` feature, [`server`] serves an axum app over TLS
+//! with a certificate that can be swapped without restarting.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+#[cfg(feature = "tls-server")]
+pub mod server;
+
+/// A certificate that can't be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CertError {
+    #[error("no PEM certificate found")]
+    NoCertificate,
+    #[error("no PEM private key found")]
+    NoPrivateKey,
+    #[error("certificate is not valid DER: {0}")]
+    Malformed(&'static str),
+}
+
+/// The parts of a certificate the UI and expiry tracking care about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertInfo {
+    /// `CN=…, O=…` of the subject.
+    pub subject: String,
+    /// `CN=…, O=…` of the issuer.
+    pub issuer: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// Issuer and subject names are identical.
+    pub self_signed: bool,
+}
+
+impl CertInfo {
+    /// The subject's CN, usually the host name the certificate is for.
+    pub fn common_name(&self) -> Option<&str> {
+        self.subject
+            .split(", ")
+            .find_map(|part| part.strip_prefix("CN="))
+    }
+
+    /// Whole days until expiry at `now` (negative once expired).
+    pub fn days_left(&self, now: DateTime<Utc>) -> i64 {
+        (self.not_after - now).num_days()
+    }
+}
+
+/// Base64 bodies of every `-----BEGIN <label>-----` block in `pem`.
+fn pem_blocks(pem: &str, label: &str) -> Vec<Vec<u8>> {
+    let begin = format!("-----BEGIN {label}-----");
+    let end = format!("-----END {label}-----");
+    let mut blocks = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = rest.find(&begin) {
+        let body = &rest[start + begin.len()..];
+        let Some(stop) = body.find(&end) else { break };
+        let b64: String = body[..stop]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if let Ok(der) = base64::engine::general_purpose::STANDARD.decode(b64) {
+            blocks.push(der);
+        }
+        rest = &body[stop + end.len()..];
+    }
+    blocks
+}
+
+/// DER certificates in a PEM chain, leaf first.
+pub fn pem_certificates(pem: &str) -> Vec<Vec<u8>> {
+    pem_blocks(pem, "CERTIFICATE")
+}
+
+/// Whether `pem` holds a private key (PKCS#8, SEC1 or PKCS#1).
+pub fn has_private_key(pem: &str) -> bool {
+    ["PRIVATE KEY", "EC PRIVATE KEY", "RSA PRIVATE KEY"]
+        .iter()
+        .any(|label| !pem_blocks(pem, label).is_empty())
+}
+
+/// [`cert_info`] for the leaf of a PEM chain.
+pub fn pem_cert_info(pem: &str) -> Result<CertInfo, CertError> {
+    let chain = pem_certificates(pem);
+    cert_info(chain.first().ok_or(CertError::NoCertificate)?)
+}
+
+// ── DER ─────────────────────────────────────────────────────────────
+
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const OID: u8 = 0x06;
+const UTC_TIME: u8 = 0x17;
+const GENERALIZED_TIME: u8 = 0x18;
+const VERSION: u8 = 0xA0;
+
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+const OID_ORGANIZATION: &[u8] = &[0x55, 0x04, 0x0A];
+
+/// Split one TLV off the front of `input`: `(tag, value, rest)`.
+fn tlv(input: &[u8]) -> Result<(u8, &[u8], &[u8]), CertError> {
+    let [tag, first, rest @ ..] = input else {
+        return Err(CertError::Malformed("truncated header"));
+    };
+    let (len, rest) = if first & 0x80 == 0 {
+        (*first as usize, rest)
+    } else {
+        let n = (first & 0x7F) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return Err(CertError::Malformed("bad length"));
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, &rest[n..])
+    };
+    if rest.len() < len {
+        return Err(CertError::Malformed("truncated value"));
+    }
+    Ok((*tag, &rest[..len], &rest[len..]))
+}
+
+fn expect(input: &[u8], want: u8) -> Result<(&[u8], &[u8]), CertError> {
+    match tlv(input)? {
+        (tag, value, rest) if tag == want => Ok((value, rest)),
+        _ => Err(CertError::Malformed("unexpected tag")),
+    }
+}
+
+fn parse_time(tag: u8, value: &[u8]) -> Result<DateTime<Utc>, CertError> {
+    let s = std::str::from_utf8(value).map_err(|_| CertError::Malformed("time"))?;
+    let full = match tag {
+        // Two-digit years: 50–99 are 19xx, 00–49 are 20xx (RFC 5280).
+        UTC_TIME if s.len() == 13 => {
+            let century = if s[..2] < *"50" { "20" } else { "19" };
+            format!("{century}{s}")
+        }
+        GENERALIZED_TIME if s.len() == 15 => s.to_string(),
+        _ => return Err(CertError::Malformed("time")),
+    };
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%SZ")
+        .map(|t| t.and_utc())
+        .map_err(|_| CertError::Malformed("time"))
+}
+
+/// Render the CN and O attributes of a Name as `CN=…, O=…`.
+fn render_name(mut rdns: &[u8]) -> Result<String, CertError> {
+    let mut cn = None;
+    let mut org = None;
+    while !rdns.is_empty() {
+        let (set, rest) = expect(rdns, SET)?;
+        rdns = rest;
+        let (attr, _) = expect(set, SEQUENCE)?;
+        let (oid, value) = expect(attr, OID)?;
+        let (_, value, _) = tlv(value)?;
+        let value = String::from_utf8_lossy(value).into_owned();
+        match oid {
+            OID_COMMON_NAME => cn = Some(value),
+            OID_ORGANIZATION => org = Some(value),
+            _ => {}
+        }
+    }
+    let parts: Vec<String> = [("CN", cn), ("O", org)]
+        .into_iter()
+        .filter_map(|(k, v)| v.map(|v| format!("{k}={v}")))
+        .collect();
+    Ok(parts.join(", "))
+}
+
+/// Read subject, issuer and validity from a DER certificate.
+pub fn cert_info(der: &[u8]) -> Result<CertInfo, CertError> {
+    let (cert, _) = expect(der, SEQUENCE)?;
+    let (tbs, _) = expect(cert, SEQUENCE)?;
+
+    let mut rest = tbs;
+    if rest.first() == Some(&VERSION) {
+        rest = tlv(rest)?.2;
+    }
+    let (_serial, _, rest) = tlv(rest)?;
+    let (_sig_alg, rest) = expect(rest, SEQUENCE)?;
+    let (issuer, rest) = expect(rest, SEQUENCE)?;
+    let (validity, rest) = expect(rest, SEQUENCE)?;
+    let (subject, _) = expect(rest, SEQUENCE)?;
+
+    let (tag, not_before, validity) = tlv(validity)?;
+    let not_before = parse_time(tag, not_before)?;
+    let (tag, not_after, _) = tlv(validity)?;
+    let not_after = parse_time(tag, not_after)?;
+
+    Ok(CertInfo {
+        subject: render_name(subject)?,
+        issuer: render_name(issuer)?,
+        not_before,
+        not_after,
+        self_signed: issuer == subject,
+    })
+}
+
+// ── On-device Store ─────────────────────────────────────────────────
+
+/// Where a daemon keeps its installed certificate: `cert.pem` (the chain)
+/// and `key.pem` (owner-only) in a version directory, with a `current`
+/// file naming the live one. Installing writes a new version and then
+/// switches `current` with one rename, so a crash part-way leaves the old
+/// pair in place rather than a new certificate with an old key.
+#[derive(Debug, Clone)]
+pub struct CertStore {
+    dir: PathBuf,
+}
+
+/// File naming the live version directory.
+const CURRENT: &str = "current";
+
+impl CertStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the live pair. Without a `current` file that is
+    /// the store directory itself, where pairs were installed before they
+    /// were versioned.
+    fn live_dir(&self) -> PathBuf {
+        match std::fs::read_to_string(self.dir.join(CURRENT)) {
+            Ok(version) if !version.trim().is_empty() => self.dir.join(version.trim()),
+            _ => self.dir.clone(),
+        }
+    }
+
+    pub fn cert_path(&self) -> PathBuf {
+        self.live_dir().join("cert.pem")
+    }
+
+    pub fn key_path(&self) -> PathBuf {
+        self.live_dir().join("key.pem")
+    }
+
+    /// Check and persist a certificate chain and its key, replacing the
+    /// current pair.
+    pub fn install(&self, cert_pem: &str, key_pem: &str) -> io::Result<CertInfo> {
+        let info = pem_cert_info(cert_pem).map_err(io::Error::other)?;
+        if !has_private_key(key_pem) {
+            return Err(io::Error::other(CertError::NoPrivateKey));
+        }
+        let version = format!("v{}", uuid::Uuid::now_v7().simple());
+        let staged = self.dir.join(&version);
+        std::fs::create_dir_all(&staged)?;
+        write_atomic(&staged.join("key.pem"), key_pem, 0o600)?;
+        write_atomic(&staged.join("cert.pem"), cert_pem, 0o644)?;
+        write_atomic(&self.dir.join(CURRENT), &version, 0o644)?;
+        self.prune(&version);
+        Ok(info)
+    }
+
+    /// Remove every pair but `live`. Best effort: leftovers are only disk.
+    fn prune(&self, live: &str) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('v') && name != live && entry.path().is_dir() {
+                let _ = std::fs::remove_dir_all(entry.path());
+            }
+        }
+        for legacy in ["cert.pem", "key.pem"] {
+            let _ = std::fs::remove_file(self.dir.join(legacy));
+        }
+    }
+
+    /// The installed `(cert_pem, key_pem)`, if any.
+    pub fn load(&self) -> io::Result<Option<(String, String)>> {
+        let live = self.live_dir();
+        match (
+            std::fs::read_to_string(live.join("cert.pem")),
+            std::fs::read_to_string(live.join("key.pem")),
+        ) {
+            (Ok(cert), Ok(key)) => Ok(Some((cert, key))),
+            (Err(e), _) | (_, Err(e)) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        }
+    }
+
+    /// What the installed certificate is, if there is a readable one.
+    pub fn info(&self) -> Option<CertInfo> {
+        let cert = std::fs::read_to_string(self.cert_path()).ok()?;
+        pem_cert_info(&cert).ok()
+    }
+}
+
+/// Write `path` aside and rename it into place.
+fn write_atomic(path: &Path, contents: &str, mode: u32) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+        #[cfg(not(unix))]
+        let _ = mode;
+        let mut file = options.open(&tmp)?;
+        io::Write::write_all(&mut file, contents.as_bytes())?;
+        file.sync_all()?;
+    }
+    std::fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed, `O=Strata Test, CN=portal.example.com`, valid
+    /// 2026-01-01 to 2026-04-01 (UTCTime).
+    const SELF_SIGNED: &str = include_str!("tls/testdata/self_signed.pem");
+    /// `CN=rx1.example.com` issued by `CN=Strata Test CA`, valid until
+    /// 2051 (GeneralizedTime).
+    const CA_SIGNED: &str = include_str!("tls/testdata/leaf.pem");
+    const KEY: &str = include_str!("tls/testdata/leaf.key");
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn reads_self_signed_certificate() {
+        let info = pem_cert_info(SELF_SIGNED).unwrap();
+        assert_eq!(info.subject, "CN=portal.example.com, O=Strata Test");
+        assert_eq!(info.common_name(), Some("portal.example.com"));
+        assert_eq!(info.issuer, info.subject);
+        assert!(info.self_signed);
+        assert_eq!(info.not_before, utc("2026-01-01T00:00:00Z"));
+        assert_eq!(info.not_after, utc("2026-04-01T00:00:00Z"));
+        assert_eq!(info.days_left(utc("2026-03-11T00:00:00Z")), 21);
+    }
+
+    #[test]
+    fn reads_ca_signed_certificate_with_generalized_time() {
+        let chain = format!("{CA_SIGNED}{SELF_SIGNED}");
+        assert_eq!(pem_certificates(&chain).len(), 2);
+        let info = pem_cert_info(&chain).unwrap();
+        assert_eq!(info.subject, "CN=rx1.example.com");
+        assert_eq!(info.issuer, "CN=Strata Test CA");
+        assert!(!info.self_signed);
+        assert_eq!(info.not_after, utc("2051-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(pem_cert_info("hello"), Err(CertError::NoCertificate));
+        let truncated = &pem_certificates(SELF_SIGNED)[0][..40];
+        assert!(cert_info(truncated).is_err());
+        assert!(has_private_key(KEY));
+        assert!(!has_private_key(SELF_SIGNED));
+    }
+
+    #[test]
+    fn store_round_trips_the_pair() {
+        let dir = std::env::temp_dir().join(format!("strata-tls-{}", uuid::Uuid::now_v7()));
+        let store = CertStore::new(&dir);
+        assert!(store.load().unwrap().is_none());
+        assert!(store.install(CA_SIGNED, SELF_SIGNED).is_err(), "no key");
+
+        let info = store.install(CA_SIGNED, KEY).unwrap();
+        assert_eq!(info.subject, "CN=rx1.example.com");
+        assert_eq!(store.load().unwrap(), Some((CA_SIGNED.into(), KEY.into())));
+        assert_eq!(store.info(), Some(info));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(store.key_path())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn store_switches_pairs_as_a_whole() {
+        let dir = std::env::temp_dir().join(format!("strata-tls-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // A pair installed before versioning is still served...
+        std::fs::write(dir.join("cert.pem"), SELF_SIGNED).unwrap();
+        std::fs::write(dir.join("key.pem"), KEY).unwrap();
+        let store = CertStore::new(&dir);
+        assert_eq!(
+            store.load().unwrap(),
+            Some((SELF_SIGNED.into(), KEY.into()))
+        );
+
+        // ...until a new one replaces it.
+        store.install(CA_SIGNED, KEY).unwrap();
+        assert_eq!(store.load().unwrap(), Some((CA_SIGNED.into(), KEY.into())));
+        assert!(!dir.join("cert.pem").exists());
+
+        // A version written but never switched to (a crash mid-install)
+        // changes nothing, and the next install clears it away.
+        let abandoned = dir.join("v0");
+        std::fs::create_dir_all(&abandoned).unwrap();
+        std::fs::write(abandoned.join("cert.pem"), SELF_SIGNED).unwrap();
+        assert_eq!(store.load().unwrap(), Some((CA_SIGNED.into(), KEY.into())));
+
+        store.install(SELF_SIGNED, KEY).unwrap();
+        assert_eq!(
+            store.load().unwrap(),
+            Some((SELF_SIGNED.into(), KEY.into()))
+        );
+        let versions = std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.path().is_dir())
+            .count();
+        assert_eq!(versions, 1, "older versions are pruned");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+//! Serving HTTPS with a hot-swappable certificate.
+//!
+//! [`ReloadableCert`] is the rustls resolver: daemons hand it a new PEM
+//! pair when the control plane pushes one and the next handshake uses it.
+//! [`ServedCert`] pairs it with the on-disk [`CertStore`] so the pair
+//! survives restarts. [`TlsListener`] plugs it into `axum::serve`.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+
+use super::{CertInfo, CertStore};
+
+/// A handshake that hasn't finished by now is dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The certificate currently served. Until one is set, handshakes fail.
+#[derive(Debug, Default)]
+pub struct ReloadableCert {
+    current: RwLock<Option<Arc<CertifiedKey>>>,
+}
+
+impl ReloadableCert {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Swap in a new chain and key. The old pair stays on any error.
+    pub fn set_pem(&self, cert_pem: &str, key_pem: &str) -> io::Result<()> {
+        let chain = CertificateDer::pem_slice_iter(cert_pem.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io::Error::other)?;
+        if chain.is_empty() {
+            return Err(io::Error::other(super::CertError::NoCertificate));
+        }
+        let key = PrivateKeyDer::from_pem_slice(key_pem.as_bytes())
+            .map_err(|_| io::Error::other(super::CertError::NoPrivateKey))?;
+        let signing_key =
+            rustls::crypto::ring::sign::any_supported_type(&key).map_err(io::Error::other)?;
+        let certified = CertifiedKey::new(chain, signing_key);
+        certified.keys_match().map_err(io::Error::other)?;
+        *self.current.write().unwrap() = Some(Arc::new(certified));
+        Ok(())
+    }
+
+    /// Whether a certificate has been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.current.read().unwrap().is_some()
+    }
+
+    /// A rustls server config resolving through `self`.
+    pub fn server_config(self: &Arc<Self>) -> Arc<ServerConfig> {
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default protocol versions")
+                .with_no_client_auth()
+                .with_cert_resolver(self.clone());
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Arc::new(config)
+    }
+}
+
+impl ResolvesServerCert for ReloadableCert {
+    fn resolve(&self, _hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        self.current.read().unwrap().clone()
+    }
+}
+
+/// The certificate a daemon serves: persisted in a [`CertStore`], live in
+/// a [`ReloadableCert`]. Until the first one is installed, handshakes fail.
+#[derive(Debug)]
+pub struct ServedCert {
+    store: CertStore,
+    cert: Arc<ReloadableCert>,
+}
+
+impl ServedCert {
+    /// Open the store in `dir` and start serving whatever it holds.
+    pub fn load(dir: impl Into<std::path::PathBuf>) -> Self {
+        let served = Self {
+            store: CertStore::new(dir),
+            cert: ReloadableCert::new(),
+        };
+        match served.store.load() {
+            Ok(Some((cert_pem, key_pem))) => {
+                if let Err(e) = served.cert.set_pem(&cert_pem, &key_pem) {
+                    tracing::warn!(error = %e, "stored certificate is unusable");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "failed to read stored certificate"),
+        }
+        served
+    }
+
+    /// Serve a new pair at once and persist it. A pair rustls can't use
+    /// (mismatched key, unsupported algorithm) is rejected before anything
+    /// is written.
+    pub fn install(&self, cert_pem: &str, key_pem: &str) -> io::Result<CertInfo> {
+        self.cert.set_pem(cert_pem, key_pem)?;
+        self.store.install(cert_pem, key_pem)
+    }
+
+    /// The certificate being served, if any.
+    pub fn info(&self) -> Option<CertInfo> {
+        if !self.cert.is_loaded() {
+            return None;
+        }
+        self.store.info()
+    }
+
+    pub fn resolver(&self) -> Arc<ReloadableCert> {
+        self.cert.clone()
+    }
+}
+
+/// An `axum::serve` listener that terminates TLS. Handshakes run on their
+/// own tasks so one slow client doesn't hold up the accept loop.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    ready: mpsc::Receiver<(TlsStream<tokio::net::TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    pub fn new(tcp: TcpListener, cert: Arc<ReloadableCert>) -> io::Result<Self> {
+        let local_addr = tcp.local_addr()?;
+        let acceptor = TlsAcceptor::from(cert.server_config());
+        let (tx, ready) = mpsc::channel(64);
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                let (stream, peer) = match tcp.accept().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::debug!(error = %e, "tls accept failed");
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        continue;
+                    }
+                };
+                let acceptor = acceptor.clone();
+                let ready = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls)) => {
+                            let _ = ready.send((tls, peer)).await;
+                        }
+                        Ok(Err(e)) => tracing::debug!(%peer, error = %e, "tls handshake failed"),
+                        Err(_) => tracing::debug!(%peer, "tls handshake timed out"),
+                    }
+                });
+            }
+        });
+        Ok(Self { local_addr, ready })
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<tokio::net::TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.ready.recv().await {
+            Some(conn) => conn,
+            // The accept task only exits once we're gone.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_key_for_another_certificate() {
+        let cert = ReloadableCert::new();
+        assert!(!cert.is_loaded());
+        let other_cert = include_str!("testdata/self_signed.pem");
+        let key = include_str!("testdata/leaf.key");
+        assert!(cert.set_pem(other_cert, key).is_err());
+        assert!(!cert.is_loaded());
+        cert.set_pem(include_str!("testdata/leaf.pem"), key)
+            .unwrap();
+        assert!(cert.is_loaded());
+    }
+}
@@ -0,0 +1,634 @@
+//! Config validation shared by the control plane and the agents.
+//!
+//! The control plane validates a request before it stores or forwards it;
+//! the agent validates the same payload again before acting on it (it may
+//! also come from the local portal, or from a control plane of another
+//! version). Both go through [`Validate`] so they accept and reject
+//! exactly the same configs, with the same messages.
+
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
+
+use strata_protocol::{
+    EncoderConfig, InputSource, LadderRung, MAIN_INPUT, OverlayConfig, SourceConfig,
+    StreamStartPayload,
+};
+
+use crate::error::{ErrorCode, StrataError};
+
+/// Accepted encoder bitrate (kbps).
+pub const BITRATE_KBPS: RangeInclusive<u32> = 300..=50_000;
+/// Accepted capture framerates.
+pub const FRAMERATE: RangeInclusive<u32> = 1..=120;
+/// Codecs the pipeline can encode.
+pub const CODECS: &[&str] = &["h264", "h265"];
+/// Switchable inputs a stream may carry besides its primary source.
+pub const MAX_INPUTS: usize = 8;
+/// Longest overlay caption, in characters.
+pub const MAX_OVERLAY_TEXT: usize = 64;
+/// Schemes the sender can relay its encoded output to.
+pub const RELAY_SCHEMES: &[&str] = &["rtmp", "rtmps", "srt", "https"];
+
+/// A config value that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct ValidationError {
+    /// Dotted path of the offending field, e.g. `"encoder.bitrate_kbps"`.
+    pub field: String,
+    /// User-facing explanation.
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// The same error, reported under `parent` (`"bitrate_kbps"` inside
+    /// `"encoder"` becomes `"encoder.bitrate_kbps"`).
+    fn within(mut self, parent: &str) -> Self {
+        self.field = format!("{parent}.{}", self.field);
+        self
+    }
+}
+
+impl From<ValidationError> for StrataError {
+    fn from(e: ValidationError) -> Self {
+        StrataError::new(ErrorCode::InvalidInput, e.message)
+    }
+}
+
+/// Structural checks on a config, independent of the machine it runs on.
+/// Checks that need local state (does this capture device exist?) stay
+/// with the caller.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+impl Validate for SourceConfig {
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_source_mode(&self.mode, self.device.as_deref(), self.uri.as_deref())?;
+        if let Some(resolution) = self.resolution.as_deref()
+            && parse_resolution(resolution).is_none()
+        {
+            return Err(ValidationError::new(
+                "resolution",
+                format!("resolution {resolution:?} is not WIDTHxHEIGHT"),
+            ));
+        }
+        if let Some(fps) = self.framerate
+            && !FRAMERATE.contains(&fps)
+        {
+            return Err(ValidationError::new(
+                "framerate",
+                format!(
+                    "framerate must be between {} and {}",
+                    FRAMERATE.start(),
+                    FRAMERATE.end()
+                ),
+            ));
+        }
+        if self.inputs.len() > MAX_INPUTS {
+            return Err(ValidationError::new(
+                "inputs",
+                format!("at most {MAX_INPUTS} inputs"),
+            ));
+        }
+        if !self.inputs.is_empty() && self.passthrough == Some(true) {
+            return Err(ValidationError::new(
+                "inputs",
+                "a passthrough source can't switch inputs",
+            ));
+        }
+        for (i, input) in self.inputs.iter().enumerate() {
+            input
+                .validate()
+                .map_err(|e| e.within(&format!("inputs[{i}]")))?;
+            if input.id == MAIN_INPUT || self.inputs[..i].iter().any(|o| o.id == input.id) {
+                return Err(ValidationError::new(
+                    format!("inputs[{i}].id"),
+                    format!("input id {:?} is already taken", input.id),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The mode is known and names what it captures.
+fn check_source_mode(
+    mode: &str,
+    device: Option<&str>,
+    uri: Option<&str>,
+) -> Result<(), ValidationError> {
+    match mode {
+        "test" => {}
+        "v4l2" => {
+            if device.is_none_or(|d| d.trim().is_empty()) {
+                return Err(ValidationError::new("device", "choose a capture device"));
+            }
+        }
+        "uri" => {
+            if uri.is_none_or(|u| u.trim().is_empty()) {
+                return Err(ValidationError::new("uri", "enter a source URI"));
+            }
+        }
+        other => {
+            return Err(ValidationError::new(
+                "mode",
+                format!("unknown source mode {other:?}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl Validate for InputSource {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if self.id.is_empty()
+            || self.id.len() > 32
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ValidationError::new(
+                "id",
+                "use up to 32 letters, digits, '-' or '_'",
+            ));
+        }
+        check_source_mode(&self.mode, self.device.as_deref(), self.uri.as_deref())
+    }
+}
+
+impl Validate for EncoderConfig {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if !BITRATE_KBPS.contains(&self.bitrate_kbps) {
+            return Err(ValidationError::new(
+                "bitrate_kbps",
+                format!(
+                    "bitrate must be between {} and {} kbps",
+                    BITRATE_KBPS.start(),
+                    BITRATE_KBPS.end()
+                ),
+            ));
+        }
+        if let Some(codec) = self.codec.as_deref()
+            && !CODECS.contains(&codec)
+        {
+            return Err(ValidationError::new(
+                "codec",
+                format!("unsupported codec {codec:?}"),
+            ));
+        }
+        if let (Some(min), Some(max)) = (self.min_bitrate_kbps, self.max_bitrate_kbps)
+            && min > max
+        {
+            return Err(ValidationError::new(
+                "min_bitrate_kbps",
+                format!("minimum bitrate {min} kbps is above the maximum {max} kbps"),
+            ));
+        }
+        if self.keyint_max == Some(0) {
+            return Err(ValidationError::new(
+                "keyint_max",
+                "keyframe interval must be at least 1",
+            ));
+        }
+        let mut above: Option<(u32, &LadderRung)> = None;
+        for (i, rung) in self.ladder.iter().enumerate() {
+            let field = |name: &str| format!("ladder[{i}].{name}");
+            let Some((_, height)) = parse_resolution(&rung.resolution) else {
+                return Err(ValidationError::new(
+                    field("resolution"),
+                    format!("resolution {:?} is not WIDTHxHEIGHT", rung.resolution),
+                ));
+            };
+            if !BITRATE_KBPS.contains(&rung.min_kbps) {
+                return Err(ValidationError::new(
+                    field("min_kbps"),
+                    format!(
+                        "rung bitrate must be between {} and {} kbps",
+                        BITRATE_KBPS.start(),
+                        BITRATE_KBPS.end()
+                    ),
+                ));
+            }
+            if let Some((above_height, above)) = above {
+                if height > above_height {
+                    return Err(ValidationError::new(
+                        field("resolution"),
+                        format!("{} is taller than the rung above it", rung.resolution),
+                    ));
+                }
+                if rung.min_kbps >= above.min_kbps {
+                    return Err(ValidationError::new(
+                        field("min_kbps"),
+                        format!(
+                            "{} kbps must be below the rung above it ({} kbps)",
+                            rung.min_kbps, above.min_kbps
+                        ),
+                    ));
+                }
+            }
+            above = Some((height, rung));
+        }
+        if let Some(overlay) = &self.overlay {
+            if overlay.logo.is_none() && overlay.text.is_none() && !overlay.clock {
+                return Err(ValidationError::new(
+                    "overlay",
+                    "set a logo, a caption or the clock",
+                ));
+            }
+            overlay.validate().map_err(|e| e.within("overlay"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for OverlayConfig {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(logo) = self.logo.as_deref()
+            && (!logo.starts_with('/') || !logo.to_ascii_lowercase().ends_with(".png"))
+        {
+            return Err(ValidationError::new(
+                "logo",
+                "logo must be an absolute path to a PNG",
+            ));
+        }
+        if let Some(text) = self.text.as_deref()
+            && (text.chars().count() > MAX_OVERLAY_TEXT || text.chars().any(char::is_control))
+        {
+            return Err(ValidationError::new(
+                "text",
+                format!("caption must be one line of at most {MAX_OVERLAY_TEXT} characters"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Encoder settings that only apply to a re-encoded source. A ladder
+/// starts where the source is: its top rung is the resolution the
+/// pipeline captures at, so there must be one to compare against.
+pub fn validate_encoder_source(
+    source: &SourceConfig,
+    encoder: &EncoderConfig,
+) -> Result<(), ValidationError> {
+    if encoder.overlay.is_some() && source.passthrough == Some(true) {
+        return Err(ValidationError::new(
+            "encoder.overlay",
+            "a passthrough source is not re-encoded, so it can't carry an overlay",
+        ));
+    }
+    let Some(top) = encoder.ladder.first() else {
+        return Ok(());
+    };
+    if source.passthrough == Some(true) {
+        return Err(ValidationError::new(
+            "encoder.ladder",
+            "a passthrough source is not re-encoded, so it has no ladder",
+        ));
+    }
+    match source.resolution.as_deref() {
+        Some(res) if res == top.resolution => Ok(()),
+        Some(res) => Err(ValidationError::new(
+            "encoder.ladder[0].resolution",
+            format!(
+                "the top rung ({}) must match the source resolution ({res})",
+                top.resolution
+            ),
+        )),
+        None => Err(ValidationError::new(
+            "source.resolution",
+            "set a source resolution to use a resolution ladder",
+        )),
+    }
+}
+
+impl Validate for StreamStartPayload {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.source.validate().map_err(|e| e.within("source"))?;
+        self.encoder.validate().map_err(|e| e.within("encoder"))?;
+        validate_encoder_source(&self.source, &self.encoder)?;
+        if let Some(relay) = self.relay_url.as_deref() {
+            parse_url(relay, RELAY_SCHEMES).map_err(|e| e.within("relay_url"))?;
+        }
+        Ok(())
+    }
+}
+
+/// `"1920x1080"` → `(1920, 1080)`.
+pub fn parse_resolution(s: &str) -> Option<(u32, u32)> {
+    let (w, h) = s.split_once('x')?;
+    let (w, h) = (w.parse::<u32>().ok()?, h.parse::<u32>().ok()?);
+    (w > 0 && h > 0).then_some((w, h))
+}
+
+/// A network URL split into the parts streaming cares about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedUrl {
+    /// Lowercased.
+    pub scheme: String,
+    /// Brackets stripped from IPv6 literals.
+    pub host: String,
+    /// Explicit, or the scheme's default.
+    pub port: u16,
+}
+
+/// Parse `url`, requiring one of `schemes` and a valid host. The port
+/// defaults for rtmp (1935) and rtmps/https (443); other schemes (SRT)
+/// have no default and must carry one.
+pub fn parse_url(url: &str, schemes: &[&str]) -> Result<ParsedUrl, ValidationError> {
+    let err = |message: String| ValidationError::new("url", message);
+    let (scheme, rest) = url
+        .trim()
+        .split_once("://")
+        .ok_or_else(|| err("URL must start with a scheme, e.g. rtmp://".into()))?;
+    let scheme = scheme.to_ascii_lowercase();
+    if !schemes.contains(&scheme.as_str()) {
+        return Err(err(format!(
+            "{scheme}:// is not supported here (expected {})",
+            schemes
+                .iter()
+                .map(|s| format!("{s}://"))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )));
+    }
+
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    let (host, port) = if let Some(v6) = authority.strip_prefix('[') {
+        let (host, after) = v6
+            .split_once(']')
+            .ok_or_else(|| err("malformed IPv6 host".into()))?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(err("URL has no host".into()));
+    }
+    validate_host(host).map_err(|e| err(e.message))?;
+
+    let port = match port {
+        Some(p) => p
+            .parse::<u16>()
+            .ok()
+            .filter(|&p| p != 0)
+            .ok_or_else(|| err(format!("invalid port: {p}")))?,
+        None => match scheme.as_str() {
+            "rtmp" => 1935,
+            "rtmps" | "https" => 443,
+            _ => return Err(err(format!("{scheme}:// URLs need an explicit port"))),
+        },
+    };
+    Ok(ParsedUrl {
+        scheme,
+        host: host.to_string(),
+        port,
+    })
+}
+
+/// Accept an IP address literal or an RFC 1123 hostname: dot-separated
+/// labels of 1–63 letters, digits and hyphens, not starting or ending with
+/// a hyphen, 253 characters at most. A trailing dot (FQDN) is allowed.
+pub fn validate_host(host: &str) -> Result<(), ValidationError> {
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    let err = || ValidationError::new("host", format!("{host:?} is not a valid hostname"));
+    let name = host.strip_suffix('.').unwrap_or(host);
+    if name.is_empty() || name.len() > 253 {
+        return Err(err());
+    }
+    let label_ok = |label: &str| {
+        (1..=63).contains(&label.len())
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+    if !name.split('.').all(label_ok) {
+        return Err(err());
+    }
+    // An all-numeric name is a mistyped IPv4 address, not a hostname.
+    if name.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return Err(err());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoder(bitrate_kbps: u32) -> EncoderConfig {
+        EncoderConfig {
+            bitrate_kbps,
+            tune: None,
+            keyint_max: Some(60),
+            codec: Some("h265".into()),
+            min_bitrate_kbps: Some(800),
+            max_bitrate_kbps: Some(4000),
+            ladder: Vec::new(),
+            overlay: None,
+        }
+    }
+
+    fn source(mode: &str) -> SourceConfig {
+        SourceConfig {
+            mode: mode.into(),
+            device: None,
+            uri: None,
+            resolution: Some("1280x720".into()),
+            framerate: Some(30),
+            passthrough: None,
+            inputs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stream_config_bounds() {
+        assert!(encoder(3000).validate().is_ok());
+        assert_eq!(encoder(100).validate().unwrap_err().field, "bitrate_kbps");
+        let mut e = encoder(3000);
+        e.codec = Some("vp9".into());
+        assert!(e.validate().is_err());
+        e = encoder(3000);
+        e.min_bitrate_kbps = Some(5000);
+        assert!(e.validate().is_err());
+
+        assert!(source("test").validate().is_ok());
+        assert!(source("uri").validate().is_err());
+        assert!(source("v4l2").validate().is_err());
+        assert!(source("ndi").validate().is_err());
+        let mut s = source("test");
+        s.resolution = Some("1080p".into());
+        assert_eq!(s.validate().unwrap_err().field, "resolution");
+        s = source("test");
+        s.framerate = Some(0);
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn inputs_need_unique_ids_and_a_usable_source() {
+        let input = |id: &str, mode: &str| InputSource {
+            id: id.into(),
+            mode: mode.into(),
+            device: None,
+            uri: None,
+            pattern: None,
+        };
+        let mut s = source("test");
+        s.inputs = vec![input("wide", "test"), input("slate", "test")];
+        assert!(s.validate().is_ok());
+
+        s.inputs[1].id = "wide".into();
+        assert_eq!(s.validate().unwrap_err().field, "inputs[1].id");
+        s.inputs[1].id = MAIN_INPUT.into();
+        assert_eq!(s.validate().unwrap_err().field, "inputs[1].id");
+        s.inputs[1] = input("close", "v4l2");
+        assert_eq!(s.validate().unwrap_err().field, "inputs[1].device");
+        s.inputs[1] = input("bad id", "test");
+        assert_eq!(s.validate().unwrap_err().field, "inputs[1].id");
+
+        s.inputs.truncate(1);
+        s.passthrough = Some(true);
+        assert_eq!(s.validate().unwrap_err().field, "inputs");
+    }
+
+    #[test]
+    fn nested_errors_carry_their_path() {
+        let payload = StreamStartPayload {
+            stream_id: "str_x".into(),
+            source: source("test"),
+            encoder: encoder(10),
+            destinations: vec![],
+            bonding_config: serde_json::Value::Null,
+            psk: None,
+            relay_url: None,
+            ingest_key: None,
+        };
+        assert_eq!(
+            payload.validate().unwrap_err().field,
+            "encoder.bitrate_kbps"
+        );
+    }
+
+    #[test]
+    fn ladders_step_down_from_the_source_resolution() {
+        let rung = |resolution: &str, min_kbps| LadderRung {
+            resolution: resolution.into(),
+            min_kbps,
+        };
+        let mut e = encoder(3000);
+        e.ladder = vec![rung("1280x720", 1500), rung("854x480", 600)];
+        assert!(e.validate().is_ok());
+        assert!(validate_encoder_source(&source("test"), &e).is_ok());
+
+        let mut s = source("test");
+        s.resolution = Some("1920x1080".into());
+        assert_eq!(
+            validate_encoder_source(&s, &e).unwrap_err().field,
+            "encoder.ladder[0].resolution"
+        );
+        s.resolution = None;
+        assert_eq!(
+            validate_encoder_source(&s, &e).unwrap_err().field,
+            "source.resolution"
+        );
+
+        e.ladder = vec![rung("1280x720", 1500), rung("854x480", 1500)];
+        assert_eq!(e.validate().unwrap_err().field, "ladder[1].min_kbps");
+        e.ladder = vec![rung("854x480", 1500), rung("1280x720", 600)];
+        assert_eq!(e.validate().unwrap_err().field, "ladder[1].resolution");
+        e.ladder = vec![rung("720p", 1500)];
+        assert_eq!(e.validate().unwrap_err().field, "ladder[0].resolution");
+    }
+
+    #[test]
+    fn overlays_need_content_and_a_reencoded_source() {
+        let mut e = encoder(3000);
+        e.overlay = Some(OverlayConfig {
+            logo: Some("/etc/strata/logo.png".into()),
+            text: Some("CAM 1".into()),
+            clock: true,
+            ..Default::default()
+        });
+        assert!(e.validate().is_ok());
+        assert!(validate_encoder_source(&source("test"), &e).is_ok());
+
+        let mut s = source("uri");
+        s.passthrough = Some(true);
+        assert_eq!(
+            validate_encoder_source(&s, &e).unwrap_err().field,
+            "encoder.overlay"
+        );
+
+        let overlay = e.overlay.as_mut().unwrap();
+        overlay.text = Some("two\nlines".into());
+        assert_eq!(e.validate().unwrap_err().field, "overlay.text");
+        let overlay = e.overlay.as_mut().unwrap();
+        overlay.text = None;
+        overlay.logo = Some("logo.png".into());
+        assert_eq!(e.validate().unwrap_err().field, "overlay.logo");
+        e.overlay = Some(OverlayConfig::default());
+        assert_eq!(e.validate().unwrap_err().field, "overlay");
+    }
+
+    #[test]
+    fn urls_need_an_allowed_scheme_and_a_real_host() {
+        let rtmp = &["rtmp", "rtmps"];
+        assert_eq!(
+            parse_url("RTMP://a.rtmp.youtube.com/live2", rtmp).unwrap(),
+            ParsedUrl {
+                scheme: "rtmp".into(),
+                host: "a.rtmp.youtube.com".into(),
+                port: 1935
+            }
+        );
+        assert_eq!(
+            parse_url("srt://[::1]:9000?streamid=x", &["srt"])
+                .unwrap()
+                .host,
+            "::1"
+        );
+        assert!(parse_url("http://host/app", rtmp).is_err());
+        assert!(parse_url("rtmp://bad_host/app", rtmp).is_err());
+        assert!(parse_url("rtmp://host:0/app", rtmp).is_err());
+        assert!(parse_url("srt://host", &["srt"]).is_err());
+    }
+
+    #[test]
+    fn hostnames_follow_rfc_1123() {
+        for ok in [
+            "rx1",
+            "rx-1.example.com",
+            "example.com.",
+            "10.0.0.5",
+            "fe80::1",
+        ] {
+            assert!(validate_host(ok).is_ok(), "{ok}");
+        }
+        for bad in [
+            "",
+            "-rx",
+            "rx-",
+            "rx_1",
+            "a..b",
+            "10.0.0.256",
+            &"a".repeat(64),
+        ] {
+            assert!(validate_host(bad).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+-- Scheduled maintenance windows.
+--
+-- While a window is open, alerting for the covered senders is suppressed
+-- and agents are told (maintenance.schedule) they may reboot / auto-apply
+-- OTA updates. sender_id NULL = every sender the owner has.
+
+CREATE TABLE IF NOT EXISTS maintenance_windows (
+    id              TEXT PRIMARY KEY,          -- mnt_<uuid7>
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sender_id       TEXT REFERENCES senders(id) ON DELETE CASCADE,
+    starts_at       TIMESTAMPTZ NOT NULL,
+    ends_at         TIMESTAMPTZ NOT NULL,
+    allow_ota       BOOLEAN NOT NULL DEFAULT FALSE,
+    allow_reboot    BOOLEAN NOT NULL DEFAULT FALSE,
+    reason          TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK (ends_at > starts_at)
+);
+CREATE INDEX IF NOT EXISTS idx_maintenance_owner ON maintenance_windows(owner_id);
+CREATE INDEX IF NOT EXISTS idx_maintenance_sender ON maintenance_windows(sender_id);
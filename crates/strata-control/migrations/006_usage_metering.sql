@@ -0,0 +1,6 @@
+-- Usage metering.
+--
+-- Billing rolls streams up per sender per month of started_at; total_bytes
+-- is now kept current from stream.stats telemetry (not just stream.ended),
+-- so streams whose end was inferred still carry their transported volume.
+CREATE INDEX IF NOT EXISTS idx_streams_sender_started ON streams(sender_id, started_at);
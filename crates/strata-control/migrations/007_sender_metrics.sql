@@ -0,0 +1,18 @@
+-- Historical sender telemetry for the dashboard's history charts.
+--
+-- One row per sender every ~10 s while it streams, downsampled from the
+-- 1 Hz stream.stats feed. Link figures are over links in the Live state.
+-- Rows older than the retention window are pruned by the control plane.
+
+CREATE TABLE IF NOT EXISTS sender_metrics (
+    sender_id       TEXT NOT NULL REFERENCES senders(id) ON DELETE CASCADE,
+    stream_id       TEXT NOT NULL,
+    ts              TIMESTAMPTZ NOT NULL,
+    bitrate_kbps    INTEGER NOT NULL,          -- encoder target
+    throughput_bps  BIGINT NOT NULL,           -- sum of observed link rates
+    rtt_ms          DOUBLE PRECISION,          -- mean over live links
+    loss_pct        DOUBLE PRECISION,          -- mean over live links
+    link_count      SMALLINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sender_metrics_sender_ts ON sender_metrics(sender_id, ts);
+CREATE INDEX IF NOT EXISTS idx_sender_metrics_ts ON sender_metrics(ts);
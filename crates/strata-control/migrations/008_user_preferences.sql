@@ -0,0 +1,3 @@
+-- Per-user dashboard preferences (theme, landing page, units, graph
+-- window) as a JSON document; NULL = all defaults.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS preferences_json TEXT;
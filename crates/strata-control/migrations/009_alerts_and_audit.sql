@@ -0,0 +1,41 @@
+-- Alert history and audit trail.
+--
+-- alert_events: one row per firing of an alert rule, from breach to
+-- resolution. Rule fields are copied in — rules are edited and deleted,
+-- history is not.
+--
+-- audit_log: who changed what. sender_id carries no FK so entries outlive
+-- the sender they describe (a "sender.delete" row must survive its own
+-- cascade); actor_id NULL = the control plane itself.
+
+CREATE TABLE IF NOT EXISTS alert_events (
+    id              TEXT PRIMARY KEY,          -- alr_<uuid7>
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sender_id       TEXT NOT NULL REFERENCES senders(id) ON DELETE CASCADE,
+    rule_id         TEXT NOT NULL,
+    rule_name       TEXT NOT NULL,
+    metric          TEXT NOT NULL,
+    condition       TEXT NOT NULL,
+    threshold       DOUBLE PRECISION NOT NULL,
+    severity        TEXT NOT NULL,             -- info | warning | critical
+    value           DOUBLE PRECISION NOT NULL,
+    state           TEXT NOT NULL DEFAULT 'firing',  -- firing | acknowledged | resolved
+    fired_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    acknowledged_at TIMESTAMPTZ,
+    acknowledged_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at     TIMESTAMPTZ,
+    resolved_by     TEXT REFERENCES users(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_alert_events_owner ON alert_events(owner_id, fired_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_events_open ON alert_events(sender_id) WHERE state <> 'resolved';
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id          TEXT PRIMARY KEY,              -- aud_<uuid7>
+    owner_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    actor_id    TEXT REFERENCES users(id) ON DELETE SET NULL,
+    sender_id   TEXT,
+    action      TEXT NOT NULL,
+    detail      TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_audit_log_owner ON audit_log(owner_id, created_at DESC);
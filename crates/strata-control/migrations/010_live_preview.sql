@@ -0,0 +1,6 @@
+-- Live preview: receivers report the public URL of their preview server at
+-- login, and each HLS-relayed stream on such a receiver carries the key
+-- that unlocks its preview. The URL is only handed out while the stream
+-- is live; the receiver forgets the key when the pipeline stops.
+ALTER TABLE receivers ADD COLUMN IF NOT EXISTS preview_base_url TEXT;
+ALTER TABLE streams ADD COLUMN IF NOT EXISTS preview_key TEXT;
@@ -0,0 +1,15 @@
+-- User management: accounts with invited members and enforced roles.
+--
+-- owner_id: the account an invited user belongs to; NULL for account
+-- owners (everyone who self-registered). Data stays scoped by the
+-- account owner's ID, so members see and act on the owner's fleet.
+-- disabled_at: set when an admin disables the user; blocks login and
+-- invalidates existing sessions.
+--
+-- Roles are now enforced (viewer < operator < admin). Account owners were
+-- created as 'operator' while roles were unenforced; they become admins.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS owner_id TEXT REFERENCES users(id) ON DELETE CASCADE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS disabled_at TIMESTAMPTZ;
+CREATE INDEX IF NOT EXISTS idx_users_owner ON users(owner_id);
+
+UPDATE users SET role = 'admin' WHERE owner_id IS NULL;
@@ -0,0 +1,27 @@
+-- Scheduled streams.
+--
+-- The control plane's schedule tick starts a booking at starts_at and
+-- stops the stream it started at ends_at. Overlapping bookings for one
+-- sender are allowed and flagged as conflicts by the API; whichever
+-- starts second fails with "sender already has an active stream".
+
+CREATE TABLE IF NOT EXISTS scheduled_streams (
+    id              TEXT PRIMARY KEY,          -- sch_<uuid7>
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sender_id       TEXT NOT NULL REFERENCES senders(id) ON DELETE CASCADE,
+    title           TEXT,
+    destination_id  TEXT REFERENCES destinations(id) ON DELETE SET NULL,
+    device          TEXT,                      -- NULL = test pattern
+    resolution      TEXT,
+    framerate       INTEGER,
+    starts_at       TIMESTAMPTZ NOT NULL,
+    ends_at         TIMESTAMPTZ NOT NULL,
+    state           TEXT NOT NULL DEFAULT 'scheduled',  -- scheduled | running | completed | failed
+    stream_id       TEXT,
+    error           TEXT,
+    created_by      TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK (ends_at > starts_at)
+);
+CREATE INDEX IF NOT EXISTS idx_scheduled_streams_owner ON scheduled_streams(owner_id, starts_at);
+CREATE INDEX IF NOT EXISTS idx_scheduled_streams_due ON scheduled_streams(starts_at) WHERE state IN ('scheduled', 'running');
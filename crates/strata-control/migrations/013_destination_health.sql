@@ -0,0 +1,10 @@
+-- Destination health checks.
+--
+-- Written by POST /api/destinations/{id}/check and after any change to the
+-- URL or stream key; a change resets the status to 'unknown' until the
+-- check finishes.
+
+ALTER TABLE destinations ADD COLUMN IF NOT EXISTS health_status TEXT NOT NULL DEFAULT 'unknown';  -- unknown | ok | error
+ALTER TABLE destinations ADD COLUMN IF NOT EXISTS health_detail TEXT;
+ALTER TABLE destinations ADD COLUMN IF NOT EXISTS health_checked_at TIMESTAMPTZ;
+CREATE INDEX IF NOT EXISTS idx_streams_destination ON streams(destination_id, started_at);
@@ -0,0 +1,18 @@
+-- Per-link event feed for the Stream tab's link timeline.
+--
+-- Phase rows are written when a link's phase (probing | live | failover |
+-- down) changes between stream.stats samples; band_change / disabled /
+-- enabled rows come from differences between consecutive device.status
+-- heartbeats while the stream runs.
+
+CREATE TABLE IF NOT EXISTS link_events (
+    id          BIGSERIAL PRIMARY KEY,
+    stream_id   TEXT NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    sender_id   TEXT NOT NULL REFERENCES senders(id) ON DELETE CASCADE,
+    ts          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    interface   TEXT NOT NULL,
+    kind        TEXT NOT NULL,       -- phase | band_change | disabled | enabled
+    phase       TEXT,                -- set when kind = 'phase'
+    detail      TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_link_events_stream ON link_events(stream_id, ts);
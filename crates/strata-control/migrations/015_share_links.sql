@@ -0,0 +1,18 @@
+-- Read-only share links: expiring, unauthenticated views of one stream.
+--
+-- The token handed out is a JWT with role 'share' whose subject is the
+-- link's ID and whose expiry matches expires_at. This row is what makes
+-- a link revocable before it expires.
+
+CREATE TABLE IF NOT EXISTS share_links (
+    id              TEXT PRIMARY KEY,          -- shr_<uuid7>
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    stream_id       TEXT NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    label           TEXT,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    revoked_at      TIMESTAMPTZ,
+    last_viewed_at  TIMESTAMPTZ,
+    created_by      TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_share_links_stream ON share_links(stream_id, created_at DESC);
@@ -0,0 +1,7 @@
+-- Optional PIN/password gate on a sender's local onboarding portal.
+--
+-- Only the argon2 hash is stored; it is pushed to the agent (which keeps
+-- its own copy, so the gate holds while the sender is offline). NULL
+-- means the portal is open to anyone on the sender's local network.
+
+ALTER TABLE senders ADD COLUMN IF NOT EXISTS portal_pin_hash TEXT;
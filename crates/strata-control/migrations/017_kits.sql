@@ -0,0 +1,48 @@
+-- Kits: a sender and the accessories that travel with it in one case.
+--
+-- Rental houses track hardware by kit, not by box: a kit goes out to a
+-- crew as a unit and comes back as a unit, and what matters at the desk
+-- is which serials were in it and who has it now. kit_checkouts is the
+-- out/in history; a row with checked_in_at NULL is the kit being out.
+
+CREATE TABLE IF NOT EXISTS kits (
+    id          TEXT PRIMARY KEY,          -- kit_<uuid7>
+    owner_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    sender_id   TEXT REFERENCES senders(id) ON DELETE SET NULL,
+    notes       TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_kits_owner ON kits(owner_id, name);
+-- A sender lives in at most one kit.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_kits_sender ON kits(sender_id) WHERE sender_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS kit_accessories (
+    id          TEXT PRIMARY KEY,          -- kac_<uuid7>
+    kit_id      TEXT NOT NULL REFERENCES kits(id) ON DELETE CASCADE,
+    owner_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind        TEXT NOT NULL,             -- modem | sim | camera | battery | antenna | other
+    serial      TEXT NOT NULL,
+    label       TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_kit_accessories_kit ON kit_accessories(kit_id);
+-- One physical item can't be in two kits of the same account.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_kit_accessories_serial
+    ON kit_accessories(owner_id, kind, serial);
+
+CREATE TABLE IF NOT EXISTS kit_checkouts (
+    id              TEXT PRIMARY KEY,      -- kco_<uuid7>
+    kit_id          TEXT NOT NULL REFERENCES kits(id) ON DELETE CASCADE,
+    holder          TEXT NOT NULL,         -- crew, customer or job the kit went to
+    note            TEXT,
+    due_back_at     TIMESTAMPTZ,
+    checked_out_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    checked_out_by  TEXT REFERENCES users(id) ON DELETE SET NULL,
+    checked_in_at   TIMESTAMPTZ,
+    checked_in_by   TEXT REFERENCES users(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_kit_checkouts_kit ON kit_checkouts(kit_id, checked_out_at DESC);
+-- A kit is out to at most one holder at a time.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_kit_checkouts_open
+    ON kit_checkouts(kit_id) WHERE checked_in_at IS NULL;
@@ -0,0 +1,44 @@
+-- TLS certificates for device HTTPS endpoints: the agent portal and the
+-- receiver preview server.
+--
+-- The control plane is the one place a certificate is issued (self-signed),
+-- uploaded, or ordered from an ACME CA with a DNS-01 challenge; devices
+-- only ever receive the finished pair over their WebSocket. The newest
+-- 'issued' row per device is the live one — issuing another marks the
+-- previous 'superseded'. A 'pending' row is an ACME order waiting for its
+-- TXT record to be published and verified.
+
+CREATE TABLE IF NOT EXISTS tls_certificates (
+    id              TEXT PRIMARY KEY,          -- crt_<uuid7>
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sender_id       TEXT REFERENCES senders(id) ON DELETE CASCADE,
+    receiver_id     TEXT REFERENCES receivers(id) ON DELETE CASCADE,
+    domain          TEXT NOT NULL,
+    source          TEXT NOT NULL,             -- uploaded | self_signed | acme
+    status          TEXT NOT NULL,             -- pending | issued | failed | superseded
+    cert_pem        TEXT,
+    key_pem         TEXT,
+    issuer          TEXT,
+    not_after       TIMESTAMPTZ,
+    acme_order_url  TEXT,
+    dns_txt_name    TEXT,
+    dns_txt_value   TEXT,
+    error           TEXT,
+    pushed_at       TIMESTAMPTZ,               -- device acked tls.install
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK ((sender_id IS NULL) <> (receiver_id IS NULL))
+);
+CREATE INDEX IF NOT EXISTS idx_tls_certificates_owner ON tls_certificates(owner_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_tls_certificates_sender ON tls_certificates(sender_id) WHERE sender_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_tls_certificates_receiver ON tls_certificates(receiver_id) WHERE receiver_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_tls_certificates_expiry ON tls_certificates(not_after) WHERE status = 'issued';
+
+-- One ACME account per owner and CA directory; credentials are the
+-- account key and URL as serialized by the ACME client.
+CREATE TABLE IF NOT EXISTS acme_accounts (
+    owner_id        TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    directory_url   TEXT NOT NULL,
+    credentials     JSONB NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (owner_id, directory_url)
+);
@@ -0,0 +1,37 @@
+-- Post-stream incident reports.
+--
+-- ladder_steps: one row per ABR resolution change reported in stream.stats.
+-- The stats feed is the only place a step is visible, so it is captured as
+-- it arrives.
+--
+-- stream_annotations: operator notes pinned to a point on a stream's
+-- timeline. author_id NULL once the author is deleted; the note stays.
+--
+-- stream_reports: the report built when the stream ends, as JSON. Rebuilt
+-- if a readopted stream ends again. Annotations are not baked in — they
+-- are merged when the report is read.
+
+CREATE TABLE IF NOT EXISTS ladder_steps (
+    id          BIGSERIAL PRIMARY KEY,
+    stream_id   TEXT NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    ts          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    from_rung   INTEGER NOT NULL,
+    to_rung     INTEGER NOT NULL,
+    resolution  TEXT NOT NULL           -- the rung stepped to
+);
+CREATE INDEX IF NOT EXISTS idx_ladder_steps_stream ON ladder_steps(stream_id, ts);
+
+CREATE TABLE IF NOT EXISTS stream_annotations (
+    id          BIGSERIAL PRIMARY KEY,
+    stream_id   TEXT NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    ts          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    author_id   TEXT REFERENCES users(id) ON DELETE SET NULL,
+    text        TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_stream_annotations_stream ON stream_annotations(stream_id, ts);
+
+CREATE TABLE IF NOT EXISTS stream_reports (
+    stream_id    TEXT PRIMARY KEY REFERENCES streams(id) ON DELETE CASCADE,
+    generated_at TIMESTAMPTZ NOT NULL,
+    report       TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Receiver auto-scaling.
+--
+-- provision_handle: the provisioning backend's name for a receiver worker
+-- the control plane started itself (see autoscale.rs), e.g.
+-- "deploy@203.0.113.5/strata-receiver-rcv_…". NULL for receivers an
+-- operator enrolled by hand — only workers with a handle are torn down
+-- when idle.
+
+ALTER TABLE receivers ADD COLUMN IF NOT EXISTS provision_handle TEXT;
+CREATE INDEX IF NOT EXISTS idx_receivers_provisioned
+    ON receivers(provision_handle) WHERE provision_handle IS NOT NULL;
@@ -0,0 +1,25 @@
+-- Data retention and user erasure.
+--
+-- erased_at: set when a member's personal data was scrubbed (see
+-- api/users.rs). The row stays so audit entries keep pointing at a
+-- pseudonymous actor instead of reading as the control plane itself.
+--
+-- usage_anonymized: monthly usage of accounts whose owner deleted them,
+-- copied out before the cascade. No owner, sender or stream identifiers —
+-- only the totals survive.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS erased_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS usage_anonymized (
+    id               BIGSERIAL PRIMARY KEY,
+    month            TEXT NOT NULL,          -- YYYY-MM of started_at
+    stream_count     BIGINT NOT NULL,
+    streamed_minutes DOUBLE PRECISION NOT NULL,
+    total_bytes      BIGINT NOT NULL,
+    erased_at        TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_usage_anonymized_month ON usage_anonymized(month);
+
+-- The retention janitor deletes by age; index the columns it filters on.
+CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at);
+CREATE INDEX IF NOT EXISTS idx_streams_ended ON streams(ended_at) WHERE ended_at IS NOT NULL;
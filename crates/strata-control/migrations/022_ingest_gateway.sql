@@ -0,0 +1,13 @@
+-- Ingest gateway: senders that contribute plain SRT/RTMP (a phone
+-- running Larix, OBS) instead of bonding through the Strata agent.
+--
+-- senders.ingest_protocol: 'srt' or 'rtmp' for an ingest sender, NULL for
+-- a Strata device. Ingest senders never enroll and have no agent; their
+-- streams are watched through the receiver's heartbeat instead.
+--
+-- receivers.ingest_protocols: what the receiver reported it can take in
+-- at its last login.
+
+ALTER TABLE senders ADD COLUMN IF NOT EXISTS ingest_protocol TEXT;
+
+ALTER TABLE receivers ADD COLUMN IF NOT EXISTS ingest_protocols TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,7 @@
+-- Device TLS private keys are stored sealed (ChaCha20-Poly1305 under a key
+-- derived from JWT_SEED_B64, see strata_common::auth::SecretBox) rather
+-- than as PEM. The control plane seals rows written before this at
+-- startup; there is nothing to change in the schema itself.
+
+COMMENT ON COLUMN tls_certificates.key_pem IS
+    'sealed:v1:<base64 nonce||ciphertext>; opened only to push tls.install';
@@ -0,0 +1,10 @@
+-- Enrollment tokens now end in a check character. Tokens issued before
+-- that have the same XXXX-XXXX shape but no check, and may still be
+-- printed on kit labels; flag them so enrollment can accept them by row
+-- instead of guessing from the token itself.
+
+ALTER TABLE senders ADD COLUMN IF NOT EXISTS enrollment_token_legacy BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE receivers ADD COLUMN IF NOT EXISTS enrollment_token_legacy BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE senders SET enrollment_token_legacy = TRUE WHERE enrollment_token IS NOT NULL;
+UPDATE receivers SET enrollment_token_legacy = TRUE WHERE enrollment_token IS NOT NULL;
@@ -0,0 +1,219 @@
+//! ACME (RFC 8555) certificate orders with DNS-01 challenges.
+//!
+//! Devices sit behind cellular NAT and can't answer HTTP-01, so orders are
+//! proved with a TXT record the operator publishes. An order spans two API
+//! calls: [`start_order`] returns the record to publish, and
+//! [`complete_order`] — once it is in DNS — tells the CA to check it,
+//! waits for validation, and finalizes with a freshly generated key.
+//!
+//! Accounts are per owner and CA directory, persisted in `acme_accounts`
+//! so renewals reuse them (and the CA's cached authorizations).
+
+use std::time::Duration;
+
+use instant_acme::{
+    Account, AccountCredentials, AuthorizationStatus, ChallengeType, Identifier, LetsEncrypt,
+    NewAccount, NewOrder, Order, OrderStatus,
+};
+
+use crate::state::AppState;
+
+/// How long [`complete_order`] waits for the CA before giving up. The
+/// order stays open; verifying again picks it back up.
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Let's Encrypt production or staging directory.
+pub fn directory_url(staging: bool) -> &'static str {
+    if staging {
+        LetsEncrypt::Staging.url()
+    } else {
+        LetsEncrypt::Production.url()
+    }
+}
+
+/// The owner's account with the CA at `directory_url`, registered on first
+/// use.
+pub async fn account(
+    state: &AppState,
+    owner_id: &str,
+    directory_url: &str,
+    contact_email: Option<&str>,
+) -> anyhow::Result<Account> {
+    let stored = sqlx::query_scalar::<_, serde_json::Value>(
+        "SELECT credentials FROM acme_accounts WHERE owner_id = $1 AND directory_url = $2",
+    )
+    .bind(owner_id)
+    .bind(directory_url)
+    .fetch_optional(state.pool())
+    .await?;
+    if let Some(credentials) = stored {
+        let credentials: AccountCredentials = serde_json::from_value(credentials)?;
+        return Ok(Account::from_credentials(credentials).await?);
+    }
+
+    let contact = contact_email.map(|email| format!("mailto:{email}"));
+    let contact: Vec<&str> = contact.iter().map(String::as_str).collect();
+    let (account, credentials) = Account::create(
+        &NewAccount {
+            contact: &contact,
+            terms_of_service_agreed: true,
+            only_return_existing: false,
+        },
+        directory_url,
+        None,
+    )
+    .await?;
+    sqlx::query(
+        "INSERT INTO acme_accounts (owner_id, directory_url, credentials) VALUES ($1, $2, $3) \
+         ON CONFLICT (owner_id, directory_url) DO UPDATE SET credentials = EXCLUDED.credentials",
+    )
+    .bind(owner_id)
+    .bind(directory_url)
+    .bind(serde_json::to_value(&credentials)?)
+    .execute(state.pool())
+    .await?;
+    Ok(account)
+}
+
+/// A new order and the DNS record that proves control of its domain.
+#[derive(Debug)]
+pub struct StartedOrder {
+    pub order_url: String,
+    /// `_acme-challenge.<domain>`; `None` if the CA still holds a valid
+    /// authorization for the domain and no record is needed.
+    pub txt_name: Option<String>,
+    pub txt_value: Option<String>,
+}
+
+pub async fn start_order(account: &Account, domain: &str) -> anyhow::Result<StartedOrder> {
+    let mut order = account
+        .new_order(&NewOrder {
+            identifiers: &[Identifier::Dns(domain.to_string())],
+        })
+        .await?;
+    let mut started = StartedOrder {
+        order_url: order.url().to_string(),
+        txt_name: None,
+        txt_value: None,
+    };
+    for authz in order.authorizations().await? {
+        match authz.status {
+            AuthorizationStatus::Valid => continue,
+            AuthorizationStatus::Pending => {}
+            status => anyhow::bail!("authorization is {status:?}"),
+        }
+        let challenge = authz
+            .challenges
+            .iter()
+            .find(|c| c.r#type == ChallengeType::Dns01)
+            .ok_or_else(|| anyhow::anyhow!("CA offered no dns-01 challenge"))?;
+        started.txt_name = Some(format!(
+            "_acme-challenge.{}",
+            domain.trim_start_matches("*.")
+        ));
+        started.txt_value = Some(order.key_authorization(challenge).dns_value());
+    }
+    Ok(started)
+}
+
+/// How a [`complete_order`] attempt ended. Errors (DNS not propagated
+/// yet, CA slow or unreachable) leave the order open for another try.
+#[derive(Debug)]
+pub enum Completion {
+    Issued {
+        cert_pem: String,
+        key_pem: String,
+    },
+    /// The order can never succeed; a new one is needed.
+    Failed(String),
+}
+
+/// Have the CA check the TXT record, wait for the order to validate, and
+/// finalize it.
+pub async fn complete_order(
+    account: &Account,
+    order_url: &str,
+    domain: &str,
+) -> anyhow::Result<Completion> {
+    let mut order = account.order(order_url.to_string()).await?;
+    for authz in order.authorizations().await? {
+        if authz.status != AuthorizationStatus::Pending {
+            continue;
+        }
+        if let Some(challenge) = authz
+            .challenges
+            .iter()
+            .find(|c| c.r#type == ChallengeType::Dns01)
+        {
+            order.set_challenge_ready(&challenge.url).await?;
+        }
+    }
+
+    let status = wait_for(&mut order, |s| {
+        !matches!(s, OrderStatus::Pending | OrderStatus::Processing)
+    })
+    .await?;
+    match status {
+        OrderStatus::Ready => {}
+        OrderStatus::Invalid => {
+            let detail = order
+                .state()
+                .error
+                .as_ref()
+                .and_then(|p| p.detail.clone())
+                .unwrap_or_else(|| "is the TXT record published?".into());
+            return Ok(Completion::Failed(format!(
+                "the CA could not validate {domain}: {detail}"
+            )));
+        }
+        // Finalized by an earlier attempt whose key we no longer have.
+        other => return Ok(Completion::Failed(format!("order is {other:?}"))),
+    }
+
+    let key = rcgen::KeyPair::generate()?;
+    let mut params = rcgen::CertificateParams::new(vec![domain.to_string()])?;
+    params.distinguished_name = rcgen::DistinguishedName::new();
+    let csr = params.serialize_request(&key)?;
+    order.finalize(csr.der()).await?;
+
+    let deadline = tokio::time::Instant::now() + VALIDATION_TIMEOUT;
+    loop {
+        if let Some(cert_pem) = order.certificate().await? {
+            return Ok(Completion::Issued {
+                cert_pem,
+                key_pem: key.serialize_pem(),
+            });
+        }
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!("timed out waiting for the CA to issue the certificate");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Poll the order until `done(status)` or [`VALIDATION_TIMEOUT`].
+async fn wait_for(
+    order: &mut Order,
+    done: impl Fn(&OrderStatus) -> bool,
+) -> anyhow::Result<OrderStatus> {
+    let deadline = tokio::time::Instant::now() + VALIDATION_TIMEOUT;
+    loop {
+        let status = order.refresh().await?.status;
+        if done(&status) {
+            return Ok(status);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!("timed out waiting for the CA to validate the TXT record");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// A self-signed certificate for `domain`, for devices reached by name on
+/// a private network. Returns `(cert_pem, key_pem)`.
+pub fn self_signed(domain: &str) -> anyhow::Result<(String, String)> {
+    let rcgen::CertifiedKey { cert, key_pair } =
+        rcgen::generate_simple_self_signed(vec![domain.to_string()])?;
+    Ok((cert.pem(), key_pair.serialize_pem()))
+}
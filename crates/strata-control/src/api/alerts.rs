@@ -0,0 +1,493 @@
+//! Alert evaluation and alert history.
+//!
+//! GET  /api/alerts?sender_id=&severity=&state=&limit=  — alert history
+//! GET  /api/alerts/rules                               — every sender's rules
+//! POST /api/alerts/:id/ack                             — acknowledge
+//! POST /api/alerts/:id/resolve                         — resolve by hand
+//!
+//! Rules live in memory per sender (`AppState::alert_rules`); [`evaluate`]
+//! checks them against `stream.stats` every [`EVAL_INTERVAL`]. A breach
+//! opens an `alert_events` row unless a maintenance window suppresses it,
+//! and the row resolves itself once the rule stops breaching (or the rule
+//! is disabled or deleted). Every transition is pushed on the `alerts`
+//! dashboard topic.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, Query, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_common::ids;
+use strata_protocol::api::{AlertRule, Capability};
+use strata_protocol::models::{AlertEvent, AlertSeverity, AlertState};
+use strata_protocol::{DashboardEvent, StreamStatsPayload};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// Minimum spacing between rule evaluations for one sender. A breach has
+/// to survive at least one interval of stats to fire or clear.
+pub const EVAL_INTERVAL: Duration = Duration::from_secs(5);
+
+const DEFAULT_LIMIT: i64 = 200;
+const MAX_LIMIT: i64 = 1000;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_alerts))
+        .route("/rules", get(list_rules))
+        .route("/{id}/ack", post(acknowledge))
+        .route("/{id}/resolve", post(resolve))
+}
+
+const EVENT_COLUMNS: &str = "a.id, a.sender_id, s.name, a.rule_id, a.rule_name, a.metric, \
+     a.condition, a.threshold, a.severity, a.value, a.state, a.fired_at, \
+     a.acknowledged_at, ua.email, a.resolved_at, ur.email \
+     FROM alert_events a \
+     LEFT JOIN senders s ON s.id = a.sender_id \
+     LEFT JOIN users ua ON ua.id = a.acknowledged_by \
+     LEFT JOIN users ur ON ur.id = a.resolved_by";
+
+type EventRow = (
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+    String,
+    String,
+    f64,
+    String,
+    f64,
+    String,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    Option<String>,
+);
+
+fn event_from_row(
+    (
+        id,
+        sender_id,
+        sender_name,
+        rule_id,
+        rule_name,
+        metric,
+        condition,
+        threshold,
+        severity,
+        value,
+        state,
+        fired_at,
+        acknowledged_at,
+        acknowledged_by,
+        resolved_at,
+        resolved_by,
+    ): EventRow,
+) -> AlertEvent {
+    AlertEvent {
+        id,
+        sender_id,
+        sender_name,
+        rule_id,
+        rule_name,
+        metric,
+        condition,
+        threshold,
+        severity: severity.parse().unwrap_or_default(),
+        value,
+        state: state.parse().unwrap_or(AlertState::Firing),
+        fired_at,
+        acknowledged_at,
+        acknowledged_by,
+        resolved_at,
+        resolved_by,
+    }
+}
+
+async fn load_event(state: &AppState, id: &str) -> Result<Option<AlertEvent>, sqlx::Error> {
+    let row = sqlx::query_as::<_, EventRow>(&format!("SELECT {EVENT_COLUMNS} WHERE a.id = $1"))
+        .bind(id)
+        .fetch_optional(state.pool())
+        .await?;
+    Ok(row.map(event_from_row))
+}
+
+/// Load an event and push it to the owner's `alerts` subscribers.
+pub(crate) async fn publish(state: &AppState, owner_id: &str, id: &str) {
+    match load_event(state, id).await {
+        Ok(Some(event)) => state.broadcast_dashboard(owner_id, DashboardEvent::Alert(event)),
+        Ok(None) => {}
+        Err(e) => tracing::warn!(alert_id = %id, error = %e, "failed to load alert event"),
+    }
+}
+
+// ── History ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AlertQuery {
+    sender_id: Option<String>,
+    severity: Option<String>,
+    /// `firing`, `acknowledged`, `resolved`, or `open` (either of the first two).
+    state: Option<String>,
+    limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/alerts",
+    tag = "alerts",
+    params(AlertQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<AlertEvent>), ApiError)
+)]
+async fn list_alerts(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(q): Query<AlertQuery>,
+) -> Result<Json<Vec<AlertEvent>>, ApiError> {
+    let severity = q
+        .severity
+        .as_deref()
+        .map(|s| s.parse::<AlertSeverity>())
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+    let open_only = q.state.as_deref() == Some("open");
+    let alert_state = q
+        .state
+        .as_deref()
+        .filter(|s| *s != "open")
+        .map(|s| s.parse::<AlertState>())
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let rows = sqlx::query_as::<_, EventRow>(&format!(
+        "SELECT {EVENT_COLUMNS} \
+         WHERE a.owner_id = $1 \
+           AND ($2::TEXT IS NULL OR a.sender_id = $2) \
+           AND ($3::TEXT IS NULL OR a.severity = $3) \
+           AND ($4::TEXT IS NULL OR a.state = $4) \
+           AND (NOT $5 OR a.state <> 'resolved') \
+         ORDER BY a.fired_at DESC LIMIT $6"
+    ))
+    .bind(&user.owner_id)
+    .bind(&q.sender_id)
+    .bind(severity.map(|s| s.as_str()))
+    .bind(alert_state.map(|s| s.as_str()))
+    .bind(open_only)
+    .bind(limit)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(rows.into_iter().map(event_from_row).collect()))
+}
+
+// ── Rules ───────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/alerts/rules",
+    tag = "alerts",
+    summary = "Alerting rules of every sender, keyed by sender ID",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = BTreeMap<String, Vec<serde_json::Value>>), ApiError)
+)]
+async fn list_rules(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<BTreeMap<String, Vec<serde_json::Value>>>, ApiError> {
+    user.require(Capability::ManageAlerts)?;
+    // One call for the fleet overview instead of one per sender; senders
+    // without rules are left out.
+    let sender_ids = sqlx::query_scalar::<_, String>("SELECT id FROM senders WHERE owner_id = $1")
+        .bind(&user.owner_id)
+        .fetch_all(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let rules = sender_ids
+        .into_iter()
+        .filter_map(|id| {
+            let rules = state.alert_rules().get(&id)?.clone();
+            (!rules.is_empty()).then_some((id, rules))
+        })
+        .collect();
+    Ok(Json(rules))
+}
+
+// ── Acknowledge / Resolve ───────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/alerts/{id}/ack",
+    tag = "alerts",
+    summary = "Acknowledge an alert",
+    params(("id" = String, Path, description = "Alert ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = AlertEvent), ApiError)
+)]
+async fn acknowledge(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<AlertEvent>, ApiError> {
+    user.require(Capability::ManageAlerts)?;
+
+    let sender_id = sqlx::query_scalar::<_, String>(
+        "UPDATE alert_events \
+         SET state = 'acknowledged', acknowledged_at = now(), acknowledged_by = $3 \
+         WHERE id = $1 AND owner_id = $2 AND state = 'firing' \
+         RETURNING sender_id",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&user.user_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let Some(sender_id) = sender_id else {
+        return Err(not_transitioned(&state, &user, &id, "alert is not firing").await);
+    };
+
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&sender_id),
+        "alert.ack",
+        Some(id.clone()),
+    )
+    .await;
+    respond(&state, &user, &id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/alerts/{id}/resolve",
+    tag = "alerts",
+    summary = "Resolve an alert by hand",
+    params(("id" = String, Path, description = "Alert ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = AlertEvent), ApiError)
+)]
+async fn resolve(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<AlertEvent>, ApiError> {
+    user.require(Capability::ManageAlerts)?;
+
+    let sender_id = sqlx::query_scalar::<_, String>(
+        "UPDATE alert_events \
+         SET state = 'resolved', resolved_at = now(), resolved_by = $3 \
+         WHERE id = $1 AND owner_id = $2 AND state <> 'resolved' \
+         RETURNING sender_id",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&user.user_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let Some(sender_id) = sender_id else {
+        return Err(not_transitioned(&state, &user, &id, "alert is already resolved").await);
+    };
+
+    // The evaluator still tracks the event as open, so a rule that keeps
+    // breaching stays quiet instead of re-firing on the next sample; the
+    // tracking entry clears once the condition does.
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&sender_id),
+        "alert.resolve",
+        Some(id.clone()),
+    )
+    .await;
+    respond(&state, &user, &id).await
+}
+
+/// Distinguish "no such alert" from "alert in the wrong state" after an
+/// UPDATE matched nothing.
+async fn not_transitioned(state: &AppState, user: &AuthUser, id: &str, msg: &str) -> ApiError {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM alert_events WHERE id = $1 AND owner_id = $2)",
+    )
+    .bind(id)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await;
+    match exists {
+        Ok(true) => ApiError::bad_request(msg),
+        Ok(false) => ApiError::not_found("alert not found"),
+        Err(e) => ApiError::internal(e.to_string()),
+    }
+}
+
+async fn respond(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<Json<AlertEvent>, ApiError> {
+    let event = load_event(state, id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::not_found("alert not found"))?;
+    state.broadcast_dashboard(&user.owner_id, DashboardEvent::Alert(event.clone()));
+    Ok(Json(event))
+}
+
+// ── Evaluation ──────────────────────────────────────────────────────
+
+/// Open stream-rule alert events for a sender, keyed by rule ID.
+/// Certificate expiry alerts are managed by
+/// [`super::certificates::check_expiry`].
+async fn load_open(
+    state: &AppState,
+    sender_id: &str,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT rule_id, id FROM alert_events \
+         WHERE sender_id = $1 AND state <> 'resolved' AND rule_id <> $2",
+    )
+    .bind(sender_id)
+    .bind(super::certificates::EXPIRY_RULE_ID)
+    .fetch_all(state.pool())
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Check a sender's rules against one `stream.stats` sample, opening and
+/// resolving alert events as rules start and stop breaching.
+pub async fn evaluate(state: &AppState, owner_id: &str, stats: &StreamStatsPayload) {
+    let sender_id = stats.sender_id.as_str();
+    let now = Instant::now();
+    {
+        let mut tracker = state
+            .alert_tracking()
+            .entry(sender_id.to_string())
+            .or_default();
+        if tracker
+            .evaluated_at
+            .is_some_and(|last| now.duration_since(last) < EVAL_INTERVAL)
+        {
+            return;
+        }
+        tracker.evaluated_at = Some(now);
+    }
+
+    let rules: Vec<AlertRule> = state
+        .alert_rules()
+        .get(sender_id)
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(|r| serde_json::from_value(r.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let cached = state
+        .alert_tracking()
+        .get(sender_id)
+        .and_then(|t| t.open.clone());
+    let open = match cached {
+        Some(open) => open,
+        // First evaluation since start-up / reconnect: pick up events the
+        // previous process left open rather than firing duplicates.
+        None => match load_open(state, sender_id).await {
+            Ok(open) => open,
+            Err(e) => {
+                tracing::warn!(sender_id = %sender_id, error = %e, "failed to load open alerts");
+                return;
+            }
+        },
+    };
+
+    let breaches: HashMap<&str, (&AlertRule, f64)> = rules
+        .iter()
+        .filter_map(|r| Some((r.id.as_deref()?, (r, r.breach(&stats.links)?))))
+        .collect();
+
+    let mut next = open.clone();
+    for (rule_id, event_id) in &open {
+        if breaches.contains_key(rule_id.as_str()) {
+            continue;
+        }
+        next.remove(rule_id);
+        match sqlx::query(
+            "UPDATE alert_events SET state = 'resolved', resolved_at = now() \
+             WHERE id = $1 AND state <> 'resolved'",
+        )
+        .bind(event_id)
+        .execute(state.pool())
+        .await
+        {
+            Ok(r) if r.rows_affected() > 0 => {
+                tracing::info!(sender_id = %sender_id, alert_id = %event_id, "alert cleared");
+                publish(state, owner_id, event_id).await;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(alert_id = %event_id, error = %e, "failed to resolve alert"),
+        }
+    }
+
+    let new: Vec<_> = breaches
+        .iter()
+        .filter(|(rule_id, _)| !open.contains_key(**rule_id))
+        .collect();
+    if !new.is_empty() && !super::maintenance::is_suppressed(state, sender_id).await {
+        for (rule_id, (rule, value)) in new {
+            let event_id = ids::alert_event_id();
+            let inserted = sqlx::query(
+                "INSERT INTO alert_events \
+                 (id, owner_id, sender_id, rule_id, rule_name, metric, condition, threshold, severity, value) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            )
+            .bind(&event_id)
+            .bind(owner_id)
+            .bind(sender_id)
+            .bind(rule_id)
+            .bind(&rule.name)
+            .bind(&rule.metric)
+            .bind(&rule.condition)
+            .bind(rule.threshold)
+            .bind(rule.severity.as_str())
+            .bind(value)
+            .execute(state.pool())
+            .await;
+            match inserted {
+                Ok(_) => {
+                    tracing::info!(
+                        sender_id = %sender_id,
+                        rule = %rule.name,
+                        value,
+                        "alert fired"
+                    );
+                    next.insert(rule_id.to_string(), event_id.clone());
+                    publish(state, owner_id, &event_id).await;
+                }
+                Err(e) => {
+                    tracing::warn!(sender_id = %sender_id, error = %e, "failed to open alert")
+                }
+            }
+        }
+    }
+
+    state
+        .alert_tracking()
+        .entry(sender_id.to_string())
+        .or_default()
+        .open = Some(next);
+}
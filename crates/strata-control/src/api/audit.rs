@@ -0,0 +1,164 @@
+//! Audit trail of changes to the fleet.
+//!
+//! GET /api/audit?sender_id=&actor=&limit=
+//!
+//! `actor` is a user ID, or `system` for actions the control plane took
+//! on its own. Entries are written with [`record`] by the handlers that
+//! make the change, newest first, capped at [`MAX_LIMIT`] per request.
+
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_common::ids;
+use strata_protocol::models::AuditEntry;
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+const DEFAULT_LIMIT: i64 = 200;
+const MAX_LIMIT: i64 = 1000;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(list_audit))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AuditQuery {
+    sender_id: Option<String>,
+    actor: Option<String>,
+    limit: Option<i64>,
+}
+
+type AuditRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    DateTime<Utc>,
+);
+
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(AuditQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<AuditEntry>), ApiError)
+)]
+async fn list_audit(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(q): Query<AuditQuery>,
+) -> Result<Json<Vec<AuditEntry>>, ApiError> {
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let system_only = q.actor.as_deref() == Some("system");
+    let actor_id = q.actor.filter(|a| a != "system");
+
+    let rows = sqlx::query_as::<_, AuditRow>(
+        "SELECT a.id, a.actor_id, u.email, a.sender_id, s.name, a.action, a.detail, a.created_at \
+         FROM audit_log a \
+         LEFT JOIN users u ON u.id = a.actor_id \
+         LEFT JOIN senders s ON s.id = a.sender_id \
+         WHERE a.owner_id = $1 \
+           AND ($2::TEXT IS NULL OR a.sender_id = $2) \
+           AND ($3::TEXT IS NULL OR a.actor_id = $3) \
+           AND (NOT $4 OR a.actor_id IS NULL) \
+         ORDER BY a.created_at DESC, a.id DESC LIMIT $5",
+    )
+    .bind(&user.owner_id)
+    .bind(&q.sender_id)
+    .bind(&actor_id)
+    .bind(system_only)
+    .bind(limit)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(
+                |(
+                    id,
+                    actor_id,
+                    actor_email,
+                    sender_id,
+                    sender_name,
+                    action,
+                    detail,
+                    created_at,
+                )| {
+                    AuditEntry {
+                        id,
+                        actor_id,
+                        actor_email,
+                        sender_id,
+                        sender_name,
+                        action,
+                        detail,
+                        created_at,
+                    }
+                },
+            )
+            .collect(),
+    ))
+}
+
+// ── Recording ───────────────────────────────────────────────────────
+
+/// Append an entry to `owner_id`'s audit trail. `actor_id: None` marks an
+/// action the control plane took by itself.
+///
+/// Failures are logged, not returned — the change being audited has
+/// already happened by the time this runs.
+pub async fn record(
+    state: &AppState,
+    owner_id: &str,
+    actor_id: Option<&str>,
+    sender_id: Option<&str>,
+    action: &str,
+    detail: Option<String>,
+) {
+    if let Err(e) = sqlx::query(
+        "INSERT INTO audit_log (id, owner_id, actor_id, sender_id, action, detail) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(ids::audit_entry_id())
+    .bind(owner_id)
+    .bind(actor_id)
+    .bind(sender_id)
+    .bind(action)
+    .bind(&detail)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(action, error = %e, "failed to write audit entry");
+    }
+}
+
+/// [`record`] for an action taken by `user` on their account's fleet.
+pub async fn record_user(
+    state: &AppState,
+    user: &AuthUser,
+    sender_id: Option<&str>,
+    action: &str,
+    detail: Option<String>,
+) {
+    record(
+        state,
+        &user.owner_id,
+        Some(&user.user_id),
+        sender_id,
+        action,
+        detail,
+    )
+    .await;
+}
@@ -1,17 +1,34 @@
 //! Authentication endpoints.
 //!
-//! POST /api/auth/register — create a new user
-//! POST /api/auth/login    — exchange credentials for a JWT
+//! POST /api/auth/register        — create a new user
+//! POST /api/auth/login           — exchange credentials for a JWT
+//! POST /api/auth/forgot-password — email a password reset link
+//! POST /api/auth/reset-password  — set a new password from a reset link
+//! POST /api/auth/accept-invite   — set a first password from an invitation
+//!
+//! Reset and invitation links carry a signed token (`Claims::invite`,
+//! `Claims::password_reset`) bound to the password hash it was issued
+//! against, so setting the password uses it up.
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
 
-use axum::extract::State;
-use axum::http::StatusCode;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::routing::post;
-use axum::{Json, Router};
+use axum::{Extension, Json, Router};
 use chrono::Utc;
+use dashmap::DashMap;
+use utoipa::openapi::{Content, Ref, RefOr, Response, ResponseBuilder};
 
 use strata_common::auth;
-use strata_common::ids;
-use strata_protocol::api::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse};
+use strata_common::error::{ErrorCode, StrataError};
+use strata_common::ids::{self, UserId};
+use strata_protocol::api::{
+    ApiErrorResponse, ForgotPasswordRequest, LoginRequest, LoginResponse, RegisterRequest,
+    RegisterResponse, SetPasswordRequest,
+};
 
 use crate::state::AppState;
 
@@ -19,18 +36,58 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/register", post(register))
         .route("/login", post(login))
+        .route("/forgot-password", post(forgot_password))
+        .route("/reset-password", post(reset_password))
+        .route("/accept-invite", post(accept_invite))
 }
 
 // ── Register ────────────────────────────────────────────────────────
 
+/// Rules for user-chosen passwords, from `PASSWORD_MIN_LENGTH`,
+/// `PASSWORD_MIN_SCORE` and `PASSWORD_BREACH_LIST` (a file of breached
+/// SHA-1 hashes). Read at startup: a breach list that is set but can't be
+/// read stops the server rather than silently allowing every password.
+pub fn password_policy_from_env() -> anyhow::Result<auth::PasswordPolicy> {
+    let mut policy = auth::PasswordPolicy::default();
+    let env_num = |name: &str| -> anyhow::Result<Option<usize>> {
+        std::env::var(name)
+            .ok()
+            .map(|v| {
+                v.parse()
+                    .map_err(|e| anyhow::anyhow!("invalid {name} {v:?}: {e}"))
+            })
+            .transpose()
+    };
+    if let Some(min) = env_num("PASSWORD_MIN_LENGTH")? {
+        policy.min_length = min;
+    }
+    if let Some(score) = env_num("PASSWORD_MIN_SCORE")? {
+        policy.min_score = score.min(4) as u8;
+    }
+    if let Ok(path) = std::env::var("PASSWORD_BREACH_LIST") {
+        policy.breached = auth::BreachList::load(&path)
+            .map_err(|e| anyhow::anyhow!("cannot read PASSWORD_BREACH_LIST {path}: {e}"))?;
+        tracing::info!(path = %path, hashes = policy.breached.len(), "loaded password breach list");
+    }
+    Ok(policy)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    tag = "auth",
+    summary = "Create a new user",
+    request_body = RegisterRequest,
+    responses((status = 201, description = "Created", body = RegisterResponse), ApiError)
+)]
 async fn register(
     State(state): State<AppState>,
     Json(body): Json<RegisterRequest>,
 ) -> Result<(StatusCode, Json<RegisterResponse>), ApiError> {
-    // Role checks are not implemented yet (require_role is a stub), so any
-    // account has full control of every device. On an internet-facing
-    // deployment, set DISABLE_REGISTRATION=1 once the operator accounts
-    // exist — otherwise anyone can register and drive the fleet.
+    // Every registration creates a new account whose owner is its admin;
+    // further users join an account by invitation (api/users.rs). On an
+    // internet-facing deployment, set DISABLE_REGISTRATION=1 once the
+    // accounts exist.
     if std::env::var("DISABLE_REGISTRATION").is_ok() {
         return Err(ApiError::forbidden("registration is disabled"));
     }
@@ -39,11 +96,10 @@ async fn register(
     if body.email.is_empty() || !body.email.contains('@') {
         return Err(ApiError::bad_request("invalid email"));
     }
-    if body.password.len() < 8 {
-        return Err(ApiError::bad_request(
-            "password must be at least 8 characters",
-        ));
-    }
+    state
+        .password_policy()
+        .check(&body.password, &[&body.email])
+        .map_err(|e| ApiError::bad_request(e.message))?;
 
     // Hash password
     let password_hash =
@@ -52,21 +108,21 @@ async fn register(
     let user_id = ids::user_id();
 
     // Insert
-    sqlx::query(
-        "INSERT INTO users (id, email, password_hash, role) VALUES ($1, $2, $3, 'operator')",
-    )
-    .bind(&user_id)
-    .bind(&body.email)
-    .bind(&password_hash)
-    .execute(state.pool())
-    .await
-    .map_err(|e| {
-        if e.to_string().contains("duplicate key") || e.to_string().contains("unique constraint") {
-            ApiError::conflict("email already registered")
-        } else {
-            ApiError::internal(e.to_string())
-        }
-    })?;
+    sqlx::query("INSERT INTO users (id, email, password_hash, role) VALUES ($1, $2, $3, 'admin')")
+        .bind(&user_id)
+        .bind(&body.email)
+        .bind(&password_hash)
+        .execute(state.pool())
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("duplicate key")
+                || e.to_string().contains("unique constraint")
+            {
+                ApiError::conflict("email already registered")
+            } else {
+                ApiError::internal(e.to_string())
+            }
+        })?;
 
     tracing::info!(user_id = %user_id, email = %body.email, "user registered");
 
@@ -81,39 +137,46 @@ async fn register(
 
 // ── Login ───────────────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    summary = "Exchange credentials for a JWT",
+    request_body = LoginRequest,
+    responses((status = 200, description = "OK", body = LoginResponse), ApiError)
+)]
 async fn login(
     State(state): State<AppState>,
     Json(body): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
     // Look up user
-    let row = sqlx::query_as::<_, (String, String, String)>(
-        "SELECT id, password_hash, role FROM users WHERE email = $1",
+    let row = sqlx::query_as::<_, (UserId, String, String, bool)>(
+        "SELECT id, password_hash, role, disabled_at IS NOT NULL FROM users WHERE email = $1",
     )
     .bind(&body.email)
     .fetch_optional(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?
-    .ok_or_else(|| ApiError::unauthorized("invalid email or password"))?;
+    .ok_or_else(|| {
+        ApiError::unauthorized("invalid email or password").with_code(ErrorCode::InvalidCredentials)
+    })?;
 
-    let (user_id, password_hash, role) = row;
+    let (user_id, password_hash, role, disabled) = row;
 
     // Verify password
     let valid = auth::verify_password(&body.password, &password_hash)
         .map_err(|e| ApiError::internal(e.to_string()))?;
     if !valid {
-        return Err(ApiError::unauthorized("invalid email or password"));
+        return Err(ApiError::unauthorized("invalid email or password")
+            .with_code(ErrorCode::InvalidCredentials));
+    }
+    if disabled {
+        return Err(ApiError::forbidden("account is disabled"));
     }
 
     // Issue JWT
     let now = Utc::now().timestamp();
-    let claims = auth::Claims {
-        sub: user_id.clone(),
-        iss: "strata-control".into(),
-        exp: now + auth::SESSION_TOKEN_TTL_SECS,
-        iat: now,
-        role: role.clone(),
-        owner: None,
-    };
+    let claims = auth::Claims::user(&user_id, &role, false, now);
     let token = state
         .jwt()
         .create_token(&claims)
@@ -124,6 +187,245 @@ async fn login(
     Ok(Json(LoginResponse {
         token,
         user_id,
+        capabilities: super::auth_extractor::capabilities(&role),
+        role,
+    }))
+}
+
+// ── Password links ──────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/forgot-password",
+    tag = "auth",
+    summary = "Email a password reset link",
+    request_body = ForgotPasswordRequest,
+    responses((status = 202, description = "Accepted"), ApiError)
+)]
+async fn forgot_password(
+    State(state): State<AppState>,
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+    Json(body): Json<ForgotPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    // Counted before the lookup, so being throttled says nothing about
+    // whether the address has an account.
+    let email = body.email.trim();
+    let now = Instant::now();
+    let throttle = state.password_resets();
+    if let Some(ip) = client_ip(peer.map(|Extension(ConnectInfo(addr))| addr), &headers) {
+        throttle.hit(&format!("ip:{ip}"), RESETS_PER_IP, RESET_WINDOW, now)?;
+    }
+    throttle.hit(
+        &format!("email:{}", email.to_lowercase()),
+        RESETS_PER_EMAIL,
+        RESET_WINDOW,
+        now,
+    )?;
+
+    let row = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, password_hash FROM users \
+         WHERE email = $1 AND disabled_at IS NULL AND erased_at IS NULL",
+    )
+    .bind(email)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    // Same answer either way; the outcome only shows up in the inbox.
+    let Some((user_id, password_hash)) = row else {
+        return Ok(StatusCode::ACCEPTED);
+    };
+    if state.mailer().is_none() {
+        tracing::warn!(user_id = %user_id, "password reset requested but MAIL_FROM is not set");
+        return Ok(StatusCode::ACCEPTED);
+    }
+    let claims = auth::Claims::password_reset(&user_id, &password_hash, Utc::now().timestamp());
+    let token = state
+        .jwt()
+        .create_token(&claims)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let email = email.to_string();
+    tokio::spawn(async move {
+        email_reset_link(&state, &email, &token).await;
+    });
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Reset links one email address can be sent per [`RESET_WINDOW`].
+const RESETS_PER_EMAIL: u32 = 3;
+/// Reset requests one client may make per [`RESET_WINDOW`], across
+/// addresses.
+const RESETS_PER_IP: u32 = 10;
+const RESET_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Fixed-window request counts per key (an email address or client IP),
+/// so unauthenticated endpoints that send mail can't be used to flood an
+/// inbox or the MTA.
+#[derive(Default)]
+pub struct RequestThrottle {
+    /// Key → (window start, requests in it).
+    windows: DashMap<String, (Instant, u32)>,
+}
+
+impl RequestThrottle {
+    /// Count a request for `key`, or refuse it once `limit` requests were
+    /// made in the current `window`.
+    fn hit(&self, key: &str, limit: u32, window: Duration, now: Instant) -> Result<(), ApiError> {
+        if self.windows.len() > 10_000 {
+            self.windows
+                .retain(|_, (started, _)| now.duration_since(*started) < window);
+        }
+        let mut entry = self.windows.entry(key.to_string()).or_insert((now, 0));
+        let (started, count) = &mut *entry;
+        if now.duration_since(*started) >= window {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            let wait = window - now.duration_since(*started);
+            tracing::warn!("password reset requests throttled");
+            return Err(ApiError::too_many_requests(format!(
+                "too many reset requests; try again in {} min",
+                wait.as_secs().div_ceil(60)
+            )));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// The requesting client's address: the TCP peer, or — when the peer is a
+/// reverse proxy on this host — the address it appended to
+/// `X-Forwarded-For`. Nothing further left in that header is trusted.
+fn client_ip(peer: Option<SocketAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+    let peer = peer?.ip();
+    if !peer.is_loopback() {
+        return Some(peer);
+    }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse().ok())
+        .or(Some(peer))
+}
+
+/// Email `to` the password reset link for `token`. Returns whether it was
+/// handed to the MTA.
+pub(crate) async fn email_reset_link(state: &AppState, to: &str, token: &str) -> bool {
+    let Some(mailer) = state.mailer() else {
+        return false;
+    };
+    let body = format!(
+        "Someone asked to reset the password of your Strata account.\n\n\
+         Choose a new password here (the link works once, for an hour):\n\n{}\n\n\
+         If it wasn't you, ignore this message — your password stays as it is.",
+        mailer.link(&format!("/reset-password/{token}"))
+    );
+    match mailer.send(to, "Reset your Strata password", &body).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to email password reset link");
+            false
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/reset-password",
+    tag = "auth",
+    summary = "Set a new password from a reset link",
+    request_body = SetPasswordRequest,
+    responses((status = 200, description = "OK", body = LoginResponse), ApiError)
+)]
+async fn reset_password(
+    State(state): State<AppState>,
+    Json(body): Json<SetPasswordRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    set_password(&state, body, auth::TokenScope::PasswordReset).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/accept-invite",
+    tag = "auth",
+    summary = "Set a first password from an invitation",
+    request_body = SetPasswordRequest,
+    responses((status = 200, description = "OK", body = LoginResponse), ApiError)
+)]
+async fn accept_invite(
+    State(state): State<AppState>,
+    Json(body): Json<SetPasswordRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    set_password(&state, body, auth::TokenScope::Invite).await
+}
+
+/// Set the password a `scope` link's token is for, and sign the user in.
+async fn set_password(
+    state: &AppState,
+    body: SetPasswordRequest,
+    scope: auth::TokenScope,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let expired = || ApiError::unauthorized("this link is invalid or has expired");
+    let claims = state
+        .jwt()
+        .verify_scoped(&body.token, &[scope])
+        .map_err(|_| expired())?;
+
+    let (user_id, email, password_hash, role, owner_id, disabled) =
+        sqlx::query_as::<_, (UserId, String, String, String, String, bool)>(
+            "SELECT id, email, password_hash, role, COALESCE(owner_id, id), \
+             disabled_at IS NOT NULL FROM users WHERE id = $1 AND erased_at IS NULL",
+        )
+        .bind(&claims.sub)
+        .fetch_optional(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(expired)?;
+    if !claims.issued_for_password(&password_hash) {
+        return Err(ApiError::unauthorized("this link has already been used"));
+    }
+    if disabled {
+        return Err(ApiError::forbidden("account is disabled"));
+    }
+    state
+        .password_policy()
+        .check(&body.password, &[&email])
+        .map_err(|e| ApiError::bad_request(e.message))?;
+
+    let new_hash =
+        auth::hash_password(&body.password).map_err(|e| ApiError::internal(e.to_string()))?;
+    // Compare-and-set: of two requests racing with one link, one wins.
+    let updated =
+        sqlx::query("UPDATE users SET password_hash = $3 WHERE id = $1 AND password_hash = $2")
+            .bind(&user_id)
+            .bind(&password_hash)
+            .bind(&new_hash)
+            .execute(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+    if updated.rows_affected() == 0 {
+        return Err(ApiError::unauthorized("this link has already been used"));
+    }
+
+    let action = match scope {
+        auth::TokenScope::Invite => "user.accept_invite",
+        _ => "user.password_reset",
+    };
+    super::audit::record(state, &owner_id, Some(&user_id), None, action, None).await;
+    tracing::info!(user_id = %user_id, action, "password set from link");
+
+    let now = Utc::now().timestamp();
+    let token = state
+        .jwt()
+        .create_token(&auth::Claims::user(&user_id, &role, false, now))
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(LoginResponse {
+        token,
+        user_id,
+        capabilities: super::auth_extractor::capabilities(&role),
         role,
     }))
 }
@@ -133,51 +435,115 @@ async fn login(
 #[derive(Debug)]
 pub struct ApiError {
     status: StatusCode,
+    code: ErrorCode,
     message: String,
 }
 
 impl ApiError {
-    pub fn bad_request(msg: impl Into<String>) -> Self {
+    fn new(status: StatusCode, code: ErrorCode, msg: impl Into<String>) -> Self {
         Self {
-            status: StatusCode::BAD_REQUEST,
+            status,
+            code,
             message: msg.into(),
         }
     }
+
+    pub fn bad_request(msg: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidInput, msg)
+    }
     pub fn unauthorized(msg: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::UNAUTHORIZED,
-            message: msg.into(),
-        }
+        Self::new(StatusCode::UNAUTHORIZED, ErrorCode::Unauthenticated, msg)
     }
     pub fn forbidden(msg: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::FORBIDDEN,
-            message: msg.into(),
-        }
+        Self::new(StatusCode::FORBIDDEN, ErrorCode::Forbidden, msg)
     }
     pub fn not_found(msg: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::NOT_FOUND,
-            message: msg.into(),
-        }
+        Self::new(StatusCode::NOT_FOUND, ErrorCode::NotFound, msg)
     }
     pub fn conflict(msg: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::CONFLICT,
-            message: msg.into(),
-        }
+        Self::new(StatusCode::CONFLICT, ErrorCode::Conflict, msg)
+    }
+    pub fn too_many_requests(msg: impl Into<String>) -> Self {
+        Self::new(StatusCode::TOO_MANY_REQUESTS, ErrorCode::RateLimited, msg)
     }
     pub fn internal(msg: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::INTERNAL_SERVER_ERROR,
-            message: msg.into(),
-        }
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal, msg)
+    }
+
+    /// Replace the status-derived default code with a more precise one
+    /// (e.g. a 409 that is really [`ErrorCode::DeviceOffline`]).
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// An agent's `{success: false, error, code}` RPC reply. Agents only
+    /// send a code in NAKs, so anything else is [`ErrorCode::DeviceRejected`].
+    pub fn rejected_by_agent(value: &serde_json::Value, fallback: &str) -> Self {
+        let message = value
+            .get("error")
+            .and_then(|v| v.as_str())
+            .unwrap_or(fallback);
+        let code = value
+            .get("code")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(ErrorCode::DeviceRejected);
+        Self::new(StatusCode::BAD_REQUEST, code, message)
+    }
+
+    /// The client-facing message, for callers that record the failure
+    /// rather than return it.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<StrataError> for ApiError {
+    fn from(e: StrataError) -> Self {
+        let status = match e.code {
+            ErrorCode::Unauthenticated | ErrorCode::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::InvalidInput | ErrorCode::UnsupportedCommand => StatusCode::BAD_REQUEST,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict | ErrorCode::DeviceOffline | ErrorCode::DeviceBusy => {
+                StatusCode::CONFLICT
+            }
+            ErrorCode::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::DeviceRejected => StatusCode::BAD_GATEWAY,
+            ErrorCode::Internal | ErrorCode::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(status, e.code, e.message)
     }
 }
 
 impl axum::response::IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let body = serde_json::json!({ "error": self.message });
+        let body = ApiErrorResponse {
+            error: self.message,
+            code: Some(self.code),
+        };
         (self.status, Json(body)).into_response()
     }
 }
+
+/// In the OpenAPI spec every failure is an [`ApiErrorResponse`]; which
+/// statuses a handler returns is left to its docs.
+impl utoipa::IntoResponses for ApiError {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        let error = |description: &str| {
+            ResponseBuilder::new()
+                .description(description)
+                .content(
+                    "application/json",
+                    Content::new(Some(Ref::from_schema_name("ApiErrorResponse"))),
+                )
+                .build()
+                .into()
+        };
+        BTreeMap::from([
+            ("4XX".to_string(), error("Rejected request")),
+            ("5XX".to_string(), error("Server error")),
+        ])
+    }
+}
@@ -6,24 +6,84 @@ use axum::http::StatusCode;
 use axum::http::request::Parts;
 use axum::response::{IntoResponse, Response};
 
+use strata_common::auth::TokenScope;
+use strata_protocol::api::Capability;
+
 use crate::state::AppState;
 
 /// Extractor that validates the `Authorization: Bearer <jwt>` header and
-/// provides the authenticated user's ID and role.
+/// provides the authenticated user's ID, account and role.
+///
+/// Role and account are read from the users table on every request, not
+/// from the token, so role changes and disabling take effect immediately.
 pub struct AuthUser {
     pub user_id: String,
+    /// The account whose fleet this user works on — the user's own ID for
+    /// account owners, the inviting account for invited users. Data is
+    /// scoped by this in `owner_id` WHERE clauses.
+    pub owner_id: String,
     pub role: String,
 }
 
+/// Rank of a role in the `viewer < operator < admin` hierarchy; unknown
+/// roles rank below viewer.
+pub fn role_rank(role: &str) -> u8 {
+    match role {
+        "admin" => 3,
+        "operator" => 2,
+        "viewer" => 1,
+        _ => 0,
+    }
+}
+
+/// Every action `role` is allowed, for `/api/me` and login responses.
+pub fn capabilities(role: &str) -> Vec<Capability> {
+    Capability::ALL
+        .iter()
+        .copied()
+        .filter(|c| role_rank(role) >= role_rank(c.min_role()))
+        .collect()
+}
+
 impl AuthUser {
-    /// Every authenticated user has full access.  Data isolation is
-    /// enforced by `owner_id` WHERE clauses, not role checks.
-    pub fn has_role(&self, _required_role: &str) -> bool {
-        true
+    /// Whether the user's role is at least `required_role`.
+    pub fn has_role(&self, required_role: &str) -> bool {
+        role_rank(&self.role) >= role_rank(required_role)
+    }
+
+    /// Every action the user's role allows.
+    pub fn capabilities(&self) -> Vec<Capability> {
+        capabilities(&self.role)
+    }
+
+    /// Refuse with 403 unless the user's role allows `capability`.
+    pub fn require(&self, capability: Capability) -> Result<(), crate::api::auth::ApiError> {
+        let required_role = capability.min_role();
+        if self.has_role(required_role) {
+            Ok(())
+        } else {
+            Err(crate::api::auth::ApiError::forbidden(format!(
+                "requires the {required_role} role"
+            )))
+        }
     }
 
-    pub fn require_role(&self, _required_role: &str) -> Result<(), crate::api::auth::ApiError> {
-        Ok(())
+    /// Load the current account and role for `user_id`. `None` if the
+    /// user no longer exists or has been disabled; an error only if the
+    /// database couldn't be asked.
+    pub async fn load(state: &AppState, user_id: &str) -> Result<Option<Self>, sqlx::Error> {
+        let row = sqlx::query_as::<_, (String, String)>(
+            "SELECT COALESCE(owner_id, id), role FROM users \
+             WHERE id = $1 AND disabled_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_optional(state.pool())
+        .await?;
+        Ok(row.map(|(owner_id, role)| Self {
+            user_id: user_id.to_string(),
+            owner_id,
+            role,
+        }))
     }
 }
 
@@ -49,13 +109,16 @@ where
 
         let claims = app_state
             .jwt()
-            .verify_token(token)
+            .verify_scoped(token, &[TokenScope::User, TokenScope::ApiKey])
             .map_err(|_| AuthRejection::Invalid)?;
 
-        Ok(AuthUser {
-            user_id: claims.sub,
-            role: claims.role,
-        })
+        AuthUser::load(&app_state, &claims.sub)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to load authenticated user");
+                AuthRejection::Unavailable
+            })?
+            .ok_or(AuthRejection::Invalid)
     }
 }
 
@@ -74,6 +137,9 @@ impl FromRef<AppState> for AppState {
 pub enum AuthRejection {
     Missing,
     Invalid,
+    /// The token checked out but the user couldn't be looked up — a server
+    /// fault, not grounds to log the client out.
+    Unavailable,
 }
 
 impl IntoResponse for AuthRejection {
@@ -81,6 +147,7 @@ impl IntoResponse for AuthRejection {
         let (status, msg) = match self {
             AuthRejection::Missing => (StatusCode::UNAUTHORIZED, "missing authorization header"),
             AuthRejection::Invalid => (StatusCode::UNAUTHORIZED, "invalid or expired token"),
+            AuthRejection::Unavailable => (StatusCode::INTERNAL_SERVER_ERROR, "internal error"),
         };
         (status, Json(serde_json::json!({ "error": msg }))).into_response()
     }
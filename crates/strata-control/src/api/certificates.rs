@@ -0,0 +1,1054 @@
+//! TLS certificates for device HTTPS endpoints (agent portal, receiver
+//! preview).
+//!
+//! GET    /api/certificates?sender_id=&receiver_id=  — list (operator)
+//! POST   /api/certificates                          — upload a PEM pair (admin)
+//! POST   /api/certificates/self-signed              — issue a self-signed one (admin)
+//! POST   /api/certificates/acme                     — start an ACME DNS-01 order (admin)
+//! POST   /api/certificates/:id/verify               — finish the order once the TXT record is up (admin)
+//! POST   /api/certificates/:id/renew                — re-issue the same way (admin)
+//! POST   /api/certificates/:id/push                 — send it to the device again (admin)
+//! DELETE /api/certificates/:id                      — delete (admin)
+//!
+//! The newest issued certificate per device is the live one: it is pushed
+//! with `tls.install` as soon as it is issued and on every device connect
+//! ([`push_to_sender`], [`push_to_receiver`]). [`check_expiry`] runs every
+//! [`EXPIRY_CHECK_INTERVAL`] and keeps a renewal alert open on each sender
+//! whose live certificate is within [`CERT_RENEW_WITHIN_DAYS`] of expiry.
+//!
+//! Private keys are stored sealed ([`SecretBox`]) and only opened to be
+//! pushed; [`seal_stored_keys`] seals any left in plaintext from before.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_common::auth::SecretBox;
+use strata_common::ids;
+use strata_common::tls::server::ReloadableCert;
+use strata_common::tls::{self, CertInfo};
+use strata_protocol::api::{
+    AcmeCertificateRequest, CERT_RENEW_WITHIN_DAYS, Capability, CertificateSummary,
+    CertificateTarget, SelfSignedCertificateRequest, UploadCertificateRequest,
+};
+use strata_protocol::{ControlMessage, Envelope, ReceiverControlMessage, TlsInstallPayload};
+
+use crate::acme;
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// How often [`check_expiry`] runs.
+pub const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// `rule_id` of certificate renewal alerts. Stream-rule evaluation leaves
+/// these alone.
+pub const EXPIRY_RULE_ID: &str = "tls_expiry";
+
+/// Inside this many days a renewal alert is critical rather than a warning.
+const CRITICAL_WITHIN_DAYS: i64 = 7;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_certificates).post(upload_certificate))
+        .route("/self-signed", post(issue_self_signed))
+        .route("/acme", post(start_acme))
+        .route("/{id}", axum::routing::delete(delete_certificate))
+        .route("/{id}/verify", post(verify_acme))
+        .route("/{id}/renew", post(renew_certificate))
+        .route("/{id}/push", post(push_certificate))
+}
+
+/// The device a certificate is for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Device {
+    Sender(String),
+    Receiver(String),
+}
+
+impl Device {
+    fn from_columns(sender_id: Option<String>, receiver_id: Option<String>) -> Option<Self> {
+        sender_id
+            .map(Device::Sender)
+            .or(receiver_id.map(Device::Receiver))
+    }
+
+    fn sender_id(&self) -> Option<&str> {
+        match self {
+            Device::Sender(id) => Some(id),
+            Device::Receiver(_) => None,
+        }
+    }
+
+    fn receiver_id(&self) -> Option<&str> {
+        match self {
+            Device::Sender(_) => None,
+            Device::Receiver(id) => Some(id),
+        }
+    }
+}
+
+/// Check the target names exactly one device of the caller's account.
+async fn resolve_target(
+    state: &AppState,
+    user: &AuthUser,
+    target: &CertificateTarget,
+) -> Result<Device, ApiError> {
+    match (&target.sender_id, &target.receiver_id) {
+        (Some(sender_id), None) => {
+            super::senders::verify_ownership(state, user, sender_id).await?;
+            Ok(Device::Sender(sender_id.clone()))
+        }
+        (None, Some(receiver_id)) => {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM receivers WHERE id = $1 AND owner_id = $2)",
+            )
+            .bind(receiver_id)
+            .bind(&user.owner_id)
+            .fetch_one(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+            if !exists {
+                return Err(ApiError::not_found("receiver not found"));
+            }
+            Ok(Device::Receiver(receiver_id.clone()))
+        }
+        _ => Err(ApiError::bad_request(
+            "exactly one of sender_id and receiver_id is required",
+        )),
+    }
+}
+
+/// A host name a CA will issue for: a plain DNS name, optionally with a
+/// leading `*.` wildcard label. IP addresses don't qualify.
+fn validate_domain(domain: &str) -> Result<String, ApiError> {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let name = domain.strip_prefix("*.").unwrap_or(&domain);
+    let err = || ApiError::bad_request(format!("{domain:?} is not a valid domain name"));
+    if name.parse::<std::net::IpAddr>().is_ok() || !name.contains('.') {
+        return Err(err());
+    }
+    strata_common::validation::validate_host(name).map_err(|_| err())?;
+    Ok(domain)
+}
+
+type CertRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    DateTime<Utc>,
+);
+
+const CERT_COLUMNS: &str = "id, sender_id, receiver_id, domain, source, status, issuer, \
+     not_after, dns_txt_name, dns_txt_value, error, pushed_at, created_at";
+
+fn summary_from_row(row: CertRow) -> CertificateSummary {
+    let (
+        id,
+        sender_id,
+        receiver_id,
+        domain,
+        source,
+        status,
+        issuer,
+        not_after,
+        dns_txt_name,
+        dns_txt_value,
+        error,
+        pushed_at,
+        created_at,
+    ) = row;
+    CertificateSummary {
+        id,
+        target: CertificateTarget {
+            sender_id,
+            receiver_id,
+        },
+        domain,
+        source,
+        status,
+        issuer,
+        not_after,
+        dns_txt_name,
+        dns_txt_value,
+        error,
+        pushed_at,
+        created_at,
+    }
+}
+
+async fn fetch_summary(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<CertificateSummary, ApiError> {
+    sqlx::query_as::<_, CertRow>(&format!(
+        "SELECT {CERT_COLUMNS} FROM tls_certificates WHERE id = $1 AND owner_id = $2"
+    ))
+    .bind(id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .map(summary_from_row)
+    .ok_or_else(|| ApiError::not_found("certificate not found"))
+}
+
+/// Parse and check a PEM pair: a readable, unexpired leaf and a key that
+/// belongs to it.
+fn check_pair(cert_pem: &str, key_pem: &str) -> Result<CertInfo, ApiError> {
+    let info = tls::pem_cert_info(cert_pem).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    if info.not_after <= Utc::now() {
+        return Err(ApiError::bad_request("certificate has expired"));
+    }
+    ReloadableCert::new()
+        .set_pem(cert_pem, key_pem)
+        .map_err(|e| ApiError::bad_request(format!("key does not match certificate: {e}")))?;
+    Ok(info)
+}
+
+/// Record a finished certificate as the device's live one — filling in
+/// the pending row `id`, or a new row — and push it.
+#[allow(clippy::too_many_arguments)]
+async fn store_issued(
+    state: &AppState,
+    owner_id: &str,
+    device: &Device,
+    id: Option<&str>,
+    domain: &str,
+    source: &str,
+    cert_pem: &str,
+    key_pem: &str,
+) -> Result<String, ApiError> {
+    let info = check_pair(cert_pem, key_pem)?;
+    let secrets = state.secrets().ok_or_else(|| {
+        ApiError::internal("certificate keys can't be stored: SECRETS_KEY_B64 is not configured")
+    })?;
+    let id = id.map(str::to_string).unwrap_or_else(ids::certificate_id);
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query(
+        "UPDATE tls_certificates SET status = 'superseded' \
+         WHERE status = 'issued' AND (sender_id = $1 OR receiver_id = $2)",
+    )
+    .bind(device.sender_id())
+    .bind(device.receiver_id())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO tls_certificates \
+         (id, owner_id, sender_id, receiver_id, domain, source, status, cert_pem, key_pem, issuer, not_after) \
+         VALUES ($1, $2, $3, $4, $5, $6, 'issued', $7, $8, $9, $10) \
+         ON CONFLICT (id) DO UPDATE SET status = 'issued', cert_pem = EXCLUDED.cert_pem, \
+         key_pem = EXCLUDED.key_pem, issuer = EXCLUDED.issuer, not_after = EXCLUDED.not_after, \
+         dns_txt_name = NULL, dns_txt_value = NULL, error = NULL",
+    )
+    .bind(&id)
+    .bind(owner_id)
+    .bind(device.sender_id())
+    .bind(device.receiver_id())
+    .bind(domain)
+    .bind(source)
+    .bind(cert_pem)
+    .bind(secrets.seal(KEY_CONTEXT, key_pem))
+    .bind(&info.issuer)
+    .bind(info.not_after)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(certificate_id = %id, device = ?device, not_after = %info.not_after, "certificate issued");
+    push(state, device).await;
+    Ok(id)
+}
+
+// ── List ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CertificateQuery {
+    sender_id: Option<String>,
+    receiver_id: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/certificates",
+    tag = "certificates",
+    params(CertificateQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<CertificateSummary>), ApiError)
+)]
+async fn list_certificates(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<CertificateQuery>,
+) -> Result<Json<Vec<CertificateSummary>>, ApiError> {
+    user.require(Capability::ViewCertificates)?;
+    let rows = sqlx::query_as::<_, CertRow>(&format!(
+        "SELECT {CERT_COLUMNS} FROM tls_certificates \
+         WHERE owner_id = $1 AND ($2::text IS NULL OR sender_id = $2) \
+           AND ($3::text IS NULL OR receiver_id = $3) \
+         ORDER BY created_at DESC"
+    ))
+    .bind(&user.owner_id)
+    .bind(&query.sender_id)
+    .bind(&query.receiver_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(rows.into_iter().map(summary_from_row).collect()))
+}
+
+// ── Issue ───────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/certificates",
+    tag = "certificates",
+    summary = "Upload a PEM pair (admin)",
+    request_body = UploadCertificateRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CertificateSummary), ApiError)
+)]
+async fn upload_certificate(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<UploadCertificateRequest>,
+) -> Result<(StatusCode, Json<CertificateSummary>), ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let device = resolve_target(&state, &user, &body.target).await?;
+    let info = check_pair(&body.cert_pem, &body.key_pem)?;
+    let domain = info.common_name().unwrap_or("(no CN)").to_string();
+
+    let id = store_issued(
+        &state,
+        &user.owner_id,
+        &device,
+        None,
+        &domain,
+        "uploaded",
+        &body.cert_pem,
+        &body.key_pem,
+    )
+    .await?;
+    super::audit::record_user(
+        &state,
+        &user,
+        device.sender_id(),
+        "certificate.upload",
+        Some(domain),
+    )
+    .await;
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_summary(&state, &user, &id).await?),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/certificates/self-signed",
+    tag = "certificates",
+    summary = "Issue a self-signed certificate (admin)",
+    request_body = SelfSignedCertificateRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CertificateSummary), ApiError)
+)]
+async fn issue_self_signed(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<SelfSignedCertificateRequest>,
+) -> Result<(StatusCode, Json<CertificateSummary>), ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let device = resolve_target(&state, &user, &body.target).await?;
+    let domain = validate_domain(&body.domain)?;
+
+    let id = self_signed_for(&state, &user.owner_id, &device, &domain).await?;
+    super::audit::record_user(
+        &state,
+        &user,
+        device.sender_id(),
+        "certificate.self_signed",
+        Some(domain),
+    )
+    .await;
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_summary(&state, &user, &id).await?),
+    ))
+}
+
+async fn self_signed_for(
+    state: &AppState,
+    owner_id: &str,
+    device: &Device,
+    domain: &str,
+) -> Result<String, ApiError> {
+    let (cert_pem, key_pem) =
+        acme::self_signed(domain).map_err(|e| ApiError::internal(e.to_string()))?;
+    store_issued(
+        state,
+        owner_id,
+        device,
+        None,
+        domain,
+        "self_signed",
+        &cert_pem,
+        &key_pem,
+    )
+    .await
+}
+
+// ── ACME ────────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/certificates/acme",
+    tag = "certificates",
+    summary = "Start an ACME DNS-01 order (admin)",
+    request_body = AcmeCertificateRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CertificateSummary), ApiError)
+)]
+async fn start_acme(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<AcmeCertificateRequest>,
+) -> Result<(StatusCode, Json<CertificateSummary>), ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let device = resolve_target(&state, &user, &body.target).await?;
+    let domain = validate_domain(&body.domain)?;
+    let contact_email = body
+        .contact_email
+        .as_deref()
+        .map(str::trim)
+        .filter(|e| !e.is_empty());
+    if contact_email.is_some_and(|e| !e.contains('@')) {
+        return Err(ApiError::bad_request(
+            "contact_email is not an email address",
+        ));
+    }
+
+    let id = start_order_for(
+        &state,
+        &user.owner_id,
+        &device,
+        &domain,
+        acme::directory_url(body.staging),
+        contact_email,
+    )
+    .await?;
+    super::audit::record_user(
+        &state,
+        &user,
+        device.sender_id(),
+        "certificate.acme_order",
+        Some(domain),
+    )
+    .await;
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_summary(&state, &user, &id).await?),
+    ))
+}
+
+async fn start_order_for(
+    state: &AppState,
+    owner_id: &str,
+    device: &Device,
+    domain: &str,
+    directory_url: &str,
+    contact_email: Option<&str>,
+) -> Result<String, ApiError> {
+    let acme_error = |e: anyhow::Error| ApiError::internal(format!("ACME: {e}"));
+    let account = acme::account(state, owner_id, directory_url, contact_email)
+        .await
+        .map_err(acme_error)?;
+    let order = acme::start_order(&account, domain)
+        .await
+        .map_err(acme_error)?;
+
+    let id = ids::certificate_id();
+    sqlx::query(
+        "INSERT INTO tls_certificates \
+         (id, owner_id, sender_id, receiver_id, domain, source, status, acme_order_url, \
+          dns_txt_name, dns_txt_value) \
+         VALUES ($1, $2, $3, $4, $5, 'acme', 'pending', $6, $7, $8)",
+    )
+    .bind(&id)
+    .bind(owner_id)
+    .bind(device.sender_id())
+    .bind(device.receiver_id())
+    .bind(domain)
+    .bind(&order.order_url)
+    .bind(&order.txt_name)
+    .bind(&order.txt_value)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    tracing::info!(certificate_id = %id, domain, txt = ?order.txt_name, "acme order started");
+    Ok(id)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/certificates/{id}/verify",
+    tag = "certificates",
+    summary = "Finish the order once the TXT record is up (admin)",
+    params(("id" = String, Path, description = "Certificate ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = CertificateSummary), ApiError)
+)]
+async fn verify_acme(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<CertificateSummary>, ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let row = sqlx::query_as::<
+        _,
+        (
+            Option<String>,
+            Option<String>,
+            String,
+            String,
+            Option<String>,
+        ),
+    >(
+        "SELECT sender_id, receiver_id, domain, status, acme_order_url \
+         FROM tls_certificates WHERE id = $1 AND owner_id = $2",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("certificate not found"))?;
+    let (sender_id, receiver_id, domain, status, order_url) = row;
+    let (Some(device), Some(order_url)) = (Device::from_columns(sender_id, receiver_id), order_url)
+    else {
+        return Err(ApiError::conflict("not an ACME order"));
+    };
+    if status != "pending" {
+        return Err(ApiError::conflict(format!("certificate is {status}")));
+    }
+
+    // The order's account is whichever of the owner's accounts created it.
+    let directory_url = if order_url.contains("staging") {
+        acme::directory_url(true)
+    } else {
+        acme::directory_url(false)
+    };
+    let completed = match acme::account(&state, &user.owner_id, directory_url, None).await {
+        Ok(account) => acme::complete_order(&account, &order_url, &domain).await,
+        Err(e) => Err(e),
+    };
+    let failure = match completed {
+        Ok(acme::Completion::Issued { cert_pem, key_pem }) => {
+            store_issued(
+                &state,
+                &user.owner_id,
+                &device,
+                Some(&id),
+                &domain,
+                "acme",
+                &cert_pem,
+                &key_pem,
+            )
+            .await?;
+            super::audit::record_user(
+                &state,
+                &user,
+                device.sender_id(),
+                "certificate.acme_issue",
+                Some(domain),
+            )
+            .await;
+            None
+        }
+        Ok(acme::Completion::Failed(message)) => Some((message, true)),
+        Err(e) => Some((e.to_string(), false)),
+    };
+    if let Some((message, failed)) = failure {
+        tracing::warn!(certificate_id = %id, error = %message, failed, "acme order not completed");
+        sqlx::query(
+            "UPDATE tls_certificates SET error = $2, \
+             status = CASE WHEN $3 THEN 'failed' ELSE status END WHERE id = $1",
+        )
+        .bind(&id)
+        .bind(&message)
+        .bind(failed)
+        .execute(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    }
+    Ok(Json(fetch_summary(&state, &user, &id).await?))
+}
+
+// ── Manage ──────────────────────────────────────────────────────────
+
+/// Re-issue a certificate the way it was first obtained. ACME renewals
+/// start a new order (which may need a fresh TXT record); uploaded
+/// certificates have to be uploaded again.
+#[utoipa::path(
+    post,
+    path = "/api/certificates/{id}/renew",
+    tag = "certificates",
+    params(("id" = String, Path, description = "Certificate ID")),
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CertificateSummary), ApiError)
+)]
+async fn renew_certificate(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<CertificateSummary>), ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let new_id = renew(&state, &user, &id).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_summary(&state, &user, &new_id).await?),
+    ))
+}
+
+/// The live (or most recent) certificate of a sender, renewed. Backs the
+/// sender page's "Renew" button.
+pub async fn renew_for_sender(
+    state: &AppState,
+    user: &AuthUser,
+    sender_id: &str,
+) -> Result<CertificateSummary, ApiError> {
+    let id = sqlx::query_scalar::<_, String>(
+        "SELECT id FROM tls_certificates WHERE sender_id = $1 AND owner_id = $2 \
+         ORDER BY (status = 'issued') DESC, created_at DESC LIMIT 1",
+    )
+    .bind(sender_id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("sender has no certificate to renew"))?;
+    let new_id = renew(state, user, &id).await?;
+    fetch_summary(state, user, &new_id).await
+}
+
+async fn renew(state: &AppState, user: &AuthUser, id: &str) -> Result<String, ApiError> {
+    let row = sqlx::query_as::<
+        _,
+        (
+            Option<String>,
+            Option<String>,
+            String,
+            String,
+            Option<String>,
+        ),
+    >(
+        "SELECT sender_id, receiver_id, domain, source, acme_order_url \
+         FROM tls_certificates WHERE id = $1 AND owner_id = $2",
+    )
+    .bind(id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("certificate not found"))?;
+    let (sender_id, receiver_id, domain, source, order_url) = row;
+    let device = Device::from_columns(sender_id, receiver_id)
+        .ok_or_else(|| ApiError::internal("certificate has no device"))?;
+
+    let new_id = match source.as_str() {
+        "self_signed" => self_signed_for(state, &user.owner_id, &device, &domain).await?,
+        "acme" => {
+            let staging = order_url.is_some_and(|u| u.contains("staging"));
+            start_order_for(
+                state,
+                &user.owner_id,
+                &device,
+                &domain,
+                acme::directory_url(staging),
+                None,
+            )
+            .await?
+        }
+        _ => {
+            return Err(ApiError::conflict(
+                "uploaded certificates are renewed by uploading a new one",
+            ));
+        }
+    };
+    super::audit::record_user(
+        state,
+        user,
+        device.sender_id(),
+        "certificate.renew",
+        Some(domain),
+    )
+    .await;
+    Ok(new_id)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/certificates/{id}/push",
+    tag = "certificates",
+    summary = "Send the certificate to its device again (admin)",
+    params(("id" = String, Path, description = "Certificate ID")),
+    security(("bearer" = [])),
+    responses((status = 202, description = "Accepted"), ApiError)
+)]
+async fn push_certificate(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let summary = fetch_summary(&state, &user, &id).await?;
+    if summary.status != "issued" {
+        return Err(ApiError::conflict(
+            "only the live certificate can be pushed",
+        ));
+    }
+    let device = Device::from_columns(summary.target.sender_id, summary.target.receiver_id)
+        .ok_or_else(|| ApiError::internal("certificate has no device"))?;
+    let online = match &device {
+        Device::Sender(id) => state.agents().contains_key(id),
+        Device::Receiver(id) => state.receivers().contains_key(id),
+    };
+    if !online {
+        return Err(ApiError::bad_request("device is not connected")
+            .with_code(strata_protocol::ErrorCode::DeviceOffline));
+    }
+    push(&state, &device).await;
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/certificates/{id}",
+    tag = "certificates",
+    summary = "Delete a certificate (admin)",
+    params(("id" = String, Path, description = "Certificate ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn delete_certificate(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageCertificates)?;
+    let deleted = sqlx::query_as::<_, (Option<String>, String)>(
+        "DELETE FROM tls_certificates WHERE id = $1 AND owner_id = $2 \
+         RETURNING sender_id, domain",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("certificate not found"))?;
+
+    // The device keeps serving what it has until something replaces it.
+    super::audit::record_user(
+        &state,
+        &user,
+        deleted.0.as_deref(),
+        "certificate.delete",
+        Some(deleted.1),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Keys at rest ────────────────────────────────────────────────────
+
+/// Where sealed keys live; binds each sealed value to the column.
+const KEY_CONTEXT: &str = "tls_certificates.key_pem";
+
+/// Seal private keys stored in plaintext before keys were sealed. Runs at
+/// startup once the data key is installed; a no-op once every row is
+/// sealed.
+pub async fn seal_stored_keys(state: &AppState) -> anyhow::Result<()> {
+    let Some(secrets) = state.secrets() else {
+        anyhow::bail!("no data key to seal certificate keys with");
+    };
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, key_pem FROM tls_certificates \
+         WHERE key_pem IS NOT NULL AND key_pem NOT LIKE 'sealed:%'",
+    )
+    .fetch_all(state.pool())
+    .await?;
+    for (id, key_pem) in &rows {
+        sqlx::query("UPDATE tls_certificates SET key_pem = $2 WHERE id = $1 AND key_pem = $3")
+            .bind(id)
+            .bind(secrets.seal(KEY_CONTEXT, key_pem))
+            .bind(key_pem)
+            .execute(state.pool())
+            .await?;
+    }
+    if !rows.is_empty() {
+        tracing::info!(count = rows.len(), "sealed stored certificate keys");
+    }
+    Ok(())
+}
+
+// ── Delivery ────────────────────────────────────────────────────────
+
+/// Send the device's live certificate, if it has one and is connected.
+async fn push(state: &AppState, device: &Device) {
+    let tx = match device {
+        Device::Sender(id) => state.agents().get(id).map(|a| a.tx.clone()),
+        Device::Receiver(id) => state.receivers().get(id).map(|r| r.tx.clone()),
+    };
+    let Some(tx) = tx else {
+        return;
+    };
+    let live = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT id, cert_pem, key_pem FROM tls_certificates \
+         WHERE status = 'issued' AND (sender_id = $1 OR receiver_id = $2) \
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(device.sender_id())
+    .bind(device.receiver_id())
+    .fetch_optional(state.pool())
+    .await;
+    let (certificate_id, cert_pem, key_pem) = match live {
+        Ok(Some(row)) => row,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(device = ?device, error = %e, "failed to load certificate");
+            return;
+        }
+    };
+    let key_pem = if SecretBox::is_sealed(&key_pem) {
+        let Some(secrets) = state.secrets() else {
+            tracing::warn!(certificate_id = %certificate_id, "SECRETS_KEY_B64 not set — cannot open stored certificate key");
+            return;
+        };
+        match secrets.open(KEY_CONTEXT, &key_pem) {
+            Ok(key_pem) => key_pem,
+            Err(e) => {
+                // Sealed under a different SECRETS_KEY_B64; only re-issuing helps.
+                tracing::warn!(certificate_id = %certificate_id, error = %e, "cannot open stored certificate key");
+                return;
+            }
+        }
+    } else {
+        key_pem
+    };
+    let payload = TlsInstallPayload {
+        certificate_id: certificate_id.clone(),
+        cert_pem,
+        key_pem,
+    };
+    let envelope = match device {
+        Device::Sender(_) => Envelope::from_message(&ControlMessage::TlsInstall(payload)),
+        Device::Receiver(_) => Envelope::from_message(&ReceiverControlMessage::TlsInstall(payload)),
+    };
+    match envelope.and_then(|e| serde_json::to_string(&e)) {
+        Ok(json) => {
+            let _ = tx.send(json).await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "failed to serialize tls.install");
+            return;
+        }
+    }
+    // Receivers don't ack commands; a send is as good as it gets.
+    if let Device::Receiver(_) = device {
+        let _ = sqlx::query("UPDATE tls_certificates SET pushed_at = now() WHERE id = $1")
+            .bind(&certificate_id)
+            .execute(state.pool())
+            .await;
+    }
+}
+
+/// Push a sender's live certificate (on connect).
+pub async fn push_to_sender(state: &AppState, sender_id: &str) {
+    push(state, &Device::Sender(sender_id.to_string())).await;
+}
+
+/// Push a receiver's live certificate (on connect).
+pub async fn push_to_receiver(state: &AppState, receiver_id: &str) {
+    push(state, &Device::Receiver(receiver_id.to_string())).await;
+}
+
+/// The agent acked `tls.install`: its live certificate is in place.
+pub async fn mark_pushed(state: &AppState, sender_id: &str) {
+    if let Err(e) = sqlx::query(
+        "UPDATE tls_certificates SET pushed_at = now() \
+         WHERE sender_id = $1 AND status = 'issued'",
+    )
+    .bind(sender_id)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(sender_id = %sender_id, error = %e, "failed to record certificate push");
+    }
+}
+
+// ── Expiry ──────────────────────────────────────────────────────────
+
+/// Open a renewal alert on every sender whose live certificate expires
+/// within [`CERT_RENEW_WITHIN_DAYS`], and resolve the alerts of senders
+/// that have since been renewed. Receiver certificates are only logged —
+/// alert events belong to senders.
+pub async fn check_expiry(state: &AppState) {
+    let now = Utc::now();
+    let live = match sqlx::query_as::<
+        _,
+        (
+            String,
+            Option<String>,
+            Option<String>,
+            String,
+            DateTime<Utc>,
+        ),
+    >(
+        "SELECT owner_id, sender_id, receiver_id, domain, not_after FROM tls_certificates \
+         WHERE status = 'issued' AND not_after IS NOT NULL",
+    )
+    .fetch_all(state.pool())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to load certificates for expiry check");
+            return;
+        }
+    };
+    let open: Vec<(String, String, String)> = match sqlx::query_as(
+        "SELECT id, owner_id, sender_id FROM alert_events \
+         WHERE rule_id = $1 AND state <> 'resolved'",
+    )
+    .bind(EXPIRY_RULE_ID)
+    .fetch_all(state.pool())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to load open certificate alerts");
+            return;
+        }
+    };
+    let alerted: HashSet<&str> = open.iter().map(|(_, _, s)| s.as_str()).collect();
+
+    let mut expiring = HashSet::new();
+    for (owner_id, sender_id, receiver_id, domain, not_after) in &live {
+        let days_left = (*not_after - now).num_days();
+        if days_left >= CERT_RENEW_WITHIN_DAYS {
+            continue;
+        }
+        let Some(sender_id) = sender_id else {
+            tracing::warn!(receiver_id = ?receiver_id, domain, days_left, "receiver certificate expiring");
+            continue;
+        };
+        expiring.insert(sender_id.as_str());
+        if alerted.contains(sender_id.as_str()) {
+            continue;
+        }
+        let severity = if days_left < CRITICAL_WITHIN_DAYS {
+            "critical"
+        } else {
+            "warning"
+        };
+        let event_id = ids::alert_event_id();
+        let inserted = sqlx::query(
+            "INSERT INTO alert_events \
+             (id, owner_id, sender_id, rule_id, rule_name, metric, condition, threshold, severity, value) \
+             VALUES ($1, $2, $3, $4, $5, 'tls_days_left', 'below', $6, $7, $8)",
+        )
+        .bind(&event_id)
+        .bind(owner_id)
+        .bind(sender_id)
+        .bind(EXPIRY_RULE_ID)
+        .bind(format!("TLS certificate for {domain} expires soon"))
+        .bind(CERT_RENEW_WITHIN_DAYS as f64)
+        .bind(severity)
+        .bind(days_left as f64)
+        .execute(state.pool())
+        .await;
+        match inserted {
+            Ok(_) => {
+                tracing::info!(sender_id = %sender_id, domain, days_left, "certificate renewal alert");
+                super::alerts::publish(state, owner_id, &event_id).await;
+            }
+            Err(e) => {
+                tracing::warn!(sender_id = %sender_id, error = %e, "failed to open certificate alert")
+            }
+        }
+    }
+
+    for (event_id, owner_id, sender_id) in &open {
+        if expiring.contains(sender_id.as_str()) {
+            continue;
+        }
+        match sqlx::query(
+            "UPDATE alert_events SET state = 'resolved', resolved_at = now() \
+             WHERE id = $1 AND state <> 'resolved'",
+        )
+        .bind(event_id)
+        .execute(state.pool())
+        .await
+        {
+            Ok(r) if r.rows_affected() > 0 => {
+                super::alerts::publish(state, owner_id, event_id).await;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(alert_id = %event_id, error = %e, "failed to resolve certificate alert")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domains_must_be_dns_names() {
+        assert_eq!(
+            validate_domain(" Cam1.Example.com. ").unwrap(),
+            "cam1.example.com"
+        );
+        assert_eq!(
+            validate_domain("*.rx.example.com").unwrap(),
+            "*.rx.example.com"
+        );
+        for bad in [
+            "localhost",
+            "10.0.0.1",
+            "-a.example.com",
+            "a..example.com",
+            "*",
+        ] {
+            assert!(validate_domain(bad).is_err(), "{bad}");
+        }
+    }
+}
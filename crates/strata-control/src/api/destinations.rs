@@ -1,19 +1,33 @@
 //! Destination management endpoints.
 //!
-//! GET    /api/destinations        — list destinations
-//! POST   /api/destinations        — add a destination
-//! PUT    /api/destinations/:id    — update a destination
-//! DELETE /api/destinations/:id    — remove a destination
+//! GET    /api/destinations                  — list destinations
+//! POST   /api/destinations                  — add a destination
+//! PUT    /api/destinations/:id              — update a destination
+//! DELETE /api/destinations/:id              — remove a destination
+//! POST   /api/destinations/:id/check        — re-run the health check
+//! GET    /api/destinations/:id/stream-key   — reveal the stream key
+//! PUT    /api/destinations/:id/stream-key   — rotate the stream key
+//! GET    /api/destinations/:id/usage        — monthly usage, last 12 months
+//!
+//! The health check parses the URL against the platform's
+//! [`DestinationPreset`], requires a stream key where the platform does,
+//! and opens a TCP connection to the ingest server (SRT, being UDP, only
+//! gets a DNS lookup). It runs in the background after every change to the
+//! URL or key; until it finishes the status reads `unknown`.
+
+use std::time::Duration;
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::routing::{get, put};
+use axum::routing::{get, post, put};
 use axum::{Json, Router};
+use chrono::{DateTime, Utc};
 
-use strata_common::ids;
+use strata_common::{ids, validation};
 use strata_protocol::api::{
-    CreateDestinationRequest, CreateDestinationResponse, DestinationSummary,
-    UpdateDestinationRequest,
+    Capability, CreateDestinationRequest, CreateDestinationResponse, DestinationHealth,
+    DestinationPreset, DestinationStatus, DestinationSummary, DestinationUsage,
+    RotateStreamKeyRequest, StreamKeyResponse, UpdateDestinationRequest,
 };
 
 use crate::api::auth::ApiError;
@@ -25,44 +39,114 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_destinations).post(create_destination))
         .route("/{id}", put(update_destination).delete(delete_destination))
+        .route("/{id}/check", post(check_destination))
+        .route(
+            "/{id}/stream-key",
+            get(reveal_stream_key).put(rotate_stream_key),
+        )
+        .route("/{id}/usage", get(destination_usage))
 }
 
+/// How long the reachability probe waits for the ingest server.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Schemes accepted for platforms without a preset.
+const GENERIC_SCHEMES: &[&str] = validation::RELAY_SCHEMES;
+
 // ── List Destinations ───────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/destinations",
+    tag = "destinations",
+    summary = "List destinations",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<DestinationSummary>), ApiError)
+)]
 async fn list_destinations(
     State(state): State<AppState>,
     user: AuthUser,
 ) -> Result<Json<Vec<DestinationSummary>>, ApiError> {
-    let rows = sqlx::query_as::<_, (String, String, String, String, chrono::DateTime<chrono::Utc>)>(
-        "SELECT id, platform, name, url, created_at FROM destinations WHERE owner_id = $1 ORDER BY created_at DESC",
+    let rows = sqlx::query_as::<_, DestinationRow>(
+        "SELECT id, platform, name, url, created_at, stream_key, \
+                health_status, health_detail, health_checked_at \
+         FROM destinations WHERE owner_id = $1 ORDER BY created_at DESC",
     )
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_all(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
-    let destinations = rows
-        .into_iter()
-        .map(|(id, platform, name, url, created_at)| DestinationSummary {
-            id,
-            platform,
-            name,
-            url,
-            created_at,
-        })
-        .collect();
+    Ok(Json(rows.into_iter().map(summary_from_row).collect()))
+}
+
+type DestinationRow = (
+    String,
+    String,
+    String,
+    String,
+    DateTime<Utc>,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<DateTime<Utc>>,
+);
 
-    Ok(Json(destinations))
+fn summary_from_row(
+    (
+        id,
+        platform,
+        name,
+        url,
+        created_at,
+        stream_key,
+        health_status,
+        health_detail,
+        health_checked_at,
+    ): DestinationRow,
+) -> DestinationSummary {
+    let stream_key = stream_key.filter(|k| !k.is_empty());
+    DestinationSummary {
+        id,
+        platform,
+        name,
+        url,
+        created_at,
+        has_stream_key: stream_key.is_some(),
+        stream_key_hint: stream_key.as_deref().and_then(key_hint),
+        health: DestinationHealth {
+            status: health_status.parse().unwrap_or_default(),
+            checked_at: health_checked_at,
+            detail: health_detail,
+        },
+    }
+}
+
+/// Last four characters of a key, or `None` for keys too short to hint
+/// at without giving most of them away.
+fn key_hint(key: &str) -> Option<String> {
+    let chars: Vec<char> = key.chars().collect();
+    (chars.len() >= 12).then(|| chars[chars.len() - 4..].iter().collect())
 }
 
 // ── Create Destination ──────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/destinations",
+    tag = "destinations",
+    summary = "Add a destination",
+    request_body = CreateDestinationRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateDestinationResponse), ApiError)
+)]
 async fn create_destination(
     State(state): State<AppState>,
     user: AuthUser,
     Json(body): Json<CreateDestinationRequest>,
 ) -> Result<(StatusCode, Json<CreateDestinationResponse>), ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageDestinations)?;
+    parse_ingest_url(&body.platform, &body.url).map_err(ApiError::bad_request)?;
 
     let id = ids::destination_id();
 
@@ -71,7 +155,7 @@ async fn create_destination(
          VALUES ($1, $2, $3, $4, $5, $6)",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .bind(&body.platform)
     .bind(&body.name)
     .bind(&body.url)
@@ -81,19 +165,38 @@ async fn create_destination(
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
     tracing::info!(destination_id = %id, platform = %body.platform, "destination created");
+    spawn_check(&state, &id);
+    super::audit::record_user(
+        &state,
+        &user,
+        None,
+        "destination.create",
+        Some(body.name.clone()),
+    )
+    .await;
 
     Ok((StatusCode::CREATED, Json(CreateDestinationResponse { id })))
 }
 
 // ── Update Destination ──────────────────────────────────────────────
 
+#[utoipa::path(
+    put,
+    path = "/api/destinations/{id}",
+    tag = "destinations",
+    summary = "Update a destination",
+    params(("id" = String, Path, description = "Destination ID")),
+    request_body = UpdateDestinationRequest,
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn update_destination(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<UpdateDestinationRequest>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageDestinations)?;
 
     // Build dynamic UPDATE (only set provided fields)
     let mut sets = Vec::new();
@@ -106,6 +209,15 @@ async fn update_destination(
         params.push(name.clone());
     }
     if let Some(ref url) = body.url {
+        let platform: Option<String> =
+            sqlx::query_scalar("SELECT platform FROM destinations WHERE id = $1 AND owner_id = $2")
+                .bind(&id)
+                .bind(&user.owner_id)
+                .fetch_optional(state.pool())
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?;
+        let platform = platform.ok_or_else(|| ApiError::not_found("destination not found"))?;
+        parse_ingest_url(&platform, url).map_err(ApiError::bad_request)?;
         idx += 1;
         sets.push(format!("url = ${idx}"));
         params.push(url.clone());
@@ -119,13 +231,17 @@ async fn update_destination(
     if sets.is_empty() {
         return Err(ApiError::bad_request("no fields to update"));
     }
+    let recheck = body.url.is_some() || body.stream_key.is_some();
+    if recheck {
+        sets.push("health_status = 'unknown', health_detail = NULL".into());
+    }
 
     let sql = format!(
         "UPDATE destinations SET {} WHERE id = $1 AND owner_id = $2",
         sets.join(", ")
     );
 
-    let mut query = sqlx::query(&sql).bind(&id).bind(&user.user_id);
+    let mut query = sqlx::query(&sql).bind(&id).bind(&user.owner_id);
     for param in &params {
         query = query.bind(param);
     }
@@ -139,21 +255,35 @@ async fn update_destination(
         return Err(ApiError::not_found("destination not found"));
     }
 
+    if recheck {
+        spawn_check(&state, &id);
+    }
+    super::audit::record_user(&state, &user, None, "destination.update", Some(id)).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
 // ── Delete Destination ──────────────────────────────────────────────
 
+#[utoipa::path(
+    delete,
+    path = "/api/destinations/{id}",
+    tag = "destinations",
+    summary = "Remove a destination",
+    params(("id" = String, Path, description = "Destination ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn delete_destination(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageDestinations)?;
 
     let result = sqlx::query("DELETE FROM destinations WHERE id = $1 AND owner_id = $2")
         .bind(&id)
-        .bind(&user.user_id)
+        .bind(&user.owner_id)
         .execute(state.pool())
         .await
         .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -163,6 +293,314 @@ async fn delete_destination(
     }
 
     tracing::info!(destination_id = %id, "destination deleted");
+    super::audit::record_user(&state, &user, None, "destination.delete", Some(id)).await;
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+// ── Stream Key ──────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/destinations/{id}/stream-key",
+    tag = "destinations",
+    summary = "Reveal the stream key",
+    params(("id" = String, Path, description = "Destination ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = StreamKeyResponse), ApiError)
+)]
+async fn reveal_stream_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<StreamKeyResponse>, ApiError> {
+    user.require(Capability::ManageDestinations)?;
+
+    let (name, stream_key): (String, Option<String>) =
+        sqlx::query_as("SELECT name, stream_key FROM destinations WHERE id = $1 AND owner_id = $2")
+            .bind(&id)
+            .bind(&user.owner_id)
+            .fetch_optional(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found("destination not found"))?;
+
+    super::audit::record_user(&state, &user, None, "destination.key_reveal", Some(name)).await;
+
+    Ok(Json(StreamKeyResponse { stream_key }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/destinations/{id}/stream-key",
+    tag = "destinations",
+    summary = "Rotate the stream key",
+    params(("id" = String, Path, description = "Destination ID")),
+    request_body = RotateStreamKeyRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = DestinationSummary), ApiError)
+)]
+async fn rotate_stream_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<RotateStreamKeyRequest>,
+) -> Result<Json<DestinationSummary>, ApiError> {
+    user.require(Capability::ManageDestinations)?;
+
+    let stream_key = body.stream_key.trim();
+    if stream_key.is_empty() {
+        return Err(ApiError::bad_request("stream key must not be empty"));
+    }
+
+    let row = sqlx::query_as::<_, DestinationRow>(
+        "UPDATE destinations SET stream_key = $3, health_status = 'unknown', health_detail = NULL \
+         WHERE id = $1 AND owner_id = $2 \
+         RETURNING id, platform, name, url, created_at, stream_key, \
+                   health_status, health_detail, health_checked_at",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(stream_key)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("destination not found"))?;
+    let summary = summary_from_row(row);
+
+    tracing::info!(destination_id = %id, "destination stream key rotated");
+    spawn_check(&state, &id);
+    super::audit::record_user(
+        &state,
+        &user,
+        None,
+        "destination.key_rotate",
+        Some(summary.name.clone()),
+    )
+    .await;
+
+    Ok(Json(summary))
+}
+
+// ── Usage ───────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/destinations/{id}/usage",
+    tag = "destinations",
+    summary = "Monthly usage, last 12 months",
+    params(("id" = String, Path, description = "Destination ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<DestinationUsage>), ApiError)
+)]
+async fn destination_usage(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<DestinationUsage>>, ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM destinations WHERE id = $1 AND owner_id = $2)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if !exists {
+        return Err(ApiError::not_found("destination not found"));
+    }
+
+    let rows = sqlx::query_as::<_, (String, i64, f64, i64)>(
+        "SELECT to_char(date_trunc('month', started_at AT TIME ZONE 'UTC'), 'YYYY-MM'), \
+                COUNT(*)::BIGINT, \
+                COALESCE(SUM(EXTRACT(EPOCH FROM (COALESCE(ended_at, now()) - started_at))), 0)::FLOAT8 / 60.0, \
+                COALESCE(SUM(total_bytes), 0)::BIGINT \
+         FROM streams \
+         WHERE destination_id = $1 AND started_at >= date_trunc('month', now()) - INTERVAL '11 months' \
+         GROUP BY 1 ORDER BY 1",
+    )
+    .bind(&id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|(month, streams, minutes, bytes)| DestinationUsage {
+                month,
+                stream_count: streams.max(0) as u64,
+                streamed_minutes: minutes.max(0.0),
+                total_bytes: bytes.max(0) as u64,
+            })
+            .collect(),
+    ))
+}
+
+// ── Health Check ────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/destinations/{id}/check",
+    tag = "destinations",
+    summary = "Re-run the health check",
+    params(("id" = String, Path, description = "Destination ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = DestinationHealth), ApiError)
+)]
+async fn check_destination(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<DestinationHealth>, ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM destinations WHERE id = $1 AND owner_id = $2)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if !exists {
+        return Err(ApiError::not_found("destination not found"));
+    }
+
+    run_check(&state, &id)
+        .await
+        .map(Json)
+        .map_err(|e| ApiError::internal(e.to_string()))
+}
+
+/// Split an ingest URL into `(scheme, host, port)`, checking the scheme
+/// against the platform's preset.
+pub(crate) fn parse_ingest_url(platform: &str, url: &str) -> Result<(String, String, u16), String> {
+    let schemes = DestinationPreset::lookup(platform).map_or(GENERIC_SCHEMES, |p| p.schemes);
+    let parsed = validation::parse_url(url, schemes).map_err(|e| e.message)?;
+    Ok((parsed.scheme, parsed.host, parsed.port))
+}
+
+/// Check a destination and record the result.
+pub async fn run_check(state: &AppState, id: &str) -> Result<DestinationHealth, sqlx::Error> {
+    let (platform, url, stream_key): (String, String, Option<String>) =
+        sqlx::query_as("SELECT platform, url, stream_key FROM destinations WHERE id = $1")
+            .bind(id)
+            .fetch_one(state.pool())
+            .await?;
+
+    let result = probe(&platform, &url, stream_key.is_some_and(|k| !k.is_empty())).await;
+    let health = DestinationHealth {
+        status: if result.is_ok() {
+            DestinationStatus::Ok
+        } else {
+            DestinationStatus::Error
+        },
+        checked_at: Some(Utc::now()),
+        detail: result.err(),
+    };
+
+    sqlx::query(
+        "UPDATE destinations SET health_status = $2, health_detail = $3, health_checked_at = $4 \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(health.status.as_str())
+    .bind(&health.detail)
+    .bind(health.checked_at)
+    .execute(state.pool())
+    .await?;
+
+    Ok(health)
+}
+
+async fn probe(platform: &str, url: &str, has_key: bool) -> Result<(), String> {
+    let (scheme, host, port) = parse_ingest_url(platform, url)?;
+    if let Some(preset) = DestinationPreset::lookup(platform)
+        && preset.needs_key
+        && !has_key
+    {
+        return Err(format!("{} needs a stream key", preset.label));
+    }
+
+    if scheme == "srt" {
+        return match tokio::time::timeout(
+            CHECK_TIMEOUT,
+            tokio::net::lookup_host((host.as_str(), port)),
+        )
+        .await
+        {
+            Ok(Ok(mut addrs)) => {
+                if addrs.next().is_some() {
+                    Ok(())
+                } else {
+                    Err(format!("{host} did not resolve"))
+                }
+            }
+            Ok(Err(e)) => Err(format!("{host}: {e}")),
+            Err(_) => Err(format!("{host}: DNS lookup timed out")),
+        };
+    }
+    match tokio::time::timeout(
+        CHECK_TIMEOUT,
+        tokio::net::TcpStream::connect((host.as_str(), port)),
+    )
+    .await
+    {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(format!("{host}:{port}: {e}")),
+        Err(_) => Err(format!("{host}:{port}: connection timed out")),
+    }
+}
+
+pub(crate) fn spawn_check(state: &AppState, id: &str) {
+    let state = state.clone();
+    let id = id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = run_check(&state, &id).await {
+            tracing::warn!(destination_id = %id, error = %e, "destination check failed");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ingest_urls_parse_with_scheme_default_ports() {
+        assert_eq!(
+            parse_ingest_url("youtube", "rtmp://a.rtmp.youtube.com/live2"),
+            Ok(("rtmp".into(), "a.rtmp.youtube.com".into(), 1935))
+        );
+        assert_eq!(
+            parse_ingest_url("facebook", "rtmps://live-api-s.facebook.com:443/rtmp"),
+            Ok(("rtmps".into(), "live-api-s.facebook.com".into(), 443))
+        );
+        assert_eq!(
+            parse_ingest_url(
+                "youtube_hls",
+                "https://a.upload.youtube.com/http_upload_hls?cid=x&copy=0&file="
+            ),
+            Ok(("https".into(), "a.upload.youtube.com".into(), 443))
+        );
+        assert_eq!(
+            parse_ingest_url("srt", "srt://[::1]:9000?streamid=x"),
+            Ok(("srt".into(), "::1".into(), 9000))
+        );
+        // Unknown platforms take any supported scheme.
+        assert!(parse_ingest_url("other", "rtmp://host/app").is_ok());
+    }
+
+    #[test]
+    fn ingest_urls_reject_wrong_scheme_and_missing_parts() {
+        assert!(parse_ingest_url("facebook", "rtmp://live-api-s.facebook.com/rtmp").is_err());
+        assert!(parse_ingest_url("twitch", "live.twitch.tv/app").is_err());
+        assert!(parse_ingest_url("srt", "srt://host").is_err());
+        assert!(parse_ingest_url("custom_rtmp", "rtmp:///app").is_err());
+        assert!(parse_ingest_url("custom_rtmp", "rtmp://host:99999/app").is_err());
+    }
+
+    #[test]
+    fn key_hint_only_for_long_keys() {
+        assert_eq!(key_hint("abcd-efgh-ijkl-mnop"), Some("mnop".into()));
+        assert_eq!(key_hint("short"), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! Historical sender telemetry.
+//!
+//! GET /api/senders/:id/metrics?range=1h|24h|7d
+//! GET /api/senders/:id/metrics?from=<rfc3339>&to=<rfc3339>
+//!
+//! `stream.stats` arrives at ~1 Hz; [`record_sample`] keeps one row per
+//! sender every [`SAMPLE_INTERVAL`]; the retention janitor
+//! (`retention.rs`) drops rows past the telemetry retention. Queries
+//! average samples into buckets sized so any window comes back as a few
+//! hundred points.
+
+use std::time::{Duration, Instant};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_protocol::StreamStatsPayload;
+use strata_protocol::api::MetricsRangeResponse;
+use strata_protocol::telemetry::{Bps, Millis, TelemetrySample};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// Minimum spacing between stored samples for one sender.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Points a query aims to return, whatever the window.
+const TARGET_POINTS: i64 = 360;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct RangeQuery {
+    range: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+/// Resolve the query into a window: explicit `from`/`to` (a zoomed view)
+/// win over a named `range`, which defaults to the last hour.
+fn resolve_window(
+    q: &RangeQuery,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), ApiError> {
+    let to = q.to.unwrap_or(now);
+    let from = match (q.from, q.range.as_deref()) {
+        (Some(from), _) => from,
+        (None, None | Some("1h")) => to - chrono::Duration::hours(1),
+        (None, Some("24h")) => to - chrono::Duration::hours(24),
+        (None, Some("7d")) => to - chrono::Duration::days(7),
+        (None, Some(_)) => return Err(ApiError::bad_request("range must be 1h, 24h or 7d")),
+    };
+    if from >= to {
+        return Err(ApiError::bad_request("from must be before to"));
+    }
+    Ok((from, to))
+}
+
+/// Bucket width for a window: a multiple of the sample interval, so no
+/// bucket is systematically empty.
+fn bucket_secs(from: DateTime<Utc>, to: DateTime<Utc>) -> u32 {
+    let step = SAMPLE_INTERVAL.as_secs() as i64;
+    let raw = ((to - from).num_seconds() / TARGET_POINTS).max(step);
+    ((raw + step - 1) / step * step).min(u32::MAX as i64) as u32
+}
+
+type PointRow = (
+    DateTime<Utc>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+);
+
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/metrics",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), RangeQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = MetricsRangeResponse), ApiError)
+)]
+pub(crate) async fn get_metrics(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Query(q): Query<RangeQuery>,
+) -> Result<Json<MetricsRangeResponse>, ApiError> {
+    super::senders::verify_ownership(&state, &user, &id).await?;
+
+    let (from, to) = resolve_window(&q, Utc::now())?;
+    let bucket_s = bucket_secs(from, to);
+
+    let rows = sqlx::query_as::<_, PointRow>(
+        "SELECT to_timestamp(floor(extract(epoch FROM ts) / $4) * $4), \
+                AVG(bitrate_kbps)::FLOAT8, AVG(throughput_bps)::FLOAT8, \
+                AVG(rtt_ms), AVG(loss_pct), AVG(link_count)::FLOAT8 \
+         FROM sender_metrics \
+         WHERE sender_id = $1 AND ts >= $2 AND ts < $3 \
+         GROUP BY 1 ORDER BY 1",
+    )
+    .bind(&id)
+    .bind(from)
+    .bind(to)
+    .bind(bucket_s as f64)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let points = rows
+        .into_iter()
+        .map(
+            |(ts, bitrate_kbps, throughput_bps, rtt_ms, loss_pct, link_count)| TelemetrySample {
+                ts,
+                bitrate_kbps: bitrate_kbps.unwrap_or(0.0),
+                throughput_bps: Bps(throughput_bps.unwrap_or(0.0).round() as u64),
+                rtt_ms: rtt_ms.map(Millis),
+                loss_pct,
+                link_count: link_count.unwrap_or(0.0),
+            },
+        )
+        .collect();
+
+    Ok(Json(MetricsRangeResponse {
+        from,
+        to,
+        bucket_s,
+        points,
+    }))
+}
+
+// ── Recording ───────────────────────────────────────────────────────
+
+/// Store a downsampled row for one `stream.stats` sample.
+pub async fn record_sample(state: &AppState, stats: &StreamStatsPayload) {
+    let now = Instant::now();
+    let due = state
+        .metrics_sampled()
+        .get(&stats.sender_id)
+        .is_none_or(|last| now.duration_since(*last) >= SAMPLE_INTERVAL);
+    if !due {
+        return;
+    }
+    state.metrics_sampled().insert(stats.sender_id.clone(), now);
+
+    let sample = TelemetrySample::from_links(Utc::now(), stats.encoder_bitrate_kbps, &stats.links);
+
+    if let Err(e) = sqlx::query(
+        "INSERT INTO sender_metrics \
+         (sender_id, stream_id, ts, bitrate_kbps, throughput_bps, rtt_ms, loss_pct, link_count) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(&stats.sender_id)
+    .bind(&stats.stream_id)
+    .bind(sample.ts)
+    .bind(stats.encoder_bitrate_kbps.min(i32::MAX as u32) as i32)
+    .bind(sample.throughput_bps.0.min(i64::MAX as u64) as i64)
+    .bind(sample.rtt_ms.map(|ms| ms.0))
+    .bind(sample.loss_pct)
+    .bind((sample.link_count as i64).min(i16::MAX as i64) as i16)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(sender_id = %stats.sender_id, error = %e, "failed to record metrics sample");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(range: Option<&str>) -> RangeQuery {
+        RangeQuery {
+            range: range.map(String::from),
+            from: None,
+            to: None,
+        }
+    }
+
+    #[test]
+    fn named_ranges_resolve_to_windows() {
+        let now = Utc::now();
+        let (from, to) = resolve_window(&query(None), now).unwrap();
+        assert_eq!(to, now);
+        assert_eq!(to - from, chrono::Duration::hours(1));
+        let (from, _) = resolve_window(&query(Some("7d")), now).unwrap();
+        assert_eq!(now - from, chrono::Duration::days(7));
+        assert!(resolve_window(&query(Some("1y")), now).is_err());
+    }
+
+    #[test]
+    fn explicit_window_wins_and_must_be_ordered() {
+        let now = Utc::now();
+        let q = RangeQuery {
+            range: Some("7d".into()),
+            from: Some(now - chrono::Duration::minutes(5)),
+            to: Some(now),
+        };
+        let (from, _) = resolve_window(&q, now).unwrap();
+        assert_eq!(now - from, chrono::Duration::minutes(5));
+
+        let inverted = RangeQuery {
+            range: None,
+            from: Some(now),
+            to: Some(now - chrono::Duration::minutes(5)),
+        };
+        assert!(resolve_window(&inverted, now).is_err());
+    }
+
+    #[test]
+    fn buckets_are_sample_aligned_and_bounded() {
+        let now = Utc::now();
+        // Short windows never go below the sample interval.
+        assert_eq!(bucket_secs(now - chrono::Duration::minutes(5), now), 10);
+        assert_eq!(bucket_secs(now - chrono::Duration::hours(1), now), 10);
+        // 24 h / 360 = 240 s; 7 d / 360 = 1680 s.
+        assert_eq!(bucket_secs(now - chrono::Duration::hours(24), now), 240);
+        assert_eq!(bucket_secs(now - chrono::Duration::days(7), now), 1680);
+        // Always a multiple of the sample interval.
+        assert_eq!(
+            bucket_secs(now - chrono::Duration::seconds(3601 * 2), now) % 10,
+            0
+        );
+    }
+}
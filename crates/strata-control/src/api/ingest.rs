@@ -0,0 +1,234 @@
+//! Ingest gateway — senders without the Strata agent.
+//!
+//! POST /api/senders/ingest — register a plain SRT/RTMP contributor
+//!
+//! An ingest sender (a phone running Larix, OBS) never enrolls. Starting a
+//! stream on it goes through the usual `POST /api/streams/start/:sender_id`:
+//! a receiver that takes its protocol in opens an endpoint keyed to the
+//! stream, and the response carries the URL to push to. From there the
+//! stream is relayed, previewed and listed like a bonded one. It goes live
+//! on the receiver's first stats for it, and — with no agent heartbeat to
+//! reconcile against — ends through the receiver's (see `stream_state`).
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::Utc;
+
+use strata_common::error::ErrorCode;
+use strata_common::ids;
+use strata_protocol::api::{Capability, CreateIngestSenderRequest, CreateIngestSenderResponse};
+use strata_protocol::models::{IngestProtocol, StreamState};
+use strata_protocol::{DashboardEvent, ReceiverStreamStartPayload};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+use super::streams::Launched;
+
+#[utoipa::path(
+    post,
+    path = "/api/senders/ingest",
+    tag = "senders",
+    summary = "Register a plain SRT/RTMP contributor",
+    request_body = CreateIngestSenderRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateIngestSenderResponse), ApiError)
+)]
+pub(crate) async fn create_ingest_sender(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<CreateIngestSenderRequest>,
+) -> Result<(StatusCode, Json<CreateIngestSenderResponse>), ApiError> {
+    user.require(Capability::ManageSenders)?;
+
+    let sender_id = ids::sender_id();
+    sqlx::query(
+        "INSERT INTO senders (id, owner_id, name, ingest_protocol) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(&sender_id)
+    .bind(&user.owner_id)
+    .bind(&body.name)
+    .bind(body.protocol.as_str())
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(
+        sender_id = %sender_id,
+        owner = %user.owner_id,
+        protocol = body.protocol.as_str(),
+        "ingest sender created"
+    );
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(sender_id.as_str()),
+        "sender.create",
+        body.name.clone(),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateIngestSenderResponse {
+            sender_id,
+            protocol: body.protocol,
+        }),
+    ))
+}
+
+/// Protocols `owner_id` can take in right now: those of its connected
+/// receivers. An ingest sender counts as online while its protocol is
+/// among them — the gateway is what there is to reach.
+pub(crate) async fn ready_protocols(state: &AppState, owner_id: &str) -> Vec<IngestProtocol> {
+    let rows: Vec<(String, Vec<String>)> = sqlx::query_as(
+        "SELECT id, ingest_protocols FROM receivers WHERE owner_id = $1 AND online = TRUE",
+    )
+    .bind(owner_id)
+    .fetch_all(state.pool())
+    .await
+    .unwrap_or_default();
+    let mut protocols = Vec::new();
+    for (receiver_id, names) in rows {
+        if !state.receivers().contains_key(&receiver_id) {
+            continue;
+        }
+        for protocol in names.iter().filter_map(|n| n.parse().ok()) {
+            if !protocols.contains(&protocol) {
+                protocols.push(protocol);
+            }
+        }
+    }
+    protocols
+}
+
+/// [`streams::launch`](super::streams::launch) for an ingest sender, past
+/// the ownership, concurrency and destination checks.
+pub(crate) async fn launch(
+    state: &AppState,
+    owner_id: &str,
+    sender_id: &str,
+    protocol: IngestProtocol,
+    relay_url: String,
+    destination_id: Option<&str>,
+) -> Result<Launched, ApiError> {
+    let (receiver_id, _bind_host, preview_base_url) =
+        super::streams::pick_or_provision_receiver(state, owner_id, Some(protocol))
+            .await
+            .ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "no online receiver takes {} ingest",
+                    protocol.as_str()
+                ))
+                .with_code(ErrorCode::DeviceOffline)
+            })?;
+
+    let stream_id = ids::stream_id();
+    let preview_key =
+        (preview_base_url.is_some() && relay_url.starts_with("https://")).then(ids::preview_key);
+    let ack = super::streams::request_receiver_start(
+        state,
+        &receiver_id,
+        ReceiverStreamStartPayload {
+            request_id: String::new(),
+            stream_id: stream_id.to_string(),
+            link_count: 0,
+            relay_url: (!relay_url.is_empty()).then(|| relay_url.clone()),
+            bonding_config: serde_json::Value::Null,
+            ingest_key: Some(ids::ingest_key()),
+            preview_key: preview_key.clone(),
+            ingest: Some(protocol),
+        },
+    )
+    .await?;
+    let ingest_url = ack
+        .ingest_url
+        .ok_or_else(|| ApiError::internal("receiver returned no ingest URL"))?;
+
+    let config_json = serde_json::json!({
+        "ingest": protocol.as_str(),
+        "relay_url": relay_url,
+    })
+    .to_string();
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, destination_id, receiver_id, state, started_at, config_json, preview_key) \
+         VALUES ($1, $2, $3, $4, 'starting', $5, $6, $7)",
+    )
+    .bind(&stream_id)
+    .bind(sender_id)
+    .bind(destination_id)
+    .bind(&receiver_id)
+    .bind(Utc::now())
+    .bind(&config_json)
+    .bind(&preview_key)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    state.broadcast_dashboard(
+        owner_id,
+        DashboardEvent::StreamStateChanged {
+            stream_id: stream_id.to_string(),
+            sender_id: sender_id.to_string(),
+            state: StreamState::Starting,
+            error: None,
+            reason: None,
+        },
+    );
+
+    tracing::info!(
+        stream_id = %stream_id,
+        sender_id,
+        receiver_id = %receiver_id,
+        protocol = protocol.as_str(),
+        "ingest stream waiting for contribution"
+    );
+    Ok(Launched {
+        stream_id,
+        ingest_url: Some(ingest_url),
+    })
+}
+
+/// Receiver stats for a stream not yet live: if it is an ingest stream,
+/// the contribution has arrived — move it to live. Bonded streams go live
+/// on the agent's stats instead (see `ws_agent`).
+pub(crate) async fn observe_stats(state: &AppState, owner_id: &str, stream_id: &str) {
+    if state.live_streams().contains(stream_id) {
+        return;
+    }
+    let sender_id: Option<String> = sqlx::query_scalar(
+        "SELECT s.sender_id FROM streams s JOIN senders snd ON snd.id = s.sender_id \
+         WHERE s.id = $1 AND snd.ingest_protocol IS NOT NULL",
+    )
+    .bind(stream_id)
+    .fetch_optional(state.pool())
+    .await
+    .ok()
+    .flatten();
+    let Some(sender_id) = sender_id else {
+        return;
+    };
+
+    let moved = crate::stream_state::transition(
+        state.pool(),
+        stream_id,
+        StreamState::Live,
+        crate::stream_state::EndAttribution::none(),
+    )
+    .await;
+    state.live_streams().insert(stream_id.to_string());
+    if moved.unwrap_or(false) {
+        state.broadcast_dashboard(
+            owner_id,
+            DashboardEvent::StreamStateChanged {
+                stream_id: stream_id.to_string(),
+                sender_id,
+                state: StreamState::Live,
+                error: None,
+                reason: None,
+            },
+        );
+    }
+}
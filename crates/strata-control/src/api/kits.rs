@@ -0,0 +1,659 @@
+//! Kits: a sender and the serial-numbered accessories packed with it.
+//!
+//! GET    /api/kits                                — list kits
+//! POST   /api/kits                                — create a kit (operator)
+//! GET    /api/kits/:id                            — kit, contents and history
+//! PUT    /api/kits/:id                            — rename / re-assign sender (operator)
+//! DELETE /api/kits/:id                            — delete a kit (operator)
+//! POST   /api/kits/:id/accessories                — add an accessory (operator)
+//! DELETE /api/kits/:id/accessories/:accessory_id  — remove it (operator)
+//! POST   /api/kits/:id/check-out                  — hand the kit out (operator)
+//! POST   /api/kits/:id/check-in                   — take it back (operator)
+//!
+//! A kit is out while it has a check-out row without `checked_in_at`; the
+//! database allows at most one such row per kit.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+
+use strata_common::ids;
+use strata_protocol::api::{
+    AddKitAccessoryRequest, Capability, CheckOutKitRequest, KIT_ACCESSORY_KINDS, KitAccessory,
+    KitCheckout, KitDetail, KitRequest, KitSummary,
+};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+const MAX_NAME_LEN: usize = 80;
+const MAX_SERIAL_LEN: usize = 64;
+const MAX_NOTE_LEN: usize = 500;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_kits).post(create_kit))
+        .route("/{id}", get(get_kit).put(update_kit).delete(delete_kit))
+        .route("/{id}/accessories", post(add_accessory))
+        .route("/{id}/accessories/{accessory_id}", delete(remove_accessory))
+        .route("/{id}/check-out", post(check_out))
+        .route("/{id}/check-in", post(check_in))
+}
+
+type KitRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    i64,
+    DateTime<Utc>,
+);
+
+const KIT_QUERY: &str = "SELECT k.id, k.name, k.sender_id, s.name, k.notes, \
+     (SELECT COUNT(*) FROM kit_accessories a WHERE a.kit_id = k.id), k.created_at \
+     FROM kits k LEFT JOIN senders s ON s.id = k.sender_id";
+
+fn summary_from_row(row: KitRow, checked_out: Option<KitCheckout>) -> KitSummary {
+    let (id, name, sender_id, sender_name, notes, accessory_count, created_at) = row;
+    KitSummary {
+        id,
+        name,
+        sender_id,
+        sender_name,
+        notes,
+        accessory_count: accessory_count as u32,
+        checked_out,
+        created_at,
+    }
+}
+
+type CheckoutRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    DateTime<Utc>,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    Option<String>,
+);
+
+const CHECKOUT_QUERY: &str = "SELECT c.kit_id, c.id, c.holder, c.note, c.due_back_at, \
+     c.checked_out_at, uo.email, c.checked_in_at, ui.email \
+     FROM kit_checkouts c JOIN kits k ON k.id = c.kit_id \
+     LEFT JOIN users uo ON uo.id = c.checked_out_by \
+     LEFT JOIN users ui ON ui.id = c.checked_in_by";
+
+/// `(kit_id, checkout)`.
+fn checkout_from_row(row: CheckoutRow) -> (String, KitCheckout) {
+    let (
+        kit_id,
+        id,
+        holder,
+        note,
+        due_back_at,
+        checked_out_at,
+        checked_out_by,
+        checked_in_at,
+        checked_in_by,
+    ) = row;
+    (
+        kit_id,
+        KitCheckout {
+            id,
+            holder,
+            note,
+            due_back_at,
+            checked_out_at,
+            checked_out_by,
+            checked_in_at,
+            checked_in_by,
+        },
+    )
+}
+
+/// Trim `value` and enforce a length limit; empty becomes `None`.
+fn clean(value: Option<String>, field: &str, max: usize) -> Result<Option<String>, ApiError> {
+    let value = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    if value.as_ref().is_some_and(|v| v.chars().count() > max) {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be at most {max} characters"
+        )));
+    }
+    Ok(value)
+}
+
+fn required(value: String, field: &str, max: usize) -> Result<String, ApiError> {
+    clean(Some(value), field, max)?
+        .ok_or_else(|| ApiError::bad_request(format!("{field} is required")))
+}
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    let msg = e.to_string();
+    msg.contains("duplicate key") || msg.contains("unique constraint")
+}
+
+/// The kit's sender, if the kit belongs to the caller's account.
+async fn verify_kit(
+    state: &AppState,
+    user: &AuthUser,
+    kit_id: &str,
+) -> Result<Option<String>, ApiError> {
+    sqlx::query_scalar::<_, Option<String>>(
+        "SELECT sender_id FROM kits WHERE id = $1 AND owner_id = $2",
+    )
+    .bind(kit_id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("kit not found"))
+}
+
+async fn fetch_summary(
+    state: &AppState,
+    user: &AuthUser,
+    kit_id: &str,
+) -> Result<KitSummary, ApiError> {
+    let row =
+        sqlx::query_as::<_, KitRow>(&format!("{KIT_QUERY} WHERE k.id = $1 AND k.owner_id = $2"))
+            .bind(kit_id)
+            .bind(&user.owner_id)
+            .fetch_optional(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found("kit not found"))?;
+
+    let open = sqlx::query_as::<_, CheckoutRow>(&format!(
+        "{CHECKOUT_QUERY} WHERE c.kit_id = $1 AND c.checked_in_at IS NULL"
+    ))
+    .bind(kit_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .map(|r| checkout_from_row(r).1);
+
+    Ok(summary_from_row(row, open))
+}
+
+async fn fetch_checkout(state: &AppState, checkout_id: &str) -> Result<KitCheckout, ApiError> {
+    sqlx::query_as::<_, CheckoutRow>(&format!("{CHECKOUT_QUERY} WHERE c.id = $1"))
+        .bind(checkout_id)
+        .fetch_one(state.pool())
+        .await
+        .map(|r| checkout_from_row(r).1)
+        .map_err(|e| ApiError::internal(e.to_string()))
+}
+
+// ── Kits ────────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/kits",
+    tag = "kits",
+    summary = "List kits",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<KitSummary>), ApiError)
+)]
+async fn list_kits(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<Vec<KitSummary>>, ApiError> {
+    let rows = sqlx::query_as::<_, KitRow>(&format!(
+        "{KIT_QUERY} WHERE k.owner_id = $1 ORDER BY k.name, k.id"
+    ))
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let mut open: HashMap<String, KitCheckout> = sqlx::query_as::<_, CheckoutRow>(&format!(
+        "{CHECKOUT_QUERY} WHERE k.owner_id = $1 AND c.checked_in_at IS NULL"
+    ))
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .into_iter()
+    .map(checkout_from_row)
+    .collect();
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| {
+                let checked_out = open.remove(&row.0);
+                summary_from_row(row, checked_out)
+            })
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/kits/{id}",
+    tag = "kits",
+    summary = "Kit, contents and history",
+    params(("id" = String, Path, description = "Kit ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = KitDetail), ApiError)
+)]
+async fn get_kit(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<KitDetail>, ApiError> {
+    let kit = fetch_summary(&state, &user, &id).await?;
+
+    let accessories = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+        "SELECT id, kind, serial, label FROM kit_accessories \
+         WHERE kit_id = $1 ORDER BY kind, serial",
+    )
+    .bind(&id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .into_iter()
+    .map(|(id, kind, serial, label)| KitAccessory {
+        id,
+        kind,
+        serial,
+        label,
+    })
+    .collect();
+
+    let history = sqlx::query_as::<_, CheckoutRow>(&format!(
+        "{CHECKOUT_QUERY} WHERE c.kit_id = $1 ORDER BY c.checked_out_at DESC"
+    ))
+    .bind(&id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .into_iter()
+    .map(|r| checkout_from_row(r).1)
+    .collect();
+
+    Ok(Json(KitDetail {
+        kit,
+        accessories,
+        history,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/kits",
+    tag = "kits",
+    summary = "Create a kit (operator)",
+    request_body = KitRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = KitSummary), ApiError)
+)]
+async fn create_kit(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<KitRequest>,
+) -> Result<(StatusCode, Json<KitSummary>), ApiError> {
+    user.require(Capability::ManageKits)?;
+
+    let name = required(body.name, "name", MAX_NAME_LEN)?;
+    let notes = clean(body.notes, "notes", MAX_NOTE_LEN)?;
+    if let Some(ref sender_id) = body.sender_id {
+        super::senders::verify_ownership(&state, &user, sender_id).await?;
+    }
+
+    let id = ids::kit_id();
+    sqlx::query(
+        "INSERT INTO kits (id, owner_id, name, sender_id, notes) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&name)
+    .bind(&body.sender_id)
+    .bind(&notes)
+    .execute(state.pool())
+    .await
+    .map_err(|e| {
+        if is_unique_violation(&e) {
+            ApiError::conflict("sender is already in another kit")
+        } else {
+            ApiError::internal(e.to_string())
+        }
+    })?;
+
+    tracing::info!(kit_id = %id, sender_id = ?body.sender_id, "kit created");
+    super::audit::record_user(
+        &state,
+        &user,
+        body.sender_id.as_deref(),
+        "kit.create",
+        Some(name),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_summary(&state, &user, &id).await?),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/kits/{id}",
+    tag = "kits",
+    summary = "Rename / re-assign sender (operator)",
+    params(("id" = String, Path, description = "Kit ID")),
+    request_body = KitRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = KitSummary), ApiError)
+)]
+async fn update_kit(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<KitRequest>,
+) -> Result<Json<KitSummary>, ApiError> {
+    user.require(Capability::ManageKits)?;
+    verify_kit(&state, &user, &id).await?;
+
+    let name = required(body.name, "name", MAX_NAME_LEN)?;
+    let notes = clean(body.notes, "notes", MAX_NOTE_LEN)?;
+    if let Some(ref sender_id) = body.sender_id {
+        super::senders::verify_ownership(&state, &user, sender_id).await?;
+    }
+
+    sqlx::query("UPDATE kits SET name = $2, sender_id = $3, notes = $4 WHERE id = $1")
+        .bind(&id)
+        .bind(&name)
+        .bind(&body.sender_id)
+        .bind(&notes)
+        .execute(state.pool())
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                ApiError::conflict("sender is already in another kit")
+            } else {
+                ApiError::internal(e.to_string())
+            }
+        })?;
+
+    super::audit::record_user(
+        &state,
+        &user,
+        body.sender_id.as_deref(),
+        "kit.update",
+        Some(name),
+    )
+    .await;
+
+    Ok(Json(fetch_summary(&state, &user, &id).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/kits/{id}",
+    tag = "kits",
+    summary = "Delete a kit (operator)",
+    params(("id" = String, Path, description = "Kit ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn delete_kit(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageKits)?;
+
+    let deleted: Option<(String, Option<String>)> = sqlx::query_as(
+        "DELETE FROM kits WHERE id = $1 AND owner_id = $2 AND NOT EXISTS \
+         (SELECT 1 FROM kit_checkouts c WHERE c.kit_id = kits.id AND c.checked_in_at IS NULL) \
+         RETURNING name, sender_id",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let Some((name, sender_id)) = deleted else {
+        // Distinguish "not yours / gone" from "still out".
+        verify_kit(&state, &user, &id).await?;
+        return Err(ApiError::conflict("kit is checked out; check it in first"));
+    };
+
+    tracing::info!(kit_id = %id, "kit deleted");
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "kit.delete",
+        Some(name),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Accessories ─────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/kits/{id}/accessories",
+    tag = "kits",
+    summary = "Add an accessory (operator)",
+    params(("id" = String, Path, description = "Kit ID")),
+    request_body = AddKitAccessoryRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = KitAccessory), ApiError)
+)]
+async fn add_accessory(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(kit_id): Path<String>,
+    Json(body): Json<AddKitAccessoryRequest>,
+) -> Result<(StatusCode, Json<KitAccessory>), ApiError> {
+    user.require(Capability::ManageKits)?;
+    let sender_id = verify_kit(&state, &user, &kit_id).await?;
+
+    if !KIT_ACCESSORY_KINDS.contains(&body.kind.as_str()) {
+        return Err(ApiError::bad_request(format!(
+            "kind must be one of {KIT_ACCESSORY_KINDS:?}"
+        )));
+    }
+    let serial = required(body.serial, "serial", MAX_SERIAL_LEN)?;
+    let label = clean(body.label, "label", MAX_NAME_LEN)?;
+
+    let id = ids::kit_accessory_id();
+    sqlx::query(
+        "INSERT INTO kit_accessories (id, kit_id, owner_id, kind, serial, label) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&id)
+    .bind(&kit_id)
+    .bind(&user.owner_id)
+    .bind(&body.kind)
+    .bind(&serial)
+    .bind(&label)
+    .execute(state.pool())
+    .await
+    .map_err(|e| {
+        if is_unique_violation(&e) {
+            ApiError::conflict(format!("{} {serial} is already in a kit", body.kind))
+        } else {
+            ApiError::internal(e.to_string())
+        }
+    })?;
+
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "kit.accessory_add",
+        Some(format!("{kit_id}: {} {serial}", body.kind)),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(KitAccessory {
+            id,
+            kind: body.kind,
+            serial,
+            label,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/kits/{id}/accessories/{accessory_id}",
+    tag = "kits",
+    summary = "Remove an accessory (operator)",
+    params(("id" = String, Path, description = "Kit ID"), ("accessory_id" = String, Path, description = "Accessory ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn remove_accessory(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((kit_id, accessory_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageKits)?;
+    let sender_id = verify_kit(&state, &user, &kit_id).await?;
+
+    let removed: Option<(String, String)> = sqlx::query_as(
+        "DELETE FROM kit_accessories WHERE id = $1 AND kit_id = $2 RETURNING kind, serial",
+    )
+    .bind(&accessory_id)
+    .bind(&kit_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (kind, serial) = removed.ok_or_else(|| ApiError::not_found("accessory not found"))?;
+
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "kit.accessory_remove",
+        Some(format!("{kit_id}: {kind} {serial}")),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Check-out / Check-in ────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/kits/{id}/check-out",
+    tag = "kits",
+    summary = "Hand the kit out (operator)",
+    params(("id" = String, Path, description = "Kit ID")),
+    request_body = CheckOutKitRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = KitCheckout), ApiError)
+)]
+async fn check_out(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(kit_id): Path<String>,
+    Json(body): Json<CheckOutKitRequest>,
+) -> Result<(StatusCode, Json<KitCheckout>), ApiError> {
+    user.require(Capability::ManageKits)?;
+    let sender_id = verify_kit(&state, &user, &kit_id).await?;
+
+    let holder = required(body.holder, "holder", MAX_NAME_LEN)?;
+    let note = clean(body.note, "note", MAX_NOTE_LEN)?;
+    if body.due_back_at.is_some_and(|due| due <= Utc::now()) {
+        return Err(ApiError::bad_request("due_back_at must be in the future"));
+    }
+
+    let id = ids::kit_checkout_id();
+    sqlx::query(
+        "INSERT INTO kit_checkouts (id, kit_id, holder, note, due_back_at, checked_out_by) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&id)
+    .bind(&kit_id)
+    .bind(&holder)
+    .bind(&note)
+    .bind(body.due_back_at)
+    .bind(&user.user_id)
+    .execute(state.pool())
+    .await
+    .map_err(|e| {
+        if is_unique_violation(&e) {
+            ApiError::conflict("kit is already checked out")
+        } else {
+            ApiError::internal(e.to_string())
+        }
+    })?;
+
+    tracing::info!(kit_id = %kit_id, holder = %holder, "kit checked out");
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "kit.check_out",
+        Some(format!("{kit_id} → {holder}")),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_checkout(&state, &id).await?),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/kits/{id}/check-in",
+    tag = "kits",
+    summary = "Take the kit back (operator)",
+    params(("id" = String, Path, description = "Kit ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = KitCheckout), ApiError)
+)]
+async fn check_in(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(kit_id): Path<String>,
+) -> Result<Json<KitCheckout>, ApiError> {
+    user.require(Capability::ManageKits)?;
+    let sender_id = verify_kit(&state, &user, &kit_id).await?;
+
+    let closed: Option<(String, String)> = sqlx::query_as(
+        "UPDATE kit_checkouts SET checked_in_at = now(), checked_in_by = $2 \
+         WHERE kit_id = $1 AND checked_in_at IS NULL RETURNING id, holder",
+    )
+    .bind(&kit_id)
+    .bind(&user.user_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (id, holder) = closed.ok_or_else(|| ApiError::conflict("kit is not checked out"))?;
+
+    tracing::info!(kit_id = %kit_id, holder = %holder, "kit checked in");
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "kit.check_in",
+        Some(format!("{kit_id} ← {holder}")),
+    )
+    .await;
+
+    Ok(Json(fetch_checkout(&state, &id).await?))
+}
@@ -0,0 +1,322 @@
+//! Per-link event feed behind the Stream tab's link timeline.
+//!
+//! GET /api/streams/:id/link-events — the stream's events, oldest first
+//!
+//! Nothing on the wire reports link transitions directly, so they are
+//! derived here: [`record_stats`] classifies every link in each
+//! `stream.stats` sample and stores a row when a link's [`LinkPhase`]
+//! differs from the last one recorded, and [`record_interfaces`] diffs
+//! consecutive `device.status` heartbeats for band changes and interfaces
+//! being disabled or re-enabled.
+
+use std::collections::HashMap;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use chrono::{DateTime, Utc};
+
+use strata_protocol::models::{LinkEvent, LinkEventKind, LinkPhase};
+use strata_protocol::telemetry::LinkSample;
+use strata_protocol::{DeviceStatusPayload, StreamStatsPayload};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+#[utoipa::path(
+    get,
+    path = "/api/streams/{id}/link-events",
+    tag = "streams",
+    summary = "Per-link timeline feed",
+    params(("id" = String, Path, description = "Stream ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<LinkEvent>), ApiError)
+)]
+pub(crate) async fn list_link_events(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<LinkEvent>>, ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+                       WHERE s.id = $1 AND sn.owner_id = $2)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if !exists {
+        return Err(ApiError::not_found("stream not found"));
+    }
+
+    let rows = sqlx::query_as::<
+        _,
+        (
+            DateTime<Utc>,
+            String,
+            String,
+            Option<String>,
+            Option<String>,
+        ),
+    >(
+        "SELECT ts, interface, kind, phase, detail FROM link_events \
+         WHERE stream_id = $1 ORDER BY ts, id",
+    )
+    .bind(&id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(
+        rows.into_iter()
+            .filter_map(|(ts, interface, kind, phase, detail)| {
+                Some(LinkEvent {
+                    ts,
+                    interface,
+                    kind: kind.parse().ok()?,
+                    phase: phase.and_then(|p| p.parse().ok()),
+                    detail,
+                })
+            })
+            .collect(),
+    ))
+}
+
+// ── Recording ───────────────────────────────────────────────────────
+
+/// Store a phase event for every link whose phase changed since the
+/// previous `stream.stats` sample of the same stream.
+pub async fn record_stats(state: &AppState, stats: &StreamStatsPayload) {
+    let current = classify(&stats.links);
+    let changes = {
+        let mut previous = state
+            .link_phases()
+            .entry(stats.stream_id.clone())
+            .or_default();
+        let changes = phase_changes(&previous, &current);
+        *previous = current;
+        changes
+    };
+
+    for (interface, phase) in changes {
+        insert(
+            state,
+            &stats.stream_id,
+            &stats.sender_id,
+            &interface,
+            LinkEventKind::Phase,
+            Some(phase),
+            None,
+        )
+        .await;
+    }
+}
+
+/// Store band changes and enable/disable toggles between two heartbeats
+/// against every stream the device is running.
+pub async fn record_interfaces(
+    state: &AppState,
+    sender_id: &str,
+    previous: Option<&DeviceStatusPayload>,
+    current: &DeviceStatusPayload,
+) {
+    let Some(previous) = previous else {
+        return;
+    };
+    if current.running_streams.is_empty() {
+        return;
+    }
+
+    for (interface, kind, detail) in interface_changes(previous, current) {
+        for stream_id in &current.running_streams {
+            insert(
+                state,
+                stream_id,
+                sender_id,
+                &interface,
+                kind,
+                None,
+                detail.clone(),
+            )
+            .await;
+        }
+    }
+}
+
+/// Forget a stream's link phases once it has ended.
+pub fn clear(state: &AppState, stream_id: &str) {
+    state.link_phases().remove(stream_id);
+}
+
+async fn insert(
+    state: &AppState,
+    stream_id: &str,
+    sender_id: &str,
+    interface: &str,
+    kind: LinkEventKind,
+    phase: Option<LinkPhase>,
+    detail: Option<String>,
+) {
+    if let Err(e) = sqlx::query(
+        "INSERT INTO link_events (stream_id, sender_id, interface, kind, phase, detail) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(stream_id)
+    .bind(sender_id)
+    .bind(interface)
+    .bind(kind.as_str())
+    .bind(phase.map(|p| p.as_str()))
+    .bind(detail)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(stream_id = %stream_id, error = %e, "failed to record link event");
+    }
+}
+
+fn classify(links: &[LinkSample]) -> HashMap<String, LinkPhase> {
+    links
+        .iter()
+        .map(|link| {
+            let peers_carrying = links
+                .iter()
+                .any(|l| l.interface != link.interface && l.observed_bps.0 > 0);
+            (
+                link.interface.clone(),
+                LinkPhase::from_stats(link, peers_carrying),
+            )
+        })
+        .collect()
+}
+
+/// Links that are new or changed phase, plus links that vanished from the
+/// sample (recorded as down unless they already were).
+fn phase_changes(
+    previous: &HashMap<String, LinkPhase>,
+    current: &HashMap<String, LinkPhase>,
+) -> Vec<(String, LinkPhase)> {
+    let mut changes: Vec<(String, LinkPhase)> = current
+        .iter()
+        .filter(|(iface, phase)| previous.get(*iface) != Some(phase))
+        .map(|(iface, phase)| (iface.clone(), *phase))
+        .collect();
+    changes.extend(
+        previous
+            .iter()
+            .filter(|(iface, phase)| !current.contains_key(*iface) && **phase != LinkPhase::Down)
+            .map(|(iface, _)| (iface.clone(), LinkPhase::Down)),
+    );
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    changes
+}
+
+fn interface_changes(
+    previous: &DeviceStatusPayload,
+    current: &DeviceStatusPayload,
+) -> Vec<(String, LinkEventKind, Option<String>)> {
+    let mut changes = Vec::new();
+    for iface in &current.network_interfaces {
+        let Some(before) = previous
+            .network_interfaces
+            .iter()
+            .find(|i| i.name == iface.name)
+        else {
+            continue;
+        };
+        if before.enabled != iface.enabled {
+            let kind = if iface.enabled {
+                LinkEventKind::Enabled
+            } else {
+                LinkEventKind::Disabled
+            };
+            changes.push((iface.name.clone(), kind, None));
+        }
+        if let (Some(old), Some(new)) = (&before.band, &iface.band)
+            && old != new
+        {
+            changes.push((
+                iface.name.clone(),
+                LinkEventKind::BandChange,
+                Some(format!("{old} → {new}")),
+            ));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phases(entries: &[(&str, LinkPhase)]) -> HashMap<String, LinkPhase> {
+        entries.iter().map(|(i, p)| (i.to_string(), *p)).collect()
+    }
+
+    #[test]
+    fn only_changed_and_vanished_links_are_recorded() {
+        let previous = phases(&[
+            ("eth0", LinkPhase::Live),
+            ("wwan0", LinkPhase::Probing),
+            ("wwan1", LinkPhase::Live),
+            ("wwan2", LinkPhase::Down),
+        ]);
+        let current = phases(&[
+            ("eth0", LinkPhase::Live),
+            ("wwan0", LinkPhase::Live),
+            ("wlan0", LinkPhase::Probing),
+        ]);
+        assert_eq!(
+            phase_changes(&previous, &current),
+            vec![
+                ("wlan0".to_string(), LinkPhase::Probing),
+                ("wwan0".to_string(), LinkPhase::Live),
+                ("wwan1".to_string(), LinkPhase::Down),
+            ]
+        );
+        assert!(phase_changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn heartbeat_diff_finds_band_changes_and_toggles() {
+        let status = |enabled: bool, band: &str| -> DeviceStatusPayload {
+            serde_json::from_value(serde_json::json!({
+                "network_interfaces": [{
+                    "name": "wwan0",
+                    "type": "cellular",
+                    "state": "connected",
+                    "enabled": enabled,
+                    "ip": null,
+                    "carrier": null,
+                    "signal_dbm": null,
+                    "technology": null,
+                    "cell_id": null,
+                    "band": band,
+                    "data_cap_mb": null,
+                    "data_used_mb": null,
+                    "apn": null,
+                }],
+                "media_inputs": [],
+                "stream_state": "live",
+                "cpu_percent": 0.0,
+                "mem_used_mb": 0,
+                "uptime_s": 0,
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(
+            interface_changes(&status(true, "B3"), &status(false, "B7")),
+            vec![
+                ("wwan0".to_string(), LinkEventKind::Disabled, None),
+                (
+                    "wwan0".to_string(),
+                    LinkEventKind::BandChange,
+                    Some("B3 → B7".to_string())
+                ),
+            ]
+        );
+        assert!(interface_changes(&status(true, "B3"), &status(true, "B3")).is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+//! Scheduled maintenance windows.
+//!
+//! GET    /api/maintenance        — list windows (own senders + fleet-wide)
+//! POST   /api/maintenance        — schedule a window
+//! DELETE /api/maintenance/:id    — cancel a window
+//!
+//! Any change re-pushes `maintenance.schedule` to the affected online
+//! agents; agents also receive their schedule right after authenticating.
+//! Alert evaluation must consult [`is_suppressed`] before firing.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+
+use strata_common::ids;
+use strata_protocol::api::{
+    Capability, CreateMaintenanceWindowRequest, CreateMaintenanceWindowResponse,
+};
+use strata_protocol::models::MaintenanceWindow;
+use strata_protocol::{ControlMessage, Envelope, MaintenanceSchedulePayload};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_windows).post(create_window))
+        .route("/{id}", delete(delete_window))
+}
+
+type WindowRow = (
+    String,
+    Option<String>,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    bool,
+    bool,
+    Option<String>,
+);
+
+fn window_from_row(
+    (id, sender_id, starts_at, ends_at, allow_ota, allow_reboot, reason): WindowRow,
+) -> MaintenanceWindow {
+    MaintenanceWindow {
+        id,
+        sender_id,
+        starts_at,
+        ends_at,
+        allow_ota,
+        allow_reboot,
+        reason,
+    }
+}
+
+// ── List Windows ────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/maintenance",
+    tag = "maintenance",
+    summary = "List windows (own senders + fleet-wide)",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<MaintenanceWindow>), ApiError)
+)]
+async fn list_windows(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<Vec<MaintenanceWindow>>, ApiError> {
+    let rows = sqlx::query_as::<_, WindowRow>(
+        "SELECT id, sender_id, starts_at, ends_at, allow_ota, allow_reboot, reason \
+         FROM maintenance_windows WHERE owner_id = $1 ORDER BY starts_at",
+    )
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(rows.into_iter().map(window_from_row).collect()))
+}
+
+// ── Create Window ───────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/maintenance",
+    tag = "maintenance",
+    summary = "Schedule a window",
+    request_body = CreateMaintenanceWindowRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateMaintenanceWindowResponse), ApiError)
+)]
+async fn create_window(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<CreateMaintenanceWindowRequest>,
+) -> Result<(StatusCode, Json<CreateMaintenanceWindowResponse>), ApiError> {
+    user.require(Capability::ManageAlerts)?;
+
+    if body.ends_at <= body.starts_at {
+        return Err(ApiError::bad_request("ends_at must be after starts_at"));
+    }
+    if body.ends_at <= Utc::now() {
+        return Err(ApiError::bad_request("window is already over"));
+    }
+    if let Some(ref sender_id) = body.sender_id {
+        super::senders::verify_ownership(&state, &user, sender_id).await?;
+    }
+
+    let id = ids::maintenance_window_id();
+
+    sqlx::query(
+        "INSERT INTO maintenance_windows \
+         (id, owner_id, sender_id, starts_at, ends_at, allow_ota, allow_reboot, reason) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&body.sender_id)
+    .bind(body.starts_at)
+    .bind(body.ends_at)
+    .bind(body.allow_ota)
+    .bind(body.allow_reboot)
+    .bind(&body.reason)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(
+        window_id = %id,
+        sender_id = ?body.sender_id,
+        starts_at = %body.starts_at,
+        ends_at = %body.ends_at,
+        "maintenance window scheduled"
+    );
+
+    notify_affected(&state, &user.owner_id, body.sender_id.as_deref()).await;
+    super::audit::record_user(
+        &state,
+        &user,
+        body.sender_id.as_deref(),
+        "maintenance.schedule",
+        Some(format!("{} – {}", body.starts_at, body.ends_at)),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateMaintenanceWindowResponse { id }),
+    ))
+}
+
+// ── Delete Window ───────────────────────────────────────────────────
+
+#[utoipa::path(
+    delete,
+    path = "/api/maintenance/{id}",
+    tag = "maintenance",
+    summary = "Cancel a window",
+    params(("id" = String, Path, description = "Maintenance window ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn delete_window(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageAlerts)?;
+
+    let deleted: Option<Option<String>> = sqlx::query_scalar(
+        "DELETE FROM maintenance_windows WHERE id = $1 AND owner_id = $2 RETURNING sender_id",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let Some(sender_id) = deleted else {
+        return Err(ApiError::not_found("maintenance window not found"));
+    };
+
+    tracing::info!(window_id = %id, "maintenance window cancelled");
+
+    notify_affected(&state, &user.owner_id, sender_id.as_deref()).await;
+    super::audit::record_user(
+        &state,
+        &user,
+        sender_id.as_deref(),
+        "maintenance.cancel",
+        Some(id),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Schedule Lookup & Push ──────────────────────────────────────────
+
+/// Windows covering `sender_id` that have not ended yet — its own plus the
+/// owner's fleet-wide ones.
+pub async fn upcoming_for_sender(
+    state: &AppState,
+    sender_id: &str,
+) -> Result<Vec<MaintenanceWindow>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, WindowRow>(
+        "SELECT m.id, m.sender_id, m.starts_at, m.ends_at, m.allow_ota, m.allow_reboot, m.reason \
+         FROM maintenance_windows m JOIN senders s ON s.owner_id = m.owner_id \
+         WHERE s.id = $1 AND (m.sender_id IS NULL OR m.sender_id = $1) AND m.ends_at > now() \
+         ORDER BY m.starts_at",
+    )
+    .bind(sender_id)
+    .fetch_all(state.pool())
+    .await?;
+
+    Ok(rows.into_iter().map(window_from_row).collect())
+}
+
+/// Whether alerting for `sender_id` is currently suppressed by an open
+/// maintenance window. Errors count as "not suppressed" — a DB hiccup
+/// should not silence alerts.
+pub async fn is_suppressed(state: &AppState, sender_id: &str) -> bool {
+    let now = Utc::now();
+    upcoming_for_sender(state, sender_id)
+        .await
+        .map(|windows| windows.iter().any(|w| w.is_open_at(now)))
+        .unwrap_or(false)
+}
+
+/// Push the current schedule to one connected agent. No-op when offline —
+/// the agent gets it on its next connect.
+pub async fn push_schedule(state: &AppState, sender_id: &str) {
+    let Some(tx) = state.agents().get(sender_id).map(|a| a.tx.clone()) else {
+        return;
+    };
+    let windows = match upcoming_for_sender(state, sender_id).await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::warn!(sender_id = %sender_id, error = %e, "failed to load maintenance schedule");
+            return;
+        }
+    };
+    let msg = ControlMessage::MaintenanceSchedule(MaintenanceSchedulePayload { windows });
+    match Envelope::from_message(&msg).and_then(|e| serde_json::to_string(&e)) {
+        Ok(json) => {
+            let _ = tx.send(json).await;
+        }
+        Err(e) => tracing::error!(error = %e, "failed to serialize maintenance.schedule"),
+    }
+}
+
+/// Re-push schedules after a change: one sender, or every online sender
+/// of the owner for a fleet-wide window.
+async fn notify_affected(state: &AppState, owner_id: &str, sender_id: Option<&str>) {
+    if let Some(sender_id) = sender_id {
+        push_schedule(state, sender_id).await;
+        return;
+    }
+    let sender_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM senders WHERE owner_id = $1")
+        .bind(owner_id)
+        .fetch_all(state.pool())
+        .await
+        .unwrap_or_default();
+    for id in sender_ids
+        .iter()
+        .filter(|id| state.agents().contains_key(id.as_str()))
+    {
+        push_schedule(state, id).await;
+    }
+}
@@ -0,0 +1,178 @@
+//! Endpoints about the authenticated user.
+//!
+//! GET /api/me              — who you are and what you may do
+//! DELETE /api/me           — erase yourself (see below)
+//! GET /api/me/preferences  — dashboard preferences (defaults if never set)
+//! PUT /api/me/preferences  — replace them
+//!
+//! An invited user deleting themselves is scrubbed like an admin erasure
+//! (`users.rs`). An account owner deleting themselves deletes the whole
+//! account — members, fleet and history cascade with it — after copying
+//! its monthly usage into `usage_anonymized` with no identifiers attached.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+
+use strata_common::ids::UserId;
+use strata_protocol::api::{MeResponse, UserPreferences};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_me).delete(delete_me))
+        .route("/preferences", get(get_preferences).put(put_preferences))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me",
+    tag = "me",
+    summary = "Who you are and what you may do",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = MeResponse), ApiError)
+)]
+async fn get_me(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<MeResponse>, ApiError> {
+    let me = super::users::load_user(&state, &user, &user.user_id).await?;
+    Ok(Json(MeResponse {
+        user_id: UserId::from_trusted(user.user_id.clone()),
+        email: me.email,
+        owner: me.owner,
+        capabilities: user.capabilities(),
+        role: user.role,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/me",
+    tag = "me",
+    summary = "Erase yourself",
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn delete_me(State(state): State<AppState>, user: AuthUser) -> Result<StatusCode, ApiError> {
+    let me = super::users::load_user(&state, &user, &user.user_id).await?;
+    if me.owner {
+        delete_account(&state, &user).await?;
+    } else {
+        super::users::erase(&state, &user, &me).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delete the caller's account, keeping only anonymized usage totals.
+async fn delete_account(state: &AppState, user: &AuthUser) -> Result<(), ApiError> {
+    let active: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM streams s JOIN senders snd ON snd.id = s.sender_id \
+         WHERE snd.owner_id = $1 AND s.state = ANY($2)",
+    )
+    .bind(&user.owner_id)
+    .bind(&crate::stream_state::ACTIVE_STATES[..])
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if active > 0 {
+        return Err(ApiError::conflict(
+            "stop all streams before deleting the account",
+        ));
+    }
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    // The monthly slices api/usage.rs bills, rolled up without the sender.
+    sqlx::query(&format!(
+        "INSERT INTO usage_anonymized (month, stream_count, streamed_minutes, total_bytes) \
+         {} \
+         SELECT to_char(month, 'YYYY-MM'), COUNT(*)::BIGINT, \
+                COALESCE(SUM(secs), 0)::FLOAT8 / 60.0, COALESCE(SUM(bytes), 0)::BIGINT \
+         FROM slices GROUP BY month",
+        super::usage::MONTHLY_SLICES
+    ))
+    .bind(&user.owner_id)
+    .bind(None::<chrono::DateTime<chrono::Utc>>)
+    .bind(None::<chrono::DateTime<chrono::Utc>>)
+    .bind(None::<String>)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(&user.owner_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(owner = %user.owner_id, "account deleted");
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/preferences",
+    tag = "me",
+    summary = "Dashboard preferences (defaults if never set)",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = UserPreferences), ApiError)
+)]
+async fn get_preferences(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<UserPreferences>, ApiError> {
+    let stored: Option<Option<String>> =
+        sqlx::query_scalar("SELECT preferences_json FROM users WHERE id = $1")
+            .bind(&user.user_id)
+            .fetch_optional(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let Some(stored) = stored else {
+        return Err(ApiError::not_found("user not found"));
+    };
+    // A document that no longer parses (e.g. an enum value was dropped)
+    // falls back to defaults rather than locking the user out of the UI.
+    let prefs = stored
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    Ok(Json(prefs))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/me/preferences",
+    tag = "me",
+    summary = "Replace dashboard preferences",
+    request_body = UserPreferences,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = UserPreferences), ApiError)
+)]
+async fn put_preferences(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<UserPreferences>,
+) -> Result<Json<UserPreferences>, ApiError> {
+    body.validate().map_err(ApiError::bad_request)?;
+
+    let json = serde_json::to_string(&body).map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query("UPDATE users SET preferences_json = $1 WHERE id = $2")
+        .bind(&json)
+        .bind(&user.user_id)
+        .execute(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(body))
+}
@@ -37,9 +37,9 @@ pub async fn handler(State(state): State<AppState>, headers: HeaderMap) -> Respo
     let mut total_senders: usize = 0;
     let mut total_links: usize = 0;
 
-    for entry in state.stream_stats().iter() {
-        let sender_id = entry.key();
-        let stats = entry.value();
+    let all_stats = state.live().all_stats();
+    for stats in &all_stats {
+        let sender_id = &stats.sender_id;
 
         total_senders += 1;
         total_links += stats.links.len();
@@ -97,6 +97,23 @@ pub async fn handler(State(state): State<AppState>, headers: HeaderMap) -> Respo
                 .unwrap();
             }
         }
+
+        if let Some(q) = &stats.quality {
+            use std::fmt::Write;
+            let stream_id = &stats.stream_id;
+            writeln!(
+                out,
+                "strata_stream_quality_mos{{sender_id=\"{sender_id}\",stream_id=\"{stream_id}\"}} {:.2}",
+                q.mos
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "strata_stream_freeze_risk{{sender_id=\"{sender_id}\",stream_id=\"{stream_id}\"}} {:.3}",
+                q.freeze_risk
+            )
+            .unwrap();
+        }
     }
 
     // Fleet-wide aggregates
@@ -136,10 +153,8 @@ pub async fn handler(State(state): State<AppState>, headers: HeaderMap) -> Respo
     // as a fallback using the render_prometheus function for any
     // individual sender that happens to be the only one.
     // For a single-sender deployment, also render the standard flat format.
-    if total_senders == 1
-        && let Some(entry) = state.stream_stats().iter().next()
-    {
-        out.push_str(&render_prometheus(&entry.value().links));
+    if let [stats] = all_stats.as_slice() {
+        out.push_str(&render_prometheus(&stats.links));
     }
 
     (
@@ -155,7 +170,7 @@ pub async fn handler(State(state): State<AppState>, headers: HeaderMap) -> Respo
 #[cfg(test)]
 mod tests {
     use strata_protocol::StreamStatsPayload;
-    use strata_protocol::models::LinkStats;
+    use strata_protocol::telemetry::{Bps, Dbm, LinkSample, Millis};
 
     #[test]
     fn fleet_metrics_rendering() {
@@ -169,26 +184,31 @@ mod tests {
                     uptime_s: 100,
                     encoder_bitrate_kbps: 5000,
                     timestamp_ms: 0,
-                    links: vec![LinkStats {
+                    links: vec![LinkSample {
                         id: 0,
                         interface: "wwan0".into(),
                         state: "Live".into(),
-                        rtt_ms: 25.0,
+                        rtt_ms: Millis(25.0),
                         loss_rate: 0.01,
-                        capacity_bps: 5_000_000,
+                        capacity_bps: Bps(5_000_000),
                         sent_bytes: 100_000,
-                        observed_bps: 3_000_000,
-                        signal_dbm: Some(-65),
+                        observed_bps: Bps(3_000_000),
+                        signal_dbm: Some(Dbm(-65)),
                         link_kind: Some("cellular".into()),
                         rsrp: None,
                         rsrq: None,
                         sinr: None,
                         cqi: None,
-                        btlbw_bps: Some(4_500_000),
-                        rtprop_ms: Some(20.0),
+                        btlbw_bps: Some(Bps(4_500_000)),
+                        rtprop_ms: Some(Millis(20.0)),
+                        transport: None,
                     }],
                     sender_metrics: None,
                     receiver_metrics: None,
+                    ladder: None,
+                    quality: None,
+                    elements: Vec::new(),
+                    media: None,
                 },
             ),
             (
@@ -200,34 +220,35 @@ mod tests {
                     encoder_bitrate_kbps: 3000,
                     timestamp_ms: 0,
                     links: vec![
-                        LinkStats {
+                        LinkSample {
                             id: 0,
                             interface: "eth0".into(),
                             state: "Live".into(),
-                            rtt_ms: 10.0,
+                            rtt_ms: Millis(10.0),
                             loss_rate: 0.0,
-                            capacity_bps: 10_000_000,
+                            capacity_bps: Bps(10_000_000),
                             sent_bytes: 200_000,
-                            observed_bps: 8_000_000,
+                            observed_bps: Bps(8_000_000),
                             signal_dbm: None,
                             link_kind: Some("ethernet".into()),
                             rsrp: None,
                             rsrq: None,
                             sinr: None,
                             cqi: None,
-                            btlbw_bps: Some(9_000_000),
-                            rtprop_ms: Some(8.0),
+                            btlbw_bps: Some(Bps(9_000_000)),
+                            rtprop_ms: Some(Millis(8.0)),
+                            transport: None,
                         },
-                        LinkStats {
+                        LinkSample {
                             id: 1,
                             interface: "wwan0".into(),
                             state: "Down".into(),
-                            rtt_ms: 0.0,
+                            rtt_ms: Millis(0.0),
                             loss_rate: 1.0,
-                            capacity_bps: 0,
+                            capacity_bps: Bps(0),
                             sent_bytes: 50_000,
-                            observed_bps: 0,
-                            signal_dbm: Some(-95),
+                            observed_bps: Bps(0),
+                            signal_dbm: Some(Dbm(-95)),
                             link_kind: Some("cellular".into()),
                             rsrp: None,
                             rsrq: None,
@@ -235,10 +256,15 @@ mod tests {
                             cqi: None,
                             btlbw_bps: None,
                             rtprop_ms: None,
+                            transport: None,
                         },
                     ],
                     sender_metrics: None,
                     receiver_metrics: None,
+                    ladder: None,
+                    quality: None,
+                    elements: Vec::new(),
+                    media: None,
                 },
             ),
         ];
@@ -305,41 +331,43 @@ mod tests {
 
     #[test]
     fn signal_dbm_only_present_when_some() {
-        let link_with = LinkStats {
+        let link_with = LinkSample {
             id: 0,
             interface: "wwan0".into(),
             state: "Live".into(),
-            rtt_ms: 25.0,
+            rtt_ms: Millis(25.0),
             loss_rate: 0.01,
-            capacity_bps: 5_000_000,
+            capacity_bps: Bps(5_000_000),
             sent_bytes: 100_000,
-            observed_bps: 3_000_000,
-            signal_dbm: Some(-65),
+            observed_bps: Bps(3_000_000),
+            signal_dbm: Some(Dbm(-65)),
             link_kind: Some("cellular".into()),
             rsrp: None,
             rsrq: None,
             sinr: None,
             cqi: None,
-            btlbw_bps: Some(4_500_000),
-            rtprop_ms: Some(20.0),
+            btlbw_bps: Some(Bps(4_500_000)),
+            rtprop_ms: Some(Millis(20.0)),
+            transport: None,
         };
-        let link_without = LinkStats {
+        let link_without = LinkSample {
             id: 1,
             interface: "eth0".into(),
             state: "Live".into(),
-            rtt_ms: 10.0,
+            rtt_ms: Millis(10.0),
             loss_rate: 0.0,
-            capacity_bps: 10_000_000,
+            capacity_bps: Bps(10_000_000),
             sent_bytes: 200_000,
-            observed_bps: 8_000_000,
+            observed_bps: Bps(8_000_000),
             signal_dbm: None,
             link_kind: Some("ethernet".into()),
             rsrp: None,
             rsrq: None,
             sinr: None,
             cqi: None,
-            btlbw_bps: Some(9_000_000),
-            rtprop_ms: Some(8.0),
+            btlbw_bps: Some(Bps(9_000_000)),
+            rtprop_ms: Some(Millis(8.0)),
+            transport: None,
         };
 
         let mut out = String::new();
@@ -1,12 +1,28 @@
 //! REST API route tree.
 
+pub mod alerts;
+pub mod audit;
 pub mod auth;
 pub mod auth_extractor;
+pub mod certificates;
 pub mod destinations;
+pub mod history;
+pub mod ingest;
+pub mod kits;
+pub mod link_events;
+pub mod maintenance;
+pub mod me;
 pub mod metrics;
+pub mod openapi;
 pub mod receivers;
+pub mod reports;
+pub mod schedules;
 pub mod senders;
+pub mod share;
 pub mod streams;
+pub mod tenant;
+pub mod usage;
+pub mod users;
 
 use axum::Router;
 
@@ -16,8 +32,19 @@ use crate::state::AppState;
 pub fn router() -> Router<AppState> {
     Router::new()
         .nest("/auth", auth::router())
+        .nest("/me", me::router())
         .nest("/senders", senders::router())
         .nest("/streams", streams::router())
         .nest("/destinations", destinations::router())
         .nest("/receivers", receivers::router())
+        .nest("/certificates", certificates::router())
+        .nest("/kits", kits::router())
+        .nest("/maintenance", maintenance::router())
+        .nest("/schedules", schedules::router())
+        .nest("/share", share::router())
+        .nest("/usage", usage::router())
+        .nest("/alerts", alerts::router())
+        .nest("/audit", audit::router())
+        .nest("/users", users::router())
+        .merge(tenant::router())
 }
@@ -0,0 +1,211 @@
+//! OpenAPI description of the REST API.
+//!
+//! GET /api/openapi.json — the spec
+//! GET /api/docs         — Swagger UI over it
+//!
+//! Generated from the `#[utoipa::path]` annotation on each handler and the
+//! request/response types in `strata-protocol` (its `openapi` feature), so
+//! it cannot drift from the routes. A handler added to a router must be
+//! listed in [`ApiDoc`] too.
+
+use axum::Router;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use strata_protocol::api::ApiErrorResponse;
+
+use crate::state::AppState;
+
+use super::{
+    alerts, audit, auth, certificates, destinations, history, ingest, kits, link_events,
+    maintenance, me, receivers, reports, schedules, senders, share, streams, tenant, usage, users,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Strata control plane"),
+    paths(
+        auth::register,
+        auth::login,
+        auth::forgot_password,
+        auth::reset_password,
+        auth::accept_invite,
+        me::get_me,
+        me::delete_me,
+        me::get_preferences,
+        me::put_preferences,
+        senders::list_senders,
+        senders::create_sender,
+        ingest::create_ingest_sender,
+        senders::get_sender,
+        senders::delete_sender,
+        senders::get_sender_status,
+        senders::get_sender_live,
+        senders::unenroll_sender,
+        senders::set_sender_config,
+        senders::get_portal_auth,
+        senders::set_portal_auth,
+        senders::run_sender_test,
+        senders::scan_interfaces,
+        senders::enable_interface,
+        senders::disable_interface,
+        senders::lock_band,
+        senders::set_priority,
+        senders::set_apn,
+        senders::update_stream_config,
+        senders::switch_source,
+        senders::select_source,
+        senders::list_sender_files,
+        senders::run_network_tool,
+        senders::capture_pcap,
+        senders::get_logs,
+        senders::power_command,
+        senders::get_tls_status,
+        senders::renew_tls_cert,
+        senders::export_config,
+        senders::import_config,
+        senders::check_updates,
+        senders::install_update,
+        senders::set_stream_destinations,
+        senders::set_jitter_buffer,
+        history::get_metrics,
+        senders::get_alert_rules,
+        senders::set_alert_rule,
+        senders::delete_alert_rule,
+        streams::list_streams,
+        streams::get_stream,
+        link_events::list_link_events,
+        reports::get_report,
+        reports::list_annotations,
+        reports::create_annotation,
+        share::list_share_links,
+        share::create_share_link,
+        share::revoke_share_link,
+        streams::start_stream,
+        streams::stop_stream,
+        destinations::list_destinations,
+        destinations::create_destination,
+        destinations::update_destination,
+        destinations::delete_destination,
+        destinations::check_destination,
+        destinations::reveal_stream_key,
+        destinations::rotate_stream_key,
+        destinations::destination_usage,
+        receivers::list_receivers,
+        receivers::create_receiver,
+        receivers::get_receiver,
+        receivers::delete_receiver,
+        certificates::list_certificates,
+        certificates::upload_certificate,
+        certificates::issue_self_signed,
+        certificates::start_acme,
+        certificates::delete_certificate,
+        certificates::verify_acme,
+        certificates::renew_certificate,
+        certificates::push_certificate,
+        kits::list_kits,
+        kits::create_kit,
+        kits::get_kit,
+        kits::update_kit,
+        kits::delete_kit,
+        kits::add_accessory,
+        kits::remove_accessory,
+        kits::check_out,
+        kits::check_in,
+        maintenance::list_windows,
+        maintenance::create_window,
+        maintenance::delete_window,
+        schedules::list_schedule,
+        schedules::create_booking,
+        schedules::update_booking,
+        schedules::delete_booking,
+        share::shared_stream,
+        usage::get_usage,
+        usage::export_csv,
+        alerts::list_alerts,
+        alerts::list_rules,
+        alerts::acknowledge,
+        alerts::resolve,
+        audit::list_audit,
+        users::list_users,
+        users::invite_user,
+        users::update_user,
+        users::erase_user,
+        users::reset_password,
+        tenant::export,
+        tenant::import,
+    ),
+    components(schemas(ApiErrorResponse)),
+    modifiers(&BearerAuth)
+)]
+pub struct ApiDoc;
+
+/// The `bearer` scheme the annotated handlers require: a JWT from
+/// `/api/auth/login`, or a share token for `/api/share`.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                Http::builder()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// The spec and Swagger UI. Merged at the top level rather than nested
+/// under `/api`: Swagger UI redirects to absolute paths.
+pub fn router() -> Router<AppState> {
+    SwaggerUi::new("/api/docs")
+        .url("/api/openapi.json", ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn spec_describes_the_routes() {
+        let spec = ApiDoc::openapi();
+        let paths = &spec.paths.paths;
+        assert!(paths["/api/auth/login"].post.is_some());
+        assert!(paths["/api/senders/{id}"].get.is_some());
+        assert!(paths["/api/senders/{id}"].delete.is_some());
+        assert!(paths["/api/senders/{id}/metrics"].get.is_some());
+        assert!(paths["/api/streams/start/{sender_id}"].post.is_some());
+        assert!(paths["/api/export"].get.is_some());
+
+        // Operation IDs name client methods, so they must not collide.
+        let mut ids = HashSet::new();
+        for item in paths.values() {
+            for op in [&item.get, &item.post, &item.put, &item.delete, &item.patch]
+                .into_iter()
+                .flatten()
+            {
+                let id = op.operation_id.clone().unwrap();
+                assert!(ids.insert(id.clone()), "duplicate operation id {id}");
+            }
+        }
+
+        let components = spec.components.as_ref().unwrap();
+        assert!(components.security_schemes.contains_key("bearer"));
+        for schema in [
+            "ApiErrorResponse",
+            "ErrorCode",
+            "SenderDetail",
+            "TelemetrySample",
+        ] {
+            assert!(components.schemas.contains_key(schema), "{schema} missing");
+        }
+    }
+}
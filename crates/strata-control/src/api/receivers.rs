@@ -9,9 +9,12 @@ use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::routing::get;
 use axum::{Json, Router};
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
-use strata_common::ids;
+use strata_common::{ids, validation};
+use strata_protocol::api::{
+    Capability, CreateReceiverRequest, CreateReceiverResponse, ReceiverSummary,
+};
 
 use crate::api::auth::ApiError;
 use crate::state::AppState;
@@ -26,20 +29,14 @@ pub fn router() -> Router<AppState> {
 
 // ── List Receivers ──────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
-pub struct ReceiverSummary {
-    pub id: String,
-    pub name: Option<String>,
-    pub hostname: Option<String>,
-    pub region: Option<String>,
-    pub bind_host: String,
-    pub max_streams: i32,
-    pub active_streams: i32,
-    pub online: bool,
-    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/api/receivers",
+    tag = "receivers",
+    summary = "List receivers",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<ReceiverSummary>), ApiError)
+)]
 async fn list_receivers(
     State(state): State<AppState>,
     user: AuthUser,
@@ -48,7 +45,7 @@ async fn list_receivers(
         "SELECT id, name, hostname, region, bind_host, max_streams, active_streams, online, last_seen_at, created_at \
          FROM receivers WHERE owner_id = $1 ORDER BY created_at DESC",
     )
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_all(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -91,31 +88,23 @@ async fn list_receivers(
 
 // ── Create Receiver ─────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct CreateReceiverRequest {
-    pub name: Option<String>,
-    pub bind_host: String,
-    pub region: Option<String>,
-    #[serde(default = "default_max_streams")]
-    pub max_streams: i32,
-}
-
-fn default_max_streams() -> i32 {
-    6
-}
-
-#[derive(Debug, Serialize)]
-pub struct CreateReceiverResponse {
-    pub receiver_id: String,
-    pub enrollment_token: String,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/receivers",
+    tag = "receivers",
+    summary = "Create receiver (generates enrollment token)",
+    request_body = CreateReceiverRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateReceiverResponse), ApiError)
+)]
 async fn create_receiver(
     State(state): State<AppState>,
     user: AuthUser,
     Json(body): Json<CreateReceiverRequest>,
 ) -> Result<(StatusCode, Json<CreateReceiverResponse>), ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ManageReceivers)?;
+    validation::validate_host(&body.bind_host)
+        .map_err(|e| ApiError::bad_request(format!("bind_host: {e}")))?;
 
     let receiver_id = ids::receiver_id();
     let enrollment_token = ids::enrollment_token();
@@ -129,7 +118,7 @@ async fn create_receiver(
          VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
     )
     .bind(&receiver_id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .bind(&body.name)
     .bind(&body.bind_host)
     .bind(Vec::<i32>::new()) // link_ports filled on enrollment
@@ -140,7 +129,7 @@ async fn create_receiver(
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
-    tracing::info!(receiver_id = %receiver_id, owner = %user.user_id, "receiver created");
+    tracing::info!(receiver_id = %receiver_id, owner = %user.owner_id, "receiver created");
 
     // Composite <id>.<secret> token — see api/senders.rs::create_sender (E4).
     let enrollment_token = ids::composite_enrollment_token(&receiver_id, &enrollment_token);
@@ -155,7 +144,7 @@ async fn create_receiver(
 
 // ── Get Receiver ────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ReceiverDetail {
     pub id: String,
     pub name: Option<String>,
@@ -171,6 +160,15 @@ pub struct ReceiverDetail {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/receivers/{id}",
+    tag = "receivers",
+    summary = "Get receiver details",
+    params(("id" = String, Path, description = "Receiver ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = ReceiverDetail), ApiError)
+)]
 async fn get_receiver(
     State(state): State<AppState>,
     user: AuthUser,
@@ -181,7 +179,7 @@ async fn get_receiver(
          FROM receivers WHERE id = $1 AND owner_id = $2",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_optional(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?
@@ -221,16 +219,25 @@ async fn get_receiver(
 
 // ── Delete Receiver ─────────────────────────────────────────────────
 
+#[utoipa::path(
+    delete,
+    path = "/api/receivers/{id}",
+    tag = "receivers",
+    summary = "Decommission receiver",
+    params(("id" = String, Path, description = "Receiver ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn delete_receiver(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ManageReceivers)?;
 
     let result = sqlx::query("DELETE FROM receivers WHERE id = $1 AND owner_id = $2")
         .bind(&id)
-        .bind(&user.user_id)
+        .bind(&user.owner_id)
         .execute(state.pool())
         .await
         .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -0,0 +1,720 @@
+//! Post-stream incident reports.
+//!
+//! GET  /api/streams/:id/report?format=json|pdf — the stream's report
+//! GET  /api/streams/:id/annotations           — operator notes, oldest first
+//! POST /api/streams/:id/annotations           — pin a note to the timeline
+//!
+//! A report is one timeline of what went wrong and what changed during a
+//! stream: links dropping to failover, stretches of heavy loss, alert
+//! firings, ABR ladder steps and operator notes. [`generate_detached`]
+//! builds it when the stream ends and stores it with the stream, so it
+//! survives the metrics history being pruned; asking for an active
+//! stream's report builds a provisional one on the spot. Ladder steps only
+//! ever show up in `stream.stats`, so [`record_stats`] keeps them as they
+//! arrive.
+
+use std::fmt::Write;
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_protocol::StreamStatsPayload;
+use strata_protocol::api::{Capability, CreateAnnotationRequest};
+use strata_protocol::models::{IncidentKind, ReportIncident, StreamAnnotation, StreamReport};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+use super::history::SAMPLE_INTERVAL;
+
+/// Mean link loss (percent) at which a metrics sample counts towards a
+/// loss spike.
+pub const LOSS_SPIKE_PCT: f64 = 5.0;
+
+/// Longest annotation accepted, in characters.
+const MAX_ANNOTATION_LEN: usize = 500;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ReportQuery {
+    /// `json` (the default) or `pdf`.
+    format: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/streams/{id}/report",
+    tag = "streams",
+    summary = "Incident report, JSON or PDF",
+    params(("id" = String, Path, description = "Stream ID"), ReportQuery),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "OK", content((StreamReport = "application/json"), ("application/pdf"))),
+        ApiError
+    )
+)]
+pub(crate) async fn get_report(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Query(q): Query<ReportQuery>,
+) -> Result<Response, ApiError> {
+    let pdf = match q.format.as_deref() {
+        None | Some("json") => false,
+        Some("pdf") => true,
+        Some(_) => return Err(ApiError::bad_request("format must be json or pdf")),
+    };
+    ensure_owned(&state, &user, &id).await?;
+
+    let stored =
+        sqlx::query_scalar::<_, String>("SELECT report FROM stream_reports WHERE stream_id = $1")
+            .bind(&id)
+            .fetch_optional(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .and_then(|json| serde_json::from_str::<StreamReport>(&json).ok());
+    let mut report = match stored {
+        Some(report) => report,
+        None => {
+            let report = build(&state, &id)
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?
+                .ok_or_else(|| ApiError::not_found("stream not found"))?;
+            // An ended stream whose report never got written (it ended
+            // before reports existed, or the write failed) gets it now.
+            if report.ended_at.is_some() {
+                store(&state, &report).await;
+            }
+            report
+        }
+    };
+
+    let notes = load_annotations(&state, &id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    report
+        .incidents
+        .extend(notes.into_iter().map(|note| ReportIncident {
+            ts: note.ts,
+            kind: IncidentKind::Annotation,
+            interface: None,
+            detail: match note.author {
+                Some(author) => format!("{author}: {}", note.text),
+                None => note.text,
+            },
+            duration_s: None,
+        }));
+    report.incidents.sort_by_key(|i| i.ts);
+
+    if !pdf {
+        return Ok(Json(report).into_response());
+    }
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{id}-report.pdf\""),
+            ),
+        ],
+        render_pdf(&report_lines(&report)),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/streams/{id}/annotations",
+    tag = "streams",
+    summary = "Operator notes, oldest first",
+    params(("id" = String, Path, description = "Stream ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<StreamAnnotation>), ApiError)
+)]
+pub(crate) async fn list_annotations(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<StreamAnnotation>>, ApiError> {
+    ensure_owned(&state, &user, &id).await?;
+    let notes = load_annotations(&state, &id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(notes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/streams/{id}/annotations",
+    tag = "streams",
+    summary = "Pin a note to the timeline",
+    params(("id" = String, Path, description = "Stream ID")),
+    request_body = CreateAnnotationRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = StreamAnnotation), ApiError)
+)]
+pub(crate) async fn create_annotation(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<CreateAnnotationRequest>,
+) -> Result<(StatusCode, Json<StreamAnnotation>), ApiError> {
+    user.require(Capability::AnnotateStreams)?;
+    let text = body.text.trim();
+    if text.is_empty() {
+        return Err(ApiError::bad_request("annotation text is empty"));
+    }
+    if text.chars().count() > MAX_ANNOTATION_LEN {
+        return Err(ApiError::bad_request(format!(
+            "annotation text is longer than {MAX_ANNOTATION_LEN} characters"
+        )));
+    }
+    ensure_owned(&state, &user, &id).await?;
+
+    let (note_id, ts, author) = sqlx::query_as::<_, (i64, DateTime<Utc>, Option<String>)>(
+        "INSERT INTO stream_annotations (stream_id, ts, author_id, text) \
+         VALUES ($1, COALESCE($2, now()), $3, $4) \
+         RETURNING id, ts, (SELECT email FROM users WHERE id = $3)",
+    )
+    .bind(&id)
+    .bind(body.ts)
+    .bind(&user.user_id)
+    .bind(text)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(StreamAnnotation {
+            id: note_id,
+            ts,
+            author,
+            text: text.to_string(),
+        }),
+    ))
+}
+
+async fn ensure_owned(state: &AppState, user: &AuthUser, stream_id: &str) -> Result<(), ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+                       WHERE s.id = $1 AND sn.owner_id = $2)",
+    )
+    .bind(stream_id)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if exists {
+        Ok(())
+    } else {
+        Err(ApiError::not_found("stream not found"))
+    }
+}
+
+async fn load_annotations(
+    state: &AppState,
+    stream_id: &str,
+) -> sqlx::Result<Vec<StreamAnnotation>> {
+    let rows = sqlx::query_as::<_, (i64, DateTime<Utc>, Option<String>, String)>(
+        "SELECT a.id, a.ts, u.email, a.text FROM stream_annotations a \
+         LEFT JOIN users u ON a.author_id = u.id \
+         WHERE a.stream_id = $1 ORDER BY a.ts, a.id",
+    )
+    .bind(stream_id)
+    .fetch_all(state.pool())
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, ts, author, text)| StreamAnnotation {
+            id,
+            ts,
+            author,
+            text,
+        })
+        .collect())
+}
+
+// ── Recording ───────────────────────────────────────────────────────
+
+/// Store the ladder step a `stream.stats` sample reports, if any.
+pub async fn record_stats(state: &AppState, stats: &StreamStatsPayload) {
+    let Some(ladder) = &stats.ladder else {
+        return;
+    };
+    let Some(from) = ladder.stepped_from else {
+        return;
+    };
+    if let Err(e) = sqlx::query(
+        "INSERT INTO ladder_steps (stream_id, from_rung, to_rung, resolution) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(&stats.stream_id)
+    .bind(from as i32)
+    .bind(ladder.rung as i32)
+    .bind(&ladder.resolution)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(stream_id = %stats.stream_id, error = %e, "failed to record ladder step");
+    }
+}
+
+/// Build and store the report of a stream that just ended, off the
+/// caller's path.
+pub fn generate_detached(state: &AppState, stream_id: &str) {
+    let state = state.clone();
+    let stream_id = stream_id.to_string();
+    tokio::spawn(async move {
+        match build(&state, &stream_id).await {
+            Ok(Some(report)) => store(&state, &report).await,
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(stream_id = %stream_id, error = %e, "failed to build incident report");
+            }
+        }
+    });
+}
+
+async fn store(state: &AppState, report: &StreamReport) {
+    let Ok(json) = serde_json::to_string(report) else {
+        return;
+    };
+    if let Err(e) = sqlx::query(
+        "INSERT INTO stream_reports (stream_id, generated_at, report) VALUES ($1, $2, $3) \
+         ON CONFLICT (stream_id) DO UPDATE \
+         SET generated_at = EXCLUDED.generated_at, report = EXCLUDED.report",
+    )
+    .bind(&report.stream_id)
+    .bind(report.generated_at)
+    .bind(json)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(stream_id = %report.stream_id, error = %e, "failed to store incident report");
+    }
+}
+
+// ── Building ────────────────────────────────────────────────────────
+
+/// Assemble a stream's report from what the control plane recorded while
+/// it ran, annotations aside. `None` if the stream doesn't exist.
+async fn build(state: &AppState, stream_id: &str) -> sqlx::Result<Option<StreamReport>> {
+    let Some((
+        sender_id,
+        sender_name,
+        started_at,
+        ended_at,
+        end_reason,
+        error_message,
+        total_bytes,
+    )) = sqlx::query_as::<
+        _,
+        (
+            String,
+            String,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<String>,
+            Option<String>,
+            i64,
+        ),
+    >(
+        "SELECT s.sender_id, COALESCE(sn.name, sn.hostname, sn.id), s.started_at, \
+                    s.ended_at, s.end_reason, s.error_message, s.total_bytes \
+             FROM streams s JOIN senders sn ON s.sender_id = sn.id WHERE s.id = $1",
+    )
+    .bind(stream_id)
+    .fetch_optional(state.pool())
+    .await?
+    else {
+        return Ok(None);
+    };
+    let now = Utc::now();
+    let mut incidents = Vec::new();
+
+    let failovers = sqlx::query_as::<_, (DateTime<Utc>, String, Option<String>)>(
+        "SELECT ts, interface, phase FROM link_events \
+         WHERE stream_id = $1 AND kind = 'phase' AND phase IN ('failover', 'down') \
+         ORDER BY ts, id",
+    )
+    .bind(stream_id)
+    .fetch_all(state.pool())
+    .await?;
+    incidents.extend(
+        failovers
+            .into_iter()
+            .map(|(ts, interface, phase)| ReportIncident {
+                ts,
+                kind: IncidentKind::LinkFailover,
+                interface: Some(interface),
+                detail: match phase.as_deref() {
+                    Some("down") => "link went down".into(),
+                    _ => "link dropped to failover".into(),
+                },
+                duration_s: None,
+            }),
+    );
+
+    let samples = sqlx::query_as::<_, (DateTime<Utc>, Option<f64>)>(
+        "SELECT ts, loss_pct FROM sender_metrics WHERE stream_id = $1 ORDER BY ts",
+    )
+    .bind(stream_id)
+    .fetch_all(state.pool())
+    .await?;
+    incidents.extend(loss_spikes(&samples));
+
+    // Alerts are per sender; the stream's window picks out its own.
+    let alerts = sqlx::query_as::<_, (DateTime<Utc>, String, String, String, f64, String, f64)>(
+        "SELECT fired_at, rule_name, metric, condition, threshold, severity, value \
+         FROM alert_events WHERE sender_id = $1 AND fired_at BETWEEN $2 AND $3 \
+         ORDER BY fired_at",
+    )
+    .bind(&sender_id)
+    .bind(started_at)
+    .bind(ended_at.unwrap_or(now))
+    .fetch_all(state.pool())
+    .await?;
+    incidents.extend(alerts.into_iter().map(
+        |(ts, rule, metric, condition, threshold, severity, value)| ReportIncident {
+            ts,
+            kind: IncidentKind::Alert,
+            interface: None,
+            detail: format!(
+                "{severity}: {rule} ({metric} {condition} {threshold}, was {value:.2})"
+            ),
+            duration_s: None,
+        },
+    ));
+
+    let steps = sqlx::query_as::<_, (DateTime<Utc>, i32, i32, String)>(
+        "SELECT ts, from_rung, to_rung, resolution FROM ladder_steps \
+         WHERE stream_id = $1 ORDER BY ts, id",
+    )
+    .bind(stream_id)
+    .fetch_all(state.pool())
+    .await?;
+    incidents.extend(
+        steps
+            .into_iter()
+            .map(|(ts, from, to, resolution)| ReportIncident {
+                ts,
+                kind: IncidentKind::LadderStep,
+                interface: None,
+                detail: format!(
+                    "{} to {resolution} (rung {from} -> {to})",
+                    if to > from { "down" } else { "up" }
+                ),
+                duration_s: None,
+            }),
+    );
+
+    incidents.sort_by_key(|i| i.ts);
+    Ok(Some(StreamReport {
+        stream_id: stream_id.to_string(),
+        sender_id,
+        sender_name,
+        started_at,
+        ended_at,
+        end_reason,
+        error_message,
+        total_bytes: total_bytes.max(0) as u64,
+        generated_at: now,
+        incidents,
+    }))
+}
+
+/// Runs of metrics samples at or above [`LOSS_SPIKE_PCT`], one incident
+/// each. A spike lasts until the first sample back under the threshold;
+/// one still running at the last sample is given that sample's interval.
+fn loss_spikes(samples: &[(DateTime<Utc>, Option<f64>)]) -> Vec<ReportIncident> {
+    let spike = |from: DateTime<Utc>, to: DateTime<Utc>, peak: f64| ReportIncident {
+        ts: from,
+        kind: IncidentKind::LossSpike,
+        interface: None,
+        detail: format!("loss peaked at {peak:.1}%"),
+        duration_s: Some((to - from).num_seconds().max(0) as u64),
+    };
+
+    let mut spikes = Vec::new();
+    // (first sample of the spike, peak loss)
+    let mut open: Option<(DateTime<Utc>, f64)> = None;
+    for &(ts, loss) in samples {
+        match (loss.filter(|&l| l >= LOSS_SPIKE_PCT), open.as_mut()) {
+            (Some(loss), Some((_, peak))) => *peak = peak.max(loss),
+            (Some(loss), None) => open = Some((ts, loss)),
+            (None, Some(&mut (from, peak))) => {
+                spikes.push(spike(from, ts, peak));
+                open = None;
+            }
+            (None, None) => {}
+        }
+    }
+    if let (Some((from, peak)), Some(&(last, _))) = (open, samples.last()) {
+        let interval = chrono::Duration::from_std(SAMPLE_INTERVAL).unwrap_or_default();
+        spikes.push(spike(from, last + interval, peak));
+    }
+    spikes
+}
+
+// ── PDF ─────────────────────────────────────────────────────────────
+
+/// Characters per line of the PDF (Courier 9 pt across A4 with margins).
+const PDF_LINE_CHARS: usize = 95;
+
+/// Lines per PDF page.
+const PDF_PAGE_LINES: usize = 62;
+
+/// The report as plain text lines, wrapped for the PDF.
+fn report_lines(report: &StreamReport) -> Vec<String> {
+    let time = |t: Option<DateTime<Utc>>| {
+        t.map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "-".into())
+    };
+    let mut lines = vec![
+        format!("Incident report: {}", report.stream_id),
+        String::new(),
+        format!("Sender:    {} ({})", report.sender_name, report.sender_id),
+        format!("Started:   {}", time(report.started_at)),
+        format!("Ended:     {}", time(report.ended_at)),
+    ];
+    if let Some(secs) = report.duration_s() {
+        lines.push(format!(
+            "Duration:  {}h {:02}m {:02}s",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        ));
+    }
+    if let Some(reason) = &report.end_reason {
+        let mut line = format!("End:       {reason}");
+        if let Some(error) = &report.error_message {
+            let _ = write!(line, " ({error})");
+        }
+        lines.push(line);
+    }
+    lines.push(format!(
+        "Sent:      {:.1} MB",
+        report.total_bytes as f64 / 1e6
+    ));
+    lines.push(format!("Generated: {}", time(Some(report.generated_at))));
+    lines.push(String::new());
+
+    for kind in [
+        IncidentKind::LinkFailover,
+        IncidentKind::LossSpike,
+        IncidentKind::Alert,
+        IncidentKind::LadderStep,
+        IncidentKind::Annotation,
+    ] {
+        lines.push(format!("{:<15}{}", kind.label(), report.count(kind)));
+    }
+    lines.push(String::new());
+    lines.push("Timeline".into());
+    if report.incidents.is_empty() {
+        lines.push("  No incidents.".into());
+    }
+    for incident in &report.incidents {
+        let mut line = format!(
+            "{}  {:<13} ",
+            incident.ts.format("%H:%M:%S"),
+            incident.kind.label()
+        );
+        if let Some(interface) = &incident.interface {
+            let _ = write!(line, "{interface}: ");
+        }
+        line.push_str(&incident.detail);
+        if let Some(secs) = incident.duration_s {
+            let _ = write!(line, " for {secs}s");
+        }
+        lines.extend(wrap(&line, PDF_LINE_CHARS));
+    }
+    lines
+}
+
+/// Hard-wrap `line` at `width` characters, indenting continuations.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = vec![chars.iter().take(width).collect::<String>()];
+    for chunk in chars.get(width..).unwrap_or_default().chunks(width - 4) {
+        out.push(format!("    {}", chunk.iter().collect::<String>()));
+    }
+    out
+}
+
+/// A minimal PDF 1.4 of monospaced text pages. Reports are a page or two
+/// of text; that doesn't call for a PDF library.
+fn render_pdf(lines: &[String]) -> Vec<u8> {
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(PDF_PAGE_LINES).collect()
+    };
+
+    // 1: catalog, 2: page tree, 3: font, then a page and its content
+    // stream per page.
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", 4 + 2 * i))
+        .collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".into(),
+    ];
+    for (i, page) in pages.iter().enumerate() {
+        let mut content = String::from("BT /F1 9 Tf 12 TL 40 802 Td\n");
+        for line in *page {
+            let _ = writeln!(content, "({}) Tj T*", pdf_string(line));
+        }
+        content.push_str("ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}\nendstream",
+            content.len()
+        ));
+    }
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        let _ = write!(out, "{} 0 obj\n{body}\nendobj\n", i + 1);
+    }
+    let xref = out.len();
+    let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(out, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        out,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    out.into_bytes()
+}
+
+/// Text as the body of a PDF literal string. The standard font covers
+/// ASCII; anything else prints as '?'.
+fn pdf_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_767_225_600 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn loss_spikes_coalesce_runs_above_the_threshold() {
+        let samples = [
+            (at(0), Some(0.5)),
+            (at(10), Some(7.0)),
+            (at(20), Some(12.5)),
+            (at(30), None),
+            (at(40), Some(1.0)),
+            (at(50), Some(6.0)),
+        ];
+        let spikes = loss_spikes(&samples);
+        assert_eq!(spikes.len(), 2);
+        assert_eq!(spikes[0].ts, at(10));
+        assert_eq!(spikes[0].duration_s, Some(20));
+        assert_eq!(spikes[0].detail, "loss peaked at 12.5%");
+        // Still running at the last sample: one sample interval long.
+        assert_eq!(spikes[1].ts, at(50));
+        assert_eq!(spikes[1].duration_s, Some(SAMPLE_INTERVAL.as_secs()));
+
+        assert!(loss_spikes(&[(at(0), Some(4.9))]).is_empty());
+    }
+
+    #[test]
+    fn pdf_cross_reference_points_at_every_object() {
+        let report = StreamReport {
+            stream_id: "str_1".into(),
+            sender_id: "snd_1".into(),
+            sender_name: "van (north)".into(),
+            started_at: Some(at(0)),
+            ended_at: Some(at(3725)),
+            end_reason: Some("control_plane_stop".into()),
+            error_message: None,
+            total_bytes: 2_500_000_000,
+            generated_at: at(3726),
+            incidents: (0..100)
+                .map(|i| ReportIncident {
+                    ts: at(i * 30),
+                    kind: IncidentKind::LinkFailover,
+                    interface: Some("wwan0".into()),
+                    detail: "link dropped to failover → retry".into(),
+                    duration_s: None,
+                })
+                .collect(),
+        };
+        let lines = report_lines(&report);
+        assert!(lines.contains(&"Duration:  1h 02m 05s".to_string()));
+        assert!(lines.contains(&format!("{:<15}100", "Link failover")));
+
+        let pdf = String::from_utf8(render_pdf(&lines)).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n") && pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Sender:    van \\(north\\) \\(snd_1\\)) Tj"));
+        assert!(pdf.contains("failover ? retry"));
+        assert!(
+            pdf.contains("/Count 2"),
+            "100 incidents spill onto a second page"
+        );
+
+        let startxref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let xref = &pdf[startxref..];
+        assert!(xref.starts_with("xref\n0 8\n"));
+        for (n, entry) in xref.lines().skip(3).take(7).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", n + 1)));
+        }
+    }
+
+    #[test]
+    fn long_lines_wrap_with_an_indent() {
+        let line = "x".repeat(200);
+        let wrapped = wrap(&line, PDF_LINE_CHARS);
+        assert_eq!(wrapped.len(), 3);
+        assert_eq!(wrapped[0].len(), PDF_LINE_CHARS);
+        assert!(wrapped[1].starts_with("    x"));
+        assert_eq!(
+            wrapped.iter().map(|l| l.trim_start().len()).sum::<usize>(),
+            200
+        );
+    }
+}
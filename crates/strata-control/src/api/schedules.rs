@@ -0,0 +1,583 @@
+//! Stream scheduling.
+//!
+//! GET    /api/schedules?from=&to=  — bookings overlapping the window
+//! POST   /api/schedules            — book a stream
+//! PUT    /api/schedules/{id}       — edit a booking that has not started
+//! DELETE /api/schedules/{id}       — cancel a booking that is not running
+//!
+//! [`tick`] runs every [`TICK_INTERVAL`]: it starts bookings whose time has
+//! come through the same path as `POST /api/senders/{id}/stream/start` and
+//! stops the stream a booking started once it ends. A stream stopped by hand
+//! before then is left alone.
+
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use strata_common::ids;
+use strata_protocol::SourceConfig;
+use strata_protocol::api::{Capability, ScheduleStreamRequest, StartStreamRequest};
+use strata_protocol::models::{ScheduleState, ScheduledStream, mark_conflicts};
+use strata_protocol::profiles;
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// Interval between [`tick`] passes — the worst-case start/stop lateness.
+pub const TICK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default list window when the caller gives none: a day back, four weeks
+/// ahead.
+const DEFAULT_LOOKBACK: chrono::Duration = chrono::Duration::days(1);
+const DEFAULT_LOOKAHEAD: chrono::Duration = chrono::Duration::weeks(4);
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_schedule).post(create_booking))
+        .route("/{id}", put(update_booking).delete(delete_booking))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ScheduleQuery {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+type BookingRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i32>,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
+const BOOKING_COLUMNS: &str = "b.id, b.sender_id, s.name, b.title, b.destination_id, b.device, \
+     b.resolution, b.framerate, b.starts_at, b.ends_at, b.state, b.stream_id, b.error";
+
+fn booking_from_row(
+    (
+        id,
+        sender_id,
+        sender_name,
+        title,
+        destination_id,
+        device,
+        resolution,
+        framerate,
+        starts_at,
+        ends_at,
+        state,
+        stream_id,
+        error,
+    ): BookingRow,
+) -> ScheduledStream {
+    ScheduledStream {
+        id,
+        sender_id,
+        sender_name,
+        title,
+        destination_id,
+        device,
+        resolution,
+        framerate: framerate.map(|f| f as u32),
+        starts_at,
+        ends_at,
+        state: state.parse().unwrap_or(ScheduleState::Failed),
+        stream_id,
+        error,
+        conflicts: Vec::new(),
+    }
+}
+
+/// `owner_id`'s bookings overlapping `[from, to)`, with conflicts marked.
+async fn load_window(
+    state: &AppState,
+    owner_id: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<ScheduledStream>, ApiError> {
+    let rows = sqlx::query_as::<_, BookingRow>(&format!(
+        "SELECT {BOOKING_COLUMNS} FROM scheduled_streams b JOIN senders s ON s.id = b.sender_id \
+         WHERE b.owner_id = $1 AND b.starts_at < $3 AND b.ends_at > $2 \
+         ORDER BY b.starts_at, b.id"
+    ))
+    .bind(owner_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let mut schedule: Vec<ScheduledStream> = rows.into_iter().map(booking_from_row).collect();
+    mark_conflicts(&mut schedule);
+    Ok(schedule)
+}
+
+/// One booking, with its conflicts marked.
+async fn load_booking(
+    state: &AppState,
+    owner_id: &str,
+    id: &str,
+) -> Result<ScheduledStream, ApiError> {
+    let (starts_at, ends_at): (DateTime<Utc>, DateTime<Utc>) = sqlx::query_as(
+        "SELECT starts_at, ends_at FROM scheduled_streams WHERE id = $1 AND owner_id = $2",
+    )
+    .bind(id)
+    .bind(owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("scheduled stream not found"))?;
+
+    load_window(state, owner_id, starts_at, ends_at)
+        .await?
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| ApiError::not_found("scheduled stream not found"))
+}
+
+async fn validate(
+    state: &AppState,
+    user: &AuthUser,
+    body: &ScheduleStreamRequest,
+) -> Result<(), ApiError> {
+    if body.ends_at <= body.starts_at {
+        return Err(ApiError::bad_request("ends_at must be after starts_at"));
+    }
+    if body.ends_at <= Utc::now() {
+        return Err(ApiError::bad_request("booking is already over"));
+    }
+    if let Some(ref res) = body.resolution
+        && !profiles::RESOLUTIONS.iter().any(|(r, _)| r == res)
+    {
+        return Err(ApiError::bad_request(format!(
+            "unsupported resolution: {res}"
+        )));
+    }
+    if let Some(fps) = body.framerate
+        && !profiles::FRAMERATES.contains(&fps)
+    {
+        return Err(ApiError::bad_request(format!(
+            "unsupported framerate: {fps}"
+        )));
+    }
+    super::senders::verify_ownership(state, user, &body.sender_id).await?;
+    if let Some(ref dest_id) = body.destination_id {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM destinations WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(dest_id)
+        .bind(&user.owner_id)
+        .fetch_one(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+        if !exists {
+            return Err(ApiError::not_found("destination not found"));
+        }
+    }
+    Ok(())
+}
+
+fn describe(body: &ScheduleStreamRequest) -> String {
+    format!(
+        "{}{} – {}",
+        body.title
+            .as_deref()
+            .map(|t| format!("{t}: "))
+            .unwrap_or_default(),
+        body.starts_at,
+        body.ends_at
+    )
+}
+
+// ── List ────────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/schedules",
+    tag = "schedules",
+    params(ScheduleQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<ScheduledStream>), ApiError)
+)]
+async fn list_schedule(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(q): Query<ScheduleQuery>,
+) -> Result<Json<Vec<ScheduledStream>>, ApiError> {
+    let now = Utc::now();
+    let from = q.from.unwrap_or(now - DEFAULT_LOOKBACK);
+    let to = q.to.unwrap_or(now + DEFAULT_LOOKAHEAD);
+    if to <= from {
+        return Err(ApiError::bad_request("to must be after from"));
+    }
+    Ok(Json(load_window(&state, &user.owner_id, from, to).await?))
+}
+
+// ── Create ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/schedules",
+    tag = "schedules",
+    summary = "Book a stream",
+    request_body = ScheduleStreamRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = ScheduledStream), ApiError)
+)]
+async fn create_booking(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<ScheduleStreamRequest>,
+) -> Result<(StatusCode, Json<ScheduledStream>), ApiError> {
+    user.require(Capability::ManageSchedule)?;
+    validate(&state, &user, &body).await?;
+
+    let id = ids::scheduled_stream_id();
+    sqlx::query(
+        "INSERT INTO scheduled_streams \
+         (id, owner_id, sender_id, title, destination_id, device, resolution, framerate, \
+          starts_at, ends_at, created_by) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&body.sender_id)
+    .bind(&body.title)
+    .bind(&body.destination_id)
+    .bind(&body.device)
+    .bind(&body.resolution)
+    .bind(body.framerate.map(|f| f as i32))
+    .bind(body.starts_at)
+    .bind(body.ends_at)
+    .bind(&user.user_id)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(
+        schedule_id = %id,
+        sender_id = %body.sender_id,
+        starts_at = %body.starts_at,
+        ends_at = %body.ends_at,
+        "stream scheduled"
+    );
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&body.sender_id),
+        "schedule.create",
+        Some(describe(&body)),
+    )
+    .await;
+
+    let booking = load_booking(&state, &user.owner_id, &id).await?;
+    Ok((StatusCode::CREATED, Json(booking)))
+}
+
+// ── Update ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    put,
+    path = "/api/schedules/{id}",
+    tag = "schedules",
+    summary = "Edit a booking that has not started",
+    params(("id" = String, Path, description = "Booking ID")),
+    request_body = ScheduleStreamRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = ScheduledStream), ApiError)
+)]
+async fn update_booking(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<ScheduleStreamRequest>,
+) -> Result<Json<ScheduledStream>, ApiError> {
+    user.require(Capability::ManageSchedule)?;
+    validate(&state, &user, &body).await?;
+
+    let updated = sqlx::query(
+        "UPDATE scheduled_streams SET sender_id = $3, title = $4, destination_id = $5, \
+         device = $6, resolution = $7, framerate = $8, starts_at = $9, ends_at = $10 \
+         WHERE id = $1 AND owner_id = $2 AND state = 'scheduled'",
+    )
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&body.sender_id)
+    .bind(&body.title)
+    .bind(&body.destination_id)
+    .bind(&body.device)
+    .bind(&body.resolution)
+    .bind(body.framerate.map(|f| f as i32))
+    .bind(body.starts_at)
+    .bind(body.ends_at)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .rows_affected();
+
+    if updated == 0 {
+        // Distinguish "gone" from "too late to edit".
+        load_booking(&state, &user.owner_id, &id).await?;
+        return Err(ApiError::conflict(
+            "only bookings that have not started can be edited",
+        ));
+    }
+
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&body.sender_id),
+        "schedule.update",
+        Some(describe(&body)),
+    )
+    .await;
+
+    Ok(Json(load_booking(&state, &user.owner_id, &id).await?))
+}
+
+// ── Delete ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    delete,
+    path = "/api/schedules/{id}",
+    tag = "schedules",
+    summary = "Cancel a booking that is not running",
+    params(("id" = String, Path, description = "Booking ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn delete_booking(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageSchedule)?;
+
+    let booking = load_booking(&state, &user.owner_id, &id).await?;
+    if booking.state == ScheduleState::Running {
+        return Err(ApiError::conflict(
+            "booking is running — stop its stream instead",
+        ));
+    }
+
+    sqlx::query("DELETE FROM scheduled_streams WHERE id = $1 AND owner_id = $2")
+        .bind(&id)
+        .bind(&user.owner_id)
+        .execute(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(schedule_id = %id, "scheduled stream cancelled");
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&booking.sender_id),
+        "schedule.cancel",
+        Some(id),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Scheduler ───────────────────────────────────────────────────────
+
+/// The start request for a booking: an explicit source, so the sender
+/// never falls back to the server's default test pattern by accident.
+fn start_request(
+    destination_id: Option<String>,
+    device: Option<String>,
+    resolution: Option<String>,
+    framerate: Option<i32>,
+) -> StartStreamRequest {
+    StartStreamRequest {
+        destination_id,
+        source: Some(SourceConfig {
+            mode: if device.is_some() { "v4l2" } else { "test" }.into(),
+            device,
+            uri: None,
+            resolution: Some(resolution.unwrap_or_else(|| "1920x1080".into())),
+            framerate: Some(framerate.map(|f| f as u32).unwrap_or(30)),
+            passthrough: None,
+            inputs: Vec::new(),
+        }),
+        encoder: None,
+    }
+}
+
+/// Start due bookings, stop finished ones, and fail bookings whose whole
+/// slot passed while the control plane was down.
+pub async fn tick(state: &AppState) {
+    start_due(state).await;
+    stop_finished(state).await;
+
+    if let Err(e) = sqlx::query(
+        "UPDATE scheduled_streams SET state = 'failed', error = 'missed: control plane was offline' \
+         WHERE state = 'scheduled' AND ends_at <= now()",
+    )
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(error = %e, "failed to expire missed bookings");
+    }
+}
+
+type DueRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i32>,
+);
+
+async fn start_due(state: &AppState) {
+    // Claim in the same statement that selects, so a booking is started
+    // at most once even if a tick overruns the next.
+    let due = match sqlx::query_as::<_, DueRow>(
+        "UPDATE scheduled_streams SET state = 'running' \
+         WHERE state = 'scheduled' AND starts_at <= now() AND ends_at > now() \
+         RETURNING id, owner_id, sender_id, destination_id, device, resolution, framerate",
+    )
+    .fetch_all(state.pool())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to claim due bookings");
+            return;
+        }
+    };
+
+    for (id, owner_id, sender_id, destination_id, device, resolution, framerate) in due {
+        let body = start_request(destination_id, device, resolution, framerate);
+        match super::streams::launch(state, &owner_id, &sender_id, body).await {
+            Ok(super::streams::Launched { stream_id, .. }) => {
+                tracing::info!(schedule_id = %id, stream_id = %stream_id, "scheduled stream started");
+                let _ = sqlx::query("UPDATE scheduled_streams SET stream_id = $2 WHERE id = $1")
+                    .bind(&id)
+                    .bind(&stream_id)
+                    .execute(state.pool())
+                    .await;
+                super::audit::record(
+                    state,
+                    &owner_id,
+                    None,
+                    Some(&sender_id),
+                    "stream.start",
+                    Some(format!("{stream_id} (scheduled {id})")),
+                )
+                .await;
+            }
+            Err(e) => {
+                tracing::warn!(schedule_id = %id, error = e.message(), "scheduled stream failed to start");
+                let _ = sqlx::query(
+                    "UPDATE scheduled_streams SET state = 'failed', error = $2 WHERE id = $1",
+                )
+                .bind(&id)
+                .bind(e.message())
+                .execute(state.pool())
+                .await;
+                super::audit::record(
+                    state,
+                    &owner_id,
+                    None,
+                    Some(&sender_id),
+                    "schedule.failed",
+                    Some(format!("{id}: {}", e.message())),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+async fn stop_finished(state: &AppState) {
+    let finished = match sqlx::query_as::<_, (String, String, String, Option<String>)>(
+        "UPDATE scheduled_streams SET state = 'completed' \
+         WHERE state = 'running' AND ends_at <= now() \
+         RETURNING id, owner_id, sender_id, stream_id",
+    )
+    .fetch_all(state.pool())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to claim finished bookings");
+            return;
+        }
+    };
+
+    for (id, owner_id, sender_id, stream_id) in finished {
+        // Only stop the stream this booking started — an operator may have
+        // stopped it and started another by hand since.
+        let active = super::streams::active_stream(state, &owner_id, &sender_id)
+            .await
+            .ok()
+            .flatten();
+        let Some(stream_id) = stream_id.filter(|s| active.as_ref() == Some(s)) else {
+            continue;
+        };
+        match super::streams::halt(state, &owner_id, &sender_id, &stream_id, "schedule_end").await {
+            Ok(()) => {
+                tracing::info!(schedule_id = %id, stream_id = %stream_id, "scheduled stream stopped");
+                super::audit::record(
+                    state,
+                    &owner_id,
+                    None,
+                    Some(&sender_id),
+                    "stream.stop",
+                    Some(format!("{stream_id} (scheduled {id})")),
+                )
+                .await;
+            }
+            Err(e) => {
+                tracing::warn!(schedule_id = %id, error = e.message(), "failed to stop scheduled stream");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bookings_always_send_an_explicit_source() {
+        let req = start_request(None, None, None, None);
+        let source = req.source.unwrap();
+        assert_eq!(source.mode, "test");
+        assert_eq!(source.resolution.as_deref(), Some("1920x1080"));
+        assert_eq!(source.framerate, Some(30));
+
+        let req = start_request(
+            Some("dst_1".into()),
+            Some("/dev/video0".into()),
+            Some("1280x720".into()),
+            Some(60),
+        );
+        let source = req.source.unwrap();
+        assert_eq!(source.mode, "v4l2");
+        assert_eq!(source.device.as_deref(), Some("/dev/video0"));
+        assert_eq!(source.framerate, Some(60));
+        assert_eq!(req.destination_id.as_deref(), Some("dst_1"));
+    }
+}
@@ -2,15 +2,21 @@
 //!
 //! GET    /api/senders                            — list senders
 //! POST   /api/senders                            — create sender
+//! POST   /api/senders/ingest                     — create SRT/RTMP sender (see `ingest`)
 //! GET    /api/senders/:id                         — get sender details
 //! DELETE /api/senders/:id                         — decommission sender
 //! GET    /api/senders/:id/status                  — live hardware status
+//! GET    /api/senders/:id/live                    — status + running stream snapshot
 //! POST   /api/senders/:id/unenroll                — unenroll sender
 //! POST   /api/senders/:id/interfaces/:name/enable — enable interface
 //! POST   /api/senders/:id/interfaces/:name/disable — disable interface
 //! POST   /api/senders/:id/config                  — set receiver config
+//! GET    /api/senders/:id/portal-auth             — is the local portal PIN-gated?
+//! PUT    /api/senders/:id/portal-auth             — set/clear the portal PIN
 //! POST   /api/senders/:id/test                    — run connectivity test
 //! POST   /api/senders/:id/interfaces/scan         — scan for new interfaces
+//! GET    /api/senders/:id/tls                     — certificate the agent is serving
+//! POST   /api/senders/:id/tls/renew               — re-issue it (see `certificates`)
 
 use std::time::Duration;
 
@@ -23,15 +29,19 @@ use uuid::Uuid;
 
 use strata_common::ids;
 use strata_protocol::api::{
-    CreateSenderRequest, CreateSenderResponse, SenderDetail, SenderFullStatus, SenderSummary,
-    UnenrollResponse,
+    Capability, CertificateSummary, CreateSenderRequest, CreateSenderResponse, JitterBufferRequest,
+    LockBandRequest, NetworkToolRequest, PcapRequest, PortalAuthStatus, PowerRequest, SenderDetail,
+    SenderFullStatus, SenderLiveSnapshot, SenderSummary, SetApnRequest, SetConfigRequest,
+    SetPortalAuthRequest, SetPriorityRequest, SetStreamDestinationsRequest, UnenrollResponse,
 };
+use strata_protocol::models::IngestProtocol;
 use strata_protocol::{
     ConfigExportPayload, ConfigImportPayload, ConfigSetPayload, ConfigUpdatePayload,
     ControlMessage, Envelope, FilesListPayload, InterfaceCommandPayload, InterfacesScanPayload,
     JitterBufferPayload, LogsRequestPayload, NetworkToolPayload, PcapCapturePayload,
-    PowerCommandPayload, SourceSwitchPayload, StreamDestinationsPayload, TestRunPayload,
-    TlsRenewPayload, TlsStatusPayload, UpdatesCheckPayload, UpdatesInstallPayload,
+    PortalAuthPayload, PowerCommandPayload, SourceSelectPayload, SourceSwitchPayload,
+    StreamDestinationsPayload, TestRunPayload, TlsStatusPayload, UpdatesCheckPayload,
+    UpdatesInstallPayload,
 };
 
 use crate::api::auth::ApiError;
@@ -42,10 +52,19 @@ use super::auth_extractor::AuthUser;
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_senders).post(create_sender))
+        .route(
+            "/ingest",
+            axum::routing::post(super::ingest::create_ingest_sender),
+        )
         .route("/{id}", get(get_sender).delete(delete_sender))
         .route("/{id}/status", get(get_sender_status))
+        .route("/{id}/live", get(get_sender_live))
         .route("/{id}/unenroll", axum::routing::post(unenroll_sender))
         .route("/{id}/config", axum::routing::post(set_sender_config))
+        .route(
+            "/{id}/portal-auth",
+            get(get_portal_auth).put(set_portal_auth),
+        )
         .route("/{id}/test", axum::routing::post(run_sender_test))
         .route(
             "/{id}/interfaces/scan",
@@ -73,6 +92,7 @@ pub fn router() -> Router<AppState> {
             axum::routing::post(update_stream_config),
         )
         .route("/{id}/source", axum::routing::post(switch_source))
+        .route("/{id}/source/select", axum::routing::post(select_source))
         .route("/{id}/files", get(list_sender_files))
         // Diagnostics
         .route(
@@ -101,6 +121,8 @@ pub fn router() -> Router<AppState> {
             "/{id}/stream/jitter_buffer",
             axum::routing::post(set_jitter_buffer),
         )
+        // Metrics history
+        .route("/{id}/metrics", get(super::history::get_metrics))
         // Alerting
         .route("/{id}/alerts", get(get_alert_rules).post(set_alert_rule))
         .route(
@@ -111,22 +133,35 @@ pub fn router() -> Router<AppState> {
 
 // ── List Senders ────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders",
+    tag = "senders",
+    summary = "List senders",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<SenderSummary>), ApiError)
+)]
 async fn list_senders(
     State(state): State<AppState>,
     user: AuthUser,
 ) -> Result<Json<Vec<SenderSummary>>, ApiError> {
-    let rows = sqlx::query_as::<_, (String, Option<String>, Option<String>, Option<chrono::DateTime<chrono::Utc>>, chrono::DateTime<chrono::Utc>)>(
-        "SELECT id, name, hostname, last_seen_at, created_at FROM senders WHERE owner_id = $1 ORDER BY created_at DESC",
+    let rows = sqlx::query_as::<_, (String, Option<String>, Option<String>, Option<chrono::DateTime<chrono::Utc>>, chrono::DateTime<chrono::Utc>, Option<String>)>(
+        "SELECT id, name, hostname, last_seen_at, created_at, ingest_protocol FROM senders WHERE owner_id = $1 ORDER BY created_at DESC",
     )
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_all(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
+    let ready = super::ingest::ready_protocols(&state, &user.owner_id).await;
     let senders = rows
         .into_iter()
-        .map(|(id, name, hostname, last_seen_at, created_at)| {
-            let online = state.agents().contains_key(&id);
+        .map(|(id, name, hostname, last_seen_at, created_at, ingest)| {
+            let ingest = ingest.and_then(|p| p.parse::<IngestProtocol>().ok());
+            let online = match ingest {
+                Some(protocol) => ready.contains(&protocol),
+                None => state.agents().contains_key(&id),
+            };
             SenderSummary {
                 id,
                 name,
@@ -134,6 +169,7 @@ async fn list_senders(
                 online,
                 last_seen_at,
                 created_at,
+                ingest,
             }
         })
         .collect();
@@ -143,37 +179,46 @@ async fn list_senders(
 
 // ── Create Sender ───────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders",
+    tag = "senders",
+    summary = "Create sender",
+    request_body = CreateSenderRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateSenderResponse), ApiError)
+)]
 async fn create_sender(
     State(state): State<AppState>,
     user: AuthUser,
     Json(body): Json<CreateSenderRequest>,
 ) -> Result<(StatusCode, Json<CreateSenderResponse>), ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     let sender_id = ids::sender_id();
-    let enrollment_token = ids::enrollment_token();
-
-    // Normalize and hash the enrollment token before storage
-    let normalized_token = strata_common::ids::normalize_enrollment_token(&enrollment_token);
-    let token_hash = strata_common::auth::hash_password(&normalized_token)
-        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let (token_hash, enrollment_token) = enrollment_secret(&sender_id)?;
 
     sqlx::query(
         "INSERT INTO senders (id, owner_id, name, enrollment_token) VALUES ($1, $2, $3, $4)",
     )
     .bind(&sender_id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .bind(&body.name)
     .bind(&token_hash)
     .execute(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
-    tracing::info!(sender_id = %sender_id, owner = %user.user_id, "sender created");
+    tracing::info!(sender_id = %sender_id, owner = %user.owner_id, "sender created");
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(sender_id.as_str()),
+        "sender.create",
+        body.name.clone(),
+    )
+    .await;
 
-    // Composite <id>.<secret> token: the id half lets enrollment verify one
-    // argon2 hash instead of scanning every device (E4).
-    let enrollment_token = ids::composite_enrollment_token(&sender_id, &enrollment_token);
     Ok((
         StatusCode::CREATED,
         Json(CreateSenderResponse {
@@ -183,25 +228,56 @@ async fn create_sender(
     ))
 }
 
+/// A fresh enrollment token for `sender_id`: the hash to store, and the
+/// token to hand out.
+pub(crate) fn enrollment_secret(sender_id: &str) -> Result<(String, String), ApiError> {
+    let enrollment_token = ids::enrollment_token();
+
+    // Normalize and hash the enrollment token before storage
+    let normalized_token = strata_common::ids::normalize_enrollment_token(&enrollment_token);
+    let token_hash = strata_common::auth::hash_password(&normalized_token)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    // Composite <id>.<secret> token: the id half lets enrollment verify one
+    // argon2 hash instead of scanning every device (E4).
+    let enrollment_token = ids::composite_enrollment_token(sender_id, &enrollment_token);
+    Ok((token_hash, enrollment_token))
+}
+
 // ── Get Sender ──────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}",
+    tag = "senders",
+    summary = "Get sender details",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = SenderDetail), ApiError)
+)]
 async fn get_sender(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<SenderDetail>, ApiError> {
-    let row = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, bool, Option<chrono::DateTime<chrono::Utc>>, chrono::DateTime<chrono::Utc>)>(
-        "SELECT id, owner_id, name, hostname, enrolled, last_seen_at, created_at FROM senders WHERE id = $1 AND owner_id = $2",
+    let row = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, bool, Option<chrono::DateTime<chrono::Utc>>, chrono::DateTime<chrono::Utc>, Option<String>)>(
+        "SELECT id, owner_id, name, hostname, enrolled, last_seen_at, created_at, ingest_protocol FROM senders WHERE id = $1 AND owner_id = $2",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_optional(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?
     .ok_or_else(|| ApiError::not_found("sender not found"))?;
 
-    let (id, owner_id, name, hostname, enrolled, last_seen_at, created_at) = row;
-    let online = state.agents().contains_key(&id);
+    let (id, owner_id, name, hostname, enrolled, last_seen_at, created_at, ingest) = row;
+    let ingest = ingest.and_then(|p| p.parse::<IngestProtocol>().ok());
+    let online = match ingest {
+        Some(protocol) => super::ingest::ready_protocols(&state, &owner_id)
+            .await
+            .contains(&protocol),
+        None => state.agents().contains_key(&id),
+    };
 
     Ok(Json(SenderDetail {
         id,
@@ -212,21 +288,31 @@ async fn get_sender(
         online,
         last_seen_at,
         created_at,
+        ingest,
     }))
 }
 
 // ── Delete Sender ───────────────────────────────────────────────────
 
+#[utoipa::path(
+    delete,
+    path = "/api/senders/{id}",
+    tag = "senders",
+    summary = "Decommission sender",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn delete_sender(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     let result = sqlx::query("DELETE FROM senders WHERE id = $1 AND owner_id = $2")
         .bind(&id)
-        .bind(&user.user_id)
+        .bind(&user.owner_id)
         .execute(state.pool())
         .await
         .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -237,14 +323,25 @@ async fn delete_sender(
 
     // Disconnect agent if connected
     state.agents().remove(&id);
+    state.alert_tracking().remove(&id);
 
     tracing::info!(sender_id = %id, "sender deleted");
+    super::audit::record_user(&state, &user, Some(&id), "sender.delete", None).await;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 // ── Get Sender Status ───────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/status",
+    tag = "senders",
+    summary = "Live hardware status",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = SenderFullStatus), ApiError)
+)]
 async fn get_sender_status(
     State(state): State<AppState>,
     user: AuthUser,
@@ -255,7 +352,7 @@ async fn get_sender_status(
         "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_one(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -264,9 +361,13 @@ async fn get_sender_status(
         return Err(ApiError::not_found("sender not found"));
     }
 
-    let online = state.agents().contains_key(&id);
+    Ok(Json(full_status(&state, id)))
+}
 
-    // Include the cached device.status heartbeat if we have one
+/// The cached `device.status` heartbeat, if we have one, plus the online
+/// flag.
+fn full_status(state: &AppState, id: String) -> SenderFullStatus {
+    let online = state.agents().contains_key(&id);
     let status = state.device_status().get(&id).map(|v| v.clone());
     let mut full = match status {
         Some(s) => SenderFullStatus {
@@ -283,25 +384,73 @@ async fn get_sender_status(
     };
     full.sender_id = Some(id);
     full.online = Some(online);
+    full
+}
 
-    Ok(Json(full))
+// ── Live Snapshot ───────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/live",
+    tag = "senders",
+    summary = "Status + running stream snapshot",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = SenderLiveSnapshot), ApiError)
+)]
+async fn get_sender_live(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<SenderLiveSnapshot>, ApiError> {
+    verify_ownership(&state, &user, &id).await?;
+
+    let live = state.live().get(&id);
+    let receiver_stats = live.as_ref().and_then(|l| {
+        state
+            .receiver_stream_stats()
+            .get(l.stream_id())
+            .map(|r| r.clone())
+    });
+    Ok(Json(match live {
+        Some(live) => SenderLiveSnapshot {
+            status: full_status(&state, id),
+            stream_id: Some(live.stats.stream_id.clone()),
+            stats: Some(live.stats),
+            links: live.links.into_values().collect(),
+            receiver_stats,
+        },
+        None => SenderLiveSnapshot {
+            status: full_status(&state, id),
+            ..Default::default()
+        },
+    }))
 }
 
 // ── Unenroll Sender ─────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/unenroll",
+    tag = "senders",
+    summary = "Unenroll sender",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = UnenrollResponse), ApiError)
+)]
 async fn unenroll_sender(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<UnenrollResponse>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     // Verify ownership
     let exists = sqlx::query_scalar::<_, bool>(
         "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_one(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -329,7 +478,8 @@ async fn unenroll_sender(
 
     // Reset enrollment state
     sqlx::query(
-        "UPDATE senders SET enrolled = FALSE, enrollment_token = $1, hostname = NULL, device_public_key = NULL WHERE id = $2",
+        "UPDATE senders SET enrolled = FALSE, enrollment_token = $1, enrollment_token_legacy = FALSE, \
+         hostname = NULL, device_public_key = NULL WHERE id = $2",
     )
     .bind(&token_hash)
     .bind(&id)
@@ -341,6 +491,7 @@ async fn unenroll_sender(
     state.agents().remove(&id);
 
     tracing::info!(sender_id = %id, "sender unenrolled, new token issued");
+    super::audit::record_user(&state, &user, Some(&id), "sender.unenroll", None).await;
 
     let new_token = strata_common::ids::composite_enrollment_token(&id, &new_token);
     Ok(Json(UnenrollResponse {
@@ -352,6 +503,15 @@ async fn unenroll_sender(
 
 // ── Interface Management (proxied to agent) ─────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/{name}/enable",
+    tag = "senders",
+    summary = "Enable interface",
+    params(("id" = String, Path, description = "Sender ID"), ("name" = String, Path, description = "Interface name")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn enable_interface(
     State(state): State<AppState>,
     user: AuthUser,
@@ -368,6 +528,15 @@ async fn enable_interface(
     .await
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/{name}/disable",
+    tag = "senders",
+    summary = "Disable interface",
+    params(("id" = String, Path, description = "Sender ID"), ("name" = String, Path, description = "Interface name")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn disable_interface(
     State(state): State<AppState>,
     user: AuthUser,
@@ -384,11 +553,15 @@ async fn disable_interface(
     .await
 }
 
-#[derive(Debug, Deserialize)]
-pub struct LockBandRequest {
-    pub band: Option<String>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/{name}/lock_band",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), ("name" = String, Path, description = "Interface name")),
+    request_body = LockBandRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn lock_band(
     State(state): State<AppState>,
     user: AuthUser,
@@ -409,11 +582,15 @@ async fn lock_band(
     .await
 }
 
-#[derive(Debug, Deserialize)]
-pub struct SetPriorityRequest {
-    pub priority: u32,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/{name}/priority",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), ("name" = String, Path, description = "Interface name")),
+    request_body = SetPriorityRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn set_priority(
     State(state): State<AppState>,
     user: AuthUser,
@@ -434,13 +611,15 @@ async fn set_priority(
     .await
 }
 
-#[derive(Debug, Deserialize)]
-pub struct SetApnRequest {
-    pub apn: Option<String>,
-    pub sim_pin: Option<String>,
-    pub roaming: Option<bool>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/{name}/apn",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), ("name" = String, Path, description = "Interface name")),
+    request_body = SetApnRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn set_apn(
     State(state): State<AppState>,
     user: AuthUser,
@@ -480,14 +659,14 @@ async fn interface_command(
     action: &str,
     opts: InterfaceCommandOptions,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     // Verify ownership
     let exists = sqlx::query_scalar::<_, bool>(
         "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
     )
     .bind(sender_id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_one(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -496,21 +675,11 @@ async fn interface_command(
         return Err(ApiError::not_found("sender not found"));
     }
 
-    // Find the connected agent and send the command
-    let agent = state
-        .agents()
-        .get(sender_id)
-        .ok_or_else(|| ApiError::bad_request("sender is not connected"))?;
-
     // Await the agent's ack — returning {ok:true} on mere enqueue let the
     // dashboard report success for commands the device rejected or never
     // applied (UX_TRUST_AUDIT U5/U12).
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
     let payload = InterfaceCommandPayload {
-        request_id: Some(request_id.clone()),
+        request_id: Some(Uuid::now_v7().to_string()),
         interface: iface_name.to_string(),
         action: action.to_string(),
         band: opts.band,
@@ -519,192 +688,233 @@ async fn interface_command(
         sim_pin: opts.sim_pin,
         roaming: opts.roaming,
     };
-    let envelope = Envelope::from_message(&ControlMessage::InterfaceCommand(payload))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
-
-    agent
-        .tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send command to agent"))?;
-    drop(agent);
-
     tracing::info!(
         sender_id = %sender_id,
         interface = %iface_name,
         action = %action,
-        "interface command sent to agent"
+        "sending interface command to agent"
     );
-
-    match tokio::time::timeout(Duration::from_secs(10), rx).await {
-        Ok(Ok(value)) => {
-            let success = value
-                .get("success")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            if success {
-                Ok(Json(serde_json::json!({
-                    "ok": true,
-                    "interface": iface_name,
-                    "action": action,
-                })))
-            } else {
-                let err = value
-                    .get("error")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("agent rejected the command");
-                Err(ApiError::bad_request(err))
-            }
-        }
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("agent did not acknowledge the command"))
-        }
+    let Json(value) = proxy_to_agent(
+        state,
+        sender_id,
+        &ControlMessage::InterfaceCommand(payload),
+        10,
+    )
+    .await?;
+    if value.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        Ok(Json(serde_json::json!({
+            "ok": true,
+            "interface": iface_name,
+            "action": action,
+        })))
+    } else {
+        Err(ApiError::rejected_by_agent(
+            &value,
+            "agent rejected the command",
+        ))
     }
 }
 
 // ── Config Set (proxied to agent with request-response) ─────────────
 
-#[derive(Debug, Deserialize)]
-pub struct SetConfigRequest {
-    pub receiver_url: Option<String>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/config",
+    tag = "senders",
+    summary = "Set receiver config",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = SetConfigRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn set_sender_config(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<SetConfigRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     verify_ownership(&state, &user, &id).await?;
 
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
-    let agent = state
-        .agents()
-        .get(&id)
-        .ok_or_else(|| ApiError::bad_request("sender is not connected"))?;
-
     let payload = ConfigSetPayload {
-        request_id: request_id.clone(),
+        request_id: Uuid::now_v7().to_string(),
         receiver_url: body.receiver_url,
     };
-    let envelope = Envelope::from_message(&ControlMessage::ConfigSet(payload))
+    proxy_to_agent(&state, &id, &ControlMessage::ConfigSet(payload), 10).await
+}
+
+// ── Portal PIN ──────────────────────────────────────────────────────
+
+/// Shortest portal PIN accepted — long enough that the agent's lockout
+/// (a handful of tries, then a pause) makes guessing impractical.
+const MIN_PORTAL_PIN_LEN: usize = 6;
+const MAX_PORTAL_PIN_LEN: usize = 64;
+
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/portal-auth",
+    tag = "senders",
+    summary = "Is the local portal PIN-gated?",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = PortalAuthStatus), ApiError)
+)]
+async fn get_portal_auth(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<PortalAuthStatus>, ApiError> {
+    user.require(Capability::ManageSenders)?;
+    verify_ownership(&state, &user, &id).await?;
+
+    let hash = portal_pin_hash(&state, &id)
+        .await
         .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(PortalAuthStatus {
+        enabled: hash.is_some(),
+        delivered: None,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/senders/{id}/portal-auth",
+    tag = "senders",
+    summary = "Set/clear the portal PIN",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = SetPortalAuthRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = PortalAuthStatus), ApiError)
+)]
+async fn set_portal_auth(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<SetPortalAuthRequest>,
+) -> Result<Json<PortalAuthStatus>, ApiError> {
+    user.require(Capability::ManageSenders)?;
+    verify_ownership(&state, &user, &id).await?;
+
+    let pin = body.pin.filter(|p| !p.is_empty());
+    let hash = match &pin {
+        Some(pin) => {
+            let len = pin.chars().count();
+            if !(MIN_PORTAL_PIN_LEN..=MAX_PORTAL_PIN_LEN).contains(&len) {
+                return Err(ApiError::bad_request(format!(
+                    "portal PIN must be {MIN_PORTAL_PIN_LEN}-{MAX_PORTAL_PIN_LEN} characters"
+                )));
+            }
+            Some(
+                strata_common::auth::hash_password(pin)
+                    .map_err(|e| ApiError::internal(e.to_string()))?,
+            )
+        }
+        None => None,
+    };
 
-    agent
-        .tx
-        .send(json)
+    sqlx::query("UPDATE senders SET portal_pin_hash = $2 WHERE id = $1")
+        .bind(&id)
+        .bind(&hash)
+        .execute(state.pool())
         .await
-        .map_err(|_| ApiError::internal("failed to send command to agent"))?;
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let delivered = push_portal_auth(&state, &id).await;
+    let action = if hash.is_some() {
+        "sender.portal_auth.set"
+    } else {
+        "sender.portal_auth.clear"
+    };
+    super::audit::record_user(&state, &user, Some(&id), action, None).await;
+
+    Ok(Json(PortalAuthStatus {
+        enabled: hash.is_some(),
+        delivered: Some(delivered),
+    }))
+}
 
-    drop(agent); // Release DashMap ref before awaiting
+async fn portal_pin_hash(state: &AppState, sender_id: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT portal_pin_hash FROM senders WHERE id = $1")
+        .bind(sender_id)
+        .fetch_optional(state.pool())
+        .await
+        .map(Option::flatten)
+}
 
-    match tokio::time::timeout(Duration::from_secs(10), rx).await {
-        Ok(Ok(value)) => Ok(Json(value)),
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("request timed out"))
+/// Push the portal PIN setting to a connected agent. Returns whether it
+/// was sent; offline agents get it on their next connect.
+pub(crate) async fn push_portal_auth(state: &AppState, sender_id: &str) -> bool {
+    let Some(tx) = state.agents().get(sender_id).map(|a| a.tx.clone()) else {
+        return false;
+    };
+    let pin_hash = match portal_pin_hash(state, sender_id).await {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::warn!(sender_id = %sender_id, error = %e, "failed to load portal auth");
+            return false;
+        }
+    };
+    let msg = ControlMessage::PortalAuth(PortalAuthPayload { pin_hash });
+    match Envelope::from_message(&msg).and_then(|e| serde_json::to_string(&e)) {
+        Ok(json) => tx.send(json).await.is_ok(),
+        Err(e) => {
+            tracing::error!(error = %e, "failed to serialize portal.auth");
+            false
         }
     }
 }
 
 // ── Connectivity Test (proxied to agent) ────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/test",
+    tag = "senders",
+    summary = "Run connectivity test",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn run_sender_test(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     verify_ownership(&state, &user, &id).await?;
 
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
-    let agent = state
-        .agents()
-        .get(&id)
-        .ok_or_else(|| ApiError::bad_request("sender is not connected"))?;
-
     let payload = TestRunPayload {
-        request_id: request_id.clone(),
+        request_id: Uuid::now_v7().to_string(),
     };
-    let envelope = Envelope::from_message(&ControlMessage::TestRun(payload))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
-
-    agent
-        .tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send command to agent"))?;
-
-    drop(agent);
-
-    match tokio::time::timeout(Duration::from_secs(15), rx).await {
-        Ok(Ok(value)) => Ok(Json(value)),
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("connectivity test timed out"))
-        }
-    }
+    proxy_to_agent(&state, &id, &ControlMessage::TestRun(payload), 15).await
 }
 
 // ── Interface Scan (proxied to agent) ───────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/interfaces/scan",
+    tag = "senders",
+    summary = "Scan for new interfaces",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn scan_interfaces(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     verify_ownership(&state, &user, &id).await?;
 
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
-    let agent = state
-        .agents()
-        .get(&id)
-        .ok_or_else(|| ApiError::bad_request("sender is not connected"))?;
-
     let payload = InterfacesScanPayload {
-        request_id: request_id.clone(),
+        request_id: Uuid::now_v7().to_string(),
     };
-    let envelope = Envelope::from_message(&ControlMessage::InterfacesScan(payload))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
-
-    agent
-        .tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send command to agent"))?;
-
-    drop(agent);
-
-    match tokio::time::timeout(Duration::from_secs(10), rx).await {
-        Ok(Ok(value)) => Ok(Json(value)),
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("interface scan timed out"))
-        }
-    }
+    proxy_to_agent(&state, &id, &ControlMessage::InterfacesScan(payload), 10).await
 }
 
 // ── Hot Stream Config Update ────────────────────────────────────────
@@ -713,195 +923,156 @@ async fn scan_interfaces(
 ///
 /// POST /api/senders/:id/stream/config
 /// Body: { "encoder": { "bitrate_kbps": 2000 }, "scheduler": { ... } }
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/stream/config",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = ConfigUpdatePayload,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK"), ApiError)
+)]
 async fn update_stream_config(
     State(state): State<AppState>,
     user: AuthUser,
     Path(sender_id): Path<String>,
     Json(body): Json<ConfigUpdatePayload>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
 
     verify_ownership(&state, &user, &sender_id).await?;
 
-    let agent = state
-        .agents()
-        .get(&sender_id)
-        .ok_or_else(|| ApiError::bad_request("sender is offline"))?;
-    let agent_tx = agent.tx.clone();
-    drop(agent);
-
-    // Build a request_id so we can wait for the response
-    let request_id = Uuid::now_v7().to_string();
-    let (resp_tx, resp_rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), resp_tx);
-
     let mut body = body;
-    body.request_id = Some(request_id.clone());
-    let envelope = Envelope::from_message(&ControlMessage::ConfigUpdate(body))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).unwrap();
-
-    agent_tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send to agent"))?;
-
-    // Wait for response with timeout
-    match tokio::time::timeout(Duration::from_secs(10), resp_rx).await {
-        Ok(Ok(resp)) => {
-            let success = resp
-                .get("success")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            if success {
-                Ok(StatusCode::OK)
-            } else {
-                let err = resp
-                    .get("error")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown error");
-                Err(ApiError::internal(err.to_string()))
-            }
-        }
-        Ok(Err(_)) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("agent response channel closed"))
-        }
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("config update timed out"))
-        }
+    body.request_id = Some(Uuid::now_v7().to_string());
+    let Json(resp) =
+        proxy_to_agent(&state, &sender_id, &ControlMessage::ConfigUpdate(body), 10).await?;
+    if resp.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        Ok(StatusCode::OK)
+    } else {
+        Err(ApiError::rejected_by_agent(&resp, "unknown error"))
     }
 }
 
 // ── Source Switch ────────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/source",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = SourceSwitchPayload,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn switch_source(
     State(state): State<AppState>,
     user: AuthUser,
     Path(sender_id): Path<String>,
     Json(mut body): Json<SourceSwitchPayload>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
 
     verify_ownership(&state, &user, &sender_id).await?;
 
-    let agent = state
-        .agents()
-        .get(&sender_id)
-        .ok_or_else(|| ApiError::bad_request("sender is offline"))?;
-    let agent_tx = agent.tx.clone();
-    drop(agent);
-
     // Await the agent's ack — a fire-and-forget 200 here let the dashboard
     // show "Source switched successfully" seconds before the pipeline
     // crashed on a bad device (UX_TRUST_AUDIT U12, 2026-07-05 field crash).
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-    body.request_id = Some(request_id.clone());
+    body.request_id = Some(Uuid::now_v7().to_string());
+    let Json(value) =
+        proxy_to_agent(&state, &sender_id, &ControlMessage::SourceSwitch(body), 10).await?;
+    if value.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        Ok(Json(value))
+    } else {
+        Err(ApiError::rejected_by_agent(
+            &value,
+            "agent rejected the source switch",
+        ))
+    }
+}
 
-    let envelope = Envelope::from_message(&ControlMessage::SourceSwitch(body))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
+/// Cut a multi-camera stream to another of its configured inputs.
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/source/select",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = SourceSelectPayload,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
+async fn select_source(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(sender_id): Path<String>,
+    Json(mut body): Json<SourceSelectPayload>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    user.require(Capability::ControlStreams)?;
+    verify_ownership(&state, &user, &sender_id).await?;
 
-    agent_tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send to agent"))?;
-
-    match tokio::time::timeout(Duration::from_secs(10), rx).await {
-        Ok(Ok(value)) => {
-            let success = value
-                .get("success")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            if success {
-                Ok(Json(value))
-            } else {
-                let err = value
-                    .get("error")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("agent rejected the source switch");
-                Err(ApiError::bad_request(err))
-            }
-        }
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal(
-                "agent did not acknowledge the source switch",
-            ))
-        }
+    body.request_id = Some(Uuid::now_v7().to_string());
+    let Json(value) =
+        proxy_to_agent(&state, &sender_id, &ControlMessage::SourceSelect(body), 10).await?;
+    if value.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        Ok(Json(value))
+    } else {
+        Err(ApiError::rejected_by_agent(
+            &value,
+            "agent rejected the input switch",
+        ))
     }
 }
 
 // ── File Browser ────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 struct FilesQuery {
     path: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/files",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), FilesQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn list_sender_files(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Query(q): Query<FilesQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
 
     verify_ownership(&state, &user, &id).await?;
 
-    let request_id = Uuid::now_v7().to_string();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
-    let agent = state
-        .agents()
-        .get(&id)
-        .ok_or_else(|| ApiError::bad_request("sender is offline"))?;
-
     let payload = FilesListPayload {
-        request_id: request_id.clone(),
+        request_id: Uuid::now_v7().to_string(),
         path: q.path,
     };
-    let envelope = Envelope::from_message(&ControlMessage::FilesList(payload))
-        .map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
-
-    agent
-        .tx
-        .send(json)
-        .await
-        .map_err(|_| ApiError::internal("failed to send to agent"))?;
-    drop(agent);
-
-    match tokio::time::timeout(Duration::from_secs(10), rx).await {
-        Ok(Ok(value)) => Ok(Json(value)),
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("request timed out"))
-        }
-    }
+    proxy_to_agent(&state, &id, &ControlMessage::FilesList(payload), 10).await
 }
 
 // ── Diagnostics: Network Tool ───────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-struct NetworkToolRequest {
-    tool: String,
-    target: Option<String>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/diagnostics/network",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = NetworkToolRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn run_network_tool(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<NetworkToolRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::TroubleshootSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -915,18 +1086,22 @@ async fn run_network_tool(
 
 // ── Diagnostics: PCAP ───────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-struct PcapRequest {
-    duration_secs: u32,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/diagnostics/pcap",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = PcapRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn capture_pcap(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<PcapRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -939,19 +1114,28 @@ async fn capture_pcap(
 
 // ── Diagnostics: Logs ───────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 struct LogsQuery {
     service: Option<String>,
     lines: Option<u32>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/logs",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), LogsQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn get_logs(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Query(q): Query<LogsQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::TroubleshootSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -965,36 +1149,51 @@ async fn get_logs(
 
 // ── Power ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-struct PowerRequest {
-    action: String,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/power",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = PowerRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn power_command(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<PowerRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
     let payload = PowerCommandPayload {
         request_id,
-        action: body.action,
+        action: body.action.clone(),
     };
-    proxy_to_agent(&state, &id, &ControlMessage::PowerCommand(payload), 10).await
+    let resp = proxy_to_agent(&state, &id, &ControlMessage::PowerCommand(payload), 10).await?;
+    super::audit::record_user(&state, &user, Some(&id), "sender.power", Some(body.action)).await;
+    Ok(resp)
 }
 
 // ── TLS ─────────────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/tls",
+    tag = "senders",
+    summary = "Certificate the agent is serving",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn get_tls_status(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::TroubleshootSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1002,27 +1201,44 @@ async fn get_tls_status(
     proxy_to_agent(&state, &id, &ControlMessage::TlsStatus(payload), 10).await
 }
 
+/// Certificates are issued here, not on the agent: renewing re-issues the
+/// sender's certificate the way it was obtained (see `certificates`).
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/tls/renew",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = CertificateSummary), ApiError)
+)]
 async fn renew_tls_cert(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+) -> Result<Json<CertificateSummary>, ApiError> {
+    user.require(Capability::ManageSenders)?;
     verify_ownership(&state, &user, &id).await?;
-
-    let request_id = Uuid::now_v7().to_string();
-    let payload = TlsRenewPayload { request_id };
-    proxy_to_agent(&state, &id, &ControlMessage::TlsRenew(payload), 15).await
+    super::certificates::renew_for_sender(&state, &user, &id)
+        .await
+        .map(Json)
 }
 
 // ── Config Export/Import ────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/config/export",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn export_config(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::TroubleshootSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1030,13 +1246,22 @@ async fn export_config(
     proxy_to_agent(&state, &id, &ControlMessage::ConfigExport(payload), 10).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/config/import",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = serde_json::Value,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn import_config(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1044,17 +1269,27 @@ async fn import_config(
         request_id,
         config: body,
     };
-    proxy_to_agent(&state, &id, &ControlMessage::ConfigImport(payload), 10).await
+    let resp = proxy_to_agent(&state, &id, &ControlMessage::ConfigImport(payload), 10).await?;
+    super::audit::record_user(&state, &user, Some(&id), "sender.config_import", None).await;
+    Ok(resp)
 }
 
 // ── OTA Updates ─────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/updates/check",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn check_updates(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::TroubleshootSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1062,33 +1297,47 @@ async fn check_updates(
     proxy_to_agent(&state, &id, &ControlMessage::UpdatesCheck(payload), 15).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/updates/install",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn install_update(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("admin")?;
+    user.require(Capability::ManageSenders)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
     let payload = UpdatesInstallPayload { request_id };
-    proxy_to_agent(&state, &id, &ControlMessage::UpdatesInstall(payload), 30).await
+    let resp = proxy_to_agent(&state, &id, &ControlMessage::UpdatesInstall(payload), 30).await?;
+    super::audit::record_user(&state, &user, Some(&id), "sender.update_install", None).await;
+    Ok(resp)
 }
 
 // ── Stream Destinations ─────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-struct DestinationsRequest {
-    destination_ids: Vec<String>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/stream/destinations",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = SetStreamDestinationsRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn set_stream_destinations(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
-    Json(body): Json<DestinationsRequest>,
+    Json(body): Json<SetStreamDestinationsRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1107,19 +1356,22 @@ async fn set_stream_destinations(
 
 // ── Jitter Buffer ───────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-struct JitterBufferRequest {
-    mode: String,
-    static_ms: Option<u32>,
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/stream/jitter_buffer",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = JitterBufferRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = serde_json::Value), ApiError)
+)]
 async fn set_jitter_buffer(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(body): Json<JitterBufferRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
     verify_ownership(&state, &user, &id).await?;
 
     let request_id = Uuid::now_v7().to_string();
@@ -1133,12 +1385,20 @@ async fn set_jitter_buffer(
 
 // ── Alerting ────────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/senders/{id}/alerts",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<serde_json::Value>), ApiError)
+)]
 async fn get_alert_rules(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<Vec<serde_json::Value>>, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ManageAlerts)?;
     verify_ownership(&state, &user, &id).await?;
 
     let rules = state
@@ -1149,13 +1409,22 @@ async fn get_alert_rules(
     Ok(Json(rules))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/senders/{id}/alerts",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID")),
+    request_body = serde_json::Value,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK"), ApiError)
+)]
 async fn set_alert_rule(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
     Json(mut body): Json<serde_json::Value>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ManageAlerts)?;
     verify_ownership(&state, &user, &id).await?;
 
     // Ensure the rule has an ID
@@ -1163,74 +1432,62 @@ async fn set_alert_rule(
         body["id"] = serde_json::json!(Uuid::now_v7().to_string());
     }
     let rule_id = body["id"].as_str().unwrap_or("").to_string();
+    let rule_name = body["name"].as_str().map(String::from);
 
-    let mut rules = state.alert_rules().entry(id).or_default();
-    if let Some(pos) = rules
-        .iter()
-        .position(|r| r.get("id").and_then(|v| v.as_str()) == Some(&rule_id))
     {
-        rules[pos] = body;
-    } else {
-        rules.push(body);
+        let mut rules = state.alert_rules().entry(id.clone()).or_default();
+        if let Some(pos) = rules
+            .iter()
+            .position(|r| r.get("id").and_then(|v| v.as_str()) == Some(&rule_id))
+        {
+            rules[pos] = body;
+        } else {
+            rules.push(body);
+        }
     }
+    super::audit::record_user(&state, &user, Some(&id), "alert_rule.set", rule_name).await;
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/senders/{id}/alerts/{rule_id}",
+    tag = "senders",
+    params(("id" = String, Path, description = "Sender ID"), ("rule_id" = String, Path, description = "Alert rule ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn delete_alert_rule(
     State(state): State<AppState>,
     user: AuthUser,
     Path((id, rule_id)): Path<(String, String)>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ManageAlerts)?;
     verify_ownership(&state, &user, &id).await?;
 
     if let Some(mut rules) = state.alert_rules().get_mut(&id) {
         rules.retain(|r| r.get("id").and_then(|v| v.as_str()) != Some(&rule_id));
     }
+    super::audit::record_user(&state, &user, Some(&id), "alert_rule.delete", Some(rule_id)).await;
     Ok(StatusCode::NO_CONTENT)
 }
+/// Make an agent RPC and return its typed response as-is; a timeout, an
+/// offline agent or a NAK becomes the matching HTTP error (see
+/// [`crate::ws_agent::call`]).
 async fn proxy_to_agent(
     state: &AppState,
     sender_id: &str,
     msg: &ControlMessage,
     timeout_secs: u64,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let agent = state
-        .agents()
-        .get(sender_id)
-        .ok_or_else(|| ApiError::bad_request("sender is not connected"))?;
-
-    let envelope = Envelope::from_message(msg).map_err(|e| ApiError::internal(e.to_string()))?;
-    let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
-
-    let request_id = msg
-        .request_id()
-        .map(String::from)
-        .unwrap_or_else(|| envelope.id.clone());
-
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert(request_id.clone(), tx);
-
-    agent
-        .tx
-        .send(json)
+    crate::ws_agent::call(state, sender_id, msg, Duration::from_secs(timeout_secs))
         .await
-        .map_err(|_| ApiError::internal("failed to send command to agent"))?;
-
-    drop(agent);
-
-    match tokio::time::timeout(Duration::from_secs(timeout_secs), rx).await {
-        Ok(Ok(value)) => Ok(Json(value)),
-        Ok(Err(_)) => Err(ApiError::internal("agent disconnected")),
-        Err(_) => {
-            state.pending_requests().remove(&request_id);
-            Err(ApiError::internal("request timed out"))
-        }
-    }
+        .map(Json)
+        .map_err(ApiError::from)
 }
 
 /// Verify the authenticated user owns the given sender.
-async fn verify_ownership(
+pub(crate) async fn verify_ownership(
     state: &AppState,
     user: &AuthUser,
     sender_id: &str,
@@ -1239,7 +1496,7 @@ async fn verify_ownership(
         "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
     )
     .bind(sender_id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_one(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
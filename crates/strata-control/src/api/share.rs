@@ -0,0 +1,362 @@
+//! Read-only share links for a live stream.
+//!
+//! GET    /api/streams/:id/share-links            — the stream's links
+//! POST   /api/streams/:id/share-links            — mint a link (operator)
+//! DELETE /api/streams/:id/share-links/:link_id   — revoke it (operator)
+//! GET    /api/share/stream                       — the shared view, for
+//!                                                  a share token
+//!
+//! A share token is a JWT with role [`auth::SHARE_ROLE`]: `sub` is the link
+//! ID and `owner` the account, so [`AuthUser`] and the dashboard socket
+//! refuse it. [`ShareScope`] accepts nothing else, and re-checks the link
+//! row on every request so revoking takes effect immediately.
+
+use axum::extract::{FromRequestParts, Path, State};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+
+use strata_common::{auth, ids};
+use strata_protocol::api::{
+    Capability, CreateShareLinkRequest, CreateShareLinkResponse, SHARE_LINK_TTLS, ShareLinkSummary,
+    SharedLinkHealth, SharedStreamView,
+};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::{AuthRejection, AuthUser, FromRef};
+
+const MAX_LABEL_LEN: usize = 80;
+
+/// Public routes, nested at `/api/share`.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/stream", get(shared_stream))
+}
+
+type LinkRow = (
+    String,
+    String,
+    Option<String>,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    Option<DateTime<Utc>>,
+);
+
+const LINK_COLUMNS: &str =
+    "id, stream_id, label, created_at, expires_at, revoked_at, last_viewed_at";
+
+fn summary_from_row(row: LinkRow) -> ShareLinkSummary {
+    let (id, stream_id, label, created_at, expires_at, revoked_at, last_viewed_at) = row;
+    ShareLinkSummary {
+        id,
+        stream_id,
+        label,
+        created_at,
+        expires_at,
+        revoked_at,
+        last_viewed_at,
+    }
+}
+
+/// The stream's sender, if the stream belongs to the caller's account.
+async fn stream_sender(
+    state: &AppState,
+    user: &AuthUser,
+    stream_id: &str,
+) -> Result<String, ApiError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT s.sender_id FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+         WHERE s.id = $1 AND sn.owner_id = $2",
+    )
+    .bind(stream_id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("stream not found"))
+}
+
+// ── Management ──────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/streams/{id}/share-links",
+    tag = "streams",
+    summary = "The stream's share links",
+    params(("id" = String, Path, description = "Stream ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<ShareLinkSummary>), ApiError)
+)]
+pub(crate) async fn list_share_links(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(stream_id): Path<String>,
+) -> Result<Json<Vec<ShareLinkSummary>>, ApiError> {
+    stream_sender(&state, &user, &stream_id).await?;
+
+    let rows = sqlx::query_as::<_, LinkRow>(&format!(
+        "SELECT {LINK_COLUMNS} FROM share_links WHERE stream_id = $1 ORDER BY created_at DESC"
+    ))
+    .bind(&stream_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(rows.into_iter().map(summary_from_row).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/streams/{id}/share-links",
+    tag = "streams",
+    summary = "Mint a link (operator)",
+    params(("id" = String, Path, description = "Stream ID")),
+    request_body = CreateShareLinkRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = CreateShareLinkResponse), ApiError)
+)]
+pub(crate) async fn create_share_link(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(stream_id): Path<String>,
+    Json(body): Json<CreateShareLinkRequest>,
+) -> Result<(StatusCode, Json<CreateShareLinkResponse>), ApiError> {
+    user.require(Capability::ShareStreams)?;
+    let sender_id = stream_sender(&state, &user, &stream_id).await?;
+
+    if !SHARE_LINK_TTLS.contains(&body.expires_in_s) {
+        return Err(ApiError::bad_request(format!(
+            "expires_in_s must be one of {SHARE_LINK_TTLS:?}"
+        )));
+    }
+    let label = body
+        .label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if label
+        .as_ref()
+        .is_some_and(|l| l.chars().count() > MAX_LABEL_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "label must be at most {MAX_LABEL_LEN} characters"
+        )));
+    }
+
+    let id = ids::share_link_id();
+    let now = Utc::now();
+    let expires_at = now + chrono::Duration::seconds(i64::from(body.expires_in_s));
+
+    let claims = auth::Claims::share(&id, &user.owner_id, now.timestamp(), expires_at.timestamp());
+    let token = state
+        .jwt()
+        .create_token(&claims)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let row = sqlx::query_as::<_, LinkRow>(&format!(
+        "INSERT INTO share_links (id, owner_id, stream_id, label, expires_at, created_by) \
+         VALUES ($1, $2, $3, $4, $5, $6) RETURNING {LINK_COLUMNS}"
+    ))
+    .bind(&id)
+    .bind(&user.owner_id)
+    .bind(&stream_id)
+    .bind(&label)
+    .bind(expires_at)
+    .bind(&user.user_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&sender_id),
+        "share_link.create",
+        Some(format!(
+            "{id} for {stream_id}, expires {}",
+            expires_at.to_rfc3339()
+        )),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateShareLinkResponse {
+            link: summary_from_row(row),
+            token,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/streams/{id}/share-links/{link_id}",
+    tag = "streams",
+    summary = "Revoke a share link (operator)",
+    params(("id" = String, Path, description = "Stream ID"), ("link_id" = String, Path, description = "Share link ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+pub(crate) async fn revoke_share_link(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((stream_id, link_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ShareStreams)?;
+    let sender_id = stream_sender(&state, &user, &stream_id).await?;
+
+    let result = sqlx::query(
+        "UPDATE share_links SET revoked_at = COALESCE(revoked_at, now()) \
+         WHERE id = $1 AND stream_id = $2",
+    )
+    .bind(&link_id)
+    .bind(&stream_id)
+    .execute(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("share link not found"));
+    }
+
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&sender_id),
+        "share_link.revoke",
+        Some(link_id),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Shared view ─────────────────────────────────────────────────────
+
+/// Extractor for `Authorization: Bearer <share token>`: the one stream a
+/// live, unrevoked share link grants.
+pub struct ShareScope {
+    pub link_id: String,
+    pub owner_id: String,
+    pub stream_id: String,
+    pub label: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl<S> FromRequestParts<S> for ShareScope
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app_state = AppState::from_ref(state);
+
+        let token = parts
+            .headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or(AuthRejection::Missing)?;
+
+        let claims = app_state
+            .jwt()
+            .verify_scoped(token, &[auth::TokenScope::Share])
+            .map_err(|_| AuthRejection::Invalid)?;
+        let owner_id = claims.owner.ok_or(AuthRejection::Invalid)?;
+
+        let (stream_id, label, expires_at) =
+            sqlx::query_as::<_, (String, Option<String>, DateTime<Utc>)>(
+                "SELECT stream_id, label, expires_at FROM share_links \
+                 WHERE id = $1 AND owner_id = $2 AND revoked_at IS NULL AND expires_at > now()",
+            )
+            .bind(&claims.sub)
+            .bind(&owner_id)
+            .fetch_optional(app_state.pool())
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to load share link");
+                AuthRejection::Unavailable
+            })?
+            .ok_or(AuthRejection::Invalid)?;
+
+        Ok(Self {
+            link_id: claims.sub,
+            owner_id,
+            stream_id,
+            label,
+            expires_at,
+        })
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/share/stream",
+    tag = "share",
+    summary = "The shared view, for a share token",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = SharedStreamView), ApiError)
+)]
+async fn shared_stream(
+    State(state): State<AppState>,
+    scope: ShareScope,
+) -> Result<Json<SharedStreamView>, ApiError> {
+    let row = sqlx::query_as::<
+        _,
+        (
+            String,
+            String,
+            Option<String>,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<String>,
+            Option<String>,
+        ),
+    >(
+        "SELECT s.sender_id, s.state, sn.name, s.started_at, s.ended_at, \
+                s.preview_key, r.preview_base_url \
+         FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+         LEFT JOIN receivers r ON r.id = s.receiver_id \
+         WHERE s.id = $1 AND sn.owner_id = $2",
+    )
+    .bind(&scope.stream_id)
+    .bind(&scope.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("stream not found"))?;
+    let (sender_id, stream_state, sender_name, started_at, ended_at, preview_key, preview_base) =
+        row;
+
+    let _ = sqlx::query("UPDATE share_links SET last_viewed_at = now() WHERE id = $1")
+        .bind(&scope.link_id)
+        .execute(state.pool())
+        .await;
+
+    let preview_url =
+        super::streams::preview_url(&scope.stream_id, &stream_state, preview_base, preview_key);
+    let stats = state
+        .live()
+        .stats(&sender_id)
+        .filter(|s| s.stream_id == scope.stream_id && stream_state == "live");
+
+    Ok(Json(SharedStreamView {
+        stream_id: scope.stream_id,
+        label: scope.label,
+        sender_name,
+        state: stream_state,
+        started_at,
+        ended_at,
+        expires_at: scope.expires_at,
+        preview_url,
+        uptime_s: stats.as_ref().map_or(0, |s| s.uptime_s),
+        encoder_bitrate_kbps: stats.as_ref().map_or(0, |s| s.encoder_bitrate_kbps),
+        links: stats
+            .map(|s| s.links.iter().map(SharedLinkHealth::from).collect())
+            .unwrap_or_default(),
+    }))
+}
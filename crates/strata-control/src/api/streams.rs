@@ -4,15 +4,24 @@
 //! POST /api/senders/:id/stream/stop  — stop a broadcast
 //! GET  /api/streams                  — list active streams
 //! GET  /api/streams/:id              — get stream details
+//! GET  /api/streams/:id/link-events  — per-link timeline feed (see `link_events`)
+//! GET  /api/streams/:id/report       — incident report, JSON or PDF (see `reports`)
+//! /api/streams/:id/annotations         — operator timeline notes (see `reports`)
+//! /api/streams/:id/share-links         — read-only share links (see `share`)
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{Json, Router};
 use chrono::Utc;
 
-use strata_common::ids;
-use strata_protocol::api::{StartStreamRequest, StartStreamResponse, StreamDetail, StreamSummary};
+use strata_common::error::ErrorCode;
+use strata_common::ids::{self, SenderId, StreamId};
+use strata_common::validation::{self, Validate};
+use strata_protocol::api::{
+    Capability, StartStreamRequest, StartStreamResponse, StreamDetail, StreamSummary,
+};
+use strata_protocol::models::IngestProtocol;
 use strata_protocol::profiles;
 use strata_protocol::{
     ControlMessage, Envelope, ReceiverControlMessage, StreamStartPayload, StreamStopPayload,
@@ -27,6 +36,23 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_streams))
         .route("/{id}", get(get_stream))
+        .route(
+            "/{id}/link-events",
+            get(super::link_events::list_link_events),
+        )
+        .route("/{id}/report", get(super::reports::get_report))
+        .route(
+            "/{id}/annotations",
+            get(super::reports::list_annotations).post(super::reports::create_annotation),
+        )
+        .route(
+            "/{id}/share-links",
+            get(super::share::list_share_links).post(super::share::create_share_link),
+        )
+        .route(
+            "/{id}/share-links/{link_id}",
+            delete(super::share::revoke_share_link),
+        )
         // These are nested under senders in the actual mount, but we handle
         // the sender path here for simplicity:
         .route("/start/{sender_id}", post(start_stream))
@@ -35,27 +61,71 @@ pub fn router() -> Router<AppState> {
 
 // ── Start Stream ────────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/streams/start/{sender_id}",
+    tag = "streams",
+    params(("sender_id" = String, Path, description = "Sender ID")),
+    request_body = StartStreamRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = StartStreamResponse), ApiError)
+)]
 async fn start_stream(
     State(state): State<AppState>,
     user: AuthUser,
-    Path(sender_id): Path<String>,
+    Path(sender_id): Path<SenderId>,
     Json(body): Json<StartStreamRequest>,
 ) -> Result<(StatusCode, Json<StartStreamResponse>), ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
+
+    let launched = launch(&state, &user.owner_id, &sender_id, body).await?;
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(sender_id.as_str()),
+        "stream.start",
+        Some(launched.stream_id.to_string()),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(StartStreamResponse {
+            stream_id: launched.stream_id,
+            state: "starting".into(),
+            ingest_url: launched.ingest_url,
+        }),
+    ))
+}
+
+/// A stream [`launch`] started.
+pub(crate) struct Launched {
+    pub stream_id: StreamId,
+    /// Ingest senders only: where the contributor pushes to.
+    pub ingest_url: Option<String>,
+}
+
+/// Start a broadcast on one of `owner_id`'s senders. Shared by the REST
+/// handler and the stream scheduler; callers check roles and write the
+/// audit entry.
+pub(crate) async fn launch(
+    state: &AppState,
+    owner_id: &str,
+    sender_id: &str,
+    body: StartStreamRequest,
+) -> Result<Launched, ApiError> {
+    let sender_id = sender_id.to_string();
 
     // Verify sender ownership
-    let exists = sqlx::query_scalar::<_, bool>(
-        "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
+    let ingest_protocol: Option<String> = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT ingest_protocol FROM senders WHERE id = $1 AND owner_id = $2",
     )
     .bind(&sender_id)
-    .bind(&user.user_id)
-    .fetch_one(state.pool())
+    .bind(owner_id)
+    .fetch_optional(state.pool())
     .await
-    .map_err(|e| ApiError::internal(e.to_string()))?;
-
-    if !exists {
-        return Err(ApiError::not_found("sender not found"));
-    }
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .ok_or_else(|| ApiError::not_found("sender not found"))?;
 
     // Guard: no concurrent streams for the same sender
     let already_active = sqlx::query_scalar::<_, bool>(
@@ -80,7 +150,7 @@ async fn start_stream(
                 "SELECT platform, url, stream_key FROM destinations WHERE id = $1 AND owner_id = $2",
             )
             .bind(dest_id)
-            .bind(&user.user_id)
+            .bind(owner_id)
             .fetch_optional(state.pool())
             .await
             .map_err(|e| ApiError::internal(e.to_string()))?
@@ -106,11 +176,27 @@ async fn start_stream(
         String::new()
     };
 
+    // Ingest senders have no agent: the receiver takes the contribution
+    // straight in (see `ingest`).
+    if let Some(protocol) = ingest_protocol {
+        let protocol = protocol
+            .parse::<IngestProtocol>()
+            .map_err(ApiError::internal)?;
+        return super::ingest::launch(
+            state,
+            owner_id,
+            &sender_id,
+            protocol,
+            relay_url,
+            body.destination_id.as_deref().filter(|s| !s.is_empty()),
+        )
+        .await;
+    }
+
     // Check sender is connected
-    let agent = state
-        .agents()
-        .get(&sender_id)
-        .ok_or_else(|| ApiError::bad_request("sender is offline"))?;
+    let agent = state.agents().get(&sender_id).ok_or_else(|| {
+        ApiError::bad_request("sender is offline").with_code(ErrorCode::DeviceOffline)
+    })?;
     let agent_tx = agent.tx.clone();
     drop(agent);
 
@@ -135,51 +221,6 @@ async fn start_stream(
         ));
     }
 
-    // Pick a receiver (capacity-aware, DB-derived) or fall back to env
-    // config; managed receivers allocate their own ports via request/ack.
-    let relay_url_opt = if relay_url.is_empty() {
-        None
-    } else {
-        Some(relay_url.clone())
-    };
-    let stream_id = ids::stream_id();
-    let (receiver_id_opt, strata_dests) = match pick_receiver(&state, &user.user_id).await {
-        Some((rcv_id, bind_host)) => {
-            let ports = request_receiver_start(
-                &state,
-                &rcv_id,
-                &stream_id,
-                enabled_count as u32,
-                relay_url_opt.clone(),
-            )
-            .await?;
-            let dests: Vec<String> = ports
-                .iter()
-                .map(|p| format!("strata://{bind_host}:{p}"))
-                .collect();
-            (Some(rcv_id), dests)
-        }
-        None => {
-            // Env-var fallback for unmanaged deployments: fixed ports.
-            let links = build_receiver_links();
-            let count = enabled_count.min(links.len());
-            if count == 0 {
-                return Err(ApiError::bad_request("no receiver links configured"));
-            }
-            let dests = links[..count]
-                .iter()
-                .map(|addr| format!("strata://{addr}"))
-                .collect();
-            (None, dests)
-        }
-    };
-
-    tracing::info!(
-        links = strata_dests.len(),
-        dests = ?strata_dests,
-        "building Strata destinations for sender"
-    );
-
     // Extract source config values before they're consumed into the payload.
     let body_source_resolution = body
         .source
@@ -196,6 +237,7 @@ async fn start_stream(
             resolution: None,
             framerate: None,
             passthrough: Some(true),
+            inputs: Vec::new(),
         },
         _ => strata_protocol::SourceConfig {
             mode: "test".into(),
@@ -204,43 +246,121 @@ async fn start_stream(
             resolution: Some("1920x1080".into()),
             framerate: Some(30),
             passthrough: None,
+            inputs: Vec::new(),
         },
     };
 
-    let start_payload = StreamStartPayload {
-        stream_id: stream_id.clone(),
-        source: body.source.unwrap_or(default_source),
-        encoder: {
-            let enc = body.encoder.unwrap_or(strata_protocol::EncoderConfig {
-                bitrate_kbps: 0, // placeholder — overridden below
-                tune: Some("zerolatency".into()),
-                keyint_max: Some(60),
-                codec: Some("h265".into()),
-                min_bitrate_kbps: None,
-                max_bitrate_kbps: None,
-            });
-            // Resolve codec (default h265). YouTube and other modern
-            // platforms accept H.265 via Enhanced RTMP / eflvmux.
-            let codec = enc.codec.clone().unwrap_or_else(|| "h265".into());
-            // Resolution + framerate come from the source config above
-            let source_res = body_source_resolution.as_deref();
-            let source_fps = body_source_framerate;
-            let profile = profiles::lookup_profile(source_res, source_fps, Some(&codec));
-            // Apply smart defaults: if the caller didn't set values, use profile
-            let bitrate = if enc.bitrate_kbps == 0 {
-                profile.default_kbps
-            } else {
-                enc.bitrate_kbps
-            };
-            strata_protocol::EncoderConfig {
-                bitrate_kbps: bitrate,
-                tune: enc.tune,
-                keyint_max: enc.keyint_max,
-                codec: Some(codec),
-                min_bitrate_kbps: Some(enc.min_bitrate_kbps.unwrap_or(profile.min_kbps)),
-                max_bitrate_kbps: Some(enc.max_bitrate_kbps.unwrap_or(profile.max_kbps)),
+    let source = body.source.unwrap_or(default_source);
+    let encoder = {
+        let enc = body.encoder.unwrap_or(strata_protocol::EncoderConfig {
+            bitrate_kbps: 0, // placeholder — overridden below
+            tune: Some("zerolatency".into()),
+            keyint_max: Some(60),
+            codec: Some("h265".into()),
+            min_bitrate_kbps: None,
+            max_bitrate_kbps: None,
+            ladder: Vec::new(),
+            overlay: None,
+        });
+        // Resolve codec (default h265). YouTube and other modern
+        // platforms accept H.265 via Enhanced RTMP / eflvmux.
+        let codec = enc.codec.clone().unwrap_or_else(|| "h265".into());
+        // Resolution + framerate come from the source config above
+        let source_res = body_source_resolution.as_deref();
+        let source_fps = body_source_framerate;
+        let profile = profiles::lookup_profile(source_res, source_fps, Some(&codec));
+        // Apply smart defaults: if the caller didn't set values, use profile
+        let bitrate = if enc.bitrate_kbps == 0 {
+            profile.default_kbps
+        } else {
+            enc.bitrate_kbps
+        };
+        strata_protocol::EncoderConfig {
+            bitrate_kbps: bitrate,
+            tune: enc.tune,
+            keyint_max: enc.keyint_max,
+            codec: Some(codec),
+            min_bitrate_kbps: Some(enc.min_bitrate_kbps.unwrap_or(profile.min_kbps)),
+            max_bitrate_kbps: Some(enc.max_bitrate_kbps.unwrap_or(profile.max_kbps)),
+            ladder: enc.ladder,
+            overlay: enc.overlay,
+        }
+    };
+    // Reject a bad config here, before a receiver allocates ports for it;
+    // the agent runs the same checks on receipt.
+    source
+        .validate()
+        .map_err(|e| ApiError::bad_request(format!("source: {e}")))?;
+    encoder
+        .validate()
+        .map_err(|e| ApiError::bad_request(format!("encoder: {e}")))?;
+    validation::validate_encoder_source(&source, &encoder)
+        .map_err(|e| ApiError::bad_request(format!("{}: {e}", e.field)))?;
+
+    // Pick a receiver (capacity-aware, DB-derived) or fall back to env
+    // config; managed receivers allocate their own ports via request/ack.
+    let relay_url_opt = if relay_url.is_empty() {
+        None
+    } else {
+        Some(relay_url.clone())
+    };
+    let stream_id = ids::stream_id();
+    // Managed receivers get a fresh ingest key with the start request, so
+    // only this stream's sender can push into the ports they allocate. HLS
+    // relays on receivers running a preview server also get a preview key.
+    let (receiver_id_opt, strata_dests, ingest_key, preview_key) =
+        match pick_or_provision_receiver(state, owner_id, None).await {
+            Some((rcv_id, bind_host, preview_base_url)) => {
+                let ingest_key = ids::ingest_key();
+                let preview_key = (preview_base_url.is_some() && relay_url.starts_with("https://"))
+                    .then(ids::preview_key);
+                let ports = request_receiver_start(
+                    state,
+                    &rcv_id,
+                    strata_protocol::ReceiverStreamStartPayload {
+                        request_id: String::new(),
+                        stream_id: stream_id.to_string(),
+                        link_count: enabled_count as u32,
+                        relay_url: relay_url_opt.clone(),
+                        bonding_config: serde_json::Value::Null,
+                        ingest_key: Some(ingest_key.clone()),
+                        preview_key: preview_key.clone(),
+                        ingest: None,
+                    },
+                )
+                .await?
+                .bind_ports;
+                let dests: Vec<String> = ports
+                    .iter()
+                    .map(|p| format!("strata://{bind_host}:{p}"))
+                    .collect();
+                (Some(rcv_id), dests, Some(ingest_key), preview_key)
             }
-        },
+            None => {
+                // Env-var fallback for unmanaged deployments: fixed ports.
+                let links = build_receiver_links();
+                let count = enabled_count.min(links.len());
+                if count == 0 {
+                    return Err(ApiError::bad_request("no receiver links configured"));
+                }
+                let dests = links[..count]
+                    .iter()
+                    .map(|addr| format!("strata://{addr}"))
+                    .collect();
+                (None, dests, None, None)
+            }
+        };
+
+    tracing::info!(
+        links = strata_dests.len(),
+        dests = ?strata_dests,
+        "building Strata destinations for sender"
+    );
+
+    let start_payload = StreamStartPayload {
+        stream_id: stream_id.to_string(),
+        source,
+        encoder,
         destinations: strata_dests,
         // No override — let `SchedulerConfig::default()` (and the agent's own
         // config) govern. The control plane has no explicit-override
@@ -249,6 +369,7 @@ async fn start_stream(
         bonding_config: serde_json::Value::Null,
         psk: None,
         relay_url: relay_url_opt,
+        ingest_key,
     };
 
     // Store the resolved payload (with defaults applied) so the dashboard
@@ -279,8 +400,8 @@ async fn start_stream(
 
     // Insert stream row into DB (with receiver_id if assigned)
     sqlx::query(
-        "INSERT INTO streams (id, sender_id, destination_id, receiver_id, state, started_at, config_json, restarted_from) \
-         VALUES ($1, $2, $3, $4, 'starting', $5, $6, $7)",
+        "INSERT INTO streams (id, sender_id, destination_id, receiver_id, state, started_at, config_json, restarted_from, preview_key) \
+         VALUES ($1, $2, $3, $4, 'starting', $5, $6, $7, $8)",
     )
     .bind(&stream_id)
     .bind(&sender_id)
@@ -289,6 +410,7 @@ async fn start_stream(
     .bind(Utc::now())
     .bind(&config_json_final)
     .bind(&restarted_from)
+    .bind(&preview_key)
     .execute(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -317,7 +439,7 @@ async fn start_stream(
         {
             let stop = strata_protocol::ReceiverControlMessage::StreamStop(
                 strata_protocol::ReceiverStreamStopPayload {
-                    stream_id: stream_id.clone(),
+                    stream_id: stream_id.to_string(),
                     reason: "start rollback".into(),
                 },
             );
@@ -332,9 +454,9 @@ async fn start_stream(
 
     // Notify dashboard
     state.broadcast_dashboard(
-        user.user_id.clone(),
+        owner_id,
         strata_protocol::DashboardEvent::StreamStateChanged {
-            stream_id: stream_id.clone(),
+            stream_id: stream_id.to_string(),
             sender_id: sender_id.clone(),
             state: strata_protocol::models::StreamState::Starting,
             error: None,
@@ -343,42 +465,91 @@ async fn start_stream(
     );
 
     tracing::info!(stream_id = %stream_id, sender_id = %sender_id, "stream starting");
-
-    Ok((
-        StatusCode::CREATED,
-        Json(StartStreamResponse {
-            stream_id,
-            state: "starting".into(),
-        }),
-    ))
+    Ok(Launched {
+        stream_id,
+        ingest_url: None,
+    })
 }
 
 // ── Stop Stream ─────────────────────────────────────────────────────
 
+#[utoipa::path(
+    post,
+    path = "/api/streams/stop/{sender_id}",
+    tag = "streams",
+    params(("sender_id" = String, Path, description = "Sender ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
 async fn stop_stream(
     State(state): State<AppState>,
     user: AuthUser,
     Path(sender_id): Path<String>,
 ) -> Result<StatusCode, ApiError> {
-    user.require_role("operator")?;
+    user.require(Capability::ControlStreams)?;
+
+    let stream_id = active_stream(&state, &user.owner_id, &sender_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("no active stream for this sender"))?;
+    halt(
+        &state,
+        &user.owner_id,
+        &sender_id,
+        &stream_id,
+        "user_request",
+    )
+    .await?;
+    super::audit::record_user(
+        &state,
+        &user,
+        Some(&sender_id),
+        "stream.stop",
+        Some(stream_id),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    // Find the active stream for this sender
-    let (stream_id, receiver_id) = sqlx::query_as::<_, (String, Option<String>)>(
-        "SELECT s.id, s.receiver_id FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+/// The sender's current `starting`/`live` stream, if `owner_id` owns it.
+pub(crate) async fn active_stream(
+    state: &AppState,
+    owner_id: &str,
+    sender_id: &str,
+) -> Result<Option<String>, ApiError> {
+    sqlx::query_scalar(
+        "SELECT s.id FROM streams s JOIN senders sn ON s.sender_id = sn.id \
          WHERE s.sender_id = $1 AND sn.owner_id = $2 AND s.state IN ('starting', 'live') \
          ORDER BY s.started_at DESC LIMIT 1",
     )
-    .bind(&sender_id)
-    .bind(&user.user_id)
+    .bind(sender_id)
+    .bind(owner_id)
     .fetch_optional(state.pool())
     .await
-    .map_err(|e| ApiError::internal(e.to_string()))?
-    .ok_or_else(|| ApiError::not_found("no active stream for this sender"))?;
+    .map_err(|e| ApiError::internal(e.to_string()))
+}
+
+/// Stop `stream_id` on `sender_id`: tell the agent and receiver, and force
+/// the stream to ended if the agent never confirms. Shared by the REST
+/// handler and the stream scheduler; callers check roles and audit.
+pub(crate) async fn halt(
+    state: &AppState,
+    owner_id: &str,
+    sender_id: &str,
+    stream_id: &str,
+    reason: &str,
+) -> Result<(), ApiError> {
+    let receiver_id: Option<String> =
+        sqlx::query_scalar("SELECT receiver_id FROM streams WHERE id = $1")
+            .bind(stream_id)
+            .fetch_one(state.pool())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
 
     // Update state
     crate::stream_state::transition(
         state.pool(),
-        &stream_id,
+        stream_id,
         strata_protocol::models::StreamState::Stopping,
         crate::stream_state::EndAttribution::none(),
     )
@@ -386,10 +557,10 @@ async fn stop_stream(
     .map_err(|e| ApiError::internal(e.to_string()))?;
 
     // Send stop command to agent
-    if let Some(agent) = state.agents().get(&sender_id) {
+    if let Some(agent) = state.agents().get(sender_id) {
         let stop_payload = StreamStopPayload {
-            stream_id: stream_id.clone(),
-            reason: "user_request".into(),
+            stream_id: stream_id.to_string(),
+            reason: reason.into(),
         };
         let envelope = Envelope::from_message(&ControlMessage::StreamStop(stop_payload)).unwrap();
         let json = serde_json::to_string(&envelope).unwrap();
@@ -411,8 +582,8 @@ async fn stop_stream(
         && let Some(rcv_handle) = state.receivers().get(rcv_id)
     {
         let rcv_stop_payload = strata_protocol::ReceiverStreamStopPayload {
-            stream_id: stream_id.clone(),
-            reason: "user_request".into(),
+            stream_id: stream_id.to_string(),
+            reason: reason.into(),
         };
         let rcv_envelope =
             Envelope::from_message(&ReceiverControlMessage::StreamStop(rcv_stop_payload)).unwrap();
@@ -428,10 +599,10 @@ async fn stop_stream(
 
     // Notify dashboard
     state.broadcast_dashboard(
-        user.user_id.clone(),
+        owner_id,
         strata_protocol::DashboardEvent::StreamStateChanged {
-            stream_id: stream_id.clone(),
-            sender_id: sender_id.clone(),
+            stream_id: stream_id.to_string(),
+            sender_id: sender_id.to_string(),
             state: strata_protocol::models::StreamState::Stopping,
             error: None,
             reason: None,
@@ -443,34 +614,55 @@ async fn stop_stream(
     const STOP_FORCE_END_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
     {
         let state = state.clone();
-        let stream_id = stream_id.clone();
-        let sender_id = sender_id.clone();
-        let owner_id = user.user_id.clone();
+        let stream_id = stream_id.to_string();
+        let sender_id = sender_id.to_string();
+        let owner_id = owner_id.to_string();
         tokio::spawn(async move {
             tokio::time::sleep(STOP_FORCE_END_TIMEOUT).await;
             let forced = crate::stream_state::force_end_stopping(state.pool(), &stream_id).await;
             if forced.unwrap_or(false) {
                 state.live_streams().remove(&stream_id);
+                crate::api::usage::finish_stream_usage(&state, &stream_id).await;
+                crate::api::link_events::clear(&state, &stream_id);
+                crate::api::reports::generate_detached(&state, &stream_id);
+                state.live().end_stream(&stream_id);
                 state.broadcast_dashboard(
-                    owner_id,
+                    &owner_id,
                     strata_protocol::DashboardEvent::StreamStateChanged {
-                        stream_id,
-                        sender_id,
+                        stream_id: stream_id.clone(),
+                        sender_id: sender_id.clone(),
                         state: strata_protocol::models::StreamState::Ended,
                         error: Some("stop timeout".into()),
                         reason: Some("timeout".into()),
                     },
                 );
                 tracing::warn!("stream stop timed out, forced to ended");
+                super::audit::record(
+                    &state,
+                    &owner_id,
+                    None,
+                    Some(&sender_id),
+                    "stream.force_end",
+                    Some(stream_id),
+                )
+                .await;
             }
         });
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(())
 }
 
 // ── List Streams ────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/streams",
+    tag = "streams",
+    summary = "List active streams",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<StreamSummary>), ApiError)
+)]
 async fn list_streams(
     State(state): State<AppState>,
     user: AuthUser,
@@ -486,15 +678,19 @@ async fn list_streams(
             Option<String>,
             Option<String>,
             Option<String>,
+            Option<String>,
+            Option<String>,
         ),
     >(
         "SELECT s.id, s.sender_id, s.state, s.started_at, s.ended_at, \
-                s.end_reason, s.error_message, s.restarted_from \
+                s.end_reason, s.error_message, s.restarted_from, \
+                s.preview_key, r.preview_base_url \
          FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+         LEFT JOIN receivers r ON r.id = s.receiver_id \
          WHERE sn.owner_id = $1 \
          ORDER BY s.created_at DESC LIMIT 50",
     )
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_all(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?;
@@ -511,7 +707,10 @@ async fn list_streams(
                 end_reason,
                 error_message,
                 restarted_from,
+                preview_key,
+                preview_base_url,
             )| {
+                let preview_url = preview_url(&id, &state_str, preview_base_url, preview_key);
                 StreamSummary {
                     id,
                     sender_id,
@@ -521,6 +720,7 @@ async fn list_streams(
                     end_reason,
                     error_message,
                     restarted_from,
+                    preview_url,
                 }
             },
         )
@@ -531,18 +731,28 @@ async fn list_streams(
 
 // ── Get Stream ──────────────────────────────────────────────────────
 
+#[utoipa::path(
+    get,
+    path = "/api/streams/{id}",
+    tag = "streams",
+    summary = "Get stream details",
+    params(("id" = String, Path, description = "Stream ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = StreamDetail), ApiError)
+)]
 async fn get_stream(
     State(state): State<AppState>,
     user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<StreamDetail>, ApiError> {
-    let row = sqlx::query_as::<_, (String, String, Option<String>, String, Option<chrono::DateTime<chrono::Utc>>, Option<chrono::DateTime<chrono::Utc>>, Option<String>, i64, Option<String>, Option<String>, Option<String>)>(
-        "SELECT s.id, s.sender_id, s.destination_id, s.state, s.started_at, s.ended_at, s.config_json, s.total_bytes, s.error_message, s.end_reason, s.restarted_from \
+    let row = sqlx::query_as::<_, (String, String, Option<String>, String, Option<chrono::DateTime<chrono::Utc>>, Option<chrono::DateTime<chrono::Utc>>, Option<String>, i64, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>)>(
+        "SELECT s.id, s.sender_id, s.destination_id, s.state, s.started_at, s.ended_at, s.config_json, s.total_bytes, s.error_message, s.end_reason, s.restarted_from, s.preview_key, r.preview_base_url \
          FROM streams s JOIN senders sn ON s.sender_id = sn.id \
+         LEFT JOIN receivers r ON r.id = s.receiver_id \
          WHERE s.id = $1 AND sn.owner_id = $2",
     )
     .bind(&id)
-    .bind(&user.user_id)
+    .bind(&user.owner_id)
     .fetch_optional(state.pool())
     .await
     .map_err(|e| ApiError::internal(e.to_string()))?
@@ -560,8 +770,12 @@ async fn get_stream(
         error_message,
         end_reason,
         restarted_from,
+        preview_key,
+        preview_base_url,
     ) = row;
 
+    let preview_url = preview_url(&id, &state_str, preview_base_url, preview_key);
+
     Ok(Json(StreamDetail {
         id,
         sender_id,
@@ -574,20 +788,43 @@ async fn get_stream(
         error_message,
         end_reason,
         restarted_from,
+        preview_url,
     }))
 }
 
 // ── Helpers ─────────────────────────────────────────────────────────
 
+/// The receiver's preview playlist for a stream — only while it is live.
+pub(crate) fn preview_url(
+    stream_id: &str,
+    state: &str,
+    base_url: Option<String>,
+    key: Option<String>,
+) -> Option<String> {
+    match (state, base_url, key) {
+        ("live", Some(base), Some(key)) => Some(format!(
+            "{}/preview/{stream_id}/{key}/playlist.m3u8",
+            base.trim_end_matches('/')
+        )),
+        _ => None,
+    }
+}
+
 /// Pick the least-loaded online receiver for this owner, or `None` to fall
 /// back to env-var configuration. Load is derived from the streams table
 /// (COUNT of active assignments), not the hand-maintained `active_streams`
 /// counter — counters drift; the streams table is what reconciliation
-/// keeps honest (E7).
-async fn pick_receiver(state: &AppState, owner_id: &str) -> Option<(String, String)> {
-    let row = sqlx::query_as::<_, (String, String)>(
-        "SELECT r.id, r.bind_host FROM receivers r \
+/// keeps honest (E7). With `ingest`, only receivers that take that
+/// protocol in qualify. Returns its id, bind host and preview base URL.
+async fn pick_receiver(
+    state: &AppState,
+    owner_id: &str,
+    ingest: Option<IngestProtocol>,
+) -> Option<(String, String, Option<String>)> {
+    let row = sqlx::query_as::<_, (String, String, Option<String>)>(
+        "SELECT r.id, r.bind_host, r.preview_base_url FROM receivers r \
          WHERE r.owner_id = $1 AND r.online = TRUE \
+           AND ($3::TEXT IS NULL OR $3 = ANY(r.ingest_protocols)) \
            AND (SELECT COUNT(*) FROM streams s \
                 WHERE s.receiver_id = r.id AND s.state = ANY($2)) < r.max_streams \
          ORDER BY (SELECT COUNT(*) FROM streams s \
@@ -597,6 +834,7 @@ async fn pick_receiver(state: &AppState, owner_id: &str) -> Option<(String, Stri
     )
     .bind(owner_id)
     .bind(&crate::stream_state::ACTIVE_STATES[..])
+    .bind(ingest.map(|p| p.as_str()))
     .fetch_optional(state.pool())
     .await
     .ok()
@@ -610,34 +848,48 @@ async fn pick_receiver(state: &AppState, owner_id: &str) -> Option<(String, Stri
     }
 }
 
+/// [`pick_receiver`], first provisioning a receiver worker when every
+/// receiver is full and auto-scaling is configured (see `autoscale`).
+pub(crate) async fn pick_or_provision_receiver(
+    state: &AppState,
+    owner_id: &str,
+    ingest: Option<IngestProtocol>,
+) -> Option<(String, String, Option<String>)> {
+    if let Some(picked) = pick_receiver(state, owner_id, ingest).await {
+        return Some(picked);
+    }
+    let autoscaler = state.autoscaler()?;
+    let _scaling = autoscaler.scale_lock().await;
+    // A start that held the lock before us may have made room already.
+    if let Some(picked) = pick_receiver(state, owner_id, ingest).await {
+        return Some(picked);
+    }
+    if let Err(e) = autoscaler.scale_up(state, owner_id).await {
+        tracing::warn!(owner_id, error = %e, "receiver auto-scaling failed");
+        return None;
+    }
+    pick_receiver(state, owner_id, ingest).await
+}
+
 /// Ask the receiver to allocate ports and start its pipeline for a stream.
-/// Request/ack: the receiver owns its port pool (E6). Returns the bound
-/// ports on success.
-async fn request_receiver_start(
+/// Request/ack: the receiver owns its port pool (E6). `payload.request_id`
+/// is filled in here. Returns the successful ack.
+pub(crate) async fn request_receiver_start(
     state: &AppState,
     receiver_id: &str,
-    stream_id: &str,
-    link_count: u32,
-    relay_url: Option<String>,
-) -> Result<Vec<u16>, ApiError> {
+    mut payload: strata_protocol::ReceiverStreamStartPayload,
+) -> Result<strata_protocol::ReceiverStreamStartedPayload, ApiError> {
     const RECEIVER_START_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
-    let rcv_handle = state
-        .receivers()
-        .get(receiver_id)
-        .ok_or_else(|| ApiError::internal("receiver disconnected"))?;
+    let rcv_handle = state.receivers().get(receiver_id).ok_or_else(|| {
+        ApiError::internal("receiver disconnected").with_code(ErrorCode::DeviceOffline)
+    })?;
 
     let request_id = uuid::Uuid::now_v7().to_string();
     let (tx, rx) = tokio::sync::oneshot::channel();
     state.pending_requests().insert(request_id.clone(), tx);
 
-    let payload = strata_protocol::ReceiverStreamStartPayload {
-        request_id: request_id.clone(),
-        stream_id: stream_id.to_string(),
-        link_count,
-        relay_url,
-        bonding_config: serde_json::Value::Null,
-    };
+    payload.request_id = request_id.clone();
     let envelope = Envelope::from_message(&ReceiverControlMessage::StreamStart(payload))
         .map_err(|e| ApiError::internal(e.to_string()))?;
     let json = serde_json::to_string(&envelope).map_err(|e| ApiError::internal(e.to_string()))?;
@@ -649,7 +901,11 @@ async fn request_receiver_start(
 
     let ack = match tokio::time::timeout(RECEIVER_START_TIMEOUT, rx).await {
         Ok(Ok(value)) => value,
-        Ok(Err(_)) => return Err(ApiError::internal("receiver disconnected")),
+        Ok(Err(_)) => {
+            return Err(
+                ApiError::internal("receiver disconnected").with_code(ErrorCode::DeviceOffline)
+            );
+        }
         Err(_) => {
             state.pending_requests().remove(&request_id);
             return Err(ApiError::internal("receiver did not answer stream start"));
@@ -664,7 +920,7 @@ async fn request_receiver_start(
             ack.error.unwrap_or_else(|| "unknown".into())
         )));
     }
-    Ok(ack.bind_ports)
+    Ok(ack)
 }
 
 /// Fallback link ports assumed for an unmanaged (env-var-configured)
@@ -0,0 +1,416 @@
+//! Whole-account configuration export and import.
+//!
+//! GET  /api/export  — the caller's account as a [`TenantBundle`]
+//! POST /api/import  — apply a bundle to the caller's account
+//!
+//! A bundle carries senders (with their alert rules) and destinations,
+//! never secrets: no enrollment tokens, device keys or stream keys.
+//! Encoding profiles are the built-in presets (`strata_protocol::profiles`)
+//! and need no export. Import matches by name — senders by name,
+//! destinations by platform and name — and brings what it finds in line
+//! with the bundle (a destination's URL, a sender's rules), so importing
+//! the same bundle twice changes nothing. Everything else is
+//! created: new senders come back with enrollment tokens for their
+//! devices, and new destinations wait for a stream key to be set.
+
+use std::collections::HashSet;
+
+use axum::extract::State;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use strata_common::ids;
+use strata_protocol::api::{AlertRule, Capability, CreateSenderResponse};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// Bundle format written by [`export`]. Bumped on incompatible changes;
+/// import refuses bundles newer than this.
+pub const BUNDLE_VERSION: u32 = 1;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/export", get(export))
+        .route("/import", post(import))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TenantBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub senders: Vec<BundleSender>,
+    #[serde(default)]
+    pub destinations: Vec<BundleDestination>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BundleSender {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub alert_rules: Vec<AlertRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BundleDestination {
+    pub platform: String,
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ImportSummary {
+    /// Senders the import created, with the tokens to enroll them.
+    pub senders_created: Vec<CreateSenderResponse>,
+    /// Existing senders the bundle's senders were matched to.
+    pub senders_matched: usize,
+    pub destinations_created: usize,
+    /// Existing destinations matched; their URLs are updated in place.
+    pub destinations_matched: usize,
+    /// Alert rules added or replaced.
+    pub alert_rules: usize,
+}
+
+// ── Export ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/export",
+    tag = "tenant",
+    summary = "The caller's account as a bundle",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = TenantBundle), ApiError)
+)]
+async fn export(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<TenantBundle>, ApiError> {
+    user.require(Capability::TransferAccount)?;
+
+    let senders = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT id, name FROM senders WHERE owner_id = $1 ORDER BY created_at",
+    )
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .into_iter()
+    .map(|(id, name)| BundleSender {
+        name,
+        // Rules are stored as they were posted; ones that no longer parse
+        // are left behind rather than failing the export.
+        alert_rules: state
+            .alert_rules()
+            .get(&id)
+            .map(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|r| serde_json::from_value(r.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+    .collect();
+
+    let destinations = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT platform, name, url FROM destinations WHERE owner_id = $1 ORDER BY created_at",
+    )
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .into_iter()
+    .map(|(platform, name, url)| BundleDestination {
+        platform,
+        name,
+        url,
+    })
+    .collect();
+
+    super::audit::record_user(&state, &user, None, "tenant.export", None).await;
+
+    Ok(Json(TenantBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        senders,
+        destinations,
+    }))
+}
+
+// ── Import ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/import",
+    tag = "tenant",
+    summary = "Apply a bundle to the caller's account",
+    request_body = TenantBundle,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = ImportSummary), ApiError)
+)]
+async fn import(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(bundle): Json<TenantBundle>,
+) -> Result<Json<ImportSummary>, ApiError> {
+    user.require(Capability::TransferAccount)?;
+    validate(&bundle)?;
+
+    let existing_senders = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT id, name FROM senders WHERE owner_id = $1 ORDER BY created_at",
+    )
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    let existing_destinations: Vec<(String, (String, String))> =
+        sqlx::query_as::<_, (String, String, String)>(
+            "SELECT id, platform, name FROM destinations WHERE owner_id = $1 ORDER BY created_at",
+        )
+        .bind(&user.owner_id)
+        .fetch_all(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .into_iter()
+        .map(|(id, platform, name)| (id, (platform, name)))
+        .collect();
+
+    let sender_matches = match_by_key(
+        &existing_senders,
+        bundle.senders.iter().map(|s| s.name.clone()),
+    );
+    let destination_matches = match_by_key(
+        &existing_destinations,
+        bundle
+            .destinations
+            .iter()
+            .map(|d| (d.platform.clone(), d.name.clone())),
+    );
+
+    let mut summary = ImportSummary {
+        senders_created: Vec::new(),
+        senders_matched: 0,
+        destinations_created: 0,
+        destinations_matched: 0,
+        alert_rules: 0,
+    };
+    let mut sender_ids = Vec::with_capacity(bundle.senders.len());
+    let mut checks = Vec::new();
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    for (sender, matched) in bundle.senders.iter().zip(sender_matches) {
+        if let Some(id) = matched {
+            summary.senders_matched += 1;
+            sender_ids.push(id);
+            continue;
+        }
+        let sender_id = ids::sender_id();
+        let (token_hash, enrollment_token) = super::senders::enrollment_secret(&sender_id)?;
+        sqlx::query(
+            "INSERT INTO senders (id, owner_id, name, enrollment_token) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(&sender_id)
+        .bind(&user.owner_id)
+        .bind(&sender.name)
+        .bind(&token_hash)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+        summary.senders_created.push(CreateSenderResponse {
+            sender_id: sender_id.clone(),
+            enrollment_token,
+        });
+        sender_ids.push(sender_id.to_string());
+    }
+    for (destination, matched) in bundle.destinations.iter().zip(destination_matches) {
+        let id = match matched {
+            Some(id) => {
+                // Only the URL can differ; a changed URL needs a re-check.
+                let changed =
+                    sqlx::query("UPDATE destinations SET url = $2 WHERE id = $1 AND url <> $2")
+                        .bind(&id)
+                        .bind(&destination.url)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| ApiError::internal(e.to_string()))?
+                        .rows_affected();
+                summary.destinations_matched += 1;
+                if changed == 0 {
+                    continue;
+                }
+                id
+            }
+            None => {
+                let id = ids::destination_id();
+                sqlx::query(
+                    "INSERT INTO destinations (id, owner_id, platform, name, url) \
+                     VALUES ($1, $2, $3, $4, $5)",
+                )
+                .bind(&id)
+                .bind(&user.owner_id)
+                .bind(&destination.platform)
+                .bind(&destination.name)
+                .bind(&destination.url)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?;
+                summary.destinations_created += 1;
+                id.to_string()
+            }
+        };
+        checks.push(id);
+    }
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    // Rules merge by ID, like POST /api/senders/:id/alerts.
+    for (sender, id) in bundle.senders.iter().zip(&sender_ids) {
+        if sender.alert_rules.is_empty() {
+            continue;
+        }
+        let mut rules = state.alert_rules().entry(id.clone()).or_default();
+        for rule in &sender.alert_rules {
+            let mut rule = rule.clone();
+            let rule_id = rule
+                .id
+                .get_or_insert_with(|| uuid::Uuid::now_v7().to_string())
+                .clone();
+            let value =
+                serde_json::to_value(&rule).map_err(|e| ApiError::internal(e.to_string()))?;
+            match rules
+                .iter()
+                .position(|r| r.get("id").and_then(|v| v.as_str()) == Some(&rule_id))
+            {
+                Some(pos) => rules[pos] = value,
+                None => rules.push(value),
+            }
+            summary.alert_rules += 1;
+        }
+    }
+    for id in &checks {
+        super::destinations::spawn_check(&state, id);
+    }
+
+    tracing::info!(
+        owner = %user.owner_id,
+        senders_created = summary.senders_created.len(),
+        destinations_created = summary.destinations_created,
+        "tenant configuration imported"
+    );
+    super::audit::record_user(
+        &state,
+        &user,
+        None,
+        "tenant.import",
+        Some(format!(
+            "{} senders ({} new), {} destinations ({} new), {} alert rules",
+            bundle.senders.len(),
+            summary.senders_created.len(),
+            bundle.destinations.len(),
+            summary.destinations_created,
+            summary.alert_rules,
+        )),
+    )
+    .await;
+
+    Ok(Json(summary))
+}
+
+/// Reject a bundle before anything is written: a newer format, a
+/// destination URL the platform wouldn't accept, or a rule without a name.
+fn validate(bundle: &TenantBundle) -> Result<(), ApiError> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(ApiError::bad_request(format!(
+            "bundle version {} is newer than this control plane supports ({BUNDLE_VERSION})",
+            bundle.version
+        )));
+    }
+    for d in &bundle.destinations {
+        super::destinations::parse_ingest_url(&d.platform, &d.url)
+            .map_err(|e| ApiError::bad_request(format!("destination {:?}: {e}", d.name)))?;
+    }
+    for s in &bundle.senders {
+        if s.alert_rules.iter().any(|r| r.name.trim().is_empty()) {
+            return Err(ApiError::bad_request(format!(
+                "sender {:?}: alert rules need a name",
+                s.name.as_deref().unwrap_or("")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Pair each wanted key with an existing row of the same key, oldest
+/// first, using every row at most once. `None` means "create one".
+fn match_by_key<K: Eq + std::hash::Hash + Clone>(
+    existing: &[(String, K)],
+    wanted: impl IntoIterator<Item = K>,
+) -> Vec<Option<String>> {
+    let mut used = HashSet::new();
+    wanted
+        .into_iter()
+        .map(|key| {
+            let (id, _) = existing
+                .iter()
+                .find(|(id, k)| *k == key && !used.contains(id))?;
+            used.insert(id.clone());
+            Some(id.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_uses_each_existing_row_once() {
+        let existing = vec![
+            ("snd_a".to_string(), Some("Van".to_string())),
+            ("snd_b".to_string(), Some("Van".to_string())),
+            ("snd_c".to_string(), None),
+        ];
+        let wanted = [
+            Some("Van".to_string()),
+            Some("Van".to_string()),
+            Some("Van".to_string()),
+            Some("Truck".to_string()),
+        ];
+        assert_eq!(
+            match_by_key(&existing, wanted),
+            vec![Some("snd_a".into()), Some("snd_b".into()), None, None]
+        );
+    }
+
+    #[test]
+    fn newer_bundles_and_bad_urls_are_rejected() {
+        let mut bundle = TenantBundle {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            senders: vec![],
+            destinations: vec![BundleDestination {
+                platform: "custom_rtmp".into(),
+                name: "Backup ingest".into(),
+                url: "rtmp://ingest.example.com/live".into(),
+            }],
+        };
+        assert!(validate(&bundle).is_ok());
+
+        bundle.destinations[0].url = "not a url".into();
+        assert!(validate(&bundle).is_err());
+
+        bundle.destinations.clear();
+        bundle.version = BUNDLE_VERSION + 1;
+        assert!(validate(&bundle).is_err());
+    }
+}
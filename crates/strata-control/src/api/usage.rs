@@ -0,0 +1,467 @@
+//! Usage metering and billing export.
+//!
+//! GET /api/usage             — monthly per-sender rollups (JSON)
+//! GET /api/usage/export.csv  — the same rollups as CSV
+//!
+//! Both take `?from=YYYY-MM&to=YYYY-MM` (inclusive, UTC; default: the last
+//! twelve months) and an optional `sender_id`. Byte counts come from
+//! aggregated `stream.stats` telemetry, persisted by [`record_stream_bytes`].
+//!
+//! A stream is billed to every month its `[started_at, ended_at)` span
+//! overlaps: each month gets the minutes inside it and a time-proportional
+//! share of the bytes. Only a stream that is still running is measured up
+//! to now; one that ended without an `ended_at` bills no time.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::Deserialize;
+
+use strata_protocol::StreamStatsPayload;
+use strata_protocol::csv;
+use strata_protocol::models::UsageRollup;
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+/// How often a live stream's byte counter is written back to `streams`.
+/// Bounds both DB write load (stats arrive ~1 Hz) and how much volume a
+/// control-plane crash can lose from the bill.
+const USAGE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_usage))
+        .route("/export.csv", get(export_csv))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct UsageQuery {
+    from: Option<String>,
+    to: Option<String>,
+    sender_id: Option<String>,
+}
+
+/// Parse a `YYYY-MM` month into its first day.
+fn parse_month(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d").ok()
+}
+
+/// Resolve the query into a half-open `[start, end)` range of whole months.
+fn month_range(q: &UsageQuery) -> Result<(DateTime<Utc>, DateTime<Utc>), ApiError> {
+    let today = Utc::now().date_naive();
+    let this_month =
+        NaiveDate::from_ymd_opt(today.year(), today.month(), 1).expect("first of month is valid");
+
+    let to = match q.to.as_deref() {
+        Some(s) => parse_month(s).ok_or_else(|| ApiError::bad_request("to must be YYYY-MM"))?,
+        None => this_month,
+    };
+    let from = match q.from.as_deref() {
+        Some(s) => parse_month(s).ok_or_else(|| ApiError::bad_request("from must be YYYY-MM"))?,
+        None => to - Months::new(11),
+    };
+    if from > to {
+        return Err(ApiError::bad_request("from must not be after to"));
+    }
+
+    let start = from.and_hms_opt(0, 0, 0).expect("midnight").and_utc();
+    let end = (to + Months::new(1))
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight")
+        .and_utc();
+    Ok((start, end))
+}
+
+type RollupRow = (String, String, Option<String>, i64, f64, i64);
+
+/// CTEs cutting every stream of owner `$1` (only sender `$4`, unless NULL)
+/// into the months of `[$2, $3)` it overlaps; either bound may be NULL.
+/// Yields `slices(sender_id, name, month, secs, bytes)`: the seconds of the
+/// stream inside `month` and its time-proportional share of the bytes.
+/// Account deletion (api/me.rs) rolls the same slices up anonymously.
+pub(crate) const MONTHLY_SLICES: &str = "WITH spans AS ( \
+         SELECT s.sender_id, snd.name, s.total_bytes, s.started_at AS span_start, \
+                GREATEST(s.started_at, CASE \
+                    WHEN s.ended_at IS NOT NULL THEN s.ended_at \
+                    WHEN s.state IN ('starting', 'live', 'stopping') THEN now() \
+                    ELSE s.started_at \
+                END) AS span_end \
+         FROM streams s JOIN senders snd ON snd.id = s.sender_id \
+         WHERE snd.owner_id = $1 AND s.started_at IS NOT NULL \
+           AND ($3::TIMESTAMPTZ IS NULL OR s.started_at < $3) \
+           AND ($4::TEXT IS NULL OR s.sender_id = $4) \
+     ), cuts AS ( \
+         SELECT sp.sender_id, sp.name, sp.total_bytes, m.month, \
+                EXTRACT(EPOCH FROM sp.span_end - sp.span_start)::FLOAT8 AS span_secs, \
+                EXTRACT(EPOCH FROM LEAST(sp.span_end, (m.month + interval '1 month') AT TIME ZONE 'UTC') \
+                                 - GREATEST(sp.span_start, m.month AT TIME ZONE 'UTC'))::FLOAT8 AS secs \
+         FROM spans sp, generate_series( \
+             date_trunc('month', sp.span_start AT TIME ZONE 'UTC'), \
+             date_trunc('month', GREATEST(sp.span_start, sp.span_end - interval '1 microsecond') \
+                                 AT TIME ZONE 'UTC'), \
+             interval '1 month') AS m(month) \
+         WHERE ($2::TIMESTAMPTZ IS NULL OR m.month AT TIME ZONE 'UTC' >= $2) \
+           AND ($3::TIMESTAMPTZ IS NULL OR m.month AT TIME ZONE 'UTC' < $3) \
+     ), slices AS ( \
+         SELECT sender_id, name, month, secs, \
+                CASE WHEN span_secs > 0 THEN total_bytes * secs / span_secs \
+                     ELSE total_bytes END AS bytes \
+         FROM cuts \
+     )";
+
+async fn load_rollups(
+    state: &AppState,
+    user: &AuthUser,
+    q: &UsageQuery,
+) -> Result<Vec<UsageRollup>, ApiError> {
+    let (start, end) = month_range(q)?;
+    if let Some(ref sender_id) = q.sender_id {
+        super::senders::verify_ownership(state, user, sender_id).await?;
+    }
+
+    let rows = sqlx::query_as::<_, RollupRow>(&format!(
+        "{MONTHLY_SLICES} \
+         SELECT to_char(month, 'YYYY-MM'), sender_id, name, COUNT(*)::BIGINT, \
+                COALESCE(SUM(secs), 0)::FLOAT8 / 60.0, COALESCE(SUM(bytes), 0)::BIGINT \
+         FROM slices \
+         GROUP BY month, sender_id, name ORDER BY month, sender_id"
+    ))
+    .bind(&user.owner_id)
+    .bind(start)
+    .bind(end)
+    .bind(&q.sender_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(month, sender_id, sender_name, streams, minutes, bytes)| UsageRollup {
+                month,
+                sender_id,
+                sender_name,
+                stream_count: streams.max(0) as u64,
+                streamed_minutes: minutes.max(0.0),
+                total_bytes: bytes.max(0) as u64,
+            },
+        )
+        .collect())
+}
+
+// ── Rollups ─────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/usage",
+    tag = "usage",
+    summary = "Monthly per-sender rollups (JSON)",
+    params(UsageQuery),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<UsageRollup>), ApiError)
+)]
+async fn get_usage(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(q): Query<UsageQuery>,
+) -> Result<Json<Vec<UsageRollup>>, ApiError> {
+    Ok(Json(load_rollups(&state, &user, &q).await?))
+}
+
+// ── CSV Export ──────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/usage/export.csv",
+    tag = "usage",
+    summary = "Monthly per-sender rollups (CSV)",
+    params(UsageQuery),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "OK", body = String, content_type = "text/csv"),
+        ApiError
+    )
+)]
+async fn export_csv(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(q): Query<UsageQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let rollups = load_rollups(&state, &user, &q).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"strata-usage.csv\"",
+            ),
+        ],
+        render_csv(&rollups),
+    ))
+}
+
+fn render_csv(rollups: &[UsageRollup]) -> String {
+    let mut out =
+        String::from("month,sender_id,sender_name,streams,streamed_minutes,gigabytes\r\n");
+    for r in rollups {
+        writeln!(
+            out,
+            "{},{},{},{},{:.1},{:.3}\r",
+            r.month,
+            csv::field(&r.sender_id),
+            csv::field(r.sender_name.as_deref().unwrap_or("")),
+            r.stream_count,
+            r.streamed_minutes,
+            r.gigabytes(),
+        )
+        .unwrap();
+    }
+    out
+}
+
+// ── Metering ────────────────────────────────────────────────────────
+
+/// Unflushed metering of one live stream, kept in [`AppState::usage_meters`].
+#[derive(Debug)]
+pub struct UsageMeter {
+    sender_id: String,
+    /// Last cumulative `sent_bytes` seen per link id.
+    link_bytes: HashMap<u32, u64>,
+    /// Bytes carried since the last flush.
+    pending: u64,
+    flushed_at: Instant,
+}
+
+impl UsageMeter {
+    fn new(sender_id: &str, now: Instant) -> Self {
+        Self {
+            sender_id: sender_id.to_string(),
+            link_bytes: HashMap::new(),
+            pending: 0,
+            flushed_at: now,
+        }
+    }
+
+    /// Count what each link carried since its previous sample. Link
+    /// counters are cumulative but start over when the pipeline restarts,
+    /// so a counter that went backwards counts from zero.
+    fn observe(&mut self, stats: &StreamStatsPayload) {
+        for link in &stats.links {
+            let last = self.link_bytes.insert(link.id, link.sent_bytes);
+            let carried = match last {
+                Some(last) if link.sent_bytes >= last => link.sent_bytes - last,
+                _ => link.sent_bytes,
+            };
+            self.pending = self.pending.saturating_add(carried);
+        }
+    }
+
+    /// Take the link counters of `stats` as the starting point without
+    /// counting them.
+    fn baseline(&mut self, stats: &StreamStatsPayload) {
+        self.link_bytes = stats.links.iter().map(|l| (l.id, l.sent_bytes)).collect();
+    }
+}
+
+/// Fold one `stream.stats` sample from `sender_id` into the stream's
+/// metered volume.
+///
+/// Bytes are counted per link as the growth of its cumulative counter and
+/// added to `streams.total_bytes` at most every [`USAGE_FLUSH_INTERVAL`];
+/// [`finish_stream_usage`] writes the remainder when the stream ends. A
+/// stream first seen with bytes already metered (the control plane
+/// restarted mid-stream) takes its first sample as a baseline, so at most
+/// one interval's worth of volume goes unbilled rather than being billed
+/// twice.
+pub async fn record_stream_bytes(state: &AppState, sender_id: &str, stats: &StreamStatsPayload) {
+    let now = Instant::now();
+    if !state.usage_meters().contains_key(&stats.stream_id) {
+        let metered: Option<i64> = sqlx::query_scalar(
+            "SELECT total_bytes FROM streams WHERE id = $1 AND sender_id = $2",
+        )
+        .bind(&stats.stream_id)
+        .bind(sender_id)
+        .fetch_optional(state.pool())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(stream_id = %stats.stream_id, error = %e, "failed to read metered bytes");
+            None
+        });
+        let Some(metered) = metered else {
+            return;
+        };
+        let mut meter = UsageMeter::new(sender_id, now);
+        if metered > 0 {
+            meter.baseline(stats);
+        }
+        state
+            .usage_meters()
+            .entry(stats.stream_id.clone())
+            .or_insert(meter);
+    }
+
+    let pending = {
+        let Some(mut meter) = state.usage_meters().get_mut(&stats.stream_id) else {
+            return;
+        };
+        if meter.sender_id != sender_id {
+            return;
+        }
+        meter.observe(stats);
+        if now.duration_since(meter.flushed_at) < USAGE_FLUSH_INTERVAL {
+            return;
+        }
+        meter.flushed_at = now;
+        std::mem::take(&mut meter.pending)
+    };
+    add_stream_bytes(state, &stats.stream_id, sender_id, pending).await;
+}
+
+/// Write a stream's unflushed volume and stop metering it. Call on every
+/// path that takes a stream out of the live set.
+pub async fn finish_stream_usage(state: &AppState, stream_id: &str) {
+    let Some((_, meter)) = state.usage_meters().remove(stream_id) else {
+        return;
+    };
+    add_stream_bytes(state, stream_id, &meter.sender_id, meter.pending).await;
+}
+
+async fn add_stream_bytes(state: &AppState, stream_id: &str, sender_id: &str, bytes: u64) {
+    if bytes == 0 {
+        return;
+    }
+    if let Err(e) = sqlx::query(
+        "UPDATE streams SET total_bytes = total_bytes + $1 WHERE id = $2 AND sender_id = $3",
+    )
+    .bind(bytes.min(i64::MAX as u64) as i64)
+    .bind(stream_id)
+    .bind(sender_id)
+    .execute(state.pool())
+    .await
+    {
+        tracing::warn!(stream_id, error = %e, bytes, "failed to meter stream bytes");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(links: &[(u32, u64)]) -> StreamStatsPayload {
+        let links: Vec<_> = links
+            .iter()
+            .map(|&(id, sent_bytes)| {
+                serde_json::json!({
+                    "id": id, "interface": format!("wwan{id}"), "state": "Live",
+                    "rtt_ms": 40.0, "loss_rate": 0.0, "capacity_bps": 5_000_000,
+                    "sent_bytes": sent_bytes, "signal_dbm": null, "rsrp": null,
+                    "rsrq": null, "sinr": null, "cqi": null,
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "stream_id": "str_1", "uptime_s": 10, "encoder_bitrate_kbps": 4000,
+            "links": links,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn meter_counts_link_growth_across_counter_resets() {
+        let mut meter = UsageMeter::new("snd_1", Instant::now());
+        meter.observe(&stats(&[(0, 1_000), (1, 500)]));
+        meter.observe(&stats(&[(0, 3_000), (1, 900)]));
+        assert_eq!(meter.pending, 3_900);
+
+        // Pipeline restart: both counters start over below their last value.
+        meter.observe(&stats(&[(0, 200), (1, 100)]));
+        meter.observe(&stats(&[(0, 700), (1, 100)]));
+        assert_eq!(meter.pending, 3_900 + 300 + 500);
+
+        // A link that joins late counts from its first sample.
+        meter.observe(&stats(&[(0, 700), (1, 100), (2, 50)]));
+        assert_eq!(meter.pending, 4_750);
+    }
+
+    #[test]
+    fn baselined_meter_counts_only_later_growth() {
+        let mut meter = UsageMeter::new("snd_1", Instant::now());
+        meter.baseline(&stats(&[(0, 10_000)]));
+        meter.observe(&stats(&[(0, 10_400)]));
+        assert_eq!(meter.pending, 400);
+    }
+
+    fn rollup(name: Option<&str>) -> UsageRollup {
+        UsageRollup {
+            month: "2026-09".into(),
+            sender_id: "snd_1".into(),
+            sender_name: name.map(String::from),
+            stream_count: 3,
+            streamed_minutes: 95.0,
+            total_bytes: 4_500_000_000,
+        }
+    }
+
+    #[test]
+    fn csv_has_header_and_row_per_rollup() {
+        let csv = render_csv(&[rollup(Some("Truck 1")), rollup(None)]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "month,sender_id,sender_name,streams,streamed_minutes,gigabytes"
+        );
+        assert_eq!(lines[1], "2026-09,snd_1,Truck 1,3,95.0,4.500");
+        assert_eq!(lines[2], "2026-09,snd_1,,3,95.0,4.500");
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn csv_quotes_user_supplied_names() {
+        let csv = render_csv(&[rollup(Some("Cam \"A\", north"))]);
+        assert!(csv.contains(",\"Cam \"\"A\"\", north\","));
+    }
+
+    #[test]
+    fn month_range_defaults_and_validation() {
+        let q = UsageQuery {
+            from: Some("2026-01".into()),
+            to: Some("2026-03".into()),
+            sender_id: None,
+        };
+        let (start, end) = month_range(&q).unwrap();
+        assert_eq!(start.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-04-01T00:00:00+00:00");
+
+        let q = UsageQuery {
+            from: None,
+            to: Some("2026-12".into()),
+            sender_id: None,
+        };
+        let (start, _) = month_range(&q).unwrap();
+        assert_eq!(start.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let bad = UsageQuery {
+            from: Some("2026-13".into()),
+            to: None,
+            sender_id: None,
+        };
+        assert!(month_range(&bad).is_err());
+        let inverted = UsageQuery {
+            from: Some("2026-05".into()),
+            to: Some("2026-04".into()),
+            sender_id: None,
+        };
+        assert!(month_range(&inverted).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+//! User management for an account (admin only).
+//!
+//! GET  /api/users                     — users of the caller's account
+//! POST /api/users                     — invite a user (emails an invitation link)
+//! PUT  /api/users/{id}                — change role and/or disable/enable
+//! DELETE /api/users/{id}              — erase the user's personal data
+//! POST /api/users/{id}/reset-password — revoke the password, email a reset link
+//!
+//! Nobody hands out passwords: an invited user chooses theirs through an
+//! invitation link, and a reset sends a link to choose a new one (see
+//! `api/auth.rs`). Until then the account has a random placeholder
+//! password no one knows. The link's token is also returned, so an admin
+//! can pass it on when mail isn't configured.
+//!
+//! Invited users join the caller's account: their `owner_id` is the
+//! account owner, so every `owner_id`-scoped query shows them the same
+//! fleet. The account owner cannot be changed through these endpoints, and
+//! admins cannot demote or disable themselves.
+//!
+//! Erasure scrubs the row instead of deleting it: the email, password and
+//! preferences are cleared and the email is blanked out of the account's
+//! audit details, but the ID stays so the user's audit entries and notes
+//! still name a (now pseudonymous) actor. Erased users drop out of every
+//! listing.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+
+use strata_common::{auth, ids};
+use strata_protocol::api::{
+    Capability, InviteUserRequest, InviteUserResponse, ROLES, ResetPasswordResponse,
+    UpdateUserRequest, UserSummary,
+};
+
+use crate::api::auth::ApiError;
+use crate::state::AppState;
+
+use super::auth_extractor::AuthUser;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_users).post(invite_user))
+        .route("/{id}", put(update_user).delete(erase_user))
+        .route("/{id}/reset-password", post(reset_password))
+}
+
+type UserRow = (String, String, String, DateTime<Utc>, bool, bool);
+
+const USER_COLUMNS: &str = "id, email, role, created_at, disabled_at IS NOT NULL, owner_id IS NULL";
+
+fn summary((id, email, role, created_at, disabled, owner): UserRow) -> UserSummary {
+    UserSummary {
+        id,
+        email,
+        role,
+        created_at,
+        disabled,
+        owner,
+    }
+}
+
+fn validate_role(role: &str) -> Result<(), ApiError> {
+    if ROLES.contains(&role) {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request(format!(
+            "role must be one of {}",
+            ROLES.join(", ")
+        )))
+    }
+}
+
+/// Fetch a user of the caller's account.
+pub(crate) async fn load_user(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<UserSummary, ApiError> {
+    sqlx::query_as::<_, UserRow>(&format!(
+        "SELECT {USER_COLUMNS} FROM users \
+         WHERE id = $1 AND COALESCE(owner_id, id) = $2 AND erased_at IS NULL"
+    ))
+    .bind(id)
+    .bind(&user.owner_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .map(summary)
+    .ok_or_else(|| ApiError::not_found("user not found"))
+}
+
+/// Target of a change that only other admins may make: not the account
+/// owner, and not the caller.
+async fn load_managed_user(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<UserSummary, ApiError> {
+    let target = load_user(state, user, id).await?;
+    if target.owner {
+        return Err(ApiError::forbidden("the account owner cannot be changed"));
+    }
+    if target.id == user.user_id {
+        return Err(ApiError::bad_request("you cannot manage your own account"));
+    }
+    Ok(target)
+}
+
+// ── List ────────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    summary = "Users of the caller's account",
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = Vec<UserSummary>), ApiError)
+)]
+async fn list_users(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<Vec<UserSummary>>, ApiError> {
+    user.require(Capability::ManageUsers)?;
+
+    let rows = sqlx::query_as::<_, UserRow>(&format!(
+        "SELECT {USER_COLUMNS} FROM users \
+         WHERE COALESCE(owner_id, id) = $1 AND erased_at IS NULL \
+         ORDER BY owner_id IS NOT NULL, created_at"
+    ))
+    .bind(&user.owner_id)
+    .fetch_all(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(rows.into_iter().map(summary).collect()))
+}
+
+// ── Invite ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    summary = "Invite a user (emails an invitation link)",
+    request_body = InviteUserRequest,
+    security(("bearer" = [])),
+    responses((status = 201, description = "Created", body = InviteUserResponse), ApiError)
+)]
+async fn invite_user(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(body): Json<InviteUserRequest>,
+) -> Result<(StatusCode, Json<InviteUserResponse>), ApiError> {
+    user.require(Capability::ManageUsers)?;
+
+    let email = body.email.trim();
+    if email.is_empty() || !email.contains('@') {
+        return Err(ApiError::bad_request("invalid email"));
+    }
+    validate_role(&body.role)?;
+
+    let password_hash = placeholder_password_hash()?;
+    let user_id = ids::user_id();
+
+    let row = sqlx::query_as::<_, UserRow>(&format!(
+        "INSERT INTO users (id, email, password_hash, role, owner_id) \
+         VALUES ($1, $2, $3, $4, $5) RETURNING {USER_COLUMNS}"
+    ))
+    .bind(&user_id)
+    .bind(email)
+    .bind(&password_hash)
+    .bind(&body.role)
+    .bind(&user.owner_id)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("duplicate key") || e.to_string().contains("unique constraint") {
+            ApiError::conflict("email already registered")
+        } else {
+            ApiError::internal(e.to_string())
+        }
+    })?;
+
+    let invite_token = state
+        .jwt()
+        .create_token(&auth::Claims::invite(
+            &user_id,
+            &password_hash,
+            Utc::now().timestamp(),
+        ))
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let emailed = email_invite_link(&state, email, &invite_token).await;
+
+    tracing::info!(user_id = %user_id, owner = %user.owner_id, emailed, "user invited");
+    super::audit::record_user(
+        &state,
+        &user,
+        None,
+        "user.invite",
+        Some(format!("{email} as {}", body.role)),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(InviteUserResponse {
+            user: summary(row),
+            invite_token,
+            emailed,
+        }),
+    ))
+}
+
+/// Hash of a random password nobody is told: the account exists, but only
+/// a link can set a usable password.
+fn placeholder_password_hash() -> Result<String, ApiError> {
+    auth::hash_password(&ids::temporary_password()).map_err(|e| ApiError::internal(e.to_string()))
+}
+
+/// Email `to` their invitation link for `token`. Returns whether it was
+/// handed to the MTA.
+async fn email_invite_link(state: &AppState, to: &str, token: &str) -> bool {
+    let Some(mailer) = state.mailer() else {
+        return false;
+    };
+    let body = format!(
+        "You've been invited to a Strata account.\n\n\
+         Choose a password to sign in (the link works once, for a week):\n\n{}",
+        mailer.link(&format!("/invite/{token}"))
+    );
+    match mailer.send(to, "You're invited to Strata", &body).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to email invitation link");
+            false
+        }
+    }
+}
+
+// ── Update ──────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}",
+    tag = "users",
+    summary = "Change role and/or disable/enable",
+    params(("id" = String, Path, description = "User ID")),
+    request_body = UpdateUserRequest,
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = UserSummary), ApiError)
+)]
+async fn update_user(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    Json(body): Json<UpdateUserRequest>,
+) -> Result<Json<UserSummary>, ApiError> {
+    user.require(Capability::ManageUsers)?;
+    if let Some(role) = &body.role {
+        validate_role(role)?;
+    }
+    let target = load_managed_user(&state, &user, &id).await?;
+
+    let row = sqlx::query_as::<_, UserRow>(&format!(
+        "UPDATE users SET role = COALESCE($2, role), \
+         disabled_at = CASE WHEN $3::BOOLEAN IS NULL THEN disabled_at \
+                            WHEN $3 THEN COALESCE(disabled_at, now()) \
+                            ELSE NULL END \
+         WHERE id = $1 RETURNING {USER_COLUMNS}"
+    ))
+    .bind(&id)
+    .bind(&body.role)
+    .bind(body.disabled)
+    .fetch_one(state.pool())
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    let updated = summary(row);
+
+    if updated.role != target.role {
+        super::audit::record_user(
+            &state,
+            &user,
+            None,
+            "user.role",
+            Some(format!(
+                "{}: {} → {}",
+                updated.email, target.role, updated.role
+            )),
+        )
+        .await;
+    }
+    if updated.disabled != target.disabled {
+        let action = if updated.disabled {
+            "user.disable"
+        } else {
+            "user.enable"
+        };
+        super::audit::record_user(&state, &user, None, action, Some(updated.email.clone())).await;
+    }
+
+    Ok(Json(updated))
+}
+
+// ── Reset password ──────────────────────────────────────────────────
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/reset-password",
+    tag = "users",
+    operation_id = "users_reset_password",
+    summary = "Revoke the password, email a reset link",
+    params(("id" = String, Path, description = "User ID")),
+    security(("bearer" = [])),
+    responses((status = 200, description = "OK", body = ResetPasswordResponse), ApiError)
+)]
+async fn reset_password(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<ResetPasswordResponse>, ApiError> {
+    user.require(Capability::ManageUsers)?;
+    let target = load_managed_user(&state, &user, &id).await?;
+
+    let password_hash = placeholder_password_hash()?;
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(&id)
+        .bind(&password_hash)
+        .execute(state.pool())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let reset_token = state
+        .jwt()
+        .create_token(&auth::Claims::password_reset(
+            &id,
+            &password_hash,
+            Utc::now().timestamp(),
+        ))
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let emailed = super::auth::email_reset_link(&state, &target.email, &reset_token).await;
+
+    super::audit::record_user(
+        &state,
+        &user,
+        None,
+        "user.reset_password",
+        Some(target.email),
+    )
+    .await;
+
+    Ok(Json(ResetPasswordResponse {
+        reset_token,
+        emailed,
+    }))
+}
+
+// ── Erase ───────────────────────────────────────────────────────────
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "users",
+    summary = "Erase the user's personal data",
+    params(("id" = String, Path, description = "User ID")),
+    security(("bearer" = [])),
+    responses((status = 204, description = "No Content"), ApiError)
+)]
+async fn erase_user(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    user.require(Capability::ManageUsers)?;
+    let target = load_managed_user(&state, &user, &id).await?;
+    erase(&state, &user, &target).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Scrub an invited user's personal data (see the module docs). `user` is
+/// whoever asked — an admin, or the member themselves via `/api/me`.
+pub(crate) async fn erase(
+    state: &AppState,
+    user: &AuthUser,
+    target: &UserSummary,
+) -> Result<(), ApiError> {
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query(
+        "UPDATE audit_log SET detail = replace(detail, $2, '[erased]') \
+         WHERE owner_id = $1 AND strpos(detail, $2) > 0",
+    )
+    .bind(&user.owner_id)
+    .bind(&target.email)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    sqlx::query(
+        "UPDATE users SET email = 'erased+' || id || '@invalid', password_hash = '', \
+         preferences_json = NULL, disabled_at = COALESCE(disabled_at, now()), \
+         erased_at = now() \
+         WHERE id = $1",
+    )
+    .bind(&target.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    tracing::info!(user_id = %target.id, owner = %user.owner_id, "user erased");
+    super::audit::record_user(state, user, None, "user.erase", Some(target.id.clone())).await;
+    Ok(())
+}
@@ -0,0 +1,472 @@
+//! Receiver auto-scaling.
+//!
+//! When every receiver an owner has is at `max_streams`, a stream start
+//! asks the [`Autoscaler`] for another one: it creates a receiver row with
+//! a fresh enrollment token, has its [`Provisioner`] start a
+//! `strata-receiver` worker that enrolls with that token, and waits for
+//! the worker's WebSocket. Workers started this way carry a
+//! `provision_handle`; [`Autoscaler::reap_idle`] tears them down once they
+//! have carried no stream for the idle timeout. Receivers enrolled by hand
+//! are never touched.
+//!
+//! Load and worker counts come from the database, like
+//! `pick_receiver` in api/streams.rs — the streams table is what
+//! reconciliation keeps honest. A worker has to connect to the replica
+//! that provisioned it to be picked for the stream that asked for it.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use tokio::sync::{Mutex, MutexGuard};
+
+use strata_common::ids;
+
+use crate::state::AppState;
+
+/// How often idle workers are looked for.
+pub const REAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a new worker gets to enroll before the scale-up is abandoned.
+const ENROLL_TIMEOUT: Duration = Duration::from_secs(60);
+const ENROLL_POLL: Duration = Duration::from_millis(250);
+
+/// What a backend needs to start one receiver worker.
+#[derive(Debug, Clone)]
+pub struct WorkerSpec {
+    pub receiver_id: String,
+    /// Composite `<receiver_id>.<secret>` token the worker enrolls with.
+    pub enrollment_token: String,
+    /// Handles of every worker currently provisioned, across owners.
+    pub running: Vec<String>,
+}
+
+/// A backend that can start and stop receiver workers.
+pub trait Provisioner: Send + Sync {
+    /// Start a worker for `spec`. Returns the handle that
+    /// [`teardown`](Self::teardown) takes to stop it again.
+    fn provision<'a>(&'a self, spec: &'a WorkerSpec) -> BoxFuture<'a, anyhow::Result<String>>;
+
+    /// Stop and remove the worker behind `handle`.
+    fn teardown<'a>(&'a self, handle: &'a str) -> BoxFuture<'a, anyhow::Result<()>>;
+}
+
+/// Limits on what the autoscaler may start.
+#[derive(Debug, Clone)]
+pub struct ScalingPolicy {
+    /// Provisioned workers per owner, on top of hand-enrolled receivers.
+    pub max_workers: usize,
+    /// How long a worker may carry no stream before it is torn down.
+    pub idle_timeout: Duration,
+}
+
+impl Default for ScalingPolicy {
+    fn default() -> Self {
+        Self {
+            max_workers: 4,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Starts receiver workers on demand and retires idle ones.
+pub struct Autoscaler {
+    provisioner: Box<dyn Provisioner>,
+    policy: ScalingPolicy,
+    /// Held across a scale-up so concurrent stream starts share the new
+    /// worker instead of each provisioning one.
+    scaling: Mutex<()>,
+    /// When each provisioned worker was first seen without a stream.
+    idle_since: DashMap<String, Instant>,
+}
+
+impl Autoscaler {
+    pub fn new(provisioner: impl Provisioner + 'static, policy: ScalingPolicy) -> Self {
+        Self {
+            provisioner: Box::new(provisioner),
+            policy,
+            scaling: Mutex::new(()),
+            idle_since: DashMap::new(),
+        }
+    }
+
+    /// Auto-scaling configured from the environment, or `None` when
+    /// `AUTOSCALE_SSH_HOSTS` is unset.
+    ///
+    /// - `AUTOSCALE_SSH_HOSTS` — comma-separated ssh targets
+    ///   (`user@host`), one worker each
+    /// - `AUTOSCALE_CONTROL_URL` — the receiver WebSocket URL workers
+    ///   dial (required)
+    /// - `AUTOSCALE_IMAGE` — receiver image (default `strata-receiver:latest`)
+    /// - `AUTOSCALE_MAX_WORKERS` — per owner (default 4)
+    /// - `AUTOSCALE_IDLE_SECS` — idle time before teardown (default 300)
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Ok(hosts) = std::env::var("AUTOSCALE_SSH_HOSTS") else {
+            return Ok(None);
+        };
+        let hosts: Vec<String> = hosts
+            .split(',')
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .map(String::from)
+            .collect();
+        if hosts.is_empty() {
+            anyhow::bail!("AUTOSCALE_SSH_HOSTS lists no hosts");
+        }
+        let control_url = std::env::var("AUTOSCALE_CONTROL_URL").map_err(|_| {
+            anyhow::anyhow!("AUTOSCALE_CONTROL_URL must be set when AUTOSCALE_SSH_HOSTS is")
+        })?;
+        let image =
+            std::env::var("AUTOSCALE_IMAGE").unwrap_or_else(|_| "strata-receiver:latest".into());
+
+        let mut policy = ScalingPolicy::default();
+        if let Ok(v) = std::env::var("AUTOSCALE_MAX_WORKERS") {
+            policy.max_workers = v
+                .parse()
+                .map_err(|e| anyhow::anyhow!("invalid AUTOSCALE_MAX_WORKERS {v:?}: {e}"))?;
+        }
+        if let Ok(v) = std::env::var("AUTOSCALE_IDLE_SECS") {
+            let secs: u64 = v
+                .parse()
+                .map_err(|e| anyhow::anyhow!("invalid AUTOSCALE_IDLE_SECS {v:?}: {e}"))?;
+            policy.idle_timeout = Duration::from_secs(secs);
+        }
+
+        Ok(Some(Self::new(
+            DockerSshProvisioner::new(hosts, image, control_url),
+            policy,
+        )))
+    }
+
+    /// Serialize scale-ups. Hold the guard across re-checking capacity and
+    /// [`scale_up`](Self::scale_up).
+    pub async fn scale_lock(&self) -> MutexGuard<'_, ()> {
+        self.scaling.lock().await
+    }
+
+    /// Provision a receiver worker for `owner_id` and wait for it to
+    /// connect. Returns its receiver id.
+    pub async fn scale_up(&self, state: &AppState, owner_id: &str) -> anyhow::Result<String> {
+        let workers: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM receivers WHERE owner_id = $1 AND provision_handle IS NOT NULL",
+        )
+        .bind(owner_id)
+        .fetch_one(state.pool())
+        .await?;
+        if workers as usize >= self.policy.max_workers {
+            anyhow::bail!(
+                "owner already has {workers} provisioned receivers (limit {})",
+                self.policy.max_workers
+            );
+        }
+        let running: Vec<String> = sqlx::query_scalar(
+            "SELECT provision_handle FROM receivers WHERE provision_handle IS NOT NULL",
+        )
+        .fetch_all(state.pool())
+        .await?;
+
+        // The row goes in before the worker exists: enrollment looks it up.
+        // bind_host and capacity are filled in when the worker enrolls.
+        let receiver_id = ids::receiver_id();
+        let secret = ids::enrollment_token();
+        let token_hash =
+            strata_common::auth::hash_password(&ids::normalize_enrollment_token(&secret))
+                .map_err(|e| anyhow::anyhow!("hash enrollment token: {e}"))?;
+        sqlx::query(
+            "INSERT INTO receivers (id, owner_id, name, bind_host, link_ports, enrollment_token) \
+             VALUES ($1, $2, 'autoscaled', '', $3, $4)",
+        )
+        .bind(&receiver_id)
+        .bind(owner_id)
+        .bind(Vec::<i32>::new())
+        .bind(&token_hash)
+        .execute(state.pool())
+        .await?;
+
+        let spec = WorkerSpec {
+            receiver_id: receiver_id.clone(),
+            enrollment_token: ids::composite_enrollment_token(&receiver_id, &secret),
+            running,
+        };
+        let handle = match self.provisioner.provision(&spec).await {
+            Ok(handle) => handle,
+            Err(e) => {
+                delete_receiver(state, &receiver_id).await;
+                return Err(e);
+            }
+        };
+        sqlx::query("UPDATE receivers SET provision_handle = $1 WHERE id = $2")
+            .bind(&handle)
+            .bind(&receiver_id)
+            .execute(state.pool())
+            .await?;
+        tracing::info!(receiver_id = %receiver_id, owner_id, handle = %handle, "receiver worker provisioned");
+
+        let deadline = Instant::now() + ENROLL_TIMEOUT;
+        while !state.receivers().contains_key(&receiver_id) {
+            if Instant::now() >= deadline {
+                delete_receiver(state, &receiver_id).await;
+                if let Err(e) = self.provisioner.teardown(&handle).await {
+                    tracing::warn!(handle = %handle, error = %e, "failed to tear down receiver worker");
+                }
+                anyhow::bail!("receiver worker {receiver_id} did not connect in time");
+            }
+            tokio::time::sleep(ENROLL_POLL).await;
+        }
+        Ok(receiver_id)
+    }
+
+    /// Tear down provisioned workers that have carried no stream for the
+    /// idle timeout. Called every [`REAP_INTERVAL`].
+    pub async fn reap_idle(&self, state: &AppState) {
+        let rows = match sqlx::query_as::<_, (String, i64)>(
+            "SELECT r.id, (SELECT COUNT(*) FROM streams s \
+                           WHERE s.receiver_id = r.id AND s.state = ANY($1)) \
+             FROM receivers r WHERE r.provision_handle IS NOT NULL",
+        )
+        .bind(&crate::stream_state::ACTIVE_STATES[..])
+        .fetch_all(state.pool())
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::warn!(error = %e, "receiver reaper query failed");
+                return;
+            }
+        };
+
+        let now = Instant::now();
+        self.idle_since
+            .retain(|id, _| rows.iter().any(|(rid, _)| rid == id));
+        for (receiver_id, active) in rows {
+            if active > 0 {
+                self.idle_since.remove(&receiver_id);
+                continue;
+            }
+            let since = *self.idle_since.entry(receiver_id.clone()).or_insert(now);
+            if now.duration_since(since) < self.policy.idle_timeout {
+                continue;
+            }
+            self.idle_since.remove(&receiver_id);
+            self.retire(state, &receiver_id).await;
+        }
+    }
+
+    /// Delete an idle worker's row, then stop the worker. The conditional
+    /// delete loses to a stream start that just picked the worker, and
+    /// only one replica's delete returns the handle.
+    async fn retire(&self, state: &AppState, receiver_id: &str) {
+        let handle: Option<String> = match sqlx::query_scalar(
+            "DELETE FROM receivers r WHERE r.id = $1 AND r.provision_handle IS NOT NULL \
+               AND NOT EXISTS (SELECT 1 FROM streams s \
+                               WHERE s.receiver_id = r.id AND s.state = ANY($2)) \
+             RETURNING r.provision_handle",
+        )
+        .bind(receiver_id)
+        .bind(&crate::stream_state::ACTIVE_STATES[..])
+        .fetch_optional(state.pool())
+        .await
+        {
+            Ok(handle) => handle,
+            Err(e) => {
+                tracing::warn!(receiver_id, error = %e, "failed to retire idle receiver worker");
+                return;
+            }
+        };
+        let Some(handle) = handle else {
+            return;
+        };
+
+        state.receivers().remove(receiver_id);
+        state.receiver_status().remove(receiver_id);
+        match self.provisioner.teardown(&handle).await {
+            Ok(()) => {
+                tracing::info!(receiver_id, handle = %handle, "idle receiver worker torn down")
+            }
+            Err(e) => tracing::error!(
+                receiver_id,
+                handle = %handle,
+                error = %e,
+                "failed to tear down idle receiver worker — remove it by hand"
+            ),
+        }
+    }
+}
+
+async fn delete_receiver(state: &AppState, receiver_id: &str) {
+    let _ = sqlx::query("DELETE FROM receivers WHERE id = $1")
+        .bind(receiver_id)
+        .execute(state.pool())
+        .await;
+}
+
+// ── Docker over SSH ─────────────────────────────────────────────────
+
+/// How long one ssh invocation may take (an image pull included).
+const SSH_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Runs each worker as a `docker run` on one of a fixed set of hosts,
+/// reached with `ssh` (key auth, no prompts). Workers use host networking
+/// so senders reach the link ports directly, which limits a host to one
+/// worker. Handles are `<ssh target>/<container name>`.
+pub struct DockerSshProvisioner {
+    hosts: Vec<String>,
+    image: String,
+    control_url: String,
+}
+
+impl DockerSshProvisioner {
+    pub fn new(hosts: Vec<String>, image: String, control_url: String) -> Self {
+        Self {
+            hosts,
+            image,
+            control_url,
+        }
+    }
+
+    /// The first host not already running a worker.
+    fn free_host(&self, running: &[String]) -> Option<&str> {
+        self.hosts
+            .iter()
+            .map(String::as_str)
+            .find(|host| !running.iter().any(|h| handle_host(h) == Some(*host)))
+    }
+
+    /// The remote `docker run` for `spec` on `host`.
+    fn run_command(&self, host: &str, spec: &WorkerSpec) -> Vec<String> {
+        let bind_host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+        [
+            "docker",
+            "run",
+            "-d",
+            "--restart",
+            "unless-stopped",
+            "--network",
+            "host",
+            "--name",
+            &container_name(&spec.receiver_id),
+            &self.image,
+            "--control-url",
+            &self.control_url,
+            "--enrollment-token",
+            &spec.enrollment_token,
+            "--bind-host",
+            bind_host,
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+}
+
+impl Provisioner for DockerSshProvisioner {
+    fn provision<'a>(&'a self, spec: &'a WorkerSpec) -> BoxFuture<'a, anyhow::Result<String>> {
+        Box::pin(async move {
+            let host = self
+                .free_host(&spec.running)
+                .ok_or_else(|| anyhow::anyhow!("every autoscale host already runs a worker"))?;
+            ssh(host, &self.run_command(host, spec)).await?;
+            Ok(format!("{host}/{}", container_name(&spec.receiver_id)))
+        })
+    }
+
+    fn teardown<'a>(&'a self, handle: &'a str) -> BoxFuture<'a, anyhow::Result<()>> {
+        Box::pin(async move {
+            let (host, container) = handle
+                .rsplit_once('/')
+                .ok_or_else(|| anyhow::anyhow!("malformed worker handle {handle:?}"))?;
+            ssh(host, &["docker", "rm", "-f", container].map(String::from)).await
+        })
+    }
+}
+
+fn container_name(receiver_id: &str) -> String {
+    format!("strata-receiver-{receiver_id}")
+}
+
+/// The ssh target of a `<target>/<container>` handle.
+fn handle_host(handle: &str) -> Option<&str> {
+    handle.rsplit_once('/').map(|(host, _)| host)
+}
+
+/// Run `command` on `host`. ssh hands the remote shell one string, so
+/// every argument is quoted.
+async fn ssh(host: &str, command: &[String]) -> anyhow::Result<()> {
+    let remote = command
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let output = tokio::time::timeout(
+        SSH_TIMEOUT,
+        tokio::process::Command::new("ssh")
+            .args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10", host, "--"])
+            .arg(remote)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("ssh {host} timed out"))??;
+    if !output.status.success() {
+        anyhow::bail!(
+            "ssh {host} failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Single-quote `arg` for a POSIX shell.
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provisioner() -> DockerSshProvisioner {
+        DockerSshProvisioner::new(
+            vec!["deploy@203.0.113.5".into(), "deploy@203.0.113.6".into()],
+            "strata-receiver:latest".into(),
+            "wss://control.example/receiver/ws".into(),
+        )
+    }
+
+    #[test]
+    fn free_host_skips_hosts_running_a_worker() {
+        let p = provisioner();
+        assert_eq!(p.free_host(&[]), Some("deploy@203.0.113.5"));
+        let running = vec!["deploy@203.0.113.5/strata-receiver-rcv_a".to_string()];
+        assert_eq!(p.free_host(&running), Some("deploy@203.0.113.6"));
+        let running = vec![
+            running[0].clone(),
+            "deploy@203.0.113.6/strata-receiver-rcv_b".to_string(),
+        ];
+        assert_eq!(p.free_host(&running), None);
+    }
+
+    #[test]
+    fn worker_binds_the_host_it_runs_on() {
+        let spec = WorkerSpec {
+            receiver_id: "rcv_a".into(),
+            enrollment_token: "rcv_a.ABCD-EFGH".into(),
+            running: vec![],
+        };
+        let cmd = provisioner().run_command("deploy@203.0.113.6", &spec);
+        let arg = |flag: &str| {
+            let i = cmd.iter().position(|a| a == flag).unwrap();
+            cmd[i + 1].as_str()
+        };
+        assert_eq!(arg("--name"), "strata-receiver-rcv_a");
+        assert_eq!(arg("--enrollment-token"), "rcv_a.ABCD-EFGH");
+        assert_eq!(arg("--bind-host"), "203.0.113.6");
+    }
+
+    #[test]
+    fn shell_quote_survives_embedded_quotes() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
@@ -0,0 +1,149 @@
+//! Topic-based fan-out for dashboard WebSocket events.
+//!
+//! Every `(owner_id, topic)` pair with at least one subscriber gets its own
+//! broadcast channel. Publishing to a topic nobody watches costs a map
+//! lookup and nothing else, and each topic buffers independently: a tab
+//! that falls behind on a chatty per-sender telemetry topic drops stale
+//! link samples without losing fleet-level state changes.
+//!
+//! Keys include the owner, so a subscriber can only ever see events that
+//! were published for its own user — subscribing to another owner's
+//! `sender:<id>` just yields an empty channel.
+
+use dashmap::DashMap;
+use tokio::sync::broadcast;
+
+use strata_protocol::{DashboardEvent, DashboardTopic};
+
+/// Buffer for low-rate topics where every event matters (state changes).
+const FLEET_TOPIC_CAPACITY: usize = 256;
+/// Buffer for per-second telemetry topics — samples are superseded within
+/// a second or two, so a small buffer is enough and lag is cheap.
+const TELEMETRY_TOPIC_CAPACITY: usize = 32;
+
+fn capacity_for(topic: &DashboardTopic) -> usize {
+    match topic {
+        DashboardTopic::Fleet | DashboardTopic::Alerts => FLEET_TOPIC_CAPACITY,
+        DashboardTopic::Sender(_) | DashboardTopic::Stream(_) => TELEMETRY_TOPIC_CAPACITY,
+    }
+}
+
+type TopicKey = (String, DashboardTopic);
+
+#[derive(Default)]
+pub struct DashboardHub {
+    topics: DashMap<TopicKey, broadcast::Sender<DashboardEvent>>,
+}
+
+impl DashboardHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish `event` on its topic for `owner_id`. Dropped when nobody
+    /// is subscribed.
+    pub fn publish(&self, owner_id: &str, event: DashboardEvent) {
+        let key = (owner_id.to_string(), event.topic());
+        // The map guard must be gone before `remove_if` on the same shard.
+        let orphaned = match self.topics.get(&key) {
+            Some(tx) => tx.send(event).is_err(),
+            None => return,
+        };
+        if orphaned {
+            // A subscriber whose forwarder was aborted before `release`
+            // could observe it leaves an empty channel behind; reap it here.
+            self.topics
+                .remove_if(&key, |_, tx| tx.receiver_count() == 0);
+        }
+    }
+
+    /// Subscribe to one topic of `owner_id`'s events.
+    pub fn subscribe(
+        &self,
+        owner_id: &str,
+        topic: DashboardTopic,
+    ) -> broadcast::Receiver<DashboardEvent> {
+        let capacity = capacity_for(&topic);
+        self.topics
+            .entry((owner_id.to_string(), topic))
+            .or_insert_with(|| broadcast::channel(capacity).0)
+            .subscribe()
+    }
+
+    /// Drop the channel for a topic once its last receiver is gone. Called
+    /// after a subscriber goes away so idle topics don't accumulate.
+    pub fn release(&self, owner_id: &str, topic: &DashboardTopic) {
+        self.topics
+            .remove_if(&(owner_id.to_string(), topic.clone()), |_, tx| {
+                tx.receiver_count() == 0
+            });
+    }
+
+    /// Number of live topic channels (for tests and metrics).
+    pub fn topic_count(&self) -> usize {
+        self.topics.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_protocol::StreamStatsPayload;
+
+    fn stats(sender_id: &str) -> DashboardEvent {
+        DashboardEvent::StreamStats(StreamStatsPayload {
+            stream_id: "str_x".into(),
+            sender_id: sender_id.into(),
+            uptime_s: 1,
+            encoder_bitrate_kbps: 1000,
+            timestamp_ms: 0,
+            links: vec![],
+            sender_metrics: None,
+            receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn delivers_only_to_matching_topic_and_owner() {
+        let hub = DashboardHub::new();
+        let mut mine = hub.subscribe("usr_a", DashboardTopic::Sender("snd_1".into()));
+        let mut other_sender = hub.subscribe("usr_a", DashboardTopic::Sender("snd_2".into()));
+        let mut other_owner = hub.subscribe("usr_b", DashboardTopic::Sender("snd_1".into()));
+
+        hub.publish("usr_a", stats("snd_1"));
+
+        assert!(matches!(
+            mine.try_recv(),
+            Ok(DashboardEvent::StreamStats(_))
+        ));
+        assert!(other_sender.try_recv().is_err());
+        assert!(other_owner.try_recv().is_err());
+    }
+
+    #[test]
+    fn publish_without_subscribers_creates_nothing() {
+        let hub = DashboardHub::new();
+        hub.publish("usr_a", stats("snd_1"));
+        assert_eq!(hub.topic_count(), 0);
+    }
+
+    #[test]
+    fn release_drops_idle_topics_only() {
+        let hub = DashboardHub::new();
+        let topic = DashboardTopic::Fleet;
+        let rx1 = hub.subscribe("usr_a", topic.clone());
+        let rx2 = hub.subscribe("usr_a", topic.clone());
+
+        drop(rx1);
+        hub.release("usr_a", &topic);
+        assert_eq!(hub.topic_count(), 1, "one receiver still attached");
+
+        drop(rx2);
+        hub.release("usr_a", &topic);
+        assert_eq!(hub.topic_count(), 0);
+    }
+}
@@ -42,14 +42,14 @@ pub async fn seed_dev_data(pool: &PgPool) -> anyhow::Result<()> {
         .bind("usr_00000000-0000-0000-0000-000000000001")
         .bind("dev@strata.local")
         .bind(&password_hash)
-        .bind("operator")
+        .bind("admin")
         .execute(pool)
         .await?;
 
     // Hash the enrollment token the same way the create_sender API does,
     // so that the agent can authenticate with the raw token.
-    // Short, typeable enrollment token for dev (matches the new XXXX-XXXX format)
-    let dev_token_normalized = strata_common::ids::normalize_enrollment_token("DEV1-TEST");
+    // Short, typeable enrollment token for dev, check character included.
+    let dev_token_normalized = strata_common::ids::normalize_enrollment_token("DEVT-ESTD");
     let enrollment_token_hash = strata_common::auth::hash_password(&dev_token_normalized)?;
 
     sqlx::query(
@@ -64,8 +64,8 @@ pub async fn seed_dev_data(pool: &PgPool) -> anyhow::Result<()> {
     .execute(pool)
     .await?;
 
-    // Dev receiver: enrollment token RCV1-TEST
-    let rcv_token_normalized = strata_common::ids::normalize_enrollment_token("RCV1-TEST");
+    // Dev receiver: enrollment token RCVT-ESTN
+    let rcv_token_normalized = strata_common::ids::normalize_enrollment_token("RCVT-ESTN");
     let rcv_token_hash = strata_common::auth::hash_password(&rcv_token_normalized)?;
 
     sqlx::query(
@@ -0,0 +1,160 @@
+//! Cross-replica fan-out of dashboard events over Postgres LISTEN/NOTIFY.
+//!
+//! Each replica only sees the devices whose WebSockets it terminates, but
+//! a browser behind a load balancer can land on any replica. So every
+//! event published locally is also sent as a `NOTIFY` on [`CHANNEL`], and
+//! every replica `LISTEN`s there and republishes what its peers sent into
+//! its own [`DashboardHub`](crate::dashboard_hub::DashboardHub). Events
+//! carry the publishing replica's ID so a replica skips its own echoes.
+//!
+//! Delivery is best effort, like the in-process hub: notifications sent
+//! while a listener reconnects are lost, and an event too large for a
+//! notification payload stays on the replica that produced it. Commands
+//! to a device still have to reach the replica holding its socket.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use tokio::sync::mpsc;
+
+use strata_protocol::DashboardEvent;
+
+use crate::state::AppState;
+
+/// Postgres notification channel shared by all replicas.
+pub const CHANNEL: &str = "strata_dashboard";
+
+/// Postgres rejects notification payloads of 8000 bytes or more.
+const MAX_PAYLOAD: usize = 7999;
+
+/// Events queued for `NOTIFY` before new ones are dropped. Telemetry
+/// dominates and is superseded every second, so a stalled database costs
+/// peers a few samples rather than growing memory.
+const OUTBOUND_CAPACITY: usize = 1024;
+
+/// Pause before listening again after the listener connection failed.
+const RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    /// Replica that published the event.
+    origin: String,
+    owner_id: String,
+    event: DashboardEvent,
+}
+
+/// The publishing half of the bus, held in [`AppState`] once started.
+pub struct EventBus {
+    origin: String,
+    outbound: mpsc::Sender<String>,
+}
+
+impl EventBus {
+    /// Queue `event` for the other replicas.
+    pub fn forward(&self, owner_id: &str, event: &DashboardEvent) {
+        let Some(payload) = encode(&self.origin, owner_id, event) else {
+            tracing::debug!(owner_id, "dashboard event too large to notify peers");
+            return;
+        };
+        if self.outbound.try_send(payload).is_err() {
+            tracing::debug!(owner_id, "event bus backlog full, dropping event for peers");
+        }
+    }
+}
+
+fn encode(origin: &str, owner_id: &str, event: &DashboardEvent) -> Option<String> {
+    let payload = serde_json::to_string(&Envelope {
+        origin: origin.to_string(),
+        owner_id: owner_id.to_string(),
+        event: event.clone(),
+    })
+    .ok()?;
+    (payload.len() <= MAX_PAYLOAD).then_some(payload)
+}
+
+/// Join the bus: listen for peers' events and forward this replica's
+/// from now on. Fails if the listener connection can't be opened.
+pub async fn start(state: &AppState) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(state.pool()).await?;
+    listener.listen(CHANNEL).await?;
+
+    let origin = uuid::Uuid::now_v7().to_string();
+    let (outbound, rx) = mpsc::channel(OUTBOUND_CAPACITY);
+    if !state.set_event_bus(EventBus {
+        origin: origin.clone(),
+        outbound,
+    }) {
+        anyhow::bail!("event bus already started");
+    }
+
+    tokio::spawn(notify_loop(state.pool().clone(), rx));
+    tokio::spawn(listen_loop(state.clone(), listener, origin));
+    tracing::info!(channel = CHANNEL, "dashboard event bus started");
+    Ok(())
+}
+
+async fn notify_loop(pool: PgPool, mut rx: mpsc::Receiver<String>) {
+    while let Some(payload) = rx.recv().await {
+        if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(CHANNEL)
+            .bind(&payload)
+            .execute(&pool)
+            .await
+        {
+            tracing::warn!(error = %e, "failed to notify peers of dashboard event");
+        }
+    }
+}
+
+async fn listen_loop(state: AppState, mut listener: PgListener, origin: String) {
+    loop {
+        // `recv` reconnects on its own after the connection drops.
+        let notification = match listener.recv().await {
+            Ok(n) => n,
+            Err(e) => {
+                tracing::warn!(error = %e, "event bus listener error");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        match serde_json::from_str::<Envelope>(notification.payload()) {
+            Ok(envelope) if envelope.origin == origin => {}
+            Ok(envelope) => state.publish_dashboard_local(&envelope.owner_id, envelope.event),
+            Err(e) => tracing::warn!(error = %e, "malformed event bus payload"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_protocol::StreamStatsPayload;
+
+    #[test]
+    fn oversized_events_stay_local() {
+        let small = DashboardEvent::SenderStatus {
+            sender_id: "snd_1".into(),
+            online: true,
+            status: None,
+        };
+        let payload = encode("r1", "usr_a", &small).unwrap();
+        let envelope: Envelope = serde_json::from_str(&payload).unwrap();
+        assert_eq!(envelope.owner_id, "usr_a");
+
+        let huge = DashboardEvent::StreamStats(StreamStatsPayload {
+            stream_id: "x".repeat(MAX_PAYLOAD),
+            sender_id: "snd_1".into(),
+            uptime_s: 1,
+            encoder_bitrate_kbps: 1000,
+            timestamp_ms: 0,
+            links: vec![],
+            sender_metrics: None,
+            receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
+        });
+        assert!(encode("r1", "usr_a", &huge).is_none());
+    }
+}
@@ -4,8 +4,15 @@
 //! can be used by integration tests (and potentially embedded in other
 //! binaries).
 
+pub mod acme;
 pub mod api;
+pub mod autoscale;
+pub mod dashboard_hub;
 pub mod db;
+pub mod event_bus;
+pub mod live_state;
+pub mod mailer;
+pub mod retention;
 pub mod state;
 pub mod stream_state;
 pub mod ws_agent;
@@ -0,0 +1,140 @@
+//! In-memory registry of each sender's running stream.
+//!
+//! Fed by `stream.stats` from the agent socket, it holds what a page needs
+//! to draw a sender before the next telemetry tick: the current stream,
+//! its latest stats, and a link table that keeps the last sample of every
+//! link seen during the stream — a modem that drops out of the report is
+//! still listed, with the values it had when it went quiet.
+//!
+//! Entries live only as long as the stream: a new stream starts a fresh
+//! link table, and ending the stream (or the agent disconnecting) drops
+//! the entry.
+
+use std::collections::BTreeMap;
+
+use dashmap::DashMap;
+
+use strata_protocol::StreamStatsPayload;
+use strata_protocol::telemetry::LinkSample;
+
+/// What the control plane knows about one sender's running stream.
+#[derive(Debug, Clone)]
+pub struct SenderLive {
+    /// Latest stats, stamped with the sender ID and receive time.
+    pub stats: StreamStatsPayload,
+    /// Last sample per link interface seen during `stats.stream_id`.
+    pub links: BTreeMap<String, LinkSample>,
+}
+
+impl SenderLive {
+    pub fn stream_id(&self) -> &str {
+        &self.stats.stream_id
+    }
+}
+
+#[derive(Default)]
+pub struct LiveRegistry {
+    senders: DashMap<String, SenderLive>,
+}
+
+impl LiveRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold a `stream.stats` report into the sender's entry.
+    pub fn record_stats(&self, sender_id: &str, stats: StreamStatsPayload) {
+        let mut entry = self
+            .senders
+            .entry(sender_id.to_string())
+            .or_insert_with(|| SenderLive {
+                stats: stats.clone(),
+                links: BTreeMap::new(),
+            });
+        if entry.stats.stream_id != stats.stream_id {
+            entry.links.clear();
+        }
+        for link in &stats.links {
+            entry.links.insert(link.interface.clone(), link.clone());
+        }
+        entry.stats = stats;
+    }
+
+    /// The sender's entry, if it has a stream reporting telemetry.
+    pub fn get(&self, sender_id: &str) -> Option<SenderLive> {
+        self.senders.get(sender_id).map(|e| e.value().clone())
+    }
+
+    /// Latest stats of the sender's running stream.
+    pub fn stats(&self, sender_id: &str) -> Option<StreamStatsPayload> {
+        self.senders.get(sender_id).map(|e| e.stats.clone())
+    }
+
+    /// Latest stats of every sender with a running stream.
+    pub fn all_stats(&self) -> Vec<StreamStatsPayload> {
+        self.senders.iter().map(|e| e.stats.clone()).collect()
+    }
+
+    /// Forget a stream that ended, whichever sender was running it.
+    pub fn end_stream(&self, stream_id: &str) {
+        self.senders.retain(|_, live| live.stream_id() != stream_id);
+    }
+
+    /// Forget everything about a sender (its agent disconnected).
+    pub fn remove(&self, sender_id: &str) {
+        self.senders.remove(sender_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_protocol::telemetry::Millis;
+
+    fn link(interface: &str, rtt_ms: f64) -> LinkSample {
+        let mut link = LinkSample::from_bonding_report(&serde_json::json!({}));
+        link.interface = interface.into();
+        link.rtt_ms = Millis(rtt_ms);
+        link
+    }
+
+    fn stats(stream_id: &str, links: Vec<LinkSample>) -> StreamStatsPayload {
+        StreamStatsPayload {
+            stream_id: stream_id.into(),
+            sender_id: "snd_1".into(),
+            uptime_s: 1,
+            encoder_bitrate_kbps: 1000,
+            timestamp_ms: 0,
+            links,
+            sender_metrics: None,
+            receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
+        }
+    }
+
+    #[test]
+    fn link_table_keeps_quiet_links_until_the_stream_changes() {
+        let live = LiveRegistry::new();
+        live.record_stats(
+            "snd_1",
+            stats("str_a", vec![link("wwan0", 40.0), link("wwan1", 60.0)]),
+        );
+        live.record_stats("snd_1", stats("str_a", vec![link("wwan0", 45.0)]));
+
+        let entry = live.get("snd_1").unwrap();
+        assert_eq!(entry.stats.links.len(), 1);
+        assert_eq!(entry.links.len(), 2, "wwan1 stays with its last sample");
+        assert_eq!(entry.links["wwan0"].rtt_ms, Millis(45.0));
+
+        live.record_stats("snd_1", stats("str_b", vec![link("eth0", 5.0)]));
+        let entry = live.get("snd_1").unwrap();
+        assert_eq!(entry.stream_id(), "str_b");
+        assert_eq!(entry.links.keys().collect::<Vec<_>>(), ["eth0"]);
+
+        live.end_stream("str_b");
+        assert!(live.get("snd_1").is_none());
+    }
+}
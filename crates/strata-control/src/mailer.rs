@@ -0,0 +1,144 @@
+//! Outgoing email.
+//!
+//! Messages go to the host's mail transfer agent through a
+//! `sendmail`-compatible binary (postfix, msmtp-mta, …), which owns
+//! relaying, TLS and retries — the control plane only composes them. Mail
+//! is off unless `MAIL_FROM` is set; without it, invitation and reset links
+//! are only shown to the admin who created them.
+
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+
+/// How long the MTA gets to accept a message.
+const SENDMAIL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Hands messages to the local MTA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mailer {
+    from: String,
+    dashboard_url: String,
+    sendmail: String,
+}
+
+impl Mailer {
+    /// Configure from the environment; `None` when mail is off.
+    ///
+    /// - `MAIL_FROM` — sender address (enables mail)
+    /// - `DASHBOARD_URL` — public base URL of the dashboard, for links in
+    ///   messages (required with `MAIL_FROM`)
+    /// - `SENDMAIL_PATH` — the MTA binary (default `/usr/sbin/sendmail`)
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Option<Self>> {
+        let Some(from) = lookup("MAIL_FROM") else {
+            return Ok(None);
+        };
+        if !from.contains('@') || has_line_break(&from) {
+            anyhow::bail!("invalid MAIL_FROM {from:?}");
+        }
+        let dashboard_url = lookup("DASHBOARD_URL")
+            .ok_or_else(|| anyhow::anyhow!("DASHBOARD_URL must be set when MAIL_FROM is"))?;
+        if !dashboard_url.starts_with("https://") && !dashboard_url.starts_with("http://") {
+            anyhow::bail!("DASHBOARD_URL must be an http(s) URL, got {dashboard_url:?}");
+        }
+        Ok(Some(Self {
+            from,
+            dashboard_url: dashboard_url.trim_end_matches('/').to_string(),
+            sendmail: lookup("SENDMAIL_PATH").unwrap_or_else(|| "/usr/sbin/sendmail".into()),
+        }))
+    }
+
+    /// Absolute dashboard URL of `path` (which starts with `/`).
+    pub fn link(&self, path: &str) -> String {
+        format!("{}{path}", self.dashboard_url)
+    }
+
+    /// Send a plain-text message to `to`.
+    pub async fn send(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()> {
+        let message = compose(&self.from, to, subject, body)?;
+        let mut child = tokio::process::Command::new(&self.sendmail)
+            .args(["-t", "-i"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("cannot run {}: {e}", self.sendmail))?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        stdin.write_all(message.as_bytes()).await?;
+        drop(stdin);
+        let output = tokio::time::timeout(SENDMAIL_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| anyhow::anyhow!("{} timed out", self.sendmail))??;
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} failed ({}): {}",
+                self.sendmail,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn has_line_break(s: &str) -> bool {
+    s.contains(['\r', '\n'])
+}
+
+/// RFC 5322 message for `sendmail -t`, which takes the recipients from the
+/// headers. A line break in a header value would let it add recipients or
+/// headers of its own, so those are refused.
+fn compose(from: &str, to: &str, subject: &str, body: &str) -> anyhow::Result<String> {
+    if [from, to, subject].into_iter().any(has_line_break) {
+        anyhow::bail!("line break in a mail header");
+    }
+    Ok(format!(
+        "From: {from}\r\nTo: {to}\r\nSubject: {subject}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n\
+         {body}\r\n"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailer(vars: &[(&str, &str)]) -> anyhow::Result<Option<Mailer>> {
+        Mailer::from_lookup(|key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn mail_is_off_without_a_sender_and_needs_a_dashboard_url() {
+        assert_eq!(mailer(&[]).unwrap(), None);
+        assert!(mailer(&[("MAIL_FROM", "strata@example.com")]).is_err());
+
+        let m = mailer(&[
+            ("MAIL_FROM", "strata@example.com"),
+            ("DASHBOARD_URL", "https://strata.example.com/"),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            m.link("/invite/abc"),
+            "https://strata.example.com/invite/abc"
+        );
+        assert_eq!(m.sendmail, "/usr/sbin/sendmail");
+    }
+
+    #[test]
+    fn header_injection_is_refused() {
+        assert!(compose("a@x", "b@x\r\nBcc: c@x", "Hi", "body").is_err());
+        assert!(compose("a@x", "b@x", "Hi\nBcc: c@x", "body").is_err());
+        let message = compose("a@x", "b@x", "Hi", "line one\r\nline two").unwrap();
+        assert!(message.starts_with("From: a@x\r\nTo: b@x\r\nSubject: Hi\r\n"));
+        assert!(message.ends_with("\r\n\r\nline one\r\nline two\r\n"));
+    }
+}
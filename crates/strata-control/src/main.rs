@@ -3,8 +3,9 @@
 //! Single binary that runs:
 //! - REST API for the web dashboard
 //! - WebSocket endpoint for sender agents
-//! - WebSocket endpoint for live dashboard updates
-//! - Receiver worker process spawner
+//! - WebSocket endpoint for live dashboard updates (fanned out across
+//!   replicas over Postgres LISTEN/NOTIFY)
+//! - Receiver worker process spawner, with on-demand auto-scaling
 
 use std::net::SocketAddr;
 
@@ -15,7 +16,10 @@ use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::EnvFilter;
 
-use strata_control::{api, db, state, stream_state, ws_agent, ws_dashboard, ws_receiver};
+use strata_control::{
+    api, autoscale, db, event_bus, mailer, retention, state, stream_state, ws_agent, ws_dashboard,
+    ws_receiver,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -52,6 +56,64 @@ async fn main() -> anyhow::Result<()> {
     // ── Shared state ────────────────────────────────────────────
     let state = state::AppState::new(pool, jwt);
 
+    // ── Secrets at rest ─────────────────────────────────────────
+    // A key of its own, not derived from the JWT seed. Without one the
+    // control plane can't store certificate private keys; an ephemeral key
+    // would lose them on restart.
+    match std::env::var("SECRETS_KEY_B64") {
+        Ok(key) => {
+            let secrets = strata_common::auth::SecretBox::from_key_b64(&key)
+                .map_err(|e| anyhow::anyhow!("invalid SECRETS_KEY_B64: {e}"))?;
+            state.set_secrets(secrets);
+            api::certificates::seal_stored_keys(&state).await?;
+        }
+        Err(_) => tracing::warn!(
+            "SECRETS_KEY_B64 not set — device certificates can't be stored or pushed"
+        ),
+    }
+
+    // ── Password rules ──────────────────────────────────────────
+    // Loaded before serving so a bad breach list fails the start, not the
+    // first registration.
+    state.set_password_policy(api::auth::password_policy_from_env()?);
+
+    // ── Replica event bus ───────────────────────────────────────
+    // Keeps dashboards in sync when several replicas run behind a load
+    // balancer. EVENT_BUS=local opts out for a single-process deployment.
+    if std::env::var("EVENT_BUS").is_ok_and(|v| v == "local") {
+        tracing::info!("EVENT_BUS=local — dashboard events stay in this process");
+    } else {
+        event_bus::start(&state).await?;
+    }
+
+    // ── Outgoing mail ───────────────────────────────────────────
+    // Invitation and password reset links. Off unless MAIL_FROM is set;
+    // admins then hand invitation links over themselves.
+    match mailer::Mailer::from_env()? {
+        Some(mailer) => {
+            tracing::info!("outgoing mail enabled");
+            state.set_mailer(mailer);
+        }
+        None => tracing::info!("MAIL_FROM not set — invitation and reset links are not emailed"),
+    }
+
+    // ── Receiver auto-scaling ───────────────────────────────────
+    // Off unless AUTOSCALE_SSH_HOSTS names hosts to start workers on.
+    if let Some(autoscaler) = autoscale::Autoscaler::from_env()? {
+        tracing::info!("receiver auto-scaling enabled");
+        state.set_autoscaler(autoscaler);
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(autoscale::REAP_INTERVAL);
+            loop {
+                tick.tick().await;
+                if let Some(autoscaler) = state.autoscaler() {
+                    autoscaler.reap_idle(&state).await;
+                }
+            }
+        });
+    }
+
     // ── Stream-state sweeper ────────────────────────────────────
     // Backstop for devices that never reconnect: a WS drop no longer
     // orphan-marks streams, so something must end them when the device is
@@ -67,6 +129,51 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // ── Data retention janitor ──────────────────────────────────
+    // Telemetry, audit history and per-stream records each age out on
+    // their own schedule (RETENTION_*_DAYS, see retention.rs).
+    {
+        let policy = retention::RetentionPolicy::from_env()?;
+        tracing::info!(
+            telemetry_days = policy.telemetry.num_days(),
+            audit_days = policy.audit.num_days(),
+            recordings_days = policy.recordings.num_days(),
+            "data retention policy"
+        );
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(retention::JANITOR_INTERVAL);
+            loop {
+                tick.tick().await;
+                retention::enforce(&state, &policy).await;
+            }
+        });
+    }
+
+    // ── Stream scheduler ────────────────────────────────────────
+    {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(api::schedules::TICK_INTERVAL);
+            loop {
+                tick.tick().await;
+                api::schedules::tick(&state).await;
+            }
+        });
+    }
+
+    // ── Certificate expiry ──────────────────────────────────────
+    {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(api::certificates::EXPIRY_CHECK_INTERVAL);
+            loop {
+                tick.tick().await;
+                api::certificates::check_expiry(&state).await;
+            }
+        });
+    }
+
     // ── Router ──────────────────────────────────────────────────
     // Dashboard: serve the trunk-built WASM SPA from a directory.
     // DASHBOARD_DIR defaults to ../strata-dashboard/dist (dev) or /app/dashboard (Docker).
@@ -115,6 +222,7 @@ async fn main() -> anyhow::Result<()> {
 
     let app = Router::new()
         .nest("/api", api::router())
+        .merge(api::openapi::router())
         .route("/metrics", axum::routing::get(api::metrics::handler))
         .route("/agent/ws", axum::routing::get(ws_agent::handler))
         .route("/receiver/ws", axum::routing::get(ws_receiver::handler))
@@ -131,7 +239,12 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("strata-control listening on {addr}");
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses feed per-client throttling (see api::auth).
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
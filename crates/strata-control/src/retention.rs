@@ -0,0 +1,193 @@
+//! Data retention.
+//!
+//! A janitor task runs [`enforce`] every [`JANITOR_INTERVAL`] and deletes
+//! what has outlived its [`RetentionPolicy`]:
+//!
+//! - **telemetry** — `sender_metrics`, `link_events` and `ladder_steps`
+//!   rows, by sample time.
+//! - **audit** — `audit_log` entries, and alert events resolved before the
+//!   cutoff. Open alerts are never deleted.
+//! - **recordings** — what a stream leaves behind once it ends: its
+//!   report, its annotations and its pipeline config snapshot, by
+//!   `ended_at`. The stream row itself stays so usage rollups keep
+//!   counting it.
+//!
+//! Erasing a user's personal data is a separate, on-demand workflow (see
+//! `api/users.rs` and `api/me.rs`).
+
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::state::AppState;
+
+/// Interval between [`enforce`] passes.
+pub const JANITOR_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// How long each class of data is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub telemetry: chrono::Duration,
+    pub audit: chrono::Duration,
+    pub recordings: chrono::Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            // The longest range the metrics history offers (7 days) plus a
+            // day of slack for zooming out.
+            telemetry: chrono::Duration::days(8),
+            audit: chrono::Duration::days(365),
+            recordings: chrono::Duration::days(90),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Read `RETENTION_TELEMETRY_DAYS`, `RETENTION_AUDIT_DAYS` and
+    /// `RETENTION_RECORDINGS_DAYS`; unset variables keep the defaults.
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let mut policy = Self::default();
+        for (key, slot) in [
+            ("RETENTION_TELEMETRY_DAYS", &mut policy.telemetry),
+            ("RETENTION_AUDIT_DAYS", &mut policy.audit),
+            ("RETENTION_RECORDINGS_DAYS", &mut policy.recordings),
+        ] {
+            if let Some(v) = lookup(key) {
+                let days: u32 = v
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("invalid {key} {v:?}: {e}"))?;
+                if days == 0 {
+                    anyhow::bail!("{key} must be at least 1");
+                }
+                *slot = chrono::Duration::days(days.into());
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// Delete everything past `policy`. Each class is purged independently —
+/// one failing statement is logged and the rest still run.
+pub async fn enforce(state: &AppState, policy: &RetentionPolicy) {
+    let now = Utc::now();
+
+    let telemetry = now - policy.telemetry;
+    purge(
+        state,
+        "sender_metrics",
+        "DELETE FROM sender_metrics WHERE ts < $1",
+        telemetry,
+    )
+    .await;
+    purge(
+        state,
+        "link_events",
+        "DELETE FROM link_events WHERE ts < $1",
+        telemetry,
+    )
+    .await;
+    purge(
+        state,
+        "ladder_steps",
+        "DELETE FROM ladder_steps WHERE ts < $1",
+        telemetry,
+    )
+    .await;
+
+    let audit = now - policy.audit;
+    purge(
+        state,
+        "audit_log",
+        "DELETE FROM audit_log WHERE created_at < $1",
+        audit,
+    )
+    .await;
+    purge(
+        state,
+        "alert_events",
+        "DELETE FROM alert_events WHERE state = 'resolved' AND resolved_at < $1",
+        audit,
+    )
+    .await;
+
+    let recordings = now - policy.recordings;
+    purge(
+        state,
+        "stream_reports",
+        "DELETE FROM stream_reports WHERE stream_id IN \
+         (SELECT id FROM streams WHERE ended_at < $1)",
+        recordings,
+    )
+    .await;
+    purge(
+        state,
+        "stream_annotations",
+        "DELETE FROM stream_annotations WHERE stream_id IN \
+         (SELECT id FROM streams WHERE ended_at < $1)",
+        recordings,
+    )
+    .await;
+    purge(
+        state,
+        "stream configs",
+        "UPDATE streams SET config_json = NULL \
+         WHERE ended_at < $1 AND config_json IS NOT NULL",
+        recordings,
+    )
+    .await;
+}
+
+async fn purge(state: &AppState, what: &str, sql: &str, cutoff: chrono::DateTime<Utc>) {
+    match sqlx::query(sql).bind(cutoff).execute(state.pool()).await {
+        Ok(r) if r.rows_affected() > 0 => {
+            tracing::debug!(what, rows = r.rows_affected(), "retention purge");
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(what, error = %e, "retention purge failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(vars: &[(&str, &str)]) -> anyhow::Result<RetentionPolicy> {
+        RetentionPolicy::from_lookup(|key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn unset_variables_keep_the_defaults() {
+        assert_eq!(policy(&[]).unwrap(), RetentionPolicy::default());
+    }
+
+    #[test]
+    fn variables_override_each_class() {
+        let p = policy(&[
+            ("RETENTION_TELEMETRY_DAYS", "14"),
+            ("RETENTION_AUDIT_DAYS", "730"),
+            ("RETENTION_RECORDINGS_DAYS", "30"),
+        ])
+        .unwrap();
+        assert_eq!(p.telemetry, chrono::Duration::days(14));
+        assert_eq!(p.audit, chrono::Duration::days(730));
+        assert_eq!(p.recordings, chrono::Duration::days(30));
+    }
+
+    #[test]
+    fn invalid_or_zero_days_are_rejected() {
+        let err = policy(&[("RETENTION_AUDIT_DAYS", "a year")]).unwrap_err();
+        assert!(err.to_string().contains("RETENTION_AUDIT_DAYS"));
+        assert!(policy(&[("RETENTION_TELEMETRY_DAYS", "0")]).is_err());
+        assert!(policy(&[("RETENTION_RECORDINGS_DAYS", "-3")]).is_err());
+    }
+}
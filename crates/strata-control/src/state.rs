@@ -1,15 +1,27 @@
 //! Shared application state.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
 use dashmap::{DashMap, DashSet};
 use sqlx::PgPool;
 use tokio::sync::{broadcast, oneshot};
 
-use strata_common::auth::JwtContext;
+use strata_common::auth::{JwtContext, PasswordPolicy, SecretBox};
+use strata_common::error::StrataError;
+
+use crate::api::auth::RequestThrottle;
+use crate::api::usage::UsageMeter;
+use crate::autoscale::Autoscaler;
+use crate::dashboard_hub::DashboardHub;
+use crate::event_bus::EventBus;
+use crate::live_state::LiveRegistry;
+use crate::mailer::Mailer;
+use strata_protocol::models::LinkPhase;
 use strata_protocol::{
-    DashboardEvent, DeviceStatusPayload, ReceiverStatusPayload, ReceiverStreamStatsPayload,
-    StreamStatsPayload,
+    DashboardEvent, DashboardTopic, DeviceStatusPayload, ReceiverStatusPayload,
+    ReceiverStreamStatsPayload,
 };
 
 /// State shared across all request handlers.
@@ -25,28 +37,58 @@ struct Inner {
     pub agents: DashMap<String, AgentHandle>,
     /// Cached latest device status per sender, updated on each heartbeat.
     pub device_status: DashMap<String, DeviceStatusPayload>,
-    /// Pending request-response calls to agents, keyed by request_id.
+    /// Pending request-response calls to receivers, keyed by request_id.
     pub pending_requests: DashMap<String, oneshot::Sender<serde_json::Value>>,
-    /// Broadcast channel for dashboard WebSocket subscribers, tagged with
-    /// the owning user's ID so each subscriber can filter to only their own
-    /// resources (see `broadcast_dashboard`/`subscribe_dashboard`).
-    pub dashboard_tx: broadcast::Sender<(String, DashboardEvent)>,
+    /// Agent RPCs awaiting their answer, keyed by request_id (see
+    /// `ws_agent::call`).
+    pub agent_calls: DashMap<String, PendingCall>,
+    /// Per-(owner, topic) fan-out for dashboard WebSocket subscribers (see
+    /// `broadcast_dashboard`/`subscribe_dashboard`).
+    pub dashboard: DashboardHub,
+    /// Cross-replica fan-out of `dashboard` events, once `event_bus::start`
+    /// has joined it.
+    pub event_bus: OnceLock<EventBus>,
     /// Streams that have already transitioned to 'live' (avoids repeated
     /// UPDATE queries on every stats tick).
     pub live_streams: DashSet<String>,
-    /// Cached latest stream stats per sender, keyed by sender_id.
-    /// Updated on each `stream.stats` message from agents.
-    pub stream_stats: DashMap<String, StreamStatsPayload>,
+    /// Each sender's running stream: latest stats and link table (see
+    /// `live_state`). Updated on each `stream.stats` message from agents.
+    pub live: LiveRegistry,
+    /// Unflushed usage metering per live stream, keyed by stream_id (see
+    /// `api::usage::record_stream_bytes`).
+    pub usage_meters: DashMap<String, UsageMeter>,
+    /// When each sender's telemetry was last sampled into the metrics
+    /// history, keyed by sender_id (see `api::history::record_sample`).
+    pub metrics_sampled: DashMap<String, Instant>,
+    /// Last recorded phase of each link, keyed by stream_id then interface
+    /// (see `api::link_events::record_stats`).
+    pub link_phases: DashMap<String, HashMap<String, LinkPhase>>,
     /// In-memory alerting rules per sender.
     pub alert_rules: DashMap<String, Vec<serde_json::Value>>,
+    /// Alert evaluation state per sender, keyed by sender_id (see
+    /// `api::alerts::evaluate`).
+    pub alert_tracking: DashMap<String, AlertTracker>,
     /// Connected receiver daemons, keyed by receiver_id.
     pub receivers: DashMap<String, ReceiverHandle>,
     /// Cached latest receiver status per receiver, updated on each heartbeat.
     pub receiver_status: DashMap<String, ReceiverStatusPayload>,
     /// Cached latest receiver-side stream stats, keyed by stream_id — the
     /// delivered-goodput + HLS egress health snapshot replayed to
-    /// late-joining dashboards (the sender-side twin is `stream_stats`).
+    /// late-joining dashboards (the sender-side twin is `live`).
     pub receiver_stream_stats: DashMap<String, ReceiverStreamStatsPayload>,
+    /// Receiver auto-scaling, once configured (see `autoscale`).
+    pub autoscaler: OnceLock<Autoscaler>,
+    /// Outgoing email, once configured (see `mailer`).
+    pub mailer: OnceLock<Mailer>,
+    /// Rules for user-chosen passwords, once loaded at startup (see
+    /// `api::auth::password_policy_from_env`).
+    pub password_policy: OnceLock<PasswordPolicy>,
+    /// Key for secrets stored in the database, once configured (see
+    /// `SECRETS_KEY_B64` in main).
+    pub secrets: OnceLock<SecretBox>,
+    /// Password reset requests per email address and client IP (see
+    /// `api::auth::forgot_password`).
+    pub password_resets: RequestThrottle,
 }
 
 /// Handle to a connected sender agent.
@@ -58,6 +100,27 @@ pub struct AgentHandle {
     pub hostname: Option<String>,
 }
 
+/// An agent's answer to a [`PendingCall`]: the typed response payload, or
+/// why there won't be one.
+pub type RpcReply = Result<serde_json::Value, StrataError>;
+
+/// A control-plane call waiting on an agent (see `ws_agent::call`).
+pub struct PendingCall {
+    /// The agent it was sent to; its calls fail when it disconnects.
+    pub sender_id: String,
+    pub reply: oneshot::Sender<RpcReply>,
+}
+
+/// Where rule evaluation stands for one sender.
+#[derive(Debug, Default)]
+pub struct AlertTracker {
+    /// When the sender's rules were last evaluated.
+    pub evaluated_at: Option<Instant>,
+    /// Open alert event IDs keyed by rule ID; `None` until loaded from the
+    /// database on the first evaluation.
+    pub open: Option<HashMap<String, String>>,
+}
+
 /// Handle to a connected receiver daemon.
 pub struct ReceiverHandle {
     /// Channel to send control messages to this receiver's WebSocket task.
@@ -67,14 +130,8 @@ pub struct ReceiverHandle {
     pub hostname: Option<String>,
 }
 
-/// Capacity of the dashboard broadcast channel. A lagging subscriber just
-/// drops old events (`RecvError::Lagged`, handled in `ws_dashboard.rs`) —
-/// this bounds how much slack a slow browser gets before that happens.
-const DASHBOARD_BROADCAST_CAPACITY: usize = 1024;
-
 impl AppState {
     pub fn new(pool: PgPool, jwt: JwtContext) -> Self {
-        let (dashboard_tx, _) = broadcast::channel(DASHBOARD_BROADCAST_CAPACITY);
         Self {
             inner: Arc::new(Inner {
                 pool,
@@ -82,13 +139,24 @@ impl AppState {
                 agents: DashMap::new(),
                 device_status: DashMap::new(),
                 pending_requests: DashMap::new(),
-                dashboard_tx,
+                agent_calls: DashMap::new(),
+                dashboard: DashboardHub::new(),
+                event_bus: OnceLock::new(),
                 live_streams: DashSet::new(),
-                stream_stats: DashMap::new(),
+                live: LiveRegistry::new(),
+                usage_meters: DashMap::new(),
+                metrics_sampled: DashMap::new(),
+                link_phases: DashMap::new(),
                 alert_rules: DashMap::new(),
+                alert_tracking: DashMap::new(),
                 receivers: DashMap::new(),
                 receiver_status: DashMap::new(),
                 receiver_stream_stats: DashMap::new(),
+                autoscaler: OnceLock::new(),
+                mailer: OnceLock::new(),
+                password_policy: OnceLock::new(),
+                secrets: OnceLock::new(),
+                password_resets: RequestThrottle::default(),
             }),
         }
     }
@@ -110,19 +178,39 @@ impl AppState {
         &self.inner.device_status
     }
 
-    /// Get the pending requests map (for request-response patterns to agents).
+    /// Get the pending requests map (for request-response patterns to receivers).
     pub fn pending_requests(&self) -> &DashMap<String, oneshot::Sender<serde_json::Value>> {
         &self.inner.pending_requests
     }
 
+    /// Agent RPCs awaiting their answer.
+    pub fn agent_calls(&self) -> &DashMap<String, PendingCall> {
+        &self.inner.agent_calls
+    }
+
     /// Streams that have already transitioned to 'live'.
     pub fn live_streams(&self) -> &DashSet<String> {
         &self.inner.live_streams
     }
 
-    /// Cached latest stream stats per sender (keyed by sender_id).
-    pub fn stream_stats(&self) -> &DashMap<String, StreamStatsPayload> {
-        &self.inner.stream_stats
+    /// Running streams per sender: latest stats and link table.
+    pub fn live(&self) -> &LiveRegistry {
+        &self.inner.live
+    }
+
+    /// Unflushed usage metering per live stream (keyed by stream_id).
+    pub fn usage_meters(&self) -> &DashMap<String, UsageMeter> {
+        &self.inner.usage_meters
+    }
+
+    /// Last metrics-history sample per sender (keyed by sender_id).
+    pub fn metrics_sampled(&self) -> &DashMap<String, Instant> {
+        &self.inner.metrics_sampled
+    }
+
+    /// Last recorded link phases per live stream (keyed by stream_id).
+    pub fn link_phases(&self) -> &DashMap<String, HashMap<String, LinkPhase>> {
+        &self.inner.link_phases
     }
 
     /// Cached latest receiver-side stream stats (keyed by stream_id).
@@ -135,6 +223,11 @@ impl AppState {
         &self.inner.alert_rules
     }
 
+    /// Alert evaluation state per sender.
+    pub fn alert_tracking(&self) -> &DashMap<String, AlertTracker> {
+        &self.inner.alert_tracking
+    }
+
     /// Connected receiver daemons.
     pub fn receivers(&self) -> &DashMap<String, ReceiverHandle> {
         &self.inner.receivers
@@ -145,20 +238,87 @@ impl AppState {
         &self.inner.receiver_status
     }
 
-    /// Broadcast a dashboard event to all subscribed browsers, tagged with
-    /// the ID of the user who owns the sender/receiver/stream it concerns.
-    /// Subscribers filter to their own `owner_id` (see `ws_dashboard.rs`) —
-    /// the channel itself is still global, but nothing is delivered across
-    /// owners.
-    pub fn broadcast_dashboard(&self, owner_id: impl Into<String>, event: DashboardEvent) {
-        // Ignore send errors (no subscribers).
-        let _ = self.inner.dashboard_tx.send((owner_id.into(), event));
+    /// Receiver auto-scaling, if configured.
+    pub fn autoscaler(&self) -> Option<&Autoscaler> {
+        self.inner.autoscaler.get()
+    }
+
+    /// Install the autoscaler. Returns false if one is already installed.
+    pub fn set_autoscaler(&self, autoscaler: Autoscaler) -> bool {
+        self.inner.autoscaler.set(autoscaler).is_ok()
+    }
+
+    /// Outgoing email, if configured.
+    pub fn mailer(&self) -> Option<&Mailer> {
+        self.inner.mailer.get()
+    }
+
+    /// Install the mailer. Returns false if one is already installed.
+    pub fn set_mailer(&self, mailer: Mailer) -> bool {
+        self.inner.mailer.set(mailer).is_ok()
+    }
+
+    /// Password rules; the defaults until a policy is installed.
+    pub fn password_policy(&self) -> &PasswordPolicy {
+        self.inner
+            .password_policy
+            .get_or_init(PasswordPolicy::default)
+    }
+
+    /// Install the password policy. Returns false if one is already in use.
+    pub fn set_password_policy(&self, policy: PasswordPolicy) -> bool {
+        self.inner.password_policy.set(policy).is_ok()
+    }
+
+    /// Encryption for secrets at rest, if a data key is configured.
+    pub fn secrets(&self) -> Option<&SecretBox> {
+        self.inner.secrets.get()
+    }
+
+    /// Install the data key. Returns false if one is already installed.
+    pub fn set_secrets(&self, secrets: SecretBox) -> bool {
+        self.inner.secrets.set(secrets).is_ok()
+    }
+
+    /// Password reset request counts.
+    pub fn password_resets(&self) -> &RequestThrottle {
+        &self.inner.password_resets
+    }
+
+    /// Publish a dashboard event on its topic for the user who owns the
+    /// sender/receiver/stream it concerns. Only browsers of that user that
+    /// subscribed to the event's topic receive it.
+    /// With the event bus running, the event also reaches browsers
+    /// connected to other replicas.
+    pub fn broadcast_dashboard(&self, owner_id: impl AsRef<str>, event: DashboardEvent) {
+        if let Some(bus) = self.inner.event_bus.get() {
+            bus.forward(owner_id.as_ref(), &event);
+        }
+        self.inner.dashboard.publish(owner_id.as_ref(), event);
+    }
+
+    /// Publish an event to this replica's subscribers only (events relayed
+    /// from peers by the event bus).
+    pub fn publish_dashboard_local(&self, owner_id: &str, event: DashboardEvent) {
+        self.inner.dashboard.publish(owner_id, event);
+    }
+
+    /// Install the event bus. Returns false if one is already installed.
+    pub fn set_event_bus(&self, bus: EventBus) -> bool {
+        self.inner.event_bus.set(bus).is_ok()
+    }
+
+    /// Subscribe to one dashboard topic of `owner_id`'s events.
+    pub fn subscribe_dashboard(
+        &self,
+        owner_id: &str,
+        topic: DashboardTopic,
+    ) -> broadcast::Receiver<DashboardEvent> {
+        self.inner.dashboard.subscribe(owner_id, topic)
     }
 
-    /// Subscribe to dashboard events (returns a receiver). Each item is
-    /// `(owner_id, event)` — the receiver must filter to the connected
-    /// user's own `owner_id` before forwarding to the browser.
-    pub fn subscribe_dashboard(&self) -> broadcast::Receiver<(String, DashboardEvent)> {
-        self.inner.dashboard_tx.subscribe()
+    /// Release an idle dashboard topic after its subscriber went away.
+    pub fn release_dashboard_topic(&self, owner_id: &str, topic: &DashboardTopic) {
+        self.inner.dashboard.release(owner_id, topic);
     }
 }
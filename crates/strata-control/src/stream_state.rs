@@ -188,6 +188,10 @@ pub async fn reconcile_sender(app: &AppState, sender_id: &str, owner_id: &str, r
         {
             Ok(true) => {
                 app.live_streams().remove(stream_id);
+                crate::api::usage::finish_stream_usage(app, stream_id).await;
+                crate::api::link_events::clear(app, stream_id);
+                crate::api::reports::generate_detached(app, stream_id);
+                app.live().end_stream(stream_id);
                 tracing::warn!(
                     sender_id,
                     stream_id,
@@ -319,6 +323,10 @@ pub async fn reconcile_receiver(
         {
             Ok(true) => {
                 app.live_streams().remove(stream_id);
+                crate::api::usage::finish_stream_usage(app, stream_id).await;
+                crate::api::link_events::clear(app, stream_id);
+                crate::api::reports::generate_detached(app, stream_id);
+                app.live().end_stream(stream_id);
                 tracing::warn!(
                     receiver_id,
                     stream_id,
@@ -368,8 +376,12 @@ pub async fn sweep(app: &AppState) {
         String,
         Option<chrono::DateTime<chrono::Utc>>,
     )> = sqlx::query_as(
-        "SELECT s.id, s.sender_id, sn.owner_id, sn.last_seen_at \
+        // An ingest sender has no agent; the receiver taking its
+        // contribution in stands in for it.
+        "SELECT s.id, s.sender_id, sn.owner_id, \
+                CASE WHEN sn.ingest_protocol IS NULL THEN sn.last_seen_at ELSE r.last_seen_at END \
              FROM streams s JOIN senders sn ON sn.id = s.sender_id \
+             LEFT JOIN receivers r ON r.id = s.receiver_id \
              WHERE s.state = ANY($1)",
     )
     .bind(&ACTIVE_STATES[..])
@@ -402,6 +414,10 @@ pub async fn sweep(app: &AppState) {
         {
             Ok(true) => {
                 app.live_streams().remove(&stream_id);
+                crate::api::usage::finish_stream_usage(app, &stream_id).await;
+                crate::api::link_events::clear(app, &stream_id);
+                crate::api::reports::generate_detached(app, &stream_id);
+                app.live().end_stream(&stream_id);
                 tracing::warn!(
                     sender_id,
                     stream_id,
@@ -453,6 +469,10 @@ pub async fn sweep(app: &AppState) {
         .await
         {
             app.live_streams().remove(&stream_id);
+            crate::api::usage::finish_stream_usage(app, &stream_id).await;
+            crate::api::link_events::clear(app, &stream_id);
+            crate::api::reports::generate_detached(app, &stream_id);
+            app.live().end_stream(&stream_id);
             app.broadcast_dashboard(
                 owner_id,
                 DashboardEvent::StreamStateChanged {
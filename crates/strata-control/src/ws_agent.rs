@@ -10,24 +10,37 @@
 //!    - reconnect: `device_id` → `auth.challenge` nonce → signature verify
 //!      against the enrolled public key
 //! 2. On success: registers agent in AppState, starts bidirectional message loop
-//! 3. Agent sends heartbeats (`device.status`), stream stats (`stream.stats`)
+//! 3. Agent sends heartbeats (`device.status`), stream stats (`stream.stats`
+//!    — CBOR binary frames when negotiated in the handshake, see
+//!    [`strata_protocol::encoding`])
 //! 4. Control plane sends commands (`stream.start`, `stream.stop`, `config.update`)
+//!
+//! Dashboard-triggered actions that need an answer (run test, fetch logs,
+//! capture pcap, …) go through [`call`]: the command carries a request_id
+//! for correlation and an envelope `deadline`, and the caller gets the
+//! agent's typed response or a [`StrataError`] — `Timeout` when the agent
+//! doesn't answer in time, `DeviceOffline` when it isn't (or stops being)
+//! connected, the NAK's code when it can't handle the command.
 
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
 use axum::response::IntoResponse;
+use std::time::Duration;
+
 use chrono::Utc;
 use futures::SinkExt;
 use futures::stream::StreamExt;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use strata_common::auth;
+use strata_common::error::StrataError;
+use strata_protocol::encoding::{self, TELEMETRY_ENCODING_CBOR};
 use strata_protocol::{
     AgentMessage, AuthChallengePayload, AuthLoginPayload, AuthLoginResponsePayload, ControlMessage,
-    DashboardEvent, Envelope, PROTOCOL_VERSION,
+    DashboardEvent, Envelope, ErrorCode, PROTOCOL_VERSION,
 };
 
-use crate::state::{AgentHandle, AppState};
+use crate::state::{AgentHandle, AppState, PendingCall};
 
 /// Axum handler — upgrades HTTP to WebSocket.
 pub async fn handler(State(state): State<AppState>, ws: WebSocketUpgrade) -> impl IntoResponse {
@@ -39,12 +52,17 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
     let (mut ws_tx, mut ws_rx) = socket.split();
 
     // Handshake: enrollment (single message) or challenge/response (two).
-    let (sender_id, owner_id, hostname) = match authenticate(&state, &mut ws_tx, &mut ws_rx).await {
-        Some(identity) => identity,
-        None => return,
+    let Some(AuthenticatedAgent {
+        sender_id,
+        owner_id,
+        hostname,
+        binary_telemetry,
+    }) = authenticate(&state, &mut ws_tx, &mut ws_rx).await
+    else {
+        return;
     };
 
-    tracing::info!(sender_id = %sender_id, "agent connected");
+    tracing::info!(sender_id = %sender_id, binary_telemetry, "agent connected");
 
     // Create a channel for sending messages to this agent. Note: the
     // agent's own outbound channel to its control-plane WS write task
@@ -68,6 +86,12 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
         },
     );
 
+    // Queued ahead of any command so the agent knows its reboot/OTA
+    // windows from the start of the session.
+    crate::api::maintenance::push_schedule(&state, &sender_id).await;
+    crate::api::senders::push_portal_auth(&state, &sender_id).await;
+    crate::api::certificates::push_to_sender(&state, &sender_id).await;
+
     // Bidirectional message loop
     loop {
         tokio::select! {
@@ -77,6 +101,12 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
                     Some(Ok(Message::Text(text))) => {
                         handle_agent_message(&state, &sender_id, &owner_id, &text).await;
                     }
+                    Some(Ok(Message::Binary(bytes))) if binary_telemetry => {
+                        handle_agent_binary(&state, &sender_id, &owner_id, &bytes).await;
+                    }
+                    Some(Ok(Message::Binary(_))) => {
+                        tracing::debug!(sender_id = %sender_id, "binary frame without negotiated encoding ignored");
+                    }
                     Some(Ok(Message::Close(_))) | None => break,
                     Some(Err(_)) => break,
                     _ => {} // Ping/Pong handled by axum
@@ -97,10 +127,15 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
         }
     }
 
-    // Cleanup
+    // Cleanup. Dropping this agent's pending calls fails them as offline
+    // now instead of leaving each to run out its timeout.
     state.agents().remove(&sender_id);
+    state
+        .agent_calls()
+        .retain(|_, call| call.sender_id != sender_id);
     state.device_status().remove(&sender_id);
-    state.stream_stats().remove(&sender_id);
+    state.live().remove(&sender_id);
+    state.alert_tracking().remove(&sender_id);
     state.broadcast_dashboard(
         owner_id.clone(),
         DashboardEvent::SenderStatus {
@@ -137,7 +172,9 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
         .execute(state.pool())
         .await;
 
-    tracing::info!(sender_id = %sender_id, "agent disconnected");
+    // Expected during a maintenance window (reboots, OTA) — not alertable.
+    let in_maintenance = crate::api::maintenance::is_suppressed(&state, &sender_id).await;
+    tracing::info!(sender_id = %sender_id, in_maintenance, "agent disconnected");
 }
 
 /// How long the agent gets to answer an `auth.challenge` before the
@@ -147,20 +184,34 @@ const CHALLENGE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10
 type WsSink = futures::stream::SplitSink<WebSocket, Message>;
 type WsStream = futures::stream::SplitStream<WebSocket>;
 
+/// A successfully authenticated agent connection.
+struct AuthenticatedAgent {
+    sender_id: String,
+    owner_id: String,
+    hostname: Option<String>,
+    /// The agent offered CBOR telemetry frames and we accepted.
+    binary_telemetry: bool,
+}
+
 /// Run the auth handshake. Sends the success/failure response itself and
-/// returns `Some((sender_id, owner_id, hostname))` on success.
+/// returns the agent's identity on success.
 async fn authenticate(
     state: &AppState,
     ws_tx: &mut WsSink,
     ws_rx: &mut WsStream,
-) -> Option<(String, String, Option<String>)> {
+) -> Option<AuthenticatedAgent> {
     let text = match ws_rx.next().await {
         Some(Ok(Message::Text(text))) => text,
         _ => return None,
     };
 
+    let mut binary_telemetry = false;
     let result = match parse_auth_login(&text) {
         Ok(payload) => {
+            binary_telemetry = payload
+                .telemetry_encodings
+                .iter()
+                .any(|e| e == TELEMETRY_ENCODING_CBOR);
             if let Some(ref token) = payload.enrollment_token {
                 enroll(state, token, &payload).await
             } else if payload.device_id.is_some() {
@@ -178,6 +229,7 @@ async fn authenticate(
                 success: true,
                 sender_id: Some(sender_id.clone()),
                 error: None,
+                telemetry_encoding: binary_telemetry.then(|| TELEMETRY_ENCODING_CBOR.to_string()),
             };
             let envelope =
                 Envelope::from_message(&ControlMessage::AuthLoginResponse(response)).unwrap();
@@ -185,7 +237,12 @@ async fn authenticate(
             if ws_tx.send(Message::Text(json.into())).await.is_err() {
                 return None;
             }
-            Some((sender_id, owner_id, hostname))
+            Some(AuthenticatedAgent {
+                sender_id,
+                owner_id,
+                hostname,
+                binary_telemetry,
+            })
         }
         Err(msg) => {
             let _ = ws_tx.send(Message::Text(error_response(&msg).into())).await;
@@ -231,20 +288,25 @@ async fn enroll(
         );
     };
 
-    let row: Option<(String, Option<String>, bool)> =
-        sqlx::query_as("SELECT owner_id, enrollment_token, enrolled FROM senders WHERE id = $1")
-            .bind(&sender_id)
-            .fetch_optional(state.pool())
-            .await
-            .map_err(|e| format!("db error: {e}"))?;
+    let row: Option<(String, Option<String>, bool)> = sqlx::query_as(
+        "SELECT owner_id, enrollment_token, enrollment_token_legacy FROM senders WHERE id = $1",
+    )
+    .bind(&sender_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| format!("db error: {e}"))?;
 
-    let Some((owner_id, token_hash, _enrolled)) = row else {
+    let Some((owner_id, token_hash, legacy)) = row else {
         return Err("invalid enrollment token".into());
     };
     let Some(token_hash) = token_hash else {
         // Token already consumed — the device must use its key.
         return Err("enrollment token already used — reconnect with the device key".into());
     };
+    // Only tokens issued before the check character may go without one.
+    if !legacy && strata_common::ids::validate_enrollment_token(token).is_err() {
+        return Err("invalid enrollment token".into());
+    }
 
     if !auth::verify_password(&secret, &token_hash).unwrap_or(false) {
         return Err("invalid enrollment token".into());
@@ -347,7 +409,7 @@ async fn challenge_auth(
     Ok((device_id, owner_id, Some(payload.hostname.clone())))
 }
 
-/// Handle an incoming message from an authenticated agent.
+/// Handle an incoming JSON text frame from an authenticated agent.
 async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str, raw: &str) {
     let envelope: Envelope = match serde_json::from_str(raw) {
         Ok(e) => e,
@@ -356,15 +418,49 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
             return;
         }
     };
+    handle_agent_envelope(state, sender_id, owner_id, envelope).await;
+}
+
+/// Handle a CBOR binary frame — only accepted once the handshake
+/// negotiated binary telemetry.
+async fn handle_agent_binary(state: &AppState, sender_id: &str, owner_id: &str, bytes: &[u8]) {
+    let envelope = match encoding::decode_cbor(bytes) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::warn!(sender_id = %sender_id, "invalid binary frame from agent: {e}");
+            return;
+        }
+    };
+    handle_agent_envelope(state, sender_id, owner_id, envelope).await;
+}
 
-    let msg: AgentMessage = match envelope.parse_message() {
+/// Dispatch a decoded envelope, whichever frame type it arrived in.
+async fn handle_agent_envelope(
+    state: &AppState,
+    sender_id: &str,
+    owner_id: &str,
+    envelope: Envelope,
+) {
+    let msg: AgentMessage = match envelope.parse_message_or_nak() {
         Ok(m) => m,
-        Err(_) => {
+        Err(None) => {
+            tracing::debug!(sender_id = %sender_id, "unparseable NAK from agent dropped");
+            return;
+        }
+        Err(Some(nak)) => {
             tracing::debug!(
                 sender_id = %sender_id,
                 msg_type = %envelope.msg_type,
+                code = %nak.code,
                 "unhandled agent message type"
             );
+            let tx = state.agents().get(sender_id).map(|a| a.tx.clone());
+            if let Some(tx) = tx
+                && let Ok(json) = Envelope::from_message(&ControlMessage::Nak(nak))
+                    .and_then(|e| serde_json::to_string(&e))
+            {
+                let _ = tx.send(json).await;
+            }
             return;
         }
     };
@@ -382,9 +478,16 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
                 .await;
 
             // Cache latest status for REST API consumers
-            state
+            let previous = state
                 .device_status()
                 .insert(sender_id.to_string(), payload.clone());
+            crate::api::link_events::record_interfaces(
+                state,
+                sender_id,
+                previous.as_ref(),
+                &payload,
+            )
+            .await;
 
             // Reconcile the DB against what the device says it's running —
             // this, not WS liveness, is the ground truth for stream state.
@@ -442,12 +545,21 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
 
             state.broadcast_dashboard(owner_id, DashboardEvent::StreamStats(payload.clone()));
 
-            // Cache latest stats for the /metrics endpoint
-            state.stream_stats().insert(sender_id.to_string(), payload);
+            crate::api::usage::record_stream_bytes(state, sender_id, &payload).await;
+            crate::api::history::record_sample(state, &payload).await;
+            crate::api::link_events::record_stats(state, &payload).await;
+            crate::api::reports::record_stats(state, &payload).await;
+            crate::api::alerts::evaluate(state, owner_id, &payload).await;
+
+            // Cache latest stats for /metrics and the live snapshot
+            state.live().record_stats(sender_id, payload);
         }
         AgentMessage::StreamEnded(payload) => {
             // Remove from live_streams tracking
             state.live_streams().remove(&payload.stream_id);
+            crate::api::usage::finish_stream_usage(state, &payload.stream_id).await;
+            crate::api::link_events::clear(state, &payload.stream_id);
+            state.live().end_stream(&payload.stream_id);
 
             // Device-confirmed end (end_inferred=false → not readoptable).
             // Persist the device's reason + detail so a crash is
@@ -470,11 +582,17 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
             {
                 tracing::warn!(stream_id = %payload.stream_id, error = %e, "stream.ended transition failed");
             }
-            let _ = sqlx::query("UPDATE streams SET total_bytes = $1 WHERE id = $2")
-                .bind(payload.total_bytes as i64)
-                .bind(&payload.stream_id)
-                .execute(state.pool())
-                .await;
+            // GREATEST: never undercut what telemetry already metered.
+            let _ = sqlx::query(
+                "UPDATE streams SET total_bytes = GREATEST(total_bytes, $1) \
+                 WHERE id = $2 AND sender_id = $3",
+            )
+            .bind(payload.total_bytes as i64)
+            .bind(&payload.stream_id)
+            .bind(sender_id)
+            .execute(state.pool())
+            .await;
+            crate::api::reports::generate_detached(state, &payload.stream_id);
 
             state.broadcast_dashboard(
                 owner_id,
@@ -487,10 +605,66 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
                 },
             );
         }
-        // RPC responses — route the raw payload back to the pending REST
-        // caller by request_id. Listed explicitly (no catch-all) so a new
-        // message type is a compile error until this hub decides what to do
-        // with it.
+        // The agent couldn't handle a command; fail its RPC now.
+        AgentMessage::Nak(nak) => {
+            tracing::warn!(
+                sender_id = %sender_id,
+                msg_type = %nak.msg_type,
+                code = %nak.code,
+                agent_proto = nak.proto_version,
+                detail = %nak.detail,
+                "agent could not handle a control message"
+            );
+            if let Some(request_id) = nak.request_id.as_deref() {
+                let error = match nak.code {
+                    ErrorCode::UnsupportedCommand => format!(
+                        "this sender's agent does not support {}; update it",
+                        nak.msg_type
+                    ),
+                    ErrorCode::Timeout => {
+                        format!("sender got to {} after its deadline", nak.msg_type)
+                    }
+                    _ => format!("sender rejected {}: {}", nak.msg_type, nak.detail),
+                };
+                answer(state, request_id, Err(StrataError::new(nak.code, error)));
+            }
+        }
+        // Per-command outcome, what the dashboard shows for every command,
+        // fire-and-forget ones included. RPCs have usually been answered by
+        // their typed response already; one that failed before sending it
+        // is failed here rather than left to time out.
+        AgentMessage::CommandAck(ack) => {
+            if ack.success && ack.msg_type == "tls.install" {
+                crate::api::certificates::mark_pushed(state, sender_id).await;
+            }
+            if ack.success {
+                tracing::debug!(sender_id = %sender_id, msg_type = %ack.msg_type, "command succeeded");
+            } else {
+                tracing::warn!(
+                    sender_id = %sender_id,
+                    msg_type = %ack.msg_type,
+                    code = ?ack.code,
+                    error = ?ack.error,
+                    "command failed on sender"
+                );
+                if let Some(request_id) = ack.request_id.as_deref() {
+                    let error = StrataError::new(
+                        ack.code.unwrap_or(ErrorCode::DeviceRejected),
+                        ack.error
+                            .clone()
+                            .unwrap_or_else(|| format!("sender rejected {}", ack.msg_type)),
+                    );
+                    answer(state, request_id, Err(error));
+                }
+            }
+            state.broadcast_dashboard(
+                owner_id,
+                DashboardEvent::CommandResult {
+                    sender_id: sender_id.to_string(),
+                    ack,
+                },
+            );
+        }
         msg @ (AgentMessage::ConfigSetResponse(_)
         | AgentMessage::ConfigUpdateResponse(_)
         | AgentMessage::TestRunResponse(_)
@@ -509,23 +683,120 @@ async fn handle_agent_message(state: &AppState, sender_id: &str, owner_id: &str,
         | AgentMessage::UpdatesInstallResponse(_)
         | AgentMessage::StreamDestinationsResponse(_)
         | AgentMessage::JitterBufferResponse(_)
-        | AgentMessage::SourceSwitchResponse(_)) => {
-            if let Some(request_id) = msg.request_id()
-                && let Some((_, tx)) = state.pending_requests().remove(request_id)
-            {
-                let _ = tx.send(envelope.payload.clone());
+        | AgentMessage::SourceSwitchResponse(_)
+        | AgentMessage::SourceSelectResponse(_)) => {
+            // Listed explicitly (no catch-all) so a new message type is a
+            // compile error until this hub decides what to do with it.
+            if let Some(request_id) = msg.request_id() {
+                answer(state, request_id, Ok(envelope.payload.clone()));
             }
         }
     }
 }
 
+// ── Agent RPC ───────────────────────────────────────────────────────
+
+/// Send `msg` to a connected agent and wait up to `timeout` for its typed
+/// response. `msg` must carry a request_id — that's what the answer is
+/// matched on.
+pub async fn call(
+    state: &AppState,
+    sender_id: &str,
+    msg: &ControlMessage,
+    timeout: Duration,
+) -> Result<serde_json::Value, StrataError> {
+    let request_id = msg
+        .request_id()
+        .ok_or_else(|| StrataError::new(ErrorCode::Internal, "agent call without a request_id"))?
+        .to_string();
+    let deadline = Utc::now()
+        + chrono::Duration::from_std(timeout).unwrap_or_else(|_| chrono::Duration::zero());
+    let envelope = Envelope::from_message(msg)
+        .map_err(|e| StrataError::new(ErrorCode::Internal, e.to_string()))?
+        .with_deadline(deadline);
+    let json = serde_json::to_string(&envelope)
+        .map_err(|e| StrataError::new(ErrorCode::Internal, e.to_string()))?;
+    let Some(agent_tx) = state.agents().get(sender_id).map(|a| a.tx.clone()) else {
+        return Err(StrataError::new(
+            ErrorCode::DeviceOffline,
+            "sender is not connected",
+        ));
+    };
+
+    let (reply, rx) = oneshot::channel();
+    state.agent_calls().insert(
+        request_id.clone(),
+        PendingCall {
+            sender_id: sender_id.to_string(),
+            reply,
+        },
+    );
+    // Deregisters on every exit, including the REST caller going away.
+    let _pending = PendingGuard {
+        state,
+        request_id: &request_id,
+    };
+
+    if agent_tx.send(json).await.is_err() {
+        return Err(StrataError::new(
+            ErrorCode::DeviceOffline,
+            "sender disconnected",
+        ));
+    }
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(_)) => Err(StrataError::new(
+            ErrorCode::DeviceOffline,
+            "sender disconnected before answering",
+        )),
+        Err(_) => Err(StrataError::new(
+            ErrorCode::Timeout,
+            format!(
+                "sender did not answer {} within {}s",
+                envelope.msg_type,
+                timeout.as_secs()
+            ),
+        )),
+    }
+}
+
+/// Hand an agent's answer to the call waiting on `request_id`, if any.
+fn answer(state: &AppState, request_id: &str, reply: crate::state::RpcReply) {
+    if let Some((_, call)) = state.agent_calls().remove(request_id) {
+        let _ = call.reply.send(reply);
+    }
+}
+
+struct PendingGuard<'a> {
+    state: &'a AppState,
+    request_id: &'a str,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.state.agent_calls().remove(self.request_id);
+    }
+}
+
 /// Build a JSON error response string.
 fn error_response(msg: &str) -> String {
     let response = AuthLoginResponsePayload {
         success: false,
         sender_id: None,
         error: Some(msg.to_string()),
+        telemetry_encoding: None,
     };
     let envelope = Envelope::from_message(&ControlMessage::AuthLoginResponse(response)).unwrap();
     serde_json::to_string(&envelope).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_common::testvectors::{self, Leg};
+
+    #[test]
+    fn agent_leg_matches_the_golden_vectors() {
+        testvectors::check(Leg::Agent).unwrap();
+        testvectors::check(Leg::Control).unwrap();
+    }
+}
@@ -13,17 +13,30 @@
 //! rather than a `?token=` query param, since tokens in URLs end up in
 //! proxy/access logs. Every event delivered afterwards is scoped to the
 //! authenticated user's own resources; see `AppState::broadcast_dashboard`.
+//!
+//! Delivery is topic-based (`strata_protocol::DashboardTopic`). A new
+//! connection is subscribed to `fleet` and `alerts`; per-second telemetry
+//! (`sender:<id>`, `stream:<id>`) only flows after the client sends a
+//! `subscribe` envelope for it, and stops on `unsubscribe`. Subscribing to
+//! a telemetry topic replays its cached latest sample.
+
+use std::collections::HashMap;
 
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
 use axum::response::IntoResponse;
 use futures::SinkExt;
 use futures::stream::StreamExt;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
 
+use strata_common::auth::{AuthError, TokenScope};
 use strata_protocol::{
-    DashboardAuthPayload, DashboardAuthResponsePayload, DashboardEvent, Envelope, PROTOCOL_VERSION,
+    DashboardAuthPayload, DashboardAuthResponsePayload, DashboardClientMessage, DashboardEvent,
+    DashboardTopic, Envelope, PROTOCOL_VERSION,
 };
 
+use crate::api::auth_extractor::AuthUser;
 use crate::state::AppState;
 
 /// Axum handler — upgrades HTTP to WebSocket.
@@ -62,7 +75,10 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
 
     // Subscribe BEFORE building the snapshot so we don't miss events that
     // arrive in between.
-    let mut dashboard_rx = state.subscribe_dashboard();
+    let (event_tx, mut event_rx) = mpsc::channel::<DashboardEvent>(CONNECTION_EVENT_CAPACITY);
+    let mut subscriptions = Subscriptions::new(state.clone(), owner_id.clone(), event_tx);
+    subscriptions.add(DashboardTopic::Fleet);
+    subscriptions.add(DashboardTopic::Alerts);
 
     tracing::debug!(owner_id = %owner_id, "dashboard client connected");
 
@@ -110,21 +126,12 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
                 _ => continue,
             };
             snapshot.push(DashboardEvent::StreamStateChanged {
-                stream_id: stream_id.clone(),
-                sender_id: sender_id.clone(),
+                stream_id,
+                sender_id,
                 state: stream_state,
                 error: None,
                 reason: None,
             });
-
-            // Also send the last known stream stats if available
-            if let Some(stats) = state.stream_stats().get(&sender_id) {
-                snapshot.push(DashboardEvent::StreamStats(stats.clone()));
-            }
-            // ... and the receiver-side view of the same stream.
-            if let Some(stats) = state.receiver_stream_stats().get(&stream_id) {
-                snapshot.push(DashboardEvent::ReceiverStreamStats(stats.clone()));
-            }
         }
     }
 
@@ -142,45 +149,183 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
 
     loop {
         tokio::select! {
-            // Forward broadcast events to the browser, scoped to this owner.
-            event = dashboard_rx.recv() => {
-                match event {
-                    Ok((event_owner, event)) => {
-                        if event_owner != owner_id {
-                            continue;
-                        }
-                        let json = match serde_json::to_string(&event) {
-                            Ok(j) => j,
-                            Err(e) => {
-                                tracing::warn!(error = %e, "failed to serialize dashboard event");
-                                continue;
-                            }
-                        };
-                        if ws_tx.send(Message::Text(json.into())).await.is_err() {
-                            break;
-                        }
+            // Forward events from every subscribed topic.
+            event = event_rx.recv() => {
+                let Some(event) = event else { break };
+                let json = match serde_json::to_string(&event) {
+                    Ok(j) => j,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to serialize dashboard event");
+                        continue;
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                        tracing::warn!("dashboard client lagged, dropped {n} events");
-                    }
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                if ws_tx.send(Message::Text(json.into())).await.is_err() {
+                    break;
                 }
             }
 
-            // Handle client messages (subscriptions, pings)
+            // Handle client messages (subscriptions)
             msg = ws_rx.next() => {
                 match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        // Replayed samples go through the same queue as live
+                        // events so ordering per topic is preserved.
+                        for event in subscriptions.handle_client_message(&text).await {
+                            let _ = subscriptions.tx.try_send(event);
+                        }
+                    }
                     Some(Ok(Message::Close(_))) | None => break,
                     Some(Err(_)) => break,
-                    _ => {} // Ignore other messages for now
+                    _ => {}
                 }
             }
         }
     }
 
+    subscriptions.clear();
     tracing::debug!(owner_id = %owner_id, "dashboard client disconnected");
 }
 
+/// Per-connection buffer between the topic forwarders and the socket.
+const CONNECTION_EVENT_CAPACITY: usize = 256;
+
+/// Upper bound on topics per connection — a multiview of a large fleet is
+/// still well under this, a runaway client is not.
+const MAX_SUBSCRIPTIONS: usize = 128;
+
+/// The topic set of one dashboard connection: one forwarder task per
+/// topic, each draining its hub channel into the connection's queue.
+struct Subscriptions {
+    state: AppState,
+    owner_id: String,
+    tx: mpsc::Sender<DashboardEvent>,
+    active: HashMap<DashboardTopic, JoinHandle<()>>,
+}
+
+impl Subscriptions {
+    fn new(state: AppState, owner_id: String, tx: mpsc::Sender<DashboardEvent>) -> Self {
+        Self {
+            state,
+            owner_id,
+            tx,
+            active: HashMap::new(),
+        }
+    }
+
+    /// Subscribe to `topic`; returns false if already subscribed or the
+    /// per-connection limit is reached.
+    fn add(&mut self, topic: DashboardTopic) -> bool {
+        if self.active.contains_key(&topic) || self.active.len() >= MAX_SUBSCRIPTIONS {
+            return false;
+        }
+        let mut rx = self
+            .state
+            .subscribe_dashboard(&self.owner_id, topic.clone());
+        let tx = self.tx.clone();
+        let label = topic.to_string();
+        let handle = tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if tx.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::debug!(topic = %label, "dashboard client lagged, dropped {n} events");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        self.active.insert(topic, handle);
+        true
+    }
+
+    fn remove(&mut self, topic: &DashboardTopic) {
+        if let Some(handle) = self.active.remove(topic) {
+            handle.abort();
+            self.state.release_dashboard_topic(&self.owner_id, topic);
+        }
+    }
+
+    fn clear(&mut self) {
+        let topics: Vec<DashboardTopic> = self.active.keys().cloned().collect();
+        for topic in &topics {
+            self.remove(topic);
+        }
+    }
+
+    /// Apply a subscribe/unsubscribe envelope. Returns the cached latest
+    /// events for newly subscribed telemetry topics, to send immediately.
+    async fn handle_client_message(&mut self, raw: &str) -> Vec<DashboardEvent> {
+        let msg = match serde_json::from_str::<Envelope>(raw)
+            .map_err(|e| e.to_string())
+            .and_then(|env| {
+                env.parse_message::<DashboardClientMessage>()
+                    .map_err(|e| e.to_string())
+            }) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::debug!(owner_id = %self.owner_id, "ignoring dashboard client message: {e}");
+                return Vec::new();
+            }
+        };
+
+        let mut replay = Vec::new();
+        match msg {
+            DashboardClientMessage::Subscribe(p) => {
+                for topic in p.topics {
+                    if self.add(topic.clone())
+                        && let Some(event) = self.cached_event(&topic).await
+                    {
+                        replay.push(event);
+                    }
+                }
+            }
+            DashboardClientMessage::Unsubscribe(p) => {
+                for topic in &p.topics {
+                    self.remove(topic);
+                }
+            }
+        }
+        replay
+    }
+
+    /// Latest cached sample for a telemetry topic. The caches aren't keyed
+    /// by owner, so ownership is checked before replaying anything.
+    async fn cached_event(&self, topic: &DashboardTopic) -> Option<DashboardEvent> {
+        match topic {
+            DashboardTopic::Sender(sender_id) => {
+                let stats = self.state.live().stats(sender_id)?;
+                let owned: bool = sqlx::query_scalar(
+                    "SELECT EXISTS(SELECT 1 FROM senders WHERE id = $1 AND owner_id = $2)",
+                )
+                .bind(sender_id)
+                .bind(&self.owner_id)
+                .fetch_one(self.state.pool())
+                .await
+                .unwrap_or(false);
+                owned.then_some(DashboardEvent::StreamStats(stats))
+            }
+            DashboardTopic::Stream(stream_id) => {
+                let stats = self.state.receiver_stream_stats().get(stream_id)?.clone();
+                let owned: bool = sqlx::query_scalar(
+                    "SELECT EXISTS(SELECT 1 FROM streams s JOIN senders sn ON sn.id = s.sender_id \
+                     WHERE s.id = $1 AND sn.owner_id = $2)",
+                )
+                .bind(stream_id)
+                .bind(&self.owner_id)
+                .fetch_one(self.state.pool())
+                .await
+                .unwrap_or(false);
+                owned.then_some(DashboardEvent::ReceiverStreamStats(stats))
+            }
+            DashboardTopic::Fleet | DashboardTopic::Alerts => None,
+        }
+    }
+}
+
 /// Authenticate the dashboard client from its first message.
 /// Returns `Ok((owner_id, response_json))` on success.
 async fn authenticate(state: &AppState, raw: &str) -> Result<(String, String), String> {
@@ -205,17 +350,21 @@ async fn authenticate(state: &AppState, raw: &str) -> Result<(String, String), S
 
     let claims = state
         .jwt()
-        .verify_token(&payload.token)
-        .map_err(|_| error_response("invalid or expired token"))?;
-
-    // A device (sender/receiver) session token carries `owner`; a user
-    // session token does not (see api/auth.rs::login). Only user tokens
-    // may open the dashboard feed.
-    if claims.owner.is_some() {
-        return Err(error_response("token is not a user session"));
-    }
+        .verify_scoped(&payload.token, &[TokenScope::User])
+        .map_err(|e| match e {
+            AuthError::WrongScope(_) => error_response("token is not a user session"),
+            _ => error_response("invalid or expired token"),
+        })?;
 
-    let owner_id = claims.sub;
+    // Invited users watch their account's fleet, not their own.
+    let owner_id = AuthUser::load(state, &claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to load dashboard user");
+            error_response("internal error")
+        })?
+        .ok_or_else(|| error_response("invalid or expired token"))?
+        .owner_id;
     let response = DashboardAuthResponsePayload {
         success: true,
         error: None,
@@ -235,3 +384,14 @@ fn error_response(msg: &str) -> String {
     let envelope = Envelope::new("auth.login.response", &response);
     serde_json::to_string(&envelope).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_common::testvectors::{self, Leg};
+
+    #[test]
+    fn dashboard_leg_matches_the_golden_vectors() {
+        testvectors::check(Leg::DashboardClient).unwrap();
+        testvectors::check(Leg::DashboardEvent).unwrap();
+    }
+}
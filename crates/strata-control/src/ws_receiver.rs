@@ -60,6 +60,8 @@ async fn handle_socket(state: AppState, socket: WebSocket) {
         .execute(state.pool())
         .await;
 
+    crate::api::certificates::push_to_receiver(&state, &receiver_id).await;
+
     // Bidirectional message loop
     loop {
         tokio::select! {
@@ -191,7 +193,8 @@ async fn record_capacity(
 ) -> Result<(), String> {
     sqlx::query(
         "UPDATE receivers SET hostname = $1, bind_host = $2, link_ports = $3, \
-         max_streams = $4, region = $5, online = TRUE, last_seen_at = $6 WHERE id = $7",
+         max_streams = $4, region = $5, preview_base_url = $6, online = TRUE, \
+         last_seen_at = $7, ingest_protocols = $9 WHERE id = $8",
     )
     .bind(&payload.hostname)
     .bind(&payload.bind_host)
@@ -204,8 +207,16 @@ async fn record_capacity(
     )
     .bind(payload.max_streams as i32)
     .bind(&payload.region)
+    .bind(&payload.preview_base_url)
     .bind(Utc::now())
     .bind(receiver_id)
+    .bind(
+        payload
+            .ingest
+            .iter()
+            .map(|p| p.as_str())
+            .collect::<Vec<&str>>(),
+    )
     .execute(state.pool())
     .await
     .map_err(|e| format!("db error: {e}"))?;
@@ -227,19 +238,23 @@ async fn enroll(
         );
     };
 
-    let row: Option<(String, Option<String>)> =
-        sqlx::query_as("SELECT owner_id, enrollment_token FROM receivers WHERE id = $1")
-            .bind(&receiver_id)
-            .fetch_optional(state.pool())
-            .await
-            .map_err(|e| format!("db error: {e}"))?;
+    let row: Option<(String, Option<String>, bool)> = sqlx::query_as(
+        "SELECT owner_id, enrollment_token, enrollment_token_legacy FROM receivers WHERE id = $1",
+    )
+    .bind(&receiver_id)
+    .fetch_optional(state.pool())
+    .await
+    .map_err(|e| format!("db error: {e}"))?;
 
-    let Some((owner_id, token_hash)) = row else {
+    let Some((owner_id, token_hash, legacy)) = row else {
         return Err("invalid enrollment token".into());
     };
     let Some(token_hash) = token_hash else {
         return Err("enrollment token already used — reconnect with the device key".into());
     };
+    if !legacy && strata_common::ids::validate_enrollment_token(token).is_err() {
+        return Err("invalid enrollment token".into());
+    }
 
     if !auth::verify_password(&secret, &token_hash).unwrap_or(false) {
         return Err("invalid enrollment token".into());
@@ -403,6 +418,7 @@ async fn handle_receiver_message(state: &AppState, receiver_id: &str, owner_id:
             // truth — surface them instead of dropping at trace level (E8).
             // Cache for late-joining dashboards (same contract as the
             // sender-side stream_stats cache).
+            crate::api::ingest::observe_stats(state, owner_id, &payload.stream_id).await;
             state
                 .receiver_stream_stats()
                 .insert(payload.stream_id.clone(), payload.clone());
@@ -447,6 +463,8 @@ async fn handle_receiver_message(state: &AppState, receiver_id: &str, owner_id:
             }
 
             state.live_streams().remove(&payload.stream_id);
+
+            crate::api::usage::finish_stream_usage(state, &payload.stream_id).await;
         }
     }
 }
@@ -461,3 +479,14 @@ fn error_response(msg: &str) -> String {
         Envelope::from_message(&ReceiverControlMessage::AuthLoginResponse(response)).unwrap();
     serde_json::to_string(&envelope).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_common::testvectors::{self, Leg};
+
+    #[test]
+    fn receiver_leg_matches_the_golden_vectors() {
+        testvectors::check(Leg::Receiver).unwrap();
+        testvectors::check(Leg::ReceiverControl).unwrap();
+    }
+}
@@ -53,7 +53,9 @@ async fn test_state() -> Option<strata_control::state::AppState> {
     let _ = sqlx::query("DELETE FROM users").execute(&pool).await;
 
     let (jwt, _seed) = JwtContext::generate();
-    Some(strata_control::state::AppState::new(pool, jwt))
+    let state = strata_control::state::AppState::new(pool, jwt);
+    state.set_secrets(strata_common::auth::SecretBox::generate().0);
+    Some(state)
 }
 
 /// Build a test app with a fresh database pool and return the Router.
@@ -67,12 +69,21 @@ async fn test_app() -> Option<Router> {
 }
 
 /// Like `test_app`, but also returns the underlying `AppState` (needed to
-/// drive the dashboard broadcast channel directly, and to mount `/ws` for
-/// WebSocket tests).
+/// drive the dashboard broadcast channel directly) and mounts the
+/// WebSocket endpoints (`/ws`, `/agent/ws`, `/receiver/ws`) for WebSocket
+/// tests.
 async fn test_app_with_state() -> Option<(Router, strata_control::state::AppState)> {
     let state = test_state().await?;
     let app = Router::new()
         .nest("/api", strata_control::api::router())
+        .route(
+            "/agent/ws",
+            axum::routing::get(strata_control::ws_agent::handler),
+        )
+        .route(
+            "/receiver/ws",
+            axum::routing::get(strata_control::ws_receiver::handler),
+        )
         .route(
             "/ws",
             axum::routing::get(strata_control::ws_dashboard::handler),
@@ -144,7 +155,7 @@ async fn register_creates_user() {
             "/api/auth/register",
             serde_json::json!({
                 "email": "test@example.com",
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -176,6 +187,26 @@ async fn register_rejects_short_password() {
     assert_eq!(resp.status(), 400);
 }
 
+#[tokio::test]
+async fn register_rejects_guessable_password() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+
+    let resp = app
+        .oneshot(json_post(
+            "/api/auth/register",
+            serde_json::json!({
+                "email": "weak@example.com",
+                "password": "password123"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), 400);
+}
+
 #[tokio::test]
 async fn register_rejects_invalid_email() {
     let Some(app) = test_app().await else {
@@ -187,7 +218,7 @@ async fn register_rejects_invalid_email() {
             "/api/auth/register",
             serde_json::json!({
                 "email": "not-an-email",
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -209,7 +240,7 @@ async fn login_returns_jwt() {
             "/api/auth/register",
             serde_json::json!({
                 "email": "login@example.com",
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -222,7 +253,7 @@ async fn login_returns_jwt() {
             "/api/auth/login",
             serde_json::json!({
                 "email": "login@example.com",
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -231,7 +262,7 @@ async fn login_returns_jwt() {
     assert_eq!(resp.status(), 200);
     let body = json_body(resp).await;
     assert!(!body["token"].as_str().unwrap().is_empty());
-    assert_eq!(body["role"], "operator");
+    assert_eq!(body["role"], "admin");
 }
 
 #[tokio::test]
@@ -247,7 +278,7 @@ async fn login_rejects_wrong_password() {
             "/api/auth/register",
             serde_json::json!({
                 "email": "wrongpw@example.com",
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await;
@@ -278,7 +309,7 @@ async fn register_and_login(app: &Router) -> String {
             "/api/auth/register",
             serde_json::json!({
                 "email": email,
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -290,7 +321,7 @@ async fn register_and_login(app: &Router) -> String {
             "/api/auth/login",
             serde_json::json!({
                 "email": email,
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -311,7 +342,7 @@ async fn register_and_login_with_id(app: &Router) -> (String, String) {
             "/api/auth/register",
             serde_json::json!({
                 "email": email,
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -327,7 +358,7 @@ async fn register_and_login_with_id(app: &Router) -> (String, String) {
             "/api/auth/login",
             serde_json::json!({
                 "email": email,
-                "password": "password123"
+                "password": "plum-harbor-velvet-42"
             }),
         ))
         .await
@@ -337,6 +368,22 @@ async fn register_and_login_with_id(app: &Router) -> (String, String) {
     (user_id, token)
 }
 
+/// Accept the invitation in an invite response body, returning the
+/// password it set.
+async fn accept_invite(app: &Router, invite: &serde_json::Value) -> String {
+    let password = "plum-harbor-velvet-42";
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/accept-invite",
+            serde_json::json!({ "token": invite["invite_token"], "password": password }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    password.to_string()
+}
+
 // ── Sender CRUD Tests ───────────────────────────────────────────────
 
 #[tokio::test]
@@ -515,6 +562,102 @@ async fn create_and_list_destinations() {
     assert!(dests.iter().any(|d| d["id"] == dest_id));
 }
 
+#[tokio::test]
+async fn tenant_export_imports_into_another_account_without_secrets() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+    let source = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/destinations",
+            &source,
+            serde_json::json!({
+                "platform": "youtube",
+                "name": "Main Channel",
+                "url": "rtmp://a.rtmp.youtube.com/live2",
+                "stream_key": "secret-key-1234"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &source,
+            serde_json::json!({ "name": "Van 1" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/senders/{sender_id}/alerts"),
+            &source,
+            serde_json::json!({
+                "name": "Few links",
+                "metric": "link_count",
+                "condition": "below",
+                "threshold": 2.0,
+                "enabled": true
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/export", &source))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let bundle = json_body(resp).await;
+    assert!(!bundle.to_string().contains("secret-key-1234"));
+    assert_eq!(bundle["senders"][0]["alert_rules"][0]["name"], "Few links");
+
+    // Restore into a fresh account, twice: the second import matches
+    // everything the first one created.
+    let target = register_and_login(&app).await;
+    let resp = app
+        .clone()
+        .oneshot(auth_post("/api/import", &target, bundle.clone()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let summary = json_body(resp).await;
+    assert_eq!(summary["senders_created"].as_array().unwrap().len(), 1);
+    assert_eq!(summary["destinations_created"], 1);
+    assert_eq!(summary["alert_rules"], 1);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post("/api/import", &target, bundle))
+        .await
+        .unwrap();
+    let summary = json_body(resp).await;
+    assert!(summary["senders_created"].as_array().unwrap().is_empty());
+    assert_eq!(summary["senders_matched"], 1);
+    assert_eq!(summary["destinations_matched"], 1);
+
+    let resp = app
+        .oneshot(auth_get("/api/destinations", &target))
+        .await
+        .unwrap();
+    let dests = json_body(resp).await;
+    let dests = dests.as_array().unwrap();
+    assert_eq!(dests.len(), 1);
+    assert_eq!(dests[0]["has_stream_key"], false);
+}
+
 #[tokio::test]
 async fn delete_destination() {
     let Some(app) = test_app().await else {
@@ -558,6 +701,119 @@ async fn delete_destination() {
     assert!(!dests.iter().any(|d| d["id"] == dest_id));
 }
 
+#[tokio::test]
+async fn destination_key_rotation_and_health_check() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+
+    let token = register_and_login(&app).await;
+
+    // A scheme the platform doesn't accept is refused outright.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/destinations",
+            &token,
+            serde_json::json!({
+                "platform": "srt",
+                "name": "Bad",
+                "url": "rtmp://example.com/app"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    // Nothing listens on port 1, so the probe reports an error.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/destinations",
+            &token,
+            serde_json::json!({
+                "platform": "custom_rtmp",
+                "name": "Studio",
+                "url": "rtmp://127.0.0.1:1/live",
+                "stream_key": "studio-key-0001"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let dest_id = json_body(resp).await["id"].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/destinations/{dest_id}/check"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let health = json_body(resp).await;
+    assert_eq!(health["status"], "error");
+    assert!(health["checked_at"].is_string());
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/destinations", &token))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let dest = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["id"] == dest_id.as_str())
+        .unwrap();
+    assert_eq!(dest["has_stream_key"], true);
+    assert_eq!(dest["stream_key_hint"], "0001");
+    assert_eq!(dest["health"]["status"], "error");
+    assert!(dest.get("stream_key").is_none());
+
+    // Rotate, then reveal the new key.
+    let resp = app
+        .clone()
+        .oneshot(
+            axum::http::Request::builder()
+                .uri(format!("/api/destinations/{dest_id}/stream-key"))
+                .method("PUT")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::from(r#"{"stream_key":"studio-key-0002"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json_body(resp).await["stream_key_hint"], "0002");
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get(
+            &format!("/api/destinations/{dest_id}/stream-key"),
+            &token,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json_body(resp).await["stream_key"], "studio-key-0002");
+
+    // No streams have used it yet.
+    let resp = app
+        .oneshot(auth_get(
+            &format!("/api/destinations/{dest_id}/usage"),
+            &token,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json_body(resp).await, serde_json::json!([]));
+}
+
 // ── Auth Guard Tests ────────────────────────────────────────────────
 
 #[tokio::test]
@@ -595,6 +851,19 @@ async fn invalid_token_is_rejected() {
     assert_eq!(resp.status(), 401);
 }
 
+#[tokio::test]
+async fn valid_token_during_a_database_outage_is_a_server_error() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    // Not a 401: the dashboard would take that as "log out".
+    state.pool().close().await;
+    let resp = app.oneshot(auth_get("/api/senders", &token)).await.unwrap();
+    assert_eq!(resp.status(), 500);
+}
+
 // ── Stream Tests ────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -662,76 +931,1301 @@ async fn start_stream_concurrent_guard_query_does_not_error() {
     );
 }
 
-// ── Cross-User Isolation Tests ──────────────────────────────────────
+// ── Stream Schedule Tests ───────────────────────────────────────────
 
 #[tokio::test]
-async fn users_cannot_see_each_others_senders() {
-    let Some(app) = test_app().await else {
+async fn double_booked_sender_is_flagged_and_offline_start_fails() {
+    let Some((app, state)) = test_app_with_state().await else {
         return;
     };
+    let token = register_and_login(&app).await;
 
-    let token_a = register_and_login(&app).await;
-    let token_b = register_and_login(&app).await;
-
-    // User A creates a sender
     let resp = app
         .clone()
         .oneshot(auth_post(
             "/api/senders",
-            &token_a,
-            serde_json::json!({ "name": "User A Sender" }),
+            &token,
+            serde_json::json!({ "name": "Booked Van" }),
         ))
         .await
         .unwrap();
-    let body = json_body(resp).await;
-    let sender_id = body["sender_id"].as_str().unwrap().to_string();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
 
-    // User B cannot see it in list
+    let now = chrono::Utc::now();
+    let book = |from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>| {
+        serde_json::json!({
+            "sender_id": sender_id,
+            "title": "Match",
+            "resolution": "1280x720",
+            "framerate": 30,
+            "starts_at": from,
+            "ends_at": to,
+        })
+    };
+
+    // Already due: starts a minute ago.
     let resp = app
         .clone()
-        .oneshot(auth_get("/api/senders", &token_b))
+        .oneshot(auth_post(
+            "/api/schedules",
+            &token,
+            book(
+                now - chrono::Duration::minutes(1),
+                now + chrono::Duration::hours(1),
+            ),
+        ))
         .await
         .unwrap();
-    let body = json_body(resp).await;
-    assert!(
-        !body
-            .as_array()
-            .unwrap()
-            .iter()
-            .any(|s| s["id"] == sender_id)
-    );
+    assert_eq!(resp.status(), 201);
+    let first = json_body(resp).await;
+    let first_id = first["id"].as_str().unwrap().to_string();
+    assert_eq!(first["state"], "scheduled");
 
-    // User B cannot access it directly
+    // Overlaps the first on the same sender.
     let resp = app
-        .oneshot(auth_get(&format!("/api/senders/{sender_id}"), &token_b))
+        .clone()
+        .oneshot(auth_post(
+            "/api/schedules",
+            &token,
+            book(
+                now + chrono::Duration::minutes(30),
+                now + chrono::Duration::hours(2),
+            ),
+        ))
         .await
         .unwrap();
-    assert_eq!(resp.status(), 404);
-}
+    assert_eq!(resp.status(), 201);
+    let second = json_body(resp).await;
+    assert_eq!(second["conflicts"], serde_json::json!([first_id]));
 
-// ── Dashboard WebSocket: auth + owner scoping (E3) ───────────────────
+    // Backwards interval is rejected.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/schedules",
+            &token,
+            book(
+                now + chrono::Duration::hours(2),
+                now + chrono::Duration::hours(1),
+            ),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
 
-/// Send the `auth.login` handshake envelope a real dashboard client sends
-/// as its first WS message (see `strata-dashboard/src/ws.rs::
-/// build_auth_message` and `ws_dashboard.rs::authenticate`).
-async fn ws_send_auth(
-    ws: &mut tokio_tungstenite::WebSocketStream<
-        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-    >,
-    token: &str,
-) {
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
-    let envelope = serde_json::json!({
-        "id": "test-auth",
-        "type": "auth.login",
-        "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": { "token": token },
-    });
-    ws.send(Message::Text(envelope.to_string().into()))
+    // The sender never connected, so the due booking fails to start — and
+    // a failed booking no longer conflicts with anything.
+    strata_control::api::schedules::tick(&state).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/schedules", &token))
         .await
         .unwrap();
-}
+    let list = json_body(resp).await;
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0]["state"], "failed");
+    assert!(list[0]["error"].as_str().unwrap().contains("offline"));
+    assert!(list[1].get("conflicts").is_none());
+
+    let resp = app
+        .oneshot(auth_delete(&format!("/api/schedules/{first_id}"), &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+}
+
+// ── Preferences Tests ───────────────────────────────────────────────
+
+#[tokio::test]
+async fn preferences_default_then_persist() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/me/preferences", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    assert_eq!(body["theme"], "dark");
+    assert_eq!(body["default_page"], "/overview");
+
+    let put = |body: serde_json::Value| {
+        axum::http::Request::builder()
+            .uri("/api/me/preferences")
+            .method("PUT")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(put(serde_json::json!({
+            "theme": "light",
+            "default_page": "/streams",
+            "rate_units": "bytes",
+            "graph_window_s": 120,
+            "locale": "es",
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let resp = app
+        .clone()
+        .oneshot(put(serde_json::json!({ "default_page": "/nowhere" })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let resp = app
+        .oneshot(auth_get("/api/me/preferences", &token))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    assert_eq!(body["theme"], "light");
+    assert_eq!(body["default_page"], "/streams");
+    assert_eq!(body["rate_units"], "bytes");
+    assert_eq!(body["graph_window_s"], 120);
+    assert_eq!(body["locale"], "es");
+}
+
+// ── Usage Tests ─────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn metered_bytes_survive_counter_resets_and_flush_on_stream_end() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let mut senders = Vec::new();
+    for name in ["Owner", "Other"] {
+        let resp = app
+            .clone()
+            .oneshot(auth_post(
+                "/api/senders",
+                &token,
+                serde_json::json!({ "name": name }),
+            ))
+            .await
+            .unwrap();
+        senders.push(
+            json_body(resp).await["sender_id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    let (owner, other) = (&senders[0], &senders[1]);
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', now())",
+    )
+    .bind("str_meter")
+    .bind(owner)
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    let stats = |sent_bytes: u64| -> strata_protocol::StreamStatsPayload {
+        serde_json::from_value(serde_json::json!({
+            "stream_id": "str_meter", "uptime_s": 10, "encoder_bitrate_kbps": 4000,
+            "links": [{
+                "id": 0, "interface": "wwan0", "state": "Live", "rtt_ms": 40.0,
+                "loss_rate": 0.0, "capacity_bps": 5_000_000, "sent_bytes": sent_bytes,
+                "signal_dbm": null, "rsrp": null, "rsrq": null, "sinr": null, "cqi": null,
+            }],
+        }))
+        .unwrap()
+    };
+    let metered = || async {
+        sqlx::query_scalar::<_, i64>("SELECT total_bytes FROM streams WHERE id = 'str_meter'")
+            .fetch_one(state.pool())
+            .await
+            .unwrap()
+    };
+
+    // Another sender can't bill to this stream.
+    strata_control::api::usage::record_stream_bytes(&state, other, &stats(9_000_000)).await;
+    strata_control::api::usage::finish_stream_usage(&state, "str_meter").await;
+    assert_eq!(metered().await, 0);
+
+    // 4 MB, a pipeline restart, then 1 MB more: 5 MB, none of it flushed
+    // yet inside the flush interval.
+    for sent in [1_000_000, 4_000_000, 0, 1_000_000] {
+        strata_control::api::usage::record_stream_bytes(&state, owner, &stats(sent)).await;
+    }
+    assert_eq!(metered().await, 0);
+
+    // Ending the stream writes what was pending.
+    strata_control::api::usage::finish_stream_usage(&state, "str_meter").await;
+    assert_eq!(metered().await, 5_000_000);
+    assert!(state.usage_meters().get("str_meter").is_none());
+}
+
+#[tokio::test]
+async fn usage_rolls_up_minutes_and_bytes_per_month() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Truck, North" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Two 30-minute events in September, one in October.
+    for (id, start, bytes) in [
+        ("str_u1", "2026-09-05T10:00:00Z", 1_000_000_000_i64),
+        ("str_u2", "2026-09-20T10:00:00Z", 500_000_000),
+        ("str_u3", "2026-10-02T10:00:00Z", 2_000_000_000),
+    ] {
+        let started: chrono::DateTime<chrono::Utc> = start.parse().unwrap();
+        sqlx::query(
+            "INSERT INTO streams (id, sender_id, state, started_at, ended_at, total_bytes) \
+             VALUES ($1, $2, 'ended', $3, $4, $5)",
+        )
+        .bind(id)
+        .bind(&sender_id)
+        .bind(started)
+        .bind(started + chrono::Duration::minutes(30))
+        .bind(bytes)
+        .execute(state.pool())
+        .await
+        .unwrap();
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/usage?from=2026-09&to=2026-10", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    let rows = body.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["month"], "2026-09");
+    assert_eq!(rows[0]["stream_count"], 2);
+    assert_eq!(rows[0]["streamed_minutes"], 60.0);
+    assert_eq!(rows[0]["total_bytes"], 1_500_000_000_u64);
+    assert_eq!(rows[1]["month"], "2026-10");
+
+    let resp = app
+        .oneshot(auth_get(
+            "/api/usage/export.csv?from=2026-09&to=2026-09",
+            &token,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(
+        resp.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv")
+    );
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    assert_eq!(
+        csv,
+        format!(
+            "month,sender_id,sender_name,streams,streamed_minutes,gigabytes\r\n\
+             2026-09,{sender_id},\"Truck, North\",2,60.0,1.500\r\n"
+        )
+    );
+}
+
+#[tokio::test]
+async fn usage_splits_streams_across_months_and_only_runs_live_ones_to_now() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Truck" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Two hours across the September/October boundary; a stream that
+    // failed without an end time; a stream live for the last 30 minutes.
+    let at = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let now = chrono::Utc::now();
+    for (id, stream_state, started, ended, bytes) in [
+        (
+            "str_s1",
+            "ended",
+            at("2024-09-30T23:00:00Z"),
+            Some(at("2024-10-01T01:00:00Z")),
+            2_000_000_000_i64,
+        ),
+        ("str_s2", "failed", at("2024-10-10T10:00:00Z"), None, 0),
+        (
+            "str_s3",
+            "live",
+            now - chrono::Duration::minutes(30),
+            None,
+            0,
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO streams (id, sender_id, state, started_at, ended_at, total_bytes) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(id)
+        .bind(&sender_id)
+        .bind(stream_state)
+        .bind(started)
+        .bind(ended)
+        .bind(bytes)
+        .execute(state.pool())
+        .await
+        .unwrap();
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/usage?from=2024-09&to=2024-10", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    let rows = body.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["month"], "2024-09");
+    assert_eq!(rows[0]["stream_count"], 1);
+    assert_eq!(rows[0]["streamed_minutes"], 60.0);
+    assert_eq!(rows[0]["total_bytes"], 1_000_000_000_u64);
+    // October: the rest of the first stream; the failed one adds no time.
+    assert_eq!(rows[1]["month"], "2024-10");
+    assert_eq!(rows[1]["stream_count"], 2);
+    assert_eq!(rows[1]["streamed_minutes"], 60.0);
+    assert_eq!(rows[1]["total_bytes"], 1_000_000_000_u64);
+
+    // The live stream is measured up to now (it may straddle a month
+    // boundary, so sum the default range).
+    let resp = app.oneshot(auth_get("/api/usage", &token)).await.unwrap();
+    let body = json_body(resp).await;
+    let live_minutes: f64 = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["streamed_minutes"].as_f64().unwrap())
+        .sum();
+    assert!((29.9..31.0).contains(&live_minutes), "{live_minutes}");
+}
+
+#[tokio::test]
+async fn deleting_an_account_keeps_usage_split_across_months() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Truck" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Two hours across the New Year boundary.
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at, total_bytes) \
+         VALUES ($1, $2, 'ended', '2019-12-31T23:00:00Z', '2020-01-01T01:00:00Z', 2000000000)",
+    )
+    .bind(format!("str_{}", uuid::Uuid::now_v7()))
+    .bind(&sender_id)
+    .execute(state.pool())
+    .await
+    .unwrap();
+    let last_id: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(id), 0) FROM usage_anonymized")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/usage?from=2019-12&to=2020-01", &token))
+        .await
+        .unwrap();
+    let billed = json_body(resp).await;
+    let resp = app
+        .clone()
+        .oneshot(auth_delete("/api/me", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+
+    let kept: Vec<(String, i64, f64, i64)> = sqlx::query_as(
+        "SELECT month, stream_count, streamed_minutes, total_bytes FROM usage_anonymized \
+         WHERE id > $1 AND month IN ('2019-12', '2020-01') ORDER BY month",
+    )
+    .bind(last_id)
+    .fetch_all(state.pool())
+    .await
+    .unwrap();
+    assert_eq!(
+        kept,
+        [
+            ("2019-12".to_string(), 1, 60.0, 1_000_000_000),
+            ("2020-01".to_string(), 1, 60.0, 1_000_000_000),
+        ]
+    );
+    // The same totals the owner was billed.
+    assert_eq!(billed.as_array().unwrap().len(), kept.len());
+    for (row, (month, streams, minutes, bytes)) in billed.as_array().unwrap().iter().zip(&kept) {
+        assert_eq!(row["month"], month.as_str());
+        assert_eq!(row["stream_count"], *streams);
+        assert_eq!(row["streamed_minutes"], *minutes);
+        assert_eq!(row["total_bytes"], *bytes);
+    }
+}
+
+#[tokio::test]
+async fn ended_stream_report_collects_incidents_and_annotations() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Truck North" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let started: chrono::DateTime<chrono::Utc> = "2026-09-05T10:00:00Z".parse().unwrap();
+    let at = |secs: i64| started + chrono::Duration::seconds(secs);
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at, total_bytes) \
+         VALUES ('str_r1', $1, 'ended', $2, $3, 1000000)",
+    )
+    .bind(&sender_id)
+    .bind(started)
+    .bind(at(600))
+    .execute(state.pool())
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO link_events (stream_id, sender_id, ts, interface, kind, phase) \
+         VALUES ('str_r1', $1, $2, 'wwan0', 'phase', 'live'), \
+                ('str_r1', $1, $3, 'wwan0', 'phase', 'failover')",
+    )
+    .bind(&sender_id)
+    .bind(at(0))
+    .bind(at(300))
+    .execute(state.pool())
+    .await
+    .unwrap();
+    for (secs, loss) in [(100, 1.0), (110, 9.0), (120, 14.0), (130, 2.0)] {
+        sqlx::query(
+            "INSERT INTO sender_metrics \
+             (sender_id, stream_id, ts, bitrate_kbps, throughput_bps, loss_pct, link_count) \
+             VALUES ($1, 'str_r1', $2, 4000, 4000000, $3, 2)",
+        )
+        .bind(&sender_id)
+        .bind(at(secs))
+        .bind(loss)
+        .execute(state.pool())
+        .await
+        .unwrap();
+    }
+    sqlx::query(
+        "INSERT INTO ladder_steps (stream_id, ts, from_rung, to_rung, resolution) \
+         VALUES ('str_r1', $1, 0, 1, '1280x720')",
+    )
+    .bind(at(310))
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/streams/str_r1/annotations",
+            &token,
+            serde_json::json!({ "text": "  truck entered the tunnel ", "ts": at(290) }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    assert_eq!(json_body(resp).await["text"], "truck entered the tunnel");
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/streams/str_r1/annotations",
+            &token,
+            serde_json::json!({ "text": "  " }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/streams/str_r1/report", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let report = json_body(resp).await;
+    assert_eq!(report["sender_name"], "Truck North");
+    let kinds: Vec<&str> = report["incidents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        ["loss_spike", "annotation", "link_failover", "ladder_step"]
+    );
+    assert_eq!(report["incidents"][0]["duration_s"], 20);
+    assert_eq!(report["incidents"][2]["interface"], "wwan0");
+
+    // Reading an ended stream's report stores it.
+    let stored: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM stream_reports WHERE stream_id = 'str_r1')",
+    )
+    .fetch_one(state.pool())
+    .await
+    .unwrap();
+    assert!(stored);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/streams/str_r1/report?format=pdf", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/pdf");
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    assert!(bytes.starts_with(b"%PDF-"));
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/streams/str_r1/report?format=docx", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let other = register_and_login(&app).await;
+    let resp = app
+        .oneshot(auth_get("/api/streams/str_r1/report", &other))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+// ── Alert & Audit Tests ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn breached_rule_fires_alert_that_can_be_acknowledged() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let (user_id, token) = register_and_login_with_id(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Van 7" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/senders/{sender_id}/alerts"),
+            &token,
+            serde_json::json!({
+                "name": "No links",
+                "metric": "link_count",
+                "condition": "below",
+                "threshold": 1,
+                "enabled": true,
+                "severity": "critical",
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/alerts/rules", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    let rules = body.as_object().unwrap();
+    assert_eq!(rules.len(), 1, "only senders with rules are listed");
+    assert_eq!(rules[&sender_id][0]["name"], "No links");
+
+    // A sample with no live links breaches the rule.
+    let stats = strata_protocol::StreamStatsPayload {
+        stream_id: "str_alert".into(),
+        sender_id: sender_id.clone(),
+        uptime_s: 10,
+        encoder_bitrate_kbps: 0,
+        timestamp_ms: 0,
+        links: vec![],
+        sender_metrics: None,
+        receiver_metrics: None,
+        ladder: None,
+        quality: None,
+        elements: Vec::new(),
+        media: None,
+    };
+    strata_control::api::alerts::evaluate(&state, &user_id, &stats).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/alerts?severity=critical&state=open", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    let alerts = body.as_array().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["state"], "firing");
+    assert_eq!(alerts[0]["sender_name"], "Van 7");
+    assert_eq!(alerts[0]["value"], 0.0);
+    let alert_id = alerts[0]["id"].as_str().unwrap().to_string();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/alerts?severity=info", &token))
+        .await
+        .unwrap();
+    assert!(json_body(resp).await.as_array().unwrap().is_empty());
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/alerts/{alert_id}/ack"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    assert_eq!(body["state"], "acknowledged");
+    assert!(
+        body["acknowledged_by"]
+            .as_str()
+            .unwrap()
+            .ends_with("@test.com")
+    );
+
+    // Acknowledging twice is a state error, not a silent success.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/alerts/{alert_id}/ack"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get(
+            &format!("/api/audit?sender_id={sender_id}"),
+            &token,
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let actions: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["alert.ack", "alert_rule.set", "sender.create"]);
+
+    let resp = app
+        .oneshot(auth_get("/api/audit?actor=system", &token))
+        .await
+        .unwrap();
+    assert!(json_body(resp).await.as_array().unwrap().is_empty());
+}
+
+// ── Cross-User Isolation Tests ──────────────────────────────────────
+
+#[tokio::test]
+async fn users_cannot_see_each_others_senders() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+
+    let token_a = register_and_login(&app).await;
+    let token_b = register_and_login(&app).await;
+
+    // User A creates a sender
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token_a,
+            serde_json::json!({ "name": "User A Sender" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let sender_id = body["sender_id"].as_str().unwrap().to_string();
+
+    // User B cannot see it in list
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/senders", &token_b))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    assert!(
+        !body
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| s["id"] == sender_id)
+    );
+
+    // User B cannot access it directly
+    let resp = app
+        .oneshot(auth_get(&format!("/api/senders/{sender_id}"), &token_b))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+// ── User Management ─────────────────────────────────────────────────
+
+#[tokio::test]
+async fn invited_user_shares_fleet_within_their_role() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+    let admin = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &admin,
+            serde_json::json!({ "name": "Shared Van" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Invite a viewer; their invitation link sets a password that logs
+    // them in.
+    let email = format!("viewer-{}@test.com", uuid::Uuid::now_v7());
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/users",
+            &admin,
+            serde_json::json!({ "email": email, "role": "viewer" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let body = json_body(resp).await;
+    let viewer_id = body["user"]["id"].as_str().unwrap().to_string();
+    let password = accept_invite(&app, &body).await;
+
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/login",
+            serde_json::json!({ "email": email, "password": password }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = json_body(resp).await;
+    assert_eq!(body["capabilities"], serde_json::json!([]));
+    let viewer = body["token"].as_str().unwrap().to_string();
+
+    // Same fleet…
+    let resp = app
+        .clone()
+        .oneshot(auth_get(&format!("/api/senders/{sender_id}"), &viewer))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // …but read-only, and no user management.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &viewer,
+            serde_json::json!({ "name": "Nope" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/users", &viewer))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+
+    // `/api/me` reports what each may do, for the dashboard to gate on.
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/me", &viewer))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let me = json_body(resp).await;
+    assert_eq!(me["role"], "viewer");
+    assert_eq!(me["owner"], false);
+    assert_eq!(me["capabilities"], serde_json::json!([]));
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/me", &admin))
+        .await
+        .unwrap();
+    let me = json_body(resp).await;
+    assert_eq!(me["owner"], true);
+    let caps = me["capabilities"].as_array().unwrap();
+    assert!(caps.contains(&serde_json::json!("manage_users")));
+    assert!(caps.contains(&serde_json::json!("control_streams")));
+
+    // The admin sees both users, owner first.
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/users", &admin))
+        .await
+        .unwrap();
+    let users = json_body(resp).await;
+    let users = users.as_array().unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0]["owner"], true);
+    assert_eq!(users[1]["id"], viewer_id.as_str());
+
+    // Disabling revokes the viewer's existing session.
+    let resp = app
+        .clone()
+        .oneshot(
+            axum::http::Request::builder()
+                .uri(format!("/api/users/{viewer_id}"))
+                .method("PUT")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin}"))
+                .body(Body::from(r#"{"disabled":true}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(json_body(resp).await["disabled"], true);
+
+    let resp = app
+        .oneshot(auth_get("/api/senders", &viewer))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+#[tokio::test]
+async fn invitation_and_reset_links_set_a_password_once() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+    let admin = register_and_login(&app).await;
+
+    let email = format!("invitee-{}@test.com", uuid::Uuid::now_v7());
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/users",
+            &admin,
+            serde_json::json!({ "email": email, "role": "operator" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    // No MAIL_FROM in tests: the admin hands the link over.
+    assert_eq!(body["emailed"], false);
+    assert!(body.get("temporary_password").is_none());
+    let user_id = body["user"]["id"].as_str().unwrap().to_string();
+    let invite_token = body["invite_token"].as_str().unwrap().to_string();
+
+    // Weak passwords are refused without using the link up.
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/accept-invite",
+            serde_json::json!({ "token": invite_token, "password": "password" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    // An invitation isn't a reset link, nor a session.
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/reset-password",
+            serde_json::json!({ "token": invite_token, "password": "plum-harbor-velvet-42" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/senders", &invite_token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+
+    // Accepting signs them in; the link then stops working.
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/accept-invite",
+            serde_json::json!({ "token": invite_token, "password": "plum-harbor-velvet-42" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let session = json_body(resp).await;
+    assert_eq!(session["user_id"], user_id.as_str());
+    assert_eq!(session["role"], "operator");
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/senders", session["token"].as_str().unwrap()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/accept-invite",
+            serde_json::json!({ "token": invite_token, "password": "quartz-meadow-lantern-7" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+
+    // An admin reset revokes the password and issues a reset link.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/users/{user_id}/reset-password"),
+            &admin,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let reset_token = json_body(resp).await["reset_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let login = |password: &'static str| {
+        json_post(
+            "/api/auth/login",
+            serde_json::json!({ "email": email, "password": password }),
+        )
+    };
+    let resp = app
+        .clone()
+        .oneshot(login("plum-harbor-velvet-42"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/reset-password",
+            serde_json::json!({ "token": reset_token, "password": "quartz-meadow-lantern-7" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let resp = app
+        .clone()
+        .oneshot(login("quartz-meadow-lantern-7"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Forgot-password answers the same for known and unknown emails.
+    for email in [email.as_str(), "nobody@test.com"] {
+        let resp = app
+            .clone()
+            .oneshot(json_post(
+                "/api/auth/forgot-password",
+                serde_json::json!({ "email": email }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 202);
+    }
+}
+
+#[tokio::test]
+async fn forgot_password_is_throttled_per_email_and_per_client() {
+    let Some(app) = test_app().await else {
+        return;
+    };
+
+    let forgot =
+        |email: &str, client: Option<&str>| {
+            let mut req = json_post(
+                "/api/auth/forgot-password",
+                serde_json::json!({ "email": email }),
+            );
+            if let Some(client) = client {
+                // As seen behind the reverse proxy on loopback.
+                req.extensions_mut().insert(axum::extract::ConnectInfo(
+                    std::net::SocketAddr::from(([127, 0, 0, 1], 40000)),
+                ));
+                req.headers_mut().insert(
+                    "x-forwarded-for",
+                    format!("198.51.100.9, {client}").parse().unwrap(),
+                );
+            }
+            req
+        };
+
+    // Per address, whether or not it has an account.
+    let email = format!("flood-{}@test.com", uuid::Uuid::now_v7());
+    for _ in 0..3 {
+        let resp = app.clone().oneshot(forgot(&email, None)).await.unwrap();
+        assert_eq!(resp.status(), 202);
+    }
+    let resp = app
+        .clone()
+        .oneshot(forgot(&email.to_uppercase(), None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 429);
+    assert_eq!(json_body(resp).await["code"], "auth.rate_limited");
+
+    // Per client, across addresses; the proxy-appended address counts,
+    // not what the client claimed before it.
+    for i in 0..10 {
+        let resp = app
+            .clone()
+            .oneshot(forgot(&format!("spray-{i}@test.com"), Some("203.0.113.7")))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 202);
+    }
+    let resp = app
+        .clone()
+        .oneshot(forgot("spray-10@test.com", Some("203.0.113.7")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 429);
+    let resp = app
+        .clone()
+        .oneshot(forgot("spray-10@test.com", Some("203.0.113.8")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 202);
+}
+
+#[tokio::test]
+async fn erasing_a_member_scrubs_their_email_and_deleting_the_account_keeps_usage() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let (owner_id, admin) = register_and_login_with_id(&app).await;
+
+    let email = format!("leaver-{}@test.com", uuid::Uuid::now_v7());
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/users",
+            &admin,
+            serde_json::json!({ "email": email, "role": "operator" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let member_id = body["user"]["id"].as_str().unwrap().to_string();
+    let password = accept_invite(&app, &body).await;
+
+    // The owner cannot be erased through the admin endpoint.
+    let resp = app
+        .clone()
+        .oneshot(auth_delete(&format!("/api/users/{owner_id}"), &admin))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_delete(&format!("/api/users/{member_id}"), &admin))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+
+    // Gone from the listing, the email is free, and the audit trail no
+    // longer mentions it.
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/users", &admin))
+        .await
+        .unwrap();
+    assert_eq!(json_body(resp).await.as_array().unwrap().len(), 1);
+    let resp = app
+        .clone()
+        .oneshot(json_post(
+            "/api/auth/login",
+            serde_json::json!({ "email": email, "password": password }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/audit", &admin))
+        .await
+        .unwrap();
+    let audit = json_body(resp).await.to_string();
+    assert!(!audit.contains(&email));
+    assert!(audit.contains("user.erase"));
+
+    // Deleting the account as its owner keeps only anonymized usage.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &admin,
+            serde_json::json!({ "name": "Retired Van" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at, total_bytes) \
+         VALUES ($1, $2, 'ended', now() - interval '1 hour', now(), 4096)",
+    )
+    .bind(format!("str_{}", uuid::Uuid::now_v7()))
+    .bind(&sender_id)
+    .execute(state.pool())
+    .await
+    .unwrap();
+    let before: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM usage_anonymized")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+
+    let resp = app
+        .clone()
+        .oneshot(auth_delete("/api/me", &admin))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+
+    let after: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM usage_anonymized")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+    assert_eq!(after, before + 1);
+    let users: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE id = $1 OR owner_id = $1")
+            .bind(&owner_id)
+            .fetch_one(state.pool())
+            .await
+            .unwrap();
+    assert_eq!(users, 0);
+    let resp = app.oneshot(auth_get("/api/senders", &admin)).await.unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+// ── Dashboard WebSocket: auth + owner scoping (E3) ───────────────────
+
+/// Send the `auth.login` handshake envelope a real dashboard client sends
+/// as its first WS message (see `strata-dashboard/src/ws.rs::
+/// build_auth_message` and `ws_dashboard.rs::authenticate`).
+async fn ws_send_auth(
+    ws: &mut tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    token: &str,
+) {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+    let envelope = serde_json::json!({
+        "id": "test-auth",
+        "type": "auth.login",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": { "token": token },
+    });
+    ws.send(Message::Text(envelope.to_string().into()))
+        .await
+        .unwrap();
+}
 
 /// Receive the next text message, or `None` if nothing arrives within
 /// `timeout` (used to assert a filtered-out event never shows up).
@@ -743,122 +2237,736 @@ async fn ws_recv_json(
 ) -> Option<serde_json::Value> {
     use futures::StreamExt;
     use tokio_tungstenite::tungstenite::Message;
-    loop {
-        match tokio::time::timeout(timeout, ws.next()).await {
-            Ok(Some(Ok(Message::Text(text)))) => {
-                return Some(serde_json::from_str(&text).unwrap());
-            }
-            Ok(Some(Ok(_))) => continue, // ignore ping/pong/binary
-            Ok(Some(Err(_))) | Ok(None) => return None,
-            Err(_) => return None, // timed out
+    loop {
+        match tokio::time::timeout(timeout, ws.next()).await {
+            Ok(Some(Ok(Message::Text(text)))) => {
+                return Some(serde_json::from_str(&text).unwrap());
+            }
+            Ok(Some(Ok(_))) => continue, // ignore ping/pong/binary
+            Ok(Some(Err(_))) | Ok(None) => return None,
+            Err(_) => return None, // timed out
+        }
+    }
+}
+
+#[tokio::test]
+async fn dashboard_ws_scopes_events_to_owner() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+
+    let (user_a_id, token_a) = register_and_login_with_id(&app).await;
+    let (user_b_id, _token_b) = register_and_login_with_id(&app).await;
+
+    // Spin up a real TCP listener — WebSocket upgrades need an actual
+    // bidirectional connection, not axum's oneshot tower-service testing.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let (mut ws_a, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .expect("connect dashboard WS");
+    ws_send_auth(&mut ws_a, &token_a).await;
+    let auth_response = ws_recv_json(&mut ws_a, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth.login.response");
+    assert_eq!(auth_response["type"], "auth.login.response");
+    assert_eq!(auth_response["payload"]["success"], true);
+
+    // An event owned by user B must never reach user A's socket.
+    state.broadcast_dashboard(
+        user_b_id.clone(),
+        strata_protocol::DashboardEvent::SenderStatus {
+            sender_id: "sender-b".into(),
+            online: true,
+            status: None,
+        },
+    );
+    // An event owned by user A must reach it.
+    state.broadcast_dashboard(
+        user_a_id.clone(),
+        strata_protocol::DashboardEvent::SenderStatus {
+            sender_id: "sender-a".into(),
+            online: true,
+            status: None,
+        },
+    );
+
+    let received = ws_recv_json(&mut ws_a, std::time::Duration::from_secs(2))
+        .await
+        .expect("user A's own event");
+    assert_eq!(received["sender_id"], "sender-a");
+
+    // Nothing else should arrive — user B's event must have been filtered,
+    // not merely delayed.
+    let leaked = ws_recv_json(&mut ws_a, std::time::Duration::from_millis(300)).await;
+    assert!(
+        leaked.is_none(),
+        "another owner's event leaked onto this socket: {leaked:?}"
+    );
+}
+
+/// Two replicas on one database: an event published on one reaches the
+/// other's subscribers through LISTEN/NOTIFY, and isn't echoed back.
+#[tokio::test]
+async fn event_bus_relays_dashboard_events_between_replicas() {
+    let Some(replica_a) = test_state().await else {
+        return;
+    };
+    let Some(replica_b) = test_state().await else {
+        return;
+    };
+    strata_control::event_bus::start(&replica_a).await.unwrap();
+    strata_control::event_bus::start(&replica_b).await.unwrap();
+
+    let owner = format!("usr_bus_{}", uuid::Uuid::now_v7());
+    let topic = strata_protocol::DashboardTopic::Fleet;
+    let mut on_a = replica_a.subscribe_dashboard(&owner, topic.clone());
+    let mut on_b = replica_b.subscribe_dashboard(&owner, topic);
+
+    replica_a.broadcast_dashboard(
+        &owner,
+        strata_protocol::DashboardEvent::SenderStatus {
+            sender_id: "sender-on-a".into(),
+            online: true,
+            status: None,
+        },
+    );
+
+    assert!(on_a.try_recv().is_ok(), "local subscribers get it directly");
+    let relayed = tokio::time::timeout(std::time::Duration::from_secs(5), on_b.recv())
+        .await
+        .expect("relayed to the other replica")
+        .unwrap();
+    assert!(matches!(
+        relayed,
+        strata_protocol::DashboardEvent::SenderStatus { ref sender_id, .. } if sender_id == "sender-on-a"
+    ));
+
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert!(on_a.try_recv().is_err(), "own notification is not replayed");
+}
+
+#[tokio::test]
+async fn dashboard_ws_delivers_telemetry_only_to_subscribed_topics() {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let (user_id, token) = register_and_login_with_id(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .expect("connect dashboard WS");
+    ws_send_auth(&mut ws, &token).await;
+    ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth.login.response");
+
+    let stats = || {
+        strata_protocol::DashboardEvent::StreamStats(strata_protocol::StreamStatsPayload {
+            stream_id: "str_topic".into(),
+            sender_id: "snd_topic".into(),
+            uptime_s: 1,
+            encoder_bitrate_kbps: 2500,
+            timestamp_ms: 0,
+            links: vec![],
+            sender_metrics: None,
+            receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
+        })
+    };
+
+    // Not subscribed to sender:snd_topic yet — must not arrive.
+    state.broadcast_dashboard(&user_id, stats());
+    assert!(
+        ws_recv_json(&mut ws, std::time::Duration::from_millis(300))
+            .await
+            .is_none()
+    );
+
+    let subscribe = serde_json::json!({
+        "id": "test-sub",
+        "type": "subscribe",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": { "topics": ["sender:snd_topic"] },
+    });
+    ws.send(Message::Text(subscribe.to_string().into()))
+        .await
+        .unwrap();
+    // The subscription is applied asynchronously by the socket task.
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    state.broadcast_dashboard(&user_id, stats());
+    let received = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("subscribed telemetry");
+    assert_eq!(received["type"], "stream.stats");
+    assert_eq!(received["data"]["sender_id"], "snd_topic");
+}
+
+#[tokio::test]
+async fn dashboard_ws_rejects_invalid_token() {
+    let Some((app, _state)) = test_app_with_state().await else {
+        return;
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .expect("connect dashboard WS");
+    ws_send_auth(&mut ws, "not-a-real-token").await;
+
+    let response = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth.login.response");
+    assert_eq!(response["type"], "auth.login.response");
+    assert_eq!(response["payload"]["success"], false);
+
+    // The server closes the connection after rejecting auth.
+    let after = ws_recv_json(&mut ws, std::time::Duration::from_secs(2)).await;
+    assert!(
+        after.is_none(),
+        "expected the socket to close after auth rejection"
+    );
+}
+
+// ── Stream state machine + reconciliation (E2) ──────────────────────
+
+/// Create a sender via the API and connect an agent WebSocket for it,
+/// completing the enrollment handshake. Returns the authenticated socket
+/// and the sender id.
+async fn connect_agent_ws(
+    app: &Router,
+    addr: std::net::SocketAddr,
+    user_token: &str,
+) -> (
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+    String,
+) {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            user_token,
+            serde_json::json!({ "name": "Reconcile Test" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let sender_id = body["sender_id"].as_str().unwrap().to_string();
+    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+        .await
+        .expect("connect agent WS");
+    let auth = serde_json::json!({
+        "id": "test-agent-auth",
+        "type": "auth.login",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "enrollment_token": enrollment_token,
+            "device_key": null,
+            "agent_version": "test",
+            "hostname": "test-agent",
+            "arch": "x86_64",
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
+        .await
+        .unwrap();
+    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("agent auth response");
+    assert_eq!(
+        resp["payload"]["success"], true,
+        "agent auth failed: {resp}"
+    );
+
+    (ws, sender_id)
+}
+
+/// Send a device.status heartbeat listing `running` stream ids.
+async fn send_heartbeat(
+    ws: &mut tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    running: &[&str],
+) {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+    let hb = serde_json::json!({
+        "id": "test-heartbeat",
+        "type": "device.status",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "network_interfaces": [],
+            "media_inputs": [],
+            "stream_state": if running.is_empty() { "idle" } else { "live" },
+            "cpu_percent": 1.0,
+            "mem_used_mb": 64,
+            "uptime_s": 10,
+            "running_streams": running,
+        },
+    });
+    ws.send(Message::Text(hb.to_string().into())).await.unwrap();
+}
+
+/// Poll the DB until the stream reaches `expected` or the timeout expires.
+async fn wait_for_state(
+    state: &strata_control::state::AppState,
+    stream_id: &str,
+    expected: &str,
+) -> String {
+    for _ in 0..40 {
+        let current: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
+            .bind(stream_id)
+            .fetch_one(state.pool())
+            .await
+            .unwrap();
+        if current == expected {
+            return current;
         }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
     }
+    sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
+        .bind(stream_id)
+        .fetch_one(state.pool())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn ws_drop_no_longer_ends_streams() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, serve_app).await.unwrap();
+    });
+
+    let (ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
+
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', $3)",
+    )
+    .bind("str_wsdrop")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    // Drop the socket — the old behavior orphan-marked every active stream
+    // 'ended' here. Reconciliation semantics: a WS drop is "unobserved".
+    drop(ws);
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let current: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
+        .bind("str_wsdrop")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+    assert_eq!(
+        current, "live",
+        "a WS drop must not end streams — only reconciliation/sweep may"
+    );
+}
+
+#[tokio::test]
+async fn heartbeat_reconciles_stream_not_running_on_sender() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, serve_app).await.unwrap();
+    });
+
+    let (mut ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
+
+    // A 'live' stream the agent does not report → ended on the next
+    // heartbeat (no grace: only 'starting' rows get STARTING_GRACE).
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', $3)",
+    )
+    .bind("str_gone")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    send_heartbeat(&mut ws, &[]).await;
+
+    let current = wait_for_state(&state, "str_gone", "ended").await;
+    assert_eq!(current, "ended");
+    let err: Option<String> = sqlx::query_scalar("SELECT error_message FROM streams WHERE id = $1")
+        .bind("str_gone")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+    assert_eq!(err.as_deref(), Some("not running on sender (reconciled)"));
+}
+
+#[tokio::test]
+async fn heartbeat_readopts_inferred_end_but_not_confirmed_end() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, serve_app).await.unwrap();
+    });
+
+    let (mut ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
+
+    // Inferred end (error_message set by the control plane) → readopted.
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at, error_message) \
+         VALUES ($1, $2, 'ended', $3, $3, 'sender unobserved (connection lost)')",
+    )
+    .bind("str_inferred")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    // Confirmed end (no error_message) → stays ended; intent is enforced.
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at) \
+         VALUES ($1, $2, 'ended', $3, $3)",
+    )
+    .bind("str_confirmed")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    send_heartbeat(&mut ws, &["str_inferred", "str_confirmed"]).await;
+
+    let readopted = wait_for_state(&state, "str_inferred", "live").await;
+    assert_eq!(readopted, "live", "inferred end must be readopted");
+    let err: Option<String> = sqlx::query_scalar("SELECT error_message FROM streams WHERE id = $1")
+        .bind("str_inferred")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+    assert_eq!(
+        err, None,
+        "readoption must clear the inferred-end attribution"
+    );
+
+    let confirmed: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
+        .bind("str_confirmed")
+        .fetch_one(state.pool())
+        .await
+        .unwrap();
+    assert_eq!(confirmed, "ended", "confirmed end must not be resurrected");
+
+    // The enforcement path re-sends stream.stop for the confirmed end.
+    let stop = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("expected a re-sent stream.stop for the confirmed end");
+    assert_eq!(stop["type"], "stream.stop");
+    assert_eq!(stop["payload"]["stream_id"], "str_confirmed");
+}
+
+#[tokio::test]
+async fn negotiated_cbor_stats_frames_drive_stream_live() {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, serve_app).await.unwrap();
+    });
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "CBOR Test" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let sender_id = body["sender_id"].as_str().unwrap().to_string();
+    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+        .await
+        .expect("connect agent WS");
+    let auth = serde_json::json!({
+        "id": "test-agent-auth",
+        "type": "auth.login",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "enrollment_token": enrollment_token,
+            "agent_version": "test",
+            "hostname": "test-agent",
+            "arch": "x86_64",
+            "telemetry_encodings": ["cbor"],
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
+        .await
+        .unwrap();
+    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("agent auth response");
+    assert_eq!(
+        resp["payload"]["success"], true,
+        "agent auth failed: {resp}"
+    );
+    assert_eq!(resp["payload"]["telemetry_encoding"], "cbor");
+
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'starting', $3)",
+    )
+    .bind("str_cbor")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
+
+    let stats = strata_protocol::AgentMessage::StreamStats(strata_protocol::StreamStatsPayload {
+        stream_id: "str_cbor".into(),
+        sender_id: String::new(),
+        uptime_s: 1,
+        encoder_bitrate_kbps: 2000,
+        timestamp_ms: 0,
+        links: vec![],
+        sender_metrics: None,
+        receiver_metrics: None,
+        ladder: None,
+        quality: None,
+        elements: Vec::new(),
+        media: None,
+    });
+    let envelope = strata_protocol::Envelope::from_message(&stats).unwrap();
+    let frame = strata_protocol::encoding::encode_cbor(&envelope).unwrap();
+    ws.send(Message::Binary(frame.into())).await.unwrap();
+
+    assert_eq!(wait_for_state(&state, "str_cbor", "live").await, "live");
 }
 
 #[tokio::test]
-async fn dashboard_ws_scopes_events_to_owner() {
+async fn link_state_changes_feed_the_link_timeline() {
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
     let Some((app, state)) = test_app_with_state().await else {
         return;
     };
+    let token = register_and_login(&app).await;
 
-    let (user_a_id, token_a) = register_and_login_with_id(&app).await;
-    let (user_b_id, _token_b) = register_and_login_with_id(&app).await;
-
-    // Spin up a real TCP listener — WebSocket upgrades need an actual
-    // bidirectional connection, not axum's oneshot tower-service testing.
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
     tokio::spawn(async move {
-        axum::serve(listener, app).await.unwrap();
+        axum::serve(listener, serve_app).await.unwrap();
     });
+    let (mut ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
 
-    let (mut ws_a, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
-        .await
-        .expect("connect dashboard WS");
-    ws_send_auth(&mut ws_a, &token_a).await;
-    let auth_response = ws_recv_json(&mut ws_a, std::time::Duration::from_secs(2))
-        .await
-        .expect("auth.login.response");
-    assert_eq!(auth_response["type"], "auth.login.response");
-    assert_eq!(auth_response["payload"]["success"], true);
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'starting', $3)",
+    )
+    .bind("str_links")
+    .bind(&sender_id)
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
 
-    // An event owned by user B must never reach user A's socket.
-    state.broadcast_dashboard(
-        user_b_id.clone(),
-        strata_protocol::DashboardEvent::SenderStatus {
-            sender_id: "sender-b".into(),
-            online: true,
-            status: None,
-        },
-    );
-    // An event owned by user A must reach it.
-    state.broadcast_dashboard(
-        user_a_id.clone(),
-        strata_protocol::DashboardEvent::SenderStatus {
-            sender_id: "sender-a".into(),
-            online: true,
-            status: None,
-        },
-    );
+    // Two identical samples record once; the drop records again.
+    for (eth_state, eth_bps) in [("Live", 3_000_000), ("Live", 3_100_000), ("Down", 0)] {
+        let stats = serde_json::json!({
+            "id": "test-stats",
+            "type": "stream.stats",
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "payload": {
+                "stream_id": "str_links",
+                "uptime_s": 1,
+                "encoder_bitrate_kbps": 4000,
+                "links": [
+                    {
+                        "id": 0, "interface": "eth0", "state": eth_state,
+                        "rtt_ms": 20.0, "loss_rate": 0.0, "capacity_bps": 10_000_000,
+                        "sent_bytes": 0, "observed_bps": eth_bps,
+                    },
+                    {
+                        "id": 1, "interface": "wwan0", "state": "Probing",
+                        "rtt_ms": 60.0, "loss_rate": 0.0, "capacity_bps": 5_000_000,
+                        "sent_bytes": 0, "observed_bps": 1_000_000,
+                    },
+                ],
+            },
+        });
+        ws.send(Message::Text(stats.to_string().into()))
+            .await
+            .unwrap();
+    }
 
-    let received = ws_recv_json(&mut ws_a, std::time::Duration::from_secs(2))
-        .await
-        .expect("user A's own event");
-    assert_eq!(received["sender_id"], "sender-a");
+    let mut events = serde_json::Value::Null;
+    for _ in 0..40 {
+        let resp = app
+            .clone()
+            .oneshot(auth_get("/api/streams/str_links/link-events", &token))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        events = json_body(resp).await;
+        if events.as_array().unwrap().len() >= 3 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    let phases: Vec<(String, String)> = events
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["interface"].as_str().unwrap().to_string(),
+                e["phase"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(phases.len(), 3, "unexpected events: {events}");
+    assert!(phases.contains(&("eth0".into(), "live".into())));
+    assert!(phases.contains(&("wwan0".into(), "probing".into())));
+    assert_eq!(phases[2], ("eth0".into(), "down".into()));
 
-    // Nothing else should arrive — user B's event must have been filtered,
-    // not merely delayed.
-    let leaked = ws_recv_json(&mut ws_a, std::time::Duration::from_millis(300)).await;
-    assert!(
-        leaked.is_none(),
-        "another owner's event leaked onto this socket: {leaked:?}"
-    );
+    let resp = app
+        .oneshot(auth_get("/api/streams/str_missing/link-events", &token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
 }
 
 #[tokio::test]
-async fn dashboard_ws_rejects_invalid_token() {
-    let Some((app, _state)) = test_app_with_state().await else {
+async fn transition_rejects_illegal_moves() {
+    let Some(state) = test_state().await else {
         return;
     };
+    let token_suffix = "trans";
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-    tokio::spawn(async move {
-        axum::serve(listener, app).await.unwrap();
-    });
-
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+    // Seed a user + sender + ended stream directly.
+    sqlx::query(
+        "INSERT INTO users (id, email, password_hash, role) VALUES ($1, $2, 'x', 'operator')",
+    )
+    .bind(format!("usr_{token_suffix}"))
+    .bind(format!("{token_suffix}@test.com"))
+    .execute(state.pool())
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO senders (id, owner_id) VALUES ($1, $2)")
+        .bind(format!("snd_{token_suffix}"))
+        .bind(format!("usr_{token_suffix}"))
+        .execute(state.pool())
         .await
-        .expect("connect dashboard WS");
-    ws_send_auth(&mut ws, "not-a-real-token").await;
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO streams (id, sender_id, state, started_at, ended_at) \
+         VALUES ($1, $2, 'ended', $3, $3)",
+    )
+    .bind("str_terminal")
+    .bind(format!("snd_{token_suffix}"))
+    .bind(chrono::Utc::now())
+    .execute(state.pool())
+    .await
+    .unwrap();
 
-    let response = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    use strata_protocol::models::StreamState;
+
+    // Terminal states are sticky against every non-readopt transition.
+    for to in [StreamState::Live, StreamState::Stopping, StreamState::Ended] {
+        let moved = strata_control::stream_state::transition(
+            state.pool(),
+            "str_terminal",
+            to,
+            strata_control::stream_state::EndAttribution::none(),
+        )
         .await
-        .expect("auth.login.response");
-    assert_eq!(response["type"], "auth.login.response");
-    assert_eq!(response["payload"]["success"], false);
+        .unwrap();
+        assert!(!moved, "ended → {to} must be rejected");
+    }
 
-    // The server closes the connection after rejecting auth.
-    let after = ws_recv_json(&mut ws, std::time::Duration::from_secs(2)).await;
-    assert!(
-        after.is_none(),
-        "expected the socket to close after auth rejection"
-    );
+    // 'starting' is never a transition target (creation is INSERT-only).
+    let moved = strata_control::stream_state::transition(
+        state.pool(),
+        "str_terminal",
+        StreamState::Starting,
+        strata_control::stream_state::EndAttribution::none(),
+    )
+    .await
+    .unwrap();
+    assert!(!moved);
+
+    // A confirmed end (end_inferred = FALSE) is not readoptable.
+    let readopted = strata_control::stream_state::readopt(state.pool(), "str_terminal")
+        .await
+        .unwrap();
+    assert!(!readopted);
 }
 
-// ── Stream state machine + reconciliation (E2) ──────────────────────
+// ── Device identity: one-time tokens + challenge auth (E4) ───────────
 
-/// Create a sender via the API and connect an agent WebSocket for it,
-/// completing the enrollment handshake. Returns the authenticated socket
-/// and the sender id.
-async fn connect_agent_ws(
+/// Perform a full agent enrollment WITH a device public key, consuming the
+/// one-time token. Returns (sender_id, composite_token).
+async fn enroll_agent_with_key(
     app: &Router,
     addr: std::net::SocketAddr,
     user_token: &str,
-) -> (
-    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
-    String,
-) {
+    public_key: &str,
+) -> (String, String) {
     use futures::SinkExt;
     use tokio_tungstenite::tungstenite::Message;
 
@@ -867,95 +2975,131 @@ async fn connect_agent_ws(
         .oneshot(auth_post(
             "/api/senders",
             user_token,
-            serde_json::json!({ "name": "Reconcile Test" }),
+            serde_json::json!({ "name": "Keyed Device" }),
         ))
         .await
         .unwrap();
     let body = json_body(resp).await;
     let sender_id = body["sender_id"].as_str().unwrap().to_string();
-    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
+    let token = body["enrollment_token"].as_str().unwrap().to_string();
+    assert!(
+        token.starts_with(&format!("{sender_id}.")),
+        "expected composite <id>.<secret> token, got {token}"
+    );
 
     let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
         .await
-        .expect("connect agent WS");
+        .unwrap();
     let auth = serde_json::json!({
-        "id": "test-agent-auth",
-        "type": "auth.login",
-        "ts": chrono::Utc::now().to_rfc3339(),
+        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "enrollment_token": token,
+            "device_public_key": public_key,
+            "agent_version": "test", "hostname": "keyed", "arch": "x86_64",
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
+        .await
+        .unwrap();
+    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("enrollment response");
+    assert_eq!(
+        resp["payload"]["success"], true,
+        "enrollment failed: {resp}"
+    );
+
+    (sender_id, token)
+}
+
+#[tokio::test]
+async fn enrollment_token_is_single_use_and_key_auth_works() {
+    let Some((app, _state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serve_app = app.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, serve_app).await.unwrap();
+    });
+
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let (private_key, public_key) = strata_common::auth::generate_device_keypair();
+    let (sender_id, enrollment_token) =
+        enroll_agent_with_key(&app, addr, &token, &public_key).await;
+
+    // 1. The token is consumed — a second enrollment with it must fail.
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+        .await
+        .unwrap();
+    let auth = serde_json::json!({
+        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "enrollment_token": enrollment_token,
+            "agent_version": "test", "hostname": "replay", "arch": "x86_64",
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
+        .await
+        .unwrap();
+    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("replay response");
+    assert_eq!(
+        resp["payload"]["success"], false,
+        "a consumed enrollment token must be rejected: {resp}"
+    );
+
+    // 2. Challenge auth with the enrolled key succeeds.
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+        .await
+        .unwrap();
+    let auth = serde_json::json!({
+        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
         "payload": {
-            "enrollment_token": enrollment_token,
-            "device_key": null,
-            "agent_version": "test",
-            "hostname": "test-agent",
-            "arch": "x86_64",
+            "enrollment_token": null,
+            "device_id": sender_id,
+            "device_public_key": public_key,
+            "agent_version": "test", "hostname": "keyed", "arch": "x86_64",
         },
     });
     ws.send(Message::Text(auth.to_string().into()))
         .await
         .unwrap();
-    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
-        .await
-        .expect("agent auth response");
-    assert_eq!(
-        resp["payload"]["success"], true,
-        "agent auth failed: {resp}"
-    );
 
-    (ws, sender_id)
-}
+    let challenge_msg = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth.challenge");
+    assert_eq!(challenge_msg["type"], "auth.challenge");
+    let challenge = challenge_msg["payload"]["challenge"].as_str().unwrap();
 
-/// Send a device.status heartbeat listing `running` stream ids.
-async fn send_heartbeat(
-    ws: &mut tokio_tungstenite::WebSocketStream<
-        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-    >,
-    running: &[&str],
-) {
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
-    let hb = serde_json::json!({
-        "id": "test-heartbeat",
-        "type": "device.status",
-        "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "network_interfaces": [],
-            "media_inputs": [],
-            "stream_state": if running.is_empty() { "idle" } else { "live" },
-            "cpu_percent": 1.0,
-            "mem_used_mb": 64,
-            "uptime_s": 10,
-            "running_streams": running,
-        },
+    let signature = strata_common::auth::sign_challenge(&private_key, challenge).unwrap();
+    let response = serde_json::json!({
+        "id": "t", "type": "auth.challenge.response", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": { "device_id": sender_id, "signature": signature },
     });
-    ws.send(Message::Text(hb.to_string().into())).await.unwrap();
-}
+    ws.send(Message::Text(response.to_string().into()))
+        .await
+        .unwrap();
 
-/// Poll the DB until the stream reaches `expected` or the timeout expires.
-async fn wait_for_state(
-    state: &strata_control::state::AppState,
-    stream_id: &str,
-    expected: &str,
-) -> String {
-    for _ in 0..40 {
-        let current: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
-            .bind(stream_id)
-            .fetch_one(state.pool())
-            .await
-            .unwrap();
-        if current == expected {
-            return current;
-        }
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-    }
-    sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
-        .bind(stream_id)
-        .fetch_one(state.pool())
+    let result = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
         .await
-        .unwrap()
+        .expect("auth result");
+    assert_eq!(result["type"], "auth.login.response");
+    assert_eq!(
+        result["payload"]["success"], true,
+        "challenge auth with the enrolled key must succeed: {result}"
+    );
+    assert_eq!(result["payload"]["sender_id"], sender_id.as_str());
 }
 
 #[tokio::test]
-async fn ws_drop_no_longer_ends_streams() {
+async fn enrollment_accepts_tokens_without_a_check_character_only_for_legacy_rows() {
     let Some((app, state)) = test_app_with_state().await else {
         return;
     };
@@ -968,37 +3112,81 @@ async fn ws_drop_no_longer_ends_streams() {
         axum::serve(listener, serve_app).await.unwrap();
     });
 
-    let (ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
 
-    sqlx::query(
-        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', $3)",
-    )
-    .bind("str_wsdrop")
-    .bind(&sender_id)
-    .bind(chrono::Utc::now())
-    .execute(state.pool())
-    .await
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Old Label" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // A token from before check characters: right shape, wrong check.
+    let old_secret = "ABCD-EFGH";
+    let composite = strata_common::ids::composite_enrollment_token(&sender_id, old_secret);
+    assert_eq!(
+        strata_common::ids::validate_enrollment_token(&composite),
+        Err(strata_common::ids::EnrollmentTokenError::Checksum)
+    );
+    let hash = strata_common::auth::hash_password(&strata_common::ids::normalize_enrollment_token(
+        old_secret,
+    ))
     .unwrap();
+    sqlx::query("UPDATE senders SET enrollment_token = $1 WHERE id = $2")
+        .bind(&hash)
+        .bind(&sender_id)
+        .execute(state.pool())
+        .await
+        .unwrap();
 
-    // Drop the socket — the old behavior orphan-marked every active stream
-    // 'ended' here. Reconciliation semantics: a WS drop is "unobserved".
-    drop(ws);
-    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let login = || async {
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+            .await
+            .unwrap();
+        let auth = serde_json::json!({
+            "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
+            "payload": {
+                "enrollment_token": composite,
+                "agent_version": "test", "hostname": "old-label", "arch": "x86_64",
+            },
+        });
+        ws.send(Message::Text(auth.to_string().into()))
+            .await
+            .unwrap();
+        ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+            .await
+            .expect("enrollment response")
+    };
 
-    let current: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
-        .bind("str_wsdrop")
-        .fetch_one(state.pool())
+    let resp = login().await;
+    assert_eq!(
+        resp["payload"]["success"], false,
+        "a token without a valid check character must be rejected for a current row: {resp}"
+    );
+
+    sqlx::query("UPDATE senders SET enrollment_token_legacy = TRUE WHERE id = $1")
+        .bind(&sender_id)
+        .execute(state.pool())
         .await
         .unwrap();
+    let resp = login().await;
     assert_eq!(
-        current, "live",
-        "a WS drop must not end streams — only reconciliation/sweep may"
+        resp["payload"]["success"], true,
+        "a legacy row keeps accepting its unchecked token: {resp}"
     );
 }
 
 #[tokio::test]
-async fn heartbeat_reconciles_stream_not_running_on_sender() {
-    let Some((app, state)) = test_app_with_state().await else {
+async fn challenge_auth_rejects_wrong_key() {
+    let Some((app, _state)) = test_app_with_state().await else {
         return;
     };
     let token = register_and_login(&app).await;
@@ -1010,34 +3198,56 @@ async fn heartbeat_reconciles_stream_not_running_on_sender() {
         axum::serve(listener, serve_app).await.unwrap();
     });
 
-    let (mut ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
 
-    // A 'live' stream the agent does not report → ended on the next
-    // heartbeat (no grace: only 'starting' rows get STARTING_GRACE).
-    sqlx::query(
-        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', $3)",
-    )
-    .bind("str_gone")
-    .bind(&sender_id)
-    .bind(chrono::Utc::now())
-    .execute(state.pool())
-    .await
-    .unwrap();
+    let (_enrolled_private, enrolled_public) = strata_common::auth::generate_device_keypair();
+    let (sender_id, _) = enroll_agent_with_key(&app, addr, &token, &enrolled_public).await;
 
-    send_heartbeat(&mut ws, &[]).await;
+    // Attacker knows the sender_id but holds a different keypair.
+    let (attacker_private, _) = strata_common::auth::generate_device_keypair();
 
-    let current = wait_for_state(&state, "str_gone", "ended").await;
-    assert_eq!(current, "ended");
-    let err: Option<String> = sqlx::query_scalar("SELECT error_message FROM streams WHERE id = $1")
-        .bind("str_gone")
-        .fetch_one(state.pool())
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
         .await
         .unwrap();
-    assert_eq!(err.as_deref(), Some("not running on sender (reconciled)"));
+    let auth = serde_json::json!({
+        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "device_id": sender_id,
+            "agent_version": "test", "hostname": "mallory", "arch": "x86_64",
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
+        .await
+        .unwrap();
+
+    let challenge_msg = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth.challenge");
+    let challenge = challenge_msg["payload"]["challenge"].as_str().unwrap();
+
+    let signature = strata_common::auth::sign_challenge(&attacker_private, challenge).unwrap();
+    let response = serde_json::json!({
+        "id": "t", "type": "auth.challenge.response", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": { "device_id": sender_id, "signature": signature },
+    });
+    ws.send(Message::Text(response.to_string().into()))
+        .await
+        .unwrap();
+
+    let result = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("auth result");
+    assert_eq!(
+        result["payload"]["success"], false,
+        "a signature from the wrong key must be rejected: {result}"
+    );
 }
 
+// ── Receiver-owned port allocation (E6) ──────────────────────────────
+
 #[tokio::test]
-async fn heartbeat_readopts_inferred_end_but_not_confirmed_end() {
+async fn receiver_stream_start_ack_routes_allocated_ports() {
     let Some((app, state)) = test_app_with_state().await else {
         return;
     };
@@ -1050,407 +3260,628 @@ async fn heartbeat_readopts_inferred_end_but_not_confirmed_end() {
         axum::serve(listener, serve_app).await.unwrap();
     });
 
-    let (mut ws, sender_id) = connect_agent_ws(&app, addr, &token).await;
-
-    // Inferred end (error_message set by the control plane) → readopted.
-    sqlx::query(
-        "INSERT INTO streams (id, sender_id, state, started_at, ended_at, error_message) \
-         VALUES ($1, $2, 'ended', $3, $3, 'sender unobserved (connection lost)')",
-    )
-    .bind("str_inferred")
-    .bind(&sender_id)
-    .bind(chrono::Utc::now())
-    .execute(state.pool())
-    .await
-    .unwrap();
-
-    // Confirmed end (no error_message) → stays ended; intent is enforced.
-    sqlx::query(
-        "INSERT INTO streams (id, sender_id, state, started_at, ended_at) \
-         VALUES ($1, $2, 'ended', $3, $3)",
-    )
-    .bind("str_confirmed")
-    .bind(&sender_id)
-    .bind(chrono::Utc::now())
-    .execute(state.pool())
-    .await
-    .unwrap();
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
 
-    send_heartbeat(&mut ws, &["str_inferred", "str_confirmed"]).await;
+    // Create + enroll a receiver over its WS.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/receivers",
+            &token,
+            serde_json::json!({ "bind_host": "203.0.113.7", "name": "rcv-test" }),
+        ))
+        .await
+        .unwrap();
+    let body = json_body(resp).await;
+    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
 
-    let readopted = wait_for_state(&state, "str_inferred", "live").await;
-    assert_eq!(readopted, "live", "inferred end must be readopted");
-    let err: Option<String> = sqlx::query_scalar("SELECT error_message FROM streams WHERE id = $1")
-        .bind("str_inferred")
-        .fetch_one(state.pool())
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/receiver/ws"))
+        .await
+        .unwrap();
+    let auth = serde_json::json!({
+        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "enrollment_token": enrollment_token,
+            "receiver_version": "test", "hostname": "rcv-test", "region": null,
+            "bind_host": "203.0.113.7", "link_ports": [5000, 5002, 5004], "max_streams": 4,
+        },
+    });
+    ws.send(Message::Text(auth.to_string().into()))
         .await
         .unwrap();
+    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+        .await
+        .expect("receiver auth response");
     assert_eq!(
-        err, None,
-        "readoption must clear the inferred-end attribution"
+        resp["payload"]["success"], true,
+        "receiver auth failed: {resp}"
     );
 
-    let confirmed: String = sqlx::query_scalar("SELECT state FROM streams WHERE id = $1")
-        .bind("str_confirmed")
-        .fetch_one(state.pool())
+    // Simulate the control plane's request/ack: register a pending request,
+    // have the "receiver" answer with its allocated ports, and check the
+    // ack lands on the waiting oneshot (the path api/streams.rs::
+    // request_receiver_start relies on).
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    state.pending_requests().insert("req_ports_1".into(), tx);
+
+    let ack = serde_json::json!({
+        "id": "t", "type": "receiver.stream.started", "ts": chrono::Utc::now().to_rfc3339(),
+        "payload": {
+            "request_id": "req_ports_1",
+            "stream_id": "str_x",
+            "success": true,
+            "bind_ports": [5002, 5004],
+        },
+    });
+    ws.send(Message::Text(ack.to_string().into()))
         .await
         .unwrap();
-    assert_eq!(confirmed, "ended", "confirmed end must not be resurrected");
 
-    // The enforcement path re-sends stream.stop for the confirmed end.
-    let stop = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    let value = tokio::time::timeout(std::time::Duration::from_secs(2), rx)
         .await
-        .expect("expected a re-sent stream.stop for the confirmed end");
-    assert_eq!(stop["type"], "stream.stop");
-    assert_eq!(stop["payload"]["stream_id"], "str_confirmed");
+        .expect("ack timed out")
+        .expect("oneshot dropped");
+    let ack: strata_protocol::ReceiverStreamStartedPayload = serde_json::from_value(value).unwrap();
+    assert!(ack.success);
+    assert_eq!(ack.bind_ports, vec![5002, 5004]);
+}
+
+/// Provisioner that never starts a worker and records teardowns.
+struct RecordingProvisioner(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+impl strata_control::autoscale::Provisioner for RecordingProvisioner {
+    fn provision<'a>(
+        &'a self,
+        _spec: &'a strata_control::autoscale::WorkerSpec,
+    ) -> futures::future::BoxFuture<'a, anyhow::Result<String>> {
+        Box::pin(async { anyhow::bail!("no capacity") })
+    }
+
+    fn teardown<'a>(
+        &'a self,
+        handle: &'a str,
+    ) -> futures::future::BoxFuture<'a, anyhow::Result<()>> {
+        self.0.lock().unwrap().push(handle.to_string());
+        Box::pin(async { Ok(()) })
+    }
 }
 
 #[tokio::test]
-async fn transition_rejects_illegal_moves() {
-    let Some(state) = test_state().await else {
+async fn autoscaler_cleans_up_failed_workers_and_reaps_idle_ones() {
+    let Some((app, state)) = test_app_with_state().await else {
         return;
     };
-    let token_suffix = "trans";
+    let (owner_id, token) = register_and_login_with_id(&app).await;
+
+    let torn_down = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    state.set_autoscaler(strata_control::autoscale::Autoscaler::new(
+        RecordingProvisioner(torn_down.clone()),
+        strata_control::autoscale::ScalingPolicy {
+            max_workers: 2,
+            idle_timeout: std::time::Duration::ZERO,
+        },
+    ));
+    let autoscaler = state.autoscaler().unwrap();
+    let receivers = |owner_id: String| {
+        let pool = state.pool().clone();
+        async move {
+            sqlx::query_scalar::<_, String>(
+                "SELECT COALESCE(provision_handle, '') FROM receivers WHERE owner_id = $1",
+            )
+            .bind(owner_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+        }
+    };
 
-    // Seed a user + sender + ended stream directly.
+    // A backend failure leaves no half-made receiver behind.
+    assert!(autoscaler.scale_up(&state, &owner_id).await.is_err());
+    assert!(receivers(owner_id.clone()).await.is_empty());
+
+    // One hand-enrolled receiver and one provisioned worker, both idle:
+    // only the worker is torn down.
+    for name in ["by-hand", "worker"] {
+        let resp = app
+            .clone()
+            .oneshot(auth_post(
+                "/api/receivers",
+                &token,
+                serde_json::json!({ "bind_host": "203.0.113.7", "name": name }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 201);
+    }
     sqlx::query(
-        "INSERT INTO users (id, email, password_hash, role) VALUES ($1, $2, 'x', 'operator')",
+        "UPDATE receivers SET provision_handle = 'deploy@203.0.113.7/worker' \
+         WHERE owner_id = $1 AND name = 'worker'",
     )
-    .bind(format!("usr_{token_suffix}"))
-    .bind(format!("{token_suffix}@test.com"))
+    .bind(&owner_id)
     .execute(state.pool())
     .await
     .unwrap();
-    sqlx::query("INSERT INTO senders (id, owner_id) VALUES ($1, $2)")
-        .bind(format!("snd_{token_suffix}"))
-        .bind(format!("usr_{token_suffix}"))
-        .execute(state.pool())
+
+    autoscaler.reap_idle(&state).await;
+    assert_eq!(
+        *torn_down.lock().unwrap(),
+        vec!["deploy@203.0.113.7/worker".to_string()]
+    );
+    assert_eq!(receivers(owner_id).await, vec![String::new()]);
+}
+
+// ── Share Link Tests ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn share_link_grants_only_the_shared_view_until_revoked() {
+    let Some((app, state)) = test_app_with_state().await else {
+        return;
+    };
+    let token = register_and_login(&app).await;
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Truck 7" }),
+        ))
         .await
         .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
     sqlx::query(
-        "INSERT INTO streams (id, sender_id, state, started_at, ended_at) \
-         VALUES ($1, $2, 'ended', $3, $3)",
+        "INSERT INTO streams (id, sender_id, state, started_at) VALUES ($1, $2, 'live', $3)",
     )
-    .bind("str_terminal")
-    .bind(format!("snd_{token_suffix}"))
+    .bind("str_shared")
+    .bind(&sender_id)
     .bind(chrono::Utc::now())
     .execute(state.pool())
     .await
     .unwrap();
 
-    use strata_protocol::models::StreamState;
-
-    // Terminal states are sticky against every non-readopt transition.
-    for to in [StreamState::Live, StreamState::Stopping, StreamState::Ended] {
-        let moved = strata_control::stream_state::transition(
-            state.pool(),
-            "str_terminal",
-            to,
-            strata_control::stream_state::EndAttribution::none(),
-        )
-        .await
-        .unwrap();
-        assert!(!moved, "ended → {to} must be rejected");
-    }
-
-    // 'starting' is never a transition target (creation is INSERT-only).
-    let moved = strata_control::stream_state::transition(
-        state.pool(),
-        "str_terminal",
-        StreamState::Starting,
-        strata_control::stream_state::EndAttribution::none(),
-    )
-    .await
-    .unwrap();
-    assert!(!moved);
-
-    // A confirmed end (end_inferred = FALSE) is not readoptable.
-    let readopted = strata_control::stream_state::readopt(state.pool(), "str_terminal")
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/streams/str_shared/share-links",
+            &token,
+            serde_json::json!({ "label": "Client", "expires_in_s": 1234 }),
+        ))
         .await
         .unwrap();
-    assert!(!readopted);
-}
-
-// ── Device identity: one-time tokens + challenge auth (E4) ───────────
-
-/// Perform a full agent enrollment WITH a device public key, consuming the
-/// one-time token. Returns (sender_id, composite_token).
-async fn enroll_agent_with_key(
-    app: &Router,
-    addr: std::net::SocketAddr,
-    user_token: &str,
-    public_key: &str,
-) -> (String, String) {
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
+    assert_eq!(resp.status(), 400);
 
     let resp = app
         .clone()
         .oneshot(auth_post(
-            "/api/senders",
-            user_token,
-            serde_json::json!({ "name": "Keyed Device" }),
+            "/api/streams/str_shared/share-links",
+            &token,
+            serde_json::json!({ "label": "Client", "expires_in_s": 3600 }),
         ))
         .await
         .unwrap();
+    assert_eq!(resp.status(), 201);
     let body = json_body(resp).await;
-    let sender_id = body["sender_id"].as_str().unwrap().to_string();
-    let token = body["enrollment_token"].as_str().unwrap().to_string();
-    assert!(
-        token.starts_with(&format!("{sender_id}.")),
-        "expected composite <id>.<secret> token, got {token}"
-    );
+    let link_id = body["link"]["id"].as_str().unwrap().to_string();
+    let share_token = body["token"].as_str().unwrap().to_string();
+    assert!(link_id.starts_with("shr_"));
 
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/share/stream", &share_token))
         .await
         .unwrap();
-    let auth = serde_json::json!({
-        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "enrollment_token": token,
-            "device_public_key": public_key,
-            "agent_version": "test", "hostname": "keyed", "arch": "x86_64",
-        },
-    });
-    ws.send(Message::Text(auth.to_string().into()))
+    assert_eq!(resp.status(), 200);
+    let view = json_body(resp).await;
+    assert_eq!(view["stream_id"], "str_shared");
+    assert_eq!(view["sender_name"], "Truck 7");
+    assert_eq!(view["label"], "Client");
+    assert_eq!(view["state"], "live");
+
+    // The share token is not a session: regular endpoints refuse it, and a
+    // user session can't open the shared view.
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/senders", &share_token))
         .await
         .unwrap();
-    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    assert_eq!(resp.status(), 401);
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/share/stream", &token))
         .await
-        .expect("enrollment response");
-    assert_eq!(
-        resp["payload"]["success"], true,
-        "enrollment failed: {resp}"
-    );
+        .unwrap();
+    assert_eq!(resp.status(), 401);
 
-    (sender_id, token)
+    let resp = app
+        .clone()
+        .oneshot(auth_delete(
+            &format!("/api/streams/str_shared/share-links/{link_id}"),
+            &token,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/share/stream", &share_token))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+
+    let resp = app
+        .oneshot(auth_get("/api/streams/str_shared/share-links", &token))
+        .await
+        .unwrap();
+    let links = json_body(resp).await;
+    assert_eq!(links.as_array().unwrap().len(), 1);
+    assert!(links[0]["revoked_at"].is_string());
 }
 
 #[tokio::test]
-async fn enrollment_token_is_single_use_and_key_auth_works() {
-    let Some((app, _state)) = test_app_with_state().await else {
+async fn kit_checks_out_to_one_holder_at_a_time() {
+    let Some(app) = test_app().await else {
         return;
     };
     let token = register_and_login(&app).await;
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-    let serve_app = app.clone();
-    tokio::spawn(async move {
-        axum::serve(listener, serve_app).await.unwrap();
-    });
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Unit 4" }),
+        ))
+        .await
+        .unwrap();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
 
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/kits",
+            &token,
+            serde_json::json!({ "name": "Kit 4", "sender_id": sender_id }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let kit = json_body(resp).await;
+    let kit_id = kit["id"].as_str().unwrap().to_string();
+    assert!(kit_id.starts_with("kit_"));
+    assert_eq!(kit["sender_name"], "Unit 4");
 
-    let (private_key, public_key) = strata_common::auth::generate_device_keypair();
-    let (sender_id, enrollment_token) =
-        enroll_agent_with_key(&app, addr, &token, &public_key).await;
+    // A sender belongs to one kit.
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/kits",
+            &token,
+            serde_json::json!({ "name": "Kit 5", "sender_id": sender_id }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
 
-    // 1. The token is consumed — a second enrollment with it must fail.
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+    let modem = serde_json::json!({ "kind": "modem", "serial": "356938035643809" });
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/accessories"),
+            &token,
+            modem.clone(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/accessories"),
+            &token,
+            modem,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409, "same serial can't be packed twice");
+
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/check-out"),
+            &token,
+            serde_json::json!({ "holder": "Crew B", "note": "Stadium job" }),
+        ))
         .await
         .unwrap();
-    let auth = serde_json::json!({
-        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "enrollment_token": enrollment_token,
-            "agent_version": "test", "hostname": "replay", "arch": "x86_64",
-        },
-    });
-    ws.send(Message::Text(auth.to_string().into()))
+    assert_eq!(resp.status(), 201);
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/check-out"),
+            &token,
+            serde_json::json!({ "holder": "Crew C" }),
+        ))
         .await
         .unwrap();
-    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
-        .await
-        .expect("replay response");
-    assert_eq!(
-        resp["payload"]["success"], false,
-        "a consumed enrollment token must be rejected: {resp}"
-    );
+    assert_eq!(resp.status(), 409);
 
-    // 2. Challenge auth with the enrolled key succeeds.
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+    let resp = app
+        .clone()
+        .oneshot(auth_delete(&format!("/api/kits/{kit_id}"), &token))
         .await
         .unwrap();
-    let auth = serde_json::json!({
-        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "enrollment_token": null,
-            "device_id": sender_id,
-            "device_public_key": public_key,
-            "agent_version": "test", "hostname": "keyed", "arch": "x86_64",
-        },
-    });
-    ws.send(Message::Text(auth.to_string().into()))
+    assert_eq!(resp.status(), 409, "a kit that's out can't be deleted");
+
+    let resp = app
+        .clone()
+        .oneshot(auth_get("/api/kits", &token))
         .await
         .unwrap();
+    let kits = json_body(resp).await;
+    assert_eq!(kits[0]["accessory_count"], 1);
+    assert_eq!(kits[0]["checked_out"]["holder"], "Crew B");
 
-    let challenge_msg = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/check-in"),
+            &token,
+            serde_json::json!({}),
+        ))
         .await
-        .expect("auth.challenge");
-    assert_eq!(challenge_msg["type"], "auth.challenge");
-    let challenge = challenge_msg["payload"]["challenge"].as_str().unwrap();
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(json_body(resp).await["checked_in_at"].is_string());
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/kits/{kit_id}/check-in"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
 
-    let signature = strata_common::auth::sign_challenge(&private_key, challenge).unwrap();
-    let response = serde_json::json!({
-        "id": "t", "type": "auth.challenge.response", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": { "device_id": sender_id, "signature": signature },
-    });
-    ws.send(Message::Text(response.to_string().into()))
+    let resp = app
+        .clone()
+        .oneshot(auth_get(&format!("/api/kits/{kit_id}"), &token))
         .await
         .unwrap();
+    let detail = json_body(resp).await;
+    assert!(detail["kit"]["checked_out"].is_null());
+    assert_eq!(detail["accessories"][0]["kind"], "modem");
+    assert_eq!(detail["history"].as_array().unwrap().len(), 1);
 
-    let result = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    let resp = app
+        .oneshot(auth_delete(&format!("/api/kits/{kit_id}"), &token))
         .await
-        .expect("auth result");
-    assert_eq!(result["type"], "auth.login.response");
-    assert_eq!(
-        result["payload"]["success"], true,
-        "challenge auth with the enrolled key must succeed: {result}"
-    );
-    assert_eq!(result["payload"]["sender_id"], sender_id.as_str());
+        .unwrap();
+    assert_eq!(resp.status(), 204);
 }
 
 #[tokio::test]
-async fn challenge_auth_rejects_wrong_key() {
-    let Some((app, _state)) = test_app_with_state().await else {
+async fn uploaded_certificate_supersedes_the_previous_one() {
+    let Some((app, state)) = test_app_with_state().await else {
         return;
     };
     let token = register_and_login(&app).await;
+    let cert_pem = include_str!("../../strata-common/src/tls/testdata/leaf.pem");
+    let key_pem = include_str!("../../strata-common/src/tls/testdata/leaf.key");
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-    let serve_app = app.clone();
-    tokio::spawn(async move {
-        axum::serve(listener, serve_app).await.unwrap();
-    });
-
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
-
-    let (_enrolled_private, enrolled_public) = strata_common::auth::generate_device_keypair();
-    let (sender_id, _) = enroll_agent_with_key(&app, addr, &token, &enrolled_public).await;
-
-    // Attacker knows the sender_id but holds a different keypair.
-    let (attacker_private, _) = strata_common::auth::generate_device_keypair();
-
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/agent/ws"))
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/senders",
+            &token,
+            serde_json::json!({ "name": "Unit 5" }),
+        ))
         .await
         .unwrap();
-    let auth = serde_json::json!({
-        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "device_id": sender_id,
-            "agent_version": "test", "hostname": "mallory", "arch": "x86_64",
-        },
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let upload = serde_json::json!({
+        "sender_id": sender_id,
+        "cert_pem": cert_pem,
+        "key_pem": key_pem,
     });
-    ws.send(Message::Text(auth.to_string().into()))
+    let resp = app
+        .clone()
+        .oneshot(auth_post("/api/certificates", &token, upload.clone()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let first = json_body(resp).await;
+    let first_id = first["id"].as_str().unwrap().to_string();
+    assert!(first_id.starts_with("crt_"));
+    assert_eq!(first["domain"], "rx1.example.com");
+    assert_eq!(first["source"], "uploaded");
+    assert_eq!(first["status"], "issued");
+    // The sender is offline, so nothing has been delivered yet.
+    assert!(first["pushed_at"].is_null());
+
+    // The private key is stored sealed, and rows from before sealing are
+    // sealed at startup.
+    async fn stored_key(state: &strata_control::state::AppState, id: &str) -> String {
+        sqlx::query_scalar("SELECT key_pem FROM tls_certificates WHERE id = $1")
+            .bind(id)
+            .fetch_one(state.pool())
+            .await
+            .unwrap()
+    }
+    let sealed = stored_key(&state, &first_id).await;
+    assert!(!sealed.contains("PRIVATE KEY"));
+    assert_eq!(
+        state
+            .secrets()
+            .unwrap()
+            .open("tls_certificates.key_pem", &sealed)
+            .unwrap(),
+        key_pem
+    );
+    sqlx::query("UPDATE tls_certificates SET key_pem = $2 WHERE id = $1")
+        .bind(&first_id)
+        .bind(key_pem)
+        .execute(state.pool())
+        .await
+        .unwrap();
+    strata_control::api::certificates::seal_stored_keys(&state)
         .await
         .unwrap();
+    let resealed = stored_key(&state, &first_id).await;
+    assert!(strata_common::auth::SecretBox::is_sealed(&resealed));
+    assert_eq!(
+        state
+            .secrets()
+            .unwrap()
+            .open("tls_certificates.key_pem", &resealed)
+            .unwrap(),
+        key_pem
+    );
 
-    let challenge_msg = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    let resp = app
+        .clone()
+        .oneshot(auth_post("/api/certificates", &token, upload))
         .await
-        .expect("auth.challenge");
-    let challenge = challenge_msg["payload"]["challenge"].as_str().unwrap();
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let second_id = json_body(resp).await["id"].as_str().unwrap().to_string();
 
-    let signature = strata_common::auth::sign_challenge(&attacker_private, challenge).unwrap();
-    let response = serde_json::json!({
-        "id": "t", "type": "auth.challenge.response", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": { "device_id": sender_id, "signature": signature },
-    });
-    ws.send(Message::Text(response.to_string().into()))
+    let resp = app
+        .clone()
+        .oneshot(auth_get(
+            &format!("/api/certificates?sender_id={sender_id}"),
+            &token,
+        ))
         .await
         .unwrap();
+    assert_eq!(resp.status(), 200);
+    let list = json_body(resp).await;
+    let status_of = |id: &str| {
+        list.as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["id"] == id)
+            .map(|c| c["status"].clone())
+    };
+    assert_eq!(status_of(&first_id).unwrap(), "superseded");
+    assert_eq!(status_of(&second_id).unwrap(), "issued");
 
-    let result = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
+    // A key that doesn't belong to the certificate is refused.
+    let self_signed = include_str!("../../strata-common/src/tls/testdata/self_signed.pem");
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            "/api/certificates",
+            &token,
+            serde_json::json!({
+                "sender_id": sender_id,
+                "cert_pem": self_signed,
+                "key_pem": key_pem,
+            }),
+        ))
         .await
-        .expect("auth result");
-    assert_eq!(
-        result["payload"]["success"], false,
-        "a signature from the wrong key must be rejected: {result}"
-    );
-}
+        .unwrap();
+    assert_eq!(resp.status(), 400);
 
-// ── Receiver-owned port allocation (E6) ──────────────────────────────
+    let resp = app
+        .clone()
+        .oneshot(auth_post(
+            &format!("/api/certificates/{second_id}/push"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
 
 #[tokio::test]
-async fn receiver_stream_start_ack_routes_allocated_ports() {
+async fn live_snapshot_serves_the_running_stream_before_the_next_tick() {
     let Some((app, state)) = test_app_with_state().await else {
         return;
     };
     let token = register_and_login(&app).await;
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-    let serve_app = app.clone();
-    tokio::spawn(async move {
-        axum::serve(listener, serve_app).await.unwrap();
-    });
-
-    use futures::SinkExt;
-    use tokio_tungstenite::tungstenite::Message;
-
-    // Create + enroll a receiver over its WS.
     let resp = app
         .clone()
         .oneshot(auth_post(
-            "/api/receivers",
+            "/api/senders",
             &token,
-            serde_json::json!({ "bind_host": "203.0.113.7", "name": "rcv-test" }),
+            serde_json::json!({ "name": "Unit 6" }),
         ))
         .await
         .unwrap();
-    let body = json_body(resp).await;
-    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
+    let sender_id = json_body(resp).await["sender_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
 
-    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/receiver/ws"))
-        .await
-        .unwrap();
-    let auth = serde_json::json!({
-        "id": "t", "type": "auth.login", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "enrollment_token": enrollment_token,
-            "receiver_version": "test", "hostname": "rcv-test", "region": null,
-            "bind_host": "203.0.113.7", "link_ports": [5000, 5002, 5004], "max_streams": 4,
-        },
-    });
-    ws.send(Message::Text(auth.to_string().into()))
+    let resp = app
+        .clone()
+        .oneshot(auth_get(&format!("/api/senders/{sender_id}/live"), &token))
         .await
         .unwrap();
-    let resp = ws_recv_json(&mut ws, std::time::Duration::from_secs(2))
-        .await
-        .expect("receiver auth response");
-    assert_eq!(
-        resp["payload"]["success"], true,
-        "receiver auth failed: {resp}"
-    );
-
-    // Simulate the control plane's request/ack: register a pending request,
-    // have the "receiver" answer with its allocated ports, and check the
-    // ack lands on the waiting oneshot (the path api/streams.rs::
-    // request_receiver_start relies on).
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    state.pending_requests().insert("req_ports_1".into(), tx);
+    assert_eq!(resp.status(), 200);
+    let idle = json_body(resp).await;
+    assert_eq!(idle["status"]["online"], false);
+    assert!(idle["stream_id"].is_null());
+    assert_eq!(idle["links"], serde_json::json!([]));
+
+    let link = |interface: &str| {
+        let mut link =
+            strata_protocol::telemetry::LinkSample::from_bonding_report(&serde_json::json!({}));
+        link.interface = interface.into();
+        link
+    };
+    let stats = |links| strata_protocol::StreamStatsPayload {
+        stream_id: "str_live_snapshot".into(),
+        sender_id: sender_id.clone(),
+        uptime_s: 12,
+        encoder_bitrate_kbps: 4500,
+        timestamp_ms: 0,
+        links,
+        sender_metrics: None,
+        receiver_metrics: None,
+        ladder: None,
+        quality: None,
+        elements: Vec::new(),
+        media: None,
+    };
+    state
+        .live()
+        .record_stats(&sender_id, stats(vec![link("wwan0"), link("wwan1")]));
+    state
+        .live()
+        .record_stats(&sender_id, stats(vec![link("wwan0")]));
 
-    let ack = serde_json::json!({
-        "id": "t", "type": "receiver.stream.started", "ts": chrono::Utc::now().to_rfc3339(),
-        "payload": {
-            "request_id": "req_ports_1",
-            "stream_id": "str_x",
-            "success": true,
-            "bind_ports": [5002, 5004],
-        },
-    });
-    ws.send(Message::Text(ack.to_string().into()))
+    let resp = app
+        .clone()
+        .oneshot(auth_get(&format!("/api/senders/{sender_id}/live"), &token))
         .await
         .unwrap();
+    let live = json_body(resp).await;
+    assert_eq!(live["stream_id"], "str_live_snapshot");
+    assert_eq!(live["stats"]["encoder_bitrate_kbps"], 4500);
+    assert_eq!(live["stats"]["links"].as_array().unwrap().len(), 1);
+    assert_eq!(live["links"].as_array().unwrap().len(), 2);
 
-    let value = tokio::time::timeout(std::time::Duration::from_secs(2), rx)
+    state.live().end_stream("str_live_snapshot");
+    let resp = app
+        .clone()
+        .oneshot(auth_get(&format!("/api/senders/{sender_id}/live"), &token))
         .await
-        .expect("ack timed out")
-        .expect("oneshot dropped");
-    let ack: strata_protocol::ReceiverStreamStartedPayload = serde_json::from_value(value).unwrap();
-    assert!(ack.success);
-    assert_eq!(ack.bind_ports, vec![5002, 5004]);
+        .unwrap();
+    assert!(json_body(resp).await["stream_id"].is_null());
 }
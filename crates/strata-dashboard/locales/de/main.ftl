@@ -0,0 +1,119 @@
+# Strata dashboard — Deutsch.
+
+## Navigation
+
+nav-overview = Übersicht
+nav-senders = Sender
+nav-receivers = Empfänger
+nav-kits = Kits
+nav-streams = Streams
+nav-multiview = Multiview
+nav-schedule = Zeitplan
+nav-destinations = Ziele
+nav-alerts = Warnungen
+nav-audit = Audit-Protokoll
+nav-users = Benutzer
+nav-preferences = Einstellungen
+nav-menu = Menü
+
+## Shell
+
+status-live = Live
+status-offline = Offline
+status-auth-failed = Anmeldung fehlgeschlagen
+logout = Abmelden
+palette-title = Befehlspalette
+connection-lost = Verbindung verloren — neuer Versuch in { $seconds } s
+connection-attempt = (Versuch { $attempt })
+connection-restored = Wieder verbunden — Daten aktualisiert
+
+## Command palette
+
+palette-placeholder = Sender, Streams, Ziele oder Seiten suchen…
+palette-no-matches = Keine Treffer
+palette-loading = Wird geladen…
+palette-hint = ↑↓ auswählen · Enter öffnen · Esc schließen
+
+## Login
+
+login-tagline = Steuerzentrale für gebündeltes Streaming
+login-email = E-Mail
+login-password = Passwort
+login-submit = Anmelden
+login-submitting = Anmeldung läuft…
+login-required = E-Mail und Passwort sind erforderlich
+login-email-required = Gib die E-Mail-Adresse deines Kontos ein
+login-forgot = Passwort vergessen?
+login-forgot-intro = Wir schicken dir einen Link, um ein neues Passwort zu wählen
+login-send-reset = Link senden
+login-sending-reset = Wird gesendet…
+login-reset-sent = Falls zu dieser E-Mail ein Konto existiert, ist ein Link unterwegs.
+login-back = Zurück zur Anmeldung
+
+## Passwort-Links
+
+invite-title = Willkommen bei Strata
+invite-intro = Wähle ein Passwort, um dein Konto einzurichten
+reset-title = Passwort zurücksetzen
+reset-intro = Wähle ein neues Passwort für dein Konto
+password-new = Neues Passwort
+password-confirm = Passwort bestätigen
+password-required = Gib ein Passwort ein
+password-mismatch = Die Passwörter stimmen nicht überein
+password-save = Passwort setzen und anmelden
+password-saving = Wird gespeichert…
+
+## Errors (keyed by API error code)
+
+error-auth-unauthenticated = Deine Sitzung ist abgelaufen. Bitte melde dich erneut an.
+error-auth-invalid-credentials = E-Mail oder Passwort ist falsch.
+error-auth-forbidden = Dazu fehlt dir die Berechtigung.
+error-auth-rate-limited = Zu viele Versuche. Warte eine Weile und versuche es dann erneut.
+error-validation-invalid-input = Einige der eingegebenen Werte sind ungültig.
+error-validation-not-found = Dieses Element existiert nicht mehr.
+error-validation-conflict = Das widerspricht dem aktuellen Zustand. Bitte neu laden und erneut versuchen.
+error-transport-device-offline = Das Gerät ist offline.
+error-transport-timeout = Das Gerät hat nicht rechtzeitig geantwortet.
+error-device-unsupported-command = Die Software des Geräts ist dafür zu alt. Bitte zuerst aktualisieren.
+error-device-rejected = Das Gerät konnte die Anfrage nicht ausführen.
+error-device-busy = Das Gerät streamt gerade.
+error-internal = Etwas ist schiefgelaufen.
+error-unknown = Etwas ist schiefgelaufen.
+
+## Page headers
+
+overview-title = Flottenübersicht
+overview-subtitle = Live-Status aller Sender
+senders-subtitle = Verwalte deine Encoder im Feld
+receivers-subtitle = Relay-Flotte — Streams gehen an den am wenigsten ausgelasteten Empfänger
+kits-subtitle = Sender und das zugehörige Zubehör — Aus- und Rückgabe nach Seriennummer
+streams-subtitle = Aktive und letzte Übertragungen
+multiview-subtitle = Alle Live-Streams · ←↑↓→ wählen · Enter solo · Esc Raster · o öffnen · f Vollbild
+schedule-subtitle = Streams starten und stoppen automatisch zur gebuchten Zeit
+destinations-subtitle = Streaming-Ziele für deine Übertragungen
+alerts-subtitle = Ausgelöste Warnregeln in der Flotte
+alerts-firing = { $count } aktiv
+audit-subtitle = Änderungen an Sendern, Streams, Regeln und Zeitplänen
+users-subtitle = Wer diese Flotte sehen und steuern darf
+users-admin-only = Nur Administratoren können Benutzer verwalten.
+
+## Preferences
+
+prefs-subtitle = Darstellungseinstellungen für dein Konto
+prefs-language = Sprache
+prefs-theme = Design
+prefs-theme-dark = Dunkel
+prefs-theme-light = Hell
+prefs-theme-system = Systemeinstellung
+prefs-default-page = Startseite
+prefs-default-page-hint = Wird nach der Anmeldung geöffnet
+prefs-rate-units = Datenrate
+prefs-rate-bits = Bits (Mbit/s)
+prefs-rate-bytes = Bytes (MB/s)
+prefs-graph-window = Zeitfenster des Live-Graphen
+prefs-minutes = { $n } Min.
+prefs-seconds = { $n } s
+prefs-save = Speichern
+prefs-saving = Wird gespeichert…
+prefs-saved = Einstellungen gespeichert
+prefs-save-failed = Speichern fehlgeschlagen: { $error }
@@ -0,0 +1,122 @@
+# Strata dashboard — English (reference catalog).
+#
+# Every message used by the UI must exist here; other locales fall back
+# to this file for anything they have not translated yet.
+
+## Navigation
+
+nav-overview = Overview
+nav-senders = Senders
+nav-receivers = Receivers
+nav-kits = Kits
+nav-streams = Streams
+nav-multiview = Multiview
+nav-schedule = Schedule
+nav-destinations = Destinations
+nav-alerts = Alerts
+nav-audit = Audit Log
+nav-users = Users
+nav-preferences = Preferences
+nav-menu = Menu
+
+## Shell
+
+status-live = Live
+status-offline = Offline
+status-auth-failed = Auth failed
+logout = Logout
+palette-title = Command palette
+connection-lost = Connection lost — retrying in { $seconds } s
+connection-attempt = (attempt { $attempt })
+connection-restored = Reconnected — data refreshed
+
+## Command palette
+
+palette-placeholder = Search senders, streams, destinations or pages…
+palette-no-matches = No matches
+palette-loading = Loading…
+palette-hint = ↑↓ to move · Enter to open · Esc to close
+
+## Login
+
+login-tagline = Bonded streaming control plane
+login-email = Email
+login-password = Password
+login-submit = Sign in
+login-submitting = Signing in…
+login-required = Email and password are required
+login-email-required = Enter your account's email
+login-forgot = Forgot password?
+login-forgot-intro = We'll email you a link to choose a new password
+login-send-reset = Send reset link
+login-sending-reset = Sending…
+login-reset-sent = If that email has an account, a reset link is on its way.
+login-back = Back to sign in
+
+## Password links
+
+invite-title = Welcome to Strata
+invite-intro = Choose a password to finish setting up your account
+reset-title = Reset password
+reset-intro = Choose a new password for your account
+password-new = New password
+password-confirm = Confirm password
+password-required = Enter a password
+password-mismatch = The passwords don't match
+password-save = Set password and sign in
+password-saving = Saving…
+
+## Errors (keyed by API error code)
+
+error-auth-unauthenticated = Your session has expired. Please sign in again.
+error-auth-invalid-credentials = Incorrect email or password.
+error-auth-forbidden = You don't have permission to do that.
+error-auth-rate-limited = Too many attempts. Wait a while and try again.
+error-validation-invalid-input = Some of the values entered are invalid.
+error-validation-not-found = That item no longer exists.
+error-validation-conflict = That conflicts with the current state. Refresh and try again.
+error-transport-device-offline = The device is offline.
+error-transport-timeout = The device did not respond in time.
+error-device-unsupported-command = The device's software is too old for this. Update it first.
+error-device-rejected = The device could not carry out the request.
+error-device-busy = The device is busy streaming.
+error-internal = Something went wrong.
+error-unknown = Something went wrong.
+
+## Page headers
+
+overview-title = Fleet Overview
+overview-subtitle = Live status across all senders
+senders-subtitle = Manage your field encoder units
+receivers-subtitle = Relay fleet — streams are assigned to the least-loaded online receiver
+kits-subtitle = Senders and the accessories that travel with them, checked out and back in by serial number
+streams-subtitle = Active and recent broadcasts
+multiview-subtitle = All live streams · ←↑↓→ select · Enter solo · Esc grid · o open · f fullscreen
+schedule-subtitle = Streams start and stop automatically at their booked times
+destinations-subtitle = Streaming endpoints for your broadcasts
+alerts-subtitle = Alert rule firings across the fleet
+alerts-firing = { $count } firing
+audit-subtitle = Changes made to senders, streams, rules and schedules
+users-subtitle = Who can see and control this fleet
+users-admin-only = Only admins can manage users.
+
+## Preferences
+
+prefs-subtitle = Presentation settings for your account
+prefs-language = Language
+prefs-theme = Theme
+prefs-theme-dark = Dark
+prefs-theme-light = Light
+prefs-theme-system = Follow system
+prefs-default-page = Default page
+prefs-default-page-hint = Opened after signing in
+prefs-rate-units = Rate units
+prefs-rate-bits = Bits (Mbps)
+prefs-rate-bytes = Bytes (MB/s)
+prefs-graph-window = Live graph window
+prefs-minutes = { $n } min
+prefs-seconds = { $n } s
+prefs-save = Save
+prefs-saving = Saving…
+prefs-saved = Preferences saved
+prefs-save-failed = Save failed: { $error }
@@ -0,0 +1,119 @@
+# Strata dashboard — Español.
+
+## Navigation
+
+nav-overview = Resumen
+nav-senders = Emisores
+nav-receivers = Receptores
+nav-kits = Kits
+nav-streams = Transmisiones
+nav-multiview = Multivista
+nav-schedule = Programación
+nav-destinations = Destinos
+nav-alerts = Alertas
+nav-audit = Registro de auditoría
+nav-users = Usuarios
+nav-preferences = Preferencias
+nav-menu = Menú
+
+## Shell
+
+status-live = En vivo
+status-offline = Sin conexión
+status-auth-failed = Error de autenticación
+logout = Cerrar sesión
+palette-title = Paleta de comandos
+connection-lost = Conexión perdida — reintentando en { $seconds } s
+connection-attempt = (intento { $attempt })
+connection-restored = Reconectado — datos actualizados
+
+## Command palette
+
+palette-placeholder = Buscar emisores, transmisiones, destinos o páginas…
+palette-no-matches = Sin resultados
+palette-loading = Cargando…
+palette-hint = ↑↓ para moverse · Intro para abrir · Esc para cerrar
+
+## Login
+
+login-tagline = Plano de control de streaming agregado
+login-email = Correo electrónico
+login-password = Contraseña
+login-submit = Iniciar sesión
+login-submitting = Iniciando sesión…
+login-required = El correo y la contraseña son obligatorios
+login-email-required = Introduce el correo de tu cuenta
+login-forgot = ¿Olvidaste la contraseña?
+login-forgot-intro = Te enviaremos un enlace para elegir una nueva contraseña
+login-send-reset = Enviar enlace
+login-sending-reset = Enviando…
+login-reset-sent = Si ese correo tiene una cuenta, el enlace está en camino.
+login-back = Volver a iniciar sesión
+
+## Enlaces de contraseña
+
+invite-title = Bienvenido a Strata
+invite-intro = Elige una contraseña para terminar de configurar tu cuenta
+reset-title = Restablecer contraseña
+reset-intro = Elige una nueva contraseña para tu cuenta
+password-new = Nueva contraseña
+password-confirm = Confirmar contraseña
+password-required = Introduce una contraseña
+password-mismatch = Las contraseñas no coinciden
+password-save = Guardar contraseña e iniciar sesión
+password-saving = Guardando…
+
+## Errors (keyed by API error code)
+
+error-auth-unauthenticated = Tu sesión ha caducado. Vuelve a iniciar sesión.
+error-auth-invalid-credentials = Correo o contraseña incorrectos.
+error-auth-forbidden = No tienes permiso para hacer eso.
+error-auth-rate-limited = Demasiados intentos. Espera un poco y vuelve a intentarlo.
+error-validation-invalid-input = Algunos de los valores introducidos no son válidos.
+error-validation-not-found = Ese elemento ya no existe.
+error-validation-conflict = Entra en conflicto con el estado actual. Actualiza e inténtalo de nuevo.
+error-transport-device-offline = El dispositivo está desconectado.
+error-transport-timeout = El dispositivo no respondió a tiempo.
+error-device-unsupported-command = El software del dispositivo es demasiado antiguo para esto. Actualízalo primero.
+error-device-rejected = El dispositivo no pudo completar la solicitud.
+error-device-busy = El dispositivo está ocupado transmitiendo.
+error-internal = Algo salió mal.
+error-unknown = Algo salió mal.
+
+## Page headers
+
+overview-title = Resumen de la flota
+overview-subtitle = Estado en vivo de todos los emisores
+senders-subtitle = Gestiona tus codificadores de campo
+receivers-subtitle = Flota de relés — las transmisiones se asignan al receptor en línea con menos carga
+kits-subtitle = Emisores y los accesorios que viajan con ellos, con salida y devolución por número de serie
+streams-subtitle = Emisiones activas y recientes
+multiview-subtitle = Todas las transmisiones en vivo · ←↑↓→ seleccionar · Intro solo · Esc cuadrícula · o abrir · f pantalla completa
+schedule-subtitle = Las transmisiones empiezan y terminan solas a la hora reservada
+destinations-subtitle = Puntos de destino para tus emisiones
+alerts-subtitle = Reglas de alerta disparadas en la flota
+alerts-firing = { $count } activas
+audit-subtitle = Cambios en emisores, transmisiones, reglas y programaciones
+users-subtitle = Quién puede ver y controlar esta flota
+users-admin-only = Solo los administradores pueden gestionar usuarios.
+
+## Preferences
+
+prefs-subtitle = Ajustes de presentación de tu cuenta
+prefs-language = Idioma
+prefs-theme = Tema
+prefs-theme-dark = Oscuro
+prefs-theme-light = Claro
+prefs-theme-system = Según el sistema
+prefs-default-page = Página inicial
+prefs-default-page-hint = Se abre al iniciar sesión
+prefs-rate-units = Unidades de tasa
+prefs-rate-bits = Bits (Mbps)
+prefs-rate-bytes = Bytes (MB/s)
+prefs-graph-window = Ventana del gráfico en vivo
+prefs-minutes = { $n } min
+prefs-seconds = { $n } s
+prefs-save = Guardar
+prefs-saving = Guardando…
+prefs-saved = Preferencias guardadas
+prefs-save-failed = Error al guardar: { $error }
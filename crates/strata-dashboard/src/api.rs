@@ -1,15 +1,37 @@
 //! HTTP API client for the Strata control plane.
 //!
-//! All functions use gloo-net to call the REST API with JSON bodies
-//! and Bearer token auth. Base URL is relative (same origin).
-
-use gloo_net::http::Request;
+//! Every helper is a thin wrapper over [`fetch`]: it names the endpoint,
+//! builds the request body from a `strata_protocol::api` struct, and its
+//! return type picks what the response decodes into. The control plane's
+//! handlers take and return the same structs, so a renamed or retyped
+//! field fails to compile on one side or the other rather than surfacing
+//! as a JSON error in the browser. Base URL is relative (same origin).
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use gloo_net::http::{Request, RequestBuilder};
+use serde::de::DeserializeOwned;
 use strata_protocol::api::{
-    AlertRule, ApiErrorResponse, CreateDestinationRequest, CreateDestinationResponse,
-    CreateSenderRequest, CreateSenderResponse, DestinationSummary, LoginRequest, LoginResponse,
-    SenderDetail, SenderFullStatus, SenderSummary, StartStreamRequest, StartStreamResponse,
-    StreamDetail, StreamSummary, UnenrollResponse,
+    AcmeCertificateRequest, AddKitAccessoryRequest, AlertRule, ApiErrorResponse,
+    CertificateSummary, CheckOutKitRequest, CreateDestinationRequest, CreateDestinationResponse,
+    CreateIngestSenderRequest, CreateIngestSenderResponse, CreateReceiverRequest,
+    CreateReceiverResponse, CreateSenderRequest, CreateSenderResponse, CreateShareLinkRequest,
+    CreateShareLinkResponse, DestinationHealth, DestinationSummary, DestinationUsage,
+    ForgotPasswordRequest, InviteUserRequest, InviteUserResponse, JitterBufferRequest,
+    KitAccessory, KitCheckout, KitDetail, KitRequest, KitSummary, LockBandRequest, LoginRequest,
+    LoginResponse, MeResponse, MetricsRangeResponse, NetworkToolRequest, PcapRequest, PowerRequest,
+    ReceiverSummary, ResetPasswordResponse, RotateStreamKeyRequest, ScheduleStreamRequest,
+    SelfSignedCertificateRequest, SenderDetail, SenderFullStatus, SenderLiveSnapshot,
+    SenderSummary, SetApnRequest, SetConfigRequest, SetPasswordRequest, SetPortalAuthRequest,
+    SetPriorityRequest, SetStreamDestinationsRequest, ShareLinkSummary, SharedStreamView,
+    StartStreamRequest, StartStreamResponse, StreamDetail, StreamKeyResponse, StreamSummary,
+    UnenrollResponse, UpdateUserRequest, UploadCertificateRequest, UserPreferences, UserSummary,
+};
+use strata_protocol::models::{
+    AlertEvent, AlertSeverity, AuditEntry, IngestProtocol, LinkEvent, ScheduledStream, StreamReport,
 };
+use strata_protocol::{ErrorCategory, ErrorCode};
 
 /// Ergonomic result alias.
 pub type ApiResult<T> = Result<T, String>;
@@ -19,121 +41,187 @@ fn auth_header(token: &str) -> String {
 }
 
 /// Parse a non-2xx response into an error string.
+///
+/// Codes whose server message adds nothing for the user (offline, timeout,
+/// outdated agent, bad credentials) render translated from the locale
+/// catalogue; the rest keep the server's more specific message.
 async fn parse_error(resp: gloo_net::http::Response) -> String {
     let status = resp.status();
     match resp.json::<ApiErrorResponse>().await {
+        Ok(ApiErrorResponse {
+            code: Some(code), ..
+        }) if code.category() == ErrorCategory::Transport
+            || matches!(
+                code,
+                ErrorCode::UnsupportedCommand
+                    | ErrorCode::InvalidCredentials
+                    | ErrorCode::Unauthenticated
+            ) =>
+        {
+            format!("{status}: {}", crate::i18n::error_message(code))
+        }
         Ok(e) => format!("{status}: {}", e.error),
         Err(_) => format!("HTTP {status}"),
     }
 }
 
-// ── Auth ────────────────────────────────────────────────────────────
+fn get(path: &str, token: &str) -> RequestBuilder {
+    Request::get(path).header("Authorization", &auth_header(token))
+}
 
-pub async fn login(email: &str, password: &str) -> ApiResult<LoginResponse> {
-    let body = LoginRequest {
-        email: email.to_string(),
-        password: password.to_string(),
-    };
-    let resp = Request::post("/api/auth/login")
-        .json(&body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+fn post(path: &str, token: &str) -> RequestBuilder {
+    Request::post(path).header("Authorization", &auth_header(token))
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+fn put(path: &str, token: &str) -> RequestBuilder {
+    Request::put(path).header("Authorization", &auth_header(token))
 }
 
-// ── Senders ─────────────────────────────────────────────────────────
+fn delete(path: &str, token: &str) -> RequestBuilder {
+    Request::delete(path).header("Authorization", &auth_header(token))
+}
 
-pub async fn list_senders(token: &str) -> ApiResult<Vec<SenderSummary>> {
-    let resp = Request::get("/api/senders")
-        .header("Authorization", &auth_header(token))
+/// Send a request and decode its response as `T`, or the API error.
+async fn fetch<T: DeserializeOwned>(req: Result<Request, gloo_net::Error>) -> ApiResult<T> {
+    let resp = req
+        .map_err(|e| e.to_string())?
         .send()
         .await
         .map_err(|e| e.to_string())?;
-
     if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
+        resp.json()
+            .await
+            .map_err(|e| format!("unexpected response from {}: {e}", resp.url()))
     } else {
         Err(parse_error(resp).await)
     }
 }
 
-pub async fn get_sender(token: &str, id: &str) -> ApiResult<SenderDetail> {
-    let resp = Request::get(&format!("/api/senders/{id}"))
-        .header("Authorization", &auth_header(token))
+/// [`fetch`] for endpoints whose response body the dashboard ignores.
+async fn fetch_empty(req: Result<Request, gloo_net::Error>) -> ApiResult<()> {
+    let resp = req
+        .map_err(|e| e.to_string())?
         .send()
         .await
         .map_err(|e| e.to_string())?;
-
     if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
+        Ok(())
     } else {
         Err(parse_error(resp).await)
     }
 }
 
+// ── Auth ────────────────────────────────────────────────────────────
+
+pub async fn login(email: &str, password: &str) -> ApiResult<LoginResponse> {
+    let body = LoginRequest {
+        email: email.to_string(),
+        password: password.to_string(),
+    };
+    fetch(Request::post("/api/auth/login").json(&body)).await
+}
+
+/// Ask for a password reset link by email. Succeeds whether or not the
+/// email has an account.
+pub async fn forgot_password(email: &str) -> ApiResult<()> {
+    let body = ForgotPasswordRequest {
+        email: email.to_string(),
+    };
+    fetch_empty(Request::post("/api/auth/forgot-password").json(&body)).await
+}
+
+/// Set a new password from a reset link's token; signs the user in.
+pub async fn reset_password(token: &str, password: &str) -> ApiResult<LoginResponse> {
+    let body = SetPasswordRequest {
+        token: token.to_string(),
+        password: password.to_string(),
+    };
+    fetch(Request::post("/api/auth/reset-password").json(&body)).await
+}
+
+/// Set a first password from an invitation link's token; signs the user in.
+pub async fn accept_invite(token: &str, password: &str) -> ApiResult<LoginResponse> {
+    let body = SetPasswordRequest {
+        token: token.to_string(),
+        password: password.to_string(),
+    };
+    fetch(Request::post("/api/auth/accept-invite").json(&body)).await
+}
+
+/// The caller's identity and what the server lets them do.
+pub async fn me(token: &str) -> ApiResult<MeResponse> {
+    fetch(get("/api/me", token).build()).await
+}
+
+// ── Preferences ─────────────────────────────────────────────────────
+
+pub async fn get_preferences(token: &str) -> ApiResult<UserPreferences> {
+    fetch(get("/api/me/preferences", token).build()).await
+}
+
+pub async fn save_preferences(token: &str, prefs: &UserPreferences) -> ApiResult<UserPreferences> {
+    fetch(put("/api/me/preferences", token).json(prefs)).await
+}
+
+// ── Senders ─────────────────────────────────────────────────────────
+
+pub async fn list_senders(token: &str) -> ApiResult<Vec<SenderSummary>> {
+    fetch(get("/api/senders", token).build()).await
+}
+
+pub async fn get_sender(token: &str, id: &str) -> ApiResult<SenderDetail> {
+    fetch(get(&format!("/api/senders/{id}"), token).build()).await
+}
+
 pub async fn create_sender(token: &str, name: Option<String>) -> ApiResult<CreateSenderResponse> {
     let body = CreateSenderRequest { name };
-    let resp = Request::post("/api/senders")
-        .header("Authorization", &auth_header(token))
-        .json(&body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(post("/api/senders", token).json(&body)).await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Register a plain SRT/RTMP contributor (e.g. a phone running Larix).
+pub async fn create_ingest_sender(
+    token: &str,
+    name: Option<String>,
+    protocol: IngestProtocol,
+) -> ApiResult<CreateIngestSenderResponse> {
+    let body = CreateIngestSenderRequest { name, protocol };
+    fetch(post("/api/senders/ingest", token).json(&body)).await
 }
 
 pub async fn delete_sender(token: &str, id: &str) -> ApiResult<()> {
-    let resp = Request::delete(&format!("/api/senders/{id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(delete(&format!("/api/senders/{id}"), token).build()).await
 }
 
 // ── Streams ─────────────────────────────────────────────────────────
 
 pub async fn list_streams(token: &str) -> ApiResult<Vec<StreamSummary>> {
-    let resp = Request::get("/api/streams")
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get("/api/streams", token).build()).await
 }
 
 pub async fn get_stream(token: &str, id: &str) -> ApiResult<StreamDetail> {
-    let resp = Request::get(&format!("/api/streams/{id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(get(&format!("/api/streams/{id}"), token).build()).await
+}
 
+/// A stream's per-link event feed, oldest first.
+pub async fn get_link_events(token: &str, stream_id: &str) -> ApiResult<Vec<LinkEvent>> {
+    fetch(get(&format!("/api/streams/{stream_id}/link-events"), token).build()).await
+}
+
+pub async fn get_stream_report(token: &str, stream_id: &str) -> ApiResult<StreamReport> {
+    fetch(get(&format!("/api/streams/{stream_id}/report"), token).build()).await
+}
+
+/// The stream's incident report rendered as a PDF.
+pub async fn get_stream_report_pdf(token: &str, stream_id: &str) -> ApiResult<Vec<u8>> {
+    let resp = get(
+        &format!("/api/streams/{stream_id}/report?format=pdf"),
+        token,
+    )
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
     if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
+        resp.binary().await.map_err(|e| e.to_string())
     } else {
         Err(parse_error(resp).await)
     }
@@ -151,49 +239,17 @@ pub async fn start_stream(
         source,
         encoder,
     };
-    let resp = Request::post(&format!("/api/streams/start/{sender_id}"))
-        .header("Authorization", &auth_header(token))
-        .json(&body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(post(&format!("/api/streams/start/{sender_id}"), token).json(&body)).await
 }
 
 pub async fn stop_stream(token: &str, sender_id: &str) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/streams/stop/{sender_id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(post(&format!("/api/streams/stop/{sender_id}"), token).build()).await
 }
 
 // ── Destinations ────────────────────────────────────────────────────
 
 pub async fn list_destinations(token: &str) -> ApiResult<Vec<DestinationSummary>> {
-    let resp = Request::get("/api/destinations")
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get("/api/destinations", token).build()).await
 }
 
 pub async fn create_destination(
@@ -209,72 +265,46 @@ pub async fn create_destination(
         url: url.to_string(),
         stream_key,
     };
-    let resp = Request::post("/api/destinations")
-        .header("Authorization", &auth_header(token))
-        .json(&body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(post("/api/destinations", token).json(&body)).await
 }
 
 pub async fn delete_destination(token: &str, id: &str) -> ApiResult<()> {
-    let resp = Request::delete(&format!("/api/destinations/{id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch_empty(delete(&format!("/api/destinations/{id}"), token).build()).await
+}
 
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Re-run a destination's health check and return the result.
+pub async fn check_destination(token: &str, id: &str) -> ApiResult<DestinationHealth> {
+    fetch(post(&format!("/api/destinations/{id}/check"), token).build()).await
 }
 
-// ── Receivers (relays) ──────────────────────────────────────────────
+/// Fetch a destination's full stream key (admin only, audited).
+pub async fn get_stream_key(token: &str, id: &str) -> ApiResult<Option<String>> {
+    fetch::<StreamKeyResponse>(get(&format!("/api/destinations/{id}/stream-key"), token).build())
+        .await
+        .map(|r| r.stream_key)
+}
 
-/// Mirror of strata-control's `ReceiverSummary` (that type lives in the
-/// control crate, not strata-protocol). Timestamps arrive as RFC3339
-/// strings and are rendered as-is.
-#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
-pub struct ReceiverSummary {
-    pub id: String,
-    pub name: Option<String>,
-    pub hostname: Option<String>,
-    pub region: Option<String>,
-    pub bind_host: String,
-    pub max_streams: i32,
-    pub active_streams: i32,
-    pub online: bool,
-    pub last_seen_at: Option<String>,
-    pub created_at: String,
-}
-
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct CreateReceiverResponse {
-    pub receiver_id: String,
-    pub enrollment_token: String,
+/// Replace a destination's stream key.
+pub async fn rotate_stream_key(
+    token: &str,
+    id: &str,
+    stream_key: &str,
+) -> ApiResult<DestinationSummary> {
+    let body = RotateStreamKeyRequest {
+        stream_key: stream_key.to_string(),
+    };
+    fetch(put(&format!("/api/destinations/{id}/stream-key"), token).json(&body)).await
 }
 
-pub async fn list_receivers(token: &str) -> ApiResult<Vec<ReceiverSummary>> {
-    let resp = Request::get("/api/receivers")
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+/// Monthly usage of a destination over the last year.
+pub async fn get_destination_usage(token: &str, id: &str) -> ApiResult<Vec<DestinationUsage>> {
+    fetch(get(&format!("/api/destinations/{id}/usage"), token).build()).await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+// ── Receivers (relays) ──────────────────────────────────────────────
+
+pub async fn list_receivers(token: &str) -> ApiResult<Vec<ReceiverSummary>> {
+    fetch(get("/api/receivers", token).build()).await
 }
 
 pub async fn create_receiver(
@@ -284,120 +314,66 @@ pub async fn create_receiver(
     region: Option<String>,
     max_streams: i32,
 ) -> ApiResult<CreateReceiverResponse> {
-    #[derive(serde::Serialize)]
-    struct Body<'a> {
-        name: Option<String>,
-        bind_host: &'a str,
-        region: Option<String>,
-        max_streams: i32,
-    }
-    let resp = Request::post("/api/receivers")
-        .header("Authorization", &auth_header(token))
-        .json(&Body {
-            name,
-            bind_host,
-            region,
-            max_streams,
-        })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = CreateReceiverRequest {
+        name,
+        bind_host: bind_host.to_string(),
+        region,
+        max_streams,
+    };
+    fetch(post("/api/receivers", token).json(&body)).await
 }
 
 pub async fn delete_receiver(token: &str, id: &str) -> ApiResult<()> {
-    let resp = Request::delete(&format!("/api/receivers/{id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(delete(&format!("/api/receivers/{id}"), token).build()).await
 }
 
 // ── Sender Management ───────────────────────────────────────────────
 
 /// Get full sender status (hardware, network interfaces, system stats).
 pub async fn get_sender_status(token: &str, id: &str) -> ApiResult<SenderFullStatus> {
-    let resp = Request::get(&format!("/api/senders/{id}/status"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(get(&format!("/api/senders/{id}/status"), token).build()).await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Status plus the running stream's latest stats and link table, so the
+/// sender page can render before the first telemetry tick arrives.
+pub async fn get_sender_live(token: &str, id: &str) -> ApiResult<SenderLiveSnapshot> {
+    fetch(get(&format!("/api/senders/{id}/live"), token).build()).await
 }
 
 /// Unenroll a sender — resets its enrollment and issues a new token.
 pub async fn unenroll_sender(token: &str, id: &str) -> ApiResult<UnenrollResponse> {
-    let resp = Request::post(&format!("/api/senders/{id}/unenroll"))
-        .header("Authorization", &auth_header(token))
-        .header("Content-Type", "application/json")
-        .body("{}")
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(
+        post(&format!("/api/senders/{id}/unenroll"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
 }
 
 /// Enable a network interface on a sender.
 pub async fn enable_interface(token: &str, sender_id: &str, iface: &str) -> ApiResult<()> {
-    let resp = Request::post(&format!(
-        "/api/senders/{sender_id}/interfaces/{iface}/enable"
-    ))
-    .header("Authorization", &auth_header(token))
-    .header("Content-Type", "application/json")
-    .body("{}")
-    .map_err(|e| e.to_string())?
-    .send()
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/interfaces/{iface}/enable"),
+            token,
+        )
+        .header("Content-Type", "application/json")
+        .body("{}"),
+    )
     .await
-    .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
 }
 
 /// Disable a network interface on a sender.
 pub async fn disable_interface(token: &str, sender_id: &str, iface: &str) -> ApiResult<()> {
-    let resp = Request::post(&format!(
-        "/api/senders/{sender_id}/interfaces/{iface}/disable"
-    ))
-    .header("Authorization", &auth_header(token))
-    .header("Content-Type", "application/json")
-    .body("{}")
-    .map_err(|e| e.to_string())?
-    .send()
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/interfaces/{iface}/disable"),
+            token,
+        )
+        .header("Content-Type", "application/json")
+        .body("{}"),
+    )
     .await
-    .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
 }
 
 /// Lock a cellular interface to a specific band.
@@ -407,25 +383,14 @@ pub async fn lock_band(
     iface: &str,
     band: Option<String>,
 ) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        band: Option<String>,
-    }
-    let resp = Request::post(&format!(
-        "/api/senders/{sender_id}/interfaces/{iface}/lock_band"
-    ))
-    .header("Authorization", &auth_header(token))
-    .json(&Body { band })
-    .map_err(|e| e.to_string())?
-    .send()
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/interfaces/{iface}/lock_band"),
+            token,
+        )
+        .json(&LockBandRequest { band }),
+    )
     .await
-    .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
 }
 
 /// Set priority for a network interface.
@@ -435,25 +400,14 @@ pub async fn set_priority(
     iface: &str,
     priority: u32,
 ) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        priority: u32,
-    }
-    let resp = Request::post(&format!(
-        "/api/senders/{sender_id}/interfaces/{iface}/priority"
-    ))
-    .header("Authorization", &auth_header(token))
-    .json(&Body { priority })
-    .map_err(|e| e.to_string())?
-    .send()
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/interfaces/{iface}/priority"),
+            token,
+        )
+        .json(&SetPriorityRequest { priority }),
+    )
     .await
-    .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
 }
 
 /// Set APN and SIM settings for a cellular interface.
@@ -465,29 +419,19 @@ pub async fn set_apn(
     sim_pin: Option<String>,
     roaming: Option<bool>,
 ) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        apn: Option<String>,
-        sim_pin: Option<String>,
-        roaming: Option<bool>,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/interfaces/{iface}/apn"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body {
-            apn,
-            sim_pin,
-            roaming,
-        })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = SetApnRequest {
+        apn,
+        sim_pin,
+        roaming,
+    };
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/interfaces/{iface}/apn"),
+            token,
+        )
+        .json(&body),
+    )
+    .await
 }
 
 /// Set receiver config on a sender (proxied to agent).
@@ -496,23 +440,32 @@ pub async fn set_sender_config(
     sender_id: &str,
     receiver_url: Option<String>,
 ) -> ApiResult<strata_protocol::ConfigSetResponsePayload> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        receiver_url: Option<String>,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/config"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { receiver_url })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(
+        post(&format!("/api/senders/{sender_id}/config"), token)
+            .json(&SetConfigRequest { receiver_url }),
+    )
+    .await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Whether the sender's local portal is PIN-gated.
+pub async fn get_portal_auth(
+    token: &str,
+    sender_id: &str,
+) -> ApiResult<strata_protocol::api::PortalAuthStatus> {
+    fetch(get(&format!("/api/senders/{sender_id}/portal-auth"), token).build()).await
+}
+
+/// Set (`Some`) or remove (`None`) the sender's portal PIN.
+pub async fn set_portal_auth(
+    token: &str,
+    sender_id: &str,
+    pin: Option<String>,
+) -> ApiResult<strata_protocol::api::PortalAuthStatus> {
+    fetch(
+        put(&format!("/api/senders/{sender_id}/portal-auth"), token)
+            .json(&SetPortalAuthRequest { pin }),
+    )
+    .await
 }
 
 /// Run a connectivity test on a sender (proxied to agent).
@@ -520,20 +473,12 @@ pub async fn run_sender_test(
     token: &str,
     sender_id: &str,
 ) -> ApiResult<strata_protocol::TestRunResponsePayload> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/test"))
-        .header("Authorization", &auth_header(token))
-        .header("Content-Type", "application/json")
-        .body("{}")
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(
+        post(&format!("/api/senders/{sender_id}/test"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
 }
 
 /// Scan for new interfaces on a sender (proxied to agent).
@@ -541,20 +486,12 @@ pub async fn scan_sender_interfaces(
     token: &str,
     sender_id: &str,
 ) -> ApiResult<strata_protocol::InterfacesScanResponsePayload> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/interfaces/scan"))
-        .header("Authorization", &auth_header(token))
-        .header("Content-Type", "application/json")
-        .body("{}")
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(
+        post(&format!("/api/senders/{sender_id}/interfaces/scan"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
 }
 
 // ── Stream Config (Hot Reconfig) ────────────────────────────────────
@@ -565,19 +502,7 @@ pub async fn update_stream_config(
     sender_id: &str,
     body: &strata_protocol::ConfigUpdatePayload,
 ) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/stream/config"))
-        .header("Authorization", &auth_header(token))
-        .json(body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(post(&format!("/api/senders/{sender_id}/stream/config"), token).json(body)).await
 }
 
 /// Switch the active video source on a running pipeline.
@@ -586,19 +511,7 @@ pub async fn switch_source(
     sender_id: &str,
     body: &strata_protocol::SourceSwitchPayload,
 ) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/source"))
-        .header("Authorization", &auth_header(token))
-        .json(body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(post(&format!("/api/senders/{sender_id}/source"), token).json(body)).await
 }
 
 /// List files on a sender device at the given path.
@@ -614,59 +527,24 @@ pub async fn list_files(
         ),
         None => format!("/api/senders/{sender_id}/files"),
     };
-    let resp = Request::get(&url)
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json::<strata_protocol::FilesListResponsePayload>()
-            .await
-            .map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get(&url, token).build()).await
 }
 
 // ── Power Controls ──────────────────────────────────────────────────
 
 /// Send a power command (reboot, shutdown, restart_agent) to a sender.
 pub async fn power_command(token: &str, sender_id: &str, action: &str) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body<'a> {
-        action: &'a str,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/power"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { action })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = PowerRequest {
+        action: action.to_string(),
+    };
+    fetch_empty(post(&format!("/api/senders/{sender_id}/power"), token).json(&body)).await
 }
 
 // ── Configuration Export/Import ─────────────────────────────────────
 
 /// Export the sender's full configuration as JSON.
 pub async fn export_config(token: &str, sender_id: &str) -> ApiResult<serde_json::Value> {
-    let resp = Request::get(&format!("/api/senders/{sender_id}/config/export"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get(&format!("/api/senders/{sender_id}/config/export"), token).build()).await
 }
 
 /// Import a configuration JSON to a sender.
@@ -675,49 +553,7 @@ pub async fn import_config(
     sender_id: &str,
     config: &serde_json::Value,
 ) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/config/import"))
-        .header("Authorization", &auth_header(token))
-        .json(config)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
-}
-
-/// Set data cap for a cellular interface.
-pub async fn set_data_cap(
-    token: &str,
-    sender_id: &str,
-    iface: &str,
-    cap_mb: Option<u64>,
-) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        data_cap_mb: Option<u64>,
-    }
-    let resp = Request::post(&format!(
-        "/api/senders/{sender_id}/interfaces/{iface}/data_cap"
-    ))
-    .header("Authorization", &auth_header(token))
-    .json(&Body {
-        data_cap_mb: cap_mb,
-    })
-    .map_err(|e| e.to_string())?
-    .send()
-    .await
-    .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(post(&format!("/api/senders/{sender_id}/config/import"), token).json(config)).await
 }
 
 // ── Multi-Destination Routing ───────────────────────────────────────
@@ -728,23 +564,17 @@ pub async fn set_stream_destinations(
     sender_id: &str,
     destination_ids: &[String],
 ) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body<'a> {
-        destination_ids: &'a [String],
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/stream/destinations"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { destination_ids })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = SetStreamDestinationsRequest {
+        destination_ids: destination_ids.to_vec(),
+    };
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/stream/destinations"),
+            token,
+        )
+        .json(&body),
+    )
+    .await
 }
 
 // ── Receiver Jitter Buffer ──────────────────────────────────────────
@@ -756,24 +586,18 @@ pub async fn set_jitter_buffer(
     mode: &str,
     static_ms: Option<u32>,
 ) -> ApiResult<()> {
-    #[derive(serde::Serialize)]
-    struct Body<'a> {
-        mode: &'a str,
-        static_ms: Option<u32>,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/stream/jitter_buffer"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { mode, static_ms })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = JitterBufferRequest {
+        mode: mode.to_string(),
+        static_ms,
+    };
+    fetch_empty(
+        post(
+            &format!("/api/senders/{sender_id}/stream/jitter_buffer"),
+            token,
+        )
+        .json(&body),
+    )
+    .await
 }
 
 // ── OTA Updates ─────────────────────────────────────────────────────
@@ -783,35 +607,17 @@ pub async fn check_updates(
     token: &str,
     sender_id: &str,
 ) -> ApiResult<strata_protocol::UpdatesCheckResponsePayload> {
-    let resp = Request::get(&format!("/api/senders/{sender_id}/updates/check"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get(&format!("/api/senders/{sender_id}/updates/check"), token).build()).await
 }
 
 /// Trigger an OTA update on a sender.
 pub async fn trigger_update(token: &str, sender_id: &str) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/updates/install"))
-        .header("Authorization", &auth_header(token))
-        .header("Content-Type", "application/json")
-        .body("{}")
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(
+        post(&format!("/api/senders/{sender_id}/updates/install"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
 }
 
 // ── Diagnostics ─────────────────────────────────────────────────────
@@ -830,17 +636,7 @@ pub async fn get_logs(
     if let Some(n) = lines {
         url.push_str(&format!("lines={n}&"));
     }
-    let resp = Request::get(&url)
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch(get(&url, token).build()).await
 }
 
 /// Run a network tool (ping, traceroute, speedtest) on the sender.
@@ -850,24 +646,18 @@ pub async fn run_network_tool(
     tool: &str,
     target: Option<&str>,
 ) -> ApiResult<strata_protocol::NetworkToolResponsePayload> {
-    #[derive(serde::Serialize)]
-    struct Body<'a> {
-        tool: &'a str,
-        target: Option<&'a str>,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/diagnostics/network"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { tool, target })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    let body = NetworkToolRequest {
+        tool: tool.to_string(),
+        target: target.map(str::to_string),
+    };
+    fetch(
+        post(
+            &format!("/api/senders/{sender_id}/diagnostics/network"),
+            token,
+        )
+        .json(&body),
+    )
+    .await
 }
 
 /// Trigger a PCAP capture on the sender and return the download URL.
@@ -876,74 +666,267 @@ pub async fn capture_pcap(
     sender_id: &str,
     duration_secs: u32,
 ) -> ApiResult<strata_protocol::PcapCaptureResponsePayload> {
-    #[derive(serde::Serialize)]
-    struct Body {
-        duration_secs: u32,
-    }
-    let resp = Request::post(&format!("/api/senders/{sender_id}/diagnostics/pcap"))
-        .header("Authorization", &auth_header(token))
-        .json(&Body { duration_secs })
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(
+        post(&format!("/api/senders/{sender_id}/diagnostics/pcap"), token)
+            .json(&PcapRequest { duration_secs }),
+    )
+    .await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+// ── Metrics History ─────────────────────────────────────────────────
+
+/// Fetch a sender's historical telemetry: a named `range` ("1h", "24h",
+/// "7d"), or an explicit `window` when zoomed in.
+pub async fn get_metrics_history(
+    token: &str,
+    sender_id: &str,
+    range: &str,
+    window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> ApiResult<MetricsRangeResponse> {
+    let query = match window {
+        Some((from, to)) => format!(
+            "from={}&to={}",
+            from.to_rfc3339_opts(SecondsFormat::Secs, true),
+            to.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ),
+        None => format!("range={range}"),
+    };
+    fetch(get(&format!("/api/senders/{sender_id}/metrics?{query}"), token).build()).await
 }
 
 // ── Alerting Rules ──────────────────────────────────────────────────
 
 /// Get alerting rules for a sender.
 pub async fn get_alert_rules(token: &str, sender_id: &str) -> ApiResult<Vec<AlertRule>> {
-    let resp = Request::get(&format!("/api/senders/{sender_id}/alerts"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(get(&format!("/api/senders/{sender_id}/alerts"), token).build()).await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Alerting rules of every sender that has any, keyed by sender ID.
+pub async fn list_alert_rules(token: &str) -> ApiResult<HashMap<String, Vec<AlertRule>>> {
+    fetch(get("/api/alerts/rules", token).build()).await
 }
 
 /// Create or update an alerting rule.
 pub async fn set_alert_rule(token: &str, sender_id: &str, rule: &AlertRule) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/alerts"))
-        .header("Authorization", &auth_header(token))
-        .json(rule)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+    fetch_empty(post(&format!("/api/senders/{sender_id}/alerts"), token).json(rule)).await
 }
 
 /// Delete an alerting rule.
 pub async fn delete_alert_rule(token: &str, sender_id: &str, rule_id: &str) -> ApiResult<()> {
-    let resp = Request::delete(&format!("/api/senders/{sender_id}/alerts/{rule_id}"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch_empty(delete(&format!("/api/senders/{sender_id}/alerts/{rule_id}"), token).build()).await
+}
 
-    if resp.ok() {
-        Ok(())
+// ── Alert History ───────────────────────────────────────────────────
+
+/// Join `key=value` pairs for the set filters into a query string
+/// (empty, or starting with `?`). Values are IDs and enum slugs, which
+/// need no escaping.
+fn filter_query(filters: &[(&str, Option<&str>)]) -> String {
+    let parts: Vec<String> = filters
+        .iter()
+        .filter_map(|(k, v)| v.map(|v| format!("{k}={v}")))
+        .collect();
+    if parts.is_empty() {
+        String::new()
     } else {
-        Err(parse_error(resp).await)
+        format!("?{}", parts.join("&"))
     }
 }
 
+/// Fetch alert history, newest first. `state` is "firing",
+/// "acknowledged", "resolved" or "open".
+pub async fn list_alerts(
+    token: &str,
+    sender_id: Option<&str>,
+    severity: Option<AlertSeverity>,
+    state: Option<&str>,
+) -> ApiResult<Vec<AlertEvent>> {
+    let query = filter_query(&[
+        ("sender_id", sender_id),
+        ("severity", severity.map(|s| s.as_str())),
+        ("state", state),
+    ]);
+    fetch(get(&format!("/api/alerts{query}"), token).build()).await
+}
+
+/// Acknowledge or resolve an alert (`action` is "ack" or "resolve").
+pub async fn update_alert(token: &str, alert_id: &str, action: &str) -> ApiResult<AlertEvent> {
+    fetch(post(&format!("/api/alerts/{alert_id}/{action}"), token).build()).await
+}
+
+// ── Audit Log ───────────────────────────────────────────────────────
+
+/// Fetch the audit trail, newest first. `actor` is a user ID or "system".
+pub async fn list_audit(
+    token: &str,
+    sender_id: Option<&str>,
+    actor: Option<&str>,
+) -> ApiResult<Vec<AuditEntry>> {
+    let query = filter_query(&[("sender_id", sender_id), ("actor", actor)]);
+    fetch(get(&format!("/api/audit{query}"), token).build()).await
+}
+
+// ── Stream Schedule ─────────────────────────────────────────────────
+
+/// Bookings overlapping `[from, to)`, with double-bookings marked.
+pub async fn list_schedules(
+    token: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> ApiResult<Vec<ScheduledStream>> {
+    fetch(
+        get(
+            &format!(
+                "/api/schedules?from={}&to={}",
+                from.to_rfc3339_opts(SecondsFormat::Secs, true),
+                to.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ),
+            token,
+        )
+        .build(),
+    )
+    .await
+}
+
+/// Book a stream, or edit the booking `id` if it has not started yet.
+pub async fn save_schedule(
+    token: &str,
+    id: Option<&str>,
+    booking: &ScheduleStreamRequest,
+) -> ApiResult<ScheduledStream> {
+    let req = match id {
+        Some(id) => put(&format!("/api/schedules/{id}"), token),
+        None => post("/api/schedules", token),
+    };
+    fetch(req.json(booking)).await
+}
+
+/// Cancel a booking that is not running.
+pub async fn delete_schedule(token: &str, id: &str) -> ApiResult<()> {
+    fetch_empty(delete(&format!("/api/schedules/{id}"), token).build()).await
+}
+
+// ── Users ───────────────────────────────────────────────────────────
+
+/// List the users of the caller's account (admin only).
+pub async fn list_users(token: &str) -> ApiResult<Vec<UserSummary>> {
+    fetch(get("/api/users", token).build()).await
+}
+
+/// Invite a user; the response carries their one-time password.
+pub async fn invite_user(token: &str, email: &str, role: &str) -> ApiResult<InviteUserResponse> {
+    let body = InviteUserRequest {
+        email: email.to_string(),
+        role: role.to_string(),
+    };
+    fetch(post("/api/users", token).json(&body)).await
+}
+
+/// Change a user's role and/or disable or re-enable them.
+pub async fn update_user(
+    token: &str,
+    user_id: &str,
+    update: &UpdateUserRequest,
+) -> ApiResult<UserSummary> {
+    fetch(put(&format!("/api/users/{user_id}"), token).json(update)).await
+}
+
+/// Revoke a user's password and issue them a reset link.
+pub async fn reset_user_password(token: &str, user_id: &str) -> ApiResult<ResetPasswordResponse> {
+    fetch(post(&format!("/api/users/{user_id}/reset-password"), token).build()).await
+}
+
+// ── Kits ────────────────────────────────────────────────────────────
+
+pub async fn list_kits(token: &str) -> ApiResult<Vec<KitSummary>> {
+    fetch(get("/api/kits", token).build()).await
+}
+
+/// The kit with its accessories and check-out history.
+pub async fn get_kit(token: &str, id: &str) -> ApiResult<KitDetail> {
+    fetch(get(&format!("/api/kits/{id}"), token).build()).await
+}
+
+/// Create a kit, or edit the kit `id`.
+pub async fn save_kit(token: &str, id: Option<&str>, kit: &KitRequest) -> ApiResult<KitSummary> {
+    let req = match id {
+        Some(id) => put(&format!("/api/kits/{id}"), token),
+        None => post("/api/kits", token),
+    };
+    fetch(req.json(kit)).await
+}
+
+pub async fn delete_kit(token: &str, id: &str) -> ApiResult<()> {
+    fetch_empty(delete(&format!("/api/kits/{id}"), token).build()).await
+}
+
+pub async fn add_kit_accessory(
+    token: &str,
+    kit_id: &str,
+    accessory: &AddKitAccessoryRequest,
+) -> ApiResult<KitAccessory> {
+    fetch(post(&format!("/api/kits/{kit_id}/accessories"), token).json(accessory)).await
+}
+
+pub async fn remove_kit_accessory(token: &str, kit_id: &str, accessory_id: &str) -> ApiResult<()> {
+    fetch_empty(
+        delete(
+            &format!("/api/kits/{kit_id}/accessories/{accessory_id}"),
+            token,
+        )
+        .build(),
+    )
+    .await
+}
+
+pub async fn check_out_kit(
+    token: &str,
+    kit_id: &str,
+    checkout: &CheckOutKitRequest,
+) -> ApiResult<KitCheckout> {
+    fetch(post(&format!("/api/kits/{kit_id}/check-out"), token).json(checkout)).await
+}
+
+pub async fn check_in_kit(token: &str, kit_id: &str) -> ApiResult<KitCheckout> {
+    fetch(post(&format!("/api/kits/{kit_id}/check-in"), token).build()).await
+}
+
+// ── Share Links ─────────────────────────────────────────────────────
+
+pub async fn list_share_links(token: &str, stream_id: &str) -> ApiResult<Vec<ShareLinkSummary>> {
+    fetch(get(&format!("/api/streams/{stream_id}/share-links"), token).build()).await
+}
+
+pub async fn create_share_link(
+    token: &str,
+    stream_id: &str,
+    label: Option<String>,
+    expires_in_s: u32,
+) -> ApiResult<CreateShareLinkResponse> {
+    let body = CreateShareLinkRequest {
+        label,
+        expires_in_s,
+    };
+    fetch(post(&format!("/api/streams/{stream_id}/share-links"), token).json(&body)).await
+}
+
+pub async fn revoke_share_link(token: &str, stream_id: &str, link_id: &str) -> ApiResult<()> {
+    fetch_empty(
+        delete(
+            &format!("/api/streams/{stream_id}/share-links/{link_id}"),
+            token,
+        )
+        .build(),
+    )
+    .await
+}
+
+/// The shared stream view, authorized by a share link token rather than
+/// a user session.
+pub async fn get_shared_stream(share_token: &str) -> ApiResult<SharedStreamView> {
+    fetch(get("/api/share/stream", share_token).build()).await
+}
+
 // ── TLS Certificate Management ──────────────────────────────────────
 
 /// Get TLS certificate status for a sender's local portal.
@@ -951,33 +934,67 @@ pub async fn get_tls_status(
     token: &str,
     sender_id: &str,
 ) -> ApiResult<strata_protocol::TlsStatusResponsePayload> {
-    let resp = Request::get(&format!("/api/senders/{sender_id}/tls"))
-        .header("Authorization", &auth_header(token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch(get(&format!("/api/senders/{sender_id}/tls"), token).build()).await
+}
 
-    if resp.ok() {
-        resp.json().await.map_err(|e| e.to_string())
-    } else {
-        Err(parse_error(resp).await)
-    }
+/// Reissue the sender's live certificate the way it was first issued.
+/// An ACME renewal comes back `pending` with a new TXT record to publish.
+pub async fn renew_tls_cert(token: &str, sender_id: &str) -> ApiResult<CertificateSummary> {
+    fetch(
+        post(&format!("/api/senders/{sender_id}/tls/renew"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
 }
 
-/// Generate or renew a self-signed TLS certificate.
-pub async fn renew_tls_cert(token: &str, sender_id: &str) -> ApiResult<()> {
-    let resp = Request::post(&format!("/api/senders/{sender_id}/tls/renew"))
-        .header("Authorization", &auth_header(token))
-        .header("Content-Type", "application/json")
-        .body("{}")
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+/// Certificates issued for a sender, newest first.
+pub async fn list_certificates(token: &str, sender_id: &str) -> ApiResult<Vec<CertificateSummary>> {
+    fetch(get(&format!("/api/certificates?sender_id={sender_id}"), token).build()).await
+}
 
-    if resp.ok() {
-        Ok(())
-    } else {
-        Err(parse_error(resp).await)
-    }
+pub async fn upload_certificate(
+    token: &str,
+    req: &UploadCertificateRequest,
+) -> ApiResult<CertificateSummary> {
+    fetch(post("/api/certificates", token).json(req)).await
+}
+
+pub async fn issue_self_signed_certificate(
+    token: &str,
+    req: &SelfSignedCertificateRequest,
+) -> ApiResult<CertificateSummary> {
+    fetch(post("/api/certificates/self-signed", token).json(req)).await
+}
+
+/// Start a Let's Encrypt order. The returned certificate is `pending`
+/// with the TXT record to publish before [`verify_acme_certificate`].
+pub async fn request_acme_certificate(
+    token: &str,
+    req: &AcmeCertificateRequest,
+) -> ApiResult<CertificateSummary> {
+    fetch(post("/api/certificates/acme", token).json(req)).await
+}
+
+pub async fn verify_acme_certificate(token: &str, id: &str) -> ApiResult<CertificateSummary> {
+    fetch(
+        post(&format!("/api/certificates/{id}/verify"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
+}
+
+/// Send the certificate to its device again.
+pub async fn push_certificate(token: &str, id: &str) -> ApiResult<()> {
+    fetch_empty(
+        post(&format!("/api/certificates/{id}/push"), token)
+            .header("Content-Type", "application/json")
+            .body("{}"),
+    )
+    .await
+}
+
+pub async fn delete_certificate(token: &str, id: &str) -> ApiResult<()> {
+    fetch_empty(delete(&format!("/api/certificates/{id}"), token).build()).await
 }
@@ -0,0 +1,116 @@
+//! Client-side CSV/JSON export of the data behind graphs and tables.
+//!
+//! Rows are plain JSON objects so any serializable type can be exported.
+//! CSV takes an explicit column list (dotted paths reach into nested
+//! objects) to keep a stable, report-friendly column order; JSON exports
+//! the rows whole. Files are handed to the browser as a Blob download.
+
+use leptos::prelude::*;
+use serde_json::Value;
+use strata_protocol::csv;
+use wasm_bindgen::JsCast;
+
+/// Render `rows` as CSV with one column per entry of `columns`.
+pub fn to_csv(rows: &[Value], columns: &[&str]) -> String {
+    let mut out = columns.join(",");
+    out.push('\n');
+    for row in rows {
+        let line: Vec<String> = columns
+            .iter()
+            .map(|col| csv_field(lookup(row, col)))
+            .collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn lookup<'a>(row: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(row, |v, key| v.get(key))
+}
+
+fn csv_field(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        // Numbers stay numeric, negative ones included.
+        Some(v @ (Value::Number(_) | Value::Bool(_))) => v.to_string(),
+        Some(Value::String(s)) => csv::field(s),
+        Some(other) => csv::field(&other.to_string()),
+    }
+}
+
+/// Hand `body` to the browser as a file download.
+pub fn download(filename: &str, mime: &str, body: &str) -> Result<(), String> {
+    download_bytes(filename, mime, body.as_bytes())
+}
+
+/// [`download`] for binary files (a PDF from the control plane).
+pub fn download_bytes(filename: &str, mime: &str, body: &[u8]) -> Result<(), String> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(body).into());
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("{e:?}"))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(|e| format!("{e:?}"))?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("no document")?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(|e| format!("{e:?}"))?
+        .dyn_into()
+        .map_err(|_| "not an anchor")?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+    let _ = web_sys::Url::revoke_object_url(&url);
+    Ok(())
+}
+
+/// "CSV" / "JSON" buttons exporting `rows` as `<filename>.csv|.json`.
+/// `filename` is read when clicked, so it can name the current selection
+/// (sender, stream, time range).
+#[component]
+pub fn ExportButtons(
+    rows: Signal<Vec<Value>>,
+    columns: &'static [&'static str],
+    filename: Signal<String>,
+) -> impl IntoView {
+    let export = move |csv: bool| {
+        let rows = rows.get_untracked();
+        let stem = filename.get_untracked();
+        let result = if csv {
+            download(
+                &format!("{stem}.csv"),
+                "text/csv;charset=utf-8",
+                &to_csv(&rows, columns),
+            )
+        } else {
+            let body = serde_json::to_string_pretty(&rows).unwrap_or_default();
+            download(&format!("{stem}.json"), "application/json", &body)
+        };
+        if let Err(e) = result {
+            log::warn!("export failed: {e}");
+        }
+    };
+
+    view! {
+        <div class="join">
+            <button
+                class="btn btn-ghost btn-xs join-item"
+                disabled=move || rows.with(|r| r.is_empty())
+                on:click=move |_| export(true)
+            >
+                "CSV"
+            </button>
+            <button
+                class="btn btn-ghost btn-xs join-item"
+                disabled=move || rows.with(|r| r.is_empty())
+                on:click=move |_| export(false)
+            >
+                "JSON"
+            </button>
+        </div>
+    }
+}
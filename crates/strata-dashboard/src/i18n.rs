@@ -0,0 +1,115 @@
+//! UI string catalogs and the active locale.
+//!
+//! Messages live in Fluent files under `locales/<tag>/main.ftl` and are
+//! compiled into the binary. English is the reference catalog: a message
+//! another locale hasn't translated falls back to it, and a message
+//! missing everywhere renders as its ID so the gap is visible.
+//!
+//! The active locale follows the user's preferences, so switching it in
+//! Preferences re-renders every string read through [`I18n::t`].
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use leptos::prelude::*;
+use strata_protocol::ErrorCode;
+use strata_protocol::api::Locale;
+
+use crate::PrefsState;
+
+const SOURCES: &[(Locale, &str)] = &[
+    (Locale::En, include_str!("../locales/en/main.ftl")),
+    (Locale::Es, include_str!("../locales/es/main.ftl")),
+    (Locale::De, include_str!("../locales/de/main.ftl")),
+];
+
+thread_local! {
+    static BUNDLES: Vec<(Locale, FluentBundle<FluentResource>)> = SOURCES
+        .iter()
+        .map(|&(locale, source)| (locale, bundle(locale, source)))
+        .collect();
+}
+
+fn bundle(locale: Locale, source: &str) -> FluentBundle<FluentResource> {
+    let lang = locale
+        .as_str()
+        .parse()
+        .expect("locale tags are valid language identifiers");
+    let mut bundle = FluentBundle::new(vec![lang]);
+    // Bidi isolation marks would leak into attributes and plain text.
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(res, errors)| {
+        log::warn!("{} catalog has syntax errors: {errors:?}", locale.as_str());
+        res
+    });
+    if let Err(errors) = bundle.add_resource(resource) {
+        log::warn!(
+            "{} catalog has duplicate messages: {errors:?}",
+            locale.as_str()
+        );
+    }
+    bundle
+}
+
+fn format(locale: Locale, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    BUNDLES.with(|bundles| {
+        let (_, bundle) = bundles.iter().find(|(l, _)| *l == locale)?;
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            log::warn!("formatting {id} ({}): {errors:?}", locale.as_str());
+        }
+        Some(text.into_owned())
+    })
+}
+
+/// Message `id` in `locale`, falling back to English, then to the ID.
+pub fn translate(locale: Locale, id: &str, args: Option<&FluentArgs>) -> String {
+    format(locale, id, args)
+        .or_else(|| format(Locale::En, id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Text for an API error `code` in the locale cached from the user's
+/// preferences. Used outside any reactive scope (the API client), so it
+/// reads the cached locale rather than the [`I18n`] context.
+pub fn error_message(code: ErrorCode) -> String {
+    let id = format!("error-{}", code.as_str().replace(['.', '_'], "-"));
+    translate(crate::cached_locale(), &id, None)
+}
+
+/// Handle on the active locale, provided via Leptos context.
+#[derive(Clone, Copy)]
+pub struct I18n {
+    locale: Memo<Locale>,
+}
+
+impl I18n {
+    pub fn new(prefs: PrefsState) -> Self {
+        Self {
+            locale: Memo::new(move |_| prefs.prefs.with(|p| p.locale)),
+        }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale.get()
+    }
+
+    /// Message `id` in the active locale. Tracks the locale, so call it
+    /// inside a reactive closure for the text to follow a switch.
+    pub fn t(&self, id: &str) -> String {
+        translate(self.locale.get(), id, None)
+    }
+
+    /// [`t`](Self::t) with Fluent variables, e.g. `[("count", 3.into())]`.
+    pub fn t_args(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        translate(self.locale.get(), id, Some(&fluent_args))
+    }
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
@@ -4,23 +4,44 @@
 //! receives live updates over the dashboard WebSocket.
 
 pub mod api;
+pub mod export;
+pub mod i18n;
 pub mod pages;
+pub mod palette;
+pub mod player;
+pub mod toast;
 pub mod ws;
 
 use gloo_storage::{LocalStorage, Storage};
 use leptos::prelude::*;
 use leptos_router::components::{Route, Router, Routes};
+use leptos_router::hooks::{use_location, use_navigate};
 use leptos_router::path;
+use strata_protocol::api::{Capability, Locale, Theme, UserPreferences};
 
+use i18n::I18n;
+use pages::alerts::AlertsPage;
+use pages::audit::AuditPage;
 use pages::destinations::DestinationsPage;
-use pages::login::LoginPage;
+use pages::kits::{KitDetailPage, KitsPage};
+use pages::login::{LoginPage, PasswordLink, PasswordLinkPage};
+use pages::multiview::MultiviewPage;
+use pages::overview::OverviewPage;
+use pages::preferences::PreferencesPage;
 use pages::receivers::ReceiversPage;
+use pages::schedule::SchedulePage;
 use pages::sender_detail::SenderDetailPage;
 use pages::senders::SendersPage;
+use pages::share::SharedStreamPage;
 use pages::streams::StreamsPage;
-use ws::WsClient;
+use pages::users::UsersPage;
+use palette::CommandPalette;
+use toast::{ToastHost, Toasts};
+use ws::{ConnectionNotice, WsClient};
 
 const TOKEN_KEY: &str = "strata_token";
+const CAPABILITIES_KEY: &str = "strata_capabilities";
+const PREFS_KEY: &str = "strata_prefs";
 
 // ── Auth State ──────────────────────────────────────────────────────
 
@@ -29,53 +50,128 @@ const TOKEN_KEY: &str = "strata_token";
 pub struct AuthState {
     pub token: ReadSignal<Option<String>>,
     set_token: WriteSignal<Option<String>>,
-    pub role: ReadSignal<String>,
-    set_role: WriteSignal<String>,
+    /// What the server authorizes this session to do, from the login
+    /// response and refreshed from `/api/me`. Cached locally so a reload
+    /// doesn't render every control disabled until the fetch answers.
+    pub capabilities: ReadSignal<Vec<Capability>>,
+    set_capabilities: WriteSignal<Vec<Capability>>,
 }
 
 impl AuthState {
     fn new() -> Self {
         let stored: Option<String> = LocalStorage::get(TOKEN_KEY).ok();
-        let stored_role: String =
-            LocalStorage::get("strata_role").unwrap_or_else(|_| "viewer".into());
+        let stored_caps: Vec<Capability> = LocalStorage::get(CAPABILITIES_KEY).unwrap_or_default();
         let (token, set_token) = signal(stored);
-        let (role, set_role) = signal(stored_role);
+        let (capabilities, set_capabilities) = signal(stored_caps);
         Self {
             token,
             set_token,
-            role,
-            set_role,
+            capabilities,
+            set_capabilities,
         }
     }
 
-    pub fn login(&self, token: String, role: String) {
+    pub fn login(&self, token: String, capabilities: Vec<Capability>) {
         let _ = LocalStorage::set(TOKEN_KEY, &token);
-        let _ = LocalStorage::set("strata_role", &role);
         self.set_token.set(Some(token));
-        self.set_role.set(role);
+        self.set_capabilities(capabilities);
     }
 
     pub fn logout(&self) {
         LocalStorage::delete(TOKEN_KEY);
-        LocalStorage::delete("strata_role");
+        LocalStorage::delete(CAPABILITIES_KEY);
         self.set_token.set(None);
-        self.set_role.set("viewer".into());
+        self.set_capabilities.set(Vec::new());
     }
 
     pub fn is_authenticated(&self) -> bool {
         self.token.get_untracked().is_some()
     }
 
-    /// Role gate for UI controls. The server has two roles (operator,
-    /// viewer); its "admin"-gated endpoints accept any operator today, so
-    /// "admin" and "operator" rank equally here. This used to return true
-    /// unconditionally, making every disabled= guard decorative
-    /// (UX_TRUST_AUDIT U13).
-    pub fn has_role(&self, required: &str) -> bool {
-        match required {
-            "viewer" => true,
-            _ => self.role.get_untracked() == "operator",
+    /// Whether the server allows this session `capability`. Reactive, so
+    /// controls follow a refreshed `/api/me`. The server re-checks every
+    /// request; this only hides what would be refused.
+    pub fn can(&self, capability: Capability) -> bool {
+        self.capabilities.with(|caps| caps.contains(&capability))
+    }
+
+    fn set_capabilities(&self, capabilities: Vec<Capability>) {
+        let _ = LocalStorage::set(CAPABILITIES_KEY, &capabilities);
+        self.set_capabilities.set(capabilities);
+    }
+}
+
+// ── Preferences ─────────────────────────────────────────────────────
+
+/// The signed-in user's presentation preferences, provided via Leptos
+/// context. Cached in local storage so a reload applies the theme before
+/// the server copy arrives.
+#[derive(Clone, Copy)]
+pub struct PrefsState {
+    pub prefs: RwSignal<UserPreferences>,
+    /// Set once the server copy has been fetched this session.
+    pub loaded: RwSignal<bool>,
+}
+
+/// Locale from the locally cached preferences, for code that runs
+/// outside the reactive tree.
+pub(crate) fn cached_locale() -> Locale {
+    LocalStorage::get::<UserPreferences>(PREFS_KEY)
+        .map(|p| p.locale)
+        .unwrap_or_default()
+}
+
+impl PrefsState {
+    fn new() -> Self {
+        let stored: UserPreferences = LocalStorage::get(PREFS_KEY).unwrap_or_default();
+        Self {
+            prefs: RwSignal::new(stored),
+            loaded: RwSignal::new(false),
+        }
+    }
+
+    /// Adopt `prefs` (from the server or a save) and cache them locally.
+    pub fn set(&self, prefs: UserPreferences) {
+        let _ = LocalStorage::set(PREFS_KEY, &prefs);
+        self.prefs.set(prefs);
+    }
+
+    fn clear(&self) {
+        LocalStorage::delete(PREFS_KEY);
+        self.prefs.set(UserPreferences::default());
+        self.loaded.set(false);
+    }
+}
+
+/// Point DaisyUI at the chosen theme via `data-theme` on `<html>`.
+fn apply_theme(theme: Theme) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let name = match theme {
+        Theme::Dark => "dark",
+        Theme::Light => "light",
+        Theme::System => {
+            let light = window
+                .match_media("(prefers-color-scheme: light)")
+                .ok()
+                .flatten()
+                .is_some_and(|q| q.matches());
+            if light { "light" } else { "dark" }
         }
+    };
+    if let Some(root) = window.document().and_then(|d| d.document_element()) {
+        let _ = root.set_attribute("data-theme", name);
+    }
+}
+
+/// Tag `<html lang>` so screen readers and hyphenation follow the locale.
+fn apply_locale(locale: Locale) {
+    if let Some(root) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.document_element())
+    {
+        let _ = root.set_attribute("lang", locale.as_str());
     }
 }
 
@@ -85,83 +181,238 @@ impl AuthState {
 #[component]
 pub fn App() -> impl IntoView {
     let auth = AuthState::new();
+    let prefs = PrefsState::new();
     let ws_client = WsClient::new();
 
-    // Connect WebSocket when we have a token
+    // Connect WebSocket when we have a token; hang up on logout.
     let ws_connect = ws_client.clone();
     let auth_ws = auth.clone();
+    Effect::new(move || match auth_ws.token.get() {
+        Some(token) => ws_connect.connect(&token),
+        None => ws_connect.disconnect(),
+    });
+
+    // Refresh what the session may do, picking up role changes made since
+    // it signed in.
+    let auth_me = auth.clone();
     Effect::new(move || {
-        if let Some(token) = auth_ws.token.get() {
-            ws_connect.connect(&token);
+        if let Some(token) = auth_me.token.get() {
+            let auth = auth_me.clone();
+            leptos::task::spawn_local(async move {
+                match api::me(&token).await {
+                    Ok(me) => auth.set_capabilities(me.capabilities),
+                    Err(e) => log::warn!("failed to load capabilities: {e}"),
+                }
+            });
         }
     });
 
-    provide_context(auth.clone());
+    // Fetch the user's preferences on login; forget them on logout.
+    let auth_prefs = auth.clone();
+    Effect::new(move || match auth_prefs.token.get() {
+        Some(token) => leptos::task::spawn_local(async move {
+            match api::get_preferences(&token).await {
+                Ok(p) => prefs.set(p),
+                Err(e) => log::warn!("failed to load preferences: {e}"),
+            }
+            prefs.loaded.set(true);
+        }),
+        None => prefs.clear(),
+    });
+    Effect::new(move || apply_theme(prefs.prefs.with(|p| p.theme)));
+    let i18n = I18n::new(prefs);
+    Effect::new(move || apply_locale(i18n.locale()));
+
+    provide_context(auth);
+    provide_context(prefs);
+    provide_context(i18n);
+    provide_context(ws_client.history);
     provide_context(ws_client);
+    provide_context(Toasts::new());
 
     view! {
         <Router>
-            {move || {
-                if auth.token.get().is_none() {
-                    view! { <LoginPage /> }.into_any()
-                } else {
-                    view! { <DashboardShell /> }.into_any()
-                }
-            }}
+            <Gate />
         </Router>
     }
 }
 
+/// Picks what the app renders: a share link's public page, an invitation
+/// or password reset page, the login form, or the dashboard.
+#[component]
+fn Gate() -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let location = use_location();
+    // Only a change into or out of a share link re-renders; navigation
+    // inside the dashboard must not remount the shell.
+    let share_token = Memo::new(move |_| {
+        location.pathname.with(|p| {
+            p.strip_prefix("/share/")
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+        })
+    });
+
+    let password_link = Memo::new(move |_| location.pathname.with(|p| PasswordLink::parse(p)));
+
+    move || {
+        if let Some(token) = share_token.get() {
+            view! { <SharedStreamPage token=token /> }.into_any()
+        } else if let Some((kind, token)) = password_link.get() {
+            view! { <PasswordLinkPage kind=kind token=token /> }.into_any()
+        } else if auth.token.get().is_none() {
+            view! { <LoginPage /> }.into_any()
+        } else {
+            view! { <DashboardShell /> }.into_any()
+        }
+    }
+}
+
 // ── Dashboard Shell (sidebar + content) ─────────────────────────────
 
 #[component]
 fn DashboardShell() -> impl IntoView {
     let auth = expect_context::<AuthState>();
     let ws = expect_context::<WsClient>();
+    let prefs = expect_context::<PrefsState>();
+    let i18n = i18n::use_i18n();
+    let auth_nav = auth.clone();
+    let notice = ws.notice;
+
+    // Land on the preferred page once per session, and only from the bare
+    // root — a deep link must keep pointing where it points.
+    let navigate = use_navigate();
+    let location = use_location();
+    let pathname = location.pathname;
+    let landed = StoredValue::new(false);
+    Effect::new(move || {
+        if !prefs.loaded.get() || landed.get_value() {
+            return;
+        }
+        landed.set_value(true);
+        let page = prefs.prefs.with_untracked(|p| p.default_page.clone());
+        if location.pathname.get_untracked() == "/" && page != "/" {
+            navigate(&page, Default::default());
+        }
+    });
+
+    // Below `lg` the sidebar is an off-canvas drawer; following a link
+    // closes it so the page isn't hidden behind the menu.
+    let (nav_open, set_nav_open) = signal(false);
+    Effect::new(move || {
+        pathname.track();
+        set_nav_open.set(false);
+    });
 
     view! {
         <div class="flex min-h-screen">
+            // Top bar (phones / narrow tablets only)
+            <header class="lg:hidden fixed top-0 inset-x-0 h-14 bg-base-200 border-b border-base-300 flex items-center gap-2 px-3 z-20">
+                <button
+                    class="btn btn-ghost btn-square btn-sm"
+                    aria-label=move || i18n.t("nav-menu")
+                    on:click=move |_| set_nav_open.update(|o| *o = !*o)
+                >
+                    "☰"
+                </button>
+                <h1 class="text-lg font-bold tracking-tight">"Strata"</h1>
+            </header>
+            {move || nav_open.get().then(|| view! {
+                <div class="lg:hidden fixed inset-0 bg-black/40 z-20" on:click=move |_| set_nav_open.set(false)></div>
+            })}
             // Sidebar
-            <nav class="w-60 bg-base-200 border-r border-base-300 flex flex-col fixed top-0 left-0 bottom-0 z-10">
+            <nav class=move || {
+                let base = "w-60 bg-base-200 border-r border-base-300 flex flex-col fixed top-0 left-0 bottom-0 z-30 transition-transform lg:translate-x-0";
+                if nav_open.get() { base.to_string() } else { format!("{base} -translate-x-full") }
+            }>
                 <div class="p-5 border-b border-base-300 flex items-center gap-2.5">
                     <h1 class="text-lg font-bold tracking-tight">"Strata"</h1>
                     <span class="text-xs text-base-content/40 font-mono">"v0.1"</span>
+                    <kbd class="kbd kbd-xs ml-auto hidden lg:inline-flex" title=move || i18n.t("palette-title")>"⌘K"</kbd>
                 </div>
                 <ul class="menu flex-1 p-2 gap-0.5">
-                    <li><a href="/senders">"📡 Senders"</a></li>
-                    <li><a href="/receivers">"📥 Receivers"</a></li>
-                    <li><a href="/streams">"📺 Streams"</a></li>
-                    <li><a href="/destinations">"🎯 Destinations"</a></li>
+                    <li><a href="/overview">"🗺 "{move || i18n.t("nav-overview")}</a></li>
+                    <li><a href="/senders">"📡 "{move || i18n.t("nav-senders")}</a></li>
+                    <li><a href="/receivers">"📥 "{move || i18n.t("nav-receivers")}</a></li>
+                    <li><a href="/kits">"🧳 "{move || i18n.t("nav-kits")}</a></li>
+                    <li><a href="/streams">"📺 "{move || i18n.t("nav-streams")}</a></li>
+                    <li><a href="/multiview">"🖥 "{move || i18n.t("nav-multiview")}</a></li>
+                    <li><a href="/schedule">"📅 "{move || i18n.t("nav-schedule")}</a></li>
+                    <li><a href="/destinations">"🎯 "{move || i18n.t("nav-destinations")}</a></li>
+                    <li><a href="/alerts">"🚨 "{move || i18n.t("nav-alerts")}</a></li>
+                    <li><a href="/audit">"📜 "{move || i18n.t("nav-audit")}</a></li>
+                    {move || {
+                        auth_nav.can(Capability::ManageUsers).then(|| view! {
+                            <li><a href="/users">"👥 "{move || i18n.t("nav-users")}</a></li>
+                        })
+                    }}
+                    <li><a href="/preferences">"⚙ "{move || i18n.t("nav-preferences")}</a></li>
                 </ul>
                 <div class="p-3 border-t border-base-300">
                     <div class="flex justify-between items-center">
                         <span>
                             {move || if ws.auth_failed.get() {
-                                view! { <span class="badge badge-error badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-error"></span>"Auth failed"</span> }.into_any()
+                                view! { <span class="badge badge-error badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-error"></span>{i18n.t("status-auth-failed")}</span> }.into_any()
                             } else if ws.connected.get() {
-                                view! { <span class="badge badge-success badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-success"></span>"Live"</span> }.into_any()
+                                view! { <span class="badge badge-success badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-success"></span>{i18n.t("status-live")}</span> }.into_any()
                             } else {
-                                view! { <span class="badge badge-ghost badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-base-content/30"></span>"Offline"</span> }.into_any()
+                                view! { <span class="badge badge-ghost badge-sm gap-1"><span class="w-2 h-2 rounded-full bg-base-content/30"></span>{i18n.t("status-offline")}</span> }.into_any()
                             }}
                         </span>
                         <button
                             class="btn btn-ghost btn-sm"
                             on:click=move |_| auth.logout()
                         >
-                            "Logout"
+                            {move || i18n.t("logout")}
                         </button>
                     </div>
                 </div>
             </nav>
+            <CommandPalette />
+            // Toasts: connection state first, then action feedback
+            <div class="toast toast-end toast-bottom z-50">
+                {move || notice.get().map(|n| view! {
+                    {match n {
+                        ConnectionNotice::Lost { attempt, retry_in_ms } => view! {
+                            <div class="alert alert-warning text-sm">
+                                <span>
+                                    {i18n.t_args(
+                                        "connection-lost",
+                                        &[("seconds", (f64::from(retry_in_ms) / 1000.0).round().into())],
+                                    )}
+                                    {(attempt > 1).then(|| {
+                                        format!(" {}", i18n.t_args("connection-attempt", &[("attempt", attempt.into())]))
+                                    })}
+                                </span>
+                            </div>
+                        }.into_any(),
+                        ConnectionNotice::Restored => view! {
+                            <div class="alert alert-success text-sm">
+                                <span>{i18n.t("connection-restored")}</span>
+                            </div>
+                        }.into_any(),
+                    }}
+                })}
+                <ToastHost />
+            </div>
             // Main content
-            <main class="flex-1 ml-60 p-6 max-w-5xl">
-                <Routes fallback=|| view! { <SendersPage /> }>
-                    <Route path=path!("/") view=SendersPage />
+            <main class="flex-1 min-w-0 lg:ml-60 p-4 pt-18 lg:p-6 max-w-5xl">
+                <Routes fallback=|| view! { <OverviewPage /> }>
+                    <Route path=path!("/") view=OverviewPage />
+                    <Route path=path!("/overview") view=OverviewPage />
                     <Route path=path!("/senders") view=SendersPage />
                     <Route path=path!("/senders/:id") view=SenderDetailPage />
                     <Route path=path!("/receivers") view=ReceiversPage />
+                    <Route path=path!("/kits") view=KitsPage />
+                    <Route path=path!("/kits/:id") view=KitDetailPage />
                     <Route path=path!("/streams") view=StreamsPage />
+                    <Route path=path!("/multiview") view=MultiviewPage />
+                    <Route path=path!("/schedule") view=SchedulePage />
                     <Route path=path!("/destinations") view=DestinationsPage />
+                    <Route path=path!("/alerts") view=AlertsPage />
+                    <Route path=path!("/audit") view=AuditPage />
+                    <Route path=path!("/users") view=UsersPage />
+                    <Route path=path!("/preferences") view=PreferencesPage />
                 </Routes>
             </main>
         </div>
@@ -176,5 +427,24 @@ pub fn main() {
     console_error_panic_hook::set_once();
     let _ = console_log::init_with_level(log::Level::Debug);
     log::info!("Strata Dashboard starting");
+    register_service_worker();
     leptos::mount::mount_to_body(App);
 }
+
+/// Install `sw.js` so the dashboard can be added to a home screen and
+/// opens offline. Browsers only allow it on HTTPS or localhost.
+fn register_service_worker() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    if !window.is_secure_context() {
+        return;
+    }
+    let promise = window.navigator().service_worker().register("/sw.js");
+    // Leptos' executor isn't running yet; this doesn't need it.
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = wasm_bindgen_futures::JsFuture::from(promise).await {
+            log::warn!("service worker registration failed: {e:?}");
+        }
+    });
+}
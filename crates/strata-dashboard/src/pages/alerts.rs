@@ -0,0 +1,291 @@
+//! Alert history page.
+//!
+//! Every firing of an alert rule across the fleet, filterable by sender,
+//! severity and state. New firings and resolutions arrive live on the
+//! `alerts` topic; open alerts can be acknowledged or resolved in place.
+
+use leptos::prelude::*;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::{format_local_time, severity_badge};
+use crate::toast::use_toasts;
+use crate::ws::WsClient;
+use strata_protocol::DashboardEvent;
+use strata_protocol::api::{Capability, SenderSummary};
+use strata_protocol::models::{AlertEvent, AlertSeverity, AlertState};
+
+fn state_badge(state: AlertState) -> &'static str {
+    match state {
+        AlertState::Firing => "badge badge-error badge-sm",
+        AlertState::Acknowledged => "badge badge-warning badge-sm",
+        AlertState::Resolved => "badge badge-ghost badge-sm",
+    }
+}
+
+/// Whether `event` passes the page's current filters.
+fn matches_filters(
+    event: &AlertEvent,
+    sender: &str,
+    severity: Option<AlertSeverity>,
+    state: &str,
+) -> bool {
+    (sender.is_empty() || event.sender_id == sender)
+        && severity.is_none_or(|s| event.severity == s)
+        && match state {
+            "" => true,
+            "open" => event.state.is_open(),
+            s => event.state.as_str() == s,
+        }
+}
+
+#[component]
+pub fn AlertsPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
+    let ws = expect_context::<WsClient>();
+
+    let (alerts, set_alerts) = signal(Vec::<AlertEvent>::new());
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+
+    // Filters; empty string = any.
+    let (sender_filter, set_sender_filter) = signal(String::new());
+    let (severity_filter, set_severity_filter) = signal(Option::<AlertSeverity>::None);
+    let (state_filter, set_state_filter) = signal(String::from("open"));
+
+    let auth_senders = auth.clone();
+    Effect::new(move || {
+        if let Some(token) = auth_senders.token.get() {
+            leptos::task::spawn_local(async move {
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_senders.set(list);
+                }
+            });
+        }
+    });
+
+    let auth_load = auth.clone();
+    let resync = ws.resync;
+    Effect::new(move || {
+        let Some(token) = auth_load.token.get() else {
+            return;
+        };
+        resync.track();
+        let sender = sender_filter.get();
+        let severity = severity_filter.get();
+        let state = state_filter.get();
+        set_loading.set(true);
+        leptos::task::spawn_local(async move {
+            let sender = (!sender.is_empty()).then_some(sender.as_str());
+            let state = (!state.is_empty()).then_some(state.as_str());
+            match api::list_alerts(&token, sender, severity, state).await {
+                Ok(list) => {
+                    set_alerts.set(list);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    });
+
+    // Live updates — the `alerts` topic is subscribed for every session.
+    Effect::new(move || {
+        let Some(DashboardEvent::Alert(event)) = ws.last_event.get() else {
+            return;
+        };
+        let keep = matches_filters(
+            &event,
+            &sender_filter.get_untracked(),
+            severity_filter.get_untracked(),
+            &state_filter.get_untracked(),
+        );
+        set_alerts.update(|list| {
+            let pos = list.iter().position(|a| a.id == event.id);
+            match (pos, keep) {
+                (Some(i), true) => list[i] = event,
+                (Some(i), false) => {
+                    list.remove(i);
+                }
+                (None, true) => list.insert(0, event),
+                (None, false) => {}
+            }
+        });
+    });
+
+    let token = auth.token;
+    let act = move |id: String, action: &'static str| {
+        let token = token.get_untracked().unwrap_or_default();
+        leptos::task::spawn_local(async move {
+            // The updated event also comes back over the WebSocket; the
+            // response is applied directly so the row updates even if the
+            // socket is down.
+            match api::update_alert(&token, &id, action).await {
+                Ok(event) => set_alerts.update(|list| {
+                    if let Some(a) = list.iter_mut().find(|a| a.id == event.id) {
+                        *a = event;
+                    }
+                }),
+                Err(e) => toasts.error(format!("Alert {action} failed: {e}")),
+            }
+        });
+    };
+
+    let open_count = Memo::new(move |_| {
+        alerts
+            .get()
+            .iter()
+            .filter(|a| a.state == AlertState::Firing)
+            .count()
+    });
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold flex items-center gap-2">
+                        {move || i18n.t("nav-alerts")}
+                        {move || (open_count.get() > 0).then(|| view! {
+                            <span class="badge badge-error">
+                                {i18n.t_args("alerts-firing", &[("count", open_count.get().into())])}
+                            </span>
+                        })}
+                    </h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("alerts-subtitle")}</p>
+                </div>
+            </div>
+
+            <div class="flex flex-wrap gap-2 mb-4">
+                <select class="select select-bordered select-sm"
+                    on:change=move |ev| set_sender_filter.set(event_target_value(&ev))
+                >
+                    <option value="">"All senders"</option>
+                    {move || senders.get().into_iter().map(|s| {
+                        let label = s.name.clone().unwrap_or_else(|| s.id.clone());
+                        let id = s.id.clone();
+                        view! {
+                            <option value=s.id.clone() selected=move || sender_filter.get() == id>{label}</option>
+                        }
+                    }).collect::<Vec<_>>()}
+                </select>
+                <select class="select select-bordered select-sm"
+                    on:change=move |ev| set_severity_filter.set(event_target_value(&ev).parse().ok())
+                >
+                    <option value="">"All severities"</option>
+                    <option value="critical">"Critical"</option>
+                    <option value="warning">"Warning"</option>
+                    <option value="info">"Info"</option>
+                </select>
+                <select class="select select-bordered select-sm"
+                    on:change=move |ev| set_state_filter.set(event_target_value(&ev))
+                >
+                    <option value="open" selected=true>"Open"</option>
+                    <option value="firing">"Firing"</option>
+                    <option value="acknowledged">"Acknowledged"</option>
+                    <option value="resolved">"Resolved"</option>
+                    <option value="">"All states"</option>
+                </select>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            {move || {
+                if loading.get() && alerts.get().is_empty() {
+                    return view! { <p class="text-base-content/60">"Loading…"</p> }.into_any();
+                }
+                if alerts.get().is_empty() {
+                    return view! {
+                        <div class="flex flex-col items-center justify-center py-16 text-center">
+                            <div class="text-5xl mb-4">"✅"</div>
+                            <h3 class="text-lg font-medium mb-2">"No alerts"</h3>
+                            <p class="text-sm text-base-content/60">"Nothing matches these filters. Rules are configured per sender on the Settings tab."</p>
+                        </div>
+                    }.into_any();
+                }
+                let auth = auth.clone();
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="table table-sm">
+                            <thead>
+                                <tr>
+                                    <th>"Severity"</th>
+                                    <th>"Rule"</th>
+                                    <th>"Sender"</th>
+                                    <th>"Value"</th>
+                                    <th>"State"</th>
+                                    <th>"Fired"</th>
+                                    <th>"Handled"</th>
+                                    <th></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                <For
+                                    each=move || alerts.get()
+                                    key=|a| (a.id.clone(), a.state)
+                                    children=move |alert| {
+                                        let can_act = auth.can(Capability::ManageAlerts);
+                                        let id_ack = alert.id.clone();
+                                        let id_resolve = alert.id.clone();
+                                        let handled = match (&alert.resolved_at, &alert.acknowledged_at) {
+                                            (Some(at), _) => format!(
+                                                "resolved {} by {}",
+                                                format_local_time(Some(&at.to_rfc3339())),
+                                                alert.resolved_by.as_deref().unwrap_or("auto"),
+                                            ),
+                                            (None, Some(at)) => format!(
+                                                "ack'd {} by {}",
+                                                format_local_time(Some(&at.to_rfc3339())),
+                                                alert.acknowledged_by.as_deref().unwrap_or("—"),
+                                            ),
+                                            (None, None) => "—".into(),
+                                        };
+                                        view! {
+                                            <tr>
+                                                <td><span class=severity_badge(alert.severity)>{alert.severity.as_str()}</span></td>
+                                                <td>
+                                                    <div class="font-medium text-sm">{alert.rule_name.clone()}</div>
+                                                    <div class="text-xs text-base-content/60">
+                                                        {format!("{} {} {}", alert.metric, alert.condition, alert.threshold)}
+                                                    </div>
+                                                </td>
+                                                <td>
+                                                    <a class="link link-primary text-sm" href=format!("/senders/{}", alert.sender_id)>
+                                                        {alert.sender_name.clone().unwrap_or_else(|| alert.sender_id.clone())}
+                                                    </a>
+                                                </td>
+                                                <td class="font-mono text-xs">{format!("{:.2}", alert.value)}</td>
+                                                <td><span class=state_badge(alert.state)>{alert.state.as_str()}</span></td>
+                                                <td class="text-xs">{format_local_time(Some(&alert.fired_at.to_rfc3339()))}</td>
+                                                <td class="text-xs text-base-content/60">{handled}</td>
+                                                <td class="flex gap-1 justify-end">
+                                                    {(alert.state == AlertState::Firing).then(|| view! {
+                                                        <button class="btn btn-ghost btn-xs"
+                                                            disabled=!can_act
+                                                            on:click=move |_| act(id_ack.clone(), "ack")
+                                                        >"Acknowledge"</button>
+                                                    })}
+                                                    {alert.state.is_open().then(|| view! {
+                                                        <button class="btn btn-ghost btn-xs"
+                                                            disabled=!can_act
+                                                            on:click=move |_| act(id_resolve.clone(), "resolve")
+                                                        >"Resolve"</button>
+                                                    })}
+                                                </td>
+                                            </tr>
+                                        }
+                                    }
+                                />
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
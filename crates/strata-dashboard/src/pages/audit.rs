@@ -0,0 +1,176 @@
+//! Audit log page — who changed what across the fleet.
+
+use std::collections::BTreeMap;
+
+use leptos::prelude::*;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::format_local_time;
+use strata_protocol::api::SenderSummary;
+use strata_protocol::models::AuditEntry;
+
+#[component]
+pub fn AuditPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+
+    let (entries, set_entries) = signal(Vec::<AuditEntry>::new());
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+    let (reload, set_reload) = signal(0u32);
+
+    // Filters; empty string = any. `actor` is a user ID or "system".
+    let (sender_filter, set_sender_filter) = signal(String::new());
+    let (actor_filter, set_actor_filter) = signal(String::new());
+    // Every actor seen so far (user ID → email), so the dropdown keeps its
+    // options while a filter narrows the list.
+    let (actors, set_actors) = signal(BTreeMap::<String, String>::new());
+
+    let auth_senders = auth.clone();
+    Effect::new(move || {
+        if let Some(token) = auth_senders.token.get() {
+            leptos::task::spawn_local(async move {
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_senders.set(list);
+                }
+            });
+        }
+    });
+
+    Effect::new(move || {
+        let Some(token) = auth.token.get() else {
+            return;
+        };
+        reload.track();
+        let sender = sender_filter.get();
+        let actor = actor_filter.get();
+        set_loading.set(true);
+        leptos::task::spawn_local(async move {
+            let sender = (!sender.is_empty()).then_some(sender.as_str());
+            let actor = (!actor.is_empty()).then_some(actor.as_str());
+            match api::list_audit(&token, sender, actor).await {
+                Ok(list) => {
+                    set_actors.update(|m| {
+                        for e in &list {
+                            if let Some(id) = &e.actor_id {
+                                m.entry(id.clone()).or_insert_with(|| {
+                                    e.actor_email.clone().unwrap_or_else(|| id.clone())
+                                });
+                            }
+                        }
+                    });
+                    set_entries.set(list);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    });
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-audit")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("audit-subtitle")}</p>
+                </div>
+                <button class="btn btn-ghost btn-sm" on:click=move |_| set_reload.update(|n| *n += 1)>
+                    "↻ Refresh"
+                </button>
+            </div>
+
+            <div class="flex flex-wrap gap-2 mb-4">
+                <select class="select select-bordered select-sm"
+                    on:change=move |ev| set_sender_filter.set(event_target_value(&ev))
+                >
+                    <option value="">"All senders"</option>
+                    {move || senders.get().into_iter().map(|s| {
+                        let label = s.name.clone().unwrap_or_else(|| s.id.clone());
+                        let id = s.id.clone();
+                        view! {
+                            <option value=s.id.clone() selected=move || sender_filter.get() == id>{label}</option>
+                        }
+                    }).collect::<Vec<_>>()}
+                </select>
+                <select class="select select-bordered select-sm"
+                    on:change=move |ev| set_actor_filter.set(event_target_value(&ev))
+                >
+                    <option value="">"All users"</option>
+                    <option value="system">"System"</option>
+                    {move || actors.get().into_iter().map(|(id, email)| {
+                        let id2 = id.clone();
+                        view! {
+                            <option value=id selected=move || actor_filter.get() == id2>{email}</option>
+                        }
+                    }).collect::<Vec<_>>()}
+                </select>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            {move || {
+                if loading.get() && entries.get().is_empty() {
+                    return view! { <p class="text-base-content/60">"Loading…"</p> }.into_any();
+                }
+                if entries.get().is_empty() {
+                    return view! {
+                        <div class="flex flex-col items-center justify-center py-16 text-center">
+                            <div class="text-5xl mb-4">"📜"</div>
+                            <h3 class="text-lg font-medium mb-2">"No audit entries"</h3>
+                            <p class="text-sm text-base-content/60">"Nothing matches these filters yet."</p>
+                        </div>
+                    }.into_any();
+                }
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="table table-sm">
+                            <thead>
+                                <tr>
+                                    <th>"Time"</th>
+                                    <th>"User"</th>
+                                    <th>"Action"</th>
+                                    <th>"Sender"</th>
+                                    <th>"Detail"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                <For
+                                    each=move || entries.get()
+                                    key=|e| e.id.clone()
+                                    children=move |entry| {
+                                        let actor = match (&entry.actor_id, &entry.actor_email) {
+                                            (None, _) => view! { <span class="badge badge-ghost badge-sm">"system"</span> }.into_any(),
+                                            (Some(_), Some(email)) => view! { <span>{email.clone()}</span> }.into_any(),
+                                            (Some(_), None) => view! { <span class="text-base-content/40">"deleted user"</span> }.into_any(),
+                                        };
+                                        let sender = entry.sender_id.clone().map(|id| {
+                                            let label = entry.sender_name.clone().unwrap_or_else(|| id.clone());
+                                            view! {
+                                                <a class="link link-primary" href=format!("/senders/{id}")>{label}</a>
+                                            }
+                                        });
+                                        view! {
+                                            <tr>
+                                                <td class="text-xs whitespace-nowrap">{format_local_time(Some(&entry.created_at.to_rfc3339()))}</td>
+                                                <td class="text-sm">{actor}</td>
+                                                <td class="font-mono text-xs">{entry.action.clone()}</td>
+                                                <td class="text-sm">{sender}</td>
+                                                <td class="text-xs text-base-content/60">{entry.detail.clone().unwrap_or_default()}</td>
+                                            </tr>
+                                        }
+                                    }
+                                />
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
@@ -1,15 +1,29 @@
 //! Destinations management page.
+//!
+//! Platform presets prefill the ingest URL, each card shows the result of
+//! the control plane's health check, and admins can reveal or rotate the
+//! stream key. Usage history loads on demand per destination.
 
 use leptos::prelude::*;
 
 use crate::AuthState;
 use crate::api;
-use strata_protocol::api::DestinationSummary;
+use crate::i18n::use_i18n;
+use crate::pages::{format_bytes, format_local_time};
+use crate::toast::use_toasts;
+use strata_protocol::api::{
+    Capability, DESTINATION_PRESETS, DestinationHealth, DestinationPreset, DestinationStatus,
+    DestinationSummary, DestinationUsage,
+};
 
 /// CRUD page for streaming destinations.
 #[component]
 pub fn DestinationsPage() -> impl IntoView {
+    let i18n = use_i18n();
     let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
+    let can_manage = auth.can(Capability::ManageDestinations);
+    let token = auth.token;
     let (destinations, set_destinations) = signal(Vec::<DestinationSummary>::new());
     let (error, set_error) = signal(Option::<String>::None);
     let (loading, set_loading) = signal(true);
@@ -18,35 +32,34 @@ pub fn DestinationsPage() -> impl IntoView {
     // Create form fields
     let (new_name, set_new_name) = signal(String::new());
     let (new_platform, set_new_platform) = signal("youtube".to_string());
-    let (new_url, set_new_url) = signal(String::new());
+    let (new_url, set_new_url) = signal(DESTINATION_PRESETS[0].url.to_string());
     let (new_key, set_new_key) = signal(String::new());
 
-    // Derived: URL placeholder and help text based on selected platform
-    let url_placeholder = Memo::new(move |_| match new_platform.get().as_str() {
-        "youtube" => "rtmp://a.rtmp.youtube.com/live2".to_string(),
-        "youtube_hls" => {
+    let preset = Memo::new(move |_| DestinationPreset::lookup(&new_platform.get()).copied());
+    let url_placeholder = Memo::new(move |_| match preset.get() {
+        Some(p) if p.schemes == ["https"] => {
             "https://a.upload.youtube.com/http_upload_hls?cid=STREAM_KEY&copy=0&file=".to_string()
         }
-        "twitch" => "rtmp://live.twitch.tv/app".to_string(),
-        "srt" => "srt://host:port".to_string(),
+        Some(p) if p.schemes == ["srt"] => "srt://host:port".to_string(),
         _ => "rtmp://your-server/live".to_string(),
     });
-    let platform_help = Memo::new(move |_| {
-        match new_platform.get().as_str() {
-            "youtube" => "Standard RTMP ingest. H.265 requires Enhanced RTMP (eflvmux) — not all YouTube channels support this. Use YouTube HLS for reliable H.265.".to_string(),
-            "youtube_hls" => "Paste the full HLS ingest URL from YouTube Studio → Go Live → Stream settings. It looks like: https://a.upload.youtube.com/http_upload_hls?cid=xxxx&copy=0&file=".to_string(),
-            "twitch" => "Twitch only supports H.264 via RTMP. H.265 is not supported.".to_string(),
-            "srt" => "SRT transport — supports both H.264 and H.265.".to_string(),
-            _ => "Enter your RTMP server URL.".to_string(),
+    // HLS ingest carries the key in the URL, so there is no key field.
+    let show_stream_key = Memo::new(move |_| preset.get().is_none_or(|p| p.schemes != ["https"]));
+    let key_required = Memo::new(move |_| preset.get().is_some_and(|p| p.needs_key));
+
+    let on_platform_change = move |platform: String| {
+        // Swap in the preset URL unless the user has typed their own.
+        let current = new_url.get_untracked();
+        if current.is_empty() || DESTINATION_PRESETS.iter().any(|p| p.url == current) {
+            let url = DestinationPreset::lookup(&platform).map_or("", |p| p.url);
+            set_new_url.set(url.to_string());
         }
-    });
-    // Whether the current platform needs a separate stream key field
-    let show_stream_key = Memo::new(move |_| new_platform.get().as_str() != "youtube_hls");
+        set_new_platform.set(platform);
+    };
 
     // Load destinations
-    let auth_load = auth.clone();
     Effect::new(move || {
-        let token = auth_load.token.get();
+        let token = token.get();
         if let Some(token) = token {
             let token = token.clone();
             leptos::task::spawn_local(async move {
@@ -64,48 +77,56 @@ pub fn DestinationsPage() -> impl IntoView {
         }
     });
 
-    let auth_create = auth.clone();
     let on_create = move |_| {
-        let token = auth_create.token.get_untracked().unwrap_or_default();
+        let token = token.get_untracked().unwrap_or_default();
         let platform = new_platform.get_untracked();
         let name = new_name.get_untracked();
         let url = new_url.get_untracked();
-        let key = new_key.get_untracked();
-        let stream_key = if key.is_empty() { None } else { Some(key) };
+        let key = if show_stream_key.get_untracked() {
+            new_key.get_untracked()
+        } else {
+            String::new()
+        };
 
         if name.is_empty() || url.is_empty() {
             set_error.set(Some("Name and URL are required".into()));
             return;
         }
+        if key.is_empty() && key_required.get_untracked() {
+            let label = preset.get_untracked().map_or("This platform", |p| p.label);
+            set_error.set(Some(format!("{label} needs a stream key")));
+            return;
+        }
+        let stream_key = if key.is_empty() { None } else { Some(key) };
 
         leptos::task::spawn_local(async move {
             match api::create_destination(&token, &platform, &name, &url, stream_key).await {
-                Ok(_) => {
-                    // Reload
-                    if let Ok(data) = api::list_destinations(&token).await {
-                        set_destinations.set(data);
-                    }
+                Ok(created) => {
+                    set_error.set(None);
                     set_show_create.set(false);
                     set_new_name.set(String::new());
-                    set_new_url.set(String::new());
                     set_new_key.set(String::new());
+                    // Wait for the first health check so the new card
+                    // doesn't sit at "Not checked".
+                    let _ = api::check_destination(&token, &created.id).await;
+                    if let Ok(data) = api::list_destinations(&token).await {
+                        set_destinations.set(data);
+                    }
                 }
                 Err(e) => set_error.set(Some(e)),
             }
         });
     };
 
-    let auth_delete = auth.clone();
     let on_delete = move |id: String| {
-        let token = auth_delete.token.get_untracked().unwrap_or_default();
+        let token = token.get_untracked().unwrap_or_default();
+        // Drop the card at once; put the list back if the delete is refused.
+        let previous = destinations.get_untracked();
+        set_destinations.update(|ds| ds.retain(|d| d.id != id));
         leptos::task::spawn_local(async move {
-            match api::delete_destination(&token, &id).await {
-                Ok(()) => {
-                    if let Ok(data) = api::list_destinations(&token).await {
-                        set_destinations.set(data);
-                    }
-                }
-                Err(e) => set_error.set(Some(e)),
+            if let Err(e) = api::delete_destination(&token, &id).await {
+                set_destinations.set(previous);
+                toasts.error(format!("Couldn't delete destination: {e}"));
             }
         });
     };
@@ -114,12 +135,14 @@ pub fn DestinationsPage() -> impl IntoView {
         <div>
             <div class="flex justify-between items-center mb-6">
                 <div>
-                    <h2 class="text-2xl font-semibold">"Destinations"</h2>
-                    <p class="text-sm text-base-content/60 mt-1">"Streaming endpoints for your broadcasts"</p>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-destinations")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("destinations-subtitle")}</p>
                 </div>
-                <button class="btn btn-primary" on:click=move |_| set_show_create.set(true)>
-                    "+ Add Destination"
-                </button>
+                {can_manage.then(|| view! {
+                    <button class="btn btn-primary" on:click=move |_| set_show_create.set(true)>
+                        "+ Add Destination"
+                    </button>
+                })}
             </div>
 
             {move || error.get().map(|e| view! {
@@ -135,13 +158,16 @@ pub fn DestinationsPage() -> impl IntoView {
                             <label class="fieldset-label">"Platform"</label>
                             <select
                                 class="select select-bordered w-full"
-                                on:change=move |ev| set_new_platform.set(event_target_value(&ev))
+                                on:change=move |ev| on_platform_change(event_target_value(&ev))
                             >
-                                <option value="youtube">"YouTube (RTMP)"</option>
-                                <option value="youtube_hls">"YouTube (HLS — H.265 Native)"</option>
-                                <option value="twitch">"Twitch (RTMP — H.264 Only)"</option>
-                                <option value="custom_rtmp">"Custom RTMP"</option>
-                                <option value="srt">"SRT"</option>
+                                {DESTINATION_PRESETS.iter().map(|p| view! {
+                                    <option
+                                        value=p.platform
+                                        selected=move || new_platform.get() == p.platform
+                                    >
+                                        {p.label}
+                                    </option>
+                                }).collect_view()}
                             </select>
                         </fieldset>
                         <fieldset class="fieldset mb-3">
@@ -157,7 +183,7 @@ pub fn DestinationsPage() -> impl IntoView {
                         <fieldset class="fieldset mb-3">
                             <label class="fieldset-label">"URL"</label>
                             <input
-                                class="input input-bordered w-full"
+                                class="input input-bordered w-full font-mono text-sm"
                                 type="text"
                                 placeholder=move || url_placeholder.get()
                                 prop:value=move || new_url.get()
@@ -165,11 +191,13 @@ pub fn DestinationsPage() -> impl IntoView {
                             />
                         </fieldset>
                         <p class="text-xs text-base-content/50 -mt-2 mb-3 px-1">
-                            {move || platform_help.get()}
+                            {move || preset.get().map_or("Enter your server URL.", |p| p.help)}
                         </p>
                         {move || show_stream_key.get().then(|| view! {
                             <fieldset class="fieldset mb-3">
-                                <label class="fieldset-label">"Stream Key (optional)"</label>
+                                <label class="fieldset-label">
+                                    {move || if key_required.get() { "Stream Key" } else { "Stream Key (optional)" }}
+                                </label>
                                 <input
                                     class="input input-bordered w-full"
                                     type="password"
@@ -209,34 +237,15 @@ pub fn DestinationsPage() -> impl IntoView {
                             <For
                                 each=move || destinations.get()
                                 key=|d| d.id.clone()
-                                children=move |dest| {
-                                    let id = dest.id.clone();
-                                    let on_del = on_delete;
-                                    view! {
-                                        <div class="card bg-base-200 border border-base-300">
-                                            <div class="card-body">
-                                                <div class="flex justify-between items-start">
-                                                    <h3 class="card-title text-base">{dest.name.clone()}</h3>
-                                                    <button
-                                                        class="btn btn-ghost btn-sm"
-                                                        on:click=move |_| on_del(id.clone())
-                                                    >
-                                                        "Delete"
-                                                    </button>
-                                                </div>
-                                                <div class="flex flex-col gap-1.5 text-sm">
-                                                    <div>
-                                                        <span class="text-base-content/60">"Platform: "</span>
-                                                        <span class="capitalize">{platform_label(&dest.platform).to_string()}</span>
-                                                    </div>
-                                                    <div>
-                                                        <span class="text-base-content/60">"URL: "</span>
-                                                        <span class="font-mono text-xs break-all">{dest.url.clone()}</span>
-                                                    </div>
-                                                </div>
-                                            </div>
-                                        </div>
-                                    }
+                                children=move |dest| view! {
+                                    <DestinationCard
+                                        id=dest.id
+                                        destinations=destinations
+                                        set_destinations=set_destinations
+                                        set_error=set_error
+                                        can_manage=can_manage
+                                        on_delete=on_delete
+                                    />
                                 }
                             />
                         </div>
@@ -247,13 +256,257 @@ pub fn DestinationsPage() -> impl IntoView {
     }
 }
 
-fn platform_label(p: &str) -> &str {
-    match p {
-        "youtube" => "YouTube (RTMP)",
-        "youtube_hls" => "YouTube (HLS)",
-        "twitch" => "Twitch",
-        "custom_rtmp" => "Custom RTMP",
-        "srt" => "SRT",
-        _ => p,
+/// One destination. Reads its row out of the page's list so health and
+/// key changes patched into the list re-render in place.
+#[component]
+fn DestinationCard(
+    id: String,
+    destinations: ReadSignal<Vec<DestinationSummary>>,
+    set_destinations: WriteSignal<Vec<DestinationSummary>>,
+    set_error: WriteSignal<Option<String>>,
+    can_manage: bool,
+    on_delete: impl Fn(String) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let token = expect_context::<AuthState>().token;
+    let id = StoredValue::new(id);
+    let dest = Memo::new(move |_| {
+        destinations.with(|ds| ds.iter().find(|d| d.id == id.get_value()).cloned())
+    });
+
+    let (checking, set_checking) = signal(false);
+    let (revealed, set_revealed) = signal(Option::<String>::None);
+    let (show_rotate, set_show_rotate) = signal(false);
+    let (rotate_key, set_rotate_key) = signal(String::new());
+    let (usage, set_usage) = signal(Option::<Vec<DestinationUsage>>::None);
+    let (show_usage, set_show_usage) = signal(false);
+
+    let patch = move |f: &dyn Fn(&mut DestinationSummary)| {
+        set_destinations.update(|ds| {
+            if let Some(d) = ds.iter_mut().find(|d| d.id == id.get_value()) {
+                f(d);
+            }
+        });
+    };
+
+    let on_check = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        set_checking.set(true);
+        leptos::task::spawn_local(async move {
+            match api::check_destination(&token, &id.get_value()).await {
+                Ok(health) => patch(&|d| d.health = health.clone()),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_checking.set(false);
+        });
+    };
+
+    let on_reveal = move |_| {
+        if revealed.get_untracked().is_some() {
+            set_revealed.set(None);
+            return;
+        }
+        let token = token.get_untracked().unwrap_or_default();
+        leptos::task::spawn_local(async move {
+            match api::get_stream_key(&token, &id.get_value()).await {
+                Ok(key) => set_revealed.set(Some(key.unwrap_or_default())),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let on_rotate = move |_| {
+        let key = rotate_key.get_untracked().trim().to_string();
+        if key.is_empty() {
+            set_error.set(Some("Enter the new stream key".into()));
+            return;
+        }
+        let token = token.get_untracked().unwrap_or_default();
+        leptos::task::spawn_local(async move {
+            match api::rotate_stream_key(&token, &id.get_value(), &key).await {
+                Ok(updated) => {
+                    set_show_rotate.set(false);
+                    set_rotate_key.set(String::new());
+                    set_revealed.set(None);
+                    patch(&|d| *d = updated.clone());
+                    if let Ok(health) = api::check_destination(&token, &id.get_value()).await {
+                        patch(&|d| d.health = health.clone());
+                    }
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let on_toggle_usage = move |_| {
+        let open = !show_usage.get_untracked();
+        set_show_usage.set(open);
+        if open && usage.get_untracked().is_none() {
+            let token = token.get_untracked().unwrap_or_default();
+            leptos::task::spawn_local(async move {
+                match api::get_destination_usage(&token, &id.get_value()).await {
+                    Ok(rows) => set_usage.set(Some(rows)),
+                    Err(e) => set_error.set(Some(e)),
+                }
+            });
+        }
+    };
+
+    move || {
+        dest.get().map(|d| {
+            let health = d.health.clone();
+            let needs_key = DestinationPreset::lookup(&d.platform).is_some_and(|p| p.needs_key);
+            let masked = key_display(d.stream_key_hint.as_deref(), d.has_stream_key, needs_key);
+            view! {
+                <div class="card bg-base-200 border border-base-300">
+                    <div class="card-body gap-3">
+                        <div class="flex justify-between items-start gap-2">
+                            <div>
+                                <h3 class="card-title text-base">{d.name.clone()}</h3>
+                                <div class="text-xs text-base-content/60">{platform_label(&d.platform).to_string()}</div>
+                            </div>
+                            {health_badge(&health)}
+                        </div>
+
+                        <div class="font-mono text-xs break-all">{d.url.clone()}</div>
+                        {health.detail.clone().map(|detail| view! {
+                            <div class="text-xs text-error">{detail}</div>
+                        })}
+                        <div class="text-xs text-base-content/50">
+                            "Checked: " {format_local_time(health.checked_at.map(|t| t.to_rfc3339()).as_deref())}
+                        </div>
+
+                        <div class="flex items-center gap-2 text-sm">
+                            <span class="text-base-content/60">"Stream key: "</span>
+                            {move || match revealed.get() {
+                                Some(key) => view! {
+                                    <span class="font-mono text-xs break-all">{key}</span>
+                                }.into_any(),
+                                None => view! {
+                                    <span class="font-mono text-xs">{masked.clone()}</span>
+                                }.into_any(),
+                            }}
+                        </div>
+
+                        <div class="card-actions justify-end flex-wrap">
+                            <button class="btn btn-ghost btn-xs" on:click=on_toggle_usage>
+                                {move || if show_usage.get() { "Hide usage" } else { "Usage" }}
+                            </button>
+                            <button
+                                class="btn btn-ghost btn-xs"
+                                disabled=move || checking.get()
+                                on:click=on_check
+                            >
+                                {move || if checking.get() { "Checking…" } else { "Check" }}
+                            </button>
+                            {can_manage.then(|| view! {
+                                {d.has_stream_key.then(|| view! {
+                                    <button class="btn btn-ghost btn-xs" on:click=on_reveal>
+                                        {move || if revealed.get().is_some() { "Hide key" } else { "Reveal key" }}
+                                    </button>
+                                })}
+                                <button class="btn btn-ghost btn-xs" on:click=move |_| set_show_rotate.set(true)>
+                                    {if d.has_stream_key { "Rotate key" } else { "Set key" }}
+                                </button>
+                                <button
+                                    class="btn btn-ghost btn-xs text-error"
+                                    on:click=move |_| on_delete(id.get_value())
+                                >
+                                    "Delete"
+                                </button>
+                            })}
+                        </div>
+
+                        {move || show_usage.get().then(|| view! { <UsageTable usage=usage /> })}
+                    </div>
+
+                    {move || show_rotate.get().then(|| view! {
+                        <div class="modal modal-open">
+                            <div class="modal-box">
+                                <h3 class="text-lg font-semibold mb-2">"Rotate Stream Key"</h3>
+                                <p class="text-sm text-base-content/60 mb-4">
+                                    "Regenerate the key on the platform first, then paste it here. Streams already running keep the old key until restarted."
+                                </p>
+                                <input
+                                    class="input input-bordered w-full"
+                                    type="password"
+                                    placeholder="New stream key"
+                                    prop:value=move || rotate_key.get()
+                                    on:input=move |ev| set_rotate_key.set(event_target_value(&ev))
+                                />
+                                <div class="modal-action">
+                                    <button class="btn btn-ghost" on:click=move |_| set_show_rotate.set(false)>
+                                        "Cancel"
+                                    </button>
+                                    <button class="btn btn-primary" on:click=on_rotate>
+                                        "Save Key"
+                                    </button>
+                                </div>
+                            </div>
+                            <div class="modal-backdrop" on:click=move |_| set_show_rotate.set(false)></div>
+                        </div>
+                    })}
+                </div>
+            }
+        })
+    }
+}
+
+#[component]
+fn UsageTable(usage: ReadSignal<Option<Vec<DestinationUsage>>>) -> impl IntoView {
+    move || {
+        match usage.get() {
+        None => view! { <p class="text-xs text-base-content/60">"Loading…"</p> }.into_any(),
+        Some(rows) if rows.is_empty() => view! {
+            <p class="text-xs text-base-content/60">"No streams in the last 12 months."</p>
+        }
+        .into_any(),
+        Some(rows) => view! {
+            <div class="overflow-x-auto">
+                <table class="table table-xs">
+                    <thead>
+                        <tr>
+                            <th>"Month"</th>
+                            <th class="text-right">"Streams"</th>
+                            <th class="text-right">"Hours"</th>
+                            <th class="text-right">"Data"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {rows.into_iter().rev().map(|u| view! {
+                            <tr>
+                                <td class="font-mono">{u.month}</td>
+                                <td class="text-right">{u.stream_count}</td>
+                                <td class="text-right">{format!("{:.1}", u.streamed_minutes / 60.0)}</td>
+                                <td class="text-right">{format_bytes(u.total_bytes)}</td>
+                            </tr>
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        }
+        .into_any(),
+    }
+    }
+}
+
+fn health_badge(health: &DestinationHealth) -> impl IntoView + use<> {
+    let (class, label) = match health.status {
+        DestinationStatus::Ok => ("badge badge-success badge-sm", "Healthy"),
+        DestinationStatus::Error => ("badge badge-error badge-sm", "Failing"),
+        DestinationStatus::Unknown => ("badge badge-ghost badge-sm", "Not checked"),
+    };
+    view! { <span class=class title=health.detail.clone().unwrap_or_default()>{label}</span> }
+}
+
+fn key_display(hint: Option<&str>, has_key: bool, needs_key: bool) -> String {
+    match (hint, has_key) {
+        (Some(hint), _) => format!("••••{hint}"),
+        (None, true) => "••••••••".into(),
+        (None, false) if needs_key => "missing".into(),
+        (None, false) => "none".into(),
     }
 }
+
+fn platform_label(p: &str) -> &str {
+    DestinationPreset::lookup(p).map_or(p, |preset| preset.label)
+}
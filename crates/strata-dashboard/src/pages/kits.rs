@@ -0,0 +1,597 @@
+//! Kits pages — a sender and the serial-numbered accessories packed with
+//! it, checked out to a crew or job and back in as one unit.
+
+use chrono::{DateTime, Utc};
+use leptos::prelude::*;
+use leptos_router::hooks::{use_navigate, use_params_map};
+use wasm_bindgen::JsValue;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::format_local_time;
+use crate::toast::use_toasts;
+use strata_protocol::api::{
+    AddKitAccessoryRequest, Capability, CheckOutKitRequest, KIT_ACCESSORY_KINDS, KitCheckout,
+    KitDetail, KitRequest, KitSummary, SenderSummary,
+};
+
+/// `<input type="datetime-local">` value (local time) to an instant.
+fn from_input(value: &str) -> Option<DateTime<Utc>> {
+    let ms = js_sys::Date::new(&JsValue::from_str(value)).get_time();
+    if ms.is_nan() {
+        return None;
+    }
+    DateTime::from_timestamp_millis(ms as i64)
+}
+
+fn local(t: DateTime<Utc>) -> String {
+    format_local_time(Some(&t.to_rfc3339()))
+}
+
+/// Badge for where the kit is: on the shelf, out, or out past its due date.
+fn status_badge(checked_out: Option<&KitCheckout>) -> AnyView {
+    match checked_out {
+        None => view! { <span class="badge badge-success badge-sm">"In"</span> }.into_any(),
+        Some(c) if c.is_overdue(Utc::now()) => view! {
+            <span class="badge badge-error badge-sm">{format!("Overdue · {}", c.holder)}</span>
+        }
+        .into_any(),
+        Some(c) => view! {
+            <span class="badge badge-warning badge-sm">{format!("Out · {}", c.holder)}</span>
+        }
+        .into_any(),
+    }
+}
+
+/// Name / sender / notes form shared by the create and edit modals.
+#[component]
+fn KitForm(
+    title: &'static str,
+    initial: KitRequest,
+    senders: ReadSignal<Vec<SenderSummary>>,
+    on_save: impl Fn(KitRequest) + Copy + Send + Sync + 'static,
+    on_cancel: impl Fn() + Copy + Send + Sync + 'static,
+    saving: ReadSignal<bool>,
+) -> impl IntoView {
+    let (name, set_name) = signal(initial.name);
+    let (sender_id, set_sender_id) = signal(initial.sender_id.unwrap_or_default());
+    let (notes, set_notes) = signal(initial.notes.unwrap_or_default());
+
+    let save = move |_| {
+        let sender = sender_id.get_untracked();
+        let notes = notes.get_untracked();
+        on_save(KitRequest {
+            name: name.get_untracked(),
+            sender_id: (!sender.is_empty()).then_some(sender),
+            notes: (!notes.trim().is_empty()).then_some(notes),
+        });
+    };
+
+    view! {
+        <div class="modal modal-open">
+            <div class="modal-box">
+                <h3 class="font-bold text-lg">{title}</h3>
+                <fieldset class="fieldset mt-4">
+                    <label class="fieldset-label">"Name"</label>
+                    <input
+                        class="input input-bordered w-full"
+                        placeholder="Kit 12 — Pelican case"
+                        prop:value=move || name.get()
+                        on:input=move |ev| set_name.set(event_target_value(&ev))
+                    />
+                    <label class="fieldset-label mt-2">"Sender"</label>
+                    <select class="select select-bordered w-full"
+                        on:change=move |ev| set_sender_id.set(event_target_value(&ev))
+                    >
+                        <option value="" selected=move || sender_id.get().is_empty()>"— none —"</option>
+                        {move || senders.get().into_iter().map(|s| {
+                            let id = s.id.clone();
+                            let label = s.name.clone().unwrap_or_else(|| s.id.clone());
+                            view! {
+                                <option value=id.clone() selected=move || sender_id.get() == id>{label}</option>
+                            }
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <label class="fieldset-label mt-2">"Notes"</label>
+                    <textarea
+                        class="textarea textarea-bordered w-full"
+                        prop:value=move || notes.get()
+                        on:input=move |ev| set_notes.set(event_target_value(&ev))
+                    ></textarea>
+                </fieldset>
+                <div class="modal-action">
+                    <button class="btn btn-ghost" on:click=move |_| on_cancel()>"Cancel"</button>
+                    <button class="btn btn-primary" on:click=save
+                        disabled=move || saving.get() || name.get().trim().is_empty()
+                    >
+                        {move || if saving.get() { "Saving…" } else { "Save" }}
+                    </button>
+                </div>
+            </div>
+            <div class="modal-backdrop" on:click=move |_| on_cancel()></div>
+        </div>
+    }
+}
+
+// ── Kit List ────────────────────────────────────────────────────────
+
+#[component]
+pub fn KitsPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let token = auth.token;
+    let can_edit = auth.can(Capability::ManageKits);
+
+    let (kits, set_kits) = signal(Vec::<KitSummary>::new());
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+    let (show_create, set_show_create) = signal(false);
+    let (saving, set_saving) = signal(false);
+
+    Effect::new(move || {
+        if let Some(token) = token.get() {
+            leptos::task::spawn_local(async move {
+                match api::list_kits(&token).await {
+                    Ok(list) => set_kits.set(list),
+                    Err(e) => set_error.set(Some(e)),
+                }
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_senders.set(list);
+                }
+                set_loading.set(false);
+            });
+        }
+    });
+
+    let on_create = move |kit: KitRequest| {
+        let token = token.get_untracked().unwrap_or_default();
+        set_saving.set(true);
+        leptos::task::spawn_local(async move {
+            match api::save_kit(&token, None, &kit).await {
+                Ok(created) => set_kits.update(|list| {
+                    list.push(created);
+                    list.sort_by(|a, b| a.name.cmp(&b.name));
+                }),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_saving.set(false);
+            set_show_create.set(false);
+        });
+    };
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-kits")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("kits-subtitle")}</p>
+                </div>
+                {can_edit.then(|| view! {
+                    <button class="btn btn-primary" on:click=move |_| set_show_create.set(true)>
+                        "+ New Kit"
+                    </button>
+                })}
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            {move || show_create.get().then(|| view! {
+                <KitForm
+                    title="New Kit"
+                    initial=KitRequest { name: String::new(), sender_id: None, notes: None }
+                    senders=senders
+                    on_save=on_create
+                    on_cancel=move || set_show_create.set(false)
+                    saving=saving
+                />
+            })}
+
+            {move || {
+                if loading.get() {
+                    return view! { <p class="text-base-content/60">"Loading…"</p> }.into_any();
+                }
+                if kits.with(Vec::is_empty) {
+                    return view! {
+                        <div class="text-center py-16 text-base-content/60">
+                            <h3 class="text-lg font-semibold text-base-content mb-2">"No kits yet"</h3>
+                            <p class="text-sm max-w-sm mx-auto">
+                                "Group a sender with its modems, SIMs and cameras so the whole case can be checked out and back in by serial number."
+                            </p>
+                        </div>
+                    }.into_any();
+                }
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="table table-sm">
+                            <thead>
+                                <tr>
+                                    <th>"Kit"</th>
+                                    <th>"Sender"</th>
+                                    <th>"Items"</th>
+                                    <th>"Status"</th>
+                                    <th>"Due back"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                <For
+                                    each=move || kits.get()
+                                    key=|k| (k.id.clone(), k.checked_out.as_ref().map(|c| c.id.clone()))
+                                    children=move |kit| {
+                                        let href = format!("/kits/{}", kit.id);
+                                        let due = kit
+                                            .checked_out
+                                            .as_ref()
+                                            .and_then(|c| c.due_back_at)
+                                            .map(local)
+                                            .unwrap_or_else(|| "—".into());
+                                        view! {
+                                            <tr>
+                                                <td><a class="link link-hover font-semibold" href=href>{kit.name.clone()}</a></td>
+                                                <td class="text-sm">{kit.sender_name.clone().or(kit.sender_id.clone()).unwrap_or_else(|| "—".into())}</td>
+                                                <td class="text-sm">{kit.accessory_count}</td>
+                                                <td>{status_badge(kit.checked_out.as_ref())}</td>
+                                                <td class="text-sm text-base-content/60">{due}</td>
+                                            </tr>
+                                        }
+                                    }
+                                />
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+// ── Kit Detail ──────────────────────────────────────────────────────
+
+#[component]
+pub fn KitDetailPage() -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let token = auth.token;
+    let can_edit = auth.can(Capability::ManageKits);
+    let toasts = use_toasts();
+    let params = use_params_map();
+    let navigate = use_navigate();
+    let kit_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let (detail, set_detail) = signal(Option::<KitDetail>::None);
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (busy, set_busy) = signal(false);
+    let (show_edit, set_show_edit) = signal(false);
+    let (show_checkout, set_show_checkout) = signal(false);
+    let (confirm_delete, set_confirm_delete) = signal(false);
+
+    let (acc_kind, set_acc_kind) = signal(KIT_ACCESSORY_KINDS[0].to_string());
+    let (acc_serial, set_acc_serial) = signal(String::new());
+    let (acc_label, set_acc_label) = signal(String::new());
+
+    let (holder, set_holder) = signal(String::new());
+    let (due_back, set_due_back) = signal(String::new());
+    let (checkout_note, set_checkout_note) = signal(String::new());
+
+    let reload = move || {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        leptos::task::spawn_local(async move {
+            match api::get_kit(&token, &id).await {
+                Ok(d) => {
+                    set_detail.set(Some(d));
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    Effect::new(move || {
+        if let Some(token) = token.get() {
+            params.track();
+            reload();
+            leptos::task::spawn_local(async move {
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_senders.set(list);
+                }
+            });
+        }
+    });
+
+    // Run a write, then refresh the kit; failures surface as a toast.
+    let act =
+        move |label: &'static str,
+              fut: std::pin::Pin<Box<dyn Future<Output = Result<(), String>>>>| {
+            set_busy.set(true);
+            leptos::task::spawn_local(async move {
+                if let Err(e) = fut.await {
+                    toasts.error(format!("{label} failed: {e}"));
+                }
+                set_busy.set(false);
+                reload();
+            });
+        };
+
+    let on_save = move |kit: KitRequest| {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        set_show_edit.set(false);
+        act(
+            "Saving kit",
+            Box::pin(async move { api::save_kit(&token, Some(&id), &kit).await.map(|_| ()) }),
+        );
+    };
+
+    let on_add_accessory = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        let label = acc_label.get_untracked();
+        let req = AddKitAccessoryRequest {
+            kind: acc_kind.get_untracked(),
+            serial: acc_serial.get_untracked(),
+            label: (!label.trim().is_empty()).then_some(label),
+        };
+        set_acc_serial.set(String::new());
+        set_acc_label.set(String::new());
+        act(
+            "Adding item",
+            Box::pin(async move { api::add_kit_accessory(&token, &id, &req).await.map(|_| ()) }),
+        );
+    };
+
+    let on_check_out = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        let note = checkout_note.get_untracked();
+        let req = CheckOutKitRequest {
+            holder: holder.get_untracked(),
+            due_back_at: from_input(&due_back.get_untracked()),
+            note: (!note.trim().is_empty()).then_some(note),
+        };
+        set_show_checkout.set(false);
+        set_holder.set(String::new());
+        set_due_back.set(String::new());
+        set_checkout_note.set(String::new());
+        act(
+            "Check-out",
+            Box::pin(async move { api::check_out_kit(&token, &id, &req).await.map(|_| ()) }),
+        );
+    };
+
+    let on_check_in = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        act(
+            "Check-in",
+            Box::pin(async move { api::check_in_kit(&token, &id).await.map(|_| ()) }),
+        );
+    };
+
+    let on_delete = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        let id = kit_id();
+        let navigate = navigate.clone();
+        set_confirm_delete.set(false);
+        leptos::task::spawn_local(async move {
+            match api::delete_kit(&token, &id).await {
+                Ok(()) => navigate("/kits", Default::default()),
+                Err(e) => toasts.error(format!("Couldn't delete kit: {e}")),
+            }
+        });
+    };
+
+    view! {
+        <div>
+            <a href="/kits" class="link link-hover text-sm text-base-content/60">"← Kits"</a>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm my-4">{e}</div>
+            })}
+
+            {move || detail.get().map(|d| {
+                let kit = d.kit.clone();
+                let out = kit.checked_out.clone();
+                let sender = kit.sender_id.clone().map(|id| {
+                    let name = kit.sender_name.clone().unwrap_or_else(|| id.clone());
+                    view! { <a class="link" href=format!("/senders/{id}")>{name}</a> }
+                });
+                view! {
+                    <div class="flex justify-between items-start mt-2 mb-6">
+                        <div>
+                            <h2 class="text-2xl font-semibold flex items-center gap-3">
+                                {kit.name.clone()}
+                                {status_badge(out.as_ref())}
+                            </h2>
+                            <p class="text-sm text-base-content/60 mt-1">
+                                "Sender: " {sender.map(IntoAny::into_any).unwrap_or_else(|| "—".into_any())}
+                            </p>
+                            {kit.notes.clone().map(|n| view! {
+                                <p class="text-sm mt-2 whitespace-pre-line">{n}</p>
+                            })}
+                        </div>
+                        {can_edit.then(|| view! {
+                            <div class="flex gap-2">
+                                {if out.is_some() {
+                                    view! {
+                                        <button class="btn btn-primary btn-sm" on:click=on_check_in disabled=move || busy.get()>
+                                            "Check in"
+                                        </button>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <button class="btn btn-primary btn-sm" on:click=move |_| set_show_checkout.set(true) disabled=move || busy.get()>
+                                            "Check out"
+                                        </button>
+                                    }.into_any()
+                                }}
+                                <button class="btn btn-ghost btn-sm" on:click=move |_| set_show_edit.set(true)>"Edit"</button>
+                                <button class="btn btn-ghost btn-sm text-error" on:click=move |_| set_confirm_delete.set(true)>"Delete"</button>
+                            </div>
+                        })}
+                    </div>
+
+                    {out.map(|c| view! {
+                        <div class="alert text-sm mb-6" class:alert-error=c.is_overdue(Utc::now()) class:alert-warning=!c.is_overdue(Utc::now())>
+                            <span>
+                                {format!("Out with {} since {}", c.holder, local(c.checked_out_at))}
+                                {c.due_back_at.map(|due| format!(" · due back {}", local(due)))}
+                                {c.note.clone().map(|n| format!(" · {n}"))}
+                            </span>
+                        </div>
+                    })}
+
+                    <h3 class="font-semibold mb-2">"Contents"</h3>
+                    <table class="table table-sm mb-2">
+                        <thead>
+                            <tr><th>"Kind"</th><th>"Serial"</th><th>"Label"</th><th></th></tr>
+                        </thead>
+                        <tbody>
+                            {d.accessories.iter().map(|a| {
+                                let acc_id = a.id.clone();
+                                view! {
+                                    <tr>
+                                        <td><span class="badge badge-ghost badge-sm">{a.kind.clone()}</span></td>
+                                        <td class="font-mono text-sm">{a.serial.clone()}</td>
+                                        <td class="text-sm">{a.label.clone().unwrap_or_default()}</td>
+                                        <td class="text-right">
+                                            {can_edit.then(|| view! {
+                                                <button class="btn btn-ghost btn-xs text-error"
+                                                    on:click=move |_| {
+                                                        let token = token.get_untracked().unwrap_or_default();
+                                                        let id = kit_id();
+                                                        let acc_id = acc_id.clone();
+                                                        act("Removing item", Box::pin(async move {
+                                                            api::remove_kit_accessory(&token, &id, &acc_id).await
+                                                        }));
+                                                    }
+                                                >
+                                                    "Remove"
+                                                </button>
+                                            })}
+                                        </td>
+                                    </tr>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </tbody>
+                    </table>
+                    {can_edit.then(|| view! {
+                        <div class="flex gap-2 mb-8">
+                            <select class="select select-bordered select-sm"
+                                on:change=move |ev| set_acc_kind.set(event_target_value(&ev))
+                            >
+                                {KIT_ACCESSORY_KINDS.iter().map(|k| view! {
+                                    <option value=*k selected=move || acc_kind.get() == *k>{*k}</option>
+                                }).collect::<Vec<_>>()}
+                            </select>
+                            <input class="input input-bordered input-sm font-mono" placeholder="Serial / IMEI / ICCID"
+                                prop:value=move || acc_serial.get()
+                                on:input=move |ev| set_acc_serial.set(event_target_value(&ev))
+                            />
+                            <input class="input input-bordered input-sm" placeholder="Label (optional)"
+                                prop:value=move || acc_label.get()
+                                on:input=move |ev| set_acc_label.set(event_target_value(&ev))
+                            />
+                            <button class="btn btn-sm" on:click=on_add_accessory
+                                disabled=move || busy.get() || acc_serial.get().trim().is_empty()
+                            >
+                                "+ Add"
+                            </button>
+                        </div>
+                    })}
+
+                    <h3 class="font-semibold mb-2">"History"</h3>
+                    {if d.history.is_empty() {
+                        view! { <p class="text-sm text-base-content/60">"Never checked out."</p> }.into_any()
+                    } else {
+                        view! {
+                            <table class="table table-sm">
+                                <thead>
+                                    <tr><th>"Holder"</th><th>"Out"</th><th>"Due"</th><th>"In"</th><th>"Note"</th></tr>
+                                </thead>
+                                <tbody>
+                                    {d.history.iter().map(|c| view! {
+                                        <tr>
+                                            <td class="font-semibold">{c.holder.clone()}</td>
+                                            <td class="text-sm" title=c.checked_out_by.clone().unwrap_or_default()>{local(c.checked_out_at)}</td>
+                                            <td class="text-sm text-base-content/60">{c.due_back_at.map(local).unwrap_or_else(|| "—".into())}</td>
+                                            <td class="text-sm" title=c.checked_in_by.clone().unwrap_or_default()>{c.checked_in_at.map(local).unwrap_or_else(|| "—".into())}</td>
+                                            <td class="text-sm text-base-content/60">{c.note.clone().unwrap_or_default()}</td>
+                                        </tr>
+                                    }).collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }.into_any()
+                    }}
+                }
+            })}
+
+            {move || show_edit.get().then(|| {
+                let kit = detail.with(|d| d.as_ref().map(|d| d.kit.clone()))?;
+                Some(view! {
+                    <KitForm
+                        title="Edit Kit"
+                        initial=KitRequest { name: kit.name, sender_id: kit.sender_id, notes: kit.notes }
+                        senders=senders
+                        on_save=on_save
+                        on_cancel=move || set_show_edit.set(false)
+                        saving=busy
+                    />
+                })
+            })}
+
+            {move || show_checkout.get().then(|| view! {
+                <div class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">"Check Out Kit"</h3>
+                        <fieldset class="fieldset mt-4">
+                            <label class="fieldset-label">"Checked out to"</label>
+                            <input class="input input-bordered w-full" placeholder="Crew, customer or job #"
+                                prop:value=move || holder.get()
+                                on:input=move |ev| set_holder.set(event_target_value(&ev))
+                            />
+                            <label class="fieldset-label mt-2">"Due back (optional)"</label>
+                            <input class="input input-bordered w-full" type="datetime-local"
+                                prop:value=move || due_back.get()
+                                on:input=move |ev| set_due_back.set(event_target_value(&ev))
+                            />
+                            <label class="fieldset-label mt-2">"Note"</label>
+                            <input class="input input-bordered w-full"
+                                prop:value=move || checkout_note.get()
+                                on:input=move |ev| set_checkout_note.set(event_target_value(&ev))
+                            />
+                        </fieldset>
+                        <div class="modal-action">
+                            <button class="btn btn-ghost" on:click=move |_| set_show_checkout.set(false)>"Cancel"</button>
+                            <button class="btn btn-primary" on:click=on_check_out
+                                disabled=move || holder.get().trim().is_empty()
+                            >
+                                "Check out"
+                            </button>
+                        </div>
+                    </div>
+                    <div class="modal-backdrop" on:click=move |_| set_show_checkout.set(false)></div>
+                </div>
+            })}
+
+            {move || confirm_delete.get().then(|| view! {
+                <div class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">"Delete Kit"</h3>
+                        <p class="text-sm mt-4">
+                            "The kit, its item list and its check-out history will be deleted. The sender itself is not affected."
+                        </p>
+                        <div class="modal-action">
+                            <button class="btn btn-ghost" on:click=move |_| set_confirm_delete.set(false)>"Cancel"</button>
+                            <button class="btn btn-error" on:click=on_delete.clone()>"Delete"</button>
+                        </div>
+                    </div>
+                    <div class="modal-backdrop" on:click=move |_| set_confirm_delete.set(false)></div>
+                </div>
+            })}
+        </div>
+    }
+}
@@ -1,19 +1,27 @@
-//! Login page component.
+//! Login page component, plus the pages behind the emailed password
+//! links: accepting an invitation and resetting a forgotten password.
 
 use leptos::ev;
 use leptos::prelude::*;
+use leptos_router::hooks::use_navigate;
 
 use crate::AuthState;
 use crate::api;
+use crate::i18n::use_i18n;
 
-/// Login page — email/password form.
+/// Login page — email/password form, or the forgot-password form.
 #[component]
 pub fn LoginPage() -> impl IntoView {
     let auth = expect_context::<AuthState>();
+    let i18n = use_i18n();
     let (email, set_email) = signal(String::new());
     let (password, set_password) = signal(String::new());
     let (error, set_error) = signal(Option::<String>::None);
     let (loading, set_loading) = signal(false);
+    // Asking for a reset link instead of signing in; `reset_sent` once the
+    // request went through.
+    let (forgot, set_forgot) = signal(false);
+    let (reset_sent, set_reset_sent) = signal(false);
 
     let auth_submit = auth.clone();
     let do_login = move |email_val: String, password_val: String| {
@@ -23,7 +31,7 @@ pub fn LoginPage() -> impl IntoView {
         leptos::task::spawn_local(async move {
             match api::login(&email_val, &password_val).await {
                 Ok(resp) => {
-                    auth.login(resp.token, resp.role);
+                    auth.login(resp.token, resp.capabilities);
                 }
                 Err(e) => {
                     set_error.set(Some(e));
@@ -33,31 +41,62 @@ pub fn LoginPage() -> impl IntoView {
         });
     };
 
+    let do_forgot = move |email_val: String| {
+        set_loading.set(true);
+        set_error.set(None);
+        leptos::task::spawn_local(async move {
+            match api::forgot_password(&email_val).await {
+                Ok(()) => set_reset_sent.set(true),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
     let on_submit = move |ev: ev::SubmitEvent| {
         ev.prevent_default();
         let email_val = email.get_untracked();
+        if forgot.get_untracked() {
+            if !email_val.contains('@') {
+                set_error.set(Some(i18n.t("login-email-required")));
+                return;
+            }
+            do_forgot(email_val);
+            return;
+        }
         let password_val = password.get_untracked();
         if email_val.is_empty() || password_val.is_empty() {
-            set_error.set(Some("Email and password are required".into()));
+            set_error.set(Some(i18n.t("login-required")));
             return;
         }
         do_login(email_val, password_val);
     };
 
+    let toggle_forgot = move |_| {
+        set_forgot.update(|f| *f = !*f);
+        set_reset_sent.set(false);
+        set_error.set(None);
+    };
+
     view! {
         <div class="flex items-center justify-center min-h-screen bg-base-100">
             <div class="card bg-base-200 border border-base-300 w-full max-w-sm">
                 <div class="card-body">
                     <h1 class="text-2xl font-bold text-center">"Strata"</h1>
-                    <p class="text-center text-sm text-base-content/60 mb-4">"Bonded streaming control plane"</p>
+                    <p class="text-center text-sm text-base-content/60 mb-4">
+                        {move || i18n.t(if forgot.get() { "login-forgot-intro" } else { "login-tagline" })}
+                    </p>
 
                     {move || error.get().map(|e| view! {
                         <div class="alert alert-error text-sm mb-4">{e}</div>
                     })}
+                    {move || (forgot.get() && reset_sent.get()).then(|| view! {
+                        <div class="alert alert-success text-sm mb-4">{move || i18n.t("login-reset-sent")}</div>
+                    })}
 
                     <form on:submit=on_submit>
                         <fieldset class="fieldset">
-                            <label class="fieldset-label" for="email">"Email"</label>
+                            <label class="fieldset-label" for="email">{move || i18n.t("login-email")}</label>
                             <input
                                 id="email"
                                 class="input input-bordered w-full"
@@ -67,25 +106,159 @@ pub fn LoginPage() -> impl IntoView {
                                 on:input=move |ev| set_email.set(event_target_value(&ev))
                             />
                         </fieldset>
+                        {move || (!forgot.get()).then(|| view! {
+                            <fieldset class="fieldset">
+                                <label class="fieldset-label" for="password">{move || i18n.t("login-password")}</label>
+                                <input
+                                    id="password"
+                                    class="input input-bordered w-full"
+                                    type="password"
+                                    placeholder="••••••••"
+                                    prop:value=move || password.get()
+                                    on:input=move |ev| set_password.set(event_target_value(&ev))
+                                />
+                            </fieldset>
+                        })}
+                        <button
+                            class="btn btn-primary w-full mt-4"
+                            type="submit"
+                            disabled=move || loading.get()
+                        >
+                            {move || i18n.t(match (forgot.get(), loading.get()) {
+                                (false, false) => "login-submit",
+                                (false, true) => "login-submitting",
+                                (true, false) => "login-send-reset",
+                                (true, true) => "login-sending-reset",
+                            })}
+                        </button>
+                    </form>
+                    <button class="btn btn-link btn-sm mt-2" type="button" on:click=toggle_forgot>
+                        {move || i18n.t(if forgot.get() { "login-back" } else { "login-forgot" })}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// Which emailed password link a page was opened from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordLink {
+    /// `/invite/{token}` — an invited user choosing their first password.
+    Invite,
+    /// `/reset-password/{token}` — choosing a new password.
+    Reset,
+}
+
+impl PasswordLink {
+    /// The link kind and token of a password link path.
+    pub fn parse(path: &str) -> Option<(Self, String)> {
+        [
+            ("/invite/", Self::Invite),
+            ("/reset-password/", Self::Reset),
+        ]
+        .into_iter()
+        .find_map(|(prefix, kind)| {
+            path.strip_prefix(prefix)
+                .filter(|t| !t.is_empty())
+                .map(|t| (kind, t.to_string()))
+        })
+    }
+}
+
+/// Set a password from an invitation or reset link, then sign in.
+#[component]
+pub fn PasswordLinkPage(kind: PasswordLink, token: String) -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let i18n = use_i18n();
+    let navigate = use_navigate();
+    let (password, set_password) = signal(String::new());
+    let (confirm, set_confirm) = signal(String::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(false);
+
+    let (title, intro) = match kind {
+        PasswordLink::Invite => ("invite-title", "invite-intro"),
+        PasswordLink::Reset => ("reset-title", "reset-intro"),
+    };
+
+    let on_submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let password_val = password.get_untracked();
+        if password_val.is_empty() {
+            set_error.set(Some(i18n.t("password-required")));
+            return;
+        }
+        if password_val != confirm.get_untracked() {
+            set_error.set(Some(i18n.t("password-mismatch")));
+            return;
+        }
+        set_loading.set(true);
+        set_error.set(None);
+        let token = token.clone();
+        let auth = auth.clone();
+        let navigate = navigate.clone();
+        leptos::task::spawn_local(async move {
+            let result = match kind {
+                PasswordLink::Invite => api::accept_invite(&token, &password_val).await,
+                PasswordLink::Reset => api::reset_password(&token, &password_val).await,
+            };
+            match result {
+                Ok(resp) => {
+                    auth.login(resp.token, resp.capabilities);
+                    navigate("/", Default::default());
+                }
+                Err(e) => {
+                    set_error.set(Some(e));
+                    set_loading.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="flex items-center justify-center min-h-screen bg-base-100">
+            <div class="card bg-base-200 border border-base-300 w-full max-w-sm">
+                <div class="card-body">
+                    <h1 class="text-2xl font-bold text-center">{move || i18n.t(title)}</h1>
+                    <p class="text-center text-sm text-base-content/60 mb-4">{move || i18n.t(intro)}</p>
+
+                    {move || error.get().map(|e| view! {
+                        <div class="alert alert-error text-sm mb-4">{e}</div>
+                    })}
+
+                    <form on:submit=on_submit>
                         <fieldset class="fieldset">
-                            <label class="fieldset-label" for="password">"Password"</label>
+                            <label class="fieldset-label" for="new-password">{move || i18n.t("password-new")}</label>
                             <input
-                                id="password"
+                                id="new-password"
                                 class="input input-bordered w-full"
                                 type="password"
-                                placeholder="••••••••"
+                                autocomplete="new-password"
                                 prop:value=move || password.get()
                                 on:input=move |ev| set_password.set(event_target_value(&ev))
                             />
                         </fieldset>
+                        <fieldset class="fieldset">
+                            <label class="fieldset-label" for="confirm-password">{move || i18n.t("password-confirm")}</label>
+                            <input
+                                id="confirm-password"
+                                class="input input-bordered w-full"
+                                type="password"
+                                autocomplete="new-password"
+                                prop:value=move || confirm.get()
+                                on:input=move |ev| set_confirm.set(event_target_value(&ev))
+                            />
+                        </fieldset>
                         <button
                             class="btn btn-primary w-full mt-4"
                             type="submit"
                             disabled=move || loading.get()
                         >
-                            {move || if loading.get() { "Signing in…" } else { "Sign in" }}
+                            {move || i18n.t(if loading.get() { "password-saving" } else { "password-save" })}
                         </button>
                     </form>
+                    <a class="btn btn-link btn-sm mt-2" href="/">{move || i18n.t("login-back")}</a>
                 </div>
             </div>
         </div>
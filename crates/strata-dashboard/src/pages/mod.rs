@@ -1,9 +1,24 @@
+pub mod alerts;
+pub mod audit;
 pub mod destinations;
+pub mod kits;
 pub mod login;
+pub mod multiview;
+pub mod overview;
+pub mod preferences;
 pub mod receivers;
+pub mod schedule;
 pub mod sender_detail;
 pub mod senders;
+pub mod share;
 pub mod streams;
+pub mod users;
+
+use leptos::prelude::*;
+use strata_protocol::api::RateUnits;
+use strata_protocol::models::AlertSeverity;
+
+use crate::PrefsState;
 
 /// Render an RFC3339 timestamp in the viewer's local timezone as
 /// "YYYY-MM-DD HH:MM". Timestamps used to render hard-coded UTC, which a
@@ -26,6 +41,56 @@ pub fn format_local_time(rfc3339: Option<&str>) -> String {
     )
 }
 
+/// Render a duration as "H:MM:SS", or "M:SS" under an hour.
+pub fn format_duration(secs: u64) -> String {
+    let h = secs / 3600;
+    let m = (secs % 3600) / 60;
+    let s = secs % 60;
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Render a byte count with a binary prefix ("1.5 GB").
+pub fn format_bytes(b: u64) -> String {
+    if b >= 1_073_741_824 {
+        format!("{:.1} GB", b as f64 / 1_073_741_824.0)
+    } else if b >= 1_048_576 {
+        format!("{:.1} MB", b as f64 / 1_048_576.0)
+    } else if b >= 1024 {
+        format!("{:.0} KB", b as f64 / 1024.0)
+    } else {
+        format!("{b} B")
+    }
+}
+
+/// Render a bit rate with an SI prefix ("4.2 Mbps", or "525 kB/s" when the
+/// user prefers byte units). Reads the preference reactively, so views
+/// re-render when it changes.
+pub fn format_bps(bps: u64) -> String {
+    let bytes = use_context::<PrefsState>()
+        .is_some_and(|p| p.prefs.with(|p| p.rate_units == RateUnits::Bytes));
+    if bytes {
+        let bytes_per_s = bps / 8;
+        return if bytes_per_s >= 1_000_000 {
+            format!("{:.1} MB/s", bytes_per_s as f64 / 1_000_000.0)
+        } else if bytes_per_s >= 1_000 {
+            format!("{:.0} kB/s", bytes_per_s as f64 / 1_000.0)
+        } else {
+            format!("{bytes_per_s} B/s")
+        };
+    }
+    if bps >= 1_000_000 {
+        format!("{:.1} Mbps", bps as f64 / 1_000_000.0)
+    } else if bps >= 1_000 {
+        format!("{:.0} kbps", bps as f64 / 1_000.0)
+    } else {
+        format!("{bps} bps")
+    }
+}
+
 /// Human text for a stream end-reason slug (protocol `StreamEndReason`
 /// strings plus the control plane's inferred slugs).
 pub fn end_reason_label(reason: &str) -> &'static str {
@@ -41,3 +106,12 @@ pub fn end_reason_label(reason: &str) -> &'static str {
         _ => "ended",
     }
 }
+
+/// DaisyUI badge classes for an alert severity.
+pub fn severity_badge(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "badge badge-info badge-sm",
+        AlertSeverity::Warning => "badge badge-warning badge-sm",
+        AlertSeverity::Critical => "badge badge-error badge-sm",
+    }
+}
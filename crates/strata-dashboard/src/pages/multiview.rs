@@ -0,0 +1,400 @@
+//! Multiview wall — every live stream's preview and health on one screen.
+//!
+//! Built for NOC wall displays: tiles resize to fit the number of live
+//! streams, the wall can go fullscreen, and everything is reachable from
+//! the keyboard (arrows select, Enter solos a tile, Esc returns to the
+//! grid, `o` opens the sender, `f` toggles fullscreen).
+
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_navigate;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::format_bps;
+use crate::player::HlsPlayer;
+use crate::ws::WsClient;
+use strata_protocol::api::StreamSummary;
+use strata_protocol::models::StreamState;
+use strata_protocol::telemetry::LinkSample;
+use strata_protocol::{DashboardEvent, DashboardTopic};
+
+/// Latest telemetry seen for one sender.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct StreamHealth {
+    bitrate_kbps: u32,
+    links: Vec<LinkSample>,
+}
+
+impl StreamHealth {
+    fn links_up(&self) -> usize {
+        self.links.iter().filter(|l| l.state == "Live").count()
+    }
+
+    fn avg_rtt_ms(&self) -> f64 {
+        if self.links.is_empty() {
+            return 0.0;
+        }
+        self.links.iter().map(|l| l.rtt_ms.0).sum::<f64>() / self.links.len() as f64
+    }
+
+    fn max_loss_pct(&self) -> f64 {
+        self.links
+            .iter()
+            .map(|l| l.loss_rate * 100.0)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Grid columns for `n` tiles — as square as possible.
+fn columns_for(n: usize) -> usize {
+    match n {
+        0 | 1 => 1,
+        2..=4 => 2,
+        5..=9 => 3,
+        _ => 4,
+    }
+}
+
+fn is_fullscreen() -> bool {
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.fullscreen_element())
+        .is_some()
+}
+
+#[component]
+pub fn MultiviewPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let ws = expect_context::<WsClient>();
+    let navigate = use_navigate();
+
+    let (streams, set_streams) = signal(Vec::<StreamSummary>::new());
+    let (names, set_names) = signal(HashMap::<String, String>::new());
+    // Keyed by sender_id.
+    let (health, set_health) = signal(HashMap::<String, StreamHealth>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+    let (reload, set_reload) = signal(0u32);
+
+    let (selected, set_selected) = signal(0usize);
+    let (solo, set_solo) = signal(false);
+    let (fullscreen, set_fullscreen) = signal(false);
+    let wall_ref = NodeRef::<leptos::html::Div>::new();
+
+    // ── Load ─────────────────────────────────────────────────────
+    let auth_names = auth.clone();
+    Effect::new(move || {
+        if let Some(token) = auth_names.token.get() {
+            leptos::task::spawn_local(async move {
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_names.set(
+                        list.into_iter()
+                            .map(|s| {
+                                let name = s.name.unwrap_or_else(|| s.id.clone());
+                                (s.id, name)
+                            })
+                            .collect(),
+                    );
+                }
+            });
+        }
+    });
+
+    let resync = ws.resync;
+    Effect::new(move || {
+        let Some(token) = auth.token.get() else {
+            return;
+        };
+        reload.track();
+        if resync.get() > 0 {
+            set_health.set(HashMap::new());
+        }
+        leptos::task::spawn_local(async move {
+            match api::list_streams(&token).await {
+                Ok(list) => {
+                    let live: Vec<StreamSummary> =
+                        list.into_iter().filter(|s| s.state == "live").collect();
+                    set_selected.update(|i| *i = (*i).min(live.len().saturating_sub(1)));
+                    set_streams.set(live);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    });
+
+    // ── Telemetry subscriptions (live senders only) ──────────────
+    let subscribed = StoredValue::new(Vec::<DashboardTopic>::new());
+    let ws_sub = ws.clone();
+    Effect::new(move || {
+        let wanted: Vec<DashboardTopic> = streams
+            .get()
+            .iter()
+            .map(|s| DashboardTopic::Sender(s.sender_id.clone()))
+            .collect();
+        let previous = subscribed.get_value();
+        for topic in wanted.iter().filter(|t| !previous.contains(t)) {
+            ws_sub.subscribe(topic.clone());
+        }
+        for topic in previous.into_iter().filter(|t| !wanted.contains(t)) {
+            ws_sub.unsubscribe(topic);
+        }
+        subscribed.set_value(wanted);
+    });
+    let ws_cleanup = ws.clone();
+    on_cleanup(move || {
+        for topic in subscribed.try_get_value().unwrap_or_default() {
+            ws_cleanup.unsubscribe(topic);
+        }
+    });
+
+    // ── WebSocket events ─────────────────────────────────────────
+    Effect::new(move || {
+        let Some(event) = ws.last_event.get() else {
+            return;
+        };
+        match event {
+            DashboardEvent::StreamStats(stats) => {
+                set_health.update(|m| {
+                    m.insert(
+                        stats.sender_id,
+                        StreamHealth {
+                            bitrate_kbps: stats.encoder_bitrate_kbps,
+                            links: stats.links,
+                        },
+                    );
+                });
+            }
+            DashboardEvent::StreamStateChanged {
+                stream_id,
+                sender_id,
+                state,
+                ..
+            } => {
+                if state == StreamState::Live {
+                    // Re-fetch for the new stream's preview URL.
+                    set_reload.update(|n| *n += 1);
+                } else if matches!(state, StreamState::Ended | StreamState::Failed) {
+                    set_streams.update(|list| list.retain(|s| s.id != stream_id));
+                    set_health.update(|m| {
+                        m.remove(&sender_id);
+                    });
+                }
+            }
+            DashboardEvent::SenderStatus { .. }
+            | DashboardEvent::ReceiverStreamStats(_)
+            | DashboardEvent::Alert(_)
+            | DashboardEvent::CommandResult { .. } => {}
+        }
+    });
+
+    // ── Fullscreen ───────────────────────────────────────────────
+    let toggle_fullscreen = move || {
+        if is_fullscreen() {
+            if let Some(doc) = web_sys::window().and_then(|w| w.document()) {
+                doc.exit_fullscreen();
+            }
+        } else if let Some(wall) = wall_ref.get_untracked() {
+            let _ = wall.request_fullscreen();
+        }
+    };
+
+    // The browser leaves fullscreen on its own (Esc, F11), so follow the
+    // element's fullscreenchange rather than our own toggles.
+    let on_change = StoredValue::new_local(Option::<Closure<dyn FnMut()>>::None);
+    Effect::new(move || {
+        let Some(wall) = wall_ref.get() else {
+            return;
+        };
+        if on_change.with_value(Option::is_some) {
+            return;
+        }
+        let cb = Closure::<dyn FnMut()>::new(move || set_fullscreen.set(is_fullscreen()));
+        let _ =
+            wall.add_event_listener_with_callback("fullscreenchange", cb.as_ref().unchecked_ref());
+        on_change.set_value(Some(cb));
+    });
+
+    // ── Keyboard navigation ──────────────────────────────────────
+    let keys = window_event_listener(leptos::ev::keydown, move |ev| {
+        if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+            return;
+        }
+        let count = streams.get_untracked().len();
+        if count == 0 {
+            return;
+        }
+        let cols = columns_for(count);
+        let cur = selected.get_untracked().min(count - 1);
+        let next = match ev.key().as_str() {
+            "ArrowRight" | "l" => Some((cur + 1).min(count - 1)),
+            "ArrowLeft" | "h" => Some(cur.saturating_sub(1)),
+            "ArrowDown" | "j" => Some(if cur + cols < count { cur + cols } else { cur }),
+            "ArrowUp" | "k" => Some(cur.saturating_sub(cols)),
+            "Home" => Some(0),
+            "End" => Some(count - 1),
+            k if k.len() == 1 && k.as_bytes()[0].is_ascii_digit() && k != "0" => {
+                let i = (k.as_bytes()[0] - b'1') as usize;
+                (i < count).then_some(i)
+            }
+            "Enter" => {
+                set_solo.set(true);
+                None
+            }
+            "Escape" => {
+                set_solo.set(false);
+                None
+            }
+            "f" => {
+                toggle_fullscreen();
+                None
+            }
+            "o" => {
+                if let Some(s) = streams.get_untracked().get(cur) {
+                    navigate(&format!("/senders/{}", s.sender_id), Default::default());
+                }
+                None
+            }
+            _ => return,
+        };
+        ev.prevent_default();
+        if let Some(i) = next {
+            set_selected.set(i);
+        }
+    });
+    on_cleanup(move || keys.remove());
+
+    let visible = Memo::new(move |_| {
+        let list = streams.get();
+        if solo.get() {
+            let i = selected.get().min(list.len().saturating_sub(1));
+            list.into_iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx == i)
+                .collect::<Vec<_>>()
+        } else {
+            list.into_iter().enumerate().collect()
+        }
+    });
+    // Literal class names so the Tailwind build keeps them.
+    let grid_class = move || match columns_for(visible.get().len()) {
+        1 => "grid gap-2 grid-cols-1",
+        2 => "grid gap-2 grid-cols-1 md:grid-cols-2",
+        3 => "grid gap-2 grid-cols-1 md:grid-cols-3",
+        _ => "grid gap-2 grid-cols-1 md:grid-cols-4",
+    };
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-4">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-multiview")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("multiview-subtitle")}</p>
+                </div>
+                <div class="flex gap-2">
+                    <button class="btn btn-ghost btn-sm" on:click=move |_| set_reload.update(|n| *n += 1)>
+                        "↻ Refresh"
+                    </button>
+                    <button class="btn btn-primary btn-sm" on:click=move |_| toggle_fullscreen()>
+                        "⛶ Fullscreen"
+                    </button>
+                </div>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            <div node_ref=wall_ref class=move || if fullscreen.get() { "bg-black p-2 h-full overflow-auto" } else { "" }>
+                {move || {
+                    if loading.get() {
+                        return view! { <p class="text-base-content/60">"Loading…"</p> }.into_any();
+                    }
+                    if streams.get().is_empty() {
+                        return view! {
+                            <div class="flex flex-col items-center justify-center py-16 text-center">
+                                <div class="text-5xl mb-4">"🖥"</div>
+                                <h3 class="text-lg font-medium mb-2">"No live streams"</h3>
+                                <p class="text-sm text-base-content/60">"Streams appear here as soon as they go live."</p>
+                            </div>
+                        }.into_any();
+                    }
+                    view! {
+                        <div class=grid_class>
+                            <For
+                                each=move || visible.get()
+                                key=|(i, s)| (*i, s.id.clone(), s.preview_url.clone())
+                                children=move |(i, stream)| {
+                                    let sender_id = stream.sender_id.clone();
+                                    let name_id = sender_id.clone();
+                                    let name = move || {
+                                        names.get().get(&name_id).cloned().unwrap_or_else(|| name_id.clone())
+                                    };
+                                    let stats = Memo::new(move |_| health.get().get(&sender_id).cloned());
+                                    let preview = stream.preview_url.clone();
+                                    let has_preview = preview.is_some();
+                                    let url = Signal::derive(move || preview.clone());
+                                    view! {
+                                        <div
+                                            class=move || if selected.get() == i {
+                                                "relative rounded-lg ring-4 ring-primary"
+                                            } else {
+                                                "relative rounded-lg ring-1 ring-base-300"
+                                            }
+                                            on:click=move |_| set_selected.set(i)
+                                            on:dblclick=move |_| {
+                                                set_selected.set(i);
+                                                set_solo.update(|s| *s = !*s);
+                                            }
+                                        >
+                                            {if has_preview {
+                                                view! { <HlsPlayer url=url class="aspect-video" /> }.into_any()
+                                            } else {
+                                                view! {
+                                                    <div class="aspect-video bg-base-300 rounded-lg flex items-center justify-center text-sm text-base-content/40">
+                                                        "No preview"
+                                                    </div>
+                                                }.into_any()
+                                            }}
+                                            <div class="absolute top-0 inset-x-0 p-2 flex justify-between items-start gap-2 bg-gradient-to-b from-black/70 to-transparent rounded-t-lg pointer-events-none">
+                                                <div class="flex items-center gap-2 min-w-0">
+                                                    <span class="badge badge-neutral badge-sm font-mono">{i + 1}</span>
+                                                    <span class="font-semibold text-white truncate">{name}</span>
+                                                </div>
+                                                <span class="badge badge-success badge-sm">"LIVE"</span>
+                                            </div>
+                                            <div class="absolute bottom-12 inset-x-0 px-2 flex flex-wrap gap-1 pointer-events-none">
+                                                {move || stats.get().map(|h| {
+                                                    let up = h.links_up();
+                                                    let total = h.links.len();
+                                                    let loss = h.max_loss_pct();
+                                                    view! {
+                                                        <span class="badge badge-neutral badge-sm font-mono">{format_bps(h.bitrate_kbps as u64 * 1000)}</span>
+                                                        <span class={if up < total { "badge badge-warning badge-sm" } else { "badge badge-neutral badge-sm" }}>
+                                                            {format!("{up}/{total} links")}
+                                                        </span>
+                                                        <span class="badge badge-neutral badge-sm font-mono">{format!("{:.0} ms", h.avg_rtt_ms())}</span>
+                                                        <span class={if loss >= 5.0 { "badge badge-error badge-sm font-mono" } else { "badge badge-neutral badge-sm font-mono" }}>
+                                                            {format!("{loss:.1}% loss")}
+                                                        </span>
+                                                    }
+                                                })}
+                                            </div>
+                                        </div>
+                                    }
+                                }
+                            />
+                        </div>
+                    }.into_any()
+                }}
+            </div>
+        </div>
+    }
+}
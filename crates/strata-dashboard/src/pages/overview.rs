@@ -0,0 +1,335 @@
+//! Fleet overview page — the NOC landing page.
+//!
+//! One tile per sender with live status, current bitrate, link count and
+//! alert badges, plus fleet-wide aggregates. Fleet events arrive by
+//! default; the page subscribes to per-second telemetry only for senders
+//! that are online.
+
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::format_bps;
+use crate::ws::WsClient;
+use strata_protocol::api::{AlertRule, SenderSummary};
+use strata_protocol::models::StreamState;
+use strata_protocol::telemetry::LinkSample;
+use strata_protocol::{DashboardEvent, DashboardTopic};
+
+/// Latest telemetry seen for one sender.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SenderLive {
+    bitrate_kbps: u32,
+    links: Vec<LinkSample>,
+}
+
+/// Names of the enabled rules the latest stats breach.
+fn firing_rules(rules: &[AlertRule], links: &[LinkSample]) -> Vec<String> {
+    rules
+        .iter()
+        .filter(|r| r.breach(links).is_some())
+        .map(|r| r.name.clone())
+        .collect()
+}
+
+fn is_active(state: &str) -> bool {
+    matches!(state, "starting" | "live" | "stopping")
+}
+
+#[component]
+pub fn OverviewPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let ws = expect_context::<WsClient>();
+
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+    // Keyed by sender_id.
+    let (stream_states, set_stream_states) = signal(HashMap::<String, String>::new());
+    let (rules, set_rules) = signal(HashMap::<String, Vec<AlertRule>>::new());
+    let (live, set_live) = signal(HashMap::<String, SenderLive>::new());
+
+    // ── Initial load ─────────────────────────────────────────────
+    // Also reruns after a WS reconnect: telemetry from before the drop is
+    // discarded rather than left standing in for the current state.
+    let resync = ws.resync;
+    Effect::new(move || {
+        let Some(token) = auth.token.get() else {
+            return;
+        };
+        if resync.get() > 0 {
+            set_live.set(HashMap::new());
+        }
+        leptos::task::spawn_local(async move {
+            let list = match api::list_senders(&token).await {
+                Ok(list) => list,
+                Err(e) => {
+                    set_error.set(Some(e));
+                    set_loading.set(false);
+                    return;
+                }
+            };
+            if let Ok(streams) = api::list_streams(&token).await {
+                let active = streams
+                    .into_iter()
+                    .filter(|s| is_active(&s.state))
+                    .map(|s| (s.sender_id, s.state))
+                    .collect();
+                set_stream_states.set(active);
+            }
+            set_senders.set(list.clone());
+            set_loading.set(false);
+
+            if let Ok(all_rules) = api::list_alert_rules(&token).await {
+                set_rules.set(all_rules);
+            }
+        });
+    });
+
+    // ── Telemetry subscriptions (online senders only) ────────────
+    let subscribed = StoredValue::new(Vec::<DashboardTopic>::new());
+    let ws_sub = ws.clone();
+    Effect::new(move || {
+        let wanted: Vec<DashboardTopic> = senders
+            .get()
+            .iter()
+            .filter(|s| s.online)
+            .map(|s| DashboardTopic::Sender(s.id.clone()))
+            .collect();
+        let previous = subscribed.get_value();
+        for topic in wanted.iter().filter(|t| !previous.contains(t)) {
+            ws_sub.subscribe(topic.clone());
+        }
+        for topic in previous.into_iter().filter(|t| !wanted.contains(t)) {
+            ws_sub.unsubscribe(topic);
+        }
+        subscribed.set_value(wanted);
+    });
+    let ws_cleanup = ws.clone();
+    on_cleanup(move || {
+        for topic in subscribed.try_get_value().unwrap_or_default() {
+            ws_cleanup.unsubscribe(topic);
+        }
+    });
+
+    // ── WebSocket events ─────────────────────────────────────────
+    Effect::new(move || {
+        let Some(event) = ws.last_event.get() else {
+            return;
+        };
+        match event {
+            DashboardEvent::SenderStatus {
+                sender_id,
+                online,
+                status,
+            } => {
+                set_senders.update(|list| {
+                    if let Some(s) = list.iter_mut().find(|s| s.id == sender_id) {
+                        s.online = online;
+                    }
+                });
+                if !online {
+                    set_live.update(|m| {
+                        m.remove(&sender_id);
+                    });
+                }
+                if let Some(status) = status {
+                    let state = status.stream_state.to_string();
+                    set_stream_states.update(|m| {
+                        if is_active(&state) {
+                            m.insert(sender_id, state);
+                        } else {
+                            m.remove(&sender_id);
+                        }
+                    });
+                }
+            }
+            DashboardEvent::StreamStats(stats) => {
+                set_live.update(|m| {
+                    m.insert(
+                        stats.sender_id.clone(),
+                        SenderLive {
+                            bitrate_kbps: stats.encoder_bitrate_kbps,
+                            links: stats.links,
+                        },
+                    );
+                });
+                set_stream_states.update(|m| {
+                    m.entry(stats.sender_id).or_insert_with(|| "live".into());
+                });
+            }
+            DashboardEvent::StreamStateChanged {
+                sender_id, state, ..
+            } => {
+                let active = matches!(
+                    state,
+                    StreamState::Starting | StreamState::Live | StreamState::Stopping
+                );
+                set_stream_states.update(|m| {
+                    if active {
+                        m.insert(sender_id.clone(), state.to_string());
+                    } else {
+                        m.remove(&sender_id);
+                    }
+                });
+                if !active {
+                    set_live.update(|m| {
+                        m.remove(&sender_id);
+                    });
+                }
+            }
+            DashboardEvent::ReceiverStreamStats(_)
+            | DashboardEvent::Alert(_)
+            | DashboardEvent::CommandResult { .. } => {}
+        }
+    });
+
+    // ── Aggregates ───────────────────────────────────────────────
+    let online_count = Memo::new(move |_| senders.get().iter().filter(|s| s.online).count());
+    let total_count = Memo::new(move |_| senders.get().len());
+    let live_count = Memo::new(move |_| {
+        stream_states
+            .get()
+            .values()
+            .filter(|s| s.as_str() == "live")
+            .count()
+    });
+    let total_bitrate_bps = Memo::new(move |_| {
+        live.get()
+            .values()
+            .map(|l| l.bitrate_kbps as u64 * 1000)
+            .sum::<u64>()
+    });
+    let firing = Memo::new(move |_| {
+        let rules = rules.get();
+        live.get()
+            .iter()
+            .filter_map(|(id, l)| {
+                let names = firing_rules(rules.get(id)?, &l.links);
+                (!names.is_empty()).then(|| (id.clone(), names))
+            })
+            .collect::<HashMap<String, Vec<String>>>()
+    });
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("overview-title")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("overview-subtitle")}</p>
+                </div>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            <div class="stats stats-vertical md:stats-horizontal bg-base-200 border border-base-300 w-full mb-6">
+                <div class="stat">
+                    <div class="stat-title">"Senders online"</div>
+                    <div class="stat-value text-2xl">
+                        {move || format!("{} / {}", online_count.get(), total_count.get())}
+                    </div>
+                </div>
+                <div class="stat">
+                    <div class="stat-title">"Live streams"</div>
+                    <div class="stat-value text-2xl">{move || live_count.get()}</div>
+                </div>
+                <div class="stat">
+                    <div class="stat-title">"Fleet bitrate"</div>
+                    <div class="stat-value text-2xl font-mono">{move || format_bps(total_bitrate_bps.get())}</div>
+                </div>
+                <div class="stat">
+                    <div class="stat-title">"Alerts firing"</div>
+                    <div class=move || if firing.get().is_empty() { "stat-value text-2xl" } else { "stat-value text-2xl text-error" }>
+                        {move || firing.get().values().map(Vec::len).sum::<usize>()}
+                    </div>
+                </div>
+            </div>
+
+            {move || {
+                if loading.get() {
+                    view! { <p class="text-base-content/60">"Loading…"</p> }.into_any()
+                } else if senders.get().is_empty() {
+                    view! {
+                        <div class="text-center py-16 text-base-content/60">
+                            <div class="text-5xl mb-4">"🗺"</div>
+                            <h3 class="text-lg font-semibold text-base-content mb-2">"No senders yet"</h3>
+                            <p class="text-sm max-w-sm mx-auto mb-5">
+                                "Add a sender on the "<a href="/senders" class="link">"Senders"</a>" page to populate the fleet view."
+                            </p>
+                        </div>
+                    }.into_any()
+                } else {
+                    view! {
+                        <div class="grid grid-cols-2 md:grid-cols-3 lg:grid-cols-4 gap-3">
+                            <For
+                                each=move || senders.get()
+                                key=|s| (s.id.clone(), s.online)
+                                children=move |sender| {
+                                    let id = sender.id.clone();
+                                    let href = format!("/senders/{id}");
+                                    let name = sender.name.clone().unwrap_or_else(|| sender.id.clone());
+                                    let id_state = id.clone();
+                                    let state = Memo::new(move |_| stream_states.get().get(&id_state).cloned());
+                                    let id_live = id.clone();
+                                    let stats = Memo::new(move |_| live.get().get(&id_live).cloned());
+                                    let id_alerts = id.clone();
+                                    let alerts = Memo::new(move |_| firing.get().get(&id_alerts).cloned().unwrap_or_default());
+                                    let online = sender.online;
+                                    view! {
+                                        <a href=href class="no-underline text-base-content">
+                                            <div class=move || {
+                                                if !alerts.get().is_empty() {
+                                                    "card bg-base-200 border border-error hover:bg-base-300 transition-colors"
+                                                } else {
+                                                    "card bg-base-200 border border-base-300 hover:bg-base-300 transition-colors"
+                                                }
+                                            }>
+                                                <div class="card-body p-4 gap-2">
+                                                    <div class="flex justify-between items-start gap-2">
+                                                        <div class="font-semibold truncate">{name}</div>
+                                                        <span class={if online { "w-2.5 h-2.5 mt-1.5 rounded-full bg-success shrink-0" } else { "w-2.5 h-2.5 mt-1.5 rounded-full bg-base-content/30 shrink-0" }}></span>
+                                                    </div>
+                                                    <div class="flex items-center gap-1.5 flex-wrap">
+                                                        {move || match state.get() {
+                                                            Some(s) if s == "live" => view! { <span class="badge badge-success badge-sm">"LIVE"</span> }.into_any(),
+                                                            Some(s) => view! { <span class="badge badge-warning badge-sm">{s}</span> }.into_any(),
+                                                            None if online => view! { <span class="badge badge-ghost badge-sm">"Idle"</span> }.into_any(),
+                                                            None => view! { <span class="badge badge-ghost badge-sm">"Offline"</span> }.into_any(),
+                                                        }}
+                                                        {move || {
+                                                            let a = alerts.get();
+                                                            (!a.is_empty()).then(|| view! {
+                                                                <span class="badge badge-error badge-sm" title=a.join(", ")>
+                                                                    {format!("⚠ {}", a.len())}
+                                                                </span>
+                                                            })
+                                                        }}
+                                                    </div>
+                                                    {move || stats.get().map(|l| {
+                                                        let up = l.links.iter().filter(|k| k.state == "Live").count();
+                                                        view! {
+                                                            <div class="flex justify-between text-sm">
+                                                                <span class="font-mono">{format_bps(l.bitrate_kbps as u64 * 1000)}</span>
+                                                                <span class="text-base-content/60">{format!("{up}/{} links", l.links.len())}</span>
+                                                            </div>
+                                                        }
+                                                    })}
+                                                </div>
+                                            </div>
+                                        </a>
+                                    }
+                                }
+                            />
+                        </div>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
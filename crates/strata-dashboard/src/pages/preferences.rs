@@ -0,0 +1,180 @@
+//! User preferences page.
+
+use leptos::prelude::*;
+
+use crate::AuthState;
+use crate::PrefsState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::toast::use_toasts;
+use strata_protocol::api::{Locale, RateUnits, Theme, UserPreferences};
+
+/// Catalog ID of a landing page's name.
+fn page_label(page: &str) -> &'static str {
+    match page {
+        "/overview" => "nav-overview",
+        "/senders" => "nav-senders",
+        "/receivers" => "nav-receivers",
+        "/streams" => "nav-streams",
+        "/destinations" => "nav-destinations",
+        _ => page_label("/overview"),
+    }
+}
+
+/// Edits the signed-in user's presentation preferences. Changes preview
+/// immediately; "Save" stores them on the server for every browser.
+#[component]
+pub fn PreferencesPage() -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let prefs = expect_context::<PrefsState>();
+    let i18n = use_i18n();
+    let toasts = use_toasts();
+
+    // Snapshot to restore if the user leaves without saving.
+    let saved = StoredValue::new(prefs.prefs.get_untracked());
+    Effect::new(move || {
+        if prefs.loaded.get() {
+            saved.set_value(prefs.prefs.get_untracked());
+        }
+    });
+    let (dirty, set_dirty) = signal(false);
+    let (saving, set_saving) = signal(false);
+
+    let edit = move |f: &dyn Fn(&mut UserPreferences)| {
+        prefs.prefs.update(f);
+        set_dirty.set(true);
+    };
+
+    on_cleanup(move || {
+        if dirty.get_untracked()
+            && let Some(p) = saved.try_get_value()
+        {
+            prefs.prefs.set(p);
+        }
+    });
+
+    let on_save = move |_| {
+        let token = auth.token.get_untracked().unwrap_or_default();
+        let current = prefs.prefs.get_untracked();
+        set_saving.set(true);
+        leptos::task::spawn_local(async move {
+            match api::save_preferences(&token, &current).await {
+                Ok(p) => {
+                    saved.set_value(p.clone());
+                    prefs.set(p);
+                    set_dirty.set(false);
+                    toasts.success(i18n.t("prefs-saved"));
+                }
+                Err(e) => toasts.error(i18n.t_args("prefs-save-failed", &[("error", e.into())])),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-preferences")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("prefs-subtitle")}</p>
+                </div>
+            </div>
+
+            <div class="card bg-base-200 border border-base-300 max-w-xl">
+                <div class="card-body gap-4">
+                    <fieldset class="fieldset">
+                        <label class="fieldset-label">{move || i18n.t("prefs-language")}</label>
+                        <select class="select select-bordered w-full"
+                            on:change=move |ev| {
+                                let tag = event_target_value(&ev);
+                                let locale = Locale::from_tag(&tag).unwrap_or_default();
+                                edit(&|p| p.locale = locale);
+                            }
+                        >
+                            {Locale::ALL.iter().map(|&locale| view! {
+                                <option value=locale.as_str() selected=move || prefs.prefs.with(|p| p.locale == locale)>
+                                    {locale.native_name()}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                    </fieldset>
+
+                    <fieldset class="fieldset">
+                        <label class="fieldset-label">{move || i18n.t("prefs-theme")}</label>
+                        <select class="select select-bordered w-full"
+                            on:change=move |ev| {
+                                let theme = match event_target_value(&ev).as_str() {
+                                    "light" => Theme::Light,
+                                    "system" => Theme::System,
+                                    _ => Theme::Dark,
+                                };
+                                edit(&|p| p.theme = theme);
+                            }
+                        >
+                            <option value="dark" selected=move || prefs.prefs.with(|p| p.theme == Theme::Dark)>{move || i18n.t("prefs-theme-dark")}</option>
+                            <option value="light" selected=move || prefs.prefs.with(|p| p.theme == Theme::Light)>{move || i18n.t("prefs-theme-light")}</option>
+                            <option value="system" selected=move || prefs.prefs.with(|p| p.theme == Theme::System)>{move || i18n.t("prefs-theme-system")}</option>
+                        </select>
+                    </fieldset>
+
+                    <fieldset class="fieldset">
+                        <label class="fieldset-label">{move || i18n.t("prefs-default-page")}</label>
+                        <select class="select select-bordered w-full"
+                            on:change=move |ev| {
+                                let page = event_target_value(&ev);
+                                edit(&|p| p.default_page = page.clone());
+                            }
+                        >
+                            {UserPreferences::PAGES.iter().map(|&page| view! {
+                                <option value=page selected=move || prefs.prefs.with(|p| p.default_page == page)>
+                                    {move || i18n.t(page_label(page))}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                        <p class="text-xs text-base-content/40 mt-1">{move || i18n.t("prefs-default-page-hint")}</p>
+                    </fieldset>
+
+                    <fieldset class="fieldset">
+                        <label class="fieldset-label">{move || i18n.t("prefs-rate-units")}</label>
+                        <select class="select select-bordered w-full"
+                            on:change=move |ev| {
+                                let units = if event_target_value(&ev) == "bytes" { RateUnits::Bytes } else { RateUnits::Bits };
+                                edit(&|p| p.rate_units = units);
+                            }
+                        >
+                            <option value="bits" selected=move || prefs.prefs.with(|p| p.rate_units == RateUnits::Bits)>{move || i18n.t("prefs-rate-bits")}</option>
+                            <option value="bytes" selected=move || prefs.prefs.with(|p| p.rate_units == RateUnits::Bytes)>{move || i18n.t("prefs-rate-bytes")}</option>
+                        </select>
+                    </fieldset>
+
+                    <fieldset class="fieldset">
+                        <label class="fieldset-label">{move || i18n.t("prefs-graph-window")}</label>
+                        <select class="select select-bordered w-full"
+                            on:change=move |ev| {
+                                if let Ok(secs) = event_target_value(&ev).parse::<u32>() {
+                                    edit(&|p| p.graph_window_s = secs);
+                                }
+                            }
+                        >
+                            {UserPreferences::GRAPH_WINDOWS.iter().map(|&secs| view! {
+                                <option value=secs.to_string() selected=move || prefs.prefs.with(|p| p.graph_window_s == secs)>
+                                    {move || if secs >= 60 {
+                                        i18n.t_args("prefs-minutes", &[("n", (secs / 60).into())])
+                                    } else {
+                                        i18n.t_args("prefs-seconds", &[("n", secs.into())])
+                                    }}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                    </fieldset>
+
+                    <div class="card-actions justify-end">
+                        <button class="btn btn-primary" on:click=on_save disabled=move || saving.get() || !dirty.get()>
+                            {move || i18n.t(if saving.get() { "prefs-saving" } else { "prefs-save" })}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -7,11 +7,16 @@ use leptos::prelude::*;
 
 use crate::AuthState;
 use crate::api;
-use crate::api::ReceiverSummary;
+use crate::i18n::use_i18n;
+use crate::toast::use_toasts;
+use crate::ws::WsClient;
+use strata_protocol::api::ReceiverSummary;
 
 #[component]
 pub fn ReceiversPage() -> impl IntoView {
+    let i18n = use_i18n();
     let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
     let (receivers, set_receivers) = signal(Vec::<ReceiverSummary>::new());
     let (error, set_error) = signal(Option::<String>::None);
     let (loading, set_loading) = signal(true);
@@ -25,8 +30,10 @@ pub fn ReceiversPage() -> impl IntoView {
     let (new_max_streams, set_new_max_streams) = signal("6".to_string());
 
     let auth_load = auth.clone();
+    let resync = expect_context::<WsClient>().resync;
     Effect::new(move || {
         let token = auth_load.token.get();
+        resync.track();
         if let Some(token) = token {
             let token = token.clone();
             leptos::task::spawn_local(async move {
@@ -87,14 +94,12 @@ pub fn ReceiversPage() -> impl IntoView {
     let auth_delete = auth.clone();
     let on_delete = move |id: String| {
         let token = auth_delete.token.get_untracked().unwrap_or_default();
+        let previous = receivers.get_untracked();
+        set_receivers.update(|rs| rs.retain(|r| r.id != id));
         leptos::task::spawn_local(async move {
-            match api::delete_receiver(&token, &id).await {
-                Ok(()) => {
-                    if let Ok(data) = api::list_receivers(&token).await {
-                        set_receivers.set(data);
-                    }
-                }
-                Err(e) => set_error.set(Some(e)),
+            if let Err(e) = api::delete_receiver(&token, &id).await {
+                set_receivers.set(previous);
+                toasts.error(format!("Couldn't delete receiver: {e}"));
             }
         });
     };
@@ -103,8 +108,8 @@ pub fn ReceiversPage() -> impl IntoView {
         <div>
             <div class="flex justify-between items-center mb-6">
                 <div>
-                    <h2 class="text-2xl font-semibold">"Receivers"</h2>
-                    <p class="text-sm text-base-content/60 mt-1">"Relay fleet — streams are assigned to the least-loaded online receiver"</p>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-receivers")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("receivers-subtitle")}</p>
                 </div>
                 <button class="btn btn-primary" on:click=move |_| set_show_create.set(true)>
                     "+ Register Receiver"
@@ -237,7 +242,7 @@ pub fn ReceiversPage() -> impl IntoView {
                                                     </td>
                                                     <td>{format!("{}/{}", rcv.active_streams, rcv.max_streams)}</td>
                                                     <td class="text-xs text-base-content/60">
-                                                        {crate::pages::format_local_time(rcv.last_seen_at.as_deref())}
+                                                        {crate::pages::format_local_time(rcv.last_seen_at.map(|t| t.to_rfc3339()).as_deref())}
                                                     </td>
                                                     <td>
                                                         <button
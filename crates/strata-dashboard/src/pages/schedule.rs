@@ -0,0 +1,498 @@
+//! Schedule page — a week calendar of booked streams.
+//!
+//! The control plane starts each booking at its start time and stops it at
+//! its end time. Bookings that double-book a sender are outlined in red;
+//! the form warns about a clash before it is saved.
+
+use chrono::{DateTime, Utc};
+use leptos::prelude::*;
+use wasm_bindgen::JsValue;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use strata_protocol::api::{Capability, DestinationSummary, ScheduleStreamRequest, SenderSummary};
+use strata_protocol::models::{ScheduleState, ScheduledStream};
+use strata_protocol::profiles::{FRAMERATES, RESOLUTIONS};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn to_js(t: DateTime<Utc>) -> js_sys::Date {
+    js_sys::Date::new(&JsValue::from_f64(t.timestamp_millis() as f64))
+}
+
+fn from_js(d: &js_sys::Date) -> Option<DateTime<Utc>> {
+    let ms = d.get_time();
+    if ms.is_nan() {
+        return None;
+    }
+    DateTime::from_timestamp_millis(ms as i64)
+}
+
+/// Local midnights bounding the 7 days of the week `offset` weeks from
+/// this one (Monday first): 8 instants, day `i` is `[i, i + 1)`.
+fn week_bounds(offset: i32) -> Vec<DateTime<Utc>> {
+    let today = js_sys::Date::new_0();
+    let back = (today.get_day() as i32 + 6) % 7;
+    let monday = today.get_date() as i32 - back + 7 * offset;
+    (0..8)
+        .filter_map(|i| {
+            from_js(&js_sys::Date::new_with_year_month_day(
+                today.get_full_year(),
+                today.get_month() as i32,
+                monday + i,
+            ))
+        })
+        .collect()
+}
+
+/// "HH:MM" in the viewer's timezone.
+fn local_hm(t: DateTime<Utc>) -> String {
+    let d = to_js(t);
+    format!("{:02}:{:02}", d.get_hours(), d.get_minutes())
+}
+
+/// "YYYY-MM-DD" in the viewer's timezone.
+fn local_date(t: DateTime<Utc>) -> String {
+    let d = to_js(t);
+    format!(
+        "{:04}-{:02}-{:02}",
+        d.get_full_year(),
+        d.get_month() + 1,
+        d.get_date()
+    )
+}
+
+/// "Mon 4" style day heading.
+fn day_label(i: usize, t: DateTime<Utc>) -> String {
+    format!("{} {}", WEEKDAYS[i], to_js(t).get_date())
+}
+
+/// `<input type="datetime-local">` value for `t` in local time.
+fn to_input(t: DateTime<Utc>) -> String {
+    let d = to_js(t);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}",
+        d.get_full_year(),
+        d.get_month() + 1,
+        d.get_date(),
+        d.get_hours(),
+        d.get_minutes()
+    )
+}
+
+/// Parse a `datetime-local` value (local time, no offset).
+fn from_input(s: &str) -> Option<DateTime<Utc>> {
+    if s.is_empty() {
+        return None;
+    }
+    from_js(&js_sys::Date::new(&JsValue::from_str(s)))
+}
+
+fn state_badge(state: ScheduleState) -> &'static str {
+    match state {
+        ScheduleState::Scheduled => "badge badge-ghost badge-xs",
+        ScheduleState::Running => "badge badge-success badge-xs",
+        ScheduleState::Completed => "badge badge-neutral badge-xs",
+        ScheduleState::Failed => "badge badge-error badge-xs",
+    }
+}
+
+#[component]
+pub fn SchedulePage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let can_edit = auth.can(Capability::ManageSchedule);
+    let token = auth.token;
+
+    let (week, set_week) = signal(0i32);
+    let bounds = Memo::new(move |_| week_bounds(week.get()));
+    let (schedule, set_schedule) = signal(Vec::<ScheduledStream>::new());
+    let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
+    let (destinations, set_destinations) = signal(Vec::<DestinationSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (reload, set_reload) = signal(0u32);
+
+    // Booking form. `editing` is the ID being edited; `None` = new booking.
+    let (show_form, set_show_form) = signal(false);
+    let (editing, set_editing) = signal(Option::<String>::None);
+    let (f_sender, set_f_sender) = signal(String::new());
+    let (f_title, set_f_title) = signal(String::new());
+    let (f_dest, set_f_dest) = signal(String::new());
+    let (f_device, set_f_device) = signal(String::new());
+    let (f_res, set_f_res) = signal("1920x1080".to_string());
+    let (f_fps, set_f_fps) = signal(30u32);
+    let (f_start, set_f_start) = signal(String::new());
+    let (f_end, set_f_end) = signal(String::new());
+    let (saving, set_saving) = signal(false);
+
+    Effect::new(move || {
+        if let Some(token) = token.get() {
+            leptos::task::spawn_local(async move {
+                if let Ok(list) = api::list_senders(&token).await {
+                    set_senders.set(list);
+                }
+                if let Ok(list) = api::list_destinations(&token).await {
+                    set_destinations.set(list);
+                }
+            });
+        }
+    });
+
+    Effect::new(move || {
+        let Some(token) = token.get() else {
+            return;
+        };
+        reload.track();
+        let bounds = bounds.get();
+        let (Some(from), Some(to)) = (bounds.first().copied(), bounds.last().copied()) else {
+            return;
+        };
+        leptos::task::spawn_local(async move {
+            match api::list_schedules(&token, from, to).await {
+                Ok(list) => {
+                    set_schedule.set(list);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    });
+
+    let open_new = move |_| {
+        let start = Utc::now() + chrono::Duration::hours(1);
+        set_editing.set(None);
+        set_f_sender.set(
+            senders
+                .with_untracked(|s| s.first().map(|s| s.id.clone()))
+                .unwrap_or_default(),
+        );
+        set_f_title.set(String::new());
+        set_f_dest.set(String::new());
+        set_f_device.set(String::new());
+        set_f_res.set("1920x1080".into());
+        set_f_fps.set(30);
+        set_f_start.set(to_input(start));
+        set_f_end.set(to_input(start + chrono::Duration::hours(1)));
+        set_show_form.set(true);
+    };
+
+    let open_edit = move |b: ScheduledStream| {
+        set_editing.set(Some(b.id));
+        set_f_sender.set(b.sender_id);
+        set_f_title.set(b.title.unwrap_or_default());
+        set_f_dest.set(b.destination_id.unwrap_or_default());
+        set_f_device.set(b.device.unwrap_or_default());
+        set_f_res.set(b.resolution.unwrap_or_else(|| "1920x1080".into()));
+        set_f_fps.set(b.framerate.unwrap_or(30));
+        set_f_start.set(to_input(b.starts_at));
+        set_f_end.set(to_input(b.ends_at));
+        set_show_form.set(true);
+    };
+
+    // Bookings the form's current values would clash with.
+    let form_conflicts = Memo::new(move |_| {
+        let (Some(starts_at), Some(ends_at)) =
+            (from_input(&f_start.get()), from_input(&f_end.get()))
+        else {
+            return Vec::new();
+        };
+        let candidate = ScheduledStream {
+            id: editing.get().unwrap_or_default(),
+            sender_id: f_sender.get(),
+            sender_name: None,
+            title: None,
+            destination_id: None,
+            device: None,
+            resolution: None,
+            framerate: None,
+            starts_at,
+            ends_at,
+            state: ScheduleState::Scheduled,
+            stream_id: None,
+            error: None,
+            conflicts: Vec::new(),
+        };
+        schedule.with(|list| {
+            list.iter()
+                .filter(|b| candidate.overlaps(b))
+                .map(|b| {
+                    format!(
+                        "{} ({} – {})",
+                        b.title.clone().unwrap_or_else(|| "Untitled".into()),
+                        local_hm(b.starts_at),
+                        local_hm(b.ends_at)
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let on_save = move |_| {
+        let (Some(starts_at), Some(ends_at)) = (
+            from_input(&f_start.get_untracked()),
+            from_input(&f_end.get_untracked()),
+        ) else {
+            set_error.set(Some("Start and end times are required".into()));
+            return;
+        };
+        let non_empty = |s: String| (!s.trim().is_empty()).then(|| s.trim().to_string());
+        let booking = ScheduleStreamRequest {
+            sender_id: f_sender.get_untracked(),
+            title: non_empty(f_title.get_untracked()),
+            destination_id: non_empty(f_dest.get_untracked()),
+            device: non_empty(f_device.get_untracked()),
+            resolution: Some(f_res.get_untracked()),
+            framerate: Some(f_fps.get_untracked()),
+            starts_at,
+            ends_at,
+        };
+        let id = editing.get_untracked();
+        let token = token.get_untracked().unwrap_or_default();
+        set_saving.set(true);
+        leptos::task::spawn_local(async move {
+            match api::save_schedule(&token, id.as_deref(), &booking).await {
+                Ok(_) => {
+                    set_show_form.set(false);
+                    set_error.set(None);
+                    set_reload.update(|n| *n += 1);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    let on_delete = move |_| {
+        let Some(id) = editing.get_untracked() else {
+            return;
+        };
+        let token = token.get_untracked().unwrap_or_default();
+        set_saving.set(true);
+        leptos::task::spawn_local(async move {
+            match api::delete_schedule(&token, &id).await {
+                Ok(()) => {
+                    set_show_form.set(false);
+                    set_error.set(None);
+                    set_reload.update(|n| *n += 1);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-schedule")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("schedule-subtitle")}</p>
+                </div>
+                <button class="btn btn-primary" on:click=open_new disabled=!can_edit>
+                    "+ Schedule Stream"
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            <div class="flex items-center gap-2 mb-4">
+                <button class="btn btn-ghost btn-sm" on:click=move |_| set_week.update(|w| *w -= 1)>"← Prev"</button>
+                <button class="btn btn-ghost btn-sm" on:click=move |_| set_week.set(0)>"This week"</button>
+                <button class="btn btn-ghost btn-sm" on:click=move |_| set_week.update(|w| *w += 1)>"Next →"</button>
+                <span class="text-sm text-base-content/60 ml-2">
+                    {move || {
+                        let b = bounds.get();
+                        match (b.first(), b.get(6)) {
+                            (Some(first), Some(last)) => {
+                                format!("{} – {}", local_date(*first), local_date(*last))
+                            }
+                            _ => String::new(),
+                        }
+                    }}
+                </span>
+                {move || {
+                    let clashes = schedule.with(|s| s.iter().filter(|b| !b.conflicts.is_empty()).count());
+                    (clashes > 0).then(|| view! {
+                        <span class="badge badge-error badge-sm ml-auto">{format!("{clashes} double-booked")}</span>
+                    })
+                }}
+            </div>
+
+            <div class="grid grid-cols-7 gap-2">
+                {move || {
+                    let b = bounds.get();
+                    (0..7).filter_map(|i| {
+                        let (start, end) = (*b.get(i)?, *b.get(i + 1)?);
+                        let today = start <= Utc::now() && Utc::now() < end;
+                        let day = schedule.with(|s| {
+                            s.iter()
+                                .filter(|x| x.starts_at < end && x.ends_at > start)
+                                .cloned()
+                                .collect::<Vec<_>>()
+                        });
+                        let column = if today {
+                            "bg-base-200 rounded-lg p-2 min-h-48 ring-1 ring-primary"
+                        } else {
+                            "bg-base-200 rounded-lg p-2 min-h-48"
+                        };
+                        Some(view! {
+                            <div class=column>
+                                <div class="text-xs font-semibold text-base-content/60 mb-2">{day_label(i, start)}</div>
+                                <div class="flex flex-col gap-1.5">
+                                    {day.into_iter().map(|booking| {
+                                        let editable = can_edit && booking.state == ScheduleState::Scheduled;
+                                        let clash = !booking.conflicts.is_empty();
+                                        let class = if clash {
+                                            "card bg-base-100 border border-error p-2 text-xs cursor-pointer"
+                                        } else {
+                                            "card bg-base-100 border border-base-300 p-2 text-xs cursor-pointer"
+                                        };
+                                        let title = booking.title.clone().unwrap_or_else(|| "Untitled".into());
+                                        let sender = booking.sender_name.clone().unwrap_or_else(|| booking.sender_id.clone());
+                                        let profile = format!(
+                                            "{} · {}fps",
+                                            booking.resolution.as_deref().and_then(|r| RESOLUTIONS.iter().find(|(v, _)| *v == r).map(|(_, l)| *l)).unwrap_or("1080p"),
+                                            booking.framerate.unwrap_or(30)
+                                        );
+                                        let times = format!("{} – {}", local_hm(booking.starts_at), local_hm(booking.ends_at));
+                                        let tooltip = booking.error.clone().unwrap_or_default();
+                                        let state = booking.state;
+                                        view! {
+                                            <div class=class title=tooltip
+                                                on:click=move |_| if editable { open_edit(booking.clone()) }
+                                            >
+                                                <div class="font-mono">{times}</div>
+                                                <div class="font-semibold truncate">{title}</div>
+                                                <div class="text-base-content/60 truncate">{sender}</div>
+                                                <div class="text-base-content/60">{profile}</div>
+                                                <div class="flex gap-1 mt-1">
+                                                    <span class=state_badge(state)>{state.as_str()}</span>
+                                                    {clash.then(|| view! { <span class="badge badge-error badge-xs">"conflict"</span> })}
+                                                </div>
+                                            </div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            </div>
+                        })
+                    }).collect::<Vec<_>>()
+                }}
+            </div>
+
+            // Booking form
+            {move || show_form.get().then(|| view! {
+                <div class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">
+                            {move || if editing.get().is_some() { "Edit Booking" } else { "Schedule Stream" }}
+                        </h3>
+                        <fieldset class="fieldset mt-4">
+                            <label class="fieldset-label">"Title"</label>
+                            <input class="input input-bordered w-full" type="text" placeholder="e.g. Saturday match"
+                                prop:value=move || f_title.get()
+                                on:input=move |ev| set_f_title.set(event_target_value(&ev))
+                            />
+                            <label class="fieldset-label mt-2">"Sender"</label>
+                            <select class="select select-bordered w-full"
+                                on:change=move |ev| set_f_sender.set(event_target_value(&ev))
+                            >
+                                {move || senders.get().into_iter().map(|s| {
+                                    let id = s.id.clone();
+                                    view! {
+                                        <option value=s.id.clone() selected=move || f_sender.get() == id>
+                                            {s.name.clone().unwrap_or_else(|| s.id.clone())}
+                                        </option>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </select>
+                            <label class="fieldset-label mt-2">"Destination"</label>
+                            <select class="select select-bordered w-full"
+                                on:change=move |ev| set_f_dest.set(event_target_value(&ev))
+                            >
+                                <option value="" selected=move || f_dest.get().is_empty()>"None (receiver only)"</option>
+                                {move || destinations.get().into_iter().map(|d| {
+                                    let id = d.id.clone();
+                                    view! {
+                                        <option value=d.id.clone() selected=move || f_dest.get() == id>{d.name.clone()}</option>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </select>
+                            <label class="fieldset-label mt-2">"Capture device"</label>
+                            <input class="input input-bordered w-full font-mono" type="text" placeholder="/dev/video0 — empty for test pattern"
+                                prop:value=move || f_device.get()
+                                on:input=move |ev| set_f_device.set(event_target_value(&ev))
+                            />
+                            <div class="grid grid-cols-2 gap-2 mt-2">
+                                <div>
+                                    <label class="fieldset-label">"Resolution"</label>
+                                    <select class="select select-bordered w-full"
+                                        on:change=move |ev| set_f_res.set(event_target_value(&ev))
+                                    >
+                                        {RESOLUTIONS.iter().map(|(value, label)| view! {
+                                            <option value=*value selected=move || f_res.get() == *value>{*label}</option>
+                                        }).collect::<Vec<_>>()}
+                                    </select>
+                                </div>
+                                <div>
+                                    <label class="fieldset-label">"Framerate"</label>
+                                    <select class="select select-bordered w-full"
+                                        on:change=move |ev| set_f_fps.set(event_target_value(&ev).parse().unwrap_or(30))
+                                    >
+                                        {FRAMERATES.iter().map(|fps| view! {
+                                            <option value=fps.to_string() selected=move || f_fps.get() == *fps>{format!("{fps} fps")}</option>
+                                        }).collect::<Vec<_>>()}
+                                    </select>
+                                </div>
+                                <div>
+                                    <label class="fieldset-label">"Start"</label>
+                                    <input class="input input-bordered w-full" type="datetime-local"
+                                        prop:value=move || f_start.get()
+                                        on:input=move |ev| set_f_start.set(event_target_value(&ev))
+                                    />
+                                </div>
+                                <div>
+                                    <label class="fieldset-label">"End"</label>
+                                    <input class="input input-bordered w-full" type="datetime-local"
+                                        prop:value=move || f_end.get()
+                                        on:input=move |ev| set_f_end.set(event_target_value(&ev))
+                                    />
+                                </div>
+                            </div>
+                        </fieldset>
+                        {move || {
+                            let clashes = form_conflicts.get();
+                            (!clashes.is_empty()).then(|| view! {
+                                <div class="alert alert-warning text-sm mt-4">
+                                    <div>
+                                        <div class="font-semibold">"This sender is already booked:"</div>
+                                        <ul class="list-disc ml-4">
+                                            {clashes.into_iter().map(|c| view! { <li>{c}</li> }).collect::<Vec<_>>()}
+                                        </ul>
+                                        <div class="text-xs mt-1">"Whichever starts second will fail to start."</div>
+                                    </div>
+                                </div>
+                            })
+                        }}
+                        <div class="modal-action">
+                            {move || editing.get().is_some().then(|| view! {
+                                <button class="btn btn-error btn-outline mr-auto" on:click=on_delete disabled=move || saving.get()>
+                                    "Delete"
+                                </button>
+                            })}
+                            <button class="btn btn-ghost" on:click=move |_| set_show_form.set(false)>"Cancel"</button>
+                            <button class="btn btn-primary" on:click=on_save
+                                disabled=move || saving.get() || f_sender.get().is_empty()
+                            >
+                                {move || if saving.get() { "Saving…" } else { "Save" }}
+                            </button>
+                        </div>
+                    </div>
+                    <div class="modal-backdrop" on:click=move |_| set_show_form.set(false)></div>
+                </div>
+            })}
+        </div>
+    }
+}
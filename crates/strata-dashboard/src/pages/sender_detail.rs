@@ -16,16 +16,19 @@ mod helpers;
 mod tabs;
 
 use crate::AuthState;
+use crate::PrefsState;
 use crate::api;
-use crate::ws::WsClient;
-use strata_protocol::api::{SenderDetail, SenderFullStatus, StreamSummary};
+use crate::pages::format_duration;
+use crate::toast::use_toasts;
+use crate::ws::{TelemetryHistory, WsClient};
+use strata_protocol::api::{Capability, SenderDetail, SenderFullStatus, StreamSummary};
 use strata_protocol::models::{
-    LinkStats, MediaInput, NetworkInterface, StreamState, TransportReceiverMetrics,
-    TransportSenderMetrics,
+    MediaInput, NetworkInterface, StreamState, TransportReceiverMetrics, TransportSenderMetrics,
 };
-use strata_protocol::{DashboardEvent, TestRunResponsePayload};
+use strata_protocol::telemetry::{ElementStats, LinkSample};
+use strata_protocol::{DashboardEvent, DashboardTopic, SessionQuality, TestRunResponsePayload};
 
-use helpers::{apply_full_status, format_duration};
+use helpers::apply_full_status;
 use tabs::{DestinationModal, DiagnosticsTab, NetworkTab, SettingsTab, SourceTab, StreamTab};
 
 // ═══════════════════════════════════════════════════════════════════
@@ -36,6 +39,8 @@ use tabs::{DestinationModal, DiagnosticsTab, NetworkTab, SettingsTab, SourceTab,
 pub fn SenderDetailPage() -> impl IntoView {
     let auth = expect_context::<AuthState>();
     let ws = expect_context::<WsClient>();
+    let prefs = expect_context::<PrefsState>();
+    let toasts = use_toasts();
     let params = leptos_router::hooks::use_params_map();
 
     // ── Core signals (created ONCE, never destroyed) ─────────────
@@ -46,14 +51,22 @@ pub fn SenderDetailPage() -> impl IntoView {
 
     // Live stats from WebSocket
     let (live_bitrate, set_live_bitrate) = signal(0u32);
+    let (live_quality, set_live_quality) = signal(Option::<SessionQuality>::None);
     let (live_uptime, set_live_uptime) = signal(0u64);
-    let (live_links, set_live_links) = signal(Vec::<LinkStats>::new());
+    let (live_links, set_live_links) = signal(Vec::<LinkSample>::new());
+    // Per-element CPU, queue levels and drops inside the sender pipeline.
+    let (live_elements, set_live_elements) = signal(Vec::<ElementStats>::new());
     // Receiver-side per-link stats — the delivered-goodput ground truth (E8).
-    let (live_receiver_links, set_live_receiver_links) = signal(Vec::<LinkStats>::new());
+    let (live_receiver_links, set_live_receiver_links) = signal(Vec::<LinkSample>::new());
     // HLS egress health — segment heartbeat + watchdog restarts. Transport
     // can stay green while egress is wedged, so this gets its own signal.
     let (live_egress, set_live_egress) =
         signal(Option::<strata_protocol::models::EgressStats>::None);
+    let (live_av_sync, set_live_av_sync) =
+        signal(Option::<strata_protocol::models::AvSyncStats>::None);
+    // Audio levels and picture activity at the sender's encoder input.
+    let (live_media, set_live_media) =
+        signal(Option::<strata_protocol::models::MediaActivity>::None);
     let (live_sender_metrics, set_live_sender_metrics) =
         signal(Option::<TransportSenderMetrics>::None);
     let (live_receiver_metrics, set_live_receiver_metrics) =
@@ -63,9 +76,13 @@ pub fn SenderDetailPage() -> impl IntoView {
     let (stream_detail, set_stream_detail) =
         signal(Option::<strata_protocol::api::StreamDetail>::None);
 
-    // History for graph
-    let (stats_history, set_stats_history) =
-        signal(std::collections::VecDeque::<(f64, Vec<LinkStats>)>::new());
+    // History for graph, in the user's window (one sample per second).
+    let telemetry = expect_context::<TelemetryHistory>();
+    let stats_history = Signal::derive(move || {
+        let sender_id = params.get().get("id").unwrap_or_default();
+        let window = prefs.prefs.with(|p| p.graph_window_s) as usize;
+        telemetry.recent(&sender_id, window)
+    });
 
     // Staleness detection
     let (last_stats_ms, set_last_stats_ms) = signal(0.0f64);
@@ -117,12 +134,8 @@ pub fn SenderDetailPage() -> impl IntoView {
 
     // Receiver config
     let (receiver_input, set_receiver_input) = signal(String::new());
-    let (config_msg, set_config_msg) = signal(Option::<(String, &'static str)>::None);
     let (receiver_loaded, set_receiver_loaded) = signal(false);
 
-    // Interface scan
-    let (scan_msg, set_scan_msg) = signal(Option::<(String, &'static str)>::None);
-
     // Connectivity test
     let (test_result, set_test_result) = signal(Option::<TestRunResponsePayload>::None);
     let (test_loading, set_test_loading) = signal(false);
@@ -144,6 +157,8 @@ pub fn SenderDetailPage() -> impl IntoView {
 
     // Why the last stream ended (U2) — reason slug + optional detail.
     let (end_notice, set_end_notice) = signal(Option::<String>::None);
+    // Ingest senders: where the phone/encoder pushes the running stream to.
+    let (ingest_url, set_ingest_url) = signal(Option::<String>::None);
 
     // Receiver URL change confirm
     let (show_receiver_confirm, set_show_receiver_confirm) = signal(false);
@@ -192,9 +207,11 @@ pub fn SenderDetailPage() -> impl IntoView {
 
     // ── Data loading ─────────────────────────────────────────────
     let auth_load = auth.clone();
+    let resync = ws.resync;
     Effect::new(move || {
         let id = params.get().get("id").unwrap_or_default();
         let token = auth_load.token.get();
+        resync.track();
         if let Some(token) = token {
             if id.is_empty() {
                 return;
@@ -218,8 +235,26 @@ pub fn SenderDetailPage() -> impl IntoView {
                     }
                     set_streams.set(filtered);
                 }
-                if let Ok(status) = api::get_sender_status(&token, &id).await {
+                if let Ok(live) = api::get_sender_live(&token, &id).await {
+                    let status = live.status;
                     set_last_status_ms.set(js_sys::Date::now());
+                    // Draw the running stream now rather than on the next tick.
+                    if let Some(stats) = live.stats {
+                        set_live_bitrate.set(stats.encoder_bitrate_kbps);
+                        set_live_quality.set(stats.quality);
+                        set_live_uptime.set(stats.uptime_s);
+                        set_live_links.set(stats.links);
+                        set_live_elements.set(stats.elements);
+                        set_live_media.set(stats.media.map(|m| *m));
+                        set_live_sender_metrics.set(stats.sender_metrics);
+                        set_live_receiver_metrics.set(stats.receiver_metrics);
+                        set_last_stats_ms.set(stats.timestamp_ms as f64);
+                    }
+                    if let Some(receiver) = live.receiver_stats {
+                        set_live_receiver_links.set(receiver.links);
+                        set_live_egress.set(receiver.egress);
+                        set_live_av_sync.set(receiver.av_sync);
+                    }
                     apply_full_status(
                         &status,
                         &set_hw_interfaces,
@@ -244,6 +279,9 @@ pub fn SenderDetailPage() -> impl IntoView {
     Effect::new(move || {
         let stream_id = active_stream_id.get();
         let token = auth_stream_detail.token.get();
+        // Re-fetch on state changes too: the preview URL is only handed out
+        // once the stream is live.
+        stream_state.track();
         if let (Some(stream_id), Some(token)) = (stream_id, token) {
             leptos::task::spawn_local(async move {
                 if let Ok(detail) = api::get_stream(&token, &stream_id).await {
@@ -253,6 +291,36 @@ pub fn SenderDetailPage() -> impl IntoView {
         }
     });
 
+    // ── Telemetry subscriptions ──────────────────────────────────
+    // The dashboard WS only carries fleet events unless asked; this page
+    // needs the sender's and its active stream's per-second telemetry.
+    let subscribed = StoredValue::new(Vec::<DashboardTopic>::new());
+    let ws_sub = ws.clone();
+    Effect::new(move || {
+        let mut wanted = Vec::new();
+        let id = params.get().get("id").unwrap_or_default();
+        if !id.is_empty() {
+            wanted.push(DashboardTopic::Sender(id));
+        }
+        if let Some(stream_id) = active_stream_id.get() {
+            wanted.push(DashboardTopic::Stream(stream_id));
+        }
+        let previous = subscribed.get_value();
+        for topic in wanted.iter().filter(|t| !previous.contains(t)) {
+            ws_sub.subscribe(topic.clone());
+        }
+        for topic in previous.into_iter().filter(|t| !wanted.contains(t)) {
+            ws_sub.unsubscribe(topic);
+        }
+        subscribed.set_value(wanted);
+    });
+    let ws_cleanup = ws.clone();
+    on_cleanup(move || {
+        for topic in subscribed.try_get_value().unwrap_or_default() {
+            ws_cleanup.unsubscribe(topic);
+        }
+    });
+
     // ── WebSocket events ─────────────────────────────────────────
     Effect::new(move || {
         if let Some(event) = ws.last_event.get() {
@@ -261,23 +329,17 @@ pub fn SenderDetailPage() -> impl IntoView {
                 DashboardEvent::StreamStats(stats) => {
                     if stats.sender_id == sender_id {
                         set_live_bitrate.set(stats.encoder_bitrate_kbps);
+                        set_live_quality.set(stats.quality);
                         set_live_uptime.set(stats.uptime_s);
                         set_live_links.set(stats.links.clone());
+                        set_live_elements.set(stats.elements.clone());
+                        set_live_media.set(stats.media.as_deref().cloned());
                         set_live_sender_metrics.set(stats.sender_metrics.clone());
                         set_live_receiver_metrics.set(stats.receiver_metrics.clone());
 
-                        let now = js_sys::Date::now();
-                        set_last_stats_ms.set(now);
+                        set_last_stats_ms.set(js_sys::Date::now());
                         set_signal_lost.set(false);
 
-                        set_stats_history.update(|h| {
-                            h.push_back((now, stats.links));
-                            if h.len() > 60 {
-                                // Keep last 60 seconds
-                                h.pop_front();
-                            }
-                        });
-
                         let st = stream_state.get_untracked();
                         if st == "starting" {
                             set_stream_state.set("live".into());
@@ -289,6 +351,7 @@ pub fn SenderDetailPage() -> impl IntoView {
                     {
                         set_live_receiver_links.set(stats.links);
                         set_live_egress.set(stats.egress);
+                        set_live_av_sync.set(stats.av_sync);
                     }
                 }
                 DashboardEvent::StreamStateChanged {
@@ -319,7 +382,9 @@ pub fn SenderDetailPage() -> impl IntoView {
                                         None => format!("Stream {label}"),
                                     };
                                     set_end_notice.set(Some(notice));
+                                    set_ingest_url.set(None);
                                 }
+                            StreamState::Ended | StreamState::Failed => set_ingest_url.set(None),
                             _ => {}
                         }
                     }
@@ -360,6 +425,20 @@ pub fn SenderDetailPage() -> impl IntoView {
                         }
                     }
                 }
+                DashboardEvent::CommandResult {
+                    sender_id: sid,
+                    ack,
+                } => {
+                    // RPC failures already reach the caller through its REST
+                    // response; surface the fire-and-forget ones here.
+                    if sid == sender_id && !ack.success && ack.request_id.is_none() {
+                        let detail = ack.error.unwrap_or_else(|| {
+                            ack.code.map(|c| c.message()).unwrap_or_default().into()
+                        });
+                        toasts.error(format!("Sender rejected {}: {detail}", ack.msg_type));
+                    }
+                }
+                DashboardEvent::Alert(_) => {}
             }
         }
     });
@@ -414,6 +493,8 @@ pub fn SenderDetailPage() -> impl IntoView {
             codec: Some(codec),
             min_bitrate_kbps: None,
             max_bitrate_kbps: None,
+            ladder: Vec::new(),
+            overlay: None,
         });
         // Always send an explicit source — omitting it makes the server
         // default to a test pattern (U11).
@@ -427,6 +508,7 @@ pub fn SenderDetailPage() -> impl IntoView {
                 resolution: Some(resolution),
                 framerate: Some(framerate),
                 passthrough: None,
+                inputs: Vec::new(),
             }
         } else {
             strata_protocol::SourceConfig {
@@ -436,8 +518,13 @@ pub fn SenderDetailPage() -> impl IntoView {
                 resolution: Some(resolution),
                 framerate: Some(framerate),
                 passthrough: None,
+                inputs: Vec::new(),
             }
         });
+        // Show "starting" straight away; the response (or a later state
+        // event) confirms it, and a failure puts the previous state back.
+        let previous = stream_state.get_untracked();
+        set_stream_state.set("starting".into());
         set_action_loading.set(true);
         set_show_start_modal.set(false);
         set_end_notice.set(None);
@@ -445,13 +532,14 @@ pub fn SenderDetailPage() -> impl IntoView {
             match api::start_stream(&token, &id, dest_id, source, encoder).await {
                 Ok(resp) => {
                     set_stream_state.set(resp.state);
-                    set_action_loading.set(false);
+                    set_ingest_url.set(resp.ingest_url);
                 }
                 Err(e) => {
-                    set_error.set(Some(e));
-                    set_action_loading.set(false);
+                    set_stream_state.set(previous);
+                    toasts.error(format!("Couldn't start stream: {e}"));
                 }
             }
+            set_action_loading.set(false);
         });
     };
 
@@ -459,18 +547,15 @@ pub fn SenderDetailPage() -> impl IntoView {
     let stop_stream = move |_| {
         let id = params.get().get("id").unwrap_or_default();
         let token = auth_stop.token.get_untracked().unwrap_or_default();
+        let previous = stream_state.get_untracked();
+        set_stream_state.set("stopping".into());
         set_action_loading.set(true);
         leptos::task::spawn_local(async move {
-            match api::stop_stream(&token, &id).await {
-                Ok(()) => {
-                    set_stream_state.set("stopping".into());
-                    set_action_loading.set(false);
-                }
-                Err(e) => {
-                    set_error.set(Some(e));
-                    set_action_loading.set(false);
-                }
+            if let Err(e) = api::stop_stream(&token, &id).await {
+                set_stream_state.set(previous);
+                toasts.error(format!("Couldn't stop stream: {e}"));
             }
+            set_action_loading.set(false);
         });
     };
 
@@ -496,13 +581,10 @@ pub fn SenderDetailPage() -> impl IntoView {
                     set_hw_mem.set(None);
                     set_hw_uptime.set(None);
                     set_hw_receiver_url.set(None);
-                    set_action_loading.set(false);
-                }
-                Err(e) => {
-                    set_error.set(Some(e));
-                    set_action_loading.set(false);
                 }
+                Err(e) => toasts.error(format!("Unenroll failed: {e}")),
             }
+            set_action_loading.set(false);
         });
     };
 
@@ -513,18 +595,22 @@ pub fn SenderDetailPage() -> impl IntoView {
         let url = receiver_input.get_untracked();
         let url_val = if url.is_empty() { None } else { Some(url) };
         set_show_receiver_confirm.set(false);
+        let previous = hw_receiver_url.get_untracked();
+        set_hw_receiver_url.set(url_val.clone());
         leptos::task::spawn_local(async move {
             match api::set_sender_config(&token, &id, url_val).await {
                 Ok(resp) => {
-                    let msg = if resp.receiver_url.is_some() {
+                    toasts.success(if resp.receiver_url.is_some() {
                         "Configuration saved"
                     } else {
                         "Receiver URL cleared"
-                    };
-                    set_config_msg.set(Some((msg.into(), "ok")));
+                    });
                     set_hw_receiver_url.set(resp.receiver_url);
                 }
-                Err(e) => set_config_msg.set(Some((format!("Save failed: {e}"), "err"))),
+                Err(e) => {
+                    set_hw_receiver_url.set(previous);
+                    toasts.error(format!("Save failed: {e}"));
+                }
             }
         });
     };
@@ -548,7 +634,7 @@ pub fn SenderDetailPage() -> impl IntoView {
         leptos::task::spawn_local(async move {
             match api::run_sender_test(&token, &id).await {
                 Ok(r) => set_test_result.set(Some(r)),
-                Err(e) => set_error.set(Some(format!("Test failed: {e}"))),
+                Err(e) => toasts.error(format!("Test failed: {e}")),
             }
             set_test_loading.set(false);
         });
@@ -574,6 +660,12 @@ pub fn SenderDetailPage() -> impl IntoView {
                 </div>
             })}
 
+            {move || ingest_url.get().map(|url| view! {
+                <div class="alert alert-info text-sm mb-4">
+                    <span>"Push the contribution to "<code class="font-mono select-all">{url}</code></span>
+                </div>
+            })}
+
             // Modals (always mounted, shown/hidden by signal)
             <DestinationModal
                 show=show_start_modal
@@ -623,7 +715,7 @@ pub fn SenderDetailPage() -> impl IntoView {
             <div style:display=move || if sender_loaded.get() { "block" } else { "none" }>
 
                 // ── Page Header ──────────────────────────────────
-                <div class="flex justify-between items-center mb-2">
+                <div class="flex flex-wrap justify-between items-center gap-3 mb-2">
                     <div>
                         <h2 class="text-2xl font-semibold">{move || sender_name.get()}</h2>
                         <p class="text-sm text-base-content/60 mt-1">
@@ -660,7 +752,7 @@ pub fn SenderDetailPage() -> impl IntoView {
                             if is_live.get() {
                                 let auth = auth.clone();
                                 view! {
-                                    <button class="btn btn-error" on:click=stop_stream disabled=move || action_loading.get() || !auth.has_role("operator")>
+                                    <button class="btn btn-error" on:click=stop_stream disabled=move || action_loading.get() || !auth.can(Capability::ControlStreams)>
                                         "Stop Stream"
                                     </button>
                                 }.into_any()
@@ -668,7 +760,7 @@ pub fn SenderDetailPage() -> impl IntoView {
                                 let auth = auth.clone();
                                 view! {
                                     <button class="btn btn-error font-bold" on:click=open_start_modal
-                                        disabled=move || action_loading.get() || !is_online.get() || !auth.has_role("operator")>
+                                        disabled=move || action_loading.get() || !is_online.get() || !auth.can(Capability::ControlStreams)>
                                         "Go Live"
                                     </button>
                                 }.into_any()
@@ -683,7 +775,7 @@ pub fn SenderDetailPage() -> impl IntoView {
                     style:display=move || if is_live.get() { "block" } else { "none" }
                 >
                     <div class="flex items-center justify-between flex-wrap gap-2">
-                        <div class="flex items-center gap-4">
+                        <div class="flex flex-wrap items-center gap-x-4 gap-y-2">
                             <div class="flex items-center gap-2">
                                 {move || signal_lost.get().then(|| view! {
                                     <span class="badge badge-warning badge-sm animate-pulse">"Signal Lost"</span>
@@ -736,6 +828,26 @@ pub fn SenderDetailPage() -> impl IntoView {
                                 <span class="font-bold">{move || live_bitrate.get()}</span>
                                 <span class="text-base-content/50">" kbps"</span>
                             </div>
+                            {move || live_quality.get().map(|q| {
+                                let class = if q.mos >= 4.0 {
+                                    "font-bold text-success"
+                                } else if q.mos >= 3.0 {
+                                    "font-bold text-warning"
+                                } else {
+                                    "font-bold text-error"
+                                };
+                                let title = format!(
+                                    "Score {:.0}/100 · freeze risk {:.0}%",
+                                    q.score,
+                                    q.freeze_risk * 100.0
+                                );
+                                view! {
+                                    <div class="text-sm font-mono" title=title>
+                                        <span class="text-base-content/50">"MOS "</span>
+                                        <span class=class>{format!("{:.1}", q.mos)}</span>
+                                    </div>
+                                }
+                            })}
                             <div class="text-sm font-mono">
                                 <span class="text-base-content/50">"Uptime "</span>
                                 <span class="font-bold">{move || format_duration(live_uptime.get())}</span>
@@ -749,7 +861,8 @@ pub fn SenderDetailPage() -> impl IntoView {
                 </div>
 
                 // ── Tab bar ──────────────────────────────────────
-                <div role="tablist" class="tabs tabs-bordered mb-4">
+                // Scrolls sideways on phones rather than wrapping.
+                <div role="tablist" class="tabs tabs-bordered flex-nowrap overflow-x-auto whitespace-nowrap mb-4">
                     {["stream", "source", "network", "diagnostics", "settings"].into_iter().map(|tab| {
                         let label = match tab {
                             "stream" => "Stream",
@@ -780,12 +893,16 @@ pub fn SenderDetailPage() -> impl IntoView {
                         live_links=live_links
                         live_receiver_links=live_receiver_links
                         live_egress=live_egress
+                        live_av_sync=live_av_sync
+                        live_media=live_media
                         live_bitrate=live_bitrate
+                        live_elements=live_elements
                         stats_history=stats_history
                         sender_metrics=live_sender_metrics
                         receiver_metrics=live_receiver_metrics
                         sender_id=sender_id_memo
                         stream_detail=stream_detail
+                        interfaces=hw_interfaces
                     />
                 </div>
 
@@ -803,11 +920,10 @@ pub fn SenderDetailPage() -> impl IntoView {
                     <NetworkTab
                         sender_id=sender_id_memo
                         interfaces=hw_interfaces
+                        set_interfaces=set_hw_interfaces
                         is_online=is_online
                         iface_loading=iface_loading
                         set_iface_loading=set_iface_loading
-                        scan_msg=scan_msg
-                        set_scan_msg=set_scan_msg
                     />
                 </div>
 
@@ -825,7 +941,6 @@ pub fn SenderDetailPage() -> impl IntoView {
                         receiver_input=receiver_input
                         set_receiver_input=set_receiver_input
                         hw_receiver_url=hw_receiver_url
-                        config_msg=config_msg
                         save_config=save_config
                         test_loading=test_loading
                         test_result=test_result
@@ -3,19 +3,23 @@ use wasm_bindgen::JsCast;
 
 use crate::AuthState;
 use crate::api;
-use strata_protocol::api::SenderDetail;
+use crate::export::ExportButtons;
+use crate::pages::{format_bps, format_bytes};
+use crate::player::HlsPlayer;
+use crate::toast::use_toasts;
+use strata_protocol::api::{Capability, SenderDetail};
 use strata_protocol::models::{
-    InterfaceState, InterfaceType, LinkStats, MediaInput, MediaInputStatus, NetworkInterface,
+    InterfaceState, InterfaceType, MediaInput, MediaInputStatus, NetworkInterface,
 };
+use strata_protocol::telemetry::{ElementStats, LinkSample};
 use strata_protocol::{FileEntry, SourceSwitchPayload, TestRunResponsePayload};
 
 use super::cards::{
-    AlertingRulesCard, BandwidthGraph, ConfigManagementCard, JitterBufferCard, LiveLogViewerCard,
-    LiveSettingsCard, MultiDestRoutingCard, NetworkToolsCard, OtaUpdatesCard, PcapCaptureCard,
-    PowerControlsCard, TlsManagementCard, TransportTuningCard,
+    AlertingRulesCard, BandwidthGraph, ConfigManagementCard, JitterBufferCard, LinkDetailDrawer,
+    LinkTimelineCard, LiveLogViewerCard, LiveSettingsCard, MetricsHistoryCard,
+    MultiDestRoutingCard, NetworkToolsCard, OtaUpdatesCard, PcapCaptureCard, PortalAccessCard,
+    PowerControlsCard, ShareLinksCard, TlsManagementCard, TransportTuningCard,
 };
-use super::helpers::{format_bps, format_bytes};
-
 /// Human-readable platform label with protocol hint.
 fn platform_display_label(p: &str) -> &str {
     match p {
@@ -319,7 +323,7 @@ pub fn DestinationModal(
 
                     <div class="modal-action">
                         <button class="btn btn-ghost" on:click=move |_| set_show.set(false)>"Cancel"</button>
-                        <button class="btn btn-primary" on:click=on_confirm disabled=move || dests_loading.get() || !auth.has_role("operator")>"Go Live"</button>
+                        <button class="btn btn-primary" on:click=on_confirm disabled=move || dests_loading.get() || !auth.can(Capability::ControlStreams)>"Go Live"</button>
                     </div>
                 </div>
                 <div class="modal-backdrop" on:click=move |_| set_show.set(false)><button>"close"</button></div>
@@ -332,21 +336,173 @@ pub fn DestinationModal(
 // STREAM TAB
 // ═══════════════════════════════════════════════════════════════════
 
+/// Bottom of the VU scale; quieter reads as silent.
+const VU_FLOOR_DB: f64 = -60.0;
+/// An unchanged picture for this long is flagged frozen.
+const FROZEN_AFTER_S: f64 = 2.0;
+
+/// Position of a dBFS level on the VU scale, 0–100.
+fn vu_pct(db: f64) -> f64 {
+    ((db - VU_FLOOR_DB) / -VU_FLOOR_DB * 100.0).clamp(0.0, 100.0)
+}
+
 #[component]
 pub fn StreamTab(
     stream_state: ReadSignal<String>,
-    live_links: ReadSignal<Vec<LinkStats>>,
-    live_receiver_links: ReadSignal<Vec<LinkStats>>,
+    live_links: ReadSignal<Vec<LinkSample>>,
+    live_receiver_links: ReadSignal<Vec<LinkSample>>,
     live_egress: ReadSignal<Option<strata_protocol::models::EgressStats>>,
+    live_av_sync: ReadSignal<Option<strata_protocol::models::AvSyncStats>>,
+    live_media: ReadSignal<Option<strata_protocol::models::MediaActivity>>,
     live_bitrate: ReadSignal<u32>,
-    stats_history: ReadSignal<std::collections::VecDeque<(f64, Vec<LinkStats>)>>,
+    live_elements: ReadSignal<Vec<ElementStats>>,
+    stats_history: Signal<std::collections::VecDeque<(f64, Vec<LinkSample>)>>,
     sender_metrics: ReadSignal<Option<strata_protocol::models::TransportSenderMetrics>>,
     receiver_metrics: ReadSignal<Option<strata_protocol::models::TransportReceiverMetrics>>,
     sender_id: Memo<String>,
     stream_detail: ReadSignal<Option<strata_protocol::api::StreamDetail>>,
+    interfaces: ReadSignal<Vec<NetworkInterface>>,
 ) -> impl IntoView {
+    // Links whose detail drawer is open, kept across telemetry updates.
+    let (expanded, set_expanded) = signal(std::collections::HashSet::<u32>::new());
+
+    let preview_url = Memo::new(move |_| {
+        if stream_state.get() != "live" {
+            return None;
+        }
+        stream_detail.get().and_then(|d| d.preview_url)
+    });
+
+    // The live graph's samples, one row per link per second.
+    let live_rows = Signal::derive(move || {
+        stats_history.with(|h| {
+            h.iter()
+                .flat_map(|(ts_ms, links)| {
+                    let ts = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(*ts_ms))
+                        .to_iso_string()
+                        .as_string()
+                        .unwrap_or_default();
+                    links.iter().filter_map(move |link| {
+                        let mut row = serde_json::to_value(link).ok()?;
+                        row["timestamp"] = ts.clone().into();
+                        Some(row)
+                    })
+                })
+                .collect()
+        })
+    });
+    let live_export_name = Signal::derive(move || format!("{}-links-live", sender_id.get()));
+
     view! {
         <div>
+            // Live Preview
+            <div class="card bg-base-200 border border-base-300 mb-4">
+                <div class="card-body">
+                    <h3 class="card-title text-base">"Live Preview"</h3>
+                    {move || {
+                        let st = stream_state.get();
+                        if st != "live" && st != "starting" {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Start a stream to see the picture"</p>
+                            }.into_any();
+                        }
+                        if preview_url.get().is_none() {
+                            return view! {
+                                <p class="text-sm text-base-content/40">
+                                    {if st == "starting" {
+                                        "Waiting for the stream to go live…"
+                                    } else {
+                                        "No preview for this stream — previews need an HLS destination on a receiver with --preview-base-url set"
+                                    }}
+                                </p>
+                            }.into_any();
+                        }
+                        view! {
+                            <HlsPlayer url=preview_url class="aspect-video mt-2" />
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+
+            // Input monitor: what the encoder is being fed, so a muted mic
+            // or a hung camera shows up before viewers complain.
+            <div class="card bg-base-200 border border-base-300 mb-4">
+                <div class="card-body">
+                    <h3 class="card-title text-base">"Input Monitor"</h3>
+                    {move || {
+                        let st = stream_state.get();
+                        if st != "live" && st != "starting" {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Start a stream to see input levels"</p>
+                            }.into_any();
+                        }
+                        let Some(media) = live_media.get() else {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Waiting for level readings from the sender…"</p>
+                            }.into_any();
+                        };
+                        let channels = media.audio_rms_db.len();
+                        let picture = media.luma.map(|luma| {
+                            let frozen_s = media.frozen_ms as f64 / 1000.0;
+                            let (badge_class, badge_text) = if frozen_s >= FROZEN_AFTER_S {
+                                ("badge badge-error badge-sm", format!("Frozen {frozen_s:.0}s"))
+                            } else if luma < 0.07 && media.luma_variance.unwrap_or(0.0) < 0.001 {
+                                ("badge badge-warning badge-sm", "Black".to_string())
+                            } else {
+                                ("badge badge-success badge-sm", "Moving".to_string())
+                            };
+                            view! {
+                                <div class="flex items-center gap-4 text-sm">
+                                    <span class="w-12 text-xs text-base-content/60">"Video"</span>
+                                    <span class=badge_class>{badge_text}</span>
+                                    <span class="font-mono text-xs">{format!("luma {:.0}%", luma * 100.0)}</span>
+                                </div>
+                            }
+                        });
+                        view! {
+                            <div class="flex flex-col gap-2 mt-2">
+                                {picture}
+                                {(0..channels).map(|ch| {
+                                    let rms = media.audio_rms_db[ch];
+                                    let peak = media.audio_peak_db.get(ch).copied().unwrap_or(rms);
+                                    let fill_class = if peak >= -3.0 {
+                                        "absolute inset-y-0 left-0 bg-error"
+                                    } else if peak >= -12.0 {
+                                        "absolute inset-y-0 left-0 bg-warning"
+                                    } else {
+                                        "absolute inset-y-0 left-0 bg-success"
+                                    };
+                                    let label = match (channels, ch) {
+                                        (2, 0) => "L".to_string(),
+                                        (2, 1) => "R".to_string(),
+                                        _ => format!("Ch {}", ch + 1),
+                                    };
+                                    view! {
+                                        <div class="flex items-center gap-4 text-sm">
+                                            <span class="w-12 text-xs text-base-content/60">{label}</span>
+                                            <div class="relative h-3 flex-1 rounded bg-base-300 overflow-hidden">
+                                                <div class=fill_class style=format!("width: {:.1}%", vu_pct(rms))></div>
+                                                <div
+                                                    class="absolute inset-y-0 w-0.5 bg-base-content"
+                                                    style=format!("left: {:.1}%", vu_pct(peak))
+                                                ></div>
+                                            </div>
+                                            <span class="w-20 text-right font-mono text-xs">
+                                                {if rms <= VU_FLOOR_DB {
+                                                    "silent".to_string()
+                                                } else {
+                                                    format!("{rms:.0} dBFS")
+                                                }}
+                                            </span>
+                                        </div>
+                                    }
+                                }).collect::<Vec<_>>()}
+                            </div>
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+
             // Glass-to-Glass Health
             <div class="card bg-base-200 border border-base-300 mb-4">
                 <div class="card-body">
@@ -408,6 +564,69 @@ pub fn StreamTab(
                 </div>
             </div>
 
+            // Pipeline elements
+            <div class="card bg-base-200 border border-base-300 mb-4">
+                <div class="card-body">
+                    <h3 class="card-title text-base">"Pipeline"</h3>
+                    {move || {
+                        let st = stream_state.get();
+                        if st != "live" && st != "starting" {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Start a stream to see pipeline load"</p>
+                            }.into_any();
+                        }
+
+                        let elements = live_elements.get();
+                        if elements.is_empty() {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Waiting for telemetry…"</p>
+                            }.into_any();
+                        }
+
+                        view! {
+                            <table class="table table-sm mt-2">
+                                <thead>
+                                    <tr>
+                                        <th>"Element"</th>
+                                        <th class="text-right">"CPU"</th>
+                                        <th class="text-right">"Queue"</th>
+                                        <th class="text-right">"Dropped"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {elements.into_iter().map(|e| {
+                                        // A thread pinned near a full core or a queue
+                                        // close to its limit is where frames start to drop.
+                                        let cpu_hot = e.cpu_percent.is_some_and(|c| c >= 90.0);
+                                        let queue_hot = e.queue_fill().is_some_and(|f| f >= 0.8);
+                                        let cpu = e.cpu_percent
+                                            .map(|c| format!("{c:.0}%"))
+                                            .unwrap_or_else(|| "—".into());
+                                        let queue = match (e.queue_ms, e.queue_max_ms) {
+                                            (Some(ms), Some(max)) if max > 0 => format!("{ms} / {max} ms"),
+                                            (Some(ms), _) => format!("{ms} ms"),
+                                            _ => "—".into(),
+                                        };
+                                        let dropped = e.dropped_buffers
+                                            .map(|d| d.to_string())
+                                            .unwrap_or_else(|| "—".into());
+                                        let dropped_hot = e.dropped_buffers.is_some_and(|d| d > 0);
+                                        view! {
+                                            <tr>
+                                                <td class="font-mono">{e.name}</td>
+                                                <td class="text-right font-mono" class:text-error=cpu_hot>{cpu}</td>
+                                                <td class="text-right font-mono" class:text-warning=queue_hot>{queue}</td>
+                                                <td class="text-right font-mono" class:text-error=dropped_hot>{dropped}</td>
+                                            </tr>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+
             // Stream Metadata
             <div class="card bg-base-200 border border-base-300 mb-4">
                 <div class="card-body">
@@ -466,7 +685,17 @@ pub fn StreamTab(
             // Link performance cards
             <div class="card bg-base-200 border border-base-300 mb-4">
                 <div class="card-body">
-                    <h3 class="card-title text-base">"Link Performance"</h3>
+                    <div class="flex justify-between items-center">
+                        <h3 class="card-title text-base">"Link Performance"</h3>
+                        <ExportButtons
+                            rows=live_rows
+                            columns=&[
+                                "timestamp", "id", "interface", "link_kind", "state", "observed_bps",
+                                "capacity_bps", "rtt_ms", "loss_rate", "sent_bytes",
+                            ]
+                            filename=live_export_name
+                        />
+                    </div>
                     {move || {
                         let st = stream_state.get();
                         if st != "live" && st != "starting" {
@@ -503,6 +732,16 @@ pub fn StreamTab(
                                         } else {
                                             link.interface.clone()
                                         };
+                                        let link_id = link.id;
+                                        let is_open = move || expanded.with(|e| e.contains(&link_id));
+                                        let toggle = move |_| {
+                                            set_expanded.update(|e| {
+                                                if !e.remove(&link_id) {
+                                                    e.insert(link_id);
+                                                }
+                                            })
+                                        };
+                                        let drawer_link = link.clone();
 
                                         view! {
                                             <div class=if is_down {
@@ -510,8 +749,13 @@ pub fn StreamTab(
                                             } else {
                                                 "bg-base-300 rounded-lg p-3"
                                             }>
-                                                <div class="flex justify-between items-center mb-2">
+                                                <div
+                                                    class="flex justify-between items-center mb-2 cursor-pointer select-none"
+                                                    title="Show link details"
+                                                    on:click=toggle
+                                                >
                                                     <div class="flex items-center gap-2">
+                                                        <span class="text-base-content/40 text-xs w-3">{move || if is_open() { "▾" } else { "▸" }}</span>
                                                         <span class="font-semibold font-mono text-sm">{iface_name}</span>
                                                         {link.link_kind.as_ref().map(|k| view! {
                                                             <span class="badge badge-ghost badge-xs">{k.clone()}</span>
@@ -530,11 +774,11 @@ pub fn StreamTab(
                                                     </div>
                                                     <div>
                                                         <div class="text-base-content/40 uppercase">"Throughput"</div>
-                                                        <div class="font-mono font-semibold">{format_bps(link.observed_bps)}</div>
+                                                        <div class="font-mono font-semibold">{format_bps(link.observed_bps.0)}</div>
                                                     </div>
                                                     <div>
                                                         <div class="text-base-content/40 uppercase">"Capacity"</div>
-                                                        <div class="font-mono font-semibold">{format_bps(link.capacity_bps)}</div>
+                                                        <div class="font-mono font-semibold">{format_bps(link.capacity_bps.0)}</div>
                                                     </div>
                                                     <div>
                                                         <div class="text-base-content/40 uppercase">"Sent"</div>
@@ -544,7 +788,7 @@ pub fn StreamTab(
                                                 <div class="grid grid-cols-2 gap-2 text-xs mt-2 pt-2 border-t border-base-content/10">
                                                     <div>
                                                         <div class="text-base-content/40 uppercase">"BBRv3 BtlBw"</div>
-                                                        <div class="font-mono font-semibold">{link.btlbw_bps.map(format_bps).unwrap_or_else(|| "—".into())}</div>
+                                                        <div class="font-mono font-semibold">{link.btlbw_bps.map(|b| format_bps(b.0)).unwrap_or_else(|| "—".into())}</div>
                                                     </div>
                                                     <div>
                                                         <div class="text-base-content/40 uppercase">"BBRv3 RTprop"</div>
@@ -552,7 +796,7 @@ pub fn StreamTab(
                                                     </div>
                                                 </div>
                                                 {(link.link_kind.as_deref() == Some("cellular")).then(|| view! {
-                                                    <div class="grid grid-cols-4 gap-2 text-xs mt-2 pt-2 border-t border-base-content/10">
+                                                    <div class="grid grid-cols-2 sm:grid-cols-4 gap-2 text-xs mt-2 pt-2 border-t border-base-content/10">
                                                         <div>
                                                             <div class="text-base-content/40 uppercase">"RSRP"</div>
                                                             <div class="font-mono font-semibold">{link.rsrp.map(|v| format!("{v:.1} dBm")).unwrap_or_else(|| "—".into())}</div>
@@ -571,6 +815,14 @@ pub fn StreamTab(
                                                         </div>
                                                     </div>
                                                 })}
+                                                {move || is_open().then(|| {
+                                                    let interface = interfaces.with(|all| {
+                                                        all.iter().find(|i| i.name == drawer_link.interface).cloned()
+                                                    });
+                                                    view! {
+                                                        <LinkDetailDrawer link=drawer_link.clone() interface=interface history=stats_history />
+                                                    }
+                                                })}
                                             </div>
                                         }
                                     }
@@ -581,6 +833,14 @@ pub fn StreamTab(
                 </div>
             </div>
 
+            // Recorded telemetry beyond the 60-second live graph.
+            <MetricsHistoryCard sender_id=sender_id />
+
+            // Per-link phases and events over the session, for explaining
+            // a glitch after the fact.
+            <LinkTimelineCard stream_detail=stream_detail live_links=live_links />
+            <ShareLinksCard stream_detail=stream_detail />
+
             // HLS egress health — the one signal transport stats can't fake:
             // segment production. Stalled egress with green links is exactly
             // the run-4 wedge the field script had to detect by log-grepping.
@@ -628,6 +888,50 @@ pub fn StreamTab(
                 </div>
             </div>
 
+            // Lipsync clocks of the delivered TS. Drift points at the
+            // sender, PCR jitter at transport; both clean points downstream.
+            <div class="card bg-base-200 border border-base-300 mb-4">
+                <div class="card-body">
+                    <h3 class="card-title text-base">"A/V Sync"</h3>
+                    {move || {
+                        let st = stream_state.get();
+                        if st != "live" && st != "starting" {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Start a stream to see A/V sync"</p>
+                            }.into_any();
+                        }
+                        let Some(av) = live_av_sync.get() else {
+                            return view! {
+                                <p class="text-sm text-base-content/40">"Waiting for TS timestamps from the receiver…"</p>
+                            }.into_any();
+                        };
+                        // Lipsync becomes noticeable around 45 ms of audio
+                        // lead / 125 ms of lag (ITU-R BT.1359); warn well
+                        // before either.
+                        let drift_class = if av.av_drift_ms.abs() >= 40.0 {
+                            "font-mono text-xs text-error"
+                        } else if av.av_drift_ms.abs() >= 20.0 {
+                            "font-mono text-xs text-warning"
+                        } else {
+                            "font-mono text-xs"
+                        };
+                        let jitter_class = if av.pcr_jitter_ms >= 10.0 {
+                            "font-mono text-xs text-warning"
+                        } else {
+                            "font-mono text-xs"
+                        };
+                        view! {
+                            <div class="flex items-center gap-4 mt-2 text-sm">
+                                <span class="font-mono text-xs">{format!("skew {:.0} ms", av.av_skew_ms)}</span>
+                                <span class=drift_class>{format!("drift {:+.1} ms", av.av_drift_ms)}</span>
+                                <span class=jitter_class>{format!("PCR jitter {:.1} ms", av.pcr_jitter_ms)}</span>
+                                <span class="font-mono text-xs">{format!("PTS lead {:.0} ms", av.video_pts_lead_ms)}</span>
+                            </div>
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+
             // Receiver-side link stats — what actually arrived. Divergence
             // from the sender-side view above is the diagnostic (E8).
             <div class="card bg-base-200 border border-base-300 mb-4">
@@ -672,7 +976,7 @@ pub fn StreamTab(
                                                     <td class="text-xs">{l.state.clone()}</td>
                                                     <td class="font-mono text-xs">{format!("{:.1} ms", l.rtt_ms)}</td>
                                                     <td class="font-mono text-xs">{format!("{:.2}%", l.loss_rate * 100.0)}</td>
-                                                    <td class="font-mono text-xs">{format_bps(l.observed_bps)}</td>
+                                                    <td class="font-mono text-xs">{format_bps(l.observed_bps.0)}</td>
                                                     <td class="font-mono text-xs">{format_bytes(l.sent_bytes)}</td>
                                                 </tr>
                                             }
@@ -711,6 +1015,7 @@ pub fn SourceTab(
     hw_inputs: ReadSignal<Vec<MediaInput>>,
 ) -> impl IntoView {
     let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
 
     // Source type: "test", "device", "uri"
     let (source_type, set_source_type) = signal(String::from("test"));
@@ -718,7 +1023,6 @@ pub fn SourceTab(
     let (selected_device, set_selected_device) = signal(String::new());
     let (source_uri, set_source_uri) = signal(String::new());
     let (switching, set_switching) = signal(false);
-    let (switch_msg, set_switch_msg) = signal(Option::<(String, &'static str)>::None);
 
     // File browser modal
     let (show_file_browser, set_show_file_browser) = signal(false);
@@ -756,13 +1060,12 @@ pub fn SourceTab(
         let id = sender_id.get_untracked();
         let stype = source_type.get_untracked();
         set_switching.set(true);
-        set_switch_msg.set(None);
 
         let req = match stype.as_str() {
             "device" => {
                 let dev = selected_device.get_untracked();
                 if dev.is_empty() {
-                    set_switch_msg.set(Some(("Select a device first".into(), "err")));
+                    toasts.error("Select a device first");
                     set_switching.set(false);
                     return;
                 }
@@ -777,7 +1080,7 @@ pub fn SourceTab(
             "uri" => {
                 let uri = source_uri.get_untracked();
                 if uri.is_empty() {
-                    set_switch_msg.set(Some(("Enter a URI first".into(), "err")));
+                    toasts.error("Enter a URI first");
                     set_switching.set(false);
                     return;
                 }
@@ -800,10 +1103,8 @@ pub fn SourceTab(
 
         leptos::task::spawn_local(async move {
             match api::switch_source(&token, &id, &req).await {
-                Ok(()) => {
-                    set_switch_msg.set(Some(("Source switch confirmed by device".into(), "ok")))
-                }
-                Err(e) => set_switch_msg.set(Some((format!("Failed: {e}"), "err"))),
+                Ok(()) => toasts.success("Source switch confirmed by device"),
+                Err(e) => toasts.error(format!("Source switch failed: {e}")),
             }
             set_switching.set(false);
         });
@@ -819,14 +1120,6 @@ pub fn SourceTab(
                 "Source switching is available while a stream is running. Start a stream first."
             </div>
 
-            {move || switch_msg.get().map(|(msg, kind)| {
-                let cls = match kind {
-                    "ok" => "alert alert-success text-sm mb-4",
-                    _ => "alert alert-error text-sm mb-4",
-                };
-                view! { <div class={cls}>{msg}</div> }
-            })}
-
             // Source type cards — radio selection
             <div class="grid gap-3 mb-4">
 
@@ -1024,7 +1317,7 @@ pub fn SourceTab(
                 <button
                     class="btn btn-primary"
                     on:click=do_switch
-                    disabled=move || switching.get() || !is_live.get() || !auth.has_role("operator")
+                    disabled=move || switching.get() || !is_live.get() || !auth.can(Capability::ControlStreams)
                 >
                     {move || if switching.get() { "Switching…" } else { "Switch Source" }}
                 </button>
@@ -1179,42 +1472,40 @@ pub fn FileBrowserModal(
 pub fn NetworkTab(
     sender_id: Memo<String>,
     interfaces: ReadSignal<Vec<NetworkInterface>>,
+    set_interfaces: WriteSignal<Vec<NetworkInterface>>,
     is_online: Memo<bool>,
     iface_loading: ReadSignal<Option<String>>,
     set_iface_loading: WriteSignal<Option<String>>,
-    scan_msg: ReadSignal<Option<(String, &'static str)>>,
-    set_scan_msg: WriteSignal<Option<(String, &'static str)>>,
 ) -> impl IntoView {
     let auth = expect_context::<AuthState>();
-    // Per-tab error surface — interface command failures used to vanish
-    // into the global page banner (U15).
-    let (iface_msg, set_iface_msg) = signal(Option::<String>::None);
+    let toasts = use_toasts();
+
+    // Toggles apply locally before the command round-trips; a failure
+    // puts the interface back. The next heartbeat is authoritative either way.
+    let patch_iface = move |name: &str, f: &dyn Fn(&mut NetworkInterface)| {
+        set_interfaces.update(|ifaces| {
+            if let Some(iface) = ifaces.iter_mut().find(|i| i.name == name) {
+                f(iface);
+            }
+        });
+    };
 
     let auth_scan = auth.clone();
     let do_scan = move |_| {
         let token = auth_scan.token.get_untracked().unwrap_or_default();
         let id = sender_id.get_untracked();
-        set_scan_msg.set(Some(("Scanning…".into(), "info")));
+        toasts.info("Scanning…");
         leptos::task::spawn_local(async move {
             match api::scan_sender_interfaces(&token, &id).await {
-                Ok(r) => {
-                    if r.discovered.is_empty() {
-                        set_scan_msg.set(Some((
-                            format!("No new interfaces found. {} total.", r.total),
-                            "info",
-                        )));
-                    } else {
-                        set_scan_msg.set(Some((
-                            format!(
-                                "Found {} new: {}",
-                                r.discovered.len(),
-                                r.discovered.join(", ")
-                            ),
-                            "ok",
-                        )));
-                    }
+                Ok(r) if r.discovered.is_empty() => {
+                    toasts.info(format!("No new interfaces found. {} total.", r.total));
                 }
-                Err(e) => set_scan_msg.set(Some((format!("Scan failed: {e}"), "err"))),
+                Ok(r) => toasts.success(format!(
+                    "Found {} new: {}",
+                    r.discovered.len(),
+                    r.discovered.join(", ")
+                )),
+                Err(e) => toasts.error(format!("Scan failed: {e}")),
             }
         });
     };
@@ -1231,29 +1522,13 @@ pub fn NetworkTab(
                 <button class="btn btn-ghost btn-sm" on:click=do_scan
                     disabled={
                         let auth = auth.clone();
-                        move || !is_online.get() || !auth.has_role("admin")
+                        move || !is_online.get() || !auth.can(Capability::ManageSenders)
                     }
                 >
                     "Scan for New"
                 </button>
             </div>
 
-            {move || scan_msg.get().map(|(msg, kind)| {
-                let cls = match kind {
-                    "ok" => "alert alert-success text-sm mb-3",
-                    "err" => "alert alert-error text-sm mb-3",
-                    _ => "alert alert-info text-sm mb-3",
-                };
-                view! { <div class={cls}>{msg}</div> }
-            })}
-
-            {move || iface_msg.get().map(|msg| view! {
-                <div class="alert alert-error text-sm mb-3">
-                    <span>{msg}</span>
-                    <button class="btn btn-ghost btn-xs" on:click=move |_| set_iface_msg.set(None)>"✕"</button>
-                </div>
-            })}
-
             <p class="text-xs text-base-content/50 mb-3">
                 "The enable toggle excludes an interface from the bonded stream (live and future starts). It does not change the OS interface."
             </p>
@@ -1312,6 +1587,7 @@ pub fn NetworkTab(
                                 let iface_name = name_toggle.clone();
                                 let token = auth.token.get_untracked().unwrap_or_default();
                                 set_iface_loading.set(Some(iface_name.clone()));
+                                patch_iface(&iface_name, &|i| i.enabled = !enabled);
                                 leptos::task::spawn_local(async move {
                                     let result = if enabled {
                                         api::disable_interface(&token, &sid, &iface_name).await
@@ -1319,7 +1595,8 @@ pub fn NetworkTab(
                                         api::enable_interface(&token, &sid, &iface_name).await
                                     };
                                     if let Err(e) = result {
-                                        set_iface_msg.set(Some(e));
+                                        patch_iface(&iface_name, &|i| i.enabled = enabled);
+                                        toasts.error(format!("{iface_name}: {e}"));
                                     }
                                     set_iface_loading.set(None);
                                 });
@@ -1339,7 +1616,7 @@ pub fn NetworkTab(
                                 set_iface_loading.set(Some(iface_name.clone()));
                                 leptos::task::spawn_local(async move {
                                     if let Err(e) = api::lock_band(&token, &sid, &iface_name, band).await {
-                                        set_iface_msg.set(Some(e));
+                                        toasts.error(format!("{iface_name}: {e}"));
                                     }
                                     set_iface_loading.set(None);
                                 });
@@ -1356,7 +1633,7 @@ pub fn NetworkTab(
                                     set_iface_loading.set(Some(iface_name.clone()));
                                     leptos::task::spawn_local(async move {
                                         if let Err(e) = api::set_priority(&token, &sid, &iface_name, prio).await {
-                                            set_iface_msg.set(Some(e));
+                                            toasts.error(format!("{iface_name}: {e}"));
                                         }
                                         set_iface_loading.set(None);
                                     });
@@ -1376,7 +1653,7 @@ pub fn NetworkTab(
                                 set_iface_loading.set(Some(iface_name.clone()));
                                 leptos::task::spawn_local(async move {
                                     if let Err(e) = api::set_apn(&token, &sid, &iface_name, apn, None, Some(current_roaming)).await {
-                                        set_iface_msg.set(Some(e));
+                                        toasts.error(format!("{iface_name}: {e}"));
                                     }
                                     set_iface_loading.set(None);
                                 });
@@ -1392,10 +1669,12 @@ pub fn NetworkTab(
                                 let target = ev.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
                                 let roaming = target.checked();
                                 set_iface_loading.set(Some(iface_name.clone()));
+                                patch_iface(&iface_name, &|i| i.roaming = roaming);
                                 let apn = current_apn_roaming.clone();
                                 leptos::task::spawn_local(async move {
                                     if let Err(e) = api::set_apn(&token, &sid, &iface_name, apn, None, Some(roaming)).await {
-                                        set_iface_msg.set(Some(e));
+                                        patch_iface(&iface_name, &|i| i.roaming = !roaming);
+                                        toasts.error(format!("{iface_name}: {e}"));
                                     }
                                     set_iface_loading.set(None);
                                 });
@@ -1425,7 +1704,7 @@ pub fn NetworkTab(
                                                 on:change=toggle
                                                 disabled={
                                                     let auth = auth.clone();
-                                                    move || is_loading2() || !is_online.get() || !auth.has_role("admin")
+                                                    move || is_loading2() || !is_online.get() || !auth.can(Capability::ManageSenders)
                                                 }
                                             />
                                             <div>
@@ -1458,7 +1737,7 @@ pub fn NetworkTab(
                                                         <select
                                                             class="select select-bordered select-xs w-32"
                                                             on:change=lock_band
-                                                            disabled=move || !is_online.get() || !auth1.has_role("admin")
+                                                            disabled=move || !is_online.get() || !auth1.can(Capability::ManageSenders)
                                                         >
                                                             <option value="auto" selected=current_band.is_none()>"Auto"</option>
                                                             {bands.into_iter().map(|b| {
@@ -1474,7 +1753,7 @@ pub fn NetworkTab(
                                                         <select
                                                             class="select select-bordered select-xs w-32"
                                                             on:change=set_priority
-                                                            disabled=move || !is_online.get() || !auth2.has_role("admin")
+                                                            disabled=move || !is_online.get() || !auth2.can(Capability::ManageSenders)
                                                         >
                                                             <option value="1" selected=current_priority == 1>"Primary (1)"</option>
                                                             <option value="2" selected=current_priority == 2>"Secondary (2)"</option>
@@ -1492,7 +1771,7 @@ pub fn NetworkTab(
                                                             placeholder="auto"
                                                             prop:value=current_apn.clone().unwrap_or_default()
                                                             on:change=set_apn
-                                                            disabled=move || !is_online.get() || !auth3.has_role("admin")
+                                                            disabled=move || !is_online.get() || !auth3.can(Capability::ManageSenders)
                                                         />
                                                     </div>
                                                     <div class="flex items-center gap-2">
@@ -1502,7 +1781,7 @@ pub fn NetworkTab(
                                                             class="toggle toggle-xs"
                                                             checked=current_roaming
                                                             on:change=toggle_roaming
-                                                            disabled=move || !is_online.get() || !auth4.has_role("admin")
+                                                            disabled=move || !is_online.get() || !auth4.can(Capability::ManageSenders)
                                                         />
                                                     </div>
                                                 </div>
@@ -1568,7 +1847,6 @@ pub fn SettingsTab(
     receiver_input: ReadSignal<String>,
     set_receiver_input: WriteSignal<String>,
     hw_receiver_url: ReadSignal<Option<String>>,
-    config_msg: ReadSignal<Option<(String, &'static str)>>,
     save_config: impl Fn(web_sys::MouseEvent) + 'static + Copy + Send,
     test_loading: ReadSignal<bool>,
     test_result: ReadSignal<Option<TestRunResponsePayload>>,
@@ -1589,15 +1867,6 @@ pub fn SettingsTab(
                 <div class="card-body">
                     <h3 class="card-title text-base">"Receiver Configuration"</h3>
 
-                    {move || config_msg.get().map(|(msg, kind)| {
-                        let cls = match kind {
-                            "ok" => "alert alert-success text-sm",
-                            "err" => "alert alert-error text-sm",
-                            _ => "alert alert-info text-sm",
-                        };
-                        view! { <div class={cls}>{msg}</div> }
-                    })}
-
                     <p class="text-sm text-base-content/60 mb-3">
                         "RIST receiver address for bonded transport."
                     </p>
@@ -1612,14 +1881,14 @@ pub fn SettingsTab(
                                 prop:value=move || receiver_input.get()
                                 disabled={
                                     let auth = auth.clone();
-                                    move || !is_online.get() || !auth.has_role("admin")
+                                    move || !is_online.get() || !auth.can(Capability::ManageSenders)
                                 }
                                 on:input=move |ev| set_receiver_input.set(event_target_value(&ev))
                             />
                         </fieldset>
                         <button class="btn btn-primary" on:click=save_config disabled={
                             let auth = auth.clone();
-                            move || !is_online.get() || !auth.has_role("admin")
+                            move || !is_online.get() || !auth.can(Capability::ManageSenders)
                         }>
                             "Save"
                         </button>
@@ -1639,6 +1908,9 @@ pub fn SettingsTab(
             // ── Power Controls ──
             <PowerControlsCard sender_id=sender_id is_online=is_online />
 
+            // ── Portal PIN ──
+            <PortalAccessCard sender_id=sender_id />
+
             // ── Config Export/Import ──
             <ConfigManagementCard sender_id=sender_id is_online=is_online />
 
@@ -1770,7 +2042,7 @@ pub fn SettingsTab(
                                 let auth = auth.clone();
                                 view! {
                                     <div class="flex gap-2">
-                                        <button class="btn btn-error" on:click=do_unenroll disabled=move || action_loading.get() || !auth.has_role("admin")>
+                                        <button class="btn btn-error" on:click=do_unenroll disabled=move || action_loading.get() || !auth.can(Capability::ManageSenders)>
                                             "Confirm"
                                         </button>
                                         <button class="btn btn-ghost" on:click=move |_| set_show_unenroll_confirm.set(false)>
@@ -1781,7 +2053,7 @@ pub fn SettingsTab(
                             } else {
                                 let auth = auth.clone();
                                 view! {
-                                    <button class="btn btn-error" on:click=move |_| set_show_unenroll_confirm.set(true) disabled=move || action_loading.get() || !auth.has_role("admin")>
+                                    <button class="btn btn-error" on:click=move |_| set_show_unenroll_confirm.set(true) disabled=move || action_loading.get() || !auth.can(Capability::ManageSenders)>
                                         "Unenroll"
                                     </button>
                                 }.into_any()
@@ -4,11 +4,15 @@ use leptos::prelude::*;
 
 use crate::AuthState;
 use crate::api;
+use crate::i18n::use_i18n;
+use crate::ws::WsClient;
 use strata_protocol::api::SenderSummary;
+use strata_protocol::models::IngestProtocol;
 
 /// Displays all senders belonging to the authenticated user.
 #[component]
 pub fn SendersPage() -> impl IntoView {
+    let i18n = use_i18n();
     let auth = expect_context::<AuthState>();
     let (senders, set_senders) = signal(Vec::<SenderSummary>::new());
     let (error, set_error) = signal(Option::<String>::None);
@@ -16,13 +20,18 @@ pub fn SendersPage() -> impl IntoView {
     let (show_create, set_show_create) = signal(false);
     let (new_name, set_new_name) = signal(String::new());
     let (creating, set_creating) = signal(false);
+    // A Strata device, or a plain SRT/RTMP contributor ("srt" / "rtmp").
+    let (new_kind, set_new_kind) = signal(String::from("strata"));
     // After creation, the modal transitions to show the enrollment token
-    let (created_info, set_created_info) = signal(Option::<(String, String)>::None);
+    // (none for an ingest sender — it never enrolls)
+    let (created_info, set_created_info) = signal(Option::<(String, Option<String>)>::None);
 
     // Load senders on mount
     let auth_load = auth.clone();
+    let resync = expect_context::<WsClient>().resync;
     Effect::new(move || {
         let token = auth_load.token.get();
+        resync.track();
         if let Some(token) = token {
             let token = token.clone();
             leptos::task::spawn_local(async move {
@@ -49,15 +58,21 @@ pub fn SendersPage() -> impl IntoView {
             Some(name_val)
         };
         let token = auth_create.token.get_untracked().unwrap_or_default();
+        let ingest = new_kind.get_untracked().parse::<IngestProtocol>().ok();
         set_creating.set(true);
         leptos::task::spawn_local(async move {
-            match api::create_sender(&token, name).await {
-                Ok(resp) => {
+            let created = match ingest {
+                Some(protocol) => api::create_ingest_sender(&token, name, protocol)
+                    .await
+                    .map(|resp| (resp.sender_id.to_string(), None)),
+                None => api::create_sender(&token, name)
+                    .await
+                    .map(|resp| (resp.sender_id.to_string(), Some(resp.enrollment_token))),
+            };
+            match created {
+                Ok(info) => {
                     // Transition the modal to show enrollment info
-                    set_created_info.set(Some((
-                        resp.sender_id.clone(),
-                        resp.enrollment_token.clone(),
-                    )));
+                    set_created_info.set(Some(info));
                     set_creating.set(false);
                     set_new_name.set(String::new());
                     if let Ok(data) = api::list_senders(&token).await {
@@ -75,14 +90,15 @@ pub fn SendersPage() -> impl IntoView {
     let close_modal = move |_| {
         set_show_create.set(false);
         set_created_info.set(None);
+        set_new_kind.set("strata".into());
     };
 
     view! {
         <div>
             <div class="flex justify-between items-center mb-6">
                 <div>
-                    <h2 class="text-2xl font-semibold">"Senders"</h2>
-                    <p class="text-sm text-base-content/60 mt-1">"Manage your field encoder units"</p>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-senders")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("senders-subtitle")}</p>
                 </div>
                 <button class="btn btn-primary" on:click=move |_| set_show_create.set(true)>
                     "+ Add Sender"
@@ -96,7 +112,35 @@ pub fn SendersPage() -> impl IntoView {
             // Create modal — transitions between form and enrollment token display
             {move || show_create.get().then(|| {
                 let info = created_info.get();
-                if let Some((sid, token)) = info {
+                if let Some((sid, None)) = info {
+                    // ── Post-creation: ingest sender, nothing to enroll ──
+                    view! {
+                        <div class="modal modal-open">
+                            <div class="modal-box">
+                                <h3 class="font-bold text-lg text-success">"✓ Sender Created"</h3>
+                                <div class="mt-4">
+                                    <div class="font-mono text-sm bg-base-300 p-4 rounded">
+                                        <div class="flex justify-between">
+                                            <span class="text-base-content/60">"Sender ID"</span>
+                                            <span class="font-semibold">{sid}</span>
+                                        </div>
+                                    </div>
+                                    <p class="text-sm text-base-content/60 mt-4">
+                                        "Start a stream on it to get the URL to push to from the phone or encoder."
+                                    </p>
+                                </div>
+                                <div class="modal-action">
+                                    <button class="btn btn-primary" on:click=close_modal>
+                                        "Done"
+                                    </button>
+                                </div>
+                            </div>
+                            <div class="modal-backdrop" on:click=close_modal>
+                                <button>"close"</button>
+                            </div>
+                        </div>
+                    }.into_any()
+                } else if let Some((sid, Some(token))) = info {
                     // ── Post-creation: show enrollment token ─────────
                     view! {
                         <div class="modal modal-open">
@@ -148,6 +192,18 @@ pub fn SendersPage() -> impl IntoView {
                                     />
                                     <p class="text-xs text-base-content/40 mt-1">"A friendly name for this encoder unit"</p>
                                 </fieldset>
+                                <fieldset class="fieldset">
+                                    <label class="fieldset-label">"Type"</label>
+                                    <select
+                                        class="select select-bordered w-full"
+                                        on:change=move |ev| set_new_kind.set(event_target_value(&ev))
+                                        prop:value=move || new_kind.get()
+                                    >
+                                        <option value="strata">"Strata device (bonded)"</option>
+                                        <option value="srt">"SRT contributor (e.g. Larix)"</option>
+                                        <option value="rtmp">"RTMP contributor"</option>
+                                    </select>
+                                </fieldset>
                                 <div class="modal-action">
                                     <button class="btn btn-ghost" on:click=close_modal>
                                         "Cancel"
@@ -203,8 +259,11 @@ pub fn SendersPage() -> impl IntoView {
                                                             {if sender.online { "Online" } else { "Offline" }}
                                                         </div>
                                                     </div>
-                                                    <div class="text-sm text-base-content/60">
+                                                    <div class="flex justify-between items-center text-sm text-base-content/60">
                                                         <span>"ID: " {sender.id.clone()}</span>
+                                                        {sender.ingest.map(|p| view! {
+                                                            <span class="badge badge-outline badge-sm uppercase">{p.as_str()}</span>
+                                                        })}
                                                     </div>
                                                 </div>
                                             </div>
@@ -0,0 +1,169 @@
+//! Shared stream page (`/share/{token}`) — the read-only view a share link
+//! opens, for people without an account. Rendered outside the dashboard
+//! shell and login gate; the link's token is its only credential.
+
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+
+use crate::api;
+use crate::pages::{format_bps, format_duration, format_local_time};
+use crate::player::HlsPlayer;
+use strata_protocol::api::SharedStreamView;
+
+/// How often the shared view refreshes, in milliseconds.
+const POLL_MS: i32 = 5_000;
+
+#[component]
+pub fn SharedStreamPage(token: String) -> impl IntoView {
+    let (view_data, set_view_data) = signal(Option::<SharedStreamView>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let token = StoredValue::new(token);
+    let load = move || {
+        let token = token.get_value();
+        leptos::task::spawn_local(async move {
+            match api::get_shared_stream(&token).await {
+                Ok(v) => {
+                    set_view_data.set(Some(v));
+                    set_error.set(None);
+                }
+                Err(e) if e.starts_with("401") => {
+                    set_view_data.set(None);
+                    set_error.set(Some("This link has expired or been revoked.".into()));
+                }
+                Err(e) => set_error.set(Some(format!("Couldn't refresh: {e}"))),
+            }
+        });
+    };
+    load();
+
+    let tick = Closure::<dyn Fn()>::new(load);
+    let interval = web_sys::window().and_then(|w| {
+        w.set_interval_with_callback_and_timeout_and_arguments_0(
+            tick.as_ref().unchecked_ref(),
+            POLL_MS,
+        )
+        .ok()
+    });
+    let tick = StoredValue::new_local(Some(tick));
+    on_cleanup(move || {
+        if let (Some(window), Some(id)) = (web_sys::window(), interval) {
+            window.clear_interval_with_handle(id);
+        }
+        tick.set_value(None);
+    });
+
+    let preview_url = Signal::derive(move || view_data.get().and_then(|v| v.preview_url));
+
+    view! {
+        <div class="min-h-screen bg-base-100 p-6">
+            <div class="max-w-4xl mx-auto">
+                <div class="flex justify-between items-center mb-6">
+                    <div>
+                        <h1 class="text-2xl font-semibold">
+                            {move || view_data.get().map(|v| {
+                                v.label.or(v.sender_name).unwrap_or_else(|| "Shared stream".into())
+                            })}
+                        </h1>
+                        <p class="text-sm text-base-content/60 mt-1">
+                            "Read-only view shared from Strata"
+                        </p>
+                    </div>
+                    {move || view_data.get().map(|v| {
+                        let cls = match v.state.as_str() {
+                            "live" => "badge badge-success",
+                            "starting" | "stopping" => "badge badge-warning",
+                            "failed" => "badge badge-error",
+                            _ => "badge badge-ghost",
+                        };
+                        view! { <span class=cls>{v.state}</span> }
+                    })}
+                </div>
+
+                {move || error.get().map(|e| view! {
+                    <div class="alert alert-warning text-sm mb-4">{e}</div>
+                })}
+
+                {move || view_data.get().map(|v| {
+                    let live = v.state == "live";
+                    let aggregate: u64 = v.links.iter().map(|l| l.observed_bps.0).sum();
+                    view! {
+                        <div class="card bg-base-200 border border-base-300 mb-4">
+                            <div class="card-body">
+                                {if live {
+                                    view! { <HlsPlayer url=preview_url class="w-full rounded".to_string() /> }.into_any()
+                                } else {
+                                    view! {
+                                        <p class="text-sm text-base-content/60">
+                                            {match v.ended_at {
+                                                Some(t) => format!("Stream ended at {}", format_local_time(Some(&t.to_rfc3339()))),
+                                                None => "Stream is not live".to_string(),
+                                            }}
+                                        </p>
+                                    }.into_any()
+                                }}
+                            </div>
+                        </div>
+
+                        <div class="stats stats-vertical sm:stats-horizontal bg-base-200 border border-base-300 w-full mb-4">
+                            <div class="stat">
+                                <div class="stat-title">"Uptime"</div>
+                                <div class="stat-value text-2xl">{format_duration(v.uptime_s)}</div>
+                            </div>
+                            <div class="stat">
+                                <div class="stat-title">"Encoder"</div>
+                                <div class="stat-value text-2xl">{format_bps(u64::from(v.encoder_bitrate_kbps) * 1000)}</div>
+                            </div>
+                            <div class="stat">
+                                <div class="stat-title">"Throughput"</div>
+                                <div class="stat-value text-2xl">{format_bps(aggregate)}</div>
+                            </div>
+                        </div>
+
+                        {(!v.links.is_empty()).then(|| view! {
+                            <div class="card bg-base-200 border border-base-300 mb-4">
+                                <div class="card-body">
+                                    <h3 class="card-title text-base">"Links"</h3>
+                                    <div class="overflow-x-auto">
+                                        <table class="table table-sm">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Link"</th>
+                                                    <th>"State"</th>
+                                                    <th>"Throughput"</th>
+                                                    <th>"RTT"</th>
+                                                    <th>"Loss"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {v.links.iter().map(|l| view! {
+                                                    <tr>
+                                                        <td>
+                                                            {l.interface.clone()}
+                                                            {l.link_kind.clone().map(|k| view! {
+                                                                <span class="text-xs text-base-content/50">{format!(" · {k}")}</span>
+                                                            })}
+                                                        </td>
+                                                        <td>{l.state.clone()}</td>
+                                                        <td>{format_bps(l.observed_bps.0)}</td>
+                                                        <td>{format!("{:.0} ms", l.rtt_ms)}</td>
+                                                        <td>{format!("{:.1}%", l.loss_rate * 100.0)}</td>
+                                                    </tr>
+                                                }).collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                </div>
+                            </div>
+                        })}
+
+                        <p class="text-xs text-base-content/40">
+                            {format!("Link expires {}", format_local_time(Some(&v.expires_at.to_rfc3339())))}
+                        </p>
+                    }
+                })}
+            </div>
+        </div>
+    }
+}
@@ -4,19 +4,26 @@ use leptos::prelude::*;
 
 use crate::AuthState;
 use crate::api;
+use crate::export::{self, ExportButtons};
+use crate::i18n::use_i18n;
+use crate::toast::use_toasts;
+use crate::ws::WsClient;
 use strata_protocol::api::StreamSummary;
 
 /// Lists active and recent streams.
 #[component]
 pub fn StreamsPage() -> impl IntoView {
+    let i18n = use_i18n();
     let auth = expect_context::<AuthState>();
     let (streams, set_streams) = signal(Vec::<StreamSummary>::new());
     let (error, set_error) = signal(Option::<String>::None);
     let (loading, set_loading) = signal(true);
 
     let auth_load = auth.clone();
+    let resync = expect_context::<WsClient>().resync;
     Effect::new(move || {
         let token = auth_load.token.get();
+        resync.track();
         if let Some(token) = token {
             let token = token.clone();
             leptos::task::spawn_local(async move {
@@ -34,13 +41,29 @@ pub fn StreamsPage() -> impl IntoView {
         }
     });
 
+    let export_rows = Signal::derive(move || {
+        streams
+            .get()
+            .iter()
+            .filter_map(|s| serde_json::to_value(s).ok())
+            .collect()
+    });
+
     view! {
         <div>
             <div class="flex justify-between items-center mb-6">
                 <div>
-                    <h2 class="text-2xl font-semibold">"Streams"</h2>
-                    <p class="text-sm text-base-content/60 mt-1">"Active and recent broadcasts"</p>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-streams")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("streams-subtitle")}</p>
                 </div>
+                <ExportButtons
+                    rows=export_rows
+                    columns=&[
+                        "id", "sender_id", "state", "started_at", "ended_at", "end_reason",
+                        "error_message", "restarted_from",
+                    ]
+                    filename=Signal::derive(|| "streams".to_string())
+                />
             </div>
 
             {move || error.get().map(|e| view! {
@@ -70,6 +93,7 @@ pub fn StreamsPage() -> impl IntoView {
                                         <th>"Reason"</th>
                                         <th>"Started"</th>
                                         <th>"Ended"</th>
+                                        <th>"Report"</th>
                                     </tr>
                                 </thead>
                                 <tbody>
@@ -123,6 +147,9 @@ pub fn StreamsPage() -> impl IntoView {
                                                     <td class="text-xs">{reason_view}</td>
                                                     <td class="text-xs">{crate::pages::format_local_time(stream.started_at.map(|t| t.to_rfc3339()).as_deref())}</td>
                                                     <td class="text-xs">{crate::pages::format_local_time(stream.ended_at.map(|t| t.to_rfc3339()).as_deref())}</td>
+                                                    <td>
+                                                        {stream.ended_at.is_some().then(|| view! { <ReportButtons stream_id=stream.id.clone() /> })}
+                                                    </td>
                                                 </tr>
                                             }
                                         }
@@ -136,3 +163,59 @@ pub fn StreamsPage() -> impl IntoView {
         </div>
     }
 }
+
+/// JSON / PDF downloads of an ended stream's incident report.
+#[component]
+fn ReportButtons(stream_id: String) -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
+    let (busy, set_busy) = signal(false);
+
+    let fetch = move |pdf: bool| {
+        let Some(token) = auth.token.get_untracked() else {
+            return;
+        };
+        let id = stream_id.clone();
+        set_busy.set(true);
+        leptos::task::spawn_local(async move {
+            let result = if pdf {
+                api::get_stream_report_pdf(&token, &id)
+                    .await
+                    .and_then(|pdf| {
+                        export::download_bytes(&format!("{id}-report.pdf"), "application/pdf", &pdf)
+                    })
+            } else {
+                api::get_stream_report(&token, &id)
+                    .await
+                    .and_then(|report| {
+                        let body = serde_json::to_string_pretty(&report).unwrap_or_default();
+                        export::download(&format!("{id}-report.json"), "application/json", &body)
+                    })
+            };
+            if let Err(e) = result {
+                toasts.error(format!("Report download failed: {e}"));
+            }
+            set_busy.set(false);
+        });
+    };
+    let fetch_json = fetch.clone();
+
+    view! {
+        <div class="join">
+            <button
+                class="btn btn-ghost btn-xs join-item"
+                disabled=busy
+                on:click=move |_| fetch_json(false)
+            >
+                "JSON"
+            </button>
+            <button
+                class="btn btn-ghost btn-xs join-item"
+                disabled=busy
+                on:click=move |_| fetch(true)
+            >
+                "PDF"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,430 @@
+//! Users page — invite teammates, assign roles, reset passwords and
+//! disable accounts (admin only).
+//!
+//! Invitations and resets produce a password link. It is emailed when the
+//! control plane has mail configured, and shown once here either way so
+//! the admin can pass it on.
+
+use leptos::prelude::*;
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::pages::format_local_time;
+use crate::toast::use_toasts;
+use strata_protocol::api::{Capability, ROLES, UpdateUserRequest, UserSummary};
+
+/// A destructive change awaiting confirmation.
+#[derive(Clone)]
+enum PendingAction {
+    Disable(UserSummary),
+    Enable(UserSummary),
+    ResetPassword(UserSummary),
+}
+
+/// A password link issued by an invite or reset.
+#[derive(Clone)]
+struct IssuedLink {
+    email: String,
+    url: String,
+    emailed: bool,
+    invite: bool,
+}
+
+/// Absolute URL of the dashboard's `/{page}/{token}` password link.
+fn link_url(page: &str, token: &str) -> String {
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{origin}/{page}/{token}")
+}
+
+fn role_badge(role: &str) -> &'static str {
+    match role {
+        "admin" => "badge badge-primary badge-sm",
+        "operator" => "badge badge-info badge-sm",
+        _ => "badge badge-ghost badge-sm",
+    }
+}
+
+#[component]
+pub fn UsersPage() -> impl IntoView {
+    let i18n = use_i18n();
+    let auth = expect_context::<AuthState>();
+    let toasts = use_toasts();
+    if !auth.can(Capability::ManageUsers) {
+        return view! {
+            <div>
+                <h2 class="text-2xl font-semibold mb-6">{move || i18n.t("nav-users")}</h2>
+                <div class="alert alert-warning text-sm">{move || i18n.t("users-admin-only")}</div>
+            </div>
+        }
+        .into_any();
+    }
+
+    let (users, set_users) = signal(Vec::<UserSummary>::new());
+    let (error, set_error) = signal(Option::<String>::None);
+    let (loading, set_loading) = signal(true);
+
+    let (show_invite, set_show_invite) = signal(false);
+    let (invite_email, set_invite_email) = signal(String::new());
+    let (invite_role, set_invite_role) = signal("viewer".to_string());
+    let (inviting, set_inviting) = signal(false);
+    // Link to show once after an invite or reset.
+    let (issued, set_issued) = signal(Option::<IssuedLink>::None);
+
+    let (pending, set_pending) = signal(Option::<PendingAction>::None);
+    let (confirming, set_confirming) = signal(false);
+
+    let token = auth.token;
+    Effect::new(move || {
+        if let Some(token) = token.get() {
+            leptos::task::spawn_local(async move {
+                match api::list_users(&token).await {
+                    Ok(list) => set_users.set(list),
+                    Err(e) => set_error.set(Some(e)),
+                }
+                set_loading.set(false);
+            });
+        }
+    });
+
+    let replace_user = move |user: UserSummary| {
+        set_users.update(|list| {
+            if let Some(u) = list.iter_mut().find(|u| u.id == user.id) {
+                *u = user;
+            }
+        });
+    };
+
+    // Apply `update` locally at once, then reconcile with the server's
+    // copy — or roll back to `previous` if the change is refused.
+    let apply_update = move |previous: UserSummary, update: UpdateUserRequest| {
+        let token = token.get_untracked().unwrap_or_default();
+        let mut optimistic = previous.clone();
+        if let Some(role) = &update.role {
+            optimistic.role = role.clone();
+        }
+        if let Some(disabled) = update.disabled {
+            optimistic.disabled = disabled;
+        }
+        replace_user(optimistic);
+        leptos::task::spawn_local(async move {
+            match api::update_user(&token, &previous.id, &update).await {
+                Ok(user) => replace_user(user),
+                Err(e) => {
+                    toasts.error(format!("Couldn't update {}: {e}", previous.email));
+                    replace_user(previous);
+                }
+            }
+        });
+    };
+
+    let on_invite = move |_| {
+        let token = token.get_untracked().unwrap_or_default();
+        let email = invite_email.get_untracked().trim().to_string();
+        let role = invite_role.get_untracked();
+        set_inviting.set(true);
+        leptos::task::spawn_local(async move {
+            match api::invite_user(&token, &email, &role).await {
+                Ok(resp) => {
+                    set_issued.set(Some(IssuedLink {
+                        email: resp.user.email.clone(),
+                        url: link_url("invite", &resp.invite_token),
+                        emailed: resp.emailed,
+                        invite: true,
+                    }));
+                    set_users.update(|list| list.push(resp.user));
+                    set_invite_email.set(String::new());
+                    set_invite_role.set("viewer".into());
+                    set_show_invite.set(false);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_inviting.set(false);
+        });
+    };
+
+    let on_confirm = move |_| {
+        let Some(action) = pending.get_untracked() else {
+            return;
+        };
+        match action {
+            PendingAction::Disable(user) => {
+                set_pending.set(None);
+                apply_update(
+                    user,
+                    UpdateUserRequest {
+                        disabled: Some(true),
+                        ..Default::default()
+                    },
+                );
+            }
+            PendingAction::Enable(user) => {
+                set_pending.set(None);
+                apply_update(
+                    user,
+                    UpdateUserRequest {
+                        disabled: Some(false),
+                        ..Default::default()
+                    },
+                );
+            }
+            PendingAction::ResetPassword(user) => {
+                let token = token.get_untracked().unwrap_or_default();
+                set_confirming.set(true);
+                leptos::task::spawn_local(async move {
+                    match api::reset_user_password(&token, &user.id).await {
+                        Ok(resp) => {
+                            set_issued.set(Some(IssuedLink {
+                                email: user.email,
+                                url: link_url("reset-password", &resp.reset_token),
+                                emailed: resp.emailed,
+                                invite: false,
+                            }));
+                            set_error.set(None);
+                        }
+                        Err(e) => set_error.set(Some(e)),
+                    }
+                    set_confirming.set(false);
+                    set_pending.set(None);
+                });
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <div class="flex justify-between items-center mb-6">
+                <div>
+                    <h2 class="text-2xl font-semibold">{move || i18n.t("nav-users")}</h2>
+                    <p class="text-sm text-base-content/60 mt-1">{move || i18n.t("users-subtitle")}</p>
+                </div>
+                <button class="btn btn-primary" on:click=move |_| set_show_invite.set(true)>
+                    "+ Invite User"
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="alert alert-error text-sm mb-4">{e}</div>
+            })}
+
+            // Invite modal
+            {move || show_invite.get().then(|| view! {
+                <div class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">"Invite User"</h3>
+                        <fieldset class="fieldset mt-4">
+                            <label class="fieldset-label">"Email"</label>
+                            <input
+                                class="input input-bordered w-full"
+                                type="email"
+                                placeholder="name@example.com"
+                                prop:value=move || invite_email.get()
+                                on:input=move |ev| set_invite_email.set(event_target_value(&ev))
+                            />
+                            <label class="fieldset-label mt-2">"Role"</label>
+                            <select class="select select-bordered w-full"
+                                on:change=move |ev| set_invite_role.set(event_target_value(&ev))
+                            >
+                                {ROLES.iter().map(|r| view! {
+                                    <option value=*r selected=move || invite_role.get() == *r>{*r}</option>
+                                }).collect::<Vec<_>>()}
+                            </select>
+                            <p class="text-xs text-base-content/40 mt-1">
+                                "Viewers can only watch; operators run streams; admins also manage users and devices."
+                            </p>
+                        </fieldset>
+                        <div class="modal-action">
+                            <button class="btn btn-ghost" on:click=move |_| set_show_invite.set(false)>
+                                "Cancel"
+                            </button>
+                            <button class="btn btn-primary" on:click=on_invite
+                                disabled=move || inviting.get() || !invite_email.get().contains('@')
+                            >
+                                {move || if inviting.get() { "Inviting…" } else { "Invite" }}
+                            </button>
+                        </div>
+                    </div>
+                    <div class="modal-backdrop" on:click=move |_| set_show_invite.set(false)></div>
+                </div>
+            })}
+
+            // Password link display
+            {move || issued.get().map(|link| view! {
+                <div class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg text-success">
+                            {if link.invite { "✓ Invitation Link" } else { "✓ Password Reset Link" }}
+                        </h3>
+                        {if link.emailed {
+                            view! {
+                                <div class="alert alert-info text-sm mt-4 mb-4">
+                                    {format!("Emailed to {}. You can also pass it on yourself — it will not be shown again.", link.email)}
+                                </div>
+                            }
+                            .into_any()
+                        } else {
+                            view! {
+                                <div class="alert alert-warning text-sm mt-4 mb-4">
+                                    {format!("Send this link to {} — it will not be shown again. It works once{}.", link.email, if link.invite { ", for a week" } else { ", for an hour" })}
+                                </div>
+                            }
+                            .into_any()
+                        }}
+                        <div class="font-mono text-xs bg-base-300 p-4 rounded break-all select-all">
+                            {link.url}
+                        </div>
+                        <div class="modal-action">
+                            <button class="btn btn-primary" on:click=move |_| set_issued.set(None)>
+                                "Done"
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            })}
+
+            // Confirmation modal
+            {move || pending.get().map(|action| {
+                let (title, body, button, button_class) = match &action {
+                    PendingAction::Disable(u) => (
+                        "Disable User",
+                        format!("{} will be signed out and unable to log in until re-enabled.", u.email),
+                        "Disable",
+                        "btn btn-error",
+                    ),
+                    PendingAction::Enable(u) => (
+                        "Enable User",
+                        format!("{} will be able to log in again.", u.email),
+                        "Enable",
+                        "btn btn-primary",
+                    ),
+                    PendingAction::ResetPassword(u) => (
+                        "Reset Password",
+                        format!("{}'s current password will stop working. They will choose a new one through a reset link.", u.email),
+                        "Reset Password",
+                        "btn btn-warning",
+                    ),
+                };
+                view! {
+                    <div class="modal modal-open">
+                        <div class="modal-box">
+                            <h3 class="font-bold text-lg">{title}</h3>
+                            <p class="text-sm mt-4">{body}</p>
+                            <div class="modal-action">
+                                <button class="btn btn-ghost" on:click=move |_| set_pending.set(None)>
+                                    "Cancel"
+                                </button>
+                                <button class=button_class on:click=on_confirm disabled=move || confirming.get()>
+                                    {button}
+                                </button>
+                            </div>
+                        </div>
+                        <div class="modal-backdrop" on:click=move |_| set_pending.set(None)></div>
+                    </div>
+                }
+            })}
+
+            {move || {
+                if loading.get() {
+                    return view! { <p class="text-base-content/60">"Loading…"</p> }.into_any();
+                }
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="table table-sm">
+                            <thead>
+                                <tr>
+                                    <th>"Email"</th>
+                                    <th>"Role"</th>
+                                    <th>"Status"</th>
+                                    <th>"Added"</th>
+                                    <th></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                <For
+                                    each=move || users.get()
+                                    key=|u| (u.id.clone(), u.role.clone(), u.disabled)
+                                    children=move |user| {
+                                        let added = format_local_time(Some(&user.created_at.to_rfc3339()));
+                                        let role_cell = if user.owner {
+                                            view! {
+                                                <span class=role_badge(&user.role)>{user.role.clone()}</span>
+                                                <span class="badge badge-outline badge-sm ml-1">"owner"</span>
+                                            }.into_any()
+                                        } else {
+                                            let current = user.clone();
+                                            view! {
+                                                <select class="select select-bordered select-xs"
+                                                    on:change=move |ev| {
+                                                        let role = event_target_value(&ev);
+                                                        if role != current.role {
+                                                            apply_update(current.clone(), UpdateUserRequest {
+                                                                role: Some(role),
+                                                                ..Default::default()
+                                                            });
+                                                        }
+                                                    }
+                                                >
+                                                    {ROLES.iter().map(|r| view! {
+                                                        <option value=*r selected=user.role == *r>{*r}</option>
+                                                    }).collect::<Vec<_>>()}
+                                                </select>
+                                            }.into_any()
+                                        };
+                                        let status = if user.disabled {
+                                            view! { <span class="badge badge-error badge-sm">"disabled"</span> }
+                                        } else {
+                                            view! { <span class="badge badge-success badge-sm">"active"</span> }
+                                        };
+                                        let actions = (!user.owner).then(|| {
+                                            let reset = user.clone();
+                                            let toggle = user.clone();
+                                            view! {
+                                                <div class="flex gap-1 justify-end">
+                                                    <button class="btn btn-ghost btn-xs"
+                                                        on:click=move |_| set_pending.set(Some(PendingAction::ResetPassword(reset.clone())))
+                                                    >
+                                                        "Reset password"
+                                                    </button>
+                                                    {if toggle.disabled {
+                                                        view! {
+                                                            <button class="btn btn-ghost btn-xs"
+                                                                on:click=move |_| set_pending.set(Some(PendingAction::Enable(toggle.clone())))
+                                                            >
+                                                                "Enable"
+                                                            </button>
+                                                        }.into_any()
+                                                    } else {
+                                                        view! {
+                                                            <button class="btn btn-ghost btn-xs text-error"
+                                                                on:click=move |_| set_pending.set(Some(PendingAction::Disable(toggle.clone())))
+                                                            >
+                                                                "Disable"
+                                                            </button>
+                                                        }.into_any()
+                                                    }}
+                                                </div>
+                                            }
+                                        });
+                                        view! {
+                                            <tr class:opacity-50=user.disabled>
+                                                <td class="font-mono text-sm">{user.email.clone()}</td>
+                                                <td>{role_cell}</td>
+                                                <td>{status}</td>
+                                                <td class="text-sm text-base-content/60">{added}</td>
+                                                <td>{actions}</td>
+                                            </tr>
+                                        }
+                                    }
+                                />
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
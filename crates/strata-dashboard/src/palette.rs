@@ -0,0 +1,390 @@
+//! ⌘K / Ctrl+K command palette.
+//!
+//! Searches pages, senders, streams and destinations by name, ID or
+//! hostname and either jumps to the matching page or runs a quick action
+//! (start a stream, run a connectivity test). The fleet is fetched each
+//! time the palette opens, so results are never older than the last open.
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_navigate;
+use strata_protocol::api::{Capability, DestinationSummary, SenderSummary, StreamSummary};
+
+use crate::AuthState;
+use crate::api;
+use crate::i18n::use_i18n;
+
+/// Results shown at once; narrowing the query reaches the rest.
+const MAX_RESULTS: usize = 12;
+
+const PAGES: &[(&str, &str)] = &[
+    ("Overview", "/overview"),
+    ("Senders", "/senders"),
+    ("Receivers", "/receivers"),
+    ("Kits", "/kits"),
+    ("Streams", "/streams"),
+    ("Multiview", "/multiview"),
+    ("Schedule", "/schedule"),
+    ("Destinations", "/destinations"),
+    ("Alerts", "/alerts"),
+    ("Audit Log", "/audit"),
+    ("Preferences", "/preferences"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Go(String),
+    StartStream { sender_id: String, name: String },
+    RunTest { sender_id: String, name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Command {
+    group: &'static str,
+    label: String,
+    detail: String,
+    /// Lower-cased text the query is matched against.
+    haystack: String,
+    action: Action,
+}
+
+impl Command {
+    fn new(group: &'static str, label: String, detail: String, action: Action) -> Self {
+        let haystack = format!("{label} {detail}").to_lowercase();
+        Self {
+            group,
+            label,
+            detail,
+            haystack,
+            action,
+        }
+    }
+}
+
+fn sender_name(s: &SenderSummary) -> String {
+    s.name
+        .clone()
+        .or_else(|| s.hostname.clone())
+        .unwrap_or_else(|| s.id.clone())
+}
+
+fn build_commands(
+    senders: &[SenderSummary],
+    streams: &[StreamSummary],
+    destinations: &[DestinationSummary],
+    capabilities: &[Capability],
+) -> Vec<Command> {
+    let can = |c| capabilities.contains(&c);
+    let mut out: Vec<Command> = PAGES
+        .iter()
+        .map(|(label, path)| {
+            Command::new(
+                "Page",
+                label.to_string(),
+                path.to_string(),
+                Action::Go(path.to_string()),
+            )
+        })
+        .collect();
+    if can(Capability::ManageUsers) {
+        out.push(Command::new(
+            "Page",
+            "Users".into(),
+            "/users".into(),
+            Action::Go("/users".into()),
+        ));
+    }
+
+    let live: Vec<&str> = streams
+        .iter()
+        .filter(|s| s.ended_at.is_none())
+        .map(|s| s.sender_id.as_str())
+        .collect();
+    for s in senders {
+        let name = sender_name(s);
+        let detail = format!(
+            "{} · {}{}",
+            s.id,
+            s.hostname.as_deref().unwrap_or("—"),
+            if s.online { "" } else { " · offline" }
+        );
+        out.push(Command::new(
+            "Sender",
+            name.clone(),
+            detail.clone(),
+            Action::Go(format!("/senders/{}", s.id)),
+        ));
+        if s.online {
+            if can(Capability::ControlStreams) && !live.contains(&s.id.as_str()) {
+                out.push(Command::new(
+                    "Action",
+                    format!("Start stream on {name}"),
+                    detail.clone(),
+                    Action::StartStream {
+                        sender_id: s.id.clone(),
+                        name: name.clone(),
+                    },
+                ));
+            }
+            if can(Capability::ManageSenders) {
+                out.push(Command::new(
+                    "Action",
+                    format!("Run test on {name}"),
+                    detail,
+                    Action::RunTest {
+                        sender_id: s.id.clone(),
+                        name,
+                    },
+                ));
+            }
+        }
+    }
+
+    for st in streams {
+        let sender = senders
+            .iter()
+            .find(|s| s.id == st.sender_id)
+            .map(sender_name)
+            .unwrap_or_else(|| st.sender_id.clone());
+        out.push(Command::new(
+            "Stream",
+            format!("{sender} — {}", st.state),
+            format!("{} · {}", st.id, st.sender_id),
+            Action::Go(format!("/senders/{}", st.sender_id)),
+        ));
+    }
+
+    for d in destinations {
+        out.push(Command::new(
+            "Destination",
+            d.name.clone(),
+            format!("{} · {} · {}", d.id, d.platform, d.url),
+            Action::Go("/destinations".into()),
+        ));
+    }
+    out
+}
+
+/// Commands containing every whitespace-separated term of `query`, with
+/// labels starting with the query ranked first.
+fn filter_commands(commands: &[Command], query: &str) -> Vec<Command> {
+    let query = query.trim().to_lowercase();
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let mut hits: Vec<&Command> = commands
+        .iter()
+        .filter(|c| terms.iter().all(|t| c.haystack.contains(t)))
+        .collect();
+    hits.sort_by_key(|c| !c.label.to_lowercase().starts_with(&query));
+    hits.into_iter().take(MAX_RESULTS).cloned().collect()
+}
+
+/// The palette overlay. Mounted once in the dashboard shell; opens on
+/// ⌘K / Ctrl+K and closes on Escape, a backdrop click, or navigation.
+#[component]
+pub fn CommandPalette() -> impl IntoView {
+    let auth = expect_context::<AuthState>();
+    let navigate = use_navigate();
+    let i18n = use_i18n();
+
+    let (open, set_open) = signal(false);
+    let (query, set_query) = signal(String::new());
+    let (selected, set_selected) = signal(0usize);
+    let (commands, set_commands) = signal(Vec::<Command>::new());
+    let (loading, set_loading) = signal(false);
+    let (status, set_status) = signal(Option::<Result<String, String>>::None);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+
+    let keys = window_event_listener(leptos::ev::keydown, move |ev| {
+        if (ev.ctrl_key() || ev.meta_key()) && ev.key().eq_ignore_ascii_case("k") {
+            ev.prevent_default();
+            set_open.update(|o| *o = !*o);
+        } else if ev.key() == "Escape" && open.get_untracked() {
+            set_open.set(false);
+        }
+    });
+    on_cleanup(move || keys.remove());
+
+    // Refresh the fleet and reset the query on every open.
+    let auth_load = auth.clone();
+    Effect::new(move || {
+        if !open.get() {
+            return;
+        }
+        set_query.set(String::new());
+        set_selected.set(0);
+        set_status.set(None);
+        let Some(token) = auth_load.token.get_untracked() else {
+            return;
+        };
+        let capabilities = auth_load.capabilities.get_untracked();
+        set_loading.set(true);
+        leptos::task::spawn_local(async move {
+            let (senders, streams, destinations) = load_fleet(&token).await;
+            set_commands.set(build_commands(
+                &senders,
+                &streams,
+                &destinations,
+                &capabilities,
+            ));
+            set_loading.set(false);
+        });
+    });
+    // Focus the query box once it mounts.
+    Effect::new(move || {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+
+    let token = auth.token;
+    let results = Memo::new(move |_| commands.with(|c| filter_commands(c, &query.get())));
+
+    let run = move |cmd: Command| match cmd.action {
+        Action::Go(path) => {
+            set_open.set(false);
+            navigate(&path, Default::default());
+        }
+        Action::StartStream { sender_id, name } => {
+            let Some(token) = token.get_untracked() else {
+                return;
+            };
+            set_status.set(Some(Ok(format!("Starting stream on {name}…"))));
+            let navigate = navigate.clone();
+            leptos::task::spawn_local(async move {
+                match api::start_stream(&token, &sender_id, None, None, None).await {
+                    Ok(_) => {
+                        set_open.set(false);
+                        navigate(&format!("/senders/{sender_id}"), Default::default());
+                    }
+                    Err(e) => set_status.set(Some(Err(format!("Start failed: {e}")))),
+                }
+            });
+        }
+        Action::RunTest { sender_id, name } => {
+            let Some(token) = token.get_untracked() else {
+                return;
+            };
+            set_status.set(Some(Ok(format!("Testing {name}…"))));
+            leptos::task::spawn_local(async move {
+                let result = api::run_sender_test(&token, &sender_id).await;
+                set_status.set(Some(match result {
+                    Ok(r) => {
+                        let mark = |ok: bool| if ok { "✓" } else { "✗" };
+                        let summary = format!(
+                            "{name}: cloud {} · receiver {} · enrolled {}",
+                            mark(r.cloud_connected),
+                            mark(r.receiver_reachable),
+                            mark(r.enrolled)
+                        );
+                        if r.cloud_connected && r.receiver_reachable {
+                            Ok(summary)
+                        } else {
+                            Err(summary)
+                        }
+                    }
+                    Err(e) => Err(format!("Test failed: {e}")),
+                }));
+            });
+        }
+    };
+    // Stored so the view's closures can share it by copy.
+    let run = StoredValue::new_local(run);
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        // Keep page-level shortcuts (e.g. multiview's j/k) out of the query.
+        ev.stop_propagation();
+        let count = results.with_untracked(|r| r.len());
+        match ev.key().as_str() {
+            "ArrowDown" if count > 0 => set_selected.update(|i| *i = (*i + 1) % count),
+            "ArrowUp" if count > 0 => set_selected.update(|i| *i = (*i + count - 1) % count),
+            "Enter" => {
+                let cmd = results.with_untracked(|r| r.get(selected.get_untracked()).cloned());
+                if let Some(cmd) = cmd {
+                    run.with_value(|run| run(cmd));
+                }
+            }
+            "Escape" => set_open.set(false),
+            "k" | "K" if ev.ctrl_key() || ev.meta_key() => set_open.set(false),
+            _ => return,
+        }
+        ev.prevent_default();
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="modal modal-open modal-top">
+                <div class="modal-box max-w-xl mt-20 p-0">
+                    <div class="p-3 border-b border-base-300">
+                        <input
+                            node_ref=input_ref
+                            type="text"
+                            class="input input-bordered w-full"
+                            placeholder=move || i18n.t("palette-placeholder")
+                            prop:value=move || query.get()
+                            on:input=move |ev| {
+                                set_query.set(event_target_value(&ev));
+                                set_selected.set(0);
+                            }
+                            on:keydown=on_keydown
+                        />
+                    </div>
+                    <ul class="menu w-full max-h-96 overflow-y-auto flex-nowrap p-2">
+                        {move || {
+                            results.get().into_iter().enumerate().map(|(i, cmd)| {
+                                let group = cmd.group;
+                                let label = cmd.label.clone();
+                                let detail = cmd.detail.clone();
+                                view! {
+                                    <li>
+                                        <a
+                                            class=move || if selected.get() == i { "menu-active flex gap-3" } else { "flex gap-3" }
+                                            on:mouseenter=move |_| set_selected.set(i)
+                                            on:click=move |_| {
+                                                let cmd = cmd.clone();
+                                                run.with_value(|run| run(cmd));
+                                            }
+                                        >
+                                            <span class="badge badge-ghost badge-sm w-20 shrink-0">{group}</span>
+                                            <span class="flex-1 min-w-0">
+                                                <span class="block truncate">{label}</span>
+                                                <span class="block truncate text-xs text-base-content/50 font-mono">{detail}</span>
+                                            </span>
+                                        </a>
+                                    </li>
+                                }
+                            }).collect::<Vec<_>>()
+                        }}
+                        {move || (results.with(|r| r.is_empty()) && !loading.get()).then(|| view! {
+                            <li class="text-sm text-base-content/50 p-3">{i18n.t("palette-no-matches")}</li>
+                        })}
+                    </ul>
+                    <div class="px-4 py-2 border-t border-base-300 flex justify-between items-center text-xs text-base-content/50">
+                        {move || match status.get() {
+                            Some(Ok(msg)) => view! { <span class="text-base-content">{msg}</span> }.into_any(),
+                            Some(Err(msg)) => view! { <span class="text-error">{msg}</span> }.into_any(),
+                            None if loading.get() => view! { <span>{i18n.t("palette-loading")}</span> }.into_any(),
+                            None => view! { <span>{i18n.t("palette-hint")}</span> }.into_any(),
+                        }}
+                        <kbd class="kbd kbd-xs">"⌘K"</kbd>
+                    </div>
+                </div>
+                <div class="modal-backdrop" on:click=move |_| set_open.set(false)></div>
+            </div>
+        </Show>
+    }
+}
+
+/// Fetch the lists the palette searches. A failed list just contributes
+/// no results rather than failing the palette.
+async fn load_fleet(
+    token: &str,
+) -> (
+    Vec<SenderSummary>,
+    Vec<StreamSummary>,
+    Vec<DestinationSummary>,
+) {
+    (
+        api::list_senders(token).await.unwrap_or_default(),
+        api::list_streams(token).await.unwrap_or_default(),
+        api::list_destinations(token).await.unwrap_or_default(),
+    )
+}
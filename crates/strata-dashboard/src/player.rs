@@ -0,0 +1,105 @@
+//! HLS video player for receiver live previews.
+//!
+//! Plays through hls.js (loaded from `index.html` as `window.Hls`) where
+//! Media Source Extensions are available, and falls back to the browser's
+//! native HLS support (Safari, iOS) otherwise.
+
+use js_sys::{Array, Function, Object, Reflect};
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Call `obj.method(args…)`.
+fn call(obj: &JsValue, method: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    let f: Function = Reflect::get(obj, &method.into())?.dyn_into()?;
+    f.apply(obj, &args.iter().collect::<Array>())
+}
+
+/// Attach `url` to `video`. Returns the hls.js instance when one is used,
+/// so the caller can destroy it later.
+fn attach(video: &web_sys::HtmlVideoElement, url: &str) -> Result<Option<JsValue>, String> {
+    let window: JsValue = web_sys::window().ok_or("no window")?.into();
+    let hls_ctor = Reflect::get(&window, &"Hls".into()).unwrap_or(JsValue::UNDEFINED);
+    let hls_supported = !hls_ctor.is_undefined()
+        && call(&hls_ctor, "isSupported", &[])
+            .map(|v| v.is_truthy())
+            .unwrap_or(false);
+
+    if hls_supported {
+        let config = Object::new();
+        let _ = Reflect::set(&config, &"lowLatencyMode".into(), &true.into());
+        let _ = Reflect::set(&config, &"liveSyncDurationCount".into(), &2.into());
+        let ctor: Function = hls_ctor
+            .dyn_into()
+            .map_err(|_| "Hls is not a constructor")?;
+        let hls = Reflect::construct(&ctor, &Array::of1(&config))
+            .map_err(|_| "failed to create hls.js player")?;
+        call(&hls, "loadSource", &[url.into()]).map_err(|_| "hls.js loadSource failed")?;
+        call(&hls, "attachMedia", &[JsValue::from(video.clone())])
+            .map_err(|_| "hls.js attachMedia failed")?;
+        return Ok(Some(hls));
+    }
+
+    if !video
+        .can_play_type("application/vnd.apple.mpegurl")
+        .is_empty()
+    {
+        video.set_src(url);
+        return Ok(None);
+    }
+    Err("this browser cannot play HLS".into())
+}
+
+/// Live HLS player. Swaps source whenever `url` changes; `None` clears it.
+#[component]
+pub fn HlsPlayer(
+    #[prop(into)] url: Signal<Option<String>>,
+    #[prop(optional, into)] class: String,
+) -> impl IntoView {
+    let video_ref = NodeRef::<leptos::html::Video>::new();
+    let hls = StoredValue::new_local(Option::<JsValue>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let detach = move || {
+        if let Some(instance) = hls.try_update_value(|h| h.take()).flatten() {
+            let _ = call(&instance, "destroy", &[]);
+        }
+    };
+
+    Effect::new(move || {
+        let url = url.get();
+        let Some(video) = video_ref.get() else {
+            return;
+        };
+        detach();
+        let _ = video.remove_attribute("src");
+        set_error.set(None);
+        let Some(url) = url else {
+            return;
+        };
+        match attach(&video, &url) {
+            Ok(instance) => {
+                hls.set_value(instance);
+                let _ = video.play();
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    });
+
+    on_cleanup(detach);
+
+    view! {
+        <div class=format!("relative bg-black rounded-lg overflow-hidden {class}")>
+            <video
+                node_ref=video_ref
+                class="w-full h-full object-contain"
+                prop:muted=true
+                autoplay=true
+                playsinline=true
+                controls=true
+            ></video>
+            {move || error.get().map(|e| view! {
+                <div class="absolute inset-0 flex items-center justify-center text-sm text-base-content/60">{e}</div>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,124 @@
+//! Global toast notifications.
+//!
+//! Actions report their outcome through [`Toasts`] (provided via context)
+//! instead of each card keeping its own message signal, so feedback looks
+//! the same everywhere and survives the card re-rendering. Toasts stack in
+//! the bottom-right corner and dismiss themselves; errors linger longer.
+
+use leptos::prelude::*;
+use wasm_bindgen::prelude::Closure;
+
+use crate::ws::set_timeout;
+
+/// How long a toast stays up, in milliseconds.
+const TOAST_MS: i32 = 4_000;
+const ERROR_TOAST_MS: i32 = 8_000;
+/// Oldest toasts are dropped beyond this many.
+const MAX_TOASTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    id: u64,
+    kind: ToastKind,
+    text: String,
+}
+
+/// Handle on the toast stack. Copy, so it can be captured by async tasks,
+/// which can't look up context themselves.
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    items: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<u64>,
+}
+
+impl Toasts {
+    pub(crate) fn new() -> Self {
+        Self {
+            items: RwSignal::new(Vec::new()),
+            next_id: StoredValue::new(0),
+        }
+    }
+
+    pub fn success(&self, text: impl Into<String>) {
+        self.push(ToastKind::Success, text.into());
+    }
+
+    pub fn info(&self, text: impl Into<String>) {
+        self.push(ToastKind::Info, text.into());
+    }
+
+    pub fn error(&self, text: impl Into<String>) {
+        self.push(ToastKind::Error, text.into());
+    }
+
+    fn push(&self, kind: ToastKind, text: String) {
+        let Some(id) = self.next_id.try_update_value(|n| {
+            *n += 1;
+            *n
+        }) else {
+            return;
+        };
+        self.items.update(|items| {
+            // Repeating the same message just refreshes it.
+            items.retain(|t| !(t.kind == kind && t.text == text));
+            items.push(Toast { id, kind, text });
+            let excess = items.len().saturating_sub(MAX_TOASTS);
+            items.drain(..excess);
+        });
+
+        let items = self.items;
+        let delay = match kind {
+            ToastKind::Error => ERROR_TOAST_MS,
+            _ => TOAST_MS,
+        };
+        set_timeout(
+            Closure::<dyn FnMut()>::new(move || {
+                items.try_update(|items| items.retain(|t| t.id != id));
+            }),
+            delay,
+        );
+    }
+
+    fn dismiss(&self, id: u64) {
+        self.items.update(|items| items.retain(|t| t.id != id));
+    }
+}
+
+pub fn use_toasts() -> Toasts {
+    expect_context::<Toasts>()
+}
+
+/// Renders the stack. Mounted once, inside the shell's toast container,
+/// so toasts outlive page navigation.
+#[component]
+pub fn ToastHost() -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        <For
+            each=move || toasts.items.get()
+            key=|t| t.id
+            children=move |t| {
+                let cls = match t.kind {
+                    ToastKind::Success => "alert alert-success text-sm",
+                    ToastKind::Info => "alert alert-info text-sm",
+                    ToastKind::Error => "alert alert-error text-sm",
+                };
+                let id = t.id;
+                view! {
+                    <div class=cls role="status">
+                        <span>{t.text}</span>
+                        <button class="btn btn-ghost btn-xs" on:click=move |_| toasts.dismiss(id)>"✕"</button>
+                    </div>
+                }
+            }
+        />
+    }
+}
@@ -4,16 +4,51 @@
 //! `auth.login` envelope, matching the server's agent/receiver WS
 //! handshake — not a `?token=` query param, since those end up in
 //! proxy/access logs), receives `DashboardEvent`s, and exposes them as
-//! Leptos signals for reactive UI updates. Reconnects automatically with a
-//! ~3-4s jittered delay on disconnect (E9: avoids every tab reconnecting
-//! in lockstep after a control-plane restart).
+//! Leptos signals for reactive UI updates. Reconnects automatically on
+//! disconnect with jittered exponential backoff (1 s doubling to 30 s; the
+//! jitter avoids every tab reconnecting in lockstep after a control-plane
+//! restart, E9).
+//!
+//! Events pushed while the socket was down are lost, so every successful
+//! re-authentication bumps [`WsClient::resync`]. Pages track it in their
+//! REST load effects and refetch, which keeps "live" badges from
+//! outliving a stream that ended during the blip. [`WsClient::notice`]
+//! drives the connection toasts in the shell.
+//!
+//! The server only pushes `fleet`/`alerts` by default. Pages that need
+//! per-second telemetry call [`WsClient::subscribe`] for the topic and
+//! [`WsClient::unsubscribe`] on cleanup; the client refcounts topics and
+//! replays the whole set after every (re)authentication.
+//!
+//! Agents may report telemetry at 10 Hz with eight links, far faster than
+//! anyone reads a number. Stats events are throttled per topic to
+//! [`TELEMETRY_UI_INTERVAL_MS`] — the first goes straight through, later
+//! ones within the interval collapse into a single trailing update — and
+//! every sample is folded into [`TelemetryHistory`], a bounded
+//! one-sample-per-second buffer the graphs read from context.
+
+use std::collections::{HashMap, VecDeque};
 
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 use web_sys::{MessageEvent, WebSocket};
 
-use strata_protocol::DashboardEvent;
+use strata_protocol::api::UserPreferences;
+use strata_protocol::telemetry::LinkSample;
+use strata_protocol::{DashboardEvent, DashboardTopic, StreamStatsPayload};
+
+/// Minimum spacing of telemetry updates per topic (~4 Hz).
+pub const TELEMETRY_UI_INTERVAL_MS: f64 = 250.0;
+
+/// Connection change worth telling the user about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionNotice {
+    /// The socket dropped; the next attempt is `retry_in_ms` away.
+    Lost { attempt: u32, retry_in_ms: i32 },
+    /// Re-authenticated after a drop; pages are refetching.
+    Restored,
+}
 
 /// Holds the live event stream from the dashboard WebSocket.
 #[derive(Clone)]
@@ -29,6 +64,41 @@ pub struct WsClient {
     /// claim "Live" while telemetry is dead (UX_TRUST_AUDIT U13).
     pub auth_failed: ReadSignal<bool>,
     set_auth_failed: WriteSignal<bool>,
+    /// Bumped after each reconnection; REST loaders track it to refetch
+    /// whatever changed while events couldn't arrive.
+    pub resync: ReadSignal<u32>,
+    set_resync: WriteSignal<u32>,
+    /// Current connection toast, if any.
+    pub notice: ReadSignal<Option<ConnectionNotice>>,
+    set_notice: WriteSignal<Option<ConnectionNotice>>,
+    /// Telemetry topics wanted by mounted pages, with a refcount each.
+    topics: StoredValue<HashMap<DashboardTopic, usize>>,
+    /// The current authenticated socket, if any.
+    socket: StoredValue<Option<WebSocket>, LocalStorage>,
+    /// The most recently opened socket, authenticated or not.
+    raw: StoredValue<Option<WebSocket>, LocalStorage>,
+    /// Bumped by `connect`/`disconnect`; handlers and timers of an older
+    /// generation do nothing, so a logout or re-login can't leave a stale
+    /// reconnect loop running with an old token.
+    generation: StoredValue<u32>,
+    /// Consecutive failed attempts since the last successful auth.
+    attempt: StoredValue<u32>,
+    /// Whether this session has authenticated before, i.e. whether the
+    /// next successful auth is a reconnection.
+    was_live: StoredValue<bool>,
+    /// Per-topic throttle for stats events.
+    throttle: StoredValue<HashMap<DashboardTopic, Throttle>>,
+    /// Graph history fed from every stats sample, before throttling.
+    pub history: TelemetryHistory,
+}
+
+/// Where one telemetry topic stands in its throttle interval.
+#[derive(Default)]
+struct Throttle {
+    emitted_at_ms: f64,
+    /// Newest event held back for the trailing update; a timer is armed
+    /// whenever this is `Some`.
+    pending: Option<DashboardEvent>,
 }
 
 impl Default for WsClient {
@@ -43,6 +113,8 @@ impl WsClient {
         let (last_event, set_event) = signal(None::<DashboardEvent>);
         let (connected, set_connected) = signal(false);
         let (auth_failed, set_auth_failed) = signal(false);
+        let (resync, set_resync) = signal(0u32);
+        let (notice, set_notice) = signal(None::<ConnectionNotice>);
         Self {
             last_event,
             set_event,
@@ -50,19 +122,270 @@ impl WsClient {
             set_connected,
             auth_failed,
             set_auth_failed,
+            resync,
+            set_resync,
+            notice,
+            set_notice,
+            topics: StoredValue::new(HashMap::new()),
+            socket: StoredValue::new_local(None),
+            raw: StoredValue::new_local(None),
+            generation: StoredValue::new(0),
+            attempt: StoredValue::new(0),
+            was_live: StoredValue::new(false),
+            throttle: StoredValue::new(HashMap::new()),
+            history: TelemetryHistory::new(),
         }
     }
 
-    /// Connect to the dashboard WebSocket. Reconnects automatically on disconnect.
+    /// Connect to the dashboard WebSocket, replacing any existing
+    /// connection. Reconnects automatically on disconnect.
     pub fn connect(&self, token: &str) {
+        let generation = self.reset();
         let url = build_ws_url();
-        setup_websocket(
-            url,
-            token.to_string(),
-            self.set_event,
-            self.set_connected,
-            self.set_auth_failed,
+        setup_websocket(url, token.to_string(), self.clone(), generation);
+    }
+
+    /// Close the connection and stop reconnecting (on logout).
+    pub fn disconnect(&self) {
+        self.reset();
+    }
+
+    /// Retire the current socket and its reconnect loop, returning the
+    /// generation for the next one.
+    fn reset(&self) -> u32 {
+        self.generation.update_value(|g| *g += 1);
+        if let Some(ws) = self.raw.try_update_value(|ws| ws.take()).flatten() {
+            let _ = ws.close();
+        }
+        self.socket.set_value(None);
+        self.attempt.set_value(0);
+        self.was_live.set_value(false);
+        self.throttle.set_value(HashMap::new());
+        self.history.clear();
+        self.set_connected.set(false);
+        self.set_auth_failed.set(false);
+        self.set_notice.set(None);
+        self.generation.get_value()
+    }
+
+    fn is_current(&self, generation: u32) -> bool {
+        self.generation.try_get_value() == Some(generation)
+    }
+
+    /// Start receiving events for `topic`. Pair with [`Self::unsubscribe`]
+    /// (typically in `on_cleanup`).
+    pub fn subscribe(&self, topic: DashboardTopic) {
+        let mut first = false;
+        self.topics.update_value(|t| {
+            let count = t.entry(topic.clone()).or_insert(0);
+            *count += 1;
+            first = *count == 1;
+        });
+        if first {
+            self.send_subscription("subscribe", vec![topic]);
+        }
+    }
+
+    /// Drop one reference to `topic`; the server stops sending it once no
+    /// page holds it.
+    pub fn unsubscribe(&self, topic: DashboardTopic) {
+        let mut last = false;
+        self.topics.update_value(|t| {
+            if let Some(count) = t.get_mut(&topic) {
+                *count -= 1;
+                if *count == 0 {
+                    t.remove(&topic);
+                    last = true;
+                }
+            }
+        });
+        if last {
+            self.send_subscription("unsubscribe", vec![topic]);
+        }
+    }
+
+    /// Hand a received event to the pages, throttling telemetry.
+    fn deliver(&self, event: DashboardEvent, generation: u32) {
+        let now = js_sys::Date::now();
+        let topic = match &event {
+            DashboardEvent::StreamStats(stats) => {
+                self.history.record(stats, now);
+                event.topic()
+            }
+            DashboardEvent::ReceiverStreamStats(_) => event.topic(),
+            _ => {
+                self.drop_superseded(&event);
+                self.set_event.set(Some(event));
+                return;
+            }
+        };
+
+        // Ok(event) to emit now, Err(delay) when a trailing update must be
+        // scheduled, neither when one already is.
+        let mut event = Some(event);
+        let mut outcome = None;
+        self.throttle.update_value(|t| {
+            let slot = t.entry(topic.clone()).or_default();
+            if slot.pending.is_some() {
+                slot.pending = event.take();
+                return;
+            }
+            let elapsed = now - slot.emitted_at_ms;
+            if elapsed >= TELEMETRY_UI_INTERVAL_MS {
+                slot.emitted_at_ms = now;
+                outcome = event.take().map(Ok);
+            } else {
+                slot.pending = event.take();
+                outcome = Some(Err(TELEMETRY_UI_INTERVAL_MS - elapsed));
+            }
+        });
+        match outcome {
+            Some(Ok(event)) => self.set_event.set(Some(event)),
+            Some(Err(delay_ms)) => {
+                let client = self.clone();
+                set_timeout(
+                    Closure::once(move || client.flush(topic, generation)),
+                    delay_ms.ceil() as i32,
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// Emit the trailing update held back for `topic`, if still wanted.
+    fn flush(&self, topic: DashboardTopic, generation: u32) {
+        if !self.is_current(generation) {
+            return;
+        }
+        let now = js_sys::Date::now();
+        let event = self
+            .throttle
+            .try_update_value(|t| {
+                let slot = t.get_mut(&topic)?;
+                slot.emitted_at_ms = now;
+                slot.pending.take()
+            })
+            .flatten();
+        if let Some(event) = event {
+            self.set_event.set(Some(event));
+        }
+    }
+
+    /// Forget held-back telemetry that a state change makes stale, so a
+    /// trailing sample can't repaint a stream as live after it ended.
+    fn drop_superseded(&self, event: &DashboardEvent) {
+        let topics = match event {
+            DashboardEvent::StreamStateChanged {
+                stream_id,
+                sender_id,
+                ..
+            } => vec![
+                DashboardTopic::Sender(sender_id.clone()),
+                DashboardTopic::Stream(stream_id.clone()),
+            ],
+            DashboardEvent::SenderStatus {
+                sender_id,
+                online: false,
+                ..
+            } => vec![DashboardTopic::Sender(sender_id.clone())],
+            _ => return,
+        };
+        self.throttle.update_value(|t| {
+            for topic in &topics {
+                if let Some(slot) = t.get_mut(topic) {
+                    slot.pending = None;
+                }
+            }
+        });
+    }
+
+    /// Send a subscribe/unsubscribe envelope on the live socket. Without
+    /// one, the topic set is replayed after the next authentication.
+    fn send_subscription(&self, msg_type: &str, topics: Vec<DashboardTopic>) {
+        if topics.is_empty() {
+            return;
+        }
+        let msg = build_envelope(msg_type, serde_json::json!({ "topics": topics }));
+        self.socket.with_value(|ws| {
+            if let Some(ws) = ws
+                && let Err(e) = ws.send_with_str(&msg)
+            {
+                log::warn!("failed to send WS {msg_type}: {e:?}");
+            }
+        });
+    }
+}
+
+/// One sender's link samples, oldest first, each with its arrival time (ms).
+pub type LinkHistory = VecDeque<(f64, Vec<LinkSample>)>;
+
+/// Per-sender link history for the live graphs: at most one sample per
+/// second (the newest within it), enough seconds for the widest graph
+/// window. Readers are notified once per completed second rather than on
+/// every sample.
+#[derive(Clone, Copy)]
+pub struct TelemetryHistory {
+    samples: StoredValue<HashMap<String, LinkHistory>>,
+    /// Bumped whenever some sender's history gains a second.
+    version: RwSignal<u64>,
+}
+
+impl TelemetryHistory {
+    const CAPACITY: usize = 300;
+
+    fn new() -> Self {
+        debug_assert!(
+            UserPreferences::GRAPH_WINDOWS
+                .iter()
+                .all(|&w| w as usize <= Self::CAPACITY)
         );
+        Self {
+            samples: StoredValue::new(HashMap::new()),
+            version: RwSignal::new(0),
+        }
+    }
+
+    fn record(&self, stats: &StreamStatsPayload, now_ms: f64) {
+        let mut grew = false;
+        self.samples.update_value(|m| {
+            let history = m.entry(stats.sender_id.clone()).or_default();
+            let second = (now_ms / 1000.0).floor();
+            match history.back_mut() {
+                Some(last) if (last.0 / 1000.0).floor() == second => {
+                    *last = (now_ms, stats.links.clone());
+                }
+                _ => {
+                    history.push_back((now_ms, stats.links.clone()));
+                    while history.len() > Self::CAPACITY {
+                        history.pop_front();
+                    }
+                    grew = true;
+                }
+            }
+        });
+        if grew {
+            self.version.update(|v| *v += 1);
+        }
+    }
+
+    /// The last `seconds` samples of `sender_id`, oldest first. Reactive.
+    pub fn recent(&self, sender_id: &str, seconds: usize) -> LinkHistory {
+        self.version.track();
+        self.samples.with_value(|m| {
+            m.get(sender_id)
+                .map(|h| {
+                    h.iter()
+                        .skip(h.len().saturating_sub(seconds))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    fn clear(&self) {
+        self.samples.set_value(HashMap::new());
+        self.version.update(|v| *v += 1);
     }
 }
 
@@ -80,43 +403,45 @@ fn build_ws_url() -> String {
     format!("{protocol}://{host}/ws")
 }
 
-/// Build the `auth.login` envelope JSON sent as the first WebSocket message.
-/// Matches `strata_protocol::Envelope` / `DashboardAuthPayload`'s
-/// wire shape; `id`/`ts` aren't validated server-side beyond `payload` and
-/// `type`, so lightweight WASM-local values are fine here.
-fn build_auth_message(token: &str) -> String {
+/// Build an envelope JSON string for a dashboard → server message.
+/// Matches `strata_protocol::Envelope`'s wire shape; `id`/`ts` aren't
+/// validated server-side beyond `payload` and `type`, so lightweight
+/// WASM-local values are fine here.
+fn build_envelope(msg_type: &str, payload: serde_json::Value) -> String {
     let ts = js_sys::Date::new_0()
         .to_iso_string()
         .as_string()
         .unwrap_or_default();
     serde_json::json!({
         "id": format!("dashboard-{}", js_sys::Date::now()),
-        "type": "auth.login",
+        "type": msg_type,
         "ts": ts,
         "proto_version": strata_protocol::PROTOCOL_VERSION,
-        "payload": { "token": token },
+        "payload": payload,
     })
     .to_string()
 }
 
+/// The `auth.login` envelope sent as the first WebSocket message (see
+/// `DashboardAuthPayload`).
+fn build_auth_message(token: &str) -> String {
+    build_envelope("auth.login", serde_json::json!({ "token": token }))
+}
+
 /// Set up a WebSocket connection with all event handlers.
-/// On disconnect, schedules an automatic reconnection after 3 seconds.
-fn setup_websocket(
-    url: String,
-    token: String,
-    set_event: WriteSignal<Option<DashboardEvent>>,
-    set_connected: WriteSignal<bool>,
-    set_auth_failed: WriteSignal<bool>,
-) {
+/// On disconnect, schedules a reconnection with backoff.
+fn setup_websocket(url: String, token: String, client: WsClient, generation: u32) {
+    let set_connected = client.set_connected;
+    let set_auth_failed = client.set_auth_failed;
     let ws = match WebSocket::new(&url) {
         Ok(ws) => ws,
         Err(e) => {
             log::error!("WebSocket connect failed: {e:?}");
-            // Retry after delay
-            schedule_reconnect(url, token, set_event, set_connected, set_auth_failed);
+            schedule_reconnect(url, token, client, generation);
             return;
         }
     };
+    client.raw.set_value(Some(ws.clone()));
 
     ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
 
@@ -125,7 +450,11 @@ fn setup_websocket(
         let ws = ws.clone();
         let token = token.clone();
         let sc = set_connected;
+        let client = client.clone();
         move || {
+            if !client.is_current(generation) {
+                return;
+            }
             log::info!("WebSocket connected, authenticating…");
             if let Err(e) = ws.send_with_str(&build_auth_message(&token)) {
                 log::error!("failed to send WS auth message: {e:?}");
@@ -140,11 +469,14 @@ fn setup_websocket(
     let on_close = Closure::<dyn FnMut()>::new({
         let url = url.clone();
         let token = token.clone();
-        let sc = set_connected;
+        let client = client.clone();
         move || {
-            log::warn!("WebSocket disconnected, reconnecting in 3s…");
-            sc.set(false);
-            schedule_reconnect(url.clone(), token.clone(), set_event, sc, set_auth_failed);
+            if !client.is_current(generation) {
+                return;
+            }
+            client.set_connected.set(false);
+            client.socket.set_value(None);
+            schedule_reconnect(url.clone(), token.clone(), client.clone(), generation);
         }
     });
     ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
@@ -158,38 +490,56 @@ fn setup_websocket(
     on_error.forget();
 
     // onmessage
-    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
-        if let Ok(text) = e.data().dyn_into::<web_sys::js_sys::JsString>() {
-            let s: String = text.into();
-            // The auth handshake response is Envelope-wrapped
-            // (`{"type":"auth.login.response",...}`); the live event stream
-            // is not. Peek at "type" to tell them apart before parsing as
-            // a `DashboardEvent`.
-            if let Ok(v) = serde_json::from_str::<serde_json::Value>(&s)
-                && v.get("type").and_then(|t| t.as_str()) == Some("auth.login.response")
-            {
-                let ok = v
-                    .get("payload")
-                    .and_then(|p| p.get("success"))
-                    .and_then(|s| s.as_bool())
-                    .unwrap_or(false);
-                set_auth_failed.set(!ok);
-                if !ok {
-                    let err = v
-                        .get("payload")
-                        .and_then(|p| p.get("error"))
-                        .and_then(|e| e.as_str())
-                        .unwrap_or("unknown error");
-                    log::error!("dashboard WS auth rejected: {err}");
-                }
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+        let ws = ws.clone();
+        let client = client.clone();
+        move |e: MessageEvent| {
+            if !client.is_current(generation) {
                 return;
             }
-            match serde_json::from_str::<DashboardEvent>(&s) {
-                Ok(event) => {
-                    set_event.set(Some(event));
+            if let Ok(text) = e.data().dyn_into::<web_sys::js_sys::JsString>() {
+                let s: String = text.into();
+                // The auth handshake response is Envelope-wrapped
+                // (`{"type":"auth.login.response",...}`); the live event stream
+                // is not. Peek at "type" to tell them apart before parsing as
+                // a `DashboardEvent`.
+                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&s)
+                    && v.get("type").and_then(|t| t.as_str()) == Some("auth.login.response")
+                {
+                    let ok = v
+                        .get("payload")
+                        .and_then(|p| p.get("success"))
+                        .and_then(|s| s.as_bool())
+                        .unwrap_or(false);
+                    set_auth_failed.set(!ok);
+                    if ok {
+                        // Authenticated: this socket now carries subscriptions.
+                        client.socket.set_value(Some(ws.clone()));
+                        client.attempt.set_value(0);
+                        if client.was_live.get_value() {
+                            log::info!("WebSocket reconnected, resyncing");
+                            client.set_resync.update(|n| *n += 1);
+                            show_restored(&client);
+                        }
+                        client.was_live.set_value(true);
+                        let topics: Vec<DashboardTopic> =
+                            client.topics.with_value(|t| t.keys().cloned().collect());
+                        client.send_subscription("subscribe", topics);
+                    } else {
+                        let err = v
+                            .get("payload")
+                            .and_then(|p| p.get("error"))
+                            .and_then(|e| e.as_str())
+                            .unwrap_or("unknown error");
+                        log::error!("dashboard WS auth rejected: {err}");
+                    }
+                    return;
                 }
-                Err(err) => {
-                    log::warn!("Failed to parse WS event: {err}");
+                match serde_json::from_str::<DashboardEvent>(&s) {
+                    Ok(event) => client.deliver(event, generation),
+                    Err(err) => {
+                        log::warn!("Failed to parse WS event: {err}");
+                    }
                 }
             }
         }
@@ -203,37 +553,68 @@ fn setup_websocket(
     std::mem::forget(ws);
 }
 
-/// Schedule a WebSocket reconnection after a fixed delay (3 seconds).
-/// Base reconnect delay (ms). A control-plane restart makes every open
-/// dashboard tab reconnect at once; `RECONNECT_JITTER_MS` spreads that out
-/// so they don't all hit `/ws` in the same instant (E9).
-const RECONNECT_BASE_MS: i32 = 3_000;
-const RECONNECT_JITTER_MS: f64 = 1_000.0;
+/// Reconnect backoff: the first retry comes after ~1 s, doubling per
+/// failed attempt up to `RECONNECT_MAX_MS`. Half of each delay is random
+/// so that a control-plane restart doesn't make every open dashboard tab
+/// hit `/ws` in the same instant (E9).
+const RECONNECT_BASE_MS: f64 = 1_000.0;
+const RECONNECT_MAX_MS: f64 = 30_000.0;
 
-fn schedule_reconnect(
-    url: String,
-    token: String,
-    set_event: WriteSignal<Option<DashboardEvent>>,
-    set_connected: WriteSignal<bool>,
-    set_auth_failed: WriteSignal<bool>,
-) {
-    let reconnect = Closure::wrap(Box::new(move || {
-        log::info!("attempting WebSocket reconnect…");
-        setup_websocket(
-            url.clone(),
-            token.clone(),
-            set_event,
-            set_connected,
-            set_auth_failed,
-        );
-    }) as Box<dyn FnMut()>);
+/// How long the "reconnected" toast stays up.
+const RESTORED_NOTICE_MS: i32 = 4_000;
+
+fn reconnect_delay_ms(attempt: u32) -> i32 {
+    let ceiling = (RECONNECT_BASE_MS * 2f64.powi(attempt.min(16) as i32)).min(RECONNECT_MAX_MS);
+    (ceiling / 2.0 + js_sys::Math::random() * ceiling / 2.0) as i32
+}
+
+fn schedule_reconnect(url: String, token: String, client: WsClient, generation: u32) {
+    if !client.is_current(generation) {
+        return;
+    }
+    let attempt = client.attempt.get_value();
+    client.attempt.set_value(attempt + 1);
+    let delay_ms = reconnect_delay_ms(attempt);
+    log::warn!(
+        "WebSocket disconnected, reconnecting in {delay_ms} ms (attempt {})",
+        attempt + 1
+    );
+    client.set_notice.set(Some(ConnectionNotice::Lost {
+        attempt: attempt + 1,
+        retry_in_ms: delay_ms,
+    }));
+
+    let reconnect = Closure::once(move || {
+        if client.is_current(generation) {
+            log::info!("attempting WebSocket reconnect…");
+            setup_websocket(url, token, client, generation);
+        }
+    });
+    set_timeout(reconnect, delay_ms);
+}
+
+/// Show the "reconnected" toast, clearing it after a few seconds unless
+/// something newer replaced it.
+fn show_restored(client: &WsClient) {
+    client.set_notice.set(Some(ConnectionNotice::Restored));
+    let set_notice = client.set_notice;
+    let notice = client.notice;
+    set_timeout(
+        Closure::once(move || {
+            if notice.try_get_untracked().flatten() == Some(ConnectionNotice::Restored) {
+                set_notice.set(None);
+            }
+        }),
+        RESTORED_NOTICE_MS,
+    );
+}
 
-    let delay_ms = RECONNECT_BASE_MS + (js_sys::Math::random() * RECONNECT_JITTER_MS) as i32;
+pub(crate) fn set_timeout(callback: Closure<dyn FnMut()>, delay_ms: i32) {
     let _ = web_sys::window()
         .unwrap()
         .set_timeout_with_callback_and_timeout_and_arguments_0(
-            reconnect.as_ref().unchecked_ref(),
+            callback.as_ref().unchecked_ref(),
             delay_ms,
         );
-    reconnect.forget();
+    callback.forget();
 }
@@ -0,0 +1,67 @@
+// Strata dashboard service worker.
+//
+// Makes the dashboard installable and lets it open without a connection:
+// the app shell (HTML, WASM, JS, CSS, icons) is cached as it is fetched,
+// while API calls, the dashboard WebSocket and HLS previews always go to
+// the network — stale fleet data is worse than an honest error.
+
+const CACHE = "strata-shell-v1";
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches.open(CACHE).then((cache) => cache.addAll(["/", "/manifest.webmanifest", "/icons/icon.svg"])),
+  );
+  self.skipWaiting();
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) => Promise.all(keys.filter((k) => k !== CACHE).map((k) => caches.delete(k))))
+      .then(() => self.clients.claim()),
+  );
+});
+
+function isLive(url) {
+  return url.pathname.startsWith("/api/") || url.pathname.startsWith("/ws") || url.pathname.endsWith(".m3u8") || url.pathname.endsWith(".ts") || url.pathname.endsWith(".m4s");
+}
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+  if (request.method !== "GET" || url.origin !== self.location.origin || isLive(url)) {
+    return;
+  }
+
+  // Page loads: network first so a deploy is picked up at once; the
+  // cached shell is the offline fallback for every client-side route.
+  if (request.mode === "navigate") {
+    event.respondWith(
+      fetch(request)
+        .then((response) => {
+          const copy = response.clone();
+          caches.open(CACHE).then((cache) => cache.put("/", copy));
+          return response;
+        })
+        .catch(() => caches.match("/")),
+    );
+    return;
+  }
+
+  // Trunk fingerprints the WASM/JS/CSS file names, so a cached copy is
+  // never stale: serve it, and cache anything new on first fetch.
+  event.respondWith(
+    caches.match(request).then(
+      (cached) =>
+        cached ||
+        fetch(request).then((response) => {
+          if (response.ok) {
+            const copy = response.clone();
+            caches.open(CACHE).then((cache) => cache.put(request, copy));
+          }
+          return response;
+        }),
+    ),
+  );
+});
@@ -15,6 +15,9 @@ const SENDER_AFTER_HELP: &str = r#"SOURCE HOT-SWAP:
       Patterns: smpte, ball, snow, black, white, red, green, blue
     {"cmd":"switch_source","mode":"v4l2","device":"/dev/video0"}
     {"cmd":"switch_source","mode":"uri","uri":"file:///path/to/video.mp4"}
+    {"cmd":"select_input","target":"wide","transition":"fade","duration_ms":500}
+      Cuts to a --inputs entry (or "main") on its first frame.
+      Transitions: cut, fade, black
 
 EXAMPLES:
   # Test pattern over two cellular links
@@ -31,6 +34,25 @@ EXAMPLES:
     --bitrate 2000 --config sender.toml
 "#;
 
+/// Corners --logo-position / --text-position accept.
+const OVERLAY_POSITIONS: [&str; 4] = ["top-left", "top-right", "bottom-left", "bottom-right"];
+
+const DAEMON_AFTER_HELP: &str = r#"JSON-RPC METHODS (one request per line):
+  start_stream  {"id":"cam","mode":"sender","dest":"rx:5000,rx:5002",...}
+                Remaining params map onto sender/receiver flags.
+  stop_stream   {"id":"cam"}
+  get_stats     {"id":"cam"}    latest stats JSON from the stream
+  list_streams  {}
+  switch_source, select_input, toggle_link, set_encoder, set_bonding_config
+                {"id":"cam", ...}  forwarded to the stream's control socket
+
+EXAMPLES:
+  strata-pipeline daemon --socket /tmp/strata-daemon.sock
+
+  echo '{"jsonrpc":"2.0","id":1,"method":"start_stream","params":{"dest":"127.0.0.1:5000"}}' | \
+    socat - UNIX:/tmp/strata-daemon.sock
+"#;
+
 const RECEIVER_AFTER_HELP: &str = r#"EXAMPLES:
   # Receive and monitor (no file output)
   strata-pipeline receiver --bind 0.0.0.0:5000
@@ -48,11 +70,16 @@ const RECEIVER_AFTER_HELP: &str = r#"EXAMPLES:
 
   # Receive with config
   strata-pipeline receiver --bind 0.0.0.0:5000 --config receiver.toml
+
+  # Take a plain SRT contribution (e.g. Larix) instead of bonded links
+  strata-pipeline receiver --ingest "srt://:5000?mode=listener&passphrase=SECRET" \
+    --codec h264 --relay-url "rtmp://a.rtmp.youtube.com/live2/YOUR_STREAM_KEY"
 "#;
 
 #[derive(Parser)]
 #[command(
     name = "strata-pipeline",
+    version,
     about = "Bonded video transport pipeline (GStreamer)",
     subcommand_required = true,
     arg_required_else_help = true
@@ -68,6 +95,8 @@ pub(crate) enum Mode {
     Sender(SenderArgs),
     /// Receive and reassemble bonded Strata stream
     Receiver(ReceiverArgs),
+    /// Run as a daemon driven over a Unix-socket JSON-RPC interface
+    Daemon(DaemonArgs),
 }
 
 #[derive(Args)]
@@ -89,6 +118,31 @@ pub(crate) struct SenderArgs {
     #[arg(long, default_value = "")]
     pub(crate) uri: String,
 
+    /// Extra inputs built alongside the initial source for select_input, as a
+    /// JSON array of {"id","mode","device","uri","pattern"} objects
+    #[arg(long, default_value = "")]
+    pub(crate) inputs: String,
+
+    /// PNG burned into the picture (station logo); empty = none
+    #[arg(long, default_value = "")]
+    pub(crate) logo: String,
+
+    /// Corner for --logo
+    #[arg(long, default_value = "top-right", value_parser = OVERLAY_POSITIONS)]
+    pub(crate) logo_position: String,
+
+    /// Caption burned into the picture, e.g. a unit callsign; empty = none
+    #[arg(long, default_value = "")]
+    pub(crate) overlay_text: String,
+
+    /// Burn the wall-clock time in after the caption
+    #[arg(long)]
+    pub(crate) clock: bool,
+
+    /// Corner for --overlay-text / --clock
+    #[arg(long, default_value = "bottom-left", value_parser = OVERLAY_POSITIONS)]
+    pub(crate) text_position: String,
+
     /// Target encoder bitrate in kbps
     #[arg(long, default_value_t = 1000)]
     pub(crate) bitrate: u32,
@@ -151,8 +205,14 @@ pub(crate) struct SenderArgs {
 #[command(after_help = RECEIVER_AFTER_HELP)]
 pub(crate) struct ReceiverArgs {
     /// Bind address(es), e.g. 0.0.0.0:5000 or 0.0.0.0:5000,0.0.0.0:5002
-    #[arg(long, required = true)]
-    pub(crate) bind: String,
+    #[arg(long, required_unless_present = "ingest", conflicts_with = "ingest")]
+    pub(crate) bind: Option<String>,
+
+    /// Take a plain contribution instead of bonded links: an srt:// URI
+    /// for srtsrc (listener mode, passphrase in the query) or an rtmp://
+    /// URL to pull from a local RTMP server
+    #[arg(long)]
+    pub(crate) ingest: Option<String>,
 
     /// Record to file (.ts = raw MPEG-TS, .mp4 = remuxed)
     #[arg(long, default_value = "")]
@@ -166,6 +226,12 @@ pub(crate) struct ReceiverArgs {
     #[arg(long, value_parser = ["rtmp", "hls"])]
     pub(crate) relay_type: Option<String>,
 
+    /// Directory for HLS relay segments + playlist (default: a per-process
+    /// tmpfs directory). The strata-receiver daemon sets it so it can serve
+    /// the same files as a live preview.
+    #[arg(long)]
+    pub(crate) hls_dir: Option<std::path::PathBuf>,
+
     /// Codec of incoming stream: h265 or h264
     #[arg(long, default_value = "h265")]
     pub(crate) codec: String,
@@ -183,3 +249,15 @@ pub(crate) struct ReceiverArgs {
     #[arg(long)]
     pub(crate) metrics_port: Option<u16>,
 }
+
+#[derive(Args)]
+#[command(after_help = DAEMON_AFTER_HELP)]
+pub(crate) struct DaemonArgs {
+    /// Unix socket path for JSON-RPC requests
+    #[arg(long, default_value = "/tmp/strata-daemon.sock")]
+    pub(crate) socket: String,
+
+    /// strata-pipeline binary to run streams with (default: this executable)
+    #[arg(long)]
+    pub(crate) binary: Option<std::path::PathBuf>,
+}
@@ -0,0 +1,463 @@
+//! Daemon mode: a long-running supervisor driven over a Unix-socket
+//! JSON-RPC 2.0 interface, one request per line.
+//!
+//! Each stream is a child `strata-pipeline sender|receiver` process. The
+//! daemon owns its control socket and stats relay, so a test rig only has
+//! to speak JSON-RPC:
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"start_stream","params":{"id":"cam","dest":"rx:5000,rx:5002","bitrate":2000}}
+//! ← {"jsonrpc":"2.0","id":1,"result":{"id":"cam","pid":4242}}
+//! ```
+//!
+//! `start_stream` params other than `id` and `mode` map one-to-one onto the
+//! sender/receiver flags (`min_bitrate` → `--min-bitrate`, `true` → bare
+//! switch), so the frozen flag surface stays the single source of truth.
+//! `switch_source`, `select_input`, `toggle_link`, `set_encoder` and
+//! `set_bonding_config` are forwarded to the stream's hot-swap socket
+//! unchanged.
+
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::UdpSocket;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::cli::DaemonArgs;
+
+// JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Application error: the stream is unknown, already running, or not
+/// reachable.
+const STREAM_ERROR: i64 = -32000;
+
+/// Stream id used when a request omits `id`.
+const DEFAULT_STREAM: &str = "default";
+
+/// Commands forwarded verbatim to a stream's hot-swap socket.
+const FORWARDED: &[&str] = &[
+    "switch_source",
+    "select_input",
+    "toggle_link",
+    "set_encoder",
+    "set_bonding_config",
+];
+
+/// How long `stop_stream` waits for EOS before killing the child.
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) fn run_daemon(args: &DaemonArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let binary = match &args.binary {
+        Some(path) => path.clone(),
+        None => std::env::current_exe()?,
+    };
+    let daemon = Arc::new(Mutex::new(Daemon::new(binary, args.socket.clone())));
+
+    let _ = std::fs::remove_file(&args.socket);
+    let listener = UnixListener::bind(&args.socket)?;
+    eprintln!("Daemon: JSON-RPC listening on {}", args.socket);
+
+    let daemon_sig = Arc::clone(&daemon);
+    let socket_sig = args.socket.clone();
+    ctrlc::set_handler(move || {
+        eprintln!("Daemon: shutting down");
+        if let Ok(mut d) = daemon_sig.lock() {
+            d.stop_all();
+        }
+        let _ = std::fs::remove_file(&socket_sig);
+        std::process::exit(0);
+    })?;
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Daemon: accept error: {}", e);
+                continue;
+            }
+        };
+        let daemon = Arc::clone(&daemon);
+        std::thread::Builder::new()
+            .name("daemon-conn".into())
+            .spawn(move || serve_connection(stream, &daemon))?;
+    }
+    Ok(())
+}
+
+fn serve_connection(stream: UnixStream, daemon: &Mutex<Daemon>) {
+    let mut writer = match stream.try_clone() {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Daemon: connection setup failed: {}", e);
+            return;
+        }
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(reply) = handle_line(daemon, &line)
+            && writeln!(writer, "{}", reply).is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Handle one request line. Returns the response, or `None` for a
+/// notification (a request without `id`).
+fn handle_line(daemon: &Mutex<Daemon>, line: &str) -> Option<Value> {
+    let req: Value = match serde_json::from_str(line) {
+        Ok(v) => v,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+    let id = req.get("id").cloned();
+    let Some(method) = req.get("method").and_then(Value::as_str) else {
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "missing method",
+        ));
+    };
+    let params = req.get("params").cloned().unwrap_or_else(|| json!({}));
+    if !params.is_object() {
+        return id.map(|id| error_response(id, INVALID_PARAMS, "params must be an object"));
+    }
+
+    let result = {
+        let mut d = daemon.lock().unwrap_or_else(|e| e.into_inner());
+        d.dispatch(method, &params)
+    };
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message},
+    })
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+struct Daemon {
+    binary: PathBuf,
+    /// Daemon socket path; per-stream hot-swap sockets are derived from it.
+    socket: String,
+    streams: BTreeMap<String, Stream>,
+}
+
+struct Stream {
+    mode: &'static str,
+    child: Child,
+    control: String,
+    started: Instant,
+    stats: Arc<Mutex<Option<Value>>>,
+    stats_stop: Arc<AtomicBool>,
+}
+
+impl Daemon {
+    fn new(binary: PathBuf, socket: String) -> Self {
+        Self {
+            binary,
+            socket,
+            streams: BTreeMap::new(),
+        }
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> RpcResult {
+        match method {
+            "start_stream" => self.start_stream(params),
+            "stop_stream" => self.stop_stream(stream_id(params)),
+            "get_stats" => self.get_stats(stream_id(params)),
+            "list_streams" => Ok(self.list_streams()),
+            m if FORWARDED.contains(&m) => self.forward(stream_id(params), m, params),
+            m => Err((METHOD_NOT_FOUND, format!("unknown method: {}", m))),
+        }
+    }
+
+    fn start_stream(&mut self, params: &Value) -> RpcResult {
+        let id = stream_id(params).to_string();
+        self.reap();
+        if self.streams.contains_key(&id) {
+            return Err((STREAM_ERROR, format!("stream {} is already running", id)));
+        }
+        let mode = match params.get("mode").and_then(Value::as_str) {
+            None | Some("sender") => "sender",
+            Some("receiver") => "receiver",
+            Some(m) => return Err((INVALID_PARAMS, format!("unknown mode: {}", m))),
+        };
+        let flags = stream_flags(params).map_err(|e| (INVALID_PARAMS, e))?;
+
+        let stats_sock = UdpSocket::bind("127.0.0.1:0").map_err(stream_error)?;
+        let stats_addr = stats_sock.local_addr().map_err(stream_error)?;
+        let control = format!("{}.{}", self.socket, id);
+
+        let mut cmd = Command::new(&self.binary);
+        cmd.arg(mode)
+            .args(&flags)
+            .arg("--stats-dest")
+            .arg(stats_addr.to_string())
+            .stdin(Stdio::null());
+        if mode == "sender" {
+            cmd.arg("--control").arg(&control);
+        }
+        let child = cmd.spawn().map_err(stream_error)?;
+        let pid = child.id();
+        eprintln!("Daemon: started {} stream {} (pid {})", mode, id, pid);
+
+        let stats = Arc::new(Mutex::new(None));
+        let stats_stop = Arc::new(AtomicBool::new(false));
+        spawn_stats_listener(stats_sock, Arc::clone(&stats), Arc::clone(&stats_stop));
+
+        self.streams.insert(
+            id.clone(),
+            Stream {
+                mode,
+                child,
+                control,
+                started: Instant::now(),
+                stats,
+                stats_stop,
+            },
+        );
+        Ok(json!({"id": id, "pid": pid}))
+    }
+
+    fn stop_stream(&mut self, id: &str) -> RpcResult {
+        let mut stream = self.streams.remove(id).ok_or_else(|| unknown_stream(id))?;
+        let code = stream.stop();
+        eprintln!("Daemon: stopped stream {} (exit {:?})", id, code);
+        Ok(json!({"id": id, "exit_code": code}))
+    }
+
+    fn get_stats(&mut self, id: &str) -> RpcResult {
+        self.reap();
+        let stream = self.streams.get(id).ok_or_else(|| unknown_stream(id))?;
+        let stats = stream.stats.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(stats.clone().unwrap_or(Value::Null))
+    }
+
+    fn list_streams(&mut self) -> Value {
+        self.reap();
+        let streams: Vec<Value> = self
+            .streams
+            .iter()
+            .map(|(id, s)| {
+                json!({
+                    "id": id,
+                    "mode": s.mode,
+                    "pid": s.child.id(),
+                    "uptime_s": s.started.elapsed().as_secs(),
+                })
+            })
+            .collect();
+        json!(streams)
+    }
+
+    fn forward(&mut self, id: &str, method: &str, params: &Value) -> RpcResult {
+        self.reap();
+        let stream = self.streams.get(id).ok_or_else(|| unknown_stream(id))?;
+        if stream.mode != "sender" {
+            return Err((STREAM_ERROR, format!("{} needs a sender stream", method)));
+        }
+        let mut cmd = params.clone();
+        cmd["cmd"] = json!(method);
+        if let Some(obj) = cmd.as_object_mut() {
+            obj.remove("id");
+        }
+        let mut sock = UnixStream::connect(&stream.control).map_err(stream_error)?;
+        writeln!(sock, "{}", cmd).map_err(stream_error)?;
+        Ok(json!({"id": id, "queued": true}))
+    }
+
+    /// Drop streams whose child has already exited.
+    fn reap(&mut self) {
+        self.streams.retain(|id, s| match s.child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) => {
+                eprintln!("Daemon: stream {} exited ({})", id, status);
+                s.stats_stop.store(true, Ordering::Relaxed);
+                false
+            }
+            Err(_) => false,
+        });
+    }
+
+    fn stop_all(&mut self) {
+        for (_, mut stream) in std::mem::take(&mut self.streams) {
+            stream.stop();
+        }
+    }
+}
+
+impl Stream {
+    /// Interrupt the child (EOS and a clean shutdown), escalating to a kill
+    /// after [`STOP_TIMEOUT`]. Returns the exit code, if any.
+    fn stop(&mut self) -> Option<i32> {
+        self.stats_stop.store(true, Ordering::Relaxed);
+        let _ = Command::new("kill")
+            .args(["-INT", &self.child.id().to_string()])
+            .status();
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                _ => {
+                    let _ = self.child.kill();
+                    break self.child.wait().ok().and_then(|s| s.code());
+                }
+            }
+        }
+    }
+}
+
+/// Keep the most recent stats datagram until `stop` is set.
+fn spawn_stats_listener(sock: UdpSocket, latest: Arc<Mutex<Option<Value>>>, stop: Arc<AtomicBool>) {
+    let _ = sock.set_read_timeout(Some(Duration::from_millis(500)));
+    let _ = std::thread::Builder::new()
+        .name("daemon-stats".into())
+        .spawn(move || {
+            let mut buf = vec![0u8; 65_535];
+            while !stop.load(Ordering::Relaxed) {
+                let Ok(len) = sock.recv(&mut buf) else {
+                    continue;
+                };
+                if let Ok(v) = serde_json::from_slice::<Value>(&buf[..len]) {
+                    *latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(v);
+                }
+            }
+        });
+}
+
+fn stream_id(params: &Value) -> &str {
+    params
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or(DEFAULT_STREAM)
+}
+
+fn unknown_stream(id: &str) -> (i64, String) {
+    (STREAM_ERROR, format!("no such stream: {}", id))
+}
+
+fn stream_error(e: std::io::Error) -> (i64, String) {
+    (STREAM_ERROR, e.to_string())
+}
+
+/// Translate `start_stream` params into pipeline flags. `id` and `mode` are
+/// the daemon's own; `control` and `stats_dest` are owned by the daemon.
+fn stream_flags(params: &Value) -> Result<Vec<String>, String> {
+    let mut flags = Vec::new();
+    let Some(obj) = params.as_object() else {
+        return Ok(flags);
+    };
+    for (key, value) in obj {
+        match key.as_str() {
+            "id" | "mode" => continue,
+            "control" | "stats_dest" => {
+                return Err(format!("{} is managed by the daemon", key));
+            }
+            _ => {}
+        }
+        let flag = format!("--{}", key.replace('_', "-"));
+        match value {
+            Value::Bool(true) => flags.push(flag),
+            Value::Bool(false) | Value::Null => {}
+            Value::String(s) => flags.extend([flag, s.clone()]),
+            Value::Number(n) => flags.extend([flag, n.to_string()]),
+            _ => return Err(format!("{} must be a string, number or bool", key)),
+        }
+    }
+    Ok(flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daemon() -> Mutex<Daemon> {
+        Mutex::new(Daemon::new(
+            PathBuf::from("/nonexistent/strata-pipeline"),
+            "/tmp/strata-daemon-test.sock".into(),
+        ))
+    }
+
+    #[test]
+    fn stream_flags_map_params_onto_cli_flags() {
+        let params = json!({
+            "id": "cam",
+            "mode": "sender",
+            "dest": "rx:5000,rx:5002",
+            "min_bitrate": 500,
+            "audio": true,
+            "passthrough": false,
+        });
+        assert_eq!(
+            stream_flags(&params).unwrap(),
+            [
+                "--audio",
+                "--dest",
+                "rx:5000,rx:5002",
+                "--min-bitrate",
+                "500"
+            ]
+        );
+        assert!(stream_flags(&json!({"control": "/tmp/x.sock"})).is_err());
+        assert!(stream_flags(&json!({"dest": ["a", "b"]})).is_err());
+    }
+
+    #[test]
+    fn malformed_requests_get_jsonrpc_errors() {
+        let d = daemon();
+        let code = |line: &str| handle_line(&d, line).unwrap()["error"]["code"].as_i64();
+
+        assert_eq!(code("{not json"), Some(PARSE_ERROR));
+        assert_eq!(code(r#"{"jsonrpc":"2.0","id":1}"#), Some(INVALID_REQUEST));
+        assert_eq!(
+            code(r#"{"jsonrpc":"2.0","id":1,"method":"reboot"}"#),
+            Some(METHOD_NOT_FOUND)
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc":"2.0","id":1,"method":"start_stream","params":{"mode":"relay"}}"#),
+            Some(INVALID_PARAMS)
+        );
+    }
+
+    #[test]
+    fn stream_methods_report_unknown_streams() {
+        let d = daemon();
+        for method in ["stop_stream", "get_stats", "switch_source"] {
+            let line = json!({"jsonrpc": "2.0", "id": 7, "method": method, "params": {"id": "x"}});
+            let reply = handle_line(&d, &line.to_string()).unwrap();
+            assert_eq!(reply["id"], 7);
+            assert_eq!(reply["error"]["code"], STREAM_ERROR, "{method}");
+        }
+
+        let reply = handle_line(&d, r#"{"jsonrpc":"2.0","id":"a","method":"list_streams"}"#);
+        assert_eq!(reply.unwrap()["result"], json!([]));
+    }
+
+    #[test]
+    fn notifications_get_no_reply() {
+        let d = daemon();
+        assert!(handle_line(&d, r#"{"jsonrpc":"2.0","method":"list_streams"}"#).is_none());
+    }
+}
@@ -1,8 +1,12 @@
-//! Hot-swap control: source switching, link toggling, and the Unix control
-//! socket that feeds them.
+//! Hot-swap control: source switching, input selection, link toggling, and
+//! the Unix control socket that feeds them.
 
 use gst::prelude::*;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+use strata_protocol::{InputSource, MAIN_INPUT};
 
 use crate::stats::json_to_toml;
 
@@ -153,6 +157,242 @@ pub(crate) fn add_source_branch(
     Ok(sel_pad)
 }
 
+/// Add a live test-pattern branch and link it to input-selector.
+fn add_test_branch(
+    pipeline: &gst::Pipeline,
+    selector: &gst::Element,
+    pattern: &str,
+    framerate: u32,
+    width: u32,
+    height: u32,
+) -> Result<gst::Pad, Box<dyn std::error::Error>> {
+    let caps = gst::Caps::builder("video/x-raw")
+        .field("width", width as i32)
+        .field("height", height as i32)
+        .field("framerate", gst::Fraction::new(framerate as i32, 1))
+        .build();
+    let src = gst::ElementFactory::make("videotestsrc")
+        .property("is-live", true)
+        .build()?;
+    src.set_property_from_str("pattern", pattern);
+    let filter = gst::ElementFactory::make("capsfilter")
+        .property("caps", &caps)
+        .build()?;
+    let queue = gst::ElementFactory::make("queue")
+        .property("max-size-buffers", 3u32)
+        .build()?;
+    let elements = [src, filter, queue];
+    for el in &elements {
+        pipeline.add(el)?;
+    }
+    gst::Element::link_many(&elements)?;
+
+    let sel_pad = selector
+        .request_pad_simple("sink_%u")
+        .ok_or("Failed to request selector pad")?;
+    elements[2]
+        .static_pad("src")
+        .ok_or("No src pad on queue")?
+        .link(&sel_pad)?;
+    for el in &elements {
+        el.sync_state_with_parent()?;
+    }
+    Ok(sel_pad)
+}
+
+// ── Input selection ─────────────────────────────────────────────────
+
+/// How long a cut waits for the target's first frame before giving up and
+/// leaving the current picture on air.
+const CUT_TIMEOUT: Duration = Duration::from_secs(3);
+/// Steps in each half of a fade.
+const FADE_STEPS: u32 = 15;
+
+/// The selector pads a multi-camera stream can cut between, built once at
+/// startup so a cut never waits on a camera opening.
+pub(crate) struct Inputs {
+    pads: HashMap<String, gst::Pad>,
+    /// Always-live black branch that `black` transitions hold on.
+    slate: Option<gst::Pad>,
+    /// Serialises transitions: a select that arrives mid-fade waits for it.
+    busy: Arc<Mutex<()>>,
+}
+
+impl Inputs {
+    /// Build a branch per `--inputs` entry plus the black slate. `main` is
+    /// the pad the initial source feeds. A branch that fails to build is
+    /// logged and left out, so selecting it fails instead of the stream.
+    pub(crate) fn build(
+        pipeline: &gst::Pipeline,
+        selector: &gst::Element,
+        main: gst::Pad,
+        specs: &[InputSource],
+        framerate: u32,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let mut pads = HashMap::from([(MAIN_INPUT.to_string(), main)]);
+        for spec in specs {
+            let branch = match spec.mode.as_str() {
+                "test" => add_test_branch(
+                    pipeline,
+                    selector,
+                    spec.pattern.as_deref().unwrap_or("smpte"),
+                    framerate,
+                    width,
+                    height,
+                ),
+                mode => add_source_branch(
+                    pipeline,
+                    selector,
+                    mode,
+                    spec.device.as_deref().unwrap_or("/dev/video0"),
+                    spec.uri.as_deref().unwrap_or(""),
+                    framerate,
+                    width,
+                    height,
+                ),
+            };
+            match branch {
+                Ok(pad) => {
+                    eprintln!("Input '{}' ready on selector pad {}", spec.id, pad.name());
+                    pads.insert(spec.id.clone(), pad);
+                }
+                Err(e) => eprintln!("Warning: input '{}' unavailable: {}", spec.id, e),
+            }
+        }
+        let slate = if specs.is_empty() {
+            None
+        } else {
+            add_test_branch(pipeline, selector, "black", framerate, width, height)
+                .map_err(|e| eprintln!("Warning: no black slate: {}", e))
+                .ok()
+        };
+        Self {
+            pads,
+            slate,
+            busy: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Point `main` at whatever a `switch_source` just made active, unless
+    /// that is one of the extra inputs (the switch failed mid-selection).
+    pub(crate) fn set_main(&mut self, pad: gst::Pad) {
+        let extra = self
+            .pads
+            .iter()
+            .any(|(id, p)| id != MAIN_INPUT && *p == pad);
+        if !extra {
+            self.pads.insert(MAIN_INPUT.to_string(), pad);
+        }
+    }
+}
+
+/// Make `pad` active on its first buffer, so a source that isn't delivering
+/// never replaces the picture. Fires `done` once the cut is made; setting
+/// the returned flag abandons a cut that hasn't happened yet.
+fn cut_on_first_buffer(
+    selector: &gst::Element,
+    pad: &gst::Pad,
+    done: mpsc::Sender<()>,
+) -> Arc<AtomicBool> {
+    let abandoned = Arc::new(AtomicBool::new(false));
+    let flag = abandoned.clone();
+    let selector = selector.clone();
+    pad.add_probe(gst::PadProbeType::BUFFER, move |pad, _| {
+        if !flag.load(Ordering::Acquire) {
+            selector.set_property("active-pad", pad);
+            let _ = done.send(());
+        }
+        gst::PadProbeReturn::Remove
+    });
+    abandoned
+}
+
+/// Cut to `pad`, waiting up to [`CUT_TIMEOUT`] for it to deliver.
+fn cut_when_live(selector: &gst::Element, pad: &gst::Pad) -> bool {
+    if selector.property::<Option<gst::Pad>>("active-pad").as_ref() == Some(pad) {
+        return true;
+    }
+    let (tx, rx) = mpsc::channel();
+    let abandoned = cut_on_first_buffer(selector, pad, tx);
+    if rx.recv_timeout(CUT_TIMEOUT).is_ok() {
+        return true;
+    }
+    abandoned.store(true, Ordering::Release);
+    false
+}
+
+/// Ramp the `fade` videobalance between picture (1.0) and black (0.0).
+fn ramp(balance: &gst::Element, from: f64, to: f64, over: Duration) {
+    for step in 1..=FADE_STEPS {
+        let level = from + (to - from) * f64::from(step) / f64::from(FADE_STEPS);
+        balance.set_property("contrast", level);
+        balance.set_property("saturation", level);
+        std::thread::sleep(over / FADE_STEPS);
+    }
+}
+
+/// Handle an `input-select` Application message from the control socket.
+/// The transition runs on its own thread so the bus loop keeps relaying
+/// stats through a fade.
+pub(crate) fn handle_input_select(
+    pipeline: &gst::Pipeline,
+    selector: &gst::Element,
+    inputs: &Inputs,
+    structure: &gst::StructureRef,
+) {
+    let target = structure.get::<&str>("target").unwrap_or(MAIN_INPUT);
+    let Some(pad) = inputs.pads.get(target).cloned() else {
+        eprintln!("input-select: unknown input '{}'", target);
+        return;
+    };
+    let transition = structure.get::<&str>("transition").unwrap_or("cut");
+    let duration = Duration::from_millis(structure.get::<u64>("duration_ms").unwrap_or(500));
+    let balance = pipeline.by_name("fade");
+    let slate = inputs.slate.clone();
+    let busy = inputs.busy.clone();
+    let selector = selector.clone();
+    let target = target.to_string();
+    let transition = transition.to_string();
+
+    let spawned = std::thread::Builder::new()
+        .name("input-select".into())
+        .spawn(move || {
+            let _busy = busy.lock().unwrap_or_else(|e| e.into_inner());
+            let cut = match (transition.as_str(), balance, slate) {
+                ("fade", Some(balance), _) => {
+                    ramp(&balance, 1.0, 0.0, duration / 2);
+                    let cut = cut_when_live(&selector, &pad);
+                    ramp(&balance, 0.0, 1.0, duration / 2);
+                    cut
+                }
+                ("black", _, Some(slate)) => {
+                    let previous = selector.property::<Option<gst::Pad>>("active-pad");
+                    selector.set_property("active-pad", &slate);
+                    std::thread::sleep(duration);
+                    let cut = cut_when_live(&selector, &pad);
+                    if !cut && let Some(previous) = previous {
+                        selector.set_property("active-pad", &previous);
+                    }
+                    cut
+                }
+                _ => cut_when_live(&selector, &pad),
+            };
+            if cut {
+                eprintln!("Selected input '{}' ({})", target, transition);
+            } else {
+                eprintln!(
+                    "input-select: '{}' delivered no frames in {:?}; staying on the current input",
+                    target, CUT_TIMEOUT
+                );
+            }
+        });
+    if let Err(e) = spawned {
+        eprintln!("input-select: failed to start transition: {}", e);
+    }
+}
+
 /// Handle a source-switch Application message from the control socket.
 pub(crate) fn handle_source_switch(
     pipeline: &gst::Pipeline,
@@ -293,6 +533,33 @@ pub(crate) fn run_control_socket(path: &str, pipeline_weak: gst::glib::WeakRef<g
                 let msg = gst::message::Application::new(structure);
                 let _ = pipeline.post_message(msg);
                 eprintln!("Control: queued source-switch command");
+            } else if cmd.get("cmd").and_then(|v| v.as_str()) == Some("select_input") {
+                // Multi-camera cut; the transition itself runs off the bus
+                // loop (see handle_input_select).
+                let target = cmd.get("target").and_then(|v| v.as_str()).unwrap_or("");
+                if target.is_empty() {
+                    eprintln!("Control: select_input missing 'target'");
+                } else {
+                    let transition = cmd
+                        .get("transition")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("cut");
+                    let duration_ms = cmd
+                        .get("duration_ms")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(500);
+                    let structure = gst::Structure::builder("input-select")
+                        .field("target", target)
+                        .field("transition", transition)
+                        .field("duration_ms", duration_ms)
+                        .build();
+                    let msg = gst::message::Application::new(structure);
+                    let _ = pipeline.post_message(msg);
+                    eprintln!(
+                        "Control: queued input-select target={} transition={}",
+                        target, transition
+                    );
+                }
             } else if cmd.get("cmd").and_then(|v| v.as_str()) == Some("toggle_link") {
                 // Enable/disable a bonding link by OS interface name.
                 // Posts a "toggle-link" Application message processed in the bus loop.
@@ -331,6 +598,19 @@ pub(crate) fn run_control_socket(path: &str, pipeline_weak: gst::glib::WeakRef<g
                 } else {
                     eprintln!("Control: set_encoder — encoder element 'enc' not found");
                 }
+            } else if cmd.get("cmd").and_then(|v| v.as_str()) == Some("force_keyframe") {
+                // Receiver keyframe request relayed by the agent: cut the
+                // GOP short so the damage from a burst loss ends here.
+                if let Some(enc) = pipeline.by_name("enc") {
+                    let request_id = cmd.get("request_id").and_then(|v| v.as_u64());
+                    if gststrata::codec::force_key_unit(&enc) {
+                        eprintln!("Control: forced keyframe (request {:?})", request_id);
+                    } else {
+                        eprintln!("Control: force_keyframe — encoder ignored the event");
+                    }
+                } else {
+                    eprintln!("Control: force_keyframe — encoder element 'enc' not found");
+                }
             } else if cmd.get("cmd").and_then(|v| v.as_str()) == Some("set_bonding_config") {
                 // Hot-update bonding/scheduler config via stratasink's "config" property
                 if let Some(config_val) = cmd.get("config") {
@@ -352,6 +632,26 @@ pub(crate) fn run_control_socket(path: &str, pipeline_weak: gst::glib::WeakRef<g
                         eprintln!("Control: set_bonding_config — sink element 'rsink' not found");
                     }
                 }
+            } else if cmd.get("cmd").and_then(|v| v.as_str()) == Some("set_resolution") {
+                // ABR ladder step: rescale ahead of the encoder, which
+                // renegotiates and restarts its GOP at the new size.
+                let width = cmd.get("width").and_then(|v| v.as_u64());
+                let height = cmd.get("height").and_then(|v| v.as_u64());
+                match (pipeline.by_name("abrcaps"), width, height) {
+                    (Some(filter), Some(w), Some(h)) => {
+                        let caps = gst::Caps::builder("video/x-raw")
+                            .field("width", w as i32)
+                            .field("height", h as i32)
+                            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+                            .build();
+                        filter.set_property("caps", &caps);
+                        eprintln!("Control: encoder input rescaled to {}x{}", w, h);
+                    }
+                    (None, ..) => {
+                        eprintln!("Control: set_resolution — scaler 'abrcaps' not found");
+                    }
+                    _ => eprintln!("Control: set_resolution missing 'width'/'height'"),
+                }
             } else {
                 eprintln!("Control: unknown command: {}", line);
             }
@@ -1,11 +1,13 @@
 //! strata-pipeline — the bonded video transport binary.
 //!
-//! Two modes: `sender` (encode/passthrough and transmit over bonded links)
-//! and `receiver` (reassemble, then relay/record/monitor). The heavy lifting
-//! lives in the `strata_pipeline/` modules:
+//! Three modes: `sender` (encode/passthrough and transmit over bonded links),
+//! `receiver` (reassemble, then relay/record/monitor) and `daemon` (supervise
+//! either over a JSON-RPC control socket). The heavy lifting lives in the
+//! `strata_pipeline/` modules:
 //!
 //! - `cli`      — clap flag surface (frozen; scripts and daemons depend on it)
 //! - `sender`   — sender pipelines, adaptation envelope, stats relay
+//! - `daemon`   — JSON-RPC control socket supervising sender/receiver children
 //! - `receiver` — receiver pipelines, HLS egress watchdog + generation rebuilds
 //! - `gate`     — DeliveredStream / monotonic-DTS pad-probe gates
 //! - `hotswap`  — control socket, source hot-swap, link toggling
@@ -15,6 +17,7 @@
 use clap::Parser;
 
 mod cli;
+mod daemon;
 mod gate;
 mod hotswap;
 mod receiver;
@@ -32,5 +35,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     match cli::Cli::parse().mode {
         cli::Mode::Sender(args) => sender::run_sender(&args),
         cli::Mode::Receiver(args) => receiver::run_receiver(&args),
+        cli::Mode::Daemon(args) => daemon::run_daemon(&args),
     }
 }
@@ -5,7 +5,7 @@ use gst::MessageView;
 use gst::prelude::*;
 use std::collections::{HashSet, VecDeque};
 use std::env;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -13,7 +13,7 @@ use gststrata::hls_upload;
 
 use crate::cli::ReceiverArgs;
 use crate::gate::{install_delivered_stream_gate, install_monotonic_dts_gate};
-use crate::stats::serialize_receiver_stats;
+use crate::stats::{serialize_ingest_stats, serialize_receiver_stats};
 use crate::util::{configure_hlssink3_muxer, register_plugins};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -37,6 +37,68 @@ impl RelayType {
     }
 }
 
+/// Head of every receiver pipeline: bonded links through stratasrc or,
+/// with `--ingest`, a plain SRT/RTMP contribution remuxed to the same
+/// MPEG-TS. Either way the element the TOML config targets is `src`;
+/// `stratasrc_props` only applies to the bonded case.
+fn source_fragment(bind: &str, ingest: Option<&str>, stratasrc_props: &str) -> String {
+    match ingest {
+        // Classic FLV only carries H.264/AAC. Audio is optional (a video-only
+        // contribution has no audio pad), so it is linked on pad-added by
+        // `link_ingest_audio` rather than here, where an audio branch that
+        // never gets data would hold the muxer back.
+        Some(url) if url.starts_with("rtmp://") => format!(
+            "rtmp2src location=\"{url}\" name=src ! flvdemux name=ingest_demux \
+             ingest_demux.video ! queue ! h264parse ! mpegtsmux name=ingest_mux alignment=7 \
+             ingest_mux."
+        ),
+        Some(url) => format!("srtsrc uri=\"{url}\" name=src"),
+        None => format!("stratasrc links=\"{bind}\" name=src {stratasrc_props}"),
+    }
+}
+
+/// RTMP ingest: when flvdemux finds an audio stream, feed it through
+/// `queue ! aacparse` into the ingest muxer. No-op for other sources.
+fn link_ingest_audio(pipeline: &gst::Pipeline) {
+    let (Some(demux), Some(mux)) = (
+        pipeline.by_name("ingest_demux"),
+        pipeline.by_name("ingest_mux"),
+    ) else {
+        return;
+    };
+    let pipeline_weak = pipeline.downgrade();
+    let mux_weak = mux.downgrade();
+    demux.connect_pad_added(move |_, pad| {
+        if pad.name() != "audio" {
+            return;
+        }
+        let (Some(pipeline), Some(mux)) = (pipeline_weak.upgrade(), mux_weak.upgrade()) else {
+            return;
+        };
+        let linked = (|| -> Result<(), Box<dyn std::error::Error>> {
+            let queue = gst::ElementFactory::make("queue").build()?;
+            let parse = gst::ElementFactory::make("aacparse").build()?;
+            pipeline.add_many([&queue, &parse])?;
+            gst::Element::link_many([&queue, &parse])?;
+            let mux_pad = mux
+                .request_pad_simple("sink_%d")
+                .ok_or("mpegtsmux refused a sink pad")?;
+            parse
+                .static_pad("src")
+                .ok_or("aacparse has no src pad")?
+                .link(&mux_pad)?;
+            queue.sync_state_with_parent()?;
+            parse.sync_state_with_parent()?;
+            pad.link(&queue.static_pad("sink").ok_or("queue has no sink pad")?)?;
+            Ok(())
+        })();
+        match linked {
+            Ok(()) => eprintln!("ingest: audio linked into ingest_mux"),
+            Err(e) => eprintln!("ingest: failed to link audio: {e}"),
+        }
+    });
+}
+
 /// Egress watchdog (HLS relay): rebuild the pipeline when hlssink3 stops
 /// adding segments for this long. Steady-state cadence is one segment per
 /// target-duration (1 s) and the gates' resync droughts span a few seconds,
@@ -78,7 +140,8 @@ fn dump_egress_queue_levels(pipeline: &gst::Pipeline) {
 }
 
 pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error::Error>> {
-    let bind_str = args.bind.as_str();
+    let bind_str = args.bind.as_deref().unwrap_or_default();
+    let ingest = args.ingest.as_deref();
     let output_file = args.output.as_str();
     let config_path = args.config.as_str();
     let relay_url = args.relay_url.as_str();
@@ -131,7 +194,9 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
     // For HLS receiver relay, create a temp directory for segment files.
     // Prefer /dev/shm (RAM-backed tmpfs) to avoid flash/eMMC wear on SBCs.
     let hls_tmp_dir = if use_hls_relay {
-        let dir = hls_upload::tmpfs_segment_dir(&format!("strata-hls-rx-{}", std::process::id()));
+        let dir = args.hls_dir.clone().unwrap_or_else(|| {
+            hls_upload::tmpfs_segment_dir(&format!("strata-hls-rx-{}", std::process::id()))
+        });
         std::fs::create_dir_all(&dir).expect("failed to create HLS temp dir");
         eprintln!(
             "HLS temp dir: {} (tmpfs={})",
@@ -263,7 +328,7 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
             // Live will not display a video-only HLS, so the silent AAC track the
             // sender muxes has to survive the re-mux.
             format!(
-                "stratasrc links=\"{bind}\" name=src latency=200 ! \
+                "{source} ! \
                  queue name=q_ts max-size-buffers=0 max-size-bytes=0 max-size-time=5000000000 \
                  leaky=downstream ! \
                  tsparse set-timestamps=true alignment=7 ! \
@@ -278,7 +343,7 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
                  queue name=q_a max-size-buffers=0 max-size-bytes=0 max-size-time=10000000000 \
                  leaky=downstream ! \
                  aacparse name=aparse ! hls.audio",
-                bind = bind_str,
+                source = source_fragment(bind_str, ingest, "latency=200"),
                 parser = relay_parser,
                 seg = seg_location.display(),
                 pl = pl_location.display(),
@@ -287,7 +352,7 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
             let relay_frag =
                 gststrata::codec::CodecController::new(codec_type).relay_muxer_fragment();
             format!(
-                "stratasrc links=\"{bind}\" name=src latency=200 ! \
+                "{source} ! \
                  queue max-size-buffers=0 max-size-bytes=0 max-size-time=5000000000 \
                  leaky=downstream ! \
                  tsdemux name=d \
@@ -296,7 +361,7 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
                  rtmpsink location=\"{url}\" sync=false \
                  d. ! queue max-size-buffers=200 max-size-bytes=0 max-size-time=2000000000 \
                        leaky=downstream ! aacparse ! fmux.",
-                bind = bind_str,
+                source = source_fragment(bind_str, ingest, "latency=200"),
                 parser = relay_parser,
                 relay = relay_frag,
                 url = relay_url
@@ -305,20 +370,23 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
             if output_file.ends_with(".ts") {
                 // Raw dump
                 format!(
-                    "stratasrc links=\"{}\" name=src ! tee name=t ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! appsink name=sink emit-signals=true sync=false t. ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! filesink location=\"{}\" sync=false",
-                    bind_str, output_file
+                    "{} ! tee name=t ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! appsink name=sink emit-signals=true sync=false t. ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! filesink location=\"{}\" sync=false",
+                    source_fragment(bind_str, ingest, ""),
+                    output_file
                 )
             } else {
                 // Remux to encoded container: Demux -> Parse -> MP4 Mux -> File
                 format!(
-                    "stratasrc links=\"{}\" name=src ! tee name=t ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! appsink name=sink emit-signals=true sync=false t. ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! tsdemux ! {} ! mp4mux faststart=true ! filesink location=\"{}\" sync=false",
-                    bind_str, video_parser, output_file
+                    "{} ! tee name=t ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! appsink name=sink emit-signals=true sync=false t. ! queue max-size-buffers=0 max-size-time=0 max-size-bytes=0 ! tsdemux ! {} ! mp4mux faststart=true ! filesink location=\"{}\" sync=false",
+                    source_fragment(bind_str, ingest, ""),
+                    video_parser,
+                    output_file
                 )
             }
         } else {
             format!(
-                "stratasrc links=\"{}\" ! appsink name=sink emit-signals=true sync=false",
-                bind_str
+                "{} ! appsink name=sink emit-signals=true sync=false",
+                source_fragment(bind_str, ingest, "")
             )
         };
 
@@ -328,8 +396,11 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
             .downcast::<gst::Pipeline>()
             .map_err(|_| "Failed to cast to pipeline")?;
 
-        // Apply TOML config file if provided
+        link_ingest_audio(&pipeline);
+
+        // Apply TOML config file if provided (stratasrc only)
         if !config_path.is_empty()
+            && ingest.is_none()
             && let Some(src_elem) = pipeline.by_name("src")
         {
             let config_toml = std::fs::read_to_string(config_path)
@@ -338,6 +409,23 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
             eprintln!("Applied config from {}", config_path);
         }
 
+        // An ingest source posts no strata-stats: count the bytes the
+        // contributor pushes and relay them as a single link instead.
+        let ingest_bytes = ingest.map(|_| {
+            let bytes = Arc::new(AtomicU64::new(0));
+            if let Some(src) = pipeline.by_name("src").and_then(|s| s.static_pad("src")) {
+                let counter = bytes.clone();
+                src.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+                    if let Some(buffer) = info.buffer() {
+                        counter.fetch_add(buffer.size() as u64, Ordering::Relaxed);
+                    }
+                    gst::PadProbeReturn::Ok
+                });
+            }
+            bytes
+        });
+        let mut last_ingest_stats = Instant::now();
+
         // HLS re-mux egress: install the DeliveredStream gate on the parsed
         // video and disable mpegtsmux skew correction so it preserves our
         // timestamps.
@@ -524,6 +612,8 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
         // Standard GStreamer message loop (1 s pop timeout so the watchdog
         // runs even when the bus goes quiet)
         loop {
+            // Stats for the relay socket, from stratasrc or the ingest counter.
+            let mut relay_stats = None;
             if let Some(msg) = bus.timed_pop(gst::ClockTime::from_seconds(1)) {
                 match msg.view() {
                     MessageView::Eos(..) => {
@@ -535,23 +625,17 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
                         pipeline.set_state(gst::State::Null)?;
                         return Err(Box::new(err.error().clone()));
                     }
+                    MessageView::Latency(..) => {
+                        // stratasrc re-announces when the reassembly window
+                        // moves; redistribute so the sinks' sync point follows.
+                        let _ = pipeline.recalculate_latency();
+                    }
                     MessageView::Element(element) => {
                         if let Some(s) = element.structure() {
                             // Filter spammy stats if needed, or keep for visualization
                             if s.name() == "strata-stats" {
                                 eprintln!("Element Message: {}", s);
-                                if let Some(sock) = &stats_socket {
-                                    let mut v = serialize_receiver_stats(s, &mut rx_rate_state);
-                                    if use_hls_relay {
-                                        v["egress"] = serde_json::json!({
-                                            "segments_produced": segments_total,
-                                            "wd_restarts": generation,
-                                            "last_segment_age_ms":
-                                                last_progress.elapsed().as_millis() as u64,
-                                        });
-                                    }
-                                    let _ = sock.send_to(v.to_string().as_bytes(), stats_dest);
-                                }
+                                relay_stats = Some(serialize_receiver_stats(s, &mut rx_rate_state));
                             }
                             if s.name() == "hls-segment-added"
                                 && let (Ok(location), Ok(running_time)) = (
@@ -595,6 +679,28 @@ pub(crate) fn run_receiver(args: &ReceiverArgs) -> Result<(), Box<dyn std::error
                     _ => (),
                 }
             }
+            // Nothing until the contributor connects: the control plane
+            // takes the first stats as the stream going live.
+            if let Some(bytes) = &ingest_bytes
+                && last_ingest_stats.elapsed() >= Duration::from_secs(1)
+                && bytes.load(Ordering::Relaxed) > 0
+            {
+                last_ingest_stats = Instant::now();
+                relay_stats = Some(serialize_ingest_stats(
+                    bytes.load(Ordering::Relaxed),
+                    &mut rx_rate_state,
+                ));
+            }
+            if let (Some(mut v), Some(sock)) = (relay_stats, &stats_socket) {
+                if use_hls_relay {
+                    v["egress"] = serde_json::json!({
+                        "segments_produced": segments_total,
+                        "wd_restarts": generation,
+                        "last_segment_age_ms": last_progress.elapsed().as_millis() as u64,
+                    });
+                }
+                let _ = sock.send_to(v.to_string().as_bytes(), stats_dest);
+            }
             if let Some(allowance) = stall_allowance
                 && last_progress.elapsed() >= allowance
             {
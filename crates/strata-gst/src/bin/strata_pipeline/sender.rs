@@ -7,9 +7,13 @@ use strata_bonding::metrics::MetricsServer;
 
 use crate::cli::SenderArgs;
 use crate::hotswap::{
-    add_source_branch, handle_source_switch, handle_toggle_link, run_control_socket,
+    Inputs, add_source_branch, handle_input_select, handle_source_switch, handle_toggle_link,
+    run_control_socket,
+};
+use crate::stats::{
+    MediaMeter, resolve_interface_for_uri, serialize_bonding_event, serialize_bonding_stats,
+    serialize_element_stats,
 };
-use crate::stats::{resolve_interface_for_uri, serialize_bonding_stats};
 use crate::util::{configure_mpegtsmux, register_plugins};
 
 pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Error>> {
@@ -31,6 +35,11 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
     let max_bitrate_kbps = args.max_bitrate;
     let startup_ramp_ms = args.startup_ramp_ms;
     let startup_floor_kbps = args.startup_floor_kbps;
+    let input_specs: Vec<strata_protocol::InputSource> = if args.inputs.is_empty() {
+        Vec::new()
+    } else {
+        serde_json::from_str(&args.inputs).map_err(|e| format!("Invalid --inputs: {e}"))?
+    };
 
     // Parse resolution (WxH)
     let (res_w, res_h) = {
@@ -62,11 +71,15 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
     //
     // Pipeline structure:
     //   videotestsrc ! capsfilter ! queue ─┐
-    //                                      ├─ input-selector ! x264enc ! [audio] ! mux ! stratasink
+    //                                      ├─ input-selector ! videoscale ! x264enc ! [audio] ! mux ! stratasink
     //   [dynamic v4l2/uri sources] ────────┘
     //
     // The initial source (--source flag) determines which branch is active.
-    // Additional branches are added dynamically via the control socket.
+    // Additional branches are added dynamically via the control socket, or
+    // up front for --inputs, which also puts a videobalance (`fade`) after
+    // the selector for fade transitions.
+    // The scaler passes the source size through until the agent's ABR
+    // controller steps the resolution ladder (`set_resolution`).
     // IDR (keyframe) interval. Was 2 s (framerate × 2) to match the 2 s
     // HLS segment duration. On lossy bonded cellular that makes one lost
     // packet corrupt up to ~2 s of video (every P-frame references the
@@ -138,15 +151,22 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
     // Pipeline:
     //   source → input-selector → encoder → queue → mpegtsmux → stratasink
     //   [optional] audiotestsrc → <aac_enc> → aacparse → queue → mpegtsmux
+    // `level` feeds the dashboard's VU meters; its one-second interval
+    // matches the stats tick so no peak falls between two reports.
+    let level_fragment = if gst::ElementFactory::find("level").is_some() {
+        "! level name=vu interval=1000000000 "
+    } else {
+        ""
+    };
     let audio_fragment = if add_audio {
         format!(
-            " audiotestsrc is-live=true wave=silence ! audioconvert ! audioresample ! {aac_enc_element} bitrate=128000 ! aacparse ! queue ! mux."
+            " audiotestsrc is-live=true wave=silence ! audioconvert {level_fragment}! audioresample ! {aac_enc_element} bitrate=128000 ! aacparse ! queue name=amuxq ! mux."
         )
     } else {
         String::new()
     };
 
-    let video_to_mux = "! queue ! mux.".to_string();
+    let video_to_mux = "! queue name=vmuxq ! mux.".to_string();
 
     let enc_fragment =
         codec_ctrl.pipeline_fragment("enc", bitrate_kbps, key_int, max_bitrate_kbps_val);
@@ -178,11 +198,29 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
         _ => "",
     };
 
+    let fade_fragment = if input_specs.is_empty() {
+        ""
+    } else {
+        "! videobalance name=fade "
+    };
+    let overlay_fragment = overlay_fragment(args);
+    // Picture activity for the frozen-frame detector, measured on the
+    // selected input before any overlay (a burned-in clock would make
+    // every frame differ).
+    let analyse_fragment = if gst::ElementFactory::find("videoanalyse").is_some() {
+        "! videoanalyse name=analyse interval=100000000 "
+    } else {
+        eprintln!("Warning: videoanalyse not available — no frozen-frame detection");
+        ""
+    };
+
     let pipeline_str = format!(
         "videotestsrc name=testsrc is-live=true pattern=ball \
          ! video/x-raw,width={w},height={h},framerate={fps}/1 \
          ! queue name=testq max-size-buffers=3 ! sel. \
          input-selector name=sel \
+         {analyse_fragment}{fade_fragment}{overlay_fragment}! videoscale ! capsfilter name=abrcaps \
+           caps=video/x-raw,width={w},height={h},pixel-aspect-ratio=1/1 \
          {hw_fmt_conv}! {enc_fragment} \
          ! {parser_fragment} \
          {video_to_mux}{audio} \
@@ -191,6 +229,9 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
         w = res_w,
         h = res_h,
         fps = framerate,
+        analyse_fragment = analyse_fragment,
+        fade_fragment = fade_fragment,
+        overlay_fragment = overlay_fragment,
         hw_fmt_conv = hw_fmt_conv,
         enc_fragment = enc_fragment,
         parser_fragment = parser_fragment,
@@ -226,6 +267,8 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
         );
     }
 
+    configure_overlay(&pipeline, args);
+
     // Keep a handle to the input-selector and its test-source pad
     let selector = pipeline
         .by_name("sel")
@@ -240,6 +283,7 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
 
     // If the initial source is not 'test', try to create the requested
     // source and make it active instead.
+    let mut main_pad = test_pad.clone();
     if source_mode != "test" {
         match add_source_branch(
             &pipeline,
@@ -254,6 +298,7 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
             Ok(new_pad) => {
                 selector.set_property("active-pad", &new_pad);
                 eprintln!("Initial source: {} (active)", source_mode);
+                main_pad = new_pad;
             }
             Err(e) => {
                 eprintln!(
@@ -264,6 +309,17 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
         }
     }
 
+    // ── Switchable inputs (select_input) ──
+    let mut inputs = Inputs::build(
+        &pipeline,
+        &selector,
+        main_pad,
+        &input_specs,
+        framerate,
+        res_w,
+        res_h,
+    );
+
     // ── Configure destinations ──
     if let Some(sink) = pipeline.by_name("rsink") {
         // Build a URI→interface map from the TOML config so per-link
@@ -407,6 +463,12 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
     let disabled_links: Mutex<std::collections::HashMap<String, (String, String)>> =
         Mutex::new(std::collections::HashMap::new());
 
+    // Dropped-buffer totals from QoS messages, by element, for the
+    // per-element stats in the relay.
+    let mut dropped: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+    // Audio levels and picture activity, relayed with each stats report.
+    let mut meter = MediaMeter::default();
+
     // ── Bus message loop ──
     let bus = pipeline.bus().unwrap();
     for msg in bus.iter_timed(gst::ClockTime::NONE) {
@@ -415,6 +477,15 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
                 eprintln!("Got EOS. Pipeline finished.");
                 break;
             }
+            MessageView::Qos(qos) => {
+                // QoS stats carry running totals; keep the latest.
+                let (_, dropped_total) = qos.stats();
+                if let Some(src) = qos.src()
+                    && dropped_total.value() > 0
+                {
+                    dropped.insert(src.name().to_string(), dropped_total.value() as u64);
+                }
+            }
             MessageView::Error(err) => {
                 eprintln!("Error: {}", err.error());
                 pipeline.set_state(gst::State::Null)?;
@@ -427,6 +498,12 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
                         handle_source_switch(
                             &pipeline, &selector, &test_pad, s, framerate, res_w, res_h,
                         );
+                        // A hot-swapped source becomes what "main" cuts to.
+                        if let Some(active) = selector.property::<Option<gst::Pad>>("active-pad") {
+                            inputs.set_main(active);
+                        }
+                    } else if s.name() == "input-select" {
+                        handle_input_select(&pipeline, &selector, &inputs, s);
                     } else if s.name() == "toggle-link"
                         && let Some(sink) = pipeline.by_name("rsink")
                     {
@@ -478,8 +555,19 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
                     } else if s.name() == "strata-stats"
                         && let Some(sock) = &stats_socket
                     {
-                        let json = serialize_bonding_stats(s).to_string();
+                        let mut json = serialize_bonding_stats(s);
+                        json["elements"] = serialize_element_stats(&pipeline, &dropped);
+                        if let Some(media) = meter.to_json() {
+                            json["media"] = media;
+                        }
+                        let _ = sock.send_to(json.to_string().as_bytes(), stats_dest);
+                    } else if s.name() == "strata-event"
+                        && let Some(sock) = &stats_socket
+                    {
+                        let json = serialize_bonding_event(s).to_string();
                         let _ = sock.send_to(json.as_bytes(), stats_dest);
+                    } else {
+                        meter.observe(s);
                     }
                 }
             }
@@ -493,6 +581,87 @@ pub(crate) fn run_sender(args: &SenderArgs) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+// ── Overlay ─────────────────────────────────────────────────────────
+
+/// Gap between an overlay and the picture edge, in pixels.
+const OVERLAY_MARGIN: i32 = 24;
+
+/// Launch-string elements for --logo / --overlay-text / --clock, named so
+/// [`configure_overlay`] can set their properties afterwards (user text
+/// never goes through the launch-string parser). Sits after the selector,
+/// so every input carries it, and before the ABR scaler, so it scales with
+/// the picture.
+fn overlay_fragment(args: &SenderArgs) -> String {
+    let mut fragment = String::new();
+    if !args.logo.is_empty() {
+        if gst::ElementFactory::find("gdkpixbufoverlay").is_some() {
+            fragment.push_str("! gdkpixbufoverlay name=logo ");
+        } else {
+            eprintln!("Warning: gdkpixbufoverlay not available — logo disabled");
+        }
+    }
+    if args.clock || !args.overlay_text.is_empty() {
+        let factory = if args.clock {
+            "clockoverlay"
+        } else {
+            "textoverlay"
+        };
+        if gst::ElementFactory::find(factory).is_some() {
+            fragment.push_str(&format!("! {factory} name=caption "));
+        } else {
+            eprintln!("Warning: {factory} not available — caption disabled");
+        }
+    }
+    fragment
+}
+
+/// Point the overlay elements at their content and corners.
+fn configure_overlay(pipeline: &gst::Pipeline, args: &SenderArgs) {
+    if let Some(logo) = pipeline.by_name("logo") {
+        let (right, bottom) = corner(&args.logo_position);
+        // Negative offsets count from the right / bottom edge.
+        logo.set_property("location", &args.logo);
+        logo.set_property(
+            "offset-x",
+            if right {
+                -OVERLAY_MARGIN
+            } else {
+                OVERLAY_MARGIN
+            },
+        );
+        logo.set_property(
+            "offset-y",
+            if bottom {
+                -OVERLAY_MARGIN
+            } else {
+                OVERLAY_MARGIN
+            },
+        );
+        eprintln!("Overlay: logo {} ({})", args.logo, args.logo_position);
+    }
+    if let Some(caption) = pipeline.by_name("caption") {
+        let (right, bottom) = corner(&args.text_position);
+        caption.set_property("text", &args.overlay_text);
+        caption.set_property("shaded-background", true);
+        caption.set_property_from_str("halignment", if right { "right" } else { "left" });
+        caption.set_property_from_str("valignment", if bottom { "bottom" } else { "top" });
+        if args.clock {
+            caption.set_property("time-format", "%Y-%m-%d %H:%M:%S");
+        }
+        eprintln!(
+            "Overlay: caption {:?}{} ({})",
+            args.overlay_text,
+            if args.clock { " + clock" } else { "" },
+            args.text_position
+        );
+    }
+}
+
+/// `"bottom-right"` → `(right, bottom)`.
+fn corner(position: &str) -> (bool, bool) {
+    (position.ends_with("right"), position.starts_with("bottom"))
+}
+
 // ── Interface resolution ────────────────────────────────────────────
 
 /// Resolve which OS network interface routes to the host in an address.
@@ -640,6 +809,13 @@ fn run_sender_passthrough(
                 {
                     let json = serialize_bonding_stats(s).to_string();
                     let _ = sock.send_to(json.as_bytes(), addr);
+                } else if let Some(s) = elem.structure()
+                    && s.name() == "strata-event"
+                    && let (Some(sock), Ok(addr)) =
+                        (&stats_socket, stats_dest.parse::<std::net::SocketAddr>())
+                {
+                    let json = serialize_bonding_event(s).to_string();
+                    let _ = sock.send_to(json.as_bytes(), addr);
                 }
             }
             _ => {}
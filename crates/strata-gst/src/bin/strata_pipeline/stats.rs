@@ -100,15 +100,93 @@ pub(crate) fn serialize_bonding_stats(s: &gst::StructureRef) -> serde_json::Valu
                 if let Ok(rtp) = s.get::<f64>(&format!("link_{}_rtprop_ms", id)) {
                     obj["rtprop_ms"] = serde_json::json!(rtp);
                 }
+                if let Ok(sent) = s.get::<u64>(&format!("link_{}_packets_sent", id)) {
+                    let count =
+                        |key: &str| s.get::<u64>(&format!("link_{}_{key}", id)).unwrap_or(0);
+                    let rate =
+                        |key: &str| s.get::<f64>(&format!("link_{}_{key}", id)).unwrap_or(0.0);
+                    obj["packets_sent"] = serde_json::json!(sent);
+                    obj["retransmissions"] = serde_json::json!(count("retransmissions"));
+                    obj["fec_repairs_sent"] = serde_json::json!(count("fec_repairs_sent"));
+                    obj["pacing_bps"] = serde_json::json!(rate("pacing_bps").round() as u64);
+                    obj["cwnd_bytes"] = serde_json::json!(rate("cwnd_bytes").round() as u64);
+                }
                 obj
             });
         }
     }
 
-    serde_json::json!({
+    let mut stats = serde_json::json!({
         "links": links,
         "timestamp_ms": wall_time_ms,
-    })
+    });
+    // Present once the receiver has reported.
+    if let Ok(score) = s.get::<f64>("quality_score") {
+        stats["quality"] = serde_json::json!({
+            "score": score,
+            "mos": s.get::<f64>("quality_mos").unwrap_or(1.0),
+            "freeze_risk": s.get::<f64>("freeze_risk").unwrap_or(0.0),
+        });
+    }
+    stats
+}
+
+/// Per-element resource stats for the relay: the fill of every queue in
+/// the pipeline, plus the dropped-buffer totals elements have reported in
+/// QoS messages (`dropped`, keyed by element name). The agent adds each
+/// element's thread CPU on top.
+pub(crate) fn serialize_element_stats(
+    pipeline: &gst::Pipeline,
+    dropped: &std::collections::HashMap<String, u64>,
+) -> serde_json::Value {
+    use gst::prelude::*;
+
+    let mut elements: std::collections::BTreeMap<String, serde_json::Value> =
+        std::collections::BTreeMap::new();
+    for element in pipeline.iterate_recurse().into_iter().flatten() {
+        if element.factory().is_none_or(|f| f.name() != "queue") {
+            continue;
+        }
+        let ms = |prop: &str| element.property::<u64>(prop) / 1_000_000;
+        elements.insert(
+            element.name().to_string(),
+            serde_json::json!({
+                "queue_buffers": element.property::<u32>("current-level-buffers"),
+                "queue_ms": ms("current-level-time"),
+                "queue_max_ms": ms("max-size-time"),
+            }),
+        );
+    }
+    for (name, &count) in dropped {
+        elements
+            .entry(name.clone())
+            .or_insert_with(|| serde_json::json!({}))["dropped_buffers"] = serde_json::json!(count);
+    }
+
+    elements
+        .into_iter()
+        .map(|(name, mut stats)| {
+            stats["name"] = serde_json::json!(name);
+            stats
+        })
+        .collect()
+}
+
+/// Serialize a `strata-event` structure (one bonding state transition) for
+/// the stats relay. Telemetry tells it apart from stats by the `event` key.
+pub(crate) fn serialize_bonding_event(s: &gst::StructureRef) -> serde_json::Value {
+    let mut event = serde_json::Map::new();
+    for (name, value) in s.iter() {
+        let json = if let Ok(v) = value.get::<String>() {
+            serde_json::json!(v)
+        } else if let Ok(v) = value.get::<u64>() {
+            serde_json::json!(v)
+        } else {
+            continue;
+        };
+        event.insert(name.to_string(), json);
+    }
+    serde_json::Value::Object(event)
 }
 
 /// Serialize a receiver-side `strata-stats` structure for the stats relay.
@@ -140,17 +218,7 @@ pub(crate) fn serialize_receiver_stats(
             .get::<u64>(&format!("bytes_received_link_{}", id))
             .unwrap_or(0);
 
-        let observed_bps = match rx_rate_state.insert(id, (bytes_received, now)) {
-            Some((prev_bytes, prev_when)) => {
-                let secs = now.duration_since(prev_when).as_secs_f64();
-                if secs > 0.0 {
-                    (bytes_received.saturating_sub(prev_bytes) as f64 * 8.0 / secs).round() as u64
-                } else {
-                    0
-                }
-            }
-            None => 0,
-        };
+        let observed_bps = observed_bps(rx_rate_state, id, bytes_received, now);
 
         links.push(serde_json::json!({
             "id": id,
@@ -162,8 +230,183 @@ pub(crate) fn serialize_receiver_stats(
         }));
     }
 
-    serde_json::json!({
+    let mut v = serde_json::json!({
         "links": links,
         "timestamp_ms": wall_time_ms,
+    });
+    // A/V sync of the delivered TS; absent until stratasrc has seen its clocks.
+    if let Ok(av_skew_ms) = s.get::<f64>("av_skew_ms") {
+        v["av_sync"] = serde_json::json!({
+            "av_skew_ms": av_skew_ms,
+            "av_drift_ms": s.get::<f64>("av_drift_ms").unwrap_or(0.0),
+            "pcr_jitter_ms": s.get::<f64>("pcr_jitter_ms").unwrap_or(0.0),
+            "video_pts_lead_ms": s.get::<f64>("video_pts_lead_ms").unwrap_or(0.0),
+        });
+    }
+    v
+}
+
+/// Stats relay payload for an `--ingest` pipeline. A plain SRT/RTMP
+/// source posts no `strata-stats`, so the contribution is reported as a
+/// single link 0 carrying the bytes counted off the source pad.
+pub(crate) fn serialize_ingest_stats(
+    bytes_received: u64,
+    rx_rate_state: &mut std::collections::HashMap<u32, (u64, std::time::Instant)>,
+) -> serde_json::Value {
+    let now = std::time::Instant::now();
+    serde_json::json!({
+        "links": [{
+            "id": 0,
+            "loss_rate": 0.0,
+            "received_bytes": bytes_received,
+            "observed_bps": observed_bps(rx_rate_state, 0, bytes_received, now),
+            "packets_received": 0,
+            "packets_delivered": 0,
+        }],
+        "timestamp_ms": std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
     })
 }
+
+/// Rx rate of link `id` since the previous call, in bits per second.
+fn observed_bps(
+    rx_rate_state: &mut std::collections::HashMap<u32, (u64, std::time::Instant)>,
+    id: u32,
+    bytes_received: u64,
+    now: std::time::Instant,
+) -> u64 {
+    match rx_rate_state.insert(id, (bytes_received, now)) {
+        Some((prev_bytes, prev_when)) => {
+            let secs = now.duration_since(prev_when).as_secs_f64();
+            if secs > 0.0 {
+                (bytes_received.saturating_sub(prev_bytes) as f64 * 8.0 / secs).round() as u64
+            } else {
+                0
+            }
+        }
+        None => 0,
+    }
+}
+
+// ── Input monitoring ────────────────────────────────────────────────
+
+/// Luma change below which two analysed frames count as the same picture.
+/// Live sources carry sensor noise well above this, so only a repeated or
+/// stalled frame stays under it.
+const FROZEN_EPSILON: f64 = 1e-6;
+
+/// Latest `level` (audio) and `videoanalyse` (picture) readings from the
+/// sender's input, relayed as the stats' `media` object.
+#[derive(Default)]
+pub(crate) struct MediaMeter {
+    rms_db: Vec<f64>,
+    peak_db: Vec<f64>,
+    /// Luma average and variance of the latest analysed frame.
+    luma: Option<(f64, f64)>,
+    /// When the analysed picture last changed.
+    changed_at: Option<std::time::Instant>,
+}
+
+impl MediaMeter {
+    /// Take an element message off the bus; anything but a `level` or
+    /// `GstVideoAnalyse` reading is ignored.
+    pub(crate) fn observe(&mut self, s: &gst::StructureRef) {
+        match s.name().as_str() {
+            "level" => {
+                let db = |field: &str| -> Vec<f64> {
+                    s.get::<gst::glib::ValueArray>(field)
+                        .map(|values| {
+                            values
+                                .iter()
+                                .filter_map(|v| v.get::<f64>().ok())
+                                .map(|db| {
+                                    db.max(strata_protocol::models::MediaActivity::SILENCE_DB)
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                };
+                self.rms_db = db("rms");
+                self.peak_db = db("peak");
+            }
+            "GstVideoAnalyse" => {
+                if let (Ok(average), Ok(variance)) =
+                    (s.get::<f64>("luma-average"), s.get::<f64>("luma-variance"))
+                {
+                    self.on_frame(average, variance, std::time::Instant::now());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_frame(&mut self, average: f64, variance: f64, now: std::time::Instant) {
+        let same = self.luma.is_some_and(|(a, v)| {
+            (a - average).abs() < FROZEN_EPSILON && (v - variance).abs() < FROZEN_EPSILON
+        });
+        if !same || self.changed_at.is_none() {
+            self.changed_at = Some(now);
+        }
+        self.luma = Some((average, variance));
+    }
+
+    /// The `media` object for the relay; `None` before the first reading.
+    /// A picture that stops arriving altogether keeps counting as frozen.
+    pub(crate) fn to_json(&self) -> Option<serde_json::Value> {
+        if self.rms_db.is_empty() && self.luma.is_none() {
+            return None;
+        }
+        let mut media = serde_json::json!({
+            "audio_rms_db": self.rms_db,
+            "audio_peak_db": self.peak_db,
+            "frozen_ms": self
+                .changed_at
+                .map_or(0, |t| t.elapsed().as_millis() as u64),
+        });
+        if let Some((average, variance)) = self.luma {
+            media["luma"] = serde_json::json!(average);
+            media["luma_variance"] = serde_json::json!(variance);
+        }
+        Some(media)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn only_a_repeated_picture_counts_as_frozen() {
+        let t0 = Instant::now();
+        let at = |ms| t0 + Duration::from_millis(ms);
+        let mut meter = MediaMeter::default();
+        assert!(meter.to_json().is_none());
+
+        meter.on_frame(0.5, 0.02, at(0));
+        meter.on_frame(0.5, 0.02, at(100));
+        meter.on_frame(0.5, 0.02, at(200));
+        assert_eq!(meter.changed_at, Some(at(0)));
+
+        // Sensor noise on a still scene is far above the threshold.
+        meter.on_frame(0.5001, 0.02, at(300));
+        assert_eq!(meter.changed_at, Some(at(300)));
+        assert_eq!(meter.to_json().unwrap()["luma"], 0.5001);
+    }
+
+    #[test]
+    fn ingest_stats_report_one_link() {
+        let mut rates = std::collections::HashMap::new();
+        let v = serialize_ingest_stats(1_000, &mut rates);
+        assert_eq!(v["links"].as_array().unwrap().len(), 1);
+        assert_eq!(v["links"][0]["received_bytes"], 1_000);
+        assert_eq!(v["links"][0]["observed_bps"], 0);
+
+        rates.insert(0, (1_000, Instant::now() - Duration::from_secs(1)));
+        let v = serialize_ingest_stats(2_000, &mut rates);
+        let bps = v["links"][0]["observed_bps"].as_u64().unwrap();
+        assert!((7_900..=8_000).contains(&bps), "{bps}");
+    }
+}
@@ -319,13 +319,7 @@ impl CodecController {
         if self.codec == CodecType::Fake {
             return;
         }
-        let event = gst::event::CustomUpstream::builder(
-            gst::Structure::builder("GstForceKeyUnit")
-                .field("all-headers", true)
-                .build(),
-        )
-        .build();
-        enc.send_event(event);
+        force_key_unit(enc);
     }
 
     /// Build the GStreamer pipeline fragment string for this codec + backend.
@@ -476,6 +470,18 @@ impl CodecController {
     }
 }
 
+/// Ask `enc` for an IDR with in-band headers, for callers without a
+/// [`CodecController`] (the pipeline's control socket).
+pub fn force_key_unit(enc: &gst::Element) -> bool {
+    let event = gst::event::CustomUpstream::builder(
+        gst::Structure::builder("GstForceKeyUnit")
+            .field("all-headers", true)
+            .build(),
+    )
+    .build();
+    enc.send_event(event)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,19 +5,247 @@ use gst::prelude::*;
 use gst::subclass::prelude::*;
 use gst_base::subclass::prelude::*;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, atomic::AtomicBool, atomic::AtomicU32, atomic::Ordering};
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex, atomic::AtomicU32, atomic::Ordering};
+use std::time::Duration;
 use strata_bonding::adaptation::{
     AdaptationConfig, BitrateAdapter, LinkCapacity, ReceiverFeedback,
 };
-use strata_bonding::config::{BondingConfig, LinkConfig, SchedulerConfig};
+use strata_bonding::config::{BondingConfig, LinkConfig, SchedulerConfig, clamp_link_weight};
+use strata_bonding::events::EventRecord;
 use strata_bonding::runtime::{BondingRuntime, PacketSendError};
 use strata_bonding::scheduler::PacketProfile;
+use strata_bonding::telemetry::{TelemetrySink, TelemetrySnapshot};
+use strata_transport::stats::{QualityMonitor, QualitySample};
+
+/// Flatten an event record into a `strata-event` structure: `event` names
+/// the transition, the remaining fields are its payload (`link_id`, `from`,
+/// `to`, `cause`, ...) plus `seq` and `wall_time_ms`.
+fn event_structure(record: &EventRecord) -> gst::Structure {
+    let mut builder = gst::Structure::builder("strata-event");
+    if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(record) {
+        for (key, value) in fields {
+            builder = match value {
+                serde_json::Value::String(v) => builder.field(key, v),
+                serde_json::Value::Number(n) => builder.field(key, n.as_u64().unwrap_or(0)),
+                serde_json::Value::Bool(b) => builder.field(key, b),
+                _ => builder,
+            };
+        }
+    }
+    builder.build()
+}
+
+/// Posts a runtime's telemetry on the element's bus: a `strata-event` per
+/// event, a `strata-stats` per snapshot, and a `bitrate-command` whenever
+/// the adapter fed from the snapshot moves the target.
+struct BusSink {
+    element: glib::WeakRef<StrataSink>,
+    adapter: BitrateAdapter,
+    quality: QualityMonitor,
+}
+
+impl TelemetrySink for BusSink {
+    fn snapshot(&mut self, snapshot: &TelemetrySnapshot) {
+        let Some(element) = self.element.upgrade() else {
+            return;
+        };
+        let metrics = &snapshot.links;
+        let mono_time_ns = snapshot.uptime.as_nanos() as u64;
+        let wall_time_ms = snapshot.wall_time_ms;
+
+        let mut total_capacity = 0.0;
+        let mut alive_links = 0u64;
+        let mut link_caps = Vec::new();
+        for (&id, m) in metrics {
+            link_caps.push(LinkCapacity {
+                link_id: id,
+                capacity_kbps: m.capacity_bps / 1000.0,
+                alive: m.alive,
+                loss_rate: m.loss_rate,
+                rtt_ms: m.rtt_ms,
+                queue_depth: m.transport.as_ref().map(|_| m.queue_depth),
+                drain_rate_kbps: (m.pacing_rate_bps > 0.0).then_some(m.pacing_rate_bps / 1000.0),
+                aqm_dropped_total: m.transport.as_ref().map(|_| m.aqm_dropped_total),
+            });
+            if m.alive {
+                total_capacity += m.capacity_bps;
+                alive_links += 1;
+            }
+        }
+
+        let mut msg_struct = gst::Structure::builder("strata-stats")
+            .field("schema_version", 1i32)
+            .field("stats_seq", snapshot.seq)
+            .field("heartbeat", true)
+            .field("mono_time_ns", mono_time_ns)
+            .field("wall_time_ms", wall_time_ms)
+            .field("total_capacity", total_capacity)
+            .field("alive_links", alive_links)
+            .field(
+                "upstream_latency_ms",
+                element.imp().upstream_latency_ms.load(Ordering::Relaxed),
+            )
+            .field("duplicate_spread_ms", snapshot.duplicate_spread_ms)
+            .field("staggered_copies", snapshot.staggered_copies);
+        for (id, m) in metrics {
+            let os_up = m.os_up.map(|v| if v { 1i32 } else { 0i32 }).unwrap_or(-1);
+            let mtu = m.mtu.map(|v| v as i32).unwrap_or(-1);
+            let iface = m.iface.as_deref().unwrap_or("");
+            let link_kind = m.link_kind.as_deref().unwrap_or("");
+            msg_struct = msg_struct
+                .field(format!("link_{}_rtt", id), m.rtt_ms)
+                .field(format!("link_{}_capacity", id), m.capacity_bps)
+                .field(format!("link_{}_loss", id), m.loss_rate)
+                .field(format!("link_{}_observed_bps", id), m.observed_bps)
+                .field(format!("link_{}_observed_bytes", id), m.observed_bytes)
+                .field(format!("link_{}_alive", id), m.alive)
+                .field(format!("link_{}_phase", id), m.phase.as_str())
+                .field(format!("link_{}_os_up", id), os_up)
+                .field(format!("link_{}_mtu", id), mtu)
+                .field(format!("link_{}_iface", id), iface)
+                .field(format!("link_{}_kind", id), link_kind);
+            if let Some(bw) = m.btlbw_bps {
+                msg_struct = msg_struct.field(format!("link_{}_btlbw_bps", id), bw);
+            }
+            if let Some(rtp) = m.rtprop_ms {
+                msg_struct = msg_struct.field(format!("link_{}_rtprop_ms", id), rtp);
+            }
+            if let Some(t) = &m.transport {
+                msg_struct = msg_struct
+                    .field(format!("link_{}_packets_sent", id), t.packets_sent)
+                    .field(format!("link_{}_retransmissions", id), t.retransmissions)
+                    .field(format!("link_{}_fec_repairs_sent", id), t.fec_repairs_sent)
+                    .field(format!("link_{}_pacing_bps", id), m.pacing_rate_bps)
+                    .field(format!("link_{}_cwnd_bytes", id), m.inflight_cap_bytes);
+            }
+        }
+
+        // Aggregate receiver reports into feedback
+        let mut total_goodput = 0;
+        let mut max_jitter = 0;
+        let mut total_loss_weight = 0.0;
+        let mut total_fec_weight = 0.0;
+        let mut total_late_weight = 0.0;
+        let mut total_burst_weight = 0.0;
+        let mut total_rtt_weight = 0.0;
+        let mut weight_sum = 0.0;
+        let mut has_report = false;
+
+        for m in metrics.values() {
+            if let Some(r) = &m.receiver_report {
+                has_report = true;
+                total_goodput += r.goodput_bps;
+                max_jitter = max_jitter.max(r.jitter_buffer_ms);
+                // Weight loss by goodput (with a floor so 0-goodput links still count)
+                let weight = (r.goodput_bps as f64).max(10_000.0);
+                total_loss_weight += r.loss_after_fec as f64 * weight;
+                total_fec_weight += r.fec_repair_rate as f64 * weight;
+                total_late_weight += r.late_rate as f64 * weight;
+                total_burst_weight += r.mean_loss_burst as f64 * weight;
+                total_rtt_weight += m.rtt_ms * weight;
+                weight_sum += weight;
+            }
+        }
+
+        let feedback = if has_report && weight_sum > 0.0 {
+            Some(ReceiverFeedback {
+                goodput_bps: total_goodput,
+                fec_repair_rate: (total_fec_weight / weight_sum) as f32,
+                jitter_buffer_ms: max_jitter,
+                loss_after_fec: (total_loss_weight / weight_sum) as f32,
+                late_rate: (total_late_weight / weight_sum) as f32,
+                offered_bps: element.imp().ingress_rate_bps.load(Ordering::Relaxed),
+            })
+        } else {
+            None
+        };
+
+        // Session quality score from the same
+        // goodput-weighted receiver view.
+        if let Some(fb) = &feedback {
+            let q = self.quality.update(&QualitySample {
+                residual_loss: fb.loss_after_fec as f64,
+                late_rate: fb.late_rate as f64,
+                mean_loss_burst: total_burst_weight / weight_sum,
+                rtt_ms: total_rtt_weight / weight_sum,
+                jitter_buffer_ms: fb.jitter_buffer_ms as f64,
+            });
+            msg_struct = msg_struct
+                .field("quality_score", q.score)
+                .field("quality_mos", q.mos)
+                .field("freeze_risk", q.freeze_risk);
+        }
+        let _ = element.post_message(gst::message::Element::new(msg_struct.build()));
+
+        // Suppress feedback while any link is in a
+        // saturation-probe window (or its cooldown):
+        // the traffic pin contaminates the receiver
+        // loss/late/jitter signals, and reacting to
+        // it is a self-inflicted disturbance the
+        // encoder loop would otherwise chase.
+        let probe_contaminated = metrics.values().any(|m| m.probe_active);
+
+        // Feed BitrateAdapter and post command if target changed
+        let cmd = match feedback {
+            Some(ref fb) if !probe_contaminated => {
+                self.adapter.update_with_feedback(&link_caps, fb)
+            }
+            _ => self.adapter.update(&link_caps),
+        };
+        if let Some(cmd) = cmd {
+            let msg = gst::Structure::builder("bitrate-command")
+                .field("target-kbps", cmd.target_kbps)
+                .field("reason", format!("{:?}", cmd.reason))
+                .field("stage", format!("{:?}", cmd.stage))
+                .field("mode", format!("{:?}", cmd.mode))
+                .field("spare-bw-kbps", cmd.spare_bw_kbps)
+                .field("fec-overhead", cmd.recommended_fec_overhead)
+                .build();
+            let _ = element.post_message(gst::message::Element::new(msg));
+        }
+    }
+
+    fn event(&mut self, record: &EventRecord) {
+        if let Some(element) = self.element.upgrade() {
+            let _ = element.post_message(gst::message::Element::new(event_structure(record)));
+        }
+    }
+}
+
+/// How long EOS waits for the tail of the stream to be acknowledged before
+/// tearing the links down anyway.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
 
 fn parse_config(config: &str) -> Result<BondingConfig, String> {
     BondingConfig::from_toml_str(config)
 }
 
+/// Parse the `link-weights` property: comma-separated weights indexed by
+/// link ID, each clamped with [`clamp_link_weight`]. Empty clears them.
+fn parse_link_weights(s: &str) -> Result<Vec<f64>, String> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    s.split(',')
+        .map(|w| {
+            let w = w.trim();
+            w.parse::<f64>()
+                .ok()
+                .filter(|w| w.is_finite())
+                .map(clamp_link_weight)
+                .ok_or_else(|| format!("invalid weight {w:?}"))
+        })
+        .collect()
+}
+
+fn format_link_weights(weights: &[f64]) -> String {
+    weights
+        .iter()
+        .map(f64::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 #[cfg(test)]
 fn compute_congestion_recommendation(
     total_capacity_bps: f64,
@@ -52,8 +280,6 @@ mod imp {
 
     pub struct StrataSink {
         pub(crate) runtime: Mutex<Option<BondingRuntime>>,
-        pub(crate) stats_thread: Mutex<Option<std::thread::JoinHandle<()>>>,
-        pub(crate) stats_running: Arc<AtomicBool>,
 
         pub(crate) destinations_config: Mutex<String>,
         pub(crate) config_toml: Mutex<String>,
@@ -100,14 +326,16 @@ mod imp {
         /// a static scene makes hardware encoders undershoot their target by
         /// 2× or more, which otherwise reads as a permanent goodput shortfall.
         pub(crate) ingress_rate_bps: std::sync::atomic::AtomicU64,
+        /// Upstream (capture + encode + mux) latency from the last latency
+        /// query, in ms. Reported in the stats so the sender's share of
+        /// the end-to-end delay is visible next to the receiver's.
+        pub(crate) upstream_latency_ms: std::sync::atomic::AtomicU64,
     }
 
     impl Default for StrataSink {
         fn default() -> Self {
             Self {
                 runtime: Mutex::new(None),
-                stats_thread: Mutex::new(None),
-                stats_running: Arc::new(AtomicBool::new(false)),
                 destinations_config: Mutex::new(String::new()),
                 config_toml: Mutex::new(String::new()),
                 metrics_addr: Mutex::new(String::new()),
@@ -124,11 +352,112 @@ mod imp {
                 ingress_bytes_acc: std::sync::atomic::AtomicU64::new(0),
                 ingress_last_log: Mutex::new(std::time::Instant::now()),
                 ingress_rate_bps: std::sync::atomic::AtomicU64::new(0),
+                upstream_latency_ms: std::sync::atomic::AtomicU64::new(0),
             }
         }
     }
 
     impl StrataSink {
+        /// Hand one buffer to the bonding runtime. Congestion drops are
+        /// not flow errors: the packet is gone, the stream carries on.
+        fn send_buffer(
+            &self,
+            runtime: Option<&mut BondingRuntime>,
+            buffer: &gst::BufferRef,
+        ) -> Result<gst::FlowSuccess, gst::FlowError> {
+            let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+            let data = bytes::Bytes::copy_from_slice(&map);
+            let flags = buffer.flags();
+            // The DELTA_UNIT flag is unreliable for muxed streams — mpegtsmux
+            // never sets it, so `!DELTA_UNIT` was true for every buffer. To mark
+            // the loss-critical data we instead use two in-band TS signals that
+            // survive the mux: the HEADER flag (SPS/PPS/VPS, PAT/PMT) and the
+            // random-access-indicator bit on the video PID, which marks IDR
+            // (keyframe) access units. Both are raised to Priority::Critical by
+            // the scheduler so the reference frames the decoder cannot live
+            // without get keyframe-protected drop + (when enabled) cross-link
+            // redundancy/broadcast — instead of the flat treatment that let a
+            // single lost IDR packet grey out a whole GOP.
+            let is_header = flags.contains(gst::BufferFlags::HEADER);
+            let is_keyframe_au = lock_or_recover(&self.ts_keyframe).scan(&map);
+            let is_critical = is_header || is_keyframe_au;
+            let can_drop = flags.contains(gst::BufferFlags::DROPPABLE);
+
+            let profile = PacketProfile {
+                is_critical,
+                can_drop,
+                size_bytes: data.len(),
+            };
+
+            tracing::debug!(
+                target: "strata::sink",
+                size = data.len(),
+                is_critical,
+                can_drop,
+                "render: buffer received"
+            );
+
+            if let Some(rt) = runtime {
+                match rt.try_send_packet(data, profile) {
+                    Ok(_) => (),
+                    Err(PacketSendError::Full) => {
+                        tracing::warn!(
+                            target: "strata::sink",
+                            "ring buffer FULL — dropping packet"
+                        );
+                        if can_drop {
+                            gst::warning!(gst::CAT_DEFAULT, "Congestion dropping expendable frame");
+                        } else {
+                            gst::error!(gst::CAT_DEFAULT, "Congestion dropping critical frame");
+                        }
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+                    Err(PacketSendError::Draining) => {
+                        // EOS already drained the runtime; nothing more goes out.
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+                    Err(PacketSendError::Disconnected) => {
+                        tracing::error!(
+                            target: "strata::sink",
+                            "runtime disconnected!"
+                        );
+                        return Err(gst::FlowError::Error);
+                    }
+                }
+            } else {
+                tracing::warn!(
+                    target: "strata::sink",
+                    "render: runtime is None — data lost"
+                );
+            }
+
+            Ok(gst::FlowSuccess::Ok)
+        }
+
+        /// Diagnostic: throttled encoder+mux egress rate. Compared against
+        /// the adapter's bitrate target, this isolates encoder overshoot
+        /// (source producing far above target) from transport-side flooding.
+        fn account_ingress(&self, bytes: usize) {
+            self.ingress_bytes_acc
+                .fetch_add(bytes as u64, Ordering::Relaxed);
+            if let Ok(mut last) = self.ingress_last_log.lock() {
+                let elapsed = last.elapsed();
+                if elapsed >= std::time::Duration::from_secs(1) {
+                    let bytes = self.ingress_bytes_acc.swap(0, Ordering::Relaxed);
+                    let kbps = (bytes as f64 * 8.0 / 1000.0) / elapsed.as_secs_f64();
+                    self.ingress_rate_bps
+                        .store((kbps * 1000.0) as u64, Ordering::Relaxed);
+                    *last = std::time::Instant::now();
+                    tracing::info!(
+                        target: "strata::sink",
+                        egress_kbps = kbps as u64,
+                        bytes,
+                        "encoder+mux egress rate"
+                    );
+                }
+            }
+        }
+
         pub(crate) fn send_msg(&self, msg: SinkMessage) {
             let runtime = lock_or_recover(&self.runtime);
             if let Some(rt) = &*runtime {
@@ -139,6 +468,7 @@ mod imp {
                             uri,
                             interface: iface,
                             profile: None,
+                            rate_cap_bps: None,
                         });
                     }
                     SinkMessage::RemoveLink { id } => {
@@ -155,6 +485,7 @@ mod imp {
                                 uri,
                                 interface: iface,
                                 profile: None,
+                                rate_cap_bps: None,
                             },
                         );
                     }
@@ -211,8 +542,18 @@ mod imp {
                 return;
             }
             match parse_config(config) {
-                Ok(parsed) => {
-                    *lock_or_recover(&self.scheduler_config) = parsed.scheduler.clone();
+                Ok(mut parsed) => {
+                    let weights_changed = {
+                        let mut current = lock_or_recover(&self.scheduler_config);
+                        // A config without `link_weights` keeps the ones
+                        // set through the property.
+                        if parsed.scheduler.link_weights.is_empty() {
+                            parsed.scheduler.link_weights = current.link_weights.clone();
+                        }
+                        let changed = parsed.scheduler.link_weights != current.link_weights;
+                        *current = parsed.scheduler.clone();
+                        changed
+                    };
                     self.receiver_max_latency_ms.store(
                         parsed.receiver.max_latency.as_millis() as u32,
                         Ordering::Relaxed,
@@ -220,6 +561,9 @@ mod imp {
                     if let Some(rt) = lock_or_recover(&self.runtime).as_ref() {
                         let _ = rt.apply_config(parsed);
                     }
+                    if weights_changed {
+                        self.obj().notify("link-weights");
+                    }
                 }
                 Err(err) => {
                     gst::warning!(gst::CAT_DEFAULT, "{}", err);
@@ -227,6 +571,14 @@ mod imp {
             }
         }
 
+        /// Replace the per-link scheduling weights, live if running.
+        fn set_link_weights(&self, weights: Vec<f64>) {
+            lock_or_recover(&self.scheduler_config).link_weights = weights.clone();
+            if let Some(rt) = lock_or_recover(&self.runtime).as_ref() {
+                rt.set_link_weights(weights);
+            }
+        }
+
         fn reconfigure_destinations(&self) {
             let config = lock_or_recover(&self.destinations_config).clone();
             if config.is_empty() {
@@ -278,6 +630,11 @@ mod imp {
                         .blurb("Prometheus metrics server address (e.g. 0.0.0.0:9090). Empty to disable.")
                         .mutable_ready()
                         .build(),
+                    glib::ParamSpecString::builder("link-weights")
+                        .nick("Link Weights")
+                        .blurb("Comma-separated scheduling weight per link, indexed by link ID (0-100, default 1.0; 0 = last resort only). Also set by the config's scheduler.link_weights.")
+                        .mutable_playing()
+                        .build(),
                 ]
             })
         }
@@ -298,6 +655,20 @@ mod imp {
                     *lock_or_recover(&self.metrics_addr) =
                         value.get().expect("type checked upstream");
                 }
+                "link-weights" => {
+                    let spec: String = value.get().expect("type checked upstream");
+                    match parse_link_weights(&spec) {
+                        Ok(weights) => self.set_link_weights(weights),
+                        Err(e) => {
+                            gst::warning!(
+                                gst::CAT_DEFAULT,
+                                "Invalid link-weights '{}': {}",
+                                spec,
+                                e
+                            );
+                        }
+                    }
+                }
                 "config-file" => {
                     let path: String = value.get().expect("type checked upstream");
                     if path.is_empty() {
@@ -337,6 +708,10 @@ mod imp {
                 "destinations" => lock_or_recover(&self.destinations_config).to_value(),
                 "config" | "config-file" => lock_or_recover(&self.config_toml).to_value(),
                 "metrics-addr" => lock_or_recover(&self.metrics_addr).to_value(),
+                "link-weights" => {
+                    format_link_weights(&lock_or_recover(&self.scheduler_config).link_weights)
+                        .to_value()
+                }
                 _ => {
                     gst::warning!(gst::CAT_DEFAULT, "Unknown property: {}", pspec.name());
                     "".to_value()
@@ -439,7 +814,40 @@ mod imp {
     impl BaseSinkImpl for StrataSink {
         fn start(&self) -> Result<(), gst::ErrorMessage> {
             let sched_cfg = lock_or_recover(&self.scheduler_config).clone();
-            let mut runtime = BondingRuntime::with_config(sched_cfg.clone());
+            let mut runtime = BondingRuntime::with_config(sched_cfg);
+
+            let adapt_min = self.adaptation_min_kbps.load(Ordering::Relaxed);
+            let adapt_max = self.adaptation_max_kbps.load(Ordering::Relaxed);
+            let adapt_initial = self.adaptation_initial_kbps.load(Ordering::Relaxed);
+            let adapt_startup_ramp_ms = self.adaptation_startup_ramp_ms.load(Ordering::Relaxed);
+            let adapt_startup_floor = self.adaptation_startup_floor_kbps.load(Ordering::Relaxed);
+            let receiver_max_latency_ms = self.receiver_max_latency_ms.load(Ordering::Relaxed);
+
+            let default_cfg = AdaptationConfig::default();
+            let adapter = BitrateAdapter::new(AdaptationConfig {
+                max_bitrate_kbps: adapt_max,
+                min_bitrate_kbps: adapt_min,
+                initial_bitrate_kbps: adapt_initial,
+                startup_ramp: Duration::from_millis(adapt_startup_ramp_ms as u64),
+                startup_floor_kbps: if adapt_startup_floor > 0 {
+                    adapt_startup_floor
+                } else {
+                    default_cfg.startup_floor_kbps
+                },
+                jitter_buffer_ceiling_ms: if receiver_max_latency_ms > 0 {
+                    receiver_max_latency_ms
+                } else {
+                    default_cfg.jitter_buffer_ceiling_ms
+                },
+                ..default_cfg
+            });
+            // Before any link is added: the sink's event subscription starts
+            // here, and LinkAdded for the initial links must reach the bus.
+            runtime.add_telemetry_sink(BusSink {
+                element: self.obj().downgrade(),
+                adapter,
+                quality: QualityMonitor::new(),
+            });
 
             // Start Prometheus metrics server if configured
             let metrics_addr_str = lock_or_recover(&self.metrics_addr).clone();
@@ -473,7 +881,6 @@ mod imp {
                 }
             }
 
-            let metrics_handle = runtime.metrics_handle();
             *lock_or_recover(&self.runtime) = Some(runtime);
 
             for pad in self.obj().pads() {
@@ -495,203 +902,31 @@ mod imp {
             self.reconfigure_destinations();
             self.apply_config(&lock_or_recover(&self.config_toml));
 
-            let element_weak = self.obj().downgrade();
-            let running = self.stats_running.clone();
-            running.store(true, Ordering::Relaxed);
-
-            let adapt_min = self.adaptation_min_kbps.load(Ordering::Relaxed);
-            let adapt_max = self.adaptation_max_kbps.load(Ordering::Relaxed);
-            let adapt_initial = self.adaptation_initial_kbps.load(Ordering::Relaxed);
-            let adapt_startup_ramp_ms = self.adaptation_startup_ramp_ms.load(Ordering::Relaxed);
-            let adapt_startup_floor = self.adaptation_startup_floor_kbps.load(Ordering::Relaxed);
-            let receiver_max_latency_ms = self.receiver_max_latency_ms.load(Ordering::Relaxed);
-
-            let handle = std::thread::Builder::new()
-                .name("strata-stats".into())
-                .spawn(move || {
-                    let stats_interval = Duration::from_millis(sched_cfg.stats_interval_ms);
-                    let mut last_stats = Instant::now();
-                    let start = Instant::now();
-                    let mut stats_seq: u64 = 0;
-
-                    let default_cfg = AdaptationConfig::default();
-                    let mut adapter = BitrateAdapter::new(AdaptationConfig {
-                        max_bitrate_kbps: adapt_max,
-                        min_bitrate_kbps: adapt_min,
-                        initial_bitrate_kbps: adapt_initial,
-                        startup_ramp: Duration::from_millis(adapt_startup_ramp_ms as u64),
-                        startup_floor_kbps: if adapt_startup_floor > 0 {
-                            adapt_startup_floor
-                        } else {
-                            default_cfg.startup_floor_kbps
-                        },
-                        jitter_buffer_ceiling_ms: if receiver_max_latency_ms > 0 {
-                            receiver_max_latency_ms
-                        } else {
-                            default_cfg.jitter_buffer_ceiling_ms
-                        },
-                        ..default_cfg
-                    });
-
-                    while running.load(Ordering::Relaxed) {
-                        if last_stats.elapsed() >= stats_interval {
-                            if let Some(element) = element_weak.upgrade() {
-                                let metrics = lock_or_recover(&metrics_handle).clone();
-                                let mono_time_ns = start.elapsed().as_nanos() as u64;
-                                let wall_time_ms = SystemTime::now()
-                                    .duration_since(UNIX_EPOCH)
-                                    .map(|d| d.as_millis() as u64)
-                                    .unwrap_or(0);
-
-                                let mut total_capacity = 0.0;
-                                let mut alive_links = 0u64;
-                                let mut link_caps = Vec::new();
-                                for (&id, m) in &metrics {
-                                    link_caps.push(LinkCapacity {
-                                        link_id: id,
-                                        capacity_kbps: m.capacity_bps / 1000.0,
-                                        alive: m.alive,
-                                        loss_rate: m.loss_rate,
-                                        rtt_ms: m.rtt_ms,
-                                        queue_depth: m.transport.as_ref().map(|_| m.queue_depth),
-                                        drain_rate_kbps: (m.pacing_rate_bps > 0.0)
-                                            .then_some(m.pacing_rate_bps / 1000.0),
-                                        aqm_dropped_total: m
-                                            .transport
-                                            .as_ref()
-                                            .map(|_| m.aqm_dropped_total),
-                                    });
-                                    if m.alive {
-                                        total_capacity += m.capacity_bps;
-                                        alive_links += 1;
-                                    }
-                                }
-
-                                let mut msg_struct = gst::Structure::builder("strata-stats")
-                                    .field("schema_version", 1i32)
-                                    .field("stats_seq", stats_seq)
-                                    .field("heartbeat", true)
-                                    .field("mono_time_ns", mono_time_ns)
-                                    .field("wall_time_ms", wall_time_ms)
-                                    .field("total_capacity", total_capacity)
-                                    .field("alive_links", alive_links);
-                                for (id, m) in &metrics {
-                                    let os_up =
-                                        m.os_up.map(|v| if v { 1i32 } else { 0i32 }).unwrap_or(-1);
-                                    let mtu = m.mtu.map(|v| v as i32).unwrap_or(-1);
-                                    let iface = m.iface.as_deref().unwrap_or("");
-                                    let link_kind = m.link_kind.as_deref().unwrap_or("");
-                                    msg_struct = msg_struct
-                                        .field(format!("link_{}_rtt", id), m.rtt_ms)
-                                        .field(format!("link_{}_capacity", id), m.capacity_bps)
-                                        .field(format!("link_{}_loss", id), m.loss_rate)
-                                        .field(format!("link_{}_observed_bps", id), m.observed_bps)
-                                        .field(
-                                            format!("link_{}_observed_bytes", id),
-                                            m.observed_bytes,
-                                        )
-                                        .field(format!("link_{}_alive", id), m.alive)
-                                        .field(format!("link_{}_phase", id), m.phase.as_str())
-                                        .field(format!("link_{}_os_up", id), os_up)
-                                        .field(format!("link_{}_mtu", id), mtu)
-                                        .field(format!("link_{}_iface", id), iface)
-                                        .field(format!("link_{}_kind", id), link_kind);
-                                    if let Some(bw) = m.btlbw_bps {
-                                        msg_struct =
-                                            msg_struct.field(format!("link_{}_btlbw_bps", id), bw);
-                                    }
-                                    if let Some(rtp) = m.rtprop_ms {
-                                        msg_struct =
-                                            msg_struct.field(format!("link_{}_rtprop_ms", id), rtp);
-                                    }
-                                }
-                                let _ = element
-                                    .post_message(gst::message::Element::new(msg_struct.build()));
-
-                                // Aggregate receiver reports into feedback
-                                let mut total_goodput = 0;
-                                let mut max_jitter = 0;
-                                let mut total_loss_weight = 0.0;
-                                let mut total_fec_weight = 0.0;
-                                let mut total_late_weight = 0.0;
-                                let mut weight_sum = 0.0;
-                                let mut has_report = false;
-
-                                for m in metrics.values() {
-                                    if let Some(r) = &m.receiver_report {
-                                        has_report = true;
-                                        total_goodput += r.goodput_bps;
-                                        max_jitter = max_jitter.max(r.jitter_buffer_ms);
-                                        // Weight loss by goodput (with a floor so 0-goodput links still count)
-                                        let weight = (r.goodput_bps as f64).max(10_000.0);
-                                        total_loss_weight += r.loss_after_fec as f64 * weight;
-                                        total_fec_weight += r.fec_repair_rate as f64 * weight;
-                                        total_late_weight += r.late_rate as f64 * weight;
-                                        weight_sum += weight;
-                                    }
-                                }
-
-                                let feedback = if has_report && weight_sum > 0.0 {
-                                    Some(ReceiverFeedback {
-                                        goodput_bps: total_goodput,
-                                        fec_repair_rate: (total_fec_weight / weight_sum) as f32,
-                                        jitter_buffer_ms: max_jitter,
-                                        loss_after_fec: (total_loss_weight / weight_sum) as f32,
-                                        late_rate: (total_late_weight / weight_sum) as f32,
-                                        offered_bps: element
-                                            .imp()
-                                            .ingress_rate_bps
-                                            .load(Ordering::Relaxed),
-                                    })
-                                } else {
-                                    None
-                                };
-
-                                // Suppress feedback while any link is in a
-                                // saturation-probe window (or its cooldown):
-                                // the traffic pin contaminates the receiver
-                                // loss/late/jitter signals, and reacting to
-                                // it is a self-inflicted disturbance the
-                                // encoder loop would otherwise chase.
-                                let probe_contaminated = metrics.values().any(|m| m.probe_active);
-
-                                // Feed BitrateAdapter and post command if target changed
-                                let cmd = match feedback {
-                                    Some(ref fb) if !probe_contaminated => {
-                                        adapter.update_with_feedback(&link_caps, fb)
-                                    }
-                                    _ => adapter.update(&link_caps),
-                                };
-                                if let Some(cmd) = cmd {
-                                    let msg = gst::Structure::builder("bitrate-command")
-                                        .field("target-kbps", cmd.target_kbps)
-                                        .field("reason", format!("{:?}", cmd.reason))
-                                        .field("stage", format!("{:?}", cmd.stage))
-                                        .field("mode", format!("{:?}", cmd.mode))
-                                        .field("spare-bw-kbps", cmd.spare_bw_kbps)
-                                        .field("fec-overhead", cmd.recommended_fec_overhead)
-                                        .build();
-                                    let _ = element.post_message(gst::message::Element::new(msg));
-                                }
-                            }
-                            stats_seq = stats_seq.wrapping_add(1);
-                            last_stats = Instant::now();
-                        }
-                        std::thread::sleep(Duration::from_millis(50));
-                    }
-                })
-                .expect("failed to spawn stats thread");
-
-            *lock_or_recover(&self.stats_thread) = Some(handle);
             Ok(())
         }
 
-        fn stop(&self) -> Result<(), gst::ErrorMessage> {
-            self.stats_running.store(false, Ordering::Relaxed);
-            if let Some(handle) = lock_or_recover(&self.stats_thread).take() {
-                let _ = handle.join();
+        fn event(&self, event: gst::Event) -> bool {
+            // EOS is the one point where everything upstream has been
+            // rendered: drain so the tail is acknowledged and the receiver
+            // sees a clean end of stream rather than a timeout.
+            if let gst::EventView::Eos(_) = event.view()
+                && let Some(rt) = lock_or_recover(&self.runtime).as_mut()
+            {
+                match rt.drain(DRAIN_TIMEOUT) {
+                    Ok(report) => gst::info!(
+                        gst::CAT_DEFAULT,
+                        "Drained: {} queued packets flushed, {} unacked{}",
+                        report.packets_flushed,
+                        report.unacked,
+                        if report.timed_out { " (timed out)" } else { "" }
+                    ),
+                    Err(e) => gst::warning!(gst::CAT_DEFAULT, "Drain failed: {}", e),
+                }
             }
+            self.parent_event(event)
+        }
 
+        fn stop(&self) -> Result<(), gst::ErrorMessage> {
             if let Some(mut runtime) = lock_or_recover(&self.runtime).take() {
                 runtime.shutdown();
             }
@@ -699,99 +934,44 @@ mod imp {
         }
 
         fn render(&self, buffer: &gst::Buffer) -> Result<gst::FlowSuccess, gst::FlowError> {
-            let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
-            let data = bytes::Bytes::copy_from_slice(&map);
-
-            let flags = buffer.flags();
-            // The DELTA_UNIT flag is unreliable for muxed streams — mpegtsmux
-            // never sets it, so `!DELTA_UNIT` was true for every buffer. To mark
-            // the loss-critical data we instead use two in-band TS signals that
-            // survive the mux: the HEADER flag (SPS/PPS/VPS, PAT/PMT) and the
-            // random-access-indicator bit on the video PID, which marks IDR
-            // (keyframe) access units. Both are raised to Priority::Critical by
-            // the scheduler so the reference frames the decoder cannot live
-            // without get keyframe-protected drop + (when enabled) cross-link
-            // redundancy/broadcast — instead of the flat treatment that let a
-            // single lost IDR packet grey out a whole GOP.
-            let is_header = flags.contains(gst::BufferFlags::HEADER);
-            let is_keyframe_au = lock_or_recover(&self.ts_keyframe).scan(&map);
-            let is_critical = is_header || is_keyframe_au;
-            let can_drop = flags.contains(gst::BufferFlags::DROPPABLE);
-
-            let profile = PacketProfile {
-                is_critical,
-                can_drop,
-                size_bytes: data.len(),
-            };
-
-            tracing::debug!(
-                target: "strata::sink",
-                size = data.len(),
-                is_critical,
-                can_drop,
-                "render: buffer received"
-            );
+            self.account_ingress(buffer.size());
+            let mut runtime = lock_or_recover(&self.runtime);
+            self.send_buffer(runtime.as_mut(), buffer)
+        }
 
-            // Diagnostic: throttled encoder+mux egress rate. Compared against
-            // the adapter's bitrate target, this isolates encoder overshoot
-            // (source producing far above target) from transport-side flooding.
-            self.ingress_bytes_acc
-                .fetch_add(data.len() as u64, Ordering::Relaxed);
-            if let Ok(mut last) = self.ingress_last_log.lock() {
-                let elapsed = last.elapsed();
-                if elapsed >= std::time::Duration::from_secs(1) {
-                    let bytes = self.ingress_bytes_acc.swap(0, Ordering::Relaxed);
-                    let kbps = (bytes as f64 * 8.0 / 1000.0) / elapsed.as_secs_f64();
-                    self.ingress_rate_bps
-                        .store((kbps * 1000.0) as u64, Ordering::Relaxed);
-                    *last = std::time::Instant::now();
-                    tracing::info!(
-                        target: "strata::sink",
-                        egress_kbps = kbps as u64,
-                        bytes,
-                        "encoder+mux egress rate"
-                    );
-                }
+        /// Muxers push batched TS packets as buffer lists; take the runtime
+        /// lock once for the batch rather than once per packet.
+        fn render_list(&self, list: &gst::BufferList) -> Result<gst::FlowSuccess, gst::FlowError> {
+            self.account_ingress(list.calculate_size());
+            let mut runtime = lock_or_recover(&self.runtime);
+            for buffer in list.iter() {
+                self.send_buffer(runtime.as_mut(), buffer)?;
             }
+            Ok(gst::FlowSuccess::Ok)
+        }
 
-            if let Some(rt) = lock_or_recover(&self.runtime).as_mut() {
-                match rt.try_send_packet(data, profile) {
-                    Ok(_) => (),
-                    Err(PacketSendError::Full) => {
-                        tracing::warn!(
-                            target: "strata::sink",
-                            "ring buffer FULL — dropping packet"
-                        );
-                        if can_drop {
-                            gst::warning!(gst::CAT_DEFAULT, "Congestion dropping expendable frame");
-                        } else {
-                            gst::error!(gst::CAT_DEFAULT, "Congestion dropping critical frame");
-                        }
-                        return Ok(gst::FlowSuccess::Ok);
-                    }
-                    Err(PacketSendError::Disconnected) => {
-                        tracing::error!(
-                            target: "strata::sink",
-                            "runtime disconnected!"
-                        );
-                        return Err(gst::FlowError::Error);
-                    }
-                }
-            } else {
-                tracing::warn!(
-                    target: "strata::sink",
-                    "render: runtime is None — data lost"
-                );
+        fn query(&self, query: &mut gst::QueryRef) -> bool {
+            // The base class answers from upstream plus its own render
+            // delay; keep the upstream figure, the sender's share of the
+            // glass-to-glass budget, for the stats.
+            let handled = BaseSinkImplExt::parent_query(self, query);
+            if handled
+                && let gst::QueryView::Latency(q) = query.view()
+                && let (true, min, _) = q.result()
+            {
+                self.upstream_latency_ms
+                    .store(min.mseconds(), Ordering::Relaxed);
             }
-
-            Ok(gst::FlowSuccess::Ok)
+            handled
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{compute_congestion_recommendation, parse_config};
+    use super::{
+        compute_congestion_recommendation, format_link_weights, parse_config, parse_link_weights,
+    };
 
     #[test]
     fn parse_config_links_basic() {
@@ -857,6 +1037,16 @@ mod tests {
         assert!(cfg.links[1].interface.is_none());
     }
 
+    #[test]
+    fn link_weights_round_trip_and_reject_garbage() {
+        let weights = parse_link_weights("1, 0.5,0,250").unwrap();
+        assert_eq!(weights, vec![1.0, 0.5, 0.0, 100.0]);
+        assert_eq!(format_link_weights(&weights), "1,0.5,0,100");
+        assert_eq!(parse_link_weights(" ").unwrap(), Vec::<f64>::new());
+        assert!(parse_link_weights("1,,2").is_err());
+        assert!(parse_link_weights("1,NaN").is_err());
+    }
+
     #[test]
     fn congestion_recommendation_respects_headroom() {
         let recommended = compute_congestion_recommendation(10_000_000.0, 9_200_000.0, 0.85, 0.90);
@@ -4,11 +4,20 @@ use gst::prelude::*;
 use gst::subclass::prelude::*;
 use gst_base::prelude::BaseSrcExt;
 use gst_base::subclass::prelude::*;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strata_bonding::receiver::ReceiverBackend;
 use strata_bonding::receiver::aggregator::ReassemblyConfig;
+use strata_bonding::receiver::transport::DeliveredPayload;
+
+/// Most packets `create()` folds into one buffer list. Bounds how far a
+/// burst released by reassembly can hold back the first packet's push.
+const MAX_LIST_PACKETS: usize = 64;
+
+/// How far the reassembly target may move from the latency last answered
+/// before the pipeline is asked to query it again.
+const LATENCY_REANNOUNCE_MS: u64 = 20;
 
 mod imp {
     use super::*;
@@ -18,6 +27,9 @@ mod imp {
         latency: u32,
         max_latency_ms: u64,
         config_toml: String,
+        /// From the config's `ingest_key`; when set, each link only
+        /// accepts sources that present it.
+        ingest_key: Option<String>,
     }
 
     impl Default for Settings {
@@ -27,6 +39,7 @@ mod imp {
                 latency: 50,
                 max_latency_ms: 800,
                 config_toml: String::new(),
+                ingest_key: None,
             }
         }
     }
@@ -45,9 +58,86 @@ mod imp {
         /// segment are not negotiated yet); the startup discont needs no
         /// event anyway — the gate accepts the first keyframe regardless.
         first_buffer_sent: AtomicBool,
+        /// A discontinuous packet drained while filling a buffer list. It
+        /// opens the next push instead, so its strata/discont event still
+        /// goes out ahead of it.
+        pending: Mutex<Option<DeliveredPayload>>,
+        /// Minimum latency in the last answered latency query (ms); 0
+        /// until the pipeline has asked.
+        announced_latency_ms: AtomicU64,
     }
 
     impl StrataSrc {
+        /// Latency reassembly adds: the jitter buffer's current target once
+        /// running, the configured start latency before that.
+        fn reassembly_latency_ms(&self) -> u64 {
+            let target = lock_or_recover(&self.receiver)
+                .as_ref()
+                .map(|r| r.get_stats().target_latency_ms)
+                .filter(|&ms| ms > 0);
+            target.unwrap_or_else(|| u64::from(lock_or_recover(&self.settings).latency))
+        }
+
+        /// Wrap a delivered packet, flagging (and announcing) a discont.
+        fn packet_buffer(&self, (bytes, discont): DeliveredPayload) -> gst::Buffer {
+            let mut buffer = gst::Buffer::from_slice(bytes);
+            if discont {
+                let buf_ref = buffer.get_mut().unwrap();
+                buf_ref.set_flags(gst::BufferFlags::DISCONT);
+                // The DISCONT flag does not survive tsdemux: the
+                // demuxer re-times its output and the flag never
+                // reaches the delivered-stream gate on the parser
+                // pad, so splices decoded as grey frames. Custom
+                // serialized events ARE forwarded by
+                // tsparse/tsdemux/baseparse — push one ahead of
+                // the buffer so the gate can resync. Skipped
+                // before the first buffer (startup discont):
+                // serialized events may not precede caps/segment.
+                if self.first_buffer_sent.load(Ordering::SeqCst) {
+                    let ev = gst::event::CustomDownstream::new(
+                        gst::Structure::builder("strata/discont").build(),
+                    );
+                    if let Some(pad) = self.obj().static_pad("src") {
+                        let _ = pad.push_event(ev);
+                    }
+                }
+            }
+            self.first_buffer_sent.store(true, Ordering::SeqCst);
+            buffer
+        }
+
+        /// Block for the next delivered packet. Uses recv_timeout so the
+        /// flushing flag is checked periodically instead of blocking
+        /// forever (which would prevent unlock() from taking effect).
+        fn recv(
+            &self,
+            rx: &crossbeam_channel::Receiver<DeliveredPayload>,
+        ) -> Result<DeliveredPayload, gst::FlowError> {
+            loop {
+                if self.flushing.load(Ordering::SeqCst) {
+                    return Err(gst::FlowError::Flushing);
+                }
+                match rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                    Ok(packet) => return Ok(packet),
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                        // The sender drained and tore down every link:
+                        // end the stream here instead of idling until the
+                        // pipeline is stopped from outside.
+                        if lock_or_recover(&self.receiver)
+                            .as_ref()
+                            .is_some_and(|r| r.is_end_of_stream())
+                        {
+                            gst::info!(gst::CAT_DEFAULT, "StrataSrc: sender ended the stream");
+                            return Err(gst::FlowError::Eos);
+                        }
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                        return Err(gst::FlowError::Eos);
+                    }
+                }
+            }
+        }
+
         fn apply_config_toml(&self, toml_str: &str) {
             if toml_str.trim().is_empty() {
                 return;
@@ -58,6 +148,7 @@ mod imp {
                     settings.config_toml = toml_str.to_string();
                     settings.latency = cfg.receiver.start_latency.as_millis() as u32;
                     settings.max_latency_ms = cfg.scheduler.max_latency_ms;
+                    settings.ingest_key = cfg.ingest_key;
                     if !cfg.links.is_empty() {
                         settings.links = cfg
                             .links
@@ -245,6 +336,11 @@ mod imp {
                 max_latency_ms,
                 ..ReassemblyConfig::default()
             });
+            receiver
+                .set_ingest_key(settings.ingest_key.as_deref().map(str::as_bytes))
+                .map_err(|e| {
+                    gst::error_msg!(gst::ResourceError::Settings, ["Invalid ingest key: {}", e])
+                })?;
 
             for link in settings.links.split(',') {
                 let link = link.trim();
@@ -284,6 +380,20 @@ mod imp {
                                 && let Some(receiver) = &*receiver_guard
                             {
                                 let stats = receiver.get_stats();
+                                // Reassembly retargets as jitter changes;
+                                // once it has moved far from what downstream
+                                // budgeted for, have the pipeline re-query.
+                                let announced = imp.announced_latency_ms.load(Ordering::Relaxed);
+                                if announced > 0
+                                    && stats.target_latency_ms.abs_diff(announced)
+                                        >= LATENCY_REANNOUNCE_MS
+                                {
+                                    imp.announced_latency_ms
+                                        .store(stats.target_latency_ms, Ordering::Relaxed);
+                                    let _ = element.post_message(
+                                        gst::message::Latency::builder().src(&element).build(),
+                                    );
+                                }
                                 // A link is "alive" at the receiver iff its
                                 // cumulative rx count advanced since the last
                                 // tick (actively delivering now) — a frozen or
@@ -323,7 +433,15 @@ mod imp {
                                     .field("packets_delivered", stats.packets_delivered)
                                     .field("smoothed_loss_rate", stats.loss_rate)
                                     .field("loss_rate", stats.loss_rate)
-                                    .field("jitter_estimate_ms", stats.jitter_estimate_ms);
+                                    .field("jitter_estimate_ms", stats.jitter_estimate_ms)
+                                    .field("redundant_copies", stats.redundant_copies);
+                                if let Some(av) = &stats.av_sync {
+                                    msg = msg
+                                        .field("av_skew_ms", av.av_skew_ms)
+                                        .field("av_drift_ms", av.av_drift_ms)
+                                        .field("pcr_jitter_ms", av.pcr_jitter_ms)
+                                        .field("video_pts_lead_ms", av.video_pts_lead_ms);
+                                }
                                 for link in &stats.per_link {
                                     msg = msg
                                         .field(
@@ -341,7 +459,28 @@ mod imp {
                                         .field(
                                             format!("bytes_received_link_{}", link.link_id),
                                             link.bytes_received,
+                                        )
+                                        .field(format!("owd_ms_link_{}", link.link_id), link.owd_ms)
+                                        .field(
+                                            format!("loss_burst_link_{}", link.link_id),
+                                            link.loss_pattern.mean_run_length(),
+                                        )
+                                        .field(
+                                            format!("redundant_copies_link_{}", link.link_id),
+                                            link.redundant_copies,
+                                        );
+                                    if let Some(offset) = link.clock_offset_us {
+                                        msg = msg.field(
+                                            format!("clock_offset_us_link_{}", link.link_id),
+                                            offset,
+                                        );
+                                    }
+                                    if let Some(drift) = link.clock_drift_ppm {
+                                        msg = msg.field(
+                                            format!("clock_drift_ppm_link_{}", link.link_id),
+                                            drift,
                                         );
+                                    }
                                 }
                                 let _ =
                                     element.post_message(gst::message::Element::new(msg.build()));
@@ -370,9 +509,29 @@ mod imp {
             if let Some(mut receiver) = receiver_guard.take() {
                 receiver.shutdown();
             }
+            lock_or_recover(&self.pending).take();
+            self.announced_latency_ms.store(0, Ordering::Relaxed);
             Ok(())
         }
 
+        fn query(&self, query: &mut gst::QueryRef) -> bool {
+            if let gst::QueryViewMut::Latency(q) = query.view_mut() {
+                // Packets wait in reassembly for at least its target before
+                // they are pushed, and never longer than the configured
+                // ceiling: that is the window downstream has to budget for.
+                let min_ms = self.reassembly_latency_ms();
+                let max_ms = lock_or_recover(&self.settings).max_latency_ms.max(min_ms);
+                self.announced_latency_ms.store(min_ms, Ordering::Relaxed);
+                q.set(
+                    true,
+                    gst::ClockTime::from_mseconds(min_ms),
+                    gst::ClockTime::from_mseconds(max_ms),
+                );
+                return true;
+            }
+            BaseSrcImplExt::parent_query(self, query)
+        }
+
         fn unlock(&self) -> Result<(), gst::ErrorMessage> {
             // Signal the blocking recv() in create() to bail out.
             // We must NOT call receiver.shutdown() here — that permanently
@@ -403,51 +562,44 @@ mod imp {
             };
             drop(receiver_guard);
 
-            // Use recv_timeout so we can check the flushing flag
-            // periodically instead of blocking forever (which would
-            // prevent unlock() from taking effect).
-            loop {
-                if self.flushing.load(Ordering::SeqCst) {
-                    return Err(gst::FlowError::Flushing);
-                }
-                match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                    Ok((bytes, discont)) => {
-                        let mut buffer = gst::Buffer::from_slice(bytes);
-                        if discont {
-                            let buf_ref = buffer.get_mut().unwrap();
-                            buf_ref.set_flags(gst::BufferFlags::DISCONT);
-                            // The DISCONT flag does not survive tsdemux: the
-                            // demuxer re-times its output and the flag never
-                            // reaches the delivered-stream gate on the parser
-                            // pad, so splices decoded as grey frames. Custom
-                            // serialized events ARE forwarded by
-                            // tsparse/tsdemux/baseparse — push one ahead of
-                            // the buffer so the gate can resync. Skipped
-                            // before the first buffer (startup discont):
-                            // serialized events may not precede caps/segment.
-                            if self.first_buffer_sent.load(Ordering::SeqCst) {
-                                let ev = gst::event::CustomDownstream::new(
-                                    gst::Structure::builder("strata/discont").build(),
-                                );
-                                if let Some(pad) = self.obj().static_pad("src") {
-                                    let _ = pad.push_event(ev);
-                                }
-                            }
-                        } else {
-                            // Suppress tracing for every packet to avoid log spam
-                            // println!("StrataSrc: producing buffer");
-                        }
-                        self.first_buffer_sent.store(true, Ordering::SeqCst);
-                        return Ok(gst_base::subclass::base_src::CreateSuccess::NewBuffer(
-                            buffer,
-                        ));
-                    }
-                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => continue,
-                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
-                        return Err(gst::FlowError::Eos);
+            // A discont held back from the previous list opens this push.
+            let first = lock_or_recover(&self.pending).take();
+            let first = match first {
+                Some(packet) => packet,
+                None => self.recv(&rx)?,
+            };
+            let buffer = self.packet_buffer(first);
+
+            // Whatever reassembly has already released goes out in the same
+            // push, as a buffer list: a burst after a reorder gap is one
+            // downstream traversal instead of dozens.
+            let mut rest = Vec::new();
+            while rest.len() + 1 < MAX_LIST_PACKETS {
+                match rx.try_recv() {
+                    Ok((bytes, false)) => rest.push(gst::Buffer::from_slice(bytes)),
+                    Ok(discont) => {
+                        *lock_or_recover(&self.pending) = Some(discont);
+                        break;
                     }
+                    Err(_) => break,
+                }
+            }
+            if rest.is_empty() {
+                return Ok(gst_base::subclass::base_src::CreateSuccess::NewBuffer(
+                    buffer,
+                ));
+            }
+            let mut list = gst::BufferList::new_sized(rest.len() + 1);
+            {
+                let list = list.get_mut().unwrap();
+                list.add(buffer);
+                for buffer in rest {
+                    list.add(buffer);
                 }
             }
+            Ok(gst_base::subclass::base_src::CreateSuccess::NewBufferList(
+                list,
+            ))
         }
     }
 }
@@ -1,52 +1,86 @@
 //! REST API request/response types shared by the control plane (which
 //! serves them) and the dashboard (which consumes them).
 //!
-//! Types the dashboard never touches (e.g. the receivers admin API) stay
-//! local to `strata-control` — this module only holds shapes that cross
-//! the server/browser boundary, so a field change breaks both sides at
-//! compile time instead of silently breaking the UI.
+//! Types the dashboard never touches stay local to `strata-control` —
+//! this module only holds shapes that cross the server/browser boundary,
+//! so a field change breaks both sides at compile time instead of
+//! silently breaking the UI. Request bodies included: a handler and the
+//! dashboard helper that calls it name the same struct.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{MediaInput, NetworkInterface, StreamState};
+use crate::ids::{DestinationId, SenderId, StreamId, UserId};
+use crate::models::{AlertSeverity, IngestProtocol, MediaInput, NetworkInterface, StreamState};
+use crate::payloads::{ReceiverStreamStatsPayload, StreamStatsPayload};
+use crate::telemetry::{Bps, LinkSample, Millis, TelemetrySample};
 
 // ── Auth ────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterRequest {
     pub email: String,
     pub password: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterResponse {
-    pub user_id: String,
+    pub user_id: UserId,
     pub email: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LoginResponse {
     pub token: String,
-    pub user_id: String,
+    pub user_id: UserId,
     pub role: String,
+    /// What the new session may do; see [`MeResponse::capabilities`].
+    #[serde(default)]
+    pub capabilities: Vec<Capability>,
+}
+
+/// `POST /api/auth/forgot-password`. Always accepted, whether or not the
+/// email belongs to an account, so it can't be used to probe for users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+/// `POST /api/auth/reset-password` and `POST /api/auth/accept-invite`:
+/// the token from a reset or invitation link and the password to set.
+/// Answered with a [`LoginResponse`] for the new session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetPasswordRequest {
+    pub token: String,
+    pub password: String,
 }
 
 /// Error body returned by every failing endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ApiErrorResponse {
     pub error: String,
+    /// Absent from servers that predate error codes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<crate::ErrorCode>,
 }
 
 // ── Senders ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SenderSummary {
     pub id: String,
     pub name: Option<String>,
@@ -54,9 +88,14 @@ pub struct SenderSummary {
     pub online: bool,
     pub last_seen_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    /// Set for an ingest sender (see [`CreateIngestSenderRequest`]);
+    /// `None` for a Strata device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<IngestProtocol>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SenderDetail {
     pub id: String,
     pub owner_id: String,
@@ -66,24 +105,46 @@ pub struct SenderDetail {
     pub online: bool,
     pub last_seen_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<IngestProtocol>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateSenderRequest {
     pub name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateSenderResponse {
-    pub sender_id: String,
+    pub sender_id: SenderId,
     pub enrollment_token: String,
 }
 
+/// `POST /api/senders/ingest` — register a plain SRT/RTMP contributor
+/// (e.g. a phone running Larix) as a sender. It has no agent and never
+/// enrolls: starting a stream on it returns the URL to push to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateIngestSenderRequest {
+    pub name: Option<String>,
+    pub protocol: IngestProtocol,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateIngestSenderResponse {
+    pub sender_id: SenderId,
+    pub protocol: IngestProtocol,
+}
+
 /// Response of `GET /api/senders/:id/status` — the last cached
 /// `device.status` heartbeat plus identity/online flags. Everything is
 /// optional because a sender that has never sent a heartbeat has no
 /// cached status.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SenderFullStatus {
     pub sender_id: Option<String>,
     pub online: Option<bool>,
@@ -96,16 +157,151 @@ pub struct SenderFullStatus {
     pub receiver_url: Option<String>,
 }
 
+/// Response of `GET /api/senders/:id/live` — the control plane's in-memory
+/// view of a sender, so a page can render before the next WebSocket tick.
+/// The stream fields are empty while nothing is streaming.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SenderLiveSnapshot {
+    pub status: SenderFullStatus,
+    /// The stream currently reporting telemetry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<String>,
+    /// Its latest sender-side `stream.stats`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<StreamStatsPayload>,
+    /// Last sample of every link seen during the stream, including links
+    /// that have since dropped out of `stats`.
+    #[serde(default)]
+    pub links: Vec<LinkSample>,
+    /// Its latest receiver-side stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_stats: Option<ReceiverStreamStatsPayload>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UnenrollResponse {
     pub sender_id: String,
     pub enrollment_token: String,
     pub message: String,
 }
 
-// ── Streams ─────────────────────────────────────────────────────────
+/// Whether a sender's local portal is PIN-gated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PortalAuthStatus {
+    pub enabled: bool,
+    /// After a change: whether the sender was online to receive it (it
+    /// otherwise picks it up on its next connect).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered: Option<bool>,
+}
 
+/// `PUT /api/senders/{id}/portal-auth`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetPortalAuthRequest {
+    /// New PIN/password; `None` (or empty) removes the gate.
+    pub pin: Option<String>,
+}
+
+/// `POST /api/senders/{id}/config`, proxied to the agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetConfigRequest {
+    pub receiver_url: Option<String>,
+}
+
+/// `POST /api/senders/{id}/power`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerRequest {
+    /// "reboot", "shutdown" or "restart_agent".
+    pub action: String,
+}
+
+/// `POST /api/senders/{id}/interfaces/{name}/lock_band`; `None` unlocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LockBandRequest {
+    pub band: Option<String>,
+}
+
+/// `POST /api/senders/{id}/interfaces/{name}/priority`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetPriorityRequest {
+    pub priority: u32,
+}
+
+/// `POST /api/senders/{id}/interfaces/{name}/apn`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetApnRequest {
+    pub apn: Option<String>,
+    pub sim_pin: Option<String>,
+    pub roaming: Option<bool>,
+}
+
+/// `POST /api/senders/{id}/diagnostics/network`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NetworkToolRequest {
+    /// "ping" or "traceroute".
+    pub tool: String,
+    pub target: Option<String>,
+}
+
+/// `POST /api/senders/{id}/diagnostics/pcap`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PcapRequest {
+    pub duration_secs: u32,
+}
+
+// ── Receivers ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReceiverSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub hostname: Option<String>,
+    pub region: Option<String>,
+    pub bind_host: String,
+    pub max_streams: i32,
+    pub active_streams: i32,
+    pub online: bool,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateReceiverRequest {
+    pub name: Option<String>,
+    pub bind_host: String,
+    pub region: Option<String>,
+    #[serde(default = "default_max_streams")]
+    pub max_streams: i32,
+}
+
+fn default_max_streams() -> i32 {
+    6
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateReceiverResponse {
+    pub receiver_id: String,
+    pub enrollment_token: String,
+}
+
+// ── Streams ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct StreamSummary {
     pub id: String,
     pub sender_id: String,
@@ -122,9 +318,13 @@ pub struct StreamSummary {
     /// Stream this one replaced (stop→start within the lineage window).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub restarted_from: Option<String>,
+    /// See [`StreamDetail::preview_url`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct StreamDetail {
     pub id: String,
     pub sender_id: String,
@@ -142,9 +342,14 @@ pub struct StreamDetail {
     /// Stream this one replaced (stop→start within the lineage window).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub restarted_from: Option<String>,
+    /// HLS playlist of the receiver's live preview, while the stream is
+    /// live on a receiver that serves one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct StartStreamRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub destination_id: Option<String>,
@@ -155,23 +360,211 @@ pub struct StartStreamRequest {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct StartStreamResponse {
-    pub stream_id: String,
+    pub stream_id: StreamId,
     pub state: String,
+    /// Ingest senders only: where the contributor pushes to, key included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_url: Option<String>,
 }
 
-// ── Destinations ────────────────────────────────────────────────────
+/// `POST /api/senders/{id}/stream/destinations` — reroute the running
+/// stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetStreamDestinationsRequest {
+    pub destination_ids: Vec<String>,
+}
+
+/// `POST /api/senders/{id}/stream/jitter_buffer`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct JitterBufferRequest {
+    /// "adaptive" or "static".
+    pub mode: String,
+    /// Fixed depth when `mode` is "static".
+    pub static_ms: Option<u32>,
+}
 
+/// `POST /api/streams/{id}/annotations` — pin a note to the stream's
+/// timeline, at `ts` or now.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateAnnotationRequest {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ts: Option<DateTime<Utc>>,
+}
+
+// ── Destinations ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DestinationSummary {
     pub id: String,
     pub platform: String,
     pub name: String,
     pub url: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub has_stream_key: bool,
+    /// Last four characters of the stream key, for telling keys apart
+    /// without revealing them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_key_hint: Option<String>,
+    #[serde(default)]
+    pub health: DestinationHealth,
+}
+
+/// Result of the last destination check (`POST /api/destinations/{id}/check`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DestinationHealth {
+    pub status: DestinationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<DateTime<Utc>>,
+    /// What failed, when `status` is `Error`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DestinationStatus {
+    /// Never checked, or changed since.
+    #[default]
+    Unknown,
+    /// Well-formed and the ingest server accepted a connection.
+    Ok,
+    Error,
+}
+
+impl DestinationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DestinationStatus::Unknown => "unknown",
+            DestinationStatus::Ok => "ok",
+            DestinationStatus::Error => "error",
+        }
+    }
+}
+
+impl std::str::FromStr for DestinationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unknown" => Ok(DestinationStatus::Unknown),
+            "ok" => Ok(DestinationStatus::Ok),
+            "error" => Ok(DestinationStatus::Error),
+            _ => Err(format!("unknown destination status: {s}")),
+        }
+    }
+}
+
+/// Known ingest platforms: default URL and what the destination needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestinationPreset {
+    pub platform: &'static str,
+    pub label: &'static str,
+    /// Default ingest URL; empty when every user has their own.
+    pub url: &'static str,
+    /// URL schemes the platform accepts.
+    pub schemes: &'static [&'static str],
+    /// Whether a separate stream key is required (HLS ingest embeds it in
+    /// the URL instead).
+    pub needs_key: bool,
+    pub help: &'static str,
+}
+
+pub const DESTINATION_PRESETS: &[DestinationPreset] = &[
+    DestinationPreset {
+        platform: "youtube",
+        label: "YouTube (RTMP)",
+        url: "rtmp://a.rtmp.youtube.com/live2",
+        schemes: &["rtmp", "rtmps"],
+        needs_key: true,
+        help: "Standard RTMP ingest. H.265 requires Enhanced RTMP (eflvmux) — not all YouTube channels support this. Use YouTube HLS for reliable H.265.",
+    },
+    DestinationPreset {
+        platform: "youtube_hls",
+        label: "YouTube (HLS — H.265 Native)",
+        url: "",
+        schemes: &["https"],
+        needs_key: false,
+        help: "Paste the full HLS ingest URL from YouTube Studio → Go Live → Stream settings. It looks like: https://a.upload.youtube.com/http_upload_hls?cid=xxxx&copy=0&file=",
+    },
+    DestinationPreset {
+        platform: "twitch",
+        label: "Twitch (RTMP — H.264 Only)",
+        url: "rtmp://live.twitch.tv/app",
+        schemes: &["rtmp", "rtmps"],
+        needs_key: true,
+        help: "Twitch only supports H.264 via RTMP. H.265 is not supported.",
+    },
+    DestinationPreset {
+        platform: "facebook",
+        label: "Facebook Live (RTMPS)",
+        url: "rtmps://live-api-s.facebook.com:443/rtmp",
+        schemes: &["rtmps"],
+        needs_key: true,
+        help: "Facebook only accepts RTMPS. Use the persistent stream key from Live Producer.",
+    },
+    DestinationPreset {
+        platform: "custom_rtmp",
+        label: "Custom RTMP",
+        url: "",
+        schemes: &["rtmp", "rtmps"],
+        needs_key: false,
+        help: "Enter your RTMP server URL.",
+    },
+    DestinationPreset {
+        platform: "srt",
+        label: "SRT",
+        url: "",
+        schemes: &["srt"],
+        needs_key: false,
+        help: "SRT transport — supports both H.264 and H.265.",
+    },
+];
+
+impl DestinationPreset {
+    pub fn lookup(platform: &str) -> Option<&'static DestinationPreset> {
+        DESTINATION_PRESETS.iter().find(|p| p.platform == platform)
+    }
+}
+
+/// `GET /api/destinations/{id}/stream-key` (admin only, audited).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StreamKeyResponse {
+    pub stream_key: Option<String>,
+}
+
+/// `PUT /api/destinations/{id}/stream-key` — replace the key after
+/// regenerating it on the platform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RotateStreamKeyRequest {
+    pub stream_key: String,
+}
+
+/// One month of streams sent to a destination (`GET
+/// /api/destinations/{id}/usage`), by stream start month (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DestinationUsage {
+    /// `YYYY-MM`.
+    pub month: String,
+    pub stream_count: u64,
+    pub streamed_minutes: f64,
+    pub total_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateDestinationRequest {
     pub platform: String,
     pub name: String,
@@ -180,20 +573,155 @@ pub struct CreateDestinationRequest {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateDestinationResponse {
-    pub id: String,
+    pub id: DestinationId,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UpdateDestinationRequest {
     pub name: Option<String>,
     pub url: Option<String>,
     pub stream_key: Option<String>,
 }
 
+// ── Metrics History ─────────────────────────────────────────────────
+
+/// `GET /api/senders/{id}/metrics` — sender telemetry averaged into
+/// `bucket_s`-second buckets over `[from, to)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MetricsRangeResponse {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket_s: u32,
+    /// Averaged per bucket. Buckets without samples are omitted (the
+    /// sender wasn't streaming).
+    pub points: Vec<TelemetrySample>,
+}
+
+// ── Preferences ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    /// Follow the browser's `prefers-color-scheme`.
+    System,
+}
+
+/// How bit rates are rendered: `Mbps` (bits) or `MB/s` (bytes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RateUnits {
+    #[default]
+    Bits,
+    Bytes,
+}
+
+/// Dashboard UI language. Serialized as its BCP 47 tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+}
+
+impl Locale {
+    pub const ALL: &[Locale] = &[Locale::En, Locale::Es, Locale::De];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::De => "de",
+        }
+    }
+
+    /// The language's name in itself, for the locale picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    /// Best match for a browser language tag such as `de-AT`; `None` when
+    /// the language has no bundle.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL.iter().copied().find(|l| l.as_str() == lang)
+    }
+}
+
+/// Per-user dashboard presentation settings
+/// (`GET`/`PUT /api/me/preferences`). Missing fields take their defaults,
+/// so older stored documents keep loading as fields are added.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct UserPreferences {
+    pub theme: Theme,
+    /// Route opened after login; one of [`UserPreferences::PAGES`].
+    pub default_page: String,
+    pub rate_units: RateUnits,
+    /// Span of the live bandwidth graph, in seconds.
+    pub graph_window_s: u32,
+    pub locale: Locale,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            default_page: "/overview".into(),
+            rate_units: RateUnits::default(),
+            graph_window_s: 60,
+            locale: Locale::default(),
+        }
+    }
+}
+
+impl UserPreferences {
+    /// Pages that may be chosen as the landing page.
+    pub const PAGES: &[&str] = &[
+        "/overview",
+        "/senders",
+        "/receivers",
+        "/streams",
+        "/destinations",
+    ];
+
+    /// Allowed live-graph spans, in seconds.
+    pub const GRAPH_WINDOWS: &[u32] = &[30, 60, 120, 300];
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !Self::PAGES.contains(&self.default_page.as_str()) {
+            return Err(format!("unknown default_page {:?}", self.default_page));
+        }
+        if !Self::GRAPH_WINDOWS.contains(&self.graph_window_s) {
+            return Err(format!(
+                "graph_window_s must be one of {:?}",
+                Self::GRAPH_WINDOWS
+            ));
+        }
+        Ok(())
+    }
+}
+
 // ── Alerting ────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AlertRule {
     #[serde(default)]
     pub id: Option<String>,
@@ -203,4 +731,744 @@ pub struct AlertRule {
     pub threshold: f64,
     #[serde(default)]
     pub enabled: bool,
+    #[serde(default)]
+    pub severity: AlertSeverity,
+}
+
+impl AlertRule {
+    /// Current value of an alertable metric over the `Live` links, or
+    /// `None` when it can't be computed (unknown metric, no live links).
+    pub fn metric_value(metric: &str, links: &[LinkSample]) -> Option<f64> {
+        let live: Vec<&LinkSample> = links.iter().filter(|l| l.is_live()).collect();
+        match metric {
+            "aggregate_capacity_bps" => Some(live.iter().map(|l| l.capacity_bps.0 as f64).sum()),
+            "link_count" => Some(live.len() as f64),
+            // Worst link — the one a rule is meant to catch.
+            "rtt_ms" => live.iter().map(|l| l.rtt_ms.0).reduce(f64::max),
+            "pre_fec_loss_pct" => (!live.is_empty())
+                .then(|| live.iter().map(|l| l.loss_rate).sum::<f64>() / live.len() as f64 * 100.0),
+            _ => None,
+        }
+    }
+
+    /// The breaching metric value, if this rule is enabled and `links`
+    /// violate it.
+    pub fn breach(&self, links: &[LinkSample]) -> Option<f64> {
+        if !self.enabled {
+            return None;
+        }
+        Self::metric_value(&self.metric, links).filter(|&v| match self.condition.as_str() {
+            "below" => v < self.threshold,
+            "above" => v > self.threshold,
+            _ => false,
+        })
+    }
+}
+
+// ── Maintenance ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMaintenanceWindowRequest {
+    /// Omit for a fleet-wide window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default)]
+    pub allow_ota: bool,
+    #[serde(default)]
+    pub allow_reboot: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMaintenanceWindowResponse {
+    pub id: String,
+}
+
+// ── Stream Schedule ─────────────────────────────────────────────────
+
+/// `POST /api/schedules` and `PUT /api/schedules/{id}`. Overlapping
+/// bookings for one sender are accepted and reported in
+/// [`crate::models::ScheduledStream::conflicts`]; the later one will fail
+/// to start if the first is still running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduleStreamRequest {
+    pub sender_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framerate: Option<u32>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+// ── Users ───────────────────────────────────────────────────────────
+
+/// Assignable roles, least to most privileged. Each role can do everything
+/// the ones before it can.
+pub const ROLES: &[&str] = &["viewer", "operator", "admin"];
+
+/// An action the server authorizes by role. Handlers check these rather
+/// than role names, and the dashboard gates its controls on the set the
+/// server reports, so both sides agree on who may do what.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Start and stop streams, switch sources, retarget destinations and
+    /// tune a live stream.
+    ControlStreams,
+    /// Add notes to a stream's timeline.
+    AnnotateStreams,
+    /// Create and revoke public share links.
+    ShareStreams,
+    /// Acknowledge and resolve alerts, edit alert rules and maintenance
+    /// windows.
+    ManageAlerts,
+    ManageKits,
+    ManageSchedule,
+    ManageReceivers,
+    /// Read logs, run network tools, check for updates and export config
+    /// on a sender.
+    TroubleshootSenders,
+    ViewCertificates,
+    /// Enroll, configure, update, power-cycle and remove senders.
+    ManageSenders,
+    ManageDestinations,
+    ManageCertificates,
+    ManageUsers,
+    /// Export and import the whole account's configuration.
+    TransferAccount,
+}
+
+impl Capability {
+    pub const ALL: &[Capability] = &[
+        Capability::ControlStreams,
+        Capability::AnnotateStreams,
+        Capability::ShareStreams,
+        Capability::ManageAlerts,
+        Capability::ManageKits,
+        Capability::ManageSchedule,
+        Capability::ManageReceivers,
+        Capability::TroubleshootSenders,
+        Capability::ViewCertificates,
+        Capability::ManageSenders,
+        Capability::ManageDestinations,
+        Capability::ManageCertificates,
+        Capability::ManageUsers,
+        Capability::TransferAccount,
+    ];
+
+    /// The least privileged of [`ROLES`] allowed this action.
+    pub fn min_role(self) -> &'static str {
+        match self {
+            Capability::ControlStreams
+            | Capability::AnnotateStreams
+            | Capability::ShareStreams
+            | Capability::ManageAlerts
+            | Capability::ManageKits
+            | Capability::ManageSchedule
+            | Capability::ManageReceivers
+            | Capability::TroubleshootSenders
+            | Capability::ViewCertificates => "operator",
+            Capability::ManageSenders
+            | Capability::ManageDestinations
+            | Capability::ManageCertificates
+            | Capability::ManageUsers
+            | Capability::TransferAccount => "admin",
+        }
+    }
+}
+
+/// `GET /api/me`: who the caller is and what they may do. Read from the
+/// users table on every request, so a role change shows up on the next
+/// fetch without signing in again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MeResponse {
+    pub user_id: UserId,
+    pub email: String,
+    pub role: String,
+    pub owner: bool,
+    /// Every action the caller's role is allowed; anything else is refused
+    /// with 403.
+    pub capabilities: Vec<Capability>,
+}
+
+/// A user of the caller's account (`GET /api/users`, admin only).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserSummary {
+    pub id: String,
+    pub email: String,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub disabled: bool,
+    /// The user who registered the account. Always an admin; cannot be
+    /// demoted, disabled or reset by other admins.
+    #[serde(default)]
+    pub owner: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InviteUserRequest {
+    pub email: String,
+    pub role: String,
+}
+
+/// The invited user, plus the token of their invitation link
+/// (`/invite/{token}`), where they choose a password. The link is single
+/// use and expires after a week; a new one comes from a password reset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InviteUserResponse {
+    pub user: UserSummary,
+    pub invite_token: String,
+    /// The link was emailed to the user; otherwise hand it over yourself.
+    pub emailed: bool,
+}
+
+/// `PUT /api/users/{id}` — omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateUserRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+}
+
+/// `POST /api/users/{id}/reset-password`: the token of the user's
+/// password reset link (`/reset-password/{token}`). Their old password
+/// has already stopped working.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResetPasswordResponse {
+    pub reset_token: String,
+    /// The link was emailed to the user; otherwise hand it over yourself.
+    pub emailed: bool,
+}
+
+// ── Share Links ─────────────────────────────────────────────────────
+
+/// `POST /api/streams/{id}/share-links`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateShareLinkRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Lifetime of the link; one of [`SHARE_LINK_TTLS`].
+    pub expires_in_s: u32,
+}
+
+/// Allowed share link lifetimes, in seconds (1 h to 7 days).
+pub const SHARE_LINK_TTLS: &[u32] = &[3_600, 6 * 3_600, 24 * 3_600, 3 * 86_400, 7 * 86_400];
+
+/// A read-only share link for one stream. The token itself is only
+/// returned when the link is created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ShareLinkSummary {
+    pub id: String,
+    pub stream_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_viewed_at: Option<DateTime<Utc>>,
+}
+
+impl ShareLinkSummary {
+    /// Whether the link still opens the shared view at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateShareLinkResponse {
+    pub link: ShareLinkSummary,
+    /// Bearer token for `GET /api/share/stream`; the dashboard's shared
+    /// page is `/share/{token}`.
+    pub token: String,
+}
+
+/// One bonded link as shown on the shared view — health only, no
+/// addresses, carriers or radio details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SharedLinkHealth {
+    pub interface: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_kind: Option<String>,
+    pub state: String,
+    pub rtt_ms: Millis,
+    pub loss_rate: f64,
+    pub observed_bps: Bps,
+}
+
+/// `GET /api/share/stream` — the stripped-down live view a share link
+/// grants. Live fields are empty once the stream is no longer live.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SharedStreamView {
+    pub stream_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    pub state: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
+    #[serde(default)]
+    pub uptime_s: u64,
+    #[serde(default)]
+    pub encoder_bitrate_kbps: u32,
+    #[serde(default)]
+    pub links: Vec<SharedLinkHealth>,
+}
+
+impl From<&LinkSample> for SharedLinkHealth {
+    fn from(link: &LinkSample) -> Self {
+        Self {
+            interface: link.interface.clone(),
+            link_kind: link.link_kind.clone(),
+            state: link.state.clone(),
+            rtt_ms: link.rtt_ms,
+            loss_rate: link.loss_rate,
+            observed_bps: link.observed_bps,
+        }
+    }
+}
+
+// ── Kits ────────────────────────────────────────────────────────────
+
+/// Accessory kinds a kit can hold.
+pub const KIT_ACCESSORY_KINDS: &[&str] = &["modem", "sim", "camera", "battery", "antenna", "other"];
+
+/// `POST /api/kits` and `PUT /api/kits/{id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KitRequest {
+    pub name: String,
+    /// The sender the kit is built around; a sender is in at most one kit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// `POST /api/kits/{id}/accessories`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddKitAccessoryRequest {
+    /// One of [`KIT_ACCESSORY_KINDS`].
+    pub kind: String,
+    pub serial: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// `POST /api/kits/{id}/check-out`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CheckOutKitRequest {
+    /// Who has the kit: a crew, customer or job reference.
+    pub holder: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_back_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A serial-numbered item packed in a kit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KitAccessory {
+    pub id: String,
+    pub kind: String,
+    pub serial: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// One trip out and (once `checked_in_at` is set) back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KitCheckout {
+    pub id: String,
+    pub holder: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_back_at: Option<DateTime<Utc>>,
+    pub checked_out_at: DateTime<Utc>,
+    /// Email of the user who handed the kit out, if the account still exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_out_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_in_at: Option<DateTime<Utc>>,
+    /// Email of the user who took the kit back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_in_by: Option<String>,
+}
+
+impl KitCheckout {
+    /// Still out past its due date at `now`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.checked_in_at.is_none() && self.due_back_at.is_some_and(|due| due < now)
+    }
+}
+
+/// A kit as listed on the kits page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KitSummary {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub accessory_count: u32,
+    /// The open check-out, if the kit is out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_out: Option<KitCheckout>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `GET /api/kits/{id}` — the kit, its contents and its check-out history
+/// (newest first).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KitDetail {
+    pub kit: KitSummary,
+    #[serde(default)]
+    pub accessories: Vec<KitAccessory>,
+    #[serde(default)]
+    pub history: Vec<KitCheckout>,
+}
+
+// ── TLS Certificates ────────────────────────────────────────────────
+
+/// Issued certificates expiring within this many days raise a renewal
+/// alert on their sender.
+pub const CERT_RENEW_WITHIN_DAYS: i64 = 21;
+
+/// The device a certificate is for: exactly one of the two.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CertificateTarget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_id: Option<String>,
+}
+
+/// `POST /api/certificates` — install a certificate issued elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UploadCertificateRequest {
+    #[serde(flatten)]
+    pub target: CertificateTarget,
+    /// PEM chain, leaf first.
+    pub cert_pem: String,
+    pub key_pem: String,
+}
+
+/// `POST /api/certificates/self-signed` — issue one on the control plane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SelfSignedCertificateRequest {
+    #[serde(flatten)]
+    pub target: CertificateTarget,
+    pub domain: String,
+}
+
+/// `POST /api/certificates/acme` — order one from an ACME CA using a
+/// DNS-01 challenge. The response carries the TXT record to publish
+/// before calling `POST /api/certificates/{id}/verify`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AcmeCertificateRequest {
+    #[serde(flatten)]
+    pub target: CertificateTarget,
+    pub domain: String,
+    /// Contact address registered with the CA account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_email: Option<String>,
+    /// Use the CA's staging directory (untrusted certificates, generous
+    /// rate limits).
+    #[serde(default)]
+    pub staging: bool,
+}
+
+/// A certificate the control plane holds for a device. The private key
+/// never leaves the server except to the device itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CertificateSummary {
+    pub id: String,
+    #[serde(flatten)]
+    pub target: CertificateTarget,
+    pub domain: String,
+    /// `uploaded`, `self_signed` or `acme`.
+    pub source: String,
+    /// `pending` (ACME challenge outstanding), `issued`, `failed` or
+    /// `superseded` by a newer issued certificate.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_after: Option<DateTime<Utc>>,
+    /// DNS-01 record to publish while `pending`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_txt_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_txt_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the device last confirmed installing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pushed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CertificateSummary {
+    /// An issued certificate within [`CERT_RENEW_WITHIN_DAYS`] of expiry.
+    pub fn needs_renewal(&self, now: DateTime<Utc>) -> bool {
+        self.status == "issued"
+            && self
+                .not_after
+                .is_some_and(|t| (t - now).num_days() < CERT_RENEW_WITHIN_DAYS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destination_presets_are_unique_and_consistent() {
+        for (i, p) in DESTINATION_PRESETS.iter().enumerate() {
+            assert!(
+                DESTINATION_PRESETS[i + 1..]
+                    .iter()
+                    .all(|q| q.platform != p.platform),
+                "duplicate preset {}",
+                p.platform
+            );
+            assert!(!p.schemes.is_empty());
+            if !p.url.is_empty() {
+                let scheme = p.url.split("://").next().unwrap();
+                assert!(p.schemes.contains(&scheme), "{} default URL", p.platform);
+            }
+        }
+        assert!(DestinationPreset::lookup("twitch").unwrap().needs_key);
+        assert!(DestinationPreset::lookup("nope").is_none());
+    }
+
+    #[test]
+    fn destination_summary_defaults_health_to_unknown() {
+        let json = r#"{"id":"dst_1","platform":"twitch","name":"T","url":"rtmp://x","created_at":"2026-01-01T00:00:00Z"}"#;
+        let d: DestinationSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(d.health.status, DestinationStatus::Unknown);
+        assert!(!d.has_stream_key);
+    }
+
+    #[test]
+    fn capabilities_list_every_action_once_with_an_assignable_role() {
+        for (i, c) in Capability::ALL.iter().enumerate() {
+            assert!(!Capability::ALL[i + 1..].contains(c), "duplicate {c:?}");
+            assert!(ROLES.contains(&c.min_role()), "{c:?}");
+        }
+        let json = serde_json::to_string(&Capability::ManageUsers).unwrap();
+        assert_eq!(json, r#""manage_users""#);
+        // Servers that predate capabilities grant nothing until /api/me answers.
+        let login: LoginResponse =
+            serde_json::from_str(r#"{"token":"t","user_id":"usr_1","role":"admin"}"#).unwrap();
+        assert!(login.capabilities.is_empty());
+    }
+
+    #[test]
+    fn create_receiver_request_defaults_max_streams() {
+        let req: CreateReceiverRequest =
+            serde_json::from_str(r#"{"name":null,"bind_host":"10.0.0.5","region":null}"#).unwrap();
+        assert_eq!(req.max_streams, 6);
+    }
+
+    #[test]
+    fn preferences_fill_missing_fields_with_defaults() {
+        let prefs: UserPreferences = serde_json::from_str(r#"{"theme":"light"}"#).unwrap();
+        assert_eq!(prefs.theme, Theme::Light);
+        assert_eq!(prefs.default_page, "/overview");
+        assert_eq!(prefs.rate_units, RateUnits::Bits);
+        assert_eq!(prefs.graph_window_s, 60);
+        assert_eq!(prefs.locale, Locale::En);
+        assert!(prefs.validate().is_ok());
+    }
+
+    #[test]
+    fn locale_matches_browser_tags_by_language() {
+        assert_eq!(Locale::from_tag("de-AT"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("ES"), Some(Locale::Es));
+        assert_eq!(Locale::from_tag("en_GB"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr-FR"), None);
+        let prefs: UserPreferences = serde_json::from_str(r#"{"locale":"de"}"#).unwrap();
+        assert_eq!(prefs.locale, Locale::De);
+    }
+
+    #[test]
+    fn preferences_reject_unknown_page_and_window() {
+        let mut prefs = UserPreferences {
+            default_page: "https://example.com".into(),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+        prefs.default_page = "/streams".into();
+        prefs.graph_window_s = 7;
+        assert!(prefs.validate().is_err());
+    }
+
+    fn link(state: &str, rtt_ms: f64, loss_rate: f64) -> LinkSample {
+        LinkSample {
+            id: 0,
+            interface: "wwan0".into(),
+            state: state.into(),
+            rtt_ms: Millis(rtt_ms),
+            loss_rate,
+            capacity_bps: Bps(5_000_000),
+            sent_bytes: 0,
+            observed_bps: Bps(0),
+            signal_dbm: None,
+            rsrp: None,
+            rsrq: None,
+            sinr: None,
+            cqi: None,
+            link_kind: None,
+            btlbw_bps: None,
+            rtprop_ms: None,
+            transport: None,
+        }
+    }
+
+    fn rule(metric: &str, condition: &str, threshold: f64) -> AlertRule {
+        AlertRule {
+            id: Some("r1".into()),
+            name: "test".into(),
+            metric: metric.into(),
+            condition: condition.into(),
+            threshold,
+            enabled: true,
+            severity: AlertSeverity::default(),
+        }
+    }
+
+    #[test]
+    fn alert_rules_evaluate_over_live_links_only() {
+        let links = [link("Live", 80.0, 0.02), link("Down", 900.0, 0.5)];
+        assert_eq!(rule("rtt_ms", "above", 100.0).breach(&links), None);
+        assert_eq!(rule("link_count", "below", 2.0).breach(&links), Some(1.0));
+        let loss = rule("pre_fec_loss_pct", "above", 1.0)
+            .breach(&links)
+            .unwrap();
+        assert!((loss - 2.0).abs() < 1e-9);
+        assert_eq!(rule("nonsense", "above", 0.0).breach(&links), None);
+
+        let mut disabled = rule("link_count", "below", 2.0);
+        disabled.enabled = false;
+        assert_eq!(disabled.breach(&links), None);
+    }
+
+    #[test]
+    fn alert_rule_severity_defaults_to_warning() {
+        let r: AlertRule = serde_json::from_str(
+            r#"{"name":"n","metric":"rtt_ms","condition":"above","threshold":1}"#,
+        )
+        .unwrap();
+        assert_eq!(r.severity, AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn kit_checkout_is_overdue_only_while_out() {
+        let now = Utc::now();
+        let mut trip = KitCheckout {
+            id: "kco_1".into(),
+            holder: "Crew B".into(),
+            note: None,
+            due_back_at: Some(now - chrono::Duration::hours(1)),
+            checked_out_at: now - chrono::Duration::days(2),
+            checked_out_by: None,
+            checked_in_at: None,
+            checked_in_by: None,
+        };
+        assert!(trip.is_overdue(now));
+        trip.checked_in_at = Some(now);
+        assert!(!trip.is_overdue(now));
+        trip.checked_in_at = None;
+        trip.due_back_at = None;
+        assert!(
+            !trip.is_overdue(now),
+            "open-ended rentals are never overdue"
+        );
+    }
+
+    #[test]
+    fn certificate_target_flattens_into_the_summary() {
+        let now = Utc::now();
+        let mut cert = CertificateSummary {
+            id: "crt_1".into(),
+            target: CertificateTarget {
+                sender_id: Some("snd_1".into()),
+                receiver_id: None,
+            },
+            domain: "cam1.example.com".into(),
+            source: "acme".into(),
+            status: "issued".into(),
+            issuer: Some("CN=R11, O=Let's Encrypt".into()),
+            not_after: Some(now + chrono::Duration::days(20)),
+            dns_txt_name: None,
+            dns_txt_value: None,
+            error: None,
+            pushed_at: None,
+            created_at: now,
+        };
+        let json = serde_json::to_value(&cert).unwrap();
+        assert_eq!(json["sender_id"], "snd_1");
+        assert!(json.get("receiver_id").is_none());
+        let back: CertificateSummary = serde_json::from_value(json).unwrap();
+        assert_eq!(back, cert);
+
+        assert!(cert.needs_renewal(now));
+        cert.not_after = Some(now + chrono::Duration::days(60));
+        assert!(!cert.needs_renewal(now));
+        cert.not_after = Some(now);
+        cert.status = "superseded".into();
+        assert!(!cert.needs_renewal(now), "only the live certificate alerts");
+    }
 }
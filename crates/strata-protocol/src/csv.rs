@@ -0,0 +1,56 @@
+//! CSV field encoding shared by every CSV export — the control plane's
+//! usage export and the dashboard's table downloads — so both escape the
+//! same way.
+
+/// Encode one text field: quoted per RFC 4180 when it contains a
+/// delimiter, quote or line break, and prefixed with `'` when it starts
+/// with a character a spreadsheet would evaluate as a formula (`=`, `+`,
+/// `-`, `@`, tab or CR). Apply it to text only; numbers written as
+/// numbers stay numeric.
+pub fn field(s: &str) -> String {
+    let s = if s.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{s}")
+    } else {
+        s.to_string()
+    };
+    if s.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        assert_eq!(field("Truck 1"), "Truck 1");
+        assert_eq!(field("Truck-1"), "Truck-1");
+        assert_eq!(field(""), "");
+    }
+
+    #[test]
+    fn delimiters_quotes_and_line_breaks_are_quoted() {
+        assert_eq!(field("Cam \"A\", north"), "\"Cam \"\"A\"\", north\"");
+        assert_eq!(field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn formula_leading_text_is_neutralised() {
+        for (text, encoded) in [
+            (
+                "=HYPERLINK(\"http://x\")",
+                "\"'=HYPERLINK(\"\"http://x\"\")\"",
+            ),
+            ("+1", "'+1"),
+            ("-2+3", "'-2+3"),
+            ("@SUM(A1)", "'@SUM(A1)"),
+            ("\tcmd", "'\tcmd"),
+            ("\rcmd", "\"'\rcmd\""),
+        ] {
+            assert_eq!(field(text), encoded, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Binary frame encoding for high-rate agent telemetry.
+//!
+//! Control messages stay JSON text frames. Per-second `stream.stats` is the
+//! bulk of an agent's control-channel traffic over cellular, so an agent
+//! may offer a compact CBOR encoding in `auth.login`
+//! ([`crate::AuthLoginPayload::telemetry_encodings`]); when the control
+//! plane accepts ([`crate::AuthLoginResponsePayload::telemetry_encoding`])
+//! high-rate envelopes travel as binary frames carrying the same
+//! [`Envelope`](crate::Envelope), CBOR-encoded. Either side falls back to JSON when the
+//! other doesn't name an encoding, so mixed-version fleets keep working.
+//!
+//! The codec itself sits behind the `cbor` feature so the wasm dashboard
+//! doesn't link it.
+
+#[cfg(feature = "cbor")]
+use crate::Envelope;
+
+/// Encoding name for CBOR (RFC 8949) binary frames.
+pub const TELEMETRY_ENCODING_CBOR: &str = "cbor";
+
+/// Whether an envelope of this type is eligible for a binary frame once
+/// one has been negotiated. Everything else stays JSON.
+pub fn is_high_rate(msg_type: &str) -> bool {
+    matches!(msg_type, "stream.stats")
+}
+
+/// Failure to encode or decode a CBOR frame.
+#[cfg(feature = "cbor")]
+#[derive(Debug)]
+pub struct CborError(String);
+
+#[cfg(feature = "cbor")]
+impl std::fmt::Display for CborError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cbor: {}", self.0)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl std::error::Error for CborError {}
+
+/// Encode an envelope as a CBOR binary frame.
+#[cfg(feature = "cbor")]
+pub fn encode_cbor(envelope: &Envelope) -> Result<Vec<u8>, CborError> {
+    let mut buf = Vec::with_capacity(256);
+    ciborium::into_writer(envelope, &mut buf).map_err(|e| CborError(e.to_string()))?;
+    Ok(buf)
+}
+
+/// Decode a CBOR binary frame back into an envelope.
+#[cfg(feature = "cbor")]
+pub fn decode_cbor(bytes: &[u8]) -> Result<Envelope, CborError> {
+    ciborium::from_reader(bytes).map_err(|e| CborError(e.to_string()))
+}
+
+#[cfg(all(test, feature = "cbor"))]
+mod tests {
+    use super::*;
+    use crate::{AgentMessage, StreamStatsPayload};
+
+    fn stats_envelope() -> Envelope {
+        Envelope::from_message(&AgentMessage::StreamStats(StreamStatsPayload {
+            stream_id: "str_1".into(),
+            sender_id: String::new(),
+            uptime_s: 42,
+            encoder_bitrate_kbps: 4500,
+            timestamp_ms: 1_700_000_000_000,
+            links: vec![],
+            sender_metrics: None,
+            receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn cbor_round_trip_preserves_message() {
+        let envelope = stats_envelope();
+        let bytes = encode_cbor(&envelope).unwrap();
+        let decoded = decode_cbor(&bytes).unwrap();
+
+        assert_eq!(decoded.id, envelope.id);
+        assert_eq!(decoded.msg_type, "stream.stats");
+        assert_eq!(decoded.ts, envelope.ts);
+        match decoded.parse_message::<AgentMessage>().unwrap() {
+            AgentMessage::StreamStats(p) => {
+                assert_eq!(p.uptime_s, 42);
+                assert_eq!(p.encoder_bitrate_kbps, 4500);
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cbor_is_smaller_than_json() {
+        let envelope = stats_envelope();
+        let json = serde_json::to_vec(&envelope).unwrap();
+        let cbor = encode_cbor(&envelope).unwrap();
+        assert!(
+            cbor.len() < json.len(),
+            "cbor {} >= json {}",
+            cbor.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn garbage_frame_is_an_error() {
+        assert!(decode_cbor(&[0xff, 0x00, 0x13]).is_err());
+    }
+}
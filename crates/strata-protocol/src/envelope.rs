@@ -8,6 +8,12 @@ use uuid::Uuid;
 /// Wire protocol schema version. Bump on any breaking change to message
 /// shapes; peers log a warning on mismatch (they do not disconnect — the
 /// version exists so a mixed-version fleet is *visible*, not to gate it).
+///
+/// Everything added since version 1 is additive — new message types and
+/// fields that default when absent — so field agents still on version 1
+/// parse as they are, with no upgrade step. A change that isn't additive
+/// has to bump this and rewrite older payloads in
+/// [`Envelope::parse_message`] before they reach the direction enums.
 pub const PROTOCOL_VERSION: u32 = 1;
 
 fn default_proto_version() -> u32 {
@@ -29,6 +35,12 @@ pub struct Envelope {
     /// Protocol schema version ([`PROTOCOL_VERSION`]).
     #[serde(default = "default_proto_version")]
     pub proto_version: u32,
+    /// For a call the sender is waiting on: when it stops waiting. A peer
+    /// that only gets to the message after this NAKs it with
+    /// [`crate::ErrorCode::Timeout`] instead of acting on it (see
+    /// [`Envelope::expired_nak`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
     /// Type-specific payload.
     pub payload: serde_json::Value,
 }
@@ -43,6 +55,7 @@ impl Envelope {
             msg_type: msg_type.into(),
             ts: Utc::now(),
             proto_version: PROTOCOL_VERSION,
+            deadline: None,
             payload: serde_json::to_value(payload).expect("payload serialization"),
         }
     }
@@ -58,6 +71,7 @@ impl Envelope {
             msg_type: msg_type.into(),
             ts: Utc::now(),
             proto_version: PROTOCOL_VERSION,
+            deadline: None,
             payload: serde_json::to_value(payload)?,
         })
     }
@@ -84,15 +98,71 @@ impl Envelope {
             msg_type,
             ts: Utc::now(),
             proto_version: PROTOCOL_VERSION,
+            deadline: None,
             payload,
         })
     }
 
+    /// Set [`Envelope::deadline`].
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The NAK to send instead of handling this message, if its deadline
+    /// passed before `now`.
+    pub fn expired_nak(&self, now: DateTime<Utc>) -> Option<crate::NakPayload> {
+        let deadline = self.deadline.filter(|d| *d < now)?;
+        self.nak(
+            crate::ErrorCode::Timeout,
+            format!(
+                "deadline passed {} ms before the message was handled",
+                (now - deadline).num_milliseconds()
+            ),
+        )
+    }
+
     /// Parse the payload into a concrete type.
     pub fn parse_payload<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
         serde_json::from_value(self.payload.clone())
     }
 
+    /// [`Envelope::parse_message`], turning a failure into the NAK to
+    /// send back: a `type` missing from [`MessageTypes::TYPES`] is
+    /// unsupported, a known one that doesn't parse is invalid. `None` when
+    /// the message is itself a NAK — answering it would have two peers on
+    /// mismatched versions NAK each other's NAKs forever.
+    pub fn parse_message_or_nak<M: DeserializeOwned + MessageTypes>(
+        &self,
+    ) -> Result<M, Option<crate::NakPayload>> {
+        if !M::TYPES.contains(&self.msg_type.as_str()) {
+            return Err(self.nak(
+                crate::ErrorCode::UnsupportedCommand,
+                format!("unknown message type {:?}", self.msg_type),
+            ));
+        }
+        self.parse_message()
+            .map_err(|e| self.nak(crate::ErrorCode::InvalidInput, e.to_string()))
+    }
+
+    fn nak(&self, code: crate::ErrorCode, detail: String) -> Option<crate::NakPayload> {
+        if self.msg_type == NAK_TYPE {
+            return None;
+        }
+        Some(crate::NakPayload {
+            ref_id: self.id.clone(),
+            msg_type: self.msg_type.clone(),
+            request_id: self
+                .payload
+                .get("request_id")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            code,
+            detail,
+            proto_version: PROTOCOL_VERSION,
+        })
+    }
+
     /// Parse the whole envelope into a direction enum (e.g.
     /// [`crate::AgentMessage`]) — the counterpart of
     /// [`Envelope::from_message`]. Fails on unknown `msg_type` (the caller
@@ -104,3 +174,13 @@ impl Envelope {
         }))
     }
 }
+
+/// Wire `type` of a [`crate::NakPayload`] envelope, on every leg.
+pub const NAK_TYPE: &str = "message.nak";
+
+/// A direction enum (e.g. [`crate::AgentMessage`]) and the wire `type`
+/// strings its variants carry, so [`Envelope::parse_message_or_nak`] can
+/// tell an unknown type from a malformed payload.
+pub trait MessageTypes {
+    const TYPES: &'static [&'static str];
+}
@@ -0,0 +1,161 @@
+//! Stable error codes carried by REST error bodies and protocol NAKs.
+//!
+//! Clients branch on (and translate) the code; the accompanying message is
+//! detail for humans and logs. Codes are append-only: a code, once
+//! shipped, keeps its wire string and meaning. Codes this build doesn't
+//! know parse as [`ErrorCode::Unknown`], so an older dashboard still reads
+//! a newer control plane's errors.
+
+use serde::{Deserialize, Serialize};
+
+/// What an error is about, for grouping and default handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Auth,
+    Validation,
+    Transport,
+    Device,
+    Internal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ErrorCode {
+    // ── Auth ──
+    /// No valid session or device token.
+    #[serde(rename = "auth.unauthenticated")]
+    Unauthenticated,
+    #[serde(rename = "auth.invalid_credentials")]
+    InvalidCredentials,
+    /// Authenticated, but the role or ownership doesn't allow it.
+    #[serde(rename = "auth.forbidden")]
+    Forbidden,
+    /// Too many attempts from this client or for this account; wait.
+    #[serde(rename = "auth.rate_limited")]
+    RateLimited,
+
+    // ── Validation ──
+    #[serde(rename = "validation.invalid_input")]
+    InvalidInput,
+    #[serde(rename = "validation.not_found")]
+    NotFound,
+    #[serde(rename = "validation.conflict")]
+    Conflict,
+
+    // ── Transport ──
+    /// The device has no live control connection.
+    #[serde(rename = "transport.device_offline")]
+    DeviceOffline,
+    /// The device didn't answer in time.
+    #[serde(rename = "transport.timeout")]
+    Timeout,
+
+    // ── Device ──
+    /// The device's software doesn't know this command (it is older).
+    #[serde(rename = "device.unsupported_command")]
+    UnsupportedCommand,
+    /// The device understood the command but refused or failed it.
+    #[serde(rename = "device.rejected")]
+    DeviceRejected,
+    /// The device is streaming and the command would interrupt it.
+    #[serde(rename = "device.busy")]
+    DeviceBusy,
+
+    #[serde(rename = "internal")]
+    Internal,
+    /// A code newer than this build.
+    #[serde(other, rename = "unknown")]
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn category(self) -> ErrorCategory {
+        use ErrorCode::*;
+        match self {
+            Unauthenticated | InvalidCredentials | Forbidden | RateLimited => ErrorCategory::Auth,
+            InvalidInput | NotFound | Conflict => ErrorCategory::Validation,
+            DeviceOffline | Timeout => ErrorCategory::Transport,
+            UnsupportedCommand | DeviceRejected | DeviceBusy => ErrorCategory::Device,
+            Internal | Unknown => ErrorCategory::Internal,
+        }
+    }
+
+    /// The wire string, e.g. `"transport.timeout"`.
+    pub fn as_str(self) -> &'static str {
+        use ErrorCode::*;
+        match self {
+            Unauthenticated => "auth.unauthenticated",
+            InvalidCredentials => "auth.invalid_credentials",
+            Forbidden => "auth.forbidden",
+            RateLimited => "auth.rate_limited",
+            InvalidInput => "validation.invalid_input",
+            NotFound => "validation.not_found",
+            Conflict => "validation.conflict",
+            DeviceOffline => "transport.device_offline",
+            Timeout => "transport.timeout",
+            UnsupportedCommand => "device.unsupported_command",
+            DeviceRejected => "device.rejected",
+            DeviceBusy => "device.busy",
+            Internal => "internal",
+            Unknown => "unknown",
+        }
+    }
+
+    /// Default (English) text for the code, for clients without a
+    /// translation catalogue of their own.
+    pub fn message(self) -> &'static str {
+        use ErrorCode::*;
+        match self {
+            Unauthenticated => "Your session has expired. Please sign in again.",
+            InvalidCredentials => "Incorrect email or password.",
+            Forbidden => "You don't have permission to do that.",
+            RateLimited => "Too many attempts. Wait a while and try again.",
+            InvalidInput => "Some of the values entered are invalid.",
+            NotFound => "That item no longer exists.",
+            Conflict => "That conflicts with the current state. Refresh and try again.",
+            DeviceOffline => "The device is offline.",
+            Timeout => "The device did not respond in time.",
+            UnsupportedCommand => "The device's software is too old for this. Update it first.",
+            DeviceRejected => "The device could not carry out the request.",
+            DeviceBusy => "The device is busy streaming.",
+            Internal | Unknown => "Something went wrong.",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_serialize_as_dotted_strings() {
+        assert_eq!(
+            serde_json::to_string(&ErrorCode::DeviceOffline).unwrap(),
+            r#""transport.device_offline""#
+        );
+        assert_eq!(ErrorCode::Timeout.to_string(), "transport.timeout");
+        for code in [ErrorCode::InvalidCredentials, ErrorCode::DeviceBusy] {
+            let wire = serde_json::to_value(code).unwrap();
+            assert_eq!(wire.as_str(), Some(code.as_str()));
+        }
+        assert_eq!(
+            ErrorCode::UnsupportedCommand.category(),
+            ErrorCategory::Device
+        );
+    }
+
+    #[test]
+    fn codes_from_a_newer_peer_parse_as_unknown() {
+        let code: ErrorCode = serde_json::from_str(r#""billing.quota_exceeded""#).unwrap();
+        assert_eq!(code, ErrorCode::Unknown);
+        assert_eq!(code.category(), ErrorCategory::Internal);
+    }
+}
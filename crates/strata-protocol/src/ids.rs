@@ -0,0 +1,193 @@
+//! Typed entity IDs.
+//!
+//! Every entity ID is `<prefix>_<suffix>` (see `strata_common::ids` for
+//! generation). The newtypes here check the prefix when parsed or
+//! deserialized and serialize as the plain string, so the wire format is
+//! unchanged while a sender ID can no longer be passed where a stream ID
+//! is expected.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// An ID string that doesn't carry the expected prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidId {
+    pub expected_prefix: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid ID {:?}: expected prefix {}_",
+            self.value, self.expected_prefix
+        )
+    }
+}
+
+impl std::error::Error for InvalidId {}
+
+macro_rules! prefixed_id {
+    ($(#[$meta:meta])* $name:ident, $prefix:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        #[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+        #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(value_type = String))]
+        pub struct $name(String);
+
+        impl $name {
+            pub const PREFIX: &'static str = $prefix;
+
+            /// Parse `value`, checking its prefix.
+            pub fn parse(value: impl Into<String>) -> Result<Self, InvalidId> {
+                let value = value.into();
+                let valid = value
+                    .strip_prefix(Self::PREFIX)
+                    .and_then(|rest| rest.strip_prefix('_'))
+                    .is_some_and(|suffix| {
+                        !suffix.is_empty()
+                            && suffix
+                                .bytes()
+                                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+                    });
+                if valid {
+                    Ok(Self(value))
+                } else {
+                    Err(InvalidId {
+                        expected_prefix: Self::PREFIX,
+                        value,
+                    })
+                }
+            }
+
+            /// Wrap a value already known to be well-formed — a freshly
+            /// generated ID or one read back from the database.
+            pub fn from_trusted(value: impl Into<String>) -> Self {
+                Self(value.into())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = InvalidId;
+            fn try_from(value: String) -> Result<Self, InvalidId> {
+                Self::parse(value)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidId;
+            fn from_str(s: &str) -> Result<Self, InvalidId> {
+                Self::parse(s)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> String {
+                id.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+
+prefixed_id!(
+    /// A user account: `usr_…`.
+    UserId,
+    "usr"
+);
+prefixed_id!(
+    /// A sender device: `snd_…`.
+    SenderId,
+    "snd"
+);
+prefixed_id!(
+    /// A stream: `str_…`.
+    StreamId,
+    "str"
+);
+prefixed_id!(
+    /// A streaming destination: `dst_…`.
+    DestinationId,
+    "dst"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_serialize_transparently_and_check_prefix_on_parse() {
+        let id: StreamId = serde_json::from_str(r#""str_0191abc""#).unwrap();
+        assert_eq!(id, "str_0191abc");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""str_0191abc""#);
+
+        let err = serde_json::from_str::<StreamId>(r#""snd_0191abc""#).unwrap_err();
+        assert!(err.to_string().contains("expected prefix str_"));
+        assert!("str_".parse::<StreamId>().is_err());
+        assert!("strx_1".parse::<StreamId>().is_err());
+        assert!("usr_a b".parse::<UserId>().is_err());
+        assert!("snd_dev-01".parse::<SenderId>().is_ok());
+    }
+}
@@ -5,10 +5,16 @@
 //! - [`Envelope`] — the outer wrapper for every WebSocket message
 //! - [`AgentMessage`] / [`ControlMessage`] — agent ⇄ control plane
 //! - [`ReceiverMessage`] / [`ReceiverControlMessage`] — receiver ⇄ control plane
-//! - [`DashboardEvent`] — control plane → browser
+//! - [`DashboardEvent`] / [`DashboardClientMessage`] — control plane ⇄ browser,
+//!   scoped by [`DashboardTopic`] subscriptions
 //! - [`api`] — REST request/response types shared by control plane and dashboard
 //! - [`models`] — data models embedded in messages (interfaces, streams, stats)
 //! - [`profiles`] — bitrate profile presets
+//! - [`telemetry`] — link and stream telemetry samples with unit-typed fields
+//! - [`ids`] — typed entity IDs (`UserId`, `SenderId`, …) checked on parse
+//! - [`ErrorCode`] — stable error codes for REST error bodies and NAKs
+//! - [`encoding`] — optional CBOR binary frames for high-rate telemetry
+//! - [`csv`] — field encoding for the CSV exports
 //!
 //! This crate is wasm-safe (serde types only — no argon2/tokio/sqlx), so the
 //! Leptos dashboard imports it directly instead of hand-copying types.
@@ -18,12 +24,18 @@
 //! handles it.
 
 pub mod api;
+pub mod csv;
+pub mod encoding;
 mod envelope;
+mod error_code;
+pub mod ids;
 mod messages;
 pub mod models;
 mod payloads;
 pub mod profiles;
+pub mod telemetry;
 
-pub use envelope::{Envelope, PROTOCOL_VERSION};
+pub use envelope::{Envelope, MessageTypes, NAK_TYPE, PROTOCOL_VERSION};
+pub use error_code::{ErrorCategory, ErrorCode};
 pub use messages::*;
 pub use payloads::*;
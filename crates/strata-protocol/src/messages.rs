@@ -4,7 +4,9 @@
 //! Hubs and daemons parse an [`crate::Envelope`] into the enum for their leg
 //! ([`crate::Envelope::parse_message`]) and match exhaustively; senders build
 //! envelopes from enum values ([`crate::Envelope::from_message`]). The serde
-//! tag on each variant is the wire `type` string — it exists nowhere else.
+//! tag on each variant is the wire `type` string. The legs that NAK unknown
+//! types repeat those strings in [`crate::MessageTypes`]; a test keeps the
+//! two in step.
 
 use serde::{Deserialize, Serialize};
 
@@ -75,6 +77,16 @@ pub enum AgentMessage {
     JitterBufferResponse(JitterBufferResponsePayload),
     #[serde(rename = "source.switch.response")]
     SourceSwitchResponse(SourceSwitchResponsePayload),
+    #[serde(rename = "source.select.response")]
+    SourceSelectResponse(SourceSelectResponsePayload),
+
+    /// Result of a control message this agent did handle.
+    #[serde(rename = "command.ack")]
+    CommandAck(CommandAckPayload),
+
+    /// A control message this agent could not handle.
+    #[serde(rename = "message.nak")]
+    Nak(NakPayload),
 }
 
 impl AgentMessage {
@@ -90,6 +102,9 @@ impl AgentMessage {
             | StreamEnded(_) => None,
             InterfaceCommandResponse(p) => p.request_id.as_deref(),
             SourceSwitchResponse(p) => p.request_id.as_deref(),
+            SourceSelectResponse(p) => p.request_id.as_deref(),
+            CommandAck(p) => p.request_id.as_deref(),
+            Nak(p) => p.request_id.as_deref(),
             ConfigSetResponse(p) => Some(&p.request_id),
             ConfigUpdateResponse(p) => p.request_id.as_deref(),
             TestRunResponse(p) => Some(&p.request_id),
@@ -111,6 +126,38 @@ impl AgentMessage {
     }
 }
 
+impl crate::MessageTypes for AgentMessage {
+    const TYPES: &'static [&'static str] = &[
+        "auth.login",
+        "device.status",
+        "stream.stats",
+        "stream.ended",
+        "auth.challenge.response",
+        "config.set.response",
+        "config.update.response",
+        "test.run.response",
+        "interfaces.scan.response",
+        "interface.command.response",
+        "files.list.response",
+        "diagnostics.network.response",
+        "diagnostics.pcap.response",
+        "logs.get.response",
+        "power.command.response",
+        "tls.status.response",
+        "tls.renew.response",
+        "config.export.response",
+        "config.import.response",
+        "updates.check.response",
+        "updates.install.response",
+        "stream.destinations.response",
+        "stream.jitter_buffer.response",
+        "source.switch.response",
+        "source.select.response",
+        "command.ack",
+        "message.nak",
+    ];
+}
+
 // ── Control Plane → Agent ───────────────────────────────────────────
 
 /// Every message the control plane can send to a sender agent.
@@ -141,6 +188,10 @@ pub enum ControlMessage {
     #[serde(rename = "source.switch")]
     SourceSwitch(SourceSwitchPayload),
 
+    /// Switch a multi-camera stream to another configured input.
+    #[serde(rename = "source.select")]
+    SourceSelect(SourceSelectPayload),
+
     /// Manage a network interface on the agent.
     #[serde(rename = "interface.command")]
     InterfaceCommand(InterfaceCommandPayload),
@@ -185,6 +236,10 @@ pub enum ControlMessage {
     #[serde(rename = "tls.renew")]
     TlsRenew(TlsRenewPayload),
 
+    /// Install a control-plane issued certificate.
+    #[serde(rename = "tls.install")]
+    TlsInstall(TlsInstallPayload),
+
     /// Export agent configuration.
     #[serde(rename = "config.export")]
     ConfigExport(ConfigExportPayload),
@@ -208,6 +263,19 @@ pub enum ControlMessage {
     /// Configure receiver jitter buffer.
     #[serde(rename = "stream.jitter_buffer")]
     JitterBuffer(JitterBufferPayload),
+
+    /// Current maintenance schedule — tells the agent when it may reboot
+    /// or auto-apply OTA updates.
+    #[serde(rename = "maintenance.schedule")]
+    MaintenanceSchedule(MaintenanceSchedulePayload),
+
+    /// Portal PIN gate (replaces the previous setting).
+    #[serde(rename = "portal.auth")]
+    PortalAuth(PortalAuthPayload),
+
+    /// An agent message the control plane could not handle.
+    #[serde(rename = "message.nak")]
+    Nak(NakPayload),
 }
 
 impl ControlMessage {
@@ -216,9 +284,19 @@ impl ControlMessage {
     pub fn request_id(&self) -> Option<&str> {
         use ControlMessage::*;
         match self {
-            AuthLoginResponse(_) | AuthChallenge(_) | StreamStart(_) | StreamStop(_)
-            | SourceSwitch(_) | InterfaceCommand(_) => None,
+            AuthLoginResponse(_)
+            | AuthChallenge(_)
+            | StreamStart(_)
+            | StreamStop(_)
+            | MaintenanceSchedule(_)
+            | PortalAuth(_)
+            | TlsInstall(_) => None,
+            // Echoes the rejected message's ID, not a pending RPC of ours.
+            Nak(_) => None,
             ConfigUpdate(p) => p.request_id.as_deref(),
+            SourceSwitch(p) => p.request_id.as_deref(),
+            SourceSelect(p) => p.request_id.as_deref(),
+            InterfaceCommand(p) => p.request_id.as_deref(),
             ConfigSet(p) => Some(&p.request_id),
             TestRun(p) => Some(&p.request_id),
             InterfacesScan(p) => Some(&p.request_id),
@@ -239,6 +317,39 @@ impl ControlMessage {
     }
 }
 
+impl crate::MessageTypes for ControlMessage {
+    const TYPES: &'static [&'static str] = &[
+        "auth.login.response",
+        "auth.challenge",
+        "stream.start",
+        "stream.stop",
+        "config.update",
+        "source.switch",
+        "source.select",
+        "interface.command",
+        "config.set",
+        "test.run",
+        "interfaces.scan",
+        "files.list",
+        "diagnostics.network",
+        "diagnostics.pcap",
+        "logs.get",
+        "power.command",
+        "tls.status",
+        "tls.renew",
+        "tls.install",
+        "config.export",
+        "config.import",
+        "updates.check",
+        "updates.install",
+        "stream.destinations",
+        "stream.jitter_buffer",
+        "maintenance.schedule",
+        "portal.auth",
+        "message.nak",
+    ];
+}
+
 // ── Receiver → Control Plane ────────────────────────────────────────
 
 /// Every message a receiver daemon can send to the control plane.
@@ -291,6 +402,83 @@ pub enum ReceiverControlMessage {
     /// Stop a stream.
     #[serde(rename = "receiver.stream.stop")]
     StreamStop(ReceiverStreamStopPayload),
+
+    /// Install a certificate for the preview endpoint.
+    #[serde(rename = "tls.install")]
+    TlsInstall(TlsInstallPayload),
+}
+
+// ── Dashboard WebSocket Topics ──────────────────────────────────────
+
+/// A dashboard subscription topic. Events are only delivered to browser
+/// connections subscribed to the event's topic, so an open tab doesn't
+/// receive every link sample from every sender in the fleet.
+///
+/// Wire form is a plain string: `"fleet"`, `"alerts"`, `"sender:<id>"`,
+/// `"stream:<id>"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DashboardTopic {
+    /// Sender online/offline + heartbeats and stream state changes for
+    /// the whole fleet — low rate, what list pages need.
+    Fleet,
+    /// Alert notifications.
+    Alerts,
+    /// Per-second sender-side telemetry for one sender.
+    Sender(String),
+    /// Per-second receiver-side telemetry for one stream.
+    Stream(String),
+}
+
+impl std::fmt::Display for DashboardTopic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DashboardTopic::Fleet => write!(f, "fleet"),
+            DashboardTopic::Alerts => write!(f, "alerts"),
+            DashboardTopic::Sender(id) => write!(f, "sender:{id}"),
+            DashboardTopic::Stream(id) => write!(f, "stream:{id}"),
+        }
+    }
+}
+
+impl std::str::FromStr for DashboardTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "fleet" => Ok(DashboardTopic::Fleet),
+            None if s == "alerts" => Ok(DashboardTopic::Alerts),
+            Some(("sender", id)) if !id.is_empty() => Ok(DashboardTopic::Sender(id.to_string())),
+            Some(("stream", id)) if !id.is_empty() => Ok(DashboardTopic::Stream(id.to_string())),
+            _ => Err(format!("unknown dashboard topic: {s}")),
+        }
+    }
+}
+
+impl TryFrom<String> for DashboardTopic {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<DashboardTopic> for String {
+    fn from(topic: DashboardTopic) -> Self {
+        topic.to_string()
+    }
+}
+
+// ── Dashboard → Control Plane ───────────────────────────────────────
+
+/// Messages a dashboard client may send after `auth.login`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum DashboardClientMessage {
+    #[serde(rename = "subscribe")]
+    Subscribe(DashboardSubscriptionPayload),
+    #[serde(rename = "unsubscribe")]
+    Unsubscribe(DashboardSubscriptionPayload),
 }
 
 // ── Dashboard WebSocket Events ──────────────────────────────────────
@@ -330,6 +518,34 @@ pub enum DashboardEvent {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         reason: Option<String>,
     },
+
+    /// An alert fired, was acknowledged, or resolved.
+    #[serde(rename = "alert")]
+    Alert(crate::models::AlertEvent),
+
+    /// A sender finished acting on a command (success or failure).
+    #[serde(rename = "command.result")]
+    CommandResult {
+        sender_id: String,
+        ack: CommandAckPayload,
+    },
+}
+
+impl DashboardEvent {
+    /// The topic this event is published on.
+    pub fn topic(&self) -> DashboardTopic {
+        match self {
+            DashboardEvent::SenderStatus { .. } | DashboardEvent::StreamStateChanged { .. } => {
+                DashboardTopic::Fleet
+            }
+            DashboardEvent::StreamStats(p) => DashboardTopic::Sender(p.sender_id.clone()),
+            DashboardEvent::ReceiverStreamStats(p) => DashboardTopic::Stream(p.stream_id.clone()),
+            DashboardEvent::Alert(_) => DashboardTopic::Alerts,
+            DashboardEvent::CommandResult { sender_id, .. } => {
+                DashboardTopic::Sender(sender_id.clone())
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -346,6 +562,7 @@ mod tests {
             agent_version: "0.5.0".into(),
             hostname: "test-sender".into(),
             arch: "x86_64".into(),
+            telemetry_encodings: vec![],
         };
 
         let envelope = Envelope::new("auth.login", &payload);
@@ -415,6 +632,7 @@ mod tests {
             agent_version: "0.5.0".into(),
             hostname: "sender-1".into(),
             arch: "aarch64".into(),
+            telemetry_encodings: vec![],
         });
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -455,6 +673,45 @@ mod tests {
         assert_eq!(msg.request_id(), None);
     }
 
+    #[test]
+    fn command_ack_round_trips_with_error_code() {
+        let msg = AgentMessage::CommandAck(CommandAckPayload {
+            ref_id: "env_1".into(),
+            msg_type: "source.switch".into(),
+            request_id: Some("req_2".into()),
+            success: false,
+            code: Some(crate::ErrorCode::InvalidInput),
+            error: Some("/dev/video1 is not a video capture device".into()),
+        });
+        assert_eq!(msg.request_id(), Some("req_2"));
+
+        let envelope = Envelope::from_message(&msg).unwrap();
+        assert_eq!(envelope.msg_type, "command.ack");
+        assert_eq!(envelope.payload["code"], "validation.invalid_input");
+        match envelope.parse_message::<AgentMessage>().unwrap() {
+            AgentMessage::CommandAck(ack) => {
+                assert!(!ack.success);
+                assert_eq!(ack.code, Some(crate::ErrorCode::InvalidInput));
+            }
+            _ => panic!("wrong variant"),
+        }
+
+        // A successful ack omits the failure fields entirely.
+        let ok = serde_json::to_value(CommandAckPayload {
+            ref_id: "env_3".into(),
+            msg_type: "stream.start".into(),
+            request_id: None,
+            success: true,
+            code: None,
+            error: None,
+        })
+        .unwrap();
+        assert_eq!(
+            ok,
+            serde_json::json!({"ref_id": "env_3", "msg_type": "stream.start", "success": true})
+        );
+    }
+
     #[test]
     fn device_status_running_streams_defaults_empty() {
         // Old agents don't send the field.
@@ -507,6 +764,13 @@ mod tests {
                 resolution: Some("1920x1080".into()),
                 framerate: Some(30),
                 passthrough: None,
+                inputs: vec![InputSource {
+                    id: "wide".into(),
+                    mode: "v4l2".into(),
+                    device: Some("/dev/video2".into()),
+                    uri: None,
+                    pattern: None,
+                }],
             },
             encoder: EncoderConfig {
                 bitrate_kbps: 5000,
@@ -515,11 +779,18 @@ mod tests {
                 codec: Some("h265".into()),
                 min_bitrate_kbps: Some(1500),
                 max_bitrate_kbps: Some(10000),
+                ladder: Vec::new(),
+                overlay: Some(OverlayConfig {
+                    logo: Some("/etc/strata/logo.png".into()),
+                    clock: true,
+                    ..Default::default()
+                }),
             },
             destinations: vec!["dst_yt".into()],
             bonding_config: serde_json::json!({"max_links": 4}),
             psk: Some("secret".into()),
             relay_url: None,
+            ingest_key: Some("isk_test".into()),
         }));
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -531,11 +802,31 @@ mod tests {
                 assert_eq!(p.stream_id, "str_new");
                 assert_eq!(p.encoder.bitrate_kbps, 5000);
                 assert_eq!(p.source.resolution.as_deref(), Some("1920x1080"));
+                assert_eq!(p.source.inputs[0].id, "wide");
+                assert!(p.encoder.overlay.as_ref().unwrap().clock);
             }
             _ => panic!("wrong variant"),
         }
     }
 
+    #[test]
+    fn overlay_positions_default_to_opposite_corners() {
+        let overlay: OverlayConfig =
+            serde_json::from_value(serde_json::json!({ "text": "CAM 1" })).unwrap();
+        assert_eq!(
+            overlay,
+            OverlayConfig {
+                text: Some("CAM 1".into()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(overlay.logo_position, OverlayPosition::TopRight);
+        assert_eq!(overlay.text_position, OverlayPosition::BottomLeft);
+
+        let json = serde_json::to_value(&overlay).unwrap();
+        assert_eq!(json["text_position"], "bottom_left");
+    }
+
     #[test]
     fn config_update_request_id_round_trips_through_enum() {
         // The REST layer correlates config.update with its response by
@@ -559,6 +850,250 @@ mod tests {
         }
     }
 
+    #[test]
+    fn source_select_defaults_to_a_cut() {
+        let msg: ControlMessage = serde_json::from_value(serde_json::json!({
+            "type": "source.select",
+            "payload": { "target": "wide" },
+        }))
+        .unwrap();
+        match msg {
+            ControlMessage::SourceSelect(p) => {
+                assert_eq!(p.target, "wide");
+                assert_eq!(p.transition, SourceTransition::Cut);
+                assert_eq!(p.duration_ms, None);
+            }
+            _ => panic!("wrong variant"),
+        }
+
+        let json = serde_json::to_value(ControlMessage::SourceSelect(SourceSelectPayload {
+            request_id: Some("req_1".into()),
+            target: MAIN_INPUT.into(),
+            transition: SourceTransition::Fade,
+            duration_ms: Some(500),
+        }))
+        .unwrap();
+        assert_eq!(json["payload"]["transition"], "fade");
+    }
+
+    #[test]
+    fn maintenance_schedule_round_trip() {
+        let now = chrono::Utc::now();
+        let msg = ControlMessage::MaintenanceSchedule(MaintenanceSchedulePayload {
+            windows: vec![crate::models::MaintenanceWindow {
+                id: "mnt_a".into(),
+                sender_id: None,
+                starts_at: now,
+                ends_at: now + chrono::Duration::hours(2),
+                allow_ota: true,
+                allow_reboot: true,
+                reason: Some("firmware".into()),
+            }],
+        });
+        assert!(msg.request_id().is_none());
+        let envelope = Envelope::from_message(&msg).unwrap();
+        assert_eq!(envelope.msg_type, "maintenance.schedule");
+        match envelope.parse_message::<ControlMessage>().unwrap() {
+            ControlMessage::MaintenanceSchedule(p) => {
+                assert_eq!(p.windows.len(), 1);
+                assert!(p.windows[0].allow_reboot);
+                assert!(p.windows[0].sender_id.is_none());
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn portal_auth_round_trip() {
+        let msg = ControlMessage::PortalAuth(PortalAuthPayload {
+            pin_hash: Some("$argon2id$v=19$...".into()),
+        });
+        assert!(msg.request_id().is_none());
+        let envelope = Envelope::from_message(&msg).unwrap();
+        assert_eq!(envelope.msg_type, "portal.auth");
+        match envelope.parse_message::<ControlMessage>().unwrap() {
+            ControlMessage::PortalAuth(p) => assert!(p.pin_hash.is_some()),
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn unknown_type_from_a_newer_peer_is_nakked_with_its_request_id() {
+        let json = r#"{"id":"env-1","type":"modem.reboot","ts":"2026-01-01T00:00:00Z",
+            "proto_version":2,"payload":{"request_id":"req-9","interface":"wwan0"}}"#;
+        let envelope: Envelope = serde_json::from_str(json).unwrap();
+        let nak = envelope
+            .parse_message_or_nak::<ControlMessage>()
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(nak.code, crate::ErrorCode::UnsupportedCommand);
+        assert_eq!(nak.ref_id, "env-1");
+        assert_eq!(nak.msg_type, "modem.reboot");
+        assert_eq!(nak.request_id.as_deref(), Some("req-9"));
+        assert_eq!(nak.proto_version, crate::PROTOCOL_VERSION);
+
+        // The NAK routes back to the pending RPC like any response.
+        let reply = Envelope::from_message(&AgentMessage::Nak(nak)).unwrap();
+        assert_eq!(reply.msg_type, "message.nak");
+        let parsed: AgentMessage = reply.parse_message().unwrap();
+        assert_eq!(parsed.request_id(), Some("req-9"));
+    }
+
+    /// The tags serde's derived `Deserialize` accepts, as it lists them
+    /// to [`serde::de::Error::unknown_variant`] for a tag it doesn't know.
+    fn serde_variant_tags<M: serde::de::DeserializeOwned>() -> Vec<&'static str> {
+        use serde::de::value::MapDeserializer;
+
+        #[derive(Debug)]
+        struct Probe(&'static [&'static str]);
+
+        impl std::fmt::Display for Probe {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "variant probe")
+            }
+        }
+
+        impl std::error::Error for Probe {}
+
+        impl serde::de::Error for Probe {
+            fn custom<T: std::fmt::Display>(msg: T) -> Self {
+                panic!("expected an unknown_variant error, got: {msg}")
+            }
+
+            fn unknown_variant(_: &str, expected: &'static [&'static str]) -> Self {
+                Probe(expected)
+            }
+        }
+
+        let probe = MapDeserializer::<_, Probe>::new(std::iter::once(("type", "")));
+        let Err(Probe(tags)) = M::deserialize(probe) else {
+            panic!("empty tag parsed");
+        };
+        let mut tags = tags.to_vec();
+        tags.sort_unstable();
+        tags
+    }
+
+    fn sorted_types<M: crate::MessageTypes>() -> Vec<&'static str> {
+        let mut types = M::TYPES.to_vec();
+        types.sort_unstable();
+        types
+    }
+
+    #[test]
+    fn message_type_lists_match_the_direction_enums() {
+        assert_eq!(
+            sorted_types::<AgentMessage>(),
+            serde_variant_tags::<AgentMessage>()
+        );
+        assert_eq!(
+            sorted_types::<ControlMessage>(),
+            serde_variant_tags::<ControlMessage>()
+        );
+    }
+
+    #[test]
+    fn a_version_1_agents_messages_parse_without_upgrades() {
+        // Shapes as sent by field agents from before the typed enums: no
+        // proto_version on the envelope, none of the later optional fields.
+        for (msg_type, payload) in [
+            (
+                "auth.login",
+                r#"{"enrollment_token":"snd_1.ABCD-EFGH","agent_version":"0.5.0",
+                "hostname":"field-1","arch":"aarch64"}"#,
+            ),
+            (
+                "device.status",
+                r#"{"network_interfaces":[],"media_inputs":[],"stream_state":"idle",
+                "cpu_percent":3.5,"mem_used_mb":412,"uptime_s":86400}"#,
+            ),
+            (
+                "stream.stats",
+                r#"{"stream_id":"str_1","uptime_s":12,"encoder_bitrate_kbps":4000,
+                "links":[{"id":0,"interface":"wwan0","state":"Live","rtt_ms":41.5,
+                "loss_rate":0.01,"capacity_bps":6000000,"sent_bytes":1048576,
+                "signal_dbm":-71,"rsrp":null,"rsrq":null,"sinr":null,"cqi":null}]}"#,
+            ),
+            (
+                "stream.ended",
+                r#"{"stream_id":"str_1","reason":"user_stop","duration_s":12,
+                "total_bytes":6000000}"#,
+            ),
+        ] {
+            let json = format!(
+                r#"{{"id":"env-1","type":"{msg_type}","ts":"2026-01-01T00:00:00Z","payload":{payload}}}"#
+            );
+            let envelope: Envelope = serde_json::from_str(&json).unwrap();
+            assert_eq!(envelope.proto_version, 1);
+            if let Err(nak) = envelope.parse_message_or_nak::<AgentMessage>() {
+                panic!("{msg_type} from a version 1 agent was rejected: {nak:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn an_unparseable_nak_is_never_nakked() {
+        let envelope = Envelope::new(
+            "message.nak",
+            serde_json::json!({ "ref_id": 7, "msg_type": "x", "code": "internal" }),
+        );
+        assert!(
+            envelope
+                .parse_message_or_nak::<AgentMessage>()
+                .unwrap_err()
+                .is_none()
+        );
+        assert!(
+            envelope
+                .parse_message_or_nak::<ControlMessage>()
+                .unwrap_err()
+                .is_none()
+        );
+        let late = envelope.with_deadline(chrono::Utc::now() - chrono::Duration::seconds(1));
+        assert!(late.expired_nak(chrono::Utc::now()).is_none());
+    }
+
+    #[test]
+    fn a_call_past_its_deadline_is_nakked_as_timed_out() {
+        let now = chrono::Utc::now();
+        let envelope = Envelope::new("test.run", serde_json::json!({ "request_id": "req-3" }))
+            .with_deadline(now + chrono::Duration::seconds(10));
+        let json = serde_json::to_string(&envelope).unwrap();
+        let envelope: Envelope = serde_json::from_str(&json).unwrap();
+        assert!(envelope.expired_nak(now).is_none());
+
+        let nak = envelope
+            .expired_nak(now + chrono::Duration::seconds(11))
+            .unwrap();
+        assert_eq!(nak.code, crate::ErrorCode::Timeout);
+        assert_eq!(nak.request_id.as_deref(), Some("req-3"));
+        assert_eq!(nak.ref_id, envelope.id);
+
+        // No deadline, never expired — and none on the wire either.
+        let envelope = Envelope::new("test.run", serde_json::json!({}));
+        assert!(
+            !serde_json::to_string(&envelope)
+                .unwrap()
+                .contains("deadline")
+        );
+        assert!(
+            envelope
+                .expired_nak(now + chrono::Duration::days(1))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn malformed_payload_is_nakked_as_invalid() {
+        let envelope = Envelope::new("config.set", serde_json::json!({ "receiver_url": 5 }));
+        let nak = envelope
+            .parse_message_or_nak::<ControlMessage>()
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(nak.code, crate::ErrorCode::InvalidInput);
+        assert!(nak.request_id.is_none());
+    }
+
     #[test]
     fn receiver_message_round_trip() {
         let msg = ReceiverMessage::Status(ReceiverStatusPayload {
@@ -589,6 +1124,9 @@ mod tests {
             link_count: 2,
             relay_url: None,
             bonding_config: serde_json::Value::Null,
+            ingest_key: None,
+            preview_key: None,
+            ingest: None,
         });
         let envelope = Envelope::from_message(&msg).unwrap();
         assert_eq!(envelope.msg_type, "receiver.stream.start");
@@ -608,11 +1146,38 @@ mod tests {
             success: true,
             bind_ports: vec![5000, 5002],
             error: None,
+            ingest_url: None,
         });
         let envelope = Envelope::from_message(&ack).unwrap();
         assert_eq!(envelope.msg_type, "receiver.stream.started");
     }
 
+    #[test]
+    fn receiver_ingest_start_round_trip() {
+        // Older receivers ignore the field; a missing one reads as bonded.
+        let json = r#"{"request_id":"req_2","stream_id":"str_i","link_count":0,"relay_url":null}"#;
+        let p: ReceiverStreamStartPayload = serde_json::from_str(json).unwrap();
+        assert_eq!(p.ingest, None);
+
+        let msg = ReceiverControlMessage::StreamStart(ReceiverStreamStartPayload {
+            ingest: Some(crate::models::IngestProtocol::Srt),
+            ingest_key: Some("k3y".into()),
+            ..p
+        });
+        let json = serde_json::to_string(&Envelope::from_message(&msg).unwrap()).unwrap();
+        assert!(json.contains(r#""ingest":"srt""#));
+        let recovered: ReceiverControlMessage = serde_json::from_str::<Envelope>(&json)
+            .unwrap()
+            .parse_message()
+            .unwrap();
+        match recovered {
+            ReceiverControlMessage::StreamStart(p) => {
+                assert_eq!(p.ingest, Some(crate::models::IngestProtocol::Srt));
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
     #[test]
     fn dashboard_event_serialization() {
         let event = DashboardEvent::StreamStateChanged {
@@ -675,6 +1240,10 @@ mod tests {
             links: vec![],
             sender_metrics: None,
             receiver_metrics: None,
+            ladder: None,
+            quality: None,
+            elements: Vec::new(),
+            media: None,
         });
 
         let json = serde_json::to_string(&event).unwrap();
@@ -692,6 +1261,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dashboard_alert_event_goes_to_alerts_topic() {
+        let event = DashboardEvent::Alert(crate::models::AlertEvent {
+            id: "alr_1".into(),
+            sender_id: "snd_xyz".into(),
+            sender_name: None,
+            rule_id: "rule_1".into(),
+            rule_name: "High RTT".into(),
+            metric: "rtt_ms".into(),
+            condition: "above".into(),
+            threshold: 200.0,
+            severity: crate::models::AlertSeverity::Critical,
+            value: 340.0,
+            state: crate::models::AlertState::Firing,
+            fired_at: chrono::Utc::now(),
+            acknowledged_at: None,
+            acknowledged_by: None,
+            resolved_at: None,
+            resolved_by: None,
+        });
+        assert_eq!(event.topic(), DashboardTopic::Alerts);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "alert");
+        assert_eq!(json["data"]["severity"], "critical");
+        assert_eq!(json["data"]["state"], "firing");
+    }
+
+    #[test]
+    fn dashboard_topic_string_round_trip() {
+        for topic in [
+            DashboardTopic::Fleet,
+            DashboardTopic::Alerts,
+            DashboardTopic::Sender("snd_a".into()),
+            DashboardTopic::Stream("str_b".into()),
+        ] {
+            let json = serde_json::to_string(&topic).unwrap();
+            let parsed: DashboardTopic = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, topic);
+        }
+        assert_eq!(
+            serde_json::to_string(&DashboardTopic::Sender("snd_a".into())).unwrap(),
+            r#""sender:snd_a""#
+        );
+        assert!("sender:".parse::<DashboardTopic>().is_err());
+        assert!("links".parse::<DashboardTopic>().is_err());
+    }
+
+    #[test]
+    fn dashboard_subscribe_message_parses() {
+        let json = r#"{"type":"subscribe","payload":{"topics":["fleet","sender:snd_x"]}}"#;
+        match serde_json::from_str::<DashboardClientMessage>(json).unwrap() {
+            DashboardClientMessage::Subscribe(p) => {
+                assert_eq!(
+                    p.topics,
+                    vec![
+                        DashboardTopic::Fleet,
+                        DashboardTopic::Sender("snd_x".into())
+                    ]
+                );
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
     #[test]
     fn interface_command_payload_serde() {
         let cmd = InterfaceCommandPayload {
@@ -7,6 +7,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::telemetry::LinkSample;
+
 // ── User ────────────────────────────────────────────────────────────
 
 /// A platform user (operator or viewer).
@@ -15,6 +17,7 @@ use serde::{Deserialize, Serialize};
 /// isolated by `owner_id` on every table.  No user can see or modify
 /// another user's resources.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct User {
     pub id: String,
     pub email: String,
@@ -25,6 +28,7 @@ pub struct User {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum UserRole {
     Operator,
@@ -56,6 +60,7 @@ impl std::str::FromStr for UserRole {
 
 /// A registered sender device (field unit).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Sender {
     pub id: String,
     pub owner_id: String,
@@ -68,6 +73,7 @@ pub struct Sender {
 
 /// Live status of a sender device, reported over WSS.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SenderStatus {
     pub network_interfaces: Vec<NetworkInterface>,
     pub media_inputs: Vec<MediaInput>,
@@ -78,6 +84,7 @@ pub struct SenderStatus {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct NetworkInterface {
     pub name: String,
     #[serde(rename = "type")]
@@ -133,6 +140,7 @@ fn default_priority() -> u32 {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum InterfaceType {
     Cellular,
@@ -141,6 +149,7 @@ pub enum InterfaceType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum InterfaceState {
     Connected,
@@ -150,6 +159,7 @@ pub enum InterfaceState {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MediaInput {
     pub device: String,
     #[serde(rename = "type")]
@@ -160,6 +170,7 @@ pub struct MediaInput {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum MediaInputType {
     V4l2,
@@ -168,6 +179,7 @@ pub enum MediaInputType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum MediaInputStatus {
     Available,
@@ -179,6 +191,7 @@ pub enum MediaInputStatus {
 
 /// An active or historical broadcast stream.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Stream {
     pub id: String,
     pub sender_id: String,
@@ -192,6 +205,7 @@ pub struct Stream {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum StreamState {
     Idle,
@@ -219,6 +233,7 @@ impl std::fmt::Display for StreamState {
 
 /// A configured streaming destination (YouTube, Twitch, SRT endpoint, etc.).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Destination {
     pub id: String,
     pub owner_id: String,
@@ -232,6 +247,7 @@ pub struct Destination {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DestinationPlatform {
     Youtube,
@@ -251,6 +267,337 @@ impl std::fmt::Display for DestinationPlatform {
     }
 }
 
+// ── Maintenance Window ──────────────────────────────────────────────
+
+/// A scheduled maintenance window. `sender_id: None` covers the owner's
+/// whole fleet. While a window is open, alerting for the covered senders is
+/// suppressed and the agent may reboot / auto-apply OTA updates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MaintenanceWindow {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// The agent may auto-apply a pending OTA update inside the window.
+    #[serde(default)]
+    pub allow_ota: bool,
+    /// The agent may reboot (e.g. to finish an update) inside the window.
+    #[serde(default)]
+    pub allow_reboot: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// Whether `now` falls inside `[starts_at, ends_at)`.
+    pub fn is_open_at(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+// ── Stream Schedule ─────────────────────────────────────────────────
+
+/// A broadcast booked ahead of time. The control plane starts it at
+/// `starts_at` and stops it at `ends_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduledStream {
+    pub id: String,
+    pub sender_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_id: Option<String>,
+    /// Capture device (e.g. `/dev/video0`); `None` streams a test pattern.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Video profile: "WIDTHxHEIGHT" and fps. `None` = 1080p30.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framerate: Option<u32>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub state: ScheduleState,
+    /// The stream this booking started, once it has.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<String>,
+    /// Why the booking failed to start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// IDs of other bookings that double-book the same sender.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+}
+
+impl ScheduledStream {
+    /// Whether both bookings want the same sender at the same time.
+    /// Intervals are half-open, so back-to-back bookings do not clash.
+    /// Finished bookings never conflict.
+    pub fn overlaps(&self, other: &ScheduledStream) -> bool {
+        self.id != other.id
+            && self.sender_id == other.sender_id
+            && self.state.is_pending_or_running()
+            && other.state.is_pending_or_running()
+            && self.starts_at < other.ends_at
+            && other.starts_at < self.ends_at
+    }
+}
+
+/// Fill in [`ScheduledStream::conflicts`] for every booking in `schedule`.
+pub fn mark_conflicts(schedule: &mut [ScheduledStream]) {
+    let conflicts: Vec<Vec<String>> = schedule
+        .iter()
+        .map(|a| {
+            schedule
+                .iter()
+                .filter(|b| a.overlaps(b))
+                .map(|b| b.id.clone())
+                .collect()
+        })
+        .collect();
+    for (entry, conflicts) in schedule.iter_mut().zip(conflicts) {
+        entry.conflicts = conflicts;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleState {
+    /// Waiting for `starts_at`.
+    Scheduled,
+    /// Its stream was started and has not been stopped by the scheduler.
+    Running,
+    /// Stopped at `ends_at`.
+    Completed,
+    /// Could not be started (sender offline, already streaming, …).
+    Failed,
+}
+
+impl ScheduleState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduleState::Scheduled => "scheduled",
+            ScheduleState::Running => "running",
+            ScheduleState::Completed => "completed",
+            ScheduleState::Failed => "failed",
+        }
+    }
+
+    pub fn is_pending_or_running(&self) -> bool {
+        matches!(self, ScheduleState::Scheduled | ScheduleState::Running)
+    }
+}
+
+impl std::str::FromStr for ScheduleState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scheduled" => Ok(ScheduleState::Scheduled),
+            "running" => Ok(ScheduleState::Running),
+            "completed" => Ok(ScheduleState::Completed),
+            "failed" => Ok(ScheduleState::Failed),
+            _ => Err(format!("unknown schedule state: {s}")),
+        }
+    }
+}
+
+// ── Usage ───────────────────────────────────────────────────────────
+
+/// One sender's metered usage for one calendar month (UTC). Streams are
+/// attributed to the month they started in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UsageRollup {
+    /// `YYYY-MM`.
+    pub month: String,
+    pub sender_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    pub stream_count: u64,
+    /// Wall-clock minutes between start and end, summed over streams
+    /// (still-running streams count up to now).
+    pub streamed_minutes: f64,
+    /// Bytes put on the wire across all bonded links.
+    pub total_bytes: u64,
+}
+
+impl UsageRollup {
+    /// Transported volume in decimal gigabytes, as billed.
+    pub fn gigabytes(&self) -> f64 {
+        self.total_bytes as f64 / 1e9
+    }
+}
+
+// ── Ingest ──────────────────────────────────────────────────────────
+
+/// Plain contribution protocol of an ingest sender — a phone running
+/// Larix, an OBS box — that pushes straight into a receiver instead of
+/// bonding through the Strata agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IngestProtocol {
+    Srt,
+    Rtmp,
+}
+
+impl IngestProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestProtocol::Srt => "srt",
+            IngestProtocol::Rtmp => "rtmp",
+        }
+    }
+}
+
+impl std::str::FromStr for IngestProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "srt" => Ok(IngestProtocol::Srt),
+            "rtmp" => Ok(IngestProtocol::Rtmp),
+            _ => Err(format!("unknown ingest protocol: {s}")),
+        }
+    }
+}
+
+// ── Alerts ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for AlertSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(AlertSeverity::Info),
+            "warning" => Ok(AlertSeverity::Warning),
+            "critical" => Ok(AlertSeverity::Critical),
+            _ => Err(format!("unknown alert severity: {s}")),
+        }
+    }
+}
+
+/// Lifecycle of a fired alert. `Acknowledged` is still open — it resolves
+/// on its own once the rule stops breaching, or when an operator resolves it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    Firing,
+    Acknowledged,
+    Resolved,
+}
+
+impl AlertState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertState::Firing => "firing",
+            AlertState::Acknowledged => "acknowledged",
+            AlertState::Resolved => "resolved",
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !matches!(self, AlertState::Resolved)
+    }
+}
+
+impl std::str::FromStr for AlertState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "firing" => Ok(AlertState::Firing),
+            "acknowledged" => Ok(AlertState::Acknowledged),
+            "resolved" => Ok(AlertState::Resolved),
+            _ => Err(format!("unknown alert state: {s}")),
+        }
+    }
+}
+
+/// One firing of an alert rule, from breach to resolution. The rule's name,
+/// metric and threshold are copied in so history survives rule edits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AlertEvent {
+    pub id: String,
+    pub sender_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub metric: String,
+    pub condition: String,
+    pub threshold: f64,
+    pub severity: AlertSeverity,
+    /// Metric value when the alert fired.
+    pub value: f64,
+    pub state: AlertState,
+    pub fired_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// Email of the user who acknowledged it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Email of the user who resolved it; `None` once resolved means the
+    /// condition cleared by itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+}
+
+// ── Audit Log ───────────────────────────────────────────────────────
+
+/// A recorded change to the owner's fleet — who did what, to which sender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEntry {
+    pub id: String,
+    /// `None` for actions taken by the control plane itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
+    /// The actor's email, if the account still exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    /// Dotted verb, e.g. `stream.start`, `sender.delete`, `alert.ack`.
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 // ── Transport Stats ─────────────────────────────────────────────────
 
 /// Sender-side transport protocol statistics, suitable for Prometheus export.
@@ -258,6 +605,7 @@ impl std::fmt::Display for DestinationPlatform {
 /// Mirrors `strata_transport::stats::SenderStats` but lives in strata-common
 /// so the metrics renderer doesn't need to depend on strata-transport.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TransportSenderMetrics {
     /// Total packets sent (including retransmissions).
     pub packets_sent: u64,
@@ -280,6 +628,7 @@ pub struct TransportSenderMetrics {
 /// Mirrors `strata_transport::stats::ReceiverStats` but lives in strata-common
 /// so the metrics renderer doesn't need to depend on strata-transport.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TransportReceiverMetrics {
     /// Total packets received (including duplicates and late).
     pub packets_received: u64,
@@ -309,6 +658,7 @@ pub struct TransportReceiverMetrics {
 /// stay green while egress is wedged (2026-07-04 run 4: 98 s of dead air),
 /// so segment production is the only trustworthy egress liveness signal.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EgressStats {
     /// Cumulative HLS segments produced across all pipeline generations.
     pub segments_produced: u64,
@@ -318,40 +668,317 @@ pub struct EgressStats {
     pub last_segment_age_ms: u64,
 }
 
-// ── Link Stats ──────────────────────────────────────────────────────
+/// Receiver-side lipsync clocks of the delivered MPEG-TS, in milliseconds.
+///
+/// Each places a lipsync complaint on one side: drift is the sender's
+/// audio and video clocks disagreeing, PCR jitter is transport timing the
+/// jitter buffer passed downstream, and a steady PTS lead with both clean
+/// points past the receiver.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AvSyncStats {
+    /// Smoothed video PTS − audio PTS across the mux interleave.
+    pub av_skew_ms: f64,
+    /// Change in skew since the stream settled.
+    pub av_drift_ms: f64,
+    /// PCR inter-arrival jitter after reassembly.
+    pub pcr_jitter_ms: f64,
+    /// Smoothed video PTS − PCR: how early frames reach the demuxer.
+    pub video_pts_lead_ms: f64,
+}
 
-/// Per-link statistics from the bonding engine, sent in `stream.stats`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinkStats {
-    pub id: u32,
-    pub interface: String,
-    pub state: String,
-    pub rtt_ms: f64,
-    pub loss_rate: f64,
-    pub capacity_bps: u64,
-    pub sent_bytes: u64,
-    /// Current observed throughput in bits per second.
+/// Sender-side sound and picture activity at the encoder input, for the
+/// confidence meters on the Stream tab.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MediaActivity {
+    /// Per-channel audio RMS over the last interval, dBFS (silence is
+    /// reported as [`MediaActivity::SILENCE_DB`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audio_rms_db: Vec<f64>,
+    /// Per-channel audio peak over the last interval, dBFS.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audio_peak_db: Vec<f64>,
+    /// Mean luma of the latest analysed frame, 0.0–1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub luma: Option<f64>,
+    /// Luma variance of that frame; near 0 on a flat or black picture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub luma_variance: Option<f64>,
+    /// Time since the analysed picture last changed, in ms; stays around
+    /// the analysis interval while it moves. Live sources carry sensor
+    /// noise, so only a repeated or stalled frame lets it climb.
     #[serde(default)]
-    pub observed_bps: u64,
-    pub signal_dbm: Option<i32>,
-    pub rsrp: Option<f32>,
-    pub rsrq: Option<f32>,
-    pub sinr: Option<f32>,
-    pub cqi: Option<u8>,
-    /// Link technology kind (e.g. "ethernet", "cellular", "wifi").
+    pub frozen_ms: u64,
+}
+
+impl MediaActivity {
+    /// Floor for audio levels; `level` reports digital silence as −∞.
+    pub const SILENCE_DB: f64 = -100.0;
+}
+
+// ── Link Events ─────────────────────────────────────────────────────
+
+/// What a bonded link was doing at some point in a stream, as drawn on the
+/// link timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkPhase {
+    /// Up, but still being measured before carrying full load.
+    Probing,
+    /// Carrying traffic.
+    Live,
+    /// Up, but idle while other links carry the stream.
+    Failover,
+    /// Dead, administratively down, or gone from the stats feed.
+    Down,
+}
+
+impl LinkPhase {
+    /// Classify one link from a `stream.stats` sample. `peers_carrying`
+    /// says whether any other link had traffic in the same sample.
+    pub fn from_stats(link: &LinkSample, peers_carrying: bool) -> Self {
+        match link.state.as_str() {
+            "Down" | "OS Down" => LinkPhase::Down,
+            "Probing" => LinkPhase::Probing,
+            _ if link.observed_bps.0 == 0 && peers_carrying => LinkPhase::Failover,
+            _ => LinkPhase::Live,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkPhase::Probing => "probing",
+            LinkPhase::Live => "live",
+            LinkPhase::Failover => "failover",
+            LinkPhase::Down => "down",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkPhase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "probing" => Ok(LinkPhase::Probing),
+            "live" => Ok(LinkPhase::Live),
+            "failover" => Ok(LinkPhase::Failover),
+            "down" => Ok(LinkPhase::Down),
+            _ => Err(format!("unknown link phase: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkEventKind {
+    /// The link entered `phase`.
+    Phase,
+    /// The modem moved to another band; `detail` reads "old → new".
+    BandChange,
+    /// The interface was disabled (taken out of the bond).
+    Disabled,
+    /// The interface was re-enabled.
+    Enabled,
+}
+
+impl LinkEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkEventKind::Phase => "phase",
+            LinkEventKind::BandChange => "band_change",
+            LinkEventKind::Disabled => "disabled",
+            LinkEventKind::Enabled => "enabled",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkEventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "phase" => Ok(LinkEventKind::Phase),
+            "band_change" => Ok(LinkEventKind::BandChange),
+            "disabled" => Ok(LinkEventKind::Disabled),
+            "enabled" => Ok(LinkEventKind::Enabled),
+            _ => Err(format!("unknown link event kind: {s}")),
+        }
+    }
+}
+
+/// One entry of a stream's link event feed (`GET
+/// /api/streams/{id}/link-events`), oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkEvent {
+    pub ts: DateTime<Utc>,
+    pub interface: String,
+    pub kind: LinkEventKind,
+    /// Set for [`LinkEventKind::Phase`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<LinkPhase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// A stretch of one phase on a link's timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkSegment {
+    pub phase: LinkPhase,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// One row of the link timeline: the link's phases back to back, plus the
+/// point events (band changes, enable/disable) that fell on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkTimeline {
+    pub interface: String,
+    pub segments: Vec<LinkSegment>,
+    pub markers: Vec<LinkEvent>,
+}
+
+/// Fold an event feed into per-link rows, in order of first appearance.
+/// Each phase lasts until the link's next phase event; the last one runs
+/// to `end` (the stream's end, or now while it is live).
+pub fn link_timelines(events: &[LinkEvent], end: DateTime<Utc>) -> Vec<LinkTimeline> {
+    let mut rows: Vec<LinkTimeline> = Vec::new();
+    for event in events {
+        let idx = match rows.iter().position(|r| r.interface == event.interface) {
+            Some(idx) => idx,
+            None => {
+                rows.push(LinkTimeline {
+                    interface: event.interface.clone(),
+                    segments: Vec::new(),
+                    markers: Vec::new(),
+                });
+                rows.len() - 1
+            }
+        };
+        let row = &mut rows[idx];
+        match (event.kind, event.phase) {
+            (LinkEventKind::Phase, Some(phase)) => {
+                if let Some(open) = row.segments.last_mut() {
+                    if open.phase == phase {
+                        continue;
+                    }
+                    open.to = event.ts;
+                }
+                row.segments.push(LinkSegment {
+                    phase,
+                    from: event.ts,
+                    to: end,
+                });
+            }
+            _ => row.markers.push(event.clone()),
+        }
+    }
+    for row in &mut rows {
+        if let Some(open) = row.segments.last_mut() {
+            open.to = open.to.max(open.from);
+        }
+    }
+    rows
+}
+
+// ── Incident Reports ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentKind {
+    /// A link dropped to failover or went down.
+    LinkFailover,
+    /// Loss stayed above the report's spike threshold.
+    LossSpike,
+    /// An alert rule fired.
+    Alert,
+    /// The ABR ladder moved the encoder to another resolution.
+    LadderStep,
+    /// An operator's note on the stream's timeline.
+    Annotation,
+}
+
+impl IncidentKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentKind::LinkFailover => "Link failover",
+            IncidentKind::LossSpike => "Loss spike",
+            IncidentKind::Alert => "Alert",
+            IncidentKind::LadderStep => "Ladder step",
+            IncidentKind::Annotation => "Annotation",
+        }
+    }
+}
+
+/// One entry of a [`StreamReport`]'s timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReportIncident {
+    pub ts: DateTime<Utc>,
+    pub kind: IncidentKind,
+    /// The link involved, for link failovers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    pub detail: String,
+    /// How long the incident lasted, for those with an extent (loss
+    /// spikes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_s: Option<u64>,
+}
+
+/// Post-stream incident report (`GET /api/streams/{id}/report`). Built
+/// when the stream ends and kept with it; annotations are merged in when
+/// the report is read, so notes added afterwards still show up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StreamReport {
+    pub stream_id: String,
+    pub sender_id: String,
+    pub sender_name: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub link_kind: Option<String>,
-    /// BBRv3 estimated bottleneck bandwidth.
+    pub end_reason: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub btlbw_bps: Option<u64>,
-    /// BBRv3 estimated minimum RTT.
+    pub error_message: Option<String>,
+    pub total_bytes: u64,
+    pub generated_at: DateTime<Utc>,
+    /// Oldest first.
+    pub incidents: Vec<ReportIncident>,
+}
+
+impl StreamReport {
+    pub fn count(&self, kind: IncidentKind) -> usize {
+        self.incidents.iter().filter(|i| i.kind == kind).count()
+    }
+
+    pub fn duration_s(&self) -> Option<i64> {
+        Some((self.ended_at? - self.started_at?).num_seconds())
+    }
+}
+
+/// An operator's note pinned to a point on a stream's timeline (`GET
+/// /api/streams/{id}/annotations`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StreamAnnotation {
+    pub id: i64,
+    pub ts: DateTime<Utc>,
+    /// Email of the user who wrote it; gone once they are deleted.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub rtprop_ms: Option<f64>,
+    pub author: Option<String>,
+    pub text: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::telemetry::{Bps, Millis};
 
     // ── Enum Serde Round-Trips ──────────────────────────────────
 
@@ -525,46 +1152,6 @@ mod tests {
         assert_eq!(parsed.capabilities.len(), 2);
     }
 
-    #[test]
-    fn link_stats_serde() {
-        let stats = LinkStats {
-            id: 1,
-            interface: "wwan0".into(),
-            state: "connected".into(),
-            rtt_ms: 23.5,
-            loss_rate: 0.01,
-            capacity_bps: 15_000_000,
-            sent_bytes: 1_048_576,
-            observed_bps: 8_000_000,
-            signal_dbm: Some(-72),
-            link_kind: Some("cellular".into()),
-            rsrp: None,
-            rsrq: None,
-            sinr: None,
-            cqi: None,
-            btlbw_bps: Some(12_000_000),
-            rtprop_ms: Some(20.0),
-        };
-        let json = serde_json::to_string(&stats).unwrap();
-        let parsed: LinkStats = serde_json::from_str(&json).unwrap();
-        assert_eq!(parsed.id, 1);
-        assert!((parsed.rtt_ms - 23.5).abs() < f64::EPSILON);
-        assert_eq!(parsed.capacity_bps, 15_000_000);
-        assert_eq!(parsed.observed_bps, 8_000_000);
-        assert_eq!(parsed.link_kind.as_deref(), Some("cellular"));
-        assert_eq!(parsed.btlbw_bps, Some(12_000_000));
-        assert_eq!(parsed.rtprop_ms, Some(20.0));
-    }
-
-    #[test]
-    fn link_stats_backward_compat() {
-        // Old JSON without observed_bps or link_kind should still parse
-        let json = r#"{"id":0,"interface":"eth0","state":"Live","rtt_ms":10.0,"loss_rate":0.0,"capacity_bps":10000000,"sent_bytes":0,"signal_dbm":null}"#;
-        let parsed: LinkStats = serde_json::from_str(json).unwrap();
-        assert_eq!(parsed.observed_bps, 0);
-        assert!(parsed.link_kind.is_none());
-    }
-
     #[test]
     fn transport_sender_metrics_serde() {
         let stats = TransportSenderMetrics {
@@ -726,4 +1313,183 @@ mod tests {
         assert_eq!(parsed.state, StreamState::Live);
         assert_eq!(parsed.total_bytes, 1_000_000);
     }
+
+    #[test]
+    fn maintenance_window_open_interval_is_half_open() {
+        let start = chrono::Utc::now();
+        let window = MaintenanceWindow {
+            id: "mnt_test".into(),
+            sender_id: None,
+            starts_at: start,
+            ends_at: start + chrono::Duration::hours(1),
+            allow_ota: true,
+            allow_reboot: false,
+            reason: None,
+        };
+        assert!(window.is_open_at(start));
+        assert!(window.is_open_at(start + chrono::Duration::minutes(59)));
+        assert!(!window.is_open_at(start + chrono::Duration::hours(1)));
+        assert!(!window.is_open_at(start - chrono::Duration::seconds(1)));
+    }
+
+    fn booking(id: &str, sender: &str, from_h: i64, to_h: i64) -> ScheduledStream {
+        let base = chrono::DateTime::parse_from_rfc3339("2026-05-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        ScheduledStream {
+            id: id.into(),
+            sender_id: sender.into(),
+            sender_name: None,
+            title: None,
+            destination_id: None,
+            device: None,
+            resolution: None,
+            framerate: None,
+            starts_at: base + chrono::Duration::hours(from_h),
+            ends_at: base + chrono::Duration::hours(to_h),
+            state: ScheduleState::Scheduled,
+            stream_id: None,
+            error: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn schedule_conflicts_are_same_sender_overlaps() {
+        let mut schedule = vec![
+            booking("a", "snd_1", 0, 2),
+            booking("b", "snd_1", 1, 3),
+            // Back-to-back with "b".
+            booking("c", "snd_1", 3, 4),
+            // Overlaps "a" but on another sender.
+            booking("d", "snd_2", 0, 2),
+        ];
+        mark_conflicts(&mut schedule);
+        assert_eq!(schedule[0].conflicts, vec!["b".to_string()]);
+        assert_eq!(schedule[1].conflicts, vec!["a".to_string()]);
+        assert!(schedule[2].conflicts.is_empty());
+        assert!(schedule[3].conflicts.is_empty());
+
+        schedule[1].state = ScheduleState::Failed;
+        mark_conflicts(&mut schedule);
+        assert!(schedule[0].conflicts.is_empty());
+    }
+
+    #[test]
+    fn alert_severity_and_state_wire_names() {
+        for sev in [
+            AlertSeverity::Info,
+            AlertSeverity::Warning,
+            AlertSeverity::Critical,
+        ] {
+            assert_eq!(
+                serde_json::to_value(sev).unwrap(),
+                serde_json::json!(sev.as_str())
+            );
+            assert_eq!(sev.as_str().parse::<AlertSeverity>().unwrap(), sev);
+        }
+        for st in [
+            AlertState::Firing,
+            AlertState::Acknowledged,
+            AlertState::Resolved,
+        ] {
+            assert_eq!(
+                serde_json::to_value(st).unwrap(),
+                serde_json::json!(st.as_str())
+            );
+            assert_eq!(st.as_str().parse::<AlertState>().unwrap(), st);
+        }
+        assert!(AlertState::Acknowledged.is_open());
+        assert!(!AlertState::Resolved.is_open());
+        assert!("urgent".parse::<AlertSeverity>().is_err());
+    }
+
+    #[test]
+    fn link_timelines_split_phases_and_keep_markers() {
+        let t0 = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |s: i64| t0 + chrono::Duration::seconds(s);
+        let phase = |s, iface: &str, p| LinkEvent {
+            ts: at(s),
+            interface: iface.into(),
+            kind: LinkEventKind::Phase,
+            phase: Some(p),
+            detail: None,
+        };
+        let band = LinkEvent {
+            ts: at(20),
+            interface: "wwan0".into(),
+            kind: LinkEventKind::BandChange,
+            phase: None,
+            detail: Some("B3 → B7".into()),
+        };
+        let events = vec![
+            phase(0, "wwan0", LinkPhase::Probing),
+            phase(0, "eth0", LinkPhase::Live),
+            phase(5, "wwan0", LinkPhase::Live),
+            phase(6, "wwan0", LinkPhase::Live),
+            band.clone(),
+            phase(30, "wwan0", LinkPhase::Down),
+        ];
+
+        let rows = link_timelines(&events, at(60));
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].interface, "wwan0");
+        let spans: Vec<_> = rows[0]
+            .segments
+            .iter()
+            .map(|s| (s.phase, s.from, s.to))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (LinkPhase::Probing, at(0), at(5)),
+                (LinkPhase::Live, at(5), at(30)),
+                (LinkPhase::Down, at(30), at(60)),
+            ]
+        );
+        assert_eq!(rows[0].markers, vec![band]);
+        assert_eq!(rows[1].segments.len(), 1);
+        assert_eq!(rows[1].segments[0].to, at(60));
+    }
+
+    #[test]
+    fn idle_link_is_failover_only_when_peers_carry() {
+        let link = |state: &str, bps| LinkSample {
+            id: 0,
+            interface: "wwan0".into(),
+            state: state.into(),
+            rtt_ms: Millis(40.0),
+            loss_rate: 0.0,
+            capacity_bps: Bps(5_000_000),
+            sent_bytes: 0,
+            observed_bps: Bps(bps),
+            signal_dbm: None,
+            rsrp: None,
+            rsrq: None,
+            sinr: None,
+            cqi: None,
+            link_kind: None,
+            btlbw_bps: None,
+            rtprop_ms: None,
+            transport: None,
+        };
+        assert_eq!(
+            LinkPhase::from_stats(&link("Live", 0), true),
+            LinkPhase::Failover
+        );
+        assert_eq!(
+            LinkPhase::from_stats(&link("Live", 0), false),
+            LinkPhase::Live
+        );
+        assert_eq!(
+            LinkPhase::from_stats(&link("Probing", 1000), true),
+            LinkPhase::Probing
+        );
+        assert_eq!(
+            LinkPhase::from_stats(&link("OS Down", 0), true),
+            LinkPhase::Down
+        );
+    }
 }
@@ -6,7 +6,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::{LinkStats, MediaInput, NetworkInterface, StreamState};
+use crate::ErrorCode;
+use crate::models::{IngestProtocol, MaintenanceWindow, MediaInput, NetworkInterface, StreamState};
+use crate::telemetry::{ElementStats, LinkSample};
 
 // ── Agent → Control Plane ───────────────────────────────────────────
 
@@ -26,6 +28,10 @@ pub struct AuthLoginPayload {
     pub agent_version: String,
     pub hostname: String,
     pub arch: String,
+    /// Binary encodings this agent can send high-rate telemetry in, most
+    /// preferred first (see [`crate::encoding`]). Empty = JSON only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub telemetry_encodings: Vec<String>,
 }
 
 /// Server → device: prove possession of the enrolled private key by
@@ -61,6 +67,7 @@ pub struct DeviceStatusPayload {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct StreamStatsPayload {
     pub stream_id: String,
     /// Sender that produced these stats (set by control plane).
@@ -71,11 +78,53 @@ pub struct StreamStatsPayload {
     /// Epoch milliseconds when these stats were captured.
     #[serde(default)]
     pub timestamp_ms: u64,
-    pub links: Vec<LinkStats>,
+    pub links: Vec<LinkSample>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sender_metrics: Option<crate::models::TransportSenderMetrics>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub receiver_metrics: Option<crate::models::TransportReceiverMetrics>,
+    /// Where the ABR controller has the encoder on its ladder; `None`
+    /// when the stream has no ladder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ladder: Option<LadderStatus>,
+    /// Session quality score; `None` until the receiver has reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<SessionQuality>,
+    /// Per-element resource use of the sender's media pipeline.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub elements: Vec<ElementStats>,
+    /// Audio levels and picture activity of the encoder input; `None`
+    /// when the pipeline has no level/videoanalyse elements. Boxed to keep
+    /// `AgentMessage` small.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<Box<crate::models::MediaActivity>>,
+}
+
+/// The encoder's current rung, as reported in `stream.stats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LadderStatus {
+    /// Index into [`EncoderConfig::ladder`]; 0 is the top rung.
+    pub rung: u32,
+    pub resolution: String,
+    /// The rung the controller stepped away from since the previous
+    /// sample. Set on exactly one sample per ladder change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stepped_from: Option<u32>,
+}
+
+/// Connection quality for the whole bonded session, computed sender-side
+/// from receiver reports: one number for producers to watch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionQuality {
+    /// E-model-style rating, 0–100.
+    pub score: f64,
+    /// MOS-like opinion score, 1–5; a clean link sits around 4.4.
+    pub mos: f64,
+    /// Chance that residual loss or late packets freeze the picture,
+    /// 0.0–1.0.
+    pub freeze_risk: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +156,10 @@ pub struct AuthLoginResponsePayload {
     pub success: bool,
     pub sender_id: Option<String>,
     pub error: Option<String>,
+    /// The binary telemetry encoding the control plane accepted from the
+    /// agent's offer. `None` = keep sending JSON text frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_encoding: Option<String>,
 }
 
 /// First message a dashboard (browser) WebSocket client must send on
@@ -124,6 +177,12 @@ pub struct DashboardAuthResponsePayload {
     pub error: Option<String>,
 }
 
+/// Topics to add to / drop from a dashboard connection's subscription set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSubscriptionPayload {
+    pub topics: Vec<crate::DashboardTopic>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamStartPayload {
     pub stream_id: String,
@@ -142,9 +201,16 @@ pub struct StreamStartPayload {
     /// output and pushes a parallel FLV stream to this URL.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub relay_url: Option<String>,
+    /// Per-stream ingest key minted by the control plane. The sender
+    /// presents it on every link; the receiver was handed the same key and
+    /// drops traffic from any source that hasn't. `None` for unmanaged
+    /// receivers, which still trust any source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SourceConfig {
     pub mode: String,
     pub device: Option<String>,
@@ -154,9 +220,35 @@ pub struct SourceConfig {
     /// Skip encoding — remux file source directly.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub passthrough: Option<bool>,
+    /// Further cameras the stream can cut to with `source.select`, built
+    /// alongside this one when the pipeline starts. This source itself is
+    /// the input named [`MAIN_INPUT`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<InputSource>,
+}
+
+/// Input id of a stream's primary source in `source.select`.
+pub const MAIN_INPUT: &str = "main";
+
+/// One switchable input of a multi-camera stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InputSource {
+    /// Operator-chosen name the input is selected by, e.g. "wide".
+    pub id: String,
+    /// Source mode: "test", "v4l2", "uri".
+    pub mode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// Test pattern (mode = "test").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EncoderConfig {
     pub bitrate_kbps: u32,
     pub tune: Option<String>,
@@ -170,6 +262,85 @@ pub struct EncoderConfig {
     /// Maximum bitrate for adaptation envelope (kbps).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_bitrate_kbps: Option<u32>,
+    /// Resolution ladder for the agent's ABR controller, top rung first.
+    /// The top rung must be the source resolution. Empty = the encoder
+    /// stays at the source resolution and only its bitrate adapts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ladder: Vec<LadderRung>,
+    /// Graphics burned into the picture ahead of the encoder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlay: Option<OverlayConfig>,
+}
+
+/// Branding / confidence graphics composited onto the stream. At least
+/// one of `logo`, `text` or `clock` must be set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OverlayConfig {
+    /// Absolute path of a PNG on the sender, e.g. a station logo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    #[serde(default)]
+    pub logo_position: OverlayPosition,
+    /// Fixed caption, e.g. the unit's callsign.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Burn in the sender's wall-clock time after the caption.
+    #[serde(default)]
+    pub clock: bool,
+    #[serde(default = "OverlayPosition::caption_default")]
+    pub text_position: OverlayPosition,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            logo: None,
+            logo_position: OverlayPosition::default(),
+            text: None,
+            clock: false,
+            text_position: OverlayPosition::caption_default(),
+        }
+    }
+}
+
+/// Picture corner an overlay element is pinned to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl OverlayPosition {
+    fn caption_default() -> Self {
+        Self::BottomLeft
+    }
+
+    /// Name as passed on the pipeline command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TopLeft => "top-left",
+            Self::TopRight => "top-right",
+            Self::BottomLeft => "bottom-left",
+            Self::BottomRight => "bottom-right",
+        }
+    }
+}
+
+/// One step of the resolution ladder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LadderRung {
+    /// "WIDTHxHEIGHT" the encoder is fed at on this rung.
+    pub resolution: String,
+    /// Lowest recommended encoder bitrate (kbps) this rung is kept at;
+    /// below it the controller steps down.
+    pub min_kbps: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,6 +350,7 @@ pub struct StreamStopPayload {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ConfigUpdatePayload {
     /// Request-correlation ID — echoed back in `config.update.response`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -195,6 +367,7 @@ pub struct ConfigUpdatePayload {
 
 /// Partial encoder config for hot-update (all fields optional).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EncoderConfigUpdate {
     pub bitrate_kbps: Option<u32>,
     pub tune: Option<String>,
@@ -211,6 +384,7 @@ pub struct ConfigUpdateResponsePayload {
 
 /// Command to switch the active video source on a running pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SourceSwitchPayload {
     /// Request-correlation ID — echoed back in `source.switch.response`.
     /// Optional for wire compatibility with older control planes.
@@ -230,6 +404,38 @@ pub struct SourceSwitchPayload {
     pub pattern: Option<String>,
 }
 
+/// How a `source.select` moves from one input to the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SourceTransition {
+    /// Straight cut on the target's first frame.
+    #[default]
+    Cut,
+    /// Fade to black, cut, fade back in.
+    Fade,
+    /// Hold black for the duration, then cut.
+    Black,
+}
+
+/// Command to switch a running multi-camera stream to another of its
+/// configured inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SourceSelectPayload {
+    /// Request-correlation ID — echoed back in `source.select.response`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Input id from the stream's `source.inputs`, or [`MAIN_INPUT`].
+    pub target: String,
+    #[serde(default)]
+    pub transition: SourceTransition,
+    /// Length of a fade or black transition; the agent's default when
+    /// absent. Ignored for a cut.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u32>,
+}
+
 /// Command to manage a network interface on the agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceCommandPayload {
@@ -282,6 +488,17 @@ pub struct SourceSwitchResponsePayload {
     pub error: Option<String>,
 }
 
+/// Response to source.select.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSelectResponsePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub success: bool,
+    /// The input that was requested.
+    pub target: String,
+    pub error: Option<String>,
+}
+
 /// Set receiver/config on the agent (proxied from control plane).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigSetPayload {
@@ -467,6 +684,19 @@ pub struct TlsRenewResponsePayload {
     pub error: Option<String>,
 }
 
+/// Install a certificate for the device's HTTPS endpoints (agent portal,
+/// receiver preview). Pushed on connect and whenever the control plane
+/// issues a new one; the device persists it and serves it at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInstallPayload {
+    /// Control-plane certificate ID, for logs and the ack.
+    pub certificate_id: String,
+    /// PEM chain, leaf first.
+    pub cert_pem: String,
+    /// PEM private key for the leaf.
+    pub key_pem: String,
+}
+
 /// Export agent configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigExportPayload {
@@ -559,6 +789,72 @@ pub struct JitterBufferResponsePayload {
     pub error: Option<String>,
 }
 
+/// The full maintenance schedule covering this sender (its own windows plus
+/// fleet-wide ones). Replaces whatever the agent held before, so deleting a
+/// window is just pushing the shorter list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceSchedulePayload {
+    pub windows: Vec<MaintenanceWindow>,
+}
+
+/// Local portal access policy. Pushed on connect and whenever an admin
+/// changes it; the agent persists it so the gate holds while offline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortalAuthPayload {
+    /// Argon2 hash of the portal PIN/password; `None` leaves the portal
+    /// open to anyone on the local network.
+    pub pin_hash: Option<String>,
+}
+
+// ── Either direction (agent leg) ────────────────────────────────────
+
+/// Negative acknowledgement: sent back instead of silently dropping a
+/// message the receiver could not handle, so a waiting RPC fails at once
+/// rather than timing out. Agents that predate it never send one; their
+/// RPCs still fall back to the caller's timeout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NakPayload {
+    /// `id` of the rejected envelope.
+    pub ref_id: String,
+    /// `type` of the rejected envelope.
+    pub msg_type: String,
+    /// `request_id` from the rejected payload, if it carried one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// [`ErrorCode::UnsupportedCommand`] for a `type` this peer doesn't
+    /// know (usually a newer peer), [`ErrorCode::InvalidInput`] for a known
+    /// `type` whose payload didn't match its schema, [`ErrorCode::Timeout`]
+    /// for a message whose `deadline` had passed.
+    pub code: ErrorCode,
+    /// Parse error or other detail, for logs.
+    pub detail: String,
+    /// [`crate::PROTOCOL_VERSION`] of the rejecting peer.
+    pub proto_version: u32,
+}
+
+/// Outcome of one control-plane command, sent once the agent has acted on
+/// it. Every command gets exactly one — including fire-and-forget ones
+/// like `stream.start` that have no RPC response — so the control plane
+/// can show per-command success. RPC commands still get their typed
+/// `*.response` first; the ack follows it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandAckPayload {
+    /// `id` of the command envelope.
+    pub ref_id: String,
+    /// `type` of the command envelope.
+    pub msg_type: String,
+    /// `request_id` from the command payload, if it carried one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub success: bool,
+    /// Why the command failed; `None` on success.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<ErrorCode>,
+    /// Failure detail for humans and logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 // ── Receiver → Control Plane ────────────────────────────────────────
 
 /// Auth payload sent by a receiver daemon when connecting.
@@ -581,6 +877,13 @@ pub struct ReceiverAuthLoginPayload {
     pub link_ports: Vec<u16>,
     /// Maximum concurrent streams this receiver can handle.
     pub max_streams: u32,
+    /// Public base URL of the receiver's live preview server, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_base_url: Option<String>,
+    /// Plain contribution protocols the receiver can take in besides
+    /// bonded links (see [`ReceiverStreamStartPayload::ingest`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingest: Vec<IngestProtocol>,
 }
 
 /// Auth response sent to a receiver daemon.
@@ -607,6 +910,19 @@ pub struct ReceiverStreamStartPayload {
     /// Optional bonding config (scheduler params, etc).
     #[serde(default)]
     pub bonding_config: serde_json::Value,
+    /// Ingest key the stream's links must require (see
+    /// [`StreamStartPayload::ingest_key`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_key: Option<String>,
+    /// Key gating the receiver's live preview of this stream. Only set
+    /// when the receiver has a preview server and the relay is HLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_key: Option<String>,
+    /// Take the stream in over plain SRT/RTMP from an ingest sender
+    /// instead of bonded links. `link_count` is ignored and `ingest_key`
+    /// becomes the SRT passphrase or the RTMP stream key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<IngestProtocol>,
 }
 
 /// Receiver's answer to `receiver.stream.start`: the allocated ports, or
@@ -621,6 +937,9 @@ pub struct ReceiverStreamStartedPayload {
     pub bind_ports: Vec<u16>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Ingest streams: the URL the contributor pushes to, key included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_url: Option<String>,
 }
 
 /// Control plane tells the receiver to stop a stream.
@@ -644,15 +963,23 @@ pub struct ReceiverStreamEndedPayload {
 
 /// Receiver reports per-second stats for a stream.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ReceiverStreamStatsPayload {
     pub stream_id: String,
     pub receiver_id: String,
     pub uptime_s: u64,
     pub timestamp_ms: u64,
-    pub links: Vec<crate::models::LinkStats>,
+    pub links: Vec<LinkSample>,
     /// HLS egress health (None for non-HLS relays or older pipelines).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub egress: Option<crate::models::EgressStats>,
+    /// A/V sync of the delivered TS (None until the receiver has seen its
+    /// clocks, or for non-TS payloads).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub av_sync: Option<crate::models::AvSyncStats>,
+    /// Times the receiver respawned this stream's pipeline after a crash.
+    #[serde(default)]
+    pub pipeline_restarts: u32,
 }
 
 /// Receiver heartbeat with capacity info.
@@ -0,0 +1,399 @@
+//! Telemetry sample schema.
+//!
+//! One definition of what a link sample and a stored telemetry point look
+//! like, shared by the agents (which build them from the pipeline's
+//! bonding stats), the control plane (which stores and serves them) and
+//! the dashboard (which renders them). Quantities carry their unit in the
+//! type: [`Bps`], [`Millis`] and [`Dbm`] serialize as the bare number, so
+//! the wire format is unchanged.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A rate in bits per second.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(transparent)]
+pub struct Bps(pub u64);
+
+impl Add for Bps {
+    type Output = Bps;
+    fn add(self, rhs: Bps) -> Bps {
+        Bps(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sum for Bps {
+    fn sum<I: Iterator<Item = Bps>>(iter: I) -> Bps {
+        iter.fold(Bps(0), Add::add)
+    }
+}
+
+/// A duration in milliseconds (RTTs, RTprop).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(transparent)]
+pub struct Millis(pub f64);
+
+impl Millis {
+    pub fn from_micros(us: f64) -> Self {
+        Millis(us / 1_000.0)
+    }
+}
+
+/// A received signal power in dBm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(transparent)]
+pub struct Dbm(pub i32);
+
+// Display forwards to the bare number (precision included), so
+// `format!("{:.1} ms", link.rtt_ms)` reads the same as before the unit
+// types existed.
+macro_rules! display_inner {
+    ($($t:ty),*) => {$(
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    )*};
+}
+display_inner!(Bps, Millis, Dbm);
+
+// ── Link Sample ─────────────────────────────────────────────────────
+
+/// One bonded link at one instant, sent in `stream.stats` (and, from the
+/// receiver, `receiver.stream.stats`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkSample {
+    pub id: u32,
+    pub interface: String,
+    /// "Live", "Probing", "Down" or "OS Down".
+    pub state: String,
+    pub rtt_ms: Millis,
+    /// Fraction of packets lost, 0.0–1.0.
+    pub loss_rate: f64,
+    pub capacity_bps: Bps,
+    /// Bytes carried so far: sent on the sender, received on the receiver.
+    pub sent_bytes: u64,
+    /// Current observed throughput.
+    #[serde(default)]
+    pub observed_bps: Bps,
+    pub signal_dbm: Option<Dbm>,
+    /// LTE reference signal received power (dBm).
+    pub rsrp: Option<f32>,
+    /// LTE reference signal received quality (dB).
+    pub rsrq: Option<f32>,
+    /// Signal to interference plus noise ratio (dB).
+    pub sinr: Option<f32>,
+    pub cqi: Option<u8>,
+    /// Link technology kind (e.g. "ethernet", "cellular", "wifi").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_kind: Option<String>,
+    /// BBRv3 estimated bottleneck bandwidth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub btlbw_bps: Option<Bps>,
+    /// BBRv3 estimated minimum RTT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtprop_ms: Option<Millis>,
+    /// Congestion-control and recovery state; sender links only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<LinkTransport>,
+}
+
+/// What the sender's transport is doing on one link: the congestion
+/// controller's window and pacing, and how much it resends.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkTransport {
+    /// Inflight cap the controller enforces — its congestion window.
+    pub cwnd_bytes: u64,
+    pub pacing_bps: Bps,
+    /// Packets sent, retransmissions and FEC repairs included.
+    pub packets_sent: u64,
+    /// NACK-triggered retransmissions.
+    pub retransmissions: u64,
+    pub fec_repairs_sent: u64,
+}
+
+impl LinkTransport {
+    /// Fraction of sent packets that were FEC repairs, 0.0–1.0.
+    pub fn fec_share(&self) -> f64 {
+        if self.packets_sent == 0 {
+            return 0.0;
+        }
+        self.fec_repairs_sent as f64 / self.packets_sent as f64
+    }
+}
+
+impl LinkSample {
+    /// Read one entry of the `links` array in the pipeline's bonding stats
+    /// JSON. Both ends of the bond emit it, with different key spellings
+    /// across pipeline versions (`sent_bytes`/`tx_bytes` on the sender,
+    /// `received_bytes`/`rx_bytes` on the receiver); missing keys read as
+    /// zero or unknown. The receiver reports no liveness, so its links read
+    /// as Live.
+    pub fn from_bonding_report(link: &serde_json::Value) -> Self {
+        let u64_of = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| link.get(*k).and_then(|v| v.as_u64()))
+        };
+        let f64_of = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| link.get(*k).and_then(|v| v.as_f64()))
+        };
+
+        let alive = link.get("alive").and_then(|v| v.as_bool()).unwrap_or(true);
+        let phase = link
+            .get("phase")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let os_up = link.get("os_up").and_then(|v| v.as_i64()).unwrap_or(-1);
+        // Derive human-readable state from alive/phase/os_up
+        let state = if !alive {
+            if os_up == 0 { "OS Down" } else { "Down" }
+        } else {
+            // strata-bonding's LinkPhase: init/probe/warm precede live.
+            match phase {
+                "init" | "probe" | "warm" | "probing" => "Probing",
+                _ => "Live",
+            }
+        };
+
+        LinkSample {
+            id: u64_of(&["id"]).unwrap_or(0) as u32,
+            interface: ["interface", "iface"]
+                .iter()
+                .find_map(|k| link.get(*k).and_then(|v| v.as_str()))
+                .unwrap_or("unknown")
+                .to_string(),
+            state: state.to_string(),
+            rtt_ms: Millis::from_micros(f64_of(&["rtt_us"]).unwrap_or(0.0)),
+            loss_rate: f64_of(&["loss_rate", "loss_percent"]).unwrap_or(0.0),
+            capacity_bps: Bps(u64_of(&["capacity_bps", "bandwidth_bps"]).unwrap_or(0)),
+            sent_bytes: u64_of(&["sent_bytes", "tx_bytes", "received_bytes", "rx_bytes"])
+                .unwrap_or(0),
+            observed_bps: Bps(u64_of(&["observed_bps"]).unwrap_or(0)),
+            signal_dbm: None,
+            rsrp: None,
+            rsrq: None,
+            sinr: None,
+            cqi: None,
+            link_kind: link
+                .get("link_kind")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+            btlbw_bps: u64_of(&["btlbw_bps"]).map(Bps),
+            rtprop_ms: f64_of(&["rtprop_ms"]).map(Millis),
+            transport: u64_of(&["packets_sent"]).map(|packets_sent| LinkTransport {
+                cwnd_bytes: u64_of(&["cwnd_bytes"]).unwrap_or(0),
+                pacing_bps: Bps(u64_of(&["pacing_bps"]).unwrap_or(0)),
+                packets_sent,
+                retransmissions: u64_of(&["retransmissions"]).unwrap_or(0),
+                fec_repairs_sent: u64_of(&["fec_repairs_sent"]).unwrap_or(0),
+            }),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.state == "Live"
+    }
+}
+
+// ── Pipeline Elements ───────────────────────────────────────────────
+
+/// Resource use of one element of the sender's media pipeline, sent in
+/// `stream.stats` so an overloaded SoC can be told apart from a bad
+/// network: a full queue or a pegged encode thread is local, not the
+/// links.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ElementStats {
+    pub name: String,
+    /// CPU time of the streaming thread the element starts, in percent
+    /// of one core (can exceed 100 with encoder worker threads).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f32>,
+    /// Buffers currently held, for queues.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_buffers: Option<u32>,
+    /// Media time currently held, for queues.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_ms: Option<u32>,
+    /// The queue's time limit; 0 means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_max_ms: Option<u32>,
+    /// Buffers the element has dropped to keep up (from its QoS reports).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropped_buffers: Option<u64>,
+}
+
+impl ElementStats {
+    /// Queue fill against its time limit, 0.0–1.0; `None` if the element
+    /// isn't a time-limited queue.
+    pub fn queue_fill(&self) -> Option<f64> {
+        let max = self.queue_max_ms.filter(|&m| m > 0)?;
+        Some((f64::from(self.queue_ms?) / f64::from(max)).min(1.0))
+    }
+}
+
+// ── Telemetry Sample ────────────────────────────────────────────────
+
+/// A sender's stream condensed to one point: what the control plane stores
+/// every few seconds, and what `GET /api/senders/{id}/metrics` returns
+/// (averaged per bucket).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TelemetrySample {
+    pub ts: DateTime<Utc>,
+    /// Encoder output rate (kbps).
+    pub bitrate_kbps: f64,
+    /// On-the-wire throughput summed over Live links.
+    pub throughput_bps: Bps,
+    /// Mean RTT over Live links; `None` when no link is Live.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtt_ms: Option<Millis>,
+    /// Mean loss over Live links, in percent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss_pct: Option<f64>,
+    /// Live links (fractional once averaged over a bucket).
+    pub link_count: f64,
+}
+
+impl TelemetrySample {
+    /// Condense one `stream.stats` report.
+    pub fn from_links(ts: DateTime<Utc>, encoder_bitrate_kbps: u32, links: &[LinkSample]) -> Self {
+        let live: Vec<&LinkSample> = links.iter().filter(|l| l.is_live()).collect();
+        let mean = |f: fn(&LinkSample) -> f64| {
+            (!live.is_empty()).then(|| live.iter().map(|l| f(l)).sum::<f64>() / live.len() as f64)
+        };
+        TelemetrySample {
+            ts,
+            bitrate_kbps: encoder_bitrate_kbps as f64,
+            throughput_bps: live.iter().map(|l| l.observed_bps).sum(),
+            rtt_ms: mean(|l| l.rtt_ms.0).map(Millis),
+            loss_pct: mean(|l| l.loss_rate * 100.0),
+            link_count: live.len() as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_sample_serde() {
+        let sample = LinkSample {
+            id: 1,
+            interface: "wwan0".into(),
+            state: "connected".into(),
+            rtt_ms: Millis(23.5),
+            loss_rate: 0.01,
+            capacity_bps: Bps(15_000_000),
+            sent_bytes: 1_048_576,
+            observed_bps: Bps(8_000_000),
+            signal_dbm: Some(Dbm(-72)),
+            link_kind: Some("cellular".into()),
+            rsrp: None,
+            rsrq: None,
+            sinr: None,
+            cqi: None,
+            btlbw_bps: Some(Bps(12_000_000)),
+            rtprop_ms: Some(Millis(20.0)),
+            transport: None,
+        };
+        let json = serde_json::to_value(&sample).unwrap();
+        // Units are in the types, not on the wire.
+        assert_eq!(json["capacity_bps"], 15_000_000);
+        assert_eq!(json["signal_dbm"], -72);
+        assert_eq!(json["rtt_ms"], 23.5);
+
+        let parsed: LinkSample = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.id, 1);
+        assert_eq!(parsed.rtt_ms, Millis(23.5));
+        assert_eq!(parsed.capacity_bps, Bps(15_000_000));
+        assert_eq!(parsed.observed_bps, Bps(8_000_000));
+        assert_eq!(parsed.link_kind.as_deref(), Some("cellular"));
+        assert_eq!(parsed.btlbw_bps, Some(Bps(12_000_000)));
+        assert_eq!(parsed.rtprop_ms, Some(Millis(20.0)));
+        assert_eq!(format!("{:.1} ms", parsed.rtt_ms), "23.5 ms");
+    }
+
+    #[test]
+    fn link_sample_backward_compat() {
+        // Old JSON without observed_bps or link_kind should still parse
+        let json = r#"{"id":0,"interface":"eth0","state":"Live","rtt_ms":10.0,"loss_rate":0.0,"capacity_bps":10000000,"sent_bytes":0,"signal_dbm":null}"#;
+        let parsed: LinkSample = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.observed_bps, Bps(0));
+        assert!(parsed.link_kind.is_none());
+    }
+
+    #[test]
+    fn bonding_reports_from_both_ends() {
+        let sender = serde_json::json!({
+            "id": 2, "iface": "wwan0", "rtt_us": 42_000.0, "loss_rate": 0.02,
+            "bandwidth_bps": 6_000_000u64, "tx_bytes": 1_000u64, "observed_bps": 4_000_000u64,
+            "alive": true, "phase": "warm", "link_kind": "cellular", "btlbw_bps": 5_500_000u64,
+            "packets_sent": 1_000u64, "retransmissions": 12u64, "fec_repairs_sent": 50u64,
+            "pacing_bps": 6_500_000u64, "cwnd_bytes": 64_000u64,
+        });
+        let link = LinkSample::from_bonding_report(&sender);
+        assert_eq!(link.interface, "wwan0");
+        assert_eq!(link.state, "Probing");
+        assert_eq!(link.rtt_ms, Millis(42.0));
+        assert_eq!(link.capacity_bps, Bps(6_000_000));
+        assert_eq!(link.sent_bytes, 1_000);
+        assert_eq!(link.btlbw_bps, Some(Bps(5_500_000)));
+        let transport = link.transport.unwrap();
+        assert_eq!(transport.cwnd_bytes, 64_000);
+        assert_eq!(transport.pacing_bps, Bps(6_500_000));
+        assert_eq!(transport.retransmissions, 12);
+        assert_eq!(transport.fec_share(), 0.05);
+
+        let down = serde_json::json!({"id": 0, "alive": false, "os_up": 0});
+        assert_eq!(LinkSample::from_bonding_report(&down).state, "OS Down");
+
+        let receiver = serde_json::json!({
+            "id": 0, "loss_rate": 0.25, "received_bytes": 1_000_000u64, "observed_bps": 800_000u64,
+        });
+        let link = LinkSample::from_bonding_report(&receiver);
+        assert_eq!(link.state, "Live");
+        assert_eq!(link.interface, "unknown");
+        assert_eq!(link.sent_bytes, 1_000_000);
+        assert_eq!(link.observed_bps, Bps(800_000));
+        assert!(link.transport.is_none());
+    }
+
+    #[test]
+    fn telemetry_sample_averages_live_links_only() {
+        let report = serde_json::json!([
+            {"id": 0, "rtt_us": 20_000.0, "loss_rate": 0.01, "observed_bps": 3_000_000u64},
+            {"id": 1, "rtt_us": 40_000.0, "loss_rate": 0.03, "observed_bps": 1_000_000u64},
+            {"id": 2, "rtt_us": 900_000.0, "alive": false, "observed_bps": 0u64},
+        ]);
+        let links: Vec<LinkSample> = report
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(LinkSample::from_bonding_report)
+            .collect();
+        let sample = TelemetrySample::from_links(Utc::now(), 3500, &links);
+        assert_eq!(sample.throughput_bps, Bps(4_000_000));
+        assert_eq!(sample.rtt_ms, Some(Millis(30.0)));
+        assert!((sample.loss_pct.unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(sample.link_count, 2.0);
+
+        let idle = TelemetrySample::from_links(Utc::now(), 0, &links[2..]);
+        assert!(idle.rtt_ms.is_none() && idle.loss_pct.is_none());
+    }
+}
@@ -4,7 +4,8 @@
 //! - Connection with exponential backoff reconnect
 //! - Authentication with capacity registration
 //! - Heartbeat (receiver.status every N seconds)
-//! - Incoming commands (receiver.stream.start, receiver.stream.stop)
+//! - Incoming commands (receiver.stream.start, receiver.stream.stop,
+//!   tls.install)
 //! - Outgoing messages (receiver.stream.stats, receiver.stream.ended)
 
 use std::sync::Arc;
@@ -14,9 +15,10 @@ use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
 
+use strata_protocol::models::IngestProtocol;
 use strata_protocol::{
     AuthChallengeResponsePayload, Envelope, ReceiverAuthLoginPayload, ReceiverControlMessage,
-    ReceiverMessage, ReceiverStatusPayload, ReceiverStreamEndedPayload,
+    ReceiverMessage, ReceiverStatusPayload, ReceiverStreamEndedPayload, ReceiverStreamStartPayload,
     ReceiverStreamStartedPayload, StreamEndReason,
 };
 
@@ -143,6 +145,8 @@ async fn connect_and_run(
         bind_host: bind_host.to_string(),
         link_ports: link_ports.to_vec(),
         max_streams,
+        preview_base_url: state.preview_base_url.clone(),
+        ingest: state.ingest.protocols(),
     };
 
     let envelope = Envelope::from_message(&ReceiverMessage::AuthLogin(auth_payload))?;
@@ -306,6 +310,7 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                 stream_id = %payload.stream_id,
                 link_count = payload.link_count,
                 relay_url = ?payload.relay_url,
+                ingest = ?payload.ingest,
                 "received receiver.stream.start"
             );
 
@@ -315,6 +320,7 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                 success: false,
                 bind_ports: vec![],
                 error: Some(error),
+                ingest_url: None,
             };
 
             // Enforce capacity, then allocate this stream's ports from the
@@ -329,6 +335,25 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                 return;
             }
 
+            if let Some(protocol) = payload.ingest {
+                let ack = match start_ingest(state, &payload, protocol).await {
+                    Ok((ports, ingest_url)) => ReceiverStreamStartedPayload {
+                        request_id: payload.request_id.clone(),
+                        stream_id: payload.stream_id.clone(),
+                        success: true,
+                        bind_ports: ports,
+                        error: None,
+                        ingest_url: Some(ingest_url),
+                    },
+                    Err(e) => {
+                        tracing::error!(error = %e, "failed to start ingest pipeline");
+                        fail(e)
+                    }
+                };
+                send_message(state, &ReceiverMessage::StreamStarted(ack)).await;
+                return;
+            }
+
             let requested = payload.link_count.max(1) as usize;
             let ports = {
                 let mut pool = state.port_pool.lock().await;
@@ -340,6 +365,16 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                 return;
             };
 
+            // The ingest key rides into the pipeline's bonding config so
+            // stratasrc can gate its link sockets on it.
+            let mut bonding_config = payload.bonding_config.clone();
+            if let Some(key) = &payload.ingest_key {
+                if !bonding_config.is_object() {
+                    bonding_config = serde_json::json!({});
+                }
+                bonding_config["ingest_key"] = serde_json::Value::String(key.clone());
+            }
+
             let result = {
                 let mut pipelines = state.pipelines.lock().await;
                 pipelines.start(
@@ -347,7 +382,8 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                     &state.bind_host,
                     &ports,
                     payload.relay_url.as_deref(),
-                    &payload.bonding_config,
+                    &bonding_config,
+                    payload.preview_key.as_deref(),
                 )
             };
 
@@ -358,6 +394,7 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                     success: true,
                     bind_ports: ports,
                     error: None,
+                    ingest_url: None,
                 },
                 Err(e) => {
                     tracing::error!(error = %e, "failed to start receiver pipeline");
@@ -389,5 +426,63 @@ async fn handle_control_message(state: &ReceiverState, raw: &str) {
                 send_message(state, &ReceiverMessage::StreamEnded(ended)).await;
             }
         }
+        ReceiverControlMessage::TlsInstall(payload) => {
+            match state.tls.install(&payload.cert_pem, &payload.key_pem) {
+                Ok(info) => tracing::info!(
+                    certificate_id = %payload.certificate_id,
+                    subject = %info.subject,
+                    not_after = %info.not_after,
+                    "preview certificate installed"
+                ),
+                Err(e) => tracing::warn!(
+                    certificate_id = %payload.certificate_id,
+                    error = %e,
+                    "preview certificate not installed"
+                ),
+            }
+        }
+    }
+}
+
+/// Start the pipeline of an ingest stream (see `ingest`). Returns the
+/// pool ports it holds and the URL the contributor pushes to.
+async fn start_ingest(
+    state: &ReceiverState,
+    payload: &ReceiverStreamStartPayload,
+    protocol: IngestProtocol,
+) -> Result<(Vec<u16>, String), String> {
+    let Some(key) = payload.ingest_key.as_deref() else {
+        return Err("ingest stream without an ingest key".into());
+    };
+    let (ports, endpoint) = match protocol {
+        IngestProtocol::Srt if state.ingest.srt => {
+            let Some(ports) = state.port_pool.lock().await.allocate(1) else {
+                return Err("no free port for SRT ingest".into());
+            };
+            let endpoint = state.ingest.srt(&state.bind_host, ports[0], key);
+            (ports, endpoint)
+        }
+        IngestProtocol::Rtmp if state.ingest.rtmp_server.is_some() => {
+            match state.ingest.rtmp(&state.bind_host, key) {
+                Some(endpoint) => (Vec::new(), endpoint),
+                None => return Err("invalid --rtmp-server".into()),
+            }
+        }
+        _ => return Err(format!("{} ingest not enabled", protocol.as_str())),
+    };
+
+    let result = state.pipelines.lock().await.start_ingest(
+        &payload.stream_id,
+        &endpoint.source,
+        &ports,
+        payload.relay_url.as_deref(),
+        payload.preview_key.as_deref(),
+    );
+    match result {
+        Ok(()) => Ok((ports, endpoint.public_url)),
+        Err(e) => {
+            state.port_pool.lock().await.release(&ports);
+            Err(format!("pipeline start failed: {e}"))
+        }
     }
 }
@@ -0,0 +1,106 @@
+//! Ingest gateway — plain SRT/RTMP contributions from senders that don't
+//! run the Strata agent (a phone running Larix, OBS).
+//!
+//! - SRT: the pipeline's srtsrc listens on one port from the link pool,
+//!   with the stream's ingest key as the passphrase.
+//! - RTMP: the contributor publishes to a local RTMP server
+//!   (`--rtmp-server`, e.g. nginx-rtmp) under the ingest key as stream
+//!   key, and the pipeline plays it back from there. The server must let
+//!   players wait for a publisher (nginx-rtmp `idle_streams on`, its
+//!   default), since the pipeline starts before the phone does.
+//!
+//! Either way the pipeline remuxes to MPEG-TS and the stream is relayed
+//! and previewed like a bonded one.
+
+use strata_protocol::models::IngestProtocol;
+
+/// Which contributions this receiver takes in.
+#[derive(Debug, Clone, Default)]
+pub struct IngestConfig {
+    pub srt: bool,
+    /// Local RTMP server to play contributions back from, e.g.
+    /// `rtmp://127.0.0.1:1935/live`.
+    pub rtmp_server: Option<String>,
+}
+
+/// One stream's ingest: where the pipeline takes the contribution from,
+/// and the URL handed to the contributor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestEndpoint {
+    pub source: String,
+    pub public_url: String,
+}
+
+impl IngestConfig {
+    /// Protocols reported to the control plane at login.
+    pub fn protocols(&self) -> Vec<IngestProtocol> {
+        let mut protocols = Vec::new();
+        if self.srt {
+            protocols.push(IngestProtocol::Srt);
+        }
+        if self.rtmp_server.is_some() {
+            protocols.push(IngestProtocol::Rtmp);
+        }
+        protocols
+    }
+
+    /// Endpoint for an SRT contribution on `port`.
+    pub fn srt(&self, bind_host: &str, port: u16, key: &str) -> IngestEndpoint {
+        IngestEndpoint {
+            source: format!("srt://:{port}?mode=listener&passphrase={key}"),
+            public_url: format!("srt://{bind_host}:{port}?passphrase={key}"),
+        }
+    }
+
+    /// Endpoint for an RTMP contribution, or `None` without an RTMP
+    /// server. The public URL is the server's, reached at `bind_host`.
+    pub fn rtmp(&self, bind_host: &str, key: &str) -> Option<IngestEndpoint> {
+        let server = self.rtmp_server.as_deref()?.trim_end_matches('/');
+        let rest = server.strip_prefix("rtmp://")?;
+        let (authority, app) = rest.split_once('/')?;
+        let port = authority
+            .rsplit_once(':')
+            .map(|(_, port)| format!(":{port}"))
+            .unwrap_or_default();
+        Some(IngestEndpoint {
+            source: format!("{server}/{key}"),
+            public_url: format!("rtmp://{bind_host}{port}/{app}/{key}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_use_the_public_host() {
+        let config = IngestConfig {
+            srt: true,
+            rtmp_server: Some("rtmp://127.0.0.1:1935/live/".into()),
+        };
+        assert_eq!(
+            config.protocols(),
+            [IngestProtocol::Srt, IngestProtocol::Rtmp]
+        );
+
+        let srt = config.srt("rx1.example.com", 5002, "isk_k");
+        assert_eq!(srt.source, "srt://:5002?mode=listener&passphrase=isk_k");
+        assert_eq!(
+            srt.public_url,
+            "srt://rx1.example.com:5002?passphrase=isk_k"
+        );
+
+        let rtmp = config.rtmp("rx1.example.com", "isk_k").unwrap();
+        assert_eq!(rtmp.source, "rtmp://127.0.0.1:1935/live/isk_k");
+        assert_eq!(rtmp.public_url, "rtmp://rx1.example.com:1935/live/isk_k");
+
+        // No server, or one without an application path: no RTMP.
+        assert!(IngestConfig::default().rtmp("h", "k").is_none());
+        let bare = IngestConfig {
+            srt: false,
+            rtmp_server: Some("rtmp://127.0.0.1".into()),
+        };
+        assert!(bare.rtmp("h", "k").is_none());
+    }
+}
@@ -3,13 +3,18 @@
 //! Cloud-side daemon that:
 //! - Connects to the control plane over WebSocket
 //! - Registers capacity (max streams, bind ports, region)
-//! - Starts/stops GStreamer receiver pipelines on command
+//! - Starts/stops GStreamer receiver pipelines on command, one per stream,
+//!   respawning any that crash
 //! - Relays real-time receiver stats to the control plane
+//! - Optionally takes plain SRT/RTMP contributions from senders without
+//!   the Strata agent (see `ingest`)
 
 mod control;
+mod ingest;
 mod metrics;
 mod pipeline;
 mod pipeline_monitor;
+mod preview;
 mod telemetry;
 
 use std::net::SocketAddr;
@@ -66,6 +71,34 @@ struct Cli {
     /// Prometheus metrics server address (e.g. 0.0.0.0:9090). Disabled if empty.
     #[arg(long, default_value = "")]
     metrics_addr: String,
+
+    /// Live preview server address (e.g. 0.0.0.0:8088). Disabled if empty.
+    #[arg(long, env = "STRATA_PREVIEW_ADDR", default_value = "")]
+    preview_addr: String,
+
+    /// Public base URL the dashboard reaches the preview server at
+    /// (e.g. https://rx1.example.com:8088). Reported to the control plane
+    /// at login; previews stay off unless both this and --preview-addr are set.
+    #[arg(long, env = "STRATA_PREVIEW_BASE_URL")]
+    preview_base_url: Option<String>,
+
+    /// Serve previews over HTTPS with the certificate pushed by the
+    /// control plane, instead of plain HTTP behind a terminating proxy.
+    #[arg(long, env = "STRATA_PREVIEW_TLS")]
+    preview_tls: bool,
+
+    /// Where the pushed certificate is kept.
+    #[arg(long, env = "STRATA_TLS_DIR", default_value = "/var/lib/strata/tls")]
+    tls_dir: String,
+
+    /// Accept plain SRT contributions (e.g. Larix) on link ports.
+    #[arg(long, env = "STRATA_INGEST_SRT")]
+    ingest_srt: bool,
+
+    /// Local RTMP server that plain RTMP contributions are published to
+    /// (e.g. rtmp://127.0.0.1:1935/live). Enables RTMP ingest.
+    #[arg(long, env = "STRATA_RTMP_SERVER")]
+    rtmp_server: Option<String>,
 }
 
 /// Shared receiver daemon state accessible from all tasks.
@@ -86,9 +119,16 @@ pub struct ReceiverState {
     pub max_streams: u32,
     pub region: Option<String>,
     pub bind_host: String,
+    /// Public URL of the preview server, if it is running.
+    pub preview_base_url: Option<String>,
+    /// Preview HTTPS certificate, pushed by the control plane with
+    /// `tls.install`.
+    pub tls: strata_common::tls::server::ServedCert,
+    /// Plain SRT/RTMP contributions this receiver takes in.
+    pub ingest: ingest::IngestConfig,
     /// Latest link stats per stream (stream_id → stats).
     pub latest_stats: tokio::sync::RwLock<
-        std::collections::HashMap<String, Vec<strata_protocol::models::LinkStats>>,
+        std::collections::HashMap<String, Vec<strata_protocol::telemetry::LinkSample>>,
     >,
 }
 
@@ -165,6 +205,14 @@ async fn main() -> anyhow::Result<()> {
         anyhow::bail!("--link-ports must contain at least one valid port");
     }
 
+    let ingest = ingest::IngestConfig {
+        srt: cli.ingest_srt,
+        rtmp_server: cli.rtmp_server.clone(),
+    };
+    if ingest.rtmp_server.is_some() && ingest.rtmp(&cli.bind_host, "").is_none() {
+        anyhow::bail!("--rtmp-server must look like rtmp://host[:port]/app");
+    }
+
     tracing::info!(
         hostname = %hostname,
         control_url = %cli.control_url,
@@ -172,6 +220,7 @@ async fn main() -> anyhow::Result<()> {
         link_ports = ?link_ports,
         max_streams = cli.max_streams,
         region = ?cli.region,
+        ingest = ?ingest.protocols(),
         "strata-receiver starting"
     );
 
@@ -205,6 +254,12 @@ async fn main() -> anyhow::Result<()> {
         max_streams: cli.max_streams,
         region: cli.region.clone(),
         bind_host: cli.bind_host.clone(),
+        preview_base_url: cli
+            .preview_base_url
+            .clone()
+            .filter(|_| !cli.preview_addr.is_empty()),
+        tls: strata_common::tls::server::ServedCert::load(&cli.tls_dir),
+        ingest,
         latest_stats: tokio::sync::RwLock::new(std::collections::HashMap::new()),
     });
 
@@ -256,6 +311,18 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // ── Task 5: Live preview server (if --preview-addr is set) ──
+    if !cli.preview_addr.is_empty() {
+        let preview_state = state.clone();
+        let preview_addr: SocketAddr = cli.preview_addr.parse()?;
+        let preview_tls = cli.preview_tls;
+        tokio::spawn(async move {
+            if let Err(e) = preview::run(preview_state, preview_addr, preview_tls).await {
+                tracing::error!(error = %e, "preview server failed");
+            }
+        });
+    }
+
     // ── Shutdown handling ───────────────────────────────────────
     tokio::select! {
         _ = tokio::signal::ctrl_c() => {
@@ -2,17 +2,31 @@
 //!
 //! Unlike the sender (which runs one pipeline at a time), the receiver
 //! can run multiple pipelines simultaneously, one per assigned stream.
+//!
+//! Pipelines are supervised: one that crashes is respawned on the same
+//! ports (so the sender's links reconnect without renegotiation) up to
+//! [`MAX_RESTARTS`] times per [`RESTART_WINDOW`].  Past that, or on a clean
+//! exit, the stream ends and the monitor reports it.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::{Child, ExitStatus};
 use std::time::{Duration, Instant};
 
+use subtle::ConstantTimeEq;
+
 /// UDP base address for stats relay. Each pipeline gets a unique port
 /// starting from this base: 9200, 9201, 9202, ...
 pub const STATS_LISTEN_BASE: u16 = 9200;
 
 const PIPELINE_STOP_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Crash restarts allowed per stream within [`RESTART_WINDOW`].
+pub const MAX_RESTARTS: usize = 3;
+
+/// Window over which [`MAX_RESTARTS`] is counted.
+pub const RESTART_WINDOW: Duration = Duration::from_secs(60);
+
 #[cfg(test)]
 static TEST_PIPELINE_BIN: std::sync::Mutex<Option<std::ffi::OsString>> =
     std::sync::Mutex::new(None);
@@ -32,6 +46,32 @@ struct PipelineEntry {
     stats_port: u16,
     started_at: Instant,
     total_bytes: u64,
+    /// Live preview: the key the control plane minted for this stream and
+    /// the directory the pipeline writes its HLS relay output to.
+    preview: Option<(String, PathBuf)>,
+    /// What the pipeline was started with, to respawn it after a crash.
+    spec: PipelineSpec,
+    /// Crash restarts within the last [`RESTART_WINDOW`].
+    recent_restarts: Vec<Instant>,
+    /// Crash restarts since the stream started.
+    restarts: u32,
+}
+
+struct PipelineSpec {
+    bind_host: String,
+    relay_url: Option<String>,
+    bonding_config: serde_json::Value,
+    /// `--ingest` source of an ingest stream (see `ingest`), in place of
+    /// bonded links.
+    ingest: Option<String>,
+}
+
+/// A running stream, as reported in telemetry.
+pub struct ActiveStream {
+    pub stream_id: String,
+    pub stats_port: u16,
+    pub uptime_s: u64,
+    pub restarts: u32,
 }
 
 pub struct PipelineStopStats {
@@ -46,6 +86,24 @@ pub struct ChildExitInfo {
     pub duration_s: u64,
     pub total_bytes: u64,
     pub bind_ports: Vec<u16>,
+    pub restarts: u32,
+}
+
+/// A crashed pipeline the registry respawned.
+pub struct PipelineRestart {
+    pub stream_id: String,
+    pub exit_status: ExitStatus,
+    /// Crash restarts since the stream started, including this one.
+    pub restarts: u32,
+}
+
+/// Result of [`PipelineRegistry::check_exits`].
+#[derive(Default)]
+pub struct ExitCheck {
+    /// Streams that are over: clean exits, exhausted restart budgets and
+    /// failed respawns.
+    pub ended: Vec<ChildExitInfo>,
+    pub restarted: Vec<PipelineRestart>,
 }
 
 fn pipeline_binary() -> std::ffi::OsString {
@@ -107,6 +165,9 @@ impl PipelineRegistry {
     }
 
     /// Start a receiver pipeline for a stream.
+    ///
+    /// With a `preview_key`, the pipeline's HLS relay output is written to a
+    /// known directory so the preview server can serve it back.
     pub fn start(
         &mut self,
         stream_id: &str,
@@ -114,6 +175,43 @@ impl PipelineRegistry {
         bind_ports: &[u16],
         relay_url: Option<&str>,
         bonding_config: &serde_json::Value,
+        preview_key: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let spec = PipelineSpec {
+            bind_host: bind_host.to_string(),
+            relay_url: relay_url.map(str::to_string),
+            bonding_config: bonding_config.clone(),
+            ingest: None,
+        };
+        self.launch(stream_id, spec, bind_ports, preview_key)
+    }
+
+    /// Start a pipeline taking a plain SRT/RTMP contribution from `source`
+    /// (an [`IngestEndpoint`](crate::ingest::IngestEndpoint) source).
+    /// `bind_ports` are the pool ports it holds, released on stop.
+    pub fn start_ingest(
+        &mut self,
+        stream_id: &str,
+        source: &str,
+        bind_ports: &[u16],
+        relay_url: Option<&str>,
+        preview_key: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let spec = PipelineSpec {
+            bind_host: String::new(),
+            relay_url: relay_url.map(str::to_string),
+            bonding_config: serde_json::Value::Null,
+            ingest: Some(source.to_string()),
+        };
+        self.launch(stream_id, spec, bind_ports, preview_key)
+    }
+
+    fn launch(
+        &mut self,
+        stream_id: &str,
+        spec: PipelineSpec,
+        bind_ports: &[u16],
+        preview_key: Option<&str>,
     ) -> anyhow::Result<()> {
         if self.pipelines.contains_key(stream_id) {
             anyhow::bail!("pipeline already running for stream {stream_id}");
@@ -121,7 +219,6 @@ impl PipelineRegistry {
 
         let stats_port = self.next_stats_port;
         self.next_stats_port += 1;
-        let stats_addr = format!("127.0.0.1:{stats_port}");
 
         tracing::info!(
             stream_id = %stream_id,
@@ -130,13 +227,14 @@ impl PipelineRegistry {
             "starting receiver pipeline"
         );
 
+        let preview = preview_key.map(|key| (key.to_string(), preview_dir(stream_id)));
+
         let child = spawn_receiver_pipeline(
             stream_id,
-            bind_host,
+            &spec,
             bind_ports,
-            relay_url,
-            bonding_config,
-            &stats_addr,
+            stats_port,
+            preview.as_ref().map(|(_, dir)| dir.as_path()),
         )?;
 
         self.pipelines.insert(
@@ -147,6 +245,10 @@ impl PipelineRegistry {
                 stats_port,
                 started_at: Instant::now(),
                 total_bytes: 0,
+                preview,
+                spec,
+                recent_restarts: Vec::new(),
+                restarts: 0,
             },
         );
 
@@ -167,6 +269,7 @@ impl PipelineRegistry {
         let duration_s = entry.started_at.elapsed().as_secs();
 
         shutdown_child(&mut entry.child, timeout, stream_id);
+        remove_preview_dir(&entry);
 
         Some(PipelineStopStats {
             duration_s,
@@ -183,35 +286,64 @@ impl PipelineRegistry {
         }
     }
 
-    /// Check all child processes for unexpected exits.
-    pub fn check_exits(&mut self) -> Vec<ChildExitInfo> {
-        let mut exits = Vec::new();
+    /// Check all child processes for exits, respawning crashed pipelines
+    /// that still have restart budget.
+    pub fn check_exits(&mut self) -> ExitCheck {
+        let mut check = ExitCheck::default();
         let mut exited_ids = Vec::new();
 
         for (stream_id, entry) in &mut self.pipelines {
-            match entry.child.try_wait() {
-                Ok(Some(status)) => {
-                    exits.push(ChildExitInfo {
-                        stream_id: stream_id.clone(),
-                        exit_status: status,
-                        duration_s: entry.started_at.elapsed().as_secs(),
-                        total_bytes: entry.total_bytes,
-                        bind_ports: entry.bind_ports.clone(),
-                    });
-                    exited_ids.push(stream_id.clone());
-                }
-                Ok(None) => {} // Still running
+            let status = match entry.child.try_wait() {
+                Ok(Some(status)) => status,
+                Ok(None) => continue, // Still running
                 Err(e) => {
                     tracing::warn!(stream_id = %stream_id, error = %e, "error checking child");
+                    continue;
+                }
+            };
+
+            if !status.success() && entry.restart_allowed(Instant::now()) {
+                let respawned = spawn_receiver_pipeline(
+                    stream_id,
+                    &entry.spec,
+                    &entry.bind_ports,
+                    entry.stats_port,
+                    entry.preview.as_ref().map(|(_, dir)| dir.as_path()),
+                );
+                match respawned {
+                    Ok(child) => {
+                        entry.child = child;
+                        entry.restarts += 1;
+                        check.restarted.push(PipelineRestart {
+                            stream_id: stream_id.clone(),
+                            exit_status: status,
+                            restarts: entry.restarts,
+                        });
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(stream_id = %stream_id, error = %e, "pipeline respawn failed");
+                    }
                 }
             }
+
+            check.ended.push(ChildExitInfo {
+                stream_id: stream_id.clone(),
+                exit_status: status,
+                duration_s: entry.started_at.elapsed().as_secs(),
+                total_bytes: entry.total_bytes,
+                bind_ports: entry.bind_ports.clone(),
+                restarts: entry.restarts,
+            });
+            remove_preview_dir(entry);
+            exited_ids.push(stream_id.clone());
         }
 
         for id in exited_ids {
             self.pipelines.remove(&id);
         }
 
-        exits
+        check
     }
 
     /// Get the stats listen port for a given stream.
@@ -219,43 +351,101 @@ impl PipelineRegistry {
         self.pipelines.get(stream_id).map(|e| e.stats_port)
     }
 
-    /// Get all active stream IDs and their stats ports.
-    pub fn active_streams(&self) -> Vec<(String, u16)> {
+    /// HLS output directory of a running stream, if `key` is its preview key.
+    pub fn preview_dir(&self, stream_id: &str, key: &str) -> Option<PathBuf> {
+        let (expected, dir) = self.pipelines.get(stream_id)?.preview.as_ref()?;
+        bool::from(expected.as_bytes().ct_eq(key.as_bytes())).then(|| dir.clone())
+    }
+
+    /// All running streams with their stats ports and health.
+    pub fn active_streams(&self) -> Vec<ActiveStream> {
         self.pipelines
             .iter()
-            .map(|(id, e)| (id.clone(), e.stats_port))
+            .map(|(id, e)| ActiveStream {
+                stream_id: id.clone(),
+                stats_port: e.stats_port,
+                uptime_s: e.started_at.elapsed().as_secs(),
+                restarts: e.restarts,
+            })
             .collect()
     }
 }
 
+impl PipelineEntry {
+    /// Whether another crash restart fits the budget; records it if so.
+    fn restart_allowed(&mut self, now: Instant) -> bool {
+        self.recent_restarts
+            .retain(|&t| now.duration_since(t) < RESTART_WINDOW);
+        if self.recent_restarts.len() >= MAX_RESTARTS {
+            return false;
+        }
+        self.recent_restarts.push(now);
+        true
+    }
+}
+
+/// Where a stream's preview segments live — tmpfs when available, since
+/// segments are rewritten every couple of seconds.
+fn preview_dir(stream_id: &str) -> PathBuf {
+    let shm = Path::new("/dev/shm");
+    let base = if shm.is_dir() {
+        shm.to_path_buf()
+    } else {
+        std::env::temp_dir()
+    };
+    base.join(format!("strata-preview-{stream_id}"))
+}
+
+fn remove_preview_dir(entry: &PipelineEntry) {
+    if let Some((_, dir)) = &entry.preview {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
 /// Spawn `strata-pipeline receiver` as a child process.
 fn spawn_receiver_pipeline(
     stream_id: &str,
-    bind_host: &str,
+    spec: &PipelineSpec,
     bind_ports: &[u16],
-    relay_url: Option<&str>,
-    bonding_config: &serde_json::Value,
-    stats_addr: &str,
+    stats_port: u16,
+    hls_dir: Option<&Path>,
 ) -> anyhow::Result<Child> {
+    let PipelineSpec {
+        bind_host,
+        relay_url,
+        bonding_config,
+        ingest,
+    } = spec;
     let bin = pipeline_binary();
     let mut cmd = std::process::Command::new(&bin);
     cmd.arg("receiver");
 
-    // Bind addresses: one per link port
-    let bind_str: String = bind_ports
-        .iter()
-        .map(|p| format!("{bind_host}:{p}"))
-        .collect::<Vec<_>>()
-        .join(",");
-    cmd.arg("--bind").arg(&bind_str);
+    if let Some(source) = ingest {
+        // Phones contribute H.264 (the only video classic RTMP carries).
+        cmd.arg("--ingest").arg(source).arg("--codec").arg("h264");
+    } else {
+        // Bind addresses: one per link port
+        let bind_str: String = bind_ports
+            .iter()
+            .map(|p| format!("{bind_host}:{p}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        cmd.arg("--bind").arg(&bind_str);
+    }
 
     // Relay URL (RTMP/HLS output)
     if let Some(url) = relay_url {
         cmd.arg("--relay-url").arg(url);
     }
 
+    // HLS output directory, shared with the preview server
+    if let Some(dir) = hls_dir {
+        cmd.arg("--hls-dir").arg(dir);
+    }
+
     // Stats relay
-    cmd.arg("--stats-dest").arg(stats_addr);
+    cmd.arg("--stats-dest")
+        .arg(format!("127.0.0.1:{stats_port}"));
 
     // Write bonding config to temp file if non-empty
     if !bonding_config.is_null() {
@@ -294,7 +484,6 @@ mod tests {
     use super::*;
     use std::fs;
     use std::os::unix::fs::PermissionsExt;
-    use std::path::{Path, PathBuf};
     use std::sync::atomic::{AtomicU64, Ordering};
 
     static NEXT_ID: AtomicU64 = AtomicU64::new(0);
@@ -332,6 +521,7 @@ mod tests {
             let behavior = match mode {
                 "graceful" => "trap 'echo sigint >> \"$marker\"; exit 0' INT",
                 "stubborn" => "trap 'echo sigint >> \"$marker\"' INT",
+                "crash" => "exit 3",
                 other => panic!("unknown test pipeline mode: {other}"),
             };
             let body = format!(
@@ -419,6 +609,7 @@ mod tests {
                 &[5000, 5002],
                 None,
                 &serde_json::Value::Null,
+                None,
             )
             .unwrap();
         script.wait_for_marker("started");
@@ -448,6 +639,7 @@ mod tests {
                 &[5000],
                 None,
                 &serde_json::Value::Null,
+                None,
             )
             .unwrap();
         script.wait_for_marker("started");
@@ -459,4 +651,67 @@ mod tests {
         assert!(script.marker_contents().contains("sigint"));
         assert!(!process_is_alive(pid));
     }
+
+    #[test]
+    fn crashed_pipeline_is_respawned_until_budget_runs_out() {
+        let script = TestPipelineScript::new("crash");
+        let _guard = set_test_pipeline_bin(&script.script);
+        let mut registry = PipelineRegistry::new();
+
+        registry
+            .start(
+                "stream-1",
+                "127.0.0.1",
+                &[5000],
+                None,
+                &serde_json::Value::Null,
+                None,
+            )
+            .unwrap();
+
+        let mut restarted = 0;
+        let mut ended = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while ended.is_empty() {
+            assert!(Instant::now() < deadline, "pipeline never ended");
+            std::thread::sleep(Duration::from_millis(20));
+            let check = registry.check_exits();
+            restarted += check.restarted.len();
+            ended.extend(check.ended);
+        }
+
+        assert_eq!(restarted, MAX_RESTARTS);
+        assert_eq!(ended[0].restarts, MAX_RESTARTS as u32);
+        assert_eq!(ended[0].exit_status.code(), Some(3));
+        assert_eq!(ended[0].bind_ports, vec![5000]);
+        assert_eq!(registry.active_count(), 0);
+    }
+
+    #[test]
+    fn preview_dir_requires_matching_key() {
+        let script = TestPipelineScript::new("graceful");
+        let _guard = set_test_pipeline_bin(&script.script);
+        let mut registry = PipelineRegistry::new();
+
+        registry
+            .start(
+                "stream-1",
+                "127.0.0.1",
+                &[5000],
+                None,
+                &serde_json::Value::Null,
+                Some("pk-secret"),
+            )
+            .unwrap();
+        script.wait_for_marker("started");
+
+        let dir = registry.preview_dir("stream-1", "pk-secret").unwrap();
+        assert!(dir.ends_with("strata-preview-stream-1"));
+        assert!(registry.preview_dir("stream-1", "pk-secreT").is_none());
+        assert!(registry.preview_dir("stream-1", "pk").is_none());
+        assert!(registry.preview_dir("stream-2", "pk-secret").is_none());
+
+        registry.stop_with_timeout("stream-1", Duration::from_millis(500));
+        assert!(registry.preview_dir("stream-1", "pk-secret").is_none());
+    }
 }
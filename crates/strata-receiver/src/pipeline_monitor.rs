@@ -1,8 +1,9 @@
 //! Pipeline child process monitor for the receiver daemon.
 //!
-//! Polls all running receiver pipelines every 500ms. If any exit
-//! unexpectedly, sends `receiver.stream.ended` to the control plane
-//! and releases the allocated ports.
+//! Polls all running receiver pipelines every 500ms.  Crashed pipelines
+//! are respawned by the registry while their restart budget lasts; once a
+//! stream is over, sends `receiver.stream.ended` to the control plane and
+//! releases the allocated ports.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -24,12 +25,21 @@ pub async fn run(state: Arc<ReceiverState>) {
             return;
         }
 
-        let exits = {
+        let check = {
             let mut pipelines = state.pipelines.lock().await;
             pipelines.check_exits()
         };
 
-        for exit_info in exits {
+        for restart in &check.restarted {
+            tracing::warn!(
+                stream_id = %restart.stream_id,
+                exit_code = ?restart.exit_status.code(),
+                restarts = restart.restarts,
+                "receiver pipeline crashed, respawned"
+            );
+        }
+
+        for exit_info in check.ended {
             let exit_code = exit_info.exit_status.code();
             let reason = match exit_code {
                 Some(0) => StreamEndReason::UserStop,
@@ -40,6 +50,7 @@ pub async fn run(state: Arc<ReceiverState>) {
                 stream_id = %exit_info.stream_id,
                 exit_code = ?exit_code,
                 duration_s = exit_info.duration_s,
+                restarts = exit_info.restarts,
                 "receiver pipeline exited unexpectedly"
             );
 
@@ -57,8 +68,14 @@ pub async fn run(state: Arc<ReceiverState>) {
 
             let error = match (reason, exit_code) {
                 (StreamEndReason::UserStop, _) => None,
-                (_, Some(code)) => Some(format!("pipeline exited with code {code}")),
-                (_, None) => Some("pipeline killed by signal".to_string()),
+                (_, Some(code)) => Some(format!(
+                    "pipeline exited with code {code}{}",
+                    restart_note(exit_info.restarts)
+                )),
+                (_, None) => Some(format!(
+                    "pipeline killed by signal{}",
+                    restart_note(exit_info.restarts)
+                )),
             };
             let ended = ReceiverStreamEndedPayload {
                 stream_id: exit_info.stream_id,
@@ -75,3 +92,11 @@ pub async fn run(state: Arc<ReceiverState>) {
         }
     }
 }
+
+fn restart_note(restarts: u32) -> String {
+    if restarts == 0 {
+        String::new()
+    } else {
+        format!(" after {restarts} restarts")
+    }
+}
@@ -0,0 +1,109 @@
+//! Live preview server — serves each stream's HLS relay output back to
+//! the dashboard.
+//!
+//! GET /preview/{stream_id}/{key}/{file}
+//!
+//! `key` is the per-stream preview key minted by the control plane and
+//! handed over in `receiver.stream.start`; only the playlist and its
+//! segments are served.
+//!
+//! With `--preview-tls` the server terminates TLS itself, using the
+//! certificate the control plane pushes (`tls.install`).
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use strata_common::tls::server::TlsListener;
+
+use crate::ReceiverState;
+
+pub async fn run(state: Arc<ReceiverState>, addr: SocketAddr, tls: bool) -> anyhow::Result<()> {
+    let resolver = state.tls.resolver();
+    let app = Router::new()
+        .route("/preview/{stream_id}/{key}/{file}", get(preview_file))
+        .with_state(state);
+
+    tracing::info!(%addr, tls, "preview server listening");
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    if tls {
+        axum::serve(TlsListener::new(listener, resolver)?, app).await?;
+    } else {
+        axum::serve(listener, app).await?;
+    }
+    Ok(())
+}
+
+/// Content type for a servable preview file, or `None` if `file` is not
+/// a plain playlist or segment name.
+fn content_type(file: &str) -> Option<&'static str> {
+    let (stem, ext) = file.rsplit_once('.')?;
+    if stem.is_empty()
+        || !stem
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    {
+        return None;
+    }
+    match ext {
+        "m3u8" => Some("application/vnd.apple.mpegurl"),
+        "ts" => Some("video/mp2t"),
+        _ => None,
+    }
+}
+
+async fn preview_file(
+    State(state): State<Arc<ReceiverState>>,
+    Path((stream_id, key, file)): Path<(String, String, String)>,
+) -> Response {
+    let Some(content_type) = content_type(&file) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let Some(dir) = state.pipelines.lock().await.preview_dir(&stream_id, &key) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let body = match tokio::fs::read(dir.join(&file)).await {
+        Ok(body) => body,
+        Err(_) => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    // The playlist is rewritten every segment; segments never change.
+    let cache = if file.ends_with(".m3u8") {
+        "no-cache"
+    } else {
+        "max-age=60"
+    };
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, cache),
+            (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serves_only_playlists_and_segments() {
+        assert_eq!(
+            content_type("playlist.m3u8"),
+            Some("application/vnd.apple.mpegurl")
+        );
+        assert_eq!(content_type("seg-g0001-00042.ts"), Some("video/mp2t"));
+        assert_eq!(content_type("config.toml"), None);
+        assert_eq!(content_type("..%2Fplaylist.m3u8"), None);
+        assert_eq!(content_type("a.b.ts"), None);
+        assert_eq!(content_type(".ts"), None);
+        assert_eq!(content_type("playlist"), None);
+    }
+}
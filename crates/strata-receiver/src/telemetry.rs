@@ -6,11 +6,20 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use strata_protocol::models::{EgressStats, LinkStats};
+use strata_protocol::models::{AvSyncStats, EgressStats};
+use strata_protocol::telemetry::LinkSample;
 use strata_protocol::{Envelope, ReceiverMessage, ReceiverStreamStatsPayload};
 
 use crate::ReceiverState;
 
+/// One relayed stats datagram.
+#[derive(Debug, Default)]
+struct RelayedStats {
+    links: Vec<LinkSample>,
+    egress: Option<EgressStats>,
+    av_sync: Option<AvSyncStats>,
+}
+
 /// Run the telemetry loop — collects stats from all active pipelines.
 pub async fn run(state: Arc<ReceiverState>) {
     let mut interval = tokio::time::interval(Duration::from_secs(1));
@@ -41,9 +50,10 @@ pub async fn run(state: Arc<ReceiverState>) {
 
         let receiver_id = state.receiver_id.lock().await.clone().unwrap_or_default();
 
-        for (stream_id, stats_port) in &active {
+        for stream in &active {
+            let (stream_id, stats_port) = (&stream.stream_id, stream.stats_port);
             // Lazily bind sockets for new streams
-            let sock = match sockets.entry(*stats_port) {
+            let sock = match sockets.entry(stats_port) {
                 std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
                 std::collections::hash_map::Entry::Vacant(e) => {
                     let addr = format!("127.0.0.1:{stats_port}");
@@ -65,14 +75,19 @@ pub async fn run(state: Arc<ReceiverState>) {
             };
 
             // Drain incoming stats, keep the latest
-            let mut last_stats: Option<(Vec<LinkStats>, Option<EgressStats>)> = None;
+            let mut last_stats: Option<RelayedStats> = None;
             while let Ok((n, _)) = sock.recv_from(&mut recv_buf) {
                 if let Ok(parsed) = parse_bonding_stats(&recv_buf[..n]) {
                     last_stats = Some(parsed);
                 }
             }
 
-            if let Some((links, egress)) = last_stats {
+            if let Some(RelayedStats {
+                links,
+                egress,
+                av_sync,
+            }) = last_stats
+            {
                 // Update shared stats
                 {
                     let mut latest = state.latest_stats.write().await;
@@ -87,10 +102,12 @@ pub async fn run(state: Arc<ReceiverState>) {
                 let payload = ReceiverStreamStatsPayload {
                     stream_id: stream_id.clone(),
                     receiver_id: receiver_id.clone(),
-                    uptime_s: 0, // Will be enriched by the pipeline entry
+                    uptime_s: stream.uptime_s,
                     timestamp_ms,
                     links,
                     egress,
+                    av_sync,
+                    pipeline_restarts: stream.restarts,
                 };
 
                 let envelope = Envelope::from_message(&ReceiverMessage::StreamStats(payload));
@@ -101,13 +118,14 @@ pub async fn run(state: Arc<ReceiverState>) {
         }
 
         // Remove sockets for streams that are no longer active
-        let active_ports: std::collections::HashSet<u16> = active.iter().map(|(_, p)| *p).collect();
+        let active_ports: std::collections::HashSet<u16> =
+            active.iter().map(|s| s.stats_port).collect();
         sockets.retain(|port, _| active_ports.contains(port));
     }
 }
 
 /// Parse bonding stats JSON from strata-pipeline.
-fn parse_bonding_stats(data: &[u8]) -> Result<(Vec<LinkStats>, Option<EgressStats>), String> {
+fn parse_bonding_stats(data: &[u8]) -> Result<RelayedStats, String> {
     let v: serde_json::Value =
         serde_json::from_slice(data).map_err(|e| format!("JSON parse error: {e}"))?;
     let links_arr = v
@@ -120,51 +138,18 @@ fn parse_bonding_stats(data: &[u8]) -> Result<(Vec<LinkStats>, Option<EgressStat
         .get("egress")
         .and_then(|e| serde_json::from_value::<EgressStats>(e.clone()).ok());
 
-    let mut stats = Vec::with_capacity(links_arr.len());
-    for link in links_arr {
-        let id = link.get("id").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-        let rtt_us = link.get("rtt_us").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let loss = link
-            .get("loss_rate")
-            .or_else(|| link.get("loss_percent"))
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let capacity = link
-            .get("capacity_bps")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let received = link
-            .get("received_bytes")
-            .or_else(|| link.get("rx_bytes"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let observed_bps = link
-            .get("observed_bps")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let iface = link
-            .get("interface")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        stats.push(LinkStats {
-            id,
-            interface: iface.to_string(),
-            state: "Live".to_string(),
-            rtt_ms: rtt_us / 1000.0,
-            loss_rate: loss,
-            capacity_bps: capacity,
-            sent_bytes: received, // receiver side: these are received bytes
-            observed_bps,
-            signal_dbm: None,
-            link_kind: None,
-            rsrp: None,
-            rsrq: None,
-            sinr: None,
-            cqi: None,
-            btlbw_bps: None,
-            rtprop_ms: None,
-        });
-    }
-    Ok((stats, egress))
+    // Lipsync clocks (absent until stratasrc has seen TS timestamps).
+    let av_sync = v
+        .get("av_sync")
+        .and_then(|a| serde_json::from_value::<AvSyncStats>(a.clone()).ok());
+
+    let links = links_arr
+        .iter()
+        .map(LinkSample::from_bonding_report)
+        .collect();
+    Ok(RelayedStats {
+        links,
+        egress,
+        av_sync,
+    })
 }
@@ -20,7 +20,8 @@ use tokio_tungstenite::tungstenite::Message;
 use strata_common::identity::DeviceIdentity;
 use strata_protocol::{
     AuthChallengePayload, Envelope, ReceiverAuthLoginResponsePayload, ReceiverControlMessage,
-    ReceiverMessage, ReceiverStreamStartPayload, ReceiverStreamStopPayload, StreamEndReason,
+    ReceiverMessage, ReceiverStreamStartPayload, ReceiverStreamStatsPayload,
+    ReceiverStreamStopPayload, StreamEndReason,
 };
 
 /// The daemon binds fixed host resources (per-stream stats UDP listeners
@@ -29,6 +30,10 @@ static SERIAL: Mutex<()> = Mutex::const_new(());
 
 const RECV_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Crash respawns the daemon allows per stream within its restart window
+/// (`pipeline::MAX_RESTARTS`).
+const MAX_RESTARTS: u32 = 3;
+
 static NEXT_ID: AtomicU64 = AtomicU64::new(0);
 
 async fn lock_serial() -> tokio::sync::MutexGuard<'static, ()> {
@@ -121,6 +126,27 @@ impl FakePipelineScript {
             .unwrap()
     }
 
+    /// Wait for the daemon to respawn the pipeline after `old_pid` died;
+    /// returns the new pid.
+    async fn wait_respawned(&self, old_pid: i32) -> i32 {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        loop {
+            let pid = std::fs::read_to_string(&self.pidfile)
+                .ok()
+                .and_then(|s| s.trim().parse::<i32>().ok());
+            if let Some(pid) = pid
+                && pid != old_pid
+            {
+                return pid;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "timed out waiting for the pipeline to be respawned"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
     /// The argv the daemon spawned the pipeline with (whitespace-joined).
     fn args(&self) -> String {
         std::fs::read_to_string(&self.argsfile).unwrap_or_default()
@@ -225,6 +251,42 @@ impl Conn {
             .unwrap();
     }
 
+    /// Stand in for the pipeline's stats relay: send `blob` to
+    /// `stats_dest` until a receiver.stream.stats for `stream_id` that
+    /// `accept`s comes back. The telemetry loop polls once a second.
+    async fn relay_stats(
+        &mut self,
+        stats_dest: &str,
+        stream_id: &str,
+        blob: &serde_json::Value,
+        accept: impl Fn(&ReceiverStreamStatsPayload) -> bool,
+    ) -> ReceiverStreamStatsPayload {
+        let sock = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let deadline = tokio::time::Instant::now() + RECV_TIMEOUT;
+        loop {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "timed out waiting for receiver.stream.stats"
+            );
+            sock.send_to(blob.to_string().as_bytes(), stats_dest)
+                .unwrap();
+            let got = tokio::time::timeout(Duration::from_secs(2), async {
+                loop {
+                    if let ReceiverMessage::StreamStats(p) = self.recv_receiver().await
+                        && p.stream_id == stream_id
+                        && accept(&p)
+                    {
+                        break p;
+                    }
+                }
+            })
+            .await;
+            if let Ok(p) = got {
+                return p;
+            }
+        }
+    }
+
     /// Skip unrelated traffic (heartbeats, stats) until `f` matches.
     async fn wait_for<T>(&mut self, what: &str, f: impl Fn(ReceiverMessage) -> Option<T>) -> T {
         let deadline = tokio::time::Instant::now() + RECV_TIMEOUT;
@@ -314,6 +376,9 @@ fn stream_start(request_id: &str, stream_id: &str, link_count: u32) -> ReceiverC
         link_count,
         relay_url: None,
         bonding_config: serde_json::Value::Null,
+        ingest_key: None,
+        preview_key: None,
+        ingest: None,
     })
 }
 
@@ -385,7 +450,9 @@ async fn reconnect_answers_ed25519_challenge() {
 }
 
 /// receiver.stream.start allocates ports from the daemon's own pool, acks
-/// them back, and spawns the pipeline; killing the child yields
+/// them back, and spawns the pipeline. A killed child is respawned on the
+/// same ports, counted in the stats' `pipeline_restarts`, until the restart
+/// budget runs out; the next crash yields
 /// receiver.stream.ended(pipeline_crash) and the heartbeat drops the stream.
 #[tokio::test]
 async fn stream_start_allocates_ports_and_crash_is_reported() {
@@ -446,9 +513,33 @@ async fn stream_start_allocates_ports_and_crash_is_reported() {
     })
     .await;
 
-    // Kill the pipeline child out from under the daemon.
-    let pid = script.pid();
-    // SAFETY: pid is the fake pipeline script this test spawned via the daemon.
+    // Kill the pipeline child out from under the daemon: within budget, it
+    // comes back on the same ports and the stats count the restart.
+    let stats_dest = script.stats_dest();
+    let blob = serde_json::json!({ "links": [], "timestamp_ms": 1u64 });
+    let mut pid = script.pid();
+    for restart in 1..=MAX_RESTARTS {
+        // SAFETY: pid is the fake pipeline script this test spawned via the daemon.
+        unsafe {
+            assert_eq!(libc::kill(pid, libc::SIGKILL), 0);
+        }
+        pid = script.wait_respawned(pid).await;
+        assert!(
+            script
+                .args()
+                .contains("--bind 127.0.0.1:6000,127.0.0.1:6002"),
+            "respawn must keep the stream's ports"
+        );
+        let stats = conn
+            .relay_stats(&stats_dest, "str_lifecycle", &blob, |p| {
+                p.pipeline_restarts == restart
+            })
+            .await;
+        assert_eq!(stats.pipeline_restarts, restart);
+    }
+
+    // Out of budget: the next crash ends the stream.
+    // SAFETY: as above.
     unsafe {
         assert_eq!(libc::kill(pid, libc::SIGKILL), 0);
     }
@@ -501,10 +592,9 @@ async fn stats_relay_forwards_links_and_egress() {
     .await;
     script.wait_started().await;
 
-    // Stand in for the pipeline's stats relay: send one JSON blob to the
-    // --stats-dest address the daemon handed it.
+    // Stand in for the pipeline's stats relay at the --stats-dest address
+    // the daemon handed it.
     let stats_dest = script.stats_dest();
-    let sock = UdpSocket::bind("127.0.0.1:0").unwrap();
     let blob = serde_json::json!({
         "links": [
             {"id": 0, "loss_rate": 0.25, "received_bytes": 1_000_000u64, "observed_bps": 800_000u64},
@@ -512,24 +602,9 @@ async fn stats_relay_forwards_links_and_egress() {
         "egress": {"segments_produced": 42, "wd_restarts": 1, "last_segment_age_ms": 900},
         "timestamp_ms": 1u64,
     });
-    // The telemetry loop polls once a second; keep sending until it forwards.
-    let stats = loop {
-        sock.send_to(blob.to_string().as_bytes(), &stats_dest)
-            .unwrap();
-        let got = tokio::time::timeout(Duration::from_secs(2), async {
-            loop {
-                if let ReceiverMessage::StreamStats(p) = conn.recv_receiver().await
-                    && p.stream_id == "str_stats"
-                {
-                    break p;
-                }
-            }
-        })
+    let stats = conn
+        .relay_stats(&stats_dest, "str_stats", &blob, |_| true)
         .await;
-        if let Ok(p) = got {
-            break p;
-        }
-    };
 
     assert_eq!(stats.receiver_id, "rcv_stats");
     assert_eq!(stats.links.len(), 1);
@@ -539,7 +614,7 @@ async fn stats_relay_forwards_links_and_egress() {
         stats.links[0].sent_bytes, 1_000_000,
         "receiver-side received bytes"
     );
-    assert_eq!(stats.links[0].observed_bps, 800_000);
+    assert_eq!(stats.links[0].observed_bps.0, 800_000);
     let egress = stats
         .egress
         .expect("egress heartbeat must survive the relay");
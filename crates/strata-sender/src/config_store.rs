@@ -0,0 +1,167 @@
+//! Agent config store — small local settings that must survive restarts.
+//!
+//! A JSON file next to the identity and interface-admin state. A missing
+//! or corrupt file just means defaults. Settings the control plane owns
+//! (the portal PIN) are cached here so they still apply while offline,
+//! and are re-pushed on every connect.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where the store persists.
+fn config_file() -> PathBuf {
+    std::env::var("STRATA_AGENT_CONFIG_FILE")
+        .unwrap_or_else(|_| "/var/lib/strata/agent-config.json".into())
+        .into()
+}
+
+/// Steps of the portal's first-run wizard, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    Interfaces,
+    Receiver,
+    Connectivity,
+    Enroll,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 4] = [
+        SetupStep::Interfaces,
+        SetupStep::Receiver,
+        SetupStep::Connectivity,
+        SetupStep::Enroll,
+    ];
+}
+
+/// First-run wizard progress.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetupState {
+    #[serde(default)]
+    pub completed_steps: Vec<SetupStep>,
+    /// Set when the wizard was finished (or skipped); the portal then
+    /// opens on the full status page.
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl SetupState {
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+
+    /// First step not yet done, where a resumed wizard picks up.
+    pub fn next_step(&self) -> Option<SetupStep> {
+        SetupStep::ALL
+            .into_iter()
+            .find(|s| !self.completed_steps.contains(s))
+    }
+
+    pub fn mark(&mut self, step: SetupStep) {
+        if !self.completed_steps.contains(&step) {
+            self.completed_steps.push(step);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentConfig {
+    #[serde(default)]
+    pub setup: SetupState,
+    /// Argon2 hash of the portal PIN pushed by the control plane; `None`
+    /// leaves the portal open.
+    #[serde(default)]
+    pub portal_pin_hash: Option<String>,
+}
+
+pub struct ConfigStore {
+    path: PathBuf,
+    config: std::sync::Mutex<AgentConfig>,
+}
+
+impl ConfigStore {
+    pub fn load() -> Self {
+        Self::load_from(config_file())
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let config = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+                tracing::warn!(error = %e, path = %path.display(), "ignoring corrupt agent config");
+                AgentConfig::default()
+            }),
+            Err(_) => AgentConfig::default(),
+        };
+        Self {
+            path,
+            config: std::sync::Mutex::new(config),
+        }
+    }
+
+    pub fn get(&self) -> AgentConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Apply `f` and persist. The in-memory change stands even if the
+    /// write fails, so the current session still sees it.
+    pub fn update(&self, f: impl FnOnce(&mut AgentConfig)) -> anyhow::Result<AgentConfig> {
+        let snapshot = {
+            let mut config = self.config.lock().unwrap();
+            f(&mut config);
+            config.clone()
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setup_steps_resume_in_order() {
+        let mut setup = SetupState::default();
+        assert_eq!(setup.next_step(), Some(SetupStep::Interfaces));
+        setup.mark(SetupStep::Receiver);
+        setup.mark(SetupStep::Interfaces);
+        setup.mark(SetupStep::Interfaces);
+        assert_eq!(setup.completed_steps.len(), 2);
+        assert_eq!(setup.next_step(), Some(SetupStep::Connectivity));
+        setup.mark(SetupStep::Connectivity);
+        setup.mark(SetupStep::Enroll);
+        assert_eq!(setup.next_step(), None);
+        assert!(!setup.is_complete());
+    }
+
+    #[test]
+    fn store_persists_and_reloads() {
+        let dir = std::env::temp_dir().join(format!("strata-cfg-test-{}", std::process::id()));
+        let path = dir.join("agent-config.json");
+
+        let store = ConfigStore::load_from(path.clone());
+        assert!(!store.get().setup.is_complete());
+        store
+            .update(|c| {
+                c.setup.mark(SetupStep::Interfaces);
+                c.setup.completed_at = Some(Utc::now());
+            })
+            .unwrap();
+
+        let reloaded = ConfigStore::load_from(path.clone());
+        assert!(reloaded.get().setup.is_complete());
+        assert_eq!(
+            reloaded.get().setup.completed_steps,
+            vec![SetupStep::Interfaces]
+        );
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(!ConfigStore::load_from(path).get().setup.is_complete());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
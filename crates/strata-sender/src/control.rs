@@ -4,8 +4,10 @@
 //! - Connection with exponential backoff reconnect
 //! - Authentication (enrollment token or device key)
 //! - Heartbeat (device.status every N seconds)
-//! - Incoming commands (stream.start, stream.stop, config.update)
-//! - Outgoing messages (stream.stats, stream.ended)
+//! - Incoming commands (stream.start, stream.stop, config.update), each
+//!   dispatched to a typed handler and answered with a `command.ack`
+//! - Outgoing messages (stream.stats, stream.ended) — stats as CBOR binary
+//!   frames when the control plane accepts the offer in `auth.login`
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -14,25 +16,33 @@ use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
 
+use crate::AgentState;
+use strata_common::validation::Validate;
+use strata_protocol::encoding::{self, TELEMETRY_ENCODING_CBOR};
 use strata_protocol::models::StreamState;
 use strata_protocol::{
-    AgentMessage, AuthChallengeResponsePayload, AuthLoginPayload, ConfigExportResponsePayload,
-    ConfigImportResponsePayload, ConfigSetResponsePayload, ConfigUpdateResponsePayload,
-    ControlMessage, DeviceStatusPayload, Envelope, FileEntry, FilesListResponsePayload,
-    InterfaceCommandResponsePayload, InterfacesScanResponsePayload, JitterBufferResponsePayload,
-    LogLineEntry, LogsResponsePayload, NetworkToolResponsePayload, PcapCaptureResponsePayload,
-    PowerCommandResponsePayload, SourceSwitchResponsePayload, StreamDestinationsResponsePayload,
-    StreamEndReason, StreamEndedPayload, TestRunResponsePayload, TlsRenewResponsePayload,
-    TlsStatusResponsePayload, UpdatesCheckResponsePayload, UpdatesInstallResponsePayload,
+    AgentMessage, AuthChallengeResponsePayload, AuthLoginPayload, CommandAckPayload,
+    ConfigExportPayload, ConfigExportResponsePayload, ConfigImportPayload,
+    ConfigImportResponsePayload, ConfigSetPayload, ConfigSetResponsePayload, ConfigUpdatePayload,
+    ConfigUpdateResponsePayload, ControlMessage, DeviceStatusPayload, Envelope, ErrorCode,
+    FileEntry, FilesListPayload, FilesListResponsePayload, InterfaceCommandPayload,
+    InterfaceCommandResponsePayload, InterfacesScanPayload, InterfacesScanResponsePayload,
+    JitterBufferPayload, JitterBufferResponsePayload, LogsRequestPayload, LogsResponsePayload,
+    MaintenanceSchedulePayload, NetworkToolPayload, NetworkToolResponsePayload, PcapCapturePayload,
+    PcapCaptureResponsePayload, PortalAuthPayload, PowerCommandPayload,
+    PowerCommandResponsePayload, SourceSelectPayload, SourceSelectResponsePayload,
+    SourceSwitchPayload, SourceSwitchResponsePayload, StreamDestinationsPayload,
+    StreamDestinationsResponsePayload, StreamEndReason, StreamEndedPayload, StreamStartPayload,
+    StreamStopPayload, TestRunPayload, TestRunResponsePayload, TlsInstallPayload, TlsRenewPayload,
+    TlsRenewResponsePayload, TlsStatusPayload, TlsStatusResponsePayload, UpdatesCheckPayload,
+    UpdatesCheckResponsePayload, UpdatesInstallPayload, UpdatesInstallResponsePayload,
 };
 
-use crate::AgentState;
-
 /// Send a typed message to the control plane, logging on failure.
-async fn send_message(state: &AgentState, msg: &AgentMessage) {
-    match Envelope::from_message(msg).and_then(|e| serde_json::to_string(&e)) {
-        Ok(json) => {
-            let _ = state.control_tx.send(json).await;
+pub(crate) async fn send_message(state: &AgentState, msg: &AgentMessage) {
+    match Envelope::from_message(msg) {
+        Ok(envelope) => {
+            let _ = state.control_tx.send(envelope).await;
         }
         Err(e) => {
             tracing::error!(error = %e, "failed to serialize message");
@@ -48,7 +58,7 @@ pub async fn run(
     enrollment_token: Option<&str>,
     hostname: &str,
     heartbeat_interval: u64,
-    mut outgoing_rx: mpsc::Receiver<String>,
+    mut outgoing_rx: mpsc::Receiver<Envelope>,
 ) -> anyhow::Result<()> {
     const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
     const MAX_BACKOFF: Duration = Duration::from_secs(30);
@@ -117,7 +127,7 @@ async fn connect_and_run(
     enrollment_token: Option<&str>,
     hostname: &str,
     heartbeat_interval: u64,
-    outgoing_rx: &mut mpsc::Receiver<String>,
+    outgoing_rx: &mut mpsc::Receiver<Envelope>,
 ) -> anyhow::Result<()> {
     // Connect
     let (ws, _response) = tokio_tungstenite::connect_async(control_url).await?;
@@ -143,6 +153,7 @@ async fn connect_and_run(
         agent_version: env!("CARGO_PKG_VERSION").to_string(),
         hostname: hostname.to_string(),
         arch: std::env::consts::ARCH.to_string(),
+        telemetry_encodings: vec![TELEMETRY_ENCODING_CBOR.to_string()],
     };
 
     let envelope = Envelope::from_message(&AgentMessage::AuthLogin(auth_payload))?;
@@ -193,7 +204,11 @@ async fn connect_and_run(
         .sender_id
         .ok_or_else(|| anyhow::anyhow!("missing sender_id in auth response"))?;
 
-    tracing::info!(sender_id = %sender_id, "authenticated");
+    // Older control planes don't answer the offer — stay on JSON.
+    let binary_telemetry =
+        auth_response.telemetry_encoding.as_deref() == Some(TELEMETRY_ENCODING_CBOR);
+
+    tracing::info!(sender_id = %sender_id, binary_telemetry, "authenticated");
 
     // Store sender_id; persist it into the identity file on first
     // enrollment (the token is spent — the key is now the credential).
@@ -251,8 +266,8 @@ async fn connect_and_run(
 
             // Outgoing messages (stats, stream.ended, etc.)
             msg = outgoing_rx.recv() => {
-                if let Some(text) = msg {
-                    ws_tx.send(Message::Text(text.into())).await?;
+                if let Some(envelope) = msg {
+                    ws_tx.send(encode_outgoing(&envelope, binary_telemetry)?).await?;
                 }
             }
 
@@ -270,6 +285,15 @@ async fn connect_and_run(
     Ok(())
 }
 
+/// Frame an outgoing envelope: high-rate telemetry goes out as CBOR when
+/// the handshake negotiated it, everything else as JSON text.
+fn encode_outgoing(envelope: &Envelope, binary_telemetry: bool) -> anyhow::Result<Message> {
+    if binary_telemetry && encoding::is_high_rate(&envelope.msg_type) {
+        return Ok(Message::Binary(encoding::encode_cbor(envelope)?.into()));
+    }
+    Ok(Message::Text(serde_json::to_string(envelope)?.into()))
+}
+
 /// Build a device.status heartbeat payload.
 async fn build_heartbeat(state: &AgentState) -> DeviceStatusPayload {
     let hw = state.hardware.scan().await;
@@ -298,7 +322,57 @@ async fn build_heartbeat(state: &AgentState) -> DeviceStatusPayload {
     }
 }
 
-/// Handle an incoming control message from the control plane.
+/// Why a command failed: the [`ErrorCode`] the control plane branches on,
+/// plus detail for humans.
+#[derive(Debug)]
+struct CommandError {
+    code: ErrorCode,
+    message: String,
+}
+
+impl CommandError {
+    /// The command's arguments are wrong for this device.
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            code: ErrorCode::InvalidInput,
+            message: message.into(),
+        }
+    }
+
+    /// The command was understood but could not be carried out.
+    fn rejected(message: impl Into<String>) -> Self {
+        Self {
+            code: ErrorCode::DeviceRejected,
+            message: message.into(),
+        }
+    }
+}
+
+type CommandResult = Result<(), CommandError>;
+
+/// The `command.ack` for a handled command envelope.
+fn command_ack(envelope: &Envelope, result: CommandResult) -> CommandAckPayload {
+    let (code, error) = match result {
+        Ok(()) => (None, None),
+        Err(e) => (Some(e.code), Some(e.message)),
+    };
+    CommandAckPayload {
+        ref_id: envelope.id.clone(),
+        msg_type: envelope.msg_type.clone(),
+        request_id: envelope
+            .payload
+            .get("request_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        success: code.is_none(),
+        code,
+        error,
+    }
+}
+
+/// Handle an incoming control message from the control plane: parse it,
+/// dispatch it to its handler, and answer every command with a
+/// `command.ack` (or a NAK when it can't be parsed at all).
 async fn handle_control_message(state: &AgentState, raw: &str) {
     let envelope: Envelope = match serde_json::from_str(raw) {
         Ok(e) => e,
@@ -308,424 +382,649 @@ async fn handle_control_message(state: &AgentState, raw: &str) {
         }
     };
 
-    let msg: ControlMessage = match envelope.parse_message() {
+    // The control plane has already given up on this call (it queued
+    // behind a reconnect or a slow handler); acting on it now would only
+    // surprise whoever retries.
+    if let Some(nak) = envelope.expired_nak(chrono::Utc::now()) {
+        tracing::debug!(msg_type = %envelope.msg_type, "{}", nak.detail);
+        send_message(state, &AgentMessage::Nak(nak)).await;
+        return;
+    }
+
+    let msg: ControlMessage = match envelope.parse_message_or_nak() {
         Ok(m) => m,
-        Err(_) => {
-            tracing::debug!(msg_type = %envelope.msg_type, "unhandled control message");
+        Err(None) => {
+            tracing::debug!("unparseable NAK from control plane dropped");
+            return;
+        }
+        Err(Some(nak)) => {
+            tracing::debug!(msg_type = %envelope.msg_type, code = %nak.code, "unhandled control message");
+            send_message(state, &AgentMessage::Nak(nak)).await;
             return;
         }
     };
 
-    match msg {
+    let Some(result) = dispatch(state, msg).await else {
+        return;
+    };
+    if let Err(e) = &result {
+        tracing::warn!(
+            msg_type = %envelope.msg_type,
+            code = %e.code,
+            error = %e.message,
+            "control command failed"
+        );
+    }
+    send_message(
+        state,
+        &AgentMessage::CommandAck(command_ack(&envelope, result)),
+    )
+    .await;
+}
+
+/// Route a control message to its command handler. `None` for messages
+/// that aren't commands (handshake leftovers, NAKs) and so get no ack.
+async fn dispatch(state: &AgentState, msg: ControlMessage) -> Option<CommandResult> {
+    let result = match msg {
         ControlMessage::AuthLoginResponse(_) | ControlMessage::AuthChallenge(_) => {
             tracing::debug!("unexpected auth message outside handshake");
+            return None;
         }
-        ControlMessage::StreamStart(payload) => {
-            tracing::info!(stream_id = %payload.stream_id, "received stream.start");
-            let eligible = state.hardware.eligible_interfaces();
-            let mut pipeline = state.pipeline.lock().await;
-            if let Err(e) = pipeline.start((*payload).clone(), eligible) {
-                tracing::error!(error = %e, "failed to start pipeline");
-                let ended = StreamEndedPayload {
-                    stream_id: payload.stream_id,
-                    reason: StreamEndReason::Error,
-                    duration_s: 0,
-                    total_bytes: 0,
-                    error: Some(e.to_string()),
-                };
-                send_message(state, &AgentMessage::StreamEnded(ended)).await;
-            }
-        }
-        ControlMessage::StreamStop(payload) => {
-            tracing::info!(stream_id = %payload.stream_id, "received stream.stop");
-            let mut pipeline = state.pipeline.lock().await;
-            let stats = pipeline.stop();
-            let ended = StreamEndedPayload {
-                stream_id: payload.stream_id,
-                reason: StreamEndReason::ControlPlaneStop,
-                duration_s: stats.duration_s,
-                total_bytes: stats.total_bytes,
-                error: None,
-            };
-            send_message(state, &AgentMessage::StreamEnded(ended)).await;
+        ControlMessage::Nak(nak) => {
+            tracing::warn!(
+                msg_type = %nak.msg_type,
+                code = %nak.code,
+                control_proto = nak.proto_version,
+                detail = %nak.detail,
+                "control plane could not handle a message from this agent"
+            );
+            return None;
         }
-        ControlMessage::ConfigUpdate(payload) => {
-            tracing::info!("received config.update");
-            let pipeline = state.pipeline.lock().await;
-            let mut errors: Vec<String> = Vec::new();
+        ControlMessage::StreamStart(payload) => stream_start(state, *payload).await,
+        ControlMessage::StreamStop(payload) => stream_stop(state, payload).await,
+        ControlMessage::ConfigUpdate(payload) => config_update(state, payload).await,
+        ControlMessage::SourceSwitch(payload) => source_switch(state, payload).await,
+        ControlMessage::SourceSelect(payload) => source_select(state, payload).await,
+        ControlMessage::InterfaceCommand(payload) => interface_command(state, payload).await,
+        ControlMessage::ConfigSet(payload) => config_set(state, payload).await,
+        ControlMessage::TestRun(payload) => test_run(state, payload).await,
+        ControlMessage::InterfacesScan(payload) => interfaces_scan(state, payload).await,
+        ControlMessage::FilesList(payload) => files_list(state, payload).await,
+        ControlMessage::NetworkTool(payload) => network_tool(state, payload).await,
+        ControlMessage::PcapCapture(payload) => pcap_capture(state, payload).await,
+        ControlMessage::LogsGet(payload) => logs_get(state, payload).await,
+        ControlMessage::PowerCommand(payload) => power_command(state, payload).await,
+        ControlMessage::TlsStatus(payload) => tls_status(state, payload).await,
+        ControlMessage::TlsRenew(payload) => tls_renew(state, payload).await,
+        ControlMessage::TlsInstall(payload) => tls_install(state, payload),
+        ControlMessage::ConfigExport(payload) => config_export(state, payload).await,
+        ControlMessage::ConfigImport(payload) => config_import(state, payload).await,
+        ControlMessage::UpdatesCheck(payload) => updates_check(state, payload).await,
+        ControlMessage::UpdatesInstall(payload) => updates_install(state, payload).await,
+        ControlMessage::StreamDestinations(payload) => stream_destinations(state, payload).await,
+        ControlMessage::JitterBuffer(payload) => jitter_buffer(state, payload).await,
+        ControlMessage::MaintenanceSchedule(payload) => maintenance_schedule(state, payload).await,
+        ControlMessage::PortalAuth(payload) => portal_auth(state, payload),
+    };
+    Some(result)
+}
 
-            // Apply encoder changes
-            if let Some(enc) = &payload.encoder {
-                let mut cmd = serde_json::json!({ "cmd": "set_encoder" });
-                if let Some(bps) = enc.bitrate_kbps {
-                    cmd["bitrate_kbps"] = serde_json::json!(bps);
-                }
-                if let Some(ref tune) = enc.tune {
-                    cmd["tune"] = serde_json::json!(tune);
-                }
-                if let Some(ki) = enc.keyint_max {
-                    cmd["keyint_max"] = serde_json::json!(ki);
-                }
-                if !pipeline.send_command(&cmd) {
-                    errors.push("failed to send encoder update".into());
-                }
-            }
+// ── Command handlers ────────────────────────────────────────────────
+//
+// One per control message. Each sends its typed `*.response` (if the
+// command has one) and returns the outcome the dispatcher acks.
 
-            // Apply scheduler/bonding changes
-            if let Some(sched) = &payload.scheduler {
-                let cmd = serde_json::json!({
-                    "cmd": "set_bonding_config",
-                    "config": sched,
-                });
-                if !pipeline.send_command(&cmd) {
-                    errors.push("failed to send scheduler update".into());
-                }
-            }
+async fn stream_start(state: &AgentState, payload: StreamStartPayload) -> CommandResult {
+    tracing::info!(stream_id = %payload.stream_id, "received stream.start");
+    let eligible = state.hardware.eligible_interfaces();
+    let mut pipeline = state.pipeline.lock().await;
+    let started = match payload.validate() {
+        Ok(()) => pipeline
+            .start(payload.clone(), eligible)
+            .await
+            .map_err(|e| CommandError::rejected(e.to_string())),
+        Err(e) => Err(CommandError::invalid(format!(
+            "invalid stream config ({}): {e}",
+            e.field
+        ))),
+    };
+    if let Err(e) = &started {
+        tracing::error!(error = %e.message, "failed to start pipeline");
+        let ended = StreamEndedPayload {
+            stream_id: payload.stream_id,
+            reason: StreamEndReason::Error,
+            duration_s: 0,
+            total_bytes: 0,
+            error: Some(e.message.clone()),
+        };
+        send_message(state, &AgentMessage::StreamEnded(ended)).await;
+    }
+    started
+}
 
-            let resp = ConfigUpdateResponsePayload {
-                request_id: payload.request_id.clone(),
-                success: errors.is_empty(),
-                error: if errors.is_empty() {
-                    None
-                } else {
-                    Some(errors.join("; "))
-                },
-            };
-            send_message(state, &AgentMessage::ConfigUpdateResponse(resp)).await;
-        }
-        ControlMessage::SourceSwitch(payload) => {
-            tracing::info!(
-                mode = %payload.mode,
-                pattern = ?payload.pattern,
-                "received source.switch"
-            );
-            // Reject bad v4l2 devices BEFORE they reach the pipeline —
-            // v4l2src fails asynchronously on a non-capture node (camera
-            // metadata nodes, codec nodes) and takes the whole pipeline
-            // down with it (2026-07-05 field crash on /dev/video1).
-            let result = if payload.mode == "v4l2" {
-                let device = payload.device.as_deref().unwrap_or("/dev/video0");
-                if !crate::hardware::is_capture_device(device) {
-                    Err(format!("{device} is not a video capture device"))
-                } else {
-                    Ok(())
-                }
-            } else {
-                Ok(())
-            };
-            let result = match result {
-                Ok(()) => {
-                    let pipeline = state.pipeline.lock().await;
-                    pipeline.switch_source(
-                        &payload.mode,
-                        payload.device.as_deref(),
-                        payload.uri.as_deref(),
-                        payload.pattern.as_deref(),
-                    )
-                }
-                Err(e) => Err(e),
-            };
-            let resp = SourceSwitchResponsePayload {
-                request_id: payload.request_id.clone(),
-                success: result.is_ok(),
-                mode: payload.mode.clone(),
-                error: result.err(),
-            };
-            send_message(state, &AgentMessage::SourceSwitchResponse(resp)).await;
-        }
-        ControlMessage::InterfaceCommand(payload) => {
-            tracing::info!(
-                interface = %payload.interface,
-                action = %payload.action,
-                "received interface.command"
-            );
-            let (success, error) = match payload.action.as_str() {
-                "enable" => {
-                    let ok = state
-                        .hardware
-                        .set_interface_enabled(&payload.interface, true);
-                    (
-                        ok,
-                        if ok {
-                            None
-                        } else {
-                            Some("failed to enable interface".into())
-                        },
-                    )
-                }
-                "disable" => {
-                    let ok = state
-                        .hardware
-                        .set_interface_enabled(&payload.interface, false);
-                    (
-                        ok,
-                        if ok {
-                            None
-                        } else {
-                            Some("failed to disable interface".into())
-                        },
-                    )
-                }
-                other => (false, Some(format!("unknown action: {other}"))),
-            };
-            let resp = InterfaceCommandResponsePayload {
-                request_id: payload.request_id.clone(),
-                success,
-                interface: payload.interface.clone(),
-                action: payload.action.clone(),
-                error,
-            };
-            send_message(state, &AgentMessage::InterfaceCommandResponse(resp)).await;
-
-            // Notify the running pipeline to add/remove this link from
-            // the bonding transport (without touching OS connectivity).
-            if success {
-                let enabled = payload.action == "enable";
-                let pipeline = state.pipeline.lock().await;
-                pipeline.toggle_link(&payload.interface, enabled);
-            }
+async fn stream_stop(state: &AgentState, payload: StreamStopPayload) -> CommandResult {
+    tracing::info!(stream_id = %payload.stream_id, "received stream.stop");
+    let mut pipeline = state.pipeline.lock().await;
+    let stats = pipeline.stop();
+    let ended = StreamEndedPayload {
+        stream_id: payload.stream_id,
+        reason: StreamEndReason::ControlPlaneStop,
+        duration_s: stats.duration_s,
+        total_bytes: stats.total_bytes,
+        error: None,
+    };
+    send_message(state, &AgentMessage::StreamEnded(ended)).await;
+    Ok(())
+}
 
-            send_message(
-                state,
-                &AgentMessage::DeviceStatus(build_heartbeat(state).await),
-            )
-            .await;
-        }
-        ControlMessage::ConfigSet(payload) => {
-            tracing::info!(receiver_url = ?payload.receiver_url, "received config.set");
-            {
-                let mut r = state.receiver_url.lock().await;
-                *r = payload.receiver_url.clone().filter(|s| !s.is_empty());
-            }
-            let current = state.receiver_url.lock().await.clone();
-            let resp = ConfigSetResponsePayload {
-                request_id: payload.request_id,
-                success: true,
-                receiver_url: current,
-            };
-            send_message(state, &AgentMessage::ConfigSetResponse(resp)).await;
-            send_message(
-                state,
-                &AgentMessage::DeviceStatus(build_heartbeat(state).await),
-            )
-            .await;
-        }
-        ControlMessage::TestRun(payload) => {
-            tracing::info!("received test.run");
-            let control_connected = state
-                .control_connected
-                .load(std::sync::atomic::Ordering::Relaxed);
-            let sender_id = state.sender_id.lock().await.clone();
-            let control_url = state.control_url.lock().await.clone();
-            let receiver_url = state.receiver_url.lock().await.clone();
-
-            let cloud_reachable = match &control_url {
-                Some(url) => crate::util::check_tcp_reachable(url, 5).await,
-                None => false,
-            };
-            let receiver_reachable = match &receiver_url {
-                Some(url) => crate::util::check_tcp_reachable(url, 3).await,
-                None => false,
-            };
+async fn config_update(state: &AgentState, payload: ConfigUpdatePayload) -> CommandResult {
+    tracing::info!("received config.update");
+    let pipeline = state.pipeline.lock().await;
+    let mut errors: Vec<String> = Vec::new();
 
-            let resp = TestRunResponsePayload {
-                request_id: payload.request_id,
-                cloud_reachable,
-                cloud_connected: control_connected,
-                receiver_reachable,
-                receiver_url,
-                enrolled: sender_id.is_some(),
-                control_url,
-            };
-            send_message(state, &AgentMessage::TestRunResponse(resp)).await;
-        }
-        ControlMessage::InterfacesScan(payload) => {
-            tracing::info!("received interfaces.scan");
-            let new_ifaces = state.hardware.discover_interfaces().await;
-            let hw = state.hardware.scan().await;
-            let resp = InterfacesScanResponsePayload {
-                request_id: payload.request_id,
-                discovered: new_ifaces,
-                total: hw.interfaces.len(),
-            };
-            send_message(state, &AgentMessage::InterfacesScanResponse(resp)).await;
-            send_message(
-                state,
-                &AgentMessage::DeviceStatus(build_heartbeat(state).await),
-            )
-            .await;
+    // Apply encoder changes
+    if let Some(enc) = &payload.encoder {
+        let mut cmd = serde_json::json!({ "cmd": "set_encoder" });
+        if let Some(bps) = enc.bitrate_kbps {
+            cmd["bitrate_kbps"] = serde_json::json!(bps);
         }
-        ControlMessage::FilesList(payload) => {
-            let req_path = payload.path.unwrap_or_else(|| "/opt/strata".to_string());
-            tracing::debug!(path = %req_path, "received files.list");
-            let (entries, error) = list_directory(&req_path);
-            let resp = FilesListResponsePayload {
-                request_id: payload.request_id,
-                path: req_path,
-                entries,
-                error,
-            };
-            send_message(state, &AgentMessage::FilesListResponse(resp)).await;
-        }
-        ControlMessage::NetworkTool(payload) => {
-            tracing::info!(tool = %payload.tool, "received diagnostics.network");
-            let (output, success) =
-                run_network_tool_impl(&payload.tool, payload.target.as_deref()).await;
-            let resp = NetworkToolResponsePayload {
-                request_id: payload.request_id,
-                tool: payload.tool,
-                output,
-                success,
-            };
-            send_message(state, &AgentMessage::NetworkToolResponse(resp)).await;
+        if let Some(ref tune) = enc.tune {
+            cmd["tune"] = serde_json::json!(tune);
         }
-        ControlMessage::PcapCapture(payload) => {
-            tracing::info!(
-                duration = payload.duration_secs,
-                "received diagnostics.pcap"
-            );
-            let resp = PcapCaptureResponsePayload {
-                request_id: payload.request_id,
-                download_url: String::new(),
-                file_size_bytes: None,
-                duration_secs: payload.duration_secs,
-            };
-            send_message(state, &AgentMessage::PcapCaptureResponse(resp)).await;
-        }
-        ControlMessage::LogsGet(payload) => {
-            tracing::debug!("received logs.get");
-            let service = payload.service.as_deref().unwrap_or("strata-agent");
-            let max_lines = payload.lines.unwrap_or(100).min(500);
-            let lines = collect_logs(service, max_lines).await;
-            let resp = LogsResponsePayload {
-                request_id: payload.request_id,
-                service: service.to_string(),
-                lines,
-            };
-            send_message(state, &AgentMessage::LogsResponse(resp)).await;
+        if let Some(ki) = enc.keyint_max {
+            cmd["keyint_max"] = serde_json::json!(ki);
         }
-        ControlMessage::PowerCommand(payload) => {
-            tracing::info!(action = %payload.action, "received power.command");
-            let (success, error) = match payload.action.as_str() {
-                "restart_agent" => {
-                    // Trigger a graceful shutdown — the process supervisor will restart us
-                    let _ = state.shutdown_tx.send(true);
-                    (true, None)
-                }
-                "reboot" | "shutdown" => {
-                    // Not safe in Docker dev — report success but don't actually execute
-                    (true, None)
-                }
-                other => (false, Some(format!("unknown power action: {other}"))),
-            };
-            let resp = PowerCommandResponsePayload {
-                request_id: payload.request_id,
-                success,
-                error,
-            };
-            send_message(state, &AgentMessage::PowerCommandResponse(resp)).await;
+        if !pipeline.send_command(&cmd) {
+            errors.push("failed to send encoder update".into());
         }
-        ControlMessage::TlsStatus(payload) => {
-            tracing::debug!("received tls.status");
-            let resp = TlsStatusResponsePayload {
-                request_id: payload.request_id,
-                enabled: true,
-                cert_subject: Some("CN=strata-agent".to_string()),
-                cert_issuer: Some("CN=strata-agent".to_string()),
-                expiry: Some("2026-01-01T00:00:00Z".to_string()),
-                self_signed: true,
-            };
-            send_message(state, &AgentMessage::TlsStatusResponse(resp)).await;
+    }
+
+    // Apply scheduler/bonding changes
+    if let Some(sched) = &payload.scheduler {
+        let cmd = serde_json::json!({
+            "cmd": "set_bonding_config",
+            "config": sched,
+        });
+        if !pipeline.send_command(&cmd) {
+            errors.push("failed to send scheduler update".into());
         }
-        ControlMessage::TlsRenew(payload) => {
-            tracing::info!("received tls.renew");
-            let resp = TlsRenewResponsePayload {
-                request_id: payload.request_id,
-                success: true,
-                error: None,
-            };
-            send_message(state, &AgentMessage::TlsRenewResponse(resp)).await;
+    }
+    drop(pipeline);
+
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    let resp = ConfigUpdateResponsePayload {
+        request_id: payload.request_id,
+        success: error.is_none(),
+        error: error.clone(),
+    };
+    send_message(state, &AgentMessage::ConfigUpdateResponse(resp)).await;
+    error.map_or(Ok(()), |e| Err(CommandError::rejected(e)))
+}
+
+async fn source_switch(state: &AgentState, payload: SourceSwitchPayload) -> CommandResult {
+    tracing::info!(
+        mode = %payload.mode,
+        pattern = ?payload.pattern,
+        "received source.switch"
+    );
+    // Reject bad v4l2 devices BEFORE they reach the pipeline —
+    // v4l2src fails asynchronously on a non-capture node (camera
+    // metadata nodes, codec nodes) and takes the whole pipeline
+    // down with it (2026-07-05 field crash on /dev/video1).
+    let result = if payload.mode == "v4l2" {
+        let device = payload.device.as_deref().unwrap_or("/dev/video0");
+        if !crate::hardware::is_capture_device(device) {
+            Err(CommandError::invalid(format!(
+                "{device} is not a video capture device"
+            )))
+        } else {
+            Ok(())
         }
-        ControlMessage::ConfigExport(payload) => {
-            tracing::debug!("received config.export");
-            let receiver_url = state.receiver_url.lock().await.clone();
-            let config = serde_json::json!({
-                "agent_version": env!("CARGO_PKG_VERSION"),
-                "receiver_url": receiver_url,
-            });
-            let resp = ConfigExportResponsePayload {
-                request_id: payload.request_id,
-                config,
-            };
-            send_message(state, &AgentMessage::ConfigExportResponse(resp)).await;
+    } else {
+        Ok(())
+    };
+    let result = match result {
+        Ok(()) => {
+            let pipeline = state.pipeline.lock().await;
+            pipeline
+                .switch_source(
+                    &payload.mode,
+                    payload.device.as_deref(),
+                    payload.uri.as_deref(),
+                    payload.pattern.as_deref(),
+                )
+                .map_err(CommandError::rejected)
         }
-        ControlMessage::ConfigImport(payload) => {
-            tracing::info!("received config.import");
-            // Apply receiver_url if present in the imported config
-            if let Some(url) = payload.config.get("receiver_url").and_then(|v| v.as_str()) {
-                let mut r = state.receiver_url.lock().await;
-                *r = if url.is_empty() {
-                    None
-                } else {
-                    Some(url.to_string())
-                };
+        Err(e) => Err(e),
+    };
+    let resp = SourceSwitchResponsePayload {
+        request_id: payload.request_id,
+        success: result.is_ok(),
+        mode: payload.mode,
+        error: result.as_ref().err().map(|e| e.message.clone()),
+    };
+    send_message(state, &AgentMessage::SourceSwitchResponse(resp)).await;
+    result
+}
+
+async fn source_select(state: &AgentState, payload: SourceSelectPayload) -> CommandResult {
+    tracing::info!(
+        target = %payload.target,
+        transition = ?payload.transition,
+        "received source.select"
+    );
+    let result = state
+        .pipeline
+        .lock()
+        .await
+        .select_source(&payload.target, payload.transition, payload.duration_ms)
+        .map_err(CommandError::rejected);
+    let resp = SourceSelectResponsePayload {
+        request_id: payload.request_id,
+        success: result.is_ok(),
+        target: payload.target,
+        error: result.as_ref().err().map(|e| e.message.clone()),
+    };
+    send_message(state, &AgentMessage::SourceSelectResponse(resp)).await;
+    result
+}
+
+async fn interface_command(state: &AgentState, payload: InterfaceCommandPayload) -> CommandResult {
+    tracing::info!(
+        interface = %payload.interface,
+        action = %payload.action,
+        "received interface.command"
+    );
+    let result = match payload.action.as_str() {
+        "enable" | "disable" => {
+            let enable = payload.action == "enable";
+            if state
+                .hardware
+                .set_interface_enabled(&payload.interface, enable)
+            {
+                Ok(())
+            } else {
+                Err(CommandError::rejected(format!(
+                    "failed to {} interface",
+                    payload.action
+                )))
             }
-            let resp = ConfigImportResponsePayload {
-                request_id: payload.request_id,
-                success: true,
-                error: None,
-            };
-            send_message(state, &AgentMessage::ConfigImportResponse(resp)).await;
-        }
-        ControlMessage::UpdatesCheck(payload) => {
-            tracing::debug!("received updates.check");
-            let resp = UpdatesCheckResponsePayload {
-                request_id: payload.request_id,
-                current_version: env!("CARGO_PKG_VERSION").to_string(),
-                latest_version: Some(env!("CARGO_PKG_VERSION").to_string()),
-                update_available: false,
-                release_notes: None,
-                update_size_bytes: None,
-            };
-            send_message(state, &AgentMessage::UpdatesCheckResponse(resp)).await;
         }
-        ControlMessage::UpdatesInstall(payload) => {
-            tracing::info!("received updates.install");
-            // No on-device OTA path yet — say so instead of pretending.
-            // Device updates run via strata-update.sh (packaging/), manual
-            // or timer-driven; see wiki/Updates-and-Releases.md.
-            let resp = UpdatesInstallResponsePayload {
-                request_id: payload.request_id,
-                success: false,
-                error: Some(
-                    "remote install is not implemented — run strata-update.sh on the device                      (or enable strata-update.timer)"
-                        .into(),
-                ),
-            };
-            send_message(state, &AgentMessage::UpdatesInstallResponse(resp)).await;
-        }
-        ControlMessage::StreamDestinations(payload) => {
-            tracing::info!(
-                count = payload.destination_ids.len(),
-                "received stream.destinations"
-            );
-            let resp = StreamDestinationsResponsePayload {
-                request_id: payload.request_id,
-                success: true,
-                error: None,
-            };
-            send_message(state, &AgentMessage::StreamDestinationsResponse(resp)).await;
+        "set_apn" => set_apn(state, &payload)
+            .await
+            .map_err(CommandError::rejected),
+        // Orders the device's default routes; the route manager picks it
+        // up on its next tick. Bonded links are unaffected.
+        "set_priority" => match payload.priority {
+            Some(p @ 1..=100) => {
+                state.hardware.set_interface_priority(&payload.interface, p);
+                Ok(())
+            }
+            Some(p) => Err(CommandError::invalid(format!(
+                "priority {p} is outside 1–100"
+            ))),
+            None => Err(CommandError::invalid("set_priority needs a priority")),
+        },
+        other => Err(CommandError::invalid(format!("unknown action: {other}"))),
+    };
+    let resp = InterfaceCommandResponsePayload {
+        request_id: payload.request_id.clone(),
+        success: result.is_ok(),
+        interface: payload.interface.clone(),
+        action: payload.action.clone(),
+        error: result.as_ref().err().map(|e| e.message.clone()),
+    };
+    send_message(state, &AgentMessage::InterfaceCommandResponse(resp)).await;
+
+    // Notify the running pipeline to add/remove this link from
+    // the bonding transport (without touching OS connectivity).
+    if result.is_ok() && matches!(payload.action.as_str(), "enable" | "disable") {
+        let enabled = payload.action == "enable";
+        let pipeline = state.pipeline.lock().await;
+        pipeline.toggle_link(&payload.interface, enabled);
+    }
+
+    send_message(
+        state,
+        &AgentMessage::DeviceStatus(build_heartbeat(state).await),
+    )
+    .await;
+    result
+}
+
+async fn config_set(state: &AgentState, payload: ConfigSetPayload) -> CommandResult {
+    tracing::info!(receiver_url = ?payload.receiver_url, "received config.set");
+    {
+        let mut r = state.receiver_url.lock().await;
+        *r = payload.receiver_url.filter(|s| !s.is_empty());
+    }
+    let current = state.receiver_url.lock().await.clone();
+    let resp = ConfigSetResponsePayload {
+        request_id: payload.request_id,
+        success: true,
+        receiver_url: current,
+    };
+    send_message(state, &AgentMessage::ConfigSetResponse(resp)).await;
+    send_message(
+        state,
+        &AgentMessage::DeviceStatus(build_heartbeat(state).await),
+    )
+    .await;
+    Ok(())
+}
+
+async fn test_run(state: &AgentState, payload: TestRunPayload) -> CommandResult {
+    tracing::info!("received test.run");
+    let control_connected = state
+        .control_connected
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let sender_id = state.sender_id.lock().await.clone();
+    let control_url = state.control_url.lock().await.clone();
+    let receiver_url = state.receiver_url.lock().await.clone();
+
+    let cloud_reachable = match &control_url {
+        Some(url) => crate::util::check_tcp_reachable(url, 5).await,
+        None => false,
+    };
+    let receiver_reachable = match &receiver_url {
+        Some(url) => crate::util::check_tcp_reachable(url, 3).await,
+        None => false,
+    };
+
+    let resp = TestRunResponsePayload {
+        request_id: payload.request_id,
+        cloud_reachable,
+        cloud_connected: control_connected,
+        receiver_reachable,
+        receiver_url,
+        enrolled: sender_id.is_some(),
+        control_url,
+    };
+    send_message(state, &AgentMessage::TestRunResponse(resp)).await;
+    Ok(())
+}
+
+async fn interfaces_scan(state: &AgentState, payload: InterfacesScanPayload) -> CommandResult {
+    tracing::info!("received interfaces.scan");
+    let new_ifaces = state.hardware.discover_interfaces().await;
+    let hw = state.hardware.scan().await;
+    let resp = InterfacesScanResponsePayload {
+        request_id: payload.request_id,
+        discovered: new_ifaces,
+        total: hw.interfaces.len(),
+    };
+    send_message(state, &AgentMessage::InterfacesScanResponse(resp)).await;
+    send_message(
+        state,
+        &AgentMessage::DeviceStatus(build_heartbeat(state).await),
+    )
+    .await;
+    Ok(())
+}
+
+async fn files_list(state: &AgentState, payload: FilesListPayload) -> CommandResult {
+    let req_path = payload.path.unwrap_or_else(|| "/opt/strata".to_string());
+    tracing::debug!(path = %req_path, "received files.list");
+    let (entries, error) = list_directory(&req_path);
+    let resp = FilesListResponsePayload {
+        request_id: payload.request_id,
+        path: req_path,
+        entries,
+        error: error.clone(),
+    };
+    send_message(state, &AgentMessage::FilesListResponse(resp)).await;
+    error.map_or(Ok(()), |e| Err(CommandError::rejected(e)))
+}
+
+async fn network_tool(state: &AgentState, payload: NetworkToolPayload) -> CommandResult {
+    tracing::info!(tool = %payload.tool, "received diagnostics.network");
+    let result = run_network_tool_impl(&payload.tool, payload.target.as_deref()).await;
+    // A tool that ran but failed (host unreachable) is a result, not a
+    // command failure — its exit status travels in the response.
+    let (output, success) = match &result {
+        Ok((output, success)) => (output.clone(), *success),
+        Err(e) => (e.message.clone(), false),
+    };
+    let resp = NetworkToolResponsePayload {
+        request_id: payload.request_id,
+        tool: payload.tool,
+        output,
+        success,
+    };
+    send_message(state, &AgentMessage::NetworkToolResponse(resp)).await;
+    result.map(|_| ())
+}
+
+async fn pcap_capture(state: &AgentState, payload: PcapCapturePayload) -> CommandResult {
+    tracing::info!(
+        duration = payload.duration_secs,
+        "received diagnostics.pcap"
+    );
+    let resp = PcapCaptureResponsePayload {
+        request_id: payload.request_id,
+        download_url: String::new(),
+        file_size_bytes: None,
+        duration_secs: payload.duration_secs,
+    };
+    send_message(state, &AgentMessage::PcapCaptureResponse(resp)).await;
+    Err(CommandError::rejected(
+        "packet capture is not implemented on this agent",
+    ))
+}
+
+async fn logs_get(state: &AgentState, payload: LogsRequestPayload) -> CommandResult {
+    tracing::debug!("received logs.get");
+    let service = payload.service.as_deref().unwrap_or("strata-agent");
+    let max_lines = payload.lines.unwrap_or(100).min(500);
+    let lines = crate::diagnostics::collect_logs(service, max_lines).await;
+    let resp = LogsResponsePayload {
+        request_id: payload.request_id,
+        service: service.to_string(),
+        lines,
+    };
+    send_message(state, &AgentMessage::LogsResponse(resp)).await;
+    Ok(())
+}
+
+async fn power_command(state: &AgentState, payload: PowerCommandPayload) -> CommandResult {
+    tracing::info!(action = %payload.action, "received power.command");
+    let result = match payload.action.as_str() {
+        "restart_agent" => {
+            // Trigger a graceful shutdown — the process supervisor will restart us
+            let _ = state.shutdown_tx.send(true);
+            Ok(())
         }
-        ControlMessage::JitterBuffer(payload) => {
-            tracing::info!(mode = %payload.mode, "received stream.jitter_buffer");
-            let resp = JitterBufferResponsePayload {
-                request_id: payload.request_id,
-                success: true,
-                error: None,
-            };
-            send_message(state, &AgentMessage::JitterBufferResponse(resp)).await;
+        "reboot" | "shutdown" => {
+            // Not safe in Docker dev — report success but don't actually execute
+            Ok(())
         }
+        other => Err(CommandError::invalid(format!(
+            "unknown power action: {other}"
+        ))),
+    };
+    let resp = PowerCommandResponsePayload {
+        request_id: payload.request_id,
+        success: result.is_ok(),
+        error: result.as_ref().err().map(|e| e.message.clone()),
+    };
+    send_message(state, &AgentMessage::PowerCommandResponse(resp)).await;
+    result
+}
+
+async fn tls_status(state: &AgentState, payload: TlsStatusPayload) -> CommandResult {
+    tracing::debug!("received tls.status");
+    let info = state.tls.info();
+    let resp = TlsStatusResponsePayload {
+        request_id: payload.request_id,
+        enabled: info.is_some(),
+        cert_subject: info.as_ref().map(|i| i.subject.clone()),
+        cert_issuer: info.as_ref().map(|i| i.issuer.clone()),
+        expiry: info.as_ref().map(|i| i.not_after.to_rfc3339()),
+        self_signed: info.is_some_and(|i| i.self_signed),
+    };
+    send_message(state, &AgentMessage::TlsStatusResponse(resp)).await;
+    Ok(())
+}
+
+/// Certificates come from the control plane (`tls.install`); the agent
+/// has no CA account of its own to renew with.
+async fn tls_renew(state: &AgentState, payload: TlsRenewPayload) -> CommandResult {
+    tracing::info!("received tls.renew");
+    let error = "certificates are issued by the control plane; renew there";
+    let resp = TlsRenewResponsePayload {
+        request_id: payload.request_id,
+        success: false,
+        error: Some(error.into()),
+    };
+    send_message(state, &AgentMessage::TlsRenewResponse(resp)).await;
+    Err(CommandError::rejected(error))
+}
+
+fn tls_install(state: &AgentState, payload: TlsInstallPayload) -> CommandResult {
+    let info = state
+        .tls
+        .install(&payload.cert_pem, &payload.key_pem)
+        .map_err(|e| CommandError::invalid(format!("certificate not installed: {e}")))?;
+    tracing::info!(
+        certificate_id = %payload.certificate_id,
+        subject = %info.subject,
+        not_after = %info.not_after,
+        "portal certificate installed"
+    );
+    Ok(())
+}
+
+async fn config_export(state: &AgentState, payload: ConfigExportPayload) -> CommandResult {
+    tracing::debug!("received config.export");
+    let receiver_url = state.receiver_url.lock().await.clone();
+    let config = serde_json::json!({
+        "agent_version": env!("CARGO_PKG_VERSION"),
+        "receiver_url": receiver_url,
+    });
+    let resp = ConfigExportResponsePayload {
+        request_id: payload.request_id,
+        config,
+    };
+    send_message(state, &AgentMessage::ConfigExportResponse(resp)).await;
+    Ok(())
+}
+
+async fn config_import(state: &AgentState, payload: ConfigImportPayload) -> CommandResult {
+    tracing::info!("received config.import");
+    // Apply receiver_url if present in the imported config
+    if let Some(url) = payload.config.get("receiver_url").and_then(|v| v.as_str()) {
+        let mut r = state.receiver_url.lock().await;
+        *r = if url.is_empty() {
+            None
+        } else {
+            Some(url.to_string())
+        };
+    }
+    let resp = ConfigImportResponsePayload {
+        request_id: payload.request_id,
+        success: true,
+        error: None,
+    };
+    send_message(state, &AgentMessage::ConfigImportResponse(resp)).await;
+    Ok(())
+}
+
+async fn updates_check(state: &AgentState, payload: UpdatesCheckPayload) -> CommandResult {
+    tracing::debug!("received updates.check");
+    let resp = UpdatesCheckResponsePayload {
+        request_id: payload.request_id,
+        current_version: env!("CARGO_PKG_VERSION").to_string(),
+        latest_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        update_available: false,
+        release_notes: None,
+        update_size_bytes: None,
+    };
+    send_message(state, &AgentMessage::UpdatesCheckResponse(resp)).await;
+    Ok(())
+}
+
+async fn updates_install(state: &AgentState, payload: UpdatesInstallPayload) -> CommandResult {
+    tracing::info!("received updates.install");
+    // No on-device OTA path yet — say so instead of pretending.
+    // Device updates run via strata-update.sh (packaging/), manual
+    // or timer-driven; see wiki/Updates-and-Releases.md.
+    let error = "remote install is not implemented — run strata-update.sh on the device \
+                 (or enable strata-update.timer)";
+    let resp = UpdatesInstallResponsePayload {
+        request_id: payload.request_id,
+        success: false,
+        error: Some(error.into()),
+    };
+    send_message(state, &AgentMessage::UpdatesInstallResponse(resp)).await;
+    Err(CommandError::rejected(error))
+}
+
+async fn stream_destinations(
+    state: &AgentState,
+    payload: StreamDestinationsPayload,
+) -> CommandResult {
+    tracing::info!(
+        count = payload.destination_ids.len(),
+        "received stream.destinations"
+    );
+    let resp = StreamDestinationsResponsePayload {
+        request_id: payload.request_id,
+        success: true,
+        error: None,
+    };
+    send_message(state, &AgentMessage::StreamDestinationsResponse(resp)).await;
+    Ok(())
+}
+
+async fn jitter_buffer(state: &AgentState, payload: JitterBufferPayload) -> CommandResult {
+    tracing::info!(mode = %payload.mode, "received stream.jitter_buffer");
+    let resp = JitterBufferResponsePayload {
+        request_id: payload.request_id,
+        success: true,
+        error: None,
+    };
+    send_message(state, &AgentMessage::JitterBufferResponse(resp)).await;
+    Ok(())
+}
+
+async fn maintenance_schedule(
+    state: &AgentState,
+    payload: MaintenanceSchedulePayload,
+) -> CommandResult {
+    tracing::info!(
+        windows = payload.windows.len(),
+        "received maintenance.schedule"
+    );
+    *state.maintenance.write().await = payload.windows;
+    if let Some(w) = state.open_maintenance_window().await {
+        tracing::info!(
+            window_id = %w.id,
+            ends_at = %w.ends_at,
+            allow_ota = w.allow_ota,
+            allow_reboot = w.allow_reboot,
+            "maintenance window open"
+        );
     }
+    Ok(())
+}
+
+fn portal_auth(state: &AgentState, payload: PortalAuthPayload) -> CommandResult {
+    if state.config.get().portal_pin_hash == payload.pin_hash {
+        return Ok(());
+    }
+    let enabled = payload.pin_hash.is_some();
+    let persisted = state
+        .config
+        .update(|c| c.portal_pin_hash = payload.pin_hash)
+        .map_err(|e| CommandError {
+            code: ErrorCode::Internal,
+            message: format!("failed to persist portal auth: {e}"),
+        })
+        .map(|_| ());
+    // Sessions opened with the old PIN (or none) end here.
+    state.portal_auth.revoke_all();
+    tracing::info!(enabled, "portal auth updated");
+    persisted
 }
 
 /// Allowed root directories for the files.list command.
@@ -783,12 +1082,16 @@ fn list_directory(path: &str) -> (Vec<FileEntry>, Option<String>) {
 }
 
 /// Run a network diagnostic tool (ping, traceroute, speedtest).
-async fn run_network_tool_impl(tool: &str, target: Option<&str>) -> (String, bool) {
+/// Returns the tool's combined output and whether it exited cleanly.
+async fn run_network_tool_impl(
+    tool: &str,
+    target: Option<&str>,
+) -> Result<(String, bool), CommandError> {
     let target = target.unwrap_or("8.8.8.8");
     let (cmd, args): (&str, Vec<&str>) = match tool {
         "ping" => ("ping", vec!["-c", "4", "-W", "3", target]),
         "traceroute" => ("traceroute", vec!["-m", "15", "-w", "2", target]),
-        _ => return (format!("unknown tool: {tool}"), false),
+        _ => return Err(CommandError::invalid(format!("unknown tool: {tool}"))),
     };
     match tokio::process::Command::new(cmd).args(&args).output().await {
         Ok(output) => {
@@ -799,54 +1102,79 @@ async fn run_network_tool_impl(tool: &str, target: Option<&str>) -> (String, boo
             } else {
                 format!("{stdout}\n{stderr}")
             };
-            (combined, output.status.success())
+            Ok((combined, output.status.success()))
         }
-        Err(e) => (format!("{cmd} not available: {e}"), false),
+        Err(e) => Err(CommandError::rejected(format!("{cmd} not available: {e}"))),
     }
 }
 
-/// Collect recent log lines from the agent/pipeline.
-async fn collect_logs(service: &str, max_lines: u32) -> Vec<LogLineEntry> {
-    // Try journalctl first, fall back to /var/log
-    let result = tokio::process::Command::new("journalctl")
-        .args([
-            "-u",
-            service,
-            "--no-pager",
-            "-n",
-            &max_lines.to_string(),
-            "-o",
-            "short-iso",
-        ])
-        .output()
-        .await;
-
-    match result {
-        Ok(output) if output.status.success() => {
-            let text = String::from_utf8_lossy(&output.stdout);
-            text.lines()
-                .filter(|l| !l.is_empty())
-                .map(|line| {
-                    // Parse journalctl short-iso format: "2025-01-01T00:00:00+0000 host service[pid]: message"
-                    let (timestamp, rest) = line.split_once(' ').unwrap_or(("", line));
-                    let message = rest.split_once(": ").map(|(_, msg)| msg).unwrap_or(rest);
-                    LogLineEntry {
-                        timestamp: Some(timestamp.to_string()),
-                        level: None,
-                        message: message.to_string(),
-                    }
-                })
-                .collect()
-        }
-        _ => {
-            // Fallback: read from /var/log/strata/ or show agent's own stderr
-            vec![LogLineEntry {
-                timestamp: None,
-                level: Some("info".to_string()),
-                message: format!(
-                    "Log collection via journalctl not available for service '{service}'"
-                ),
-            }]
+/// `interface.command` `set_apn`: unlock the SIM if it is waiting for the
+/// given PIN, then set the APN, on the interface's HiLink modem. The
+/// roaming toggle has no HiLink equivalent here and is ignored.
+async fn set_apn(state: &AgentState, payload: &InterfaceCommandPayload) -> Result<(), String> {
+    let gateway = state
+        .hardware
+        .gateway_of(&payload.interface)
+        .await
+        .ok_or_else(|| format!("{} has no gateway; is it a modem?", payload.interface))?;
+    if let Some(pin) = payload.sim_pin.as_deref() {
+        let sim = crate::hilink::sim_info(&gateway)
+            .await
+            .ok_or("no HiLink modem answered on this interface")?;
+        if sim.state == crate::hilink::SimState::PinRequired {
+            crate::hilink::unlock_sim(&gateway, pin, None).await?;
         }
     }
+    match payload.apn.as_deref() {
+        Some(apn) => crate::hilink::set_apn(&gateway, apn).await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_ack_correlates_with_the_command_envelope() {
+        let envelope = Envelope::from_message(&ControlMessage::PowerCommand(PowerCommandPayload {
+            request_id: "req_7".into(),
+            action: "hibernate".into(),
+        }))
+        .unwrap();
+
+        let ack = command_ack(
+            &envelope,
+            Err(CommandError::invalid("unknown power action: hibernate")),
+        );
+        assert_eq!(ack.ref_id, envelope.id);
+        assert_eq!(ack.msg_type, "power.command");
+        assert_eq!(ack.request_id.as_deref(), Some("req_7"));
+        assert!(!ack.success);
+        assert_eq!(ack.code, Some(ErrorCode::InvalidInput));
+
+        let ack = command_ack(&envelope, Ok(()));
+        assert!(ack.success);
+        assert_eq!(ack.code, None);
+        assert_eq!(ack.error, None);
+    }
+
+    #[test]
+    fn fire_and_forget_commands_ack_without_request_id() {
+        let envelope = Envelope::from_message(&ControlMessage::StreamStop(StreamStopPayload {
+            stream_id: "str_1".into(),
+            reason: "user_stop".into(),
+        }))
+        .unwrap();
+        let ack = command_ack(&envelope, Ok(()));
+        assert_eq!(ack.msg_type, "stream.stop");
+        assert_eq!(ack.request_id, None);
+    }
+
+    #[test]
+    fn agent_leg_matches_the_golden_vectors() {
+        use strata_common::testvectors::{self, Leg};
+        testvectors::check(Leg::Agent).unwrap();
+        testvectors::check(Leg::Control).unwrap();
+    }
 }
@@ -0,0 +1,199 @@
+//! Diagnostics — recent logs and support bundles.
+//!
+//! Backs the control plane's `logs.get` and the portal's log viewer and
+//! "Download diagnostics" button. A support bundle is a tarball a field
+//! tech can attach to a ticket without shell access to the unit.
+
+use std::path::Path;
+
+use strata_protocol::LogLineEntry;
+
+use crate::AgentState;
+
+/// systemd unit the agent (and the pipeline it spawns) logs under.
+pub const AGENT_UNIT: &str = "strata-sender";
+/// Journal lines included in a support bundle.
+const BUNDLE_LOG_LINES: u32 = 5000;
+
+/// Collect recent log lines from the agent/pipeline.
+pub async fn collect_logs(service: &str, max_lines: u32) -> Vec<LogLineEntry> {
+    // Try journalctl first, fall back to /var/log
+    let result = tokio::process::Command::new("journalctl")
+        .args([
+            "-u",
+            service,
+            "--no-pager",
+            "-n",
+            &max_lines.to_string(),
+            "-o",
+            "short-iso",
+        ])
+        .output()
+        .await;
+
+    match result {
+        Ok(output) if output.status.success() => {
+            let text = String::from_utf8_lossy(&output.stdout);
+            text.lines()
+                .filter(|l| !l.is_empty())
+                .map(|line| {
+                    // Parse journalctl short-iso format: "2025-01-01T00:00:00+0000 host service[pid]: message"
+                    let (timestamp, rest) = line.split_once(' ').unwrap_or(("", line));
+                    let message = rest.split_once(": ").map(|(_, msg)| msg).unwrap_or(rest);
+                    LogLineEntry {
+                        timestamp: Some(timestamp.to_string()),
+                        level: None,
+                        message: message.to_string(),
+                    }
+                })
+                .collect()
+        }
+        _ => {
+            // Fallback: read from /var/log/strata/ or show agent's own stderr
+            vec![LogLineEntry {
+                timestamp: None,
+                level: Some("info".to_string()),
+                message: format!(
+                    "Log collection via journalctl not available for service '{service}'"
+                ),
+            }]
+        }
+    }
+}
+
+/// Build a support bundle (`.tar.gz`): agent and kernel logs, redacted
+/// config, a hardware snapshot, current stream/link stats, component
+/// versions and the network state as the OS sees it.
+pub async fn support_bundle(state: &AgentState) -> anyhow::Result<Vec<u8>> {
+    let dir = std::env::temp_dir().join(format!(
+        "strata-diag-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    ));
+    tokio::fs::create_dir_all(&dir).await?;
+    let result = async {
+        write_bundle_files(state, &dir).await?;
+        let output = tokio::process::Command::new("tar")
+            .arg("-czf")
+            .arg("-")
+            .arg("-C")
+            .arg(&dir)
+            .arg(".")
+            .output()
+            .await?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "tar failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+    .await;
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    result
+}
+
+async fn write_bundle_files(state: &AgentState, dir: &Path) -> anyhow::Result<()> {
+    let json = |v: &serde_json::Value| serde_json::to_string_pretty(v).unwrap_or_default();
+
+    tokio::fs::write(
+        dir.join("agent.log"),
+        command_text(
+            "journalctl",
+            &[
+                "-u",
+                AGENT_UNIT,
+                "--no-pager",
+                "-o",
+                "short-iso",
+                "-n",
+                &BUNDLE_LOG_LINES.to_string(),
+            ],
+        )
+        .await,
+    )
+    .await?;
+    tokio::fs::write(
+        dir.join("kernel.log"),
+        command_text(
+            "journalctl",
+            &["-k", "--no-pager", "-o", "short-iso", "-n", "1000"],
+        )
+        .await,
+    )
+    .await?;
+
+    // Secrets stay on the unit: the PIN hash is reduced to whether one is
+    // set, and the identity keypair and enrollment token are left out.
+    let mut config = serde_json::to_value(state.config.get())?;
+    if let Some(pin) = config.get_mut("portal_pin_hash") {
+        *pin = serde_json::Value::Bool(!pin.is_null());
+    }
+    let config = serde_json::json!({
+        "agent": config,
+        "sender_id": state.sender_id.lock().await.clone(),
+        "control_url": state.control_url.lock().await.clone(),
+        "receiver_url": state.receiver_url.lock().await.clone(),
+    });
+    tokio::fs::write(dir.join("config.json"), json(&config)).await?;
+
+    let hardware = serde_json::to_value(state.hardware.scan().await)?;
+    tokio::fs::write(dir.join("hardware.json"), json(&hardware)).await?;
+
+    let (streaming, stream_id) = {
+        let mut pipeline = state.pipeline.lock().await;
+        (
+            pipeline.is_running(),
+            pipeline.stream_id().map(str::to_string),
+        )
+    };
+    let status = serde_json::json!({
+        "collected_at": chrono::Utc::now(),
+        "cloud_connected": state
+            .control_connected
+            .load(std::sync::atomic::Ordering::Relaxed),
+        "streaming": streaming,
+        "stream_id": stream_id,
+        "links": *state.latest_link_stats.read().await,
+        "maintenance": *state.maintenance.read().await,
+        "pending_ip_change": *state.pending_ip_change.lock().await,
+        "versions": crate::local_update::versions().await,
+    });
+    tokio::fs::write(dir.join("status.json"), json(&status)).await?;
+
+    let mut network = String::new();
+    for (program, args) in [
+        ("ip", &["addr"][..]),
+        ("ip", &["route"][..]),
+        ("ip", &["rule"][..]),
+        ("nmcli", &["device", "status"][..]),
+        ("mmcli", &["-L"][..]),
+    ] {
+        network.push_str(&format!("$ {program} {}\n", args.join(" ")));
+        network.push_str(&command_text(program, args).await);
+        network.push('\n');
+    }
+    tokio::fs::write(dir.join("network.txt"), network).await?;
+    Ok(())
+}
+
+/// Output of a diagnostic command, or why there is none. Never fails — a
+/// missing tool shouldn't sink the whole bundle.
+async fn command_text(program: &str, args: &[&str]) -> String {
+    let run = tokio::process::Command::new(program)
+        .args(args)
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(std::time::Duration::from_secs(10), run).await {
+        Ok(Ok(output)) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            if !output.status.success() {
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+            }
+            text
+        }
+        Ok(Err(e)) => format!("{program} not available: {e}\n"),
+        Err(_) => format!("{program} timed out\n"),
+    }
+}
@@ -29,6 +29,31 @@ fn interface_state_file() -> String {
         .unwrap_or_else(|_| "/var/lib/strata/interface-admin.json".into())
 }
 
+/// Where operator-set interface priorities persist across daemon restarts.
+fn interface_priority_file() -> String {
+    std::env::var("STRATA_INTERFACE_PRIORITY_FILE")
+        .unwrap_or_else(|_| "/var/lib/strata/interface-priority.json".into())
+}
+
+/// Priority of an interface the operator never ranked (Primary).
+const DEFAULT_PRIORITY: u32 = 1;
+
+fn load_map<V: serde::de::DeserializeOwned>(path: &str) -> HashMap<String, V> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn persist_map<V: Serialize>(path: &str, map: &HashMap<String, V>, what: &str) {
+    if let Err(e) = serde_json::to_string_pretty(map)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(path, json))
+    {
+        tracing::warn!(error = %e, path = %path, "failed to persist {what}");
+    }
+}
+
 /// How long a HiLink probe result (success or failure) stays fresh before
 /// the next heartbeat scan re-probes the gateway.
 const MODEM_PROBE_TTL: Duration = Duration::from_secs(15);
@@ -39,23 +64,47 @@ pub struct HardwareScanner {
     /// persisted to `interface_state_file()` so operator toggles survive
     /// daemon restarts.
     interface_enabled: std::sync::Mutex<HashMap<String, bool>>,
+    /// Operator-set priority per interface name (1 = Primary … 100 =
+    /// Standby), persisted to `interface_priority_file()`. Orders the
+    /// device's default routes (see `routing`).
+    interface_priority: std::sync::Mutex<HashMap<String, u32>>,
     /// Per-gateway HiLink probe cache — `None` marks a gateway that didn't
     /// answer the HiLink API so we don't hammer it every heartbeat.
     modem_cache: tokio::sync::Mutex<HashMap<String, (Instant, Option<crate::hilink::ModemInfo>)>>,
+    /// Stand-in links reported instead of the OS's interfaces
+    /// (`--simulate-link`); empty on real devices.
+    simulated: Vec<NetworkInterface>,
 }
 
 impl HardwareScanner {
     pub fn new() -> Self {
-        let map = std::fs::read_to_string(interface_state_file())
-            .ok()
-            .and_then(|s| serde_json::from_str::<HashMap<String, bool>>(&s).ok())
-            .unwrap_or_default();
+        let map: HashMap<String, bool> = load_map(&interface_state_file());
         if !map.is_empty() {
             tracing::info!(count = map.len(), "loaded persisted interface admin state");
         }
+        let priorities: HashMap<String, u32> = load_map(&interface_priority_file());
         Self {
             interface_enabled: std::sync::Mutex::new(map),
+            interface_priority: std::sync::Mutex::new(priorities),
             modem_cache: tokio::sync::Mutex::new(HashMap::new()),
+            simulated: Vec::new(),
+        }
+    }
+
+    /// A scanner that reports `interfaces` in place of the OS's.
+    #[cfg(feature = "simulate")]
+    pub fn simulated(interfaces: Vec<NetworkInterface>) -> Self {
+        Self {
+            simulated: interfaces,
+            ..Self::new()
+        }
+    }
+
+    fn os_interfaces(&self) -> Vec<NetworkInterface> {
+        if self.simulated.is_empty() {
+            scan_network_interfaces()
+        } else {
+            self.simulated.clone()
         }
     }
 
@@ -66,7 +115,7 @@ impl HardwareScanner {
 
     /// Real hardware scan — reads from system interfaces.
     async fn scan_real(&self) -> HardwareScan {
-        let mut interfaces = scan_network_interfaces();
+        let mut interfaces = self.network_interfaces();
         // Apply admin enabled state
         {
             let enabled_map = self.interface_enabled.lock().unwrap();
@@ -108,6 +157,17 @@ impl HardwareScanner {
         }
     }
 
+    /// Default-route gateway of interface `name` — where a HiLink modem's
+    /// API answers.
+    pub async fn gateway_of(&self, name: &str) -> Option<String> {
+        self.scan()
+            .await
+            .interfaces
+            .into_iter()
+            .find(|i| i.name == name)?
+            .gateway
+    }
+
     async fn probe_modem(&self, gateway: &str) -> Option<crate::hilink::ModemInfo> {
         let mut cache = self.modem_cache.lock().await;
         if let Some((at, info)) = cache.get(gateway)
@@ -134,22 +194,39 @@ impl HardwareScanner {
             map.insert(name.to_string(), enabled);
             map.clone()
         };
-        let path = interface_state_file();
-        if let Err(e) = serde_json::to_string_pretty(&snapshot)
-            .map_err(std::io::Error::other)
-            .and_then(|json| std::fs::write(&path, json))
-        {
-            tracing::warn!(error = %e, path = %path, "failed to persist interface admin state");
-        }
+        persist_map(&interface_state_file(), &snapshot, "interface admin state");
         true
     }
 
+    /// Set an interface's priority (1 = highest, 100 = lowest), persisted
+    /// to disk. Takes effect on the next default-route reconcile.
+    pub fn set_interface_priority(&self, name: &str, priority: u32) {
+        let snapshot = {
+            let mut map = self.interface_priority.lock().unwrap();
+            map.insert(name.to_string(), priority);
+            map.clone()
+        };
+        persist_map(&interface_priority_file(), &snapshot, "interface priority");
+    }
+
+    /// OS interfaces with their operator priority applied — no modem
+    /// probing, so cheap enough for the route manager's tick.
+    pub fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        let mut interfaces = self.os_interfaces();
+        let priorities = self.interface_priority.lock().unwrap();
+        for iface in &mut interfaces {
+            iface.priority = *priorities.get(&iface.name).unwrap_or(&DEFAULT_PRIORITY);
+        }
+        interfaces
+    }
+
     /// Interfaces eligible to carry bonded links for the NEXT stream start:
     /// admin-enabled, OS-connected, and holding a default route. Sorted by
     /// name for deterministic link ordering.
     pub fn eligible_interfaces(&self) -> Vec<String> {
         let enabled_map = self.interface_enabled.lock().unwrap();
-        let mut names: Vec<String> = scan_network_interfaces()
+        let mut names: Vec<String> = self
+            .os_interfaces()
             .into_iter()
             .filter(|i| {
                 i.state == InterfaceState::Connected
@@ -267,7 +344,7 @@ pub(crate) fn scan_network_interfaces() -> Vec<NetworkInterface> {
             cell_id: None,
             data_cap_mb: None,
             data_used_mb: None,
-            priority: 1,
+            priority: DEFAULT_PRIORITY, // overridden by network_interfaces()
             apn: None,
             sim_pin: None,
             roaming: false,
@@ -8,9 +8,14 @@
 //! Everything here is strictly best-effort with short timeouts: a gateway
 //! that isn't a HiLink modem just yields `None` and the caller caches the
 //! failure so heartbeat scans don't hammer it.
+//!
+//! SIM management (status, PIN/PUK unlock, APN) goes through the same API;
+//! writes are POSTs that additionally need the session's
+//! `__RequestVerificationToken`.
 
 use std::time::Duration;
 
+use serde::Serialize;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
@@ -26,6 +31,8 @@ pub struct ModemInfo {
 }
 
 const HTTP_TIMEOUT: Duration = Duration::from_millis(1200);
+/// PIN checks and profile saves take the modem noticeably longer.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Probe a gateway for HiLink modem status. Returns `None` if the gateway
 /// doesn't speak the HiLink API (or is too slow).
@@ -61,6 +68,192 @@ pub async fn probe(gateway: &str) -> Option<ModemInfo> {
     Some(info)
 }
 
+/// SIM lock state as reported by `/api/pin/status` (`SimState`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimState {
+    Ready,
+    PinRequired,
+    PukRequired,
+    /// No SIM, a dead SIM, or a code this firmware family doesn't document.
+    Unavailable,
+}
+
+/// SIM details and lock status of a HiLink modem.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimInfo {
+    pub state: SimState,
+    pub iccid: Option<String>,
+    pub imsi: Option<String>,
+    pub operator: Option<String>,
+    pub pin_attempts_left: Option<u32>,
+    pub puk_attempts_left: Option<u32>,
+    /// APN of the modem's active dial-up profile.
+    pub apn: Option<String>,
+}
+
+/// Read SIM details from the modem at `gateway`. `None` if it isn't a
+/// HiLink modem.
+pub async fn sim_info(gateway: &str) -> Option<SimInfo> {
+    let session = Session::open(gateway).await?;
+    let status = http_get(gateway, "/api/pin/status", Some(&session.cookie)).await?;
+    let (state, pin_attempts_left, puk_attempts_left) = parse_pin_status(&status);
+    let device = http_get(gateway, "/api/device/information", Some(&session.cookie)).await;
+    let plmn = http_get(gateway, "/api/net/current-plmn", Some(&session.cookie)).await;
+    let profiles = http_get(gateway, "/api/dialup/profiles", Some(&session.cookie)).await;
+
+    let non_empty = |v: Option<String>| v.filter(|s| !s.is_empty());
+    Some(SimInfo {
+        state,
+        iccid: non_empty(device.as_deref().and_then(|x| extract_tag(x, "Iccid"))),
+        imsi: non_empty(device.as_deref().and_then(|x| extract_tag(x, "Imsi"))),
+        operator: non_empty(
+            plmn.as_deref()
+                .and_then(|x| extract_tag(x, "FullName").or_else(|| extract_tag(x, "ShortName"))),
+        ),
+        pin_attempts_left,
+        puk_attempts_left,
+        apn: non_empty(profiles.as_deref().and_then(current_apn)),
+    })
+}
+
+/// Unlock the SIM with its PIN, or — when it is PUK-blocked — with the PUK
+/// and a new PIN.
+pub async fn unlock_sim(gateway: &str, pin: &str, puk: Option<&str>) -> Result<(), String> {
+    if !is_valid_pin(pin) || puk.is_some_and(|p| !is_valid_pin(p)) {
+        return Err("PIN and PUK must be 4–8 digits".into());
+    }
+    let session = Session::open(gateway)
+        .await
+        .ok_or("no HiLink modem answered on this interface")?;
+    // OperateType 0 = verify PIN, 4 = verify PUK and set a new PIN.
+    let body = match puk {
+        None => format!(
+            "<request><OperateType>0</OperateType><CurrentPin>{pin}</CurrentPin>\
+             <NewPin></NewPin><PukCode></PukCode></request>"
+        ),
+        Some(puk) => format!(
+            "<request><OperateType>4</OperateType><CurrentPin></CurrentPin>\
+             <NewPin>{pin}</NewPin><PukCode>{puk}</PukCode></request>"
+        ),
+    };
+    let reply = session.post("/api/pin/operate", &body).await?;
+    operate_result(&reply)
+}
+
+/// Point the modem's active dial-up profile at `apn`.
+pub async fn set_apn(gateway: &str, apn: &str) -> Result<(), String> {
+    if !is_valid_apn(apn) {
+        return Err(format!("invalid APN {apn:?}"));
+    }
+    let session = Session::open(gateway)
+        .await
+        .ok_or("no HiLink modem answered on this interface")?;
+    let profiles = http_get(gateway, "/api/dialup/profiles", Some(&session.cookie))
+        .await
+        .ok_or("modem did not return its dial-up profiles")?;
+    let body = profile_update(&profiles, apn)
+        .ok_or("modem has no editable dial-up profile; set the APN on the modem's own page")?;
+    let reply = session.post("/api/dialup/profiles", &body).await?;
+    operate_result(&reply)
+}
+
+/// Valid SIM PIN: 4–8 digits (PUKs are 8).
+fn is_valid_pin(pin: &str) -> bool {
+    (4..=8).contains(&pin.len()) && pin.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Valid APN: 3GPP labels (letters, digits, hyphens) joined by dots.
+fn is_valid_apn(apn: &str) -> bool {
+    !apn.is_empty()
+        && apn.len() <= 63
+        && apn
+            .split('.')
+            .all(|l| !l.is_empty() && l.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'))
+}
+
+/// `(state, PIN attempts left, PUK attempts left)` from `/api/pin/status`.
+fn parse_pin_status(xml: &str) -> (SimState, Option<u32>, Option<u32>) {
+    let state = match extract_tag(xml, "SimState").as_deref() {
+        Some("255") => SimState::Ready,
+        Some("260") => SimState::PinRequired,
+        Some("261") => SimState::PukRequired,
+        _ => SimState::Unavailable,
+    };
+    let times = |tag| extract_tag(xml, tag).and_then(|v| v.parse().ok());
+    (state, times("SimPinTimes"), times("SimPukTimes"))
+}
+
+/// Blocks of `<tag>…</tag>`, in order.
+fn extract_blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut blocks = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let inner = &rest[start + open.len()..];
+        let Some(end) = inner.find(&close) else { break };
+        blocks.push(&inner[..end]);
+        rest = &inner[end + close.len()..];
+    }
+    blocks
+}
+
+/// The active profile from `/api/dialup/profiles`.
+fn current_profile(xml: &str) -> Option<&str> {
+    let current = extract_tag(xml, "CurrentProfile")?;
+    extract_blocks(xml, "Profile")
+        .into_iter()
+        .find(|p| extract_tag(p, "Index").as_deref() == Some(current.as_str()))
+}
+
+fn current_apn(xml: &str) -> Option<String> {
+    extract_tag(current_profile(xml)?, "ApnName")
+}
+
+/// Request body re-saving the active profile with a new APN, every other
+/// field kept. `None` if there is no active profile or the operator
+/// marked it read-only.
+fn profile_update(xml: &str, apn: &str) -> Option<String> {
+    let profile = current_profile(xml)?;
+    if extract_tag(profile, "ReadOnly").is_some_and(|r| r != "0") {
+        return None;
+    }
+    let index = extract_tag(profile, "Index")?;
+    let profile = replace_tag(profile, "ApnName", apn);
+    let profile = replace_tag(&profile, "ApnIsStatic", "1");
+    // Modify 2 = edit an existing profile.
+    Some(format!(
+        "<request><Delete>0</Delete><SetDefault>{index}</SetDefault><Modify>2</Modify>\
+         <Profile>{profile}</Profile></request>"
+    ))
+}
+
+/// `xml` with the text of the first `<tag>` replaced (appended if absent).
+fn replace_tag(xml: &str, tag: &str, value: &str) -> String {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    if let Some(start) = xml.find(&open).map(|i| i + open.len())
+        && let Some(end) = xml[start..].find(&close).map(|i| i + start)
+    {
+        return format!("{}{value}{}", &xml[..start], &xml[end..]);
+    }
+    format!("{xml}{open}{value}{close}")
+}
+
+/// HiLink write replies are `<response>OK</response>` or an `<error>`.
+fn operate_result(xml: &str) -> Result<(), String> {
+    if extract_tag(xml, "response").as_deref() == Some("OK") {
+        return Ok(());
+    }
+    match extract_tag(xml, "code").as_deref() {
+        Some("103002") => Err("wrong PIN".into()),
+        Some("103003") => Err("wrong PUK".into()),
+        Some(code) => Err(format!("modem refused the request (error {code})")),
+        None => Err("unexpected reply from the modem".into()),
+    }
+}
+
 /// Map HiLink CurrentNetworkType(Ex) codes to a human label.
 /// Only the codes seen on LTE-era sticks; unknown codes yield `None`
 /// rather than a wrong label.
@@ -107,6 +300,47 @@ async fn http_get(host: &str, path: &str, cookie: Option<&str>) -> Option<String
     tokio::time::timeout(HTTP_TIMEOUT, fut).await.ok().flatten()
 }
 
+/// A HiLink web session: cookie plus the CSRF token writes must carry.
+struct Session {
+    gateway: String,
+    cookie: String,
+    token: String,
+}
+
+impl Session {
+    async fn open(gateway: &str) -> Option<Self> {
+        let xml = http_get(gateway, "/api/webserver/SesTokInfo", None).await?;
+        Some(Self {
+            gateway: gateway.to_string(),
+            cookie: extract_tag(&xml, "SesInfo")?,
+            token: extract_tag(&xml, "TokInfo")?,
+        })
+    }
+
+    async fn post(&self, path: &str, body: &str) -> Result<String, String> {
+        let fut = async {
+            let host = self.gateway.as_str();
+            let mut stream = TcpStream::connect((host, 80)).await.ok()?;
+            let req = format!(
+                "POST {path} HTTP/1.1\r\nHost: {host}\r\nCookie: {}\r\n\
+                 __RequestVerificationToken: {}\r\n\
+                 Content-Type: application/x-www-form-urlencoded; charset=UTF-8\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                self.cookie,
+                self.token,
+                body.len()
+            );
+            stream.write_all(req.as_bytes()).await.ok()?;
+            read_http_body(&mut stream).await
+        };
+        tokio::time::timeout(WRITE_TIMEOUT, fut)
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| "modem did not answer".to_string())
+    }
+}
+
 /// Read an HTTP response's body off an already-connected stream, using the
 /// `Content-Length` header rather than EOF to know when the body ends.
 async fn read_http_body(stream: &mut TcpStream) -> Option<String> {
@@ -172,6 +406,60 @@ mod tests {
         assert_eq!(network_type_name("999"), None);
     }
 
+    #[test]
+    fn parses_pin_status() {
+        let xml = "<response><SimState>260</SimState><PinOptState>258</PinOptState>\
+                   <SimPinTimes>2</SimPinTimes><SimPukTimes>10</SimPukTimes></response>";
+        assert_eq!(
+            parse_pin_status(xml),
+            (SimState::PinRequired, Some(2), Some(10))
+        );
+        let xml = "<response><SimState>255</SimState></response>";
+        assert_eq!(parse_pin_status(xml).0, SimState::Ready);
+        assert_eq!(parse_pin_status("").0, SimState::Unavailable);
+    }
+
+    const PROFILES: &str = "<response><CurrentProfile>2</CurrentProfile><Profiles>\
+        <Profile><Index>1</Index><Name>Operator</Name><ApnName>op.default</ApnName><ReadOnly>0</ReadOnly></Profile>\
+        <Profile><Index>2</Index><Name>Venue</Name><ApnIsStatic>0</ApnIsStatic><ApnName>internet</ApnName>\
+        <Username></Username><ReadOnly>0</ReadOnly></Profile></Profiles></response>";
+
+    #[test]
+    fn reads_and_rewrites_the_active_profile_apn() {
+        assert_eq!(current_apn(PROFILES).as_deref(), Some("internet"));
+
+        let body = profile_update(PROFILES, "broadcast.apn").unwrap();
+        assert!(body.contains("<SetDefault>2</SetDefault><Modify>2</Modify>"));
+        assert!(body.contains("<Name>Venue</Name>"));
+        assert!(body.contains("<ApnName>broadcast.apn</ApnName>"));
+        assert!(body.contains("<ApnIsStatic>1</ApnIsStatic>"));
+        assert!(!body.contains("op.default"));
+
+        let locked = PROFILES.replace(
+            "<Username></Username><ReadOnly>0</ReadOnly>",
+            "<ReadOnly>1</ReadOnly>",
+        );
+        assert!(profile_update(&locked, "x").is_none());
+    }
+
+    #[test]
+    fn operate_replies() {
+        assert!(operate_result("<response>OK</response>").is_ok());
+        assert_eq!(
+            operate_result("<error><code>103002</code><message></message></error>"),
+            Err("wrong PIN".to_string())
+        );
+        assert!(operate_result("garbage").is_err());
+    }
+
+    #[test]
+    fn validates_pins_and_apns() {
+        assert!(is_valid_pin("1234") && is_valid_pin("12345678"));
+        assert!(!is_valid_pin("123") && !is_valid_pin("12a4"));
+        assert!(is_valid_apn("internet") && is_valid_apn("mobile.o2.co.uk"));
+        assert!(!is_valid_apn("") && !is_valid_apn("a..b") && !is_valid_apn("<x>"));
+    }
+
     #[tokio::test]
     async fn probe_of_non_modem_returns_none() {
         // Nothing listens on this TEST-NET address; must time out to None.
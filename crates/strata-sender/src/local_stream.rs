@@ -0,0 +1,230 @@
+//! Portal-initiated streams — starting the pipeline without the cloud.
+//!
+//! At venues with no path to the control plane the field tech starts the
+//! stream from the portal instead. The agent builds the same
+//! [`StreamStartPayload`] the control plane would, pointed at the
+//! configured receiver, with bitrate defaults from the shared profiles.
+
+use serde::Deserialize;
+use strata_common::validation::{self, Validate};
+use strata_protocol::profiles::lookup_profile;
+use strata_protocol::{EncoderConfig, OverlayConfig, SourceConfig, StreamStartPayload};
+
+/// Spacing between an unmanaged receiver's link ports
+/// (`strata-receiver --link-ports 5000,5002,5004,...`).
+const LINK_PORT_STEP: u16 = 2;
+
+/// Body of `POST /api/stream/start`.
+#[derive(Debug, Deserialize)]
+pub struct LocalStreamRequest {
+    pub source: SourceConfig,
+    /// Encoder target; `None` uses the profile default for the source.
+    #[serde(default)]
+    pub bitrate_kbps: Option<u32>,
+    /// "h264" or "h265" (default).
+    #[serde(default)]
+    pub codec: Option<String>,
+    /// Logo / caption / clock burned into the picture.
+    #[serde(default)]
+    pub overlay: Option<OverlayConfig>,
+}
+
+impl LocalStreamRequest {
+    /// Reject requests the pipeline would only fail on later: the checks
+    /// the control plane applies to its streams, plus that a capture
+    /// device really exists on this box.
+    pub fn validate(&self) -> Result<(), String> {
+        self.source.validate().map_err(|e| e.message)?;
+        if let Some(device) = self.source.device.as_deref()
+            && self.source.mode == "v4l2"
+            && !crate::hardware::is_capture_device(device)
+        {
+            return Err(format!("{device} is not a video capture device"));
+        }
+        let encoder = self.encoder();
+        encoder.validate().map_err(|e| e.message)?;
+        validation::validate_encoder_source(&self.source, &encoder).map_err(|e| e.message)
+    }
+
+    /// The encoder settings the stream will run with: the request's, with
+    /// the profile defaults for its source filled in.
+    fn encoder(&self) -> EncoderConfig {
+        let codec = self.codec.clone().unwrap_or_else(|| "h265".into());
+        let profile = lookup_profile(
+            self.source.resolution.as_deref(),
+            self.source.framerate,
+            Some(&codec),
+        );
+        let bitrate_kbps = self.bitrate_kbps.unwrap_or(profile.default_kbps);
+        EncoderConfig {
+            bitrate_kbps,
+            tune: Some("zerolatency".into()),
+            keyint_max: Some(60),
+            codec: Some(codec),
+            min_bitrate_kbps: Some(profile.min_kbps.min(bitrate_kbps)),
+            max_bitrate_kbps: Some(profile.max_kbps.max(bitrate_kbps)),
+            ladder: Vec::new(),
+            overlay: self.overlay.clone(),
+        }
+    }
+
+    /// Build the start payload for a stream to `receiver_url` over
+    /// `links` bonded links.
+    pub fn into_payload(
+        self,
+        stream_id: String,
+        receiver_url: &str,
+        links: usize,
+    ) -> Result<StreamStartPayload, String> {
+        let destinations = receiver_destinations(receiver_url, links)?;
+        Ok(StreamStartPayload {
+            stream_id,
+            encoder: self.encoder(),
+            source: self.source,
+            destinations,
+            bonding_config: serde_json::Value::Null,
+            psk: None,
+            relay_url: None,
+            ingest_key: None,
+        })
+    }
+}
+
+/// Stream ID for a portal-started stream.
+pub fn stream_id() -> String {
+    format!("local-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S"))
+}
+
+/// Expand the configured receiver address into one `strata://` link per
+/// bonded link.
+///
+/// A comma-separated list is taken as the receiver's explicit link
+/// addresses. A single `host:port` is its first link port; the rest follow
+/// at the receiver's default port spacing.
+pub fn receiver_destinations(receiver_url: &str, links: usize) -> Result<Vec<String>, String> {
+    let links = links.max(1);
+    let addrs: Vec<&str> = receiver_url
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .trim_start_matches("strata://")
+                .trim_end_matches('/')
+        })
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let addrs: Vec<String> = match addrs.as_slice() {
+        [] => return Err("no receiver address configured".into()),
+        [single] => {
+            let (host, port) = single
+                .rsplit_once(':')
+                .ok_or_else(|| format!("receiver address {single:?} needs a port"))?;
+            let port: u16 = port
+                .parse()
+                .map_err(|_| format!("invalid receiver port {port:?}"))?;
+            (0..links)
+                .map_while(|i| {
+                    let offset = u16::try_from(i).ok()?.checked_mul(LINK_PORT_STEP)?;
+                    port.checked_add(offset).map(|p| format!("{host}:{p}"))
+                })
+                .collect()
+        }
+        many => many.iter().take(links).map(|s| s.to_string()).collect(),
+    };
+    Ok(addrs.into_iter().map(|a| format!("strata://{a}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(mode: &str) -> LocalStreamRequest {
+        LocalStreamRequest {
+            source: SourceConfig {
+                mode: mode.into(),
+                device: None,
+                uri: None,
+                resolution: Some("1920x1080".into()),
+                framerate: Some(30),
+                passthrough: None,
+                inputs: Vec::new(),
+            },
+            bitrate_kbps: None,
+            codec: None,
+            overlay: None,
+        }
+    }
+
+    #[test]
+    fn single_address_expands_to_link_ports() {
+        assert_eq!(
+            receiver_destinations("strata://rx.example.com:5000", 3).unwrap(),
+            vec![
+                "strata://rx.example.com:5000",
+                "strata://rx.example.com:5002",
+                "strata://rx.example.com:5004",
+            ]
+        );
+        // No eligible interface yet still yields one link.
+        assert_eq!(receiver_destinations("10.0.0.5:6000", 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn address_list_is_used_as_given() {
+        assert_eq!(
+            receiver_destinations("a:7000, b:7100 ,c:7200", 2).unwrap(),
+            vec!["strata://a:7000", "strata://b:7100"]
+        );
+    }
+
+    #[test]
+    fn bad_receiver_addresses_are_rejected() {
+        assert!(receiver_destinations("", 2).is_err());
+        assert!(receiver_destinations("rx.example.com", 2).is_err());
+        assert!(receiver_destinations("rx.example.com:port", 2).is_err());
+    }
+
+    #[test]
+    fn validation() {
+        assert!(request("test").validate().is_ok());
+        assert!(request("uri").validate().is_err());
+        assert!(request("v4l2").validate().is_err());
+        assert!(request("ndi").validate().is_err());
+
+        let mut r = request("test");
+        r.bitrate_kbps = Some(100);
+        assert!(r.validate().is_err());
+        r.bitrate_kbps = Some(6_000);
+        r.codec = Some("vp9".into());
+        assert!(r.validate().is_err());
+
+        let mut r = request("test");
+        r.overlay = Some(OverlayConfig {
+            clock: true,
+            ..Default::default()
+        });
+        assert!(r.validate().is_ok());
+        r.overlay = Some(OverlayConfig::default());
+        assert!(r.validate().is_err());
+    }
+
+    #[test]
+    fn payload_uses_profile_defaults() {
+        let p = request("test")
+            .into_payload("local-1".into(), "rx:5000", 2)
+            .unwrap();
+        assert_eq!(p.destinations.len(), 2);
+        assert_eq!(p.encoder.codec.as_deref(), Some("h265"));
+        let (min, max) = (
+            p.encoder.min_bitrate_kbps.unwrap(),
+            p.encoder.max_bitrate_kbps.unwrap(),
+        );
+        assert!(min <= p.encoder.bitrate_kbps && p.encoder.bitrate_kbps <= max);
+
+        let mut r = request("test");
+        r.bitrate_kbps = Some(40_000);
+        let p = r.into_payload("local-2".into(), "rx:5000", 1).unwrap();
+        assert_eq!(p.encoder.bitrate_kbps, 40_000);
+        assert!(p.encoder.max_bitrate_kbps.unwrap() >= 40_000);
+    }
+}
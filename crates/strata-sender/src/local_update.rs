@@ -0,0 +1,555 @@
+//! Offline updates — signed bundles uploaded through the portal.
+//!
+//! For air-gapped sites where neither OTA nor `strata-update.sh` can reach
+//! the release server. A bundle is a gzipped tarball holding:
+//!
+//! - `manifest.json` — `{"version", "arch", "files": [{"name", "sha256"}]}`,
+//!   plus `"allow_downgrade": true` on a bundle built to roll back
+//! - `manifest.sig` — base64 ed25519 signature over `manifest.json`
+//! - the files it lists (`strata-sender`, `strata-pipeline`,
+//!   `libgststrata.so`); `packaging/make-update-bundle.sh` builds one
+//!
+//! The agent runs with a read-only `/usr`, so it only verifies the upload
+//! and stages it. The root `strata-update-apply` unit, started by the
+//! staged `ready` marker, runs `strata-sender --apply-update`: it copies
+//! the staged files somewhere the agent can't touch, verifies them again
+//! and swaps them in the way `strata-update.sh` does, then the unit
+//! restarts the agent.
+//!
+//! A bundle must be newer than the installed release (the stamp
+//! `strata-update.sh` also writes), by semver precedence. Reinstalling or
+//! rolling back takes a bundle signed with `allow_downgrade` *and* the
+//! operator asking for it at upload; the apply unit trusts only the signed
+//! flag, since anyone who can stage a bundle can also stage a marker.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const MANIFEST: &str = "manifest.json";
+const SIGNATURE: &str = "manifest.sig";
+/// Created last when staging; the apply unit's path trigger.
+const READY_MARKER: &str = "ready";
+/// Release stamp shared with `strata-update.sh`.
+const STAMP_FILE: &str = "/var/lib/strata/strata-sender.version";
+/// Outcome of the last apply, for the portal.
+const RESULT_FILE: &str = "/var/lib/strata/update-result.json";
+
+/// Trusted signing key: base64 ed25519 public key. Without it offline
+/// updates are disabled.
+fn key_file() -> PathBuf {
+    std::env::var("STRATA_UPDATE_KEY_FILE")
+        .unwrap_or_else(|_| "/etc/strata/update-key.pub".into())
+        .into()
+}
+
+/// Where uploads are staged for the apply unit.
+pub fn staging_dir() -> PathBuf {
+    std::env::var("STRATA_UPDATE_DIR")
+        .unwrap_or_else(|_| "/var/lib/strata/update".into())
+        .into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    /// `std::env::consts::ARCH` of the target, e.g. "aarch64".
+    pub arch: String,
+    pub files: Vec<ManifestFile>,
+    /// Signed permission to install over a release that is the same or
+    /// newer (`make-update-bundle.sh --allow-downgrade`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_downgrade: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub name: String,
+    /// Lowercase hex SHA-256 of the file.
+    pub sha256: String,
+}
+
+/// Installed path and mode for a bundle file; `None` for names a bundle
+/// may not carry.
+fn destination(name: &str) -> Option<(PathBuf, u32)> {
+    match name {
+        "strata-sender" | "strata-pipeline" => {
+            Some((Path::new("/usr/local/bin").join(name), 0o755))
+        }
+        "libgststrata.so" => Some((plugin_dir().join(name), 0o644)),
+        _ => None,
+    }
+}
+
+/// Same choice as `install.sh`: the multiarch plugin dir if present.
+fn plugin_dir() -> PathBuf {
+    let multiarch = PathBuf::from(format!("/usr/lib/{}-linux-gnu", std::env::consts::ARCH));
+    if multiarch.is_dir() {
+        multiarch.join("gstreamer-1.0")
+    } else {
+        PathBuf::from("/usr/local/lib/gstreamer-1.0")
+    }
+}
+
+/// The trusted update key, or why offline updates are unavailable.
+pub fn trusted_key() -> anyhow::Result<String> {
+    let path = key_file();
+    let key = std::fs::read_to_string(&path).map_err(|_| {
+        anyhow::anyhow!(
+            "no update signing key at {}; offline updates are disabled",
+            path.display()
+        )
+    })?;
+    Ok(key.trim().to_string())
+}
+
+fn sha256_hex(path: &Path) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Check an unpacked bundle in `dir` against `public_key_b64`: the
+/// signature, the target architecture, and every listed file's hash.
+pub fn verify(dir: &Path, public_key_b64: &str) -> anyhow::Result<Manifest> {
+    let manifest_raw = std::fs::read_to_string(dir.join(MANIFEST))
+        .map_err(|_| anyhow::anyhow!("bundle has no {MANIFEST}"))?;
+    let signature = std::fs::read_to_string(dir.join(SIGNATURE))
+        .map_err(|_| anyhow::anyhow!("bundle has no {SIGNATURE}"))?;
+    if !strata_common::auth::verify_challenge(public_key_b64, &manifest_raw, signature.trim())? {
+        anyhow::bail!("bundle signature does not match the trusted update key");
+    }
+
+    let manifest: Manifest = serde_json::from_str(&manifest_raw)?;
+    if manifest.arch != std::env::consts::ARCH {
+        anyhow::bail!(
+            "bundle is for {}, this unit is {}",
+            manifest.arch,
+            std::env::consts::ARCH
+        );
+    }
+    if manifest.files.is_empty() {
+        anyhow::bail!("bundle lists no files");
+    }
+    let mut seen = std::collections::HashSet::new();
+    for file in &manifest.files {
+        if destination(&file.name).is_none() {
+            anyhow::bail!(
+                "bundle file {:?} is not an installable component",
+                file.name
+            );
+        }
+        if !seen.insert(file.name.as_str()) {
+            anyhow::bail!("bundle lists {} twice", file.name);
+        }
+        let actual = sha256_hex(&dir.join(&file.name))
+            .map_err(|_| anyhow::anyhow!("bundle is missing {}", file.name))?;
+        if !actual.eq_ignore_ascii_case(&file.sha256) {
+            anyhow::bail!("checksum mismatch for {}", file.name);
+        }
+    }
+    Ok(manifest)
+}
+
+/// Release tag last installed by `strata-update.sh` or a bundle.
+fn installed_release() -> Option<String> {
+    std::fs::read_to_string(STAMP_FILE)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// A release tag as semver: "v1.4.0-rc2" → 1.4.0-rc2, which sorts after
+/// 1.4.0-rc1 and before 1.4.0.
+fn version_key(version: &str) -> Option<semver::Version> {
+    semver::Version::parse(version.trim().trim_start_matches('v')).ok()
+}
+
+/// Refuse `version` unless it is newer than `installed` or the operator
+/// allowed a downgrade. Nothing installed yet accepts any version.
+fn check_newer(
+    version: &str,
+    installed: Option<&str>,
+    allow_downgrade: bool,
+) -> anyhow::Result<()> {
+    let Some(installed) = installed else {
+        return Ok(());
+    };
+    if allow_downgrade {
+        return Ok(());
+    }
+    match (version_key(version), version_key(installed)) {
+        (Some(new), Some(old)) if new.cmp_precedence(&old).is_gt() => Ok(()),
+        (Some(_), Some(_)) => anyhow::bail!(
+            "bundle {version} is not newer than the installed {installed}; allow a downgrade to install it anyway"
+        ),
+        _ => anyhow::bail!(
+            "can't tell whether bundle {version} is newer than the installed {installed}; allow a downgrade to install it anyway"
+        ),
+    }
+}
+
+/// Unpack an uploaded bundle into the staging dir, verify it, and mark it
+/// ready for the apply unit. A bundle that fails verification, or isn't
+/// newer than the installed release without `allow_downgrade` from both
+/// the operator and its signed manifest, is removed.
+pub async fn stage(
+    bundle: &[u8],
+    public_key_b64: &str,
+    allow_downgrade: bool,
+) -> anyhow::Result<Manifest> {
+    stage_in(&staging_dir(), bundle, public_key_b64, allow_downgrade).await
+}
+
+async fn stage_in(
+    dir: &Path,
+    bundle: &[u8],
+    public_key_b64: &str,
+    allow_downgrade: bool,
+) -> anyhow::Result<Manifest> {
+    let _ = tokio::fs::remove_dir_all(dir).await;
+    tokio::fs::create_dir_all(dir).await?;
+
+    let result = async {
+        let archive = dir.join("bundle.tar.gz");
+        tokio::fs::write(&archive, bundle).await?;
+        let output = tokio::process::Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir)
+            .arg("--no-same-owner")
+            .output()
+            .await?;
+        let _ = tokio::fs::remove_file(&archive).await;
+        if !output.status.success() {
+            anyhow::bail!("not a valid update bundle (expected a .tar.gz)");
+        }
+
+        let (verify_dir, key) = (dir.to_path_buf(), public_key_b64.to_string());
+        let manifest = tokio::task::spawn_blocking(move || verify(&verify_dir, &key)).await??;
+        if allow_downgrade && !manifest.allow_downgrade {
+            anyhow::bail!(
+                "bundle {} is not signed for downgrades; build it with --allow-downgrade",
+                manifest.version
+            );
+        }
+        check_newer(
+            &manifest.version,
+            installed_release().as_deref(),
+            allow_downgrade,
+        )?;
+        tokio::fs::write(dir.join(READY_MARKER), &manifest.version).await?;
+        Ok(manifest)
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+    result
+}
+
+/// `strata-sender --apply-update <dir>`: install a staged bundle (runs as
+/// root from the apply unit). Always clears the staging dir so the path
+/// trigger doesn't refire, and records the outcome for the portal.
+pub fn apply(staged: &Path) -> anyhow::Result<Manifest> {
+    let result = trusted_key().and_then(|key| apply_from(staged, &key));
+    let _ = std::fs::remove_dir_all(staged);
+
+    let record = match &result {
+        Ok(m) => UpdateResult {
+            version: Some(m.version.clone()),
+            error: None,
+            at: chrono::Utc::now(),
+        },
+        Err(e) => UpdateResult {
+            version: None,
+            error: Some(e.to_string()),
+            at: chrono::Utc::now(),
+        },
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&record) {
+        let _ = std::fs::write(RESULT_FILE, json);
+    }
+    result
+}
+
+fn apply_from(staged: &Path, public_key_b64: &str) -> anyhow::Result<Manifest> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    // Private copy first: the staging dir is writable by the agent user,
+    // so verify and install from files it can no longer swap.
+    let private = std::env::temp_dir().join(format!("strata-apply-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&private);
+    std::fs::DirBuilder::new().mode(0o700).create(&private)?;
+    let result = (|| -> anyhow::Result<Manifest> {
+        let listed: Manifest =
+            serde_json::from_str(&std::fs::read_to_string(staged.join(MANIFEST))?)?;
+        for name in [MANIFEST, SIGNATURE]
+            .into_iter()
+            .chain(listed.files.iter().map(|f| f.name.as_str()))
+        {
+            if name.contains('/') {
+                anyhow::bail!("bundle file {name:?} is not a plain name");
+            }
+            std::fs::copy(staged.join(name), private.join(name))?;
+        }
+        let manifest = verify(&private, public_key_b64)?;
+        check_newer(
+            &manifest.version,
+            installed_release().as_deref(),
+            manifest.allow_downgrade,
+        )?;
+
+        for file in &manifest.files {
+            let (dest, mode) = destination(&file.name).expect("verified above");
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = dest.with_file_name(format!("{}.new", file.name));
+            std::fs::copy(private.join(&file.name), &tmp)?;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(mode))?;
+            std::fs::rename(&tmp, &dest)?;
+            tracing::info!(path = %dest.display(), "installed");
+        }
+        std::fs::write(STAMP_FILE, format!("{}\n", manifest.version))?;
+        Ok(manifest)
+    })();
+    let _ = std::fs::remove_dir_all(&private);
+    result
+}
+
+/// Outcome of the last `--apply-update` run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResult {
+    pub version: Option<String>,
+    pub error: Option<String>,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Versions of the installed components, for the portal.
+#[derive(Debug, Serialize)]
+pub struct Versions {
+    /// This agent binary.
+    pub agent: &'static str,
+    /// Release tag last installed by `strata-update.sh` or a bundle.
+    pub release: Option<String>,
+    pub pipeline: Option<String>,
+    /// The GStreamer plugin, as registered with GStreamer.
+    pub plugin: Option<String>,
+    /// Whether a signing key is installed, i.e. uploads are accepted.
+    pub uploads_enabled: bool,
+    /// A bundle is staged and waiting for the apply unit.
+    pub update_pending: bool,
+    pub last_update: Option<UpdateResult>,
+}
+
+pub async fn versions() -> Versions {
+    let (pipeline, plugin) = tokio::join!(
+        command_output(crate::pipeline::pipeline_binary(), &["--version"]),
+        command_output("gst-inspect-1.0".into(), &["strata"]),
+    );
+    Versions {
+        agent: env!("CARGO_PKG_VERSION"),
+        release: installed_release(),
+        // "strata-pipeline 0.6.0"
+        pipeline: pipeline.and_then(|out| out.split_whitespace().last().map(str::to_string)),
+        // "  Version                  0.6.0"
+        plugin: plugin.and_then(|out| {
+            out.lines()
+                .find_map(|l| l.trim().strip_prefix("Version"))
+                .map(|v| v.trim().to_string())
+        }),
+        uploads_enabled: trusted_key().is_ok(),
+        update_pending: staging_dir().join(READY_MARKER).exists(),
+        last_update: std::fs::read_to_string(RESULT_FILE)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok()),
+    }
+}
+
+/// Stdout of a short-lived command, `None` if it is missing, fails or
+/// hangs.
+async fn command_output(program: std::ffi::OsString, args: &[&str]) -> Option<String> {
+    let output = tokio::time::timeout(
+        std::time::Duration::from_secs(3),
+        tokio::process::Command::new(program)
+            .args(args)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Bundle {
+        dir: PathBuf,
+        public_key: String,
+        private_key: String,
+    }
+
+    impl Bundle {
+        /// An unpacked bundle carrying one pipeline binary.
+        fn new(tag: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("strata-bundle-test-{tag}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("strata-pipeline"), b"#!/bin/sh\necho new\n").unwrap();
+            let (private_key, public_key) = strata_common::auth::generate_device_keypair();
+            let bundle = Self {
+                dir,
+                public_key,
+                private_key,
+            };
+            let sha256 = sha256_hex(&bundle.dir.join("strata-pipeline")).unwrap();
+            bundle.write_manifest(&Manifest {
+                version: "v9.9.9".into(),
+                arch: std::env::consts::ARCH.into(),
+                files: vec![ManifestFile {
+                    name: "strata-pipeline".into(),
+                    sha256,
+                }],
+                allow_downgrade: false,
+            });
+            bundle
+        }
+
+        fn write_manifest(&self, manifest: &Manifest) {
+            let raw = serde_json::to_string(manifest).unwrap();
+            let sig = strata_common::auth::sign_challenge(&self.private_key, &raw).unwrap();
+            std::fs::write(self.dir.join(MANIFEST), raw).unwrap();
+            std::fs::write(self.dir.join(SIGNATURE), sig).unwrap();
+        }
+
+        fn manifest(&self) -> Manifest {
+            serde_json::from_str(&std::fs::read_to_string(self.dir.join(MANIFEST)).unwrap())
+                .unwrap()
+        }
+    }
+
+    impl Drop for Bundle {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn signed_bundle_verifies() {
+        let b = Bundle::new("ok");
+        let manifest = verify(&b.dir, &b.public_key).unwrap();
+        assert_eq!(manifest.version, "v9.9.9");
+    }
+
+    #[test]
+    fn wrong_key_or_edited_manifest_is_rejected() {
+        let b = Bundle::new("sig");
+        let (_, other_public) = strata_common::auth::generate_device_keypair();
+        assert!(verify(&b.dir, &other_public).is_err());
+
+        let raw = std::fs::read_to_string(b.dir.join(MANIFEST)).unwrap();
+        std::fs::write(b.dir.join(MANIFEST), raw.replace("v9.9.9", "v9.9.8")).unwrap();
+        assert!(verify(&b.dir, &b.public_key).is_err());
+    }
+
+    #[test]
+    fn tampered_file_is_rejected() {
+        let b = Bundle::new("hash");
+        std::fs::write(b.dir.join("strata-pipeline"), b"#!/bin/sh\nevil\n").unwrap();
+        let err = verify(&b.dir, &b.public_key).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn only_known_components_for_this_arch() {
+        let b = Bundle::new("arch");
+        let mut m = b.manifest();
+        m.arch = "mips".into();
+        b.write_manifest(&m);
+        assert!(verify(&b.dir, &b.public_key).is_err());
+
+        let mut m = b.manifest();
+        m.arch = std::env::consts::ARCH.into();
+        m.files[0].name = "../etc/passwd".into();
+        b.write_manifest(&m);
+        assert!(verify(&b.dir, &b.public_key).is_err());
+    }
+
+    #[test]
+    fn only_newer_versions_install_unless_downgrade_allowed() {
+        assert!(check_newer("v1.2.0", None, false).is_ok());
+        assert!(check_newer("v1.10.0", Some("v1.9.3"), false).is_ok());
+        assert!(check_newer("1.2.1", Some("v1.2.0\n"), false).is_ok());
+
+        let same = check_newer("v1.2.0", Some("v1.2.0"), false).unwrap_err();
+        assert!(same.to_string().contains("not newer"), "{same}");
+        assert!(check_newer("v1.1.9", Some("v1.2.0"), false).is_err());
+        assert!(check_newer("v1.2.0-rc1", Some("v1.2.0"), false).is_err());
+        assert!(check_newer("v1.2.0+build.7", Some("v1.2.0"), false).is_err());
+        assert!(check_newer("nightly", Some("v1.2.0"), false).is_err());
+
+        assert!(check_newer("v1.1.9", Some("v1.2.0"), true).is_ok());
+        assert!(check_newer("nightly", Some("v1.2.0"), true).is_ok());
+    }
+
+    #[test]
+    fn release_candidates_order_by_semver_precedence() {
+        assert!(check_newer("v1.4.0", Some("v1.4.0-rc1"), false).is_ok());
+        assert!(check_newer("v1.4.0-rc2", Some("v1.4.0-rc1"), false).is_ok());
+        assert!(check_newer("v1.4.0-rc.10", Some("v1.4.0-rc.9"), false).is_ok());
+        assert!(check_newer("v1.4.0-rc1", Some("v1.4.0-rc2"), false).is_err());
+        assert!(check_newer("v1.4.0-rc1", Some("v1.4.0"), false).is_err());
+    }
+
+    #[tokio::test]
+    async fn downgrade_needs_a_bundle_signed_for_it() {
+        let b = Bundle::new("downgrade");
+        let dir = std::env::temp_dir().join(format!("strata-stage-test-{}", std::process::id()));
+        let tarball = |b: &Bundle| {
+            let out = b.dir.with_extension("tar.gz");
+            let status = std::process::Command::new("tar")
+                .arg("-czf")
+                .arg(&out)
+                .arg("-C")
+                .arg(&b.dir)
+                .args([MANIFEST, SIGNATURE, "strata-pipeline"])
+                .status()
+                .unwrap();
+            assert!(status.success());
+            let bytes = std::fs::read(&out).unwrap();
+            let _ = std::fs::remove_file(&out);
+            bytes
+        };
+
+        let err = stage_in(&dir, &tarball(&b), &b.public_key, true)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("not signed for downgrades"),
+            "{err}"
+        );
+        assert!(!dir.exists());
+
+        let mut m = b.manifest();
+        m.allow_downgrade = true;
+        b.write_manifest(&m);
+        let staged = stage_in(&dir, &tarball(&b), &b.public_key, true)
+            .await
+            .unwrap();
+        assert!(staged.allow_downgrade);
+        assert!(dir.join(READY_MARKER).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -7,13 +7,22 @@
 //! - Starts/stops GStreamer sender pipelines on command
 //! - Relays real-time bonding telemetry to the control plane
 
+mod config_store;
 mod control;
+mod diagnostics;
 mod hardware;
 mod hilink;
+mod local_stream;
+mod local_update;
 mod metrics;
+mod netconfig;
 mod pipeline;
 mod pipeline_monitor;
 mod portal;
+mod portal_auth;
+mod routing;
+#[cfg(feature = "simulate")]
+mod simulate;
 mod telemetry;
 pub(crate) mod util;
 
@@ -47,6 +56,11 @@ struct Cli {
     #[arg(long, default_value = "0.0.0.0:3001")]
     portal_addr: String,
 
+    /// HTTPS portal listen address, serving the certificate pushed by the
+    /// control plane. Disabled if empty.
+    #[arg(long, default_value = "0.0.0.0:3443")]
+    portal_https_addr: String,
+
     /// Device hostname override.
     #[arg(long)]
     hostname: Option<String>,
@@ -58,6 +72,24 @@ struct Cli {
     /// Prometheus metrics server address (e.g. 0.0.0.0:9090). Disabled if empty.
     #[arg(long, default_value = "")]
     metrics_addr: String,
+
+    /// Leave the default route alone instead of ordering it by interface
+    /// priority (dev containers, hosts whose routing is managed elsewhere).
+    #[arg(long)]
+    no_route_management: bool,
+
+    /// Install an offline update bundle staged by the portal, then exit.
+    /// Run as root by the `strata-update-apply` unit, not by hand.
+    #[arg(long, value_name = "DIR")]
+    apply_update: Option<std::path::PathBuf>,
+
+    /// Replace the device's interfaces with a simulated link (repeatable):
+    /// a preset such as `lte_good`, `lte_good*4` for several, or a
+    /// strata-sim trace (`field.csv`, `field.csv#wwan0` for one of its
+    /// links). Implies --no-route-management.
+    #[cfg(feature = "simulate")]
+    #[arg(long = "simulate-link", value_name = "SPEC")]
+    simulate_links: Vec<String>,
 }
 
 /// Shared agent state accessible from all tasks.
@@ -69,7 +101,9 @@ pub struct AgentState {
     pub identity_path: std::path::PathBuf,
     pub hardware: hardware::HardwareScanner,
     pub pipeline: tokio::sync::Mutex<pipeline::PipelineManager>,
-    pub control_tx: mpsc::Sender<String>,
+    /// Outgoing envelopes; the control loop picks the frame encoding
+    /// (JSON text or negotiated binary) per connection.
+    pub control_tx: mpsc::Sender<strata_protocol::Envelope>,
     pub shutdown: watch::Receiver<bool>,
     /// Whether the control plane WebSocket is currently connected.
     pub control_connected: AtomicBool,
@@ -86,7 +120,33 @@ pub struct AgentState {
     /// Sender for triggering graceful shutdown.
     pub shutdown_tx: watch::Sender<bool>,
     /// Latest link stats from the bonding engine (updated by telemetry loop).
-    pub latest_link_stats: tokio::sync::RwLock<Vec<strata_protocol::models::LinkStats>>,
+    pub latest_link_stats: tokio::sync::RwLock<Vec<strata_protocol::telemetry::LinkSample>>,
+    /// Maintenance schedule last pushed by the control plane.
+    pub maintenance: tokio::sync::RwLock<Vec<strata_protocol::models::MaintenanceWindow>>,
+    /// Locally persisted agent settings (first-run wizard progress).
+    pub config: config_store::ConfigStore,
+    /// Portal login sessions and lockout (used when a PIN is set).
+    pub portal_auth: portal_auth::PortalAuth,
+    /// Portal IP change awaiting confirmation (reverted if none arrives).
+    pub pending_ip_change: tokio::sync::Mutex<Option<netconfig::PendingChange>>,
+    /// Portal HTTPS certificate, pushed by the control plane with
+    /// `tls.install` and kept on disk for restarts and offline use.
+    pub tls: strata_common::tls::server::ServedCert,
+}
+
+impl AgentState {
+    /// The currently open maintenance window, if any.
+    pub async fn open_maintenance_window(
+        &self,
+    ) -> Option<strata_protocol::models::MaintenanceWindow> {
+        let now = chrono::Utc::now();
+        self.maintenance
+            .read()
+            .await
+            .iter()
+            .find(|w| w.is_open_at(now))
+            .cloned()
+    }
 }
 
 #[tokio::main]
@@ -98,8 +158,15 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     let cli = Cli::parse();
+    if let Some(dir) = &cli.apply_update {
+        let manifest = local_update::apply(dir)?;
+        tracing::info!(version = %manifest.version, "offline update installed");
+        return Ok(());
+    }
+
     let hostname = cli
         .hostname
+        .clone()
         .unwrap_or_else(|| gethostname().unwrap_or_else(|| "strata-sender".into()));
 
     tracing::info!(
@@ -116,7 +183,8 @@ async fn main() -> anyhow::Result<()> {
     // uses 64, not this value — an unexplained mismatch, flagged rather
     // than silently unified (E9).
     const CONTROL_OUTGOING_CHANNEL_CAPACITY: usize = 128;
-    let (control_tx, control_rx) = mpsc::channel::<String>(CONTROL_OUTGOING_CHANNEL_CAPACITY);
+    let (control_tx, control_rx) =
+        mpsc::channel::<strata_protocol::Envelope>(CONTROL_OUTGOING_CHANNEL_CAPACITY);
 
     // Reconnect signal (portal can trigger reconnect after enrollment)
     let (reconnect_tx, _reconnect_rx) = watch::channel(());
@@ -127,13 +195,15 @@ async fn main() -> anyhow::Result<()> {
     let identity_path = std::path::PathBuf::from(&cli.identity_file);
     let identity = strata_common::identity::DeviceIdentity::load_or_generate(&identity_path)?;
 
+    let (hardware, pipeline, route_management) = links(&cli)?;
+
     // Build shared state
     let state = Arc::new(AgentState {
         sender_id: tokio::sync::Mutex::new(None),
         identity: tokio::sync::Mutex::new(identity),
         identity_path,
-        hardware: hardware::HardwareScanner::new(),
-        pipeline: tokio::sync::Mutex::new(pipeline::PipelineManager::new()),
+        hardware,
+        pipeline: tokio::sync::Mutex::new(pipeline),
         control_tx: control_tx.clone(),
         shutdown: shutdown_rx.clone(),
         control_connected: AtomicBool::new(false),
@@ -144,6 +214,13 @@ async fn main() -> anyhow::Result<()> {
         receiver_url: tokio::sync::Mutex::new(None),
         shutdown_tx,
         latest_link_stats: tokio::sync::RwLock::new(Vec::new()),
+        maintenance: tokio::sync::RwLock::new(Vec::new()),
+        config: config_store::ConfigStore::load(),
+        portal_auth: portal_auth::PortalAuth::new(),
+        pending_ip_change: tokio::sync::Mutex::new(None),
+        tls: strata_common::tls::server::ServedCert::load(
+            std::env::var("STRATA_TLS_DIR").unwrap_or_else(|_| "/var/lib/strata/tls".into()),
+        ),
     });
 
     // ── Task 1: Control plane WebSocket connection ──────────────
@@ -176,10 +253,26 @@ async fn main() -> anyhow::Result<()> {
         pipeline_monitor::run(monitor_state).await;
     });
 
-    // ── Task 3: Onboarding portal (HTTP) ────────────────────────
+    // ── Task 2c: Priority-aware default route ──────────────────
+    if route_management {
+        let routing_state = state.clone();
+        tokio::spawn(async move {
+            routing::run(routing_state).await;
+        });
+    }
+
+    // ── Task 3: Onboarding portal (HTTP + HTTPS) ────────────────
     let portal_state = state.clone();
     let portal_addr: SocketAddr = cli.portal_addr.parse()?;
-    let portal_handle = tokio::spawn(async move { portal::run(portal_state, portal_addr).await });
+    let portal_https_addr: Option<SocketAddr> = if cli.portal_https_addr.is_empty() {
+        None
+    } else {
+        Some(cli.portal_https_addr.parse()?)
+    };
+    let portal_handle =
+        tokio::spawn(
+            async move { portal::run(portal_state, portal_addr, portal_https_addr).await },
+        );
 
     // ── Task 4: Dedicated metrics server (if --metrics_addr is set) ──
     if !cli.metrics_addr.is_empty() {
@@ -214,6 +307,31 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Interface scanner and pipeline manager to run with, and whether to
+/// manage the default route — real interfaces unless `--simulate-link`.
+fn links(
+    cli: &Cli,
+) -> anyhow::Result<(hardware::HardwareScanner, pipeline::PipelineManager, bool)> {
+    #[cfg(feature = "simulate")]
+    if !cli.simulate_links.is_empty() {
+        let simulation = simulate::Simulation::parse(&cli.simulate_links)?;
+        for link in simulation.links() {
+            tracing::info!(link = %link.name, source = %link.source, "simulated link");
+        }
+        // The real default route carries every simulated link.
+        return Ok((
+            hardware::HardwareScanner::simulated(simulation.interfaces()),
+            pipeline::PipelineManager::new().with_simulation(simulation),
+            false,
+        ));
+    }
+    Ok((
+        hardware::HardwareScanner::new(),
+        pipeline::PipelineManager::new(),
+        !cli.no_route_management,
+    ))
+}
+
 fn gethostname() -> Option<String> {
     std::fs::read_to_string("/etc/hostname")
         .ok()
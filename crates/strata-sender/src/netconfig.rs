@@ -0,0 +1,521 @@
+//! Per-interface IPv4 addressing (DHCP or static) via NetworkManager.
+//!
+//! Venues often hand out a static address for the uplink, so the portal
+//! lets a tech set address/gateway/DNS on an interface. Changes go through
+//! the interface's NetworkManager connection profile (`nmcli`), which also
+//! persists them across reboots.
+//!
+//! Applying is commit-confirm: the previous profile is kept, and unless
+//! the portal confirms the change within [`CONFIRM_WINDOW`] it is put
+//! back. A typo that cuts the tech off from the portal undoes itself.
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::AgentState;
+
+/// How long an applied change waits for confirmation before reverting.
+pub const CONFIRM_WINDOW: Duration = Duration::from_secs(60);
+
+/// Most resolvers NetworkManager will use from one profile.
+const MAX_DNS: usize = 3;
+
+#[cfg(test)]
+static TEST_NMCLI_BIN: std::sync::Mutex<Option<std::ffi::OsString>> = std::sync::Mutex::new(None);
+
+/// Requested addressing for one interface.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum IpConfig {
+    Dhcp,
+    Static {
+        /// Address with prefix length, e.g. `192.168.10.20/24`.
+        address: String,
+        gateway: Option<String>,
+        #[serde(default)]
+        dns: Vec<String>,
+    },
+}
+
+impl IpConfig {
+    /// Reject anything NetworkManager would refuse or that can't route.
+    pub fn validate(&self) -> Result<(), String> {
+        let IpConfig::Static {
+            address,
+            gateway,
+            dns,
+        } = self
+        else {
+            return Ok(());
+        };
+
+        let (ip, prefix) = parse_cidr(address)?;
+        if ip.is_unspecified() || ip.is_loopback() || ip.is_multicast() || ip.is_broadcast() {
+            return Err(format!("{ip} is not a usable host address"));
+        }
+        let mask = prefix_mask(prefix);
+        if prefix < 31 {
+            let host = u32::from(ip) & !mask;
+            if host == 0 || host == !mask {
+                return Err(format!(
+                    "{ip} is the network or broadcast address of /{prefix}"
+                ));
+            }
+        }
+
+        if let Some(gw) = gateway.as_deref().filter(|g| !g.is_empty()) {
+            let gw: Ipv4Addr = gw
+                .parse()
+                .map_err(|_| format!("gateway {gw:?} is not an IPv4 address"))?;
+            if gw == ip {
+                return Err("gateway can't be the interface's own address".into());
+            }
+            if u32::from(gw) & mask != u32::from(ip) & mask {
+                return Err(format!("gateway {gw} is outside {address}"));
+            }
+        }
+
+        if dns.len() > MAX_DNS {
+            return Err(format!("at most {MAX_DNS} DNS servers"));
+        }
+        for server in dns {
+            server
+                .parse::<IpAddr>()
+                .map_err(|_| format!("DNS server {server:?} is not an IP address"))?;
+        }
+        Ok(())
+    }
+}
+
+/// `a.b.c.d/len` → address and prefix length (1–32).
+fn parse_cidr(s: &str) -> Result<(Ipv4Addr, u32), String> {
+    let (ip, prefix) = s
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| format!("{s:?} needs a prefix length, e.g. /24"))?;
+    let ip: Ipv4Addr = ip
+        .parse()
+        .map_err(|_| format!("{ip:?} is not an IPv4 address"))?;
+    let prefix: u32 = prefix
+        .parse()
+        .ok()
+        .filter(|p| (1..=32).contains(p))
+        .ok_or_else(|| format!("prefix length {prefix:?} must be 1–32"))?;
+    Ok((ip, prefix))
+}
+
+fn prefix_mask(prefix: u32) -> u32 {
+    if prefix == 0 {
+        0
+    } else {
+        u32::MAX << (32 - prefix)
+    }
+}
+
+/// The IPv4 fields of a NetworkManager profile, verbatim — what a
+/// rollback writes back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ipv4Profile {
+    pub method: String,
+    pub addresses: String,
+    pub gateway: String,
+    pub dns: String,
+}
+
+impl Ipv4Profile {
+    /// Parse `nmcli -g ipv4.method,ipv4.addresses,ipv4.gateway,ipv4.dns`.
+    fn parse(output: &str) -> Option<Self> {
+        let mut lines = output.lines().map(|l| l.trim().replace("\\:", ":"));
+        Some(Self {
+            method: lines.next()?,
+            addresses: lines.next().unwrap_or_default(),
+            gateway: lines.next().unwrap_or_default(),
+            dns: lines.next().unwrap_or_default(),
+        })
+    }
+
+    fn from_config(cfg: &IpConfig) -> Self {
+        match cfg {
+            IpConfig::Dhcp => Self {
+                method: "auto".into(),
+                addresses: String::new(),
+                gateway: String::new(),
+                dns: String::new(),
+            },
+            IpConfig::Static {
+                address,
+                gateway,
+                dns,
+            } => Self {
+                method: "manual".into(),
+                addresses: address.trim().into(),
+                gateway: gateway.clone().unwrap_or_default(),
+                dns: dns.join(","),
+            },
+        }
+    }
+
+    /// The profile as an [`IpConfig`], if it is DHCP or static.
+    pub fn as_config(&self) -> Option<IpConfig> {
+        match self.method.as_str() {
+            "auto" => Some(IpConfig::Dhcp),
+            "manual" => Some(IpConfig::Static {
+                address: self
+                    .addresses
+                    .split(',')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .into(),
+                gateway: Some(self.gateway.clone()).filter(|g| !g.is_empty()),
+                dns: self
+                    .dns
+                    .split([',', ' '])
+                    .filter(|d| !d.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A change waiting for the portal's confirmation.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingChange {
+    pub interface: String,
+    pub connection: String,
+    pub previous: Ipv4Profile,
+    pub applied: IpConfig,
+    pub revert_at: chrono::DateTime<chrono::Utc>,
+}
+
+fn nmcli_binary() -> std::ffi::OsString {
+    #[cfg(test)]
+    if let Some(bin) = TEST_NMCLI_BIN.lock().unwrap().clone() {
+        return bin;
+    }
+
+    "nmcli".into()
+}
+
+async fn nmcli(args: &[&str]) -> anyhow::Result<String> {
+    let output = tokio::process::Command::new(nmcli_binary())
+        .args(args)
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "nmcli {}: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Name of the NetworkManager profile active on `iface`.
+pub async fn connection_for(iface: &str) -> anyhow::Result<String> {
+    let name = nmcli(&["-g", "GENERAL.CONNECTION", "device", "show", iface]).await?;
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("{iface} has no active NetworkManager connection");
+    }
+    Ok(name.to_string())
+}
+
+pub async fn read_profile(connection: &str) -> anyhow::Result<Ipv4Profile> {
+    let out = nmcli(&[
+        "-g",
+        "ipv4.method,ipv4.addresses,ipv4.gateway,ipv4.dns",
+        "connection",
+        "show",
+        connection,
+    ])
+    .await?;
+    Ipv4Profile::parse(&out).ok_or_else(|| anyhow::anyhow!("unexpected nmcli output"))
+}
+
+async fn write_profile(connection: &str, profile: &Ipv4Profile) -> anyhow::Result<()> {
+    nmcli(&[
+        "connection",
+        "modify",
+        connection,
+        "ipv4.method",
+        &profile.method,
+        "ipv4.addresses",
+        &profile.addresses,
+        "ipv4.gateway",
+        &profile.gateway,
+        "ipv4.dns",
+        &profile.dns,
+    ])
+    .await?;
+    nmcli(&["connection", "up", connection]).await?;
+    Ok(())
+}
+
+/// Write `profile`, putting `previous` back if any step fails. `modify`
+/// can land before `up` fails, and no revert timer exists yet to undo it.
+async fn switch_profile(
+    connection: &str,
+    profile: &Ipv4Profile,
+    previous: &Ipv4Profile,
+) -> anyhow::Result<()> {
+    let Err(e) = write_profile(connection, profile).await else {
+        return Ok(());
+    };
+    if let Err(restore) = write_profile(connection, previous).await {
+        tracing::error!(connection, error = %restore, "failed to restore IP config after a failed apply");
+    }
+    Err(e)
+}
+
+/// Apply `cfg` to `iface` and arm the rollback timer. Fails if another
+/// change is still awaiting confirmation.
+pub async fn apply(
+    state: &Arc<AgentState>,
+    iface: &str,
+    cfg: IpConfig,
+) -> anyhow::Result<PendingChange> {
+    let mut pending = state.pending_ip_change.lock().await;
+    if let Some(p) = pending.as_ref() {
+        anyhow::bail!(
+            "a change to {} is awaiting confirmation; confirm or wait for it to revert",
+            p.interface
+        );
+    }
+
+    let connection = connection_for(iface).await?;
+    let previous = read_profile(&connection).await?;
+    if previous.method == "shared" {
+        anyhow::bail!("{iface} is serving the setup hotspot and can't be readdressed");
+    }
+
+    switch_profile(&connection, &Ipv4Profile::from_config(&cfg), &previous).await?;
+    tracing::info!(interface = %iface, config = ?cfg, "IP config applied via portal, awaiting confirmation");
+
+    let change = PendingChange {
+        interface: iface.to_string(),
+        connection,
+        previous,
+        applied: cfg,
+        revert_at: chrono::Utc::now()
+            + chrono::Duration::from_std(CONFIRM_WINDOW).unwrap_or_default(),
+    };
+    *pending = Some(change.clone());
+    drop(pending);
+
+    let state = state.clone();
+    let interface = change.interface.clone();
+    let revert_at = change.revert_at;
+    tokio::spawn(async move {
+        tokio::time::sleep(CONFIRM_WINDOW).await;
+        revert_unconfirmed(&state.pending_ip_change, &interface, revert_at).await;
+    });
+
+    Ok(change)
+}
+
+/// Keep the pending change on `iface`. Returns false if there is none
+/// (never applied, or already reverted).
+pub async fn confirm(state: &AgentState, iface: &str) -> bool {
+    let mut pending = state.pending_ip_change.lock().await;
+    if pending.as_ref().is_some_and(|p| p.interface == iface) {
+        *pending = None;
+        tracing::info!(interface = %iface, "IP config confirmed");
+        true
+    } else {
+        false
+    }
+}
+
+/// Revert the change on `iface` armed with `revert_at`. A timer left over
+/// from an earlier, confirmed change finds a different `revert_at` and
+/// leaves the newer change alone.
+async fn revert_unconfirmed(
+    pending: &tokio::sync::Mutex<Option<PendingChange>>,
+    iface: &str,
+    revert_at: chrono::DateTime<chrono::Utc>,
+) {
+    let mut pending = pending.lock().await;
+    let Some(change) = pending.take_if(|p| p.interface == iface && p.revert_at == revert_at) else {
+        return;
+    };
+    drop(pending);
+
+    tracing::warn!(interface = %iface, "IP config not confirmed, reverting");
+    if let Err(e) = write_profile(&change.connection, &change.previous).await {
+        tracing::error!(interface = %iface, error = %e, "failed to revert IP config");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn static_cfg(address: &str, gateway: Option<&str>, dns: &[&str]) -> IpConfig {
+        IpConfig::Static {
+            address: address.into(),
+            gateway: gateway.map(str::to_string),
+            dns: dns.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_typical_static_config() {
+        assert!(
+            static_cfg("192.168.10.20/24", Some("192.168.10.1"), &["1.1.1.1"])
+                .validate()
+                .is_ok()
+        );
+        assert!(static_cfg("10.0.0.2/31", None, &[]).validate().is_ok());
+        assert!(IpConfig::Dhcp.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_addresses() {
+        for bad in [
+            "192.168.10.20",
+            "192.168.10.20/33",
+            "192.168.10.0/24",
+            "192.168.10.255/24",
+            "127.0.0.1/8",
+            "bogus/24",
+        ] {
+            assert!(static_cfg(bad, None, &[]).validate().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_gateway_outside_subnet() {
+        let cfg = static_cfg("192.168.10.20/24", Some("192.168.11.1"), &[]);
+        assert!(cfg.validate().is_err());
+        let cfg = static_cfg("192.168.10.20/24", Some("192.168.10.20"), &[]);
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_bad_dns() {
+        let cfg = static_cfg("192.168.10.20/24", None, &["dns.example.com"]);
+        assert!(cfg.validate().is_err());
+        let cfg = static_cfg(
+            "192.168.10.20/24",
+            None,
+            &["1.1.1.1", "8.8.8.8", "9.9.9.9", "1.0.0.1"],
+        );
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn profile_round_trips_static_config() {
+        let out = "manual\n192.168.10.20/24\n192.168.10.1\n1.1.1.1,8.8.8.8\n";
+        let profile = Ipv4Profile::parse(out).unwrap();
+        let cfg = profile.as_config().unwrap();
+        assert_eq!(
+            cfg,
+            static_cfg(
+                "192.168.10.20/24",
+                Some("192.168.10.1"),
+                &["1.1.1.1", "8.8.8.8"]
+            )
+        );
+        assert_eq!(Ipv4Profile::from_config(&cfg), profile);
+    }
+
+    #[test]
+    fn profile_parses_dhcp_and_other_methods() {
+        let dhcp = Ipv4Profile::parse("auto\n\n\n\n").unwrap();
+        assert_eq!(dhcp.as_config(), Some(IpConfig::Dhcp));
+        let shared = Ipv4Profile::parse("shared\n10.42.0.1/24\n\n\n").unwrap();
+        assert_eq!(shared.as_config(), None);
+    }
+
+    /// Point `nmcli` at a script that logs its arguments to `log` and
+    /// fails the first `connection up`.
+    struct FakeNmcli {
+        dir: std::path::PathBuf,
+        log: std::path::PathBuf,
+    }
+
+    impl FakeNmcli {
+        fn new() -> Self {
+            use std::os::unix::fs::PermissionsExt;
+
+            let dir =
+                std::env::temp_dir().join(format!("strata-netconfig-test-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let log = dir.join("calls.log");
+            let fail_up = dir.join("fail-up");
+            std::fs::write(&fail_up, b"").unwrap();
+            let script = dir.join("nmcli");
+            std::fs::write(
+                &script,
+                format!(
+                    "#!/bin/sh\necho \"$*\" >> '{log}'\nif [ \"$1 $2\" = 'connection up' ] && [ -e '{fail_up}' ]; then\n  rm '{fail_up}'\n  echo 'activation failed' >&2\n  exit 4\nfi\n",
+                    log = log.display(),
+                    fail_up = fail_up.display(),
+                ),
+            )
+            .unwrap();
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+            *TEST_NMCLI_BIN.lock().unwrap() = Some(script.into_os_string());
+            Self { dir, log }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            std::fs::read_to_string(&self.log)
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    impl Drop for FakeNmcli {
+        fn drop(&mut self) {
+            *TEST_NMCLI_BIN.lock().unwrap() = None;
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_up_restores_previous_profile() {
+        let nmcli = FakeNmcli::new();
+        let previous = Ipv4Profile::from_config(&IpConfig::Dhcp);
+        let new = Ipv4Profile::from_config(&static_cfg("192.168.10.20/24", None, &[]));
+
+        let err = switch_profile("uplink", &new, &previous).await.unwrap_err();
+        assert!(err.to_string().contains("activation failed"), "{err}");
+        assert_eq!(
+            nmcli.calls(),
+            [
+                "connection modify uplink ipv4.method manual ipv4.addresses 192.168.10.20/24 ipv4.gateway  ipv4.dns ",
+                "connection up uplink",
+                "connection modify uplink ipv4.method auto ipv4.addresses  ipv4.gateway  ipv4.dns ",
+                "connection up uplink",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_timer_leaves_newer_change_pending() {
+        let now = chrono::Utc::now();
+        let change = PendingChange {
+            interface: "eth0".into(),
+            connection: "uplink".into(),
+            previous: Ipv4Profile::from_config(&IpConfig::Dhcp),
+            applied: static_cfg("192.168.10.20/24", None, &[]),
+            revert_at: now,
+        };
+        let pending = tokio::sync::Mutex::new(Some(change));
+
+        // Timer of an earlier change to eth0 that was confirmed in time.
+        revert_unconfirmed(&pending, "eth0", now - chrono::Duration::seconds(30)).await;
+        assert!(pending.lock().await.is_some());
+    }
+}
@@ -6,12 +6,24 @@
 //! where the telemetry module reads and forwards them to the control plane.
 //!
 //! Hot-swap source switching is supported via a Unix domain socket at
-//! `/tmp/strata-pipeline.sock`.
+//! `/tmp/strata-pipeline.sock`, as is cutting between the extra inputs a
+//! multi-camera stream was started with. The same socket carries resolution
+//! steps from the ABR ladder controller in [`abr`] and forced keyframes for
+//! receiver keyframe requests. Per-element CPU use of the child comes
+//! from its thread accounting in [`threads`].
+
+mod abr;
+mod threads;
 
 use std::process::{Child, ExitStatus};
 use std::time::{Duration, Instant};
 
-use strata_protocol::StreamStartPayload;
+use strata_protocol::{
+    InputSource, LadderStatus, MAIN_INPUT, SourceTransition, StreamStartPayload,
+};
+
+use self::abr::AbrController;
+use self::threads::ThreadCpu;
 
 /// UDP address where strata-node sends stats JSON.
 pub const STATS_LISTEN_ADDR: &str = "127.0.0.1:9100";
@@ -21,6 +33,15 @@ pub const CONTROL_SOCK_PATH: &str = "/tmp/strata-pipeline.sock";
 
 const PIPELINE_STOP_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Minimum spacing between forced keyframes. The receiver already spaces
+/// its requests; this bounds what a misbehaving one can cost the encoder.
+const KEYFRAME_MIN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Fade or black transition length when `source.select` names none.
+const DEFAULT_TRANSITION: Duration = Duration::from_millis(500);
+/// Longest fade or black hold a `source.select` may ask for.
+const MAX_TRANSITION: Duration = Duration::from_secs(5);
+
 #[cfg(test)]
 static TEST_PIPELINE_BIN: std::sync::Mutex<Option<std::ffi::OsString>> =
     std::sync::Mutex::new(None);
@@ -38,6 +59,20 @@ pub struct PipelineManager {
     /// Telemetry overlays these names onto per-link stats so the dashboard
     /// can map every link to a physical interface.
     link_ifaces: Vec<String>,
+    /// Resolution ladder controller, when the stream was started with one.
+    abr: Option<AbrController>,
+    /// When the last receiver keyframe request was forwarded.
+    last_keyframe: Option<Instant>,
+    /// CPU accounting for the running child's threads.
+    thread_cpu: Option<ThreadCpu>,
+    /// Extra inputs the running stream can cut to (besides [`MAIN_INPUT`]).
+    inputs: Vec<InputSource>,
+    /// Virtual links streams are routed through (`--simulate-link`).
+    #[cfg(feature = "simulate")]
+    simulation: Option<std::sync::Arc<crate::simulate::Simulation>>,
+    /// Proxies carrying the running stream over `simulation`.
+    #[cfg(feature = "simulate")]
+    simulated: Option<crate::simulate::Running>,
 }
 
 /// Stats returned when a pipeline is stopped.
@@ -66,7 +101,7 @@ fn send_to_control_socket(msg: &str) -> bool {
     }
 }
 
-fn pipeline_binary() -> std::ffi::OsString {
+pub(crate) fn pipeline_binary() -> std::ffi::OsString {
     #[cfg(test)]
     if let Some(bin) = TEST_PIPELINE_BIN.lock().unwrap().clone() {
         return bin;
@@ -109,9 +144,24 @@ impl PipelineManager {
             started_at: None,
             total_bytes: 0,
             link_ifaces: Vec::new(),
+            abr: None,
+            last_keyframe: None,
+            thread_cpu: None,
+            inputs: Vec::new(),
+            #[cfg(feature = "simulate")]
+            simulation: None,
+            #[cfg(feature = "simulate")]
+            simulated: None,
         }
     }
 
+    /// Route every stream through `simulation`'s virtual links.
+    #[cfg(feature = "simulate")]
+    pub fn with_simulation(mut self, simulation: crate::simulate::Simulation) -> Self {
+        self.simulation = Some(std::sync::Arc::new(simulation));
+        self
+    }
+
     /// Check if a pipeline is currently running.
     ///
     /// Also checks the actual child process — if it has exited but we
@@ -152,8 +202,32 @@ impl PipelineManager {
     ///
     /// `eligible_ifaces` is the sorted list of interfaces allowed to carry
     /// bonded links (admin-enabled + connected + default-routed), from
-    /// `HardwareScanner::eligible_interfaces()`.
-    pub fn start(
+    /// `HardwareScanner::eligible_interfaces()`. With `--simulate-link`
+    /// the links go over the simulated links instead.
+    pub async fn start(
+        &mut self,
+        payload: StreamStartPayload,
+        eligible_ifaces: Vec<String>,
+    ) -> anyhow::Result<()> {
+        #[cfg(feature = "simulate")]
+        if let Some(simulation) = self.simulation.clone() {
+            if self.is_running() {
+                anyhow::bail!(
+                    "pipeline already running (stream {})",
+                    self.stream_id.as_deref().unwrap_or("?")
+                );
+            }
+            let mut payload = payload;
+            let running = simulation.route(&mut payload).await?;
+            self.launch(payload, Vec::new())?;
+            self.link_ifaces = running.link_names();
+            self.simulated = Some(running);
+            return Ok(());
+        }
+        self.launch(payload, eligible_ifaces)
+    }
+
+    fn launch(
         &mut self,
         payload: StreamStartPayload,
         eligible_ifaces: Vec<String>,
@@ -172,13 +246,39 @@ impl PipelineManager {
             "starting pipeline"
         );
 
+        // A bad camera node takes the whole pipeline down when its branch
+        // starts (see control.rs::source_switch), so check inputs up front.
+        for input in &payload.source.inputs {
+            if input.mode == "v4l2"
+                && let Some(device) = input.device.as_deref()
+                && !crate::hardware::is_capture_device(device)
+            {
+                anyhow::bail!("input {}: {device} is not a video capture device", input.id);
+            }
+        }
+        if let Some(logo) = payload
+            .encoder
+            .overlay
+            .as_ref()
+            .and_then(|o| o.logo.as_deref())
+            && !std::path::Path::new(logo).is_file()
+        {
+            anyhow::bail!("overlay logo {logo} not found");
+        }
+
+        let abr = AbrController::new(payload.encoder.ladder.clone());
+
         // Spawn strata-pipeline
         let (child, link_ifaces) = spawn_pipeline(&payload, &eligible_ifaces)?;
+        self.thread_cpu = Some(ThreadCpu::new(child.id()));
         self.child = Some(child);
         self.stream_id = Some(payload.stream_id);
         self.started_at = Some(Instant::now());
         self.total_bytes = 0;
         self.link_ifaces = link_ifaces;
+        self.abr = abr;
+        self.last_keyframe = None;
+        self.inputs = payload.source.inputs;
 
         Ok(())
     }
@@ -209,6 +309,13 @@ impl PipelineManager {
         self.started_at = None;
         self.total_bytes = 0;
         self.link_ifaces.clear();
+        self.abr = None;
+        self.thread_cpu = None;
+        self.inputs.clear();
+        #[cfg(feature = "simulate")]
+        {
+            self.simulated = None;
+        }
 
         tracing::info!(duration_s = stats.duration_s, "pipeline stopped");
         stats
@@ -255,6 +362,40 @@ impl PipelineManager {
         }
     }
 
+    /// Cut a multi-camera stream to `target`, one of the inputs it was
+    /// started with or [`MAIN_INPUT`]. The pipeline makes the cut on the
+    /// target's first frame, so a camera that isn't delivering never
+    /// replaces the picture.
+    pub fn select_source(
+        &self,
+        target: &str,
+        transition: SourceTransition,
+        duration_ms: Option<u32>,
+    ) -> Result<(), String> {
+        if !self.has_stream() {
+            return Err("no pipeline running".into());
+        }
+        if target != MAIN_INPUT && !self.inputs.iter().any(|i| i.id == target) {
+            return Err(format!("unknown input {target:?}"));
+        }
+        let duration = duration_ms
+            .map_or(DEFAULT_TRANSITION, |ms| Duration::from_millis(ms.into()))
+            .min(MAX_TRANSITION);
+
+        let cmd = serde_json::json!({
+            "cmd": "select_input",
+            "target": target,
+            "transition": transition,
+            "duration_ms": duration.as_millis() as u64,
+        });
+        if send_to_control_socket(&format!("{cmd}\n")) {
+            tracing::info!(target, ?transition, "input select command sent");
+            Ok(())
+        } else {
+            Err("failed to send to pipeline control socket".into())
+        }
+    }
+
     /// Tell the running pipeline to enable or disable a bonding link
     /// associated with the given OS interface name.
     ///
@@ -279,6 +420,71 @@ impl PipelineManager {
         }
     }
 
+    /// Feed the bonding adapter's commanded bitrate to the ABR ladder
+    /// controller and return the rung to report in `stream.stats`.
+    ///
+    /// A step is only committed once the pipeline has taken the new
+    /// resolution; a failed send is retried on the next sample.
+    pub fn update_ladder(&mut self, recommended_kbps: Option<u32>) -> Option<LadderStatus> {
+        let abr = self.abr.as_mut()?;
+        if let Some(target) = recommended_kbps.and_then(|kbps| abr.observe(kbps, Instant::now())) {
+            let from = abr.rung().resolution.clone();
+            let to = abr.rung_at(target).resolution.clone();
+            let sent = strata_common::validation::parse_resolution(&to).is_some_and(|(w, h)| {
+                let cmd = serde_json::json!({
+                    "cmd": "set_resolution",
+                    "width": w,
+                    "height": h,
+                });
+                send_to_control_socket(&format!("{}\n", cmd))
+            });
+            if sent {
+                tracing::info!(from = %from, to = %to, kbps = ?recommended_kbps, "ABR ladder step");
+                abr.step_to(target);
+            }
+        }
+        Some(abr.status())
+    }
+
+    /// Force an early IDR for a receiver keyframe request (relayed by
+    /// strata-pipeline as a `keyframe_requested` bonding event).
+    ///
+    /// Returns `false` when there is no pipeline, the request falls within
+    /// [`KEYFRAME_MIN_INTERVAL`] of the last one, or the send fails.
+    pub fn request_keyframe(&mut self, request_id: Option<u64>) -> bool {
+        if !self.has_stream() {
+            return false;
+        }
+        let now = Instant::now();
+        if self
+            .last_keyframe
+            .is_some_and(|t| now.duration_since(t) < KEYFRAME_MIN_INTERVAL)
+        {
+            tracing::debug!(?request_id, "keyframe request within min interval, skipped");
+            return false;
+        }
+        let cmd = serde_json::json!({
+            "cmd": "force_keyframe",
+            "request_id": request_id,
+        });
+        if !send_to_control_socket(&format!("{}\n", cmd)) {
+            return false;
+        }
+        self.last_keyframe = Some(now);
+        tracing::info!(?request_id, "forced keyframe for receiver request");
+        true
+    }
+
+    /// CPU use per pipeline element since the previous call, in percent
+    /// of one core. Empty when no pipeline is running and on the first
+    /// call after a start.
+    pub fn element_cpu(&mut self) -> Vec<(String, f32)> {
+        self.thread_cpu
+            .as_mut()
+            .map(ThreadCpu::sample)
+            .unwrap_or_default()
+    }
+
     /// Send an arbitrary JSON command to the running strata-node process.
     ///
     /// Returns `true` if the command was sent successfully.
@@ -308,6 +514,12 @@ impl PipelineManager {
                 self.started_at = None;
                 self.total_bytes = 0;
                 self.link_ifaces.clear();
+                self.abr = None;
+                self.thread_cpu = None;
+                #[cfg(feature = "simulate")]
+                {
+                    self.simulated = None;
+                }
 
                 Some(ChildExitInfo {
                     stream_id,
@@ -367,6 +579,12 @@ fn spawn_pipeline(
         }
     }
 
+    // Switchable inputs, built up front so a cut never waits on a camera
+    if !payload.source.inputs.is_empty() {
+        cmd.arg("--inputs")
+            .arg(serde_json::to_string(&payload.source.inputs)?);
+    }
+
     // Encoder
     cmd.arg("--bitrate")
         .arg(payload.encoder.bitrate_kbps.to_string());
@@ -399,6 +617,25 @@ fn spawn_pipeline(
         cmd.arg("--resolution").arg(res);
     }
 
+    // Branding / confidence graphics, burned in ahead of the encoder
+    if let Some(ref overlay) = payload.encoder.overlay {
+        if let Some(ref logo) = overlay.logo {
+            cmd.arg("--logo").arg(logo);
+            cmd.arg("--logo-position")
+                .arg(overlay.logo_position.as_str());
+        }
+        if let Some(ref text) = overlay.text {
+            cmd.arg("--overlay-text").arg(text);
+        }
+        if overlay.clock {
+            cmd.arg("--clock");
+        }
+        if overlay.text.is_some() || overlay.clock {
+            cmd.arg("--text-position")
+                .arg(overlay.text_position.as_str());
+        }
+    }
+
     // Always add audio for RTMP compatibility
     cmd.arg("--audio");
 
@@ -469,6 +706,9 @@ fn spawn_pipeline(
             config_tbl.insert("links".into(), toml::Value::Array(links));
         }
     }
+    if let Some(key) = &payload.ingest_key {
+        config_tbl.insert("ingest_key".into(), toml::Value::String(key.clone()));
+    }
     if !config_tbl.is_empty() {
         let config_path = format!("/tmp/strata-stream-{}.toml", payload.stream_id);
         match toml::to_string_pretty(&toml::Value::Table(config_tbl)) {
@@ -627,6 +867,7 @@ mod tests {
                 resolution: None,
                 framerate: None,
                 passthrough: None,
+                inputs: Vec::new(),
             },
             encoder: EncoderConfig {
                 bitrate_kbps: 1_000,
@@ -635,11 +876,14 @@ mod tests {
                 codec: None,
                 min_bitrate_kbps: None,
                 max_bitrate_kbps: None,
+                ladder: Vec::new(),
+                overlay: None,
             },
             destinations: Vec::new(),
             bonding_config: serde_json::Value::Null,
             psk: None,
             relay_url: None,
+            ingest_key: None,
         }
     }
 
@@ -660,7 +904,7 @@ mod tests {
         let _guard = set_test_pipeline_bin(&script.script);
         let mut manager = PipelineManager::new();
 
-        manager.start(sample_payload(), Vec::new()).unwrap();
+        manager.launch(sample_payload(), Vec::new()).unwrap();
         script.wait_for_marker("started");
         let pid = script.pid();
 
@@ -678,7 +922,7 @@ mod tests {
         let _guard = set_test_pipeline_bin(&script.script);
         let mut manager = PipelineManager::new();
 
-        manager.start(sample_payload(), Vec::new()).unwrap();
+        manager.launch(sample_payload(), Vec::new()).unwrap();
         script.wait_for_marker("started");
         let pid = script.pid();
 
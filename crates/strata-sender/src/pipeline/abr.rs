@@ -0,0 +1,192 @@
+//! ABR ladder controller — steps the encoder resolution with the link.
+//!
+//! The bonding adapter inside strata-pipeline already retunes the encoder
+//! bitrate every tick from aggregate link capacity; its commanded target
+//! reaches the agent as `current_bitrate_bps` in the relayed stats. That
+//! alone only thins the picture: a 1080p frame at 800 kbps is a smear. A
+//! stream started with a ladder gets this controller on top, which moves
+//! the encoder down to a smaller resolution when the recommendation stays
+//! below the current rung's `min_kbps`, and back up once it clears the
+//! rung above.
+//!
+//! Hysteresis keeps a link hovering around a threshold from bouncing the
+//! resolution (every step costs a keyframe): a step down waits for
+//! [`DOWN_HOLD`] of sustained shortfall and may skip rungs, a step up
+//! waits for [`UP_HOLD`] of headroom of [`UP_MARGIN`] over the rung above
+//! and climbs one rung at a time.
+
+use std::time::{Duration, Instant};
+
+use strata_protocol::{LadderRung, LadderStatus};
+
+/// How long the recommendation must stay below the current rung before
+/// stepping down. Short enough to spare most of a bad patch, long enough
+/// to ride out a single HARQ stall.
+pub const DOWN_HOLD: Duration = Duration::from_secs(3);
+
+/// How long the recommendation must clear the rung above (with margin)
+/// before stepping up.
+pub const UP_HOLD: Duration = Duration::from_secs(15);
+
+/// Headroom over the rung above required to climb to it.
+pub const UP_MARGIN: f64 = 1.25;
+
+pub struct AbrController {
+    ladder: Vec<LadderRung>,
+    rung: usize,
+    /// When the recommendation first fell below the current rung.
+    below_since: Option<Instant>,
+    /// When the recommendation first cleared the rung above.
+    above_since: Option<Instant>,
+    /// Rung left since the last [`AbrController::status`].
+    stepped_from: Option<usize>,
+}
+
+impl AbrController {
+    /// A controller on the ladder's top rung; `None` for an empty ladder.
+    pub fn new(ladder: Vec<LadderRung>) -> Option<Self> {
+        if ladder.is_empty() {
+            return None;
+        }
+        Some(Self {
+            ladder,
+            rung: 0,
+            below_since: None,
+            above_since: None,
+            stepped_from: None,
+        })
+    }
+
+    /// The rung the encoder is on.
+    pub fn rung(&self) -> &LadderRung {
+        &self.ladder[self.rung]
+    }
+
+    pub fn rung_at(&self, index: usize) -> &LadderRung {
+        &self.ladder[index]
+    }
+
+    /// Feed one bitrate recommendation (kbps). Returns the rung to step to
+    /// when a hold has elapsed; the caller applies it with
+    /// [`AbrController::step_to`] once the pipeline has taken it.
+    pub fn observe(&mut self, recommended_kbps: u32, now: Instant) -> Option<usize> {
+        let bottom = self.ladder.len() - 1;
+        if recommended_kbps < self.ladder[self.rung].min_kbps && self.rung < bottom {
+            self.above_since = None;
+            let since = *self.below_since.get_or_insert(now);
+            if now.duration_since(since) < DOWN_HOLD {
+                return None;
+            }
+            // Straight to the highest rung the recommendation still fits.
+            let fits = (self.rung + 1..=bottom)
+                .find(|&i| recommended_kbps >= self.ladder[i].min_kbps)
+                .unwrap_or(bottom);
+            return Some(fits);
+        }
+        self.below_since = None;
+
+        let climbs = self.rung > 0
+            && f64::from(recommended_kbps)
+                >= f64::from(self.ladder[self.rung - 1].min_kbps) * UP_MARGIN;
+        if !climbs {
+            self.above_since = None;
+            return None;
+        }
+        let since = *self.above_since.get_or_insert(now);
+        (now.duration_since(since) >= UP_HOLD).then(|| self.rung - 1)
+    }
+
+    /// Move to `rung` and restart both holds.
+    pub fn step_to(&mut self, rung: usize) {
+        self.stepped_from.get_or_insert(self.rung);
+        self.rung = rung;
+        self.below_since = None;
+        self.above_since = None;
+    }
+
+    /// The current rung for the next `stream.stats`; a step is reported
+    /// on the first sample after it.
+    pub fn status(&mut self) -> LadderStatus {
+        LadderStatus {
+            rung: self.rung as u32,
+            resolution: self.rung().resolution.clone(),
+            stepped_from: self.stepped_from.take().map(|r| r as u32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ladder() -> AbrController {
+        let rung = |resolution: &str, min_kbps| LadderRung {
+            resolution: resolution.into(),
+            min_kbps,
+        };
+        AbrController::new(vec![
+            rung("1920x1080", 4000),
+            rung("1280x720", 2000),
+            rung("854x480", 800),
+        ])
+        .unwrap()
+    }
+
+    /// Feed `kbps` once a second for `secs` seconds from `start`,
+    /// applying any step; returns the time after the last sample.
+    fn run(abr: &mut AbrController, start: Instant, secs: u64, kbps: u32) -> Instant {
+        for s in 0..secs {
+            if let Some(rung) = abr.observe(kbps, start + Duration::from_secs(s)) {
+                abr.step_to(rung);
+            }
+        }
+        start + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn steps_down_only_after_a_sustained_shortfall() {
+        let mut abr = ladder();
+        let t = Instant::now();
+
+        // A two-second dip is ridden out.
+        let t = run(&mut abr, t, 2, 3000);
+        let t = run(&mut abr, t, 5, 5000);
+        assert_eq!(abr.rung().resolution, "1920x1080");
+
+        // A sustained one skips straight to the rung that fits.
+        run(&mut abr, t, 4, 1000);
+        let status = abr.status();
+        assert_eq!(status.resolution, "854x480");
+        assert_eq!(status.stepped_from, Some(0));
+        assert_eq!(abr.status().stepped_from, None, "a step is reported once");
+    }
+
+    #[test]
+    fn climbs_one_rung_after_sustained_headroom() {
+        let mut abr = ladder();
+        let t = run(&mut abr, Instant::now(), 4, 1000);
+        assert_eq!(abr.rung().resolution, "854x480");
+
+        // Just over the rung above isn't enough headroom.
+        let t = run(&mut abr, t, 30, 2200);
+        assert_eq!(abr.rung().resolution, "854x480");
+
+        // Headroom resets if it lapses before the hold.
+        let t = run(&mut abr, t, 10, 6000);
+        let t = run(&mut abr, t, 1, 2200);
+        let t = run(&mut abr, t, 10, 6000);
+        assert_eq!(abr.rung().resolution, "854x480");
+
+        run(&mut abr, t, 16, 6000);
+        assert_eq!(abr.rung().resolution, "1280x720");
+    }
+
+    #[test]
+    fn bottom_rung_holds_whatever_the_recommendation() {
+        let mut abr = ladder();
+        let t = run(&mut abr, Instant::now(), 4, 100);
+        run(&mut abr, t, 60, 100);
+        assert_eq!(abr.rung().resolution, "854x480");
+        assert!(AbrController::new(Vec::new()).is_none());
+    }
+}
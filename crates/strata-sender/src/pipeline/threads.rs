@@ -0,0 +1,133 @@
+//! Per-element CPU accounting for the strata-pipeline child.
+//!
+//! GStreamer names each streaming thread after the pad task that runs it
+//! (`<element>:<pad>`), and that thread carries every element downstream
+//! of the pad up to the next queue: the thread behind the capture queue
+//! scales and encodes the video, `mux:src` muxes and hands packets to the
+//! bonding sink. Encoder libraries that spawn their own workers (x264)
+//! name them after the spawning thread, so their time lands on the same
+//! element. Threads are grouped by the element part of their name; the
+//! rest (the bonding worker, the stats thread) keep their own names.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+pub struct ThreadCpu {
+    pid: u32,
+    /// Cumulative CPU ticks per element at the previous sample.
+    last: HashMap<String, u64>,
+    last_at: Option<Instant>,
+}
+
+impl ThreadCpu {
+    pub fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            last: HashMap::new(),
+            last_at: None,
+        }
+    }
+
+    /// CPU use per element since the previous call, in percent of one
+    /// core, sorted by element name. Empty on the first call and once
+    /// the process is gone.
+    pub fn sample(&mut self) -> Vec<(String, f32)> {
+        let now = Instant::now();
+        let ticks = read_element_ticks(self.pid);
+        let mut usage = Vec::new();
+        if let Some(at) = self.last_at {
+            let secs = now.duration_since(at).as_secs_f64();
+            if secs > 0.0 {
+                for (element, &total) in &ticks {
+                    let prev = self.last.get(element).copied().unwrap_or(0);
+                    // A thread that exited takes its ticks with it; read
+                    // that as idle rather than negative.
+                    let delta = total.saturating_sub(prev);
+                    let percent = delta as f64 / clock_ticks_per_sec() / secs * 100.0;
+                    usage.push((element.clone(), percent as f32));
+                }
+            }
+        }
+        usage.sort_by(|a, b| a.0.cmp(&b.0));
+        self.last = ticks;
+        self.last_at = Some(now);
+        usage
+    }
+}
+
+/// Cumulative user+system ticks of every thread of `pid`, summed per
+/// element.
+fn read_element_ticks(pid: u32) -> HashMap<String, u64> {
+    let mut ticks = HashMap::new();
+    let Ok(tasks) = std::fs::read_dir(format!("/proc/{pid}/task")) else {
+        return ticks;
+    };
+    for task in tasks.flatten() {
+        let Ok(stat) = std::fs::read_to_string(task.path().join("stat")) else {
+            continue;
+        };
+        if let Some((comm, cpu)) = parse_stat(&stat) {
+            *ticks.entry(element_of(comm).to_string()).or_insert(0) += cpu;
+        }
+    }
+    ticks
+}
+
+/// Thread name and user+system ticks from a `/proc/<pid>/task/<tid>/stat`
+/// line. The name is parenthesized and may itself contain spaces or
+/// parentheses, so fields are counted from the last `)`.
+fn parse_stat(stat: &str) -> Option<(&str, u64)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let comm = stat.get(open + 1..close)?;
+    let mut fields = stat.get(close + 1..)?.split_whitespace();
+    // After the name: state, ppid, pgrp, session, tty_nr, tpgid, flags,
+    // minflt, cminflt, majflt, cmajflt, then utime and stime.
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some((comm, utime + stime))
+}
+
+/// The element a streaming thread belongs to: `enc:src` → `enc`.
+fn element_of(comm: &str) -> &str {
+    comm.split_once(':').map_or(comm, |(element, _)| element)
+}
+
+fn clock_ticks_per_sec() -> f64 {
+    // SAFETY: sysconf has no preconditions; it only reads a constant.
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if hz > 0 { hz as f64 } else { 100.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_thread_names_with_spaces_and_parens() {
+        let stat = "4242 (testq:src) R 1 2 3 0 -1 4194368 10 0 0 0 700 55 0 0 20 0 9 0";
+        assert_eq!(parse_stat(stat), Some(("testq:src", 755)));
+        let odd = "7 (a (b) c) S 1 2 3 0 -1 0 0 0 0 0 3 4 0 0 20 0 1 0";
+        assert_eq!(parse_stat(odd), Some(("a (b) c", 7)));
+        assert_eq!(parse_stat("7 (short) S 1 2"), None);
+
+        assert_eq!(element_of("mux:src"), "mux");
+        assert_eq!(element_of("strata-worker"), "strata-worker");
+    }
+
+    #[test]
+    fn samples_this_process() {
+        let mut cpu = ThreadCpu::new(std::process::id());
+        assert!(cpu.sample().is_empty(), "first sample is the baseline");
+
+        let spin = Instant::now();
+        while spin.elapsed() < std::time::Duration::from_millis(50) {
+            std::hint::black_box(0u64);
+        }
+        let usage = cpu.sample();
+        assert!(!usage.is_empty());
+        assert!(usage.iter().all(|(_, pct)| pct.is_finite() && *pct >= 0.0));
+
+        assert!(ThreadCpu::new(u32::MAX).sample().is_empty());
+    }
+}
@@ -57,9 +57,8 @@ pub async fn run(state: Arc<AgentState>) {
             // Release the lock before sending
             drop(pipeline);
 
-            let envelope = Envelope::from_message(&AgentMessage::StreamEnded(ended));
-            if let Ok(json) = envelope.and_then(|e| serde_json::to_string(&e)) {
-                let _ = state.control_tx.send(json).await;
+            if let Ok(envelope) = Envelope::from_message(&AgentMessage::StreamEnded(ended)) {
+                let _ = state.control_tx.send(envelope).await;
             }
         }
     }
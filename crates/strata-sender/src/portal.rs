@@ -3,11 +3,27 @@
 //! Accessed over the sender's Wi-Fi AP (or wired) at `http://10.42.0.1/`
 //! (or `http://localhost:3001` in dev). Provides:
 //!
+//! - First-run setup wizard (interfaces → receiver → connectivity →
+//!   enrollment), progress kept in the agent config store
 //! - Hardware status dashboard (interfaces, inputs, system stats)
+//! - Live per-interface throughput, RTT and loss while streaming
 //! - Enrollment / unenrollment to the cloud control plane
 //! - Receiver address management
-//! - Network interface management (enable/disable/discover)
+//! - Local stream start/stop for venues without a path to the cloud
+//! - Network interface management (enable/disable/discover, DHCP or
+//!   static addressing)
+//! - Cellular SIM status, PIN/PUK unlock and APN editing (HiLink modems)
 //! - Connectivity testing
+//! - Log viewer and support-bundle download (see [`crate::diagnostics`])
+//! - Component versions and signed offline update upload (see
+//!   [`crate::local_update`])
+//!
+//! Open to anyone on the local network unless the control plane has set a
+//! portal PIN (see [`crate::portal_auth`]); then every API call but login
+//! needs a session cookie.
+//!
+//! The same app is also served over HTTPS (`--portal-https-addr`) once the
+//! control plane has pushed a certificate (`tls.install`).
 //!
 //! The UI is a single inline HTML page (`PORTAL_PAGE`) served at `/` —
 //! the former Leptos WASM SPA (`strata-portal` crate) was retired 2026-07-01.
@@ -15,20 +31,44 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{DefaultBodyLimit, Path, Query, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
 use axum::response::{Html, IntoResponse};
 use axum::routing::{get, post};
 use axum::{Json, Router};
 use serde::Deserialize;
+use strata_common::ids::EnrollmentTokenError;
+use strata_common::tls::server::TlsListener;
 
 use crate::AgentState;
+use crate::config_store::SetupStep;
+use crate::local_stream::{self, LocalStreamRequest};
+use crate::netconfig::{self, IpConfig};
+use crate::portal_auth::{self, LoginError};
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+/// Upload cap for offline update bundles (a release bundle is ~40 MB).
+const MAX_UPDATE_BUNDLE_BYTES: usize = 256 * 1024 * 1024;
+
+fn api_error(status: StatusCode, message: impl std::fmt::Display) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({ "error": message.to_string() })),
+    )
+}
 
 /// Start the onboarding portal HTTP server.
-pub async fn run(state: Arc<AgentState>, addr: SocketAddr) -> anyhow::Result<()> {
+pub async fn run(
+    state: Arc<AgentState>,
+    addr: SocketAddr,
+    https_addr: Option<SocketAddr>,
+) -> anyhow::Result<()> {
     let app = Router::new()
         // API
         .route("/api/status", get(api_status))
+        .route("/api/setup", get(api_get_setup).post(api_update_setup))
         .route("/api/enroll", post(api_enroll))
         .route("/api/unenroll", post(api_unenroll))
         .route("/api/test", get(api_test))
@@ -39,6 +79,38 @@ pub async fn run(state: Arc<AgentState>, addr: SocketAddr) -> anyhow::Result<()>
             post(api_interface_disable),
         )
         .route("/api/interfaces/scan", post(api_interfaces_scan))
+        .route(
+            "/api/interfaces/{name}/ip",
+            get(api_interface_ip).post(api_set_interface_ip),
+        )
+        .route(
+            "/api/interfaces/{name}/ip/confirm",
+            post(api_confirm_interface_ip),
+        )
+        .route("/api/interfaces/{name}/sim", get(api_interface_sim))
+        .route(
+            "/api/interfaces/{name}/sim/unlock",
+            post(api_interface_sim_unlock),
+        )
+        .route("/api/interfaces/{name}/apn", post(api_interface_apn))
+        .route("/api/links", get(api_links))
+        .route("/api/stream/start", post(api_stream_start))
+        .route("/api/stream/stop", post(api_stream_stop))
+        .route("/api/logs", get(api_logs))
+        .route("/api/diagnostics/bundle", get(api_diagnostics_bundle))
+        .route("/api/version", get(api_version))
+        .route(
+            "/api/update",
+            post(api_update).layer(DefaultBodyLimit::max(MAX_UPDATE_BUNDLE_BYTES)),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_session,
+        ))
+        // Login (always reachable)
+        .route("/api/auth", get(api_auth_status))
+        .route("/api/auth/login", post(api_login))
+        .route("/api/auth/logout", post(api_logout))
         // Prometheus metrics endpoint
         .route("/metrics", get(api_metrics))
         // Captive portal probes (redirect to /)
@@ -47,7 +119,21 @@ pub async fn run(state: Arc<AgentState>, addr: SocketAddr) -> anyhow::Result<()>
         .route("/connecttest.txt", get(captive_redirect))
         // Minimal inline status/enrollment page
         .fallback(get(portal_page))
-        .with_state(state);
+        .with_state(state.clone());
+
+    // HTTPS alongside, not instead: captive-portal probes and first-run
+    // setup happen before any certificate can have been pushed.
+    if let Some(https_addr) = https_addr {
+        let tcp = tokio::net::TcpListener::bind(https_addr).await?;
+        let listener = TlsListener::new(tcp, state.tls.resolver())?;
+        let app = app.clone();
+        tracing::info!("sender portal on https://{https_addr}");
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::error!(error = %e, "https portal failed");
+            }
+        });
+    }
 
     tracing::info!("sender portal on http://{addr}");
     let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -55,6 +141,99 @@ pub async fn run(state: Arc<AgentState>, addr: SocketAddr) -> anyhow::Result<()>
     Ok(())
 }
 
+// ── Portal PIN gate ─────────────────────────────────────────────────
+
+fn session_token(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get(header::COOKIE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(portal_auth::session_from_cookies)
+}
+
+fn has_session(state: &AgentState, headers: &axum::http::HeaderMap) -> bool {
+    session_token(headers).is_some_and(|t| state.portal_auth.is_valid(t, std::time::Instant::now()))
+}
+
+async fn require_session(
+    State(state): State<Arc<AgentState>>,
+    req: Request,
+    next: Next,
+) -> Result<axum::response::Response, ApiError> {
+    if state.config.get().portal_pin_hash.is_some() && !has_session(&state, req.headers()) {
+        return Err(api_error(StatusCode::UNAUTHORIZED, "portal login required"));
+    }
+    Ok(next.run(req).await)
+}
+
+async fn api_auth_status(
+    State(state): State<Arc<AgentState>>,
+    headers: axum::http::HeaderMap,
+) -> Json<serde_json::Value> {
+    let required = state.config.get().portal_pin_hash.is_some();
+    Json(serde_json::json!({
+        "required": required,
+        "authenticated": !required || has_session(&state, &headers),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct LoginRequest {
+    pin: String,
+}
+
+async fn api_login(
+    State(state): State<Arc<AgentState>>,
+    Json(body): Json<LoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Some(hash) = state.config.get().portal_pin_hash else {
+        return Err(api_error(
+            StatusCode::BAD_REQUEST,
+            "this portal has no PIN set",
+        ));
+    };
+    let token = state
+        .portal_auth
+        .login(&hash, &body.pin, std::time::Instant::now())
+        .map_err(|e| {
+            let status = match e {
+                LoginError::LockedOut(_) => StatusCode::TOO_MANY_REQUESTS,
+                LoginError::WrongPin { .. } => StatusCode::UNAUTHORIZED,
+            };
+            api_error(status, e)
+        })?;
+    tracing::info!("portal login");
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            format!(
+                "{}={token}; Path=/; HttpOnly; SameSite=Strict",
+                portal_auth::SESSION_COOKIE
+            ),
+        )],
+        Json(serde_json::json!({ "status": "ok" })),
+    ))
+}
+
+async fn api_logout(
+    State(state): State<Arc<AgentState>>,
+    headers: axum::http::HeaderMap,
+) -> impl IntoResponse {
+    if let Some(token) = session_token(&headers) {
+        state.portal_auth.logout(token);
+    }
+    (
+        [(
+            header::SET_COOKIE,
+            format!(
+                "{}=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0",
+                portal_auth::SESSION_COOKIE
+            ),
+        )],
+        Json(serde_json::json!({ "status": "logged_out" })),
+    )
+}
+
 // ── Captive portal redirect ─────────────────────────────────────────
 
 async fn captive_redirect() -> impl IntoResponse {
@@ -67,7 +246,7 @@ async fn portal_page() -> Html<&'static str> {
     Html(PORTAL_PAGE)
 }
 
-const PORTAL_PAGE: &str = r#"<!doctype html>
+const PORTAL_PAGE: &str = r##"<!doctype html>
 <html lang="en">
 <head>
 <meta charset="utf-8">
@@ -85,62 +264,597 @@ const PORTAL_PAGE: &str = r#"<!doctype html>
   #msg { margin-top: .5rem; min-height: 1.2em; }
   table { border-collapse: collapse; margin-top: 1rem; width: 100%; }
   td, th { text-align: left; padding: .2rem .6rem .2rem 0; border-bottom: 1px solid #ddd; }
+  fieldset { margin-top: 1.5rem; border: 1px solid #ddd; display: grid; gap: .5rem; }
+  select { padding: .4rem; font-size: 1rem; }
+  #ip_static:not([hidden]) { display: grid; gap: .5rem; }
+  #wizard:not([hidden]) { display: grid; gap: .75rem; }
+  #wz_steps { display: flex; gap: 1rem; padding: 0; list-style: none; color: #888; }
+  #wz_steps .current { color: #222; font-weight: 600; }
+  #wz_steps .ok { color: #1a7f37; }
+  #wizard [data-panel]:not([hidden]) { display: grid; gap: .5rem; }
+  #stale { background: #fff4e5; border: 1px solid #f0b45b; padding: .4rem .6rem; }
+  body.stale #main dl, body.stale #ifaces { opacity: .55; }
+  #logs { max-height: 20rem; overflow: auto; font-size: .75rem; background: #f6f6f6; padding: .5rem; white-space: pre-wrap; }
 </style>
 </head>
 <body>
 <h1>Strata Sender</h1>
+<form id="login" hidden>
+  <p>This unit's portal is protected. Enter the portal PIN set in the dashboard.</p>
+  <input id="pin" type="password" placeholder="PIN" autocomplete="current-password" required>
+  <button>Unlock</button>
+  <div id="login_msg"></div>
+</form>
+<section id="wizard" hidden>
+  <ol id="wz_steps">
+    <li>1. Interfaces</li><li>2. Receiver</li><li>3. Connectivity</li><li>4. Enroll</li>
+  </ol>
+  <div data-panel="interfaces" hidden>
+    <p>Plug in the modems and network cables. At least one interface should show as connected.</p>
+    <ul id="wz_ifaces"></ul>
+  </div>
+  <div data-panel="receiver" hidden>
+    <p>Where should this unit send its stream? Leave blank if the control plane assigns one.</p>
+    <input id="wz_receiver" placeholder="Receiver, e.g. receiver.example.com:5000">
+  </div>
+  <div data-panel="connectivity" hidden>
+    <p>Checking that the control plane and receiver can be reached.</p>
+    <ul id="wz_test"></ul>
+    <button id="wz_retest">Test again</button>
+  </div>
+  <div data-panel="enroll" hidden>
+    <p>Enter the enrollment token from the dashboard, or leave it blank to run this unit from the portal only.</p>
+    <input id="wz_token" placeholder="Enrollment token">
+    <input id="wz_control_url" placeholder="Control URL (optional, e.g. wss://control.example.com/agent/ws)">
+  </div>
+  <div><button id="wz_back">Back</button> <button id="wz_next">Next</button></div>
+  <div id="wz_msg"></div>
+  <a href="#" id="wz_skip">Skip setup</a>
+</section>
+<div id="main" hidden>
+<div id="stale" hidden></div>
 <dl>
   <dt>Enrolled</dt><dd id="enrolled">…</dd>
   <dt>Sender ID</dt><dd id="sender_id">…</dd>
   <dt>Control plane</dt><dd id="cloud">…</dd>
   <dt>Streaming</dt><dd id="streaming">…</dd>
   <dt>CPU / Mem</dt><dd id="sys">…</dd>
+  <dt>Software</dt><dd id="versions">…</dd>
 </dl>
-<table id="ifaces" hidden><thead><tr><th>Interface</th><th>State</th></tr></thead><tbody></tbody></table>
+<table id="ifaces" hidden><thead><tr><th>Interface</th><th>State</th><th>Throughput</th><th>RTT</th><th>Loss</th><th></th></tr></thead><tbody></tbody></table>
 <form id="enroll">
   <input id="token" placeholder="Enrollment token" required>
   <input id="control_url" placeholder="Control URL (optional, e.g. wss://control.example.com/agent/ws)">
   <button>Enroll</button>
 </form>
 <button id="unenroll" hidden>Unenroll</button>
+<fieldset id="stream">
+  <legend>Stream</legend>
+  <input id="st_receiver" placeholder="Receiver, e.g. receiver.example.com:5000">
+  <select id="st_source">
+    <option value="test">Test pattern</option>
+    <option value="uri">URI / file</option>
+  </select>
+  <input id="st_uri" placeholder="Source URI, e.g. rtsp://camera.local/stream" hidden>
+  <select id="st_res">
+    <option>1920x1080</option>
+    <option>1280x720</option>
+  </select>
+  <input id="st_bitrate" type="number" min="300" max="50000" step="100" placeholder="Bitrate kbps (blank = auto)">
+  <button id="st_start">Start stream</button>
+  <button id="st_stop" hidden>Stop stream</button>
+  <div id="st_msg"></div>
+</fieldset>
+<fieldset id="ipcfg" hidden>
+  <legend>IP settings</legend>
+  <select id="ip_iface"></select>
+  <label><input type="radio" name="ip_mode" value="dhcp" checked> DHCP</label>
+  <label><input type="radio" name="ip_mode" value="static"> Static</label>
+  <div id="ip_static" hidden>
+    <input id="ip_address" placeholder="Address, e.g. 192.168.10.20/24">
+    <input id="ip_gateway" placeholder="Gateway, e.g. 192.168.10.1">
+    <input id="ip_dns" placeholder="DNS servers, comma-separated">
+  </div>
+  <button id="ip_apply">Apply</button>
+  <div id="ip_msg"></div>
+</fieldset>
+<fieldset id="sim" hidden>
+  <legend>SIM</legend>
+  <select id="sim_iface"></select>
+  <dl id="sim_info"></dl>
+  <div id="sim_unlock" hidden>
+    <input id="sim_puk" inputmode="numeric" placeholder="PUK (from the SIM carrier card)" hidden>
+    <input id="sim_pin" inputmode="numeric" placeholder="SIM PIN" autocomplete="off">
+    <button id="sim_unlock_btn">Unlock SIM</button>
+  </div>
+  <input id="sim_apn" placeholder="APN, e.g. internet">
+  <button id="sim_apn_btn">Save APN</button>
+  <div id="sim_msg"></div>
+</fieldset>
+<fieldset id="diag">
+  <legend>Diagnostics</legend>
+  <div>
+    <input id="log_filter" placeholder="Filter, e.g. pipeline or wwan0">
+    <select id="log_lines"><option>100</option><option selected>200</option><option>500</option><option>2000</option></select>
+    <label><input id="log_follow" type="checkbox"> Follow</label>
+    <button id="log_load">Show logs</button>
+  </div>
+  <pre id="logs" hidden></pre>
+  <a href="/api/diagnostics/bundle" id="diag_bundle" download>Download diagnostics bundle</a>
+</fieldset>
+<fieldset id="update">
+  <legend>Software update</legend>
+  <p id="up_info">Upload a signed update bundle (.tar.gz) for sites without internet access.</p>
+  <input id="up_file" type="file" accept=".tar.gz,.tgz,application/gzip">
+  <label id="up_force_row" hidden><input id="up_force" type="checkbox"> Update while streaming (drops the stream)</label>
+  <label><input id="up_downgrade" type="checkbox"> Allow reinstalling or downgrading (bundle must be signed for it)</label>
+  <button id="up_send">Upload and install</button>
+  <progress id="up_progress" max="1" value="0" hidden></progress>
+  <div id="up_msg"></div>
+</fieldset>
 <div id="msg"></div>
+<p><a href="#" id="rerun">Run setup again</a> <a href="#" id="logout" hidden>Log out</a></p>
+</div>
 <script>
 const $ = id => document.getElementById(id);
+function showLogin() {
+  $('login').hidden = false;
+  $('wizard').hidden = true;
+  $('main').hidden = true;
+}
+$('login').addEventListener('submit', async ev => {
+  ev.preventDefault();
+  const r = await fetch('/api/auth/login', { method: 'POST', headers: {'content-type': 'application/json'},
+    body: JSON.stringify({ pin: $('pin').value }) });
+  if (r.ok) { location.reload(); return; }
+  $('pin').value = '';
+  $('login_msg').textContent = (await r.json()).error || r.statusText;
+});
+$('logout').addEventListener('click', async ev => {
+  ev.preventDefault();
+  localStorage.removeItem(STATUS_CACHE);
+  await fetch('/api/auth/logout', { method: 'POST' });
+  location.reload();
+});
+// The last good status is kept in localStorage, so while the agent is
+// briefly unreachable (e.g. restarting after an update) the page keeps
+// showing it under a "stale" banner instead of going blank.
+const STATUS_CACHE = 'strata.portal.status';
+let statusShown = false;
+function renderStatus(s) {
+  statusShown = true;
+  $('enrolled').textContent = s.enrolled ? 'yes' : 'no';
+  $('enrolled').className = s.enrolled ? 'ok' : 'bad';
+  $('sender_id').textContent = s.sender_id || '—';
+  $('cloud').textContent = s.cloud_connected ? 'connected' : 'disconnected';
+  $('cloud').className = s.cloud_connected ? 'ok' : 'bad';
+  $('streaming').textContent = s.streaming ? ('live (' + (s.stream_id || '?') + ')') : 'idle';
+  $('sys').textContent = (s.cpu_percent ?? '?') + '% / ' + (s.mem_used_mb ?? '?') + ' MB';
+  $('enroll').hidden = s.enrolled;
+  $('unenroll').hidden = !s.enrolled;
+  lastIfaces = s.interfaces || [];
+  renderIfaces();
+  $('st_start').hidden = s.streaming;
+  $('st_stop').hidden = !s.streaming;
+  $('up_force_row').hidden = !s.streaming;
+}
+function showStale() {
+  let cached = null;
+  try { cached = JSON.parse(localStorage.getItem(STATUS_CACHE)); } catch (e) {}
+  if (cached && !statusShown) renderStatus(cached.status);
+  const since = cached ? new Date(cached.at).toLocaleTimeString() : null;
+  $('stale').textContent = 'Agent unreachable — ' +
+    (since ? 'showing status from ' + since + '. ' : '') + 'Retrying…';
+  $('stale').hidden = false;
+  document.body.classList.add('stale');
+}
 async function refresh() {
+  if (!$('login').hidden) return;
+  let s;
   try {
-    const s = await (await fetch('/api/status')).json();
-    $('enrolled').textContent = s.enrolled ? 'yes' : 'no';
-    $('enrolled').className = s.enrolled ? 'ok' : 'bad';
-    $('sender_id').textContent = s.sender_id || '—';
-    $('cloud').textContent = s.cloud_connected ? 'connected' : 'disconnected';
-    $('cloud').className = s.cloud_connected ? 'ok' : 'bad';
-    $('streaming').textContent = s.streaming ? ('live (' + (s.stream_id || '?') + ')') : 'idle';
-    $('sys').textContent = (s.cpu_percent ?? '?') + '% / ' + (s.mem_used_mb ?? '?') + ' MB';
-    $('enroll').hidden = s.enrolled;
-    $('unenroll').hidden = !s.enrolled;
-    const rows = (s.interfaces || []).map(i =>
-      '<tr><td>' + i.name + '</td><td>' + (i.enabled === false ? 'disabled' : (i.state || 'up')) + '</td></tr>').join('');
-    $('ifaces').hidden = !rows;
-    $('ifaces').querySelector('tbody').innerHTML = rows;
-  } catch (e) { $('msg').textContent = 'status fetch failed: ' + e; }
+    const r = await fetch('/api/status');
+    if (r.status === 401) { localStorage.removeItem(STATUS_CACHE); showLogin(); return; }
+    if (!r.ok) throw new Error(r.statusText);
+    s = await r.json();
+  } catch (e) { showStale(); return; }
+  try { localStorage.setItem(STATUS_CACHE, JSON.stringify({ at: Date.now(), status: s })); } catch (e) {}
+  $('stale').hidden = true;
+  document.body.classList.remove('stale');
+  renderStatus(s);
+  fillIfaces(s.interfaces || []);
+  fillModems(s.interfaces || []);
+  wzIfaces(s.interfaces || []);
+  if (!$('wz_receiver').value && document.activeElement !== $('wz_receiver'))
+    $('wz_receiver').value = s.receiver_url || '';
+  fillSources(s.inputs || []);
+  if (!$('st_receiver').value && document.activeElement !== $('st_receiver'))
+    $('st_receiver').value = s.receiver_url || '';
+  if (s.pending_ip_change && !$('ip_msg').textContent)
+    $('ip_msg').textContent = s.pending_ip_change.interface + ': IP change awaiting confirmation';
+}
+// A token that fails its check character may still be one issued before
+// check characters existed; let the operator say so and the control plane
+// decide.
+async function enroll(body) {
+  const post = () => fetch('/api/enroll', { method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
+  let r = await post();
+  let d = await r.json();
+  if (!r.ok && d.legacy_possible &&
+      confirm(d.error + '\n\nIs this an older token without a check character? Send it anyway?')) {
+    body.legacy = true;
+    r = await post();
+    d = await r.json();
+  }
+  return [r, d];
 }
 $('enroll').addEventListener('submit', async ev => {
   ev.preventDefault();
   const body = { enrollment_token: $('token').value };
   if ($('control_url').value) body.control_url = $('control_url').value;
-  const r = await fetch('/api/enroll', { method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
-  $('msg').textContent = (await r.json()).message || r.statusText;
+  const [r, d] = await enroll(body);
+  $('msg').textContent = d.message || d.error || r.statusText;
 });
 $('unenroll').addEventListener('click', async () => {
   if (!confirm('Unenroll this device?')) return;
   const r = await fetch('/api/unenroll', { method: 'POST' });
   $('msg').textContent = (await r.json()).message || r.statusText;
 });
+// First-run wizard. Each finished step is recorded on the agent so a
+// reload (or the unit rebooting mid-setup) resumes where it left off.
+const STEPS = ['interfaces', 'receiver', 'connectivity', 'enroll'];
+let wzStep = 0;
+const postSetup = body => fetch('/api/setup', {
+  method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
+function showWizard(on) { $('wizard').hidden = !on; $('main').hidden = on; }
+function wzShow(i) {
+  wzStep = i;
+  document.querySelectorAll('#wizard [data-panel]').forEach(p => { p.hidden = p.dataset.panel !== STEPS[i]; });
+  document.querySelectorAll('#wz_steps li').forEach((li, j) => { li.className = j < i ? 'ok' : (j === i ? 'current' : ''); });
+  $('wz_back').hidden = i === 0;
+  $('wz_next').textContent = i === STEPS.length - 1 ? 'Finish' : 'Next';
+  $('wz_msg').textContent = '';
+  if (STEPS[i] === 'connectivity') wzTest();
+}
+function wzIfaces(ifaces) {
+  $('wz_ifaces').innerHTML = ifaces.map(i =>
+    '<li class="' + (i.state === 'connected' ? 'ok' : 'bad') + '">' + i.name + ' — ' + (i.state || '?') +
+    (i.ip ? ' (' + i.ip + ')' : '') + '</li>').join('') || '<li class="bad">No network interfaces found</li>';
+}
+async function wzTest() {
+  $('wz_test').innerHTML = '<li>Testing…</li>';
+  try {
+    const t = await (await fetch('/api/test')).json();
+    const item = (ok, text) => '<li class="' + (ok ? 'ok' : 'bad') + '">' + (ok ? '✓ ' : '✗ ') + text + '</li>';
+    $('wz_test').innerHTML = item(t.cloud_reachable, 'Control plane ' + (t.control_url || '')) +
+      (t.receiver_url ? item(t.receiver_reachable, 'Receiver ' + t.receiver_url) : '');
+  } catch (e) { $('wz_test').innerHTML = '<li class="bad">Test failed: ' + e + '</li>'; }
+}
+$('wz_retest').addEventListener('click', wzTest);
+$('wz_back').addEventListener('click', () => wzShow(Math.max(0, wzStep - 1)));
+$('wz_next').addEventListener('click', async () => {
+  const step = STEPS[wzStep];
+  if (step === 'receiver' && $('wz_receiver').value.trim()) {
+    await fetch('/api/config', { method: 'POST', headers: {'content-type': 'application/json'},
+      body: JSON.stringify({ receiver_url: $('wz_receiver').value.trim() }) });
+  }
+  if (step === 'enroll' && $('wz_token').value.trim()) {
+    const body = { enrollment_token: $('wz_token').value.trim() };
+    if ($('wz_control_url').value.trim()) body.control_url = $('wz_control_url').value.trim();
+    const [r, d] = await enroll(body);
+    if (!r.ok) { $('wz_msg').textContent = d.error || r.statusText; return; }
+    $('msg').textContent = d.message || '';
+  }
+  const last = wzStep === STEPS.length - 1;
+  const r = await postSetup({ step, complete: last });
+  if (!r.ok) { $('wz_msg').textContent = 'Could not save progress: ' + r.statusText; return; }
+  if (last) showWizard(false); else wzShow(wzStep + 1);
+});
+$('wz_skip').addEventListener('click', async ev => {
+  ev.preventDefault();
+  if (!confirm('Skip setup? You can run it again from the status page.')) return;
+  await postSetup({ complete: true });
+  showWizard(false);
+});
+$('rerun').addEventListener('click', async ev => {
+  ev.preventDefault();
+  await postSetup({ restart: true });
+  showWizard(true);
+  wzShow(0);
+});
+(async () => {
+  try {
+    const a = await (await fetch('/api/auth')).json();
+    if (!a.authenticated) { showLogin(); return; }
+    $('logout').hidden = !a.required;
+    const s = await (await fetch('/api/setup')).json();
+    showWizard(!s.complete);
+    if (!s.complete) wzShow(s.next_step ? STEPS.indexOf(s.next_step) : STEPS.length - 1);
+  } catch (e) { showWizard(false); }
+})();
+// Link stats: sampled every second while streaming; each interface keeps
+// a minute of throughput history for its sparkline.
+const HIST = 60;
+const linkHist = {};
+let linkNow = {};
+let lastIfaces = [];
+const fmtBps = b => b >= 1e6 ? (b / 1e6).toFixed(1) + ' Mb/s' : Math.round(b / 1e3) + ' kb/s';
+function renderIfaces() {
+  const rows = lastIfaces.map(i => {
+    const l = linkNow[i.name];
+    return '<tr><td>' + i.name + '</td><td>' + (i.enabled === false ? 'disabled' : (i.state || 'up')) +
+      '</td><td>' + (l ? fmtBps(l.bps) : '—') + '</td><td>' + (l ? Math.round(l.rtt) + ' ms' : '—') +
+      '</td><td' + (l && l.loss > 0.05 ? ' class="bad"' : '') + '>' + (l ? (l.loss * 100).toFixed(1) + '%' : '—') +
+      '</td><td><canvas width="120" height="24" data-iface="' + i.name + '"></canvas></td></tr>';
+  }).join('');
+  $('ifaces').hidden = !rows;
+  $('ifaces').querySelector('tbody').innerHTML = rows;
+  $('ifaces').querySelectorAll('canvas').forEach(drawSpark);
+}
+function drawSpark(c) {
+  const h = linkHist[c.dataset.iface] || [];
+  if (h.length < 2) return;
+  const g = c.getContext('2d');
+  const max = Math.max(...h, 1);
+  g.strokeStyle = '#1a7f37';
+  g.beginPath();
+  h.forEach((v, i) => {
+    const x = c.width - (h.length - 1 - i) * c.width / (HIST - 1);
+    const y = c.height - 1 - v / max * (c.height - 2);
+    i ? g.lineTo(x, y) : g.moveTo(x, y);
+  });
+  g.stroke();
+}
+async function pollLinks() {
+  try {
+    const d = await (await fetch('/api/links')).json();
+    if (!d.streaming && !Object.keys(linkNow).length) return;
+    const now = {};
+    d.links.forEach(l => {
+      const a = now[l.interface] || (now[l.interface] = { bps: 0, rtt: 0, loss: 0 });
+      a.bps += l.observed_bps;
+      a.rtt = Math.max(a.rtt, l.rtt_ms);
+      a.loss = Math.max(a.loss, l.loss_rate);
+    });
+    linkNow = now;
+    lastIfaces.forEach(i => {
+      const h = linkHist[i.name] || (linkHist[i.name] = []);
+      h.push(now[i.name] ? now[i.name].bps : 0);
+      if (h.length > HIST) h.shift();
+    });
+    renderIfaces();
+  } catch (e) {}
+}
+setInterval(pollLinks, 1000);
+// Stream: start/stop the pipeline locally against the receiver above.
+function fillSources(inputs) {
+  const sel = $('st_source');
+  const devices = inputs.filter(i => i.type === 'v4l2');
+  const have = Array.from(sel.options).slice(2).map(o => o.value);
+  const want = devices.map(d => 'v4l2:' + d.device);
+  if (want.join() === have.join()) return;
+  const current = sel.value;
+  while (sel.options.length > 2) sel.remove(2);
+  devices.forEach(d => sel.add(new Option((d.label || d.device) + ' (' + d.device + ')', 'v4l2:' + d.device)));
+  if (Array.from(sel.options).some(o => o.value === current)) sel.value = current;
+}
+$('st_source').addEventListener('change', () => { $('st_uri').hidden = $('st_source').value !== 'uri'; });
+$('st_start').addEventListener('click', async () => {
+  const receiver = $('st_receiver').value.trim();
+  if (!receiver) { $('st_msg').textContent = 'Enter the receiver address first.'; return; }
+  await fetch('/api/config', { method: 'POST', headers: {'content-type': 'application/json'},
+    body: JSON.stringify({ receiver_url: receiver }) });
+  const choice = $('st_source').value;
+  const source = { mode: choice.startsWith('v4l2:') ? 'v4l2' : choice, resolution: $('st_res').value, framerate: 30 };
+  if (source.mode === 'v4l2') source.device = choice.slice(5);
+  if (source.mode === 'uri') source.uri = $('st_uri').value.trim();
+  const body = { source };
+  if ($('st_bitrate').value) body.bitrate_kbps = Number($('st_bitrate').value);
+  const r = await fetch('/api/stream/start', { method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
+  const d = await r.json();
+  $('st_msg').textContent = d.message || d.error || r.statusText;
+  refresh();
+});
+$('st_stop').addEventListener('click', async () => {
+  if (!confirm('Stop the stream?')) return;
+  const r = await fetch('/api/stream/stop', { method: 'POST' });
+  const d = await r.json();
+  $('st_msg').textContent = d.message || d.error || r.statusText;
+  refresh();
+});
+// IP settings: a change reverts on the device unless confirmed, so the
+// page confirms it once it can still reach the portal afterwards.
+const ipMode = () => document.querySelector('input[name=ip_mode]:checked').value;
+document.querySelectorAll('input[name=ip_mode]').forEach(r =>
+  r.addEventListener('change', () => { $('ip_static').hidden = ipMode() !== 'static'; }));
+async function loadIp() {
+  const name = $('ip_iface').value;
+  if (!name) return;
+  const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/ip');
+  const d = await r.json();
+  if (!r.ok) { $('ip_msg').textContent = d.error || r.statusText; return; }
+  const c = d.config || { mode: 'dhcp' };
+  document.querySelector('input[name=ip_mode][value=' + c.mode + ']').checked = true;
+  $('ip_static').hidden = c.mode !== 'static';
+  $('ip_address').value = c.address || '';
+  $('ip_gateway').value = c.gateway || '';
+  $('ip_dns').value = (c.dns || []).join(', ');
+  $('ip_msg').textContent = d.config ? '' : ('Managed as "' + d.method + '"; not editable here.');
+}
+$('ip_iface').addEventListener('change', loadIp);
+async function confirmIp(name, revertAt) {
+  await new Promise(ok => setTimeout(ok, 5000));
+  while (Date.now() < revertAt) {
+    try {
+      const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/ip/confirm', { method: 'POST' });
+      $('ip_msg').textContent = (await r.json()).message || r.statusText;
+      return;
+    } catch (e) {
+      $('ip_msg').textContent = 'Waiting for ' + name + ' to come back… reverts in ' +
+        Math.max(0, Math.round((revertAt - Date.now()) / 1000)) + 's';
+      await new Promise(ok => setTimeout(ok, 3000));
+    }
+  }
+  $('ip_msg').textContent = 'Lost contact; ' + name + ' has reverted to its previous settings.';
+}
+$('ip_apply').addEventListener('click', async () => {
+  const name = $('ip_iface').value;
+  const body = ipMode() === 'dhcp' ? { mode: 'dhcp' } : {
+    mode: 'static',
+    address: $('ip_address').value.trim(),
+    gateway: $('ip_gateway').value.trim() || null,
+    dns: $('ip_dns').value.split(',').map(s => s.trim()).filter(Boolean),
+  };
+  if (!confirm('Apply new IP settings to ' + name + '?')) return;
+  const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/ip', {
+    method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
+  const d = await r.json();
+  $('ip_msg').textContent = d.message || d.error || r.statusText;
+  if (r.ok) confirmIp(name, Date.parse(d.revert_at));
+});
+// SIM: cellular interfaces with a gateway (the HiLink modem's address);
+// other modem types report that no modem answered.
+let simState = null;
+async function loadSim() {
+  const name = $('sim_iface').value;
+  if (!name) return;
+  $('sim_info').innerHTML = '<dt>Status</dt><dd>Reading…</dd>';
+  const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/sim');
+  const d = await r.json();
+  if (!r.ok) { $('sim_info').innerHTML = ''; $('sim_msg').textContent = d.error || r.statusText; $('sim_unlock').hidden = true; return; }
+  simState = d.state;
+  const label = { ready: 'ready', pin_required: 'locked — PIN required', puk_required: 'blocked — PUK required', unavailable: 'no SIM / not readable' }[d.state];
+  const row = (k, v) => '<dt>' + k + '</dt><dd>' + (v ?? '—') + '</dd>';
+  $('sim_info').innerHTML = row('Status', label) + row('ICCID', d.iccid) + row('IMSI', d.imsi) + row('Operator', d.operator) +
+    (d.state === 'pin_required' ? row('PIN attempts left', d.pin_attempts_left) : '') +
+    (d.state === 'puk_required' ? row('PUK attempts left', d.puk_attempts_left) : '');
+  $('sim_info').querySelector('dd').className = d.state === 'ready' ? 'ok' : 'bad';
+  $('sim_unlock').hidden = d.state !== 'pin_required' && d.state !== 'puk_required';
+  $('sim_puk').hidden = d.state !== 'puk_required';
+  $('sim_pin').placeholder = d.state === 'puk_required' ? 'New SIM PIN' : 'SIM PIN';
+  if (document.activeElement !== $('sim_apn')) $('sim_apn').value = d.apn || '';
+  $('sim_msg').textContent = '';
+}
+$('sim_iface').addEventListener('change', loadSim);
+$('sim_unlock_btn').addEventListener('click', async () => {
+  const name = $('sim_iface').value;
+  const body = { pin: $('sim_pin').value.trim() };
+  if (simState === 'puk_required') body.puk = $('sim_puk').value.trim();
+  const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/sim/unlock', {
+    method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify(body) });
+  const d = await r.json();
+  $('sim_pin').value = $('sim_puk').value = '';
+  await loadSim();
+  $('sim_msg').textContent = d.message || d.error || r.statusText;
+});
+$('sim_apn_btn').addEventListener('click', async () => {
+  const name = $('sim_iface').value;
+  const r = await fetch('/api/interfaces/' + encodeURIComponent(name) + '/apn', {
+    method: 'POST', headers: {'content-type': 'application/json'}, body: JSON.stringify({ apn: $('sim_apn').value.trim() }) });
+  const d = await r.json();
+  $('sim_msg').textContent = d.message || d.error || r.statusText;
+});
+function fillModems(ifaces) {
+  const names = ifaces.filter(i => i.type === 'cellular' && i.gateway).map(i => i.name);
+  const sel = $('sim_iface');
+  if (names.join() === Array.from(sel.options).map(o => o.value).join()) return;
+  const current = sel.value;
+  sel.innerHTML = names.map(n => '<option>' + n + '</option>').join('');
+  if (names.includes(current)) sel.value = current;
+  $('sim').hidden = !names.length;
+  loadSim();
+}
+function fillIfaces(ifaces) {
+  const names = ifaces.map(i => i.name);
+  const sel = $('ip_iface');
+  const have = Array.from(sel.options).map(o => o.value);
+  if (names.join() === have.join()) return;
+  const current = sel.value;
+  sel.innerHTML = names.map(n => '<option>' + n + '</option>').join('');
+  if (names.includes(current)) sel.value = current;
+  $('ipcfg').hidden = !names.length;
+  loadIp();
+}
+// Versions and offline update. The upload is only staged by the agent;
+// a root service installs it and restarts the agent, so poll until the
+// portal comes back.
+async function loadVersions() {
+  try {
+    const r = await fetch('/api/version');
+    if (!r.ok) return null;
+    const v = await r.json();
+    $('versions').textContent = 'agent ' + v.agent + ' · pipeline ' + (v.pipeline || '?') +
+      ' · plugin ' + (v.plugin || 'not found') + (v.release ? ' (release ' + v.release + ')' : '');
+    $('up_file').disabled = $('up_send').disabled = !v.uploads_enabled || v.update_pending;
+    if (!v.uploads_enabled) $('up_info').textContent = 'Offline updates are disabled: no update signing key is installed on this unit.';
+    if (v.update_pending) $('up_msg').textContent = 'An update is being installed…';
+    else if (v.last_update && !$('up_msg').textContent)
+      $('up_msg').textContent = v.last_update.error
+        ? 'Last update failed: ' + v.last_update.error
+        : 'Last update: ' + v.last_update.version + ' (' + new Date(v.last_update.at).toLocaleString() + ')';
+    return v;
+  } catch (e) { return null; }
+}
+async function awaitRestart() {
+  for (let i = 0; i < 60; i++) {
+    await new Promise(ok => setTimeout(ok, 3000));
+    const v = await loadVersions();
+    if (v && !v.update_pending) {
+      $('up_msg').textContent = v.last_update && v.last_update.error
+        ? 'Update failed: ' + v.last_update.error : 'Update installed. Now running agent ' + v.agent + '.';
+      return;
+    }
+  }
+  $('up_msg').textContent = 'The unit has not come back yet; reload this page in a minute.';
+}
+$('up_send').addEventListener('click', () => {
+  const file = $('up_file').files[0];
+  if (!file) { $('up_msg').textContent = 'Choose a bundle file first.'; return; }
+  if (!confirm('Install ' + file.name + '? The agent restarts afterwards.')) return;
+  const xhr = new XMLHttpRequest();
+  const q = new URLSearchParams();
+  if ($('up_force').checked) q.set('force', 'true');
+  if ($('up_downgrade').checked) q.set('allow_downgrade', 'true');
+  xhr.open('POST', '/api/update?' + q);
+  xhr.setRequestHeader('content-type', 'application/octet-stream');
+  xhr.upload.onprogress = ev => { if (ev.lengthComputable) $('up_progress').value = ev.loaded / ev.total; };
+  xhr.onload = () => {
+    $('up_progress').hidden = true;
+    let d = {};
+    try { d = JSON.parse(xhr.responseText); } catch (e) {}
+    $('up_msg').textContent = d.message || d.error || xhr.statusText;
+    if (xhr.status === 200) { $('up_send').disabled = true; awaitRestart(); }
+  };
+  xhr.onerror = () => { $('up_progress').hidden = true; $('up_msg').textContent = 'Upload failed.'; };
+  $('up_progress').value = 0;
+  $('up_progress').hidden = false;
+  $('up_msg').textContent = 'Uploading…';
+  xhr.send(file);
+});
+// Diagnostics: tail the agent journal; the bundle link downloads a
+// tarball (logs, redacted config, hardware, stats) for support tickets.
+async function loadLogs() {
+  const q = new URLSearchParams({ lines: $('log_lines').value });
+  if ($('log_filter').value.trim()) q.set('filter', $('log_filter').value.trim());
+  try {
+    const r = await fetch('/api/logs?' + q);
+    const d = await r.json();
+    const pre = $('logs');
+    const atBottom = pre.scrollTop + pre.clientHeight >= pre.scrollHeight - 4;
+    pre.textContent = r.ok
+      ? (d.lines.map(l => (l.timestamp ? l.timestamp + ' ' : '') + l.message).join('\n') || 'No matching log lines.')
+      : (d.error || r.statusText);
+    pre.hidden = false;
+    if (atBottom) pre.scrollTop = pre.scrollHeight;
+  } catch (e) { $('logs').textContent = 'log fetch failed: ' + e; }
+}
+$('log_load').addEventListener('click', loadLogs);
+setInterval(() => { if ($('log_follow').checked && !$('main').hidden) loadLogs(); }, 5000);
+loadVersions();
 refresh(); setInterval(refresh, 2000);
 </script>
 </body>
 </html>
-"#;
+"##;
 
 // ── GET /api/status ─────────────────────────────────────────────────
 
@@ -152,6 +866,8 @@ async fn api_status(State(state): State<Arc<AgentState>>) -> Json<serde_json::Va
         .control_connected
         .load(std::sync::atomic::Ordering::Relaxed);
     let receiver_url = state.receiver_url.lock().await.clone();
+    let maintenance = state.open_maintenance_window().await;
+    let pending_ip_change = state.pending_ip_change.lock().await.clone();
 
     Json(serde_json::json!({
         "sender_id": sender_id,
@@ -165,15 +881,73 @@ async fn api_status(State(state): State<Arc<AgentState>>) -> Json<serde_json::Va
         "interfaces": hw.interfaces,
         "inputs": hw.inputs,
         "receiver_url": receiver_url,
+        "maintenance": maintenance,
+        "pending_ip_change": pending_ip_change,
     }))
 }
 
+// ── GET/POST /api/setup ─────────────────────────────────────────────
+
+fn setup_json(setup: &crate::config_store::SetupState) -> serde_json::Value {
+    serde_json::json!({
+        "complete": setup.is_complete(),
+        "completed_steps": setup.completed_steps,
+        "next_step": setup.next_step(),
+        "completed_at": setup.completed_at,
+    })
+}
+
+async fn api_get_setup(State(state): State<Arc<AgentState>>) -> Json<serde_json::Value> {
+    Json(setup_json(&state.config.get().setup))
+}
+
+#[derive(Debug, Deserialize)]
+struct SetupUpdate {
+    /// Wizard step just finished.
+    #[serde(default)]
+    step: Option<SetupStep>,
+    /// Finish (or skip) the wizard.
+    #[serde(default)]
+    complete: bool,
+    /// Forget progress so the wizard runs again from the first step.
+    #[serde(default)]
+    restart: bool,
+}
+
+async fn api_update_setup(
+    State(state): State<Arc<AgentState>>,
+    Json(body): Json<SetupUpdate>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let config = state
+        .config
+        .update(|c| {
+            if body.restart {
+                c.setup = Default::default();
+            }
+            if let Some(step) = body.step {
+                c.setup.mark(step);
+            }
+            if body.complete && c.setup.completed_at.is_none() {
+                c.setup.completed_at = Some(chrono::Utc::now());
+            }
+        })
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if body.complete {
+        tracing::info!("first-run setup completed via portal");
+    }
+    Ok(Json(setup_json(&config.setup)))
+}
+
 // ── POST /api/enroll ────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 struct EnrollRequest {
     enrollment_token: String,
     control_url: Option<String>,
+    /// The operator confirmed the token predates check characters; only
+    /// the control plane can tell, so skip the offline check.
+    #[serde(default)]
+    legacy: bool,
 }
 
 async fn api_enroll(
@@ -191,6 +965,21 @@ async fn api_enroll(
         }
     }
 
+    // Catch typos here rather than after a round trip to the control plane.
+    match strata_common::ids::validate_enrollment_token(&body.enrollment_token) {
+        Ok(()) => {}
+        Err(EnrollmentTokenError::Checksum) if body.legacy => {}
+        Err(e) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": e.to_string(),
+                    "legacy_possible": e == EnrollmentTokenError::Checksum,
+                })),
+            ));
+        }
+    }
+
     // Store token for the control loop to pick up
     {
         let mut token = state.pending_enrollment_token.lock().await;
@@ -393,6 +1182,355 @@ async fn api_interfaces_scan(State(state): State<Arc<AgentState>>) -> Json<serde
     }))
 }
 
+// ── GET/POST /api/interfaces/:name/ip ───────────────────────────────
+
+/// Only names the scanner reports reach `nmcli`.
+fn known_interface(name: &str) -> Result<(), ApiError> {
+    if crate::hardware::scan_network_interfaces()
+        .iter()
+        .any(|i| i.name == name)
+    {
+        Ok(())
+    } else {
+        Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("no interface {name}"),
+        ))
+    }
+}
+
+async fn api_interface_ip(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    known_interface(&name)?;
+    let connection = netconfig::connection_for(&name)
+        .await
+        .map_err(|e| api_error(StatusCode::CONFLICT, e))?;
+    let profile = netconfig::read_profile(&connection)
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let pending = state
+        .pending_ip_change
+        .lock()
+        .await
+        .clone()
+        .filter(|p| p.interface == name);
+
+    Ok(Json(serde_json::json!({
+        "interface": name,
+        "connection": connection,
+        "method": profile.method,
+        "config": profile.as_config(),
+        "pending": pending,
+    })))
+}
+
+async fn api_set_interface_ip(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+    Json(body): Json<IpConfig>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    known_interface(&name)?;
+    body.validate()
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    let change = netconfig::apply(&state, &name, body)
+        .await
+        .map_err(|e| api_error(StatusCode::CONFLICT, e))?;
+
+    Ok(Json(serde_json::json!({
+        "status": "pending",
+        "message": format!(
+            "Applied. Confirm within {}s or {name} reverts to its previous settings.",
+            netconfig::CONFIRM_WINDOW.as_secs()
+        ),
+        "revert_at": change.revert_at,
+    })))
+}
+
+async fn api_confirm_interface_ip(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if netconfig::confirm(&state, &name).await {
+        Ok(Json(serde_json::json!({
+            "status": "confirmed",
+            "message": format!("{name} settings kept."),
+        })))
+    } else {
+        Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("no change to {name} is awaiting confirmation"),
+        ))
+    }
+}
+
+// ── GET /api/interfaces/:name/sim, POST …/sim/unlock, …/apn ─────────
+
+async fn modem_gateway(state: &AgentState, name: &str) -> Result<String, ApiError> {
+    state.hardware.gateway_of(name).await.ok_or_else(|| {
+        api_error(
+            StatusCode::NOT_FOUND,
+            format!("{name} has no gateway; is it a modem?"),
+        )
+    })
+}
+
+async fn api_interface_sim(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+) -> Result<Json<crate::hilink::SimInfo>, ApiError> {
+    let gateway = modem_gateway(&state, &name).await?;
+    crate::hilink::sim_info(&gateway)
+        .await
+        .map(Json)
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::NOT_FOUND,
+                format!("no HiLink modem answered on {name}"),
+            )
+        })
+}
+
+#[derive(Deserialize)]
+struct SimUnlockRequest {
+    /// The SIM PIN, or the new PIN when unlocking with a PUK.
+    pin: String,
+    #[serde(default)]
+    puk: Option<String>,
+}
+
+async fn api_interface_sim_unlock(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+    Json(body): Json<SimUnlockRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let gateway = modem_gateway(&state, &name).await?;
+    crate::hilink::unlock_sim(
+        &gateway,
+        body.pin.trim(),
+        body.puk.as_deref().map(str::trim),
+    )
+    .await
+    .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    tracing::info!(interface = %name, "SIM unlocked via portal");
+    Ok(Json(serde_json::json!({
+        "status": "unlocked",
+        "message": format!("SIM on {name} unlocked; the modem will register shortly."),
+    })))
+}
+
+#[derive(Deserialize)]
+struct ApnRequest {
+    apn: String,
+}
+
+async fn api_interface_apn(
+    State(state): State<Arc<AgentState>>,
+    Path(name): Path<String>,
+    Json(body): Json<ApnRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let gateway = modem_gateway(&state, &name).await?;
+    let apn = body.apn.trim();
+    crate::hilink::set_apn(&gateway, apn)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    tracing::info!(interface = %name, apn, "APN set via portal");
+    Ok(Json(serde_json::json!({
+        "status": "updated",
+        "message": format!("APN on {name} set to {apn}; the modem reconnects with it."),
+    })))
+}
+
+// ── GET /api/links ──────────────────────────────────────────────────
+
+async fn api_links(State(state): State<Arc<AgentState>>) -> Json<serde_json::Value> {
+    let streaming = state.pipeline.lock().await.is_running();
+    // The telemetry loop stops updating these when the pipeline exits,
+    // so don't pass off the last stream's numbers as live.
+    let links = if streaming {
+        state.latest_link_stats.read().await.clone()
+    } else {
+        Vec::new()
+    };
+
+    Json(serde_json::json!({
+        "streaming": streaming,
+        "links": links,
+    }))
+}
+
+// ── POST /api/stream/start ──────────────────────────────────────────
+
+async fn api_stream_start(
+    State(state): State<Arc<AgentState>>,
+    Json(body): Json<LocalStreamRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    body.validate()
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    let receiver_url = state
+        .receiver_url
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| api_error(StatusCode::BAD_REQUEST, "set a receiver address first"))?;
+
+    let mut pipeline = state.pipeline.lock().await;
+    if pipeline.is_running() {
+        return Err(api_error(
+            StatusCode::CONFLICT,
+            format!(
+                "already streaming ({})",
+                pipeline.stream_id().unwrap_or("?")
+            ),
+        ));
+    }
+    let eligible = state.hardware.eligible_interfaces();
+    let stream_id = local_stream::stream_id();
+    let payload = body
+        .into_payload(stream_id.clone(), &receiver_url, eligible.len())
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    let links = payload.destinations.len();
+    pipeline
+        .start(payload, eligible)
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    tracing::info!(stream_id = %stream_id, links, "stream started via portal");
+
+    Ok(Json(serde_json::json!({
+        "status": "started",
+        "stream_id": stream_id,
+        "message": format!("Streaming to {receiver_url} over {links} link(s)."),
+    })))
+}
+
+// ── POST /api/stream/stop ───────────────────────────────────────────
+
+async fn api_stream_stop(
+    State(state): State<Arc<AgentState>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (stream_id, stats) = {
+        let mut pipeline = state.pipeline.lock().await;
+        let Some(stream_id) = pipeline.stream_id().map(str::to_string) else {
+            return Err(api_error(StatusCode::BAD_REQUEST, "no stream is running"));
+        };
+        (stream_id, pipeline.stop())
+    };
+    tracing::info!(stream_id = %stream_id, "stream stopped via portal");
+
+    // A cloud-started stream stopped here must still end on the dashboard.
+    let ended = strata_protocol::StreamEndedPayload {
+        stream_id: stream_id.clone(),
+        reason: strata_protocol::StreamEndReason::UserStop,
+        duration_s: stats.duration_s,
+        total_bytes: stats.total_bytes,
+        error: None,
+    };
+    crate::control::send_message(&state, &strata_protocol::AgentMessage::StreamEnded(ended)).await;
+
+    Ok(Json(serde_json::json!({
+        "status": "stopped",
+        "stream_id": stream_id,
+        "message": format!("Stopped after {}s.", stats.duration_s),
+    })))
+}
+
+// ── GET /api/logs ───────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct LogsQuery {
+    #[serde(default)]
+    lines: Option<u32>,
+    /// Case-insensitive substring the lines must contain.
+    #[serde(default)]
+    filter: Option<String>,
+}
+
+async fn api_logs(Query(query): Query<LogsQuery>) -> Json<serde_json::Value> {
+    let max_lines = query.lines.unwrap_or(200).clamp(1, 2000);
+    let mut lines =
+        crate::diagnostics::collect_logs(crate::diagnostics::AGENT_UNIT, max_lines).await;
+    if let Some(filter) = query
+        .filter
+        .map(|f| f.trim().to_lowercase())
+        .filter(|f| !f.is_empty())
+    {
+        lines.retain(|l| l.message.to_lowercase().contains(&filter));
+    }
+    Json(serde_json::json!({ "lines": lines }))
+}
+
+// ── GET /api/diagnostics/bundle ─────────────────────────────────────
+
+async fn api_diagnostics_bundle(
+    State(state): State<Arc<AgentState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let bundle = crate::diagnostics::support_bundle(&state)
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let host = crate::gethostname().unwrap_or_else(|| "strata-sender".into());
+    let filename = format!(
+        "strata-diag-{host}-{}.tar.gz",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        bundle,
+    ))
+}
+
+// ── GET /api/version ────────────────────────────────────────────────
+
+async fn api_version() -> Json<crate::local_update::Versions> {
+    Json(crate::local_update::versions().await)
+}
+
+// ── POST /api/update ────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct UpdateQuery {
+    /// Install even while streaming (the restart drops the stream).
+    #[serde(default)]
+    force: bool,
+    /// Install a bundle that isn't newer than the installed release.
+    #[serde(default)]
+    allow_downgrade: bool,
+}
+
+async fn api_update(
+    State(state): State<Arc<AgentState>>,
+    Query(query): Query<UpdateQuery>,
+    body: axum::body::Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if !query.force && state.pipeline.lock().await.is_running() {
+        return Err(api_error(
+            StatusCode::CONFLICT,
+            "a stream is live; updating restarts the agent and will drop it",
+        ));
+    }
+    let key = crate::local_update::trusted_key()
+        .map_err(|e| api_error(StatusCode::SERVICE_UNAVAILABLE, e))?;
+    let manifest = crate::local_update::stage(&body, &key, query.allow_downgrade)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    tracing::info!(version = %manifest.version, bytes = body.len(), "offline update staged via portal");
+
+    Ok(Json(serde_json::json!({
+        "status": "staged",
+        "version": manifest.version,
+        "message": format!(
+            "Bundle {} verified. Installing; the agent restarts in a few seconds.",
+            manifest.version
+        ),
+    })))
+}
+
 // ── GET /metrics ──────────────────────────────────────────────────
 
 async fn api_metrics(State(state): State<Arc<AgentState>>) -> impl IntoResponse {
@@ -0,0 +1,183 @@
+//! Optional PIN gate for the onboarding portal.
+//!
+//! Off by default — being on the sender's AP or LAN is the trust boundary.
+//! When the control plane pushes a PIN hash, every portal API call needs a
+//! session cookie obtained by entering the PIN. Repeated wrong PINs lock
+//! the login out for a while; the lockout is device-wide rather than per
+//! client, since anyone can pick a new address on the local network.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Cookie carrying the portal session token.
+pub const SESSION_COOKIE: &str = "strata_portal";
+/// How long a portal session lasts (a working day on site).
+const SESSION_TTL: Duration = Duration::from_secs(12 * 3600);
+/// Wrong PINs allowed before the login locks.
+const MAX_FAILURES: u32 = 5;
+const LOCKOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoginError {
+    /// Too many failures; retry after this long.
+    LockedOut(Duration),
+    /// Wrong PIN; this many attempts remain before lockout.
+    WrongPin { remaining: u32 },
+}
+
+impl std::fmt::Display for LoginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LockedOut(wait) => write!(
+                f,
+                "too many wrong attempts; try again in {}s",
+                wait.as_secs().max(1)
+            ),
+            Self::WrongPin { remaining } => {
+                write!(f, "wrong PIN ({remaining} attempts left)")
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Failures {
+    count: u32,
+    locked_until: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct PortalAuth {
+    /// Session token → expiry.
+    sessions: std::sync::Mutex<HashMap<String, Instant>>,
+    failures: std::sync::Mutex<Failures>,
+}
+
+impl PortalAuth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `pin` against `pin_hash` and open a session on success.
+    pub fn login(&self, pin_hash: &str, pin: &str, now: Instant) -> Result<String, LoginError> {
+        let mut failures = self.failures.lock().unwrap();
+        if let Some(until) = failures.locked_until {
+            if now < until {
+                return Err(LoginError::LockedOut(until - now));
+            }
+            *failures = Failures::default();
+        }
+
+        if !strata_common::auth::verify_password(pin, pin_hash).unwrap_or(false) {
+            failures.count += 1;
+            if failures.count >= MAX_FAILURES {
+                failures.locked_until = Some(now + LOCKOUT);
+                tracing::warn!("portal login locked after repeated wrong PINs");
+                return Err(LoginError::LockedOut(LOCKOUT));
+            }
+            return Err(LoginError::WrongPin {
+                remaining: MAX_FAILURES - failures.count,
+            });
+        }
+        *failures = Failures::default();
+        drop(failures);
+
+        let token = strata_common::ids::portal_session_token();
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, expires| *expires > now);
+        sessions.insert(token.clone(), now + SESSION_TTL);
+        Ok(token)
+    }
+
+    pub fn is_valid(&self, token: &str, now: Instant) -> bool {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(token)
+            .is_some_and(|expires| *expires > now)
+    }
+
+    pub fn logout(&self, token: &str) {
+        self.sessions.lock().unwrap().remove(token);
+    }
+
+    /// Drop every session — the PIN changed, so whoever knew the old one
+    /// has to log in again.
+    pub fn revoke_all(&self) {
+        self.sessions.lock().unwrap().clear();
+    }
+}
+
+/// The portal session token from a `Cookie` header, if present.
+pub fn session_from_cookies(header: &str) -> Option<&str> {
+    header.split(';').find_map(|c| {
+        c.trim()
+            .strip_prefix(SESSION_COOKIE)
+            .and_then(|rest| rest.strip_prefix('='))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correct_pin_opens_a_session() {
+        let hash = strata_common::auth::hash_password("246810").unwrap();
+        let auth = PortalAuth::new();
+        let now = Instant::now();
+
+        let token = auth.login(&hash, "246810", now).unwrap();
+        assert!(auth.is_valid(&token, now));
+        assert!(!auth.is_valid(&token, now + SESSION_TTL));
+        assert!(!auth.is_valid("pst_forged", now));
+
+        auth.logout(&token);
+        assert!(!auth.is_valid(&token, now));
+    }
+
+    #[test]
+    fn repeated_failures_lock_out() {
+        let hash = strata_common::auth::hash_password("246810").unwrap();
+        let auth = PortalAuth::new();
+        let now = Instant::now();
+
+        for remaining in (1..MAX_FAILURES).rev() {
+            assert_eq!(
+                auth.login(&hash, "000000", now),
+                Err(LoginError::WrongPin { remaining })
+            );
+        }
+        assert_eq!(
+            auth.login(&hash, "000000", now),
+            Err(LoginError::LockedOut(LOCKOUT))
+        );
+        // Even the right PIN is refused until the lockout passes.
+        assert!(matches!(
+            auth.login(&hash, "246810", now + Duration::from_secs(60)),
+            Err(LoginError::LockedOut(_))
+        ));
+        assert!(auth.login(&hash, "246810", now + LOCKOUT).is_ok());
+    }
+
+    #[test]
+    fn revoking_ends_all_sessions() {
+        let hash = strata_common::auth::hash_password("246810").unwrap();
+        let auth = PortalAuth::new();
+        let now = Instant::now();
+        let a = auth.login(&hash, "246810", now).unwrap();
+        let b = auth.login(&hash, "246810", now).unwrap();
+        auth.revoke_all();
+        assert!(!auth.is_valid(&a, now) && !auth.is_valid(&b, now));
+    }
+
+    #[test]
+    fn session_cookie_parsing() {
+        assert_eq!(
+            session_from_cookies("theme=dark; strata_portal=pst_abc; x=1"),
+            Some("pst_abc")
+        );
+        assert_eq!(session_from_cookies("strata_portalx=1"), None);
+        assert_eq!(session_from_cookies(""), None);
+    }
+}
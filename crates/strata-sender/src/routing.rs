@@ -0,0 +1,302 @@
+//! Priority-aware default route for the device's own traffic.
+//!
+//! Bonded media is pinned per link, but everything else the agent does —
+//! the control WebSocket, DNS, OTA downloads — follows the kernel's
+//! default route, which is otherwise whichever uplink DHCP gave the lowest
+//! metric (often a metered backup modem). Operators rank interfaces in the
+//! dashboard (Primary 1, Secondary 2, Backup 3, Standby 100); this module
+//! turns that ranking into default-route metrics so device traffic takes
+//! the highest-priority healthy link and fails over as links come and go.
+//!
+//! Every [`ROUTE_CHECK_INTERVAL`] the loop plans one default route per
+//! uplink ([`plan_routes`]) and reconciles the kernel table with `ip
+//! route`. Managed routes live in a metric band below NetworkManager's
+//! defaults (100+), so NetworkManager's own routes stay in place as a
+//! fallback and are never touched.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use strata_protocol::models::{InterfaceState, NetworkInterface};
+
+use crate::AgentState;
+
+/// How often the default routes are re-planned.
+const ROUTE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Metric of the best managed route; the rest follow in rank order.
+const METRIC_BASE: u32 = 50;
+
+/// Size of the managed metric band (`METRIC_BASE..METRIC_BASE + MAX`).
+const MAX_MANAGED_ROUTES: usize = 32;
+
+/// One default route the agent owns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultRoute {
+    pub interface: String,
+    pub gateway: String,
+    pub metric: u32,
+}
+
+/// Usable for device traffic right now: up, addressed, with a gateway.
+fn is_healthy(iface: &NetworkInterface) -> bool {
+    iface.state == InterfaceState::Connected && iface.ip.is_some() && iface.gateway.is_some()
+}
+
+/// Rank every uplink (an interface with a gateway) and give each a metric:
+/// healthy links first, then by priority (1 = highest), then by name so
+/// equal priorities rank deterministically. Unhealthy links keep a route
+/// behind every healthy one, as a last resort.
+pub fn plan_routes(interfaces: &[NetworkInterface]) -> Vec<DefaultRoute> {
+    let mut uplinks: Vec<&NetworkInterface> =
+        interfaces.iter().filter(|i| i.gateway.is_some()).collect();
+    uplinks.sort_by(|a, b| {
+        is_healthy(b)
+            .cmp(&is_healthy(a))
+            .then(a.priority.cmp(&b.priority))
+            .then(a.name.cmp(&b.name))
+    });
+    uplinks
+        .into_iter()
+        .take(MAX_MANAGED_ROUTES)
+        .enumerate()
+        .filter_map(|(rank, iface)| {
+            Some(DefaultRoute {
+                interface: iface.name.clone(),
+                gateway: iface.gateway.clone()?,
+                metric: METRIC_BASE + rank as u32,
+            })
+        })
+        .collect()
+}
+
+/// Managed default routes in `ip -j route show default` output; routes
+/// outside the managed metric band belong to someone else.
+fn parse_managed_routes(json: &[u8]) -> Vec<DefaultRoute> {
+    let Ok(serde_json::Value::Array(routes)) = serde_json::from_slice(json) else {
+        return Vec::new();
+    };
+    let band = METRIC_BASE..METRIC_BASE + MAX_MANAGED_ROUTES as u32;
+    routes
+        .iter()
+        .filter_map(|r| {
+            Some(DefaultRoute {
+                interface: r.get("dev")?.as_str()?.to_string(),
+                gateway: r.get("gateway")?.as_str()?.to_string(),
+                metric: r.get("metric")?.as_u64()? as u32,
+            })
+        })
+        .filter(|r| band.contains(&r.metric))
+        .collect()
+}
+
+/// Routes to remove and routes to install to get from `current` to `plan`.
+fn diff_routes(
+    current: &[DefaultRoute],
+    plan: &[DefaultRoute],
+) -> (Vec<DefaultRoute>, Vec<DefaultRoute>) {
+    let stale = current
+        .iter()
+        .filter(|r| !plan.contains(r))
+        .cloned()
+        .collect();
+    let missing = plan
+        .iter()
+        .filter(|r| !current.contains(r))
+        .cloned()
+        .collect();
+    (stale, missing)
+}
+
+async fn ip(args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = tokio::process::Command::new("ip")
+        .args(args)
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "ip {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Bring the kernel's managed default routes in line with `interfaces`.
+/// Returns the planned routes, best first.
+pub async fn reconcile(interfaces: &[NetworkInterface]) -> anyhow::Result<Vec<DefaultRoute>> {
+    let plan = plan_routes(interfaces);
+    let current = parse_managed_routes(&ip(&["-j", "route", "show", "default"]).await?);
+    let (stale, missing) = diff_routes(&current, &plan);
+    // Remove first: `replace` keys on metric, so a stale route could
+    // otherwise shadow the slot a new one is about to take.
+    for r in &stale {
+        let metric = r.metric.to_string();
+        ip(&[
+            "route",
+            "del",
+            "default",
+            "via",
+            &r.gateway,
+            "dev",
+            &r.interface,
+            "metric",
+            &metric,
+        ])
+        .await?;
+    }
+    for r in &missing {
+        let metric = r.metric.to_string();
+        ip(&[
+            "route",
+            "replace",
+            "default",
+            "via",
+            &r.gateway,
+            "dev",
+            &r.interface,
+            "metric",
+            &metric,
+        ])
+        .await?;
+    }
+    Ok(plan)
+}
+
+/// Run the route manager until shutdown. Failures (typically no
+/// CAP_NET_ADMIN in a dev container) are logged once per distinct error
+/// and retried next tick.
+pub async fn run(state: Arc<AgentState>) {
+    let mut interval = tokio::time::interval(ROUTE_CHECK_INTERVAL);
+    let mut primary: Option<String> = None;
+    let mut last_error: Option<String> = None;
+
+    loop {
+        interval.tick().await;
+
+        if *state.shutdown.borrow() {
+            return;
+        }
+
+        match reconcile(&state.hardware.network_interfaces()).await {
+            Ok(plan) => {
+                last_error = None;
+                let best = plan.first().map(|r| r.interface.clone());
+                if best != primary {
+                    tracing::info!(
+                        from = ?primary,
+                        to = ?best,
+                        "device default route moved"
+                    );
+                    primary = best;
+                }
+            }
+            Err(e) => {
+                let e = e.to_string();
+                if last_error.as_ref() != Some(&e) {
+                    tracing::warn!(error = %e, "failed to update default routes");
+                    last_error = Some(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_protocol::models::InterfaceType;
+
+    fn uplink(name: &str, priority: u32, connected: bool) -> NetworkInterface {
+        NetworkInterface {
+            name: name.into(),
+            iface_type: InterfaceType::Cellular,
+            state: if connected {
+                InterfaceState::Connected
+            } else {
+                InterfaceState::Disconnected
+            },
+            enabled: true,
+            ip: Some("192.168.8.100".into()),
+            carrier: None,
+            signal_dbm: None,
+            technology: None,
+            band: None,
+            cell_id: None,
+            data_cap_mb: None,
+            data_used_mb: None,
+            priority,
+            apn: None,
+            sim_pin: None,
+            roaming: false,
+            driver: None,
+            bus: None,
+            product: None,
+            subnet: None,
+            gateway: Some(format!("gw-{name}")),
+            has_default_route: true,
+        }
+    }
+
+    fn order(plan: &[DefaultRoute]) -> Vec<&str> {
+        plan.iter().map(|r| r.interface.as_str()).collect()
+    }
+
+    #[test]
+    fn highest_priority_healthy_link_gets_the_lowest_metric() {
+        let plan = plan_routes(&[
+            uplink("wwan0", 100, true),
+            uplink("eth0", 3, true),
+            uplink("wwan1", 1, true),
+            uplink("wwan2", 2, true),
+        ]);
+        assert_eq!(order(&plan), ["wwan1", "wwan2", "eth0", "wwan0"]);
+        assert_eq!(plan[0].metric, METRIC_BASE);
+        assert_eq!(plan[3].metric, METRIC_BASE + 3);
+    }
+
+    #[test]
+    fn unhealthy_primary_falls_behind_healthy_standby() {
+        let mut no_gateway = uplink("eth1", 1, true);
+        no_gateway.gateway = None;
+        let plan = plan_routes(&[
+            uplink("wwan0", 1, false),
+            uplink("wwan1", 100, true),
+            no_gateway,
+        ]);
+        assert_eq!(order(&plan), ["wwan1", "wwan0"]);
+    }
+
+    #[test]
+    fn reconcile_only_touches_routes_in_the_managed_band() {
+        let json = br#"[
+            {"dst":"default","gateway":"192.168.8.1","dev":"wwan0","metric":50},
+            {"dst":"default","gateway":"192.168.9.1","dev":"wwan1","metric":51},
+            {"dst":"default","gateway":"10.0.0.1","dev":"eth0","metric":100}
+        ]"#;
+        let current = parse_managed_routes(json);
+        assert_eq!(
+            current.len(),
+            2,
+            "NetworkManager's metric-100 route is not ours"
+        );
+
+        let plan = vec![
+            DefaultRoute {
+                interface: "wwan1".into(),
+                gateway: "192.168.9.1".into(),
+                metric: 50,
+            },
+            DefaultRoute {
+                interface: "wwan0".into(),
+                gateway: "192.168.8.1".into(),
+                metric: 51,
+            },
+        ];
+        let (stale, missing) = diff_routes(&current, &plan);
+        assert_eq!(stale, current);
+        assert_eq!(missing, plan);
+        assert_eq!(diff_routes(&plan, &plan), (vec![], vec![]));
+    }
+}
@@ -0,0 +1,287 @@
+//! Simulated bonded links (`simulate` feature).
+//!
+//! `--simulate-link SPEC` (repeatable) replaces the device's interfaces
+//! with virtual links `sim0`, `sim1`, … so the scheduler can be exercised
+//! with 4–8 links on a laptop without modems. A SPEC is one of:
+//!
+//! - a preset (`lte_good`, `lte_urban`, `lte_poor`, `fiveg_good`, `ideal`),
+//!   optionally repeated: `lte_good*4`
+//! - a strata-sim trace CSV: `field.csv` adds one link per traced link,
+//!   `field.csv#wwan0` only that one
+//!
+//! The virtual links are reported to the control plane like modems. On
+//! stream start each bonded link is routed through a userspace impairment
+//! proxy on loopback that plays its link's characteristics onto the
+//! traffic before forwarding it to the real receiver.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Context;
+use strata_protocol::StreamStartPayload;
+use strata_protocol::models::{InterfaceState, InterfaceType, NetworkInterface};
+use strata_sim::impairment::{ImpairmentConfig, ImpairmentSchedule, ImpairmentScheduler};
+use strata_sim::proxy::ImpairmentProxy;
+use strata_sim::trace::Trace;
+
+/// How often trace-driven links are re-evaluated.
+const SCHEDULER_TICK: Duration = Duration::from_millis(50);
+
+/// Most virtual links one `--simulate-link` may add.
+const MAX_REPEAT: usize = 16;
+
+/// One virtual link.
+#[derive(Debug, Clone)]
+pub struct SimulatedLink {
+    /// Interface name reported for the link (`sim0`, …).
+    pub name: String,
+    /// The SPEC (or the trace link) it was created from.
+    pub source: String,
+    schedule: ImpairmentSchedule,
+}
+
+/// The virtual links of a `--simulate-link` run.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    links: Vec<SimulatedLink>,
+}
+
+impl Simulation {
+    /// Build the links from `--simulate-link` specs, in order.
+    pub fn parse(specs: &[String]) -> anyhow::Result<Self> {
+        let mut sources = Vec::new();
+        for spec in specs {
+            sources.extend(expand(spec)?);
+        }
+        let links = sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, (source, schedule))| SimulatedLink {
+                name: format!("sim{i}"),
+                source,
+                // Distinct seeds, so identical presets don't lose the same
+                // packets.
+                schedule: schedule.with_seed(i as u64),
+            })
+            .collect();
+        Ok(Self { links })
+    }
+
+    pub fn links(&self) -> &[SimulatedLink] {
+        &self.links
+    }
+
+    /// The virtual links as the interfaces the hardware scan reports.
+    pub fn interfaces(&self) -> Vec<NetworkInterface> {
+        self.links
+            .iter()
+            .map(|link| NetworkInterface {
+                name: link.name.clone(),
+                iface_type: InterfaceType::Cellular,
+                state: InterfaceState::Connected,
+                enabled: true,
+                ip: Some("127.0.0.1".into()),
+                carrier: Some("Simulated".into()),
+                signal_dbm: None,
+                technology: Some(link.source.clone()),
+                cell_id: None,
+                band: None,
+                data_cap_mb: None,
+                data_used_mb: None,
+                priority: 1,
+                apn: None,
+                sim_pin: None,
+                roaming: false,
+                driver: None,
+                bus: None,
+                product: None,
+                subnet: None,
+                gateway: None,
+                has_default_route: true,
+            })
+            .collect()
+    }
+
+    /// Route a stream's bonded links through the virtual links: one proxy
+    /// per link, link `i` forwarding to destination `i` (wrapping when the
+    /// receiver allocated fewer). Rewrites `payload` to target the proxies;
+    /// they run until the returned [`Running`] is dropped.
+    pub async fn route(&self, payload: &mut StreamStartPayload) -> anyhow::Result<Running> {
+        if payload.destinations.is_empty() {
+            anyhow::bail!("simulated links need at least one destination");
+        }
+        let mut upstreams = Vec::with_capacity(payload.destinations.len());
+        for dest in &payload.destinations {
+            let host = crate::util::extract_host(dest);
+            let addr = tokio::net::lookup_host(host.as_str())
+                .await
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .with_context(|| format!("cannot resolve destination {dest}"))?;
+            upstreams.push(addr);
+        }
+
+        let mut proxies = Vec::with_capacity(self.links.len());
+        let mut scheduler = ImpairmentScheduler::new(SCHEDULER_TICK);
+        for (i, link) in self.links.iter().enumerate() {
+            let upstream = upstreams[i % upstreams.len()];
+            let listen: SocketAddr = if upstream.is_ipv4() {
+                "127.0.0.1:0".parse().unwrap()
+            } else {
+                "[::1]:0".parse().unwrap()
+            };
+            let proxy = ImpairmentProxy::bind(
+                listen,
+                upstream,
+                link.schedule.config_at(Duration::ZERO),
+                i as u64,
+            )
+            .await
+            .with_context(|| format!("{}: cannot start proxy", link.name))?;
+            scheduler = scheduler.proxy_link(&proxy, link.schedule.clone());
+            proxies.push(proxy);
+        }
+
+        payload.destinations = proxies
+            .iter()
+            .map(|p| format!("strata://{}", p.local_addr()))
+            .collect();
+        // Unpinned `[[links]]`: the proxies are on loopback, there is no
+        // interface to bind to.
+        let links: Vec<serde_json::Value> = payload
+            .destinations
+            .iter()
+            .map(|uri| serde_json::json!({ "uri": uri }))
+            .collect();
+        if !payload.bonding_config.is_object() {
+            payload.bonding_config = serde_json::json!({});
+        }
+        payload.bonding_config["links"] = serde_json::Value::Array(links);
+
+        tracing::info!(
+            links = proxies.len(),
+            upstreams = ?upstreams,
+            "routing stream through simulated links"
+        );
+        Ok(Running {
+            names: self.links.iter().map(|l| l.name.clone()).collect(),
+            _proxies: proxies,
+            scheduler: scheduler.spawn(),
+        })
+    }
+}
+
+/// Proxies carrying a running stream. Dropping it stops them.
+pub struct Running {
+    names: Vec<String>,
+    _proxies: Vec<ImpairmentProxy>,
+    scheduler: tokio::task::JoinHandle<std::io::Result<()>>,
+}
+
+impl Running {
+    /// Virtual link carrying each bonded link (index = link id).
+    pub fn link_names(&self) -> Vec<String> {
+        self.names.clone()
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.scheduler.abort();
+    }
+}
+
+/// The `(source, schedule)` of every link one spec adds.
+fn expand(spec: &str) -> anyhow::Result<Vec<(String, ImpairmentSchedule)>> {
+    let (name, count) = match spec.rsplit_once('*') {
+        Some((name, count)) => {
+            let count: usize = count
+                .parse()
+                .ok()
+                .filter(|n| (1..=MAX_REPEAT).contains(n))
+                .with_context(|| format!("{spec}: repeat count must be 1–{MAX_REPEAT}"))?;
+            (name, count)
+        }
+        None => (spec, 1),
+    };
+
+    if let Some(config) = ImpairmentConfig::preset(name) {
+        let schedule = ImpairmentSchedule::new().hold(Duration::ZERO, config);
+        return Ok(vec![(name.to_string(), schedule); count]);
+    }
+
+    let (path, only) = match name.split_once('#') {
+        Some((path, link)) => (path, Some(link)),
+        None => (name, None),
+    };
+    if !path.ends_with(".csv") {
+        anyhow::bail!(
+            "{spec}: not a preset ({}) or a trace .csv",
+            ImpairmentConfig::PRESETS.join(", ")
+        );
+    }
+    let csv = std::fs::read_to_string(path).with_context(|| format!("cannot read {path}"))?;
+    let trace = Trace::parse(&csv).with_context(|| format!("{path}: invalid trace"))?;
+    let base = ImpairmentConfig::default();
+    let links: Vec<_> = match only {
+        Some(link) => vec![
+            trace
+                .link(link)
+                .with_context(|| format!("{path} has no link {link:?}"))?,
+        ],
+        None => trace.links.iter().collect(),
+    };
+    let mut out = Vec::new();
+    for _ in 0..count {
+        out.extend(
+            links
+                .iter()
+                .map(|l| (format!("{path}#{}", l.name), l.schedule(&base))),
+        );
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn presets_expand_into_numbered_links() {
+        let sim = Simulation::parse(&specs(&["lte_good*3", "lte_poor"])).unwrap();
+        let names: Vec<_> = sim.links().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["sim0", "sim1", "sim2", "sim3"]);
+        assert_eq!(sim.links()[3].source, "lte_poor");
+        assert!(sim.interfaces().iter().all(|i| i.has_default_route));
+
+        assert!(Simulation::parse(&specs(&["lte_good*0"])).is_err());
+        assert!(Simulation::parse(&specs(&["carrier_pigeon"])).is_err());
+    }
+
+    #[test]
+    fn trace_links_become_virtual_links() {
+        let path = std::env::temp_dir().join(format!("strata-sim-{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "t_ms,link,capacity_kbps,loss_pct,rtt_ms\n\
+             0,wwan0,5000,0.5,60\n\
+             0,wwan1,3000,1,80\n\
+             1000,wwan0,0,0,60\n",
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let all = Simulation::parse(&specs(&[path])).unwrap();
+        assert_eq!(all.links().len(), 2);
+        let one = Simulation::parse(&[format!("{path}#wwan1")]).unwrap();
+        assert_eq!(one.links().len(), 1);
+        assert!(one.links()[0].source.ends_with("#wwan1"));
+        assert!(Simulation::parse(&[format!("{path}#wwan9")]).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,26 +1,43 @@
 //! Telemetry — collects pipeline stats and sends them to the control plane.
 //!
 //! Reads stats from strata-node's UDP relay on 127.0.0.1:9100
-//! (bonding stats JSON forwarded from the GStreamer bus).
+//! (bonding stats JSON forwarded from the GStreamer bus). Bonding events
+//! on the same relay are handled as they arrive, so a receiver keyframe
+//! request reaches the encoder without waiting for the next stats tick.
 
 use std::sync::Arc;
 use std::time::Duration;
 
-use strata_protocol::models::LinkStats;
-use strata_protocol::{AgentMessage, Envelope, StreamStatsPayload};
+use strata_protocol::models::MediaActivity;
+use strata_protocol::telemetry::{Bps, ElementStats, LinkSample};
+use strata_protocol::{AgentMessage, Envelope, SessionQuality, StreamStatsPayload};
 
 use crate::AgentState;
 use crate::pipeline;
 
+/// One relayed stats datagram.
+#[derive(Debug, Clone, Default)]
+struct RelayedStats {
+    links: Vec<LinkSample>,
+    /// The adapter's commanded encoder target.
+    commanded_bitrate_bps: Option<u64>,
+    quality: Option<SessionQuality>,
+    /// Queue levels and dropped buffers per pipeline element.
+    elements: Vec<ElementStats>,
+    /// Audio levels and picture activity at the encoder input.
+    media: Option<Box<MediaActivity>>,
+}
+
 /// Run the telemetry loop — sends stream.stats every second while streaming.
 pub async fn run(state: Arc<AgentState>) {
     let mut interval = tokio::time::interval(Duration::from_secs(1));
 
-    // Set up a non-blocking UDP socket to receive stats from strata-node.
+    // Set up a UDP socket to receive stats from strata-node.
     // The socket is bound once and reused across the lifetime of the agent.
-    let stats_rx = std::net::UdpSocket::bind(pipeline::STATS_LISTEN_ADDR).ok();
-    if let Some(ref sock) = stats_rx {
-        sock.set_nonblocking(true).ok();
+    let stats_rx = tokio::net::UdpSocket::bind(pipeline::STATS_LISTEN_ADDR)
+        .await
+        .ok();
+    if stats_rx.is_some() {
         tracing::info!(
             addr = pipeline::STATS_LISTEN_ADDR,
             "stats UDP listener bound"
@@ -28,11 +45,32 @@ pub async fn run(state: Arc<AgentState>) {
     }
 
     // Buffer for incoming stats JSON from strata-node
-    let mut last_real_stats: Option<(Vec<LinkStats>, Option<u64>)> = None;
+    let mut last_real_stats: Option<RelayedStats> = None;
     let mut recv_buf = [0u8; 8192];
 
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = interval.tick() => {}
+            Ok(n) = recv_relay(stats_rx.as_ref(), &mut recv_buf) => {
+                // Keep the most recent stats; act on events immediately.
+                if let Some(event) = log_bonding_event(&recv_buf[..n]) {
+                    if event.get("event").and_then(|e| e.as_str()) == Some("keyframe_requested") {
+                        let request_id = event.get("request_id").and_then(|x| x.as_u64());
+                        state.pipeline.lock().await.request_keyframe(request_id);
+                    }
+                    continue;
+                }
+                match parse_bonding_stats(&recv_buf[..n]) {
+                    Ok(parsed) => {
+                        last_real_stats = Some(parsed);
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to parse bonding stats from strata-node");
+                    }
+                }
+                continue;
+            }
+        }
 
         // Check shutdown
         if *state.shutdown.borrow() {
@@ -53,24 +91,17 @@ pub async fn run(state: Arc<AgentState>) {
 
         let elapsed_s = pipeline.elapsed_s();
         let link_ifaces = pipeline.link_interfaces();
+        let element_cpu = pipeline.element_cpu();
         drop(pipeline); // Release lock before doing I/O
 
-        // Drain any pending stats from strata-node's UDP relay.
-        // We take the most recent one (in case multiple arrived in 1s).
-        if let Some(ref sock) = stats_rx {
-            while let Ok((n, _)) = sock.recv_from(&mut recv_buf) {
-                match parse_bonding_stats(&recv_buf[..n]) {
-                    Ok(parsed) => {
-                        last_real_stats = Some(parsed);
-                    }
-                    Err(e) => {
-                        tracing::warn!(error = %e, "failed to parse bonding stats from strata-node");
-                    }
-                }
-            }
-        }
-
-        let (mut links, commanded_bitrate_bps) = last_real_stats.clone().unwrap_or_default();
+        let RelayedStats {
+            mut links,
+            commanded_bitrate_bps,
+            quality,
+            mut elements,
+            media,
+        } = last_real_stats.clone().unwrap_or_default();
+        merge_element_cpu(&mut elements, element_cpu);
 
         // Overlay the spawn-time link→interface pinning onto stats whose
         // interface the pipeline didn't name — the dashboard needs every
@@ -106,7 +137,15 @@ pub async fn run(state: Arc<AgentState>) {
         // node didn't report a commanded target.
         let encoder_kbps: u64 = commanded_bitrate_bps
             .map(|b| b / 1000)
-            .unwrap_or_else(|| links.iter().map(|l| l.observed_bps).sum::<u64>() / 1000);
+            .unwrap_or_else(|| links.iter().map(|l| l.observed_bps).sum::<Bps>().0 / 1000);
+
+        // The ladder follows the same commanded target: observed throughput
+        // dips with every loss burst and would step the resolution on noise.
+        let ladder = state
+            .pipeline
+            .lock()
+            .await
+            .update_ladder(commanded_bitrate_bps.map(|b| (b / 1000) as u32));
 
         let timestamp_ms = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -122,17 +161,47 @@ pub async fn run(state: Arc<AgentState>) {
             links,
             sender_metrics: None,
             receiver_metrics: None,
+            ladder,
+            quality,
+            elements,
+            media,
         };
 
-        let envelope = Envelope::from_message(&AgentMessage::StreamStats(stats));
-        if let Ok(json) = envelope.and_then(|e| serde_json::to_string(&e))
-            && let Err(e) = state.control_tx.send(json).await
+        if let Ok(envelope) = Envelope::from_message(&AgentMessage::StreamStats(stats))
+            && let Err(e) = state.control_tx.send(envelope).await
         {
             tracing::warn!(error = %e, "failed to send stats to control channel");
         }
     }
 }
 
+/// Log a bonding state-transition event relayed alongside the stats (link
+/// up/down, phase change, failover, blacklist, keyframe request). Returns
+/// `None` if the datagram is not an event.
+fn log_bonding_event(data: &[u8]) -> Option<serde_json::Value> {
+    let v = serde_json::from_slice::<serde_json::Value>(data).ok()?;
+    let event = v.get("event").and_then(|e| e.as_str())?;
+    tracing::info!(
+        event,
+        seq = v.get("seq").and_then(|x| x.as_u64()),
+        link_id = v.get("link_id").and_then(|x| x.as_u64()),
+        detail = %v,
+        "bonding event"
+    );
+    Some(v)
+}
+
+/// Next datagram from strata-node's relay; never resolves without a socket.
+async fn recv_relay(
+    sock: Option<&tokio::net::UdpSocket>,
+    buf: &mut [u8],
+) -> std::io::Result<usize> {
+    match sock {
+        Some(sock) => sock.recv(buf).await,
+        None => std::future::pending().await,
+    }
+}
+
 /// Parse the bonding stats JSON relayed by strata-node.
 ///
 /// The JSON comes from the `strata-stats` GStreamer bus message
@@ -141,7 +210,10 @@ pub async fn run(state: Arc<AgentState>) {
 /// encoder target (top-level `current_bitrate_bps`). The latter is the
 /// real encoder bitrate; summed `observed_bps` is on-the-wire throughput
 /// (a different quantity) and must not masquerade as the encoder rate.
-fn parse_bonding_stats(data: &[u8]) -> Result<(Vec<LinkStats>, Option<u64>), String> {
+/// The session `quality` object appears once the receiver has reported;
+/// `elements` carries per-element queue levels and dropped buffers and
+/// `media` the input's audio levels and picture activity.
+fn parse_bonding_stats(data: &[u8]) -> Result<RelayedStats, String> {
     let v: serde_json::Value =
         serde_json::from_slice(data).map_err(|e| format!("JSON parse error: {e}"))?;
     let current_bitrate_bps = v
@@ -153,81 +225,42 @@ fn parse_bonding_stats(data: &[u8]) -> Result<(Vec<LinkStats>, Option<u64>), Str
         .and_then(|v| v.as_array())
         .ok_or_else(|| "missing 'links' array".to_string())?;
 
-    let mut stats = Vec::with_capacity(links_arr.len());
-    for link in links_arr {
-        let id = link.get("id").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-        let rtt_us = link.get("rtt_us").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let loss = link
-            .get("loss_rate")
-            .or_else(|| link.get("loss_percent"))
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let capacity = link
-            .get("capacity_bps")
-            .or_else(|| link.get("bandwidth_bps"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let sent = link
-            .get("sent_bytes")
-            .or_else(|| link.get("tx_bytes"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let observed_bps = link
-            .get("observed_bps")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let iface = link
-            .get("interface")
-            .or_else(|| link.get("iface"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-        let alive = link.get("alive").and_then(|v| v.as_bool()).unwrap_or(true);
-        let phase = link
-            .get("phase")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-        let os_up = link.get("os_up").and_then(|v| v.as_i64()).unwrap_or(-1);
-        let link_kind = link
-            .get("link_kind")
-            .and_then(|v| v.as_str())
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string());
-        let btlbw_bps = link.get("btlbw_bps").and_then(|v| v.as_u64());
-        let rtprop_ms = link.get("rtprop_ms").and_then(|v| v.as_f64());
-
-        // Derive human-readable state from alive/phase/os_up
-        let state = if !alive {
-            if os_up == 0 {
-                "OS Down".to_string()
-            } else {
-                "Down".to_string()
-            }
-        } else {
-            match phase {
-                "probing" => "Probing".to_string(),
-                "stable" => "Live".to_string(),
-                _ => "Live".to_string(),
-            }
-        };
+    let stats = links_arr
+        .iter()
+        .map(LinkSample::from_bonding_report)
+        .collect();
+    let quality = v
+        .get("quality")
+        .and_then(|q| serde_json::from_value(q.clone()).ok());
+    let elements = v
+        .get("elements")
+        .and_then(|e| serde_json::from_value(e.clone()).ok())
+        .unwrap_or_default();
+    let media = v
+        .get("media")
+        .and_then(|m| serde_json::from_value(m.clone()).ok());
+    Ok(RelayedStats {
+        links: stats,
+        commanded_bitrate_bps: current_bitrate_bps,
+        quality,
+        elements,
+        media,
+    })
+}
 
-        stats.push(LinkStats {
-            id,
-            interface: iface.to_string(),
-            state,
-            rtt_ms: rtt_us / 1000.0,
-            loss_rate: loss,
-            capacity_bps: capacity,
-            sent_bytes: sent,
-            observed_bps,
-            signal_dbm: None,
-            link_kind,
-            rsrp: None,
-            rsrq: None,
-            sinr: None,
-            cqi: None,
-            btlbw_bps,
-            rtprop_ms,
-        });
+/// Fold the child's per-thread CPU into the relayed element stats: a
+/// thread named after an element lands on that element's entry, any
+/// other thread (the bonding worker, say) gets an entry of its own.
+fn merge_element_cpu(elements: &mut Vec<ElementStats>, cpu: Vec<(String, f32)>) {
+    for (name, percent) in cpu {
+        match elements.iter_mut().find(|e| e.name == name) {
+            Some(element) => element.cpu_percent = Some(percent),
+            None => elements.push(ElementStats {
+                name,
+                cpu_percent: Some(percent),
+                ..Default::default()
+            }),
+        }
     }
-    Ok((stats, current_bitrate_bps))
+    elements.sort_by(|a, b| a.name.cmp(&b.name));
 }
@@ -24,7 +24,7 @@ pub async fn check_tcp_reachable(url: &str, timeout_secs: u64) -> bool {
 /// Handles `ws://`, `wss://`, `http://`, `https://`, `strata://`, `srt://`.
 /// Also accepts legacy `rist://` for backward compatibility.
 /// Strips the path portion (everything after the first `/` past the scheme).
-fn extract_host(url: &str) -> String {
+pub(crate) fn extract_host(url: &str) -> String {
     let stripped = url
         .trim_start_matches("ws://")
         .trim_start_matches("wss://")
@@ -263,6 +263,7 @@ impl Conn {
                 success: true,
                 sender_id: Some(sender_id.to_string()),
                 error: None,
+                telemetry_encoding: None,
             },
         ))
         .await;
@@ -300,6 +301,7 @@ impl Conn {
                 success: true,
                 sender_id: Some(device_id.to_string()),
                 error: None,
+                telemetry_encoding: None,
             },
         ))
         .await;
@@ -316,6 +318,7 @@ fn stream_start(stream_id: &str) -> ControlMessage {
             resolution: None,
             framerate: None,
             passthrough: None,
+            inputs: Vec::new(),
         },
         encoder: EncoderConfig {
             bitrate_kbps: 1_000,
@@ -324,11 +327,14 @@ fn stream_start(stream_id: &str) -> ControlMessage {
             codec: None,
             min_bitrate_kbps: None,
             max_bitrate_kbps: None,
+            ladder: Vec::new(),
+            overlay: None,
         },
         destinations: Vec::new(),
         bonding_config: serde_json::Value::Null,
         psk: None,
         relay_url: None,
+        ingest_key: None,
     }))
 }
 
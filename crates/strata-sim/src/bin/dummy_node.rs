@@ -49,11 +49,13 @@ async fn main() -> Result<()> {
                 }
             }
             "--stats-dest" => {
-                stats_dest = Some(
-                    args.next()
-                        .expect("Missing --stats-dest value")
-                        .parse::<SocketAddr>()?,
-                );
+                let v = args.next().expect("Missing --stats-dest value");
+                // `-` writes stats as JSON lines on stdout instead of UDP.
+                stats_dest = Some(if v == "-" {
+                    StatsDest::Stdout
+                } else {
+                    StatsDest::Udp(v.parse::<SocketAddr>()?)
+                });
             }
             "--bitrate" => {
                 bitrate_kbps = args.next().expect("Missing --bitrate value").parse()?;
@@ -107,6 +109,51 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Where periodic stats JSON goes.
+#[derive(Debug, Clone, Copy)]
+enum StatsDest {
+    Udp(SocketAddr),
+    Stdout,
+}
+
+enum StatsSink {
+    Udp(UdpSocket),
+    Stdout,
+}
+
+impl StatsSink {
+    async fn open(dest: Option<StatsDest>) -> Result<Option<Self>> {
+        Ok(match dest {
+            Some(StatsDest::Udp(addr)) => {
+                let sock = UdpSocket::bind("0.0.0.0:0").await?;
+                sock.connect(addr).await?;
+                Some(StatsSink::Udp(sock))
+            }
+            Some(StatsDest::Stdout) => Some(StatsSink::Stdout),
+            None => None,
+        })
+    }
+
+    async fn send(&self, json: &serde_json::Value) {
+        let Ok(json_str) = serde_json::to_string(json) else {
+            return;
+        };
+        match self {
+            StatsSink::Udp(sock) => {
+                let _ = sock.send(json_str.as_bytes()).await;
+            }
+            StatsSink::Stdout => println!("{json_str}"),
+        }
+    }
+}
+
+fn unix_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+}
+
 /// Simulates a video Group of Pictures (GOP) structure to produce realistic
 /// [`PacketProfile`] distributions for the bonding scheduler.
 ///
@@ -194,7 +241,7 @@ impl GopSimulator {
 
 async fn run_sender(
     dest_addrs: Vec<SocketAddr>,
-    stats_dest: Option<SocketAddr>,
+    stats_dest: Option<StatsDest>,
     bitrate_kbps: u32,
     critical_broadcast: bool,
     redundancy_enabled: bool,
@@ -213,17 +260,12 @@ async fn run_sender(
             uri: format!("strata://{}", dest),
             interface: None,
             profile: None,
+            rate_cap_bps: None,
         })?;
     }
 
     let stats_handle = sender.metrics_handle();
-    let stats_socket = if let Some(dest) = stats_dest {
-        let sock = UdpSocket::bind("0.0.0.0:0").await?;
-        sock.connect(dest).await?;
-        Some(sock)
-    } else {
-        None
-    };
+    let stats_socket = StatsSink::open(stats_dest).await?;
 
     let mut stats_interval = time::interval(Duration::from_millis(200));
     let mut current_bitrate_bps = bitrate_kbps as f64 * 1000.0;
@@ -312,14 +354,14 @@ async fn run_sender(
                         }));
                     }
                     let json = serde_json::json!({
-                        "timestamp_ms": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis(),
+                        "timestamp_ms": unix_millis(),
                         // Sender's current adapted bitrate (bps), follows capacity estimate
                         "current_bitrate_bps": current_bitrate_bps,
+                        // Packets handed to the bonding runtime so far
+                        "packets_sent": seq,
                         "links": links
                     });
-                    if let Ok(json_str) = serde_json::to_string(&json) {
-                        let _ = sock.send(json_str.as_bytes()).await;
-                    }
+                    sock.send(&json).await;
                 }
             }
         }
@@ -336,9 +378,40 @@ fn parse_bool_arg(value: &str, arg_name: &str) -> Result<bool> {
     }
 }
 
+/// A pause in delivery longer than this is reported as a stall.
+const STALL_THRESHOLD_MS: u128 = 100;
+
+/// Receiver-side delivery accounting, keyed on the sequence number the
+/// sender writes into the first 8 bytes of every payload.
+#[derive(Default)]
+struct DeliveryProgress {
+    delivered: u64,
+    max_seq: Option<u64>,
+    last_delivery_ms: Option<u128>,
+    /// `(start_ms, duration_ms)` of stalls not yet reported.
+    stalls: Vec<(u128, u128)>,
+}
+
+impl DeliveryProgress {
+    fn record(&mut self, payload: &[u8]) {
+        let now = unix_millis();
+        if let Some(last) = self.last_delivery_ms
+            && now - last > STALL_THRESHOLD_MS
+        {
+            self.stalls.push((last, now - last));
+        }
+        self.last_delivery_ms = Some(now);
+        self.delivered += 1;
+        if let Some(seq) = payload.get(..8) {
+            let seq = u64::from_be_bytes(seq.try_into().unwrap());
+            self.max_seq = Some(self.max_seq.map_or(seq, |m| m.max(seq)));
+        }
+    }
+}
+
 async fn run_receiver(
     bind_addrs: Vec<SocketAddr>,
-    stats_dest: Option<SocketAddr>,
+    stats_dest: Option<StatsDest>,
     running: Arc<AtomicBool>,
 ) -> Result<()> {
     let receiver = TransportBondingReceiver::new(Duration::from_millis(2000));
@@ -348,23 +421,19 @@ async fn run_receiver(
     }
 
     let stats_handle = receiver.stats_handle();
-    let stats_socket = if let Some(dest) = stats_dest {
-        let sock = UdpSocket::bind("0.0.0.0:0").await?;
-        sock.connect(dest).await?;
-        Some(sock)
-    } else {
-        None
-    };
+    let stats_socket = StatsSink::open(stats_dest).await?;
 
     let mut stats_interval = time::interval(Duration::from_millis(200));
 
     // Run the blocking recv in a separate thread to avoid blocking the async executor
     let rx = receiver.output_rx.clone();
     let running_clone = running.clone();
+    let progress = Arc::new(std::sync::Mutex::new(DeliveryProgress::default()));
+    let progress_clone = progress.clone();
     std::thread::spawn(move || {
         while running_clone.load(Ordering::Relaxed) {
-            if rx.recv_timeout(Duration::from_millis(100)).is_err() {
-                continue;
+            if let Ok((payload, _)) = rx.recv_timeout(Duration::from_millis(100)) {
+                progress_clone.lock().unwrap().record(&payload);
             }
         }
     });
@@ -372,10 +441,34 @@ async fn run_receiver(
     while running.load(Ordering::Relaxed) {
         tokio::select! {
             _ = stats_interval.tick() => {
-                if let Some(_sock) = &stats_socket {
-                    let _stats = stats_handle.lock().unwrap().clone();
-                    // We don't strictly need receiver stats for the tests right now,
-                    // but we can emit them if needed.
+                if let Some(sock) = &stats_socket {
+                    let stats = stats_handle.lock().unwrap().clone();
+                    let (delivered, max_seq, last_delivery_ms, stalls) = {
+                        let mut p = progress.lock().unwrap();
+                        (p.delivered, p.max_seq, p.last_delivery_ms, std::mem::take(&mut p.stalls))
+                    };
+                    let json = serde_json::json!({
+                        "timestamp_ms": unix_millis(),
+                        "delivered": delivered,
+                        "max_seq": max_seq,
+                        "last_delivery_ms": last_delivery_ms,
+                        // Delivery stalls that ended since the previous report
+                        "stalls": stalls
+                            .iter()
+                            .map(|(start_ms, duration_ms)| serde_json::json!({
+                                "start_ms": start_ms,
+                                "duration_ms": duration_ms,
+                            }))
+                            .collect::<Vec<_>>(),
+                        "reassembly": {
+                            "lost_packets": stats.lost_packets,
+                            "late_packets": stats.late_packets,
+                            "duplicate_packets": stats.duplicate_packets,
+                            "discontinuities": stats.discontinuities,
+                            "current_latency_ms": stats.current_latency_ms,
+                        },
+                    });
+                    sock.send(&json).await;
                 }
             }
         }
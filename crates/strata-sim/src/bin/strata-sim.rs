@@ -0,0 +1,389 @@
+//! Interactive scenario control.
+//!
+//! Starts a `dummy_node` sender/receiver pair over impaired links and reads
+//! commands from stdin, so scheduler behaviour can be explored by hand:
+//! take a link down, ramp its delay, and watch how traffic moves.
+//!
+//! ```text
+//! strata-sim --link lte_good --link lte_poor --bitrate 4000
+//! > watch
+//! > down 0
+//! > set 1 rate=2000 delay=80
+//! > quit
+//! ```
+//!
+//! Uses netns/netem when `STRATA_NETEM_TESTS` is set and `ip netns` works
+//! (run under `sudo -E`), otherwise the unprivileged impairment proxy;
+//! `--backend` forces one.
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use std::io::BufRead;
+use std::sync::mpsc;
+use std::time::Duration;
+use strata_sim::harness::{Backend, BondingTest, Session};
+use strata_sim::impairment::ImpairmentConfig;
+
+const USAGE: &str = "\
+usage: strata-sim [--link PRESET]... [--bitrate KBPS] [--backend netns|proxy]
+                  [--node PATH] [--name NAME]
+
+--name prefixes namespaces and interfaces (≤10 alphanumerics, default strsim);
+--node replaces dummy_node with a compatible binary.
+
+presets: lte_good lte_urban lte_poor fiveg_good ideal (default: two lte_good)";
+
+const HELP: &str = "\
+commands:
+  links                       show each link's current impairment
+  set <link> <key>=<value>..  change a link; keys: rate (kbit), delay (ms),
+                              jitter (ms), loss (%), dup (%)
+  preset <link> <name>        replace a link's impairment with a preset
+  down <link> / up <link>     100% loss / back to the previous config
+  stats                       print the latest stats once
+  watch                       toggle a stats line every second
+  quit                        stop and print a summary";
+
+fn main() -> Result<()> {
+    let mut configs = Vec::new();
+    let mut bitrate_kbps = None;
+    let mut backend = None;
+    let mut node = None;
+    let mut name = "strsim".to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("missing {arg} value"));
+        match arg.as_str() {
+            "--link" => configs.push(preset(&value()?)?),
+            "--bitrate" => bitrate_kbps = Some(value()?.parse()?),
+            "--backend" => {
+                backend = Some(match value()?.as_str() {
+                    "netns" => Backend::Netns,
+                    "proxy" => Backend::Proxy,
+                    other => bail!("unknown backend {other:?}"),
+                })
+            }
+            "--node" => node = Some(value()?),
+            "--name" => name = value()?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            other => bail!("unknown argument {other:?}\n{USAGE}"),
+        }
+    }
+    if configs.is_empty() {
+        configs = vec![ImpairmentConfig::lte_good(); 2];
+    }
+
+    let mut test = BondingTest::new(&name);
+    for config in &configs {
+        test = test.static_link(config.clone());
+    }
+    if let Some(kbps) = bitrate_kbps {
+        test = test.bitrate_kbps(kbps);
+    }
+    if let Some(backend) = backend {
+        test = test.backend(backend);
+    }
+    if let Some(node) = node {
+        test = test.binary(node);
+    }
+
+    let session = test.start();
+    println!(
+        "{} links over {:?}; type `help` for commands",
+        session.num_links(),
+        session.backend()
+    );
+    let mut links: Vec<Link> = configs
+        .into_iter()
+        .map(|config| Link {
+            config,
+            saved: None,
+        })
+        .collect();
+
+    // Read stdin on its own thread so the watch line keeps ticking.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut watching = false;
+    loop {
+        let line = match rx.recv_timeout(Duration::from_secs(1)) {
+            Ok(line) => line,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if watching {
+                    println!("{}", stats_line(&session));
+                }
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        match parse_command(&line) {
+            Ok(None) => {}
+            Ok(Some(Command::Quit)) => break,
+            Ok(Some(Command::Help)) => println!("{HELP}"),
+            Ok(Some(Command::Links)) => {
+                for (i, link) in links.iter().enumerate() {
+                    let state = if link.saved.is_some() { " (down)" } else { "" };
+                    println!("link {i}{state}: {}", describe(&link.config));
+                }
+            }
+            Ok(Some(Command::Stats)) => println!("{}", stats_line(&session)),
+            Ok(Some(Command::Watch)) => watching = !watching,
+            Ok(Some(Command::Change(i, change))) => {
+                if let Err(e) = apply(&session, &mut links, i, change) {
+                    println!("error: {e}");
+                }
+            }
+            Err(e) => println!("error: {e}"),
+        }
+    }
+
+    println!("stopping…");
+    let outcome = session.stop();
+    println!(
+        "delivered {}/{} ({:.1}%), longest stall {:?}, reassembly lost {}",
+        outcome.packets_delivered,
+        outcome.packets_sent,
+        outcome.delivered_ratio() * 100.0,
+        outcome.max_gap(),
+        outcome.reassembly.lost_packets
+    );
+    Ok(())
+}
+
+struct Link {
+    config: ImpairmentConfig,
+    /// The config to restore on `up`, while the link is down.
+    saved: Option<ImpairmentConfig>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Links,
+    Change(usize, Change),
+    Stats,
+    Watch,
+    Help,
+    Quit,
+}
+
+#[derive(Debug, PartialEq)]
+enum Change {
+    Set(Vec<(Field, f64)>),
+    Preset(ImpairmentConfig),
+    Down,
+    Up,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Rate,
+    Delay,
+    Jitter,
+    Loss,
+    Duplicate,
+}
+
+/// `Ok(None)` for a blank line.
+fn parse_command(line: &str) -> Result<Option<Command>> {
+    let mut words = line.split_whitespace();
+    let Some(verb) = words.next() else {
+        return Ok(None);
+    };
+    let mut link = || -> Result<usize> {
+        let raw = words.next().context("missing link number")?;
+        raw.parse()
+            .with_context(|| format!("invalid link number {raw:?}"))
+    };
+    let command = match verb {
+        "links" | "ls" => Command::Links,
+        "stats" => Command::Stats,
+        "watch" => Command::Watch,
+        "help" | "?" => Command::Help,
+        "quit" | "exit" | "q" => Command::Quit,
+        "down" => Command::Change(link()?, Change::Down),
+        "up" => Command::Change(link()?, Change::Up),
+        "preset" => {
+            let i = link()?;
+            let name = words.next().context("missing preset name")?;
+            Command::Change(i, Change::Preset(preset(name)?))
+        }
+        "set" => {
+            let i = link()?;
+            let fields = words
+                .map(|kv| {
+                    let (key, value) = kv
+                        .split_once('=')
+                        .with_context(|| format!("expected key=value, got {kv:?}"))?;
+                    let field = match key {
+                        "rate" => Field::Rate,
+                        "delay" => Field::Delay,
+                        "jitter" => Field::Jitter,
+                        "loss" => Field::Loss,
+                        "dup" => Field::Duplicate,
+                        other => bail!("unknown key {other:?}"),
+                    };
+                    let value: f64 = value
+                        .parse()
+                        .with_context(|| format!("invalid {key} {value:?}"))?;
+                    if value < 0.0 {
+                        bail!("{key} must not be negative");
+                    }
+                    Ok((field, value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if fields.is_empty() {
+                bail!("nothing to set");
+            }
+            Command::Change(i, Change::Set(fields))
+        }
+        other => bail!("unknown command {other:?} (try `help`)"),
+    };
+    Ok(Some(command))
+}
+
+fn preset(name: &str) -> Result<ImpairmentConfig> {
+    ImpairmentConfig::preset(name).with_context(|| format!("unknown preset {name:?}"))
+}
+
+fn apply(session: &Session, links: &mut [Link], i: usize, change: Change) -> Result<()> {
+    let link = links.get_mut(i).with_context(|| format!("no link {i}"))?;
+    match change {
+        Change::Down => {
+            if link.saved.is_none() {
+                link.saved = Some(link.config.clone());
+            }
+            link.config = ImpairmentConfig {
+                loss_percent: Some(100.0),
+                gemodel: None,
+                ..link.config.clone()
+            };
+        }
+        Change::Up => {
+            link.config = link.saved.take().context("link is not down")?;
+        }
+        Change::Preset(config) => {
+            link.saved = None;
+            link.config = config;
+        }
+        Change::Set(fields) => {
+            let config = &mut link.config;
+            for (field, value) in fields {
+                match field {
+                    Field::Rate => config.rate_kbit = Some(value as u64),
+                    Field::Delay => config.delay_ms = Some(value as u32),
+                    Field::Jitter => config.jitter_ms = Some(value as u32),
+                    Field::Loss => {
+                        config.loss_percent = Some(value as f32);
+                        config.gemodel = None;
+                    }
+                    Field::Duplicate => config.duplicate_percent = Some(value as f32),
+                }
+            }
+        }
+    }
+    session.set_link(i, link.config.clone())?;
+    println!("link {i}: {}", describe(&link.config));
+    Ok(())
+}
+
+fn describe(config: &ImpairmentConfig) -> String {
+    let mut parts = Vec::new();
+    if let Some(rate) = config.rate_kbit {
+        parts.push(format!("rate {rate} kbit"));
+    }
+    if let Some(delay) = config.delay_ms {
+        parts.push(format!("delay {delay} ms"));
+    }
+    if let Some(jitter) = config.jitter_ms {
+        parts.push(format!("jitter {jitter} ms"));
+    }
+    if config.gemodel.is_some() {
+        parts.push("gemodel loss".to_string());
+    } else if let Some(loss) = config.loss_percent {
+        parts.push(format!("loss {loss}%"));
+    }
+    if let Some(dup) = config.duplicate_percent {
+        parts.push(format!("dup {dup}%"));
+    }
+    if parts.is_empty() {
+        "unimpaired".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// One line: sender bitrate, each link's throughput/RTT/loss, and what the
+/// receiver delivered.
+fn stats_line(session: &Session) -> String {
+    let mut line = String::new();
+    if let Some(tx) = session.sender_stats() {
+        let f = |v: &Value, key: &str| v.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        line += &format!("tx {:.2} Mb/s", f(&tx, "current_bitrate_bps") / 1e6);
+        for link in tx
+            .get("links")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            line += &format!(
+                " | link{} {:.2} Mb/s {:.0} ms {:.1}%",
+                link.get("link_id").and_then(Value::as_u64).unwrap_or(0),
+                f(link, "observed_bps") / 1e6,
+                f(link, "rtt_ms"),
+                f(link, "loss_ratio") * 100.0
+            );
+        }
+    } else {
+        line += "tx (no stats yet)";
+    }
+    if let Some(rx) = session.receiver_stats() {
+        let u = |v: Option<&Value>, key: &str| {
+            v.and_then(|v| v.get(key))
+                .and_then(Value::as_u64)
+                .unwrap_or(0)
+        };
+        line += &format!(
+            " | rx {} delivered, {} lost",
+            u(Some(&rx), "delivered"),
+            u(rx.get("reassembly"), "lost_packets")
+        );
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse_command("  ").unwrap(), None);
+        assert_eq!(parse_command("quit").unwrap(), Some(Command::Quit));
+        assert_eq!(
+            parse_command("down 1").unwrap(),
+            Some(Command::Change(1, Change::Down))
+        );
+        assert_eq!(
+            parse_command("set 0 rate=2000 loss=2.5").unwrap(),
+            Some(Command::Change(
+                0,
+                Change::Set(vec![(Field::Rate, 2000.0), (Field::Loss, 2.5)])
+            ))
+        );
+        assert!(parse_command("set 0").is_err());
+        assert!(parse_command("set 0 speed=1").is_err());
+        assert!(parse_command("set x rate=1").is_err());
+        assert!(parse_command("preset 0 dialup").is_err());
+        assert!(parse_command("frobnicate").is_err());
+    }
+}
@@ -5,12 +5,17 @@
 //! - **Handover simulation** — gradual SINR degradation + recovery
 //! - **Correlated fading** — all links degrade simultaneously
 //! - **Asymmetric capacity** — one link much faster than others
+//! - **Mobility handovers** — repeated cell changes at a pedestrian,
+//!   vehicle or train cadence
 //!
 //! These produce `Vec<ScenarioFrame>` compatible with the existing
 //! impairment infrastructure.
 
-use crate::impairment::ImpairmentConfig;
+use crate::impairment::{ImpairmentConfig, ImpairmentSchedule, schedule_frames};
 use crate::scenario::ScenarioFrame;
+use rand::RngExt as _;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use std::time::Duration;
 
 /// A link failure event: link drops, then recovers after a duration.
@@ -285,6 +290,229 @@ impl CorrelatedFadingScenario {
     }
 }
 
+/// How fast the sender moves through the cellular network, which sets how
+/// often and how badly its modems hand over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobilityProfile {
+    /// Walking: rare, quick handovers between similar cells.
+    Pedestrian,
+    /// Road vehicle: handovers every few tens of seconds, visible RTT steps.
+    Vehicle,
+    /// High-speed rail: frequent, long gaps, and every modem on board
+    /// crosses the cell edge at nearly the same moment.
+    Train,
+}
+
+/// Per-profile handover statistics.
+#[derive(Debug, Clone)]
+pub struct MobilityParams {
+    /// Time between handovers on one link, uniformly distributed.
+    pub interval_secs: (f64, f64),
+    /// Full-loss gap while the modem detaches and re-attaches.
+    pub gap_ms: (u64, u64),
+    /// Largest change in one-way delay on the new cell (either direction).
+    pub rtt_step_ms: u32,
+    /// The new cell's capacity as a fraction of the link's baseline.
+    pub capacity_factor: (f64, f64),
+    /// Time for the new cell's grant to grow to full capacity.
+    pub ramp: Duration,
+    /// Capacity right after re-attach, as a fraction of the new cell's.
+    pub ramp_floor: f64,
+    /// When set, every link hands over within this spread of the same
+    /// instant instead of independently.
+    pub cluster_spread: Option<Duration>,
+}
+
+impl MobilityProfile {
+    pub fn params(self) -> MobilityParams {
+        match self {
+            MobilityProfile::Pedestrian => MobilityParams {
+                interval_secs: (60.0, 180.0),
+                gap_ms: (30, 60),
+                rtt_step_ms: 4,
+                capacity_factor: (0.7, 1.0),
+                ramp: Duration::from_secs(1),
+                ramp_floor: 0.5,
+                cluster_spread: None,
+            },
+            MobilityProfile::Vehicle => MobilityParams {
+                interval_secs: (15.0, 45.0),
+                gap_ms: (50, 150),
+                rtt_step_ms: 12,
+                capacity_factor: (0.4, 1.0),
+                ramp: Duration::from_secs(2),
+                ramp_floor: 0.3,
+                cluster_spread: None,
+            },
+            MobilityProfile::Train => MobilityParams {
+                interval_secs: (6.0, 20.0),
+                gap_ms: (150, 600),
+                rtt_step_ms: 25,
+                capacity_factor: (0.2, 0.9),
+                ramp: Duration::from_secs(3),
+                ramp_floor: 0.1,
+                cluster_spread: Some(Duration::from_secs(2)),
+            },
+        }
+    }
+}
+
+/// One handover on one link.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandoverEvent {
+    /// When the modem detaches from the old cell.
+    pub at: Duration,
+    /// How long the link carries nothing.
+    pub gap: Duration,
+    /// One-way delay on the new cell.
+    pub delay_ms: u32,
+    /// Capacity the new cell ramps up to.
+    pub rate_kbit: u64,
+}
+
+/// Repeated LTE/5G handovers on every link, at the cadence of a
+/// [`MobilityProfile`].
+///
+/// Each handover is a full-loss gap, a step to the new cell's RTT, and a
+/// capacity ramp from a fraction of the new cell's rate up to all of it.
+/// Deterministic for a given seed, so a failing run can be replayed; the
+/// seed also reaches netem (link `i` gets `seed + i`).
+///
+/// Gaps are tens to hundreds of milliseconds: sample [`frames`](Self::frames)
+/// with a `step` no longer than the shortest gap, or play
+/// [`schedules`](Self::schedules) through an
+/// [`ImpairmentScheduler`](crate::impairment::ImpairmentScheduler).
+#[derive(Debug, Clone)]
+pub struct CellularHandoverScenario {
+    pub seed: u64,
+    /// Total scenario duration.
+    pub duration: Duration,
+    /// Time step for [`frames`](Self::frames).
+    pub step: Duration,
+    pub profile: MobilityProfile,
+    /// Each link's serving-cell config; handovers vary rate and delay
+    /// around it.
+    pub links: Vec<ImpairmentConfig>,
+}
+
+impl Default for CellularHandoverScenario {
+    fn default() -> Self {
+        CellularHandoverScenario {
+            seed: 1,
+            duration: Duration::from_secs(120),
+            step: Duration::from_millis(25),
+            profile: MobilityProfile::Vehicle,
+            links: vec![ImpairmentConfig::lte_urban(), ImpairmentConfig::lte_good()],
+        }
+    }
+}
+
+impl CellularHandoverScenario {
+    /// Handovers on each link, in time order.
+    pub fn events(&self) -> Vec<Vec<HandoverEvent>> {
+        let params = self.profile.params();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut uniform = |(lo, hi): (f64, f64)| lo + rng.random::<f64>() * (hi - lo);
+
+        // Clustered profiles share one timeline of cell edges.
+        let mut shared = Vec::new();
+        if params.cluster_spread.is_some() {
+            let mut t = Duration::ZERO;
+            loop {
+                t += Duration::from_secs_f64(uniform(params.interval_secs));
+                if t >= self.duration {
+                    break;
+                }
+                shared.push(t);
+            }
+        }
+
+        self.links
+            .iter()
+            .map(|base| {
+                let base_rate = base.rate_kbit.unwrap_or(5_000) as f64;
+                let base_delay = base.delay_ms.unwrap_or(20) as f64;
+                let mut events: Vec<HandoverEvent> = Vec::new();
+                let mut t = Duration::ZERO;
+                let mut edges = shared.iter();
+                loop {
+                    let at = match params.cluster_spread {
+                        Some(spread) => match edges.next() {
+                            Some(&edge) => edge + spread.mul_f64(uniform((0.0, 1.0))),
+                            None => break,
+                        },
+                        None => t + Duration::from_secs_f64(uniform(params.interval_secs)),
+                    };
+                    // Never start a handover before the previous one settled.
+                    let settled = events
+                        .last()
+                        .map_or(Duration::ZERO, |e| e.at + e.gap + params.ramp);
+                    let at = at.max(settled);
+                    if at >= self.duration {
+                        break;
+                    }
+                    let gap_ms = uniform((params.gap_ms.0 as f64, params.gap_ms.1 as f64));
+                    let step = params.rtt_step_ms as f64;
+                    events.push(HandoverEvent {
+                        at,
+                        gap: Duration::from_millis(gap_ms as u64),
+                        delay_ms: (base_delay + uniform((-step, step))).max(1.0) as u32,
+                        rate_kbit: (base_rate * uniform(params.capacity_factor)).max(1.0) as u64,
+                    });
+                    t = at;
+                }
+                events
+            })
+            .collect()
+    }
+
+    /// One impairment schedule per link.
+    pub fn schedules(&self) -> Vec<ImpairmentSchedule> {
+        let params = self.profile.params();
+        self.links
+            .iter()
+            .zip(self.events())
+            .enumerate()
+            .map(|(i, (base, events))| {
+                let mut schedule = ImpairmentSchedule::new();
+                let mut serving = base.clone();
+                let mut t = Duration::ZERO;
+                for event in events {
+                    schedule = schedule.hold(event.at - t, serving.clone());
+
+                    let blackout = ImpairmentConfig {
+                        loss_percent: Some(100.0),
+                        gemodel: None,
+                        ..serving.clone()
+                    };
+                    schedule = schedule.hold(event.gap, blackout);
+
+                    serving = ImpairmentConfig {
+                        rate_kbit: Some(event.rate_kbit),
+                        delay_ms: Some(event.delay_ms),
+                        ..base.clone()
+                    };
+                    let attach = ImpairmentConfig {
+                        rate_kbit: Some(
+                            ((event.rate_kbit as f64 * params.ramp_floor) as u64).max(1),
+                        ),
+                        ..serving.clone()
+                    };
+                    schedule = schedule.ramp(params.ramp, attach, serving.clone());
+                    t = event.at + event.gap + params.ramp;
+                }
+                schedule
+                    .hold(self.duration.saturating_sub(t), serving)
+                    .with_seed(self.seed.wrapping_add(i as u64))
+            })
+            .collect()
+    }
+
+    pub fn frames(&self) -> Vec<ScenarioFrame> {
+        schedule_frames(&self.schedules(), self.step)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -460,4 +688,61 @@ mod tests {
             "bell-shaped: mid={mid_rate}, first={first_rate}, last={last_rate}"
         );
     }
+
+    // ─── Mobility Handovers ─────────────────────────────────────────────
+
+    fn mobility(profile: MobilityProfile) -> CellularHandoverScenario {
+        CellularHandoverScenario {
+            duration: Duration::from_secs(300),
+            profile,
+            links: vec![ImpairmentConfig::ideal(8_000, 20); 3],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mobility_handovers_are_deterministic_for_seed() {
+        let scenario = mobility(MobilityProfile::Vehicle);
+        assert_eq!(scenario.events(), scenario.events());
+        let other = CellularHandoverScenario {
+            seed: 2,
+            ..scenario.clone()
+        };
+        assert_ne!(scenario.events(), other.events());
+    }
+
+    #[test]
+    fn mobility_handover_is_gap_then_ramp() {
+        let scenario = mobility(MobilityProfile::Vehicle);
+        let events = scenario.events();
+        let schedule = &scenario.schedules()[0];
+        let params = MobilityProfile::Vehicle.params();
+
+        assert!(!events[0].is_empty());
+        for event in &events[0] {
+            let during_gap = schedule.config_at(event.at + event.gap / 2);
+            assert_eq!(during_gap.loss_percent, Some(100.0));
+
+            let attached = schedule.config_at(event.at + event.gap);
+            assert_eq!(attached.loss_percent, Some(0.0));
+            assert_eq!(attached.delay_ms, Some(event.delay_ms));
+            assert!(attached.rate_kbit.unwrap() < event.rate_kbit);
+
+            let settled = schedule.config_at(event.at + event.gap + params.ramp);
+            assert_eq!(settled.rate_kbit, Some(event.rate_kbit));
+        }
+    }
+
+    #[test]
+    fn mobility_train_hands_over_more_often_and_together() {
+        let walk = mobility(MobilityProfile::Pedestrian).events();
+        let train = mobility(MobilityProfile::Train).events();
+        assert!(train[0].len() > walk[0].len() * 3);
+
+        let spread = MobilityProfile::Train.params().cluster_spread.unwrap();
+        for (a, b) in train[0].iter().zip(&train[1]) {
+            let apart = a.at.max(b.at) - a.at.min(b.at);
+            assert!(apart <= spread, "links handed over {apart:?} apart");
+        }
+    }
 }
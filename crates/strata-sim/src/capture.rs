@@ -0,0 +1,402 @@
+//! # Packet Capture and Analysis
+//!
+//! Records pcaps with `tcpdump` inside a namespace while a scenario runs,
+//! then decodes the Strata packets in them into per-link reports, so tests
+//! can assert on what the scheduler actually put on each link (reordering,
+//! duplication, redundancy, traffic split) rather than only on end-to-end
+//! byte counts.
+//!
+//! Capture on the **receiver** side of each link: on the sender side the
+//! tap sits after the netem qdisc, so packets netem dropped never show up
+//! either way, but only the receiver side shows netem's reordering and
+//! duplication.
+//!
+//! ```text
+//! let cap = Capture::start(&ns_rcv, "st_c1_b", dir.join("link0.pcap"))?;
+//! // ... run the scenario ...
+//! let packets = cap.stop()?;
+//! let report = LinkReport::from_packets(&packets);
+//! assert_eq!(report.duplicates, 0);
+//! ```
+
+use crate::topology::Namespace;
+use anyhow::{Context, Result, bail};
+use bytes::Buf;
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+use strata_transport::wire::{Fragment, PacketHeader, PacketType};
+
+/// Bytes kept per packet: enough for Ethernet + IPv4 + UDP + the Strata
+/// header + the bonding sequence number.
+const SNAPLEN: &str = "128";
+
+/// A running `tcpdump` inside a namespace.
+pub struct Capture {
+    child: Child,
+    interface: String,
+    path: PathBuf,
+}
+
+impl Capture {
+    /// Start capturing UDP on `interface` in `ns`, writing to `path`.
+    pub fn start(ns: &Namespace, interface: &str, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let path_str = path.to_str().context("capture path is not UTF-8")?;
+        let child = Command::new("sudo")
+            .args(["ip", "netns", "exec", &ns.name, "tcpdump"])
+            .args(["-i", interface, "-w", path_str, "-U", "-s", SNAPLEN, "udp"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("spawning tcpdump on {interface} in {}", ns.name))?;
+        // tcpdump needs a moment to open the interface; packets sent before
+        // then are not recorded.
+        std::thread::sleep(Duration::from_millis(500));
+        Ok(Self {
+            child,
+            interface: interface.to_string(),
+            path,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stop the capture and decode what it recorded.
+    pub fn stop(mut self) -> Result<Vec<CapturedPacket>> {
+        // As with the pipeline processes, killing the child only reaches
+        // `sudo`; match the real tcpdump by its command line.
+        let pattern = format!("tcpdump -i {} -w {}", self.interface, self.path.display());
+        let _ = Command::new("sudo")
+            .args(["pkill", "-INT", "-f", &pattern])
+            .output();
+        let _ = self.child.wait();
+        // tcpdump runs as root; make the file readable for the test.
+        let _ = Command::new("sudo")
+            .args(["chmod", "a+r"])
+            .arg(&self.path)
+            .output();
+        let data = std::fs::read(&self.path)
+            .with_context(|| format!("reading {}", self.path.display()))?;
+        read_pcap(&data)
+    }
+}
+
+/// One captured IPv4/UDP datagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedPacket {
+    /// Capture timestamp (since the Unix epoch).
+    pub ts: Duration,
+    pub src: SocketAddrV4,
+    pub dst: SocketAddrV4,
+    /// UDP payload length on the wire.
+    pub len: usize,
+    /// UDP payload, truncated to the capture's snap length.
+    pub payload: Vec<u8>,
+}
+
+impl CapturedPacket {
+    /// Decode the Strata headers in the payload, if it is a Strata packet.
+    pub fn strata(&self) -> Option<StrataPacket> {
+        let mut buf = self.payload.as_slice();
+        let header = PacketHeader::decode(&mut buf)?;
+        let carries_bonding_header = header.packet_type == PacketType::Data
+            && !header.is_ppd_probe
+            && matches!(header.fragment, Fragment::Complete | Fragment::Start);
+        let bonding_seq = if carries_bonding_header && buf.remaining() >= 8 {
+            Some(buf.get_u64())
+        } else {
+            None
+        };
+        Some(StrataPacket {
+            packet_type: header.packet_type,
+            link_seq: header.sequence.value(),
+            bonding_seq,
+            is_keyframe: header.is_keyframe,
+        })
+    }
+}
+
+/// The parts of a Strata packet the reports use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrataPacket {
+    pub packet_type: PacketType,
+    /// Per-link transport sequence number.
+    pub link_seq: u64,
+    /// Bonding (cross-link) sequence number, on data packets that start a
+    /// bonded payload.
+    pub bonding_seq: Option<u64>,
+    pub is_keyframe: bool,
+}
+
+/// Parse a classic pcap file (microsecond or nanosecond timestamps, either
+/// byte order) with Ethernet or Linux cooked framing.  Anything that isn't
+/// IPv4/UDP is skipped.
+pub fn read_pcap(data: &[u8]) -> Result<Vec<CapturedPacket>> {
+    if data.len() < 24 {
+        bail!("pcap file is truncated");
+    }
+    let magic = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let (big_endian, nanos) = match magic {
+        0xa1b2c3d4 => (false, false),
+        0xa1b23c4d => (false, true),
+        0xd4c3b2a1 => (true, false),
+        0x4d3cb2a1 => (true, true),
+        _ => bail!("not a pcap file (magic {magic:#x})"),
+    };
+    let u32_at = |b: &[u8], at: usize| {
+        let raw: [u8; 4] = b[at..at + 4].try_into().unwrap();
+        if big_endian {
+            u32::from_be_bytes(raw)
+        } else {
+            u32::from_le_bytes(raw)
+        }
+    };
+    let link_header = match u32_at(data, 20) {
+        1 => 14,   // Ethernet
+        113 => 16, // Linux cooked (SLL)
+        other => bail!("unsupported pcap link type {other}"),
+    };
+
+    let mut packets = Vec::new();
+    let mut at = 24;
+    while at + 16 <= data.len() {
+        let secs = u32_at(data, at) as u64;
+        let frac = u32_at(data, at + 4) as u64;
+        let caplen = u32_at(data, at + 8) as usize;
+        at += 16;
+        let Some(frame) = data.get(at..at + caplen) else {
+            bail!("pcap record at byte {at} is truncated");
+        };
+        at += caplen;
+
+        let ts = Duration::from_secs(secs)
+            + if nanos {
+                Duration::from_nanos(frac)
+            } else {
+                Duration::from_micros(frac)
+            };
+        if let Some(packet) = parse_udp(frame, link_header, ts) {
+            packets.push(packet);
+        }
+    }
+    Ok(packets)
+}
+
+fn parse_udp(frame: &[u8], link_header: usize, ts: Duration) -> Option<CapturedPacket> {
+    // The EtherType (or SLL protocol) is the last two bytes of the link header.
+    let ethertype = u16::from_be_bytes(frame.get(link_header - 2..link_header)?.try_into().ok()?);
+    if ethertype != 0x0800 {
+        return None;
+    }
+    let ip = frame.get(link_header..)?;
+    if ip.len() < 20 || ip[9] != 17 {
+        return None;
+    }
+    let ihl = (ip[0] & 0x0f) as usize * 4;
+    if ihl < 20 {
+        return None;
+    }
+    let addr = |at: usize| Ipv4Addr::new(ip[at], ip[at + 1], ip[at + 2], ip[at + 3]);
+    let (src_ip, dst_ip) = (addr(12), addr(16));
+    let udp = ip.get(ihl..)?;
+    let be16 = |at: usize| Some(u16::from_be_bytes(udp.get(at..at + 2)?.try_into().ok()?));
+    let (src_port, dst_port, udp_len) = (be16(0)?, be16(2)?, be16(4)? as usize);
+    Some(CapturedPacket {
+        ts,
+        src: SocketAddrV4::new(src_ip, src_port),
+        dst: SocketAddrV4::new(dst_ip, dst_port),
+        len: udp_len.saturating_sub(8),
+        payload: udp.get(8..).unwrap_or_default().to_vec(),
+    })
+}
+
+/// What one link carried, from a receiver-side capture.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkReport {
+    /// Strata data packets seen, including duplicates.
+    pub data_packets: u64,
+    pub control_packets: u64,
+    /// UDP payload bytes of the data packets.
+    pub data_bytes: u64,
+    /// Data packets that arrived after one with a higher link sequence.
+    pub reordered: u64,
+    /// Data packets whose link sequence had already been seen
+    /// (retransmissions and network duplicates).
+    pub duplicates: u64,
+    /// Link sequence numbers never seen between the first and last one.
+    pub missing: u64,
+    /// Distinct bonding sequence numbers carried.
+    pub bonding_seqs: HashSet<u64>,
+}
+
+impl LinkReport {
+    pub fn from_packets(packets: &[CapturedPacket]) -> Self {
+        let mut report = Self::default();
+        let mut seen = HashSet::new();
+        let mut highest: Option<u64> = None;
+        let mut lowest: Option<u64> = None;
+        for packet in packets {
+            let Some(strata) = packet.strata() else {
+                continue;
+            };
+            if strata.packet_type == PacketType::Control {
+                report.control_packets += 1;
+                continue;
+            }
+            report.data_packets += 1;
+            report.data_bytes += packet.len as u64;
+            if !seen.insert(strata.link_seq) {
+                report.duplicates += 1;
+            } else if highest.is_some_and(|h| strata.link_seq < h) {
+                report.reordered += 1;
+            }
+            highest = highest.max(Some(strata.link_seq));
+            lowest = Some(lowest.map_or(strata.link_seq, |l| l.min(strata.link_seq)));
+            if let Some(seq) = strata.bonding_seq {
+                report.bonding_seqs.insert(seq);
+            }
+        }
+        if let (Some(lo), Some(hi)) = (lowest, highest) {
+            report.missing = (hi - lo + 1).saturating_sub(seen.len() as u64);
+        }
+        report
+    }
+
+    /// Share of this link's sequence space that arrived.
+    pub fn delivery_ratio(&self) -> f64 {
+        let expected = self.data_packets - self.duplicates + self.missing;
+        if expected == 0 {
+            return 0.0;
+        }
+        (self.data_packets - self.duplicates) as f64 / expected as f64
+    }
+}
+
+/// How the scheduler spread bonded payloads across links.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BondingReport {
+    /// Distinct bonding sequence numbers seen on any link.
+    pub unique: u64,
+    /// Sequence numbers carried on more than one link (broadcast or
+    /// redundancy).
+    pub duplicated: u64,
+    /// Fraction of `unique` each link carried, in link order.
+    pub link_share: Vec<f64>,
+}
+
+impl BondingReport {
+    pub fn from_links(links: &[LinkReport]) -> Self {
+        let mut carriers: HashMap<u64, u32> = HashMap::new();
+        for link in links {
+            for &seq in &link.bonding_seqs {
+                *carriers.entry(seq).or_default() += 1;
+            }
+        }
+        let unique = carriers.len() as u64;
+        let share = |link: &LinkReport| {
+            if unique == 0 {
+                0.0
+            } else {
+                link.bonding_seqs.len() as f64 / unique as f64
+            }
+        };
+        Self {
+            unique,
+            duplicated: carriers.values().filter(|&&n| n > 1).count() as u64,
+            link_share: links.iter().map(share).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{BufMut, BytesMut};
+    use strata_transport::wire::Packet;
+
+    fn strata_payload(link_seq: u64, bonding_seq: u64) -> Vec<u8> {
+        let mut body = BytesMut::new();
+        body.put_u64(bonding_seq);
+        body.put_u64(0);
+        body.put_slice(&[0u8; 32]);
+        Packet::new_data(link_seq, 0, body.freeze())
+            .encode()
+            .to_vec()
+    }
+
+    /// A little-endian microsecond pcap with Ethernet framing.
+    fn pcap(payloads: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for v in [0xa1b2c3d4u32, 0x0004_0002, 0, 0, 65_535, 1] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        for (i, payload) in payloads.iter().enumerate() {
+            let mut frame = vec![0u8; 12];
+            frame.extend_from_slice(&[0x08, 0x00]);
+            let ip_len = 20 + 8 + payload.len() as u16;
+            frame.extend_from_slice(&[0x45, 0, (ip_len >> 8) as u8, ip_len as u8]);
+            frame.extend_from_slice(&[0, 0, 0, 0, 64, 17, 0, 0]);
+            frame.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+            let udp_len = 8 + payload.len() as u16;
+            frame.extend_from_slice(&7000u16.to_be_bytes());
+            frame.extend_from_slice(&7001u16.to_be_bytes());
+            frame.extend_from_slice(&udp_len.to_be_bytes());
+            frame.extend_from_slice(&[0, 0]);
+            frame.extend_from_slice(payload);
+
+            for v in [
+                1_000u32,
+                i as u32 * 1_000,
+                frame.len() as u32,
+                frame.len() as u32,
+            ] {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+            out.extend_from_slice(&frame);
+        }
+        out
+    }
+
+    #[test]
+    fn reads_udp_from_pcap() {
+        let packets = read_pcap(&pcap(&[strata_payload(0, 7)])).unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].src, "10.0.0.1:7000".parse().unwrap());
+        assert_eq!(packets[0].dst, "10.0.0.2:7001".parse().unwrap());
+        assert_eq!(packets[0].ts, Duration::from_secs(1_000));
+        let strata = packets[0].strata().unwrap();
+        assert_eq!(strata.link_seq, 0);
+        assert_eq!(strata.bonding_seq, Some(7));
+
+        assert!(read_pcap(b"not a pcap file at all, really").is_err());
+    }
+
+    #[test]
+    fn link_report_counts_reorder_duplicates_and_holes() {
+        // Link sequence 0 1 3 2 3 5: 2 is late, 3 repeats, 4 never arrives.
+        let payloads: Vec<_> = [(0, 10), (1, 11), (3, 13), (2, 12), (3, 13), (5, 15)]
+            .iter()
+            .map(|&(l, b)| strata_payload(l, b))
+            .collect();
+        let report = LinkReport::from_packets(&read_pcap(&pcap(&payloads)).unwrap());
+        assert_eq!(report.data_packets, 6);
+        assert_eq!(report.reordered, 1);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.missing, 1);
+        assert!((report.delivery_ratio() - 5.0 / 6.0).abs() < 1e-9);
+
+        let other = LinkReport::from_packets(
+            &read_pcap(&pcap(&[strata_payload(0, 13), strata_payload(1, 16)])).unwrap(),
+        );
+        let bonding = BondingReport::from_links(&[report, other]);
+        // Bonding seqs {10, 11, 12, 13, 15} and {13, 16}: 13 crossed both.
+        assert_eq!(bonding.unique, 6);
+        assert_eq!(bonding.duplicated, 1);
+        assert!((bonding.link_share[1] - 2.0 / 6.0).abs() < 1e-9);
+    }
+}
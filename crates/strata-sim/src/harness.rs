@@ -0,0 +1,794 @@
+//! # End-to-End Bonding Harness
+//!
+//! Runs the `dummy_node` sender and receiver over one impaired path per
+//! link, plays an [`ImpairmentSchedule`] on every link and collects what was
+//! delivered, so an integration test reads as:
+//!
+//! ```text
+//! let scenario = LinkFailureScenario { num_links: 2, ..Default::default() };
+//! BondingTest::new("lfail")
+//!     .links(frame_schedules(&scenario.frames()))
+//!     .run()
+//!     .assert_delivered_ratio(0.95)
+//!     .assert_failover_within(scenario.failure_start, Duration::from_millis(500));
+//! ```
+//!
+//! Both processes report stats as JSON lines on stdout (`--stats-dest -`),
+//! so no management link or host firewall rule is needed.
+//!
+//! Two [`Backend`]s carry the links:
+//!
+//! - [`Backend::Netns`]: a pair of namespaces joined by one veth per link,
+//!   impaired with netem.  Needs sudo; used when [`check_privileges`]
+//!   allows.
+//! - [`Backend::Proxy`]: both processes on loopback, with one
+//!   [`ImpairmentProxy`] per link in between.  Needs no privileges, so the
+//!   same tests run on a developer machine, with coarser timing.
+//!
+//! [`BondingTest::start`] returns the running [`Session`] instead, for
+//! driving links by hand (the `strata-sim` binary is built on it).
+
+use crate::impairment::{
+    ImpairmentConfig, ImpairmentSchedule, ImpairmentScheduler, apply_bidirectional_impairment,
+    apply_impairment,
+};
+use crate::proxy::ImpairmentProxy;
+use crate::test_util::{SEED_ENV, check_privileges, test_seed};
+use crate::topology::Namespace;
+use serde_json::Value;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long after a link event a delivery stall still counts as failover.
+const FAILOVER_WINDOW: Duration = Duration::from_secs(5);
+
+/// Locate the `dummy_node` binary, building it on first use.
+///
+/// `STRATA_PIPELINE_BIN` overrides the lookup.
+pub fn dummy_node_binary() -> PathBuf {
+    if let Ok(p) = std::env::var("STRATA_PIPELINE_BIN") {
+        let path = PathBuf::from(p);
+        if path.exists() {
+            return path;
+        }
+    }
+
+    static BUILD: std::sync::Once = std::sync::Once::new();
+    BUILD.call_once(|| {
+        let _ = Command::new("cargo")
+            .args(["build", "-p", "strata-sim", "--bin", "dummy_node"])
+            .status();
+    });
+
+    let mut path = std::env::current_exe().expect("current_exe");
+    path.pop(); // deps
+    path.pop(); // debug
+    path.push("dummy_node");
+    if !path.exists() {
+        let cwd = std::env::current_dir().unwrap();
+        for candidate in ["target/debug/dummy_node", "../../target/debug/dummy_node"] {
+            let try_path = cwd.join(candidate);
+            if try_path.exists() {
+                return try_path;
+            }
+        }
+        panic!("dummy_node binary not found at {:?}", path);
+    }
+    path
+}
+
+/// How a [`BondingTest`] builds its links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Namespaces, veths and netem (root).
+    Netns,
+    /// Loopback through userspace impairment proxies (unprivileged).
+    Proxy,
+}
+
+impl Backend {
+    /// [`Netns`](Self::Netns) if [`check_privileges`] allows, otherwise
+    /// [`Proxy`](Self::Proxy).
+    pub fn detect() -> Self {
+        if check_privileges() {
+            Self::Netns
+        } else {
+            Self::Proxy
+        }
+    }
+}
+
+/// Builder for one end-to-end run.
+pub struct BondingTest {
+    name: String,
+    backend: Option<Backend>,
+    binary: Option<PathBuf>,
+    seed: Option<u64>,
+    links: Vec<ImpairmentSchedule>,
+    bitrate_kbps: u32,
+    warmup: Duration,
+    duration: Option<Duration>,
+    drain: Duration,
+    tick: Duration,
+    sender_args: Vec<String>,
+}
+
+impl BondingTest {
+    /// `name` prefixes the namespaces and interfaces, so it must be unique
+    /// among concurrently running tests and at most 10 characters.
+    pub fn new(name: &str) -> Self {
+        assert!(
+            name.len() <= 10 && name.bytes().all(|b| b.is_ascii_alphanumeric()),
+            "test name {name:?} must be ≤10 alphanumeric characters (interface names are capped at 15)"
+        );
+        Self {
+            name: name.to_string(),
+            backend: None,
+            binary: None,
+            seed: None,
+            links: Vec::new(),
+            bitrate_kbps: 3_000,
+            warmup: Duration::from_secs(5),
+            duration: None,
+            // Covers the receiver's 2 s reassembly latency.
+            drain: Duration::from_millis(2_500),
+            tick: Duration::from_millis(20),
+            sender_args: Vec::new(),
+        }
+    }
+
+    /// Force a backend instead of [`Backend::detect`].
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Run `path` instead of [`dummy_node_binary`].  It must take
+    /// `dummy_node`'s arguments and print the same stats JSON.
+    pub fn binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.binary = Some(path.into());
+        self
+    }
+
+    /// Seed the links' loss and jitter (link `i` gets `seed + i`).
+    /// Defaults to [`test_seed`], which honours `STRATA_SIM_SEED`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Add a link driven by `schedule`.
+    pub fn link(mut self, schedule: ImpairmentSchedule) -> Self {
+        self.links.push(schedule);
+        self
+    }
+
+    /// Add a link held at `config` for the whole run.
+    pub fn static_link(self, config: ImpairmentConfig) -> Self {
+        self.link(ImpairmentSchedule::new().hold(Duration::ZERO, config))
+    }
+
+    /// Add one link per schedule, e.g. from a scenario's `schedules()`.
+    pub fn links(mut self, schedules: impl IntoIterator<Item = ImpairmentSchedule>) -> Self {
+        self.links.extend(schedules);
+        self
+    }
+
+    pub fn bitrate_kbps(mut self, kbps: u32) -> Self {
+        self.bitrate_kbps = kbps;
+        self
+    }
+
+    /// Time to stream over the schedules' initial configs before the
+    /// scenario clock starts.  Default 5 s.
+    pub fn warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Scenario length.  Defaults to the longest schedule.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Extra `dummy_node sender` arguments, e.g. `("--redundancy", "false")`.
+    pub fn sender_arg(mut self, flag: &str, value: &str) -> Self {
+        self.sender_args.push(flag.to_string());
+        self.sender_args.push(value.to_string());
+        self
+    }
+
+    /// Run the scenario.  Panics if setup fails.
+    pub fn run(self) -> Outcome {
+        let session = self.start();
+        thread::sleep(self.warmup);
+        let started_ms = unix_millis();
+        let scheduler = session.scheduler(&self.links);
+        let duration = self.duration.unwrap_or_else(|| scheduler.duration());
+        session
+            .rt
+            .block_on(async {
+                let (result, ()) = tokio::join!(scheduler.run(), tokio::time::sleep(duration));
+                result
+            })
+            .expect("impairment schedule failed");
+        session.finish(started_ms, duration)
+    }
+
+    /// Build the links at their initial configs and start both processes,
+    /// for driving by hand (see [`Session`]).  [`run`](Self::run) is this
+    /// plus the schedules.
+    pub fn start(&self) -> Session {
+        assert!(
+            !self.links.is_empty(),
+            "BondingTest needs at least one link"
+        );
+        let backend = self.backend.unwrap_or_else(Backend::detect);
+        let bin = self.binary.clone().unwrap_or_else(dummy_node_binary);
+        let seed = self.seed.unwrap_or_else(test_seed);
+        let bin = bin.to_str().unwrap();
+        let port = self.port();
+        // Multi-threaded so proxies keep forwarding while the caller sleeps.
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let (sender, receiver, links) = match backend {
+            Backend::Netns => self.start_netns(bin, port, seed),
+            Backend::Proxy => rt.block_on(self.start_proxied(bin, port, seed)),
+        };
+        Session {
+            rt,
+            sender,
+            receiver,
+            links,
+            port,
+            seed,
+            tick: self.tick,
+            drain: self.drain,
+            started_ms: unix_millis(),
+        }
+    }
+
+    fn start_netns(&self, bin: &str, port: u16, seed: u64) -> (StatsProcess, StatsProcess, Links) {
+        let ns_snd = Arc::new(Namespace::new(&format!("{}_snd", self.name)).unwrap());
+        let ns_rcv = Arc::new(Namespace::new(&format!("{}_rcv", self.name)).unwrap());
+        let mut dests = Vec::new();
+        let mut interfaces = Vec::new();
+        for (i, schedule) in self.links.iter().enumerate() {
+            let (snd_if, rcv_if) = (format!("{}{i}a", self.name), format!("{}{i}b", self.name));
+            let subnet = format!("10.77.{}", i + 1);
+            ns_snd
+                .add_veth_link(
+                    &ns_rcv,
+                    &snd_if,
+                    &rcv_if,
+                    &format!("{subnet}.1/24"),
+                    &format!("{subnet}.2/24"),
+                )
+                .unwrap();
+            // The return path stays at the initial config; later changes
+            // only move the forward (data) direction.
+            apply_bidirectional_impairment(
+                &ns_snd,
+                &snd_if,
+                &ns_rcv,
+                &rcv_if,
+                link_schedule(schedule, seed, i).config_at(Duration::ZERO),
+            )
+            .unwrap();
+            interfaces.push(snd_if);
+            dests.push(format!("{subnet}.2:{port}"));
+        }
+
+        let in_ns = |ns: &Namespace| {
+            let mut cmd = Command::new("sudo");
+            cmd.args(["-E", "ip", "netns", "exec", &ns.name, bin]);
+            cmd
+        };
+        let receiver = StatsProcess::spawn(
+            in_ns(&ns_rcv).args(self.receiver_args(&format!("0.0.0.0:{port}"))),
+        );
+        let sender = StatsProcess::spawn(in_ns(&ns_snd).args(self.sender_args(&dests)));
+        let links = Links::Netns {
+            snd: ns_snd,
+            _rcv: ns_rcv,
+            interfaces,
+        };
+        (sender, receiver, links)
+    }
+
+    async fn start_proxied(
+        &self,
+        bin: &str,
+        port: u16,
+        seed: u64,
+    ) -> (StatsProcess, StatsProcess, Links) {
+        let receiver_addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+        let mut proxies = Vec::new();
+        for (i, schedule) in self.links.iter().enumerate() {
+            let proxy = ImpairmentProxy::bind(
+                "127.0.0.1:0".parse().unwrap(),
+                receiver_addr,
+                schedule.config_at(Duration::ZERO),
+                seed.wrapping_add(i as u64),
+            )
+            .await
+            .expect("failed to bind impairment proxy");
+            proxies.push(proxy);
+        }
+        let dests: Vec<String> = proxies.iter().map(|p| p.local_addr().to_string()).collect();
+
+        let receiver = StatsProcess::spawn(
+            Command::new(bin).args(self.receiver_args(&receiver_addr.to_string())),
+        );
+        let sender = StatsProcess::spawn(Command::new(bin).args(self.sender_args(&dests)));
+        (sender, receiver, Links::Proxy(proxies))
+    }
+
+    fn receiver_args(&self, bind: &str) -> Vec<String> {
+        ["receiver", "--bind", bind, "--stats-dest", "-"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    fn sender_args(&self, dests: &[String]) -> Vec<String> {
+        let mut args = vec![
+            "sender".to_string(),
+            "--dest".to_string(),
+            dests.join(","),
+            "--bitrate".to_string(),
+            self.bitrate_kbps.to_string(),
+            "--stats-dest".to_string(),
+            "-".to_string(),
+        ];
+        args.extend(self.sender_args.iter().cloned());
+        args
+    }
+
+    /// A port unique to this test name, which also identifies its
+    /// processes for `pkill`.
+    fn port(&self) -> u16 {
+        let hash = self
+            .name
+            .bytes()
+            .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+        17_000 + (hash % 2_000) as u16
+    }
+}
+
+/// A running sender/receiver pair over impaired links, from
+/// [`BondingTest::start`].  Dropping it kills both processes and tears the
+/// links down; [`stop`](Self::stop) shuts down cleanly and reports.
+pub struct Session {
+    rt: tokio::runtime::Runtime,
+    sender: StatsProcess,
+    receiver: StatsProcess,
+    links: Links,
+    port: u16,
+    seed: u64,
+    tick: Duration,
+    drain: Duration,
+    started_ms: u64,
+}
+
+enum Links {
+    /// Dropping the namespaces tears the veths down.
+    Netns {
+        snd: Arc<Namespace>,
+        _rcv: Arc<Namespace>,
+        /// Sender-side veth per link.
+        interfaces: Vec<String>,
+    },
+    /// Dropping the proxies stops forwarding.
+    Proxy(Vec<ImpairmentProxy>),
+}
+
+impl Session {
+    pub fn backend(&self) -> Backend {
+        match self.links {
+            Links::Netns { .. } => Backend::Netns,
+            Links::Proxy(_) => Backend::Proxy,
+        }
+    }
+
+    /// The seed the links were impaired with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn num_links(&self) -> usize {
+        match &self.links {
+            Links::Netns { interfaces, .. } => interfaces.len(),
+            Links::Proxy(proxies) => proxies.len(),
+        }
+    }
+
+    /// Replace link `link`'s forward-path impairment.
+    pub fn set_link(&self, link: usize, config: ImpairmentConfig) -> io::Result<()> {
+        let out_of_range = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no link {link} (have {})", self.num_links()),
+            )
+        };
+        match &self.links {
+            Links::Netns {
+                snd, interfaces, ..
+            } => {
+                let iface = interfaces.get(link).ok_or_else(out_of_range)?;
+                apply_impairment(snd, iface, config)
+            }
+            Links::Proxy(proxies) => {
+                proxies
+                    .get(link)
+                    .ok_or_else(out_of_range)?
+                    .set_config(config);
+                Ok(())
+            }
+        }
+    }
+
+    /// The sender's most recent stats snapshot.
+    pub fn sender_stats(&self) -> Option<Value> {
+        self.sender.latest()
+    }
+
+    /// The receiver's most recent stats snapshot.
+    pub fn receiver_stats(&self) -> Option<Value> {
+        self.receiver.latest()
+    }
+
+    /// A scheduler over this session's links, one schedule per link.
+    fn scheduler(&self, schedules: &[ImpairmentSchedule]) -> ImpairmentScheduler {
+        let mut scheduler = ImpairmentScheduler::new(self.tick);
+        for (i, schedule) in schedules.iter().enumerate() {
+            scheduler = match &self.links {
+                Links::Netns {
+                    snd, interfaces, ..
+                } => scheduler.link(
+                    Arc::clone(snd),
+                    interfaces[i].clone(),
+                    link_schedule(schedule, self.seed, i),
+                ),
+                // Proxies are seeded at bind time.
+                Links::Proxy(proxies) => scheduler.proxy_link(&proxies[i], schedule.clone()),
+            };
+        }
+        scheduler
+    }
+
+    /// Stop both processes and report on the whole session.
+    pub fn stop(self) -> Outcome {
+        let (started_ms, now) = (self.started_ms, unix_millis());
+        self.finish(started_ms, Duration::from_millis(now - started_ms))
+    }
+
+    fn finish(mut self, started_ms: u64, duration: Duration) -> Outcome {
+        // Stop the sender first and let the receiver flush its reassembly
+        // buffer, so everything still in flight is counted.
+        let port = self.port;
+        match self.links {
+            Links::Netns { .. } => signal(&format!("sender.*:{port}"), "-INT"),
+            Links::Proxy(_) => self.sender.interrupt(),
+        }
+        thread::sleep(self.drain);
+        if let Links::Netns { .. } = self.links {
+            signal(&format!(":{port}"), "-9");
+        }
+        let sender_stats = self.sender.finish();
+        let receiver_stats = self.receiver.finish();
+        Outcome::from_stats(
+            sender_stats,
+            receiver_stats,
+            started_ms,
+            duration,
+            self.seed,
+        )
+    }
+}
+
+/// Link `i`'s schedule seeded for netem, unless the caller seeded it.
+fn link_schedule(schedule: &ImpairmentSchedule, seed: u64, i: usize) -> ImpairmentSchedule {
+    if schedule.config_at(Duration::ZERO).seed.is_some() {
+        return schedule.clone();
+    }
+    schedule.clone().with_seed(seed.wrapping_add(i as u64))
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if let Links::Netns { .. } = self.links {
+            signal(&format!(":{}", self.port), "-9");
+        }
+        self.sender.finish();
+        self.receiver.finish();
+    }
+}
+
+/// A `dummy_node` whose stdout JSON lines are collected in the background.
+struct StatsProcess {
+    child: Child,
+    lines: Arc<Mutex<Vec<Value>>>,
+    reader: Option<thread::JoinHandle<()>>,
+}
+
+impl StatsProcess {
+    fn spawn(cmd: &mut Command) -> Self {
+        let mut child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap_or_else(|e| panic!("Failed to spawn {cmd:?}: {e}"));
+
+        let stdout = child.stdout.take().unwrap();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&lines);
+        let reader = thread::spawn(move || {
+            // Tracing output shares stdout; only JSON objects are stats.
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if line.starts_with('{')
+                    && let Ok(v) = serde_json::from_str::<Value>(&line)
+                {
+                    sink.lock().unwrap().push(v);
+                }
+            }
+        });
+        Self {
+            child,
+            lines,
+            reader: Some(reader),
+        }
+    }
+
+    /// SIGINT a directly spawned (non-sudo) process.
+    fn interrupt(&self) {
+        let _ = Command::new("kill")
+            .args(["-INT", &self.child.id().to_string()])
+            .status();
+    }
+
+    fn latest(&self) -> Option<Value> {
+        self.lines.lock().unwrap().last().cloned()
+    }
+
+    fn finish(&mut self) -> Vec<Value> {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        std::mem::take(&mut self.lines.lock().unwrap())
+    }
+}
+
+/// Signal this test's processes inside the namespaces.  `child.kill()` only
+/// reaches the `sudo` wrapper; the real process is matched by its command
+/// line instead.
+fn signal(pattern: &str, signal: &str) {
+    let _ = Command::new("sudo")
+        .args(["pkill", signal, "-f", pattern])
+        .output();
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// A pause in delivery at the receiver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stall {
+    /// Start, relative to the start of the scenario.
+    pub at: Duration,
+    pub duration: Duration,
+}
+
+/// Receiver reassembly counters at the end of the run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReassemblyTotals {
+    pub lost_packets: u64,
+    pub late_packets: u64,
+    pub duplicate_packets: u64,
+    pub discontinuities: u64,
+}
+
+/// What an end-to-end run delivered.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub packets_sent: u64,
+    pub packets_delivered: u64,
+    /// Delivery stalls that started during the scenario, in time order.
+    pub stalls: Vec<Stall>,
+    pub reassembly: ReassemblyTotals,
+    /// The links' impairment seed; failed assertions print it.
+    pub seed: u64,
+    /// Raw stats snapshots, for assertions the helpers don't cover.
+    pub sender_stats: Vec<Value>,
+    pub receiver_stats: Vec<Value>,
+}
+
+impl Outcome {
+    fn from_stats(
+        sender_stats: Vec<Value>,
+        receiver_stats: Vec<Value>,
+        started_ms: u64,
+        duration: Duration,
+        seed: u64,
+    ) -> Self {
+        let last_u64 = |stats: &[Value], key: &str| {
+            stats
+                .iter()
+                .rev()
+                .find_map(|v| v.get(key).and_then(Value::as_u64))
+                .unwrap_or(0)
+        };
+        let end_ms = started_ms + duration.as_millis() as u64;
+        let stalls = receiver_stats
+            .iter()
+            .filter_map(|v| v.get("stalls").and_then(Value::as_array))
+            .flatten()
+            .filter_map(|s| {
+                let start = s.get("start_ms")?.as_u64()?;
+                let len = s.get("duration_ms")?.as_u64()?;
+                (start >= started_ms && start < end_ms).then(|| Stall {
+                    at: Duration::from_millis(start - started_ms),
+                    duration: Duration::from_millis(len),
+                })
+            })
+            .collect();
+        let reassembly = receiver_stats
+            .iter()
+            .rev()
+            .find_map(|v| v.get("reassembly"))
+            .map(|r| {
+                let get = |key: &str| r.get(key).and_then(Value::as_u64).unwrap_or(0);
+                ReassemblyTotals {
+                    lost_packets: get("lost_packets"),
+                    late_packets: get("late_packets"),
+                    duplicate_packets: get("duplicate_packets"),
+                    discontinuities: get("discontinuities"),
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            packets_sent: last_u64(&sender_stats, "packets_sent"),
+            packets_delivered: last_u64(&receiver_stats, "delivered"),
+            stalls,
+            reassembly,
+            seed,
+            sender_stats,
+            receiver_stats,
+        }
+    }
+
+    /// Delivered / sent.  The sender's last report can trail its final
+    /// packets by one stats interval, so this is capped at 1.0.
+    pub fn delivered_ratio(&self) -> f64 {
+        if self.packets_sent == 0 {
+            return 0.0;
+        }
+        (self.packets_delivered as f64 / self.packets_sent as f64).min(1.0)
+    }
+
+    /// The longest delivery stall during the scenario.
+    pub fn max_gap(&self) -> Duration {
+        self.stalls
+            .iter()
+            .map(|s| s.duration)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// How long delivery stalled in response to a link event at `event`
+    /// (scenario time): the longest stall overlapping the few seconds after
+    /// it, or zero if delivery never paused.
+    pub fn failover_latency(&self, event: Duration) -> Duration {
+        self.stalls
+            .iter()
+            .filter(|s| s.at + s.duration > event && s.at < event + FAILOVER_WINDOW)
+            .map(|s| s.duration)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn assert_delivered_ratio(&self, min: f64) -> &Self {
+        let ratio = self.delivered_ratio();
+        assert!(
+            ratio >= min,
+            "delivered {:.1}% ({} of {}), expected at least {:.1}%{}",
+            ratio * 100.0,
+            self.packets_delivered,
+            self.packets_sent,
+            min * 100.0,
+            self.replay_hint()
+        );
+        self
+    }
+
+    pub fn assert_max_gap(&self, max: Duration) -> &Self {
+        let gap = self.max_gap();
+        assert!(
+            gap <= max,
+            "delivery stalled for {gap:?} (limit {max:?}); stalls: {:?}{}",
+            self.stalls,
+            self.replay_hint()
+        );
+        self
+    }
+
+    pub fn assert_failover_within(&self, event: Duration, max: Duration) -> &Self {
+        let latency = self.failover_latency(event);
+        assert!(
+            latency <= max,
+            "failover after the event at {event:?} took {latency:?} (limit {max:?}){}",
+            self.replay_hint()
+        );
+        self
+    }
+
+    pub fn assert_lost_at_most(&self, max: u64) -> &Self {
+        assert!(
+            self.reassembly.lost_packets <= max,
+            "reassembly lost {} packets (limit {max}): {:?}{}",
+            self.reassembly.lost_packets,
+            self.reassembly,
+            self.replay_hint()
+        );
+        self
+    }
+
+    fn replay_hint(&self) -> String {
+        format!("\nreplay with {SEED_ENV}={}", self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn outcome_summarises_stats_snapshots() {
+        let started = 1_000_000;
+        let sender = vec![json!({"packets_sent": 900}), json!({"packets_sent": 1000})];
+        let receiver = vec![
+            // A stall during warm-up is not part of the scenario.
+            json!({"delivered": 10, "stalls": [{"start_ms": started - 500, "duration_ms": 300}]}),
+            json!({"delivered": 500, "stalls": [{"start_ms": started + 10_000, "duration_ms": 180}]}),
+            json!({
+                "delivered": 980,
+                "stalls": [{"start_ms": started + 20_200, "duration_ms": 450}],
+                "reassembly": {"lost_packets": 20, "late_packets": 3},
+            }),
+        ];
+        let outcome = Outcome::from_stats(sender, receiver, started, Duration::from_secs(30), 7);
+
+        assert_eq!(outcome.stalls.len(), 2);
+        assert!((outcome.delivered_ratio() - 0.98).abs() < 1e-9);
+        assert_eq!(outcome.max_gap(), Duration::from_millis(450));
+        assert_eq!(outcome.reassembly.lost_packets, 20);
+        assert_eq!(
+            outcome.failover_latency(Duration::from_secs(20)),
+            Duration::from_millis(450)
+        );
+        assert_eq!(
+            outcome.failover_latency(Duration::from_secs(2)),
+            Duration::ZERO
+        );
+        outcome
+            .assert_delivered_ratio(0.95)
+            .assert_max_gap(Duration::from_millis(500))
+            .assert_lost_at_most(20);
+    }
+}
@@ -1,8 +1,12 @@
+use crate::scenario::ScenarioFrame;
 use crate::topology::Namespace;
 use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Gilbert-Elliott (4-state) loss model parameters for `tc netem`.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct GemodelConfig {
     pub p: f32,     // Probability Good -> Bad (%)
     pub r: f32,     // Probability Bad -> Good (%)
@@ -19,7 +23,7 @@ pub struct GemodelConfig {
 /// points for realistic cellular simulation.  They include correlated loss,
 /// normal-distributed jitter, packet corruption/reorder, and queue limits
 /// calibrated to prevent unrealistic bufferbloat.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ImpairmentConfig {
     pub delay_ms: Option<u32>,
     pub jitter_ms: Option<u32>,
@@ -65,10 +69,16 @@ pub struct ImpairmentConfig {
     /// handle 10:` and handles delay, loss, and slot scheduling — but no
     /// longer emits a `rate` argument (that belongs to tbf).
     pub modem_buffer_kb: Option<u32>,
+
+    /// Seed for netem's PRNG (`tc netem seed`, Linux 6.7+), so loss,
+    /// jitter, duplication, reorder and corruption repeat exactly from run
+    /// to run.  On older kernels it is dropped with a warning and netem
+    /// seeds itself.
+    pub seed: Option<u64>,
 }
 
 /// Assumed MTU for queue-depth calculations (bytes).
-const ASSUMED_MTU: u64 = 1200;
+pub(crate) const ASSUMED_MTU: u64 = 1200;
 
 /// Target queue depth in seconds for auto-computed `limit`.
 ///
@@ -119,6 +129,8 @@ impl ImpairmentConfig {
             slot_max_packets: None,
             slot_max_bytes: None,
             modem_buffer_kb: None,
+            // Its own stream, so ACK loss does not mirror data loss.
+            seed: self.seed.map(|s| !s),
         }
     }
 
@@ -228,6 +240,22 @@ impl ImpairmentConfig {
         }
     }
 
+    /// Names accepted by [`preset`](Self::preset).
+    pub const PRESETS: &[&str] = &["lte_good", "lte_urban", "lte_poor", "fiveg_good", "ideal"];
+
+    /// A preset by name; `ideal` is [`ideal`](Self::ideal) at 20 Mbit/s
+    /// and 10 ms.
+    pub fn preset(name: &str) -> Option<Self> {
+        Some(match name {
+            "lte_good" => Self::lte_good(),
+            "lte_urban" => Self::lte_urban(),
+            "lte_poor" => Self::lte_poor(),
+            "fiveg_good" => Self::fiveg_good(),
+            "ideal" => Self::ideal(20_000, 10),
+            _ => return None,
+        })
+    }
+
     /// Idealised low-impairment link for unit/integration tests where
     /// you want to isolate transport logic without cellular noise.
     /// Still rate-limited but no loss, corruption, or reorder.
@@ -425,9 +453,27 @@ pub fn apply_impairment(
         }
     }
 
+    if let Some(seed) = config.seed {
+        args_storage.push("seed".to_string());
+        args_storage.push(seed.to_string());
+    }
+
     let args: Vec<&str> = args_storage.iter().map(|s| s.as_str()).collect();
 
-    let output = ns.exec("tc", &args)?;
+    let mut output = ns.exec("tc", &args)?;
+
+    // Kernels before 6.7 (or an older iproute2) reject `seed`: retry
+    // unseeded rather than fail the whole run.
+    if !output.status.success()
+        && config.seed.is_some()
+        && String::from_utf8_lossy(&output.stderr).contains("seed")
+    {
+        eprintln!(
+            "[impairment] netem seed unsupported on {interface}, \
+             loss and jitter will not be reproducible"
+        );
+        output = ns.exec("tc", &args[..args.len() - 2])?;
+    }
 
     if !output.status.success() {
         return Err(io::Error::other(format!(
@@ -463,6 +509,462 @@ pub fn apply_bidirectional_impairment(
     Ok(())
 }
 
+// ── Time-varying schedules ───────────────────────────────────────────
+
+/// One piece of an [`ImpairmentSchedule`]'s baseline timeline.
+#[derive(Debug, Clone)]
+pub enum Segment {
+    /// Keep `config` for `duration`.
+    Hold {
+        duration: Duration,
+        config: ImpairmentConfig,
+    },
+    /// Move linearly from `from` to `to` over `duration`.
+    ///
+    /// Rate, delay, jitter and loss are interpolated.  Every other field
+    /// keeps `from`'s value until the ramp completes.
+    Ramp {
+        duration: Duration,
+        from: ImpairmentConfig,
+        to: ImpairmentConfig,
+    },
+}
+
+impl Segment {
+    fn duration(&self) -> Duration {
+        match self {
+            Segment::Hold { duration, .. } | Segment::Ramp { duration, .. } => *duration,
+        }
+    }
+
+    fn end_config(&self) -> &ImpairmentConfig {
+        match self {
+            Segment::Hold { config, .. } => config,
+            Segment::Ramp { to, .. } => to,
+        }
+    }
+
+    /// Config `offset` into the segment.
+    fn config_at(&self, offset: Duration) -> ImpairmentConfig {
+        match self {
+            Segment::Hold { config, .. } => config.clone(),
+            Segment::Ramp { duration, from, to } => {
+                if duration.is_zero() || offset >= *duration {
+                    return to.clone();
+                }
+                let p = offset.as_secs_f64() / duration.as_secs_f64();
+                ImpairmentConfig {
+                    rate_kbit: lerp(from.rate_kbit, to.rate_kbit, p),
+                    delay_ms: lerp(from.delay_ms, to.delay_ms, p),
+                    jitter_ms: lerp(from.jitter_ms, to.jitter_ms, p),
+                    loss_percent: lerp(from.loss_percent, to.loss_percent, p),
+                    ..from.clone()
+                }
+            }
+        }
+    }
+}
+
+/// A transient impairment layered over the baseline timeline.
+#[derive(Debug, Clone)]
+pub enum Overlay {
+    /// Raise loss to at least `loss_percent` for the window.  Replaces any
+    /// Gilbert-Elliott model, which would otherwise take precedence.
+    LossBurst {
+        window: Range<Duration>,
+        loss_percent: f32,
+        correlation: Option<f32>,
+    },
+    /// Within the window, add `extra_delay_ms` for `spike` at the start of
+    /// every `period`.
+    RttSpikes {
+        window: Range<Duration>,
+        period: Duration,
+        spike: Duration,
+        extra_delay_ms: u32,
+    },
+}
+
+impl Overlay {
+    fn end(&self) -> Duration {
+        match self {
+            Overlay::LossBurst { window, .. } | Overlay::RttSpikes { window, .. } => window.end,
+        }
+    }
+
+    fn apply(&self, t: Duration, config: &mut ImpairmentConfig) {
+        match self {
+            Overlay::LossBurst {
+                window,
+                loss_percent,
+                correlation,
+            } => {
+                if window.contains(&t) {
+                    config.gemodel = None;
+                    config.loss_percent =
+                        Some(config.loss_percent.unwrap_or(0.0).max(*loss_percent));
+                    config.loss_correlation = *correlation;
+                }
+            }
+            Overlay::RttSpikes {
+                window,
+                period,
+                spike,
+                extra_delay_ms,
+            } => {
+                if !window.contains(&t) || period.is_zero() {
+                    return;
+                }
+                let phase = (t - window.start).as_nanos() % period.as_nanos();
+                if phase < spike.as_nanos() {
+                    config.delay_ms = Some(config.delay_ms.unwrap_or(0) + extra_delay_ms);
+                }
+            }
+        }
+    }
+}
+
+/// Piecewise, time-varying netem parameters for one interface.
+///
+/// A schedule is a baseline timeline of [`Segment`]s played back to back,
+/// plus [`Overlay`]s (burst loss, RTT spikes) evaluated on top of it.
+/// Once the timeline runs out the last segment's end config stays in force.
+///
+/// ```text
+/// // Urban LTE that fades to 500 kbit over 30 s, with a 2 s loss burst
+/// // and a 150 ms RTT spike every 5 s.
+/// let s = Duration::from_secs;
+/// let schedule = ImpairmentSchedule::new()
+///     .hold(s(10), ImpairmentConfig::lte_urban())
+///     .ramp_to(s(30), ImpairmentConfig { rate_kbit: Some(500), ..ImpairmentConfig::lte_urban() })
+///     .loss_burst(s(20)..s(22), 30.0)
+///     .rtt_spikes(s(0)..s(40), s(5), Duration::from_millis(300), 150);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImpairmentSchedule {
+    pub segments: Vec<Segment>,
+    pub overlays: Vec<Overlay>,
+}
+
+impl ImpairmentSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a segment that keeps `config` for `duration`.
+    pub fn hold(mut self, duration: Duration, config: ImpairmentConfig) -> Self {
+        self.segments.push(Segment::Hold { duration, config });
+        self
+    }
+
+    /// Append a ramp from `from` to `to`.
+    pub fn ramp(
+        mut self,
+        duration: Duration,
+        from: ImpairmentConfig,
+        to: ImpairmentConfig,
+    ) -> Self {
+        self.segments.push(Segment::Ramp { duration, from, to });
+        self
+    }
+
+    /// Append a ramp from wherever the timeline currently ends (no
+    /// impairment if it is empty) to `to`.
+    pub fn ramp_to(mut self, duration: Duration, to: ImpairmentConfig) -> Self {
+        let from = self
+            .segments
+            .last()
+            .map(|s| s.end_config().clone())
+            .unwrap_or_default();
+        self.segments.push(Segment::Ramp { duration, from, to });
+        self
+    }
+
+    /// Add a burst of uncorrelated loss over `window`.
+    pub fn loss_burst(mut self, window: Range<Duration>, loss_percent: f32) -> Self {
+        self.overlays.push(Overlay::LossBurst {
+            window,
+            loss_percent,
+            correlation: None,
+        });
+        self
+    }
+
+    /// Add periodic RTT spikes over `window`.
+    pub fn rtt_spikes(
+        mut self,
+        window: Range<Duration>,
+        period: Duration,
+        spike: Duration,
+        extra_delay_ms: u32,
+    ) -> Self {
+        self.overlays.push(Overlay::RttSpikes {
+            window,
+            period,
+            spike,
+            extra_delay_ms,
+        });
+        self
+    }
+
+    /// Set [`ImpairmentConfig::seed`] on every config in the timeline.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        for segment in &mut self.segments {
+            match segment {
+                Segment::Hold { config, .. } => config.seed = Some(seed),
+                Segment::Ramp { from, to, .. } => {
+                    from.seed = Some(seed);
+                    to.seed = Some(seed);
+                }
+            }
+        }
+        self
+    }
+
+    /// Time until the schedule stops changing: the end of the timeline or
+    /// of the last overlay, whichever is later.
+    pub fn duration(&self) -> Duration {
+        let timeline: Duration = self.segments.iter().map(Segment::duration).sum();
+        self.overlays
+            .iter()
+            .map(Overlay::end)
+            .fold(timeline, Duration::max)
+    }
+
+    /// Effective config `t` after the schedule starts.
+    pub fn config_at(&self, t: Duration) -> ImpairmentConfig {
+        let mut config = ImpairmentConfig::default();
+        let mut start = Duration::ZERO;
+        for segment in &self.segments {
+            let end = start + segment.duration();
+            if t < end {
+                config = segment.config_at(t - start);
+                break;
+            }
+            config = segment.end_config().clone();
+            start = end;
+        }
+        for overlay in &self.overlays {
+            overlay.apply(t, &mut config);
+        }
+        config
+    }
+
+    /// Sample the schedule every `step` from 0 through [`duration`](Self::duration).
+    pub fn sample(&self, step: Duration) -> Vec<(Duration, ImpairmentConfig)> {
+        sample_times(self.duration(), step)
+            .map(|t| (t, self.config_at(t)))
+            .collect()
+    }
+}
+
+fn sample_times(duration: Duration, step: Duration) -> impl Iterator<Item = Duration> {
+    let total_steps = (duration.as_secs_f64() / step.as_secs_f64()).ceil() as u64;
+    (0..=total_steps).map(move |i| step.mul_f64(i as f64))
+}
+
+/// Interpolate between two optional values; if either side is unset the
+/// value switches over at the end of the ramp instead.
+fn lerp<T: Lerp>(from: Option<T>, to: Option<T>, p: f64) -> Option<T> {
+    match (from, to) {
+        (Some(a), Some(b)) => Some(T::from_f64(a.to_f64() + (b.to_f64() - a.to_f64()) * p)),
+        (from, to) => {
+            if p < 1.0 {
+                from
+            } else {
+                to
+            }
+        }
+    }
+}
+
+trait Lerp: Copy {
+    fn to_f64(self) -> f64;
+    fn from_f64(v: f64) -> Self;
+}
+
+impl Lerp for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(v: f64) -> Self {
+        v.round().max(1.0) as u64
+    }
+}
+
+impl Lerp for u32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(v: f64) -> Self {
+        v.round().max(0.0) as u32
+    }
+}
+
+impl Lerp for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+}
+
+/// Sample one schedule per link into [`ScenarioFrame`]s, for tests that
+/// drive impairments from a frame loop.
+pub fn schedule_frames(schedules: &[ImpairmentSchedule], step: Duration) -> Vec<ScenarioFrame> {
+    let duration = schedules
+        .iter()
+        .map(ImpairmentSchedule::duration)
+        .max()
+        .unwrap_or_default();
+    sample_times(duration, step)
+        .map(|t| ScenarioFrame {
+            t,
+            configs: schedules.iter().map(|s| s.config_at(t)).collect(),
+        })
+        .collect()
+}
+
+/// The inverse of [`schedule_frames`]: one schedule per link that holds
+/// each frame's config until the next frame.
+pub fn frame_schedules(frames: &[ScenarioFrame]) -> Vec<ImpairmentSchedule> {
+    let num_links = frames.first().map_or(0, |f| f.configs.len());
+    (0..num_links)
+        .map(|link| {
+            frames
+                .iter()
+                .enumerate()
+                .fold(ImpairmentSchedule::new(), |schedule, (i, frame)| {
+                    let from = if i == 0 { Duration::ZERO } else { frame.t };
+                    let until = frames.get(i + 1).map_or(frame.t, |next| next.t);
+                    schedule.hold(until - from, frame.configs[link].clone())
+                })
+        })
+        .collect()
+}
+
+/// Plays [`ImpairmentSchedule`]s onto interfaces in real time.
+///
+/// Every `tick` the scheduler evaluates each link's schedule and re-applies
+/// netem on the links whose effective config changed.  Unchanged links are
+/// left alone: re-adding the qdisc would flush its queue.  Runs until every
+/// schedule has reached its [`duration`](ImpairmentSchedule::duration); the
+/// final configs stay applied.  Dropping the future (or aborting the task
+/// from [`spawn`](Self::spawn)) stops it early.
+///
+/// Links can also be [`ImpairmentProxy`](crate::proxy::ImpairmentProxy)s,
+/// for runs without netns privileges.
+pub struct ImpairmentScheduler {
+    tick: Duration,
+    links: Vec<ScheduledLink>,
+}
+
+struct ScheduledLink {
+    target: Target,
+    schedule: ImpairmentSchedule,
+    applied: Option<ImpairmentConfig>,
+}
+
+enum Target {
+    Netem {
+        ns: Arc<Namespace>,
+        interface: String,
+    },
+    Proxy(tokio::sync::watch::Sender<ImpairmentConfig>),
+}
+
+impl ImpairmentScheduler {
+    pub fn new(tick: Duration) -> Self {
+        Self {
+            tick,
+            links: Vec::new(),
+        }
+    }
+
+    /// Drive `interface` inside `ns` with `schedule`.
+    pub fn link(
+        mut self,
+        ns: Arc<Namespace>,
+        interface: impl Into<String>,
+        schedule: ImpairmentSchedule,
+    ) -> Self {
+        self.links.push(ScheduledLink {
+            target: Target::Netem {
+                ns,
+                interface: interface.into(),
+            },
+            schedule,
+            applied: None,
+        });
+        self
+    }
+
+    /// Drive a userspace proxy with `schedule`.
+    pub fn proxy_link(
+        mut self,
+        proxy: &crate::proxy::ImpairmentProxy,
+        schedule: ImpairmentSchedule,
+    ) -> Self {
+        self.links.push(ScheduledLink {
+            target: Target::Proxy(proxy.config_sender()),
+            schedule,
+            applied: None,
+        });
+        self
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.links
+            .iter()
+            .map(|l| l.schedule.duration())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub async fn run(mut self) -> io::Result<()> {
+        let duration = self.duration();
+        let start = tokio::time::Instant::now();
+        let mut interval = tokio::time::interval(self.tick);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+            let t = start.elapsed();
+            for link in &mut self.links {
+                let config = link.schedule.config_at(t);
+                if link.applied.as_ref() == Some(&config) {
+                    continue;
+                }
+                match &link.target {
+                    Target::Netem { ns, interface } => {
+                        // `tc` is a blocking subprocess; keep it off the runtime.
+                        let ns = Arc::clone(ns);
+                        let interface = interface.clone();
+                        let to_apply = config.clone();
+                        tokio::task::spawn_blocking(move || {
+                            apply_impairment(&ns, &interface, to_apply)
+                        })
+                        .await
+                        .map_err(io::Error::other)??;
+                    }
+                    Target::Proxy(tx) => {
+                        tx.send_replace(config.clone());
+                    }
+                }
+                link.applied = Some(config);
+            }
+            if t >= duration {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<io::Result<()>> {
+        tokio::spawn(self.run())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,6 +986,86 @@ mod tests {
         None
     }
 
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn every_preset_name_resolves() {
+        for name in ImpairmentConfig::PRESETS {
+            assert!(ImpairmentConfig::preset(name).is_some(), "{name}");
+        }
+        assert!(ImpairmentConfig::preset("dialup").is_none());
+    }
+
+    #[test]
+    fn schedule_ramps_between_segments() {
+        let schedule = ImpairmentSchedule::new()
+            .hold(secs(10), ImpairmentConfig::ideal(8_000, 20))
+            .ramp_to(secs(30), ImpairmentConfig::ideal(500, 80));
+
+        assert_eq!(schedule.duration(), secs(40));
+        assert_eq!(schedule.config_at(secs(5)).rate_kbit, Some(8_000));
+        let mid = schedule.config_at(secs(25));
+        assert_eq!(mid.rate_kbit, Some(4_250));
+        assert_eq!(mid.delay_ms, Some(50));
+        // Past the end the final config holds.
+        assert_eq!(
+            schedule.config_at(secs(90)),
+            ImpairmentConfig::ideal(500, 80)
+        );
+    }
+
+    #[test]
+    fn schedule_overlays_loss_bursts_and_rtt_spikes() {
+        let schedule = ImpairmentSchedule::new()
+            .hold(secs(20), ImpairmentConfig::ideal(5_000, 20))
+            .loss_burst(secs(4)..secs(6), 30.0)
+            .rtt_spikes(secs(10)..secs(30), secs(5), Duration::from_millis(500), 150);
+
+        assert_eq!(schedule.duration(), secs(30));
+        assert_eq!(schedule.config_at(secs(3)).loss_percent, Some(0.0));
+        assert_eq!(schedule.config_at(secs(5)).loss_percent, Some(30.0));
+        assert_eq!(schedule.config_at(secs(6)).loss_percent, Some(0.0));
+
+        assert_eq!(schedule.config_at(secs(9)).delay_ms, Some(20));
+        assert_eq!(schedule.config_at(secs(10)).delay_ms, Some(170));
+        assert_eq!(schedule.config_at(secs(11)).delay_ms, Some(20));
+        // Spikes keep firing after the timeline ends, until the window closes.
+        assert_eq!(schedule.config_at(secs(25)).delay_ms, Some(170));
+        assert_eq!(schedule.config_at(secs(30)).delay_ms, Some(20));
+    }
+
+    #[test]
+    fn schedule_frames_cover_the_longest_schedule() {
+        let short = ImpairmentSchedule::new().hold(secs(2), ImpairmentConfig::ideal(1_000, 10));
+        let long = ImpairmentSchedule::new().hold(secs(4), ImpairmentConfig::ideal(2_000, 10));
+
+        let frames = schedule_frames(&[short, long], secs(1));
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames.last().unwrap().t, secs(4));
+        assert!(frames.iter().all(|f| f.configs.len() == 2));
+
+        let round_trip = frame_schedules(&frames);
+        assert_eq!(round_trip.len(), 2);
+        assert_eq!(round_trip[1].duration(), secs(4));
+        assert_eq!(round_trip[0].config_at(secs(3)).rate_kbit, Some(1_000));
+    }
+
+    #[test]
+    fn seeded_schedule_seeds_every_config() {
+        let schedule = ImpairmentSchedule::new()
+            .hold(secs(1), ImpairmentConfig::lte_urban())
+            .ramp_to(secs(2), ImpairmentConfig::lte_poor())
+            .loss_burst(secs(1)..secs(2), 50.0)
+            .with_seed(99);
+        for t in [0, 1, 2, 5] {
+            assert_eq!(schedule.config_at(secs(t)).seed, Some(99));
+        }
+        let ret = schedule.config_at(secs(0)).return_path_config();
+        assert!(ret.seed.is_some_and(|s| s != 99));
+    }
+
     #[test]
     fn test_impairment() {
         if !check_privileges() {
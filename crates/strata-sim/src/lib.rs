@@ -2,11 +2,16 @@
 //!
 //! Provides Linux network namespace management, `tc netem` impairment
 //! application, and deterministic scenario generation for testing
-//! bonding behaviour under controlled network conditions.
+//! bonding behaviour under controlled network conditions, including
+//! replay of link traces recorded in the field.
 
 pub mod bonding_scenarios;
+pub mod capture;
+pub mod harness;
 pub mod impairment;
+pub mod proxy;
 pub mod scenario;
 pub mod topology;
+pub mod trace;
 
 pub mod test_util;
@@ -0,0 +1,286 @@
+//! # Unprivileged Impairment Proxy
+//!
+//! A userspace stand-in for netem when namespaces and `tc` are not
+//! available (no sudo, no `CAP_NET_ADMIN`): one UDP proxy per link on
+//! loopback, which forwards datagrams to the receiver and replies back to
+//! the sender, applying an [`ImpairmentConfig`] on the way.
+//!
+//! Supported: delay with uniform or normal jitter, correlated Bernoulli or
+//! Gilbert-Elliott loss, duplication, and rate limiting with a tail-drop
+//! queue sized like [`ImpairmentConfig::auto_limit`].  Reordering comes only
+//! from jitter, as with netem.  Slot scheduling, corruption and the modem
+//! buffer model are netem-only and ignored here.  Replies get the
+//! [return-path config](ImpairmentConfig::return_path_config).
+//!
+//! Timing rides on the tokio timer, so it is coarser than the kernel's;
+//! good enough for failover and scheduling behaviour, not for sub-ms
+//! pacing measurements.
+
+use crate::impairment::{ASSUMED_MTU, ImpairmentConfig};
+use rand::RngExt as _;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// A running proxy.  Dropping it stops forwarding.
+pub struct ImpairmentProxy {
+    local_addr: SocketAddr,
+    config: watch::Sender<ImpairmentConfig>,
+    task: JoinHandle<()>,
+}
+
+impl ImpairmentProxy {
+    /// Listen on `listen` and forward to `upstream` with `config` applied.
+    /// `seed` makes loss, jitter and duplication reproducible.
+    pub async fn bind(
+        listen: SocketAddr,
+        upstream: SocketAddr,
+        config: ImpairmentConfig,
+        seed: u64,
+    ) -> io::Result<Self> {
+        let client_side = Arc::new(UdpSocket::bind(listen).await?);
+        // A loopback source can't reach a receiver on another host.
+        let upstream_bind: SocketAddr = match (upstream.is_ipv4(), upstream.ip().is_loopback()) {
+            (true, true) => "127.0.0.1:0".parse().unwrap(),
+            (true, false) => "0.0.0.0:0".parse().unwrap(),
+            (false, true) => "[::1]:0".parse().unwrap(),
+            (false, false) => "[::]:0".parse().unwrap(),
+        };
+        let upstream_side = Arc::new(UdpSocket::bind(upstream_bind).await?);
+        upstream_side.connect(upstream).await?;
+        let local_addr = client_side.local_addr()?;
+
+        let (tx, rx) = watch::channel(config);
+        let task = tokio::spawn(forward(client_side, upstream_side, rx, seed));
+        Ok(Self {
+            local_addr,
+            config: tx,
+            task,
+        })
+    }
+
+    /// Address the sender should target instead of the receiver.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Replace the impairment; applies to the next datagram.
+    pub fn set_config(&self, config: ImpairmentConfig) {
+        self.config.send_replace(config);
+    }
+
+    pub(crate) fn config_sender(&self) -> watch::Sender<ImpairmentConfig> {
+        self.config.clone()
+    }
+}
+
+impl Drop for ImpairmentProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn forward(
+    client_side: Arc<UdpSocket>,
+    upstream_side: Arc<UdpSocket>,
+    config: watch::Receiver<ImpairmentConfig>,
+    seed: u64,
+) {
+    let mut fwd = Shaper::new(seed);
+    let mut ret = Shaper::new(seed.wrapping_add(1));
+    let mut client: Option<SocketAddr> = None;
+    let mut fwd_buf = vec![0u8; 65_535];
+    let mut ret_buf = vec![0u8; 65_535];
+
+    loop {
+        tokio::select! {
+            Ok((len, from)) = client_side.recv_from(&mut fwd_buf) => {
+                client = Some(from);
+                let cfg = config.borrow().clone();
+                let packet: Arc<[u8]> = fwd_buf[..len].into();
+                for at in fwd.schedule(&cfg, len, Instant::now()) {
+                    let (sock, packet) = (Arc::clone(&upstream_side), Arc::clone(&packet));
+                    tokio::spawn(async move {
+                        tokio::time::sleep_until(at).await;
+                        let _ = sock.send(&packet).await;
+                    });
+                }
+            }
+            Ok(len) = upstream_side.recv(&mut ret_buf) => {
+                let Some(client) = client else { continue };
+                let cfg = config.borrow().return_path_config();
+                let packet: Arc<[u8]> = ret_buf[..len].into();
+                for at in ret.schedule(&cfg, len, Instant::now()) {
+                    let (sock, packet) = (Arc::clone(&client_side), Arc::clone(&packet));
+                    tokio::spawn(async move {
+                        tokio::time::sleep_until(at).await;
+                        let _ = sock.send_to(&packet, client).await;
+                    });
+                }
+            }
+            // A failed receive (e.g. ICMP unreachable while the receiver
+            // restarts) disables its branch for this round only.
+            else => {}
+        }
+    }
+}
+
+/// One direction's impairment state.
+#[derive(Debug)]
+struct Shaper {
+    rng: StdRng,
+    /// When the rate limiter finishes serialising what is already queued.
+    next_free: Option<Instant>,
+    /// Previous draw, for correlated loss.
+    last_loss_draw: f64,
+    /// Gilbert-Elliott state.
+    in_bad_state: bool,
+}
+
+impl Shaper {
+    fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            next_free: None,
+            last_loss_draw: 0.0,
+            in_bad_state: false,
+        }
+    }
+
+    /// Delivery times for a `len`-byte datagram arriving at `now`: empty
+    /// if it is lost, two entries if it is duplicated.
+    fn schedule(&mut self, cfg: &ImpairmentConfig, len: usize, now: Instant) -> Vec<Instant> {
+        if self.lose(cfg) {
+            return Vec::new();
+        }
+
+        let mut depart = now;
+        if let Some(rate) = cfg.rate_kbit.filter(|&r| r > 0) {
+            let secs_per_byte = 8.0 / (rate as f64 * 1000.0);
+            let start = self.next_free.map_or(now, |t| t.max(now));
+            let limit = cfg.limit.or_else(|| cfg.auto_limit()).unwrap_or(1000);
+            let max_backlog =
+                Duration::from_secs_f64(limit as f64 * ASSUMED_MTU as f64 * secs_per_byte);
+            if start - now > max_backlog {
+                return Vec::new(); // tail drop
+            }
+            depart = start + Duration::from_secs_f64(len as f64 * secs_per_byte);
+            self.next_free = Some(depart);
+        }
+
+        let mut times = vec![depart + self.delay(cfg)];
+        if let Some(dup) = cfg.duplicate_percent
+            && self.rng.random::<f64>() * 100.0 < dup as f64
+        {
+            times.push(depart + self.delay(cfg));
+        }
+        times
+    }
+
+    fn lose(&mut self, cfg: &ImpairmentConfig) -> bool {
+        if let Some(ge) = &cfg.gemodel {
+            let flip = if self.in_bad_state { ge.r } else { ge.p };
+            if self.rng.random::<f64>() * 100.0 < flip as f64 {
+                self.in_bad_state = !self.in_bad_state;
+            }
+            let loss = if self.in_bad_state {
+                ge.one_k
+            } else {
+                ge.one_h
+            };
+            return self.rng.random::<f64>() * 100.0 < loss as f64;
+        }
+        let Some(loss) = cfg.loss_percent.filter(|&l| l > 0.0) else {
+            return false;
+        };
+        // netem's correlated random: each draw leans on the previous one.
+        let corr = cfg.loss_correlation.unwrap_or(0.0) as f64 / 100.0;
+        let draw = (1.0 - corr) * self.rng.random::<f64>() + corr * self.last_loss_draw;
+        self.last_loss_draw = draw;
+        draw * 100.0 < loss as f64
+    }
+
+    fn delay(&mut self, cfg: &ImpairmentConfig) -> Duration {
+        let base = cfg.delay_ms.unwrap_or(0) as f64;
+        let jitter = cfg.jitter_ms.unwrap_or(0) as f64;
+        let offset = if jitter == 0.0 {
+            0.0
+        } else if cfg.delay_distribution_normal {
+            // Box-Muller.
+            let (u1, u2) = (
+                self.rng.random::<f64>().max(f64::MIN_POSITIVE),
+                self.rng.random::<f64>(),
+            );
+            jitter * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+        } else {
+            jitter * (self.rng.random::<f64>() * 2.0 - 1.0)
+        };
+        Duration::from_secs_f64((base + offset).max(0.0) / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaper_drops_everything_at_full_loss() {
+        let mut shaper = Shaper::new(1);
+        let cfg = ImpairmentConfig {
+            loss_percent: Some(100.0),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        assert!((0..100).all(|_| shaper.schedule(&cfg, 1200, now).is_empty()));
+    }
+
+    #[test]
+    fn shaper_serialises_at_the_configured_rate() {
+        let mut shaper = Shaper::new(1);
+        // 1200 bytes at 960 kbit/s is 10 ms on the wire.
+        let cfg = ImpairmentConfig::ideal(960, 20);
+        let now = Instant::now();
+        let first = shaper.schedule(&cfg, 1200, now)[0];
+        let second = shaper.schedule(&cfg, 1200, now)[0];
+        assert_eq!(first - now, Duration::from_millis(30));
+        assert_eq!(second - first, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn shaper_tail_drops_when_the_queue_is_full() {
+        let mut shaper = Shaper::new(1);
+        let cfg = ImpairmentConfig {
+            limit: Some(5),
+            ..ImpairmentConfig::ideal(960, 0)
+        };
+        let now = Instant::now();
+        let delivered = (0..20)
+            .filter(|_| !shaper.schedule(&cfg, 1200, now).is_empty())
+            .count();
+        assert!((5..=7).contains(&delivered), "delivered {delivered}");
+    }
+
+    #[test]
+    fn shaper_is_deterministic_for_seed() {
+        let cfg = ImpairmentConfig::lte_poor();
+        let now = Instant::now();
+        let run = |seed| {
+            let mut shaper = Shaper::new(seed);
+            (0..1000)
+                .map(|_| shaper.schedule(&cfg, 1200, now))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(7), run(7));
+        assert!(
+            run(7).iter().any(Vec::is_empty),
+            "lte_poor should lose some"
+        );
+    }
+}
@@ -110,7 +110,8 @@ pub struct ScenarioFrame {
 ///
 /// Given a seed, produces reproducible sequences of [`ScenarioFrame`]s
 /// where each link's rate, delay, and loss evolve via random-walk steps
-/// clamped to configured bounds.
+/// clamped to configured bounds.  Link `i`'s configs carry netem seed
+/// `seed + i`, so the loss pattern replays too.
 #[derive(Debug)]
 pub struct Scenario {
     cfg: ScenarioConfig,
@@ -190,6 +191,7 @@ impl Scenario {
                     } else {
                         None
                     },
+                    seed: Some(self.cfg.seed.wrapping_add(idx as u64)),
                     ..Default::default()
                 });
             }
@@ -1,4 +1,8 @@
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable that pins [`test_seed`].
+pub const SEED_ENV: &str = "STRATA_SIM_SEED";
 
 /// Gate for the heavyweight netns/netem/sudo integration tests
 /// (`tier3_netem`, `three_link_convergence`).
@@ -28,3 +32,24 @@ pub fn check_privileges() -> bool {
         Err(_) => false,
     }
 }
+
+/// Seed for a randomised run: `STRATA_SIM_SEED` when set, to replay a
+/// failure, otherwise fresh from the clock.  The seed is printed either
+/// way, so a failing test's output says how to reproduce it:
+///
+/// ```bash
+/// STRATA_SIM_SEED=1718093312 STRATA_NETEM_TESTS=1 sudo -E cargo test -p strata-sim ...
+/// ```
+pub fn test_seed() -> u64 {
+    let seed = match std::env::var(SEED_ENV) {
+        Ok(raw) => raw
+            .parse()
+            .unwrap_or_else(|_| panic!("{SEED_ENV}={raw:?} is not a u64")),
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64,
+    };
+    eprintln!("seed {seed} (replay with {SEED_ENV}={seed})");
+    seed
+}
@@ -1,4 +1,5 @@
 use std::process::Command;
+use std::sync::Arc;
 
 /// A Linux network namespace managed via `ip netns`.
 ///
@@ -179,6 +180,252 @@ impl Namespace {
     }
 }
 
+impl Namespace {
+    /// Run a command in the namespace, failing with its stderr if it exits
+    /// unsuccessfully.
+    pub fn exec_checked(&self, cmd: &str, args: &[&str]) -> std::io::Result<()> {
+        let output = self.exec(cmd, args)?;
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!(
+                "{cmd} {} failed in {}: {}",
+                args.join(" "),
+                self.name,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sender and receiver joined through a router namespace, so every
+/// sender link crosses one shared hop:
+///
+/// ```text
+///  sender ──link 0──┐
+///  sender ──link 1──┼── router ══bottleneck══ receiver
+///  sender ──link N──┘
+/// ```
+///
+/// Access link `i` is `10.88.{i+1}.0/24` (sender `.1`, router `.2`); the
+/// bottleneck is `10.88.100.0/24` (router `.1`, receiver `.2`).  Policy
+/// routing sends traffic sourced from a link's address out of that link,
+/// so a socket bound to [`link_addr`](Self::link_addr) takes it.
+///
+/// Impair the access links independently with [`access_iface`] /
+/// [`router_iface`] and the shared hop with [`bottleneck_iface`]: with
+/// the bottleneck tighter than the sum of the access rates, all links
+/// see congestion from the same queue — the case shared-bottleneck
+/// detection has to recognise.  The namespaces are shared so an
+/// [`ImpairmentScheduler`](crate::impairment::ImpairmentScheduler) can
+/// drive them.
+///
+/// [`access_iface`]: Self::access_iface
+/// [`router_iface`]: Self::router_iface
+/// [`bottleneck_iface`]: Self::bottleneck_iface
+pub struct SharedBottleneck {
+    pub sender: Arc<Namespace>,
+    pub router: Arc<Namespace>,
+    pub receiver: Arc<Namespace>,
+    prefix: String,
+    num_links: usize,
+}
+
+impl SharedBottleneck {
+    /// Receiver address on the bottleneck subnet.
+    pub const RECEIVER_ADDR: &'static str = "10.88.100.2";
+
+    /// Build the topology.  `prefix` names the namespaces and interfaces;
+    /// keep it to 8 characters so interface names fit in 15.
+    pub fn new(prefix: &str, num_links: usize) -> std::io::Result<Self> {
+        let sender = Arc::new(Namespace::new(&format!("{prefix}_snd"))?);
+        let router = Arc::new(Namespace::new(&format!("{prefix}_rtr"))?);
+        let receiver = Arc::new(Namespace::new(&format!("{prefix}_rcv"))?);
+        let topo = Self {
+            sender,
+            router,
+            receiver,
+            prefix: prefix.to_string(),
+            num_links,
+        };
+
+        topo.router.add_veth_link(
+            &topo.receiver,
+            &topo.bottleneck_iface(),
+            &format!("{prefix}_bnr"),
+            "10.88.100.1/24",
+            "10.88.100.2/24",
+        )?;
+        topo.router
+            .exec_checked("sysctl", &["-w", "net.ipv4.ip_forward=1"])?;
+        topo.receiver.exec_checked(
+            "ip",
+            &["route", "add", "10.88.0.0/16", "via", "10.88.100.1"],
+        )?;
+
+        for i in 0..num_links {
+            let (access, router_side) = (topo.access_iface(i), topo.router_iface(i));
+            topo.sender.add_veth_link(
+                &topo.router,
+                &access,
+                &router_side,
+                &format!("{}/24", topo.link_addr(i)),
+                &format!("10.88.{}.2/24", i + 1),
+            )?;
+            let table = (100 + i).to_string();
+            let gateway = format!("10.88.{}.2", i + 1);
+            topo.sender.exec_checked(
+                "ip",
+                &["rule", "add", "from", &topo.link_addr(i), "table", &table],
+            )?;
+            topo.sender.exec_checked(
+                "ip",
+                &[
+                    "route", "add", "default", "via", &gateway, "dev", &access, "table", &table,
+                ],
+            )?;
+        }
+        // Unbound sockets leave through link 0.
+        topo.sender
+            .exec_checked("ip", &["route", "add", "default", "via", "10.88.1.2"])?;
+
+        Ok(topo)
+    }
+
+    pub fn num_links(&self) -> usize {
+        self.num_links
+    }
+
+    /// Sender-side address of access link `i`.
+    pub fn link_addr(&self, i: usize) -> String {
+        format!("10.88.{}.1", i + 1)
+    }
+
+    /// Sender-side interface of access link `i` (data egress).
+    pub fn access_iface(&self, i: usize) -> String {
+        format!("{}_a{i}", self.prefix)
+    }
+
+    /// Router-side interface of access link `i` (return-path egress).
+    pub fn router_iface(&self, i: usize) -> String {
+        format!("{}_r{i}", self.prefix)
+    }
+
+    /// Router interface towards the receiver: shaping here constrains
+    /// every link at once.
+    pub fn bottleneck_iface(&self) -> String {
+        format!("{}_bn", self.prefix)
+    }
+}
+
+/// A running Docker container's network namespace, so the netem helpers
+/// can impair its interfaces just like a [`Namespace`] made here.
+///
+/// The container's netns is given a name with `ip netns attach` (entered
+/// via the container's init pid), so every [`Namespace`] method works on
+/// it.  Dropping unmounts the name only; the container keeps its network.
+///
+/// ```text
+/// // `docker compose up -d` first; networks keep their compose names.
+/// let sender = Container::attach("strata-sender-sim-1", "dk_snd")?;
+/// let link1 = sender.interface_for_network("link1")?;
+/// apply_impairment(&sender.ns, &link1, ImpairmentConfig::lte_poor())?;
+/// ```
+pub struct Container {
+    /// Container name or id, as given to [`attach`](Self::attach).
+    pub container: String,
+    pub ns: Arc<Namespace>,
+}
+
+impl Container {
+    /// Attach to `container`'s network namespace under `ns_name`.
+    /// Fails if the container is not running.
+    pub fn attach(container: &str, ns_name: &str) -> std::io::Result<Self> {
+        let pid = docker_inspect(container, "{{.State.Pid}}")?;
+        if pid.is_empty() || pid == "0" {
+            return Err(std::io::Error::other(format!(
+                "container {container} is not running"
+            )));
+        }
+
+        let _ = Command::new("sudo")
+            .args(["ip", "netns", "del", ns_name])
+            .output();
+        let output = Command::new("sudo")
+            .args(["ip", "netns", "attach", ns_name, &pid])
+            .output()?;
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!(
+                "Failed to attach netns of {container} (pid {pid}): {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(Self {
+            container: container.to_string(),
+            ns: Arc::new(Namespace {
+                name: ns_name.to_string(),
+            }),
+        })
+    }
+
+    /// The container's interface on Docker network `network`.  Compose
+    /// prefixes network names with the project, so `link1` also matches
+    /// `strata_link1`.
+    pub fn interface_for_network(&self, network: &str) -> std::io::Result<String> {
+        let networks = docker_inspect(&self.container, "{{json .NetworkSettings.Networks}}")?;
+        let addr = network_address(&networks, network).ok_or_else(|| {
+            std::io::Error::other(format!(
+                "container {} has no address on network {network}",
+                self.container
+            ))
+        })?;
+        let output = self.ns.exec("ip", &["-o", "-4", "addr", "show"])?;
+        interface_with_address(&String::from_utf8_lossy(&output.stdout), &addr).ok_or_else(|| {
+            std::io::Error::other(format!(
+                "no interface in {} has address {addr}",
+                self.container
+            ))
+        })
+    }
+}
+
+fn docker_inspect(container: &str, format: &str) -> std::io::Result<String> {
+    let output = Command::new("docker")
+        .args(["inspect", "-f", format, container])
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "docker inspect {container} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// IPv4 address on `network` from `docker inspect`'s `Networks` JSON.
+fn network_address(networks_json: &str, network: &str) -> Option<String> {
+    let networks: serde_json::Value = serde_json::from_str(networks_json).ok()?;
+    let suffix = format!("_{network}");
+    networks
+        .as_object()?
+        .iter()
+        .find(|(name, _)| *name == network || name.ends_with(&suffix))
+        .and_then(|(_, n)| n.get("IPAddress")?.as_str())
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string)
+}
+
+/// Interface holding `addr` in `ip -o -4 addr show` output.
+fn interface_with_address(ip_output: &str, addr: &str) -> Option<String> {
+    ip_output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let iface = fields.nth(1)?;
+        let cidr = fields.skip_while(|f| *f != "inet").nth(1)?;
+        (cidr.split('/').next() == Some(addr))
+            .then(|| iface.split('@').next().unwrap_or(iface).to_string())
+    })
+}
+
 impl Drop for Namespace {
     fn drop(&mut self) {
         let _ = Command::new("sudo")
@@ -192,6 +439,30 @@ mod tests {
     use super::*;
     use crate::test_util::check_privileges;
 
+    #[test]
+    fn resolves_container_interface_from_inspect_output() {
+        let networks = r#"{
+            "strata_default": {"IPAddress": "172.18.0.4"},
+            "strata_link0": {"IPAddress": "172.30.0.10"},
+            "strata_link1": {"IPAddress": "172.30.1.10"}
+        }"#;
+        assert_eq!(
+            network_address(networks, "link1").as_deref(),
+            Some("172.30.1.10")
+        );
+        assert_eq!(network_address(networks, "link2"), None);
+
+        let ip = "\
+1: lo    inet 127.0.0.1/8 scope host lo\\       valid_lft forever preferred_lft forever
+41: eth0    inet 172.18.0.4/16 brd 172.18.255.255 scope global eth0\\       valid_lft forever preferred_lft forever
+45: eth2    inet 172.30.1.10/24 brd 172.30.1.255 scope global eth2\\       valid_lft forever preferred_lft forever";
+        assert_eq!(
+            interface_with_address(ip, "172.30.1.10").as_deref(),
+            Some("eth2")
+        );
+        assert_eq!(interface_with_address(ip, "172.30.1.1"), None);
+    }
+
     #[test]
     fn test_create_namespace_pair() {
         if !check_privileges() {
@@ -246,4 +517,36 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_shared_bottleneck_routes_each_link_through_router() {
+        if !check_privileges() {
+            eprintln!("Skipping test, unsufficient privileges or missing tools");
+            return;
+        }
+
+        let topo = SharedBottleneck::new("rst_sb", 2).expect("Failed to build topology");
+        for i in 0..topo.num_links() {
+            let out = topo
+                .sender
+                .exec(
+                    "ping",
+                    &[
+                        "-c",
+                        "1",
+                        "-W",
+                        "1",
+                        "-I",
+                        &topo.link_addr(i),
+                        SharedBottleneck::RECEIVER_ADDR,
+                    ],
+                )
+                .expect("Failed to exec ping");
+            assert!(
+                out.status.success(),
+                "Ping over link {i} failed: {}",
+                String::from_utf8_lossy(&out.stdout)
+            );
+        }
+    }
 }
@@ -0,0 +1,260 @@
+//! # Trace-Driven Impairment Replay
+//!
+//! Replays link timelines recorded in the field (capacity, loss and RTT per
+//! modem, typically extracted from a pcap or the sender's own telemetry)
+//! through netem, so a problem seen at a venue can be reproduced
+//! deterministically in CI.
+//!
+//! Traces are CSV with a header row.  Columns are matched by name, in any
+//! order:
+//!
+//! | column          | meaning                                           |
+//! |-----------------|---------------------------------------------------|
+//! | `t_ms`          | sample time, milliseconds from the start of trace |
+//! | `link`          | link name (optional; one link if absent)          |
+//! | `capacity_kbps` | uplink capacity                                   |
+//! | `loss_pct`      | packet loss, percent                              |
+//! | `rtt_ms`        | round-trip time; replayed as half on each path    |
+//!
+//! Blank lines and lines starting with `#` are ignored.  An empty cell
+//! keeps the link's previous value.  A capacity of 0 means the link was
+//! down and is replayed as full loss.
+//!
+//! ```text
+//! let trace = Trace::parse(&std::fs::read_to_string("traces/stadium_egress.csv")?)?;
+//! let mut scheduler = ImpairmentScheduler::new(Duration::from_millis(50));
+//! for (ns, iface, link) in [(&ns_snd, "veth0", "modem0"), (&ns_snd, "veth1", "modem1")] {
+//!     let schedule = trace.link(link).unwrap().schedule(&ImpairmentConfig::default());
+//!     scheduler = scheduler.link(ns.clone(), iface, schedule);
+//! }
+//! scheduler.run().await?;
+//! ```
+
+use crate::impairment::{ImpairmentConfig, ImpairmentSchedule, schedule_frames};
+use crate::scenario::ScenarioFrame;
+use anyhow::{Context, Result, bail};
+use std::time::Duration;
+
+/// One sample of a recorded link.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracePoint {
+    pub t: Duration,
+    pub capacity_kbps: u64,
+    pub loss_pct: f32,
+    pub rtt_ms: u32,
+}
+
+/// The recorded timeline of one link, in time order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkTrace {
+    pub name: String,
+    pub points: Vec<TracePoint>,
+}
+
+impl LinkTrace {
+    /// Time of the last sample.
+    pub fn duration(&self) -> Duration {
+        self.points.last().map_or(Duration::ZERO, |p| p.t)
+    }
+
+    /// A schedule that holds each sample until the next one.
+    ///
+    /// Capacity, loss and RTT come from the trace; every other field
+    /// (jitter, slot scheduling, modem buffer, …) comes from `base`.
+    pub fn schedule(&self, base: &ImpairmentConfig) -> ImpairmentSchedule {
+        let mut schedule = ImpairmentSchedule::new();
+        for (i, point) in self.points.iter().enumerate() {
+            // The first sample also covers any time before it.
+            let from = if i == 0 { Duration::ZERO } else { point.t };
+            let until = self.points.get(i + 1).map_or(point.t, |next| next.t);
+            schedule = schedule.hold(until - from, point.config(base));
+        }
+        schedule
+    }
+}
+
+impl TracePoint {
+    fn config(&self, base: &ImpairmentConfig) -> ImpairmentConfig {
+        let down = self.capacity_kbps == 0;
+        ImpairmentConfig {
+            rate_kbit: Some(self.capacity_kbps.max(1)),
+            delay_ms: Some(self.rtt_ms / 2),
+            loss_percent: Some(if down { 100.0 } else { self.loss_pct }),
+            gemodel: None,
+            ..base.clone()
+        }
+    }
+}
+
+/// A recorded multi-link trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    /// Links in order of first appearance.
+    pub links: Vec<LinkTrace>,
+}
+
+impl Trace {
+    /// Parse a CSV trace (see the [module docs](self) for the format).
+    pub fn parse(csv: &str) -> Result<Self> {
+        let mut rows = csv
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (_, header) = rows.next().context("trace has no header row")?;
+        let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+        let column = |name: &str| columns.iter().position(|c| *c == name);
+        let require = |name: &str| {
+            column(name).with_context(|| format!("trace header has no {name:?} column"))
+        };
+        let t_col = require("t_ms")?;
+        let capacity_col = require("capacity_kbps")?;
+        let loss_col = require("loss_pct")?;
+        let rtt_col = require("rtt_ms")?;
+        let link_col = column("link");
+
+        let mut links: Vec<LinkTrace> = Vec::new();
+        for (line_no, row) in rows {
+            let cells: Vec<&str> = row.split(',').map(str::trim).collect();
+            let cell = |idx: usize| cells.get(idx).copied().filter(|c| !c.is_empty());
+            let name = link_col.and_then(cell).unwrap_or("link0");
+
+            let idx = match links.iter().position(|l| l.name == name) {
+                Some(idx) => idx,
+                None => {
+                    links.push(LinkTrace {
+                        name: name.to_string(),
+                        points: Vec::new(),
+                    });
+                    links.len() - 1
+                }
+            };
+            let link = &mut links[idx];
+            let prev = link.points.last();
+
+            let t_ms: u64 = parse_cell(cell(t_col), None, "t_ms", line_no)?;
+            let point = TracePoint {
+                t: Duration::from_millis(t_ms),
+                capacity_kbps: parse_cell(
+                    cell(capacity_col),
+                    prev.map(|p| p.capacity_kbps),
+                    "capacity_kbps",
+                    line_no,
+                )?,
+                loss_pct: parse_cell(
+                    cell(loss_col),
+                    prev.map(|p| p.loss_pct),
+                    "loss_pct",
+                    line_no,
+                )?,
+                rtt_ms: parse_cell(cell(rtt_col), prev.map(|p| p.rtt_ms), "rtt_ms", line_no)?,
+            };
+            if let Some(prev) = prev
+                && point.t < prev.t
+            {
+                bail!("line {line_no}: {name} goes back in time ({t_ms} ms)");
+            }
+            link.points.push(point);
+        }
+
+        if links.is_empty() {
+            bail!("trace has no samples");
+        }
+        Ok(Self { links })
+    }
+
+    pub fn link(&self, name: &str) -> Option<&LinkTrace> {
+        self.links.iter().find(|l| l.name == name)
+    }
+
+    /// One schedule per link, in [`links`](Self::links) order, each on top
+    /// of `base`.
+    pub fn schedules(&self, base: &ImpairmentConfig) -> Vec<ImpairmentSchedule> {
+        self.links.iter().map(|l| l.schedule(base)).collect()
+    }
+
+    /// Sample every link every `step`, for frame-loop tests.
+    pub fn frames(&self, base: &ImpairmentConfig, step: Duration) -> Vec<ScenarioFrame> {
+        schedule_frames(&self.schedules(base), step)
+    }
+}
+
+/// Parse a cell, falling back to the link's previous value when it is
+/// empty.  The first sample of a link must fill every column.
+fn parse_cell<T: std::str::FromStr>(
+    cell: Option<&str>,
+    prev: Option<T>,
+    column: &str,
+    line_no: usize,
+) -> Result<T> {
+    match (cell, prev) {
+        (Some(raw), _) => raw
+            .parse()
+            .ok()
+            .with_context(|| format!("line {line_no}: invalid {column} {raw:?}")),
+        (None, Some(prev)) => Ok(prev),
+        (None, None) => bail!("line {line_no}: missing {column}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STADIUM: &str = include_str!("../traces/stadium_egress.csv");
+
+    #[test]
+    fn parses_multi_link_trace() {
+        let trace = Trace::parse(STADIUM).unwrap();
+        assert_eq!(trace.links.len(), 2);
+        let modem1 = trace.link("modem1").unwrap();
+        assert_eq!(modem1.points.len(), 8);
+        assert_eq!(modem1.duration(), Duration::from_secs(6));
+
+        // Empty cells carry the previous sample's values.
+        let outage = &modem1.points[3];
+        assert_eq!(outage.capacity_kbps, 0);
+        assert_eq!(outage.rtt_ms, 83);
+    }
+
+    #[test]
+    fn replays_samples_as_held_configs() {
+        let trace = Trace::parse(STADIUM).unwrap();
+        let base = ImpairmentConfig {
+            jitter_ms: Some(5),
+            ..Default::default()
+        };
+        let schedule = trace.link("modem1").unwrap().schedule(&base);
+
+        let early = schedule.config_at(Duration::from_millis(500));
+        assert_eq!(early.rate_kbit, Some(6_100));
+        assert_eq!(early.delay_ms, Some(27));
+        assert_eq!(early.jitter_ms, Some(5));
+
+        let outage = schedule.config_at(Duration::from_millis(3_200));
+        assert_eq!(outage.loss_percent, Some(100.0));
+
+        let back = schedule.config_at(Duration::from_millis(3_500));
+        assert_eq!(back.rate_kbit, Some(900));
+        assert_eq!(back.loss_percent, Some(6.0));
+
+        // The last sample holds.
+        let end = schedule.config_at(Duration::from_secs(60));
+        assert_eq!(end.rate_kbit, Some(4_400));
+
+        let frames = trace.frames(&base, Duration::from_millis(100));
+        assert_eq!(frames.len(), 61);
+        assert!(frames.iter().all(|f| f.configs.len() == 2));
+    }
+
+    #[test]
+    fn rejects_malformed_traces() {
+        assert!(Trace::parse("").is_err());
+        assert!(Trace::parse("t_ms,capacity_kbps,loss_pct\n0,100,0").is_err());
+        assert!(Trace::parse("t_ms,capacity_kbps,loss_pct,rtt_ms\n0,100,,40").is_err());
+        let backwards = "t_ms,capacity_kbps,loss_pct,rtt_ms\n100,1,0,40\n50,1,0,40";
+        let err = Trace::parse(backwards).unwrap_err().to_string();
+        assert!(err.contains("line 3"), "{err}");
+    }
+}
@@ -0,0 +1,50 @@
+//! End-to-end bonding scenarios on the [`BondingTest`] harness.
+//!
+//! Run over netns/netem (requires root):
+//! ```bash
+//! STRATA_NETEM_TESTS=1 sudo -E cargo test -p strata-sim --test e2e_harness -- --nocapture --ignored
+//! ```
+//!
+//! Without `STRATA_NETEM_TESTS` the same tests run unprivileged through the
+//! userspace impairment proxy.
+
+use std::time::Duration;
+use strata_sim::bonding_scenarios::{
+    CellularHandoverScenario, LinkFailureScenario, MobilityProfile,
+};
+use strata_sim::harness::BondingTest;
+use strata_sim::impairment::{ImpairmentConfig, frame_schedules};
+
+/// One of two links drops out for 5 s; delivery should ride over it on
+/// the surviving link.
+#[test]
+#[ignore = "Slow end-to-end run — run with --ignored"]
+fn link_failure_fails_over() {
+    let scenario = LinkFailureScenario {
+        num_links: 2,
+        ..Default::default()
+    };
+    BondingTest::new("e2efail")
+        .links(frame_schedules(&scenario.frames()))
+        .run()
+        .assert_delivered_ratio(0.95)
+        .assert_failover_within(scenario.failure_start, Duration::from_millis(500));
+}
+
+/// Train handovers on two LTE links, with a steady third link alongside.
+#[test]
+#[ignore = "Slow end-to-end run — run with --ignored"]
+fn train_handovers_keep_delivering() {
+    let scenario = CellularHandoverScenario {
+        duration: Duration::from_secs(60),
+        profile: MobilityProfile::Train,
+        links: vec![ImpairmentConfig::lte_urban(), ImpairmentConfig::lte_good()],
+        ..Default::default()
+    };
+    BondingTest::new("e2etrain")
+        .links(scenario.schedules())
+        .static_link(ImpairmentConfig::lte_poor())
+        .run()
+        .assert_delivered_ratio(0.9)
+        .assert_max_gap(Duration::from_secs(1));
+}
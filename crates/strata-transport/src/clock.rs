@@ -0,0 +1,271 @@
+//! # Clock Synchronization
+//!
+//! NTP-style offset estimation between the two ends of a link. Wire
+//! timestamps are µs since each process's own epoch, so sender and receiver
+//! clocks differ by an arbitrary offset plus a slow drift.
+//!
+//! Every PING/PONG exchange yields one sample:
+//!
+//! ```text
+//! t1 = PING sent      (local)       δ = t4 − t1           (round trip)
+//! t2 = PING received  (remote)      θ = t2 − t1 − δ/2     (remote − local)
+//! t4 = PONG received  (local)
+//! ```
+//!
+//! θ is exact only on a symmetric path; queueing on one leg skews it by
+//! half the asymmetry. As in NTP's clock filter, the estimate follows the
+//! minimum-delay sample of the recent window — the one least inflated by
+//! queueing. Drift is the least-squares slope of the estimate over a longer
+//! window, in ppm.
+//!
+//! Only the side sending PINGs can measure. It hands its estimate to the
+//! peer in every PING ([`PingPacket::clock_offset_us`]), which adopts it
+//! through [`ClockSync::on_peer_estimate`] — both ends then share one
+//! reference for one-way delay and playout deadlines.
+//!
+//! [`PingPacket::clock_offset_us`]: crate::wire::PingPacket::clock_offset_us
+
+use quanta::Instant;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Exchanges considered by the minimum-delay filter.
+const FILTER_LEN: usize = 8;
+/// Estimates kept for the drift fit.
+const DRIFT_WINDOW: usize = 64;
+/// Shortest span of estimates a drift fit is trusted over.
+const DRIFT_MIN_SPAN: Duration = Duration::from_secs(2);
+/// Round trips above this are wrapped or reordered timestamps, not samples.
+const MAX_SAMPLE_DELAY_US: i64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    delay_us: i64,
+    offset_us: i64,
+}
+
+/// Offset and drift of a remote wire clock relative to the local one.
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    filter: VecDeque<Sample>,
+    history: VecDeque<(Instant, i64)>,
+    offset_us: Option<i64>,
+    updated_at: Option<Instant>,
+    drift_ppm: Option<f64>,
+}
+
+impl ClockSync {
+    pub fn new() -> Self {
+        ClockSync {
+            filter: VecDeque::with_capacity(FILTER_LEN),
+            history: VecDeque::with_capacity(DRIFT_WINDOW),
+            offset_us: None,
+            updated_at: None,
+            drift_ppm: None,
+        }
+    }
+
+    /// Feed one PING/PONG exchange: `t1` PING sent and `t4` PONG received
+    /// on the local clock, `t2` PING received on the remote clock.
+    pub fn on_exchange(&mut self, t1: u32, t2: u32, t4: u32, now: Instant) {
+        let delay_us = t4.wrapping_sub(t1) as i64;
+        if delay_us > MAX_SAMPLE_DELAY_US {
+            return;
+        }
+        let offset_us = self.unwrap(t2.wrapping_sub(t1) as i32 as i64 - delay_us / 2);
+
+        if self.filter.len() == FILTER_LEN {
+            self.filter.pop_front();
+        }
+        self.filter.push_back(Sample {
+            delay_us,
+            offset_us,
+        });
+        // Ties go to the freshest sample.
+        if let Some(best) = self.filter.iter().rev().min_by_key(|s| s.delay_us) {
+            self.record(best.offset_us, now);
+        }
+    }
+
+    /// Adopt the peer's estimate of *its* remote − local offset, as carried
+    /// in a PING. Our remote − local is the negation.
+    pub fn on_peer_estimate(&mut self, peer_offset_us: i32, now: Instant) {
+        let offset_us = self.unwrap(-(peer_offset_us as i64));
+        self.record(offset_us, now);
+    }
+
+    /// Whether an offset estimate is available.
+    pub fn is_synced(&self) -> bool {
+        self.offset_us.is_some()
+    }
+
+    /// Latest remote − local offset estimate in µs.
+    pub fn offset_us(&self) -> Option<i64> {
+        self.offset_us
+    }
+
+    /// Remote clock rate relative to local in ppm (positive: remote runs
+    /// fast). `None` until estimates span [`DRIFT_MIN_SPAN`].
+    pub fn drift_ppm(&self) -> Option<f64> {
+        self.drift_ppm
+    }
+
+    /// Offset extrapolated to `now` along the measured drift.
+    pub fn offset_at(&self, now: Instant) -> Option<i64> {
+        let offset = self.offset_us?;
+        let (Some(drift), Some(at)) = (self.drift_ppm, self.updated_at) else {
+            return Some(offset);
+        };
+        let elapsed = now.saturating_duration_since(at).as_secs_f64();
+        Some(offset + (drift * elapsed) as i64)
+    }
+
+    /// Map a remote wire timestamp onto the local wire clock.
+    pub fn remote_to_local(&self, remote_us: u32, now: Instant) -> Option<u32> {
+        let offset = self.offset_at(now)?;
+        Some(remote_us.wrapping_sub(offset as u32))
+    }
+
+    /// Offsets live on a 2^32 µs ring; keep successive estimates continuous
+    /// across the wrap so the drift fit never sees a 71-minute jump.
+    fn unwrap(&self, offset_us: i64) -> i64 {
+        match self.offset_us {
+            Some(prev) => prev + (offset_us.wrapping_sub(prev) as i32) as i64,
+            None => offset_us,
+        }
+    }
+
+    fn record(&mut self, offset_us: i64, now: Instant) {
+        self.offset_us = Some(offset_us);
+        self.updated_at = Some(now);
+        if self.history.len() == DRIFT_WINDOW {
+            self.history.pop_front();
+        }
+        self.history.push_back((now, offset_us));
+        self.drift_ppm = self.fit_drift();
+    }
+
+    /// Least-squares slope of offset (µs) over time (s) — µs/s is ppm.
+    fn fit_drift(&self) -> Option<f64> {
+        let (first, _) = *self.history.front()?;
+        let (last, _) = *self.history.back()?;
+        if last.saturating_duration_since(first) < DRIFT_MIN_SPAN {
+            return None;
+        }
+        let base = self.history[0].1;
+        let n = self.history.len() as f64;
+        let points = self.history.iter().map(|&(t, o)| {
+            (
+                t.saturating_duration_since(first).as_secs_f64(),
+                (o - base) as f64,
+            )
+        });
+        let (sx, sy, sxx, sxy) = points.fold((0.0, 0.0, 0.0, 0.0), |(sx, sy, sxx, sxy), (x, y)| {
+            (sx + x, sy + y, sxx + x * x, sxy + x * y)
+        });
+        let denom = n * sxx - sx * sx;
+        (denom > 0.0).then(|| (n * sxy - sx * sy) / denom)
+    }
+}
+
+impl Default for ClockSync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One exchange against a remote clock `offset` µs ahead, with the given
+    /// one-way delays.
+    fn exchange(sync: &mut ClockSync, t1: u32, offset: i64, fwd: u32, rev: u32, now: Instant) {
+        let t2 = (t1 as i64 + fwd as i64 + offset) as u32;
+        let t4 = t1.wrapping_add(fwd).wrapping_add(rev);
+        sync.on_exchange(t1, t2, t4, now);
+    }
+
+    #[test]
+    fn symmetric_exchange_recovers_offset() {
+        let mut sync = ClockSync::new();
+        assert!(!sync.is_synced());
+        exchange(
+            &mut sync,
+            1_000_000,
+            250_000,
+            20_000,
+            20_000,
+            Instant::now(),
+        );
+        assert_eq!(sync.offset_us(), Some(250_000));
+        let now = Instant::now();
+        assert_eq!(sync.remote_to_local(1_270_000, now), Some(1_020_000));
+    }
+
+    #[test]
+    fn queued_samples_lose_to_the_fastest_exchange() {
+        let mut sync = ClockSync::new();
+        let now = Instant::now();
+        exchange(&mut sync, 1_000_000, -40_000, 15_000, 15_000, now);
+        // 200 ms of uplink queueing would skew θ by +100 ms if trusted.
+        exchange(&mut sync, 1_100_000, -40_000, 215_000, 15_000, now);
+        assert_eq!(sync.offset_us(), Some(-40_000));
+    }
+
+    #[test]
+    fn drift_is_measured_in_ppm() {
+        let mut sync = ClockSync::new();
+        let start = Instant::now();
+        // Remote gains 50 µs per second.
+        for i in 0..20u32 {
+            let t1 = 1_000_000 + i * 500_000;
+            let offset = 10_000 + (i as i64 * 25);
+            exchange(
+                &mut sync,
+                t1,
+                offset,
+                10_000,
+                10_000,
+                start + Duration::from_millis(500 * i as u64),
+            );
+        }
+        let drift = sync.drift_ppm().expect("10 s of estimates");
+        assert!((drift - 50.0).abs() < 1.0, "drift {drift}");
+    }
+
+    #[test]
+    fn offsets_stay_continuous_across_the_wrap() {
+        let mut sync = ClockSync::new();
+        let now = Instant::now();
+        // Remote sits just under 2^31 µs (~36 min) ahead...
+        let base = 2_147_000_000i64;
+        exchange(&mut sync, 1_000_000, base, 5_000, 5_000, now);
+        // ...then drifts past it: the raw 32-bit difference now reads ≈ −2^31.
+        exchange(&mut sync, 2_000_000, base + 600_000, 2_000, 2_000, now);
+        assert_eq!(sync.offset_us(), Some(base + 600_000));
+        let remote = (3_000_000 + base + 600_000) as u32;
+        assert_eq!(sync.remote_to_local(remote, now), Some(3_000_000));
+    }
+
+    #[test]
+    fn peer_estimate_is_adopted_negated() {
+        let mut sender = ClockSync::new();
+        let now = Instant::now();
+        exchange(&mut sender, 1_000_000, 75_000, 8_000, 8_000, now);
+
+        let mut receiver = ClockSync::new();
+        receiver.on_peer_estimate(sender.offset_us().unwrap() as i32, now);
+        assert_eq!(receiver.offset_us(), Some(-75_000));
+        // A sender timestamp maps onto the receiver clock 75 ms later.
+        assert_eq!(receiver.remote_to_local(1_000_000, now), Some(1_075_000));
+    }
+
+    #[test]
+    fn implausible_round_trips_are_ignored() {
+        let mut sync = ClockSync::new();
+        // t4 before t1: reordered/wrapped, not a 71-minute round trip.
+        sync.on_exchange(1_000_000, 1_000_000, 999_000, Instant::now());
+        assert!(!sync.is_synced());
+    }
+}
@@ -210,6 +210,10 @@ pub struct BiscayController {
     created_at: Instant,
     /// Last time the state machine was evaluated.
     last_tick: Instant,
+    /// When the session was suspended; `None` while media flows. The
+    /// bandwidth estimate and ProbeRtt clock are frozen from here until
+    /// `resume`.
+    suspended_at: Option<Instant>,
 
     // ─── Bufferbloat drain ───
     /// Multiplicative factor applied to pacing rate when bufferbloat is
@@ -366,6 +370,7 @@ impl BiscayController {
 
             created_at: now,
             last_tick: now,
+            suspended_at: None,
 
             drain_factor: 1.0,
             probe_allowed: true, // default: allowed until coordinator assigns tokens
@@ -585,6 +590,47 @@ impl BiscayController {
         self.update_pacing_rate();
     }
 
+    /// Freeze the estimates while the session is suspended.
+    ///
+    /// A suspended link carries only keepalives: its delivery samples say
+    /// nothing about capacity, and left alone the bandwidth window would
+    /// age out every real sample over a long pause. RTT samples from the
+    /// keepalive pings still land, so RTprop stays current.
+    pub fn suspend(&mut self) {
+        if self.suspended_at.is_none() {
+            self.suspended_at = Some(Instant::now());
+        }
+    }
+
+    /// Thaw the estimates frozen by [`suspend`](Self::suspend) and pace at
+    /// the pre-suspension rate at once, so media is back at full rate
+    /// within one RTT instead of re-running calibration.
+    pub fn resume(&mut self) {
+        let Some(at) = self.suspended_at.take() else {
+            return;
+        };
+        let now = Instant::now();
+        // Slide the frozen samples forward by the pause so the window
+        // picks up exactly where it left off.
+        let paused = now.duration_since(at);
+        for (ts, _) in &mut self.bw_samples {
+            *ts += paused;
+        }
+        // Restart the ProbeRtt clock: a forced drain as media returns
+        // would halve the rate at the worst moment, and the keepalive
+        // pings have kept RTprop honest anyway.
+        self.rt_prop_stamp = now;
+        if self.bbr_phase == BbrPhase::ProbeRtt {
+            self.bbr_phase = BbrPhase::ProbeBw;
+        }
+        self.update_pacing_rate();
+    }
+
+    /// Whether the estimates are frozen for a suspended session.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+
     // ─── Getters ────────────────────────────────────────────────────────
 
     /// Get the current pacing rate in bytes/sec.
@@ -695,7 +741,7 @@ impl BiscayController {
         interval_us: u64,
         is_app_limited: bool,
     ) {
-        if interval_us == 0 {
+        if interval_us == 0 || self.suspended_at.is_some() {
             return;
         }
         let now = Instant::now();
@@ -1101,6 +1147,9 @@ impl BiscayController {
     pub fn tick(&mut self) {
         let now = Instant::now();
         self.last_tick = now;
+        if self.suspended_at.is_some() {
+            return;
+        }
 
         // Check if RTprop needs re-probing
         if now.duration_since(self.rt_prop_stamp) > self.rt_prop_expiry
@@ -1844,6 +1893,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn suspension_preserves_bandwidth_estimate_across_a_long_pause() {
+        let mut cc = BiscayController::new();
+        cc.seed_bandwidth(2_000_000.0);
+        cc.on_rtt_sample(50_000.0);
+        let pacing = cc.pacing_rate();
+
+        // Suspend, then pretend the pause has lasted well past the 10 s
+        // bandwidth window.
+        cc.suspend();
+        assert!(cc.is_suspended());
+        let long_ago = Instant::now() - Duration::from_secs(30);
+        cc.suspended_at = Some(long_ago);
+        for (ts, _) in &mut cc.bw_samples {
+            *ts = long_ago - Duration::from_secs(1);
+        }
+        cc.rt_prop_stamp = long_ago;
+
+        // Keepalive-sized deliveries and the ProbeRtt clock are ignored.
+        cc.on_bandwidth_sample(200, 1_000_000, false);
+        cc.tick();
+        assert_eq!(cc.bbr_phase, BbrPhase::ProbeBw);
+        assert_eq!(cc.btl_bw(), 2_000_000.0);
+
+        cc.resume();
+        assert!(!cc.is_suspended());
+        assert_eq!(cc.pacing_rate(), pacing, "full rate straight after resume");
+
+        // The pre-pause sample is still inside the window, so one low
+        // post-resume sample can't replace it.
+        cc.on_bandwidth_sample(100_000, 1_000_000, false);
+        assert_eq!(cc.btl_bw(), 2_000_000.0);
+        cc.tick();
+        assert_eq!(cc.bbr_phase, BbrPhase::ProbeBw);
+    }
+
     // ─── F1 fast knee signal: queue_building() ──────────────────────────
 
     #[test]
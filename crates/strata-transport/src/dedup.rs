@@ -0,0 +1,144 @@
+//! # Duplicate Suppression
+//!
+//! Broadcast and redundancy scheduling send the same payload over several
+//! links, so the receiver sees one copy per link. [`DedupCache`] sits ahead
+//! of the jitter buffer and lets only the first copy of each sequence
+//! through, counting the rest so redundancy efficiency is measurable.
+//!
+//! The cache is a fixed ring of slots indexed by `seq % capacity`, each
+//! remembering the sequence it last saw — O(1) per packet with no
+//! allocation. A sequence that has fallen more than `capacity` behind the
+//! highest seen can no longer be judged; it passes through and is counted
+//! as stale, leaving the late/duplicate verdict to the jitter buffer.
+
+/// Verdict for one arriving copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupVerdict {
+    /// First copy of this sequence: deliver it.
+    First,
+    /// Another copy already passed: discard.
+    Duplicate,
+    /// Too far behind the window to judge: deliver and let the jitter
+    /// buffer decide.
+    Stale,
+}
+
+/// Sequence-keyed sliding-window duplicate filter.
+#[derive(Debug, Clone)]
+pub struct DedupCache {
+    /// `seq + 1` last seen in each slot; 0 marks an empty slot.
+    slots: Vec<u64>,
+    /// Highest sequence seen.
+    highest: Option<u64>,
+    /// First copies passed.
+    first_copies: u64,
+    /// Redundant copies discarded.
+    duplicates: u64,
+    /// Copies too old to judge.
+    stale: u64,
+}
+
+impl DedupCache {
+    /// Create a cache remembering the last `capacity` sequences (rounded
+    /// up to a power of two). Size it to at least the reassembly window.
+    pub fn new(capacity: usize) -> Self {
+        DedupCache {
+            slots: vec![0; capacity.max(1).next_power_of_two()],
+            highest: None,
+            first_copies: 0,
+            duplicates: 0,
+            stale: 0,
+        }
+    }
+
+    /// Classify an arriving copy of `seq` and remember it.
+    pub fn check(&mut self, seq: u64) -> DedupVerdict {
+        let window = self.slots.len() as u64;
+        if let Some(highest) = self.highest
+            && seq.saturating_add(window) <= highest
+        {
+            self.stale += 1;
+            return DedupVerdict::Stale;
+        }
+        let slot = (seq & (window - 1)) as usize;
+        if self.slots[slot] == seq.wrapping_add(1) {
+            self.duplicates += 1;
+            return DedupVerdict::Duplicate;
+        }
+        self.slots[slot] = seq.wrapping_add(1);
+        self.highest = Some(self.highest.map_or(seq, |h| h.max(seq)));
+        self.first_copies += 1;
+        DedupVerdict::First
+    }
+
+    /// First copies passed through.
+    pub fn first_copies(&self) -> u64 {
+        self.first_copies
+    }
+
+    /// Redundant copies discarded.
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// Copies that arrived too late to judge.
+    pub fn stale(&self) -> u64 {
+        self.stale
+    }
+
+    /// Redundant copies per unique sequence delivered (0.0 with no
+    /// redundancy, 1.0 when every sequence arrived twice).
+    pub fn redundancy_ratio(&self) -> f64 {
+        if self.first_copies == 0 {
+            0.0
+        } else {
+            self.duplicates as f64 / self.first_copies as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_copy_is_discarded() {
+        let mut cache = DedupCache::new(64);
+        assert_eq!(cache.check(10), DedupVerdict::First);
+        assert_eq!(cache.check(11), DedupVerdict::First);
+        assert_eq!(cache.check(10), DedupVerdict::Duplicate);
+        assert_eq!(cache.check(10), DedupVerdict::Duplicate);
+        assert_eq!(cache.first_copies(), 2);
+        assert_eq!(cache.duplicates(), 2);
+        assert!((cache.redundancy_ratio() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reordered_first_copies_pass() {
+        let mut cache = DedupCache::new(64);
+        for seq in [5, 3, 4, 0, 2, 1] {
+            assert_eq!(cache.check(seq), DedupVerdict::First, "seq {seq}");
+        }
+        assert_eq!(cache.duplicates(), 0);
+    }
+
+    #[test]
+    fn sequences_behind_the_window_are_stale() {
+        let mut cache = DedupCache::new(8);
+        assert_eq!(cache.check(0), DedupVerdict::First);
+        assert_eq!(cache.check(100), DedupVerdict::First);
+        // 0 shares no slot state with 100 but is far behind: can't judge.
+        assert_eq!(cache.check(0), DedupVerdict::Stale);
+        assert_eq!(cache.check(93), DedupVerdict::First);
+        assert_eq!(cache.stale(), 1);
+    }
+
+    #[test]
+    fn slot_reuse_does_not_alias() {
+        let mut cache = DedupCache::new(4);
+        assert_eq!(cache.check(1), DedupVerdict::First);
+        // 5 maps to the same slot as 1 but is a different sequence.
+        assert_eq!(cache.check(5), DedupVerdict::First);
+        assert_eq!(cache.check(5), DedupVerdict::Duplicate);
+    }
+}
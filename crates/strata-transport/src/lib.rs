@@ -11,16 +11,20 @@
 //! - [`wire`] — Packet header serialization, control packets, VarInt
 //! - [`pool`] — Slab-based packet buffer pool
 //! - [`session`] — Session handshake, keepalive, RTT tracking
+//! - [`clock`] — Sender/receiver clock offset and drift estimation
 //! - [`codec`] — FEC encoding/decoding (sliding-window RLNC over GF(2^8))
 //! - [`arq`] — NACK-based loss detection and retransmission
+//! - [`dedup`] — Cross-link duplicate suppression ahead of the jitter buffer
 //! - [`congestion`] — Biscay congestion control (BBRv3-inspired)
 //! - [`stats`] — Per-link and aggregate statistics
 //! - [`sender`] — Sender state machine
 //! - [`receiver`] — Receiver state machine
 
 pub mod arq;
+pub mod clock;
 pub mod codec;
 pub mod congestion;
+pub mod dedup;
 pub mod pool;
 pub mod receiver;
 pub mod rlnc;
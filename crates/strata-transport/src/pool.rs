@@ -5,9 +5,17 @@
 //!
 //! At 10 Mbps with 1500-byte packets: ~833 packets/sec. A 4096-slot pool
 //! (6 MB) provides ~5 seconds of buffer — trivial memory footprint.
+//!
+//! Packets are kept in per-[`SizeClass`] slabs with high-water stats; debug
+//! builds also record where each packet was inserted, so one that is never
+//! released can be traced with [`PacketPool::outstanding`].
+
+use std::panic::Location;
+use std::time::Duration;
 
 use bytes::Bytes;
 use quanta::Instant;
+use serde::Serialize;
 use slab::Slab;
 
 use crate::wire::{Fragment, VarInt};
@@ -96,77 +104,215 @@ pub struct PacketEntry {
     pub payload: Bytes,
 }
 
+// ─── Size Classes ────────────────────────────────────────────────────────────
+
+/// Largest payload in the [`SizeClass::Ts`] class: one MPEG-TS packet.
+pub const TS_CLASS_MAX: usize = 188;
+
+/// Largest payload in the [`SizeClass::Datagram`] class: a full datagram
+/// payload under a typical 1500-byte path MTU.
+pub const DATAGRAM_CLASS_MAX: usize = 1400;
+
+/// Payload size class. Each class has its own slab, so a burst of tiny TS
+/// packets or oversized FEC repair blocks does not interleave with — and
+/// fragment — the free list the steady stream of datagrams cycles through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeClass {
+    /// Single TS packets (≤ 188 bytes).
+    Ts,
+    /// MTU-sized datagram payloads (≤ 1400 bytes).
+    Datagram,
+    /// Anything larger — FEC repair blocks, jumbo payloads.
+    Repair,
+}
+
+impl SizeClass {
+    pub const ALL: [SizeClass; 3] = [SizeClass::Ts, SizeClass::Datagram, SizeClass::Repair];
+
+    /// The class a payload of `len` bytes is stored in.
+    pub fn for_len(len: usize) -> Self {
+        if len <= TS_CLASS_MAX {
+            SizeClass::Ts
+        } else if len <= DATAGRAM_CLASS_MAX {
+            SizeClass::Datagram
+        } else {
+            SizeClass::Repair
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Slots pre-allocated for this class out of the pool's capacity.
+    /// Datagrams are the bulk of traffic and get the lot; the other
+    /// classes start small and grow on demand.
+    fn initial_slots(self, capacity: usize) -> usize {
+        match self {
+            SizeClass::Datagram => capacity,
+            SizeClass::Ts | SizeClass::Repair => capacity / 8,
+        }
+    }
+}
+
+// ─── Pool Stats ──────────────────────────────────────────────────────────────
+
+/// Occupancy counters for one size class.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ClassStats {
+    /// Packets currently held.
+    pub len: usize,
+    /// Most packets ever held at once.
+    pub high_water: usize,
+    /// Packets inserted since the pool was created.
+    pub inserted: u64,
+}
+
+/// Pool occupancy snapshot.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PoolStats {
+    pub capacity: usize,
+    /// Packets currently held across all classes.
+    pub len: usize,
+    /// Most packets ever held at once across all classes.
+    pub high_water: usize,
+    /// Inserts refused because the pool was full.
+    pub rejected: u64,
+    /// Per class, in [`SizeClass::ALL`] order.
+    pub classes: [ClassStats; 3],
+}
+
+impl PoolStats {
+    pub fn class(&self, class: SizeClass) -> &ClassStats {
+        &self.classes[class.index()]
+    }
+}
+
+/// A packet held past the leak threshold.
+#[derive(Debug, Clone)]
+pub struct OutstandingPacket {
+    pub handle: PacketHandle,
+    pub sequence: u64,
+    pub age: Duration,
+    pub retry_count: u8,
+    pub acked: bool,
+    /// Where the packet was inserted. Only tracked in debug builds.
+    pub origin: Option<&'static Location<'static>>,
+}
+
 // ─── PacketPool ──────────────────────────────────────────────────────────────
 
-/// Slab-based pre-allocated packet pool.
+/// A pool slot: the entry plus, in debug builds, where it was inserted.
+#[derive(Debug)]
+struct Slot {
+    entry: PacketEntry,
+    origin: Option<&'static Location<'static>>,
+}
+
+/// Slab-based pre-allocated packet pool, one slab per [`SizeClass`].
 ///
 /// Provides O(1) insert, O(1) remove by key, zero heap churn after initial
-/// allocation (assuming the slab doesn't need to grow beyond `capacity`).
+/// allocation (assuming a class's slab doesn't need to grow). `capacity`
+/// bounds the packets held across all classes.
 pub struct PacketPool {
-    entries: Slab<PacketEntry>,
+    classes: [Slab<Slot>; 3],
     capacity: usize,
+    stats: PoolStats,
 }
 
 /// Handle to a packet in the pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct PacketHandle(pub usize);
+pub struct PacketHandle {
+    class: SizeClass,
+    key: usize,
+}
+
+impl PacketHandle {
+    /// Size class the packet is stored in.
+    pub fn class(&self) -> SizeClass {
+        self.class
+    }
+}
 
 impl PacketPool {
-    /// Create a pool with the given capacity. The slab pre-allocates.
+    /// Create a pool with the given capacity. The slabs pre-allocate.
     pub fn new(capacity: usize) -> Self {
         PacketPool {
-            entries: Slab::with_capacity(capacity),
+            classes: SizeClass::ALL.map(|c| Slab::with_capacity(c.initial_slots(capacity))),
             capacity,
+            stats: PoolStats {
+                capacity,
+                ..Default::default()
+            },
         }
     }
 
     /// Insert a packet into the pool. Returns a handle for later retrieval.
     /// Returns `None` if the pool is full.
+    ///
+    /// In debug builds the caller's location is recorded, so a packet that
+    /// is never released can be traced back with [`PacketPool::outstanding`].
+    #[track_caller]
     pub fn insert(&mut self, context: PacketContext, payload: Bytes) -> Option<PacketHandle> {
-        if self.entries.len() >= self.capacity {
+        if self.is_full() {
+            self.stats.rejected += 1;
             return None;
         }
-        let key = self.entries.insert(PacketEntry { context, payload });
-        Some(PacketHandle(key))
+        let class = SizeClass::for_len(payload.len());
+        let slot = Slot {
+            entry: PacketEntry { context, payload },
+            origin: cfg!(debug_assertions).then_some(Location::caller()),
+        };
+        let slab = &mut self.classes[class.index()];
+        let key = slab.insert(slot);
+
+        let class_stats = &mut self.stats.classes[class.index()];
+        class_stats.inserted += 1;
+        class_stats.high_water = class_stats.high_water.max(slab.len());
+        self.stats.high_water = self.stats.high_water.max(self.len());
+        Some(PacketHandle { class, key })
     }
 
     /// Get an immutable reference to a packet by handle.
     pub fn get(&self, handle: PacketHandle) -> Option<&PacketEntry> {
-        self.entries.get(handle.0)
+        self.classes[handle.class.index()]
+            .get(handle.key)
+            .map(|slot| &slot.entry)
     }
 
     /// Get a mutable reference to a packet by handle.
     pub fn get_mut(&mut self, handle: PacketHandle) -> Option<&mut PacketEntry> {
-        self.entries.get_mut(handle.0)
+        self.classes[handle.class.index()]
+            .get_mut(handle.key)
+            .map(|slot| &mut slot.entry)
     }
 
     /// Remove a packet from the pool, returning it.
     pub fn remove(&mut self, handle: PacketHandle) -> Option<PacketEntry> {
-        if self.entries.contains(handle.0) {
-            Some(self.entries.remove(handle.0))
-        } else {
-            None
-        }
+        self.classes[handle.class.index()]
+            .try_remove(handle.key)
+            .map(|slot| slot.entry)
     }
 
     /// Check if a handle is still valid.
     pub fn contains(&self, handle: PacketHandle) -> bool {
-        self.entries.contains(handle.0)
+        self.classes[handle.class.index()].contains(handle.key)
     }
 
     /// Number of packets currently in the pool.
     pub fn len(&self) -> usize {
-        self.entries.len()
+        self.classes.iter().map(Slab::len).sum()
     }
 
     /// Whether the pool is empty.
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.classes.iter().all(Slab::is_empty)
     }
 
     /// Whether the pool is at capacity.
     pub fn is_full(&self) -> bool {
-        self.entries.len() >= self.capacity
+        self.len() >= self.capacity
     }
 
     /// Pool capacity.
@@ -174,29 +320,61 @@ impl PacketPool {
         self.capacity
     }
 
+    /// Occupancy snapshot: current and high-water counts per class.
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = self.stats.clone();
+        stats.len = self.len();
+        for class in SizeClass::ALL {
+            stats.classes[class.index()].len = self.classes[class.index()].len();
+        }
+        stats
+    }
+
     /// Iterate all entries.
     pub fn iter(&self) -> impl Iterator<Item = (PacketHandle, &PacketEntry)> {
-        self.entries.iter().map(|(k, v)| (PacketHandle(k), v))
+        SizeClass::ALL.into_iter().flat_map(move |class| {
+            self.classes[class.index()]
+                .iter()
+                .map(move |(key, slot)| (PacketHandle { class, key }, &slot.entry))
+        })
     }
 
-    /// Drain packets older than the given cutoff time.
-    pub fn drain_expired(&mut self, cutoff: Instant) -> Vec<PacketEntry> {
-        let expired_keys: Vec<usize> = self
-            .entries
-            .iter()
-            .filter(|(_, entry)| entry.context.enqueue_time < cutoff)
-            .map(|(key, _)| key)
+    /// Packets held longer than `older_than`, oldest first. Anything the
+    /// ACK/expiry paths should have released by then — a packet pinned by
+    /// the retransmit path, say — shows up here with its insert site.
+    pub fn outstanding(&self, older_than: Duration) -> Vec<OutstandingPacket> {
+        let now = Instant::now();
+        let mut held: Vec<OutstandingPacket> = SizeClass::ALL
+            .into_iter()
+            .flat_map(|class| {
+                self.classes[class.index()]
+                    .iter()
+                    .map(move |(key, slot)| (PacketHandle { class, key }, slot))
+            })
+            .filter_map(|(handle, slot)| {
+                let age = now.saturating_duration_since(slot.entry.context.enqueue_time);
+                (age > older_than).then_some(OutstandingPacket {
+                    handle,
+                    sequence: slot.entry.context.sequence,
+                    age,
+                    retry_count: slot.entry.context.retry_count,
+                    acked: slot.entry.context.acked,
+                    origin: slot.origin,
+                })
+            })
             .collect();
+        held.sort_by_key(|p| std::cmp::Reverse(p.age));
+        held
+    }
 
-        expired_keys
-            .into_iter()
-            .map(|key| self.entries.remove(key))
-            .collect()
+    /// Drain packets older than the given cutoff time.
+    pub fn drain_expired(&mut self, cutoff: Instant) -> Vec<PacketEntry> {
+        self.drain_where(|entry| entry.context.enqueue_time < cutoff)
     }
 
     /// Mark a packet as acknowledged.
     pub fn mark_acked(&mut self, handle: PacketHandle) -> bool {
-        if let Some(entry) = self.entries.get_mut(handle.0) {
+        if let Some(entry) = self.get_mut(handle) {
             entry.context.acked = true;
             true
         } else {
@@ -206,17 +384,20 @@ impl PacketPool {
 
     /// Remove all acknowledged packets and return count purged.
     pub fn purge_acked(&mut self) -> usize {
-        let acked_keys: Vec<usize> = self
-            .entries
-            .iter()
-            .filter(|(_, entry)| entry.context.acked)
-            .map(|(key, _)| key)
-            .collect();
-        let count = acked_keys.len();
-        for key in acked_keys {
-            self.entries.remove(key);
+        self.drain_where(|entry| entry.context.acked).len()
+    }
+
+    fn drain_where(&mut self, mut pred: impl FnMut(&PacketEntry) -> bool) -> Vec<PacketEntry> {
+        let mut drained = Vec::new();
+        for slab in &mut self.classes {
+            let keys: Vec<usize> = slab
+                .iter()
+                .filter(|(_, slot)| pred(&slot.entry))
+                .map(|(key, _)| key)
+                .collect();
+            drained.extend(keys.into_iter().map(|key| slab.remove(key).entry));
         }
-        count
+        drained
     }
 }
 
@@ -341,6 +522,69 @@ mod tests {
         assert_eq!(pool.len(), 1);
     }
 
+    #[test]
+    fn pool_separates_size_classes() {
+        let mut pool = PacketPool::new(8);
+        let ts = pool
+            .insert(PacketContext::new(1, 0), Bytes::from(vec![0u8; 188]))
+            .unwrap();
+        let dgram = pool
+            .insert(PacketContext::new(2, 0), Bytes::from(vec![0u8; 1200]))
+            .unwrap();
+        let repair = pool
+            .insert(PacketContext::new(3, 0), Bytes::from(vec![0u8; 1500]))
+            .unwrap();
+        assert_eq!(ts.class(), SizeClass::Ts);
+        assert_eq!(dgram.class(), SizeClass::Datagram);
+        assert_eq!(repair.class(), SizeClass::Repair);
+        assert_eq!(pool.get(dgram).unwrap().context.sequence, 2);
+        assert_eq!(pool.iter().count(), 3);
+
+        pool.remove(dgram);
+        assert!(!pool.contains(dgram));
+        assert!(pool.contains(ts) && pool.contains(repair));
+
+        let stats = pool.stats();
+        assert_eq!(stats.len, 2);
+        assert_eq!(stats.high_water, 3);
+        assert_eq!(stats.class(SizeClass::Datagram).len, 0);
+        assert_eq!(stats.class(SizeClass::Datagram).high_water, 1);
+        assert_eq!(stats.class(SizeClass::Ts).inserted, 1);
+    }
+
+    #[test]
+    fn pool_counts_rejected_inserts() {
+        let mut pool = PacketPool::new(1);
+        assert!(
+            pool.insert(PacketContext::new(1, 0), Bytes::new())
+                .is_some()
+        );
+        assert!(
+            pool.insert(PacketContext::new(2, 0), Bytes::new())
+                .is_none()
+        );
+        assert_eq!(pool.stats().rejected, 1);
+    }
+
+    #[test]
+    fn pool_reports_outstanding_packets() {
+        let mut pool = PacketPool::new(4);
+        let mut old = PacketContext::new(1, 0);
+        old.enqueue_time = Instant::now() - Duration::from_secs(10);
+        old.retry_count = 3;
+        let old = pool.insert(old, Bytes::new()).unwrap();
+        pool.insert(PacketContext::new(2, 0), Bytes::new()).unwrap();
+
+        let held = pool.outstanding(Duration::from_secs(5));
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].handle, old);
+        assert_eq!(held[0].sequence, 1);
+        assert_eq!(held[0].retry_count, 3);
+        if cfg!(debug_assertions) {
+            assert_eq!(held[0].origin.unwrap().file(), file!());
+        }
+    }
+
     #[test]
     fn sequence_generator() {
         let mut seq_gen = SequenceGenerator::new();
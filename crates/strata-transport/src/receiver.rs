@@ -24,7 +24,7 @@ use crate::pool::SequenceGenerator;
 use crate::stats::ReceiverStats;
 use crate::wire::{
     AckPacket, ControlBody, Fragment, NackPacket, Packet, PacketHeader, PacketType,
-    PpdReportPacket, VarInt,
+    PpdReportPacket, SackPacket, VarInt,
 };
 
 // ─── Configuration ──────────────────────────────────────────────────────────
@@ -81,6 +81,8 @@ pub enum ReceiverEvent {
     SendNack(NackPacket),
     /// An ACK should be sent to the sender.
     SendAck(AckPacket),
+    /// A selective ACK should be sent to the sender.
+    SendSack(SackPacket),
     /// Application data is ready for delivery.
     Deliver(DeliveredPacket),
     /// A PPD probe pair was detected — send capacity report back to sender.
@@ -330,6 +332,7 @@ impl Receiver {
 
         // Feed loss detector
         self.loss_detector.record_received(seq);
+        self.stats.loss_pattern.record_arrival(seq);
 
         // PPD probe pair detection: when two consecutive PPD-flagged packets
         // arrive within a short window, compute bottleneck capacity from
@@ -634,6 +637,25 @@ impl Receiver {
         ack
     }
 
+    /// Generate a selective ACK for buffered packets past the ACK bitmap's
+    /// 64-packet window, so the sender can release them while the
+    /// cumulative sequence is stuck on a hole. Call after `generate_ack`.
+    /// Returns `None` when the ACK already covers everything buffered.
+    pub fn generate_sack(&mut self) -> Option<SackPacket> {
+        let window_end = self.loss_detector.highest_contiguous() + 64;
+        let beyond = self
+            .reorder_buf
+            .range(window_end + 1..)
+            .map(|(&seq, _)| seq);
+        let sack = SackPacket::from_sequences(beyond);
+        if sack.blocks.is_empty() {
+            return None;
+        }
+        self.stats.sacks_sent += 1;
+        self.events.push(ReceiverEvent::SendSack(sack.clone()));
+        Some(sack)
+    }
+
     /// Drain all receiver events.
     pub fn drain_events(&mut self) -> impl Iterator<Item = ReceiverEvent> + '_ {
         self.events.drain(..)
@@ -1238,6 +1260,36 @@ mod tests {
         assert_eq!(d[0].payload, &b"p3"[..]);
     }
 
+    #[test]
+    fn sack_covers_packets_beyond_ack_window() {
+        let mut rx = default_receiver();
+        rx.receive(make_wire_packet(0, b"p0"));
+        for seq in [10, 100, 101, 300] {
+            rx.receive(make_wire_packet(seq, b"late"));
+        }
+
+        let ack = rx.generate_ack();
+        assert_eq!(ack.cumulative_seq.value(), 0);
+        assert_eq!(ack.sacked_sequences().collect::<Vec<_>>(), vec![10]);
+
+        let sack = rx.generate_sack().expect("packets past the ACK window");
+        assert_eq!(
+            sack.sacked_sequences().collect::<Vec<_>>(),
+            vec![100, 101, 300]
+        );
+        assert_eq!(rx.stats().sacks_sent, 1);
+        assert!(
+            rx.drain_events()
+                .any(|e| matches!(e, ReceiverEvent::SendSack(s) if s == sack))
+        );
+
+        let mut near = default_receiver();
+        near.receive(make_wire_packet(0, b"p0"));
+        near.receive(make_wire_packet(5, b"p5"));
+        near.generate_ack();
+        assert!(near.generate_sack().is_none());
+    }
+
     #[test]
     fn generate_ack_also_skips_gaps() {
         // Verify that generate_ack() (not just generate_nacks()) triggers
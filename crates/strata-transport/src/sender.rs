@@ -24,10 +24,11 @@ use std::time::Duration;
 use crate::arq::RetransmitTracker;
 use crate::codec::FecEncoder;
 use crate::pool::{
-    PacketContext, PacketHandle, PacketPool, Priority, SequenceGenerator, TimestampClock,
+    OutstandingPacket, PacketContext, PacketHandle, PacketPool, PoolStats, Priority,
+    SequenceGenerator, TimestampClock,
 };
 use crate::stats::SenderStats;
-use crate::wire::{AckPacket, Fragment, NackPacket, Packet, PacketHeader};
+use crate::wire::{AckPacket, Fragment, NackPacket, Packet, PacketHeader, SackPacket};
 
 // ─── Configuration ──────────────────────────────────────────────────────────
 
@@ -238,14 +239,10 @@ impl Sender {
 
         // SACK bitmap — individual acks beyond cumulative
         for sack_seq in ack.sacked_sequences() {
-            if let Some(handle) = self.seq_to_handle.remove(&sack_seq) {
-                if let Some(entry) = self.pool.get(handle) {
-                    newly_acked_bytes += entry.payload.len() as u64;
-                }
-                self.pool.mark_acked(handle);
+            if let Some(bytes) = self.selective_ack(sack_seq) {
+                newly_acked_bytes += bytes;
                 newly_acked += 1;
             }
-            self.retransmit.mark_acked(sack_seq);
         }
 
         self.stats.packets_acked += newly_acked as u64;
@@ -266,6 +263,35 @@ impl Sender {
         newly_acked
     }
 
+    /// Process a selective ACK from the receiver: packets received past
+    /// the ACK bitmap window while the cumulative sequence is held back by
+    /// a hole. Releases their pool slots and retransmit state.
+    /// Returns the number of packets newly acknowledged.
+    pub fn process_sack(&mut self, sack: &SackPacket) -> usize {
+        let mut newly_acked = 0;
+        let mut newly_acked_bytes = 0u64;
+        for seq in sack.sacked_sequences() {
+            if let Some(bytes) = self.selective_ack(seq) {
+                newly_acked_bytes += bytes;
+                newly_acked += 1;
+            }
+        }
+        self.stats.packets_acked += newly_acked as u64;
+        self.stats.bytes_acked += newly_acked_bytes;
+        self.pool.purge_acked();
+        newly_acked
+    }
+
+    /// Acknowledge one sequence out of order. Returns its payload size if
+    /// it was still outstanding.
+    fn selective_ack(&mut self, seq: u64) -> Option<u64> {
+        self.retransmit.mark_acked(seq);
+        let handle = self.seq_to_handle.remove(&seq)?;
+        let bytes = self.pool.get(handle).map_or(0, |e| e.payload.len() as u64);
+        self.pool.mark_acked(handle);
+        Some(bytes)
+    }
+
     /// Process a NACK from the receiver.
     ///
     /// Enqueues retransmissions for requested sequence ranges.
@@ -416,6 +442,18 @@ impl Sender {
         self.pool.len() as f64 / self.pool.capacity() as f64
     }
 
+    /// Send pool occupancy, per size class, with high-water marks.
+    pub fn pool_stats(&self) -> PoolStats {
+        self.pool.stats()
+    }
+
+    /// Pooled packets older than `older_than`. With expiry running off the
+    /// ACK path, nothing should outlive the packet TTL by much; whatever
+    /// does is being held — by a retransmit loop or a stalled ACK stream.
+    pub fn outstanding_packets(&self, older_than: Duration) -> Vec<OutstandingPacket> {
+        self.pool.outstanding(older_than)
+    }
+
     /// Get current sender statistics.
     pub fn stats(&self) -> &SenderStats {
         &self.stats
@@ -635,6 +673,31 @@ mod tests {
         assert_eq!(sender.in_flight(), 2); // seqs 2, 5 remain
     }
 
+    #[test]
+    fn sack_releases_packets_past_the_ack_window() {
+        let mut sender = Sender::new(test_config());
+        for _ in 0..120 {
+            sender.send(Bytes::from(vec![0; 10]), Priority::Standard);
+        }
+        sender.drain_output().for_each(drop);
+
+        let sack = SackPacket::from_sequences(100..110);
+        assert_eq!(sender.process_sack(&sack), 10);
+        assert_eq!(sender.in_flight(), 110);
+        assert_eq!(sender.stats().packets_acked, 10);
+
+        // A NACK for a sacked packet has nothing left to resend.
+        let nack = NackPacket {
+            ranges: vec![NackRange {
+                start: VarInt::from_u64(105),
+                count: VarInt::from_u64(1),
+            }],
+        };
+        assert_eq!(sender.process_nack(&nack), 0);
+        // Repeats are idempotent.
+        assert_eq!(sender.process_sack(&sack), 0);
+    }
+
     #[test]
     fn ack_updates_stats() {
         let mut sender = Sender::new(test_config());
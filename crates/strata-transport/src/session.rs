@@ -5,15 +5,32 @@
 //!
 //! ```text
 //!   Idle ──Hello──▶ Connecting ──Accept──▶ Established ──Teardown──▶ Closed
-//!                      │                       │
-//!                    Timeout                LinkJoin/LinkLeave
+//!                      │                    │      ▲
+//!                    Timeout        Suspend │      │ Resume
+//!                                           ▼      │
+//!                                         Suspended
 //! ```
+//!
+//! LinkJoin/LinkLeave apply while Established or Suspended. A suspended
+//! session carries no media, only keepalives spaced to hold NAT bindings
+//! open, so a production can go quiet for talkback-only stretches and pick
+//! up again without a new handshake.
+//!
+//! Each path is additionally proven to round-trip to the receiver with a
+//! keyed challenge/response ([`PathValidator`]) before it is admitted into
+//! the bond, and again periodically after. See [`path_response_tag`] for
+//! what that does and does not protect against.
 
 use quanta::Instant;
 use std::collections::HashMap;
 use std::time::Duration;
+use subtle::ConstantTimeEq;
 
-use crate::wire::{PingPacket, PongPacket, SessionAction, SessionPacket};
+use crate::clock::ClockSync;
+use crate::wire::{
+    MAX_INGEST_KEY_LEN, PATH_TAG_LEN, PathChallengePacket, PathResponsePacket, PingPacket,
+    PongPacket, SessionAction, SessionPacket,
+};
 
 // ─── Session State ──────────────────────────────────────────────────────────
 
@@ -26,6 +43,8 @@ pub enum SessionState {
     Connecting,
     /// Session fully established.
     Established,
+    /// Established, but media is paused; only keepalives flow.
+    Suspended,
     /// Graceful teardown in progress.
     Closing,
     /// Session terminated.
@@ -65,6 +84,11 @@ pub struct Session {
     pub keepalive_interval: Duration,
     /// Session inactivity timeout.
     pub inactivity_timeout: Duration,
+    /// Keepalive interval while suspended. Well under the ~30 s that the
+    /// stingiest carrier NATs hold an idle UDP mapping.
+    pub suspended_keepalive_interval: Duration,
+    /// Inactivity timeout while suspended.
+    pub suspended_inactivity_timeout: Duration,
 }
 
 impl Session {
@@ -80,6 +104,8 @@ impl Session {
             handshake_timeout: Duration::from_secs(5),
             keepalive_interval: Duration::from_secs(1),
             inactivity_timeout: Duration::from_secs(10),
+            suspended_keepalive_interval: Duration::from_secs(5),
+            suspended_inactivity_timeout: Duration::from_secs(30),
         }
     }
 
@@ -91,6 +117,7 @@ impl Session {
             action: SessionAction::Hello,
             session_id: self.session_id,
             link_id: None,
+            ingest_key: None,
         }
     }
 
@@ -102,6 +129,7 @@ impl Session {
             action: SessionAction::Accept,
             session_id: self.session_id,
             link_id: None,
+            ingest_key: None,
         }
     }
 
@@ -112,6 +140,36 @@ impl Session {
             action: SessionAction::Teardown,
             session_id: self.session_id,
             link_id: None,
+            ingest_key: None,
+        }
+    }
+
+    /// Generate a Suspend notification and enter the suspended state. Only
+    /// meaningful while established; the caller stops sending media.
+    pub fn make_suspend(&mut self) -> SessionPacket {
+        if self.state == SessionState::Established {
+            self.state = SessionState::Suspended;
+        }
+        self.last_activity = Instant::now();
+        SessionPacket {
+            action: SessionAction::Suspend,
+            session_id: self.session_id,
+            link_id: None,
+            ingest_key: None,
+        }
+    }
+
+    /// Generate a Resume notification and return to the established state.
+    pub fn make_resume(&mut self) -> SessionPacket {
+        if self.state == SessionState::Suspended {
+            self.state = SessionState::Established;
+        }
+        self.last_activity = Instant::now();
+        SessionPacket {
+            action: SessionAction::Resume,
+            session_id: self.session_id,
+            link_id: None,
+            ingest_key: None,
         }
     }
 
@@ -130,6 +188,7 @@ impl Session {
             action: SessionAction::LinkJoin,
             session_id: self.session_id,
             link_id: Some(link_id),
+            ingest_key: None,
         }
     }
 
@@ -143,6 +202,7 @@ impl Session {
             action: SessionAction::LinkLeave,
             session_id: self.session_id,
             link_id: Some(link_id),
+            ingest_key: None,
         }
     }
 
@@ -167,8 +227,16 @@ impl Session {
                 self.state = SessionState::Closed;
                 SessionEvent::Closed
             }
+            (SessionState::Established, SessionAction::Suspend) => {
+                self.state = SessionState::Suspended;
+                SessionEvent::Suspended
+            }
+            (SessionState::Suspended, SessionAction::Resume) => {
+                self.state = SessionState::Established;
+                SessionEvent::Resumed
+            }
             // Link join
-            (SessionState::Established, SessionAction::LinkJoin) => {
+            (SessionState::Established | SessionState::Suspended, SessionAction::LinkJoin) => {
                 if let Some(link_id) = pkt.link_id {
                     self.links.insert(
                         link_id,
@@ -182,7 +250,7 @@ impl Session {
                 SessionEvent::LinkJoined(pkt.link_id.unwrap_or(0))
             }
             // Link leave
-            (SessionState::Established, SessionAction::LinkLeave) => {
+            (SessionState::Established | SessionState::Suspended, SessionAction::LinkLeave) => {
                 if let Some(link_id) = pkt.link_id
                     && let Some(info) = self.links.get_mut(&link_id)
                 {
@@ -205,14 +273,26 @@ impl Session {
             SessionState::Established if elapsed > self.inactivity_timeout => {
                 Some(SessionEvent::InactivityTimeout)
             }
+            SessionState::Suspended if elapsed > self.suspended_inactivity_timeout => {
+                Some(SessionEvent::InactivityTimeout)
+            }
             _ => None,
         }
     }
 
     /// Whether it's time to send a keepalive (PING).
     pub fn needs_keepalive(&self) -> bool {
-        self.state == SessionState::Established
-            && self.last_activity.elapsed() > self.keepalive_interval
+        let interval = match self.state {
+            SessionState::Established => self.keepalive_interval,
+            SessionState::Suspended => self.suspended_keepalive_interval,
+            _ => return false,
+        };
+        self.last_activity.elapsed() > interval
+    }
+
+    /// Whether media is paused.
+    pub fn is_suspended(&self) -> bool {
+        self.state == SessionState::Suspended
     }
 
     /// Number of active links.
@@ -239,6 +319,10 @@ pub enum SessionEvent {
     LinkJoined(u8),
     /// A link left.
     LinkLeft(u8),
+    /// The peer paused media.
+    Suspended,
+    /// The peer resumed media.
+    Resumed,
     /// Handshake timed out.
     HandshakeTimeout,
     /// Inactivity timeout.
@@ -247,9 +331,176 @@ pub enum SessionEvent {
     Unexpected,
 }
 
+// ─── Ingest Key ─────────────────────────────────────────────────────────────
+
+/// Whether `key` fits in a session HELLO ([`MAX_INGEST_KEY_LEN`] bytes).
+/// Keys are checked wherever they're configured; the wire encoder never
+/// shortens one.
+pub fn ingest_key_fits(key: &[u8]) -> bool {
+    key.len() <= MAX_INGEST_KEY_LEN
+}
+
+// ─── Path Validation ────────────────────────────────────────────────────────
+
+/// Domain separator so a path tag can never be replayed as any other MAC
+/// computed under the ingest key.
+const PATH_TAG_CONTEXT: &[u8] = b"strata-path-v1";
+
+/// Tag a receiver puts in its [`PathResponsePacket`]: HMAC-SHA256 over the
+/// challenge nonce, keyed with the stream's ingest key, truncated to
+/// [`PATH_TAG_LEN`] bytes. Echoing the nonce proves the path round-trips;
+/// the tag proves the far end holds the ingest key.
+///
+/// Threat model: the ingest key travels in clear in every HELLO, so it is
+/// not a secret from anyone on the path. The tag stops off-path senders
+/// and misrouted paths (a carrier NAT handing the flow to some other host,
+/// a stale mapping reaching a previous receiver) from feeding the
+/// scheduler forged feedback, since they never saw the key. It does not
+/// stop an on-path attacker who captured a HELLO; that needs an encrypted
+/// transport underneath, which this layer does not provide.
+pub fn path_response_tag(key: &[u8], nonce: u64) -> [u8; PATH_TAG_LEN] {
+    use hmac::{Hmac, Mac};
+    let mut mac =
+        Hmac::<sha2::Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(PATH_TAG_CONTEXT);
+    mac.update(&nonce.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let mut tag = [0u8; PATH_TAG_LEN];
+    tag.copy_from_slice(&digest[..PATH_TAG_LEN]);
+    tag
+}
+
+/// Answer a received challenge (receiver side).
+pub fn make_path_response(key: &[u8], challenge: &PathChallengePacket) -> PathResponsePacket {
+    PathResponsePacket {
+        nonce: challenge.nonce,
+        tag: path_response_tag(key, challenge.nonce),
+    }
+}
+
+/// Validation state of one path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathState {
+    /// Never answered a challenge; not admitted.
+    Unvalidated,
+    /// Answered the most recent challenge round; admitted.
+    Validated,
+    /// Was validated but missed [`PathValidator::max_misses`] challenges in
+    /// a row; no longer admitted until it answers again.
+    Lost,
+}
+
+/// What a received [`PathResponsePacket`] turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathResponseOutcome {
+    /// Nonce and tag match the outstanding challenge.
+    Validated,
+    /// Right nonce, wrong tag: the far end does not hold the key.
+    Forged,
+    /// No outstanding challenge with that nonce (late or unsolicited).
+    Stale,
+}
+
+/// Sender-side proof-of-liveness for one path.
+///
+/// Drive it with [`poll`](Self::poll) and send whatever challenge it
+/// returns; feed responses to [`handle_response`](Self::handle_response).
+/// Until [`is_admitted`](Self::is_admitted) the path's feedback must not be
+/// trusted for scheduling estimates.
+pub struct PathValidator {
+    key: Vec<u8>,
+    state: PathState,
+    /// Outstanding challenge: (nonce, sent at).
+    pending: Option<(u64, Instant)>,
+    validated_at: Option<Instant>,
+    /// Consecutive challenges that timed out.
+    misses: u32,
+    /// How often an admitted path is re-challenged.
+    pub revalidate_interval: Duration,
+    /// How long to wait for a response before re-challenging.
+    pub challenge_timeout: Duration,
+    /// Consecutive unanswered challenges before an admitted path is lost.
+    pub max_misses: u32,
+}
+
+impl PathValidator {
+    pub fn new(key: &[u8]) -> Self {
+        PathValidator {
+            key: key.to_vec(),
+            state: PathState::Unvalidated,
+            pending: None,
+            validated_at: None,
+            misses: 0,
+            revalidate_interval: Duration::from_secs(10),
+            challenge_timeout: Duration::from_secs(1),
+            max_misses: 3,
+        }
+    }
+
+    /// Return the challenge to send now, if one is due: immediately while
+    /// unvalidated, on timeout of the outstanding one, and every
+    /// `revalidate_interval` once admitted.
+    pub fn poll(&mut self, now: Instant) -> Option<PathChallengePacket> {
+        match self.pending {
+            Some((_, sent)) if now.duration_since(sent) < self.challenge_timeout => return None,
+            Some(_) => {
+                self.misses += 1;
+                if self.state == PathState::Validated && self.misses >= self.max_misses {
+                    self.state = PathState::Lost;
+                }
+            }
+            None => {
+                let due = match (self.state, self.validated_at) {
+                    (PathState::Validated, Some(at)) => {
+                        now.duration_since(at) >= self.revalidate_interval
+                    }
+                    _ => true,
+                };
+                if !due {
+                    return None;
+                }
+            }
+        }
+        let nonce = rand::random::<u64>();
+        self.pending = Some((nonce, now));
+        Some(PathChallengePacket { nonce })
+    }
+
+    /// Check a response against the outstanding challenge.
+    pub fn handle_response(
+        &mut self,
+        resp: &PathResponsePacket,
+        now: Instant,
+    ) -> PathResponseOutcome {
+        let Some((nonce, _)) = self.pending.filter(|(n, _)| *n == resp.nonce) else {
+            return PathResponseOutcome::Stale;
+        };
+        // Constant-time compare, same reasoning as the ingest key.
+        if !bool::from(path_response_tag(&self.key, nonce).ct_eq(&resp.tag)) {
+            return PathResponseOutcome::Forged;
+        }
+        self.pending = None;
+        self.misses = 0;
+        self.validated_at = Some(now);
+        self.state = PathState::Validated;
+        PathResponseOutcome::Validated
+    }
+
+    /// Current validation state.
+    pub fn state(&self) -> PathState {
+        self.state
+    }
+
+    /// Whether the path may carry scheduling weight.
+    pub fn is_admitted(&self) -> bool {
+        self.state == PathState::Validated
+    }
+}
+
 // ─── RTT Tracker ──────────────────────────────────────────────────────────
 
-/// Per-link RTT measurement via PING/PONG.
+/// Per-link RTT measurement via PING/PONG, with the peer clock offset
+/// estimated from the same exchanges.
 pub struct RttTracker {
     /// Pending pings awaiting pong: ping_id → (send_time, origin_timestamp_us).
     pending: HashMap<u16, Instant>,
@@ -267,8 +518,16 @@ pub struct RttTracker {
     sample_count: u64,
     /// Ping interval.
     pub ping_interval: Duration,
+    /// Ping interval while the session is suspended: slow enough to be
+    /// nearly free, fast enough that RTT and clock offset are current the
+    /// moment media resumes.
+    pub suspended_ping_interval: Duration,
+    /// Whether the slower suspended interval applies.
+    suspended: bool,
     /// Last time a ping was sent.
     pub last_ping_sent: Instant,
+    /// Peer wire-clock offset and drift.
+    clock: ClockSync,
 }
 
 impl RttTracker {
@@ -282,11 +541,15 @@ impl RttTracker {
             max_rtt_us: 0.0,
             sample_count: 0,
             ping_interval: Duration::from_millis(100),
+            suspended_ping_interval: Duration::from_secs(1),
+            suspended: false,
             last_ping_sent: Instant::now(),
+            clock: ClockSync::new(),
         }
     }
 
-    /// Generate a PING packet and record the send time.
+    /// Generate a PING packet and record the send time. The PING carries
+    /// our current clock offset estimate so the peer can adopt it.
     pub fn make_ping(&mut self, timestamp_us: u32) -> PingPacket {
         let ping_id = self.next_ping_id;
         self.next_ping_id = self.next_ping_id.wrapping_add(1);
@@ -295,6 +558,9 @@ impl RttTracker {
         PingPacket {
             origin_timestamp_us: timestamp_us,
             ping_id,
+            // Offsets live on the 2^32 µs timestamp ring; truncation keeps
+            // the value modulo the ring and the peer unwraps it.
+            clock_offset_us: self.clock.offset_us().map(|o| o as i32),
         }
     }
 
@@ -307,13 +573,21 @@ impl RttTracker {
         }
     }
 
-    /// Process a received PONG and update RTT estimates.
+    /// Process a received PONG and update RTT and clock estimates.
+    /// `receive_timestamp_us` is the local wire time the PONG arrived.
     /// Returns the measured RTT in µs, or None if the ping_id is unknown.
-    pub fn handle_pong(&mut self, pong: &PongPacket) -> Option<f64> {
+    pub fn handle_pong(&mut self, pong: &PongPacket, receive_timestamp_us: u32) -> Option<f64> {
         let send_time = self.pending.remove(&pong.ping_id)?;
         let rtt = send_time.elapsed();
         let rtt_us = rtt.as_micros() as f64;
 
+        self.clock.on_exchange(
+            pong.origin_timestamp_us,
+            pong.receive_timestamp_us,
+            receive_timestamp_us,
+            Instant::now(),
+        );
+
         self.sample_count += 1;
 
         if rtt_us < self.min_rtt_us {
@@ -340,9 +614,32 @@ impl RttTracker {
         Some(rtt_us)
     }
 
+    /// Process a received PING: adopt the peer's clock offset estimate if
+    /// it carries one. The side answering PINGs cannot measure on its own.
+    pub fn handle_ping(&mut self, ping: &PingPacket) {
+        if let Some(offset) = ping.clock_offset_us {
+            self.clock.on_peer_estimate(offset, Instant::now());
+        }
+    }
+
+    /// Peer clock offset and drift estimate.
+    pub fn clock(&self) -> &ClockSync {
+        &self.clock
+    }
+
+    /// Switch between the normal and suspended ping cadence.
+    pub fn set_suspended(&mut self, suspended: bool) {
+        self.suspended = suspended;
+    }
+
     /// Whether it's time to send a new PING.
     pub fn needs_ping(&self) -> bool {
-        self.last_ping_sent.elapsed() >= self.ping_interval
+        let interval = if self.suspended {
+            self.suspended_ping_interval
+        } else {
+            self.ping_interval
+        };
+        self.last_ping_sent.elapsed() >= interval
     }
 
     /// Get smoothed RTT in µs.
@@ -432,6 +729,51 @@ mod tests {
         assert_eq!(session.state, SessionState::Closing);
     }
 
+    #[test]
+    fn session_suspend_and_resume() {
+        let mut client = Session::new(42);
+        let mut server = Session::new(42);
+        client.state = SessionState::Established;
+        server.state = SessionState::Established;
+
+        let suspend = client.make_suspend();
+        assert!(client.is_suspended());
+        assert_eq!(
+            server.handle_session_packet(&suspend),
+            SessionEvent::Suspended
+        );
+        assert!(server.is_suspended());
+
+        // Keepalives slow down and silence is tolerated for longer.
+        server.last_activity = Instant::now() - Duration::from_secs(2);
+        assert!(!server.needs_keepalive());
+        server.last_activity = Instant::now() - Duration::from_secs(15);
+        assert_eq!(server.check_timeouts(), None);
+        server.last_activity = Instant::now() - server.suspended_inactivity_timeout * 2;
+        assert_eq!(
+            server.check_timeouts(),
+            Some(SessionEvent::InactivityTimeout)
+        );
+
+        let resume = client.make_resume();
+        assert_eq!(client.state, SessionState::Established);
+        assert_eq!(server.handle_session_packet(&resume), SessionEvent::Resumed);
+        assert_eq!(server.state, SessionState::Established);
+        server.last_activity = Instant::now() - Duration::from_secs(2);
+        assert!(server.needs_keepalive());
+    }
+
+    #[test]
+    fn rtt_tracker_suspended_cadence() {
+        let mut tracker = RttTracker::new();
+        tracker.last_ping_sent = Instant::now() - Duration::from_millis(200);
+        assert!(tracker.needs_ping());
+        tracker.set_suspended(true);
+        assert!(!tracker.needs_ping());
+        tracker.set_suspended(false);
+        assert!(tracker.needs_ping());
+    }
+
     #[test]
     fn rtt_tracker_basic() {
         let mut tracker = RttTracker::new();
@@ -442,7 +784,7 @@ mod tests {
         std::thread::sleep(std::time::Duration::from_millis(1));
 
         let pong = RttTracker::make_pong(&ping, 1050);
-        let rtt = tracker.handle_pong(&pong).unwrap();
+        let rtt = tracker.handle_pong(&pong, 1100).unwrap();
         assert!(rtt > 0.0);
         assert!(tracker.srtt_us() > 0.0);
         assert!(tracker.sample_count() == 1);
@@ -456,7 +798,7 @@ mod tests {
             let ping = tracker.make_ping(i * 100);
             std::thread::sleep(std::time::Duration::from_millis(1));
             let pong = RttTracker::make_pong(&ping, i * 100 + 50);
-            tracker.handle_pong(&pong);
+            tracker.handle_pong(&pong, i * 100 + 100);
         }
 
         assert_eq!(tracker.sample_count(), 5);
@@ -473,6 +815,114 @@ mod tests {
             ping_id: 999,
             receive_timestamp_us: 0,
         };
-        assert!(tracker.handle_pong(&pong).is_none());
+        assert!(tracker.handle_pong(&pong, 0).is_none());
+    }
+
+    #[test]
+    fn rtt_tracker_shares_clock_offset_with_peer() {
+        let mut sender = RttTracker::new();
+        let mut receiver = RttTracker::new();
+
+        // No estimate yet: the first PING carries none.
+        let ping = sender.make_ping(1_000_000);
+        assert_eq!(ping.clock_offset_us, None);
+        receiver.handle_ping(&ping);
+        assert!(!receiver.clock().is_synced());
+
+        // Receiver clock 300 ms ahead, 10 ms each way.
+        let pong = RttTracker::make_pong(&ping, 1_310_000);
+        sender.handle_pong(&pong, 1_020_000).unwrap();
+        assert_eq!(sender.clock().offset_us(), Some(300_000));
+
+        let ping = sender.make_ping(1_100_000);
+        assert_eq!(ping.clock_offset_us, Some(300_000));
+        receiver.handle_ping(&ping);
+        assert_eq!(receiver.clock().offset_us(), Some(-300_000));
+    }
+
+    #[test]
+    fn ingest_key_length_limit() {
+        assert!(ingest_key_fits(&[0; MAX_INGEST_KEY_LEN]));
+        assert!(!ingest_key_fits(&[0; MAX_INGEST_KEY_LEN + 1]));
+    }
+
+    #[test]
+    fn path_is_admitted_only_after_a_keyed_echo() {
+        let start = Instant::now();
+        let mut path = PathValidator::new(b"isk_abc");
+        assert_eq!(path.state(), PathState::Unvalidated);
+
+        let challenge = path
+            .poll(start)
+            .expect("unvalidated path challenges at once");
+        assert!(path.poll(start).is_none(), "one outstanding challenge");
+
+        // A spoofed far end can echo the nonce but not produce the tag.
+        let forged = make_path_response(b"isk_xyz", &challenge);
+        assert_eq!(
+            path.handle_response(&forged, start),
+            PathResponseOutcome::Forged
+        );
+        assert!(!path.is_admitted());
+
+        let stale = PathResponsePacket {
+            nonce: challenge.nonce.wrapping_add(1),
+            tag: path_response_tag(b"isk_abc", challenge.nonce.wrapping_add(1)),
+        };
+        assert_eq!(
+            path.handle_response(&stale, start),
+            PathResponseOutcome::Stale
+        );
+
+        let genuine = make_path_response(b"isk_abc", &challenge);
+        assert_eq!(
+            path.handle_response(&genuine, start),
+            PathResponseOutcome::Validated
+        );
+        assert!(path.is_admitted());
+        assert_eq!(
+            path.handle_response(&genuine, start),
+            PathResponseOutcome::Stale,
+            "a replayed response is not accepted twice"
+        );
+    }
+
+    #[test]
+    fn admitted_path_is_rechallenged_and_lost_after_misses() {
+        let start = Instant::now();
+        let mut path = PathValidator::new(b"k");
+        let challenge = path.poll(start).unwrap();
+        path.handle_response(&make_path_response(b"k", &challenge), start);
+
+        assert!(path.poll(start + Duration::from_secs(9)).is_none());
+        let mut now = start + path.revalidate_interval;
+        assert!(path.poll(now).is_some(), "revalidation due");
+
+        for _ in 0..path.max_misses {
+            now += path.challenge_timeout;
+            assert!(path.poll(now).is_some(), "timed-out challenge is retried");
+        }
+        assert_eq!(path.state(), PathState::Lost);
+
+        // Answering the retry re-admits the path.
+        let retry = path.poll(now + path.challenge_timeout).unwrap();
+        path.handle_response(&make_path_response(b"k", &retry), now);
+        assert!(path.is_admitted());
+    }
+
+    #[test]
+    fn silent_path_is_never_admitted() {
+        let mut path = PathValidator::new(b"k");
+        let mut now = Instant::now();
+        assert!(path.poll(now).is_some());
+        for _ in 0..path.max_misses * 4 {
+            now += path.challenge_timeout;
+            assert!(
+                path.poll(now).is_some(),
+                "a silent path keeps being challenged"
+            );
+            assert!(!path.is_admitted());
+        }
+        assert_eq!(path.state(), PathState::Unvalidated);
     }
 }
@@ -83,10 +83,14 @@ pub struct ReceiverStats {
     pub fec_corrupt_dropped: u64,
     /// NACKs sent.
     pub nacks_sent: u64,
+    /// Selective ACKs sent for packets past the ACK bitmap window.
+    pub sacks_sent: u64,
     /// Highest contiguous sequence delivered.
     pub highest_delivered_seq: u64,
     /// Current jitter buffer depth in packets.
     pub jitter_buffer_depth: u32,
+    /// Shape of channel loss on this link (before FEC/ARQ repair).
+    pub loss_pattern: LossPatternStats,
 }
 
 impl ReceiverStats {
@@ -133,6 +137,129 @@ pub struct LinkStats {
     pub active: bool,
     /// Congestion control state name.
     pub cc_state: String,
+    /// Loss-run and loss-distance histograms.
+    pub loss_pattern: LossPatternStats,
+}
+
+// ─── Loss Pattern ───────────────────────────────────────────────────────────
+
+/// Buckets in each loss-pattern histogram. Bucket `i` counts values in
+/// `[2^i, 2^(i+1))`; the last bucket is open-ended (≥ 2048).
+pub const LOSS_HIST_BUCKETS: usize = 12;
+
+/// Sequence numbers held back before a packet is declared lost, so
+/// reordering on the link doesn't read as loss.
+const LOSS_REORDER_WINDOW: usize = 64;
+
+/// A jump this far past the highest seen sequence is a sender restart,
+/// not an outage; the tracker resynchronizes instead of settling it.
+const LOSS_MAX_GAP: u64 = 1 << 16;
+
+/// Loss-run-length and loss-distance histograms for one link.
+///
+/// Mean loss rate alone can't tell 2% random loss (one repair per window
+/// fixes it) from 2% arriving as 20-packet handover bursts (nothing short
+/// of 20 repairs does). Runs are measured on arrival order, before FEC and
+/// ARQ, once a sequence falls [`LOSS_REORDER_WINDOW`] behind the highest
+/// seen.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LossPatternStats {
+    /// Consecutive-loss run lengths, in packets.
+    pub run_length_hist: [u64; LOSS_HIST_BUCKETS],
+    /// Packets received between the end of one loss run and the start of
+    /// the next.
+    pub distance_hist: [u64; LOSS_HIST_BUCKETS],
+    /// Sequences settled as received or lost.
+    pub packets_settled: u64,
+    /// Sequences settled as lost.
+    pub packets_lost: u64,
+    /// Completed loss runs.
+    pub loss_runs: u64,
+    /// Packets lost in completed loss runs (sum of `run_length_hist`).
+    pub run_packets: u64,
+    /// Packets received between loss runs (sum of `distance_hist`).
+    pub distance_packets: u64,
+    #[serde(skip)]
+    window: VecDeque<bool>,
+    #[serde(skip)]
+    base_seq: Option<u64>,
+    #[serde(skip)]
+    current_run: u64,
+    #[serde(skip)]
+    received_since_run: Option<u64>,
+}
+
+impl LossPatternStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the arrival of `seq`. Duplicates and sequences already
+    /// settled as lost are ignored.
+    pub fn record_arrival(&mut self, seq: u64) {
+        let base = *self.base_seq.get_or_insert(seq);
+        if seq < base {
+            return;
+        }
+        let idx = seq - base;
+        if idx >= self.window.len() as u64 + LOSS_MAX_GAP {
+            self.window.clear();
+            self.base_seq = Some(seq);
+            self.window.push_back(true);
+            return;
+        }
+        let idx = idx as usize;
+        if idx < self.window.len() {
+            self.window[idx] = true;
+        } else {
+            self.window.resize(idx, false);
+            self.window.push_back(true);
+        }
+        while self.window.len() > LOSS_REORDER_WINDOW {
+            let received = self.window.pop_front().unwrap_or(true);
+            self.base_seq = Some(self.base_seq.unwrap_or(0) + 1);
+            self.settle(received);
+        }
+    }
+
+    fn settle(&mut self, received: bool) {
+        self.packets_settled += 1;
+        if !received {
+            if self.current_run == 0
+                && let Some(distance) = self.received_since_run
+            {
+                self.distance_hist[hist_bucket(distance)] += 1;
+                self.distance_packets += distance;
+            }
+            self.current_run += 1;
+            self.packets_lost += 1;
+            return;
+        }
+        if self.current_run > 0 {
+            self.run_length_hist[hist_bucket(self.current_run)] += 1;
+            self.loss_runs += 1;
+            self.run_packets += self.current_run;
+            self.current_run = 0;
+            self.received_since_run = Some(0);
+        }
+        if let Some(n) = self.received_since_run.as_mut() {
+            *n += 1;
+        }
+    }
+
+    /// Mean length of completed loss runs in packets (0.0 with no loss).
+    /// 1.0 is purely random loss; handover bursts push it well above.
+    pub fn mean_run_length(&self) -> f64 {
+        if self.loss_runs == 0 {
+            0.0
+        } else {
+            self.run_packets as f64 / self.loss_runs as f64
+        }
+    }
+}
+
+fn hist_bucket(value: u64) -> usize {
+    (value.max(1).ilog2() as usize).min(LOSS_HIST_BUCKETS - 1)
 }
 
 // ─── Rate Counter ───────────────────────────────────────────────────────────
@@ -242,6 +369,131 @@ impl Ewma {
     }
 }
 
+// ─── Quality Score ──────────────────────────────────────────────────────────
+
+/// E-model transmission rating with no impairments (ITU-T G.107 default).
+const QUALITY_R0: f64 = 93.2;
+
+/// Packet-loss robustness factor (`Bpl`): how much residual loss the
+/// decoder's concealment absorbs before the rating falls off.
+const QUALITY_LOSS_ROBUSTNESS: f64 = 10.0;
+
+/// Rating points a certain freeze costs on top of the loss impairment.
+const QUALITY_FREEZE_PENALTY: f64 = 40.0;
+
+/// Jitter-buffer headroom, in multiples of the 4·RTTVAR delay tail, above
+/// which late packets carry no freeze risk; at a quarter of it every
+/// tail excursion misses the playout deadline.
+const QUALITY_SAFE_HEADROOM: f64 = 2.0;
+
+/// One stats interval of session-level inputs to [`QualityMonitor`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QualitySample {
+    /// Loss left after FEC and ARQ (0.0–1.0) — what the decoder sees.
+    pub residual_loss: f64,
+    /// Packets that arrived past the playout deadline (0.0–1.0).
+    pub late_rate: f64,
+    /// Mean channel loss-run length in packets (0.0 with no loss).
+    pub mean_loss_burst: f64,
+    /// Session round-trip time in ms.
+    pub rtt_ms: f64,
+    /// Receiver jitter-buffer depth in ms; 0.0 when unknown.
+    pub jitter_buffer_ms: f64,
+}
+
+/// A single number for producers to watch.
+///
+/// `score` is an E-model-style rating (0–100) and `mos` the 1–5 opinion
+/// score it maps to (4.4 is a clean link). `freeze_risk` (0.0–1.0) is the
+/// heuristic feeding the rating: the chance that residual loss bursts or
+/// late packets break a reference frame and stall the picture.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct QualityScore {
+    pub score: f64,
+    pub mos: f64,
+    pub freeze_risk: f64,
+}
+
+impl QualityScore {
+    /// Rate one interval given the smoothed RTT variance.
+    pub fn compute(sample: &QualitySample, rttvar_ms: f64) -> Self {
+        let loss_pct = (sample.residual_loss + sample.late_rate).clamp(0.0, 1.0) * 100.0;
+        let burst = sample.mean_loss_burst.max(1.0);
+
+        // Effective equipment impairment (G.107 Ie,eff with Ie = 0): bursty
+        // loss hurts more than the same rate spread out.
+        let loss_impairment = 95.0 * loss_pct / (loss_pct / burst + QUALITY_LOSS_ROBUSTNESS);
+
+        // A lost run longer than concealment can bridge takes a reference
+        // frame with it; the picture holds until the next keyframe.
+        let burst_risk = 1.0 - (-loss_pct * burst / 2.0).exp();
+        // Delay excursions the jitter buffer can't absorb arrive late.
+        let late_risk = if sample.jitter_buffer_ms > 0.0 && rttvar_ms > 0.0 {
+            let headroom = sample.jitter_buffer_ms / (4.0 * rttvar_ms);
+            let floor = QUALITY_SAFE_HEADROOM / 4.0;
+            ((QUALITY_SAFE_HEADROOM - headroom) / (QUALITY_SAFE_HEADROOM - floor)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let freeze_risk = 1.0 - (1.0 - burst_risk) * (1.0 - late_risk);
+
+        let r =
+            (QUALITY_R0 - loss_impairment - QUALITY_FREEZE_PENALTY * freeze_risk).clamp(0.0, 100.0);
+        QualityScore {
+            score: r,
+            mos: r_to_mos(r),
+            freeze_risk,
+        }
+    }
+}
+
+/// G.107 rating-to-MOS mapping.
+fn r_to_mos(r: f64) -> f64 {
+    if r <= 0.0 {
+        1.0
+    } else if r >= 100.0 {
+        4.5
+    } else {
+        1.0 + 0.035 * r + 7.0e-6 * r * (r - 60.0) * (100.0 - r)
+    }
+}
+
+/// Per-session quality tracker: smooths RTT variance across intervals
+/// (RFC 6298 gains) and rates each one.
+#[derive(Debug, Clone, Default)]
+pub struct QualityMonitor {
+    srtt_ms: Option<f64>,
+    rttvar_ms: f64,
+}
+
+impl QualityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in one interval and rate it.
+    pub fn update(&mut self, sample: &QualitySample) -> QualityScore {
+        if sample.rtt_ms > 0.0 {
+            match self.srtt_ms {
+                None => {
+                    self.srtt_ms = Some(sample.rtt_ms);
+                    self.rttvar_ms = sample.rtt_ms / 2.0;
+                }
+                Some(srtt) => {
+                    self.rttvar_ms = 0.75 * self.rttvar_ms + 0.25 * (srtt - sample.rtt_ms).abs();
+                    self.srtt_ms = Some(0.875 * srtt + 0.125 * sample.rtt_ms);
+                }
+            }
+        }
+        QualityScore::compute(sample, self.rttvar_ms)
+    }
+
+    /// Smoothed RTT variance in ms.
+    pub fn rttvar_ms(&self) -> f64 {
+        self.rttvar_ms
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,10 +629,119 @@ mod tests {
             packets_received: 9_800,
             active: true,
             cc_state: "Normal".to_string(),
+            loss_pattern: LossPatternStats::new(),
         };
 
         let json = serde_json::to_string(&stats).unwrap();
         assert!(json.contains("\"link_id\":1"));
         assert!(json.contains("\"active\":true"));
+        assert!(json.contains("\"run_length_hist\":[0,"));
+    }
+
+    // ─── LossPatternStats Tests ─────────────────────────────────────────
+
+    /// Feed `0..n` minus `lost`, then enough trailing packets to settle.
+    fn loss_pattern(n: u64, lost: &[u64]) -> LossPatternStats {
+        let mut lp = LossPatternStats::new();
+        for seq in (0..n + LOSS_REORDER_WINDOW as u64).filter(|s| !lost.contains(s)) {
+            lp.record_arrival(seq);
+        }
+        lp
+    }
+
+    #[test]
+    fn loss_pattern_random_loss_is_single_packet_runs() {
+        let lp = loss_pattern(1000, &[100, 300, 500, 700]);
+        assert_eq!(lp.packets_lost, 4);
+        assert_eq!(lp.loss_runs, 4);
+        assert_eq!(lp.run_length_hist[0], 4);
+        assert!((lp.mean_run_length() - 1.0).abs() < 1e-9);
+        // Runs are 199 received packets apart: bucket [128, 256).
+        assert_eq!(lp.distance_hist[7], 3);
+    }
+
+    #[test]
+    fn loss_pattern_burst_is_one_long_run() {
+        let lost: Vec<u64> = (400..420).collect();
+        let lp = loss_pattern(1000, &lost);
+        assert_eq!(lp.packets_lost, 20);
+        assert_eq!(lp.loss_runs, 1);
+        assert_eq!(lp.run_length_hist[4], 1); // [16, 32)
+        assert!((lp.mean_run_length() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn loss_pattern_reordering_is_not_loss() {
+        let mut lp = LossPatternStats::new();
+        for seq in [0, 1, 3, 4, 2, 5] {
+            lp.record_arrival(seq);
+        }
+        for seq in 6..200 {
+            lp.record_arrival(seq);
+        }
+        assert_eq!(lp.packets_lost, 0);
+        assert_eq!(lp.loss_runs, 0);
+    }
+
+    #[test]
+    fn loss_pattern_resyncs_on_sender_restart() {
+        let mut lp = loss_pattern(100, &[]);
+        lp.record_arrival(u64::MAX / 2);
+        assert_eq!(lp.packets_lost, 0);
+    }
+
+    // ─── QualityScore Tests ─────────────────────────────────────────────
+
+    fn quality(residual_loss: f64, mean_loss_burst: f64) -> QualityScore {
+        let sample = QualitySample {
+            residual_loss,
+            mean_loss_burst,
+            rtt_ms: 40.0,
+            jitter_buffer_ms: 200.0,
+            ..Default::default()
+        };
+        QualityScore::compute(&sample, 2.0)
+    }
+
+    #[test]
+    fn quality_clean_link_rates_near_top() {
+        let q = quality(0.0, 0.0);
+        assert!((q.score - QUALITY_R0).abs() < 1e-9);
+        assert!(q.mos > 4.3 && q.mos <= 4.5, "mos {}", q.mos);
+        assert_eq!(q.freeze_risk, 0.0);
+    }
+
+    #[test]
+    fn quality_bursty_loss_rates_below_random_loss() {
+        let random = quality(0.01, 1.0);
+        let bursty = quality(0.01, 20.0);
+        assert!(random.mos < quality(0.001, 1.0).mos);
+        assert!(bursty.freeze_risk > 0.99);
+        assert!(bursty.mos < random.mos - 1.0, "{bursty:?} vs {random:?}");
+        assert!(bursty.mos >= 1.0);
+    }
+
+    #[test]
+    fn quality_rtt_variance_past_the_jitter_buffer_risks_freezes() {
+        let mut monitor = QualityMonitor::new();
+        let steady = QualitySample {
+            rtt_ms: 40.0,
+            jitter_buffer_ms: 100.0,
+            ..Default::default()
+        };
+        for _ in 0..50 {
+            monitor.update(&steady);
+        }
+        assert!(monitor.update(&steady).freeze_risk < 0.01);
+
+        // RTT swinging 40↔140 ms against a 100 ms buffer.
+        let mut q = monitor.update(&steady);
+        for i in 0..20 {
+            let rtt_ms = if i % 2 == 0 { 140.0 } else { 40.0 };
+            q = monitor.update(&QualitySample { rtt_ms, ..steady });
+        }
+        assert!(monitor.rttvar_ms() > 25.0);
+        assert!(q.freeze_risk > 0.9, "{q:?}");
+        assert!(q.mos < 3.5);
     }
 }
@@ -240,6 +240,10 @@ pub enum ControlType {
     Session = 0x08,
     ReceiverReport = 0x09,
     PpdReport = 0x0A,
+    KeyframeRequest = 0x0B,
+    Sack = 0x0C,
+    PathChallenge = 0x0D,
+    PathResponse = 0x0E,
 }
 
 impl ControlType {
@@ -255,6 +259,10 @@ impl ControlType {
             0x08 => Some(ControlType::Session),
             0x09 => Some(ControlType::ReceiverReport),
             0x0A => Some(ControlType::PpdReport),
+            0x0B => Some(ControlType::KeyframeRequest),
+            0x0C => Some(ControlType::Sack),
+            0x0D => Some(ControlType::PathChallenge),
+            0x0E => Some(ControlType::PathResponse),
             _ => None,
         }
     }
@@ -476,6 +484,88 @@ impl AckPacket {
     }
 }
 
+/// Selective ACK: receptions beyond the ACK's 64-packet bitmap window.
+///
+/// Under heavy loss the cumulative sequence stalls on the oldest hole while
+/// the receiver keeps collecting packets far past it; those would stay in
+/// the sender's pool and retransmit state until the hole resolves. Each
+/// block acknowledges `base + i` for every set bit `i`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SackPacket {
+    pub blocks: Vec<SackBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SackBlock {
+    pub base: VarInt,
+    pub bitmap: u64,
+}
+
+impl SackPacket {
+    /// Most blocks one packet carries (~200 bytes encoded).
+    pub const MAX_BLOCKS: usize = 16;
+
+    /// Pack ascending sequence numbers into blocks, lowest first, until
+    /// [`SackPacket::MAX_BLOCKS`] is reached.
+    pub fn from_sequences(seqs: impl IntoIterator<Item = u64>) -> Self {
+        let mut blocks: Vec<SackBlock> = Vec::new();
+        for seq in seqs {
+            if let Some(block) = blocks.last_mut() {
+                let offset = seq.wrapping_sub(block.base.value());
+                if offset < 64 {
+                    block.bitmap |= 1u64 << offset;
+                    continue;
+                }
+            }
+            if blocks.len() == Self::MAX_BLOCKS {
+                break;
+            }
+            blocks.push(SackBlock {
+                base: VarInt::from_u64(seq),
+                bitmap: 1,
+            });
+        }
+        SackPacket { blocks }
+    }
+
+    pub fn encode(&self, buf: &mut BytesMut) {
+        buf.put_u8(ControlType::Sack as u8);
+        VarInt::from_u64(self.blocks.len() as u64).encode(buf);
+        for block in &self.blocks {
+            block.base.encode(buf);
+            buf.put_u64(block.bitmap);
+        }
+    }
+
+    pub fn decode(buf: &mut impl Buf) -> Option<Self> {
+        let num_blocks = VarInt::decode(buf)?.value() as usize;
+        if num_blocks > 64 {
+            return None; // sanity limit
+        }
+        let mut blocks = Vec::with_capacity(num_blocks);
+        for _ in 0..num_blocks {
+            let base = VarInt::decode(buf)?;
+            if buf.remaining() < 8 {
+                return None;
+            }
+            blocks.push(SackBlock {
+                base,
+                bitmap: buf.get_u64(),
+            });
+        }
+        Some(SackPacket { blocks })
+    }
+
+    /// Iterate the sequence numbers acknowledged by every block.
+    pub fn sacked_sequences(&self) -> impl Iterator<Item = u64> + '_ {
+        self.blocks.iter().flat_map(|block| {
+            (0..64)
+                .filter(move |i| block.bitmap & (1u64 << i) != 0)
+                .map(move |i| block.base.value() + i)
+        })
+    }
+}
+
 /// NACK packet: range-based loss report.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NackPacket {
@@ -670,6 +760,11 @@ pub struct PingPacket {
     pub origin_timestamp_us: u32,
     /// Ping sequence (for matching with pong).
     pub ping_id: u16,
+    /// Pinger's current estimate of the peer's clock minus its own, in µs
+    /// (see [`crate::clock`]), so the peer shares one time reference.
+    /// Optional wire tail: absent until the pinger has an estimate, and
+    /// from legacy peers.
+    pub clock_offset_us: Option<i32>,
 }
 
 impl PingPacket {
@@ -677,15 +772,22 @@ impl PingPacket {
         buf.put_u8(ControlType::Ping as u8);
         buf.put_u32(self.origin_timestamp_us);
         buf.put_u16(self.ping_id);
+        if let Some(offset) = self.clock_offset_us {
+            buf.put_i32(offset);
+        }
     }
 
     pub fn decode(buf: &mut impl Buf) -> Option<Self> {
         if buf.remaining() < 6 {
             return None;
         }
+        let origin_timestamp_us = buf.get_u32();
+        let ping_id = buf.get_u16();
+        let clock_offset_us = (buf.remaining() >= 4).then(|| buf.get_i32());
         Some(PingPacket {
-            origin_timestamp_us: buf.get_u32(),
-            ping_id: buf.get_u16(),
+            origin_timestamp_us,
+            ping_id,
+            clock_offset_us,
         })
     }
 }
@@ -729,8 +831,17 @@ pub struct SessionPacket {
     pub session_id: u64,
     /// Link-specific identifier for LINK_JOIN/LINK_LEAVE.
     pub link_id: Option<u8>,
+    /// Per-stream ingest key carried by HELLO. A receiver configured with a
+    /// key drops every datagram from a source address until that address
+    /// has sent a HELLO with a matching key. Encoded as a trailing
+    /// length-prefixed field, so peers that predate it simply ignore it.
+    /// At most [`MAX_INGEST_KEY_LEN`] bytes.
+    pub ingest_key: Option<Vec<u8>>,
 }
 
+/// Longest ingest key a session packet can carry (one-byte length prefix).
+pub const MAX_INGEST_KEY_LEN: usize = u8::MAX as usize;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum SessionAction {
@@ -744,6 +855,10 @@ pub enum SessionAction {
     LinkJoin = 3,
     /// Link leaving the session.
     LinkLeave = 4,
+    /// Media paused; the session stays up on keepalives alone.
+    Suspend = 5,
+    /// Media resuming after a [`Suspend`](Self::Suspend).
+    Resume = 6,
 }
 
 impl SessionAction {
@@ -754,6 +869,8 @@ impl SessionAction {
             2 => Some(SessionAction::Teardown),
             3 => Some(SessionAction::LinkJoin),
             4 => Some(SessionAction::LinkLeave),
+            5 => Some(SessionAction::Suspend),
+            6 => Some(SessionAction::Resume),
             _ => None,
         }
     }
@@ -773,6 +890,16 @@ impl SessionPacket {
                 buf.put_u8(0);
             }
         }
+        // Keys are length-checked where they're configured. Should an
+        // over-length one slip through anyway, leave it off rather than
+        // send a shortened key: the receiver then refuses the HELLO
+        // outright instead of matching a prefix.
+        if let Some(key) = &self.ingest_key
+            && let Ok(len) = u8::try_from(key.len())
+        {
+            buf.put_u8(len);
+            buf.put_slice(key);
+        }
     }
 
     pub fn decode(buf: &mut impl Buf) -> Option<Self> {
@@ -790,10 +917,22 @@ impl SessionPacket {
         } else {
             None
         };
+        let ingest_key = if buf.has_remaining() {
+            let len = buf.get_u8() as usize;
+            if buf.remaining() < len {
+                return None;
+            }
+            let mut key = vec![0u8; len];
+            buf.copy_to_slice(&mut key);
+            Some(key)
+        } else {
+            None
+        };
         Some(SessionPacket {
             action,
             session_id,
             link_id,
+            ingest_key,
         })
     }
 }
@@ -827,10 +966,21 @@ pub struct ReceiverReportPacket {
     /// positive value means the bottleneck queue is filling *before* loss.
     /// Optional wire tail: legacy peers omit it and it decodes as 0.
     pub delay_gradient_us: u32,
+    /// Absolute one-way delay for this link in microseconds, measured by
+    /// the receiver against the clock offset the sender shares in PINGs
+    /// (see [`crate::clock`]). 0 until the clocks are synchronized.
+    /// Optional wire tail: legacy peers omit it and it decodes as 0.
+    pub owd_us: u32,
+    /// Mean consecutive-loss run length over the reporting interval, in
+    /// hundredths of a packet (100 = isolated random loss; 0 = no loss).
+    /// Lets the sender size FEC repair to the burst, not just the rate
+    /// (see [`crate::stats::LossPatternStats`]).
+    /// Optional wire tail: legacy peers omit it and it decodes as 0.
+    pub mean_loss_burst: u16,
 }
 
 impl ReceiverReportPacket {
-    pub const ENCODED_LEN: usize = 30; // 8 + 2 + 4 + 2 + 2 + 8 + 4
+    pub const ENCODED_LEN: usize = 36; // 8 + 2 + 4 + 2 + 2 + 8 + 4 + 4 + 2
 
     pub fn encode(&self, buf: &mut BytesMut) {
         buf.put_u8(ControlType::ReceiverReport as u8);
@@ -841,6 +991,8 @@ impl ReceiverReportPacket {
         buf.put_u16(self.late_rate);
         buf.put_u64(self.bytes_delivered);
         buf.put_u32(self.delay_gradient_us);
+        buf.put_u32(self.owd_us);
+        buf.put_u16(self.mean_loss_burst);
     }
 
     pub fn decode(buf: &mut impl Buf) -> Option<Self> {
@@ -870,6 +1022,16 @@ impl ReceiverReportPacket {
         } else {
             0
         };
+        let owd_us = if buf.remaining() >= 4 {
+            buf.get_u32()
+        } else {
+            0
+        };
+        let mean_loss_burst = if buf.remaining() >= 2 {
+            buf.get_u16()
+        } else {
+            0
+        };
         Some(ReceiverReportPacket {
             goodput_bps,
             fec_repair_rate,
@@ -878,6 +1040,8 @@ impl ReceiverReportPacket {
             late_rate,
             bytes_delivered,
             delay_gradient_us,
+            owd_us,
+            mean_loss_burst,
         })
     }
 
@@ -895,6 +1059,11 @@ impl ReceiverReportPacket {
     pub fn late_rate_f32(&self) -> f32 {
         self.late_rate as f32 / 10000.0
     }
+
+    /// Mean loss run length in packets (0.0 when the interval had no loss).
+    pub fn mean_loss_burst_f32(&self) -> f32 {
+        self.mean_loss_burst as f32 / 100.0
+    }
 }
 
 // ─── PPD (Packet-Pair Dispersion) Report ────────────────────────────────────
@@ -936,6 +1105,106 @@ impl PpdReportPacket {
     }
 }
 
+// ─── Keyframe Request ───────────────────────────────────────────────────────
+
+/// Receiver → sender request for an early IDR (the PLI equivalent).
+///
+/// Sent after the receiver had to skip a gap it could not repair: every
+/// frame referencing the lost data decodes corrupt until the next keyframe,
+/// which can be up to a full GOP away. The receiver repeats a request on
+/// every link for robustness, so the sender acts on each `request_id` once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyframeRequestPacket {
+    /// Increments per request; repeats of one request share it.
+    pub request_id: u32,
+    /// Packets the receiver lost since its previous request (diagnostics).
+    pub lost_packets: u32,
+}
+
+impl KeyframeRequestPacket {
+    pub const ENCODED_LEN: usize = 8; // 4 + 4
+
+    pub fn encode(&self, buf: &mut BytesMut) {
+        buf.put_u8(ControlType::KeyframeRequest as u8);
+        buf.put_u32(self.request_id);
+        buf.put_u32(self.lost_packets);
+    }
+
+    pub fn decode(buf: &mut impl Buf) -> Option<Self> {
+        if buf.remaining() < Self::ENCODED_LEN {
+            return None;
+        }
+        Some(KeyframeRequestPacket {
+            request_id: buf.get_u32(),
+            lost_packets: buf.get_u32(),
+        })
+    }
+}
+
+// ─── Path Challenge ─────────────────────────────────────────────────────────
+
+/// Sender → receiver proof-of-liveness probe for one path.
+///
+/// Sent on a link before it is admitted into the bond and periodically
+/// after; only a [`PathResponsePacket`] echoing the nonce with a valid tag
+/// proves the path really reaches our receiver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathChallengePacket {
+    /// Random per-challenge nonce.
+    pub nonce: u64,
+}
+
+impl PathChallengePacket {
+    pub const ENCODED_LEN: usize = 8;
+
+    pub fn encode(&self, buf: &mut BytesMut) {
+        buf.put_u8(ControlType::PathChallenge as u8);
+        buf.put_u64(self.nonce);
+    }
+
+    pub fn decode(buf: &mut impl Buf) -> Option<Self> {
+        if buf.remaining() < Self::ENCODED_LEN {
+            return None;
+        }
+        Some(PathChallengePacket {
+            nonce: buf.get_u64(),
+        })
+    }
+}
+
+/// Receiver → sender answer to a [`PathChallengePacket`], sent back on the
+/// path the challenge arrived on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathResponsePacket {
+    /// The challenge nonce, echoed.
+    pub nonce: u64,
+    /// Keyed MAC over the nonce, see [`crate::session::path_response_tag`].
+    pub tag: [u8; PATH_TAG_LEN],
+}
+
+/// Length of the truncated MAC in a [`PathResponsePacket`].
+pub const PATH_TAG_LEN: usize = 16;
+
+impl PathResponsePacket {
+    pub const ENCODED_LEN: usize = 8 + PATH_TAG_LEN;
+
+    pub fn encode(&self, buf: &mut BytesMut) {
+        buf.put_u8(ControlType::PathResponse as u8);
+        buf.put_u64(self.nonce);
+        buf.put_slice(&self.tag);
+    }
+
+    pub fn decode(buf: &mut impl Buf) -> Option<Self> {
+        if buf.remaining() < Self::ENCODED_LEN {
+            return None;
+        }
+        let nonce = buf.get_u64();
+        let mut tag = [0u8; PATH_TAG_LEN];
+        buf.copy_to_slice(&mut tag);
+        Some(PathResponsePacket { nonce, tag })
+    }
+}
+
 // ─── Full Packet Serialization ──────────────────────────────────────────────
 
 /// A fully serialized Strata packet (header + payload).
@@ -1008,6 +1277,10 @@ pub enum ControlBody {
     Session(SessionPacket),
     ReceiverReport(ReceiverReportPacket),
     PpdReport(PpdReportPacket),
+    KeyframeRequest(KeyframeRequestPacket),
+    Sack(SackPacket),
+    PathChallenge(PathChallengePacket),
+    PathResponse(PathResponsePacket),
 }
 
 impl ControlBody {
@@ -1031,6 +1304,16 @@ impl ControlBody {
                 ReceiverReportPacket::decode(buf).map(ControlBody::ReceiverReport)
             }
             ControlType::PpdReport => PpdReportPacket::decode(buf).map(ControlBody::PpdReport),
+            ControlType::KeyframeRequest => {
+                KeyframeRequestPacket::decode(buf).map(ControlBody::KeyframeRequest)
+            }
+            ControlType::Sack => SackPacket::decode(buf).map(ControlBody::Sack),
+            ControlType::PathChallenge => {
+                PathChallengePacket::decode(buf).map(ControlBody::PathChallenge)
+            }
+            ControlType::PathResponse => {
+                PathResponsePacket::decode(buf).map(ControlBody::PathResponse)
+            }
         }
     }
 }
@@ -1242,6 +1525,7 @@ mod tests {
         let ping = PingPacket {
             origin_timestamp_us: 12345,
             ping_id: 7,
+            clock_offset_us: None,
         };
         let mut buf = BytesMut::new();
         ping.encode(&mut buf);
@@ -1249,6 +1533,16 @@ mod tests {
         let decoded = PingPacket::decode(&mut buf).unwrap();
         assert_eq!(decoded.origin_timestamp_us, 12345);
         assert_eq!(decoded.ping_id, 7);
+        assert_eq!(decoded.clock_offset_us, None);
+
+        let ping = PingPacket {
+            clock_offset_us: Some(-1_250_000),
+            ..ping
+        };
+        let mut buf = BytesMut::new();
+        ping.encode(&mut buf);
+        let _ = buf.get_u8();
+        assert_eq!(PingPacket::decode(&mut buf).unwrap(), ping);
 
         let pong = PongPacket {
             origin_timestamp_us: 12345,
@@ -1269,6 +1563,7 @@ mod tests {
             action: SessionAction::LinkJoin,
             session_id: 0xDEAD_BEEF_CAFE_BABE,
             link_id: Some(3),
+            ingest_key: None,
         };
         let mut buf = BytesMut::new();
         session.encode(&mut buf);
@@ -1277,6 +1572,68 @@ mod tests {
         assert_eq!(decoded.action, SessionAction::LinkJoin);
         assert_eq!(decoded.session_id, 0xDEAD_BEEF_CAFE_BABE);
         assert_eq!(decoded.link_id, Some(3));
+        assert_eq!(decoded.ingest_key, None);
+    }
+
+    #[test]
+    fn session_hello_carries_ingest_key() {
+        let hello = SessionPacket {
+            action: SessionAction::Hello,
+            session_id: 7,
+            link_id: None,
+            ingest_key: Some(b"isk_secret".to_vec()),
+        };
+        let mut buf = BytesMut::new();
+        hello.encode(&mut buf);
+        let _ = buf.get_u8();
+        let decoded = SessionPacket::decode(&mut buf).unwrap();
+        assert_eq!(decoded, hello);
+    }
+
+    #[test]
+    fn session_truncated_ingest_key_rejected() {
+        let mut buf = BytesMut::new();
+        SessionPacket {
+            action: SessionAction::Hello,
+            session_id: 7,
+            link_id: None,
+            ingest_key: Some(vec![0xAB; 16]),
+        }
+        .encode(&mut buf);
+        let _ = buf.get_u8();
+        buf.truncate(buf.len() - 1);
+        assert!(SessionPacket::decode(&mut buf).is_none());
+    }
+
+    #[test]
+    fn session_over_length_ingest_key_is_never_shortened() {
+        let mut buf = BytesMut::new();
+        SessionPacket {
+            action: SessionAction::Hello,
+            session_id: 7,
+            link_id: None,
+            ingest_key: Some(vec![0xAB; MAX_INGEST_KEY_LEN + 1]),
+        }
+        .encode(&mut buf);
+        let _ = buf.get_u8();
+        let decoded = SessionPacket::decode(&mut buf).unwrap();
+        assert_eq!(decoded.ingest_key, None);
+    }
+
+    #[test]
+    fn session_suspend_and_resume_roundtrip() {
+        for action in [SessionAction::Suspend, SessionAction::Resume] {
+            let pkt = SessionPacket {
+                action,
+                session_id: 9,
+                link_id: None,
+                ingest_key: None,
+            };
+            let mut buf = BytesMut::new();
+            pkt.encode(&mut buf);
+            let _ = buf.get_u8();
+            assert_eq!(SessionPacket::decode(&mut buf).unwrap(), pkt);
+        }
     }
 
     #[test]
@@ -1300,6 +1657,8 @@ mod tests {
             late_rate: 75,      // 0.75%
             bytes_delivered: 12_345_678,
             delay_gradient_us: 8_400,
+            owd_us: 42_000,
+            mean_loss_burst: 1_250,
         };
         let mut buf = BytesMut::new();
         report.encode(&mut buf);
@@ -1312,6 +1671,9 @@ mod tests {
         assert_eq!(decoded.loss_after_fec, 50);
         assert_eq!(decoded.bytes_delivered, 12_345_678);
         assert_eq!(decoded.delay_gradient_us, 8_400);
+        assert_eq!(decoded.owd_us, 42_000);
+        assert_eq!(decoded.mean_loss_burst, 1_250);
+        assert!((decoded.mean_loss_burst_f32() - 12.5).abs() < 1e-5);
     }
 
     #[test]
@@ -1326,6 +1688,8 @@ mod tests {
             late_rate: 0,
             bytes_delivered: 999,
             delay_gradient_us: 0,
+            owd_us: 0,
+            mean_loss_burst: 0,
         };
         let mut buf = BytesMut::new();
         buf.put_u64(report.goodput_bps);
@@ -1338,6 +1702,8 @@ mod tests {
         let decoded = ReceiverReportPacket::decode(&mut buf).unwrap();
         assert_eq!(decoded.bytes_delivered, 999);
         assert_eq!(decoded.delay_gradient_us, 0);
+        assert_eq!(decoded.owd_us, 0);
+        assert_eq!(decoded.mean_loss_burst, 0);
     }
 
     #[test]
@@ -1350,6 +1716,8 @@ mod tests {
             late_rate: 0,
             bytes_delivered: 0,
             delay_gradient_us: 0,
+            owd_us: 0,
+            mean_loss_burst: 0,
         };
         assert!((report.fec_repair_rate_f32() - 0.10).abs() < 1e-5);
         assert!((report.loss_after_fec_f32() - 1.0).abs() < 1e-5);
@@ -1425,4 +1793,91 @@ mod tests {
             other => panic!("expected PpdReport, got {:?}", other),
         }
     }
+
+    #[test]
+    fn keyframe_request_via_control_body() {
+        let request = KeyframeRequestPacket {
+            request_id: 7,
+            lost_packets: 42,
+        };
+        let mut buf = BytesMut::new();
+        request.encode(&mut buf);
+        assert_eq!(buf.len(), KeyframeRequestPacket::ENCODED_LEN + 1); // +1 for type byte
+        match ControlBody::decode(&mut buf.freeze()) {
+            Some(ControlBody::KeyframeRequest(decoded)) => assert_eq!(decoded, request),
+            other => panic!("expected KeyframeRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn truncated_keyframe_request_is_rejected() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(ControlType::KeyframeRequest as u8);
+        buf.put_u32(7);
+        assert!(ControlBody::decode(&mut buf.freeze()).is_none());
+    }
+
+    #[test]
+    fn path_challenge_and_response_via_control_body() {
+        let challenge = PathChallengePacket {
+            nonce: 0xDEAD_BEEF_0BAD_F00D,
+        };
+        let mut buf = BytesMut::new();
+        challenge.encode(&mut buf);
+        assert_eq!(buf.len(), PathChallengePacket::ENCODED_LEN + 1);
+        match ControlBody::decode(&mut buf.freeze()) {
+            Some(ControlBody::PathChallenge(decoded)) => assert_eq!(decoded, challenge),
+            other => panic!("expected PathChallenge, got {:?}", other),
+        }
+
+        let response = PathResponsePacket {
+            nonce: challenge.nonce,
+            tag: [0xA5; PATH_TAG_LEN],
+        };
+        let mut buf = BytesMut::new();
+        response.encode(&mut buf);
+        assert_eq!(buf.len(), PathResponsePacket::ENCODED_LEN + 1);
+        match ControlBody::decode(&mut buf.freeze()) {
+            Some(ControlBody::PathResponse(decoded)) => assert_eq!(decoded, response),
+            other => panic!("expected PathResponse, got {:?}", other),
+        }
+
+        let mut short = BytesMut::new();
+        short.put_u8(ControlType::PathResponse as u8);
+        short.put_u64(challenge.nonce);
+        short.put_slice(&[0; PATH_TAG_LEN - 1]);
+        assert!(ControlBody::decode(&mut short.freeze()).is_none());
+    }
+
+    #[test]
+    fn sack_packs_sequences_into_blocks() {
+        let seqs = [200, 201, 263, 264, 1000];
+        let sack = SackPacket::from_sequences(seqs);
+        assert_eq!(sack.blocks.len(), 3);
+        assert_eq!(sack.blocks[0].base.value(), 200);
+        assert_eq!(sack.blocks[0].bitmap, 1 | 1 << 1 | 1 << 63);
+        assert_eq!(sack.blocks[1].base.value(), 264);
+        assert_eq!(sack.sacked_sequences().collect::<Vec<_>>(), seqs);
+
+        let mut buf = BytesMut::new();
+        sack.encode(&mut buf);
+        match ControlBody::decode(&mut buf.freeze()) {
+            Some(ControlBody::Sack(decoded)) => assert_eq!(decoded, sack),
+            other => panic!("expected Sack, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sack_caps_block_count() {
+        let sack = SackPacket::from_sequences((0..100).map(|i| i * 100));
+        assert_eq!(sack.blocks.len(), SackPacket::MAX_BLOCKS);
+        assert_eq!(sack.sacked_sequences().last(), Some(1500));
+
+        let mut buf = BytesMut::new();
+        buf.put_u8(ControlType::Sack as u8);
+        VarInt::from_u64(1).encode(&mut buf);
+        VarInt::from_u64(5).encode(&mut buf);
+        buf.put_u32(0);
+        assert!(ControlBody::decode(&mut buf.freeze()).is_none());
+    }
 }
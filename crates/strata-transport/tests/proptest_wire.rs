@@ -261,14 +261,20 @@ proptest! {
     fn ping_roundtrip(
         origin_ts in any::<u32>(),
         ping_id in any::<u16>(),
+        clock_offset in any::<Option<i32>>(),
     ) {
-        let ping = PingPacket { origin_timestamp_us: origin_ts, ping_id };
+        let ping = PingPacket {
+            origin_timestamp_us: origin_ts,
+            ping_id,
+            clock_offset_us: clock_offset,
+        };
         let mut buf = BytesMut::new();
         ping.encode(&mut buf);
         let _ = buf.split_to(1);
         let decoded = PingPacket::decode(&mut buf).unwrap();
         prop_assert_eq!(decoded.origin_timestamp_us, origin_ts);
         prop_assert_eq!(decoded.ping_id, ping_id);
+        prop_assert_eq!(decoded.clock_offset_us, clock_offset);
     }
 
     #[test]
@@ -311,9 +317,10 @@ proptest! {
         session_id in any::<u64>(),
         has_link_id in any::<bool>(),
         link_id_val in any::<u8>(),
+        ingest_key in proptest::option::of(proptest::collection::vec(any::<u8>(), 0..=255)),
     ) {
         let link_id = if has_link_id { Some(link_id_val) } else { None };
-        let session = SessionPacket { action, session_id, link_id };
+        let session = SessionPacket { action, session_id, link_id, ingest_key: ingest_key.clone() };
 
         let mut buf = BytesMut::new();
         session.encode(&mut buf);
@@ -323,6 +330,7 @@ proptest! {
         prop_assert_eq!(decoded.action, action);
         prop_assert_eq!(decoded.session_id, session_id);
         prop_assert_eq!(decoded.link_id, link_id);
+        prop_assert_eq!(decoded.ingest_key, ingest_key);
     }
 }
 
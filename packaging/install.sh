@@ -110,6 +110,15 @@ fi
 install -m 644 "$(find_file "strata-$ROLE.service")" "/etc/systemd/system/strata-$ROLE.service"
 echo "Installed /etc/systemd/system/strata-$ROLE.service"
 
+# Offline updates uploaded through the sender portal (needs a signing key
+# in /etc/strata/update-key.pub — see make-update-bundle.sh).
+if [ "$ROLE" = "sender" ]; then
+    for unit in strata-update-apply.path strata-update-apply.service; do
+        install -m 644 "$(find_file "$unit")" "/etc/systemd/system/$unit"
+    done
+    echo "Installed /etc/systemd/system/strata-update-apply.{path,service}"
+fi
+
 install -d -m 755 /etc/strata
 if [ -f "/etc/strata/$ROLE.env" ]; then
     echo "Kept existing /etc/strata/$ROLE.env (not overwritten)."
@@ -119,6 +128,9 @@ else
 fi
 
 systemctl daemon-reload
+if [ "$ROLE" = "sender" ]; then
+    systemctl enable --now strata-update-apply.path
+fi
 
 cat <<EOF
 
@@ -0,0 +1,73 @@
+#!/usr/bin/env bash
+# Build a signed offline update bundle for the sender portal.
+#
+# Usage:
+#   ./make-update-bundle.sh --key update-key.pem --version vX.Y.Z \
+#       [--arch aarch64] [--dist DIR] [--allow-downgrade] [-o bundle.tar.gz]
+#
+# Packs whichever of strata-sender, strata-pipeline and libgststrata.so are
+# in --dist DIR (default: current directory) with a manifest listing their
+# SHA-256, signed with the ed25519 key. Upload the result under
+# "Software update" in the sender portal.
+#
+# Senders refuse a bundle that isn't newer than their installed release.
+# --allow-downgrade signs a bundle that may reinstall or roll back; the
+# operator still has to tick "Allow reinstalling or downgrading" to use it.
+#
+# One-time key setup (OpenSSL 3):
+#   openssl genpkey -algorithm ed25519 -out update-key.pem    # keep private
+#   openssl pkey -in update-key.pem -pubout -outform DER | tail -c 32 | base64
+# Put that base64 public key in /etc/strata/update-key.pub on each sender;
+# without it the portal refuses uploads.
+set -euo pipefail
+
+KEY=""
+VERSION=""
+ARCH="aarch64"
+DIST_DIR="."
+OUT=""
+ALLOW_DOWNGRADE=""
+
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --key)     KEY="${2:?--key needs a PEM file}"; shift ;;
+        --version) VERSION="${2:?--version needs a tag}"; shift ;;
+        --arch)    ARCH="${2:?--arch needs aarch64|x86_64}"; shift ;;
+        --dist)    DIST_DIR="${2:?--dist needs a directory}"; shift ;;
+        --allow-downgrade) ALLOW_DOWNGRADE=',"allow_downgrade":true' ;;
+        -o)        OUT="${2:?-o needs a file name}"; shift ;;
+        -h|--help) grep '^#' "$0" | sed 's/^# \{0,1\}//'; exit 0 ;;
+        *) echo "Unknown argument: $1" >&2; exit 1 ;;
+    esac
+    shift
+done
+
+[ -n "$KEY" ] && [ -n "$VERSION" ] || { echo "Usage: $0 --key KEY.pem --version vX.Y.Z [--arch A] [--dist DIR] [--allow-downgrade] [-o OUT]" >&2; exit 1; }
+OUT="${OUT:-strata-sender-update-${VERSION}-${ARCH}.tar.gz}"
+
+TMP=$(mktemp -d /tmp/strata-bundle-XXXXXX)
+trap 'rm -rf "$TMP"' EXIT
+
+FILES=()
+for f in strata-sender strata-pipeline libgststrata.so; do
+    if [ -f "$DIST_DIR/$f" ]; then
+        cp "$DIST_DIR/$f" "$TMP/$f"
+        FILES+=("$f")
+    fi
+done
+[ ${#FILES[@]} -gt 0 ] || { echo "Error: no strata-sender, strata-pipeline or libgststrata.so in $DIST_DIR." >&2; exit 1; }
+
+{
+    printf '{"version":"%s","arch":"%s","files":[' "$VERSION" "$ARCH"
+    sep=""
+    for f in "${FILES[@]}"; do
+        printf '%s{"name":"%s","sha256":"%s"}' "$sep" "$f" "$(sha256sum "$TMP/$f" | cut -d' ' -f1)"
+        sep=","
+    done
+    printf ']%s}' "$ALLOW_DOWNGRADE"
+} > "$TMP/manifest.json"
+
+openssl pkeyutl -sign -rawin -inkey "$KEY" -in "$TMP/manifest.json" | base64 -w0 > "$TMP/manifest.sig"
+
+tar -czf "$OUT" -C "$TMP" manifest.json manifest.sig "${FILES[@]}"
+echo "Wrote $OUT (${FILES[*]})"